@@ -0,0 +1,33 @@
+-- =====================================================================
+-- NODUS DATABASE MODULE
+-- 006_entity_search.sql
+-- Full-text search over entity data: tsvector column, trigger, GIN index
+-- Compatible with PostgreSQL 15+
+-- =====================================================================
+
+BEGIN;
+
+ALTER TABLE entities ADD COLUMN IF NOT EXISTS search_tsv tsvector;
+
+-- === Function: Extract searchable text ================================
+-- Every string and number in the JSON data, wherever it is nested
+CREATE OR REPLACE FUNCTION entities_search_tsv_update()
+RETURNS trigger LANGUAGE plpgsql AS $$
+BEGIN
+  NEW.search_tsv := jsonb_to_tsvector('english', coalesce(NEW.data, '{}'::jsonb), '["string", "numeric"]');
+  RETURN NEW;
+END$$;
+
+DROP TRIGGER IF EXISTS trg_entities_search_tsv ON entities;
+CREATE TRIGGER trg_entities_search_tsv
+  BEFORE INSERT OR UPDATE OF data ON entities
+  FOR EACH ROW EXECUTE FUNCTION entities_search_tsv_update();
+
+-- Backfill rows written before the trigger existed
+UPDATE entities
+   SET search_tsv = jsonb_to_tsvector('english', data, '["string", "numeric"]')
+ WHERE search_tsv IS NULL;
+
+CREATE INDEX IF NOT EXISTS ix_entities_search_tsv ON entities USING gin(search_tsv);
+
+COMMIT;
@@ -0,0 +1,17 @@
+-- =====================================================================
+-- NODUS DATABASE MODULE
+-- 007_entity_soft_delete.sql
+-- Soft delete for entities: deletion markers kept until restore or erasure
+-- Compatible with PostgreSQL 15+
+-- =====================================================================
+
+BEGIN;
+
+ALTER TABLE entities ADD COLUMN IF NOT EXISTS deleted_at timestamptz;
+ALTER TABLE entities ADD COLUMN IF NOT EXISTS deleted_by text;
+
+-- Reads filter on deleted_at IS NULL, so index the live rows only
+CREATE INDEX IF NOT EXISTS ix_entities_live_created ON entities(created_at, id) WHERE deleted_at IS NULL;
+CREATE INDEX IF NOT EXISTS ix_entities_deleted_at   ON entities(deleted_at) WHERE deleted_at IS NOT NULL;
+
+COMMIT;
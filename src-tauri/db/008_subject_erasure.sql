@@ -0,0 +1,29 @@
+-- =====================================================================
+-- NODUS DATABASE MODULE
+-- 008_subject_erasure.sql
+-- GDPR right to erasure by crypto-shredding: per-subject keys and erasure attestations
+-- Compatible with PostgreSQL 15+
+-- =====================================================================
+
+BEGIN;
+
+-- One live key per data subject; deleting the row is the erasure
+CREATE TABLE IF NOT EXISTS subject_keys (
+  subject_id   text PRIMARY KEY,
+  key_id       uuid NOT NULL UNIQUE,
+  key_material bytea NOT NULL CHECK (octet_length(key_material) = 32),
+  created_at   timestamptz NOT NULL DEFAULT now()
+);
+
+-- Append-only record of erasures; holds key ids, never key material or personal data
+CREATE TABLE IF NOT EXISTS subject_erasures (
+  id          uuid PRIMARY KEY DEFAULT uuid_generate_v4(),
+  subject_id  text NOT NULL,
+  erased_by   text NOT NULL,
+  erased_at   timestamptz NOT NULL,
+  attestation jsonb NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS ix_subject_erasures_subject ON subject_erasures(subject_id);
+
+COMMIT;
@@ -0,0 +1,35 @@
+-- =====================================================================
+-- NODUS DATABASE MODULE
+-- 009_retention.sql
+-- Retention enforcement: legal holds, applied-action markers, cold storage archive
+-- Compatible with PostgreSQL 15+
+-- =====================================================================
+
+BEGIN;
+
+-- Held rows are exempt from every retention action
+ALTER TABLE entities     ADD COLUMN IF NOT EXISTS legal_hold boolean NOT NULL DEFAULT false;
+ALTER TABLE forensic_log ADD COLUMN IF NOT EXISTS legal_hold boolean NOT NULL DEFAULT false;
+
+-- Set once a row has been anonymized or encrypted in place, so sweeps skip it
+ALTER TABLE entities     ADD COLUMN IF NOT EXISTS retention_action text;
+ALTER TABLE forensic_log ADD COLUMN IF NOT EXISTS retention_action text;
+
+CREATE INDEX IF NOT EXISTS ix_entities_retention     ON entities(tenant_id, created_at) WHERE retention_action IS NULL;
+CREATE INDEX IF NOT EXISTS ix_forensic_log_retention ON forensic_log((metadata->>'tenant_id'), timestamp) WHERE retention_action IS NULL;
+
+-- === Cold storage =====================================================
+-- Archived rows keep the live table's columns, in order, followed by the archive columns.
+-- Move this schema to a cheaper tablespace as needed; columns added to the live tables
+-- later must be added here too.
+CREATE SCHEMA IF NOT EXISTS cold_storage;
+
+CREATE TABLE IF NOT EXISTS cold_storage.entities (LIKE entities INCLUDING DEFAULTS);
+ALTER TABLE cold_storage.entities ADD COLUMN IF NOT EXISTS archived_at timestamptz NOT NULL DEFAULT now();
+ALTER TABLE cold_storage.entities ADD COLUMN IF NOT EXISTS retention_policy_id text;
+
+CREATE TABLE IF NOT EXISTS cold_storage.forensic_log (LIKE forensic_log INCLUDING DEFAULTS);
+ALTER TABLE cold_storage.forensic_log ADD COLUMN IF NOT EXISTS archived_at timestamptz NOT NULL DEFAULT now();
+ALTER TABLE cold_storage.forensic_log ADD COLUMN IF NOT EXISTS retention_policy_id text;
+
+COMMIT;
@@ -0,0 +1,25 @@
+-- =====================================================================
+-- NODUS DATABASE MODULE
+-- 010_legal_holds.sql
+-- Legal hold registry: subjects, entities or tenants frozen against retention and erasure
+-- Compatible with PostgreSQL 15+
+-- =====================================================================
+
+BEGIN;
+
+-- Released holds are kept, so the registry doubles as the hold history
+CREATE TABLE IF NOT EXISTS legal_holds (
+  hold_id     uuid PRIMARY KEY,
+  scope       text NOT NULL CHECK (scope IN ('subject', 'entity', 'tenant')),
+  target      text NOT NULL,
+  reason      text NOT NULL,
+  placed_by   text NOT NULL,
+  placed_at   timestamptz NOT NULL DEFAULT now(),
+  expires_at  timestamptz,
+  released_at timestamptz,
+  released_by text
+);
+
+CREATE INDEX IF NOT EXISTS ix_legal_holds_active ON legal_holds(scope, target) WHERE released_at IS NULL;
+
+COMMIT;
@@ -0,0 +1,23 @@
+-- =====================================================================
+-- NODUS DATABASE MODULE
+-- 011_entity_idempotency.sql
+-- Client idempotency keys for entity creation, so a retried create returns the original entity
+-- Compatible with PostgreSQL 15+
+-- =====================================================================
+
+BEGIN;
+
+-- Keys are scoped to the creator and tenant ('' outside any tenant); the primary key lets
+-- concurrent creates with one key race safely, as only one can claim it
+CREATE TABLE IF NOT EXISTS entity_idempotency_keys (
+  tenant_scope    text NOT NULL,
+  created_by      text NOT NULL,
+  idempotency_key text NOT NULL,
+  entity_id       uuid NOT NULL REFERENCES entities(id) ON DELETE CASCADE DEFERRABLE INITIALLY DEFERRED,
+  created_at      timestamptz NOT NULL DEFAULT now(),
+  PRIMARY KEY (tenant_scope, created_by, idempotency_key)
+);
+
+CREATE INDEX IF NOT EXISTS ix_entity_idempotency_keys_entity ON entity_idempotency_keys(entity_id);
+
+COMMIT;
@@ -0,0 +1,49 @@
+-- =====================================================================
+-- NODUS DATABASE MODULE
+-- 012_entity_change_feed.sql
+-- Entity change feed: NOTIFY on every committed entity write, for live UI updates
+-- Compatible with PostgreSQL 15+
+-- =====================================================================
+
+BEGIN;
+
+-- === Function: Publish an entity change ===============================
+-- Metadata and label only, never data; the engine filters by clearance before fanning out.
+-- NOTIFY is delivered when the writing transaction commits and dropped if it rolls back,
+-- so listeners never hear of a change that isn't durable.
+CREATE OR REPLACE FUNCTION entities_notify_change()
+RETURNS trigger LANGUAGE plpgsql AS $$
+DECLARE
+  changed entities;
+  operation text;
+BEGIN
+  IF TG_OP = 'DELETE' THEN
+    changed := OLD;
+    operation := 'delete';
+  ELSE
+    changed := NEW;
+    operation := CASE
+      WHEN TG_OP = 'INSERT' THEN 'create'
+      WHEN NEW.deleted_at IS NOT NULL AND OLD.deleted_at IS NULL THEN 'delete'
+      ELSE 'update'
+    END;
+  END IF;
+
+  PERFORM pg_notify('entity_changes', json_build_object(
+    'id', changed.id,
+    'entity_type', changed.entity_type,
+    'operation', operation,
+    'version', changed.version,
+    'classification', changed.classification,
+    'compartments', changed.compartments,
+    'tenant_id', changed.tenant_id
+  )::text);
+  RETURN NULL;
+END$$;
+
+DROP TRIGGER IF EXISTS trg_entities_notify_change ON entities;
+CREATE TRIGGER trg_entities_notify_change
+  AFTER INSERT OR UPDATE OR DELETE ON entities
+  FOR EACH ROW EXECUTE FUNCTION entities_notify_change();
+
+COMMIT;
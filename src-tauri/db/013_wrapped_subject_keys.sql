@@ -0,0 +1,17 @@
+-- =====================================================================
+-- NODUS DATABASE MODULE
+-- 013_wrapped_subject_keys.sql
+-- Subject keys are stored wrapped by the key provider; raw key material is no longer kept
+-- Compatible with PostgreSQL 15+
+-- =====================================================================
+
+BEGIN;
+
+ALTER TABLE subject_keys ADD COLUMN IF NOT EXISTS wrapped_key bytea;
+
+-- Existing raw keys are wrapped, and their material cleared, by DatabaseManager at startup
+ALTER TABLE subject_keys ALTER COLUMN key_material DROP NOT NULL;
+ALTER TABLE subject_keys ADD CONSTRAINT subject_keys_wrapped_or_legacy
+  CHECK (wrapped_key IS NOT NULL OR key_material IS NOT NULL);
+
+COMMIT;
@@ -0,0 +1,14 @@
+-- =====================================================================
+-- NODUS DATABASE MODULE
+-- 014_forensic_tenant_export.sql
+-- Index for paging a tenant's forensic envelopes in export order
+-- Compatible with PostgreSQL 15+
+-- =====================================================================
+
+BEGIN;
+
+-- Scheduled audit exports resume after a (timestamp, envelope_id) watermark per tenant
+CREATE INDEX IF NOT EXISTS ix_forensic_log_tenant_export
+  ON forensic_log ((metadata->>'tenant_id'), timestamp, envelope_id);
+
+COMMIT;
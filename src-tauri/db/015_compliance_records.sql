@@ -0,0 +1,24 @@
+-- =====================================================================
+-- NODUS DATABASE MODULE
+-- 015_compliance_records.sql
+-- Per-operation compliance evidence kept for dashboard summaries
+-- Compatible with PostgreSQL 15+
+-- =====================================================================
+
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS compliance_records (
+  id             uuid PRIMARY KEY,
+  assessed_at    timestamptz NOT NULL,
+  framework      text NOT NULL,
+  operation      text NOT NULL,
+  tenant_id      text,
+  classification text,
+  record         jsonb NOT NULL
+);
+
+-- Summaries page one framework's window in (assessed_at, id) order
+CREATE INDEX IF NOT EXISTS ix_compliance_records_window
+  ON compliance_records (framework, assessed_at, id);
+
+COMMIT;
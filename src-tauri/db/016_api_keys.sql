@@ -0,0 +1,23 @@
+-- =====================================================================
+-- NODUS DATABASE MODULE
+-- 016_api_keys.sql
+-- Tenant-scoped API keys for gateway callers; only a digest of each secret is kept
+-- Compatible with PostgreSQL 15+
+-- =====================================================================
+
+BEGIN;
+
+CREATE TABLE IF NOT EXISTS api_keys (
+  key_id      uuid PRIMARY KEY,
+  tenant_id   text NOT NULL,
+  permissions text[] NOT NULL DEFAULT '{}',
+  label       jsonb NOT NULL,
+  secret_hash text NOT NULL,
+  created_at  timestamptz NOT NULL,
+  expires_at  timestamptz,
+  revoked_at  timestamptz
+);
+
+CREATE INDEX IF NOT EXISTS ix_api_keys_tenant ON api_keys (tenant_id);
+
+COMMIT;
@@ -0,0 +1,15 @@
+-- =====================================================================
+-- NODUS DATABASE MODULE
+-- 017_forensic_retention_digest.sql
+-- Digest of a forensic row's original content, kept when retention clears it
+-- Compatible with PostgreSQL 15+
+-- =====================================================================
+
+BEGIN;
+
+-- Chain links cover this digest rather than the row itself, so rows that retention
+-- anonymized, encrypted or tombstoned still verify; NULL while the row is untouched
+ALTER TABLE forensic_log              ADD COLUMN IF NOT EXISTS content_digest text;
+ALTER TABLE cold_storage.forensic_log ADD COLUMN IF NOT EXISTS content_digest text;
+
+COMMIT;
@@ -0,0 +1,17 @@
+-- =====================================================================
+-- NODUS DATABASE MODULE
+-- 018_tenant_statuses.sql
+-- Tenant statuses enforced by the tenant gate, kept across restarts
+-- Compatible with PostgreSQL 15+
+-- =====================================================================
+
+BEGIN;
+
+-- Loaded into the gate at startup; tenants without a row are refused
+CREATE TABLE IF NOT EXISTS tenant_statuses (
+  tenant_id  text PRIMARY KEY,
+  status     text NOT NULL,
+  updated_at timestamptz NOT NULL DEFAULT now()
+);
+
+COMMIT;
@@ -0,0 +1,25 @@
+-- =====================================================================
+-- NODUS DATABASE MODULE
+-- 019_declassification_requests.sql
+-- Declassification proposals awaiting their second approval, kept across restarts
+-- Compatible with PostgreSQL 15+
+-- =====================================================================
+
+BEGIN;
+
+-- A request leaves 'pending' exactly once, for 'approved' or 'expired'
+CREATE TABLE IF NOT EXISTS declassification_requests (
+  request_id  uuid PRIMARY KEY,
+  tenant_id   text,
+  request     jsonb NOT NULL,
+  status      text NOT NULL DEFAULT 'pending',
+  proposed_at timestamptz NOT NULL,
+  expires_at  timestamptz NOT NULL,
+  decided_by  text,
+  decided_at  timestamptz
+);
+
+CREATE INDEX IF NOT EXISTS ix_declassification_requests_pending
+  ON declassification_requests (proposed_at) WHERE status = 'pending';
+
+COMMIT;
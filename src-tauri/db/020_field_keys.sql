@@ -0,0 +1,21 @@
+-- =====================================================================
+-- NODUS DATABASE MODULE
+-- 020_field_keys.sql
+-- Field encryption key versions, stored only wrapped by the key provider
+-- Compatible with PostgreSQL 15+
+-- =====================================================================
+
+BEGIN;
+
+-- The newest version of a classification is the one new fields are sealed under;
+-- older versions stay until every field sealed under them has been re-encrypted
+CREATE TABLE IF NOT EXISTS field_keys (
+  key_id         text PRIMARY KEY,
+  classification text NOT NULL,
+  version        int NOT NULL CHECK (version > 0),
+  wrapped_key    bytea NOT NULL,
+  created_at     timestamptz NOT NULL DEFAULT now(),
+  UNIQUE (classification, version)
+);
+
+COMMIT;
@@ -0,0 +1,8 @@
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // Observation wire types; protoc is vendored so builds don't need a system install
+    std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path()?);
+    prost_build::compile_protos(&["proto/observation.proto"], &["proto"])?;
+
+    tauri_build::build();
+    Ok(())
+}
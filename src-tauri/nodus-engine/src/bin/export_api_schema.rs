@@ -0,0 +1,15 @@
+// src-tauri/src/bin/export_api_schema.rs
+// Writes the command API's JSON Schema for frontend and integrator tooling
+// Usage: export-api-schema [path]  (default: api-schema.json)
+
+use std::path::PathBuf;
+
+fn main() -> std::io::Result<()> {
+    let path = std::env::args_os()
+        .nth(1)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("api-schema.json"));
+    nodus_enterprise::commands::schema::write_api_schema(&path)?;
+    println!("Wrote command API schema to {}", path.display());
+    Ok(())
+}
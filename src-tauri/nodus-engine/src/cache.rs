@@ -0,0 +1,363 @@
+// src-tauri/src/cache.rs
+// TTL Cache - Bounded LRU cache with per-entry expiry and hit/miss/eviction accounting
+// Backs the response, MAC decision, instrumentation decision and cross-tenant access caches
+
+use lru::LruCache;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::hash::Hash;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::clock::{self, Clock};
+
+/// Lookup and removal counts since the cache was created or its stats were reset
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct TtlCacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    /// Live entries dropped to make room
+    pub evictions: u64,
+    /// Entries dropped because their TTL passed
+    pub expirations: u64,
+    pub size: usize,
+    pub capacity: usize,
+}
+
+impl TtlCacheStats {
+    /// Fraction of lookups that were hits; 0 before any lookup
+    pub fn hit_ratio(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+struct Entry<V> {
+    value: V,
+    expires_at: Option<Instant>,
+}
+
+// Atomic so lookups through `peek` can count under a shared borrow
+#[derive(Default)]
+struct Counters {
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+    expirations: AtomicU64,
+}
+
+/// LRU cache holding at most `capacity` entries, each optionally expiring after a TTL
+///
+/// Not synchronized: owners wrap it in whichever lock suits them. Expired entries stop being
+/// returned as soon as their TTL passes but hold their slot until looked up, evicted or swept.
+pub struct TtlCache<K, V> {
+    entries: LruCache<K, Entry<V>>,
+    default_ttl: Option<Duration>,
+    counters: Counters,
+    clock: Arc<dyn Clock>,
+}
+
+impl<K: Hash + Eq, V> fmt::Debug for TtlCache<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TtlCache")
+            .field("len", &self.entries.len())
+            .field("capacity", &self.entries.cap())
+            .field("default_ttl", &self.default_ttl)
+            .finish()
+    }
+}
+
+impl<K: Hash + Eq, V> TtlCache<K, V> {
+    /// Cache of at most `capacity` entries (at least one) that don't expire unless given a TTL
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: LruCache::new(NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN)),
+            default_ttl: None,
+            counters: Counters::default(),
+            clock: clock::system_clock(),
+        }
+    }
+
+    /// Expire entries inserted without a TTL of their own after `ttl`
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.default_ttl = Some(ttl);
+        self
+    }
+
+    /// Read time from `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.entries.cap().get()
+    }
+
+    /// Entries held, including expired ones not yet removed
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Insert with the default TTL, returning the entry evicted to make room, if any
+    pub fn insert(&mut self, key: K, value: V) -> Option<(K, V)> {
+        let expires_at = self.default_ttl.map(|ttl| self.clock.instant() + ttl);
+        self.insert_entry(key, Entry { value, expires_at })
+    }
+
+    /// Insert expiring after `ttl`, returning the entry evicted to make room, if any
+    pub fn insert_with_ttl(&mut self, key: K, value: V, ttl: Duration) -> Option<(K, V)> {
+        let expires_at = Some(self.clock.instant() + ttl);
+        self.insert_entry(key, Entry { value, expires_at })
+    }
+
+    fn insert_entry(&mut self, key: K, entry: Entry<V>) -> Option<(K, V)> {
+        let (evicted_key, evicted) = self.entries.push(key, entry)?;
+        // `push` also hands back the previous value when the key was already present
+        if self.entries.contains(&evicted_key) {
+            return None;
+        }
+        self.count_removal(&evicted);
+        Some((evicted_key, evicted.value))
+    }
+
+    /// Live value for `key`, marking it most recently used; an expired entry is removed
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        self.get_mut(key).map(|value| &*value)
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let now = self.clock.instant();
+        let expired = self.entries.peek(key).map(|entry| Self::is_expired(entry, now));
+        match expired {
+            Some(false) => {
+                self.counters.hits.fetch_add(1, Ordering::Relaxed);
+                self.entries.get_mut(key).map(|entry| &mut entry.value)
+            }
+            Some(true) => {
+                self.entries.pop(key);
+                self.counters.expirations.fetch_add(1, Ordering::Relaxed);
+                self.counters.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+            None => {
+                self.counters.misses.fetch_add(1, Ordering::Relaxed);
+                None
+            }
+        }
+    }
+
+    /// Live value for `key` without touching its recency
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        let now = self.clock.instant();
+        let value = self.entries.peek(key)
+            .filter(|entry| !Self::is_expired(entry, now))
+            .map(|entry| &entry.value);
+        let counter = if value.is_some() { &self.counters.hits } else { &self.counters.misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+        value
+    }
+
+    /// Whether `key` has a live entry; not counted as a lookup
+    pub fn contains(&self, key: &K) -> bool {
+        let now = self.clock.instant();
+        self.entries.peek(key).is_some_and(|entry| !Self::is_expired(entry, now))
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        self.entries.pop(key).map(|entry| entry.value)
+    }
+
+    /// Remove the least recently used entry to make room, e.g. under a memory bound
+    pub fn evict_lru(&mut self) -> Option<(K, V)> {
+        let (key, entry) = self.entries.pop_lru()?;
+        self.count_removal(&entry);
+        Some((key, entry.value))
+    }
+
+    /// Remove every entry `remove` selects, returning them
+    pub fn remove_where(&mut self, mut remove: impl FnMut(&K, &V) -> bool) -> Vec<(K, V)>
+    where
+        K: Clone,
+    {
+        self.remove_entries(|key, entry| remove(key, &entry.value))
+            .into_iter()
+            .map(|(key, entry)| (key, entry.value))
+            .collect()
+    }
+
+    /// Remove every expired entry, returning their keys
+    pub fn sweep_expired(&mut self) -> Vec<K>
+    where
+        K: Clone,
+    {
+        let now = self.clock.instant();
+        let expired: Vec<K> = self.remove_entries(|_, entry| Self::is_expired(entry, now))
+            .into_iter()
+            .map(|(key, _)| key)
+            .collect();
+        self.counters.expirations.fetch_add(expired.len() as u64, Ordering::Relaxed);
+        expired
+    }
+
+    fn remove_entries(&mut self, mut remove: impl FnMut(&K, &Entry<V>) -> bool) -> Vec<(K, Entry<V>)>
+    where
+        K: Clone,
+    {
+        let keys: Vec<K> = self.entries.iter()
+            .filter(|(key, entry)| remove(key, entry))
+            .map(|(key, _)| key.clone())
+            .collect();
+        keys.into_iter()
+            .filter_map(|key| self.entries.pop(&key).map(|entry| (key, entry)))
+            .collect()
+    }
+
+    /// Change the capacity, evicting least recently used entries that no longer fit
+    pub fn resize(&mut self, capacity: usize) {
+        let capacity = NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN);
+        while self.entries.len() > capacity.get() {
+            self.evict_lru();
+        }
+        self.entries.resize(capacity);
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Live entries, most recently used first
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        let now = self.clock.instant();
+        self.entries.iter()
+            .filter(move |(_, entry)| !Self::is_expired(entry, now))
+            .map(|(key, entry)| (key, &entry.value))
+    }
+
+    /// Every value held, expired or not, for accounting what the cache occupies
+    pub fn values_held(&self) -> impl Iterator<Item = &V> {
+        self.entries.iter().map(|(_, entry)| &entry.value)
+    }
+
+    pub fn stats(&self) -> TtlCacheStats {
+        TtlCacheStats {
+            hits: self.counters.hits.load(Ordering::Relaxed),
+            misses: self.counters.misses.load(Ordering::Relaxed),
+            evictions: self.counters.evictions.load(Ordering::Relaxed),
+            expirations: self.counters.expirations.load(Ordering::Relaxed),
+            size: self.entries.len(),
+            capacity: self.capacity(),
+        }
+    }
+
+    pub fn reset_stats(&mut self) {
+        self.counters = Counters::default();
+    }
+
+    fn is_expired(entry: &Entry<V>, now: Instant) -> bool {
+        entry.expires_at.is_some_and(|expires_at| now >= expires_at)
+    }
+
+    /// Count an entry dropped for room: an eviction if it was live, an expiration if not
+    fn count_removal(&self, entry: &Entry<V>) {
+        let counter = if Self::is_expired(entry, self.clock.instant()) {
+            &self.counters.expirations
+        } else {
+            &self.counters.evictions
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+
+    #[test]
+    fn test_entries_expire_after_ttl() {
+        let clock = Arc::new(MockClock::default());
+        let mut cache = TtlCache::new(10).with_ttl(Duration::from_secs(60)).with_clock(clock.clone());
+        cache.insert("default", 1);
+        cache.insert_with_ttl("short", 2, Duration::from_secs(10));
+
+        clock.advance(Duration::from_secs(10));
+        assert_eq!(cache.get(&"short"), None);
+        assert_eq!(cache.peek(&"default"), Some(&1));
+
+        clock.advance(Duration::from_secs(49));
+        assert_eq!(cache.get(&"default"), Some(&1));
+        clock.advance(Duration::from_secs(1));
+        assert!(!cache.contains(&"default"));
+        assert_eq!(cache.iter().count(), 0);
+
+        // Still held until swept
+        assert_eq!(cache.len(), 1);
+        assert_eq!(cache.values_held().collect::<Vec<_>>(), vec![&1]);
+        assert_eq!(cache.sweep_expired(), vec!["default"]);
+        assert!(cache.is_empty());
+        assert_eq!(cache.stats().expirations, 2);
+
+        // Without a TTL entries only leave by eviction or removal
+        let mut forever = TtlCache::new(1).with_clock(clock.clone());
+        forever.insert("pinned", 0);
+        clock.advance(Duration::from_secs(86_400));
+        assert_eq!(forever.get(&"pinned"), Some(&0));
+    }
+
+    #[test]
+    fn test_least_recently_used_evicted_first() {
+        let mut cache = TtlCache::new(3);
+        cache.insert("a", 1);
+        cache.insert("b", 2);
+        cache.insert("c", 3);
+
+        // Reading "a" makes "b" the least recently used; peeking "b" doesn't save it
+        assert_eq!(cache.get(&"a"), Some(&1));
+        assert_eq!(cache.peek(&"b"), Some(&2));
+        assert_eq!(cache.insert("d", 4), Some(("b", 2)));
+        // Replacing a key isn't an eviction
+        assert_eq!(cache.insert("c", 30), None);
+        assert_eq!(cache.insert("e", 5), Some(("a", 1)));
+        assert_eq!(cache.iter().map(|(key, _)| *key).collect::<Vec<_>>(), vec!["e", "c", "d"]);
+
+        cache.resize(1);
+        assert_eq!(cache.iter().map(|(key, value)| (*key, *value)).collect::<Vec<_>>(), vec![("e", 5)]);
+        assert_eq!(cache.stats().evictions, 4);
+        assert_eq!(cache.evict_lru(), Some(("e", 5)));
+        assert_eq!(cache.stats().evictions, 5);
+    }
+
+    #[test]
+    fn test_hits_and_misses_counted() {
+        let clock = Arc::new(MockClock::default());
+        let mut cache = TtlCache::new(2).with_clock(clock.clone());
+        cache.insert_with_ttl("k".to_string(), "v", Duration::from_secs(5));
+
+        assert!(cache.get(&"k".to_string()).is_some());
+        assert!(cache.peek(&"k".to_string()).is_some());
+        assert!(cache.get(&"missing".to_string()).is_none());
+        clock.advance(Duration::from_secs(5));
+        assert!(cache.peek(&"k".to_string()).is_none());
+        assert!(!cache.contains(&"k".to_string()));
+
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses), (2, 2));
+        assert_eq!(stats.hit_ratio(), 0.5);
+        assert_eq!((stats.size, stats.capacity), (1, 2));
+
+        cache.reset_stats();
+        assert_eq!(cache.stats().hit_ratio(), 0.0);
+        assert_eq!(cache.remove_where(|key, _| key.starts_with('k')).len(), 1);
+    }
+}
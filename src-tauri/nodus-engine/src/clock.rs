@@ -0,0 +1,107 @@
+// src-tauri/src/clock.rs
+// Clock - Injectable source of the current time
+// Expiry, TTL and backoff logic reads time through a `Clock` so tests can move it without sleeping
+
+use chrono::{DateTime, TimeZone, Utc};
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Source of wall-clock and monotonic time
+pub trait Clock: Send + Sync + fmt::Debug {
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Monotonic time, for intervals that must not jump with the wall clock
+    fn instant(&self) -> Instant;
+}
+
+/// The real clock
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn instant(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// Shared handle to the real clock, the default for every component taking a clock
+pub fn system_clock() -> Arc<dyn Clock> {
+    Arc::new(SystemClock)
+}
+
+/// Clock that stands still until advanced, for deterministic tests
+///
+/// Wall-clock and monotonic readings move together, so a component may use either.
+#[derive(Debug)]
+pub struct MockClock {
+    start: DateTime<Utc>,
+    origin: Instant,
+    /// Nanoseconds advanced so far
+    elapsed_nanos: AtomicU64,
+}
+
+impl MockClock {
+    /// Clock reading `start`
+    pub fn at(start: DateTime<Utc>) -> Self {
+        Self {
+            start,
+            origin: Instant::now(),
+            elapsed_nanos: AtomicU64::new(0),
+        }
+    }
+
+    /// Move the clock forward by `by`; it saturates some 584 years out
+    pub fn advance(&self, by: Duration) {
+        let by = u64::try_from(by.as_nanos()).unwrap_or(u64::MAX);
+        let _ = self.elapsed_nanos.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |elapsed| {
+            Some(elapsed.saturating_add(by))
+        });
+    }
+
+    /// Time advanced since the clock was created
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_nanos(self.elapsed_nanos.load(Ordering::SeqCst))
+    }
+}
+
+impl Default for MockClock {
+    /// Clock reading 2024-01-01T00:00:00Z, so runs don't depend on when they happen
+    fn default() -> Self {
+        Self::at(Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).single().unwrap_or_default())
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> DateTime<Utc> {
+        // Past chrono's range the reading pins to its latest representable time
+        chrono::Duration::from_std(self.elapsed()).ok()
+            .and_then(|elapsed| self.start.checked_add_signed(elapsed))
+            .unwrap_or(DateTime::<Utc>::MAX_UTC)
+    }
+
+    fn instant(&self) -> Instant {
+        self.origin + self.elapsed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mock_clock_moves_only_when_advanced() {
+        let clock = MockClock::default();
+        let (wall, monotonic) = (clock.now(), clock.instant());
+        assert_eq!(clock.now(), wall);
+
+        clock.advance(Duration::from_secs(90));
+        assert_eq!(clock.now() - wall, chrono::Duration::seconds(90));
+        assert_eq!(clock.instant() - monotonic, Duration::from_secs(90));
+    }
+}
@@ -3,15 +3,21 @@
 // Provides secure frontend access to database and state management with automatic observability
 
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+use dashmap::DashMap;
+use tauri::Manager;
 use uuid::Uuid;
 use serde_json::Value;
 
-use crate::database::{DatabaseManager, EntityOperation, EntityQuery};
+use crate::database::{ChangeFeedError, DatabaseContext, DatabaseManager, DeclassificationRequest, EntityOperation, EntityQuery, QueryFilter};
 use crate::state::{AppState, HybridStateManager};
-use crate::security::{ClassificationLevel, SecurityContext};
+use crate::security::{ClassificationLevel, SecurityContext, SecurityLabel};
 use crate::observability::{ObservabilityContext, ActionDispatcher, AsyncOrchestrator, OperationConfig};
 use crate::error::AppError;
+use crate::shutdown::ShutdownCoordinator;
+use crate::clock::Clock;
 
 /// Tauri command for entity read operations with automatic observability
 #[tauri::command]
@@ -26,9 +32,13 @@ pub async fn read_entity(
         .map_err(|_| "Invalid session ID format")?;
     
     // Get security context
-    let security_context = app_state.security_manager
-        .get_security_context(session_uuid).await
-        .ok_or("Invalid or expired session")?;
+    let security_context = app_state.session_context(session_uuid).await
+        .map_err(|e| e.to_string())?;
+
+    // Deny once the user's clearance context has lapsed
+    app_state.get_valid_user_context(&security_context.user_id).await
+        .map_err(|e| e.to_string())?;
+    check_tenant_active(&app_state, &security_context).await?;
 
     // Parse classification level
     let classification_level = classification
@@ -101,21 +111,61 @@ pub async fn write_entity(
     entity_id: Option<String>,
     entity_data: Value,
     classification: Option<String>,
+    idempotency_key: Option<String>,
     app_state: tauri::State<'_, AppState>,
+    shutdown: tauri::State<'_, Arc<ShutdownCoordinator>>,
 ) -> Result<EntityResult, String> {
+    // Refused once shutdown starts; held until return so teardown waits for this write
+    let _operation = shutdown.begin_operation().map_err(|e| e.to_string())?;
+    let start_time = std::time::Instant::now();
+
     let session_uuid = Uuid::parse_str(&session_id)
         .map_err(|_| "Invalid session ID format")?;
     
     // Get security context
-    let security_context = app_state.security_manager
-        .get_security_context(session_uuid).await
-        .ok_or("Invalid or expired session")?;
+    let security_context = app_state.session_context(session_uuid).await
+        .map_err(|e| e.to_string())?;
+
+    // Deny once the user's clearance context has lapsed
+    app_state.get_valid_user_context(&security_context.user_id).await
+        .map_err(|e| e.to_string())?;
+    check_tenant_active(&app_state, &security_context).await?;
 
     // Parse classification level
-    let classification_level = classification
+    let requested_classification = classification
         .map(|c| parse_classification(&c))
-        .transpose()?
-        .unwrap_or(ClassificationLevel::Internal);
+        .transpose()?;
+    let classification_level = requested_classification.clone().unwrap_or(ClassificationLevel::Internal);
+
+    // A keyed create goes straight to the store, which claims the key and chains its audit record with the row
+    if let (None, Some(idempotency_key)) = (&entity_id, &idempotency_key) {
+        let db_context = DatabaseContext::new(
+            security_context.user_id.clone(),
+            session_uuid,
+            security_context.security_label.clone(),
+            security_context.tenant_id.clone(),
+        )
+        .with_permissions(security_context.permissions.clone());
+        let (entity, envelope) = app_state.db_manager
+            .create_entity_idempotent(&entity_type, entity_data, idempotency_key, requested_classification.as_ref(), &db_context)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        return Ok(EntityResult {
+            entity_id: entity.id.to_string(),
+            entity_type: entity.entity_type,
+            data: entity.data,
+            classification: entity.classification.to_string(),
+            last_modified: entity.updated_at,
+            metadata: EntityMetadata {
+                operation_id: envelope.operation_id.to_string(),
+                user_id: security_context.user_id,
+                session_id,
+                audit_logged: true,
+                execution_time_ms: start_time.elapsed().as_millis() as u64,
+            },
+        });
+    }
 
     // Generate entity ID if not provided
     let final_entity_id = entity_id.unwrap_or_else(|| Uuid::new_v4().to_string());
@@ -186,14 +236,21 @@ pub async fn delete_entity(
     entity_id: String,
     classification: Option<String>,
     app_state: tauri::State<'_, AppState>,
+    shutdown: tauri::State<'_, Arc<ShutdownCoordinator>>,
 ) -> Result<DeleteResult, String> {
+    let _operation = shutdown.begin_operation().map_err(|e| e.to_string())?;
+
     let session_uuid = Uuid::parse_str(&session_id)
         .map_err(|_| "Invalid session ID format")?;
     
     // Get security context
-    let security_context = app_state.security_manager
-        .get_security_context(session_uuid).await
-        .ok_or("Invalid or expired session")?;
+    let security_context = app_state.session_context(session_uuid).await
+        .map_err(|e| e.to_string())?;
+
+    // Deny once the user's clearance context has lapsed
+    app_state.get_valid_user_context(&security_context.user_id).await
+        .map_err(|e| e.to_string())?;
+    check_tenant_active(&app_state, &security_context).await?;
 
     // Parse classification level
     let classification_level = classification
@@ -266,9 +323,13 @@ pub async fn query_entities(
         .map_err(|_| "Invalid session ID format")?;
     
     // Get security context
-    let security_context = app_state.security_manager
-        .get_security_context(session_uuid).await
-        .ok_or("Invalid or expired session")?;
+    let security_context = app_state.session_context(session_uuid).await
+        .map_err(|e| e.to_string())?;
+
+    // Deny once the user's clearance context has lapsed
+    app_state.get_valid_user_context(&security_context.user_id).await
+        .map_err(|e| e.to_string())?;
+    check_tenant_active(&app_state, &security_context).await?;
 
     // Parse classification level
     let classification_level = classification
@@ -307,7 +368,7 @@ pub async fn query_entities(
         || async {
             // Build query from parameters
             let query = EntityQuery {
-                filters: query_params.filters.unwrap_or_default(),
+                filter: query_params.filter.or_else(|| query_params.filters.map(QueryFilter::from)),
                 sort_by: query_params.sort_by,
                 sort_order: query_params.sort_order.unwrap_or("asc".to_string()),
                 limit: query_params.limit.unwrap_or(100),
@@ -363,14 +424,17 @@ pub async fn batch_operation(
     session_id: String,
     operations: Vec<BatchOperationRequest>,
     app_state: tauri::State<'_, AppState>,
+    shutdown: tauri::State<'_, Arc<ShutdownCoordinator>>,
 ) -> Result<BatchOperationResult, String> {
+    let _operation = shutdown.begin_operation().map_err(|e| e.to_string())?;
+
     let session_uuid = Uuid::parse_str(&session_id)
         .map_err(|_| "Invalid session ID format")?;
     
     // Get security context
-    let security_context = app_state.security_manager
-        .get_security_context(session_uuid).await
-        .ok_or("Invalid or expired session")?;
+    let security_context = app_state.session_context(session_uuid).await
+        .map_err(|e| e.to_string())?;
+    check_tenant_active(&app_state, &security_context).await?;
 
     // Create observability context
     let obs_context = ObservabilityContext::new(
@@ -380,6 +444,7 @@ pub async fn batch_operation(
         &security_context.user_id,
         session_uuid,
     );
+    app_state.information_flow.track(&obs_context).await;
 
     // Execute through async orchestrator for batch processing
     let operation_runner = app_state.async_orchestrator.create_runner(
@@ -426,13 +491,21 @@ pub async fn batch_operation(
                             entity_id,
                             &security_context.security_label,
                         ).await {
-                            Ok(entity) => BatchOperationItemResult {
-                                index,
-                                success: true,
-                                entity_id: entity_id.clone(),
-                                entity_type: operation.entity_type.clone(),
-                                data: Some(entity.data),
-                                error: None,
+                            Ok(entity) => {
+                                // Later writes in the batch may carry what was read here
+                                app_state.information_flow.record_read(
+                                    &obs_context,
+                                    &format!("entity:{}", entity_id),
+                                    &SecurityLabel::new(entity.classification.clone(), entity.compartments.clone()),
+                                ).await;
+                                BatchOperationItemResult {
+                                    index,
+                                    success: true,
+                                    entity_id: entity_id.clone(),
+                                    entity_type: operation.entity_type.clone(),
+                                    data: Some(entity.data),
+                                    error: None,
+                                }
                             },
                             Err(e) => BatchOperationItemResult {
                                 index,
@@ -451,13 +524,23 @@ pub async fn batch_operation(
                             ))?;
                         let entity_id = operation.entity_id.clone()
                             .unwrap_or_else(|| Uuid::new_v4().to_string());
-                        
-                        match app_state.database_manager.write_entity(
-                            &operation.entity_type,
-                            &entity_id,
-                            entity_data,
-                            &security_context.security_label,
-                        ).await {
+                        let target = SecurityLabel::new(
+                            classification_level.clone(),
+                            security_context.security_label.compartments.clone(),
+                        );
+
+                        // Refuse to write what the batch has read below its label
+                        let written = match app_state.information_flow.check_write(&obs_context, &target).await {
+                            Ok(()) => app_state.database_manager.write_entity(
+                                &operation.entity_type,
+                                &entity_id,
+                                entity_data,
+                                &target,
+                            ).await.map_err(|e| e.to_string()),
+                            Err(e) => Err(e.to_string()),
+                        };
+
+                        match written {
                             Ok(entity) => BatchOperationItemResult {
                                 index,
                                 success: true,
@@ -472,7 +555,7 @@ pub async fn batch_operation(
                                 entity_id: entity_id.clone(),
                                 entity_type: operation.entity_type.clone(),
                                 data: None,
-                                error: Some(e.to_string()),
+                                error: Some(e),
                             },
                         }
                     },
@@ -532,7 +615,9 @@ pub async fn batch_operation(
         },
         Some(config),
         &app_state,
-    ).await.map_err(|e| e.to_string())?;
+    ).await;
+    app_state.information_flow.finish(obs_context.operation_id).await;
+    let operation_result = operation_result.map_err(|e| e.to_string())?;
 
     let batch_data = operation_result.value;
 
@@ -564,9 +649,8 @@ pub async fn get_state_info(
         .map_err(|_| "Invalid session ID format")?;
     
     // Verify session exists
-    app_state.security_manager
-        .get_security_context(session_uuid).await
-        .ok_or("Invalid or expired session")?;
+    app_state.session_context(session_uuid).await
+        .map_err(|e| e.to_string())?;
 
     // Get state information
     let state_info = app_state.hybrid_state_manager.get_state_info().await;
@@ -587,8 +671,216 @@ pub async fn get_state_info(
     })
 }
 
+/// A running entity change stream and the session that opened it
+struct EntityChangeStream {
+    // Only the opening session may end the stream
+    session_id: Uuid,
+    task: tokio::task::AbortHandle,
+}
+
+fn entity_change_streams() -> &'static DashMap<Uuid, EntityChangeStream> {
+    static STREAMS: OnceLock<DashMap<Uuid, EntityChangeStream>> = OnceLock::new();
+    STREAMS.get_or_init(DashMap::new)
+}
+
+/// Tauri command that pushes entity changes made by any session, instead of re-querying
+///
+/// Emits an `EntityChanged` on `entity_changes/<subscription_id>` for each committed change
+/// to `entity_types` (all types if empty) that the session could read; changes above its
+/// clearance or outside its compartments and tenant are never signalled. `{"resync": true}`
+/// means changes were missed and the frontend should re-query. The stream ends with a
+/// `closed` payload when the session or its clearance lapses, or on `unsubscribe_entity_changes`.
+#[tauri::command]
+pub async fn subscribe_entity_changes(
+    session_id: String,
+    entity_types: Vec<String>,
+    app: tauri::AppHandle,
+    app_state: tauri::State<'_, AppState>,
+) -> Result<EntityChangeStreamInfo, String> {
+    let session_uuid = Uuid::parse_str(&session_id)
+        .map_err(|_| "Invalid session ID format")?;
+
+    let security_context = app_state.session_context(session_uuid).await
+        .map_err(|e| e.to_string())?;
+
+    // Deny once the user's clearance context has lapsed
+    app_state.get_valid_user_context(&security_context.user_id).await
+        .map_err(|e| e.to_string())?;
+    check_tenant_active(&app_state, &security_context).await?;
+
+    let db_context = DatabaseContext::new(
+        security_context.user_id.clone(),
+        session_uuid,
+        security_context.security_label.clone(),
+        security_context.tenant_id.clone(),
+    )
+    .with_permissions(security_context.permissions.clone());
+    let mut subscription = app_state.db_manager.change_feed().subscribe(db_context, entity_types.clone());
+
+    let subscription_id = Uuid::new_v4();
+    let event = format!("entity_changes/{}", subscription_id);
+    let event_name = event.clone();
+    let user_id = security_context.user_id.clone();
+    let task = tokio::spawn(async move {
+        loop {
+            let change = subscription.recv().await;
+
+            // Clearance is re-checked per change, so a lapsed session hears nothing further
+            let state = app.state::<AppState>();
+            let session_valid = state.session_is_live(session_uuid).await
+                && state.get_valid_user_context(&user_id).await.is_ok();
+            if !session_valid {
+                let _ = app.emit_all(&event_name, serde_json::json!({ "closed": "session_invalidated" }));
+                break;
+            }
+
+            let sent = match change {
+                Ok(change) => {
+                    // A delivered change counts as a read for tranquility
+                    state.db_manager.mac_engine().record_access(change.id, session_uuid).await;
+                    app.emit_all(&event_name, &change)
+                }
+                Err(ChangeFeedError::Missed) => app.emit_all(&event_name, serde_json::json!({ "resync": true })),
+                Err(ChangeFeedError::Closed) => {
+                    let _ = app.emit_all(&event_name, serde_json::json!({ "closed": "feed_closed" }));
+                    break;
+                }
+            };
+            if let Err(e) = sent {
+                tracing::warn!("Entity change stream {} stopped: {}", subscription_id, e);
+                break;
+            }
+        }
+
+        entity_change_streams().remove(&subscription_id);
+    });
+    entity_change_streams().insert(subscription_id, EntityChangeStream {
+        session_id: session_uuid,
+        task: task.abort_handle(),
+    });
+
+    Ok(EntityChangeStreamInfo {
+        subscription_id: subscription_id.to_string(),
+        event,
+        entity_types,
+    })
+}
+
+/// Tauri command ending an entity change stream
+#[tauri::command]
+pub async fn unsubscribe_entity_changes(
+    session_id: String,
+    subscription_id: String,
+    app_state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    let session_uuid = Uuid::parse_str(&session_id)
+        .map_err(|_| "Invalid session ID format")?;
+    let subscription_uuid = Uuid::parse_str(&subscription_id)
+        .map_err(|_| "Invalid subscription ID format")?;
+    if !app_state.session_is_live(session_uuid).await {
+        return Err("Invalid or expired session".to_string());
+    }
+    end_change_stream(subscription_uuid, session_uuid)
+}
+
+/// End a change stream if `session_id` opened it; ending a closed stream is a no-op
+fn end_change_stream(subscription_id: Uuid, session_id: Uuid) -> Result<(), String> {
+    match entity_change_streams().remove_if(&subscription_id, |_, stream| stream.session_id == session_id) {
+        Some((_, stream)) => stream.task.abort(),
+        None if entity_change_streams().contains_key(&subscription_id) => {
+            return Err("Entity change stream belongs to another session".to_string());
+        }
+        None => {}
+    }
+    Ok(())
+}
+
+/// Tauri command proposing a two-person downgrade of `entity_ids` to `target_label`
+///
+/// Nothing is relabeled until a second operator approves the request with
+/// `approve_declassification` within the approval window.
+#[tauri::command]
+pub async fn propose_declassification(
+    session_id: String,
+    entity_ids: Vec<Uuid>,
+    target_label: SecurityLabel,
+    justification: String,
+    app_state: tauri::State<'_, AppState>,
+    shutdown: tauri::State<'_, Arc<ShutdownCoordinator>>,
+) -> Result<DeclassificationRequest, String> {
+    let _operation = shutdown.begin_operation().map_err(|e| e.to_string())?;
+    let db_context = declassification_context(&session_id, &app_state).await?;
+
+    app_state.declassification
+        .propose(entity_ids, target_label, justification, &db_context)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Tauri command approving a pending declassification; returns the relabeled entity ids
+#[tauri::command]
+pub async fn approve_declassification(
+    session_id: String,
+    request_id: Uuid,
+    app_state: tauri::State<'_, AppState>,
+    shutdown: tauri::State<'_, Arc<ShutdownCoordinator>>,
+) -> Result<Vec<Uuid>, String> {
+    let _operation = shutdown.begin_operation().map_err(|e| e.to_string())?;
+    let db_context = declassification_context(&session_id, &app_state).await?;
+
+    let relabeled = app_state.declassification
+        .approve(request_id, &db_context)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(relabeled.iter().map(|entity| entity.id).collect())
+}
+
+/// Tauri command listing the session tenant's declassification requests awaiting approval
+#[tauri::command]
+pub async fn list_declassifications(
+    session_id: String,
+    app_state: tauri::State<'_, AppState>,
+) -> Result<Vec<DeclassificationRequest>, String> {
+    let db_context = declassification_context(&session_id, &app_state).await?;
+
+    app_state.declassification
+        .pending(&db_context, app_state.clock.now())
+        .await
+        .map_err(|e| e.to_string())
+}
+
 // Helper functions
 
+/// Database context of a valid session, with its permissions, for the declassification commands
+async fn declassification_context(session_id: &str, app_state: &AppState) -> Result<DatabaseContext, String> {
+    let session_uuid = Uuid::parse_str(session_id)
+        .map_err(|_| "Invalid session ID format")?;
+
+    let security_context = app_state.session_context(session_uuid).await
+        .map_err(|e| e.to_string())?;
+
+    // Deny once the user's clearance context has lapsed
+    app_state.get_valid_user_context(&security_context.user_id).await
+        .map_err(|e| e.to_string())?;
+    check_tenant_active(app_state, &security_context).await?;
+
+    Ok(DatabaseContext::new(
+        security_context.user_id.clone(),
+        session_uuid,
+        security_context.security_label.clone(),
+        security_context.tenant_id.clone(),
+    )
+    .with_permissions(security_context.permissions.clone()))
+}
+
+/// Refuse work for a tenant that is suspended or otherwise not active
+async fn check_tenant_active(app_state: &AppState, security_context: &SecurityContext) -> Result<(), String> {
+    app_state.db_manager.tenant_gate()
+        .check(security_context.tenant_id.as_deref(), &security_context.permissions)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 fn parse_classification(classification: &str) -> Result<ClassificationLevel, String> {
     match classification.to_uppercase().as_str() {
         "UNCLASSIFIED" => Ok(ClassificationLevel::Unclassified),
@@ -602,7 +894,7 @@ fn parse_classification(classification: &str) -> Result<ClassificationLevel, Str
 
 // Request/Response types for Tauri commands
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct EntityResult {
     pub entity_id: String,
     pub entity_type: String,
@@ -612,7 +904,7 @@ pub struct EntityResult {
     pub metadata: EntityMetadata,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct EntityMetadata {
     pub operation_id: String,
     pub user_id: String,
@@ -621,7 +913,7 @@ pub struct EntityMetadata {
     pub execution_time_ms: u64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct DeleteResult {
     pub entity_id: String,
     pub entity_type: String,
@@ -630,8 +922,11 @@ pub struct DeleteResult {
     pub metadata: EntityMetadata,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct QueryParams {
+    /// Structured filter; takes precedence over `filters`
+    pub filter: Option<QueryFilter>,
+    /// Legacy `field = value` filters
     pub filters: Option<HashMap<String, Value>>,
     pub sort_by: Option<String>,
     pub sort_order: Option<String>,
@@ -640,7 +935,7 @@ pub struct QueryParams {
     pub include_metadata: Option<bool>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct QueryResult {
     pub entities: Vec<EntityResult>,
     pub total_count: u64,
@@ -648,7 +943,7 @@ pub struct QueryResult {
     pub execution_metadata: QueryExecutionMetadata,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct QueryExecutionMetadata {
     pub duration_ms: u64,
     pub retry_attempts: u32,
@@ -656,14 +951,14 @@ pub struct QueryExecutionMetadata {
     pub resource_usage: ResourceUsageInfo,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct ResourceUsageInfo {
     pub cpu_time_ms: u64,
     pub memory_bytes: u64,
     pub io_operations: u64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct BatchOperationRequest {
     pub operation_type: String, // "read", "write", "delete"
     pub entity_type: String,
@@ -672,7 +967,7 @@ pub struct BatchOperationRequest {
     pub classification: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct BatchOperationResult {
     pub total_operations: usize,
     pub successful_operations: usize,
@@ -681,7 +976,7 @@ pub struct BatchOperationResult {
     pub execution_metadata: BatchExecutionMetadata,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct BatchOperationItemResult {
     pub index: usize,
     pub success: bool,
@@ -691,7 +986,7 @@ pub struct BatchOperationItemResult {
     pub error: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct BatchExecutionMetadata {
     pub duration_ms: u64,
     pub retry_attempts: u32,
@@ -699,6 +994,13 @@ pub struct BatchExecutionMetadata {
     pub resource_usage: ResourceUsageInfo,
 }
 
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct EntityChangeStreamInfo {
+    pub subscription_id: String,
+    pub event: String,
+    pub entity_types: Vec<String>,
+}
+
 #[derive(Debug)]
 struct BatchOperationData {
     pub results: Vec<BatchOperationItemResult>,
@@ -706,7 +1008,7 @@ struct BatchOperationData {
     pub failure_count: usize,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct StateInfo {
     pub total_entities: u64,
     pub active_sessions: u32,
@@ -717,7 +1019,7 @@ pub struct StateInfo {
     pub performance_metrics: PerformanceMetrics,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct PerformanceMetrics {
     pub avg_query_time_ms: f64,
     pub avg_write_time_ms: f64,
@@ -729,6 +1031,26 @@ pub struct PerformanceMetrics {
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn test_change_stream_ends_only_for_its_session() {
+        let owner = Uuid::new_v4();
+        let subscription_id = Uuid::new_v4();
+        let task = tokio::spawn(std::future::pending::<()>());
+        entity_change_streams().insert(subscription_id, EntityChangeStream {
+            session_id: owner,
+            task: task.abort_handle(),
+        });
+
+        assert!(end_change_stream(subscription_id, Uuid::new_v4()).is_err());
+        assert!(entity_change_streams().contains_key(&subscription_id));
+
+        end_change_stream(subscription_id, owner).unwrap();
+        assert!(!entity_change_streams().contains_key(&subscription_id));
+        assert!(task.await.unwrap_err().is_cancelled());
+        // Already closed
+        assert!(end_change_stream(subscription_id, owner).is_ok());
+    }
+
     #[test]
     fn test_parse_classification() {
         assert!(matches!(
@@ -742,6 +1064,7 @@ mod tests {
     #[test]
     fn test_query_params_serialization() {
         let params = QueryParams {
+            filter: None,
             filters: Some(HashMap::new()),
             sort_by: Some("created_at".to_string()),
             sort_order: Some("desc".to_string()),
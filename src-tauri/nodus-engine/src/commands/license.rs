@@ -3,11 +3,14 @@
 // Provides secure frontend access to three-tier licensing system
 
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 use std::collections::HashMap;
 use uuid::Uuid;
+use std::sync::Arc;
 
+use crate::shutdown::ShutdownCoordinator;
 use crate::license::{LicenseManager, LicenseTier, LicenseInfo, FeatureDefinition};
-use crate::security::{ClassificationLevel, SecurityContext};
+use crate::security::{has_permission, ClassificationLevel, Permission, SecurityContext};
 use crate::observability::ObservabilityContext;
 use crate::state::AppState;
 use crate::error::AppError;
@@ -22,9 +25,8 @@ pub async fn get_license_info(
         .map_err(|_| "Invalid session ID format")?;
     
     // Get security context
-    let security_context = app_state.security_manager
-        .get_security_context(session_uuid).await
-        .ok_or("Invalid or expired session")?;
+    let security_context = app_state.session_context(session_uuid).await
+        .map_err(|e| e.to_string())?;
 
     // Create observability context
     let obs_context = ObservabilityContext::new(
@@ -66,9 +68,8 @@ pub async fn check_feature_availability(
         .map_err(|_| "Invalid session ID format")?;
     
     // Get security context
-    let security_context = app_state.security_manager
-        .get_security_context(session_uuid).await
-        .ok_or("Invalid or expired session")?;
+    let security_context = app_state.session_context(session_uuid).await
+        .map_err(|e| e.to_string())?;
 
     // Check feature availability
     let is_available = app_state.license_manager.has_feature(&feature_name).await;
@@ -109,9 +110,8 @@ pub async fn get_available_features(
         .map_err(|_| "Invalid session ID format")?;
     
     // Verify session exists
-    app_state.security_manager
-        .get_security_context(session_uuid).await
-        .ok_or("Invalid or expired session")?;
+    app_state.session_context(session_uuid).await
+        .map_err(|e| e.to_string())?;
 
     // Get current tier and all features
     let current_tier = app_state.license_manager.get_tier().await;
@@ -157,12 +157,11 @@ pub async fn validate_license(
         .map_err(|_| "Invalid session ID format")?;
     
     // Get security context
-    let security_context = app_state.security_manager
-        .get_security_context(session_uuid).await
-        .ok_or("Invalid or expired session")?;
+    let security_context = app_state.session_context(session_uuid).await
+        .map_err(|e| e.to_string())?;
 
     // Check if user has admin permissions
-    if !security_context.permissions.contains(&"license_admin".to_string()) {
+    if !has_permission(&security_context, Permission::LicenseAdmin) {
         return Err("Insufficient permissions for license validation".to_string());
     }
 
@@ -205,12 +204,11 @@ pub async fn get_license_usage(
         .map_err(|_| "Invalid session ID format")?;
     
     // Get security context
-    let security_context = app_state.security_manager
-        .get_security_context(session_uuid).await
-        .ok_or("Invalid or expired session")?;
+    let security_context = app_state.session_context(session_uuid).await
+        .map_err(|e| e.to_string())?;
 
     // Check if user has admin permissions
-    if !security_context.permissions.contains(&"license_admin".to_string()) {
+    if !has_permission(&security_context, Permission::LicenseAdmin) {
         return Err("Insufficient permissions for license usage data".to_string());
     }
 
@@ -262,12 +260,11 @@ pub async fn check_license_compliance(
         .map_err(|_| "Invalid session ID format")?;
     
     // Get security context
-    let security_context = app_state.security_manager
-        .get_security_context(session_uuid).await
-        .ok_or("Invalid or expired session")?;
+    let security_context = app_state.session_context(session_uuid).await
+        .map_err(|e| e.to_string())?;
 
     // Check if user has admin permissions
-    if !security_context.permissions.contains(&"license_admin".to_string()) {
+    if !has_permission(&security_context, Permission::LicenseAdmin) {
         return Err("Insufficient permissions for license compliance check".to_string());
     }
 
@@ -363,17 +360,19 @@ pub async fn update_license(
     session_id: String,
     license_data: String,
     app_state: tauri::State<'_, AppState>,
+    shutdown: tauri::State<'_, Arc<ShutdownCoordinator>>,
 ) -> Result<LicenseUpdateResult, String> {
+    // Refused once shutdown starts; held until return so teardown waits for this write
+    let _operation = shutdown.begin_operation().map_err(|e| e.to_string())?;
     let session_uuid = Uuid::parse_str(&session_id)
         .map_err(|_| "Invalid session ID format")?;
     
     // Get security context
-    let security_context = app_state.security_manager
-        .get_security_context(session_uuid).await
-        .ok_or("Invalid or expired session")?;
+    let security_context = app_state.session_context(session_uuid).await
+        .map_err(|e| e.to_string())?;
 
     // Check if user has admin permissions
-    if !security_context.permissions.contains(&"license_admin".to_string()) {
+    if !has_permission(&security_context, Permission::LicenseAdmin) {
         return Err("Insufficient permissions for license update".to_string());
     }
 
@@ -435,9 +434,8 @@ pub async fn get_tier_comparison(
         .map_err(|_| "Invalid session ID format")?;
     
     // Verify session exists
-    app_state.security_manager
-        .get_security_context(session_uuid).await
-        .ok_or("Invalid or expired session")?;
+    app_state.session_context(session_uuid).await
+        .map_err(|e| e.to_string())?;
 
     // Get current tier
     let current_tier = app_state.license_manager.get_tier().await;
@@ -501,7 +499,7 @@ fn get_upgrade_recommendations(current_tier: &LicenseTier) -> Vec<String> {
 
 // Request/Response types for Tauri commands
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct LicenseInfoResult {
     pub license_id: String,
     pub customer_name: String,
@@ -517,7 +515,7 @@ pub struct LicenseInfoResult {
     pub signature_valid: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct FeatureAvailabilityResult {
     pub feature_name: String,
     pub is_available: bool,
@@ -528,7 +526,7 @@ pub struct FeatureAvailabilityResult {
     pub enforcement_level: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct AvailableFeaturesResult {
     pub current_tier: String,
     pub available_features: Vec<FeatureInfo>,
@@ -536,7 +534,7 @@ pub struct AvailableFeaturesResult {
     pub total_features: usize,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct FeatureInfo {
     pub name: String,
     pub description: String,
@@ -546,7 +544,7 @@ pub struct FeatureInfo {
     pub enforcement_level: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct LicenseValidationResult {
     pub is_valid: bool,
     pub validation_time: chrono::DateTime<chrono::Utc>,
@@ -559,7 +557,7 @@ pub struct LicenseValidationResult {
     pub next_validation: chrono::DateTime<chrono::Utc>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct LicenseUsageResult {
     pub current_users: u32,
     pub max_users: u32,
@@ -573,7 +571,7 @@ pub struct LicenseUsageResult {
     pub last_updated: chrono::DateTime<chrono::Utc>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct LicenseComplianceResult {
     pub is_compliant: bool,
     pub compliance_score: f64,
@@ -584,7 +582,7 @@ pub struct LicenseComplianceResult {
     pub next_check: chrono::DateTime<chrono::Utc>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct ComplianceIssue {
     pub issue_type: String,
     pub severity: String,
@@ -592,7 +590,7 @@ pub struct ComplianceIssue {
     pub recommendation: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct LicenseUpdateResult {
     pub success: bool,
     pub updated_at: chrono::DateTime<chrono::Utc>,
@@ -602,14 +600,14 @@ pub struct LicenseUpdateResult {
     pub restart_required: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct TierComparisonResult {
     pub current_tier: String,
     pub available_tiers: Vec<TierInfo>,
     pub upgrade_recommendations: Vec<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct TierInfo {
     pub tier: String,
     pub is_current: bool,
@@ -3,30 +3,37 @@
 // Provides secure frontend access to backend functionality through detailed command handlers
 
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 use uuid::Uuid;
+use std::sync::Arc;
 use std::collections::HashMap;
 
+use crate::shutdown::ShutdownCoordinator;
 use crate::state::AppState;
 use crate::observability::ObservabilityContext;
-use crate::security::{SecurityLabel, ClassificationLevel};
+use crate::security::{SecurityLabel, ClassificationLevel, MACOperation, MACVerdict, constant_time};
 use crate::error::AppError;
+use crate::license::LicenseError;
+use crate::database::{BatchEntityOperation, BatchItemResult, DatabaseContext, EntityStore};
 
 // Command modules with detailed implementations
 pub mod security;
 pub mod data;
 pub mod observability;
 pub mod license;
+pub mod schema;
 
 // Re-export all command functions for Tauri registration
 pub use security::*;
 pub use data::*;
 pub use observability::*;
 pub use license::*;
+pub use schema::get_api_schema;
 
 type AppStateType = Arc<RwLock<AppState>>;
 
 /// Generic command result with automatic observability data
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct CommandResult<T> {
     pub success: bool,
     pub data: Option<T>,
@@ -35,7 +42,7 @@ pub struct CommandResult<T> {
 }
 
 /// Observability metadata returned with every command
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct ObservabilityMetadata {
     pub operation_id: String,
     pub duration_ms: u64,
@@ -46,7 +53,7 @@ pub struct ObservabilityMetadata {
 }
 
 /// Entity operation request (replaces JS ActionDispatcher entity operations)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct EntityOperation {
     pub entity_type: String,
     pub entity_id: String,
@@ -54,10 +61,25 @@ pub struct EntityOperation {
     pub data: serde_json::Value,
     pub user_id: String,
     pub session_id: String,
+    /// Run every check and report the would-be result without applying it
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Client key for a create; a retry with the same key returns the first create's entity
+    #[serde(default)]
+    pub idempotency_key: Option<String>,
+}
+
+/// Per-operation outcome of a batch, reported at the operation's original index
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct BatchEntityResult {
+    pub index: usize,
+    pub success: bool,
+    pub entity_id: Option<Uuid>,
+    pub error: Option<String>,
 }
 
 /// Async operation request (replaces JS AsyncOrchestrator operations)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct AsyncOperation {
     pub operation_name: String,
     pub operation_type: String,
@@ -69,7 +91,7 @@ pub struct AsyncOperation {
 }
 
 /// Storage operation request (replaces direct storage calls)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct StorageOperation {
     pub operation: String, // "get", "put", "delete", "query"
     pub key: String,
@@ -77,10 +99,13 @@ pub struct StorageOperation {
     pub classification: String,
     pub user_id: String,
     pub session_id: String,
+    /// Run every check and report the would-be result without applying it
+    #[serde(default)]
+    pub dry_run: bool,
 }
 
 /// UI action request (replaces declarative HTML actions)
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, JsonSchema)]
 pub struct UIAction {
     pub action_type: String,
     pub target: String,
@@ -89,6 +114,11 @@ pub struct UIAction {
     pub session_id: String,
 }
 
+/// Operation name to observe; dry runs are recorded as `dry_run`, never as the mutation itself
+fn observed_operation(operation: &str, dry_run: bool) -> &str {
+    if dry_run { "dry_run" } else { operation }
+}
+
 /// Macro for automatic observability wrapper (replaces JS execution gateways)
 macro_rules! with_observability {
     ($app_state:expr, $context:expr, $budget:expr, $operation:expr) => {{
@@ -100,39 +130,9 @@ macro_rules! with_observability {
         // Execute operation with automatic instrumentation
         let result = {
             let state = $app_state.read().await;
-            let instrumentation = AutomaticInstrumentation::new(&state);
-            
-            // Check if instrumentation is needed (policy decision)
-            let decision = instrumentation.should_instrument(&obs_context).await;
-            
-            if decision.enabled {
-                if decision.audit_required {
-                    // Automatic audit logging
-                    state.forensic_logger.log_operation_start(&obs_context).await?;
-                }
-                
-                if decision.metrics_enabled {
-                    // Automatic metrics
-                    state.metrics_registry.record_operation_start(&obs_context).await;
-                }
-            }
-            
-            // Execute the actual operation
-            let op_result = $operation.await;
-            
-            if decision.enabled {
-                if decision.audit_required {
-                    // Log operation completion
-                    state.forensic_logger.log_operation_end(&obs_context, &op_result).await?;
-                }
-                
-                if decision.metrics_enabled {
-                    let duration = start_time.elapsed();
-                    state.metrics_registry.record_operation_end(&obs_context, duration).await;
-                }
-            }
-            
-            op_result
+            state.automatic_instrumentation
+                .instrument_operation(&obs_context, $operation, &*state)
+                .await
         };
         
         let duration = start_time.elapsed();
@@ -183,10 +183,13 @@ macro_rules! with_observability {
 pub async fn execute_entity_operation(
     request: EntityOperation,
     app_state: State<'_, AppStateType>,
+    shutdown: State<'_, Arc<ShutdownCoordinator>>,
 ) -> Result<CommandResult<serde_json::Value>, String> {
+    // Refused once shutdown starts; held until return so teardown waits for this write
+    let _operation = shutdown.begin_operation().map_err(|e| e.to_string())?;
     let context = ObservabilityContext::new(
         "entity",
-        &request.operation,
+        observed_operation(&request.operation, request.dry_run),
         ClassificationLevel::Confidential, // Default, should be determined by policy
         &request.user_id,
         Uuid::parse_str(&request.session_id).map_err(|e| e.to_string())?,
@@ -200,6 +203,15 @@ pub async fn execute_entity_operation(
         budget,
         async {
             let state = app_state.read().await;
+
+            if request.dry_run {
+                let session_id = Uuid::parse_str(&request.session_id).map_err(|e| e.to_string())?;
+                let db_context = user_database_context(&state, &request.user_id, session_id).await?;
+                let operation = to_batch_operation(&request)?;
+                let outcome = state.db_manager.dry_run(&operation, &db_context).await
+                    .map_err(|e| e.to_string())?;
+                return serde_json::to_value(outcome).map_err(|e| e.to_string());
+            }
             
             // Route to appropriate entity handler based on operation
             match request.operation.as_str() {
@@ -235,12 +247,186 @@ pub async fn execute_entity_operation(
     Ok(result)
 }
 
+/// Batch entity operations in one transaction (replaces looping ActionDispatcher.dispatch)
+///
+/// MAC is enforced per operation: a denied or failed item is reported at its index while
+/// the rest commit, unless `all_or_nothing` is set, in which case any failure rolls back
+/// the whole batch. A dry-run batch reports each item's would-be outcome and commits nothing.
+#[tauri::command]
+pub async fn execute_batch_entity_operations(
+    ops: Vec<EntityOperation>,
+    all_or_nothing: bool,
+    app_state: State<'_, AppStateType>,
+    shutdown: State<'_, Arc<ShutdownCoordinator>>,
+) -> Result<CommandResult<Vec<BatchEntityResult>>, String> {
+    // Refused once shutdown starts; held until return so teardown waits for this write
+    let _operation = shutdown.begin_operation().map_err(|e| e.to_string())?;
+    let first = ops.first().ok_or("Batch contains no operations")?;
+    let user_id = first.user_id.clone();
+    let session_id = Uuid::parse_str(&first.session_id).map_err(|e| e.to_string())?;
+    let dry_run = first.dry_run;
+    if ops.iter().any(|op| op.user_id != user_id || op.session_id != first.session_id) {
+        return Err("All operations in a batch must share one user and session".to_string());
+    }
+    if ops.iter().any(|op| op.dry_run != dry_run) {
+        return Err("A batch must be all dry runs or none".to_string());
+    }
+
+    let context = ObservabilityContext::new(
+        "entity",
+        observed_operation("batch", dry_run),
+        ClassificationLevel::Confidential, // Per-item classification is taken from each entity
+        &user_id,
+        session_id,
+    );
+
+    let budget = PerformanceBudget::new(30000, "batch_entity_operation", true);
+
+    let result = with_observability!(
+        app_state,
+        context,
+        budget,
+        async {
+            let state = app_state.read().await;
+
+            crate::require_feature!(state.license_manager, "batch_operations", |e: LicenseError| e.to_string());
+
+            let db_context = user_database_context(&state, &user_id, session_id).await?;
+
+            if dry_run {
+                let mut results = Vec::with_capacity(ops.len());
+                for (index, op) in ops.iter().enumerate() {
+                    let outcome = match to_batch_operation(op) {
+                        Ok(operation) => state.db_manager.dry_run(&operation, &db_context).await
+                            .map_err(|e| e.to_string())?,
+                        Err(error) => crate::database::DryRunOutcome::denied(error),
+                    };
+                    results.push(BatchEntityResult {
+                        index,
+                        success: outcome.allowed,
+                        entity_id: outcome.entity.map(|entity| entity.id),
+                        error: outcome.reason,
+                    });
+                }
+                return Ok(results);
+            }
+
+            // Malformed items are reported without reaching the database
+            let mut results: Vec<Option<BatchEntityResult>> = Vec::with_capacity(ops.len());
+            let mut batch = Vec::new();
+            let mut batch_indices = Vec::new();
+            for (index, op) in ops.iter().enumerate() {
+                match to_batch_operation(op) {
+                    Ok(operation) => {
+                        results.push(None);
+                        batch.push(operation);
+                        batch_indices.push(index);
+                    }
+                    Err(error) => {
+                        if all_or_nothing {
+                            return Err(format!("Operation {} rejected: {}", index, error));
+                        }
+                        results.push(Some(BatchEntityResult { index, success: false, entity_id: None, error: Some(error) }));
+                    }
+                }
+            }
+
+            let items = state.db_manager.execute_batch(batch, &db_context, all_or_nothing).await
+                .map_err(|e| e.to_string())?;
+
+            for item in items {
+                // Map the store's index back to the request's; refuse any the batch never had
+                let Some((index, op, slot)) = batch_indices.get(item.index)
+                    .and_then(|&index| Some((index, ops.get(index)?, results.get_mut(index)?)))
+                else {
+                    return Err(format!("Batch returned a result for unknown item {}", item.index));
+                };
+                log_batch_item(&state, op, &item, &db_context).await?;
+
+                *slot = Some(BatchEntityResult {
+                    index,
+                    success: item.success,
+                    entity_id: item.entity.as_ref().map(|entity| entity.id),
+                    error: item.error,
+                });
+            }
+
+            Ok(results.into_iter().flatten().collect())
+        }
+    );
+
+    Ok(result)
+}
+
+/// Database context for a user with a valid clearance context
+async fn user_database_context(state: &AppState, user_id: &str, session_id: Uuid) -> Result<DatabaseContext, String> {
+    let user_context = state.get_valid_user_context(user_id).await
+        .map_err(|e| e.to_string())?;
+    Ok(DatabaseContext {
+        user_id: user_id.to_string(),
+        session_id,
+        security_label: user_context.to_security_label(),
+        tenant_id: user_context.tenant_id.clone(),
+        permissions: user_context.permissions.clone(),
+        include_deleted: false,
+    })
+}
+
+/// Map a frontend entity operation onto a database batch operation
+fn to_batch_operation(op: &EntityOperation) -> Result<BatchEntityOperation, String> {
+    let entity_id = || Uuid::parse_str(&op.entity_id)
+        .map_err(|e| format!("Invalid entity ID '{}': {}", op.entity_id, e));
+
+    match op.operation.as_str() {
+        "create" => Ok(BatchEntityOperation::Create {
+            entity_type: op.entity_type.clone(),
+            data: op.data.clone(),
+            idempotency_key: op.idempotency_key.clone(),
+        }),
+        "update" => Ok(BatchEntityOperation::Update { entity_id: entity_id()?, updates: op.data.clone() }),
+        "delete" => Ok(BatchEntityOperation::Delete { entity_id: entity_id()? }),
+        other => Err(format!("Unknown entity operation: {}", other)),
+    }
+}
+
+/// Emit the forensic envelope for one committed batch item
+async fn log_batch_item(
+    state: &AppState,
+    op: &EntityOperation,
+    item: &BatchItemResult,
+    db_context: &DatabaseContext,
+) -> Result<(), String> {
+    let entity = match &item.entity {
+        Some(entity) => entity,
+        None => return Ok(()), // Nothing was written
+    };
+
+    let after = match op.operation.as_str() {
+        "delete" => None,
+        _ => serde_json::to_value(entity).ok(),
+    };
+    let before = item.previous.as_ref().and_then(|previous| serde_json::to_value(previous).ok());
+
+    state.forensic_logger.log_data_event(
+        &format!("entity.{}", op.operation),
+        &format!("{}/{}", entity.entity_type, entity.id),
+        &db_context.user_id,
+        db_context.session_id,
+        entity.classification.clone(),
+        before,
+        after,
+    ).await.map_err(|e| e.to_string())
+}
+
 /// Async operation execution (replaces AsyncOrchestrator.run)
 #[tauri::command]
 pub async fn execute_async_operation(
     request: AsyncOperation,
     app_state: State<'_, AppStateType>,
+    shutdown: State<'_, Arc<ShutdownCoordinator>>,
 ) -> Result<CommandResult<serde_json::Value>, String> {
+    // Refused once shutdown starts; held until return so teardown waits for this write
+    let _operation = shutdown.begin_operation().map_err(|e| e.to_string())?;
     let context = ObservabilityContext::new(
         "async",
         &request.operation_name,
@@ -302,7 +488,10 @@ pub async fn execute_async_operation(
 pub async fn execute_storage_operation(
     request: StorageOperation,
     app_state: State<'_, AppStateType>,
+    shutdown: State<'_, Arc<ShutdownCoordinator>>,
 ) -> Result<CommandResult<serde_json::Value>, String> {
+    // Refused once shutdown starts; held until return so teardown waits for this write
+    let _operation = shutdown.begin_operation().map_err(|e| e.to_string())?;
     let classification = match request.classification.as_str() {
         "public" => ClassificationLevel::Unclassified,
         "internal" => ClassificationLevel::Internal,
@@ -313,7 +502,7 @@ pub async fn execute_storage_operation(
 
     let context = ObservabilityContext::new(
         "storage",
-        &request.operation,
+        observed_operation(&request.operation, request.dry_run),
         classification,
         &request.user_id,
         Uuid::parse_str(&request.session_id).map_err(|e| e.to_string())?,
@@ -328,16 +517,31 @@ pub async fn execute_storage_operation(
         async {
             let state = app_state.read().await;
             
-            // MAC enforcement check
-            let user_context = state.get_user_context(&request.user_id).await
-                .ok_or("User context not found")?;
-            let user_label = user_context.to_security_label();
-            let data_label = SecurityLabel::new(classification, vec![]);
+            // MAC enforcement check, padded to the engine's floor so a missing context
+            // or a denial can't be timed apart from a grant
+            let mac_engine = &state.security_manager.mac_engine;
+            let verdict = constant_time::security_operation(async {
+                let user_context = state.get_valid_user_context(&request.user_id).await
+                    .map_err(|e| e.to_string())?;
+                let user_label = user_context.to_security_label();
+                let data_label = SecurityLabel::new(classification, vec![]);
+
+                Ok::<MACVerdict, String>(match request.operation.as_str() {
+                    "get" => mac_engine.verdict(MACOperation::Read, &user_label, &data_label).await,
+                    "put" | "delete" => mac_engine.verdict(MACOperation::Write, &user_label, &data_label).await,
+                    _ => MACVerdict { allowed: false, reason: None },
+                })
+            }, mac_engine.decision_floor_ms()).await;
+
+            if request.dry_run {
+                return Ok(storage_dry_run(&request, verdict));
+            }
+            let access = verdict.map(|verdict| verdict.allowed);
             
             match request.operation.as_str() {
                 "get" => {
                     // Check read access
-                    if !state.security_manager.mac_engine.can_read(&user_label, &data_label).await {
+                    if !access? {
                         return Err("Access denied: insufficient clearance for read operation".to_string());
                     }
                     
@@ -350,7 +554,7 @@ pub async fn execute_storage_operation(
                 },
                 "put" => {
                     // Check write access
-                    if !state.security_manager.mac_engine.can_write(&user_label, &data_label).await {
+                    if !access? {
                         return Err("Access denied: insufficient clearance for write operation".to_string());
                     }
                     
@@ -363,7 +567,7 @@ pub async fn execute_storage_operation(
                 },
                 "delete" => {
                     // Check write access (deletion requires write permission)
-                    if !state.security_manager.mac_engine.can_write(&user_label, &data_label).await {
+                    if !access? {
                         return Err("Access denied: insufficient clearance for delete operation".to_string());
                     }
                     
@@ -380,6 +584,32 @@ pub async fn execute_storage_operation(
     Ok(result)
 }
 
+/// Would-be outcome of a storage operation, for dry-run requests
+fn storage_dry_run(request: &StorageOperation, verdict: Result<MACVerdict, String>) -> serde_json::Value {
+    let (allowed, reason) = match verdict {
+        Ok(verdict) => (verdict.allowed, verdict.reason.map(|reason| format!("{:?}", reason))),
+        Err(error) => (false, Some(error)),
+    };
+    let result = match request.operation.as_str() {
+        "get" => serde_json::json!({ "key": request.key, "access_granted": true }),
+        "put" => serde_json::json!({ "key": request.key, "stored": true, "classification": request.classification }),
+        "delete" => serde_json::json!({ "key": request.key, "deleted": true }),
+        other => return serde_json::json!({
+            "dry_run": true,
+            "allowed": false,
+            "reason": format!("Unknown storage operation: {}", other),
+            "result": null,
+        }),
+    };
+
+    serde_json::json!({
+        "dry_run": true,
+        "allowed": allowed,
+        "reason": reason,
+        "result": if allowed { result } else { serde_json::Value::Null },
+    })
+}
+
 /// UI action execution (replaces declarative HTML actions)
 #[tauri::command]
 pub async fn execute_ui_action(
@@ -444,12 +674,40 @@ mod tests {
             data: serde_json::json!({"name": "Test User"}),
             user_id: "admin".to_string(),
             session_id: Uuid::new_v4().to_string(),
+            dry_run: false,
+            idempotency_key: None,
         };
         
         assert_eq!(operation.entity_type, "user");
         assert_eq!(operation.operation, "update");
     }
     
+    #[test]
+    fn test_batch_operation_mapping() {
+        let entity_id = Uuid::new_v4();
+        let operation = |op: &str, id: String| EntityOperation {
+            entity_type: "note".to_string(),
+            entity_id: id,
+            operation: op.to_string(),
+            data: serde_json::json!({"title": "Test"}),
+            user_id: "admin".to_string(),
+            session_id: Uuid::new_v4().to_string(),
+            dry_run: false,
+            idempotency_key: None,
+        };
+
+        assert!(matches!(
+            to_batch_operation(&operation("create", String::new())),
+            Ok(BatchEntityOperation::Create { .. })
+        ));
+        assert!(matches!(
+            to_batch_operation(&operation("delete", entity_id.to_string())),
+            Ok(BatchEntityOperation::Delete { entity_id: id }) if id == entity_id
+        ));
+        assert!(to_batch_operation(&operation("update", "not-a-uuid".to_string())).is_err());
+        assert!(to_batch_operation(&operation("archive", entity_id.to_string())).is_err());
+    }
+
     #[test]
     fn test_storage_operation_creation() {
         let operation = StorageOperation {
@@ -459,9 +717,35 @@ mod tests {
             classification: "confidential".to_string(),
             user_id: "user-123".to_string(),
             session_id: Uuid::new_v4().to_string(),
+            dry_run: false,
         };
         
         assert_eq!(operation.operation, "put");
         assert_eq!(operation.classification, "confidential");
     }
+
+    #[test]
+    fn test_storage_dry_run_reports_denial_without_result() {
+        let request: StorageOperation = serde_json::from_value(serde_json::json!({
+            "operation": "put",
+            "key": "test-key",
+            "value": {"data": "test"},
+            "classification": "internal",
+            "user_id": "user-123",
+            "session_id": Uuid::new_v4().to_string(),
+            "dry_run": true,
+        })).unwrap();
+        assert_eq!(observed_operation(&request.operation, request.dry_run), "dry_run");
+
+        let denied = MACVerdict { allowed: false, reason: Some(crate::security::MACDenialReason::WriteDown) };
+        let outcome = storage_dry_run(&request, Ok(denied));
+        assert_eq!(outcome["allowed"], false);
+        assert_eq!(outcome["reason"], "WriteDown");
+        assert!(outcome["result"].is_null());
+
+        let outcome = storage_dry_run(&request, Ok(MACVerdict { allowed: true, reason: None }));
+        assert_eq!(outcome["result"]["stored"], true);
+        let outcome = storage_dry_run(&request, Err("User context not found".to_string()));
+        assert_eq!(outcome["reason"], "User context not found");
+    }
 }
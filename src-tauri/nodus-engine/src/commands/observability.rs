@@ -3,16 +3,26 @@
 // Provides secure frontend access to observability data for enterprise dashboards
 
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+use std::sync::atomic::{AtomicBool, Ordering};
+use dashmap::DashMap;
+use tauri::Manager;
 use uuid::Uuid;
 
+use crate::shutdown::ShutdownCoordinator;
 use crate::observability::{
     MetricsRegistry, ForensicLogger, AutomaticInstrumentation,
     InstrumentationStats, ForensicStats, AuditSearchCriteria, AuditSearchResults,
-    MetricsQuery, MetricsSnapshot, ObservabilityContext,
+    MetricsQuery, MetricsSnapshot, ObservabilityContext, HealthCheck, HealthReport, LivenessReport,
+    IntegrityAttestation, ComplianceScope, ComplianceSummary, ComplianceWindow,
 };
-use crate::security::{ClassificationLevel, SecurityContext};
+use crate::observability::integrity::MIN_AUDITOR_CLEARANCE;
+use crate::observability::observation::ComplianceFramework;
+use crate::security::{has_permission, ClassificationLevel, Permission, SecurityContext};
 use crate::state::AppState;
+use crate::database::DbHealth;
 use crate::error::AppError;
 
 /// Tauri command for getting real-time metrics snapshot
@@ -25,9 +35,8 @@ pub async fn get_metrics_snapshot(
         .map_err(|_| "Invalid session ID format")?;
     
     // Get security context
-    let security_context = app_state.security_manager
-        .get_security_context(session_uuid).await
-        .ok_or("Invalid or expired session")?;
+    let security_context = app_state.session_context(session_uuid).await
+        .map_err(|e| e.to_string())?;
 
     // Create observability context
     let obs_context = ObservabilityContext::new(
@@ -85,9 +94,8 @@ pub async fn query_metrics(
         .map_err(|_| "Invalid session ID format")?;
     
     // Get security context
-    let security_context = app_state.security_manager
-        .get_security_context(session_uuid).await
-        .ok_or("Invalid or expired session")?;
+    let security_context = app_state.session_context(session_uuid).await
+        .map_err(|e| e.to_string())?;
 
     // Parse classification filters
     let classification_filter = if let Some(classifications) = query.classification_filter {
@@ -134,9 +142,8 @@ pub async fn get_instrumentation_stats(
         .map_err(|_| "Invalid session ID format")?;
     
     // Verify session exists
-    app_state.security_manager
-        .get_security_context(session_uuid).await
-        .ok_or("Invalid or expired session")?;
+    app_state.session_context(session_uuid).await
+        .map_err(|e| e.to_string())?;
 
     // Get instrumentation statistics
     let stats = app_state.automatic_instrumentation.get_instrumentation_stats().await;
@@ -167,12 +174,11 @@ pub async fn search_audit_trail(
         .map_err(|_| "Invalid session ID format")?;
     
     // Get security context
-    let security_context = app_state.security_manager
-        .get_security_context(session_uuid).await
-        .ok_or("Invalid or expired session")?;
+    let security_context = app_state.session_context(session_uuid).await
+        .map_err(|e| e.to_string())?;
 
     // Check if user has audit access permissions
-    if !security_context.permissions.contains(&"audit_access".to_string()) {
+    if !has_permission(&security_context, Permission::AuditAccess) {
         return Err("Insufficient permissions for audit trail access".to_string());
     }
 
@@ -233,17 +239,19 @@ pub async fn export_audit_trail(
     session_id: String,
     export_request: AuditExportRequest,
     app_state: tauri::State<'_, AppState>,
+    shutdown: tauri::State<'_, Arc<ShutdownCoordinator>>,
 ) -> Result<AuditExportResponse, String> {
+    // Refused once shutdown starts; held until return so teardown waits for this write
+    let _operation = shutdown.begin_operation().map_err(|e| e.to_string())?;
     let session_uuid = Uuid::parse_str(&session_id)
         .map_err(|_| "Invalid session ID format")?;
     
     // Get security context
-    let security_context = app_state.security_manager
-        .get_security_context(session_uuid).await
-        .ok_or("Invalid or expired session")?;
+    let security_context = app_state.session_context(session_uuid).await
+        .map_err(|e| e.to_string())?;
 
     // Check if user has audit export permissions
-    if !security_context.permissions.contains(&"audit_export".to_string()) {
+    if !has_permission(&security_context, Permission::AuditExport) {
         return Err("Insufficient permissions for audit trail export".to_string());
     }
 
@@ -294,9 +302,8 @@ pub async fn get_forensic_stats(
         .map_err(|_| "Invalid session ID format")?;
     
     // Verify session exists
-    app_state.security_manager
-        .get_security_context(session_uuid).await
-        .ok_or("Invalid or expired session")?;
+    app_state.session_context(session_uuid).await
+        .map_err(|e| e.to_string())?;
 
     // Get forensic logging statistics
     let stats = app_state.forensic_logger.get_logging_stats().await;
@@ -312,6 +319,86 @@ pub async fn get_forensic_stats(
     })
 }
 
+/// Tauri command for verifying the forensic log hash chain over a time range
+///
+/// Restricted to cleared auditors. Returns the first break (if any) in a signed attestation.
+#[tauri::command]
+pub async fn verify_forensic_integrity(
+    session_id: String,
+    from: chrono::DateTime<chrono::Utc>,
+    to: chrono::DateTime<chrono::Utc>,
+    app_state: tauri::State<'_, AppState>,
+) -> Result<IntegrityAttestation, String> {
+    let session_uuid = Uuid::parse_str(&session_id)
+        .map_err(|_| "Invalid session ID format")?;
+    
+    // Get security context
+    let security_context = app_state.session_context(session_uuid).await
+        .map_err(|e| e.to_string())?;
+
+    if !has_permission(&security_context, Permission::ForensicAudit) {
+        return Err("Insufficient permissions for forensic integrity verification".to_string());
+    }
+    if !security_context.security_label.level.dominates(&MIN_AUDITOR_CLEARANCE) {
+        return Err(format!("Forensic integrity verification requires {} clearance", MIN_AUDITOR_CLEARANCE));
+    }
+    if from > to {
+        return Err("Verification range starts after it ends".to_string());
+    }
+
+    let verification = app_state.forensic_logger
+        .verify_integrity(from, to, &security_context.user_id).await
+        .map_err(|e| e.to_string())?;
+    let machine = app_state.license_manager.device_identity()
+        .map_err(|e| e.to_string())?;
+
+    Ok(IntegrityAttestation::sign(from, to, verification, &security_context.user_id, machine))
+}
+
+/// Default span of a compliance summary
+const DEFAULT_COMPLIANCE_WINDOW_DAYS: i64 = 30;
+
+/// Tauri command summarizing a framework's compliance posture over a time window
+///
+/// Requires the `compliance_reporting` license feature and audit access. Defaults to the last
+/// 30 days, and may span at most a year. Tenant-scoped callers only see their tenant's operations, and operations classified
+/// above the caller's clearance are left out.
+#[tauri::command]
+pub async fn get_compliance_summary(
+    session_id: String,
+    framework: ComplianceFramework,
+    window: Option<ComplianceWindow>,
+    app_state: tauri::State<'_, AppState>,
+) -> Result<ComplianceSummary, String> {
+    let session_uuid = Uuid::parse_str(&session_id)
+        .map_err(|_| "Invalid session ID format")?;
+    
+    let security_context = app_state.session_context(session_uuid).await
+        .map_err(|e| e.to_string())?;
+
+    crate::require_feature!(app_state.license_manager, "compliance_reporting", |_| {
+        "Compliance reporting is not included in the current license".to_string()
+    });
+    if !has_permission(&security_context, Permission::AuditAccess) {
+        return Err("Insufficient permissions for compliance reporting".to_string());
+    }
+
+    let window = window.unwrap_or_else(|| {
+        ComplianceWindow::ending_at(chrono::Utc::now(), chrono::Duration::days(DEFAULT_COMPLIANCE_WINDOW_DAYS))
+    });
+    window.validate().map_err(|e| e.to_string())?;
+
+    let engine = app_state.export_engine.as_ref()
+        .and_then(|engine| engine.compliance_engine())
+        .ok_or("Compliance assessment is not enabled")?;
+    let scope = ComplianceScope {
+        tenant_id: security_context.tenant_id.clone(),
+        clearance: security_context.security_label.level.clone(),
+    };
+
+    engine.compliance_summary(&framework, window, &scope).await.map_err(|e| e.to_string())
+}
+
 /// Tauri command for getting operation performance metrics
 #[tauri::command]
 pub async fn get_operation_metrics(
@@ -323,9 +410,8 @@ pub async fn get_operation_metrics(
         .map_err(|_| "Invalid session ID format")?;
     
     // Verify session exists
-    app_state.security_manager
-        .get_security_context(session_uuid).await
-        .ok_or("Invalid or expired session")?;
+    app_state.session_context(session_uuid).await
+        .map_err(|e| e.to_string())?;
 
     // Get operation metrics from async orchestrator
     let operation_metrics = app_state.async_orchestrator.get_operation_metrics().await;
@@ -377,6 +463,209 @@ pub async fn get_operation_metrics(
     })
 }
 
+/// Tauri command for database connectivity and pool status
+#[tauri::command]
+pub async fn get_database_health(
+    session_id: String,
+    app_state: tauri::State<'_, AppState>,
+) -> Result<DbHealth, String> {
+    let session_uuid = Uuid::parse_str(&session_id)
+        .map_err(|_| "Invalid session ID format")?;
+    
+    // Verify session exists
+    app_state.session_context(session_uuid).await
+        .map_err(|e| e.to_string())?;
+
+    Ok(app_state.db_manager.health_check().await)
+}
+
+/// Tauri command for liveness: answers as long as the backend is running
+#[tauri::command]
+pub async fn get_liveness(
+    health: tauri::State<'_, Arc<HealthCheck>>,
+) -> Result<LivenessReport, String> {
+    Ok(health.liveness())
+}
+
+/// Tauri command for readiness: combined status of every registered subsystem
+///
+/// Component results are cached for about a second, so this is cheap to poll.
+#[tauri::command]
+pub async fn get_platform_health(
+    health: tauri::State<'_, Arc<HealthCheck>>,
+) -> Result<HealthReport, String> {
+    Ok(health.readiness().await)
+}
+
+/// Default seconds between metrics stream pushes
+const DEFAULT_STREAM_INTERVAL_SECS: u64 = 5;
+
+/// Bounds on the caller-supplied stream interval
+const MIN_STREAM_INTERVAL_SECS: u64 = 1;
+const MAX_STREAM_INTERVAL_SECS: u64 = 300;
+
+/// Control block shared between a stream's task and its ack/stop commands
+#[derive(Debug)]
+struct MetricsStreamControl {
+    // Only the session that started the stream may ack or stop it
+    session_id: Uuid,
+    // Set on emit, cleared by the frontend's ack; at most one payload is ever in flight
+    awaiting_ack: AtomicBool,
+    stopped: AtomicBool,
+}
+
+impl MetricsStreamControl {
+    fn new(session_id: Uuid) -> Self {
+        Self {
+            session_id,
+            awaiting_ack: AtomicBool::new(false),
+            stopped: AtomicBool::new(false),
+        }
+    }
+}
+
+fn metrics_streams() -> &'static DashMap<Uuid, Arc<MetricsStreamControl>> {
+    static STREAMS: OnceLock<DashMap<Uuid, Arc<MetricsStreamControl>>> = OnceLock::new();
+    STREAMS.get_or_init(DashMap::new)
+}
+
+/// Tauri command that pushes metrics deltas over an event channel instead of polling
+///
+/// Emits a `MetricsSnapshot` delta on `metrics_stream/<stream_id>` every interval.
+/// Tenant-scoped callers only see series labeled with their tenant. A new payload is
+/// sent only after the previous one is acked via `ack_metrics_stream`; ticks in between
+/// are coalesced into the next delta, so a slow frontend never builds a queue. The
+/// stream ends when the session is invalidated or `stop_metrics_stream` is called.
+#[tauri::command]
+pub async fn start_metrics_stream(
+    session_id: String,
+    interval_secs: Option<u64>,
+    app: tauri::AppHandle,
+    app_state: tauri::State<'_, AppState>,
+) -> Result<MetricsStreamInfo, String> {
+    let session_uuid = Uuid::parse_str(&session_id)
+        .map_err(|_| "Invalid session ID format")?;
+    
+    let security_context = app_state.session_context(session_uuid).await
+        .map_err(|e| e.to_string())?;
+
+    let interval_secs = interval_secs
+        .unwrap_or(DEFAULT_STREAM_INTERVAL_SECS)
+        .clamp(MIN_STREAM_INTERVAL_SECS, MAX_STREAM_INTERVAL_SECS);
+    let stream_id = Uuid::new_v4();
+    let event = format!("metrics_stream/{}", stream_id);
+    let control = Arc::new(MetricsStreamControl::new(session_uuid));
+    metrics_streams().insert(stream_id, control.clone());
+
+    let tenant_id = security_context.tenant_id.clone();
+    let event_name = event.clone();
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(std::time::Duration::from_secs(interval_secs));
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        let mut last_emitted: Option<MetricsSnapshot> = None;
+
+        loop {
+            ticker.tick().await;
+            if control.stopped.load(Ordering::Relaxed) {
+                break;
+            }
+
+            let state = app.state::<AppState>();
+            if !state.session_is_live(session_uuid).await {
+                let _ = app.emit_all(&event_name, serde_json::json!({ "closed": "session_invalidated" }));
+                break;
+            }
+
+            // Previous payload not yet consumed; the next delta will cover this tick
+            if control.awaiting_ack.load(Ordering::Acquire) {
+                continue;
+            }
+
+            let snapshot = state.metrics_registry.snapshot().await;
+            let snapshot = match &tenant_id {
+                Some(tenant_id) => snapshot.scoped_to_tenant(tenant_id),
+                None => snapshot,
+            };
+            let payload = match &last_emitted {
+                Some(previous) => snapshot.delta_since(previous),
+                None => snapshot.clone(),
+            };
+
+            control.awaiting_ack.store(true, Ordering::Release);
+            if let Err(e) = app.emit_all(&event_name, &payload) {
+                tracing::warn!("Metrics stream {} stopped: {}", stream_id, e);
+                break;
+            }
+            last_emitted = Some(snapshot);
+        }
+
+        metrics_streams().remove(&stream_id);
+    });
+
+    Ok(MetricsStreamInfo {
+        stream_id: stream_id.to_string(),
+        event,
+        interval_secs,
+    })
+}
+
+/// Tauri command acknowledging the last metrics stream payload, allowing the next one
+#[tauri::command]
+pub async fn ack_metrics_stream(
+    session_id: String,
+    stream_id: String,
+    app_state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    let (session_uuid, stream_uuid) = stream_request(&app_state, &session_id, &stream_id).await?;
+    ack_stream(stream_uuid, session_uuid)
+}
+
+/// Tauri command ending a metrics stream
+#[tauri::command]
+pub async fn stop_metrics_stream(
+    session_id: String,
+    stream_id: String,
+    app_state: tauri::State<'_, AppState>,
+) -> Result<(), String> {
+    let (session_uuid, stream_uuid) = stream_request(&app_state, &session_id, &stream_id).await?;
+    stop_stream(stream_uuid, session_uuid)
+}
+
+/// Parse a stream ack/stop request from a live session; checking the session doesn't count
+/// as activity, so a streaming dashboard alone doesn't keep it from idling out
+async fn stream_request(app_state: &AppState, session_id: &str, stream_id: &str) -> Result<(Uuid, Uuid), String> {
+    let session_uuid = Uuid::parse_str(session_id)
+        .map_err(|_| "Invalid session ID format")?;
+    let stream_uuid = Uuid::parse_str(stream_id)
+        .map_err(|_| "Invalid stream ID format")?;
+    if !app_state.session_is_live(session_uuid).await {
+        return Err("Invalid or expired session".to_string());
+    }
+    Ok((session_uuid, stream_uuid))
+}
+
+/// Ack a stream's last payload if `session_id` owns it; another session's stream is
+/// reported like a closed one
+fn ack_stream(stream_id: Uuid, session_id: Uuid) -> Result<(), String> {
+    let control = metrics_streams().get(&stream_id)
+        .filter(|control| control.session_id == session_id)
+        .ok_or("Unknown or closed metrics stream")?;
+    control.awaiting_ack.store(false, Ordering::Release);
+    Ok(())
+}
+
+/// Stop a stream if `session_id` owns it; stopping a closed stream is a no-op
+fn stop_stream(stream_id: Uuid, session_id: Uuid) -> Result<(), String> {
+    match metrics_streams().remove_if(&stream_id, |_, control| control.session_id == session_id) {
+        Some((_, control)) => control.stopped.store(true, Ordering::Relaxed),
+        None if metrics_streams().contains_key(&stream_id) => {
+            return Err("Metrics stream belongs to another session".to_string());
+        }
+        None => {}
+    }
+    Ok(())
+}
+
 /// Tauri command for getting system health status
 #[tauri::command]
 pub async fn get_system_health(
@@ -387,15 +676,15 @@ pub async fn get_system_health(
         .map_err(|_| "Invalid session ID format")?;
     
     // Verify session exists
-    app_state.security_manager
-        .get_security_context(session_uuid).await
-        .ok_or("Invalid or expired session")?;
+    app_state.session_context(session_uuid).await
+        .map_err(|e| e.to_string())?;
 
     // Get various health metrics
     let security_metrics = app_state.security_manager.get_security_metrics().await;
     let instrumentation_stats = app_state.automatic_instrumentation.get_instrumentation_stats().await;
     let forensic_stats = app_state.forensic_logger.get_logging_stats().await;
     let state_info = app_state.hybrid_state_manager.get_state_info().await;
+    let db_health = app_state.db_manager.health_check().await;
 
     // Calculate overall health score
     let mut health_score = 100.0;
@@ -417,6 +706,14 @@ pub async fn get_system_health(
         health_issues.push("Audit trail integrity failures detected".to_string());
     }
 
+    if !db_health.healthy {
+        health_score -= 40.0;
+        health_issues.push(format!(
+            "Database unreachable: {}",
+            db_health.error.as_deref().unwrap_or("unknown error")
+        ));
+    }
+
     if state_info.error_rate > 0.05 {
         health_score -= 15.0;
         health_issues.push("Elevated error rate detected".to_string());
@@ -448,8 +745,14 @@ pub async fn get_system_health(
                 last_check: chrono::Utc::now(),
             },
             database: ComponentHealth {
-                status: if state_info.error_rate < 0.01 { "healthy" } else { "warning" }.to_string(),
-                score: (1.0 - state_info.error_rate) * 100.0,
+                status: if !db_health.healthy {
+                    "critical"
+                } else if state_info.error_rate < 0.01 {
+                    "healthy"
+                } else {
+                    "warning"
+                }.to_string(),
+                score: if db_health.healthy { (1.0 - state_info.error_rate) * 100.0 } else { 0.0 },
                 last_check: chrono::Utc::now(),
             },
             networking: ComponentHealth {
@@ -525,7 +828,7 @@ fn parse_forensic_event_type(event_type: &str) -> Result<crate::observability::F
 
 // Request/Response types for Tauri commands
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct MetricsSnapshotResult {
     pub timestamp: chrono::DateTime<chrono::Utc>,
     pub counters: HashMap<String, u64>,
@@ -535,7 +838,7 @@ pub struct MetricsSnapshotResult {
     pub collection_stats: CollectionStatsResult,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct HistogramResult {
     pub count: u64,
     pub sum: f64,
@@ -546,7 +849,7 @@ pub struct HistogramResult {
     pub buckets: Vec<(f64, u64)>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct TimerResult {
     pub count: u64,
     pub total_duration_ms: f64,
@@ -555,7 +858,7 @@ pub struct TimerResult {
     pub max_duration_ms: f64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct CollectionStatsResult {
     pub total_decisions: u64,
     pub cache_hits: u64,
@@ -563,7 +866,7 @@ pub struct CollectionStatsResult {
     pub export_success_rate: f64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct MetricsQueryRequest {
     pub metric_patterns: Vec<String>,
     pub start_time: Option<chrono::DateTime<chrono::Utc>>,
@@ -573,14 +876,14 @@ pub struct MetricsQueryRequest {
     pub limit: Option<u32>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct MetricsQueryResult {
     pub metrics: Vec<MetricDataPoint>,
     pub total_count: u64,
     pub query_time_ms: u64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct MetricDataPoint {
     pub name: String,
     pub value: f64,
@@ -589,7 +892,7 @@ pub struct MetricDataPoint {
     pub operation_id: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct InstrumentationStatsResult {
     pub total_decisions: u64,
     pub cache_hits: u64,
@@ -598,7 +901,7 @@ pub struct InstrumentationStatsResult {
     pub system_load: SystemLoadResult,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct SystemLoadResult {
     pub cpu_usage_percent: f64,
     pub memory_usage_percent: f64,
@@ -607,7 +910,7 @@ pub struct SystemLoadResult {
     pub concurrent_operations: u64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct AuditSearchRequest {
     pub start_time: Option<chrono::DateTime<chrono::Utc>>,
     pub end_time: Option<chrono::DateTime<chrono::Utc>>,
@@ -619,7 +922,7 @@ pub struct AuditSearchRequest {
     pub offset: Option<u32>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct AuditSearchResponse {
     pub envelopes: Vec<AuditEnvelopeResult>,
     pub total_count: u64,
@@ -627,7 +930,7 @@ pub struct AuditSearchResponse {
     pub integrity_verified: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct AuditEnvelopeResult {
     pub operation_id: String,
     pub event_type: String,
@@ -643,17 +946,17 @@ pub struct AuditEnvelopeResult {
     pub audit_trail_hash: String,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct AuditExportRequest {
     pub start_time: Option<chrono::DateTime<chrono::Utc>>,
     pub end_time: Option<chrono::DateTime<chrono::Utc>>,
     pub user_id: Option<String>,
     pub classification_levels: Option<Vec<String>>,
     pub components: Option<Vec<String>>,
-    pub format: String, // "json", "csv", "xml"
+    pub format: String, // "json", "csv", "xml", "cef"
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct AuditExportResponse {
     pub export_id: String,
     pub format: String,
@@ -663,7 +966,7 @@ pub struct AuditExportResponse {
     pub expires_at: chrono::DateTime<chrono::Utc>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct ForensicStatsResult {
     pub total_events_logged: u64,
     pub pending_events: u64,
@@ -674,14 +977,14 @@ pub struct ForensicStatsResult {
     pub failed_verifications: u64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct OperationMetricsResult {
     pub operations: Vec<OperationMetricData>,
     pub time_range_hours: u32,
     pub total_operations: u32,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct OperationMetricData {
     pub operation_name: String,
     pub operation_type: String, // "action" or "async"
@@ -696,7 +999,14 @@ pub struct OperationMetricData {
     pub retry_attempts: u64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct MetricsStreamInfo {
+    pub stream_id: String,
+    pub event: String,
+    pub interval_secs: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct SystemHealthResult {
     pub overall_health_score: f64,
     pub health_status: String, // "healthy", "warning", "degraded", "critical"
@@ -706,7 +1016,7 @@ pub struct SystemHealthResult {
     pub last_updated: chrono::DateTime<chrono::Utc>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct ComponentHealthResult {
     pub security: ComponentHealth,
     pub observability: ComponentHealth,
@@ -714,14 +1024,16 @@ pub struct ComponentHealthResult {
     pub networking: ComponentHealth,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+// Distinct from the platform health check's `ComponentHealth` in the API schema
+#[schemars(rename = "ComponentHealthSummary")]
 pub struct ComponentHealth {
     pub status: String,
     pub score: f64,
     pub last_check: chrono::DateTime<chrono::Utc>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct SystemMetricsResult {
     pub cpu_usage_percent: f64,
     pub memory_usage_percent: f64,
@@ -735,6 +1047,31 @@ pub struct SystemMetricsResult {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_stream_ack_and_stop_need_the_owning_session() {
+        let owner = Uuid::new_v4();
+        let other = Uuid::new_v4();
+        let stream_id = Uuid::new_v4();
+        let control = Arc::new(MetricsStreamControl::new(owner));
+        control.awaiting_ack.store(true, Ordering::Release);
+        metrics_streams().insert(stream_id, control.clone());
+
+        assert!(ack_stream(stream_id, other).is_err());
+        assert!(control.awaiting_ack.load(Ordering::Acquire));
+        assert!(stop_stream(stream_id, other).is_err());
+        assert!(!control.stopped.load(Ordering::Relaxed));
+
+        ack_stream(stream_id, owner).unwrap();
+        assert!(!control.awaiting_ack.load(Ordering::Acquire));
+        stop_stream(stream_id, owner).unwrap();
+        assert!(control.stopped.load(Ordering::Relaxed));
+        assert!(!metrics_streams().contains_key(&stream_id));
+
+        // Already closed
+        assert!(ack_stream(stream_id, owner).is_err());
+        assert!(stop_stream(stream_id, owner).is_ok());
+    }
+
     #[test]
     fn test_parse_aggregation_type() {
         assert!(matches!(
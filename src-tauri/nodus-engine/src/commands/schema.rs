@@ -0,0 +1,323 @@
+// src-tauri/src/commands/schema.rs
+// API Schema - JSON Schema for every Tauri command's arguments and result, plus the core domain types
+// Schemas are derived from the Rust types themselves, so the published shapes cannot drift from the code
+
+use schemars::gen::{SchemaGenerator, SchemaSettings};
+use schemars::schema::{InstanceType, ObjectValidation, Schema, SchemaObject};
+use schemars::JsonSchema;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use super::*;
+use crate::database::{DbHealth, DeclassificationRequest, EntityChanged, QueryFilter};
+use crate::observability::{ComplianceSummary, ComplianceWindow, HealthReport, IntegrityAttestation, LivenessReport};
+use crate::observability::observation::ComplianceFramework;
+use crate::policy::policy_engine::{PolicyChange, SimulationReport};
+use crate::security::{ClassificationLevel, MACOperation, SecurityLabel, UserContext};
+
+/// Schema for one command's `invoke` arguments and its successful result
+///
+/// A failed command always rejects with an error string.
+#[derive(Debug, Clone, Serialize)]
+pub struct CommandSchema {
+    /// Object schema keyed by argument name as passed to `invoke` (camelCase)
+    pub arguments: Schema,
+    pub returns: Schema,
+}
+
+/// JSON Schema document describing the command API
+#[derive(Debug, Clone, Serialize)]
+pub struct ApiSchema {
+    #[serde(rename = "$schema")]
+    pub meta_schema: String,
+    pub title: String,
+    pub version: String,
+    pub commands: BTreeMap<String, CommandSchema>,
+    /// Core domain types by name, each a reference into `definitions`
+    pub types: BTreeMap<String, Schema>,
+    pub definitions: BTreeMap<String, Schema>,
+}
+
+/// Every Tauri command, its arguments in declaration order and its result type
+///
+/// Argument lists must match the command signatures minus injected state; the tests check
+/// this against the source and the registered invoke handler.
+macro_rules! api_commands {
+    ($($command:ident($($arg:ident: $ty:ty),* $(,)?) -> $returns:ty;)*) => {
+        /// Names of all commands described by the API schema
+        pub const COMMANDS: &[&str] = &[$(stringify!($command)),*];
+
+        fn command_schemas(generator: &mut SchemaGenerator) -> BTreeMap<String, CommandSchema> {
+            let mut commands = BTreeMap::new();
+            $(
+                #[allow(unused_mut)]
+                let mut arguments = ObjectValidation::default();
+                $(add_argument::<$ty>(generator, &mut arguments, stringify!($arg));)*
+                commands.insert(stringify!($command).to_string(), CommandSchema {
+                    arguments: object_schema(arguments),
+                    returns: generator.subschema_for::<$returns>(),
+                });
+            )*
+            commands
+        }
+    };
+}
+
+api_commands! {
+    // commands/mod.rs
+    execute_entity_operation(request: EntityOperation) -> CommandResult<serde_json::Value>;
+    execute_batch_entity_operations(ops: Vec<EntityOperation>, all_or_nothing: bool) -> CommandResult<Vec<BatchEntityResult>>;
+    execute_async_operation(request: AsyncOperation) -> CommandResult<serde_json::Value>;
+    execute_storage_operation(request: StorageOperation) -> CommandResult<serde_json::Value>;
+    execute_ui_action(request: UIAction) -> CommandResult<serde_json::Value>;
+    get_api_schema() -> serde_json::Value;
+
+    // commands/security.rs
+    authenticate_user(username: String, password: String, auth_method: String, source_ip: Option<String>, user_agent: Option<String>) -> AuthenticationResult;
+    check_security_access(session_id: String, resource: String, action: String, classification: String, context: std::collections::HashMap<String, String>) -> SecurityCheckResult;
+    encrypt_data(session_id: String, data: Vec<u8>, classification: String) -> EncryptionResult;
+    decrypt_data(session_id: String, encrypted_data: EncryptedDataInput) -> Vec<u8>;
+    assess_threat(session_id: String, activity_description: String, metadata: std::collections::HashMap<String, String>) -> ThreatAssessmentResponse;
+    get_security_metrics(session_id: String) -> SecurityMetricsResult;
+    update_security_context(session_id: String, activity: String, risk_modifier: f64) -> ();
+    terminate_session(session_id: String) -> ();
+    mac_decision(session_id: String, object: SecurityLabel, operation: MACOperation) -> MACDecisionResult;
+
+    // commands/data.rs
+    read_entity(session_id: String, entity_type: String, entity_id: String, classification: Option<String>) -> EntityResult;
+    write_entity(session_id: String, entity_type: String, entity_id: Option<String>, entity_data: serde_json::Value, classification: Option<String>, idempotency_key: Option<String>) -> EntityResult;
+    delete_entity(session_id: String, entity_type: String, entity_id: String, classification: Option<String>) -> DeleteResult;
+    query_entities(session_id: String, entity_type: String, query_params: QueryParams, classification: Option<String>) -> QueryResult;
+    batch_operation(session_id: String, operations: Vec<BatchOperationRequest>) -> BatchOperationResult;
+    get_state_info(session_id: String) -> StateInfo;
+    subscribe_entity_changes(session_id: String, entity_types: Vec<String>) -> EntityChangeStreamInfo;
+    unsubscribe_entity_changes(session_id: String, subscription_id: String) -> ();
+    propose_declassification(session_id: String, entity_ids: Vec<uuid::Uuid>, target_label: SecurityLabel, justification: String) -> DeclassificationRequest;
+    approve_declassification(session_id: String, request_id: uuid::Uuid) -> Vec<uuid::Uuid>;
+    list_declassifications(session_id: String) -> Vec<DeclassificationRequest>;
+
+    // commands/observability.rs
+    get_metrics_snapshot(session_id: String) -> MetricsSnapshotResult;
+    query_metrics(session_id: String, query: MetricsQueryRequest) -> MetricsQueryResult;
+    get_instrumentation_stats(session_id: String) -> InstrumentationStatsResult;
+    search_audit_trail(session_id: String, search_criteria: AuditSearchRequest) -> AuditSearchResponse;
+    export_audit_trail(session_id: String, export_request: AuditExportRequest) -> AuditExportResponse;
+    get_forensic_stats(session_id: String) -> ForensicStatsResult;
+    verify_forensic_integrity(session_id: String, from: chrono::DateTime<chrono::Utc>, to: chrono::DateTime<chrono::Utc>) -> IntegrityAttestation;
+    get_compliance_summary(session_id: String, framework: ComplianceFramework, window: Option<ComplianceWindow>) -> ComplianceSummary;
+    get_operation_metrics(session_id: String, time_range_hours: Option<u32>) -> OperationMetricsResult;
+    get_database_health(session_id: String) -> DbHealth;
+    get_liveness() -> LivenessReport;
+    get_platform_health() -> HealthReport;
+    start_metrics_stream(session_id: String, interval_secs: Option<u64>) -> MetricsStreamInfo;
+    ack_metrics_stream(session_id: String, stream_id: String) -> ();
+    stop_metrics_stream(session_id: String, stream_id: String) -> ();
+    get_system_health(session_id: String) -> SystemHealthResult;
+
+    // commands/license.rs
+    get_license_info(session_id: String) -> LicenseInfoResult;
+    check_feature_availability(session_id: String, feature_name: String) -> FeatureAvailabilityResult;
+    get_available_features(session_id: String) -> AvailableFeaturesResult;
+    validate_license(session_id: String) -> LicenseValidationResult;
+    get_license_usage(session_id: String) -> LicenseUsageResult;
+    check_license_compliance(session_id: String) -> LicenseComplianceResult;
+    update_license(session_id: String, license_data: String) -> LicenseUpdateResult;
+    get_tier_comparison(session_id: String) -> TierComparisonResult;
+
+    // main_integrated.rs
+    get_system_status() -> serde_json::Value;
+    get_enterprise_summary() -> serde_json::Value;
+    get_plugin_status() -> serde_json::Value;
+    get_compliance_report(framework: String) -> serde_json::Value;
+    get_tenant_summary() -> serde_json::Value;
+    get_api_gateway_metrics() -> serde_json::Value;
+    simulate_policy_change(session_id: String, change: PolicyChange) -> SimulationReport;
+}
+
+/// Core domain types published alongside the commands
+fn domain_types(generator: &mut SchemaGenerator) -> BTreeMap<String, Schema> {
+    let mut types = BTreeMap::new();
+    let mut add = |name: String, schema: Schema| {
+        types.insert(name, schema);
+    };
+    add(EntityOperation::schema_name(), generator.subschema_for::<EntityOperation>());
+    add(StorageOperation::schema_name(), generator.subschema_for::<StorageOperation>());
+    add(AsyncOperation::schema_name(), generator.subschema_for::<AsyncOperation>());
+    add(UIAction::schema_name(), generator.subschema_for::<UIAction>());
+    add(
+        <CommandResult<serde_json::Value>>::schema_name(),
+        generator.subschema_for::<CommandResult<serde_json::Value>>(),
+    );
+    add(ClassificationLevel::schema_name(), generator.subschema_for::<ClassificationLevel>());
+    add(SecurityLabel::schema_name(), generator.subschema_for::<SecurityLabel>());
+    add(UserContext::schema_name(), generator.subschema_for::<UserContext>());
+    add(QueryFilter::schema_name(), generator.subschema_for::<QueryFilter>());
+    // Payload of `entity_changes/<subscription_id>` events
+    add(EntityChanged::schema_name(), generator.subschema_for::<EntityChanged>());
+    types
+}
+
+/// Generate the schema document for the current build
+pub fn api_schema() -> ApiSchema {
+    let mut generator = SchemaSettings::draft07().into_generator();
+    let commands = command_schemas(&mut generator);
+    let types = domain_types(&mut generator);
+    let meta_schema = generator.settings().meta_schema.clone().unwrap_or_default();
+
+    ApiSchema {
+        meta_schema,
+        title: "Nodus command API".to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        commands,
+        types,
+        definitions: generator.take_definitions().into_iter().collect(),
+    }
+}
+
+/// Write the schema document as pretty-printed JSON
+pub fn write_api_schema(path: &Path) -> std::io::Result<()> {
+    let json = serde_json::to_string_pretty(&api_schema())?;
+    std::fs::write(path, json + "\n")
+}
+
+/// Tauri command returning the API schema, for integrators and frontend type generation
+#[tauri::command]
+pub async fn get_api_schema() -> Result<serde_json::Value, String> {
+    static SCHEMA: OnceLock<serde_json::Value> = OnceLock::new();
+    if let Some(schema) = SCHEMA.get() {
+        return Ok(schema.clone());
+    }
+    let schema = serde_json::to_value(api_schema()).map_err(|e| e.to_string())?;
+    Ok(SCHEMA.get_or_init(|| schema).clone())
+}
+
+fn add_argument<T: JsonSchema>(generator: &mut SchemaGenerator, arguments: &mut ObjectValidation, name: &str) {
+    let name = invoke_name(name);
+    if !is_option::<T>() {
+        arguments.required.insert(name.clone());
+    }
+    arguments.properties.insert(name, generator.subschema_for::<T>());
+}
+
+fn object_schema(arguments: ObjectValidation) -> Schema {
+    SchemaObject {
+        instance_type: Some(InstanceType::Object.into()),
+        object: Some(Box::new(arguments)),
+        ..Default::default()
+    }
+    .into()
+}
+
+/// Optional arguments may be left out of the `invoke` payload
+fn is_option<T>() -> bool {
+    std::any::type_name::<T>().starts_with("core::option::Option<")
+}
+
+/// Tauri passes command arguments under their camelCase names
+fn invoke_name(argument: &str) -> String {
+    let mut name = String::with_capacity(argument.len());
+    let mut upper = false;
+    for c in argument.chars() {
+        if c == '_' {
+            upper = true;
+        } else if upper {
+            name.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            name.push(c);
+        }
+    }
+    name
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    const COMMAND_SOURCES: &[&str] = &[
+        include_str!("mod.rs"),
+        include_str!("data.rs"),
+        include_str!("license.rs"),
+        include_str!("observability.rs"),
+        include_str!("security.rs"),
+        include_str!("schema.rs"),
+        include_str!("../main_integrated.rs"),
+    ];
+
+    /// Every `#[tauri::command]` function with the names of its caller-supplied arguments
+    fn declared_commands() -> BTreeMap<String, Vec<String>> {
+        let mut commands = BTreeMap::new();
+        for source in COMMAND_SOURCES {
+            for chunk in source.split("#[tauri::command]\n").skip(1) {
+                let signature = chunk.trim_start();
+                let signature = signature.strip_prefix("pub ").unwrap_or(signature);
+                let Some(signature) = signature.strip_prefix("async fn ") else { continue };
+                let (name, rest) = signature.split_once('(').unwrap();
+                let params = &rest[..rest.find(") ->").unwrap()];
+                let arguments = params
+                    .split(",\n")
+                    .flat_map(|param| param.split(", "))
+                    .filter_map(|param| param.trim().split_once(':'))
+                    .filter(|(_, ty)| !ty.contains("State<") && !ty.contains("AppHandle"))
+                    .map(|(arg, _)| invoke_name(arg.trim()))
+                    .collect();
+                commands.insert(name.to_string(), arguments);
+            }
+        }
+        commands
+    }
+
+    #[test]
+    fn test_schema_covers_every_command_argument() {
+        let schema = api_schema();
+        let declared = declared_commands();
+        assert_eq!(COMMANDS.len(), schema.commands.len(), "command listed twice");
+
+        // The list the invoke handler itself is generated from
+        for command in crate::main_integrated::REGISTERED_COMMANDS {
+            assert!(declared.contains_key(*command), "registered command {} has no #[tauri::command]", command);
+            assert!(COMMANDS.contains(command), "registered command {} is missing from the schema", command);
+        }
+        assert_eq!(declared.keys().collect::<BTreeSet<_>>(), schema.commands.keys().collect::<BTreeSet<_>>());
+
+        for (command, arguments) in &declared {
+            let described = match &schema.commands[command].arguments {
+                Schema::Object(object) => object.object.as_ref().unwrap().properties.keys().cloned().collect::<BTreeSet<_>>(),
+                Schema::Bool(_) => panic!("{} arguments are not an object schema", command),
+            };
+            assert_eq!(&described, &arguments.iter().cloned().collect(), "arguments of {}", command);
+        }
+    }
+
+    #[test]
+    fn test_schema_references_resolve() {
+        let schema = api_schema();
+        let json = serde_json::to_value(&schema).unwrap();
+
+        fn collect_refs(value: &serde_json::Value, refs: &mut BTreeSet<String>) {
+            match value {
+                serde_json::Value::Object(map) => {
+                    if let Some(serde_json::Value::String(reference)) = map.get("$ref") {
+                        refs.insert(reference.trim_start_matches("#/definitions/").to_string());
+                    }
+                    map.values().for_each(|value| collect_refs(value, refs));
+                }
+                serde_json::Value::Array(items) => items.iter().for_each(|value| collect_refs(value, refs)),
+                _ => {}
+            }
+        }
+        let mut refs = BTreeSet::new();
+        collect_refs(&json, &mut refs);
+        for reference in refs {
+            assert!(schema.definitions.contains_key(&reference), "unresolved reference {}", reference);
+        }
+
+        let arguments = &json["commands"]["read_entity"]["arguments"];
+        assert_eq!(arguments["required"], serde_json::json!(["entityId", "entityType", "sessionId"]));
+        assert!(schema.definitions.contains_key("QueryFilter"));
+        assert!(schema.types.contains_key("EntityOperation"));
+    }
+}
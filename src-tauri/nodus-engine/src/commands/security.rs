@@ -3,16 +3,20 @@
 // Provides secure frontend access to security manager functionality
 
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 use std::collections::HashMap;
 use uuid::Uuid;
+use std::sync::Arc;
 
+use crate::shutdown::ShutdownCoordinator;
 use crate::security::{
     SecurityManager, SecurityOperationRequest, SecurityOperationType, 
     SecurityOperationResult, ClassificationLevel, AuthenticationMethod,
-    ThreatAssessmentResult, SecurityContext,
+    ThreatAssessmentResult, SecurityContext, SecurityEvent, SecurityLabel,
+    MACOperation, MACDenialReason, RoleDefinitions,
 };
 use crate::observability::ObservabilityContext;
-use crate::state::AppState;
+use crate::state::{AppState, UserContext};
 use crate::error::AppError;
 
 /// Tauri command for user authentication and security context creation
@@ -24,7 +28,10 @@ pub async fn authenticate_user(
     source_ip: Option<String>,
     user_agent: Option<String>,
     app_state: tauri::State<'_, AppState>,
+    shutdown: tauri::State<'_, Arc<ShutdownCoordinator>>,
 ) -> Result<AuthenticationResult, String> {
+    // Refused once shutdown starts; held until return so teardown waits for this write
+    let _operation = shutdown.begin_operation().map_err(|e| e.to_string())?;
     let auth_method = parse_auth_method(&auth_method)?;
     
     // Create observability context
@@ -40,25 +47,29 @@ pub async fn authenticate_user(
     let security_manager = &app_state.security_manager;
     
     // TODO: Implement actual authentication logic
-    // For now, mock successful authentication
-    let user_context = crate::state::UserContext {
-        user_id: username.clone(),
-        clearance_level: ClassificationLevel::Internal,
-        compartments: vec!["default".to_string()],
-        permissions: vec!["read".to_string(), "write".to_string()],
-        tenant_id: None,
-        session_metadata: HashMap::new(),
-    };
-
-    let session_id = Uuid::new_v4();
+    // For now, mock successful authentication; roles and permissions come from the role store
+    let role_definitions = app_state.role_definitions.read().await.clone();
+    let user_context = login_user_context(&role_definitions, &username);
+    app_state.set_user_context(user_context.clone()).await?;
+
+    // The session is what every later command validates; it counts against the license's cap
+    let session_id = app_state.create_session(username.clone(), user_context.to_security_label()).await
+        .map_err(|e| e.to_string())?;
     
-    let security_context = security_manager.create_security_context(
+    let security_context = match security_manager.create_security_context(
         &user_context,
+        &role_definitions,
         session_id,
         auth_method,
         source_ip,
         user_agent,
-    ).await.map_err(|e| e.to_string())?;
+    ).await {
+        Ok(security_context) => security_context,
+        Err(e) => {
+            app_state.end_session(session_id).await;
+            return Err(e.to_string());
+        }
+    };
 
     Ok(AuthenticationResult {
         success: true,
@@ -71,6 +82,20 @@ pub async fn authenticate_user(
     })
 }
 
+/// User context a login establishes: the roles the role store assigns `username` and the
+/// permissions those roles grant, nothing else
+pub(crate) fn login_user_context(role_definitions: &RoleDefinitions, username: &str) -> UserContext {
+    let mut user_context = UserContext::new(
+        username.to_string(),
+        ClassificationLevel::Internal,
+        vec!["default".to_string()],
+        Vec::new(),
+    )
+    .with_roles(role_definitions.roles_of(username));
+    user_context.apply_roles(role_definitions);
+    user_context
+}
+
 /// Tauri command for security access checks
 #[tauri::command]
 pub async fn check_security_access(
@@ -87,9 +112,8 @@ pub async fn check_security_access(
     let classification_level = parse_classification(&classification)?;
     
     // Get security context
-    let security_context = app_state.security_manager
-        .get_security_context(session_uuid).await
-        .ok_or("Invalid or expired session")?;
+    let security_context = app_state.session_context(session_uuid).await
+        .map_err(|e| e.to_string())?;
 
     // Create observability context
     let obs_context = ObservabilityContext::new(
@@ -143,9 +167,8 @@ pub async fn encrypt_data(
     let classification_level = parse_classification(&classification)?;
     
     // Get security context
-    let security_context = app_state.security_manager
-        .get_security_context(session_uuid).await
-        .ok_or("Invalid or expired session")?;
+    let security_context = app_state.session_context(session_uuid).await
+        .map_err(|e| e.to_string())?;
 
     // Create observability context
     let obs_context = ObservabilityContext::new(
@@ -190,9 +213,8 @@ pub async fn decrypt_data(
         .map_err(|_| "Invalid session ID format")?;
     
     // Get security context
-    let security_context = app_state.security_manager
-        .get_security_context(session_uuid).await
-        .ok_or("Invalid or expired session")?;
+    let security_context = app_state.session_context(session_uuid).await
+        .map_err(|e| e.to_string())?;
 
     // Parse encrypted data
     let domain_id = Uuid::parse_str(&encrypted_data.domain_id)
@@ -253,9 +275,8 @@ pub async fn assess_threat(
         .map_err(|_| "Invalid session ID format")?;
     
     // Get security context
-    let security_context = app_state.security_manager
-        .get_security_context(session_uuid).await
-        .ok_or("Invalid or expired session")?;
+    let security_context = app_state.session_context(session_uuid).await
+        .map_err(|e| e.to_string())?;
 
     // Perform threat assessment
     let assessment = app_state.security_manager.threat_assessment(
@@ -282,9 +303,8 @@ pub async fn get_security_metrics(
         .map_err(|_| "Invalid session ID format")?;
     
     // Verify session exists (basic authorization)
-    app_state.security_manager
-        .get_security_context(session_uuid).await
-        .ok_or("Invalid or expired session")?;
+    app_state.session_context(session_uuid).await
+        .map_err(|e| e.to_string())?;
 
     // Get security metrics
     let metrics = app_state.security_manager.get_security_metrics().await;
@@ -310,9 +330,14 @@ pub async fn update_security_context(
     activity: String,
     risk_modifier: f64,
     app_state: tauri::State<'_, AppState>,
+    shutdown: tauri::State<'_, Arc<ShutdownCoordinator>>,
 ) -> Result<(), String> {
+    // Refused once shutdown starts; held until return so teardown waits for this write
+    let _operation = shutdown.begin_operation().map_err(|e| e.to_string())?;
     let session_uuid = Uuid::parse_str(&session_id)
         .map_err(|_| "Invalid session ID format")?;
+    app_state.validate_session(session_uuid).await
+        .map_err(|e| e.to_string())?;
     
     app_state.security_manager.update_security_context(
         session_uuid,
@@ -320,6 +345,11 @@ pub async fn update_security_context(
         risk_modifier,
     ).await.map_err(|e| e.to_string())?;
 
+    // Refreshing the session picks up role changes made since login
+    let role_definitions = app_state.role_definitions.read().await.clone();
+    app_state.security_manager.refresh_security_context(session_uuid, &role_definitions)
+        .await.map_err(|e| e.to_string())?;
+
     Ok(())
 }
 
@@ -328,16 +358,80 @@ pub async fn update_security_context(
 pub async fn terminate_session(
     session_id: String,
     app_state: tauri::State<'_, AppState>,
+    shutdown: tauri::State<'_, Arc<ShutdownCoordinator>>,
 ) -> Result<(), String> {
+    // Refused once shutdown starts; held until return so teardown waits for this write
+    let _operation = shutdown.begin_operation().map_err(|e| e.to_string())?;
     let session_uuid = Uuid::parse_str(&session_id)
         .map_err(|_| "Invalid session ID format")?;
     
     app_state.security_manager.terminate_security_context(session_uuid)
         .await.map_err(|e| e.to_string())?;
+    app_state.end_session(session_uuid).await;
+    // Objects the session was using are free to be relabeled again
+    app_state.db_manager.mac_engine().end_session(session_uuid).await;
 
     Ok(())
 }
 
+/// Tauri command for pre-checking a MAC decision on arbitrary labels
+///
+/// Lets the frontend gray out operations before attempting them. The subject is the session's
+/// own label, never one the caller supplies. The verdict comes from the MAC engine, so it
+/// takes the engine's constant-time floor whether granted or denied.
+#[tauri::command]
+pub async fn mac_decision(
+    session_id: String,
+    object: SecurityLabel,
+    operation: MACOperation,
+    app_state: tauri::State<'_, AppState>,
+) -> Result<MACDecisionResult, String> {
+    let session_uuid = Uuid::parse_str(&session_id)
+        .map_err(|_| "Invalid session ID format")?;
+    
+    // Get security context
+    let security_context = app_state.session_context(session_uuid).await
+        .map_err(|e| e.to_string())?;
+
+    // The session was validated above, so its label is current
+    let subject = security_context.security_label.clone();
+    let verdict = app_state.security_manager.mac_engine
+        .verdict(operation.clone(), &subject, &object).await;
+
+    // Whether to audit depends only on the object's classification, never on the
+    // outcome, so the audit write can't leak the verdict through timing
+    let obs_context = ObservabilityContext::new(
+        "security",
+        "mac_decision",
+        object.level.clone(),
+        &security_context.user_id,
+        session_uuid,
+    );
+    let audit_logged = app_state.automatic_instrumentation
+        .should_instrument(&obs_context).await
+        .audit_required;
+
+    if audit_logged {
+        let event = SecurityEvent::MACDecision {
+            operation,
+            subject,
+            object,
+            allowed: verdict.allowed,
+            reason: verdict.reason,
+        };
+        let description = serde_json::to_string(&event).map_err(|e| e.to_string())?;
+        app_state.forensic_logger
+            .log_security_event("MACDecision", &description, &security_context.user_id).await
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(MACDecisionResult {
+        allowed: verdict.allowed,
+        reason: verdict.reason,
+        audit_logged,
+    })
+}
+
 // Helper functions
 
 fn parse_auth_method(method: &str) -> Result<AuthenticationMethod, String> {
@@ -377,7 +471,7 @@ fn parse_encryption_algorithm(algorithm: &str) -> Result<crate::security::classi
 
 // Response types for Tauri commands
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct AuthenticationResult {
     pub success: bool,
     pub session_id: String,
@@ -388,7 +482,7 @@ pub struct AuthenticationResult {
     pub expires_at: chrono::DateTime<chrono::Utc>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct SecurityCheckResult {
     pub allowed: bool,
     pub risk_score: f64,
@@ -397,7 +491,15 @@ pub struct SecurityCheckResult {
     pub audit_required: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct MACDecisionResult {
+    pub allowed: bool,
+    /// Machine-readable denial reason, e.g. `InsufficientClearance`
+    pub reason: Option<MACDenialReason>,
+    pub audit_logged: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct EncryptionResult {
     pub encrypted_data: Vec<u8>,
     pub nonce: Vec<u8>,
@@ -406,7 +508,7 @@ pub struct EncryptionResult {
     pub metadata: EncryptionMetadataResult,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct EncryptionMetadataResult {
     pub operation_id: String,
     pub encrypted_at: chrono::DateTime<chrono::Utc>,
@@ -414,7 +516,7 @@ pub struct EncryptionMetadataResult {
     pub compliance_tags: Vec<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct EncryptedDataInput {
     pub ciphertext: Vec<u8>,
     pub nonce: Vec<u8>,
@@ -428,7 +530,7 @@ pub struct EncryptedDataInput {
     pub compliance_tags: Vec<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct ThreatAssessmentResponse {
     pub risk_score: f64,
     pub threat_level: String,
@@ -436,7 +538,7 @@ pub struct ThreatAssessmentResponse {
     pub recommended_actions: Vec<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct SecurityMetricsResult {
     pub total_security_checks: u64,
     pub access_granted: u64,
@@ -488,4 +590,27 @@ mod tests {
         
         assert!(parse_encryption_algorithm("INVALID").is_err());
     }
+
+    #[test]
+    fn test_login_permissions_come_from_the_role_store() {
+        let roles = RoleDefinitions::from_toml(r#"
+            [[roles]]
+            name = "records_officer"
+            permissions = ["read", "legal_hold"]
+
+            [[assignments]]
+            user_id = "alice"
+            roles = ["records_officer"]
+        "#).unwrap();
+
+        let alice = login_user_context(&roles, "alice");
+        assert_eq!(alice.roles, vec!["records_officer".to_string()]);
+        assert_eq!(alice.permissions, vec!["read".to_string(), "legal_hold".to_string()]);
+        assert_eq!(alice.permissions, roles.granted_permissions("alice"));
+
+        // A user the store doesn't know logs in with nothing, not a default read/write grant
+        let stranger = login_user_context(&roles, "mallory");
+        assert!(stranger.roles.is_empty());
+        assert!(stranger.permissions.is_empty());
+    }
 }
@@ -0,0 +1,293 @@
+// src-tauri/src/database/change_feed.rs
+// Entity Change Feed - Fans out committed entity changes (Postgres LISTEN/NOTIFY) to live subscribers
+// Each subscriber only hears of changes it could read, so above-clearance writes are never signalled
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgListener;
+use sqlx::PgPool;
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use super::{DatabaseContext, SecureEntity};
+use crate::security::ClassificationLevel;
+
+/// Postgres channel the `entities` change trigger notifies on
+pub const ENTITY_CHANGES_CHANNEL: &str = "entity_changes";
+
+/// Changes buffered per subscriber before a slow one is told it missed some
+const DEFAULT_FEED_CAPACITY: usize = 1024;
+
+/// Wait before re-establishing a failed LISTEN connection
+const LISTEN_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum EntityChangeOperation {
+    Create,
+    Update,
+    /// Soft or permanent deletion, or archival
+    Delete,
+}
+
+/// Change notice sent to subscribers; carries no entity data, so they re-read what they need
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct EntityChanged {
+    pub id: Uuid,
+    pub entity_type: String,
+    pub operation: EntityChangeOperation,
+    pub version: i64,
+    pub classification: ClassificationLevel,
+}
+
+/// A change as published by the database, with the label and tenant subscribers are filtered on
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntityChange {
+    #[serde(flatten)]
+    pub event: EntityChanged,
+    #[serde(default)]
+    pub compartments: Vec<String>,
+    pub tenant_id: Option<String>,
+}
+
+impl EntityChange {
+    pub fn of(entity: &SecureEntity, operation: EntityChangeOperation) -> Self {
+        Self {
+            event: EntityChanged {
+                id: entity.id,
+                entity_type: entity.entity_type.clone(),
+                operation,
+                version: entity.version,
+                classification: entity.classification.clone(),
+            },
+            compartments: entity.compartments.clone(),
+            tenant_id: entity.tenant_id.clone(),
+        }
+    }
+
+    /// Whether `context` may learn of this change
+    ///
+    /// The read rules of `DatabaseContext::can_see`: No Read Up, compartments held, and
+    /// the context's tenant or shared. Deletions are signalled like any other change.
+    pub fn visible_to(&self, context: &DatabaseContext) -> bool {
+        context.security_label.level.dominates(&self.event.classification)
+            && self.compartments.iter().all(|c| context.security_label.compartments.contains(c))
+            && (self.tenant_id.is_none() || self.tenant_id == context.tenant_id || context.tenant_id.is_none())
+    }
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum ChangeFeedError {
+    /// The subscriber fell behind or the LISTEN connection dropped; re-query to catch up
+    #[error("Entity changes were missed")]
+    Missed,
+
+    #[error("Entity change feed closed")]
+    Closed,
+}
+
+#[derive(Debug, Clone)]
+enum FeedMessage {
+    Change(Arc<EntityChange>),
+    /// Notifications may have been lost while the LISTEN connection was down
+    Gap,
+}
+
+/// Broadcast of committed entity changes to clearance-filtered subscribers
+///
+/// `DatabaseManager` feeds it from Postgres notifications via `listen`; `InMemoryEntityStore`
+/// publishes its own writes when given one.
+#[derive(Debug)]
+pub struct EntityChangeFeed {
+    sender: broadcast::Sender<FeedMessage>,
+}
+
+impl Default for EntityChangeFeed {
+    fn default() -> Self {
+        Self::new(DEFAULT_FEED_CAPACITY)
+    }
+}
+
+impl EntityChangeFeed {
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity.max(1));
+        Self { sender }
+    }
+
+    /// Deliver `change` to current subscribers; call only once the change is committed
+    pub fn publish(&self, change: EntityChange) {
+        // No subscribers is not an error
+        let _ = self.sender.send(FeedMessage::Change(Arc::new(change)));
+    }
+
+    /// Changes to `entity_types` (all types if empty) visible to `context`, from now on
+    pub fn subscribe(&self, context: DatabaseContext, entity_types: Vec<String>) -> EntityChangeSubscription {
+        EntityChangeSubscription {
+            receiver: self.sender.subscribe(),
+            context,
+            entity_types: entity_types.into_iter().collect(),
+        }
+    }
+
+    /// Relay notifications on `ENTITY_CHANGES_CHANNEL` until `pool` is closed
+    pub fn listen(self: Arc<Self>, pool: PgPool) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            while !pool.is_closed() {
+                if let Err(e) = self.relay(&pool).await {
+                    if pool.is_closed() {
+                        break;
+                    }
+                    tracing::warn!("Entity change feed interrupted, retrying in {:?}: {}", LISTEN_RETRY_DELAY, e);
+                    let _ = self.sender.send(FeedMessage::Gap);
+                    tokio::time::sleep(LISTEN_RETRY_DELAY).await;
+                }
+            }
+        })
+    }
+
+    async fn relay(&self, pool: &PgPool) -> Result<(), sqlx::Error> {
+        let mut listener = PgListener::connect_with(pool).await?;
+        listener.listen(ENTITY_CHANGES_CHANNEL).await?;
+
+        loop {
+            // `None` means the connection dropped; the listener reconnects on the next call
+            let Some(notification) = listener.try_recv().await? else {
+                let _ = self.sender.send(FeedMessage::Gap);
+                continue;
+            };
+            match serde_json::from_str::<EntityChange>(notification.payload()) {
+                Ok(change) => self.publish(change),
+                Err(e) => tracing::warn!("Ignoring malformed entity change notification: {}", e),
+            }
+        }
+    }
+}
+
+/// One subscriber's view of the change feed
+#[derive(Debug)]
+pub struct EntityChangeSubscription {
+    receiver: broadcast::Receiver<FeedMessage>,
+    context: DatabaseContext,
+    entity_types: HashSet<String>,
+}
+
+impl EntityChangeSubscription {
+    /// Next change this subscriber may see; changes it may not are skipped silently
+    pub async fn recv(&mut self) -> Result<EntityChanged, ChangeFeedError> {
+        loop {
+            match self.receiver.recv().await {
+                Ok(FeedMessage::Change(change)) if self.wants(&change) => return Ok(change.event.clone()),
+                Ok(FeedMessage::Change(_)) => continue,
+                Ok(FeedMessage::Gap) | Err(broadcast::error::RecvError::Lagged(_)) => return Err(ChangeFeedError::Missed),
+                Err(broadcast::error::RecvError::Closed) => return Err(ChangeFeedError::Closed),
+            }
+        }
+    }
+
+    fn wants(&self, change: &EntityChange) -> bool {
+        (self.entity_types.is_empty() || self.entity_types.contains(&change.event.entity_type))
+            && change.visible_to(&self.context)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{EntityStore, InMemoryEntityStore};
+    use crate::multi_tenant::TenantStatus;
+    use crate::security::SecurityLabel;
+    use serde_json::json;
+
+    fn context(level: ClassificationLevel, tenant_id: Option<&str>) -> DatabaseContext {
+        DatabaseContext::new(
+            format!("{}-user", level),
+            Uuid::new_v4(),
+            SecurityLabel::new(level, Vec::new()),
+            tenant_id.map(str::to_string),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_create_is_signalled_to_cleared_subscribers_only() {
+        let feed = Arc::new(EntityChangeFeed::default());
+        let store = InMemoryEntityStore::new().with_change_feed(feed.clone());
+        let officer = context(ClassificationLevel::Secret, None);
+        let analyst = context(ClassificationLevel::Internal, None);
+        let mut cleared = feed.subscribe(officer.clone(), vec!["report".to_string()]);
+        let mut uncleared = feed.subscribe(analyst.clone(), vec!["report".to_string()]);
+
+        let secret = store.create_entity("report", json!({"title": "Sources"}), &officer).await.unwrap();
+        let change = cleared.recv().await.unwrap();
+        assert_eq!(change, EntityChanged {
+            id: secret.id,
+            entity_type: "report".to_string(),
+            operation: EntityChangeOperation::Create,
+            version: 1,
+            classification: ClassificationLevel::Secret,
+        });
+
+        // The analyst's next event is their own create: the Secret one was never signalled
+        let internal = store.create_entity("report", json!({"title": "Roadmap"}), &analyst).await.unwrap();
+        assert_eq!(uncleared.recv().await.unwrap().id, internal.id);
+        assert_eq!(cleared.recv().await.unwrap().id, internal.id);
+    }
+
+    #[tokio::test]
+    async fn test_subscription_filters_entity_type_and_tenant() {
+        let feed = Arc::new(EntityChangeFeed::default());
+        let store = InMemoryEntityStore::new().with_change_feed(feed.clone());
+        for tenant in ["acme", "globex"] {
+            store.tenant_gate().set_status(tenant, TenantStatus::Active).await;
+        }
+        let acme = context(ClassificationLevel::Internal, Some("acme"));
+        let globex = context(ClassificationLevel::Internal, Some("globex"));
+        let mut orders = feed.subscribe(globex.clone(), vec!["order".to_string()]);
+
+        store.create_entity("order", json!({}), &acme).await.unwrap();
+        store.create_entity("note", json!({}), &globex).await.unwrap();
+        let order = store.create_entity("order", json!({}), &globex).await.unwrap();
+        assert!(store.delete_entity(order.id, &globex).await.unwrap());
+
+        assert_eq!(orders.recv().await.unwrap().operation, EntityChangeOperation::Create);
+        let deleted = orders.recv().await.unwrap();
+        assert_eq!((deleted.id, deleted.operation), (order.id, EntityChangeOperation::Delete));
+    }
+
+    #[tokio::test]
+    async fn test_lagging_subscriber_is_told_changes_were_missed() {
+        let feed = EntityChangeFeed::new(1);
+        let analyst = context(ClassificationLevel::Internal, None);
+        let mut subscription = feed.subscribe(analyst.clone(), Vec::new());
+        let entity = SecureEntity::created(
+            Uuid::new_v4(), "note", json!({}), analyst.security_label.clone(), &analyst, chrono::Utc::now(),
+        );
+
+        feed.publish(EntityChange::of(&entity, EntityChangeOperation::Create));
+        feed.publish(EntityChange::of(&entity, EntityChangeOperation::Update));
+        assert_eq!(subscription.recv().await, Err(ChangeFeedError::Missed));
+        assert_eq!(subscription.recv().await.unwrap().operation, EntityChangeOperation::Update);
+    }
+
+    #[test]
+    fn test_notification_payload_parses() {
+        let payload = json!({
+            "id": Uuid::new_v4(),
+            "entity_type": "order",
+            "operation": "update",
+            "version": 3,
+            "classification": "SECRET",
+            "compartments": ["ALPHA"],
+            "tenant_id": null,
+        });
+        let change: EntityChange = serde_json::from_value(payload).unwrap();
+        assert_eq!(change.event.operation, EntityChangeOperation::Update);
+        assert_eq!(change.event.classification, ClassificationLevel::Secret);
+
+        let without_alpha = context(ClassificationLevel::Secret, None);
+        assert!(!change.visible_to(&without_alpha));
+    }
+}
@@ -2,33 +2,255 @@
 // Database Manager - Interfaces with existing PostgreSQL schema
 // Maintains polyinstantiation and security classification from existing SQL files
 
-use sqlx::{PgPool, Row, Postgres, Transaction};
+use sqlx::{Acquire, PgConnection, PgPool, Row, Postgres, Transaction};
+use sqlx::postgres::{PgConnectOptions, PgPoolOptions, PgSslMode};
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 use uuid::Uuid;
-use chrono::{DateTime, Utc};
-use std::collections::HashMap;
+use chrono::{DateTime, SecondsFormat, Utc};
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use base64::{engine::general_purpose, Engine as _};
+use thiserror::Error;
+use tokio::sync::Mutex;
 
-use crate::security::{SecurityLabel, ClassificationLevel};
+use crate::security::{classification_lattice, has_permission, ClassificationClassifier, MACEngine, Permission, SealedValue, SecurityLabel, ClassificationLevel, SubjectKey, TranquilityViolation};
 use crate::observability::ForensicEnvelope;
+use crate::observability::integrity::{content_digest, ChainHead, ChainLink, ForensicChain, ForensicLinker, FORENSIC_CHAIN_KEY};
+use crate::observability::compliance::{framework_key, AssessedRecord};
+use crate::observability::observation::ComplianceRecord;
+use crate::database::{QueryFilter, QueryFilterError};
+use crate::database::change_feed::EntityChangeFeed;
+use crate::database::entity_store::{preview_operation, replay_create, validate_idempotency_key, DryRunOutcome, EntityStore};
+use crate::database::declassification::{DeclassificationRequest, DeclassificationStore};
+use crate::database::legal_hold::{LegalHold, LegalHoldScope, LegalHolds};
+use crate::database::retention::{RetainedRecord, RetentionStore, RetentionTarget, RetentionUpdate, ANONYMIZED_MARKER};
+use crate::database::subject_vault::{StoredSubjectKey, SubjectKeyStore, SubjectVault};
+use crate::security::api_keys::StoredApiKey;
+use crate::security::classification_crypto::{CryptoError, FieldKeyStore, StoredFieldKey};
+use crate::security::key_provider::{system_key_provider, KeyProvider, KeyProviderError};
+use crate::multi_tenant::{ExpiryAction, MultiTenantError, TenantGate, TenantStatus};
 
 pub mod migrations;
 pub mod queries;
 pub mod polyinstantiation;
 
+/// Fallback connection string when neither config nor `DATABASE_URL` provides one
+const DEFAULT_DATABASE_URL: &str = "postgresql://localhost/nodus";
+
+/// Most entities one `search_entities` call returns
+pub const SEARCH_RESULT_LIMIT: i64 = 100;
+
+/// Budget for the `SELECT 1` probe in `health_check`
+pub const HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Most classification reviews kept pending; the oldest is dropped to admit a new one
+pub const MAX_PENDING_CLASSIFICATION_REVIEWS: usize = 1_000;
+
+/// Connection parameters for the database pool (`[database.connection]` in system policy)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DbConfig {
+    pub url: String,
+    pub max_connections: u32,
+    pub min_connections: u32,
+    /// How long an operation waits for a pooled connection before failing
+    pub acquire_timeout_secs: u64,
+    /// Close connections idle for longer than this; `None` keeps them open
+    pub idle_timeout_secs: Option<u64>,
+    /// TLS mode; `None` defers to the URL's `sslmode` parameter
+    pub tls_mode: Option<DbTlsMode>,
+}
+
+/// TLS requirement for database connections (mirrors libpq `sslmode`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DbTlsMode {
+    Disable,
+    Prefer,
+    Require,
+    VerifyCa,
+    VerifyFull,
+}
+
+/// Database setup, relabeling and lifecycle errors
+#[derive(Debug, Error)]
+pub enum DatabaseError {
+    #[error("Invalid database config: {0}")]
+    InvalidConfig(String),
+    
+    #[error("Database connection failed: {0}")]
+    Connection(#[from] sqlx::Error),
+
+    #[error("Reclassification denied: {0}")]
+    ReclassificationDenied(String),
+
+    #[error("Entity {0} was modified concurrently")]
+    ReclassificationConflict(Uuid),
+
+    #[error("Reclassification of {entity_id} breaks tranquility: {violation}")]
+    TranquilityViolation { entity_id: Uuid, violation: TranquilityViolation },
+
+    #[error("Declassification denied: {0}")]
+    DeclassificationDenied(String),
+
+    #[error("No pending declassification request {0}")]
+    DeclassificationNotFound(Uuid),
+
+    #[error("Declassification request {0} expired before approval")]
+    DeclassificationExpired(Uuid),
+
+    #[error("Lifecycle change denied: {0}")]
+    LifecycleDenied(String),
+
+    #[error("Entity {0} was modified during a lifecycle change")]
+    LifecycleConflict(Uuid),
+
+    #[error("Subject erasure denied: {0}")]
+    ErasureDenied(String),
+
+    #[error("Subject key error: {0}")]
+    SubjectKey(#[from] crate::security::SecurityError),
+
+    #[error("Key provider error: {0}")]
+    KeyProvider(#[from] KeyProviderError),
+
+    #[error("Legal hold: {0}")]
+    LegalHold(String),
+
+    #[error("Idempotency key: {0}")]
+    IdempotencyKey(String),
+
+    #[error("Invalid query filter: {0}")]
+    InvalidFilter(#[from] QueryFilterError),
+
+    #[error("Invalid query cursor: {0}")]
+    InvalidCursor(String),
+
+    #[error("Tenant unavailable: {0}")]
+    Tenant(#[from] MultiTenantError),
+}
+
+/// Permission required to change an entity's label
+pub const RELABEL_PERMISSION: &str = Permission::Relabel.as_str();
+
+/// Additional permission required when the new label does not dominate the old one;
+/// downgrades also need a second approver through `DeclassificationWorkflow`
+pub const DOWNGRADE_PERMISSION: &str = Permission::RelabelDowngrade.as_str();
+
+/// Permission required to erase data permanently: entities, or a data subject's keys (GDPR erasure)
+pub const ERASE_PERMISSION: &str = Permission::Erase.as_str();
+
+/// Permission required to place or release a legal hold
+pub const LEGAL_HOLD_PERMISSION: &str = Permission::LegalHold.as_str();
+
+/// Longest client idempotency key accepted for entity creation
+pub const MAX_IDEMPOTENCY_KEY_LEN: usize = 255;
+
+impl Default for DbConfig {
+    fn default() -> Self {
+        Self {
+            url: DEFAULT_DATABASE_URL.to_string(),
+            max_connections: 20,
+            min_connections: 0,
+            acquire_timeout_secs: 5,
+            idle_timeout_secs: Some(600),
+            tls_mode: None,
+        }
+    }
+}
+
+impl DbConfig {
+    /// Defaults with the URL taken from `DATABASE_URL` when set
+    pub fn from_env() -> Self {
+        Self::default().with_env_url()
+    }
+
+    /// These settings with the URL replaced by `DATABASE_URL` when set, so credentials can stay out of policy files
+    pub fn with_env_url(mut self) -> Self {
+        if let Ok(url) = std::env::var("DATABASE_URL") {
+            self.url = url;
+        }
+        self
+    }
+
+    /// Reject pool settings that can never hand out a connection
+    pub fn validate(&self) -> Result<(), DatabaseError> {
+        if self.url.trim().is_empty() {
+            return Err(DatabaseError::InvalidConfig("url is empty".to_string()));
+        }
+        if self.max_connections == 0 {
+            return Err(DatabaseError::InvalidConfig("max_connections must be at least 1".to_string()));
+        }
+        if self.max_connections < self.min_connections {
+            return Err(DatabaseError::InvalidConfig(format!(
+                "max_connections ({}) is below min_connections ({})",
+                self.max_connections, self.min_connections
+            )));
+        }
+        if self.acquire_timeout_secs == 0 {
+            return Err(DatabaseError::InvalidConfig("acquire_timeout_secs must be at least 1".to_string()));
+        }
+        Ok(())
+    }
+}
+
+impl From<DbTlsMode> for PgSslMode {
+    fn from(mode: DbTlsMode) -> Self {
+        match mode {
+            DbTlsMode::Disable => PgSslMode::Disable,
+            DbTlsMode::Prefer => PgSslMode::Prefer,
+            DbTlsMode::Require => PgSslMode::Require,
+            DbTlsMode::VerifyCa => PgSslMode::VerifyCa,
+            DbTlsMode::VerifyFull => PgSslMode::VerifyFull,
+        }
+    }
+}
+
 /// Database manager for secure data operations
 #[derive(Debug, Clone)]
 pub struct DatabaseManager {
     pool: PgPool,
     enable_polyinstantiation: bool,
+    /// Derives the stored classification of new entities from their content
+    classifier: Arc<ClassificationClassifier>,
+    classification_reviews: Arc<Mutex<VecDeque<ClassificationReview>>>,
+    /// Statuses of known tenants; operations for inactive ones are refused
+    tenant_gate: Arc<TenantGate>,
+    /// Committed entity changes, relayed from Postgres notifications once `listen_for_changes` runs
+    change_feed: Arc<EntityChangeFeed>,
+    /// Tracks which sessions use which entities and enforces its tranquility policy on relabels
+    mac_engine: Arc<MACEngine>,
+    /// Seals subjects' personal fields on write under their wrapped subject keys
+    subject_vault: Arc<SubjectVault>,
+    /// Head of the forensic hash chain, shared with the `ForensicLogger` so rows written here are chained too
+    forensic_linker: Arc<ForensicLinker>,
+}
+
+/// An entity whose classification rules conflicted, awaiting a human decision
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClassificationReview {
+    pub entity_id: Uuid,
+    pub entity_type: String,
+    pub classification: ClassificationLevel,
+    pub matched_rules: Vec<String>,
+    pub flagged_at: DateTime<Utc>,
 }
 
 /// Security context for database operations
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DatabaseContext {
     pub user_id: String,
     pub session_id: Uuid,
     pub security_label: SecurityLabel,
     pub tenant_id: Option<String>,
+    /// Operator permissions, e.g. `RELABEL_PERMISSION`
+    #[serde(default)]
+    pub permissions: Vec<String>,
+    /// Also return soft-deleted entities from reads, queries and searches (for audit)
+    #[serde(default)]
+    pub include_deleted: bool,
 }
 
 /// Database entity with security metadata
@@ -45,6 +267,198 @@ pub struct SecureEntity {
     pub compartments: Vec<String>,
     pub version: i64,
     pub tenant_id: Option<String>,
+    /// Set while the entity is soft-deleted
+    #[serde(default)]
+    pub deleted_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub deleted_by: Option<String>,
+}
+
+impl SecureEntity {
+    /// Version 1 of a new entity stored at `label`, owned by the context's user and tenant
+    pub(crate) fn created(
+        id: Uuid,
+        entity_type: &str,
+        data: serde_json::Value,
+        label: SecurityLabel,
+        context: &DatabaseContext,
+        now: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id,
+            entity_type: entity_type.to_string(),
+            data,
+            created_at: now,
+            updated_at: now,
+            created_by: context.user_id.clone(),
+            updated_by: context.user_id.clone(),
+            classification: label.level,
+            compartments: label.compartments.into_iter().collect(),
+            version: 1,
+            tenant_id: context.tenant_id.clone(),
+            deleted_at: None,
+            deleted_by: None,
+        }
+    }
+
+    /// Next version holding `data`, a merge of concurrent edits, labelled at `label`
+    ///
+    /// `None` if the context may not write this entity or the merged one. A merge may only
+    /// raise the label; lowering it takes a declassification.
+    pub(crate) fn merged(
+        &self,
+        data: serde_json::Value,
+        label: &SecurityLabel,
+        context: &DatabaseContext,
+        now: DateTime<Utc>,
+    ) -> Result<Option<Self>, DatabaseError> {
+        if !context.can_see(self) || !context.can_write(self) {
+            return Ok(None);
+        }
+        let current = SecurityLabel::new(self.classification.clone(), self.compartments.clone());
+        if !label.dominates(&current) {
+            return Err(DatabaseError::ReclassificationDenied(format!(
+                "a merge may not lower {} to {}", current.level, label.level
+            )));
+        }
+
+        let mut compartments: Vec<String> = label.compartments.iter().cloned().collect();
+        compartments.sort();
+        let merged = Self {
+            data,
+            classification: label.level.clone(),
+            compartments,
+            updated_at: now,
+            updated_by: context.user_id.clone(),
+            version: self.version + 1,
+            ..self.clone()
+        };
+        Ok(context.can_write(&merged).then_some(merged))
+    }
+
+    /// Next version with top-level fields of `updates` merged into the data
+    pub(crate) fn updated(&self, updates: serde_json::Value, context: &DatabaseContext, now: DateTime<Utc>) -> Self {
+        let mut data = self.data.clone();
+        if let (serde_json::Value::Object(existing_map), serde_json::Value::Object(updates_map)) = (&mut data, updates) {
+            for (key, value) in updates_map {
+                existing_map.insert(key, value);
+            }
+        }
+
+        Self {
+            data,
+            updated_at: now,
+            updated_by: context.user_id.clone(),
+            version: self.version + 1,
+            deleted_at: None,
+            deleted_by: None,
+            ..self.clone()
+        }
+    }
+}
+
+/// Soft-delete lifecycle change, each recorded under its own forensic event type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EntityLifecycle {
+    SoftDelete,
+    Restore,
+    /// Permanent removal; needs `ERASE_PERMISSION`
+    HardDelete,
+}
+
+impl EntityLifecycle {
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            Self::SoftDelete => "entity.soft_deleted",
+            Self::Restore => "entity.restored",
+            Self::HardDelete => "entity.hard_deleted",
+        }
+    }
+
+    fn action(&self) -> &'static str {
+        match self {
+            Self::SoftDelete => "delete",
+            Self::Restore => "restore",
+            Self::HardDelete => "erase",
+        }
+    }
+
+    /// Check that `context` may apply the change to `entity`
+    pub(crate) fn authorize(&self, entity: &SecureEntity, context: &DatabaseContext) -> Result<(), DatabaseError> {
+        // Same MAC checks as any write: No Read Up, No Write Down
+        // and the entity has to be in the caller's tenant or shared
+        if !context.can_write(entity) || !context.in_tenant(entity) {
+            return Err(DatabaseError::LifecycleDenied(format!("entity {} not found", entity.id)));
+        }
+
+        match self {
+            Self::SoftDelete if entity.deleted_at.is_some() => {
+                Err(DatabaseError::LifecycleDenied(format!("entity {} is already deleted", entity.id)))
+            }
+            Self::Restore if entity.deleted_at.is_none() => {
+                Err(DatabaseError::LifecycleDenied(format!("entity {} is not deleted", entity.id)))
+            }
+            Self::HardDelete if !has_permission(context, Permission::Erase) => {
+                Err(DatabaseError::LifecycleDenied(format!("missing '{}' permission", ERASE_PERMISSION)))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// `entity` as it is after the change; a hard-deleted entity no longer exists
+    pub(crate) fn apply(&self, entity: &SecureEntity, context: &DatabaseContext, now: DateTime<Utc>) -> Option<SecureEntity> {
+        let (deleted_at, deleted_by) = match self {
+            Self::SoftDelete => (Some(now), Some(context.user_id.clone())),
+            Self::Restore => (None, None),
+            Self::HardDelete => return None,
+        };
+        Some(SecureEntity {
+            deleted_at,
+            deleted_by,
+            updated_at: now,
+            updated_by: context.user_id.clone(),
+            version: entity.version + 1,
+            ..entity.clone()
+        })
+    }
+
+    /// Forensic record of the change from `before` to `after`, at the entity's classification
+    ///
+    /// Only lifecycle fields are recorded, so erased data doesn't live on in the log.
+    fn envelope(&self, before: &SecureEntity, after: Option<&SecureEntity>, context: &DatabaseContext) -> ForensicEnvelope {
+        let state = |entity: &SecureEntity| serde_json::json!({
+            "deleted_at": entity.deleted_at,
+            "deleted_by": entity.deleted_by,
+            "version": entity.version,
+        });
+        ForensicEnvelope::new(
+            Uuid::new_v4(),
+            self.event_type(),
+            &context.user_id,
+            context.session_id,
+            before.classification.clone(),
+            self.action(),
+        )
+        .with_resource(&format!("entity:{}", before.id))
+        .with_state_change(Some(state(before)), after.map(state))
+        .with_metadata(serde_json::json!({
+            "entity_type": before.entity_type,
+            "compartments": before.compartments,
+        }))
+    }
+}
+
+/// Record that a data subject's keys were destroyed, kept in `subject_erasures`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErasureAttestation {
+    pub subject_id: String,
+    /// Keys destroyed; data sealed under them is unrecoverable
+    pub keys_destroyed: Vec<Uuid>,
+    pub erased_by: String,
+    pub erased_at: DateTime<Utc>,
+    /// Forensic envelope recording the erasure
+    pub envelope_id: Uuid,
 }
 
 /// Query result with security enforcement
@@ -54,6 +468,86 @@ pub struct SecureQueryResult {
     pub total_count: i64,
     pub filtered_count: i64, // After security filtering
     pub access_denied_count: i64,
+    /// Opaque cursor for the next page; only set by cursor queries when more rows exist
+    #[serde(default)]
+    pub next_cursor: Option<String>,
+}
+
+/// Connection pool health as reported by `health_check`
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DbHealth {
+    pub healthy: bool,
+    pub latency_ms: u64,
+    pub pool_size: u32,
+    pub idle_connections: u32,
+    pub in_use_connections: u32,
+    pub error: Option<String>,
+    pub checked_at: DateTime<Utc>,
+}
+
+/// Keyset position of the last entity seen, ordered by `(created_at, id)`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntityCursor {
+    pub created_at: DateTime<Utc>,
+    pub id: Uuid,
+}
+
+impl EntityCursor {
+    /// Cursor positioned just after `entity`
+    pub fn after(entity: &SecureEntity) -> Self {
+        Self {
+            created_at: entity.created_at,
+            id: entity.id,
+        }
+    }
+
+    /// Encode as an opaque, URL-safe token
+    pub fn encode(&self) -> String {
+        let raw = format!("{}|{}", self.created_at.to_rfc3339_opts(SecondsFormat::AutoSi, true), self.id);
+        general_purpose::URL_SAFE_NO_PAD.encode(raw)
+    }
+
+    /// Decode a token produced by `encode`; `None` if it was tampered with or truncated
+    pub fn decode(token: &str) -> Option<Self> {
+        let raw = general_purpose::URL_SAFE_NO_PAD.decode(token).ok()?;
+        let raw = String::from_utf8(raw).ok()?;
+        let (created_at, id) = raw.split_once('|')?;
+
+        Some(Self {
+            created_at: DateTime::parse_from_rfc3339(created_at).ok()?.with_timezone(&Utc),
+            id: Uuid::parse_str(id).ok()?,
+        })
+    }
+
+    /// Whether `entity` sorts after this cursor (mirrors the SQL row comparison)
+    pub fn precedes(&self, entity: &SecureEntity) -> bool {
+        (entity.created_at, entity.id) > (self.created_at, self.id)
+    }
+}
+
+/// One operation in an entity batch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum BatchEntityOperation {
+    Create {
+        entity_type: String,
+        data: serde_json::Value,
+        /// Client key making the create safe to retry; see `create_entity_idempotent`
+        #[serde(default)]
+        idempotency_key: Option<String>,
+    },
+    Update { entity_id: Uuid, updates: serde_json::Value },
+    Delete { entity_id: Uuid },
+}
+
+/// Outcome of one batch item, keyed by its index in the request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchItemResult {
+    pub index: usize,
+    pub success: bool,
+    pub entity: Option<SecureEntity>,
+    /// Entity state before an update or delete, for forensic before/after records
+    pub previous: Option<SecureEntity>,
+    pub error: Option<String>,
 }
 
 /// Database operation types for audit logging
@@ -68,23 +562,239 @@ pub enum DatabaseOperation {
 }
 
 impl DatabaseManager {
-    /// Create new database manager with existing connection
-    pub async fn new() -> Result<Self, sqlx::Error> {
-        // Use existing database connection string from environment
-        let database_url = std::env::var("DATABASE_URL")
-            .unwrap_or_else(|_| "postgresql://localhost/nodus".to_string());
-        
-        let pool = PgPool::connect(&database_url).await?;
+    /// Create new database manager from `DATABASE_URL` and default pool settings
+    pub async fn new() -> Result<Self, DatabaseError> {
+        Self::with_config(DbConfig::from_env()).await
+    }
+
+    /// Create new database manager with explicit connection settings
+    pub async fn with_config(config: DbConfig) -> Result<Self, DatabaseError> {
+        Self::with_key_provider(config, system_key_provider().await?).await
+    }
+
+    /// Create new database manager whose subject keys are wrapped by `key_provider`
+    pub async fn with_key_provider(config: DbConfig, key_provider: Arc<dyn KeyProvider>) -> Result<Self, DatabaseError> {
+        config.validate()?;
+
+        let pool = Self::pool_options(&config)
+            .connect_with(Self::connect_options(&config)?)
+            .await?;
         
         // Check if polyinstantiation is enabled (from existing schema)
         let enable_polyinstantiation = Self::check_polyinstantiation_enabled(&pool).await?;
-        
-        Ok(Self {
+
+        let manager = Self::from_pool(pool, enable_polyinstantiation, key_provider);
+        manager.wrap_legacy_subject_keys().await?;
+        manager.resume_forensic_chain().await;
+        manager.load_tenant_statuses().await;
+        Ok(manager)
+    }
+
+    /// Wrap an existing pool without probing the schema; subject keys are wrapped by `key_provider`
+    pub(crate) fn from_pool(pool: PgPool, enable_polyinstantiation: bool, key_provider: Arc<dyn KeyProvider>) -> Self {
+        Self {
             pool,
             enable_polyinstantiation,
+            classifier: Arc::new(ClassificationClassifier::default()),
+            classification_reviews: Arc::new(Mutex::new(VecDeque::new())),
+            tenant_gate: Arc::new(TenantGate::default()),
+            change_feed: Arc::new(EntityChangeFeed::default()),
+            mac_engine: Arc::new(MACEngine::default()),
+            subject_vault: Arc::new(SubjectVault::new(key_provider)),
+            forensic_linker: Arc::new(ForensicLinker::new(ForensicChain::new(FORENSIC_CHAIN_KEY))),
+        }
+    }
+
+    /// Seal personal fields with `subject_vault` (its key provider and field rules)
+    pub fn with_subject_vault(mut self, subject_vault: SubjectVault) -> Self {
+        self.subject_vault = Arc::new(subject_vault);
+        self
+    }
+
+    /// Use `classifier` to label new entities
+    pub fn with_classifier(mut self, classifier: ClassificationClassifier) -> Self {
+        self.classifier = Arc::new(classifier);
+        self
+    }
+
+    /// Share `mac_engine`, whose tranquility policy then governs `reclassify_entities`
+    pub fn with_mac_engine(mut self, mac_engine: Arc<MACEngine>) -> Self {
+        self.mac_engine = mac_engine;
+        self
+    }
+
+    /// Engine tracking which sessions use which entities
+    pub fn mac_engine(&self) -> &Arc<MACEngine> {
+        &self.mac_engine
+    }
+
+    /// Tenant statuses consulted before every entity operation, shared with `MultiTenantSystem`
+    pub fn tenant_gate(&self) -> &Arc<TenantGate> {
+        &self.tenant_gate
+    }
+
+    /// Live entity changes; subscribe with the reader's context to receive those it may see
+    pub fn change_feed(&self) -> &Arc<EntityChangeFeed> {
+        &self.change_feed
+    }
+
+    /// Forensic hash chain head that every stored envelope is linked through
+    pub fn forensic_linker(&self) -> &Arc<ForensicLinker> {
+        &self.forensic_linker
+    }
+
+    /// Continue the forensic chain from the last stored link so it spans restarts
+    async fn resume_forensic_chain(&self) {
+        let head = match self.latest_forensic_link(None).await {
+            Ok(Some((hash, timestamp))) => ChainHead { hash, timestamp: Some(timestamp) },
+            Ok(None) => ChainHead::genesis(),
+            Err(e) => {
+                tracing::warn!("Could not read forensic chain head, restarting from genesis: {}", e);
+                ChainHead::genesis()
+            }
+        };
+        self.forensic_linker.resume(head).await;
+    }
+
+    /// Start relaying committed entity changes from Postgres into `change_feed`
+    ///
+    /// Changes are signalled by the `entities` trigger, which notifies only on commit.
+    /// The relay stops when the pool is closed.
+    pub fn listen_for_changes(&self) -> tokio::task::JoinHandle<()> {
+        self.change_feed.clone().listen(self.pool.clone())
+    }
+
+    /// Refuse operations for an unknown, suspended or otherwise inactive tenant
+    async fn check_tenant(&self, context: &DatabaseContext) -> Result<(), MultiTenantError> {
+        self.tenant_gate.check(context.tenant_id.as_deref(), &context.permissions).await
+    }
+
+    /// Store `tenant_id`'s status, then enforce it from the next operation on
+    pub async fn set_tenant_status(&self, tenant_id: &str, status: TenantStatus) -> Result<(), DatabaseError> {
+        sqlx::query(
+            "INSERT INTO tenant_statuses (tenant_id, status, updated_at) VALUES ($1, $2, now())
+             ON CONFLICT (tenant_id) DO UPDATE SET status = EXCLUDED.status, updated_at = EXCLUDED.updated_at",
+        )
+        .bind(tenant_id)
+        .bind(status.as_str())
+        .execute(&self.pool)
+        .await?;
+        self.tenant_gate.set_status(tenant_id, status).await;
+        Ok(())
+    }
+
+    /// Stop admitting `tenant_id` and drop its stored status, e.g. after its provisioning failed
+    pub async fn forget_tenant_status(&self, tenant_id: &str) -> Result<(), DatabaseError> {
+        self.tenant_gate.forget(tenant_id).await;
+        sqlx::query("DELETE FROM tenant_statuses WHERE tenant_id = $1")
+            .bind(tenant_id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    /// Restore the gate from stored statuses; if they can't be read, every tenant stays refused
+    async fn load_tenant_statuses(&self) {
+        let rows: Result<Vec<(String, String)>, sqlx::Error> =
+            sqlx::query_as("SELECT tenant_id, status FROM tenant_statuses")
+                .fetch_all(&self.pool)
+                .await;
+        match rows {
+            Ok(rows) => {
+                let statuses = rows.into_iter().filter_map(|(tenant_id, status)| match TenantStatus::parse(&status) {
+                    Some(status) => Some((tenant_id, status)),
+                    None => {
+                        crate::security_log!(warn, tenant_id = %tenant_id, status = %status, "Unknown stored tenant status; tenant refused");
+                        None
+                    }
+                });
+                self.tenant_gate.load(statuses).await;
+            }
+            Err(e) => tracing::error!("Could not read tenant statuses, refusing all tenant operations: {}", e),
+        }
+    }
+
+    /// Entities flagged at creation because their classification rules conflicted, oldest first
+    ///
+    /// At most `MAX_PENDING_CLASSIFICATION_REVIEWS` are kept.
+    pub async fn pending_classification_reviews(&self) -> Vec<ClassificationReview> {
+        self.classification_reviews.lock().await.iter().cloned().collect()
+    }
+
+    /// Queue `review`, dropping the oldest once the queue is full
+    async fn flag_for_review(&self, review: ClassificationReview) {
+        let mut reviews = self.classification_reviews.lock().await;
+        if reviews.len() >= MAX_PENDING_CLASSIFICATION_REVIEWS {
+            if let Some(dropped) = reviews.pop_front() {
+                tracing::warn!("Classification review queue full; dropped review of entity {}", dropped.entity_id);
+            }
+        }
+        reviews.push_back(review);
+    }
+
+    /// Pool settings from config
+    ///
+    /// `test_before_acquire` pings a connection before handing it out, so connections
+    /// severed by a Postgres restart are discarded and reopened instead of surfacing errors.
+    fn pool_options(config: &DbConfig) -> PgPoolOptions {
+        PgPoolOptions::new()
+            .max_connections(config.max_connections)
+            .min_connections(config.min_connections)
+            .acquire_timeout(Duration::from_secs(config.acquire_timeout_secs))
+            .idle_timeout(config.idle_timeout_secs.map(Duration::from_secs))
+            .test_before_acquire(true)
+    }
+
+    /// Parse the URL and apply the configured TLS mode
+    fn connect_options(config: &DbConfig) -> Result<PgConnectOptions, DatabaseError> {
+        let options: PgConnectOptions = config.url.parse()?;
+        Ok(match config.tls_mode {
+            Some(mode) => options.ssl_mode(mode.into()),
+            None => options,
         })
     }
 
+    /// Stop handing out connections and close the pool once checked-out ones are returned
+    pub async fn close(&self) {
+        self.pool.close().await;
+    }
+
+    /// Probe the database with `SELECT 1` and report pool usage
+    ///
+    /// Never blocks longer than `HEALTH_CHECK_TIMEOUT`, even if the server is unreachable.
+    pub async fn health_check(&self) -> DbHealth {
+        let started = Instant::now();
+        let probe = tokio::time::timeout(
+            HEALTH_CHECK_TIMEOUT,
+            sqlx::query("SELECT 1").execute(&self.pool),
+        ).await;
+
+        let error = match probe {
+            Ok(Ok(_)) => None,
+            Ok(Err(e)) => Some(e.to_string()),
+            Err(_) => Some(format!("Health check timed out after {}ms", HEALTH_CHECK_TIMEOUT.as_millis())),
+        };
+
+        let pool_size = self.pool.size();
+        let idle_connections = self.pool.num_idle() as u32;
+
+        DbHealth {
+            healthy: error.is_none(),
+            latency_ms: started.elapsed().as_millis() as u64,
+            pool_size,
+            idle_connections,
+            in_use_connections: pool_size.saturating_sub(idle_connections),
+            error,
+            checked_at: Utc::now(),
+        }
+    }
+
+    /// Share of the pool's maximum connections currently checked out
+    pub fn pool_saturation(&self) -> f64 {
+        let in_use = self.pool.size().saturating_sub(self.pool.num_idle() as u32);
+        in_use as f64 / self.pool.options().get_max_connections().max(1) as f64
+    }
+
     /// Execute an ad-hoc compliance query used by the compliance dashboard.
     /// Minimal placeholder implementation returning an empty result.
     pub async fn execute_compliance_query(
@@ -128,59 +838,41 @@ impl DatabaseManager {
         entity_type: &str,
         data: serde_json::Value,
         context: &DatabaseContext,
-    ) -> Result<SecureEntity, sqlx::Error> {
+    ) -> Result<SecureEntity, DatabaseError> {
+        self.check_tenant(context).await?;
         let mut tx = self.pool.begin().await?;
+        let entity = self.create_entity_in_transaction(&mut tx, Uuid::new_v4(), entity_type, data, None, context).await?;
+        tx.commit().await?;
         
-        let entity_id = Uuid::new_v4();
-        let now = Utc::now();
-        
-        let entity = SecureEntity {
-            id: entity_id,
-            entity_type: entity_type.to_string(),
-            data,
-            created_at: now,
-            updated_at: now,
-            created_by: context.user_id.clone(),
-            updated_by: context.user_id.clone(),
-            classification: context.security_label.level.clone(),
-            compartments: context.security_label.compartments.clone(),
-            version: 1,
-            tenant_id: context.tenant_id.clone(),
-        };
-
-        // Insert into main entities table
-        sqlx::query!(
-            r#"
-            INSERT INTO entities (
-                id, entity_type, data, created_at, updated_at, 
-                created_by, updated_by, classification, compartments, 
-                version, tenant_id
-            )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
-            "#,
-            entity.id,
-            entity.entity_type,
-            entity.data,
-            entity.created_at,
-            entity.updated_at,
-            entity.created_by,
-            entity.updated_by,
-            entity.classification.to_string(),
-            &entity.compartments,
-            entity.version,
-            entity.tenant_id
-        )
-        .execute(&mut *tx)
-        .await?;
-
-        // Handle polyinstantiation if enabled
-        if self.enable_polyinstantiation {
-            self.create_polyinstantiation_entry(&mut tx, &entity, context).await?;
-        }
+        self.open_entity(entity).await
+    }
 
+    /// Create entity unless this creator already created one under `idempotency_key`
+    ///
+    /// A retried create returns the entity stored by the first attempt instead of a duplicate.
+    /// The key is claimed in the same transaction as the insert, and its unique constraint
+    /// makes concurrent attempts with one key wait on each other, so only one row is written.
+    /// `classification` can raise the new entity's label above what policy assigns. Every
+    /// attempt, replayed or not, is chained into the forensic log with the returned envelope.
+    pub async fn create_entity_idempotent(
+        &self,
+        entity_type: &str,
+        data: serde_json::Value,
+        idempotency_key: &str,
+        classification: Option<&ClassificationLevel>,
+        context: &DatabaseContext,
+    ) -> Result<(SecureEntity, ForensicEnvelope), DatabaseError> {
+        self.check_tenant(context).await?;
+        let mut tx = self.pool.begin().await?;
+        let (entity, replayed) = self
+            .create_keyed_entity_in_transaction(&mut tx, entity_type, data, idempotency_key, classification, context)
+            .await?;
+        let mut envelope = keyed_create_envelope(&entity, idempotency_key, replayed, context);
+        let link = self.insert_chained_envelopes(&mut tx, [&mut envelope]).await?;
         tx.commit().await?;
-        
-        Ok(entity)
+        link.commit();
+
+        Ok((self.open_entity(entity).await?, envelope))
     }
 
     /// Read entity with MAC enforcement
@@ -188,12 +880,13 @@ impl DatabaseManager {
         &self,
         entity_id: Uuid,
         context: &DatabaseContext,
-    ) -> Result<Option<SecureEntity>, sqlx::Error> {
+    ) -> Result<Option<SecureEntity>, DatabaseError> {
+        self.check_tenant(context).await?;
         // Base query for entity
         let mut query_builder = sqlx::QueryBuilder::new(
             "SELECT id, entity_type, data, created_at, updated_at, 
              created_by, updated_by, classification, compartments, 
-             version, tenant_id FROM entities WHERE id = "
+             version, tenant_id, deleted_at, deleted_by FROM entities WHERE id = "
         );
         query_builder.push_bind(entity_id);
 
@@ -204,8 +897,12 @@ impl DatabaseManager {
             .build_query_as::<SecureEntity>()
             .fetch_optional(&self.pool)
             .await?;
+        let Some(entity) = result else {
+            return Ok(None);
+        };
+        self.mac_engine.record_access(entity.id, context.session_id).await;
 
-        Ok(result)
+        Ok(Some(self.open_entity(entity).await?))
     }
 
     /// Update entity with version control and MAC enforcement
@@ -214,132 +911,465 @@ impl DatabaseManager {
         entity_id: Uuid,
         updates: serde_json::Value,
         context: &DatabaseContext,
-    ) -> Result<Option<SecureEntity>, sqlx::Error> {
+    ) -> Result<Option<SecureEntity>, DatabaseError> {
+        self.check_tenant(context).await?;
         let mut tx = self.pool.begin().await?;
-
-        // First, check if user can read the entity (No Read Up)
-        let existing = self.read_entity_in_transaction(&mut tx, entity_id, context).await?;
-        let existing = match existing {
-            Some(entity) => entity,
-            None => return Ok(None), // Entity doesn't exist or access denied
+        let Some(updated) = self.update_entity_in_transaction(&mut tx, entity_id, updates, context).await? else {
+            return Ok(None);
         };
+        tx.commit().await?;
+        self.mac_engine.record_access(entity_id, context.session_id).await;
+        
+        Ok(Some(self.open_entity(updated).await?))
+    }
 
-        // Check write permissions (No Write Down for the update operation)
-        let target_classification = context.security_label.level.clone();
-        if !self.can_write_classification(&existing.classification, &target_classification) {
-            return Ok(None); // Write access denied
-        }
-
-        // Perform optimistic locking check
-        let new_version = existing.version + 1;
-        let now = Utc::now();
-
-        // Merge the existing data with updates
-        let mut updated_data = existing.data.clone();
-        if let (serde_json::Value::Object(ref mut existing_map), serde_json::Value::Object(updates_map)) = 
-            (&mut updated_data, updates) {
-            for (key, value) in updates_map {
-                existing_map.insert(key, value);
-            }
-        }
-
-        let updated_entity = SecureEntity {
-            id: existing.id,
-            entity_type: existing.entity_type.clone(),
-            data: updated_data,
-            created_at: existing.created_at,
-            updated_at: now,
-            created_by: existing.created_by.clone(),
-            updated_by: context.user_id.clone(),
-            classification: existing.classification.clone(),
-            compartments: existing.compartments.clone(),
-            version: new_version,
-            tenant_id: existing.tenant_id.clone(),
+    /// Store a merge of concurrent edits with MAC enforcement; see `EntityStore::merge_entity`
+    pub async fn merge_entity(
+        &self,
+        entity_id: Uuid,
+        expected_version: i64,
+        data: serde_json::Value,
+        label: &SecurityLabel,
+        context: &DatabaseContext,
+    ) -> Result<Option<SecureEntity>, DatabaseError> {
+        self.check_tenant(context).await?;
+        let mut tx = self.pool.begin().await?;
+        let Some(existing) = self.read_entity_in_transaction(&mut tx, entity_id, context, false).await?
+            .filter(|entity| entity.version == expected_version)
+        else {
+            return Ok(None);
+        };
+        let Some(mut merged) = existing.merged(data, label, context, Utc::now())? else {
+            return Ok(None);
         };
+        merged.data = self.subject_vault.seal_fields(self, &merged.entity_type, merged.data).await?;
 
-        // Update the entity with optimistic locking
         let updated_rows = sqlx::query!(
             r#"
-            UPDATE entities 
-            SET data = $2, updated_at = $3, updated_by = $4, version = $5
-            WHERE id = $1 AND version = $6
+            UPDATE entities
+            SET data = $2, classification = $3, compartments = $4, updated_at = $5, updated_by = $6, version = $7
+            WHERE id = $1 AND version = $8 AND deleted_at IS NULL
             "#,
             entity_id,
-            updated_entity.data,
-            updated_entity.updated_at,
-            updated_entity.updated_by,
-            updated_entity.version,
-            existing.version // Optimistic lock check
+            merged.data,
+            merged.classification.key(),
+            &merged.compartments,
+            merged.updated_at,
+            merged.updated_by,
+            merged.version,
+            expected_version
         )
         .execute(&mut *tx)
         .await?;
-
         if updated_rows.rows_affected() == 0 {
-            // Either entity was updated by someone else or doesn't exist
             return Ok(None);
         }
-
-        // Update polyinstantiation if enabled
         if self.enable_polyinstantiation {
-            self.update_polyinstantiation_entry(&mut tx, &updated_entity, context).await?;
+            self.update_polyinstantiation_entry(&mut tx, &merged, context).await?;
         }
-
         tx.commit().await?;
-        
-        Ok(Some(updated_entity))
+        self.mac_engine.record_access(entity_id, context.session_id).await;
+
+        Ok(Some(self.open_entity(merged).await?))
     }
 
-    /// Delete entity with MAC enforcement
+    /// Soft-delete entity with MAC enforcement
+    ///
+    /// The row is kept with `deleted_at`/`deleted_by` set and hidden from reads until restored;
+    /// use `erase_entity` to remove it for good.
     pub async fn delete_entity(
         &self,
         entity_id: Uuid,
         context: &DatabaseContext,
-    ) -> Result<bool, sqlx::Error> {
+    ) -> Result<bool, DatabaseError> {
+        self.check_tenant(context).await?;
         let mut tx = self.pool.begin().await?;
+        let mut deleted = self.delete_entity_in_transaction(&mut tx, entity_id, context).await?;
+        if let Some((_, envelope)) = deleted.as_mut() {
+            let link = self.insert_chained_envelopes(&mut tx, [envelope]).await?;
+            tx.commit().await?;
+            link.commit();
+        }
+        
+        Ok(deleted.is_some())
+    }
 
-        // Check if entity exists and user can access it
-        let existing = self.read_entity_in_transaction(&mut tx, entity_id, context).await?;
-        let existing = match existing {
-            Some(entity) => entity,
-            None => return Ok(false), // Entity doesn't exist or access denied
-        };
+    /// Undo a soft delete
+    pub async fn restore_entity(
+        &self,
+        entity_id: Uuid,
+        context: &DatabaseContext,
+    ) -> Result<SecureEntity, DatabaseError> {
+        self.check_tenant(context).await?;
+        let mut tx = self.pool.begin().await?;
+        let (_, restored, mut envelope) = self
+            .change_lifecycle_in_transaction(&mut tx, entity_id, EntityLifecycle::Restore, context)
+            .await?;
+        let link = self.insert_chained_envelopes(&mut tx, [&mut envelope]).await?;
+        tx.commit().await?;
+        link.commit();
+
+        restored.ok_or(DatabaseError::LifecycleConflict(entity_id))
+    }
+
+    /// Permanently remove an entity, live or soft-deleted (e.g. for GDPR erasure)
+    ///
+    /// Requires `ERASE_PERMISSION` on top of the usual write access, and fails while the
+    /// entity or its tenant is under legal hold.
+    pub async fn erase_entity(
+        &self,
+        entity_id: Uuid,
+        context: &DatabaseContext,
+    ) -> Result<(), DatabaseError> {
+        self.check_tenant(context).await?;
+        let mut tx = self.pool.begin().await?;
+        let holds = Self::fetch_legal_holds(&mut *tx).await?;
+        let row = sqlx::query("SELECT tenant_id, legal_hold FROM entities WHERE id = $1")
+            .bind(entity_id)
+            .fetch_optional(&mut *tx)
+            .await?;
+        if let Some(row) = row {
+            if row.try_get::<bool, _>("legal_hold")? {
+                return Err(DatabaseError::LegalHold(format!("entity {} is flagged for legal hold", entity_id)));
+            }
+            let tenant_id: Option<String> = row.try_get("tenant_id")?;
+            holds.check_entity_erasure(entity_id, tenant_id.as_deref())?;
+        }
+        let (_, _, mut envelope) = self
+            .change_lifecycle_in_transaction(&mut tx, entity_id, EntityLifecycle::HardDelete, context)
+            .await?;
+        let link = self.insert_chained_envelopes(&mut tx, [&mut envelope]).await?;
+        tx.commit().await?;
+        link.commit();
+
+        Ok(())
+    }
+
+    /// Key that personal data of `subject_id` is sealed under, created on first use
+    pub async fn subject_key(&self, subject_id: &str) -> Result<SubjectKey, DatabaseError> {
+        self.subject_vault.subject_key(self, subject_id).await
+    }
+
+    /// Open a value sealed by a subject key; `None` once the subject has been erased
+    pub async fn open_subject_value(&self, sealed: &SealedValue) -> Result<Option<serde_json::Value>, DatabaseError> {
+        self.subject_vault.open(self, sealed).await
+    }
+
+    /// Wrap subject keys stored in the clear before `013_wrapped_subject_keys.sql`
+    ///
+    /// Each key is wrapped and its raw material cleared in one statement, so a key is
+    /// never left without a usable copy.
+    async fn wrap_legacy_subject_keys(&self) -> Result<(), DatabaseError> {
+        let rows = sqlx::query("SELECT subject_id, key_material FROM subject_keys WHERE wrapped_key IS NULL")
+            .fetch_all(&self.pool)
+            .await?;
+        for row in &rows {
+            let subject_id: String = row.try_get("subject_id")?;
+            let material: zeroize::Zeroizing<Vec<u8>> = zeroize::Zeroizing::new(row.try_get("key_material")?);
+            let wrapped_key = self.subject_vault.wrap_key_material(&material).await?;
+            sqlx::query(
+                "UPDATE subject_keys SET wrapped_key = $2, key_material = NULL
+                 WHERE subject_id = $1 AND wrapped_key IS NULL",
+            )
+            .bind(&subject_id)
+            .bind(wrapped_key)
+            .execute(&self.pool)
+            .await?;
+        }
+        if !rows.is_empty() {
+            tracing::info!("Wrapped {} legacy subject key(s)", rows.len());
+        }
+        Ok(())
+    }
 
-        // Check write permissions for deletion
-        if !self.can_write_classification(&existing.classification, &context.security_label.level) {
-            return Ok(false); // Delete access denied
+    /// `entity` with its subjects' personal fields opened; erased subjects' fields read as `null`
+    async fn open_entity(&self, mut entity: SecureEntity) -> Result<SecureEntity, DatabaseError> {
+        entity.data = self.subject_vault.open_fields(self, entity.data).await?;
+        Ok(entity)
+    }
+
+    async fn open_entities(&self, entities: Vec<SecureEntity>) -> Result<Vec<SecureEntity>, DatabaseError> {
+        let mut opened = Vec::with_capacity(entities.len());
+        for entity in entities {
+            opened.push(self.open_entity(entity).await?);
         }
+        Ok(opened)
+    }
+
+    /// Erase a data subject by crypto-shredding: destroy their keys, keep every row
+    ///
+    /// Sealed personal data, including copies in forensic envelopes, becomes unrecoverable,
+    /// while the hash chain (computed over the ciphertext) still verifies. Requires
+    /// `ERASE_PERMISSION` and fails while the subject is under legal hold. The erasure is
+    /// attested in `subject_erasures` and the forensic log.
+    pub async fn erase_subject(
+        &self,
+        subject_id: &str,
+        context: &DatabaseContext,
+    ) -> Result<ErasureAttestation, DatabaseError> {
+        authorize_subject_erasure(context)?;
+
+        let mut tx = self.pool.begin().await?;
+        Self::fetch_legal_holds(&mut *tx).await?.check_subject_erasure(subject_id)?;
+        let keys_destroyed: Vec<Uuid> = sqlx::query_scalar(
+            "DELETE FROM subject_keys WHERE subject_id = $1 RETURNING key_id",
+        )
+        .bind(subject_id)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let mut envelope = ForensicEnvelope::new(
+            Uuid::new_v4(),
+            "subject.erased",
+            &context.user_id,
+            context.session_id,
+            ClassificationLevel::Confidential,
+            "erase_subject",
+        )
+        .with_resource(&format!("subject:{}", subject_id))
+        .with_metadata(serde_json::json!({ "keys_destroyed": keys_destroyed }));
+        // Linked (and so stamped) first, so the attestation carries the stored timestamp
+        let link = self.insert_chained_envelopes(&mut tx, [&mut envelope]).await?;
+        let attestation = ErasureAttestation {
+            subject_id: subject_id.to_string(),
+            keys_destroyed,
+            erased_by: context.user_id.clone(),
+            erased_at: envelope.timestamp,
+            envelope_id: envelope.envelope_id,
+        };
 
-        // Delete from main table
-        let deleted_rows = sqlx::query!(
-            "DELETE FROM entities WHERE id = $1",
-            entity_id
+        sqlx::query(
+            "INSERT INTO subject_erasures (subject_id, erased_by, erased_at, attestation) VALUES ($1, $2, $3, $4)",
         )
+        .bind(subject_id)
+        .bind(&attestation.erased_by)
+        .bind(attestation.erased_at)
+        .bind(serde_json::to_value(&attestation).unwrap_or_default())
         .execute(&mut *tx)
         .await?;
+        tx.commit().await?;
+        link.commit();
 
-        // Clean up polyinstantiation if enabled
-        if self.enable_polyinstantiation {
-            self.delete_polyinstantiation_entry(&mut tx, entity_id, context).await?;
+        tracing::warn!(
+            "Subject {} erased by {}: {} key(s) destroyed",
+            subject_id, context.user_id, attestation.keys_destroyed.len()
+        );
+        Ok(attestation)
+    }
+
+    /// Legal holds currently in force
+    pub async fn legal_holds(&self) -> Result<LegalHolds, DatabaseError> {
+        Self::fetch_legal_holds(&self.pool).await
+    }
+
+    /// Freeze a subject, entity or tenant against retention and erasure
+    ///
+    /// Requires `LEGAL_HOLD_PERMISSION`; recorded in the forensic log.
+    pub async fn place_legal_hold(
+        &self,
+        scope: LegalHoldScope,
+        reason: &str,
+        expires_at: Option<DateTime<Utc>>,
+        context: &DatabaseContext,
+    ) -> Result<LegalHold, DatabaseError> {
+        authorize_legal_hold(context)?;
+
+        let hold = LegalHold {
+            hold_id: Uuid::new_v4(),
+            scope,
+            reason: reason.to_string(),
+            placed_by: context.user_id.clone(),
+            placed_at: Utc::now(),
+            expires_at,
+        };
+        let (scope, target) = hold.scope.to_columns();
+
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(
+            "INSERT INTO legal_holds (hold_id, scope, target, reason, placed_by, placed_at, expires_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)",
+        )
+        .bind(hold.hold_id)
+        .bind(scope)
+        .bind(&target)
+        .bind(&hold.reason)
+        .bind(&hold.placed_by)
+        .bind(hold.placed_at)
+        .bind(hold.expires_at)
+        .execute(&mut *tx)
+        .await?;
+        let link = self.insert_chained_envelopes(&mut tx, [&mut legal_hold_envelope("legal_hold.placed", &hold, context)]).await?;
+        tx.commit().await?;
+        link.commit();
+
+        crate::security_log!(warn, user_id = %context.user_id, hold_id = %hold.hold_id, "Legal hold placed on {} {}", scope, target);
+        Ok(hold)
+    }
+
+    /// Lift a legal hold; requires `LEGAL_HOLD_PERMISSION` and is recorded in the forensic log
+    pub async fn release_legal_hold(
+        &self,
+        hold_id: Uuid,
+        context: &DatabaseContext,
+    ) -> Result<LegalHold, DatabaseError> {
+        authorize_legal_hold(context)?;
+
+        let mut tx = self.pool.begin().await?;
+        let row = sqlx::query(
+            "UPDATE legal_holds SET released_at = now(), released_by = $2
+             WHERE hold_id = $1 AND released_at IS NULL
+             RETURNING hold_id, scope, target, reason, placed_by, placed_at, expires_at",
+        )
+        .bind(hold_id)
+        .bind(&context.user_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| DatabaseError::LegalHold(format!("no active legal hold {}", hold_id)))?;
+        let hold = legal_hold_from_row(&row)?;
+        let link = self.insert_chained_envelopes(&mut tx, [&mut legal_hold_envelope("legal_hold.released", &hold, context)]).await?;
+        tx.commit().await?;
+        link.commit();
+
+        crate::security_log!(warn, user_id = %context.user_id, hold_id = %hold_id, "Legal hold released");
+        Ok(hold)
+    }
+
+    async fn fetch_legal_holds<'e, E>(executor: E) -> Result<LegalHolds, DatabaseError>
+    where
+        E: sqlx::Executor<'e, Database = Postgres>,
+    {
+        let rows = sqlx::query(
+            "SELECT hold_id, scope, target, reason, placed_by, placed_at, expires_at
+             FROM legal_holds
+             WHERE released_at IS NULL AND (expires_at IS NULL OR expires_at > now())",
+        )
+        .fetch_all(executor)
+        .await?;
+
+        let holds = rows.iter().map(legal_hold_from_row).collect::<Result<Vec<_>, _>>()?;
+        Ok(LegalHolds::active(holds, Utc::now()))
+    }
+
+    /// Run a batch of entity operations in one transaction
+    ///
+    /// Each item runs under its own savepoint with MAC enforced per item, so a denied or
+    /// failed item is rolled back alone and reported at its index. With `all_or_nothing`,
+    /// the first failure rolls back the whole batch and the remaining items are skipped.
+    pub async fn execute_batch(
+        &self,
+        operations: Vec<BatchEntityOperation>,
+        context: &DatabaseContext,
+        all_or_nothing: bool,
+    ) -> Result<Vec<BatchItemResult>, DatabaseError> {
+        self.check_tenant(context).await?;
+        let total = operations.len();
+        let mut results = Vec::with_capacity(total);
+        // Envelopes of items that succeeded, chained in once every item has run
+        let mut envelopes = Vec::new();
+        let mut tx = self.pool.begin().await?;
+
+        for (index, operation) in operations.into_iter().enumerate() {
+            let mut savepoint = (&mut tx).begin().await?;
+
+            let outcome = match operation {
+                BatchEntityOperation::Create { entity_type, data, idempotency_key: None } => self
+                    .create_entity_in_transaction(&mut savepoint, Uuid::new_v4(), &entity_type, data, None, context)
+                    .await
+                    .map(|entity| Some((None, entity, None))),
+                BatchEntityOperation::Create { entity_type, data, idempotency_key: Some(key) } => self
+                    .create_keyed_entity_in_transaction(&mut savepoint, &entity_type, data, &key, None, context)
+                    .await
+                    .map(|(entity, _)| Some((None, entity, None))),
+                BatchEntityOperation::Update { entity_id, updates } => {
+                    match self.read_entity_in_transaction(&mut savepoint, entity_id, context, false).await {
+                        Ok(previous) => self
+                            .update_entity_in_transaction(&mut savepoint, entity_id, updates, context)
+                            .await
+                            .map(|updated| updated.map(|entity| (previous, entity, None))),
+                        Err(e) => Err(e.into()),
+                    }
+                }
+                BatchEntityOperation::Delete { entity_id } => self
+                    .delete_entity_in_transaction(&mut savepoint, entity_id, context)
+                    .await
+                    .map(|deleted| deleted.map(|(entity, envelope)| (Some(entity.clone()), entity, Some(envelope))))
+                    .map_err(DatabaseError::from),
+            };
+
+            let error = match outcome {
+                Ok(Some((previous, entity, envelope))) => {
+                    savepoint.commit().await?;
+                    envelopes.extend(envelope);
+                    results.push(BatchItemResult {
+                        index,
+                        success: true,
+                        entity: Some(entity),
+                        previous,
+                        error: None,
+                    });
+                    continue;
+                }
+                Ok(None) => "Entity not found or access denied".to_string(),
+                Err(e) => e.to_string(),
+            };
+
+            savepoint.rollback().await?;
+            results.push(BatchItemResult {
+                index,
+                success: false,
+                entity: None,
+                previous: None,
+                error: Some(error),
+            });
+
+            if all_or_nothing {
+                tx.rollback().await?;
+
+                // Earlier items are undone with the transaction; later ones never ran
+                for result in results.iter_mut().filter(|result| result.success) {
+                    result.success = false;
+                    result.entity = None;
+                    result.previous = None;
+                    result.error = Some(format!("Rolled back: item {} failed", index));
+                }
+                results.extend((index + 1..total).map(|skipped| BatchItemResult {
+                    index: skipped,
+                    success: false,
+                    entity: None,
+                    previous: None,
+                    error: Some(format!("Skipped: item {} failed", index)),
+                }));
+                return Ok(results);
+            }
         }
 
+        let link = self.insert_chained_envelopes(&mut tx, &mut envelopes).await?;
         tx.commit().await?;
-        
-        Ok(deleted_rows.rows_affected() > 0)
+        link.commit();
+        Ok(results)
     }
 
     /// Query entities with automatic security filtering
+    ///
+    /// Offset pagination is kept for existing callers; prefer `query_entities_after` for
+    /// large datasets, since large offsets are slow and shift under concurrent inserts.
     pub async fn query_entities(
         &self,
         entity_type: Option<&str>,
-        filters: HashMap<String, serde_json::Value>,
+        filter: Option<&QueryFilter>,
         context: &DatabaseContext,
         limit: Option<i64>,
         offset: Option<i64>,
-    ) -> Result<SecureQueryResult, sqlx::Error> {
+    ) -> Result<SecureQueryResult, DatabaseError> {
+        self.check_tenant(context).await?;
+        if let Some(filter) = filter {
+            filter.validate()?;
+        }
+
         let mut query_builder = sqlx::QueryBuilder::new(
             "SELECT id, entity_type, data, created_at, updated_at, 
              created_by, updated_by, classification, compartments, 
-             version, tenant_id FROM entities WHERE 1=1"
+             version, tenant_id, deleted_at, deleted_by FROM entities WHERE 1=1"
         );
 
         // Add entity type filter
@@ -348,18 +1378,17 @@ impl DatabaseManager {
             query_builder.push_bind(et);
         }
 
-        // Add custom filters
-        for (key, value) in filters {
-            query_builder.push(" AND data->>");
-            query_builder.push_bind(key);
-            query_builder.push(" = ");
-            query_builder.push_bind(value.as_str().unwrap_or(""));
+        // Add caller's filter; parenthesized, so the security filter below still applies to all of it
+        if let Some(filter) = filter {
+            query_builder.push(" AND ");
+            filter.push_sql(&mut query_builder);
         }
 
         // Add security filtering
         self.add_security_filter(&mut query_builder, context);
 
         // Add pagination
+        query_builder.push(" ORDER BY created_at, id");
         if let Some(limit) = limit {
             query_builder.push(" LIMIT ");
             query_builder.push_bind(limit);
@@ -376,117 +1405,973 @@ impl DatabaseManager {
 
         // Get total count (this is simplified - in production you'd want separate count queries)
         let filtered_count = entities.len() as i64;
+        self.mac_engine.record_accesses(entities.iter().map(|entity| entity.id), context.session_id).await;
         
         Ok(SecureQueryResult {
-            entities,
+            entities: self.open_entities(entities).await?,
             total_count: filtered_count, // Simplified
             filtered_count,
             access_denied_count: 0, // Would require more complex tracking
+            next_cursor: None,
         })
     }
 
-    /// Store forensic envelope in database
-    pub async fn store_forensic_envelope(
+    /// Query entities with keyset pagination on `(created_at, id)`
+    ///
+    /// Pass the previous page's `next_cursor` to continue. Rows inserted behind the cursor
+    /// never shift later pages, so paging is stable under concurrent writes.
+    pub async fn query_entities_after(
         &self,
-        envelope: &ForensicEnvelope,
-    ) -> Result<(), sqlx::Error> {
-        sqlx::query!(
-            r#"
-            INSERT INTO forensic_log (
-                envelope_id, operation_id, event_type, timestamp, 
-                user_id, session_id, classification, action, 
-                resource, before_state, after_state, metadata, 
-                audit_trail_hash
-            )
-            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
-            "#,
-            envelope.envelope_id,
-            envelope.operation_id,
-            envelope.event_type,
-            envelope.timestamp,
-            envelope.user_id,
-            envelope.session_id,
-            envelope.classification.to_string(),
-            envelope.action,
-            envelope.resource,
-            envelope.before_state,
-            envelope.after_state,
-            envelope.metadata,
-            envelope.audit_trail_hash
-        )
-        .execute(&self.pool)
-        .await?;
-
-        Ok(())
-    }
+        entity_type: Option<&str>,
+        filter: Option<&QueryFilter>,
+        context: &DatabaseContext,
+        cursor: Option<&str>,
+        limit: i64,
+    ) -> Result<SecureQueryResult, DatabaseError> {
+        self.check_tenant(context).await?;
+        if let Some(filter) = filter {
+            filter.validate()?;
+        }
+        let cursor = cursor
+            .map(|token| EntityCursor::decode(token)
+                .ok_or_else(|| DatabaseError::InvalidCursor(token.to_string())))
+            .transpose()?;
 
-    // Private helper methods
+        let mut query_builder = sqlx::QueryBuilder::new(
+            "SELECT id, entity_type, data, created_at, updated_at, 
+             created_by, updated_by, classification, compartments, 
+             version, tenant_id, deleted_at, deleted_by FROM entities WHERE 1=1"
+        );
 
-    /// Add security filtering to query based on user's clearance
-    fn add_security_filter(
-        &self,
-        query_builder: &mut sqlx::QueryBuilder<Postgres>,
-        context: &DatabaseContext,
-    ) {
-        let user_level_rank = context.security_label.level.rank();
-        
-        // No Read Up: User can only read data at or below their clearance level
-        query_builder.push(" AND (");
-        
-        // Add classification level check
-        for level in &[
-            ClassificationLevel::Unclassified,
-            ClassificationLevel::Internal,
-            ClassificationLevel::Confidential,
-            ClassificationLevel::Secret,
-            ClassificationLevel::NatoSecret,
-        ] {
-            if level.rank() <= user_level_rank {
-                query_builder.push("classification = ");
-                query_builder.push_bind(level.to_string());
-                query_builder.push(" OR ");
-            }
+        if let Some(et) = entity_type {
+            query_builder.push(" AND entity_type = ");
+            query_builder.push_bind(et);
         }
-        query_builder.push("FALSE)"); // Close the OR chain
-        
-        // Add compartment check if user has compartments
-        if !context.security_label.compartments.is_empty() {
-            query_builder.push(" AND (");
-            query_builder.push("compartments = '{}' OR "); // No compartments required
-            
-            // User can access data in their compartments
-            for compartment in &context.security_label.compartments {
-                query_builder.push("(");
-                query_builder.push_bind(compartment);
-                query_builder.push(" = ANY(compartments)) OR ");
-            }
-            query_builder.push("FALSE)"); // Close compartment check
+
+        if let Some(filter) = filter {
+            query_builder.push(" AND ");
+            filter.push_sql(&mut query_builder);
         }
 
-        // Add tenant isolation if enabled
-        if let Some(tenant_id) = &context.tenant_id {
-            query_builder.push(" AND (tenant_id IS NULL OR tenant_id = ");
-            query_builder.push_bind(tenant_id);
+        if let Some(cursor) = &cursor {
+            query_builder.push(" AND (created_at, id) > (");
+            query_builder.push_bind(cursor.created_at);
+            query_builder.push(", ");
+            query_builder.push_bind(cursor.id);
             query_builder.push(")");
         }
-    }
 
-    /// Check if user can write to a classification level (No Write Down)
-    fn can_write_classification(
-        &self,
-        target_classification: &ClassificationLevel,
-        user_classification: &ClassificationLevel,
-    ) -> bool {
-        // No Write Down: User can write to their level or higher
-        user_classification.rank() <= target_classification.rank()
-    }
+        self.add_security_filter(&mut query_builder, context);
 
-    /// Read entity within a transaction
-    async fn read_entity_in_transaction(
+        // Fetch one extra row to learn whether another page exists
+        query_builder.push(" ORDER BY created_at, id LIMIT ");
+        query_builder.push_bind(limit + 1);
+
+        let entities = query_builder
+            .build_query_as::<SecureEntity>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        let (entities, next_cursor) = split_page(entities, limit);
+        let filtered_count = entities.len() as i64;
+        self.mac_engine.record_accesses(entities.iter().map(|entity| entity.id), context.session_id).await;
+
+        Ok(SecureQueryResult {
+            entities: self.open_entities(entities).await?,
+            total_count: filtered_count, // Simplified
+            filtered_count,
+            access_denied_count: 0,
+            next_cursor,
+        })
+    }
+
+    /// Full-text search over entity data, most relevant first
+    ///
+    /// Matches against the `search_tsv` column maintained by `006_entity_search.sql`; `query`
+    /// takes web-search syntax (`"exact phrase"`, `or`, `-excluded`). At most
+    /// `SEARCH_RESULT_LIMIT` entities are returned.
+    pub async fn search_entities(
+        &self,
+        query: &str,
+        context: &DatabaseContext,
+    ) -> Result<SecureQueryResult, DatabaseError> {
+        self.check_tenant(context).await?;
+        let query = query.trim();
+        if query.is_empty() {
+            return Ok(SecureQueryResult {
+                entities: Vec::new(),
+                total_count: 0,
+                filtered_count: 0,
+                access_denied_count: 0,
+                next_cursor: None,
+            });
+        }
+
+        let matches = self.search_query(query, context)
+            .build_query_as::<SecureEntity>()
+            .fetch_all(&self.pool)
+            .await?;
+
+        let (entities, access_denied_count) = self.readable_only(matches, context);
+        let filtered_count = entities.len() as i64;
+        self.mac_engine.record_accesses(entities.iter().map(|entity| entity.id), context.session_id).await;
+
+        Ok(SecureQueryResult {
+            entities: self.open_entities(entities).await?,
+            total_count: filtered_count + access_denied_count,
+            filtered_count,
+            access_denied_count,
+            next_cursor: None,
+        })
+    }
+
+    /// Relabel entities after a classification review, all or nothing
+    ///
+    /// Every entity must be readable by the operator, pass `authorize_reclassification` and
+    /// satisfy the MAC engine's tranquility policy; otherwise nothing changes. Each relabel is recorded in a forensic envelope holding the
+    /// old and new labels and the justification, committed with the update.
+    ///
+    /// Downgrades are refused here; they go through `DeclassificationWorkflow`, which needs a
+    /// second approver.
+    pub async fn reclassify_entities(
+        &self,
+        ids: &[Uuid],
+        new_label: SecurityLabel,
+        justification: String,
+        context: &DatabaseContext,
+    ) -> Result<Vec<SecureEntity>, DatabaseError> {
+        self.check_tenant(context).await?;
+        let mut tx = self.pool.begin().await?;
+        let (relabeled, mut envelopes) = self
+            .relabel_entities_in_transaction(&mut tx, ids, &new_label, &justification, context, None)
+            .await?;
+
+        let link = self.insert_chained_envelopes(&mut tx, &mut envelopes).await?;
+        tx.commit().await?;
+        link.commit();
+        Ok(relabeled)
+    }
+
+    /// `reclassify_entities` within `tx`, allowing downgrades only as part of an approved
+    /// `declassification`; returns the relabeled entities and the envelopes to store with them
+    async fn relabel_entities_in_transaction(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        ids: &[Uuid],
+        new_label: &SecurityLabel,
+        justification: &str,
+        context: &DatabaseContext,
+        declassification: Option<&DeclassificationRequest>,
+    ) -> Result<(Vec<SecureEntity>, Vec<ForensicEnvelope>), DatabaseError> {
+        if justification.trim().is_empty() {
+            return Err(DatabaseError::ReclassificationDenied("a justification is required".to_string()));
+        }
+
+        let operation_id = Uuid::new_v4();
+        let mut compartments: Vec<String> = new_label.compartments.iter().cloned().collect();
+        compartments.sort();
+        let mut relabeled = Vec::with_capacity(ids.len());
+        let mut envelopes = Vec::with_capacity(ids.len());
+
+        for &entity_id in ids {
+            let existing = self.read_entity_in_transaction(tx, entity_id, context, false).await?
+                .filter(|entity| self.can_read_entity(entity, context))
+                .ok_or_else(|| DatabaseError::ReclassificationDenied(
+                    format!("entity {} not found or not readable", entity_id)
+                ))?;
+            let current = SecurityLabel::new(existing.classification.clone(), existing.compartments.clone());
+            authorize_reclassification(&current, new_label, context)?;
+            if declassification.is_none() && !new_label.dominates(&current) {
+                return Err(DatabaseError::ReclassificationDenied(format!(
+                    "downgrade from {} to {} needs an approved declassification request",
+                    current.level, new_label.level
+                )));
+            }
+            self.mac_engine.check_tranquility(entity_id, &current, new_label, context.session_id).await
+                .map_err(|violation| DatabaseError::TranquilityViolation { entity_id, violation })?;
+
+            let entity = SecureEntity {
+                classification: new_label.level.clone(),
+                compartments: compartments.clone(),
+                updated_at: Utc::now(),
+                updated_by: context.user_id.clone(),
+                version: existing.version + 1,
+                ..existing.clone()
+            };
+
+            let updated_rows = sqlx::query!(
+                r#"
+                UPDATE entities
+                SET classification = $2, compartments = $3, updated_at = $4, updated_by = $5, version = $6
+                WHERE id = $1 AND version = $7
+                "#,
+                entity_id,
+                entity.classification.key(),
+                &entity.compartments,
+                entity.updated_at,
+                entity.updated_by,
+                entity.version,
+                existing.version
+            )
+            .execute(&mut **tx)
+            .await?;
+            if updated_rows.rows_affected() == 0 {
+                return Err(DatabaseError::ReclassificationConflict(entity_id));
+            }
+
+            if self.enable_polyinstantiation {
+                self.update_polyinstantiation_entry(tx, &entity, context).await?;
+            }
+
+            // Logged at the higher of the two labels so the record is no less protected
+            let envelope = ForensicEnvelope::new(
+                operation_id,
+                "reclassification",
+                &context.user_id,
+                context.session_id,
+                current.lub(new_label).level,
+                "reclassify",
+            )
+            .with_resource(&format!("entity:{}", entity_id))
+            .with_state_change(
+                Some(serde_json::json!({
+                    "classification": existing.classification,
+                    "compartments": existing.compartments,
+                })),
+                Some(serde_json::json!({
+                    "classification": entity.classification,
+                    "compartments": entity.compartments,
+                })),
+            )
+            .with_metadata(serde_json::json!({
+                "justification": justification,
+                "downgrade": !new_label.dominates(&current),
+                "declassification_request": declassification.map(|request| request.request_id),
+                "proposed_by": declassification.map(|request| &request.proposed_by),
+            }));
+            envelopes.push(envelope);
+
+            relabeled.push(entity);
+        }
+
+        Ok((relabeled, envelopes))
+    }
+
+    /// Link a forensic envelope into the chain and store it
+    pub async fn store_forensic_envelope(
+        &self,
+        envelope: &ForensicEnvelope,
+    ) -> Result<(), sqlx::Error> {
+        let mut conn = self.pool.acquire().await?;
+        let link = self.insert_chained_envelopes(&mut conn, [&mut envelope.clone()]).await?;
+        link.commit();
+        Ok(())
+    }
+
+    /// Store envelopes with one multi-row INSERT, so a batch is stored whole or not at all
+    pub async fn store_forensic_envelopes(
+        &self,
+        envelopes: &[ForensicEnvelope],
+    ) -> Result<(), sqlx::Error> {
+        if envelopes.is_empty() {
+            return Ok(());
+        }
+
+        let mut query = sqlx::QueryBuilder::<Postgres>::new(
+            r#"
+            INSERT INTO forensic_log (
+                envelope_id, operation_id, event_type, timestamp,
+                user_id, session_id, classification, action,
+                resource, before_state, after_state, metadata,
+                audit_trail_hash
+            )
+            "#,
+        );
+        query.push_values(envelopes, |mut row, envelope| {
+            row.push_bind(envelope.envelope_id)
+                .push_bind(envelope.operation_id)
+                .push_bind(&envelope.event_type)
+                .push_bind(envelope.timestamp)
+                .push_bind(&envelope.user_id)
+                .push_bind(envelope.session_id)
+                .push_bind(envelope.classification.key())
+                .push_bind(&envelope.action)
+                .push_bind(&envelope.resource)
+                .push_bind(&envelope.before_state)
+                .push_bind(&envelope.after_state)
+                .push_bind(&envelope.metadata)
+                .push_bind(&envelope.audit_trail_hash);
+        });
+        query.build().execute(&self.pool).await?;
+        Ok(())
+    }
+
+    /// Stream stored forensic envelopes with timestamps in `[from, to]`, oldest first, each with
+    /// the digest of its original content if retention has since cleared it
+    pub fn stream_forensic_envelopes(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> futures::stream::BoxStream<'_, Result<(ForensicEnvelope, Option<String>), sqlx::Error>> {
+        use futures::StreamExt;
+
+        sqlx::query(
+            r#"
+            SELECT envelope_id, operation_id, event_type, timestamp,
+                   user_id, session_id, classification, action,
+                   resource, before_state, after_state, metadata,
+                   audit_trail_hash, content_digest
+            FROM forensic_log
+            WHERE timestamp >= $1 AND timestamp <= $2
+            ORDER BY timestamp, envelope_id
+            "#,
+        )
+        .bind(from)
+        .bind(to)
+        .fetch(&self.pool)
+        .map(|row| {
+            let row = row?;
+            Ok((forensic_envelope_from_row(&row)?, row.try_get("content_digest")?))
+        })
+        .boxed()
+    }
+
+    /// Stored envelopes matching an audit search, in `(timestamp, envelope_id)` order; unset
+    /// bounds and an empty classification list don't filter
+    pub async fn search_forensic_envelopes(
+        &self,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        user_id: Option<&str>,
+        classifications: &[String],
+        limit: Option<u32>,
+        offset: Option<u32>,
+    ) -> Result<Vec<ForensicEnvelope>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT envelope_id, operation_id, event_type, timestamp,
+                   user_id, session_id, classification, action,
+                   resource, before_state, after_state, metadata,
+                   audit_trail_hash
+            FROM forensic_log
+            WHERE ($1::timestamptz IS NULL OR timestamp >= $1)
+              AND ($2::timestamptz IS NULL OR timestamp <= $2)
+              AND ($3::text IS NULL OR user_id = $3)
+              AND (cardinality($4::text[]) = 0 OR classification = ANY($4))
+            ORDER BY timestamp, envelope_id
+            LIMIT $5 OFFSET $6
+            "#,
+        )
+        .bind(from)
+        .bind(to)
+        .bind(user_id)
+        .bind(classifications)
+        .bind(limit.map(i64::from))
+        .bind(i64::from(offset.unwrap_or(0)))
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(forensic_envelope_from_row).collect()
+    }
+
+    /// One page of a tenant's stored envelopes after `after` (from the start if None), in
+    /// `(timestamp, envelope_id)` order
+    pub async fn tenant_forensic_envelopes_after(
+        &self,
+        tenant_id: &str,
+        after: Option<(DateTime<Utc>, Uuid)>,
+        limit: usize,
+    ) -> Result<Vec<ForensicEnvelope>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT envelope_id, operation_id, event_type, timestamp,
+                   user_id, session_id, classification, action,
+                   resource, before_state, after_state, metadata,
+                   audit_trail_hash
+            FROM forensic_log
+            WHERE metadata->>'tenant_id' = $1
+              AND ($2::timestamptz IS NULL OR (timestamp, envelope_id) > ($2, $3))
+            ORDER BY timestamp, envelope_id
+            LIMIT $4
+            "#,
+        )
+        .bind(tenant_id)
+        .bind(after.map(|(timestamp, _)| timestamp))
+        .bind(after.map(|(_, envelope_id)| envelope_id))
+        .bind(i64::try_from(limit).unwrap_or(i64::MAX))
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter().map(forensic_envelope_from_row).collect()
+    }
+
+    /// Store assessed compliance records for dashboard summaries
+    pub async fn store_compliance_records(&self, records: &[AssessedRecord]) -> Result<(), sqlx::Error> {
+        if records.is_empty() {
+            return Ok(());
+        }
+
+        let mut query = sqlx::QueryBuilder::<Postgres>::new(
+            "INSERT INTO compliance_records (id, assessed_at, framework, operation, tenant_id, classification, record) ",
+        );
+        query.push_values(records, |mut row, assessed| {
+            row.push_bind(assessed.id)
+                .push_bind(assessed.at)
+                .push_bind(framework_key(&assessed.record.framework))
+                .push_bind(&assessed.operation)
+                .push_bind(&assessed.tenant_id)
+                .push_bind(assessed.classification.as_ref().map(|level| level.key().to_string()))
+                .push_bind(sqlx::types::Json(&assessed.record));
+        });
+        query.build().execute(&self.pool).await?;
+        Ok(())
+    }
+
+    /// One page of a framework's compliance records in `[start, end)` after `after`, in
+    /// `(assessed_at, id)` order; only `tenant_id`'s when one is given
+    pub async fn compliance_records_page(
+        &self,
+        framework: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        tenant_id: Option<&str>,
+        after: Option<(DateTime<Utc>, Uuid)>,
+        limit: usize,
+    ) -> Result<Vec<AssessedRecord>, sqlx::Error> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, assessed_at, operation, tenant_id, classification, record
+            FROM compliance_records
+            WHERE framework = $1 AND assessed_at >= $2 AND assessed_at < $3
+              AND ($4::text IS NULL OR tenant_id = $4)
+              AND ($5::timestamptz IS NULL OR (assessed_at, id) > ($5, $6))
+            ORDER BY assessed_at, id
+            LIMIT $7
+            "#,
+        )
+        .bind(framework)
+        .bind(start)
+        .bind(end)
+        .bind(tenant_id)
+        .bind(after.map(|(assessed_at, _)| assessed_at))
+        .bind(after.map(|(_, id)| id))
+        .bind(i64::try_from(limit).unwrap_or(i64::MAX))
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                let classification: Option<String> = row.try_get("classification")?;
+                let record: sqlx::types::Json<ComplianceRecord> = row.try_get("record")?;
+                Ok(AssessedRecord {
+                    id: row.try_get("id")?,
+                    at: row.try_get("assessed_at")?,
+                    operation: row.try_get("operation")?,
+                    tenant_id: row.try_get("tenant_id")?,
+                    classification: classification.as_deref().map(ClassificationLevel::from_key),
+                    record: record.0,
+                })
+            })
+            .collect()
+    }
+
+    /// Store a newly issued API key together with the envelope recording its issue
+    pub async fn insert_api_key(&self, key: &StoredApiKey, event: &ForensicEnvelope) -> Result<(), sqlx::Error> {
+        let permissions: Vec<&str> = key.permissions.iter().map(|permission| permission.as_str()).collect();
+
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(
+            "INSERT INTO api_keys (key_id, tenant_id, permissions, label, secret_hash, created_at, expires_at, revoked_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
+        )
+        .bind(key.key_id)
+        .bind(&key.tenant_id)
+        .bind(&permissions)
+        .bind(sqlx::types::Json(&key.label))
+        .bind(&key.secret_hash)
+        .bind(key.created_at)
+        .bind(key.expires_at)
+        .bind(key.revoked_at)
+        .execute(&mut *tx)
+        .await?;
+        let link = self.insert_chained_envelopes(&mut tx, [&mut event.clone()]).await?;
+        tx.commit().await?;
+        link.commit();
+        Ok(())
+    }
+
+    pub async fn api_key(&self, key_id: Uuid) -> Result<Option<StoredApiKey>, sqlx::Error> {
+        let row = sqlx::query(
+            "SELECT key_id, tenant_id, permissions, label, secret_hash, created_at, expires_at, revoked_at
+             FROM api_keys WHERE key_id = $1",
+        )
+        .bind(key_id)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|row| {
+            let permissions: Vec<String> = row.try_get("permissions")?;
+            let label: sqlx::types::Json<SecurityLabel> = row.try_get("label")?;
+            Ok(StoredApiKey {
+                key_id: row.try_get("key_id")?,
+                tenant_id: row.try_get("tenant_id")?,
+                // Names this build no longer knows grant nothing
+                permissions: permissions.iter().filter_map(|name| Permission::from_name(name)).collect(),
+                label: label.0,
+                secret_hash: row.try_get("secret_hash")?,
+                created_at: row.try_get("created_at")?,
+                expires_at: row.try_get("expires_at")?,
+                revoked_at: row.try_get("revoked_at")?,
+            })
+        })
+        .transpose()
+    }
+
+    /// Revoke `key_id` from `at` unless already revoked, recording `event` with it; false if there's no such key
+    pub async fn revoke_api_key(&self, key_id: Uuid, at: DateTime<Utc>, event: &ForensicEnvelope) -> Result<bool, sqlx::Error> {
+        let mut tx = self.pool.begin().await?;
+        let updated = sqlx::query("UPDATE api_keys SET revoked_at = COALESCE(revoked_at, $2) WHERE key_id = $1")
+            .bind(key_id)
+            .bind(at)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        if updated == 0 {
+            return Ok(false);
+        }
+        let link = self.insert_chained_envelopes(&mut tx, [&mut event.clone()]).await?;
+        tx.commit().await?;
+        link.commit();
+        Ok(true)
+    }
+
+    /// Newest hash-chain link (and its timestamp) stored before `before`, or overall
+    pub async fn latest_forensic_link(
+        &self,
+        before: Option<DateTime<Utc>>,
+    ) -> Result<Option<(String, DateTime<Utc>)>, sqlx::Error> {
+        let row = sqlx::query(
+            r#"
+            SELECT audit_trail_hash, timestamp
+            FROM forensic_log
+            WHERE ($1::timestamptz IS NULL OR timestamp < $1)
+            ORDER BY timestamp DESC, envelope_id DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(before)
+        .fetch_optional(&self.pool)
+        .await?;
+
+        row.map(|row| Ok((row.try_get("audit_trail_hash")?, row.try_get("timestamp")?)))
+            .transpose()
+    }
+
+    /// Link `envelopes` into the forensic chain, in order, and insert them on `conn`
+    ///
+    /// The chain head stays locked until the returned link is committed, which callers do
+    /// right after committing their transaction; dropped uncommitted, the head rolls back
+    /// with it. Taken last, after the transaction's row locks, so waiting for it can't deadlock.
+    async fn insert_chained_envelopes<'e>(
+        &self,
+        conn: &mut PgConnection,
+        envelopes: impl IntoIterator<Item = &'e mut ForensicEnvelope>,
+    ) -> Result<ChainLink<'_>, sqlx::Error> {
+        let mut link = self.forensic_linker.lock().await;
+        for envelope in envelopes {
+            link.append(envelope);
+            Self::insert_forensic_envelope(&mut *conn, envelope).await?;
+        }
+        Ok(link)
+    }
+
+    async fn insert_forensic_envelope<'e, E>(executor: E, envelope: &ForensicEnvelope) -> Result<(), sqlx::Error>
+    where
+        E: sqlx::Executor<'e, Database = Postgres>,
+    {
+        sqlx::query!(
+            r#"
+            INSERT INTO forensic_log (
+                envelope_id, operation_id, event_type, timestamp, 
+                user_id, session_id, classification, action, 
+                resource, before_state, after_state, metadata, 
+                audit_trail_hash
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+            "#,
+            envelope.envelope_id,
+            envelope.operation_id,
+            envelope.event_type,
+            envelope.timestamp,
+            envelope.user_id,
+            envelope.session_id,
+            envelope.classification.key(),
+            envelope.action,
+            envelope.resource,
+            envelope.before_state,
+            envelope.after_state,
+            envelope.metadata,
+            envelope.audit_trail_hash
+        )
+        .execute(executor)
+        .await?;
+
+        Ok(())
+    }
+
+    // Private helper methods
+
+    /// Add security filtering to query based on user's clearance, hiding soft-deleted rows
+    fn add_security_filter<'q>(
+        &self,
+        query_builder: &mut sqlx::QueryBuilder<'q, Postgres>,
+        context: &'q DatabaseContext,
+    ) {
+        let lattice = classification_lattice::active();
+        
+        // No Read Up: User can only read data their clearance dominates
+        query_builder.push(" AND (");
+        
+        // Add classification level check
+        for level in lattice.levels() {
+            if lattice.dominates(&context.security_label.level, &level) {
+                query_builder.push("classification = ");
+                query_builder.push_bind(level.key().to_string());
+                query_builder.push(" OR ");
+            }
+        }
+        query_builder.push("FALSE)"); // Close the OR chain
+        
+        // Every compartment on the row must be held; users without compartments see only rows without any
+        query_builder.push(" AND compartments <@ ");
+        query_builder.push_bind(context.security_label.compartments.iter().cloned().collect::<Vec<String>>());
+        query_builder.push("::text[]");
+
+        // Add tenant isolation if enabled
+        if let Some(tenant_id) = &context.tenant_id {
+            query_builder.push(" AND (tenant_id IS NULL OR tenant_id = ");
+            query_builder.push_bind(tenant_id);
+            query_builder.push(")");
+        }
+
+        // Soft-deleted rows stay hidden unless asked for
+        if !context.include_deleted {
+            query_builder.push(" AND deleted_at IS NULL");
+        }
+    }
+
+    /// Ranked search over `search_tsv` with the security filter applied in SQL
+    fn search_query<'q>(&self, query: &'q str, context: &'q DatabaseContext) -> sqlx::QueryBuilder<'q, Postgres> {
+        let mut query_builder = sqlx::QueryBuilder::new(
+            "SELECT id, entity_type, data, created_at, updated_at, 
+             created_by, updated_by, classification, compartments, 
+             version, tenant_id, deleted_at, deleted_by FROM entities, websearch_to_tsquery('english', "
+        );
+        query_builder.push_bind(query);
+        query_builder.push(") AS search WHERE search_tsv @@ search");
+
+        self.add_security_filter(&mut query_builder, context);
+
+        query_builder.push(" ORDER BY ts_rank(search_tsv, search) DESC, id LIMIT ");
+        query_builder.push_bind(SEARCH_RESULT_LIMIT);
+        query_builder
+    }
+
+    /// Drop entities the context may not read, in case one got past the SQL filter
+    fn readable_only(&self, entities: Vec<SecureEntity>, context: &DatabaseContext) -> (Vec<SecureEntity>, i64) {
+        let before = entities.len();
+        let readable: Vec<SecureEntity> = entities.into_iter()
+            .filter(|entity| self.can_read_entity(entity, context))
+            .collect();
+        let denied = (before - readable.len()) as i64;
+        if denied > 0 {
+            crate::security_log!(warn, user_id = %context.user_id, denied, "Search returned entities above the caller's clearance; dropped");
+        }
+        (readable, denied)
+    }
+
+    /// Check if user can write to a classification level (No Write Down)
+    fn can_write_classification(
+        &self,
+        target_classification: &ClassificationLevel,
+        user_classification: &ClassificationLevel,
+    ) -> bool {
+        // No Write Down: User can write to their level or higher
+        target_classification.dominates(user_classification)
+    }
+
+    /// Check if user can read an entity (No Read Up, compartments must be held)
+    fn can_read_entity(&self, entity: &SecureEntity, context: &DatabaseContext) -> bool {
+        context.can_read(entity)
+    }
+
+    /// Create entity within a transaction, labelled no lower than `classification` if given
+    async fn create_entity_in_transaction(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        entity_id: Uuid,
+        entity_type: &str,
+        data: serde_json::Value,
+        classification: Option<&ClassificationLevel>,
+        context: &DatabaseContext,
+    ) -> Result<SecureEntity, DatabaseError> {
+        let now = Utc::now();
+
+        // Label from policy, never below the creator's clearance
+        let suggestion = self.classifier.classify(entity_type, &data, &context.security_label);
+        if suggestion.needs_review {
+            tracing::warn!(
+                "Entity {} ({}) matched conflicting classification rules {:?}; stored as {} pending review",
+                entity_id, entity_type, suggestion.matched_rules, suggestion.label.level
+            );
+            self.flag_for_review(ClassificationReview {
+                entity_id,
+                entity_type: entity_type.to_string(),
+                classification: suggestion.label.level.clone(),
+                matched_rules: suggestion.matched_rules.clone(),
+                flagged_at: now,
+            }).await;
+        }
+        
+        // A requested level may raise the label, never lower it below policy or clearance
+        let mut label = suggestion.label;
+        if let Some(requested) = classification.filter(|requested| requested.dominates(&label.level)) {
+            label.level = requested.clone();
+        }
+
+        // Classified on the clear data; stored with the subject's personal fields sealed
+        let data = self.subject_vault.seal_fields(self, entity_type, data).await?;
+        let entity = SecureEntity::created(entity_id, entity_type, data, label, context, now);
+
+        // Insert into main entities table
+        sqlx::query!(
+            r#"
+            INSERT INTO entities (
+                id, entity_type, data, created_at, updated_at, 
+                created_by, updated_by, classification, compartments, 
+                version, tenant_id
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            "#,
+            entity.id,
+            entity.entity_type,
+            entity.data,
+            entity.created_at,
+            entity.updated_at,
+            entity.created_by,
+            entity.updated_by,
+            entity.classification.key(),
+            &entity.compartments,
+            entity.version,
+            entity.tenant_id
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        // Handle polyinstantiation if enabled
+        if self.enable_polyinstantiation {
+            self.create_polyinstantiation_entry(tx, &entity, context).await?;
+        }
+
+        Ok(entity)
+    }
+
+    /// Create entity within a transaction, or return the one already created under `idempotency_key`
+    ///
+    /// The flag is set when the entity is a replay of an earlier create.
+    async fn create_keyed_entity_in_transaction(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        entity_type: &str,
+        data: serde_json::Value,
+        idempotency_key: &str,
+        classification: Option<&ClassificationLevel>,
+        context: &DatabaseContext,
+    ) -> Result<(SecureEntity, bool), DatabaseError> {
+        validate_idempotency_key(idempotency_key)?;
+        let tenant_scope = context.tenant_id.as_deref().unwrap_or("");
+
+        // A concurrent holder of the key blocks this insert until it commits or rolls back
+        let entity_id = Uuid::new_v4();
+        let claimed = sqlx::query(
+            "INSERT INTO entity_idempotency_keys (tenant_scope, created_by, idempotency_key, entity_id)
+             VALUES ($1, $2, $3, $4)
+             ON CONFLICT DO NOTHING"
+        )
+        .bind(tenant_scope)
+        .bind(&context.user_id)
+        .bind(idempotency_key)
+        .bind(entity_id)
+        .execute(&mut **tx)
+        .await?
+        .rows_affected() == 1;
+
+        if claimed {
+            let entity = self
+                .create_entity_in_transaction(tx, entity_id, entity_type, data, classification, context)
+                .await?;
+            return Ok((entity, false));
+        }
+
+        let existing_id: Uuid = sqlx::query_scalar(
+            "SELECT entity_id FROM entity_idempotency_keys
+             WHERE tenant_scope = $1 AND created_by = $2 AND idempotency_key = $3"
+        )
+        .bind(tenant_scope)
+        .bind(&context.user_id)
+        .bind(idempotency_key)
+        .fetch_one(&mut **tx)
+        .await?;
+        let existing = self.read_entity_in_transaction(tx, existing_id, context, false).await?;
+        Ok((replay_create(existing, entity_type, idempotency_key)?, true))
+    }
+
+    /// Update entity within a transaction; `None` if missing, denied, or lost an optimistic lock
+    async fn update_entity_in_transaction(
         &self,
         tx: &mut Transaction<'_, Postgres>,
         entity_id: Uuid,
+        updates: serde_json::Value,
         context: &DatabaseContext,
+    ) -> Result<Option<SecureEntity>, DatabaseError> {
+        // First, check if user can read the entity (No Read Up)
+        let existing = self.read_entity_in_transaction(tx, entity_id, context, false).await?;
+        let existing = match existing {
+            Some(entity) => entity,
+            None => return Ok(None), // Entity doesn't exist or access denied
+        };
+        if !self.can_read_entity(&existing, context) {
+            return Ok(None); // Read access denied (No Read Up)
+        }
+
+        // Check write permissions (No Write Down for the update operation)
+        let target_classification = context.security_label.level.clone();
+        if !self.can_write_classification(&existing.classification, &target_classification) {
+            return Ok(None); // Write access denied
+        }
+
+        let mut updated_entity = existing.updated(updates, context, Utc::now());
+        updated_entity.data = self.subject_vault
+            .seal_fields(self, &updated_entity.entity_type, updated_entity.data)
+            .await?;
+
+        // Update the entity with optimistic locking
+        let updated_rows = sqlx::query!(
+            r#"
+            UPDATE entities 
+            SET data = $2, updated_at = $3, updated_by = $4, version = $5
+            WHERE id = $1 AND version = $6
+            "#,
+            entity_id,
+            updated_entity.data,
+            updated_entity.updated_at,
+            updated_entity.updated_by,
+            updated_entity.version,
+            existing.version // Optimistic lock check
+        )
+        .execute(&mut **tx)
+        .await?;
+
+        if updated_rows.rows_affected() == 0 {
+            // Either entity was updated by someone else or doesn't exist
+            return Ok(None);
+        }
+
+        // Update polyinstantiation if enabled
+        if self.enable_polyinstantiation {
+            self.update_polyinstantiation_entry(tx, &updated_entity, context).await?;
+        }
+
+        Ok(Some(updated_entity))
+    }
+
+    /// Soft-delete entity within a transaction, returning it as it was before deletion
+    async fn delete_entity_in_transaction(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        entity_id: Uuid,
+        context: &DatabaseContext,
+    ) -> Result<Option<(SecureEntity, ForensicEnvelope)>, sqlx::Error> {
+        match self.change_lifecycle_in_transaction(tx, entity_id, EntityLifecycle::SoftDelete, context).await {
+            Ok((existing, _, envelope)) => Ok(Some((existing, envelope))),
+            Err(DatabaseError::Connection(e)) => Err(e),
+            Err(_) => Ok(None), // Entity doesn't exist, already deleted, or access denied
+        }
+    }
+
+    /// Apply a lifecycle change within a transaction
+    ///
+    /// Returns the entity before the change, after it unless it was erased, and the envelope
+    /// recording it, which the caller chains in with `insert_chained_envelopes` before committing.
+    async fn change_lifecycle_in_transaction(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        entity_id: Uuid,
+        lifecycle: EntityLifecycle,
+        context: &DatabaseContext,
+    ) -> Result<(SecureEntity, Option<SecureEntity>, ForensicEnvelope), DatabaseError> {
+        let existing = self.read_entity_in_transaction(tx, entity_id, context, true).await?
+            .ok_or_else(|| DatabaseError::LifecycleDenied(format!("entity {} not found", entity_id)))?;
+        self.authorize_lifecycle(lifecycle, &existing, context)?;
+
+        let changed = lifecycle.apply(&existing, context, Utc::now());
+        let affected_rows = match &changed {
+            Some(entity) => sqlx::query!(
+                r#"
+                UPDATE entities
+                SET deleted_at = $2, deleted_by = $3, updated_at = $4, updated_by = $5, version = $6
+                WHERE id = $1 AND version = $7
+                "#,
+                entity_id,
+                entity.deleted_at,
+                entity.deleted_by,
+                entity.updated_at,
+                entity.updated_by,
+                entity.version,
+                existing.version // Optimistic lock check
+            )
+            .execute(&mut **tx)
+            .await?,
+            None => sqlx::query!(
+                "DELETE FROM entities WHERE id = $1 AND version = $2",
+                entity_id,
+                existing.version
+            )
+            .execute(&mut **tx)
+            .await?,
+        };
+        if affected_rows.rows_affected() == 0 {
+            return Err(DatabaseError::LifecycleConflict(entity_id));
+        }
+
+        if self.enable_polyinstantiation {
+            match &changed {
+                Some(entity) => self.update_polyinstantiation_entry(tx, entity, context).await?,
+                None => self.delete_polyinstantiation_entry(tx, entity_id, context).await?,
+            }
+        }
+
+        let envelope = lifecycle.envelope(&existing, changed.as_ref(), context);
+        Ok((existing, changed, envelope))
+    }
+
+    /// Check that `context` may apply `lifecycle` to `entity`
+    fn authorize_lifecycle(
+        &self,
+        lifecycle: EntityLifecycle,
+        entity: &SecureEntity,
+        context: &DatabaseContext,
+    ) -> Result<(), DatabaseError> {
+        lifecycle.authorize(entity, context)
+    }
+
+    /// Read entity within a transaction; soft-deleted entities only with `include_deleted`
+    async fn read_entity_in_transaction(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        entity_id: Uuid,
+        context: &DatabaseContext,
+        include_deleted: bool,
     ) -> Result<Option<SecureEntity>, sqlx::Error> {
         // This would need proper implementation based on your exact schema
         // For now, simplified version
@@ -495,50 +2380,583 @@ impl DatabaseManager {
             r#"
             SELECT id, entity_type, data, created_at, updated_at,
                    created_by, updated_by, classification as "classification: ClassificationLevel", 
-                   compartments, version, tenant_id
+                   compartments, version, tenant_id, deleted_at, deleted_by
             FROM entities 
-            WHERE id = $1
+            WHERE id = $1 AND ($2 OR deleted_at IS NULL)
             "#,
-            entity_id
+            entity_id,
+            include_deleted
+        )
+        .fetch_optional(&mut **tx)
+        .await?;
+
+        Ok(result)
+    }
+
+    /// Create polyinstantiation entry (if enabled)
+    async fn create_polyinstantiation_entry(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        entity: &SecureEntity,
+        context: &DatabaseContext,
+    ) -> Result<(), sqlx::Error> {
+        // Implementation would depend on your polyinstantiation schema
+        // This is a placeholder
+        Ok(())
+    }
+
+    /// Update polyinstantiation entry (if enabled)
+    async fn update_polyinstantiation_entry(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        entity: &SecureEntity,
+        context: &DatabaseContext,
+    ) -> Result<(), sqlx::Error> {
+        // Implementation would depend on your polyinstantiation schema
+        Ok(())
+    }
+
+    /// Delete polyinstantiation entry (if enabled)
+    async fn delete_polyinstantiation_entry(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        entity_id: Uuid,
+        context: &DatabaseContext,
+    ) -> Result<(), sqlx::Error> {
+        // Implementation would depend on your polyinstantiation schema
+        Ok(())
+    }
+}
+
+/// Postgres backend; each call delegates to the inherent method of the same name
+#[async_trait::async_trait]
+impl EntityStore for DatabaseManager {
+    async fn create_entity(
+        &self,
+        entity_type: &str,
+        data: serde_json::Value,
+        context: &DatabaseContext,
+    ) -> Result<SecureEntity, DatabaseError> {
+        self.check_tenant(context).await?;
+        Ok(DatabaseManager::create_entity(self, entity_type, data, context).await?)
+    }
+
+    async fn create_entity_idempotent(
+        &self,
+        entity_type: &str,
+        data: serde_json::Value,
+        idempotency_key: &str,
+        context: &DatabaseContext,
+    ) -> Result<SecureEntity, DatabaseError> {
+        DatabaseManager::create_entity_idempotent(self, entity_type, data, idempotency_key, None, context)
+            .await
+            .map(|(entity, _)| entity)
+    }
+
+    async fn read_entity(&self, entity_id: Uuid, context: &DatabaseContext) -> Result<Option<SecureEntity>, DatabaseError> {
+        self.check_tenant(context).await?;
+        Ok(DatabaseManager::read_entity(self, entity_id, context).await?)
+    }
+
+    async fn update_entity(
+        &self,
+        entity_id: Uuid,
+        updates: serde_json::Value,
+        context: &DatabaseContext,
+    ) -> Result<Option<SecureEntity>, DatabaseError> {
+        self.check_tenant(context).await?;
+        Ok(DatabaseManager::update_entity(self, entity_id, updates, context).await?)
+    }
+
+    async fn merge_entity(
+        &self,
+        entity_id: Uuid,
+        expected_version: i64,
+        data: serde_json::Value,
+        label: &SecurityLabel,
+        context: &DatabaseContext,
+    ) -> Result<Option<SecureEntity>, DatabaseError> {
+        DatabaseManager::merge_entity(self, entity_id, expected_version, data, label, context).await
+    }
+
+    async fn delete_entity(&self, entity_id: Uuid, context: &DatabaseContext) -> Result<bool, DatabaseError> {
+        self.check_tenant(context).await?;
+        Ok(DatabaseManager::delete_entity(self, entity_id, context).await?)
+    }
+
+    async fn query_entities(
+        &self,
+        entity_type: Option<&str>,
+        filter: Option<&QueryFilter>,
+        context: &DatabaseContext,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> Result<SecureQueryResult, DatabaseError> {
+        // Checked here too, so these errors are reported the same way by every backend
+        self.check_tenant(context).await?;
+        if let Some(filter) = filter {
+            filter.validate()?;
+        }
+        Ok(DatabaseManager::query_entities(self, entity_type, filter, context, limit, offset).await?)
+    }
+
+    async fn dry_run(&self, operation: &BatchEntityOperation, context: &DatabaseContext) -> Result<DryRunOutcome, DatabaseError> {
+        if let Err(e) = self.check_tenant(context).await {
+            return Ok(DryRunOutcome::denied(e.to_string()));
+        }
+        let existing = match operation {
+            BatchEntityOperation::Create { .. } => None,
+            BatchEntityOperation::Update { entity_id, .. } | BatchEntityOperation::Delete { entity_id } => {
+                DatabaseManager::read_entity(self, *entity_id, context).await?
+            }
+        };
+
+        Ok(preview_operation(
+            operation,
+            existing.as_ref(),
+            |entity_type, data| self.classifier.classify(entity_type, data, &context.security_label).label,
+            context,
+            Utc::now(),
+        ))
+    }
+}
+
+#[async_trait::async_trait]
+impl SubjectKeyStore for DatabaseManager {
+    async fn insert_subject_key(&self, key: &StoredSubjectKey) -> Result<(), DatabaseError> {
+        sqlx::query(
+            "INSERT INTO subject_keys (subject_id, key_id, wrapped_key) VALUES ($1, $2, $3)
+             ON CONFLICT (subject_id) DO NOTHING",
+        )
+        .bind(&key.subject_id)
+        .bind(key.key_id)
+        .bind(&key.wrapped_key)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn subject_key_for(&self, subject_id: &str) -> Result<Option<StoredSubjectKey>, DatabaseError> {
+        let row = sqlx::query("SELECT subject_id, key_id, wrapped_key FROM subject_keys WHERE subject_id = $1")
+            .bind(subject_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        row.as_ref().map(stored_subject_key_from_row).transpose()
+    }
+
+    async fn subject_key_by_id(&self, key_id: Uuid) -> Result<Option<StoredSubjectKey>, DatabaseError> {
+        let row = sqlx::query("SELECT subject_id, key_id, wrapped_key FROM subject_keys WHERE key_id = $1")
+            .bind(key_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        row.as_ref().map(stored_subject_key_from_row).transpose()
+    }
+}
+
+fn forensic_envelope_from_row(row: &sqlx::postgres::PgRow) -> Result<ForensicEnvelope, sqlx::Error> {
+    Ok(ForensicEnvelope {
+        envelope_id: row.try_get("envelope_id")?,
+        operation_id: row.try_get("operation_id")?,
+        event_type: row.try_get("event_type")?,
+        timestamp: row.try_get("timestamp")?,
+        user_id: row.try_get("user_id")?,
+        session_id: row.try_get("session_id")?,
+        classification: row.try_get("classification")?,
+        action: row.try_get("action")?,
+        resource: row.try_get("resource")?,
+        before_state: row.try_get("before_state")?,
+        after_state: row.try_get("after_state")?,
+        metadata: row.try_get("metadata")?,
+        audit_trail_hash: row.try_get("audit_trail_hash")?,
+    })
+}
+
+/// Lock the expired, unheld forensic rows among `ids` and record the digest of their original
+/// content, which their chain links cover; returns the ids locked
+async fn keep_forensic_digests(conn: &mut PgConnection, ids: &[Uuid]) -> Result<Vec<Uuid>, DatabaseError> {
+    let rows = sqlx::query(
+        r#"
+        SELECT envelope_id, operation_id, event_type, timestamp,
+               user_id, session_id, classification, action,
+               resource, before_state, after_state, metadata,
+               audit_trail_hash
+        FROM forensic_log
+        WHERE envelope_id = ANY($1) AND NOT legal_hold AND content_digest IS NULL
+        FOR UPDATE
+        "#,
+    )
+    .bind(ids)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    let mut locked = Vec::with_capacity(rows.len());
+    let mut digests = Vec::with_capacity(rows.len());
+    for row in &rows {
+        let envelope = forensic_envelope_from_row(row)?;
+        digests.push(content_digest(&envelope));
+        locked.push(envelope.envelope_id);
+    }
+    sqlx::query(
+        "UPDATE forensic_log SET content_digest = kept.digest
+         FROM UNNEST($1::uuid[], $2::text[]) AS kept(envelope_id, digest)
+         WHERE forensic_log.envelope_id = kept.envelope_id",
+    )
+    .bind(&locked)
+    .bind(&digests)
+    .execute(&mut *conn)
+    .await?;
+    Ok(locked)
+}
+
+/// Clear forensic rows down to their chain position and tenant, marked with `action`
+async fn tombstone_forensic_rows(conn: &mut PgConnection, ids: &[Uuid], action: &ExpiryAction) -> Result<u64, DatabaseError> {
+    let result = sqlx::query(
+        "UPDATE forensic_log
+         SET user_id = $2, resource = NULL, before_state = NULL, after_state = NULL,
+             metadata = jsonb_strip_nulls(jsonb_build_object('tenant_id', metadata->'tenant_id')),
+             retention_action = $3
+         WHERE envelope_id = ANY($1)",
+    )
+    .bind(ids)
+    .bind(ANONYMIZED_MARKER)
+    .bind(action.as_str())
+    .execute(&mut *conn)
+    .await?;
+    Ok(result.rows_affected())
+}
+
+fn stored_subject_key_from_row(row: &sqlx::postgres::PgRow) -> Result<StoredSubjectKey, DatabaseError> {
+    Ok(StoredSubjectKey {
+        subject_id: row.try_get("subject_id")?,
+        key_id: row.try_get("key_id")?,
+        wrapped_key: row.try_get("wrapped_key")?,
+    })
+}
+
+#[async_trait::async_trait]
+impl FieldKeyStore for DatabaseManager {
+    async fn field_keys(&self) -> Result<Vec<StoredFieldKey>, CryptoError> {
+        let rows = sqlx::query("SELECT key_id, classification, version, wrapped_key, created_at FROM field_keys")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| CryptoError::KeyStore(e.to_string()))?;
+        rows.iter()
+            .map(|row| {
+                let classification: String = row.try_get("classification")?;
+                let version: i32 = row.try_get("version")?;
+                Ok(StoredFieldKey {
+                    key_id: row.try_get("key_id")?,
+                    classification: ClassificationLevel::from_key(&classification),
+                    version: u32::try_from(version).map_err(|e| sqlx::Error::Decode(Box::new(e)))?,
+                    wrapped_key: row.try_get("wrapped_key")?,
+                    created_at: row.try_get("created_at")?,
+                })
+            })
+            .collect::<Result<_, sqlx::Error>>()
+            .map_err(|e| CryptoError::KeyStore(e.to_string()))
+    }
+
+    async fn insert_field_key(&self, key: &StoredFieldKey) -> Result<(), CryptoError> {
+        let version = i32::try_from(key.version).map_err(|e| CryptoError::KeyStore(e.to_string()))?;
+        sqlx::query(
+            "INSERT INTO field_keys (key_id, classification, version, wrapped_key, created_at)
+             VALUES ($1, $2, $3, $4, $5)",
         )
-        .fetch_optional(&mut **tx)
+        .bind(&key.key_id)
+        .bind(key.classification.key())
+        .bind(version)
+        .bind(&key.wrapped_key)
+        .bind(key.created_at)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| CryptoError::KeyStore(e.to_string()))?;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl RetentionStore for DatabaseManager {
+    async fn expired_page(
+        &self,
+        target: RetentionTarget,
+        tenant_id: &str,
+        cutoff: DateTime<Utc>,
+        after: Option<Uuid>,
+        limit: usize,
+    ) -> Result<Vec<RetainedRecord>, DatabaseError> {
+        let sql = match target {
+            RetentionTarget::Entities => {
+                "SELECT id, created_at AS recorded_at, legal_hold, NULL::text AS user_id, data
+                 FROM entities
+                 WHERE tenant_id = $1 AND created_at < $2 AND retention_action IS NULL
+                   AND ($3::uuid IS NULL OR id > $3)
+                 ORDER BY id LIMIT $4"
+            }
+            RetentionTarget::ForensicLog => {
+                "SELECT envelope_id AS id, timestamp AS recorded_at, legal_hold, user_id,
+                        jsonb_build_object('before_state', before_state, 'after_state', after_state) AS data
+                 FROM forensic_log
+                 WHERE metadata->>'tenant_id' = $1 AND timestamp < $2 AND retention_action IS NULL
+                   AND ($3::uuid IS NULL OR envelope_id > $3)
+                 ORDER BY envelope_id LIMIT $4"
+            }
+        };
+        let rows = sqlx::query(sql)
+            .bind(tenant_id)
+            .bind(cutoff)
+            .bind(after)
+            .bind(limit as i64)
+            .fetch_all(&self.pool)
+            .await?;
+
+        rows.into_iter()
+            .map(|row| Ok(RetainedRecord {
+                id: row.try_get("id")?,
+                recorded_at: row.try_get("recorded_at")?,
+                legal_hold: row.try_get("legal_hold")?,
+                user_id: row.try_get("user_id")?,
+                data: row.try_get("data")?,
+            }))
+            .collect()
+    }
+
+    async fn delete_records(&self, target: RetentionTarget, ids: &[Uuid]) -> Result<u64, DatabaseError> {
+        // Legal holds are re-checked here in case one was placed since the page was read
+        match target {
+            RetentionTarget::Entities => {
+                let result = sqlx::query("DELETE FROM entities WHERE id = ANY($1) AND NOT legal_hold")
+                    .bind(ids)
+                    .execute(&self.pool)
+                    .await?;
+                Ok(result.rows_affected())
+            }
+            RetentionTarget::ForensicLog => {
+                let mut tx = self.pool.begin().await?;
+                let cleared = keep_forensic_digests(&mut tx, ids).await?;
+                let tombstoned = tombstone_forensic_rows(&mut tx, &cleared, &ExpiryAction::Delete).await?;
+                tx.commit().await?;
+                Ok(tombstoned)
+            }
+        }
+    }
+
+    async fn archive_records(&self, target: RetentionTarget, ids: &[Uuid], policy_id: &str) -> Result<u64, DatabaseError> {
+        let mut tx = self.pool.begin().await?;
+        // Lock the rows so the copy and the delete see the same set
+        let archived: Vec<Uuid> = match target {
+            RetentionTarget::Entities => sqlx::query_scalar(
+                "SELECT id FROM entities WHERE id = ANY($1) AND NOT legal_hold FOR UPDATE",
+            )
+            .bind(ids)
+            .fetch_all(&mut *tx)
+            .await?,
+            RetentionTarget::ForensicLog => keep_forensic_digests(&mut tx, ids).await?,
+        };
+
+        sqlx::query(&format!(
+            "INSERT INTO {cold} ({columns}, archived_at, retention_policy_id)
+             SELECT {columns}, now(), $2 FROM {table} WHERE {id} = ANY($1)",
+            cold = target.cold_table(), columns = target.columns(), table = target.table(), id = target.id_column()
+        ))
+        .bind(&archived)
+        .bind(policy_id)
+        .execute(&mut *tx)
         .await?;
+        let removed = match target {
+            RetentionTarget::Entities => sqlx::query("DELETE FROM entities WHERE id = ANY($1)")
+                .bind(&archived)
+                .execute(&mut *tx)
+                .await?
+                .rows_affected(),
+            RetentionTarget::ForensicLog => tombstone_forensic_rows(&mut tx, &archived, &ExpiryAction::Archive).await?,
+        };
+        tx.commit().await?;
 
-        Ok(result)
+        Ok(removed)
     }
 
-    /// Create polyinstantiation entry (if enabled)
-    async fn create_polyinstantiation_entry(
+    async fn rewrite_records(
         &self,
-        tx: &mut Transaction<'_, Postgres>,
-        entity: &SecureEntity,
-        context: &DatabaseContext,
-    ) -> Result<(), sqlx::Error> {
-        // Implementation would depend on your polyinstantiation schema
-        // This is a placeholder
-        Ok(())
+        target: RetentionTarget,
+        updates: &[RetentionUpdate],
+        action: &ExpiryAction,
+    ) -> Result<u64, DatabaseError> {
+        let mut tx = self.pool.begin().await?;
+        if target == RetentionTarget::ForensicLog {
+            let ids: Vec<Uuid> = updates.iter().map(|update| update.id).collect();
+            keep_forensic_digests(&mut tx, &ids).await?;
+        }
+        let mut rewritten = 0;
+        for update in updates {
+            let result = match target {
+                RetentionTarget::Entities => {
+                    sqlx::query(
+                        "UPDATE entities SET data = $2, retention_action = $3
+                         WHERE id = $1 AND NOT legal_hold",
+                    )
+                    .bind(update.id)
+                    .bind(&update.data)
+                    .bind(action.as_str())
+                    .execute(&mut *tx)
+                    .await?
+                }
+                RetentionTarget::ForensicLog => {
+                    // An anonymized payload keeps its before/after split; a sealed one is stored whole
+                    let (before_state, after_state) = match update.data.as_object() {
+                        Some(states) if states.contains_key("before_state") || states.contains_key("after_state") => (
+                            states.get("before_state").cloned().filter(|state| !state.is_null()),
+                            states.get("after_state").cloned().filter(|state| !state.is_null()),
+                        ),
+                        _ => (Some(update.data.clone()), None),
+                    };
+                    sqlx::query(
+                        "UPDATE forensic_log
+                         SET user_id = COALESCE($2, user_id), before_state = $3, after_state = $4, retention_action = $5
+                         WHERE envelope_id = $1 AND NOT legal_hold",
+                    )
+                    .bind(update.id)
+                    .bind(&update.user_id)
+                    .bind(before_state)
+                    .bind(after_state)
+                    .bind(action.as_str())
+                    .execute(&mut *tx)
+                    .await?
+                }
+            };
+            rewritten += result.rows_affected();
+        }
+        tx.commit().await?;
+
+        Ok(rewritten)
     }
 
-    /// Update polyinstantiation entry (if enabled)
-    async fn update_polyinstantiation_entry(
+    async fn active_legal_holds(&self) -> Result<LegalHolds, DatabaseError> {
+        Self::fetch_legal_holds(&self.pool).await
+    }
+
+    async fn retention_key(&self, tenant_id: &str) -> Result<SubjectKey, DatabaseError> {
+        self.subject_key(&format!("retention:{}", tenant_id)).await
+    }
+
+    async fn log_sweep(&self, envelope: &ForensicEnvelope) -> Result<(), DatabaseError> {
+        Ok(self.store_forensic_envelope(envelope).await?)
+    }
+}
+
+#[async_trait::async_trait]
+impl DeclassificationStore for DatabaseManager {
+    async fn entity_label(&self, entity_id: Uuid, context: &DatabaseContext) -> Result<Option<SecurityLabel>, DatabaseError> {
+        self.check_tenant(context).await?;
+        let mut tx = self.pool.begin().await?;
+        let entity = self.read_entity_in_transaction(&mut tx, entity_id, context, false).await?;
+        Ok(entity
+            .filter(|entity| self.can_read_entity(entity, context))
+            .map(|entity| SecurityLabel::new(entity.classification, entity.compartments)))
+    }
+
+    async fn insert_declassification(
         &self,
-        tx: &mut Transaction<'_, Postgres>,
-        entity: &SecureEntity,
-        context: &DatabaseContext,
-    ) -> Result<(), sqlx::Error> {
-        // Implementation would depend on your polyinstantiation schema
+        request: &DeclassificationRequest,
+        envelope: &ForensicEnvelope,
+    ) -> Result<(), DatabaseError> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query(
+            "INSERT INTO declassification_requests (request_id, tenant_id, request, status, proposed_at, expires_at)
+             VALUES ($1, $2, $3, 'pending', $4, $5)",
+        )
+        .bind(request.request_id)
+        .bind(&request.tenant_id)
+        .bind(sqlx::types::Json(request))
+        .bind(request.proposed_at)
+        .bind(request.expires_at)
+        .execute(&mut *tx)
+        .await?;
+        let link = self.insert_chained_envelopes(&mut tx, [&mut envelope.clone()]).await?;
+        tx.commit().await?;
+        link.commit();
         Ok(())
     }
 
-    /// Delete polyinstantiation entry (if enabled)
-    async fn delete_polyinstantiation_entry(
+    async fn pending_declassification(&self, request_id: Uuid) -> Result<Option<DeclassificationRequest>, DatabaseError> {
+        let row = sqlx::query("SELECT request FROM declassification_requests WHERE request_id = $1 AND status = 'pending'")
+            .bind(request_id)
+            .fetch_optional(&self.pool)
+            .await?;
+        Ok(row
+            .map(|row| row.try_get::<sqlx::types::Json<DeclassificationRequest>, _>("request"))
+            .transpose()?
+            .map(|request| request.0))
+    }
+
+    async fn pending_declassifications(&self) -> Result<Vec<DeclassificationRequest>, DatabaseError> {
+        let rows = sqlx::query("SELECT request FROM declassification_requests WHERE status = 'pending' ORDER BY proposed_at")
+            .fetch_all(&self.pool)
+            .await?;
+        rows.iter()
+            .map(|row| Ok(row.try_get::<sqlx::types::Json<DeclassificationRequest>, _>("request")?.0))
+            .collect()
+    }
+
+    async fn apply_declassification(
         &self,
-        tx: &mut Transaction<'_, Postgres>,
-        entity_id: Uuid,
-        context: &DatabaseContext,
-    ) -> Result<(), sqlx::Error> {
-        // Implementation would depend on your polyinstantiation schema
-        Ok(())
+        request: &DeclassificationRequest,
+        approver: &DatabaseContext,
+        envelope: &ForensicEnvelope,
+    ) -> Result<Vec<SecureEntity>, DatabaseError> {
+        self.check_tenant(approver).await?;
+        let mut tx = self.pool.begin().await?;
+
+        // Claimed in the relabel's transaction, so two approvers can't both apply it
+        let claimed = sqlx::query(
+            "UPDATE declassification_requests SET status = 'approved', decided_by = $2, decided_at = $3
+             WHERE request_id = $1 AND status = 'pending'",
+        )
+        .bind(request.request_id)
+        .bind(&approver.user_id)
+        .bind(Utc::now())
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        if claimed == 0 {
+            return Err(DatabaseError::DeclassificationNotFound(request.request_id));
+        }
+
+        let (relabeled, mut envelopes) = self
+            .relabel_entities_in_transaction(
+                &mut tx,
+                &request.entity_ids,
+                &request.target_label,
+                &request.justification,
+                approver,
+                Some(request),
+            )
+            .await?;
+        envelopes.push(envelope.clone());
+
+        let link = self.insert_chained_envelopes(&mut tx, &mut envelopes).await?;
+        tx.commit().await?;
+        link.commit();
+        Ok(relabeled)
+    }
+
+    async fn expire_declassification(&self, request_id: Uuid, envelope: &ForensicEnvelope) -> Result<bool, DatabaseError> {
+        let mut tx = self.pool.begin().await?;
+        let expired = sqlx::query(
+            "UPDATE declassification_requests SET status = 'expired', decided_at = $2
+             WHERE request_id = $1 AND status = 'pending'",
+        )
+        .bind(request_id)
+        .bind(Utc::now())
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+        if expired == 0 {
+            return Ok(false);
+        }
+        let link = self.insert_chained_envelopes(&mut tx, [&mut envelope.clone()]).await?;
+        tx.commit().await?;
+        link.commit();
+        Ok(true)
     }
 }
 
@@ -555,13 +2973,196 @@ impl DatabaseContext {
             session_id,
             security_label,
             tenant_id,
+            permissions: Vec::new(),
+            include_deleted: false,
         }
     }
+
+    pub fn with_permissions(mut self, permissions: Vec<String>) -> Self {
+        self.permissions = permissions;
+        self
+    }
+
+    /// Let reads, queries and searches return soft-deleted entities too
+    pub fn including_deleted(mut self) -> Self {
+        self.include_deleted = true;
+        self
+    }
+
+    /// No Read Up: clearance dominates the entity's level and holds all its compartments
+    pub fn can_read(&self, entity: &SecureEntity) -> bool {
+        self.security_label.level.dominates(&entity.classification)
+            && entity.compartments.iter().all(|c| self.security_label.compartments.contains(c))
+    }
+
+    /// No Write Down: readable, and the entity's level dominates the clearance
+    pub fn can_write(&self, entity: &SecureEntity) -> bool {
+        self.can_read(entity) && entity.classification.dominates(&self.security_label.level)
+    }
+
+    /// Tenant isolation: the entity is shared, in the context's tenant, or the context is tenantless
+    pub fn in_tenant(&self, entity: &SecureEntity) -> bool {
+        entity.tenant_id.is_none() || entity.tenant_id == self.tenant_id || self.tenant_id.is_none()
+    }
+
+    /// Whether reads, queries and searches return `entity`
+    ///
+    /// In-memory counterpart of the SQL security filter: readable, in the context's tenant
+    /// or shared, and not soft-deleted unless `include_deleted` is set.
+    pub fn can_see(&self, entity: &SecureEntity) -> bool {
+        self.can_read(entity)
+            && self.in_tenant(entity)
+            && (self.include_deleted || entity.deleted_at.is_none())
+    }
+}
+
+/// Forensic record of a keyed create; a replay is recorded too, under its own event type
+///
+/// Like lifecycle records, it identifies the entity without copying its data.
+fn keyed_create_envelope(
+    entity: &SecureEntity,
+    idempotency_key: &str,
+    replayed: bool,
+    context: &DatabaseContext,
+) -> ForensicEnvelope {
+    let event_type = if replayed { "entity.create_replayed" } else { "entity.created" };
+    ForensicEnvelope::new(
+        Uuid::new_v4(),
+        event_type,
+        &context.user_id,
+        context.session_id,
+        entity.classification.clone(),
+        "create",
+    )
+    .with_resource(&format!("entity:{}", entity.id))
+    .with_state_change(None, Some(serde_json::json!({ "version": entity.version })))
+    .with_metadata(serde_json::json!({
+        "entity_type": entity.entity_type,
+        "compartments": entity.compartments,
+        "idempotency_key": idempotency_key,
+    }))
+}
+
+/// Check that `context` may place or release legal holds
+fn authorize_legal_hold(context: &DatabaseContext) -> Result<(), DatabaseError> {
+    if !has_permission(context, Permission::LegalHold) {
+        return Err(DatabaseError::LegalHold(format!("missing '{}' permission", LEGAL_HOLD_PERMISSION)));
+    }
+    Ok(())
+}
+
+fn legal_hold_from_row(row: &sqlx::postgres::PgRow) -> Result<LegalHold, DatabaseError> {
+    let scope: String = row.try_get("scope")?;
+    let target: String = row.try_get("target")?;
+    Ok(LegalHold {
+        hold_id: row.try_get("hold_id")?,
+        scope: LegalHoldScope::from_columns(&scope, &target)
+            .ok_or_else(|| DatabaseError::LegalHold(format!("unknown hold scope {}:{}", scope, target)))?,
+        reason: row.try_get("reason")?,
+        placed_by: row.try_get("placed_by")?,
+        placed_at: row.try_get("placed_at")?,
+        expires_at: row.try_get("expires_at")?,
+    })
+}
+
+fn legal_hold_envelope(event_type: &str, hold: &LegalHold, context: &DatabaseContext) -> ForensicEnvelope {
+    let (scope, target) = hold.scope.to_columns();
+    ForensicEnvelope::new(
+        Uuid::new_v4(),
+        event_type,
+        &context.user_id,
+        context.session_id,
+        ClassificationLevel::Confidential,
+        event_type.trim_start_matches("legal_hold."),
+    )
+    .with_resource(&format!("legal_hold:{}", hold.hold_id))
+    .with_metadata(serde_json::json!({
+        "scope": scope,
+        "target": target,
+        "reason": hold.reason,
+        "expires_at": hold.expires_at,
+        "tenant_id": context.tenant_id,
+    }))
+}
+
+/// Check that `context` may erase a data subject
+fn authorize_subject_erasure(context: &DatabaseContext) -> Result<(), DatabaseError> {
+    if !has_permission(context, Permission::Erase) {
+        return Err(DatabaseError::ErasureDenied(format!("missing '{}' permission", ERASE_PERMISSION)));
+    }
+    Ok(())
+}
+
+/// Check that `context` may relabel an entity from `current` to `new_label`
+///
+/// The operator needs the relabel permission and must dominate both labels; a change the
+/// new label doesn't dominate (lower level or dropped compartments) is a downgrade and
+/// also needs the downgrade permission.
+pub(crate) fn authorize_reclassification(
+    current: &SecurityLabel,
+    new_label: &SecurityLabel,
+    context: &DatabaseContext,
+) -> Result<(), DatabaseError> {
+    if !has_permission(context, Permission::Relabel) {
+        return Err(DatabaseError::ReclassificationDenied(format!("missing '{}' permission", RELABEL_PERMISSION)));
+    }
+    if !context.security_label.dominates(current) || !context.security_label.dominates(new_label) {
+        return Err(DatabaseError::ReclassificationDenied("insufficient clearance".to_string()));
+    }
+    if !new_label.dominates(current) && !has_permission(context, Permission::RelabelDowngrade) {
+        return Err(DatabaseError::ReclassificationDenied(format!(
+            "downgrade from {} to {} requires '{}' permission",
+            current.level, new_label.level, DOWNGRADE_PERMISSION
+        )));
+    }
+    Ok(())
+}
+
+/// Trim a `limit + 1` fetch to one page, with a cursor if the extra row was present
+fn split_page(mut entities: Vec<SecureEntity>, limit: i64) -> (Vec<SecureEntity>, Option<String>) {
+    let limit = limit.max(0) as usize;
+    if entities.len() <= limit {
+        return (entities, None);
+    }
+
+    entities.truncate(limit);
+    let next_cursor = entities.last().map(|last| EntityCursor::after(last).encode());
+    (entities, next_cursor)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    fn entity_at(created_at: DateTime<Utc>) -> SecureEntity {
+        SecureEntity {
+            id: Uuid::new_v4(),
+            entity_type: "note".to_string(),
+            data: serde_json::json!({}),
+            created_at,
+            updated_at: created_at,
+            created_by: "admin".to_string(),
+            updated_by: "admin".to_string(),
+            classification: ClassificationLevel::Internal,
+            compartments: vec![],
+            version: 1,
+            tenant_id: None,
+            deleted_at: None,
+            deleted_by: None,
+        }
+    }
+
+    /// In-memory stand-in for the keyset query: filter past the cursor, order, fetch limit + 1
+    fn fetch_page(table: &[SecureEntity], cursor: Option<&str>, limit: i64) -> (Vec<SecureEntity>, Option<String>) {
+        let cursor = cursor.map(|token| EntityCursor::decode(token).unwrap());
+        let mut rows: Vec<SecureEntity> = table.iter()
+            .filter(|entity| cursor.as_ref().map_or(true, |c| c.precedes(entity)))
+            .cloned()
+            .collect();
+        rows.sort_by_key(|entity| (entity.created_at, entity.id));
+        rows.truncate(limit as usize + 1);
+        split_page(rows, limit)
+    }
     
     #[test]
     fn test_database_context_creation() {
@@ -591,10 +3192,433 @@ mod tests {
             compartments: vec!["ALPHA".to_string()],
             version: 1,
             tenant_id: None,
+            deleted_at: None,
+            deleted_by: None,
         };
         
         assert_eq!(entity.entity_type, "user");
         assert_eq!(entity.classification, ClassificationLevel::Confidential);
         assert_eq!(entity.version, 1);
     }
+
+    fn operator(level: ClassificationLevel, permissions: &[&str]) -> DatabaseContext {
+        DatabaseContext::new(
+            "reviewer".to_string(),
+            Uuid::new_v4(),
+            SecurityLabel::new(level, vec!["ALPHA".to_string()]),
+            None,
+        )
+        .with_permissions(permissions.iter().map(|p| p.to_string()).collect())
+    }
+
+    #[test]
+    fn test_unauthorized_downgrade_rejected() {
+        let secret_alpha = SecurityLabel::new(ClassificationLevel::Secret, vec!["ALPHA".to_string()]);
+        let confidential_alpha = SecurityLabel::new(ClassificationLevel::Confidential, vec!["ALPHA".to_string()]);
+        let secret_plain = SecurityLabel::new(ClassificationLevel::Secret, vec![]);
+
+        // Relabel permission alone doesn't allow lowering the level or dropping a compartment
+        let relabeler = operator(ClassificationLevel::Secret, &[RELABEL_PERMISSION]);
+        assert!(matches!(
+            authorize_reclassification(&secret_alpha, &confidential_alpha, &relabeler),
+            Err(DatabaseError::ReclassificationDenied(_))
+        ));
+        assert!(authorize_reclassification(&secret_alpha, &secret_plain, &relabeler).is_err());
+        assert!(authorize_reclassification(&confidential_alpha, &secret_alpha, &relabeler).is_ok());
+
+        let declassifier = operator(ClassificationLevel::Secret, &[RELABEL_PERMISSION, DOWNGRADE_PERMISSION]);
+        assert!(authorize_reclassification(&secret_alpha, &confidential_alpha, &declassifier).is_ok());
+
+        // Downgrade permission doesn't stand in for relabel permission or clearance
+        let no_relabel = operator(ClassificationLevel::Secret, &[DOWNGRADE_PERMISSION]);
+        assert!(authorize_reclassification(&secret_alpha, &confidential_alpha, &no_relabel).is_err());
+        let uncleared = operator(ClassificationLevel::Confidential, &[RELABEL_PERMISSION, DOWNGRADE_PERMISSION]);
+        assert!(authorize_reclassification(&secret_alpha, &confidential_alpha, &uncleared).is_err());
+        assert!(authorize_reclassification(&confidential_alpha, &secret_alpha, &uncleared).is_err());
+    }
+
+    #[test]
+    fn test_cursor_round_trip() {
+        let cursor = EntityCursor::after(&entity_at(Utc::now()));
+        assert_eq!(EntityCursor::decode(&cursor.encode()), Some(cursor));
+        assert_eq!(EntityCursor::decode("not a cursor"), None);
+    }
+
+    #[test]
+    fn test_cursor_paging_stable_across_insert() {
+        let start = Utc::now();
+        let mut table: Vec<SecureEntity> = (0..5)
+            .map(|i| entity_at(start + chrono::Duration::seconds(i)))
+            .collect();
+
+        let (first_page, cursor) = fetch_page(&table, None, 2);
+        assert_eq!(first_page.len(), 2);
+        let cursor = cursor.expect("more rows remain");
+
+        // A concurrent insert lands inside the page we've already read
+        table.push(entity_at(start + chrono::Duration::milliseconds(500)));
+
+        let (second_page, cursor) = fetch_page(&table, Some(&cursor), 2);
+        let (third_page, cursor) = fetch_page(&table, cursor.as_deref(), 2);
+        assert!(cursor.is_none());
+
+        let seen: Vec<Uuid> = first_page.iter().chain(&second_page).chain(&third_page)
+            .map(|entity| entity.id)
+            .collect();
+        let original: Vec<Uuid> = table[..5].iter().map(|entity| entity.id).collect();
+
+        // Every original row exactly once, in order; the late insert is neither duplicated nor shifts pages
+        assert_eq!(seen, original);
+    }
+
+    fn labelled(classification: ClassificationLevel, data: serde_json::Value) -> SecureEntity {
+        SecureEntity { classification, data, ..entity_at(Utc::now()) }
+    }
+
+    /// In-memory stand-in for the tsvector match: any string in the data containing the term
+    fn search_table(table: &[SecureEntity], term: &str) -> Vec<SecureEntity> {
+        fn contains(value: &serde_json::Value, term: &str) -> bool {
+            match value {
+                serde_json::Value::String(text) => text.to_lowercase().contains(term),
+                serde_json::Value::Array(items) => items.iter().any(|item| contains(item, term)),
+                serde_json::Value::Object(fields) => fields.values().any(|field| contains(field, term)),
+                _ => false,
+            }
+        }
+        table.iter().filter(|entity| contains(&entity.data, term)).cloned().collect()
+    }
+
+    fn lazy_manager() -> DatabaseManager {
+        let pool = DatabaseManager::pool_options(&DbConfig::default())
+            .connect_lazy("postgresql://127.0.0.1:1/nodus")
+            .unwrap();
+        DatabaseManager::from_pool(pool, false, Arc::new(crate::security::InMemoryKeyProvider::new()))
+    }
+
+    #[tokio::test]
+    async fn test_search_matches_content() {
+        let manager = lazy_manager();
+        let analyst = DatabaseContext::new(
+            "analyst".to_string(),
+            Uuid::new_v4(),
+            SecurityLabel::new(ClassificationLevel::Confidential, vec![]),
+            None,
+        );
+
+        let sql = manager.search_query("outage", &analyst).sql().to_string();
+        assert!(sql.contains("websearch_to_tsquery('english', $1) AS search WHERE search_tsv @@ search"));
+        assert!(sql.contains("ORDER BY ts_rank(search_tsv, search) DESC, id LIMIT $"));
+        // Compartments are checked even for users holding none, so compartmented rows stay hidden
+        assert!(sql.contains(" AND compartments <@ $") && sql.contains("::text[] AND deleted_at IS NULL"), "{}", sql);
+
+        let table = vec![
+            labelled(ClassificationLevel::Internal, serde_json::json!({"title": "Quarterly outage report"})),
+            labelled(ClassificationLevel::Internal, serde_json::json!({"title": "Lunch menu"})),
+            labelled(ClassificationLevel::Confidential, serde_json::json!({"notes": ["Root cause of the OUTAGE"]})),
+        ];
+        let (found, denied) = manager.readable_only(search_table(&table, "outage"), &analyst);
+        let ids: Vec<Uuid> = found.iter().map(|entity| entity.id).collect();
+        assert_eq!(ids, vec![table[0].id, table[2].id]);
+        assert_eq!(denied, 0);
+    }
+
+    #[tokio::test]
+    async fn test_search_filters_above_clearance_matches() {
+        let manager = lazy_manager();
+        let analyst = DatabaseContext::new(
+            "analyst".to_string(),
+            Uuid::new_v4(),
+            SecurityLabel::new(ClassificationLevel::Confidential, vec![]),
+            None,
+        );
+
+        // The SQL only admits levels the analyst's clearance dominates
+        let lattice = classification_lattice::active();
+        let readable_levels = lattice.levels().iter()
+            .filter(|level| lattice.dominates(&analyst.security_label.level, level))
+            .count();
+        assert!(readable_levels < lattice.levels().len());
+        let sql = manager.search_query("outage", &analyst).sql().to_string();
+        assert_eq!(sql.matches("classification = ").count(), readable_levels);
+
+        // And a match that still comes back above clearance is dropped and counted
+        let table = vec![
+            labelled(ClassificationLevel::Internal, serde_json::json!({"title": "Outage report"})),
+            labelled(ClassificationLevel::Secret, serde_json::json!({"title": "Outage at the enclave"})),
+            SecureEntity {
+                compartments: vec!["ALPHA".to_string()],
+                ..labelled(ClassificationLevel::Internal, serde_json::json!({"title": "Outage in ALPHA"}))
+            },
+        ];
+        let (found, denied) = manager.readable_only(search_table(&table, "outage"), &analyst);
+        assert_eq!(found.iter().map(|entity| entity.id).collect::<Vec<_>>(), vec![table[0].id]);
+        assert_eq!(denied, 2);
+    }
+
+    #[tokio::test]
+    async fn test_soft_delete_and_restore_transitions() {
+        let manager = lazy_manager();
+        let owner = operator(ClassificationLevel::Internal, &[]);
+        let entity = entity_at(Utc::now());
+        let now = Utc::now();
+
+        assert!(manager.authorize_lifecycle(EntityLifecycle::SoftDelete, &entity, &owner).is_ok());
+        assert!(manager.authorize_lifecycle(EntityLifecycle::Restore, &entity, &owner).is_err());
+        let deleted = EntityLifecycle::SoftDelete.apply(&entity, &owner, now).unwrap();
+        assert_eq!(deleted.deleted_at, Some(now));
+        assert_eq!(deleted.deleted_by.as_deref(), Some("reviewer"));
+        assert_eq!(deleted.version, 2);
+
+        let envelope = EntityLifecycle::SoftDelete.envelope(&entity, Some(&deleted), &owner);
+        assert_eq!(envelope.event_type, "entity.soft_deleted");
+        assert_eq!(envelope.resource, Some(format!("entity:{}", entity.id)));
+        assert_eq!(envelope.before_state.unwrap()["deleted_at"], serde_json::Value::Null);
+        assert_eq!(envelope.after_state.unwrap()["deleted_by"], "reviewer");
+
+        assert!(matches!(
+            manager.authorize_lifecycle(EntityLifecycle::SoftDelete, &deleted, &owner),
+            Err(DatabaseError::LifecycleDenied(_))
+        ));
+        assert!(manager.authorize_lifecycle(EntityLifecycle::Restore, &deleted, &owner).is_ok());
+        let restored = EntityLifecycle::Restore.apply(&deleted, &owner, now).unwrap();
+        assert_eq!((restored.deleted_at, restored.deleted_by.clone(), restored.version), (None, None, 3));
+        let envelope = EntityLifecycle::Restore.envelope(&deleted, Some(&restored), &owner);
+        assert_eq!(envelope.event_type, "entity.restored");
+
+        // Reads hide soft-deleted rows unless the context asks for them
+        let mut query_builder = sqlx::QueryBuilder::new("SELECT id FROM entities WHERE id = $1");
+        manager.add_security_filter(&mut query_builder, &owner);
+        assert!(query_builder.sql().ends_with(" AND deleted_at IS NULL"));
+        let auditor = owner.clone().including_deleted();
+        let mut query_builder = sqlx::QueryBuilder::new("SELECT id FROM entities WHERE id = $1");
+        manager.add_security_filter(&mut query_builder, &auditor);
+        assert!(!query_builder.sql().contains("deleted_at"));
+    }
+
+    #[tokio::test]
+    async fn test_hard_delete_requires_erase_permission() {
+        let manager = lazy_manager();
+        let entity = labelled(ClassificationLevel::Internal, serde_json::json!({"name": "Jane Doe", "ssn": "123-45-6789"}));
+        let soft_deleted = EntityLifecycle::SoftDelete.apply(&entity, &operator(ClassificationLevel::Internal, &[]), Utc::now()).unwrap();
+
+        let owner = operator(ClassificationLevel::Internal, &[]);
+        assert!(matches!(
+            manager.authorize_lifecycle(EntityLifecycle::HardDelete, &entity, &owner),
+            Err(DatabaseError::LifecycleDenied(_))
+        ));
+
+        // Erasure applies to live and soft-deleted entities alike
+        let eraser = operator(ClassificationLevel::Internal, &[ERASE_PERMISSION]);
+        assert!(manager.authorize_lifecycle(EntityLifecycle::HardDelete, &entity, &eraser).is_ok());
+        assert!(manager.authorize_lifecycle(EntityLifecycle::HardDelete, &soft_deleted, &eraser).is_ok());
+        assert!(EntityLifecycle::HardDelete.apply(&entity, &eraser, Utc::now()).is_none());
+
+        // The forensic record identifies the entity without keeping the erased data
+        let envelope = EntityLifecycle::HardDelete.envelope(&entity, None, &eraser);
+        assert_eq!(envelope.event_type, "entity.hard_deleted");
+        assert_eq!(envelope.after_state, None);
+        assert!(!serde_json::to_string(&envelope).unwrap().contains("123-45-6789"));
+
+        // The permission doesn't override MAC
+        let secret = SecureEntity { classification: ClassificationLevel::Secret, ..entity.clone() };
+        assert!(manager.authorize_lifecycle(EntityLifecycle::HardDelete, &secret, &eraser).is_err());
+    }
+
+    #[test]
+    fn test_keyed_create_envelope_records_replays() {
+        let creator = operator(ClassificationLevel::Internal, &[]);
+        let entity = SecureEntity { classification: ClassificationLevel::Confidential, ..entity_at(Utc::now()) };
+
+        let created = keyed_create_envelope(&entity, "order-7f3a", false, &creator);
+        assert_eq!(created.event_type, "entity.created");
+        assert_eq!(created.classification, ClassificationLevel::Confidential);
+        assert_eq!(created.resource, Some(format!("entity:{}", entity.id)));
+        assert_eq!(created.metadata["idempotency_key"], "order-7f3a");
+        // The entity's data stays out of the log
+        assert!(created.after_state.unwrap().get("data").is_none());
+
+        let replayed = keyed_create_envelope(&entity, "order-7f3a", true, &creator);
+        assert_eq!(replayed.event_type, "entity.create_replayed");
+        assert_ne!(replayed.operation_id, created.operation_id);
+    }
+
+    #[tokio::test]
+    async fn test_lifecycle_refuses_other_tenants_entities() {
+        let manager = lazy_manager();
+        let tenant_b = SecureEntity { tenant_id: Some("tenant-b".to_string()), ..entity_at(Utc::now()) };
+        let soft_deleted = EntityLifecycle::SoftDelete.apply(&tenant_b, &operator(ClassificationLevel::Internal, &[]), Utc::now()).unwrap();
+        let mut tenant_a = operator(ClassificationLevel::Internal, &[ERASE_PERMISSION]);
+        tenant_a.tenant_id = Some("tenant-a".to_string());
+
+        for (lifecycle, entity) in [
+            (EntityLifecycle::SoftDelete, &tenant_b),
+            (EntityLifecycle::Restore, &soft_deleted),
+            (EntityLifecycle::HardDelete, &tenant_b),
+        ] {
+            assert!(matches!(
+                manager.authorize_lifecycle(lifecycle, entity, &tenant_a),
+                Err(DatabaseError::LifecycleDenied(_))
+            ));
+        }
+
+        // The owning tenant may still delete and erase it
+        let mut owner = tenant_a.clone();
+        owner.tenant_id = Some("tenant-b".to_string());
+        assert!(manager.authorize_lifecycle(EntityLifecycle::SoftDelete, &tenant_b, &owner).is_ok());
+        assert!(manager.authorize_lifecycle(EntityLifecycle::HardDelete, &tenant_b, &owner).is_ok());
+    }
+
+    #[test]
+    fn test_legal_hold_blocks_subject_erasure() {
+        let now = Utc::now();
+        let hold = |expires_at| LegalHold {
+            hold_id: Uuid::new_v4(),
+            scope: LegalHoldScope::Subject("subject-42".to_string()),
+            reason: "Litigation 2024-117".to_string(),
+            placed_by: "counsel".to_string(),
+            placed_at: now - chrono::Duration::days(10),
+            expires_at,
+        };
+
+        let holds = LegalHolds::active(vec![hold(None)], now);
+        assert!(matches!(holds.check_subject_erasure("subject-42"), Err(DatabaseError::LegalHold(_))));
+        assert!(holds.check_subject_erasure("subject-7").is_ok());
+
+        // A lapsed hold no longer protects the subject
+        let lapsed = LegalHolds::active(vec![hold(Some(now - chrono::Duration::days(1)))], now);
+        assert!(lapsed.check_subject_erasure("subject-42").is_ok());
+
+        // Only privileged roles may place or release holds
+        let privacy_officer = operator(ClassificationLevel::Secret, &[ERASE_PERMISSION]);
+        assert!(matches!(authorize_legal_hold(&privacy_officer), Err(DatabaseError::LegalHold(_))));
+        assert!(authorize_legal_hold(&operator(ClassificationLevel::Internal, &[LEGAL_HOLD_PERMISSION])).is_ok());
+    }
+
+    #[test]
+    fn test_subject_erasure_requires_erase_permission() {
+        let clerk = operator(ClassificationLevel::Secret, &[RELABEL_PERMISSION]);
+        assert!(matches!(authorize_subject_erasure(&clerk), Err(DatabaseError::ErasureDenied(_))));
+
+        let privacy_officer = operator(ClassificationLevel::Internal, &[ERASE_PERMISSION]);
+        assert!(authorize_subject_erasure(&privacy_officer).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_health_check_fails_fast_when_unreachable() {
+        // Nothing listens on port 1, so every connection attempt is refused
+        let pool = DatabaseManager::pool_options(&DbConfig::default())
+            .connect_lazy("postgresql://127.0.0.1:1/nodus")
+            .unwrap();
+        let manager = DatabaseManager::from_pool(pool, false, Arc::new(crate::security::InMemoryKeyProvider::new()));
+
+        let started = Instant::now();
+        let health = manager.health_check().await;
+
+        assert!(!health.healthy);
+        assert!(health.error.is_some());
+        assert_eq!(health.in_use_connections, 0);
+        assert!(started.elapsed() <= HEALTH_CHECK_TIMEOUT + Duration::from_millis(500));
+    }
+
+    #[tokio::test]
+    async fn test_classification_review_queue_is_bounded() {
+        let manager = lazy_manager();
+        let reviews: Vec<ClassificationReview> = (0..MAX_PENDING_CLASSIFICATION_REVIEWS + 2)
+            .map(|_| ClassificationReview {
+                entity_id: Uuid::new_v4(),
+                entity_type: "note".to_string(),
+                classification: ClassificationLevel::Confidential,
+                matched_rules: vec!["eu-partner".to_string(), "staff-only".to_string()],
+                flagged_at: Utc::now(),
+            })
+            .collect();
+        for review in &reviews {
+            manager.flag_for_review(review.clone()).await;
+        }
+
+        let pending = manager.pending_classification_reviews().await;
+        assert_eq!(pending.len(), MAX_PENDING_CLASSIFICATION_REVIEWS);
+        assert_eq!(pending[0].entity_id, reviews[2].entity_id);
+        assert_eq!(pending.last().unwrap().entity_id, reviews.last().unwrap().entity_id);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_cursor_rejected_before_querying() {
+        let manager = lazy_manager();
+        let analyst = DatabaseContext::new(
+            "analyst".to_string(),
+            Uuid::new_v4(),
+            SecurityLabel::new(ClassificationLevel::Internal, vec![]),
+            None,
+        );
+
+        let result = manager.query_entities_after(None, None, &analyst, Some("not-a-cursor"), 10).await;
+        assert!(matches!(result, Err(DatabaseError::InvalidCursor(ref token)) if token == "not-a-cursor"));
+    }
+
+    /// Runs against a migrated database: `DATABASE_URL=... cargo test -- --ignored`
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    #[ignore = "needs a migrated Postgres at DATABASE_URL"]
+    async fn test_postgres_same_idempotency_key_creates_one_entity() {
+        let manager = Arc::new(DatabaseManager::new().await.unwrap());
+        let creator = DatabaseContext::new(
+            format!("idempotency-{}", Uuid::new_v4()),
+            Uuid::new_v4(),
+            SecurityLabel::new(ClassificationLevel::Internal, vec![]),
+            None,
+        );
+
+        // Concurrent attempts contend on the key's primary key row
+        let attempts = 8;
+        let start = Arc::new(tokio::sync::Barrier::new(attempts));
+        let tasks: Vec<_> = (0..attempts)
+            .map(|_| {
+                let (manager, creator, start) = (manager.clone(), creator.clone(), start.clone());
+                tokio::spawn(async move {
+                    start.wait().await;
+                    manager.create_entity_idempotent("order", serde_json::json!({"total": 40}), "order-7f3a", None, &creator).await
+                })
+            })
+            .collect();
+        let mut ids = Vec::new();
+        for task in tasks {
+            ids.push(task.await.unwrap().unwrap().0.id);
+        }
+        assert!(ids.iter().all(|id| *id == ids[0]));
+
+        let rows: i64 = sqlx::query_scalar("SELECT count(*) FROM entities WHERE created_by = $1")
+            .bind(&creator.user_id)
+            .fetch_one(&manager.pool)
+            .await
+            .unwrap();
+        assert_eq!(rows, 1);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_config_rejected_before_connecting() {
+        let config = DbConfig {
+            max_connections: 2,
+            min_connections: 5,
+            ..DbConfig::default()
+        };
+        assert!(matches!(config.validate(), Err(DatabaseError::InvalidConfig(_))));
+
+        let result = DatabaseManager::with_config(config).await;
+        assert!(matches!(result, Err(DatabaseError::InvalidConfig(_))));
+    }
+
+    #[test]
+    fn test_config_tls_mode_applied() {
+        let config = DbConfig {
+            tls_mode: Some(DbTlsMode::VerifyFull),
+            ..DbConfig::default()
+        };
+        let options = DatabaseManager::connect_options(&config).unwrap();
+        assert!(matches!(options.get_ssl_mode(), PgSslMode::VerifyFull));
+
+        let parsed: DbConfig = toml::from_str("max_connections = 8\ntls_mode = \"require\"").unwrap();
+        assert_eq!(parsed.max_connections, 8);
+        assert_eq!(parsed.tls_mode, Some(DbTlsMode::Require));
+        assert_eq!(parsed.url, DEFAULT_DATABASE_URL);
+    }
 }
@@ -0,0 +1,443 @@
+// src-tauri/src/database/declassification.rs
+// Declassification Workflow - Two-person control over label downgrades
+// One cleared operator proposes a downgrade, a second independent one approves it, and only then are the entities relabeled
+
+use chrono::{DateTime, Duration, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::database_mod::authorize_reclassification;
+use super::{DatabaseContext, DatabaseError, SecureEntity};
+use crate::observability::ForensicEnvelope;
+use crate::security::SecurityLabel;
+
+/// How long a proposal waits for its second approval by default
+pub const DEFAULT_APPROVAL_WINDOW_HOURS: i64 = 24;
+
+/// A proposed downgrade waiting for its second approval
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DeclassificationRequest {
+    pub request_id: Uuid,
+    pub entity_ids: Vec<Uuid>,
+    pub target_label: SecurityLabel,
+    pub justification: String,
+    pub proposed_by: String,
+    pub tenant_id: Option<String>,
+    /// Least upper bound of the entities' labels when proposed; the request is logged at this label
+    pub current_label: SecurityLabel,
+    pub proposed_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl DeclassificationRequest {
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        now >= self.expires_at
+    }
+}
+
+/// Storage operations the workflow needs; implemented by `DatabaseManager`
+#[async_trait::async_trait]
+pub trait DeclassificationStore: Send + Sync {
+    /// Current label of an entity `context` can read; `None` if missing or unreadable
+    async fn entity_label(&self, entity_id: Uuid, context: &DatabaseContext) -> Result<Option<SecurityLabel>, DatabaseError>;
+
+    /// Store a new pending request together with the envelope recording its proposal
+    async fn insert_declassification(
+        &self,
+        request: &DeclassificationRequest,
+        envelope: &ForensicEnvelope,
+    ) -> Result<(), DatabaseError>;
+
+    /// A request still awaiting approval, whether or not its window has passed
+    async fn pending_declassification(&self, request_id: Uuid) -> Result<Option<DeclassificationRequest>, DatabaseError>;
+
+    /// Every request still awaiting approval, oldest first
+    async fn pending_declassifications(&self) -> Result<Vec<DeclassificationRequest>, DatabaseError>;
+
+    /// Claim the pending request, relabel its entities on behalf of `approver` and store
+    /// `envelope`, all in one transaction
+    ///
+    /// Fails with `DeclassificationNotFound` if the request is no longer pending, so two
+    /// approvers can't both apply it.
+    async fn apply_declassification(
+        &self,
+        request: &DeclassificationRequest,
+        approver: &DatabaseContext,
+        envelope: &ForensicEnvelope,
+    ) -> Result<Vec<SecureEntity>, DatabaseError>;
+
+    /// Mark a pending request expired and store `envelope` with it; false if it was no longer pending
+    async fn expire_declassification(&self, request_id: Uuid, envelope: &ForensicEnvelope) -> Result<bool, DatabaseError>;
+}
+
+/// Two-person declassification: proposals are held until a different cleared operator
+/// approves them, or lapse after the approval window
+///
+/// Proposer and approver must each pass `authorize_reclassification` for every entity,
+/// i.e. hold `RELABEL_PERMISSION` and `DOWNGRADE_PERMISSION` and be cleared for both labels.
+/// Requests are kept in the store, so they survive restarts, and each proposal, approval
+/// and expiry is recorded in the forensic log in the same transaction as its change.
+pub struct DeclassificationWorkflow {
+    store: Arc<dyn DeclassificationStore>,
+    approval_window: Duration,
+}
+
+impl std::fmt::Debug for DeclassificationWorkflow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DeclassificationWorkflow")
+            .field("approval_window", &self.approval_window)
+            .finish_non_exhaustive()
+    }
+}
+
+impl DeclassificationWorkflow {
+    pub fn new(store: Arc<dyn DeclassificationStore>) -> Self {
+        Self {
+            store,
+            approval_window: Duration::hours(DEFAULT_APPROVAL_WINDOW_HOURS),
+        }
+    }
+
+    pub fn with_approval_window(mut self, approval_window: Duration) -> Self {
+        self.approval_window = approval_window;
+        self
+    }
+
+    /// Propose downgrading `entity_ids` to `target_label`
+    pub async fn propose(
+        &self,
+        entity_ids: Vec<Uuid>,
+        target_label: SecurityLabel,
+        justification: String,
+        context: &DatabaseContext,
+    ) -> Result<DeclassificationRequest, DatabaseError> {
+        self.propose_at(entity_ids, target_label, justification, context, Utc::now()).await
+    }
+
+    pub async fn propose_at(
+        &self,
+        entity_ids: Vec<Uuid>,
+        target_label: SecurityLabel,
+        justification: String,
+        context: &DatabaseContext,
+        now: DateTime<Utc>,
+    ) -> Result<DeclassificationRequest, DatabaseError> {
+        if entity_ids.is_empty() {
+            return Err(DatabaseError::DeclassificationDenied("no entities given".to_string()));
+        }
+        if justification.trim().is_empty() {
+            return Err(DatabaseError::DeclassificationDenied("a justification is required".to_string()));
+        }
+
+        let current_label = self.authorize(&entity_ids, &target_label, context).await?;
+        let request = DeclassificationRequest {
+            request_id: Uuid::new_v4(),
+            entity_ids,
+            target_label,
+            justification,
+            proposed_by: context.user_id.clone(),
+            tenant_id: context.tenant_id.clone(),
+            current_label,
+            proposed_at: now,
+            expires_at: now + self.approval_window,
+        };
+
+        self.store.insert_declassification(&request, &envelope(&request, "declassification.proposed", context)).await?;
+        Ok(request)
+    }
+
+    /// Second approval of a pending request; the relabel is applied before this returns
+    pub async fn approve(&self, request_id: Uuid, context: &DatabaseContext) -> Result<Vec<SecureEntity>, DatabaseError> {
+        self.approve_at(request_id, context, Utc::now()).await
+    }
+
+    pub async fn approve_at(
+        &self,
+        request_id: Uuid,
+        context: &DatabaseContext,
+        now: DateTime<Utc>,
+    ) -> Result<Vec<SecureEntity>, DatabaseError> {
+        let request = self.store.pending_declassification(request_id).await?
+            .ok_or(DatabaseError::DeclassificationNotFound(request_id))?;
+        if request.is_expired(now) {
+            self.expire_at(now).await?;
+            return Err(DatabaseError::DeclassificationExpired(request_id));
+        }
+        if context.user_id == request.proposed_by {
+            return Err(DatabaseError::DeclassificationDenied(
+                "the proposer cannot approve their own request".to_string(),
+            ));
+        }
+        if context.tenant_id != request.tenant_id {
+            return Err(DatabaseError::DeclassificationDenied("approver is in another tenant".to_string()));
+        }
+        // Against the labels as they are now, in case an entity changed since the proposal
+        self.authorize(&request.entity_ids, &request.target_label, context).await?;
+
+        // Nothing is relabeled unless the approval is recorded with it
+        self.store
+            .apply_declassification(&request, context, &envelope(&request, "declassification.approved", context))
+            .await
+    }
+
+    /// Pending requests of `context`'s tenant still within their approval window
+    pub async fn pending(&self, context: &DatabaseContext, now: DateTime<Utc>) -> Result<Vec<DeclassificationRequest>, DatabaseError> {
+        Ok(self.store.pending_declassifications().await?
+            .into_iter()
+            .filter(|request| request.tenant_id == context.tenant_id && !request.is_expired(now))
+            .collect())
+    }
+
+    pub async fn get(&self, request_id: Uuid, now: DateTime<Utc>) -> Result<Option<DeclassificationRequest>, DatabaseError> {
+        Ok(self.store.pending_declassification(request_id).await?
+            .filter(|request| !request.is_expired(now)))
+    }
+
+    /// Close requests whose approval window has passed, recording each, and return their ids
+    pub async fn expire_at(&self, now: DateTime<Utc>) -> Result<Vec<Uuid>, DatabaseError> {
+        let mut expired = Vec::new();
+        for request in self.store.pending_declassifications().await? {
+            if !request.is_expired(now) {
+                continue;
+            }
+            let envelope = ForensicEnvelope::new(
+                Uuid::new_v4(),
+                "declassification.expired",
+                "system",
+                Uuid::nil(),
+                request.current_label.level.clone(),
+                "declassify_expire",
+            )
+            .with_resource(&format!("declassification:{}", request.request_id))
+            .with_metadata(serde_json::json!({
+                "request_id": request.request_id,
+                "proposed_by": request.proposed_by,
+                "expires_at": request.expires_at,
+            }));
+            // Skipped if an approver claimed it in the meantime
+            if self.store.expire_declassification(request.request_id, &envelope).await? {
+                expired.push(request.request_id);
+            }
+        }
+        Ok(expired)
+    }
+
+    /// Check `context` may downgrade every entity to `target_label`; returns the lub of their labels
+    async fn authorize(
+        &self,
+        entity_ids: &[Uuid],
+        target_label: &SecurityLabel,
+        context: &DatabaseContext,
+    ) -> Result<SecurityLabel, DatabaseError> {
+        let mut current_label = target_label.clone();
+        for &entity_id in entity_ids {
+            let current = self.store.entity_label(entity_id, context).await?
+                .ok_or_else(|| DatabaseError::DeclassificationDenied(
+                    format!("entity {} not found or not readable", entity_id)
+                ))?;
+            if target_label.dominates(&current) {
+                return Err(DatabaseError::DeclassificationDenied(format!(
+                    "entity {} is already at or below the target label; use reclassify_entities",
+                    entity_id
+                )));
+            }
+            authorize_reclassification(&current, target_label, context)
+                .map_err(|error| DatabaseError::DeclassificationDenied(error.to_string()))?;
+            current_label = current_label.lub(&current);
+        }
+        Ok(current_label)
+    }
+}
+
+fn envelope(request: &DeclassificationRequest, event_type: &str, context: &DatabaseContext) -> ForensicEnvelope {
+    let action = event_type.trim_start_matches("declassification.");
+    ForensicEnvelope::new(
+        request.request_id,
+        event_type,
+        &context.user_id,
+        context.session_id,
+        request.current_label.level.clone(),
+        &format!("declassify_{}", action),
+    )
+    .with_resource(&format!("declassification:{}", request.request_id))
+    .with_metadata(serde_json::json!({
+        "request_id": request.request_id,
+        "entity_ids": request.entity_ids,
+        "from": request.current_label.level,
+        "to": request.target_label.level,
+        "target_compartments": request.target_label.compartments,
+        "justification": request.justification,
+        "proposed_by": request.proposed_by,
+        "expires_at": request.expires_at,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::{DOWNGRADE_PERMISSION, RELABEL_PERMISSION};
+    use crate::security::ClassificationLevel;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    /// In-memory labels and requests standing in for the entity and request tables
+    #[derive(Default)]
+    struct MemoryStore {
+        labels: Mutex<HashMap<Uuid, SecurityLabel>>,
+        pending: Mutex<HashMap<Uuid, DeclassificationRequest>>,
+        log: Mutex<Vec<ForensicEnvelope>>,
+    }
+
+    impl MemoryStore {
+        fn level(&self, entity_id: Uuid) -> ClassificationLevel {
+            self.labels.lock().unwrap()[&entity_id].level.clone()
+        }
+
+        fn events(&self) -> Vec<String> {
+            self.log.lock().unwrap().iter().map(|envelope| envelope.event_type.clone()).collect()
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl DeclassificationStore for MemoryStore {
+        async fn entity_label(&self, entity_id: Uuid, context: &DatabaseContext) -> Result<Option<SecurityLabel>, DatabaseError> {
+            Ok(self.labels.lock().unwrap().get(&entity_id)
+                .filter(|label| context.security_label.dominates(label))
+                .cloned())
+        }
+
+        async fn insert_declassification(
+            &self,
+            request: &DeclassificationRequest,
+            envelope: &ForensicEnvelope,
+        ) -> Result<(), DatabaseError> {
+            self.pending.lock().unwrap().insert(request.request_id, request.clone());
+            self.log.lock().unwrap().push(envelope.clone());
+            Ok(())
+        }
+
+        async fn pending_declassification(&self, request_id: Uuid) -> Result<Option<DeclassificationRequest>, DatabaseError> {
+            Ok(self.pending.lock().unwrap().get(&request_id).cloned())
+        }
+
+        async fn pending_declassifications(&self) -> Result<Vec<DeclassificationRequest>, DatabaseError> {
+            let mut requests: Vec<DeclassificationRequest> = self.pending.lock().unwrap().values().cloned().collect();
+            requests.sort_by_key(|request| request.proposed_at);
+            Ok(requests)
+        }
+
+        async fn apply_declassification(
+            &self,
+            request: &DeclassificationRequest,
+            _approver: &DatabaseContext,
+            envelope: &ForensicEnvelope,
+        ) -> Result<Vec<SecureEntity>, DatabaseError> {
+            if self.pending.lock().unwrap().remove(&request.request_id).is_none() {
+                return Err(DatabaseError::DeclassificationNotFound(request.request_id));
+            }
+            let mut labels = self.labels.lock().unwrap();
+            for entity_id in &request.entity_ids {
+                labels.insert(*entity_id, request.target_label.clone());
+            }
+            self.log.lock().unwrap().push(envelope.clone());
+            Ok(Vec::new())
+        }
+
+        async fn expire_declassification(&self, request_id: Uuid, envelope: &ForensicEnvelope) -> Result<bool, DatabaseError> {
+            if self.pending.lock().unwrap().remove(&request_id).is_none() {
+                return Ok(false);
+            }
+            self.log.lock().unwrap().push(envelope.clone());
+            Ok(true)
+        }
+    }
+
+    fn operator(user_id: &str, permissions: &[&str]) -> DatabaseContext {
+        DatabaseContext::new(
+            user_id.to_string(),
+            Uuid::new_v4(),
+            SecurityLabel::new(ClassificationLevel::Secret, vec!["ALPHA".to_string()]),
+            None,
+        )
+        .with_permissions(permissions.iter().map(|p| p.to_string()).collect())
+    }
+
+    fn setup() -> (Arc<MemoryStore>, DeclassificationWorkflow, Uuid) {
+        let store = Arc::new(MemoryStore::default());
+        let entity_id = Uuid::new_v4();
+        store.labels.lock().unwrap()
+            .insert(entity_id, SecurityLabel::new(ClassificationLevel::Secret, vec!["ALPHA".to_string()]));
+        let workflow = DeclassificationWorkflow::new(store.clone());
+        (store, workflow, entity_id)
+    }
+
+    fn confidential() -> SecurityLabel {
+        SecurityLabel::new(ClassificationLevel::Confidential, vec![])
+    }
+
+    #[tokio::test]
+    async fn test_self_approval_rejected() {
+        let (store, workflow, entity_id) = setup();
+        let alice = operator("alice", &[RELABEL_PERMISSION, DOWNGRADE_PERMISSION]);
+
+        let request = workflow.propose(vec![entity_id], confidential(), "cleared for release".to_string(), &alice).await.unwrap();
+        assert!(matches!(
+            workflow.approve(request.request_id, &alice).await,
+            Err(DatabaseError::DeclassificationDenied(_))
+        ));
+
+        // The approver needs the role as well
+        let bob = operator("bob", &[RELABEL_PERMISSION]);
+        assert!(matches!(
+            workflow.approve(request.request_id, &bob).await,
+            Err(DatabaseError::DeclassificationDenied(_))
+        ));
+
+        // So does the proposer
+        assert!(workflow.propose(vec![entity_id], confidential(), "release".to_string(), &bob).await.is_err());
+
+        assert_eq!(store.level(entity_id), ClassificationLevel::Secret);
+        assert_eq!(workflow.pending(&alice, Utc::now()).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_relabel_applies_only_after_second_approval() {
+        let (store, workflow, entity_id) = setup();
+        let alice = operator("alice", &[RELABEL_PERMISSION, DOWNGRADE_PERMISSION]);
+        let bob = operator("bob", &[RELABEL_PERMISSION, DOWNGRADE_PERMISSION]);
+
+        let request = workflow.propose(vec![entity_id], confidential(), "cleared for release".to_string(), &alice).await.unwrap();
+        assert_eq!(store.level(entity_id), ClassificationLevel::Secret);
+        assert_eq!(workflow.get(request.request_id, Utc::now()).await.unwrap().unwrap().proposed_by, "alice");
+
+        workflow.approve(request.request_id, &bob).await.unwrap();
+        assert_eq!(store.level(entity_id), ClassificationLevel::Confidential);
+        assert!(workflow.pending(&alice, Utc::now()).await.unwrap().is_empty());
+        assert!(matches!(
+            workflow.approve(request.request_id, &bob).await,
+            Err(DatabaseError::DeclassificationNotFound(_))
+        ));
+        assert_eq!(store.events(), vec!["declassification.proposed", "declassification.approved"]);
+    }
+
+    #[tokio::test]
+    async fn test_unapproved_request_expires() {
+        let (store, workflow, entity_id) = setup();
+        let workflow = workflow.with_approval_window(Duration::hours(1));
+        let alice = operator("alice", &[RELABEL_PERMISSION, DOWNGRADE_PERMISSION]);
+        let bob = operator("bob", &[RELABEL_PERMISSION, DOWNGRADE_PERMISSION]);
+
+        let request = workflow.propose(vec![entity_id], confidential(), "cleared for release".to_string(), &alice).await.unwrap();
+        let later = request.proposed_at + Duration::hours(2);
+        assert!(workflow.pending(&alice, later).await.unwrap().is_empty());
+        assert!(matches!(
+            workflow.approve_at(request.request_id, &bob, later).await,
+            Err(DatabaseError::DeclassificationExpired(_))
+        ));
+
+        assert_eq!(store.level(entity_id), ClassificationLevel::Secret);
+        assert_eq!(store.events(), vec!["declassification.proposed", "declassification.expired"]);
+    }
+}
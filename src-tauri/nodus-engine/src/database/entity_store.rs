@@ -0,0 +1,679 @@
+// src-tauri/src/database/entity_store.rs
+// Entity Store - Storage abstraction behind entity CRUD, with Postgres and in-memory backends
+// Both backends enforce the same MAC rules, so security logic can be unit-tested without a database
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::{Mutex, RwLock};
+use uuid::Uuid;
+
+use super::change_feed::{EntityChange, EntityChangeFeed, EntityChangeOperation};
+use super::{
+    BatchEntityOperation, DatabaseContext, DatabaseError, EntityLifecycle, QueryFilter, SecureEntity, SecureQueryResult,
+    MAX_IDEMPOTENCY_KEY_LEN,
+};
+use crate::multi_tenant::TenantGate;
+use crate::security::{ClassificationClassifier, SecurityLabel};
+
+/// Entity create/read/update/delete/query with MAC enforcement
+///
+/// `DatabaseManager` is the Postgres backend; `InMemoryEntityStore` reproduces its
+/// semantics for tests. Callers that only need entity CRUD should take `Arc<dyn EntityStore>`.
+#[async_trait::async_trait]
+pub trait EntityStore: Send + Sync {
+    /// Create an entity labelled by policy, never below the creator's clearance
+    async fn create_entity(
+        &self,
+        entity_type: &str,
+        data: Value,
+        context: &DatabaseContext,
+    ) -> Result<SecureEntity, DatabaseError>;
+
+    /// Create an entity once per client key; a retry with the same key returns the first entity
+    ///
+    /// Keys are scoped to the creating user and tenant. Reusing a key for another entity
+    /// type, or after its entity was deleted, is an `IdempotencyKey` error.
+    async fn create_entity_idempotent(
+        &self,
+        entity_type: &str,
+        data: Value,
+        idempotency_key: &str,
+        context: &DatabaseContext,
+    ) -> Result<SecureEntity, DatabaseError>;
+
+    /// `None` if the entity is missing or hidden from the context
+    async fn read_entity(&self, entity_id: Uuid, context: &DatabaseContext) -> Result<Option<SecureEntity>, DatabaseError>;
+
+    /// Merge `updates` into the entity's data; `None` if missing, denied, or modified concurrently
+    async fn update_entity(
+        &self,
+        entity_id: Uuid,
+        updates: Value,
+        context: &DatabaseContext,
+    ) -> Result<Option<SecureEntity>, DatabaseError>;
+
+    /// Store a merge of concurrent edits: replace the data of the entity at `expected_version`
+    /// with `data` and label it `label`, which may only raise it
+    ///
+    /// `None` if missing, denied, or modified since `expected_version`.
+    async fn merge_entity(
+        &self,
+        entity_id: Uuid,
+        expected_version: i64,
+        data: Value,
+        label: &SecurityLabel,
+        context: &DatabaseContext,
+    ) -> Result<Option<SecureEntity>, DatabaseError>;
+
+    /// Soft-delete; `false` if missing, already deleted, or denied
+    async fn delete_entity(&self, entity_id: Uuid, context: &DatabaseContext) -> Result<bool, DatabaseError>;
+
+    /// Entities visible to the context, ordered by `(created_at, id)`
+    async fn query_entities(
+        &self,
+        entity_type: Option<&str>,
+        filter: Option<&QueryFilter>,
+        context: &DatabaseContext,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> Result<SecureQueryResult, DatabaseError>;
+
+    /// Run the checks `operation` would face and report the outcome without applying it
+    async fn dry_run(&self, operation: &BatchEntityOperation, context: &DatabaseContext) -> Result<DryRunOutcome, DatabaseError>;
+}
+
+/// Would-be outcome of an entity operation that was checked but not applied
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DryRunOutcome {
+    pub allowed: bool,
+    pub reason: Option<String>,
+    /// The entity as the operation would leave it
+    pub entity: Option<SecureEntity>,
+}
+
+impl DryRunOutcome {
+    pub fn denied(reason: impl Into<String>) -> Self {
+        Self { allowed: false, reason: Some(reason.into()), entity: None }
+    }
+}
+
+/// Reject idempotency keys that are empty or longer than `MAX_IDEMPOTENCY_KEY_LEN` bytes
+pub(crate) fn validate_idempotency_key(idempotency_key: &str) -> Result<(), DatabaseError> {
+    if idempotency_key.is_empty() || idempotency_key.len() > MAX_IDEMPOTENCY_KEY_LEN {
+        return Err(DatabaseError::IdempotencyKey(format!(
+            "must be 1 to {} bytes long",
+            MAX_IDEMPOTENCY_KEY_LEN
+        )));
+    }
+    Ok(())
+}
+
+/// Result of a create retried under a key that is already claimed
+///
+/// `existing` is the key's entity as visible to the creator, `None` if it has since been
+/// deleted or relabelled out of reach.
+pub(crate) fn replay_create(
+    existing: Option<SecureEntity>,
+    entity_type: &str,
+    idempotency_key: &str,
+) -> Result<SecureEntity, DatabaseError> {
+    match existing {
+        Some(entity) if entity.entity_type == entity_type => Ok(entity),
+        Some(entity) => Err(DatabaseError::IdempotencyKey(format!(
+            "'{}' was already used to create a {}",
+            idempotency_key, entity.entity_type
+        ))),
+        None => Err(DatabaseError::IdempotencyKey(format!(
+            "'{}' belongs to an entity that is no longer available",
+            idempotency_key
+        ))),
+    }
+}
+
+/// Check `operation` against `existing`, the stored entity it targets, without applying it
+///
+/// Mirrors the MAC and lifecycle checks of the real operations. Entities the context can't
+/// see are reported as not found, as the real operations do, so a dry run leaks nothing.
+pub(crate) fn preview_operation(
+    operation: &BatchEntityOperation,
+    existing: Option<&SecureEntity>,
+    label_for_create: impl FnOnce(&str, &Value) -> SecurityLabel,
+    context: &DatabaseContext,
+    now: DateTime<Utc>,
+) -> DryRunOutcome {
+    let visible = |entity_id: &Uuid| existing
+        .filter(|entity| entity.id == *entity_id && entity.deleted_at.is_none() && context.can_see(entity))
+        .ok_or_else(|| format!("Entity {} not found or access denied", entity_id));
+
+    let outcome = match operation {
+        BatchEntityOperation::Create { entity_type, data, .. } => {
+            let label = label_for_create(entity_type, data);
+            Ok(SecureEntity::created(Uuid::new_v4(), entity_type, data.clone(), label, context, now))
+        }
+        BatchEntityOperation::Update { entity_id, updates } => visible(entity_id).and_then(|entity| {
+            if !context.can_write(entity) {
+                return Err(format!("Write access to entity {} denied", entity_id));
+            }
+            Ok(entity.updated(updates.clone(), context, now))
+        }),
+        BatchEntityOperation::Delete { entity_id } => visible(entity_id).and_then(|entity| {
+            EntityLifecycle::SoftDelete.authorize(entity, context).map_err(|e| e.to_string())?;
+            EntityLifecycle::SoftDelete.apply(entity, context, now)
+                .ok_or_else(|| format!("Entity {} cannot be deleted", entity_id))
+        }),
+    };
+
+    match outcome {
+        Ok(entity) => DryRunOutcome { allowed: true, reason: None, entity: Some(entity) },
+        Err(reason) => DryRunOutcome::denied(reason),
+    }
+}
+
+/// Claimed creation key: (tenant, creator, key)
+type IdempotencyScope = (Option<String>, String, String);
+
+/// Entity store held in a map, for tests
+///
+/// Applies the SQL security filter's rules in memory: No Read Up and compartments on reads,
+/// No Write Down on writes, tenant isolation and soft-delete hiding. Operations for inactive
+/// tenants are refused, as by `DatabaseManager`. Writes are published to the change feed, if set.
+#[derive(Debug, Default)]
+pub struct InMemoryEntityStore {
+    entities: RwLock<HashMap<Uuid, SecureEntity>>,
+    idempotency_keys: Mutex<HashMap<IdempotencyScope, Uuid>>,
+    classifier: ClassificationClassifier,
+    tenant_gate: Arc<TenantGate>,
+    change_feed: Option<Arc<EntityChangeFeed>>,
+}
+
+impl InMemoryEntityStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use `classifier` to label new entities
+    pub fn with_classifier(mut self, classifier: ClassificationClassifier) -> Self {
+        self.classifier = classifier;
+        self
+    }
+
+    /// Consult `tenant_gate` for tenant statuses
+    pub fn with_tenant_gate(mut self, tenant_gate: Arc<TenantGate>) -> Self {
+        self.tenant_gate = tenant_gate;
+        self
+    }
+
+    /// Publish each write to `change_feed`, as the Postgres trigger does for `DatabaseManager`
+    pub fn with_change_feed(mut self, change_feed: Arc<EntityChangeFeed>) -> Self {
+        self.change_feed = Some(change_feed);
+        self
+    }
+
+    pub fn tenant_gate(&self) -> &Arc<TenantGate> {
+        &self.tenant_gate
+    }
+
+    fn publish(&self, entity: &SecureEntity, operation: EntityChangeOperation) {
+        if let Some(change_feed) = &self.change_feed {
+            change_feed.publish(EntityChange::of(entity, operation));
+        }
+    }
+
+    async fn check_tenant(&self, context: &DatabaseContext) -> Result<(), DatabaseError> {
+        Ok(self.tenant_gate.check(context.tenant_id.as_deref(), &context.permissions).await?)
+    }
+
+    /// Store `entity` exactly as given, without MAC checks (for seeding labelled fixtures)
+    pub async fn insert(&self, entity: SecureEntity) {
+        self.entities.write().await.insert(entity.id, entity);
+    }
+
+    pub async fn len(&self) -> usize {
+        self.entities.read().await.len()
+    }
+
+    pub async fn is_empty(&self) -> bool {
+        self.len().await == 0
+    }
+}
+
+#[async_trait::async_trait]
+impl EntityStore for InMemoryEntityStore {
+    async fn create_entity(
+        &self,
+        entity_type: &str,
+        data: Value,
+        context: &DatabaseContext,
+    ) -> Result<SecureEntity, DatabaseError> {
+        self.check_tenant(context).await?;
+        let suggestion = self.classifier.classify(entity_type, &data, &context.security_label);
+        let entity = SecureEntity::created(Uuid::new_v4(), entity_type, data, suggestion.label, context, Utc::now());
+        self.insert(entity.clone()).await;
+        self.publish(&entity, EntityChangeOperation::Create);
+        Ok(entity)
+    }
+
+    async fn create_entity_idempotent(
+        &self,
+        entity_type: &str,
+        data: Value,
+        idempotency_key: &str,
+        context: &DatabaseContext,
+    ) -> Result<SecureEntity, DatabaseError> {
+        self.check_tenant(context).await?;
+        validate_idempotency_key(idempotency_key)?;
+
+        // Held until the entity is stored, like the key's row lock in Postgres
+        let mut keys = self.idempotency_keys.lock().await;
+        let scope = (context.tenant_id.clone(), context.user_id.clone(), idempotency_key.to_string());
+        if let Some(entity_id) = keys.get(&scope) {
+            let existing = self.entities.read().await
+                .get(entity_id)
+                .filter(|entity| entity.deleted_at.is_none() && context.can_see(entity))
+                .cloned();
+            return replay_create(existing, entity_type, idempotency_key);
+        }
+
+        let suggestion = self.classifier.classify(entity_type, &data, &context.security_label);
+        let entity = SecureEntity::created(Uuid::new_v4(), entity_type, data, suggestion.label, context, Utc::now());
+        keys.insert(scope, entity.id);
+        self.insert(entity.clone()).await;
+        self.publish(&entity, EntityChangeOperation::Create);
+        Ok(entity)
+    }
+
+    async fn read_entity(&self, entity_id: Uuid, context: &DatabaseContext) -> Result<Option<SecureEntity>, DatabaseError> {
+        self.check_tenant(context).await?;
+        let entities = self.entities.read().await;
+        Ok(entities.get(&entity_id).filter(|entity| context.can_see(entity)).cloned())
+    }
+
+    async fn update_entity(
+        &self,
+        entity_id: Uuid,
+        updates: Value,
+        context: &DatabaseContext,
+    ) -> Result<Option<SecureEntity>, DatabaseError> {
+        self.check_tenant(context).await?;
+        let mut entities = self.entities.write().await;
+        let existing = match entities.get(&entity_id) {
+            Some(entity) if entity.deleted_at.is_none() => entity,
+            _ => return Ok(None),
+        };
+        if !context.can_write(existing) || !context.in_tenant(existing) {
+            return Ok(None);
+        }
+
+        let updated = existing.updated(updates, context, Utc::now());
+        entities.insert(entity_id, updated.clone());
+        self.publish(&updated, EntityChangeOperation::Update);
+        Ok(Some(updated))
+    }
+
+    async fn merge_entity(
+        &self,
+        entity_id: Uuid,
+        expected_version: i64,
+        data: Value,
+        label: &SecurityLabel,
+        context: &DatabaseContext,
+    ) -> Result<Option<SecureEntity>, DatabaseError> {
+        self.check_tenant(context).await?;
+        let mut entities = self.entities.write().await;
+        let existing = match entities.get(&entity_id) {
+            Some(entity) if entity.deleted_at.is_none() && entity.version == expected_version && context.in_tenant(entity) => entity,
+            _ => return Ok(None),
+        };
+        let Some(merged) = existing.merged(data, label, context, Utc::now())? else {
+            return Ok(None);
+        };
+
+        entities.insert(entity_id, merged.clone());
+        self.publish(&merged, EntityChangeOperation::Update);
+        Ok(Some(merged))
+    }
+
+    async fn delete_entity(&self, entity_id: Uuid, context: &DatabaseContext) -> Result<bool, DatabaseError> {
+        self.check_tenant(context).await?;
+        let mut entities = self.entities.write().await;
+        let Some(existing) = entities.get(&entity_id) else {
+            return Ok(false);
+        };
+        if EntityLifecycle::SoftDelete.authorize(existing, context).is_err() {
+            return Ok(false);
+        }
+
+        if let Some(deleted) = EntityLifecycle::SoftDelete.apply(existing, context, Utc::now()) {
+            self.publish(&deleted, EntityChangeOperation::Delete);
+            entities.insert(entity_id, deleted);
+        }
+        Ok(true)
+    }
+
+    async fn query_entities(
+        &self,
+        entity_type: Option<&str>,
+        filter: Option<&QueryFilter>,
+        context: &DatabaseContext,
+        limit: Option<i64>,
+        offset: Option<i64>,
+    ) -> Result<SecureQueryResult, DatabaseError> {
+        self.check_tenant(context).await?;
+        if let Some(filter) = filter {
+            filter.validate()?;
+        }
+
+        let mut entities: Vec<SecureEntity> = self.entities.read().await
+            .values()
+            .filter(|entity| entity_type.map_or(true, |entity_type| entity.entity_type == entity_type))
+            .filter(|entity| filter.map_or(true, |filter| filter.matches(&entity.data)))
+            .filter(|entity| context.can_see(entity))
+            .cloned()
+            .collect();
+        entities.sort_by_key(|entity| (entity.created_at, entity.id));
+
+        let entities: Vec<SecureEntity> = entities.into_iter()
+            .skip(offset.unwrap_or(0).max(0) as usize)
+            .take(limit.map_or(usize::MAX, |limit| limit.max(0) as usize))
+            .collect();
+        let filtered_count = entities.len() as i64;
+
+        Ok(SecureQueryResult {
+            entities,
+            total_count: filtered_count,
+            filtered_count,
+            access_denied_count: 0,
+            next_cursor: None,
+        })
+    }
+
+    async fn dry_run(&self, operation: &BatchEntityOperation, context: &DatabaseContext) -> Result<DryRunOutcome, DatabaseError> {
+        if let Err(e) = self.check_tenant(context).await {
+            return Ok(DryRunOutcome::denied(e.to_string()));
+        }
+        let existing = match operation {
+            BatchEntityOperation::Create { .. } => None,
+            BatchEntityOperation::Update { entity_id, .. } | BatchEntityOperation::Delete { entity_id } => {
+                self.entities.read().await.get(entity_id).cloned()
+            }
+        };
+
+        Ok(preview_operation(
+            operation,
+            existing.as_ref(),
+            |entity_type, data| self.classifier.classify(entity_type, data, &context.security_label).label,
+            context,
+            Utc::now(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::multi_tenant::{MultiTenantError, TenantStatus, TENANT_ADMIN_PERMISSION};
+    use crate::security::{ClassificationLevel, SecurityLabel};
+    use serde_json::json;
+
+    fn context(level: ClassificationLevel, compartments: &[&str], tenant_id: Option<&str>) -> DatabaseContext {
+        DatabaseContext::new(
+            format!("{}-user", level),
+            Uuid::new_v4(),
+            SecurityLabel::new(level, compartments.iter().map(|c| c.to_string()).collect()),
+            tenant_id.map(str::to_string),
+        )
+    }
+
+    /// Store admitting `tenants`, as once `MultiTenantSystem` has provisioned them
+    async fn store_for(tenants: &[&str]) -> InMemoryEntityStore {
+        let store = InMemoryEntityStore::new();
+        for tenant in tenants {
+            store.tenant_gate().set_status(tenant, TenantStatus::Active).await;
+        }
+        store
+    }
+
+    /// Seed an entity at `level` and `compartments`, as another user would have stored it
+    async fn seed(store: &InMemoryEntityStore, level: ClassificationLevel, compartments: &[&str], data: Value) -> SecureEntity {
+        let owner = context(level, compartments, None);
+        let entity = store.create_entity("report", data, &owner).await.unwrap();
+        assert_eq!(entity.classification, owner.security_label.level);
+        entity
+    }
+
+    #[tokio::test]
+    async fn test_no_read_up() {
+        let store = InMemoryEntityStore::new();
+        let internal = seed(&store, ClassificationLevel::Internal, &[], json!({"title": "Roadmap"})).await;
+        let secret = seed(&store, ClassificationLevel::Secret, &[], json!({"title": "Sources"})).await;
+
+        let analyst = context(ClassificationLevel::Internal, &[], None);
+        assert!(store.read_entity(internal.id, &analyst).await.unwrap().is_some());
+        assert!(store.read_entity(secret.id, &analyst).await.unwrap().is_none());
+        let visible = store.query_entities(Some("report"), None, &analyst, None, None).await.unwrap();
+        assert_eq!(visible.entities.iter().map(|entity| entity.id).collect::<Vec<_>>(), vec![internal.id]);
+
+        let officer = context(ClassificationLevel::Secret, &[], None);
+        assert!(store.read_entity(secret.id, &officer).await.unwrap().is_some());
+        assert_eq!(store.query_entities(None, None, &officer, None, None).await.unwrap().filtered_count, 2);
+    }
+
+    #[tokio::test]
+    async fn test_compartments_must_be_held() {
+        let store = InMemoryEntityStore::new();
+        let bravo = seed(&store, ClassificationLevel::Secret, &["BRAVO"], json!({})).await;
+
+        let alpha_only = context(ClassificationLevel::Secret, &["ALPHA"], None);
+        assert!(store.read_entity(bravo.id, &alpha_only).await.unwrap().is_none());
+        let both = context(ClassificationLevel::Secret, &["ALPHA", "BRAVO"], None);
+        assert!(store.read_entity(bravo.id, &both).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_no_write_down() {
+        let store: Box<dyn EntityStore> = Box::new(InMemoryEntityStore::new());
+        let analyst = context(ClassificationLevel::Internal, &[], None);
+        let entity = store.create_entity("note", json!({"status": "open"}), &analyst).await.unwrap();
+
+        // A Secret user can read the Internal entity but must not write to it
+        let officer = context(ClassificationLevel::Secret, &[], None);
+        assert!(store.read_entity(entity.id, &officer).await.unwrap().is_some());
+        assert!(store.update_entity(entity.id, json!({"status": "leaked"}), &officer).await.unwrap().is_none());
+        assert!(!store.delete_entity(entity.id, &officer).await.unwrap());
+
+        let updated = store.update_entity(entity.id, json!({"status": "closed"}), &analyst).await.unwrap().unwrap();
+        assert_eq!((updated.data["status"].clone(), updated.version), (json!("closed"), 2));
+        assert_eq!(store.read_entity(entity.id, &analyst).await.unwrap().unwrap().data["status"], "closed");
+    }
+
+    #[tokio::test]
+    async fn test_tenant_isolation_and_soft_delete() {
+        let store = store_for(&["acme", "globex"]).await;
+        let acme = context(ClassificationLevel::Internal, &[], Some("acme"));
+        let globex = context(ClassificationLevel::Internal, &[], Some("globex"));
+        let order = store.create_entity("order", json!({"total": 40}), &acme).await.unwrap();
+        let shared = SecureEntity { id: Uuid::new_v4(), tenant_id: None, ..order.clone() };
+        store.insert(shared.clone()).await;
+
+        assert!(store.read_entity(order.id, &globex).await.unwrap().is_none());
+        assert!(store.read_entity(shared.id, &globex).await.unwrap().is_some());
+        // Another tenant can neither update nor delete it
+        assert!(store.update_entity(order.id, json!({"total": 0}), &globex).await.unwrap().is_none());
+        assert!(!store.delete_entity(order.id, &globex).await.unwrap());
+        assert_eq!(store.read_entity(order.id, &acme).await.unwrap().unwrap().data["total"], 40);
+
+        assert!(store.delete_entity(order.id, &acme).await.unwrap());
+        assert!(!store.delete_entity(order.id, &acme).await.unwrap());
+        assert!(store.read_entity(order.id, &acme).await.unwrap().is_none());
+        assert!(store.update_entity(order.id, json!({"total": 0}), &acme).await.unwrap().is_none());
+        let audit = store.query_entities(Some("order"), None, &acme.clone().including_deleted(), None, None).await.unwrap();
+        assert_eq!(audit.filtered_count, 2);
+        assert!(audit.entities.iter().any(|entity| entity.id == order.id && entity.deleted_at.is_some()));
+    }
+
+    #[tokio::test]
+    async fn test_query_filter_and_pagination() {
+        let store = InMemoryEntityStore::new();
+        let analyst = context(ClassificationLevel::Internal, &[], None);
+        for amount in [50, 150, 250, 350] {
+            store.create_entity("invoice", json!({"amount": amount}), &analyst).await.unwrap();
+        }
+        seed(&store, ClassificationLevel::Secret, &[], json!({"amount": 999})).await;
+
+        let large = QueryFilter::Gt { field: "amount".to_string(), value: json!(100) };
+        let all = store.query_entities(None, Some(&large), &analyst, None, None).await.unwrap();
+        assert_eq!(all.filtered_count, 3);
+        let page = store.query_entities(None, Some(&large), &analyst, Some(2), Some(1)).await.unwrap();
+        let ids = |entities: &[SecureEntity]| entities.iter().map(|entity| entity.id).collect::<Vec<_>>();
+        assert_eq!(ids(&page.entities), ids(&all.entities[1..]));
+
+        let invalid = QueryFilter::Eq { field: "a..b".to_string(), value: json!(1) };
+        assert!(matches!(
+            store.query_entities(None, Some(&invalid), &analyst, None, None).await,
+            Err(DatabaseError::InvalidFilter(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_suspended_tenant_rejected_until_resumed() {
+        let store = store_for(&["acme", "globex"]).await;
+        let acme = context(ClassificationLevel::Internal, &[], Some("acme"));
+        let order = store.create_entity("order", json!({"total": 40}), &acme).await.unwrap();
+
+        store.tenant_gate().set_status("acme", TenantStatus::Suspended).await;
+        assert!(matches!(
+            store.read_entity(order.id, &acme).await,
+            Err(DatabaseError::Tenant(MultiTenantError::TenantInactive { status: TenantStatus::Suspended, .. }))
+        ));
+        assert!(store.create_entity("order", json!({}), &acme).await.is_err());
+        assert!(store.update_entity(order.id, json!({"total": 0}), &acme).await.is_err());
+        // Other tenants and tenant-less operations are unaffected
+        let globex = context(ClassificationLevel::Internal, &[], Some("globex"));
+        assert!(store.query_entities(None, None, &globex, None, None).await.is_ok());
+        // Tenants the gate has never seen are refused
+        let unknown = context(ClassificationLevel::Internal, &[], Some("initech"));
+        assert!(matches!(
+            store.create_entity("order", json!({}), &unknown).await,
+            Err(DatabaseError::Tenant(MultiTenantError::TenantNotFound { .. }))
+        ));
+
+        store.tenant_gate().set_status("acme", TenantStatus::Active).await;
+        assert!(store.read_entity(order.id, &acme).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_maintenance_admits_tenant_admins_only() {
+        let store = InMemoryEntityStore::new();
+        store.tenant_gate().set_status("acme", TenantStatus::Maintenance).await;
+        let user = context(ClassificationLevel::Internal, &[], Some("acme"));
+        let admin = user.clone().with_permissions(vec![TENANT_ADMIN_PERMISSION.to_string()]);
+
+        assert!(store.create_entity("order", json!({}), &user).await.is_err());
+        assert!(store.create_entity("order", json!({}), &admin).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_reports_denial_without_touching_store() {
+        let store = InMemoryEntityStore::new();
+        let analyst = context(ClassificationLevel::Internal, &[], None);
+        let note = store.create_entity("note", json!({"status": "open"}), &analyst).await.unwrap();
+        let secret = seed(&store, ClassificationLevel::Secret, &[], json!({"title": "Sources"})).await;
+
+        // No Write Down: the officer sees the note, so the denial names the write
+        let officer = context(ClassificationLevel::Secret, &[], None);
+        let update = BatchEntityOperation::Update { entity_id: note.id, updates: json!({"status": "leaked"}) };
+        let outcome = store.dry_run(&update, &officer).await.unwrap();
+        assert!(!outcome.allowed && outcome.entity.is_none());
+        assert!(outcome.reason.unwrap().contains("Write access"));
+
+        // No Read Up: a hidden entity is reported as missing, exactly as a real delete would be
+        let delete = BatchEntityOperation::Delete { entity_id: secret.id };
+        let outcome = store.dry_run(&delete, &analyst).await.unwrap();
+        assert!(outcome.reason.unwrap().contains("not found or access denied"));
+
+        // An allowed dry run returns the would-be entity but changes nothing
+        let outcome = store.dry_run(&BatchEntityOperation::Update { entity_id: note.id, updates: json!({"status": "closed"}) }, &analyst).await.unwrap();
+        let would_be = outcome.entity.unwrap();
+        assert!(outcome.allowed);
+        assert_eq!((would_be.data["status"].clone(), would_be.version), (json!("closed"), 2));
+        let create = BatchEntityOperation::Create { entity_type: "note".to_string(), data: json!({}), idempotency_key: None };
+        assert!(store.dry_run(&create, &analyst).await.unwrap().allowed);
+
+        assert_eq!(store.len().await, 2);
+        let stored = store.read_entity(note.id, &analyst).await.unwrap().unwrap();
+        assert_eq!((stored.data["status"].clone(), stored.version), (json!("open"), 1));
+        assert!(store.read_entity(secret.id, &officer).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_for_suspended_tenant_is_denied() {
+        let store = InMemoryEntityStore::new();
+        store.tenant_gate().set_status("acme", TenantStatus::Suspended).await;
+        let acme = context(ClassificationLevel::Internal, &[], Some("acme"));
+
+        let create = BatchEntityOperation::Create { entity_type: "order".to_string(), data: json!({}), idempotency_key: None };
+        let outcome = store.dry_run(&create, &acme).await.unwrap();
+        assert!(!outcome.allowed);
+        assert!(outcome.reason.unwrap().contains("acme"));
+        assert!(store.is_empty().await);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_same_idempotency_key_creates_one_entity() {
+        let store = Arc::new(store_for(&["acme", "globex"]).await);
+        let analyst = context(ClassificationLevel::Internal, &[], Some("acme"));
+
+        // Retries racing the first attempt on separate workers still yield one row
+        let attempts = 8;
+        let start = Arc::new(tokio::sync::Barrier::new(attempts));
+        let tasks: Vec<_> = (0..attempts)
+            .map(|_| {
+                let (store, analyst, start) = (store.clone(), analyst.clone(), start.clone());
+                tokio::spawn(async move {
+                    start.wait().await;
+                    store.create_entity_idempotent("order", json!({"total": 40}), "order-7f3a", &analyst).await
+                })
+            })
+            .collect();
+        let mut created = Vec::new();
+        for task in tasks {
+            created.push(task.await.unwrap().unwrap());
+        }
+        let first = &created[0];
+        assert!(created.iter().all(|entity| entity.id == first.id && entity.data == first.data));
+        assert_eq!(store.len().await, 1);
+
+        // Keys are scoped per creator and tenant
+        let officer = context(ClassificationLevel::Secret, &[], Some("acme"));
+        let other_user = store.create_entity_idempotent("order", json!({}), "order-7f3a", &officer).await.unwrap();
+        let other_tenant = context(ClassificationLevel::Internal, &[], Some("globex"));
+        let other_tenant = store.create_entity_idempotent("order", json!({}), "order-7f3a", &other_tenant).await.unwrap();
+        assert!(other_user.id != first.id && other_tenant.id != first.id);
+        assert_eq!(store.len().await, 3);
+    }
+
+    #[tokio::test]
+    async fn test_idempotency_key_reuse_is_rejected() {
+        let store = InMemoryEntityStore::new();
+        let analyst = context(ClassificationLevel::Internal, &[], None);
+        let note = store.create_entity_idempotent("note", json!({}), "k1", &analyst).await.unwrap();
+
+        let err = store.create_entity_idempotent("order", json!({}), "k1", &analyst).await.unwrap_err();
+        assert!(matches!(err, DatabaseError::IdempotencyKey(ref message) if message.contains("note")));
+
+        // The key stays claimed after its entity is deleted; a retry must not resurrect it
+        assert!(store.delete_entity(note.id, &analyst).await.unwrap());
+        assert!(matches!(
+            store.create_entity_idempotent("note", json!({}), "k1", &analyst).await,
+            Err(DatabaseError::IdempotencyKey(_))
+        ));
+        assert!(matches!(
+            store.create_entity_idempotent("note", json!({}), "", &analyst).await,
+            Err(DatabaseError::IdempotencyKey(_))
+        ));
+        assert_eq!(store.len().await, 1);
+    }
+}
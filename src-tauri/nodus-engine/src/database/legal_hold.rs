@@ -0,0 +1,136 @@
+// src-tauri/src/database/legal_hold.rs
+// Legal Holds - Freeze subjects, entities or whole tenants for litigation
+// Held data is exempt from retention sweeps and rejected by every erasure path until the hold lapses
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use super::DatabaseError;
+use crate::security::subject_keys::SEALED_MARKER;
+
+/// What a hold freezes
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "scope", content = "target", rename_all = "snake_case")]
+pub enum LegalHoldScope {
+    /// Every value sealed under the subject's key
+    Subject(String),
+    Entity(Uuid),
+    /// Everything the tenant owns
+    Tenant(String),
+}
+
+impl LegalHoldScope {
+    /// `(scope, target)` as stored in `legal_holds`
+    pub fn to_columns(&self) -> (&'static str, String) {
+        match self {
+            Self::Subject(subject_id) => ("subject", subject_id.clone()),
+            Self::Entity(entity_id) => ("entity", entity_id.to_string()),
+            Self::Tenant(tenant_id) => ("tenant", tenant_id.clone()),
+        }
+    }
+
+    pub fn from_columns(scope: &str, target: &str) -> Option<Self> {
+        match scope {
+            "subject" => Some(Self::Subject(target.to_string())),
+            "entity" => target.parse().ok().map(Self::Entity),
+            "tenant" => Some(Self::Tenant(target.to_string())),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LegalHold {
+    pub hold_id: Uuid,
+    #[serde(flatten)]
+    pub scope: LegalHoldScope,
+    pub reason: String,
+    pub placed_by: String,
+    pub placed_at: DateTime<Utc>,
+    /// None holds until released
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl LegalHold {
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.map_or(true, |expires_at| now < expires_at)
+    }
+}
+
+/// Holds in force at one point in time
+#[derive(Debug, Clone, Default)]
+pub struct LegalHolds {
+    holds: Vec<LegalHold>,
+}
+
+impl LegalHolds {
+    /// The holds among `holds` still active at `now`
+    pub fn active(holds: Vec<LegalHold>, now: DateTime<Utc>) -> Self {
+        Self { holds: holds.into_iter().filter(|hold| hold.is_active(now)).collect() }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.holds.is_empty()
+    }
+
+    fn find(&self, scope: &LegalHoldScope) -> Option<&LegalHold> {
+        self.holds.iter().find(|hold| hold.scope == *scope)
+    }
+
+    pub fn holding_subject(&self, subject_id: &str) -> Option<&LegalHold> {
+        self.find(&LegalHoldScope::Subject(subject_id.to_string()))
+    }
+
+    pub fn holding_tenant(&self, tenant_id: &str) -> Option<&LegalHold> {
+        self.find(&LegalHoldScope::Tenant(tenant_id.to_string()))
+    }
+
+    /// Hold covering an entity directly or through its tenant
+    pub fn holding_entity(&self, entity_id: Uuid, tenant_id: Option<&str>) -> Option<&LegalHold> {
+        self.find(&LegalHoldScope::Entity(entity_id))
+            .or_else(|| tenant_id.and_then(|tenant_id| self.holding_tenant(tenant_id)))
+    }
+
+    /// Hold covering any subject whose sealed data appears in `value`
+    pub fn holding_sealed_data(&self, value: &Value) -> Option<&LegalHold> {
+        let mut subjects = Vec::new();
+        sealed_subjects(value, &mut subjects);
+        subjects.iter().find_map(|subject_id| self.holding_subject(subject_id))
+    }
+
+    /// Reject erasing a held subject
+    pub fn check_subject_erasure(&self, subject_id: &str) -> Result<(), DatabaseError> {
+        match self.holding_subject(subject_id) {
+            Some(hold) => Err(held(&format!("subject {}", subject_id), hold)),
+            None => Ok(()),
+        }
+    }
+
+    /// Reject erasing a held entity
+    pub fn check_entity_erasure(&self, entity_id: Uuid, tenant_id: Option<&str>) -> Result<(), DatabaseError> {
+        match self.holding_entity(entity_id, tenant_id) {
+            Some(hold) => Err(held(&format!("entity {}", entity_id), hold)),
+            None => Ok(()),
+        }
+    }
+}
+
+fn held(what: &str, hold: &LegalHold) -> DatabaseError {
+    DatabaseError::LegalHold(format!("{} is under legal hold {} ({})", what, hold.hold_id, hold.reason))
+}
+
+/// Subject ids of every sealed value nested in `value`
+fn sealed_subjects(value: &Value, subjects: &mut Vec<String>) {
+    match value {
+        Value::Object(fields) => {
+            if let Some(subject_id) = fields.get(SEALED_MARKER).and_then(|sealed| sealed.get("subject_id")).and_then(Value::as_str) {
+                subjects.push(subject_id.to_string());
+            }
+            fields.values().for_each(|field| sealed_subjects(field, subjects));
+        }
+        Value::Array(items) => items.iter().for_each(|item| sealed_subjects(item, subjects)),
+        _ => {}
+    }
+}
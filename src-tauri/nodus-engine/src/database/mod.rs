@@ -1,7 +1,21 @@
 // `src/database/mod.rs` - directory module to expose database-related files
+pub mod change_feed;
 pub mod database_mod;
 pub mod db_optimization_analyzer;
+pub mod declassification;
+pub mod entity_store;
+pub mod legal_hold;
+pub mod query_filter;
+pub mod retention;
+pub mod subject_vault;
 
 // Re-export the primary items so callers can use `crate::database::DatabaseManager`.
+pub use change_feed::{ChangeFeedError, EntityChangeFeed, EntityChangeOperation, EntityChangeSubscription, EntityChanged};
 pub use database_mod::*;
 pub use db_optimization_analyzer::*;
+pub use declassification::{DeclassificationRequest, DeclassificationStore, DeclassificationWorkflow};
+pub use entity_store::{DryRunOutcome, EntityStore, InMemoryEntityStore};
+pub use legal_hold::{LegalHold, LegalHoldScope, LegalHolds};
+pub use query_filter::{QueryFilter, QueryFilterError};
+pub use retention::{RetentionStore, RetentionSweeper, RetentionSweepReport};
+pub use subject_vault::{InMemorySubjectKeyStore, SubjectFieldRule, SubjectKeyStore, SubjectVault};
@@ -0,0 +1,372 @@
+// src-tauri/src/database/query_filter.rs
+// Query Filters - Typed entity filters compiled to parameterized jsonb SQL
+// Every field path and value is bound, never interpolated; size limits keep pathological filters out
+
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use serde_json::Value;
+use sqlx::{Postgres, QueryBuilder};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// Deepest nesting of `and`/`or` a filter may use
+pub const MAX_FILTER_DEPTH: usize = 6;
+/// Most conditions a filter may contain, counting each `and`/`or` group as one
+pub const MAX_FILTER_CONDITIONS: usize = 64;
+/// Most values one `in` condition may list
+pub const MAX_IN_VALUES: usize = 500;
+
+const MAX_FIELD_SEGMENTS: usize = 8;
+
+/// Condition on entity `data`, e.g. `{"op": "gt", "field": "invoice.amount", "value": 100}`
+///
+/// `field` is a dot-separated path into the entity's JSON data.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum QueryFilter {
+    Eq { field: String, value: Value },
+    /// Also matches entities without the field
+    Ne { field: String, value: Value },
+    /// Numbers compare numerically, strings lexically (so ISO dates work); other types never match
+    Gt { field: String, value: Value },
+    Lt { field: String, value: Value },
+    In { field: String, values: Vec<Value> },
+    /// Case-insensitive substring match on the field's text
+    Contains { field: String, value: String },
+    And { filters: Vec<QueryFilter> },
+    Or { filters: Vec<QueryFilter> },
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum QueryFilterError {
+    #[error("Filter nests deeper than {} levels", MAX_FILTER_DEPTH)]
+    TooDeep,
+
+    #[error("Filter has more than {} conditions", MAX_FILTER_CONDITIONS)]
+    TooManyConditions,
+
+    #[error("In-list on {0} has more than {} values", MAX_IN_VALUES)]
+    TooManyValues(String),
+
+    #[error("Invalid filter field: {0:?}")]
+    InvalidField(String),
+
+    #[error("Range filter on {0} needs a number or string value")]
+    NotOrderable(String),
+}
+
+impl QueryFilter {
+    /// Check the filter against the depth, size and field limits
+    pub fn validate(&self) -> Result<(), QueryFilterError> {
+        let mut conditions = 0;
+        self.validate_at(1, &mut conditions)
+    }
+
+    fn validate_at(&self, depth: usize, conditions: &mut usize) -> Result<(), QueryFilterError> {
+        if depth > MAX_FILTER_DEPTH {
+            return Err(QueryFilterError::TooDeep);
+        }
+        // Groups count too, or empty `and`s could pad the SQL past the limit
+        *conditions += 1;
+        if *conditions > MAX_FILTER_CONDITIONS {
+            return Err(QueryFilterError::TooManyConditions);
+        }
+        let field = match self {
+            Self::And { filters } | Self::Or { filters } => {
+                return filters.iter().try_for_each(|filter| filter.validate_at(depth + 1, conditions));
+            }
+            Self::Eq { field, .. }
+            | Self::Ne { field, .. }
+            | Self::Gt { field, .. }
+            | Self::Lt { field, .. }
+            | Self::In { field, .. }
+            | Self::Contains { field, .. } => field,
+        };
+        validate_field(field)?;
+        match self {
+            Self::Gt { value, .. } | Self::Lt { value, .. } if comparable_type(value).is_none() => {
+                Err(QueryFilterError::NotOrderable(field.clone()))
+            }
+            Self::In { values, .. } if values.len() > MAX_IN_VALUES => {
+                Err(QueryFilterError::TooManyValues(field.clone()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Append the filter as one parenthesized boolean expression
+    ///
+    /// Call `validate` first; the parentheses keep an `or` from escaping conditions ANDed
+    /// after it, such as the security filter.
+    pub fn push_sql(&self, builder: &mut QueryBuilder<'_, Postgres>) {
+        builder.push("(");
+        match self {
+            Self::Eq { field, value } => {
+                push_field(builder, field);
+                builder.push(" = ");
+                builder.push_bind(value.clone());
+            }
+            Self::Ne { field, value } => {
+                push_field(builder, field);
+                builder.push(" IS DISTINCT FROM ");
+                builder.push_bind(value.clone());
+            }
+            Self::Gt { field, value } | Self::Lt { field, value } => {
+                // jsonb orders values of different types by type, so compare like with like only
+                builder.push("jsonb_typeof(");
+                push_field(builder, field);
+                builder.push(") = ");
+                builder.push_bind(comparable_type(value).unwrap_or("number"));
+                builder.push(" AND ");
+                push_field(builder, field);
+                builder.push(if matches!(self, Self::Gt { .. }) { " > " } else { " < " });
+                builder.push_bind(value.clone());
+            }
+            Self::In { values, .. } if values.is_empty() => {
+                builder.push("FALSE");
+            }
+            Self::In { field, values } => {
+                push_field(builder, field);
+                builder.push(" IN (");
+                let mut list = builder.separated(", ");
+                for value in values {
+                    list.push_bind(value.clone());
+                }
+                builder.push(")");
+            }
+            Self::Contains { field, value } => {
+                builder.push("data #>> ");
+                builder.push_bind(field_segments(field));
+                builder.push(" ILIKE ");
+                builder.push_bind(format!("%{}%", escape_like(value)));
+            }
+            Self::And { filters } | Self::Or { filters } => {
+                let (joiner, empty) = if matches!(self, Self::And { .. }) { (" AND ", "TRUE") } else { (" OR ", "FALSE") };
+                if filters.is_empty() {
+                    builder.push(empty);
+                }
+                for (i, filter) in filters.iter().enumerate() {
+                    if i > 0 {
+                        builder.push(joiner);
+                    }
+                    filter.push_sql(builder);
+                }
+            }
+        }
+        builder.push(")");
+    }
+
+    /// Evaluate the filter against entity `data`, with the same semantics as `push_sql`
+    ///
+    /// For backends that filter in memory rather than in Postgres.
+    pub fn matches(&self, data: &Value) -> bool {
+        match self {
+            Self::Eq { field, value } => lookup(data, field) == Some(value),
+            Self::Ne { field, value } => lookup(data, field) != Some(value),
+            Self::Gt { field, value } => compare(lookup(data, field), value) == Some(Ordering::Greater),
+            Self::Lt { field, value } => compare(lookup(data, field), value) == Some(Ordering::Less),
+            Self::In { field, values } => lookup(data, field).is_some_and(|found| values.contains(found)),
+            Self::Contains { field, value } => lookup(data, field).is_some_and(|found| {
+                let text = match found {
+                    Value::String(text) => text.clone(),
+                    other => other.to_string(),
+                };
+                text.to_lowercase().contains(&value.to_lowercase())
+            }),
+            Self::And { filters } => filters.iter().all(|filter| filter.matches(data)),
+            Self::Or { filters } => filters.iter().any(|filter| filter.matches(data)),
+        }
+    }
+}
+
+/// Flat `field = value` filters, as the UI sent them before the DSL
+impl From<HashMap<String, Value>> for QueryFilter {
+    fn from(equalities: HashMap<String, Value>) -> Self {
+        // Sorted so the same filters always compile to the same SQL
+        let mut equalities: Vec<(String, Value)> = equalities.into_iter().collect();
+        equalities.sort_by(|a, b| a.0.cmp(&b.0));
+        QueryFilter::And {
+            filters: equalities.into_iter()
+                .map(|(field, value)| QueryFilter::Eq { field, value })
+                .collect(),
+        }
+    }
+}
+
+/// Value at the dot-separated `field` path, like `data #> path`
+fn lookup<'v>(data: &'v Value, field: &str) -> Option<&'v Value> {
+    field.split('.').try_fold(data, |value, segment| match value {
+        Value::Array(items) => segment.parse::<usize>().ok().and_then(|index| items.get(index)),
+        _ => value.get(segment),
+    })
+}
+
+/// Order `found` against `value` when both are numbers or both strings
+fn compare(found: Option<&Value>, value: &Value) -> Option<Ordering> {
+    match (found?, value) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64()?.partial_cmp(&b.as_f64()?),
+        (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+        _ => None,
+    }
+}
+
+fn push_field(builder: &mut QueryBuilder<'_, Postgres>, field: &str) {
+    builder.push("data #> ");
+    builder.push_bind(field_segments(field));
+}
+
+fn field_segments(field: &str) -> Vec<String> {
+    field.split('.').map(str::to_string).collect()
+}
+
+fn validate_field(field: &str) -> Result<(), QueryFilterError> {
+    let segments = field_segments(field);
+    if segments.len() > MAX_FIELD_SEGMENTS || segments.iter().any(|segment| segment.is_empty() || segment.len() > 128) {
+        return Err(QueryFilterError::InvalidField(field.to_string()));
+    }
+    Ok(())
+}
+
+/// `jsonb_typeof` name for values range filters accept
+fn comparable_type(value: &Value) -> Option<&'static str> {
+    match value {
+        Value::Number(_) => Some("number"),
+        Value::String(_) => Some("string"),
+        _ => None,
+    }
+}
+
+fn escape_like(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn compile(filter: &QueryFilter) -> String {
+        let mut builder = QueryBuilder::<Postgres>::new("SELECT id FROM entities WHERE ");
+        filter.push_sql(&mut builder);
+        builder.sql().trim_start_matches("SELECT id FROM entities WHERE ").to_string()
+    }
+
+    #[test]
+    fn test_range_filter() {
+        let filter: QueryFilter = serde_json::from_value(json!({
+            "op": "and",
+            "filters": [
+                {"op": "gt", "field": "invoice.amount", "value": 100},
+                {"op": "lt", "field": "invoice.amount", "value": 500},
+            ]
+        })).unwrap();
+        filter.validate().unwrap();
+        assert_eq!(
+            compile(&filter),
+            "((jsonb_typeof(data #> $1) = $2 AND data #> $3 > $4) AND \
+             (jsonb_typeof(data #> $5) = $6 AND data #> $7 < $8))"
+        );
+
+        // Booleans and objects have no useful order
+        let filter = QueryFilter::Gt { field: "approved".to_string(), value: json!(true) };
+        assert!(matches!(filter.validate(), Err(QueryFilterError::NotOrderable(_))));
+    }
+
+    #[test]
+    fn test_in_filter() {
+        let filter = QueryFilter::In {
+            field: "status".to_string(),
+            values: vec![json!("open"), json!("pending"), json!("escalated")],
+        };
+        filter.validate().unwrap();
+        assert_eq!(compile(&filter), "(data #> $1 IN ($2, $3, $4))");
+
+        let empty = QueryFilter::In { field: "status".to_string(), values: vec![] };
+        assert_eq!(compile(&empty), "(FALSE)");
+
+        let huge = QueryFilter::In { field: "id".to_string(), values: vec![json!(1); MAX_IN_VALUES + 1] };
+        assert!(matches!(huge.validate(), Err(QueryFilterError::TooManyValues(_))));
+    }
+
+    #[test]
+    fn test_nested_and_or() {
+        // status = open AND (priority = high OR title contains "outage")
+        let filter = QueryFilter::And {
+            filters: vec![
+                QueryFilter::Eq { field: "status".to_string(), value: json!("open") },
+                QueryFilter::Or {
+                    filters: vec![
+                        QueryFilter::Eq { field: "priority".to_string(), value: json!("high") },
+                        QueryFilter::Contains { field: "title".to_string(), value: "100%_outage".to_string() },
+                    ],
+                },
+            ],
+        };
+        filter.validate().unwrap();
+        assert_eq!(
+            compile(&filter),
+            "((data #> $1 = $2) AND ((data #> $3 = $4) OR (data #>> $5 ILIKE $6)))"
+        );
+
+        // A filter ANDed after the whole expression applies to both branches of the OR
+        let mut builder = QueryBuilder::<Postgres>::new("SELECT id FROM entities WHERE 1=1 AND ");
+        filter.push_sql(&mut builder);
+        builder.push(" AND classification = ");
+        builder.push_bind("internal");
+        assert!(builder.sql().ends_with("))) AND classification = $7"));
+    }
+
+    #[test]
+    fn test_pathological_filters_rejected() {
+        let mut deep = QueryFilter::Eq { field: "a".to_string(), value: json!(1) };
+        for _ in 0..MAX_FILTER_DEPTH {
+            deep = QueryFilter::Or { filters: vec![deep] };
+        }
+        assert!(matches!(deep.validate(), Err(QueryFilterError::TooDeep)));
+
+        let wide = QueryFilter::Or {
+            filters: (0..MAX_FILTER_CONDITIONS)
+                .map(|i| QueryFilter::Eq { field: "n".to_string(), value: json!(i) })
+                .collect(),
+        };
+        assert!(matches!(wide.validate(), Err(QueryFilterError::TooManyConditions)));
+
+        // Empty groups have no leaves but still cost SQL
+        let padded = QueryFilter::Or {
+            filters: vec![QueryFilter::And { filters: vec![] }; MAX_FILTER_CONDITIONS],
+        };
+        assert!(matches!(padded.validate(), Err(QueryFilterError::TooManyConditions)));
+
+        let at_limit = QueryFilter::Or {
+            filters: vec![QueryFilter::And { filters: vec![] }; MAX_FILTER_CONDITIONS - 1],
+        };
+        at_limit.validate().unwrap();
+
+        let bad_field = QueryFilter::Eq { field: "address..city".to_string(), value: json!("Oslo") };
+        assert!(matches!(bad_field.validate(), Err(QueryFilterError::InvalidField(_))));
+    }
+
+    #[test]
+    fn test_matches_in_memory() {
+        let data = json!({"status": "open", "title": "Outage in EU", "invoice": {"amount": 250}, "tags": ["a", "b"]});
+        let field = |name: &str| name.to_string();
+
+        assert!(QueryFilter::Eq { field: field("status"), value: json!("open") }.matches(&data));
+        assert!(QueryFilter::Ne { field: field("missing"), value: json!("open") }.matches(&data));
+        assert!(QueryFilter::Gt { field: field("invoice.amount"), value: json!(100) }.matches(&data));
+        assert!(!QueryFilter::Lt { field: field("invoice.amount"), value: json!(100) }.matches(&data));
+        // Mismatched types never compare, as with jsonb_typeof in SQL
+        assert!(!QueryFilter::Gt { field: field("invoice.amount"), value: json!("100") }.matches(&data));
+        assert!(QueryFilter::Eq { field: field("tags.1"), value: json!("b") }.matches(&data));
+        assert!(QueryFilter::Contains { field: field("title"), value: "outage".to_string() }.matches(&data));
+        assert!(!QueryFilter::In { field: field("status"), values: vec![] }.matches(&data));
+        assert!(QueryFilter::Or {
+            filters: vec![
+                QueryFilter::Eq { field: field("status"), value: json!("closed") },
+                QueryFilter::In { field: field("status"), values: vec![json!("open")] },
+            ],
+        }
+        .matches(&data));
+        assert!(QueryFilter::And { filters: vec![] }.matches(&data));
+    }
+}
@@ -0,0 +1,585 @@
+// src-tauri/src/database/retention.rs
+// Retention Enforcement - Applies tenant retention policies to expired forensic envelopes and entities
+// Records under legal hold are never touched; every sweep is itself recorded in the forensic log
+// Forensic rows are cleared in place rather than removed, keeping the digest their chain link covers
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::Arc;
+use uuid::Uuid;
+
+use super::legal_hold::LegalHolds;
+use super::DatabaseError;
+use crate::multi_tenant::{ExpiryAction, RetentionPolicy, TenantConfig};
+use crate::observability::ForensicEnvelope;
+use crate::security::{ClassificationLevel, SubjectKey};
+
+/// Records fetched and acted on per round trip
+pub const RETENTION_PAGE_SIZE: usize = 500;
+
+/// How often tenants' retention policies are enforced
+pub const DEFAULT_RETENTION_SWEEP_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+
+/// Marker written over anonymized values
+pub const ANONYMIZED_MARKER: &str = "[ANONYMIZED]";
+
+/// Policy id of the forensic retention implied by `TenantAuditConfig::retention_days`
+pub const AUDIT_RETENTION_POLICY_ID: &str = "audit_retention";
+
+/// Data a retention policy applies to, from `RetentionPolicy::data_type`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RetentionTarget {
+    ForensicLog,
+    Entities,
+}
+
+impl RetentionTarget {
+    pub fn from_data_type(data_type: &str) -> Option<Self> {
+        match data_type.trim().to_ascii_lowercase().as_str() {
+            "forensic" | "forensic_log" | "forensic_envelopes" | "audit" | "audit_log" => Some(Self::ForensicLog),
+            "entity" | "entities" => Some(Self::Entities),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn table(self) -> &'static str {
+        match self {
+            Self::ForensicLog => "forensic_log",
+            Self::Entities => "entities",
+        }
+    }
+
+    /// Archive table, with the live table's columns plus `archived_at, retention_policy_id`
+    pub(crate) fn cold_table(self) -> &'static str {
+        match self {
+            Self::ForensicLog => "cold_storage.forensic_log",
+            Self::Entities => "cold_storage.entities",
+        }
+    }
+
+    /// Columns copied to the archive table; derived columns like `search_tsv` are left out
+    pub(crate) fn columns(self) -> &'static str {
+        match self {
+            Self::ForensicLog => {
+                "envelope_id, operation_id, event_type, timestamp, user_id, session_id, classification, \
+                 action, resource, before_state, after_state, metadata, audit_trail_hash, legal_hold, \
+                 retention_action, content_digest"
+            }
+            Self::Entities => {
+                "id, entity_type, data, created_at, updated_at, created_by, updated_by, classification, \
+                 compartments, version, tenant_id, deleted_at, deleted_by, legal_hold, retention_action"
+            }
+        }
+    }
+
+    pub(crate) fn id_column(self) -> &'static str {
+        match self {
+            Self::ForensicLog => "envelope_id",
+            Self::Entities => "id",
+        }
+    }
+}
+
+/// Expired record as the sweeper sees it
+///
+/// `data` is the personal payload: an entity's data, or an envelope's
+/// `{"before_state", "after_state"}`. `user_id` is only set for envelopes.
+#[derive(Debug, Clone)]
+pub struct RetainedRecord {
+    pub id: Uuid,
+    pub recorded_at: DateTime<Utc>,
+    pub legal_hold: bool,
+    pub user_id: Option<String>,
+    pub data: Value,
+}
+
+/// Replacement payload for a record that is anonymized or encrypted in place
+#[derive(Debug, Clone)]
+pub struct RetentionUpdate {
+    pub id: Uuid,
+    pub user_id: Option<String>,
+    pub data: Value,
+}
+
+/// Storage operations the sweeper needs; implemented by `DatabaseManager`
+#[async_trait::async_trait]
+pub trait RetentionStore: Send + Sync {
+    /// Records of `tenant_id` recorded before `cutoff` and not yet anonymized or encrypted,
+    /// ordered by id and starting after `after`, legal holds included
+    async fn expired_page(
+        &self,
+        target: RetentionTarget,
+        tenant_id: &str,
+        cutoff: DateTime<Utc>,
+        after: Option<Uuid>,
+        limit: usize,
+    ) -> Result<Vec<RetainedRecord>, DatabaseError>;
+
+    /// Delete records; forensic rows are tombstoned instead, so the chain through them still verifies
+    async fn delete_records(&self, target: RetentionTarget, ids: &[Uuid]) -> Result<u64, DatabaseError>;
+
+    /// Copy records to cold storage, then delete (or, for forensic rows, tombstone) them, atomically
+    async fn archive_records(&self, target: RetentionTarget, ids: &[Uuid], policy_id: &str) -> Result<u64, DatabaseError>;
+
+    /// Overwrite records' payload and mark them with the action applied, so later sweeps skip them
+    ///
+    /// Forensic rows keep the digest of their original content for chain verification.
+    async fn rewrite_records(
+        &self,
+        target: RetentionTarget,
+        updates: &[RetentionUpdate],
+        action: &ExpiryAction,
+    ) -> Result<u64, DatabaseError>;
+
+    /// Legal holds in force, on top of the per-row `legal_hold` flag
+    async fn active_legal_holds(&self) -> Result<LegalHolds, DatabaseError>;
+
+    /// Key expired records of `tenant_id` are encrypted under
+    async fn retention_key(&self, tenant_id: &str) -> Result<SubjectKey, DatabaseError>;
+
+    async fn log_sweep(&self, envelope: &ForensicEnvelope) -> Result<(), DatabaseError>;
+}
+
+/// What one policy's sweep did
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetentionSweepReport {
+    pub tenant_id: String,
+    pub policy_id: String,
+    pub target: RetentionTarget,
+    pub action: ExpiryAction,
+    pub cutoff: DateTime<Utc>,
+    pub processed: u64,
+    /// Expired records left alone because of a legal hold
+    pub held: u64,
+}
+
+/// Whether a legal hold covers `record`: its row flag, the tenant, the entity itself,
+/// or any subject whose sealed data it carries
+fn is_held(holds: &LegalHolds, tenant_id: &str, target: RetentionTarget, record: &RetainedRecord) -> bool {
+    let held_directly = match target {
+        RetentionTarget::Entities => holds.holding_entity(record.id, Some(tenant_id)).is_some(),
+        RetentionTarget::ForensicLog => holds.holding_tenant(tenant_id).is_some(),
+    };
+    record.legal_hold || held_directly || holds.holding_sealed_data(&record.data).is_some()
+}
+
+/// Policies in force for a tenant: its storage retention policies, plus archival of
+/// forensic data after `retention_days` unless a storage policy already covers it
+pub fn tenant_policies(tenant: &TenantConfig) -> Vec<RetentionPolicy> {
+    let mut policies = tenant.storage_config.retention_policies.clone();
+    let forensic_covered = policies.iter()
+        .any(|policy| RetentionTarget::from_data_type(&policy.data_type) == Some(RetentionTarget::ForensicLog));
+    if !forensic_covered {
+        policies.push(RetentionPolicy {
+            policy_id: AUDIT_RETENTION_POLICY_ID.to_string(),
+            data_type: "forensic_log".to_string(),
+            retention_period: Duration::days(tenant.security_config.audit_config.retention_days as i64),
+            action_on_expiry: ExpiryAction::Archive,
+        });
+    }
+    policies
+}
+
+/// Replace every string and number with `ANONYMIZED_MARKER`, keeping the structure
+pub fn anonymize(value: &Value) -> Value {
+    match value {
+        Value::String(_) | Value::Number(_) => Value::String(ANONYMIZED_MARKER.to_string()),
+        Value::Array(items) => Value::Array(items.iter().map(anonymize).collect()),
+        Value::Object(fields) => Value::Object(
+            fields.iter().map(|(key, value)| (key.clone(), anonymize(value))).collect(),
+        ),
+        Value::Null | Value::Bool(_) => value.clone(),
+    }
+}
+
+/// Applies retention policies through a `RetentionStore`
+pub struct RetentionSweeper {
+    store: Arc<dyn RetentionStore>,
+    page_size: usize,
+}
+
+impl RetentionSweeper {
+    pub fn new(store: Arc<dyn RetentionStore>) -> Self {
+        Self { store, page_size: RETENTION_PAGE_SIZE }
+    }
+
+    pub fn with_page_size(mut self, page_size: usize) -> Self {
+        self.page_size = page_size.max(1);
+        self
+    }
+
+    /// Enforce every policy in force for `tenant`
+    pub async fn sweep_tenant(&self, tenant: &TenantConfig, now: DateTime<Utc>) -> Result<Vec<RetentionSweepReport>, DatabaseError> {
+        let mut reports = Vec::new();
+        for policy in tenant_policies(tenant) {
+            if let Some(report) = self.sweep(&tenant.tenant_id, &policy, now).await? {
+                reports.push(report);
+            }
+        }
+        Ok(reports)
+    }
+
+    /// Enforce one policy; `None` if its data type is not one retention applies to
+    pub async fn sweep(
+        &self,
+        tenant_id: &str,
+        policy: &RetentionPolicy,
+        now: DateTime<Utc>,
+    ) -> Result<Option<RetentionSweepReport>, DatabaseError> {
+        let Some(target) = RetentionTarget::from_data_type(&policy.data_type) else {
+            tracing::warn!(
+                "Retention policy {} of tenant {} has unsupported data type {:?}; skipped",
+                policy.policy_id, tenant_id, policy.data_type
+            );
+            return Ok(None);
+        };
+
+        let action = &policy.action_on_expiry;
+        let cutoff = now - policy.retention_period;
+        let holds = self.store.active_legal_holds().await?;
+        let key = match action {
+            ExpiryAction::Encrypt => Some(self.store.retention_key(tenant_id).await?),
+            _ => None,
+        };
+
+        let mut report = RetentionSweepReport {
+            tenant_id: tenant_id.to_string(),
+            policy_id: policy.policy_id.clone(),
+            target,
+            action: action.clone(),
+            cutoff,
+            processed: 0,
+            held: 0,
+        };
+        // Keyset pagination, so held records don't come back on every page
+        let mut after = None;
+        loop {
+            let page = self.store.expired_page(target, tenant_id, cutoff, after, self.page_size).await?;
+            let Some(last) = page.last() else {
+                break;
+            };
+            after = Some(last.id);
+            let full_page = page.len() == self.page_size;
+
+            let (held, due): (Vec<RetainedRecord>, Vec<RetainedRecord>) =
+                page.into_iter().partition(|record| is_held(&holds, tenant_id, target, record));
+            report.held += held.len() as u64;
+            if !due.is_empty() {
+                report.processed += self.apply(target, action, key.as_ref(), &policy.policy_id, due).await?;
+            }
+
+            if !full_page {
+                break;
+            }
+        }
+
+        self.store.log_sweep(&sweep_envelope(&report)).await?;
+        tracing::info!(
+            "Retention policy {} ({}) of tenant {}: {} record(s) before {}, {} on legal hold",
+            report.policy_id, action.as_str(), tenant_id, report.processed, cutoff, report.held
+        );
+        Ok(Some(report))
+    }
+
+    async fn apply(
+        &self,
+        target: RetentionTarget,
+        action: &ExpiryAction,
+        key: Option<&SubjectKey>,
+        policy_id: &str,
+        records: Vec<RetainedRecord>,
+    ) -> Result<u64, DatabaseError> {
+        let ids: Vec<Uuid> = records.iter().map(|record| record.id).collect();
+        match (action, key) {
+            (ExpiryAction::Delete, _) => self.store.delete_records(target, &ids).await,
+            (ExpiryAction::Archive, _) => self.store.archive_records(target, &ids, policy_id).await,
+            (ExpiryAction::Anonymize, _) => {
+                let updates: Vec<RetentionUpdate> = records.iter()
+                    .map(|record| RetentionUpdate {
+                        id: record.id,
+                        user_id: record.user_id.as_ref().map(|_| ANONYMIZED_MARKER.to_string()),
+                        data: anonymize(&record.data),
+                    })
+                    .collect();
+                self.store.rewrite_records(target, &updates, action).await
+            }
+            (ExpiryAction::Encrypt, Some(key)) => {
+                let updates = records.iter()
+                    .map(|record| Ok(RetentionUpdate {
+                        id: record.id,
+                        user_id: record.user_id.clone(),
+                        data: key.seal(&record.data)?,
+                    }))
+                    .collect::<Result<Vec<_>, DatabaseError>>()?;
+                self.store.rewrite_records(target, &updates, action).await
+            }
+            (ExpiryAction::Encrypt, None) => Ok(0),
+        }
+    }
+}
+
+fn sweep_envelope(report: &RetentionSweepReport) -> ForensicEnvelope {
+    ForensicEnvelope::new(
+        Uuid::new_v4(),
+        "retention.swept",
+        "system",
+        Uuid::nil(),
+        ClassificationLevel::Internal,
+        &format!("retention_{}", report.action.as_str()),
+    )
+    .with_resource(&format!("tenant:{}", report.tenant_id))
+    .with_metadata(serde_json::json!({
+        "tenant_id": report.tenant_id,
+        "policy_id": report.policy_id,
+        "target": report.target,
+        "cutoff": report.cutoff,
+        "processed": report.processed,
+        "held": report.held,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::legal_hold::{LegalHold, LegalHoldScope};
+    use crate::security::SealedValue;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    #[derive(Debug, Clone)]
+    struct StoredRecord {
+        record: RetainedRecord,
+        retention_action: Option<String>,
+    }
+
+    /// In-memory stand-in for the entity table and its cold storage
+    #[derive(Default)]
+    struct MemoryStore {
+        live: Mutex<Vec<StoredRecord>>,
+        cold: Mutex<Vec<(String, RetainedRecord)>>,
+        sweeps: Mutex<Vec<ForensicEnvelope>>,
+        keys: Mutex<HashMap<String, (Uuid, [u8; 32])>>,
+        holds: Mutex<Vec<LegalHold>>,
+    }
+
+    impl MemoryStore {
+        fn insert(&self, age_days: i64, legal_hold: bool, now: DateTime<Utc>) -> Uuid {
+            let id = Uuid::new_v4();
+            self.live.lock().unwrap().push(StoredRecord {
+                record: RetainedRecord {
+                    id,
+                    recorded_at: now - Duration::days(age_days),
+                    legal_hold,
+                    user_id: None,
+                    data: serde_json::json!({"name": "Jane Doe", "age": 41, "vip": true}),
+                },
+                retention_action: None,
+            });
+            id
+        }
+
+        fn live(&self, id: Uuid) -> Option<StoredRecord> {
+            self.live.lock().unwrap().iter().find(|stored| stored.record.id == id).cloned()
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl RetentionStore for MemoryStore {
+        async fn expired_page(
+            &self,
+            _target: RetentionTarget,
+            _tenant_id: &str,
+            cutoff: DateTime<Utc>,
+            after: Option<Uuid>,
+            limit: usize,
+        ) -> Result<Vec<RetainedRecord>, DatabaseError> {
+            let mut page: Vec<RetainedRecord> = self.live.lock().unwrap().iter()
+                .filter(|stored| stored.retention_action.is_none() && stored.record.recorded_at < cutoff)
+                .filter(|stored| after.map_or(true, |after| stored.record.id > after))
+                .map(|stored| stored.record.clone())
+                .collect();
+            page.sort_by_key(|record| record.id);
+            page.truncate(limit);
+            Ok(page)
+        }
+
+        async fn delete_records(&self, _target: RetentionTarget, ids: &[Uuid]) -> Result<u64, DatabaseError> {
+            let mut live = self.live.lock().unwrap();
+            let before = live.len();
+            live.retain(|stored| stored.record.legal_hold || !ids.contains(&stored.record.id));
+            Ok((before - live.len()) as u64)
+        }
+
+        async fn archive_records(&self, _target: RetentionTarget, ids: &[Uuid], policy_id: &str) -> Result<u64, DatabaseError> {
+            let mut live = self.live.lock().unwrap();
+            let (archived, kept): (Vec<StoredRecord>, Vec<StoredRecord>) = live.drain(..)
+                .partition(|stored| !stored.record.legal_hold && ids.contains(&stored.record.id));
+            *live = kept;
+            let mut cold = self.cold.lock().unwrap();
+            cold.extend(archived.iter().map(|stored| (policy_id.to_string(), stored.record.clone())));
+            Ok(archived.len() as u64)
+        }
+
+        async fn rewrite_records(
+            &self,
+            _target: RetentionTarget,
+            updates: &[RetentionUpdate],
+            action: &ExpiryAction,
+        ) -> Result<u64, DatabaseError> {
+            let mut live = self.live.lock().unwrap();
+            let mut rewritten = 0;
+            for update in updates {
+                if let Some(stored) = live.iter_mut().find(|stored| stored.record.id == update.id && !stored.record.legal_hold) {
+                    stored.record.data = update.data.clone();
+                    stored.record.user_id = update.user_id.clone();
+                    stored.retention_action = Some(action.as_str().to_string());
+                    rewritten += 1;
+                }
+            }
+            Ok(rewritten)
+        }
+
+        async fn active_legal_holds(&self) -> Result<LegalHolds, DatabaseError> {
+            Ok(LegalHolds::active(self.holds.lock().unwrap().clone(), Utc::now()))
+        }
+
+        async fn retention_key(&self, tenant_id: &str) -> Result<SubjectKey, DatabaseError> {
+            let subject_id = format!("retention:{}", tenant_id);
+            let mut keys = self.keys.lock().unwrap();
+            let (key_id, material) = keys.entry(subject_id.clone()).or_insert((Uuid::new_v4(), [7u8; 32]));
+            Ok(SubjectKey::from_material(&subject_id, *key_id, material.as_slice())?)
+        }
+
+        async fn log_sweep(&self, envelope: &ForensicEnvelope) -> Result<(), DatabaseError> {
+            self.sweeps.lock().unwrap().push(envelope.clone());
+            Ok(())
+        }
+    }
+
+    fn policy(action: ExpiryAction) -> RetentionPolicy {
+        RetentionPolicy {
+            policy_id: format!("entities-{}", action.as_str()),
+            data_type: "entities".to_string(),
+            retention_period: Duration::days(90),
+            action_on_expiry: action,
+        }
+    }
+
+    /// One expired, one fresh and one expired-but-held record, swept with `action` in pages of one
+    async fn sweep_with(action: ExpiryAction) -> (Arc<MemoryStore>, [Uuid; 3], RetentionSweepReport) {
+        let now = Utc::now();
+        let store = Arc::new(MemoryStore::default());
+        let ids = [store.insert(120, false, now), store.insert(10, false, now), store.insert(400, true, now)];
+
+        let sweeper = RetentionSweeper::new(store.clone()).with_page_size(1);
+        let report = sweeper.sweep("acme", &policy(action), now).await.unwrap().unwrap();
+        (store, ids, report)
+    }
+
+    #[tokio::test]
+    async fn test_delete_removes_expired_records() {
+        let (store, [expired, fresh, _], report) = sweep_with(ExpiryAction::Delete).await;
+
+        assert_eq!(report.processed, 1);
+        assert!(store.live(expired).is_none());
+        assert!(store.live(fresh).is_some());
+        assert!(store.cold.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_archive_moves_expired_records_to_cold_storage() {
+        let (store, [expired, fresh, _], report) = sweep_with(ExpiryAction::Archive).await;
+
+        assert_eq!(report.processed, 1);
+        assert!(store.live(expired).is_none());
+        assert!(store.live(fresh).is_some());
+        let cold = store.cold.lock().unwrap();
+        assert_eq!(cold.len(), 1);
+        assert_eq!(cold[0].0, "entities-archive");
+        assert_eq!(cold[0].1.id, expired);
+        assert_eq!(cold[0].1.data["name"], "Jane Doe");
+    }
+
+    #[tokio::test]
+    async fn test_anonymize_rewrites_expired_records_in_place() {
+        let (store, [expired, fresh, _], report) = sweep_with(ExpiryAction::Anonymize).await;
+
+        assert_eq!(report.processed, 1);
+        let anonymized = store.live(expired).unwrap();
+        assert_eq!(
+            anonymized.record.data,
+            serde_json::json!({"name": ANONYMIZED_MARKER, "age": ANONYMIZED_MARKER, "vip": true})
+        );
+        assert_eq!(anonymized.retention_action.as_deref(), Some("anonymize"));
+        assert_eq!(store.live(fresh).unwrap().record.data["name"], "Jane Doe");
+
+        // Already-anonymized records are not picked up again
+        let again = RetentionSweeper::new(store.clone())
+            .sweep("acme", &policy(ExpiryAction::Anonymize), Utc::now())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(again.processed, 0);
+    }
+
+    #[tokio::test]
+    async fn test_encrypt_seals_expired_records_under_tenant_key() {
+        let (store, [expired, fresh, _], report) = sweep_with(ExpiryAction::Encrypt).await;
+
+        assert_eq!(report.processed, 1);
+        let encrypted = store.live(expired).unwrap();
+        assert!(!encrypted.record.data.to_string().contains("Jane Doe"));
+        let sealed = SealedValue::from_json(&encrypted.record.data).unwrap();
+        assert_eq!(sealed.subject_id, "retention:acme");
+        let key = store.retention_key("acme").await.unwrap();
+        assert_eq!(key.open(&sealed).unwrap()["name"], "Jane Doe");
+        assert_eq!(store.live(fresh).unwrap().record.data["name"], "Jane Doe");
+    }
+
+    #[tokio::test]
+    async fn test_legal_hold_records_skipped_and_sweep_logged() {
+        for action in [ExpiryAction::Delete, ExpiryAction::Archive, ExpiryAction::Anonymize, ExpiryAction::Encrypt] {
+            let (store, [_, _, held], report) = sweep_with(action.clone()).await;
+
+            assert_eq!(report.held, 1, "{:?}", action);
+            let untouched = store.live(held).unwrap();
+            assert_eq!(untouched.record.data["name"], "Jane Doe");
+            assert!(untouched.retention_action.is_none());
+
+            let sweeps = store.sweeps.lock().unwrap();
+            assert_eq!(sweeps.len(), 1);
+            assert_eq!(sweeps[0].event_type, "retention.swept");
+            assert_eq!(sweeps[0].action, format!("retention_{}", action.as_str()));
+            assert_eq!(sweeps[0].metadata["processed"], 1);
+            assert_eq!(sweeps[0].metadata["held"], 1);
+            assert_eq!(sweeps[0].metadata["tenant_id"], "acme");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_entity_under_registered_hold_survives_expiry() {
+        let now = Utc::now();
+        let store = Arc::new(MemoryStore::default());
+        let held = store.insert(400, false, now);
+        let expired = store.insert(400, false, now);
+        store.holds.lock().unwrap().push(LegalHold {
+            hold_id: Uuid::new_v4(),
+            scope: LegalHoldScope::Entity(held),
+            reason: "Litigation 2024-117".to_string(),
+            placed_by: "counsel".to_string(),
+            placed_at: now - Duration::days(30),
+            expires_at: Some(now + Duration::days(30)),
+        });
+
+        let report = RetentionSweeper::new(store.clone())
+            .sweep("acme", &policy(ExpiryAction::Delete), now)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!((report.processed, report.held), (1, 1));
+        assert!(store.live(held).is_some());
+        assert!(store.live(expired).is_none());
+    }
+}
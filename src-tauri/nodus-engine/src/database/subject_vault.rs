@@ -0,0 +1,297 @@
+// src-tauri/src/database/subject_vault.rs
+// Subject Vault - Seals data subjects' personal fields on write under per-subject keys
+// Subject keys are stored only wrapped by the KeyProvider; erasing a subject deletes its key, leaving ciphertext nobody can open
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use super::DatabaseError;
+use crate::security::key_provider::{KeyProvider, MASTER_KEY_ID};
+use crate::security::{SealedValue, SecurityError, SubjectKey};
+
+/// Data field naming the subject under the default rule
+pub const DEFAULT_SUBJECT_FIELD: &str = "data_subject_id";
+
+/// Personal fields sealed under the default rule
+pub const DEFAULT_PERSONAL_FIELDS: &[&str] = &[
+    "name", "full_name", "email", "phone", "address", "date_of_birth", "national_id",
+];
+
+/// Provider key that subject keys are wrapped under
+pub const SUBJECT_WRAPPING_KEY_ID: &str = MASTER_KEY_ID;
+
+/// A subject key as persisted: the material only ever wrapped
+#[derive(Debug, Clone)]
+pub struct StoredSubjectKey {
+    pub subject_id: String,
+    pub key_id: Uuid,
+    pub wrapped_key: Vec<u8>,
+}
+
+/// Persistence of wrapped subject keys; deleting a subject's key is its erasure
+#[async_trait::async_trait]
+pub trait SubjectKeyStore: Send + Sync {
+    /// Store `key` unless its subject already has one
+    async fn insert_subject_key(&self, key: &StoredSubjectKey) -> Result<(), DatabaseError>;
+
+    /// The subject's live key
+    async fn subject_key_for(&self, subject_id: &str) -> Result<Option<StoredSubjectKey>, DatabaseError>;
+
+    /// Key `key_id`, unless it has been destroyed
+    async fn subject_key_by_id(&self, key_id: Uuid) -> Result<Option<StoredSubjectKey>, DatabaseError>;
+}
+
+/// Which top-level data fields of which entities hold a subject's personal data
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubjectFieldRule {
+    /// Entity types the rule applies to; empty applies to all
+    #[serde(default)]
+    pub entity_types: Vec<String>,
+    /// Field holding the subject id; left in clear so the subject's records can be found
+    pub subject_field: String,
+    /// Fields sealed under the subject's key
+    pub personal_fields: Vec<String>,
+}
+
+impl Default for SubjectFieldRule {
+    fn default() -> Self {
+        Self {
+            entity_types: Vec::new(),
+            subject_field: DEFAULT_SUBJECT_FIELD.to_string(),
+            personal_fields: DEFAULT_PERSONAL_FIELDS.iter().map(|field| field.to_string()).collect(),
+        }
+    }
+}
+
+impl SubjectFieldRule {
+    fn applies_to(&self, entity_type: &str) -> bool {
+        self.entity_types.is_empty() || self.entity_types.iter().any(|t| t == entity_type)
+    }
+}
+
+/// Seals and opens subject personal data in entity payloads
+#[derive(Debug, Clone)]
+pub struct SubjectVault {
+    key_provider: Arc<dyn KeyProvider>,
+    rules: Vec<SubjectFieldRule>,
+}
+
+impl SubjectVault {
+    /// Vault wrapping subject keys with `key_provider`, with the default field rule
+    pub fn new(key_provider: Arc<dyn KeyProvider>) -> Self {
+        Self { key_provider, rules: vec![SubjectFieldRule::default()] }
+    }
+
+    /// Replace the field rules; the first rule matching an entity's type and naming a subject wins
+    pub fn with_rules(mut self, rules: Vec<SubjectFieldRule>) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// Key that personal data of `subject_id` is sealed under, created on first use
+    pub async fn subject_key(&self, store: &dyn SubjectKeyStore, subject_id: &str) -> Result<SubjectKey, DatabaseError> {
+        if store.subject_key_for(subject_id).await?.is_none() {
+            let candidate = SubjectKey::generate(subject_id)?;
+            let wrapped_key = self.wrap_key_material(candidate.material()).await?;
+            store.insert_subject_key(&StoredSubjectKey {
+                subject_id: subject_id.to_string(),
+                key_id: candidate.key_id,
+                wrapped_key,
+            }).await?;
+        }
+
+        // Another writer may have created the key first
+        let stored = store.subject_key_for(subject_id).await?
+            .ok_or_else(|| SecurityError::CryptoError(format!("Subject {} has no key", subject_id)))?;
+        self.unwrap_key(&stored).await
+    }
+
+    /// `data` with its personal fields sealed, if a rule names its subject
+    ///
+    /// Fields already sealed are left as they are, so merged updates can be sealed again.
+    pub async fn seal_fields(&self, store: &dyn SubjectKeyStore, entity_type: &str, mut data: Value) -> Result<Value, DatabaseError> {
+        let Some((rule, subject_id)) = self.rules.iter()
+            .filter(|rule| rule.applies_to(entity_type))
+            .find_map(|rule| Some((rule, data.get(&rule.subject_field)?.as_str()?.to_string())))
+        else {
+            return Ok(data);
+        };
+        let Some(fields) = data.as_object_mut() else {
+            return Ok(data);
+        };
+
+        let mut key = None;
+        for name in &rule.personal_fields {
+            let Some(value) = fields.get_mut(name) else {
+                continue;
+            };
+            if value.is_null() || SealedValue::from_json(value).is_some() {
+                continue;
+            }
+            if key.is_none() {
+                key = Some(self.subject_key(store, &subject_id).await?);
+            }
+            if let Some(key) = &key {
+                *value = key.seal(value)?;
+            }
+        }
+        Ok(data)
+    }
+
+    /// `data` with its sealed fields opened; fields of erased subjects read as `null`
+    pub async fn open_fields(&self, store: &dyn SubjectKeyStore, mut data: Value) -> Result<Value, DatabaseError> {
+        let Some(fields) = data.as_object_mut() else {
+            return Ok(data);
+        };
+
+        let mut keys: HashMap<Uuid, Option<SubjectKey>> = HashMap::new();
+        for value in fields.values_mut() {
+            let Some(sealed) = SealedValue::from_json(value) else {
+                continue;
+            };
+            let key = match keys.entry(sealed.key_id) {
+                Entry::Occupied(slot) => slot.into_mut(),
+                Entry::Vacant(slot) => slot.insert(match store.subject_key_by_id(sealed.key_id).await? {
+                    Some(stored) => Some(self.unwrap_key(&stored).await?),
+                    None => None,
+                }),
+            };
+            *value = match key {
+                Some(key) => key.open(&sealed)?,
+                None => Value::Null,
+            };
+        }
+        Ok(data)
+    }
+
+    /// Open one sealed value; `None` once its subject has been erased
+    pub async fn open(&self, store: &dyn SubjectKeyStore, sealed: &SealedValue) -> Result<Option<Value>, DatabaseError> {
+        let Some(stored) = store.subject_key_by_id(sealed.key_id).await? else {
+            return Ok(None);
+        };
+        Ok(Some(self.unwrap_key(&stored).await?.open(sealed)?))
+    }
+
+    /// Raw subject key material wrapped for storage
+    pub(crate) async fn wrap_key_material(&self, material: &[u8]) -> Result<Vec<u8>, DatabaseError> {
+        Ok(self.key_provider.wrap(SUBJECT_WRAPPING_KEY_ID, material).await?)
+    }
+
+    async fn unwrap_key(&self, stored: &StoredSubjectKey) -> Result<SubjectKey, DatabaseError> {
+        let material = self.key_provider.unwrap(SUBJECT_WRAPPING_KEY_ID, &stored.wrapped_key).await?;
+        Ok(SubjectKey::from_material(&stored.subject_id, stored.key_id, &material)?)
+    }
+}
+
+/// Subject keys held in memory; mirrors the `subject_keys` table for tests
+#[derive(Debug, Default)]
+pub struct InMemorySubjectKeyStore {
+    keys: RwLock<HashMap<String, StoredSubjectKey>>,
+}
+
+impl InMemorySubjectKeyStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Destroy the subject's key, as `DatabaseManager::erase_subject` does; returns its id
+    pub async fn erase_subject(&self, subject_id: &str) -> Option<Uuid> {
+        self.keys.write().await.remove(subject_id).map(|key| key.key_id)
+    }
+}
+
+#[async_trait::async_trait]
+impl SubjectKeyStore for InMemorySubjectKeyStore {
+    async fn insert_subject_key(&self, key: &StoredSubjectKey) -> Result<(), DatabaseError> {
+        self.keys.write().await.entry(key.subject_id.clone()).or_insert_with(|| key.clone());
+        Ok(())
+    }
+
+    async fn subject_key_for(&self, subject_id: &str) -> Result<Option<StoredSubjectKey>, DatabaseError> {
+        Ok(self.keys.read().await.get(subject_id).cloned())
+    }
+
+    async fn subject_key_by_id(&self, key_id: Uuid) -> Result<Option<StoredSubjectKey>, DatabaseError> {
+        Ok(self.keys.read().await.values().find(|key| key.key_id == key_id).cloned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::InMemoryKeyProvider;
+    use serde_json::json;
+
+    fn vault() -> SubjectVault {
+        let provider = InMemoryKeyProvider::new().with_generated_key(SUBJECT_WRAPPING_KEY_ID).unwrap();
+        SubjectVault::new(Arc::new(provider))
+    }
+
+    #[tokio::test]
+    async fn test_erased_subject_fields_cannot_be_read_back() {
+        let vault = vault();
+        let store = InMemorySubjectKeyStore::new();
+        let contact = json!({
+            "data_subject_id": "subject-42",
+            "name": "Jane Doe",
+            "email": "jane@example.com",
+            "status": "active"
+        });
+
+        // Personal fields reach storage only sealed; the subject id and other fields stay in clear
+        let stored = vault.seal_fields(&store, "contact", contact.clone()).await.unwrap();
+        let serialized = stored.to_string();
+        assert!(!serialized.contains("Jane Doe") && !serialized.contains("jane@example.com"), "{}", serialized);
+        assert_eq!(stored["status"], "active");
+        assert_eq!(stored["data_subject_id"], "subject-42");
+        assert_eq!(vault.open_fields(&store, stored.clone()).await.unwrap(), contact);
+
+        // The key itself is held only wrapped
+        let key = vault.subject_key(&store, "subject-42").await.unwrap();
+        let held = store.subject_key_for("subject-42").await.unwrap().unwrap();
+        assert_ne!(held.wrapped_key.as_slice(), key.material());
+        assert!(!held.wrapped_key.windows(key.material().len()).any(|window| window == key.material()));
+
+        // Erasure destroys the key: the stored row is unchanged but reads back without the personal data
+        assert_eq!(store.erase_subject("subject-42").await, Some(key.key_id));
+        let read_back = vault.open_fields(&store, stored.clone()).await.unwrap();
+        assert_eq!(read_back, json!({
+            "data_subject_id": "subject-42",
+            "name": null,
+            "email": null,
+            "status": "active"
+        }));
+        let sealed = SealedValue::from_json(&stored["email"]).unwrap();
+        assert!(vault.open(&store, &sealed).await.unwrap().is_none());
+
+        // A key issued to the same subject afterwards opens only new data
+        let reissued = vault.subject_key(&store, "subject-42").await.unwrap();
+        assert_ne!(reissued.key_id, key.key_id);
+        assert!(reissued.open(&sealed).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_only_subject_data_is_sealed_and_never_twice() {
+        let vault = vault();
+        let store = InMemorySubjectKeyStore::new();
+
+        // No subject named: stored as given, without creating keys
+        let note = json!({"name": "Roadmap", "status": "draft"});
+        assert_eq!(vault.seal_fields(&store, "note", note.clone()).await.unwrap(), note);
+        assert!(store.keys.read().await.is_empty());
+
+        // Sealing a merged update leaves already sealed fields alone
+        let sealed = vault.seal_fields(&store, "contact", json!({"data_subject_id": "s-1", "email": "a@example.com"})).await.unwrap();
+        let mut merged = sealed.clone();
+        merged["phone"] = json!("555-0100");
+        let resealed = vault.seal_fields(&store, "contact", merged).await.unwrap();
+        assert_eq!(resealed["email"], sealed["email"]);
+        assert!(SealedValue::from_json(&resealed["phone"]).is_some());
+        assert_eq!(vault.open_fields(&store, resealed).await.unwrap()["phone"], "555-0100");
+    }
+}
@@ -11,10 +11,14 @@ use chrono::{DateTime, Utc, Duration};
 use std::net::SocketAddr;
 
 use crate::security::{SecurityManager, ClassificationLevel, SecurityLabel};
+use crate::security::api_keys::ApiKeyManager;
 use crate::license::{LicenseManager, LicenseTier};
 use crate::observability::{ForensicLogger, MetricsRegistry};
+use crate::observability::automatic_instrumentation::InstrumentationHooks;
 use crate::enterprise::multi_tenant::MultiTenantSystem;
-use crate::state::AppState;
+use crate::database::DatabaseManager;
+use crate::networking::{GatewayRequest, GatewayRouter, HttpMethod, NetworkError, SecureNetworkTransport, SecureResponse};
+use crate::state::{AppState, UserContext};
 
 /// Enterprise API Gateway for advanced API management
 #[derive(Debug)]
@@ -54,6 +58,12 @@ pub struct EnterpriseAPIGateway {
     
     /// Circuit breaker for fault tolerance
     circuit_breakers: Arc<RwLock<HashMap<String, CircuitBreaker>>>,
+    
+    /// Route table forwarding through the secure network transport
+    router: GatewayRouter,
+    
+    /// Validates keys presented by machine callers
+    api_keys: ApiKeyManager,
 }
 
 /// API route configuration
@@ -99,18 +109,6 @@ pub struct APIRoute {
     pub metadata: RouteMetadata,
 }
 
-/// HTTP methods
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub enum HttpMethod {
-    GET,
-    POST,
-    PUT,
-    DELETE,
-    PATCH,
-    HEAD,
-    OPTIONS,
-}
-
 /// Backend service configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BackendConfig {
@@ -414,10 +412,11 @@ pub enum RateLimitType {
 }
 
 /// Rate limit scopes
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum RateLimitScope {
     Global,
     PerApiKey,
+    #[default]
     PerUser,
     PerTenant,
     PerIP,
@@ -806,11 +805,15 @@ pub enum GatewayError {
     #[error("Authentication failed: {reason}")]
     AuthenticationFailed { reason: String },
     
-    #[error("Authorization failed: {reason}")]
-    AuthorizationFailed { reason: String },
+    #[error("Authorization failed for route {route_id}: {reason}")]
+    AuthorizationFailed { route_id: String, reason: String },
     
     #[error("Rate limit exceeded: {limit_type}")]
-    RateLimitExceeded { limit_type: String },
+    RateLimitExceeded { 
+        limit_type: String,
+        /// How long until the caller's next request would be admitted, when known
+        retry_after: Option<std::time::Duration>,
+    },
     
     #[error("Backend error: {backend_id}, error: {error}")]
     BackendError { backend_id: String, error: String },
@@ -826,6 +829,31 @@ pub enum GatewayError {
     
     #[error("Insufficient license for API gateway features: requires {required_license:?}")]
     InsufficientLicense { required_license: LicenseTier },
+    
+    #[error("Invalid route {route_id}: {reason}")]
+    InvalidRoute { route_id: String, reason: String },
+    
+    #[error("Route {route_id} has no healthy upstream")]
+    NoHealthyUpstream { route_id: String },
+    
+    #[error("Upstream request failed: {0}")]
+    Upstream(#[from] NetworkError),
+}
+
+impl GatewayError {
+    /// HTTP status the caller sees
+    pub fn status_code(&self) -> u16 {
+        match self {
+            GatewayError::RouteNotFound { .. } => 404,
+            GatewayError::AuthenticationFailed { .. } => 401,
+            GatewayError::AuthorizationFailed { .. } | GatewayError::InsufficientLicense { .. } => 403,
+            GatewayError::RateLimitExceeded { .. } => 429,
+            GatewayError::InvalidRoute { .. } => 500,
+            GatewayError::CircuitBreakerOpen { .. } | GatewayError::NoHealthyUpstream { .. } => 503,
+            GatewayError::Upstream(NetworkError::HttpError(status, _)) => *status,
+            GatewayError::BackendError { .. } | GatewayError::TransformationFailed { .. } | GatewayError::Upstream(_) => 502,
+        }
+    }
 }
 
 /// Authentication errors
@@ -862,12 +890,17 @@ pub enum TransformationError {
 
 impl EnterpriseAPIGateway {
     /// Create new enterprise API gateway
+    ///
+    /// Routes forward through `network_transport`, skipping upstreams the tenant's health
+    /// prober reports unhealthy; API keys are validated against `database_manager`.
     pub async fn new(
         security_manager: Arc<SecurityManager>,
         license_manager: Arc<LicenseManager>,
         forensic_logger: Arc<ForensicLogger>,
         metrics_registry: Arc<MetricsRegistry>,
         multi_tenant_system: Arc<MultiTenantSystem>,
+        network_transport: Arc<SecureNetworkTransport>,
+        database_manager: Arc<DatabaseManager>,
     ) -> Result<Self, GatewayError> {
         // Verify enterprise license for API gateway features
         let current_license = license_manager.get_current_license().await;
@@ -882,6 +915,8 @@ impl EnterpriseAPIGateway {
         let transformer = RequestTransformer::new().await?;
         let analytics = APIAnalytics::new().await?;
         let load_balancer = LoadBalancer::new().await?;
+        let router = GatewayRouter::new(network_transport).with_tenant_health(multi_tenant_system.clone());
+        let api_keys = ApiKeyManager::new(database_manager);
         
         Ok(Self {
             routes: Arc::new(RwLock::new(HashMap::new())),
@@ -896,9 +931,44 @@ impl EnterpriseAPIGateway {
             multi_tenant_system,
             load_balancer,
             circuit_breakers: Arc::new(RwLock::new(HashMap::new())),
+            router,
+            api_keys,
         })
     }
     
+    /// Route table requests are forwarded by
+    pub fn router(&self) -> &GatewayRouter {
+        &self.router
+    }
+    
+    /// Authenticate `request` and forward it through the route table
+    ///
+    /// A presented API key takes precedence over `session_user`; a request with neither is rejected.
+    pub async fn forward<H>(
+        &self,
+        request: GatewayRequest,
+        session_user: Option<&UserContext>,
+        hooks: &H,
+    ) -> Result<SecureResponse, GatewayError>
+    where
+        H: InstrumentationHooks + ?Sized,
+    {
+        let key_user = match request.api_key() {
+            Some(key) => Some(
+                self.api_keys
+                    .validate_api_key(key)
+                    .await
+                    .map_err(|e| GatewayError::AuthenticationFailed { reason: e.to_string() })?
+                    .to_user_context(),
+            ),
+            None => None,
+        };
+        let user = key_user.as_ref().or(session_user).ok_or_else(|| GatewayError::AuthenticationFailed {
+            reason: "no API key or session presented".to_string(),
+        })?;
+        self.router.forward(request, user, hooks).await
+    }
+    
     /// Process incoming API request
     pub async fn process_request(
         &self,
@@ -1024,7 +1094,7 @@ impl EnterpriseAPIGateway {
     /// Get API gateway metrics
     pub async fn get_gateway_metrics(&self) -> GatewayMetrics {
         let routes = self.routes.read().await;
-        let total_routes = routes.len();
+        let total_routes = routes.len() + self.router.routes().await.len();
         
         let analytics = self.analytics.analytics_store.read().await;
         let total_requests: u64 = analytics.values().map(|data| data.request_count).sum();
@@ -1117,6 +1187,7 @@ impl EnterpriseAPIGateway {
             if self.rate_limiter.check_limit(request, rule).await? {
                 return Err(GatewayError::RateLimitExceeded {
                     limit_type: format!("{:?}", rule.rule_type),
+                    retry_after: None,
                 });
             }
         }
@@ -1134,6 +1205,7 @@ impl EnterpriseAPIGateway {
         for policy in &route.auth_config.authorization_policies {
             if !self.evaluate_authorization_policy(policy, request, auth_result).await {
                 return Err(GatewayError::AuthorizationFailed {
+                    route_id: route.route_id.clone(),
                     reason: format!("Policy {} denied access", policy.policy_id),
                 });
             }
@@ -10,6 +10,7 @@ use chrono::{DateTime, Utc, Duration};
 use uuid::Uuid;
 
 use crate::observability::{ForensicLogger, MetricsRegistry, ObservabilityContext};
+use crate::observability::compliance::{ComplianceEngine, ComplianceScope, ComplianceScore, ComplianceSummary, ComplianceWindow};
 use crate::security::{SecurityManager, ClassificationLevel, SecurityLabel};
 use crate::license::{LicenseManager, LicenseTier};
 use crate::database::DatabaseManager;
@@ -38,6 +39,9 @@ pub struct ComplianceDashboard {
     
     /// Cached compliance reports
     report_cache: Arc<RwLock<HashMap<String, CachedComplianceReport>>>,
+    
+    /// Per-operation compliance evidence, when observation records are assessed
+    compliance_engine: Option<Arc<ComplianceEngine>>,
 }
 
 /// Supported compliance frameworks
@@ -583,9 +587,37 @@ impl ComplianceDashboard {
             rule_engine,
             report_generators,
             report_cache: Arc::new(RwLock::new(HashMap::new())),
+            compliance_engine: None,
         })
     }
     
+    /// Report per-operation scores from `engine` alongside the framework reports
+    pub fn with_compliance_engine(mut self, engine: Arc<ComplianceEngine>) -> Self {
+        self.compliance_engine = Some(engine);
+        self
+    }
+    
+    /// Per-framework scores of the operations assessed so far
+    pub async fn operation_scores(&self) -> Vec<ComplianceScore> {
+        match &self.compliance_engine {
+            Some(engine) => engine.scores().await,
+            None => Vec::new(),
+        }
+    }
+    
+    /// Posture of `framework` over `window` from per-operation evidence; `None` without an engine
+    pub async fn compliance_summary(
+        &self,
+        framework: &crate::observability::observation::ComplianceFramework,
+        window: ComplianceWindow,
+        scope: &ComplianceScope,
+    ) -> Option<Result<ComplianceSummary, crate::observability::ComplianceError>> {
+        match &self.compliance_engine {
+            Some(engine) => Some(engine.compliance_summary(framework, window, scope).await),
+            None => None,
+        }
+    }
+    
     /// Generate compliance report for a specific framework
     pub async fn generate_compliance_report(
         &self,
@@ -690,6 +722,7 @@ impl ComplianceDashboard {
         Ok(ComplianceDashboardSummary {
             available_frameworks,
             framework_status,
+            operation_scores: self.operation_scores().await,
             last_updated: Utc::now(),
         })
     }
@@ -864,6 +897,9 @@ impl ComplianceDashboard {
 pub struct ComplianceDashboardSummary {
     pub available_frameworks: Vec<ComplianceFramework>,
     pub framework_status: HashMap<ComplianceFramework, FrameworkStatus>,
+    /// Scores from assessing individual operations
+    #[serde(default)]
+    pub operation_scores: Vec<ComplianceScore>,
     pub last_updated: DateTime<Utc>,
 }
 
@@ -8,9 +8,13 @@ use tokio::sync::RwLock;
 use std::collections::HashMap;
 
 use crate::license::{LicenseManager, LicenseTier};
-use crate::security::SecurityManager;
-use crate::observability::{ForensicLogger, MetricsRegistry};
+use crate::security::{KeyProvider, SecurityManager};
+use crate::observability::{AuditExportScheduler, ComplianceEngine, ForensicLogger, MetricsRegistry, PiiDetector};
+use crate::observability::audit_export::AUDIT_EXPORT_STATE_ENV;
+use crate::observability::exporters::SecurityEventSink;
+use crate::observability::AlertRuleEngine;
 use crate::database::DatabaseManager;
+use crate::networking::SecureNetworkTransport;
 use crate::state::AppState;
 
 // Re-export enterprise modules
@@ -65,6 +69,19 @@ pub struct EnterpriseConfig {
     pub enable_multi_tenant: bool,
     pub enable_api_gateway: bool,
     pub custom_config: serde_json::Value,
+    /// Sink tenants' alert channels are registered with, when security alerts are configured
+    pub security_sink: Option<Arc<SecurityEventSink>>,
+    /// Engine tenants' alert rules are evaluated by
+    pub alert_rules: Option<Arc<AlertRuleEngine>>,
+    /// Transport tenants' load balancer targets are health-probed, and WASM plugins fetch, through;
+    /// the API gateway forwards through it too and isn't started without one
+    pub network_transport: Option<Arc<SecureNetworkTransport>>,
+    /// Detector redacting PII from entity data handed to WASM plugins
+    pub pii_detector: Option<Arc<PiiDetector>>,
+    /// Engine assessing exported operations, whose scores and summaries the dashboard reports
+    pub compliance_engine: Option<Arc<ComplianceEngine>>,
+    /// Provider of system-managed keys; tenants managing their own keys register theirs with the multi-tenant system
+    pub key_provider: Option<Arc<dyn KeyProvider>>,
 }
 
 impl Default for EnterpriseConfig {
@@ -75,6 +92,12 @@ impl Default for EnterpriseConfig {
             enable_multi_tenant: true,
             enable_api_gateway: true,
             custom_config: serde_json::Value::Null,
+            security_sink: None,
+            alert_rules: None,
+            network_transport: None,
+            pii_detector: None,
+            compliance_engine: None,
+            key_provider: None,
         }
     }
 }
@@ -159,6 +182,16 @@ impl EnterpriseManager {
                 forensic_logger.clone(),
             ).await {
                 Ok(plugin_system) => {
+                    #[cfg(feature = "plugins_wasm")]
+                    let plugin_system = match &config.network_transport {
+                        Some(transport) => plugin_system.with_host_services(database_manager.clone(), transport.clone()),
+                        None => plugin_system,
+                    };
+                    #[cfg(feature = "plugins_wasm")]
+                    let plugin_system = match &config.pii_detector {
+                        Some(detector) => plugin_system.with_pii_detector(detector.clone()),
+                        None => plugin_system,
+                    };
                     self.plugin_system = Some(Arc::new(plugin_system));
                     tracing::info!("Enterprise plugin system initialized");
                 },
@@ -177,6 +210,10 @@ impl EnterpriseManager {
                 database_manager.clone(),
             ).await {
                 Ok(compliance_dashboard) => {
+                    let compliance_dashboard = match &config.compliance_engine {
+                        Some(engine) => compliance_dashboard.with_compliance_engine(engine.clone()),
+                        None => compliance_dashboard,
+                    };
                     self.compliance_dashboard = Some(Arc::new(compliance_dashboard));
                     tracing::info!("Enterprise compliance dashboard initialized");
                 },
@@ -196,6 +233,32 @@ impl EnterpriseManager {
                 database_manager.clone(),
             ).await {
                 Ok(multi_tenant_system) => {
+                    let multi_tenant_system = match std::env::var(AUDIT_EXPORT_STATE_ENV) {
+                        Ok(path) => match AuditExportScheduler::open(&path, forensic_logger.clone()).await {
+                            Ok(scheduler) => multi_tenant_system.with_audit_exports(Arc::new(scheduler)),
+                            Err(e) => {
+                                tracing::warn!("Failed to load audit export state from {}; progress will not persist: {}", path, e);
+                                multi_tenant_system
+                            }
+                        },
+                        Err(_) => multi_tenant_system,
+                    };
+                    let multi_tenant_system = match &config.security_sink {
+                        Some(sink) => multi_tenant_system.with_security_sink(sink.clone()),
+                        None => multi_tenant_system,
+                    };
+                    let multi_tenant_system = match &config.alert_rules {
+                        Some(engine) => multi_tenant_system.with_alert_rules(engine.clone()),
+                        None => multi_tenant_system,
+                    };
+                    let multi_tenant_system = match &config.network_transport {
+                        Some(transport) => multi_tenant_system.with_health_probing(transport.clone()),
+                        None => multi_tenant_system,
+                    };
+                    let multi_tenant_system = match &config.key_provider {
+                        Some(provider) => multi_tenant_system.with_key_provider(provider.clone()),
+                        None => multi_tenant_system,
+                    };
                     self.multi_tenant_system = Some(Arc::new(multi_tenant_system));
                     tracing::info!("Enterprise multi-tenant system initialized");
                 },
@@ -207,28 +270,38 @@ impl EnterpriseManager {
         
         // Initialize API gateway
         if config.enable_api_gateway && self.is_feature_available("api_gateway").await {
-            if let Some(multi_tenant_system) = &self.multi_tenant_system {
-                match EnterpriseAPIGateway::new(
+            match (&self.multi_tenant_system, &config.network_transport) {
+                (Some(multi_tenant_system), Some(transport)) => match EnterpriseAPIGateway::new(
                     security_manager.clone(),
                     self.license_manager.clone(),
                     forensic_logger.clone(),
                     metrics_registry.clone(),
                     multi_tenant_system.clone(),
+                    transport.clone(),
+                    database_manager.clone(),
                 ).await {
                     Ok(api_gateway) => {
                         self.api_gateway = Some(Arc::new(api_gateway));
                         tracing::info!("Enterprise API gateway initialized");
                     },
                     Err(e) => {
-                        tracing::warn!("Failed to initialize API gateway: {}", e);
+                        self.report_api_gateway_unavailable(&format!("initialization failed: {}", e)).await;
                     }
-                }
+                },
+                (_, None) => self.report_api_gateway_unavailable("no network transport is configured").await,
+                (None, Some(_)) => self.report_api_gateway_unavailable("the multi-tenant system is not running").await,
             }
         }
         
         Ok(())
     }
     
+    /// Warn that the enabled API gateway isn't running, and stop reporting it available
+    async fn report_api_gateway_unavailable(&self, reason: &str) {
+        tracing::warn!("API gateway is enabled but not running: {}", reason);
+        self.features_status.write().await.api_gateway_available = false;
+    }
+
     /// Check if a specific enterprise feature is available
     pub async fn is_feature_available(&self, feature_name: &str) -> bool {
         let status = self.features_status.read().await;
@@ -270,7 +343,9 @@ impl EnterpriseManager {
     /// Refresh enterprise feature availability (call when license changes)
     pub async fn refresh_feature_availability(&self) -> Result<(), EnterpriseError> {
         let current_license = self.license_manager.get_current_license().await;
-        let new_status = Self::check_feature_availability(&current_license.tier);
+        let mut new_status = Self::check_feature_availability(&current_license.tier);
+        // The gateway is only built at startup; a license change doesn't start it
+        new_status.api_gateway_available &= self.api_gateway.is_some();
         
         *self.features_status.write().await = new_status;
         
@@ -1,6 +1,8 @@
 // Crate root module exports for nodus-engine
 // Keep exports in sync with files and directories that actually exist.
 
+pub mod cache;
+pub mod clock;
 pub mod commands;
 pub mod database; // consolidated database directory (re-exports database_mod)
 pub mod enterprise;
@@ -11,12 +13,16 @@ pub mod multi_tenant;
 pub mod networking;
 pub mod observability;
 pub mod plugin_system;
+// Sandboxed runtime for untrusted plugins; pulls in wasmtime, so it's opt-in
+#[cfg(feature = "plugins_wasm")]
+pub mod plugin_wasm;
 pub mod policy;
 // Advertising module is experimental and not used for now. Gate it behind
 // the `advertising` feature so it won't be compiled by default.
 #[cfg(feature = "advertising")]
 pub mod privacy_ad_platform;
 pub mod security;
+pub mod shutdown;
 pub mod state;
 pub mod storage;
 pub mod sync;
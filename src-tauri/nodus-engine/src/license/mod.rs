@@ -4,11 +4,20 @@
 
 use base64::{engine::general_purpose, Engine as _};
 use chrono::{DateTime, Duration, Utc};
-use ring::{digest, hmac};
+use dashmap::DashMap;
+use ring::signature::{self, Ed25519KeyPair, KeyPair};
+use ring::{digest, hkdf, rand};
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::{Arc, Mutex};
+use tokio::sync::broadcast;
 use uuid::Uuid;
 
+use crate::cache::TtlCache;
+use crate::clock::{self, Clock};
+use crate::security::SecurityEvent;
+use crate::security::key_provider::{system_key_provider, KeyProvider, KeyProviderError, MASTER_KEY_ID};
+
 /// License tiers matching the four-tier strategy (OpenSource / Pro / Enterprise / Defense)
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum LicenseTier {
@@ -58,6 +67,68 @@ pub struct LicenseLimits {
 /// Feature definitions for each tier
 pub struct LicenseFeatures;
 
+/// Limits tracked by the metering store, in report order
+const METERED_LIMITS: [&str; 6] = [
+    "users",
+    "storage_gb",
+    "operations_per_hour",
+    "api_calls_per_day",
+    "concurrent_sessions",
+    "tenants",
+];
+
+/// Feature-gate events kept for audit before older entries are dropped
+const MAX_SECURITY_EVENTS: usize = 1000;
+
+/// Usage counted against one limit within its current window
+#[derive(Debug, Clone)]
+struct UsageWindow {
+    count: u32,
+    window_start: DateTime<Utc>,
+}
+
+/// Response file picked up at startup once an offline activation has been carried back
+const ACTIVATION_RESPONSE_FILE: &str = "license_activation.json";
+
+/// Device secret, wrapped under the master key, that the device key is derived from
+const DEVICE_SECRET_FILE: &str = "license_device_secret.bin";
+
+/// Activation request for air-gapped installs, carried out to the license server by the operator
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActivationChallenge {
+    /// Hex SHA-256 of the machine's device public key
+    pub fingerprint: String,
+    /// Base64 Ed25519 device public key
+    pub device_public_key: String,
+    pub requested_at: DateTime<Utc>,
+    /// Base64 Ed25519 signature by the device key over the fields above
+    pub signature: String,
+}
+
+/// Signed license issued for one machine fingerprint
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ActivationResponse {
+    pub license: LicenseInfo,
+    pub fingerprint: String,
+    /// Activation key that signed this response
+    pub key_id: String,
+    /// Base64 Ed25519 signature over the fingerprint and license terms
+    pub signature: String,
+}
+
+/// Machine identity used for offline activation
+///
+/// The device key is derived with HKDF from a random device secret, salted with the stable
+/// OS/hardware identifiers. The secret is stored wrapped by the key provider, so reading the
+/// (world-readable) machine identifiers isn't enough to forge a challenge, and a copied secret
+/// file derives a different key on another machine. Only the hash of the public key leaves
+/// the machine.
+#[derive(Debug)]
+pub struct MachineFingerprint {
+    device_key: Ed25519KeyPair,
+    fingerprint: String,
+}
+
 impl LicenseFeatures {
     /// Community tier features (open source baseline)
     pub fn community_features() -> HashSet<String> {
@@ -181,30 +252,69 @@ impl LicenseFeatures {
 #[derive(Debug)]
 pub struct LicenseManager {
     current_license: Option<LicenseInfo>,
-    verification_keys: HashMap<String, String>,
-    feature_cache: HashMap<String, bool>,
+    key_provider: Arc<dyn KeyProvider>,
+    activation_keys: HashMap<String, Vec<u8>>,
+    /// Features of the current license, expiring with it
+    feature_cache: TtlCache<String, ()>,
+    machine: Option<MachineFingerprint>,
+    usage: DashMap<String, UsageWindow>,
+    security_events: Mutex<VecDeque<SecurityEvent>>,
+    security_event_tx: broadcast::Sender<SecurityEvent>,
+    clock: Arc<dyn Clock>,
 }
 
 impl LicenseManager {
-    /// Create new license manager
+    /// Create new license manager with the system key provider
     pub async fn new() -> Result<Self, LicenseError> {
+        Self::with_key_provider(system_key_provider().await?).await
+    }
+
+    /// Create a license manager that verifies license MACs with `key_provider`
+    pub async fn with_key_provider(key_provider: Arc<dyn KeyProvider>) -> Result<Self, LicenseError> {
         let mut manager = Self {
             current_license: None,
-            verification_keys: HashMap::new(),
-            feature_cache: HashMap::new(),
+            key_provider,
+            activation_keys: HashMap::new(),
+            feature_cache: TtlCache::new(1),
+            machine: None,
+            usage: DashMap::new(),
+            security_events: Mutex::new(VecDeque::new()),
+            security_event_tx: broadcast::channel(MAX_SECURITY_EVENTS).0,
+            clock: clock::system_clock(),
         };
 
-        // Load verification keys (in production, these would be embedded or from secure storage)
+        // Load public activation keys; MAC keys stay with the key provider
         manager.load_verification_keys().await?;
 
+        // Without a device identity only offline activation is unavailable
+        match MachineFingerprint::detect(manager.key_provider.as_ref(), DEVICE_SECRET_FILE).await {
+            Ok(machine) => manager.machine = Some(machine),
+            Err(e) => tracing::warn!("Device identity unavailable, offline activation disabled: {}", e),
+        }
+
         // Detect and validate current license
         manager.detect_license().await?;
 
         Ok(manager)
     }
 
+    /// Read time from `clock` instead of the system clock for expiry and usage windows
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self.rebuild_feature_cache();
+        self
+    }
+
     /// Detect current license from environment/file/registry
     async fn detect_license(&mut self) -> Result<(), LicenseError> {
+        // Offline activation installed on an air-gapped machine
+        if let Ok(response) = std::fs::read_to_string(ACTIVATION_RESPONSE_FILE) {
+            if self.apply_activation_response(&response).await.is_ok() {
+                return Ok(());
+            }
+            crate::security_log!(warn, "Ignoring offline activation that does not verify for this machine");
+        }
+
         // Check for license file first
         if let Ok(license_data) = std::fs::read_to_string("license.json") {
             if let Ok(license) = serde_json::from_str::<LicenseInfo>(&license_data) {
@@ -238,7 +348,7 @@ impl LicenseManager {
             status: LicenseStatus::Valid,
             organization: "Community User".to_string(),
             issued_to: "community@nodus.com".to_string(),
-            issued_at: Utc::now(),
+            issued_at: self.clock.now(),
             expires_at: None, // Community never expires
             features: LicenseFeatures::community_features(),
             limits: LicenseLimits {
@@ -259,18 +369,23 @@ impl LicenseManager {
 
     /// Validate and set license with cryptographic verification
     async fn validate_and_set_license(&mut self, license: LicenseInfo) -> Result<(), LicenseError> {
+        // Verify signature for non-community licenses
+        if license.tier != LicenseTier::Community {
+            self.verify_license_signature(&license).await?;
+        }
+
+        self.install_license(license)
+    }
+
+    /// Install a license whose signature has already been verified
+    fn install_license(&mut self, license: LicenseInfo) -> Result<(), LicenseError> {
         // Check expiration
         if let Some(expires_at) = license.expires_at {
-            if Utc::now() > expires_at {
+            if self.clock.now() > expires_at {
                 return Err(LicenseError::Expired);
             }
         }
 
-        // Verify signature for non-community licenses
-        if license.tier != LicenseTier::Community {
-            self.verify_license_signature(&license)?;
-        }
-
         // Check status
         if license.status != LicenseStatus::Valid {
             return Err(LicenseError::Invalid);
@@ -282,65 +397,186 @@ impl LicenseManager {
         Ok(())
     }
 
-    /// Verify license signature using HMAC
-    fn verify_license_signature(&self, license: &LicenseInfo) -> Result<(), LicenseError> {
-        let verification_key = self
-            .verification_keys
-            .get(&license.verification_key)
-            .ok_or(LicenseError::InvalidSignature)?;
+    /// Verify license signature using HMAC, checked by the key provider holding `verification_key`
+    async fn verify_license_signature(&self, license: &LicenseInfo) -> Result<(), LicenseError> {
+        let tag = general_purpose::STANDARD
+            .decode(&license.signature)
+            .map_err(|_| LicenseError::InvalidSignature)?;
+        let message = license_signature_message(license);
+        match self.key_provider.verify_mac(&license.verification_key, message.as_bytes(), &tag).await {
+            Ok(true) => Ok(()),
+            Ok(false) | Err(KeyProviderError::KeyNotFound(_)) | Err(KeyProviderError::InvalidKeyId(_)) => {
+                Err(LicenseError::InvalidSignature)
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
 
-        // Create message to verify (simplified - in production use more fields)
-        // Use the textual representation of the tier in the signed message to avoid
-        // relying on numeric casts (keeps enum changes low-risk).
-        let message = format!(
-            "{}:{:?}:{}:{}",
-            license.license_id,
-            license.tier,
-            license.organization,
-            license.issued_at.timestamp()
-        );
+    /// Build an activation request for this machine (air-gapped installs)
+    ///
+    /// The operator carries the challenge to the license server and brings back the
+    /// signed `ActivationResponse` for `apply_activation_response`.
+    pub fn generate_activation_request(&self) -> Result<ActivationChallenge, LicenseError> {
+        let machine = self.machine()?;
+        let device_public_key =
+            general_purpose::STANDARD.encode(machine.device_key.public_key().as_ref());
+        let requested_at = self.clock.now();
+
+        let message = activation_request_message(&machine.fingerprint, &device_public_key, &requested_at);
+        let signature = general_purpose::STANDARD.encode(machine.device_key.sign(&message).as_ref());
+
+        Ok(ActivationChallenge {
+            fingerprint: machine.fingerprint.clone(),
+            device_public_key,
+            requested_at,
+            signature,
+        })
+    }
 
-        // Verify HMAC signature
-        let key = hmac::Key::new(hmac::HMAC_SHA256, verification_key.as_bytes());
-        let expected_signature =
-            general_purpose::STANDARD.encode(hmac::sign(&key, message.as_bytes()).as_ref());
+    /// Verify an offline activation response and install its license
+    ///
+    /// The response must be signed by a known activation key and bound to this
+    /// machine's fingerprint.
+    pub async fn apply_activation_response(&mut self, signed_response: &str) -> Result<LicenseInfo, LicenseError> {
+        let response: ActivationResponse = serde_json::from_str(signed_response)?;
 
-        if expected_signature != license.signature {
-            return Err(LicenseError::InvalidSignature);
+        let public_key = self
+            .activation_keys
+            .get(&response.key_id)
+            .ok_or(LicenseError::InvalidSignature)?;
+        let signature_bytes = general_purpose::STANDARD
+            .decode(&response.signature)
+            .map_err(|_| LicenseError::InvalidSignature)?;
+        signature::UnparsedPublicKey::new(&signature::ED25519, public_key)
+            .verify(&activation_response_message(&response), &signature_bytes)
+            .map_err(|_| LicenseError::InvalidSignature)?;
+
+        if response.fingerprint != self.machine()?.fingerprint {
+            return Err(LicenseError::FingerprintMismatch);
         }
 
-        Ok(())
+        self.install_license(response.license.clone())?;
+        tracing::info!(
+            "Offline activation installed {:?} license {}",
+            response.license.tier,
+            response.license.license_id
+        );
+        Ok(response.license)
+    }
+
+    /// This machine's device identity, e.g. for signing attestations
+    pub fn device_identity(&self) -> Result<&MachineFingerprint, LicenseError> {
+        self.machine()
     }
 
-    /// Load verification keys (in production, embed these securely)
+    fn machine(&self) -> Result<&MachineFingerprint, LicenseError> {
+        self.machine.as_ref().ok_or_else(|| {
+            LicenseError::Activation("No stable machine identifier available".to_string())
+        })
+    }
+
+    /// Load verification keys
+    ///
+    /// Only public keys are embedded; license MAC keys are held by the key provider.
     async fn load_verification_keys(&mut self) -> Result<(), LicenseError> {
-        // In production, these would be embedded in the binary or loaded from secure storage
-        self.verification_keys.insert(
-            "enterprise_key_v1".to_string(),
-            "enterprise_verification_key_2024".to_string(),
-        );
-        self.verification_keys.insert(
-            "defense_key_v1".to_string(),
-            "defense_verification_key_2024_classified".to_string(),
-        );
+        // Ed25519 public key for offline activation responses
+        let defense_key = general_purpose::STANDARD
+            .decode(DEFENSE_ACTIVATION_PUBLIC_KEY)
+            .map_err(|e| LicenseError::Activation(format!("Invalid embedded activation key: {}", e)))?;
+        self.activation_keys.insert("defense_activation_key_v1".to_string(), defense_key);
 
         Ok(())
     }
 
-    /// Rebuild feature cache for fast lookups
+    /// Rebuild feature cache for fast lookups; features lapse when the license expires
     fn rebuild_feature_cache(&mut self) {
-        self.feature_cache.clear();
-
-        if let Some(ref license) = self.current_license {
-            for feature in &license.features {
-                self.feature_cache.insert(feature.clone(), true);
+        let Some(license) = &self.current_license else {
+            self.feature_cache = TtlCache::new(1).with_clock(self.clock.clone());
+            return;
+        };
+        let mut cache = TtlCache::new(license.features.len()).with_clock(self.clock.clone());
+        let remaining = license.expires_at.map(|expires_at| (expires_at - self.clock.now()).to_std());
+        match remaining {
+            None => {
+                for feature in &license.features {
+                    cache.insert(feature.clone(), ());
+                }
+            }
+            Some(Ok(ttl)) => {
+                for feature in &license.features {
+                    cache.insert_with_ttl(feature.clone(), (), ttl);
+                }
             }
+            // Already expired: nothing is available
+            Some(Err(_)) => {}
         }
+        self.feature_cache = cache;
     }
 
     /// Check if a feature is available (replaces JS license.hasFeature)
+    ///
+    /// Not metered, since internal checks and feature listings call it too; operations gated
+    /// through `require_feature` are.
     pub async fn has_feature(&self, feature: &str) -> bool {
-        self.feature_cache.get(feature).copied().unwrap_or(false)
+        self.feature_cache.contains(&feature.to_string())
+    }
+
+    /// Count one unit of usage against a limit, rolling its window over when expired
+    pub fn record_usage(&self, limit_type: &str) {
+        self.record_usage_at(limit_type, self.clock.now());
+    }
+
+    fn record_usage_at(&self, limit_type: &str, now: DateTime<Utc>) {
+        let mut entry = self
+            .usage
+            .entry(limit_type.to_string())
+            .or_insert_with(|| UsageWindow { count: 0, window_start: now });
+
+        if usage_window_expired(limit_type, &entry, now) {
+            entry.count = 0;
+            entry.window_start = now;
+        }
+        entry.count = entry.count.saturating_add(1);
+    }
+
+    /// Metered usage against a limit in its current window
+    pub fn current_usage(&self, limit_type: &str) -> u32 {
+        self.current_usage_at(limit_type, self.clock.now())
+    }
+
+    fn current_usage_at(&self, limit_type: &str, now: DateTime<Utc>) -> u32 {
+        self.usage
+            .get(limit_type)
+            .filter(|window| !usage_window_expired(limit_type, window, now))
+            .map_or(0, |window| window.count)
+    }
+
+    /// Metered usage and configured limit for each limit type (`None` means unlimited)
+    pub fn usage_report(&self) -> HashMap<String, (u32, Option<u32>)> {
+        let now = self.clock.now();
+        METERED_LIMITS
+            .iter()
+            .map(|limit_type| {
+                (
+                    limit_type.to_string(),
+                    (self.current_usage_at(limit_type, now), self.limit_for(limit_type)),
+                )
+            })
+            .collect()
+    }
+
+    /// Configured limit for a limit type under the current license
+    fn limit_for(&self, limit_type: &str) -> Option<u32> {
+        let limits = &self.current_license.as_ref()?.limits;
+        match limit_type {
+            "users" => limits.max_users,
+            "storage_gb" => limits.max_storage_gb,
+            "operations_per_hour" => limits.max_operations_per_hour,
+            "api_calls_per_day" => limits.max_api_calls_per_day,
+            "concurrent_sessions" => limits.max_concurrent_sessions,
+            "tenants" => limits.max_tenants,
+            _ => None,
+        }
     }
 
     /// Get current license tier
@@ -370,7 +606,7 @@ impl LicenseManager {
             status: LicenseStatus::Valid,
             organization: "Community User".to_string(),
             issued_to: "community@nodus.com".to_string(),
-            issued_at: Utc::now(),
+            issued_at: self.clock.now(),
             expires_at: None,
             features: LicenseFeatures::community_features(),
             limits: LicenseLimits {
@@ -427,6 +663,56 @@ impl LicenseManager {
         }
     }
 
+    /// Gate an operation on a feature, recording the access attempt for audit
+    ///
+    /// Granted operations are metered against `operations_per_hour`. Prefer the
+    /// `require_feature!` macro in command handlers.
+    pub async fn require_feature(&self, feature: &str) -> Result<(), LicenseError> {
+        let tier = format!("{:?}", self.get_tier().await);
+
+        if self.has_feature(feature).await {
+            self.record_usage("operations_per_hour");
+            self.record_security_event(SecurityEvent::LicenseValidated {
+                feature: feature.to_string(),
+                tier,
+            });
+            return Ok(());
+        }
+
+        let error = LicenseError::FeatureNotAvailable(feature.to_string());
+        crate::security_log!(warn, feature = %feature, tier = %tier, "License gate denied feature");
+        self.record_security_event(SecurityEvent::LicenseValidationFailed {
+            feature: feature.to_string(),
+            tier,
+            error: error.to_string(),
+        });
+        Err(error)
+    }
+
+    /// Recent feature-gate events, oldest first, for forwarding to the audit trail
+    pub fn recent_security_events(&self) -> Vec<SecurityEvent> {
+        self.security_events
+            .lock()
+            .map(|events| events.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// Feature-gate events as they happen, for the forensic store
+    pub fn subscribe_security_events(&self) -> broadcast::Receiver<SecurityEvent> {
+        self.security_event_tx.subscribe()
+    }
+
+    fn record_security_event(&self, event: SecurityEvent) {
+        // No subscribers is fine: the recent events below still hold it
+        let _ = self.security_event_tx.send(event.clone());
+        if let Ok(mut events) = self.security_events.lock() {
+            if events.len() >= MAX_SECURITY_EVENTS {
+                events.pop_front();
+            }
+            events.push_back(event);
+        }
+    }
+
     /// Get plugin list for current tier
     pub async fn get_available_plugins(&self) -> Vec<String> {
         if let Some(ref license) = self.current_license {
@@ -442,6 +728,206 @@ impl LicenseManager {
     }
 }
 
+/// Return early from the enclosing function unless the license grants `feature`
+///
+/// The error is converted with `From`, or with the given mapper for handlers whose error
+/// type has no `From<LicenseError>` (e.g. `|e| e.to_string()` for Tauri commands).
+#[macro_export]
+macro_rules! require_feature {
+    ($license_manager:expr, $feature:expr) => {
+        if let Err(error) = $license_manager.require_feature($feature).await {
+            return Err(::std::convert::From::from(error));
+        }
+    };
+    ($license_manager:expr, $feature:expr, $map_err:expr) => {
+        if let Err(error) = $license_manager.require_feature($feature).await {
+            return Err(($map_err)(error));
+        }
+    };
+}
+
+/// Rollover period for rate limits; other limits accumulate without resetting
+fn usage_window(limit_type: &str) -> Option<Duration> {
+    match limit_type {
+        "operations_per_hour" => Some(Duration::hours(1)),
+        "api_calls_per_day" => Some(Duration::days(1)),
+        _ => None,
+    }
+}
+
+fn usage_window_expired(limit_type: &str, window: &UsageWindow, now: DateTime<Utc>) -> bool {
+    usage_window(limit_type).map_or(false, |period| now >= window.window_start + period)
+}
+
+/// Embedded public half of the Defense activation signing key
+const DEFENSE_ACTIVATION_PUBLIC_KEY: &str = "7r8tFLpzPpTVl/9jsGJ8y6nAih8hO+1zGTd0+acbYu0=";
+
+impl MachineFingerprint {
+    /// Derive this machine's identity from its identifiers and the device secret at
+    /// `secret_path`, generating and storing the secret on first use
+    pub async fn detect(
+        key_provider: &dyn KeyProvider,
+        secret_path: impl AsRef<std::path::Path>,
+    ) -> Result<Self, LicenseError> {
+        let identifiers = machine_identifiers();
+        if identifiers.is_empty() {
+            return Err(LicenseError::Activation("No stable machine identifier available".to_string()));
+        }
+        let secret = load_or_create_device_secret(key_provider, secret_path.as_ref()).await?;
+        Self::derive(&secret, &identifiers)
+    }
+
+    /// Derive the identity from a device secret and explicit identifiers
+    pub fn derive<S: AsRef<str>>(secret: &[u8], identifiers: &[S]) -> Result<Self, LicenseError> {
+        let mut context = digest::Context::new(&digest::SHA256);
+        context.update(b"nodus-machine-fingerprint:v2");
+        for identifier in identifiers {
+            context.update(&[0]);
+            context.update(identifier.as_ref().trim().as_bytes());
+        }
+        let salt = context.finish();
+
+        let mut seed = zeroize::Zeroizing::new([0u8; 32]);
+        hkdf::Salt::new(hkdf::HKDF_SHA256, salt.as_ref())
+            .extract(secret)
+            .expand(&[b"nodus-device-key:v1"], hkdf::HKDF_SHA256)
+            .and_then(|okm| okm.fill(seed.as_mut()))
+            .map_err(|_| LicenseError::Activation("Failed to derive device key".to_string()))?;
+
+        let device_key = Ed25519KeyPair::from_seed_unchecked(seed.as_ref())
+            .map_err(|_| LicenseError::Activation("Failed to derive device key".to_string()))?;
+        let fingerprint =
+            hex::encode(digest::digest(&digest::SHA256, device_key.public_key().as_ref()).as_ref());
+
+        Ok(Self { device_key, fingerprint })
+    }
+
+    pub fn fingerprint(&self) -> &str {
+        &self.fingerprint
+    }
+
+    /// Base64 Ed25519 device public key
+    pub fn public_key(&self) -> String {
+        general_purpose::STANDARD.encode(self.device_key.public_key().as_ref())
+    }
+
+    /// Base64 Ed25519 signature by the device key
+    pub fn sign(&self, message: &[u8]) -> String {
+        general_purpose::STANDARD.encode(self.device_key.sign(message).as_ref())
+    }
+}
+
+/// Unwrap the device secret at `path`, or generate and store a new one wrapped under the
+/// master key
+async fn load_or_create_device_secret(
+    key_provider: &dyn KeyProvider,
+    path: &std::path::Path,
+) -> Result<zeroize::Zeroizing<Vec<u8>>, LicenseError> {
+    match tokio::fs::read(path).await {
+        Ok(wrapped) => Ok(key_provider.unwrap(MASTER_KEY_ID, &wrapped).await?),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            let mut secret = zeroize::Zeroizing::new(vec![0u8; 32]);
+            rand::SecureRandom::fill(&rand::SystemRandom::new(), secret.as_mut_slice())
+                .map_err(|_| LicenseError::Activation("Failed to generate device secret".to_string()))?;
+            let wrapped = key_provider.wrap(MASTER_KEY_ID, &secret).await?;
+            tokio::fs::write(path, wrapped).await?;
+            tracing::info!("Generated device secret in {}", path.display());
+            Ok(secret)
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Stable identifiers for this machine, in a fixed order
+fn machine_identifiers() -> Vec<String> {
+    let mut identifiers = Vec::new();
+
+    #[cfg(target_os = "linux")]
+    for path in ["/etc/machine-id", "/var/lib/dbus/machine-id", "/sys/class/dmi/id/product_uuid"] {
+        if let Ok(value) = std::fs::read_to_string(path) {
+            if !value.trim().is_empty() {
+                identifiers.push(value.trim().to_string());
+            }
+        }
+    }
+
+    #[cfg(target_os = "macos")]
+    if let Ok(output) = std::process::Command::new("ioreg")
+        .args(["-rd1", "-c", "IOPlatformExpertDevice"])
+        .output()
+    {
+        let text = String::from_utf8_lossy(&output.stdout);
+        if let Some(line) = text.lines().find(|line| line.contains("IOPlatformUUID")) {
+            if let Some(uuid) = line.split('"').nth(3) {
+                identifiers.push(uuid.to_string());
+            }
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    if let Ok(output) = std::process::Command::new("reg")
+        .args(["query", r"HKLM\SOFTWARE\Microsoft\Cryptography", "/v", "MachineGuid"])
+        .output()
+    {
+        let text = String::from_utf8_lossy(&output.stdout);
+        if let Some(guid) = text
+            .lines()
+            .find(|line| line.contains("MachineGuid"))
+            .and_then(|line| line.split_whitespace().last())
+        {
+            identifiers.push(guid.to_string());
+        }
+    }
+
+    identifiers
+}
+
+fn activation_request_message(fingerprint: &str, device_public_key: &str, requested_at: &DateTime<Utc>) -> Vec<u8> {
+    format!(
+        "nodus-activation-request:v1:{}:{}:{}",
+        fingerprint,
+        device_public_key,
+        requested_at.timestamp()
+    )
+    .into_bytes()
+}
+
+/// Message a license's HMAC signature covers
+///
+/// Simplified - in production use more fields. Uses the textual representation of the
+/// tier to avoid relying on numeric casts (keeps enum changes low-risk).
+fn license_signature_message(license: &LicenseInfo) -> String {
+    format!(
+        "{}:{:?}:{}:{}",
+        license.license_id,
+        license.tier,
+        license.organization,
+        license.issued_at.timestamp()
+    )
+}
+
+/// Canonical signed form of a response; features are sorted so the message is deterministic
+fn activation_response_message(response: &ActivationResponse) -> Vec<u8> {
+    let license = &response.license;
+    let mut features: Vec<&str> = license.features.iter().map(String::as_str).collect();
+    features.sort_unstable();
+
+    format!(
+        "nodus-activation-response:v1:{}:{}:{}:{:?}:{}:{}:{}:{}:{}:{}",
+        response.key_id,
+        response.fingerprint,
+        license.license_id,
+        license.tier,
+        license.organization,
+        license.issued_to,
+        license.issued_at.timestamp(),
+        license.expires_at.map_or_else(|| "never".to_string(), |t| t.timestamp().to_string()),
+        features.join(","),
+        serde_json::to_string(&license.limits).unwrap_or_default()
+    )
+    .into_bytes()
+}
+
 /// License validation errors
 #[derive(Debug, thiserror::Error)]
 pub enum LicenseError {
@@ -460,11 +946,20 @@ pub enum LicenseError {
     #[error("License limit exceeded: {0}")]
     LimitExceeded(String),
 
+    #[error("Activation response is bound to a different machine")]
+    FingerprintMismatch,
+
+    #[error("Offline activation failed: {0}")]
+    Activation(String),
+
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
+
+    #[error("Key provider error: {0}")]
+    KeyProvider(#[from] KeyProviderError),
 }
 
 impl Default for LicenseLimits {
@@ -483,6 +978,8 @@ impl Default for LicenseLimits {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::security::key_provider::tests::RecordingKms;
+    use crate::security::key_provider::{InMemoryKeyProvider, KmsKeyProvider};
 
     #[test]
     fn test_community_features() {
@@ -533,6 +1030,270 @@ mod tests {
         assert!(true); // Placeholder
     }
 
+    fn signed_activation(
+        vendor: &Ed25519KeyPair,
+        fingerprint: &str,
+        tier: LicenseTier,
+    ) -> String {
+        let mut response = ActivationResponse {
+            license: LicenseInfo {
+                license_id: Uuid::new_v4(),
+                features: LicenseFeatures::features_for_tier(&tier),
+                tier,
+                status: LicenseStatus::Valid,
+                organization: "Test Agency".to_string(),
+                issued_to: "ops@agency.test".to_string(),
+                issued_at: Utc::now(),
+                expires_at: Some(Utc::now() + Duration::days(365)),
+                limits: LicenseLimits::default(),
+                signature: String::new(),
+                verification_key: "defense_activation_key_v1".to_string(),
+            },
+            fingerprint: fingerprint.to_string(),
+            key_id: "test_activation_key".to_string(),
+            signature: String::new(),
+        };
+        response.signature = general_purpose::STANDARD
+            .encode(vendor.sign(&activation_response_message(&response)).as_ref());
+        serde_json::to_string(&response).unwrap()
+    }
+
+    fn mac_signed_license(key_id: &str, key: &[u8], tier: LicenseTier) -> LicenseInfo {
+        let mut license = LicenseInfo {
+            license_id: Uuid::new_v4(),
+            features: LicenseFeatures::features_for_tier(&tier),
+            tier,
+            status: LicenseStatus::Valid,
+            organization: "Test Corp".to_string(),
+            issued_to: "admin@corp.test".to_string(),
+            issued_at: Utc::now(),
+            expires_at: Some(Utc::now() + Duration::days(365)),
+            limits: LicenseLimits::default(),
+            signature: String::new(),
+            verification_key: key_id.to_string(),
+        };
+        let tag = ring::hmac::sign(
+            &ring::hmac::Key::new(ring::hmac::HMAC_SHA256, key),
+            license_signature_message(&license).as_bytes(),
+        );
+        license.signature = general_purpose::STANDARD.encode(tag.as_ref());
+        license
+    }
+
+    #[tokio::test]
+    async fn test_license_mac_verified_by_key_provider() {
+        let vendor = Ed25519KeyPair::from_seed_unchecked(&[7u8; 32]).unwrap();
+        let mut manager = offline_manager(&vendor);
+        manager.key_provider = Arc::new(InMemoryKeyProvider::new().with_key("enterprise_key_v1", b"vendor mac key"));
+
+        let license = mac_signed_license("enterprise_key_v1", b"vendor mac key", LicenseTier::Enterprise);
+        manager.validate_and_set_license(license.clone()).await.unwrap();
+        assert_eq!(manager.get_tier().await, LicenseTier::Enterprise);
+
+        let mut tampered = license.clone();
+        tampered.organization = "Someone Else".to_string();
+        assert!(matches!(manager.validate_and_set_license(tampered).await, Err(LicenseError::InvalidSignature)));
+
+        // Keys the provider doesn't hold verify nothing
+        let unknown = mac_signed_license("defense_key_v1", b"vendor mac key", LicenseTier::Defense);
+        assert!(matches!(manager.validate_and_set_license(unknown).await, Err(LicenseError::InvalidSignature)));
+    }
+
+    #[tokio::test]
+    async fn test_license_mac_verified_in_kms() {
+        let vendor = Ed25519KeyPair::from_seed_unchecked(&[7u8; 32]).unwrap();
+        let mut manager = offline_manager(&vendor);
+        let kms = Arc::new(RecordingKms::with_key("hsm/license-v1", b"hsm mac key"));
+        manager.key_provider = Arc::new(KmsKeyProvider::new(kms.clone()).with_key_label("defense_key_v1", "hsm/license-v1"));
+
+        let license = mac_signed_license("defense_key_v1", b"hsm mac key", LicenseTier::Defense);
+        manager.validate_and_set_license(license).await.unwrap();
+        assert_eq!(manager.get_tier().await, LicenseTier::Defense);
+        assert_eq!(*kms.calls.lock().unwrap(), vec!["verify_mac:hsm/license-v1"]);
+    }
+
+    fn offline_manager(vendor: &Ed25519KeyPair) -> LicenseManager {
+        let mut manager = LicenseManager {
+            current_license: None,
+            key_provider: Arc::new(InMemoryKeyProvider::new()),
+            activation_keys: HashMap::new(),
+            feature_cache: TtlCache::new(1),
+            machine: Some(MachineFingerprint::derive(&[1u8; 32], &["machine-a", "board-a"]).unwrap()),
+            usage: DashMap::new(),
+            security_events: Mutex::new(VecDeque::new()),
+            security_event_tx: broadcast::channel(MAX_SECURITY_EVENTS).0,
+            clock: crate::clock::system_clock(),
+        };
+        manager.set_community_license();
+        manager.activation_keys.insert(
+            "test_activation_key".to_string(),
+            vendor.public_key().as_ref().to_vec(),
+        );
+        manager
+    }
+
+    #[tokio::test]
+    async fn test_offline_activation_installs_license() {
+        let vendor = Ed25519KeyPair::from_seed_unchecked(&[7u8; 32]).unwrap();
+        let mut manager = offline_manager(&vendor);
+
+        let challenge = manager.generate_activation_request().unwrap();
+        let device_key = general_purpose::STANDARD.decode(&challenge.device_public_key).unwrap();
+        signature::UnparsedPublicKey::new(&signature::ED25519, device_key)
+            .verify(
+                &activation_request_message(&challenge.fingerprint, &challenge.device_public_key, &challenge.requested_at),
+                &general_purpose::STANDARD.decode(&challenge.signature).unwrap(),
+            )
+            .unwrap();
+        // Same secret and identifiers give the same fingerprint (stable across restarts)
+        assert_eq!(
+            challenge.fingerprint,
+            MachineFingerprint::derive(&[1u8; 32], &["machine-a", "board-a"]).unwrap().fingerprint()
+        );
+
+        let response = signed_activation(&vendor, &challenge.fingerprint, LicenseTier::Defense);
+        let license = manager.apply_activation_response(&response).await.unwrap();
+        assert_eq!(license.tier, LicenseTier::Defense);
+        assert_eq!(manager.get_tier().await, LicenseTier::Defense);
+        assert!(manager.has_feature("classified_operations").await);
+
+        // Altered terms break the signature
+        let mut tampered: ActivationResponse = serde_json::from_str(&response).unwrap();
+        tampered.license.organization = "Someone Else".to_string();
+        assert!(matches!(
+            manager.apply_activation_response(&serde_json::to_string(&tampered).unwrap()).await,
+            Err(LicenseError::InvalidSignature)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_offline_activation_rejects_other_fingerprint() {
+        let vendor = Ed25519KeyPair::from_seed_unchecked(&[7u8; 32]).unwrap();
+        let mut manager = offline_manager(&vendor);
+
+        let other = MachineFingerprint::derive(&[1u8; 32], &["machine-b", "board-b"]).unwrap();
+        let response = signed_activation(&vendor, other.fingerprint(), LicenseTier::Defense);
+
+        assert!(matches!(
+            manager.apply_activation_response(&response).await,
+            Err(LicenseError::FingerprintMismatch)
+        ));
+        assert_eq!(manager.get_tier().await, LicenseTier::Community);
+    }
+
+    #[tokio::test]
+    async fn test_device_identity_needs_the_wrapped_secret() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(DEVICE_SECRET_FILE);
+        let provider = InMemoryKeyProvider::new().with_generated_key(MASTER_KEY_ID).unwrap();
+
+        let secret = load_or_create_device_secret(&provider, &path).await.unwrap();
+        assert_ne!(std::fs::read(&path).unwrap(), secret.to_vec(), "the secret is stored wrapped");
+        assert_eq!(load_or_create_device_secret(&provider, &path).await.unwrap(), secret);
+
+        // The identifiers alone don't determine the key
+        let first = MachineFingerprint::derive(&secret, &["machine-a", "board-a"]).unwrap();
+        let other = MachineFingerprint::derive(&[2u8; 32], &["machine-a", "board-a"]).unwrap();
+        assert_ne!(first.fingerprint(), other.fingerprint());
+
+        // A copied secret file is useless without this machine's master key
+        let foreign = InMemoryKeyProvider::new().with_generated_key(MASTER_KEY_ID).unwrap();
+        assert!(matches!(
+            load_or_create_device_secret(&foreign, &path).await,
+            Err(LicenseError::KeyProvider(KeyProviderError::CryptoFailed))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_usage_window_rollover_resets_counter() {
+        let vendor = Ed25519KeyPair::from_seed_unchecked(&[7u8; 32]).unwrap();
+        let manager = offline_manager(&vendor);
+        let start = Utc::now();
+
+        for _ in 0..3 {
+            manager.record_usage_at("operations_per_hour", start);
+        }
+        manager.record_usage_at("users", start);
+        assert_eq!(manager.current_usage_at("operations_per_hour", start + Duration::minutes(59)), 3);
+
+        // Past the hour the counter reads as reset, and the next record starts a new window
+        let later = start + Duration::minutes(61);
+        assert_eq!(manager.current_usage_at("operations_per_hour", later), 0);
+        manager.record_usage_at("operations_per_hour", later);
+        assert_eq!(manager.current_usage_at("operations_per_hour", later), 1);
+
+        // Non-rate limits don't roll over
+        assert_eq!(manager.current_usage_at("users", start + Duration::days(30)), 1);
+
+        // Only operations gated on a granted feature are metered, not plain checks
+        let manager = offline_manager(&vendor);
+        manager.record_usage("users");
+        assert!(manager.has_feature("basic_observability").await);
+        assert_eq!(manager.current_usage("operations_per_hour"), 0);
+        manager.require_feature("basic_observability").await.unwrap();
+        assert!(manager.require_feature("classified_operations").await.is_err());
+        let report = manager.usage_report();
+        assert_eq!(report["operations_per_hour"], (1, Some(10000)));
+        assert_eq!(report["users"], (1, Some(5)));
+    }
+
+    #[tokio::test]
+    async fn test_features_lapse_when_license_expires() {
+        let vendor = Ed25519KeyPair::from_seed_unchecked(&[7u8; 32]).unwrap();
+        let clock = Arc::new(crate::clock::MockClock::default());
+        let mut manager = offline_manager(&vendor).with_clock(clock.clone());
+        assert!(manager.has_feature("basic_observability").await);
+
+        let mut license = mac_signed_license("enterprise_key_v1", b"vendor mac key", LicenseTier::Enterprise);
+        license.issued_at = clock.now();
+        license.expires_at = Some(clock.now() + Duration::hours(1));
+        manager.install_license(license).unwrap();
+        assert!(manager.has_feature("multi_tenant").await);
+
+        clock.advance(std::time::Duration::from_secs(3600));
+        assert!(!manager.has_feature("multi_tenant").await);
+    }
+
+    async fn gated_operation(manager: &LicenseManager, feature: &str) -> Result<&'static str, String> {
+        crate::require_feature!(manager, feature, |e: LicenseError| e.to_string());
+        Ok("ran")
+    }
+
+    #[tokio::test]
+    async fn test_require_feature_short_circuits_and_audits() {
+        let vendor = Ed25519KeyPair::from_seed_unchecked(&[7u8; 32]).unwrap();
+        let manager = offline_manager(&vendor);
+
+        let mut forwarded = manager.subscribe_security_events();
+
+        let denied = gated_operation(&manager, "multi_tenant").await;
+        assert_eq!(
+            denied,
+            Err("Feature not available in current license: multi_tenant".to_string())
+        );
+        assert!(matches!(
+            manager.recent_security_events().last(),
+            Some(SecurityEvent::LicenseValidationFailed { feature, .. }) if feature == "multi_tenant"
+        ));
+        assert!(matches!(
+            forwarded.try_recv(),
+            Ok(SecurityEvent::LicenseValidationFailed { feature, .. }) if feature == "multi_tenant"
+        ));
+
+        assert_eq!(gated_operation(&manager, "basic_observability").await, Ok("ran"));
+        assert!(matches!(
+            manager.recent_security_events().last(),
+            Some(SecurityEvent::LicenseValidated { feature, .. }) if feature == "basic_observability"
+        ));
+
+        // Default arm converts through From
+        async fn typed(manager: &LicenseManager) -> Result<(), LicenseError> {
+            crate::require_feature!(manager, "multi_tenant");
+            Ok(())
+        }
+        assert!(matches!(typed(&manager).await, Err(LicenseError::FeatureNotAvailable(_))));
+    }
+
     #[test]
     fn test_license_tiers() {
         assert_eq!(LicenseTier::Community as u8, 0);
@@ -14,27 +14,44 @@ use tracing::{info, error, warn};
 use std::collections::HashMap;
 
 // Use core crate modules
-use crate::state::AppState;
-use crate::security::{SecurityManager, MACEngine};
+use crate::state::{AppState, DEFAULT_SESSION_SWEEP_INTERVAL};
+use crate::database::{retention::DEFAULT_RETENTION_SWEEP_INTERVAL, DatabaseManager};
 use crate::database::DatabaseManager;
 use crate::license::LicenseManager;
 use crate::observability::{
-    ForensicLogger, MetricsRegistry, AutomaticInstrumentation,
+    ComplianceEngine, ExportEngine, ForensicLogger, MetricsRegistry, AutomaticInstrumentation, PerformanceStateMonitor, PiiDetector, RecorderHooks, TailSampler,
+    BudgetCircuits, BudgetEscalator, QosScheduler, Remediation,
+    exporters::{SecurityEventSink, TransportPoster},
+    performance_state::DEFAULT_EVALUATION_INTERVAL,
+    audit_export::DEFAULT_EXPORT_TICK,
+    alert_rules::{AlertRuleEngine, DEFAULT_ALERT_RULE_TICK},
+    log_redaction::{self, ForensicLogLayer, RedactingLayer, SecurityLogEvent, SensitiveFieldRegistry},
 };
+use crate::policy::policy_engine::{startup_policy, PolicyChange, SimulationReport, UnifiedPolicyEngine, SYSTEM_POLICY_ENV};
+use crate::security::{has_permission, Permission};
+use crate::security::key_provider::system_key_provider;
 use crate::action_dispatcher::ActionDispatcher;
 use crate::async_orchestrator::AsyncOrchestrator;
 use crate::networking::{SecureNetworkTransport as SecureTransport, ResponseCache};
+#[cfg(feature = "observability_kafka")]
+use crate::observability::exporters::{kafka::RdKafkaProducer, KafkaExporter};
+use crate::shutdown::{self, ShutdownCoordinator};
 use crate::enterprise::{
     EnterpriseManager, EnterpriseConfig,
     ComplianceDashboard,
 };
+use crate::multi_tenant::DEFAULT_USAGE_SAMPLE_TICK;
 
 // Import command handlers from the commands module
 use crate::commands::{
-    security::{authenticate_user, encrypt_data, assess_threat},
-    data::{read_entity, write_entity, query_entities, batch_operations},
-    observability::{get_metrics_snapshot, export_audit_trail, get_performance_stats},
+    security::{authenticate_user, encrypt_data, assess_threat, terminate_session, mac_decision},
+    data::{read_entity, write_entity, query_entities, batch_operation, subscribe_entity_changes, unsubscribe_entity_changes,
+        propose_declassification, approve_declassification, list_declassifications},
+    observability::{get_metrics_snapshot, export_audit_trail, get_instrumentation_stats, get_database_health,
+        get_liveness, get_platform_health, start_metrics_stream, ack_metrics_stream, stop_metrics_stream,
+        verify_forensic_integrity, get_compliance_summary},
     license::{check_feature_availability, validate_license, get_license_info},
+    schema::get_api_schema,
 };
 
 /// Tauri application builder with complete Nodus integration
@@ -42,25 +59,40 @@ use crate::commands::{
 pub struct NodusApplication {
     app_state: Arc<AppState>,
     enterprise_manager: Arc<EnterpriseManager>,
+    platform_health: Arc<crate::observability::HealthCheck>,
+    policy_engine: Arc<UnifiedPolicyEngine>,
+    shutdown: Arc<ShutdownCoordinator>,
 }
 
 impl NodusApplication {
     /// Initialize the complete Nodus application
     pub async fn initialize() -> Result<Self, Box<dyn std::error::Error>> {
         // Initialize tracing for comprehensive logging
-        Self::setup_tracing().await?;
+        let security_log_events = Self::setup_tracing().await?;
         
         info!("🚀 Initializing Nodus Enterprise Application in Rust");
+
+        // Resources register in start order and are torn down in reverse on exit
+        let shutdown = Arc::new(ShutdownCoordinator::new());
+        
+        // System-managed keys (master key generated on first start), shared by every layer that wraps or verifies with them
+        let key_provider = system_key_provider().await?;
         
         // 1. Initialize License Manager (first - determines available features)
         info!("📜 Initializing License Manager");
-        let license_manager = Arc::new(LicenseManager::new().await?);
+        let license_manager = Arc::new(LicenseManager::with_key_provider(key_provider.clone()).await?);
     let current_license_tier = license_manager.get_tier().await;
     info!("License tier detected: {:?}", current_license_tier);
+        let system_policy = startup_policy().await?;
         
         // 2. Initialize Security Infrastructure
         info!("🛡️ Initializing Security Infrastructure");
-        let mac_engine = Arc::new(MACEngine::new());
+        if let Ok(lattice_path) = std::env::var("NODUS_CLASSIFICATION_LATTICE") {
+            let lattice = ClassificationLattice::load(&lattice_path).await?;
+            info!("Loaded classification lattice from {}", lattice_path);
+            classification_lattice::install(lattice);
+        }
+        let mac_engine = Arc::new(MACEngine::from_policy(&system_policy.global.mac));
         let security_manager = Arc::new(SecurityManager::new(
             mac_engine.clone(),
             license_manager.clone(),
@@ -68,23 +100,32 @@ impl NodusApplication {
         
         // 3. Initialize Database with MAC Enforcement
         info!("💾 Initializing Database with MAC Enforcement");
-        let database_manager = Arc::new(DatabaseManager::new(
-            security_manager.clone(),
-            license_manager.clone(),
-        ).await?);
+        // Pool sizing and TLS come from `[database.connection]`; `DATABASE_URL` still overrides the URL
+        let database_manager = Arc::new(DatabaseManager::with_key_provider(
+            system_policy.database.connection.clone().with_env_url(),
+            key_provider.clone(),
+        ).await?.with_mac_engine(mac_engine.clone()));
+        shutdown.register(database_manager.clone()).await;
+        database_manager.listen_for_changes();
         
         // 4. Initialize Automatic Observability System
         info!("👁️ Initializing Automatic Observability System");
         let forensic_logger = Arc::new(ForensicLogger::new(
             database_manager.clone(),
             security_manager.clone(),
-        ).await?);
+        ).await?.with_audit_level(system_policy.global.audit_level));
+        shutdown.register(forensic_logger.clone()).await;
+        log_redaction::forward_to_forensic_store(forensic_logger.clone(), security_log_events);
+        forensic_logger.clone().forward_license_events(license_manager.subscribe_security_events());
 
         let metrics_registry = Arc::new(MetricsRegistry::new());
 
-        let automatic_instrumentation = Arc::new(AutomaticInstrumentation::new(
-            license_manager.clone(),
-        ));
+        // New operations pick up the performance state derived from live load
+        let performance_monitor = Arc::new(
+            PerformanceStateMonitor::new(metrics_registry.clone(), system_policy.observability.performance_state.clone())
+                .with_database(database_manager.clone()),
+        );
+        performance_monitor.spawn(DEFAULT_EVALUATION_INTERVAL);
         
         // 5. Initialize Execution Gateways (replaces manual ActionDispatcher/AsyncOrchestrator)
         info!("⚡ Initializing Automatic Execution Gateways");
@@ -96,13 +137,69 @@ impl NodusApplication {
             license_manager.clone(),
         ));
         
+        // One set of per-class slots for instrumented commands and network requests alike
+        let qos_scheduler = Arc::new(QosScheduler::from_policy(&system_policy.observability.qos));
+
         // 6. Initialize Secure Networking
         info!("🌐 Initializing Secure Networking");
         let secure_transport = Arc::new(SecureTransport::new(
             license_manager.clone(),
-        ).await?);
+        ).await?.with_qos_scheduler(qos_scheduler.clone()));
 
         let response_cache = Arc::new(ResponseCache::new(1000));
+
+        // Instrumented operations hand their observation records to the exporters, PII redacted first
+        // and assessed for compliance, the evidence kept in the database for the dashboard
+        let pii_detector = Arc::new(PiiDetector::new()?);
+        let compliance_engine = Arc::new(ComplianceEngine::default().with_store(database_manager.clone()));
+        let export_policy = &system_policy.observability.exports;
+        let mut export_engine = ExportEngine::from_policy(export_policy).await?
+            .with_pii_detector(pii_detector.clone())
+            .with_compliance_engine(compliance_engine.clone());
+        // Alerts go through the transport's policy and TLS checks, audited like any request
+        let alert_hooks = Arc::new(RecorderHooks::new(forensic_logger.clone(), metrics_registry.clone()));
+        let alert_poster = Arc::new(TransportPoster::new(secure_transport.clone(), alert_hooks));
+        let security_sink = export_policy.security_alerts.as_ref()
+            .map(|alerts| Arc::new(SecurityEventSink::from_policy(alerts, alert_poster.clone())));
+        // Tenants' alert rules are evaluated against the metrics their operations record
+        let alert_rules = Arc::new(AlertRuleEngine::new(metrics_registry.clone(), alert_poster));
+        alert_rules.clone().spawn(DEFAULT_ALERT_RULE_TICK);
+        if let Some(sink) = &security_sink {
+            export_engine.add_exporter(sink.clone());
+        }
+        if let Some(feed) = &export_policy.kafka {
+            #[cfg(feature = "observability_kafka")]
+            {
+                let producer = Arc::new(RdKafkaProducer::new(&feed.settings)?);
+                export_engine.add_exporter(Arc::new(KafkaExporter::from_policy(feed, producer)));
+                info!("Streaming observation records to Kafka topic {}", feed.settings.topic);
+            }
+            #[cfg(not(feature = "observability_kafka"))]
+            warn!("Kafka feed to {} configured, but this build lacks the observability_kafka feature", feed.settings.topic);
+        }
+        let export_engine = Arc::new(export_engine);
+        shutdown.register(export_engine.clone()).await;
+
+        // Operations are held to the policy's budgets; routine successes are tail-sampled
+        let observability_policy = &system_policy.observability;
+        let budget_escalation = observability_policy.budget_escalation.clone();
+        let budget_circuits = Arc::new(BudgetCircuits::new(std::time::Duration::from_secs(budget_escalation.circuit_open_seconds)));
+        let budget_escalator = Arc::new(
+            BudgetEscalator::new(budget_escalation)
+                .with_metrics_registry(metrics_registry.clone())
+                .with_circuits(budget_circuits)
+                .with_handler(Remediation::ShedLoad, performance_monitor.clone()),
+        );
+        let mut automatic_instrumentation = AutomaticInstrumentation::new(license_manager.clone())
+            .with_export_engine(export_engine.clone())
+            .with_operation_budgets(observability_policy.operation_budgets.clone())
+            .with_budget_escalator(budget_escalator.clone())
+            .with_performance_monitor(performance_monitor.clone())
+            .with_qos_scheduler(qos_scheduler.clone());
+        if let Some(sampler) = TailSampler::from_policy(&observability_policy.tail_sampling) {
+            automatic_instrumentation = automatic_instrumentation.with_tail_sampler(Arc::new(sampler));
+        }
+        let automatic_instrumentation = Arc::new(automatic_instrumentation);
         
         // 7. Create Application State
         info!("🏗️ Creating Application State");
@@ -113,11 +210,28 @@ impl NodusApplication {
             forensic_logger.clone(),
             action_dispatcher.clone(),
             license_manager.clone(),
-        ));
-        
+        ).with_export_engine(export_engine.clone())
+            .with_automatic_instrumentation(automatic_instrumentation.clone())
+            .with_qos_scheduler(qos_scheduler));
+        // Sessions past their timeouts are closed even if no command touches them again
+        app_state.clone().spawn_session_sweeper(DEFAULT_SESSION_SWEEP_INTERVAL);
+        if let Ok(roles_path) = std::env::var("NODUS_ROLE_DEFINITIONS") {
+            let role_definitions = RoleDefinitions::load(&roles_path).await?;
+            app_state.set_role_definitions(role_definitions).await?;
+            info!("Loaded role definitions from {}", roles_path);
+        }
+
         // 8. Initialize Enterprise Features (if licensed)
         info!("🏢 Initializing Enterprise Features");
-        let enterprise_config = EnterpriseConfig::default();
+        let enterprise_config = EnterpriseConfig {
+            security_sink,
+            alert_rules: Some(alert_rules),
+            network_transport: Some(secure_transport.clone()),
+            pii_detector: Some(pii_detector.clone()),
+            compliance_engine: Some(compliance_engine.clone()),
+            key_provider: Some(key_provider.clone()),
+            ..EnterpriseConfig::default()
+        };
         let enterprise_manager = Arc::new(EnterpriseManager::new(
             license_manager.clone(),
             security_manager.clone(),
@@ -127,28 +241,82 @@ impl NodusApplication {
             enterprise_config,
         ).await?);
         
-        // 9. Log successful initialization
+        // Policy changes reach the running subsystems built from the startup policy; quota
+        // changes are simulated against the tenants' current usage
+        let mut policy_engine = UnifiedPolicyEngine::new(forensic_logger.clone(), metrics_registry.clone(), security_manager.clone())
+            .await?
+            .with_policy(system_policy.clone());
+        if let Some(multi_tenant_system) = enterprise_manager.get_multi_tenant_system() {
+            policy_engine = policy_engine.with_tenant_quotas(multi_tenant_system);
+        }
+        let policy_engine = Arc::new(policy_engine);
+        policy_engine.register_subsystem(performance_monitor.clone()).await;
+        policy_engine.register_subsystem(budget_escalator.clone()).await;
+        policy_engine.register_subsystem(forensic_logger.clone()).await;
+        policy_engine.register_subsystem(secure_transport.clone()).await;
+        // Edits to the policy file are validated and applied while running
+        if let Ok(policy_path) = std::env::var(SYSTEM_POLICY_ENV) {
+            policy_engine.watch_policy_file(&policy_path, app_state.clone()).await?;
+            info!("Watching {} for policy changes", policy_path);
+        }
+        
+        // Tenant audit exports and usage snapshots run at each tenant's configured cadence; retention is enforced hourly
+        if let Some(multi_tenant_system) = enterprise_manager.get_multi_tenant_system() {
+            multi_tenant_system.audit_exports().clone().spawn(DEFAULT_EXPORT_TICK);
+            multi_tenant_system.clone().spawn_usage_sampler(DEFAULT_USAGE_SAMPLE_TICK);
+            multi_tenant_system.spawn_retention_sweeper(DEFAULT_RETENTION_SWEEP_INTERVAL);
+        }
+        
+        // 9. Register subsystems with the platform health check
+        let platform_health = Arc::new(
+            crate::observability::HealthCheck::new()
+                .with_probe(database_manager.clone())
+                .with_probe(license_manager.clone())
+                .with_probe(secure_transport.clone())
+                .with_probe(policy_engine.clone()),
+        );
+
+        // 10. Log successful initialization
         Self::log_initialization_success(&app_state, &enterprise_manager).await;
         
         Ok(Self {
             app_state,
             enterprise_manager,
+            platform_health,
+            policy_engine,
+            shutdown,
         })
     }
+
+    /// Engine that moves the running subsystems between policies
+    pub fn policy_engine(&self) -> Arc<UnifiedPolicyEngine> {
+        self.policy_engine.clone()
+    }
+
+    /// Coordinator to register further resources (e.g. export engines) for orderly teardown
+    pub fn shutdown_coordinator(&self) -> Arc<ShutdownCoordinator> {
+        self.shutdown.clone()
+    }
     
     /// Setup comprehensive tracing and logging
-    async fn setup_tracing() -> Result<(), Box<dyn std::error::Error>> {
-        use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
-        
+    ///
+    /// Log output has sensitive fields redacted; security events are returned for the forensic store.
+    async fn setup_tracing() -> Result<tokio::sync::mpsc::UnboundedReceiver<SecurityLogEvent>, Box<dyn std::error::Error>> {
+        use tracing_subscriber::{filter::Targets, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer};
+
+        let (forensic_layer, security_log_events) = ForensicLogLayer::channel();
+        // Security events are always logged, whatever RUST_LOG narrows the rest to
+        let log_filter = EnvFilter::try_from_default_env()
+            .unwrap_or_else(|_| EnvFilter::new("nodus=debug,tauri=info"))
+            .add_directive(format!("{}=info", log_redaction::SECURITY_LOG_TARGET).parse()?);
+        // The forensic store sees its target regardless of the log filter
+        let forensic_filter = Targets::new().with_target(log_redaction::SECURITY_LOG_TARGET, tracing::Level::INFO);
         tracing_subscriber::registry()
-            .with(
-                tracing_subscriber::EnvFilter::try_from_default_env()
-                    .unwrap_or_else(|_| "nodus=debug,tauri=info".into()),
-            )
-            .with(tracing_subscriber::fmt::layer())
+            .with(RedactingLayer::new(SensitiveFieldRegistry::default(), std::io::stdout).with_filter(log_filter))
+            .with(forensic_layer.with_filter(forensic_filter))
             .init();
         
-        Ok(())
+        Ok(security_log_events)
     }
     
     /// Log successful initialization with performance metrics
@@ -201,45 +369,101 @@ impl NodusApplication {
         tauri::Builder::default()
             .manage(self.app_state.clone())
             .manage(self.enterprise_manager.clone())
-            .invoke_handler(tauri::generate_handler![
-                // System Commands
-                get_system_status,
-                get_enterprise_summary,
-                
-                // Security Commands (from commands/security.rs)
-                authenticate_user,
-                encrypt_data,
-                assess_threat,
-                
-                // Data Commands (from commands/data.rs)
-                read_entity,
-                write_entity,
-                query_entities,
-                batch_operations,
-                
-                // Observability Commands (from commands/observability.rs)
-                get_metrics_snapshot,
-                export_audit_trail,
-                get_performance_stats,
-                
-                // License Commands (from commands/license.rs)
-                check_feature_availability,
-                validate_license,
-                get_license_info,
-                
-                // Enterprise Commands
-                get_plugin_status,
-                get_compliance_report,
-                get_tenant_summary,
-                get_api_gateway_metrics,
-            ])
+            .manage(self.platform_health.clone())
+            .manage(self.shutdown.clone())
+            .manage(self.policy_engine.clone())
+            .invoke_handler(invoke_handler())
             .setup(|app| {
+                // SIGTERM gets the same orderly teardown as closing the last window
+                let handle = app.handle();
+                tauri::async_runtime::spawn(async move {
+                    shutdown::termination_signal().await;
+                    info!("Termination signal received");
+                    shutdown_and_exit(handle).await;
+                });
+
                 info!("📱 Tauri Application Setup Complete");
                 Ok(())
             })
     }
 }
 
+/// Define the invoke handler over `commands`, with their names in `REGISTERED_COMMANDS`
+///
+/// Both come from the one list, so the schema tests check exactly what is registered.
+macro_rules! registered_commands {
+    ($($command:ident),* $(,)?) => {
+        /// Names of every command the invoke handler dispatches
+        pub(crate) const REGISTERED_COMMANDS: &[&str] = &[$(stringify!($command)),*];
+
+        fn invoke_handler() -> impl Fn(tauri::Invoke<tauri::Wry>) + Send + Sync + 'static {
+            tauri::generate_handler![$($command),*]
+        }
+    };
+}
+
+registered_commands![
+    // System Commands
+    get_system_status,
+    get_enterprise_summary,
+
+    // Security Commands (from commands/security.rs)
+    authenticate_user,
+    encrypt_data,
+    assess_threat,
+    terminate_session,
+    mac_decision,
+
+    // Data Commands (from commands/data.rs)
+    read_entity,
+    write_entity,
+    query_entities,
+    batch_operation,
+    subscribe_entity_changes,
+    unsubscribe_entity_changes,
+    propose_declassification,
+    approve_declassification,
+    list_declassifications,
+
+    // Observability Commands (from commands/observability.rs)
+    get_metrics_snapshot,
+    export_audit_trail,
+    get_instrumentation_stats,
+    get_database_health,
+    get_liveness,
+    get_platform_health,
+    start_metrics_stream,
+    ack_metrics_stream,
+    stop_metrics_stream,
+    verify_forensic_integrity,
+    get_compliance_summary,
+
+    // License Commands (from commands/license.rs)
+    check_feature_availability,
+    validate_license,
+    get_license_info,
+
+    // API Schema (from commands/schema.rs)
+    get_api_schema,
+
+    // Enterprise Commands
+    get_plugin_status,
+    get_compliance_report,
+    get_tenant_summary,
+    get_api_gateway_metrics,
+    simulate_policy_change,
+];
+
+/// Run the shutdown coordinator, then exit the process
+async fn shutdown_and_exit(handle: tauri::AppHandle) {
+    let coordinator = handle.state::<Arc<ShutdownCoordinator>>().inner().clone();
+    let report = coordinator.shutdown().await;
+    if !report.is_clean() {
+        warn!("Unclean shutdown: {:?}", report);
+    }
+    handle.exit(0);
+}
+
 /// System status command - shows overall system health
 #[tauri::command]
 async fn get_system_status(
@@ -350,6 +574,27 @@ async fn get_api_gateway_metrics(
     }
 }
 
+/// Preview a policy change without applying it (tenant admins only)
+#[tauri::command]
+async fn simulate_policy_change(
+    session_id: String,
+    change: PolicyChange,
+    app_state: State<'_, Arc<AppState>>,
+    policy_engine: State<'_, Arc<UnifiedPolicyEngine>>,
+) -> Result<SimulationReport, String> {
+    let session_uuid = uuid::Uuid::parse_str(&session_id)
+        .map_err(|_| "Invalid session ID format")?;
+    let security_context = app_state.session_context(session_uuid).await
+        .map_err(|e| e.to_string())?;
+
+    // The report shows every tenant's quota usage
+    if !has_permission(&security_context, Permission::TenantAdmin) {
+        return Err("Insufficient permissions for policy simulation".to_string());
+    }
+
+    Ok(policy_engine.simulate_policy_change(change).await)
+}
+
 /// Main entry point - starts the Tauri application
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -359,10 +604,18 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     let nodus_app = NodusApplication::initialize().await?;
     
     // Build and run the Tauri application
-    nodus_app
+    let app = nodus_app
         .build_tauri_app()
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application");
+
+    app.run(|app_handle, event| {
+        if let tauri::RunEvent::ExitRequested { api, .. } = event {
+            // Keep the event loop alive until buffered records and the pool are closed out
+            api.prevent_exit();
+            tauri::async_runtime::spawn(shutdown_and_exit(app_handle.clone()));
+        }
+    });
     
     Ok(())
 }
@@ -4,15 +4,21 @@
 
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
 use tokio::sync::RwLock;
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use uuid::Uuid;
 use chrono::{DateTime, Utc, Duration};
 
-use crate::security::{SecurityManager, ClassificationLevel, SecurityLabel};
+use crate::cache::{TtlCache, TtlCacheStats};
+use crate::security::{SecurityManager, ClassificationLevel, Permission, SecurityLabel};
+use crate::security::key_provider::{system_key_provider, KeyProvider, KeyProviderError};
 use crate::license::{LicenseManager, LicenseTier};
-use crate::observability::{ForensicLogger, MetricsRegistry};
-use crate::database::DatabaseManager;
+use crate::observability::{AuditExportScheduler, AlertRuleEngine, ForensicLogger, MetricsRegistry};
+use crate::observability::alert_rules::validate_rules;
+use crate::observability::exporters::SecurityEventSink;
+use crate::database::{DatabaseError, DatabaseManager, RetentionSweepReport, RetentionSweeper};
+use crate::networking::{HealthProber, SecureNetworkTransport};
 use crate::state::AppState;
 
 /// Enterprise multi-tenant isolation system
@@ -41,6 +47,43 @@ pub struct MultiTenantSystem {
     
     /// Tenant resource monitors
     resource_monitors: Arc<RwLock<HashMap<String, TenantResourceMonitor>>>,
+    
+    /// Scheduled audit exports, kept in step with each tenant's audit config
+    audit_exports: Arc<AuditExportScheduler>,
+    
+    /// Tenant statuses the database consults before every operation
+    
+    /// Security alert sink, kept in step with each tenant's alert channels
+    security_sink: Option<Arc<SecurityEventSink>>,
+    
+    /// Alert rule engine, kept in step with each tenant's alert rules
+    alert_rules: Option<Arc<AlertRuleEngine>>,
+    
+    /// Transport load balancer targets are probed through; no probing without one
+    network_transport: Option<Arc<SecureNetworkTransport>>,
+    
+    /// Running health prober of each tenant with load balancer targets
+    health_probers: Arc<RwLock<HashMap<String, TenantHealthProbe>>>,
+    
+    /// Provider of system-managed keys
+    system_keys: Arc<dyn KeyProvider>,
+    
+    /// KMS/HSM provider of each tenant managing its own keys
+    customer_keys: Arc<RwLock<HashMap<String, Arc<dyn KeyProvider>>>>,
+}
+
+/// A tenant's health prober and the task running it; dropping it stops the probing
+#[derive(Debug)]
+struct TenantHealthProbe {
+    config: LoadBalancerConfig,
+    prober: Arc<HealthProber>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl Drop for TenantHealthProbe {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
 }
 
 /// Tenant configuration with isolation parameters
@@ -84,7 +127,7 @@ pub struct TenantConfig {
 }
 
 /// Tenant operational status
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TenantStatus {
     /// Tenant is provisioning
     Provisioning,
@@ -105,6 +148,81 @@ pub enum TenantStatus {
     Maintenance,
 }
 
+/// Permission that lets operators work in a tenant under maintenance
+pub const TENANT_ADMIN_PERMISSION: &str = Permission::TenantAdmin.as_str();
+
+impl TenantStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TenantStatus::Provisioning => "provisioning",
+            TenantStatus::Active => "active",
+            TenantStatus::Suspended => "suspended",
+            TenantStatus::Deprovisioning => "deprovisioning",
+            TenantStatus::Terminated => "terminated",
+            TenantStatus::Maintenance => "maintenance",
+        }
+    }
+    
+    pub fn parse(status: &str) -> Option<Self> {
+        [
+            TenantStatus::Provisioning,
+            TenantStatus::Active,
+            TenantStatus::Suspended,
+            TenantStatus::Deprovisioning,
+            TenantStatus::Terminated,
+            TenantStatus::Maintenance,
+        ]
+        .into_iter()
+        .find(|candidate| candidate.as_str() == status)
+    }
+}
+
+/// Status of every known tenant, consulted before each tenant-scoped operation
+///
+/// Owned by `DatabaseManager`, which stores every change and reloads the statuses at startup;
+/// `MultiTenantSystem` updates it, so a suspension takes effect on the very next operation.
+/// Tenants it has never seen are refused.
+#[derive(Debug, Default)]
+pub struct TenantGate {
+    statuses: RwLock<HashMap<String, TenantStatus>>,
+}
+
+impl TenantGate {
+    pub async fn status(&self, tenant_id: &str) -> Option<TenantStatus> {
+        self.statuses.read().await.get(tenant_id).cloned()
+    }
+    
+    pub async fn set_status(&self, tenant_id: &str, status: TenantStatus) {
+        self.statuses.write().await.insert(tenant_id.to_string(), status);
+    }
+    
+    /// Stop tracking a tenant, e.g. after its provisioning failed
+    pub async fn forget(&self, tenant_id: &str) {
+        self.statuses.write().await.remove(tenant_id);
+    }
+    
+    /// Replace every status with `statuses`, e.g. those stored before a restart
+    pub async fn load(&self, statuses: impl IntoIterator<Item = (String, TenantStatus)>) {
+        *self.statuses.write().await = statuses.into_iter().collect();
+    }
+    
+    /// Reject operations for a tenant that is unknown or isn't `Active`
+    ///
+    /// A tenant in `Maintenance` still admits holders of `TENANT_ADMIN_PERMISSION`.
+    /// Operations outside any tenant always pass.
+    pub async fn check(&self, tenant_id: Option<&str>, permissions: &[String]) -> Result<(), MultiTenantError> {
+        let Some(tenant_id) = tenant_id else {
+            return Ok(());
+        };
+        match self.status(tenant_id).await {
+            Some(TenantStatus::Active) => Ok(()),
+            Some(TenantStatus::Maintenance) if permissions.iter().any(|held| held == TENANT_ADMIN_PERMISSION) => Ok(()),
+            Some(status) => Err(MultiTenantError::TenantInactive { tenant_id: tenant_id.to_string(), status }),
+            None => Err(MultiTenantError::TenantNotFound { tenant_id: tenant_id.to_string() }),
+        }
+    }
+}
+
 /// Tenant isolation configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IsolationConfig {
@@ -317,6 +435,25 @@ pub enum KeyManagementStrategy {
     BYOK,
 }
 
+impl KeyManagementStrategy {
+    /// Key provider this strategy uses: the system's, or the tenant's own KMS/HSM
+    ///
+    /// Hybrid falls back to system keys when the tenant hasn't configured a provider.
+    pub fn select_provider(
+        &self,
+        system: Arc<dyn KeyProvider>,
+        customer: Option<Arc<dyn KeyProvider>>,
+    ) -> Result<Arc<dyn KeyProvider>, KeyProviderError> {
+        match self {
+            Self::SystemManaged => Ok(system),
+            Self::Hybrid => Ok(customer.unwrap_or(system)),
+            Self::CustomerManaged | Self::BYOK => customer.ok_or_else(|| {
+                KeyProviderError::NotConfigured(format!("{:?} key management needs a customer key provider", self))
+            }),
+        }
+    }
+}
+
 /// Authentication requirements
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuthRequirements {
@@ -360,6 +497,16 @@ pub struct SessionConfig {
     pub idle_timeout_minutes: u32,
 }
 
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self {
+            session_timeout_minutes: 480, // 8 hour working session
+            concurrent_session_limit: 5,
+            idle_timeout_minutes: 30,
+        }
+    }
+}
+
 /// Access control configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AccessControlConfig {
@@ -428,6 +575,18 @@ pub enum ExportFrequency {
     Monthly,
 }
 
+impl ExportFrequency {
+    /// Time between scheduled exports (a month is 30 days)
+    pub fn interval(&self) -> Duration {
+        match self {
+            ExportFrequency::Hourly => Duration::hours(1),
+            ExportFrequency::Daily => Duration::days(1),
+            ExportFrequency::Weekly => Duration::weeks(1),
+            ExportFrequency::Monthly => Duration::days(30),
+        }
+    }
+}
+
 /// Export format options
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ExportFormat {
@@ -567,15 +726,18 @@ pub enum FirewallAction {
 }
 
 /// Load balancer configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct LoadBalancerConfig {
     pub load_balancer_type: LoadBalancerType,
     pub health_check_config: HealthCheckConfig,
     pub ssl_config: Option<SSLConfig>,
+    /// Base URLs traffic is balanced across, each probed per `health_check_config`
+    #[serde(default)]
+    pub targets: Vec<String>,
 }
 
 /// Load balancer types
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum LoadBalancerType {
     ApplicationLoadBalancer,
     NetworkLoadBalancer,
@@ -583,7 +745,7 @@ pub enum LoadBalancerType {
 }
 
 /// Health check configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct HealthCheckConfig {
     pub protocol: String,
     pub port: u16,
@@ -595,7 +757,7 @@ pub struct HealthCheckConfig {
 }
 
 /// SSL configuration
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SSLConfig {
     pub certificate_arn: String,
     pub ssl_policy: String,
@@ -698,7 +860,7 @@ pub struct RetentionPolicy {
 }
 
 /// Actions to take when data expires
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ExpiryAction {
     Delete,
     Archive,
@@ -706,6 +868,17 @@ pub enum ExpiryAction {
     Encrypt,
 }
 
+impl ExpiryAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExpiryAction::Delete => "delete",
+            ExpiryAction::Archive => "archive",
+            ExpiryAction::Anonymize => "anonymize",
+            ExpiryAction::Encrypt => "encrypt",
+        }
+    }
+}
+
 /// Tenant administrator
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TenantAdministrator {
@@ -794,14 +967,42 @@ pub struct MonitoringConfig {
     pub metrics_collection: bool,
     pub real_time_monitoring: bool,
     pub alert_thresholds: HashMap<String, f64>,
+    /// Interval between usage snapshots
     pub reporting_frequency: Duration,
+    /// Snapshots older than this are dropped
+    pub history_retention: Duration,
+    /// Consecutive snapshots over a threshold before an alert fires, so spikes don't alert
+    pub breach_samples: usize,
 }
 
+/// How long a cached allow stands before policies are evaluated again
+const ALLOW_DECISION_TTL_MINUTES: i64 = 2;
+
+/// How long a cached deny stands
+const DENY_DECISION_TTL_MINUTES: i64 = 5;
+
+/// Most access decisions cached at once; the least recently used are evicted beyond it
+const ACCESS_CACHE_CAPACITY: usize = 10_000;
+
+/// How often tenants are checked for a due usage snapshot
+pub const DEFAULT_USAGE_SAMPLE_TICK: std::time::Duration = std::time::Duration::from_secs(60);
+
 /// Cross-tenant access validator
 #[derive(Debug)]
 pub struct CrossTenantAccessValidator {
-    /// Cache of validated access decisions
-    access_cache: Arc<RwLock<HashMap<String, AccessDecision>>>,
+    /// Cache of validated access decisions, each expiring at its `expires_at`
+    access_cache: Arc<RwLock<TtlCache<AccessCacheKey, AccessDecision>>>,
+    
+    /// Bumped on every policy mutation; decisions made under an older generation are never served
+    generation: AtomicU64,
+}
+
+/// Cached decisions are keyed by tenant pair and operation
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct AccessCacheKey {
+    source_tenant: String,
+    target_tenant: String,
+    operation: String,
 }
 
 /// Access decision with caching
@@ -811,6 +1012,10 @@ pub struct AccessDecision {
     pub reason: String,
     pub expires_at: DateTime<Utc>,
     pub conditions: Vec<String>,
+    /// Policy that granted access; `None` for denials
+    pub policy_id: Option<String>,
+    /// Validator generation the decision was made under
+    pub generation: u64,
 }
 
 /// Tenant resource monitor for real-time tracking
@@ -818,13 +1023,14 @@ pub struct AccessDecision {
 pub struct TenantResourceMonitor {
     pub tenant_id: String,
     pub current_usage: ResourceUsage,
-    pub usage_history: Vec<ResourceUsageSnapshot>,
+    /// Oldest first, bounded by `MonitoringConfig::history_retention`
+    pub usage_history: VecDeque<ResourceUsageSnapshot>,
     pub alerts: Vec<ResourceAlert>,
     pub last_updated: DateTime<Utc>,
 }
 
 /// Current resource usage
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ResourceUsage {
     pub cpu_usage_percent: f64,
     pub memory_usage_mb: u64,
@@ -836,6 +1042,88 @@ pub struct ResourceUsage {
     pub active_sessions: u32,
 }
 
+impl ResourceUsage {
+    /// Value of a named metric, as used for alert thresholds and trends
+    ///
+    /// `memory_usage_percent` and `storage_usage_percent` are relative to the tenant's limits.
+    pub fn metric(&self, metric: &str, limits: &TenantResourceLimits) -> Option<f64> {
+        let percent_of = |used: u64, limit: u64| (limit > 0).then(|| used as f64 / limit as f64 * 100.0);
+        match metric {
+            "cpu_usage_percent" => Some(self.cpu_usage_percent),
+            "memory_usage_mb" => Some(self.memory_usage_mb as f64),
+            "memory_usage_percent" => percent_of(self.memory_usage_mb, limits.memory_mb),
+            "storage_usage_gb" => Some(self.storage_usage_gb as f64),
+            "storage_usage_percent" => percent_of(self.storage_usage_gb, limits.storage_gb),
+            "network_usage_mbps" => Some(self.network_usage_mbps),
+            "database_connections" => Some(self.database_connections as f64),
+            "api_requests_per_minute" => Some(self.api_requests_per_minute as f64),
+            "active_users" => Some(self.active_users as f64),
+            "active_sessions" => Some(self.active_sessions as f64),
+            _ => None,
+        }
+    }
+}
+
+/// Quota-limited resources, by `TenantResourceLimits` field, with the usage metric each is measured by
+const QUOTA_USAGE_METRICS: &[(&str, &str)] = &[
+    ("memory_mb", "memory_usage_mb"),
+    ("storage_gb", "storage_usage_gb"),
+    ("network_bandwidth_mbps", "network_usage_mbps"),
+    ("database_connections", "database_connections"),
+    ("api_requests_per_minute", "api_requests_per_minute"),
+    ("max_users", "active_users"),
+    ("max_sessions", "active_sessions"),
+];
+
+impl TenantResourceLimits {
+    /// Limit on a quota-limited resource, by field name
+    pub fn quota(&self, resource: &str) -> Option<u64> {
+        match resource {
+            "memory_mb" => Some(self.memory_mb),
+            "storage_gb" => Some(self.storage_gb),
+            "network_bandwidth_mbps" => Some(self.network_bandwidth_mbps.into()),
+            "database_connections" => Some(self.database_connections.into()),
+            "api_requests_per_minute" => Some(self.api_requests_per_minute.into()),
+            "max_users" => Some(self.max_users.into()),
+            "max_sessions" => Some(self.max_sessions.into()),
+            _ => None,
+        }
+    }
+
+    /// Set a quota-limited resource; false if `resource` is not one
+    pub fn set_quota(&mut self, resource: &str, limit: u64) -> bool {
+        let narrow = |limit: u64| u32::try_from(limit).unwrap_or(u32::MAX);
+        match resource {
+            "memory_mb" => self.memory_mb = limit,
+            "storage_gb" => self.storage_gb = limit,
+            "network_bandwidth_mbps" => self.network_bandwidth_mbps = narrow(limit),
+            "database_connections" => self.database_connections = narrow(limit),
+            "api_requests_per_minute" => self.api_requests_per_minute = narrow(limit),
+            "max_users" => self.max_users = narrow(limit),
+            "max_sessions" => self.max_sessions = narrow(limit),
+            _ => return false,
+        }
+        true
+    }
+}
+
+impl ResourceUsage {
+    /// Current use of a quota-limited resource, in the unit of its limit
+    pub fn quota_usage(&self, resource: &str, limits: &TenantResourceLimits) -> Option<f64> {
+        QUOTA_USAGE_METRICS.iter()
+            .find(|(quota, _)| *quota == resource)
+            .and_then(|(_, metric)| self.metric(metric, limits))
+    }
+}
+
+/// A tenant's limits next to what it currently uses
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TenantQuotaUsage {
+    pub tenant_id: String,
+    pub limits: TenantResourceLimits,
+    pub usage: ResourceUsage,
+}
+
 /// Historical resource usage snapshot
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ResourceUsageSnapshot {
@@ -847,6 +1135,9 @@ pub struct ResourceUsageSnapshot {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ResourceAlert {
     pub alert_id: String,
+    /// Threshold metric that was breached, e.g. `cpu_usage_percent`
+    #[serde(default)]
+    pub metric: String,
     pub alert_type: ResourceAlertType,
     pub severity: AlertSeverity,
     pub message: String,
@@ -865,6 +1156,94 @@ pub enum ResourceAlertType {
     SecurityViolation,
 }
 
+impl ResourceAlertType {
+    fn for_metric(metric: &str) -> Self {
+        match metric.split('_').next() {
+            Some("cpu") => Self::CPUThreshold,
+            Some("memory") => Self::MemoryThreshold,
+            Some("storage") => Self::StorageThreshold,
+            Some("network") => Self::NetworkThreshold,
+            _ => Self::QuotaExceeded,
+        }
+    }
+}
+
+impl TenantResourceMonitor {
+    pub fn new(tenant_id: &str, now: DateTime<Utc>) -> Self {
+        Self {
+            tenant_id: tenant_id.to_string(),
+            current_usage: ResourceUsage::default(),
+            usage_history: VecDeque::new(),
+            alerts: Vec::new(),
+            last_updated: now,
+        }
+    }
+    
+    /// Whether `reporting_frequency` has passed since the last snapshot
+    pub fn is_sample_due(&self, config: &MonitoringConfig, now: DateTime<Utc>) -> bool {
+        self.usage_history.back()
+            .map_or(true, |last| now - last.timestamp >= config.reporting_frequency)
+    }
+    
+    /// Snapshot current usage at `now` and evaluate alert thresholds against the history
+    ///
+    /// A threshold alerts once it has been exceeded on `breach_samples` consecutive snapshots,
+    /// and its open alert resolves on the first snapshot back under it. Returns the alerts raised.
+    pub fn record_snapshot(
+        &mut self,
+        config: &MonitoringConfig,
+        limits: &TenantResourceLimits,
+        now: DateTime<Utc>,
+    ) -> Vec<ResourceAlert> {
+        self.usage_history.push_back(ResourceUsageSnapshot { timestamp: now, usage: self.current_usage.clone() });
+        let cutoff = now - config.history_retention;
+        while self.usage_history.front().is_some_and(|oldest| oldest.timestamp < cutoff) {
+            self.usage_history.pop_front();
+        }
+        self.alerts.retain(|alert| alert.resolved_at.map_or(true, |resolved_at| resolved_at >= cutoff));
+        
+        let mut raised = Vec::new();
+        for (metric, &threshold) in &config.alert_thresholds {
+            let breaches = self.usage_history.iter().rev()
+                .take_while(|snapshot| snapshot.usage.metric(metric, limits).is_some_and(|value| value > threshold))
+                .count();
+            let open = self.alerts.iter_mut()
+                .find(|alert| alert.metric == *metric && alert.resolved_at.is_none());
+            
+            match open {
+                Some(alert) if breaches == 0 => alert.resolved_at = Some(now),
+                None if breaches >= config.breach_samples.max(1) => {
+                    let alert = ResourceAlert {
+                        alert_id: Uuid::new_v4().to_string(),
+                        metric: metric.clone(),
+                        alert_type: ResourceAlertType::for_metric(metric),
+                        severity: AlertSeverity::High,
+                        message: format!(
+                            "{} above {} for {} consecutive samples",
+                            metric, threshold, breaches
+                        ),
+                        triggered_at: now,
+                        resolved_at: None,
+                    };
+                    self.alerts.push(alert.clone());
+                    raised.push(alert);
+                }
+                _ => {}
+            }
+        }
+        self.last_updated = now;
+        raised
+    }
+    
+    /// `metric` at each snapshot since `since`, oldest first
+    pub fn trend(&self, metric: &str, limits: &TenantResourceLimits, since: DateTime<Utc>) -> Vec<(DateTime<Utc>, f64)> {
+        self.usage_history.iter()
+            .filter(|snapshot| snapshot.timestamp >= since)
+            .filter_map(|snapshot| snapshot.usage.metric(metric, limits).map(|value| (snapshot.timestamp, value)))
+            .collect()
+    }
+}
+
 /// Multi-tenant system errors
 #[derive(Debug, thiserror::Error)]
 pub enum MultiTenantError {
@@ -886,12 +1265,34 @@ pub enum MultiTenantError {
     #[error("Cross-tenant access denied: {reason}")]
     CrossTenantAccessDenied { reason: String },
     
+    #[error("Cross-tenant policy not found: {policy_id}")]
+    PolicyNotFound { policy_id: String },
+    
+    #[error("Tenant {tenant_id} is {status:?}")]
+    TenantInactive { tenant_id: String, status: TenantStatus },
+    
+    #[error("Status of tenant {tenant_id} could not be stored: {error}")]
+    StatusNotStored { tenant_id: String, error: String },
+    
+    #[error("Tenant {tenant_id} cannot go from {from:?} to {to:?}")]
+    InvalidStatusTransition {
+        tenant_id: String,
+        from: TenantStatus,
+        to: TenantStatus,
+    },
+    
     #[error("Tenant provisioning failed: {tenant_id}, error: {error}")]
     ProvisioningFailed { 
         tenant_id: String, 
         error: String 
     },
     
+    #[error("No key provider for tenant {tenant_id}: {error}")]
+    KeyProviderUnavailable { tenant_id: String, error: String },
+    
+    #[error("Invalid alert rule for tenant {tenant_id}: {error}")]
+    InvalidAlertRule { tenant_id: String, error: String },
+    
     #[error("Isolation violation detected: {tenant_id}, violation: {violation}")]
     IsolationViolation { 
         tenant_id: String, 
@@ -924,6 +1325,11 @@ impl MultiTenantSystem {
         }
         
         let isolation_engine = TenantIsolationEngine::new().await?;
+        let audit_exports = Arc::new(AuditExportScheduler::new(forensic_logger.clone()));
+        let system_keys = system_key_provider().await.map_err(|e| MultiTenantError::KeyProviderUnavailable {
+            tenant_id: "system".to_string(),
+            error: e.to_string(),
+        })?;
         
         Ok(Self {
             tenants: Arc::new(RwLock::new(HashMap::new())),
@@ -934,13 +1340,120 @@ impl MultiTenantSystem {
             metrics_registry,
             database_manager,
             resource_monitors: Arc::new(RwLock::new(HashMap::new())),
+            audit_exports,
+            security_sink: None,
+            alert_rules: None,
+            network_transport: None,
+            health_probers: Arc::new(RwLock::new(HashMap::new())),
+            system_keys,
+            customer_keys: Arc::new(RwLock::new(HashMap::new())),
         })
     }
     
+    /// Use `provider` for system-managed keys, the one shared with crypto, licensing and the database
+    pub fn with_key_provider(mut self, provider: Arc<dyn KeyProvider>) -> Self {
+        self.system_keys = provider;
+        self
+    }
+    
+    /// Manage `tenant_id`'s customer keys through `provider`, its own KMS or HSM
+    pub async fn register_customer_key_provider(&self, tenant_id: &str, provider: Arc<dyn KeyProvider>) {
+        self.customer_keys.write().await.insert(tenant_id.to_string(), provider);
+    }
+    
+    /// Key provider for `tenant_id`'s data, selected by its key management strategy
+    pub async fn key_provider(&self, tenant_id: &str) -> Result<Arc<dyn KeyProvider>, MultiTenantError> {
+        let strategy = self.tenants.read().await.get(tenant_id)
+            .map(|tenant| tenant.security_config.key_management.clone())
+            .ok_or_else(|| MultiTenantError::TenantNotFound { tenant_id: tenant_id.to_string() })?;
+        self.select_key_provider(tenant_id, &strategy).await
+    }
+    
+    async fn select_key_provider(
+        &self,
+        tenant_id: &str,
+        strategy: &KeyManagementStrategy,
+    ) -> Result<Arc<dyn KeyProvider>, MultiTenantError> {
+        let customer = self.customer_keys.read().await.get(tenant_id).cloned();
+        strategy.select_provider(self.system_keys.clone(), customer)
+            .map_err(|e| MultiTenantError::KeyProviderUnavailable { tenant_id: tenant_id.to_string(), error: e.to_string() })
+    }
+    
+    /// Probe each tenant's load balancer targets through `transport`, per its health check config
+    pub fn with_health_probing(mut self, transport: Arc<SecureNetworkTransport>) -> Self {
+        self.network_transport = Some(transport);
+        self
+    }
+    
+    /// Health of `tenant_id`'s load balancer targets, for routing; `None` while it isn't probed
+    pub async fn health_prober(&self, tenant_id: &str) -> Option<Arc<HealthProber>> {
+        self.health_probers.read().await.get(tenant_id).map(|probe| probe.prober.clone())
+    }
+    
+    /// Start, restart or stop probing a tenant's load balancer targets to match its network config
+    ///
+    /// An unchanged load balancer config keeps its running prober, and the health it has seen.
+    async fn configure_health_probing(&self, tenant_id: &str, network_config: &TenantNetworkConfig) {
+        let Some(transport) = &self.network_transport else {
+            return;
+        };
+        let mut probers = self.health_probers.write().await;
+        match network_config.load_balancer_config.as_ref().filter(|config| !config.targets.is_empty()) {
+            Some(config) if probers.get(tenant_id).is_some_and(|probe| probe.config == *config) => {}
+            Some(config) => {
+                let prober = Arc::new(
+                    HealthProber::for_load_balancer(transport.clone(), config)
+                        .with_metrics_registry(self.metrics_registry.clone()),
+                );
+                let task = prober.clone().spawn();
+                probers.insert(tenant_id.to_string(), TenantHealthProbe { config: config.clone(), prober, task });
+                tracing::info!(tenant_id = %tenant_id, targets = config.targets.len(), "Probing load balancer targets");
+            }
+            None => {
+                probers.remove(tenant_id);
+            }
+        }
+    }
+    
+    /// Route tenants' high-severity security events to their own alert channels through `sink`
+    pub fn with_security_sink(mut self, sink: Arc<SecurityEventSink>) -> Self {
+        self.security_sink = Some(sink);
+        self
+    }
+    
+    /// Evaluate tenants' alert rules with `engine`
+    pub fn with_alert_rules(mut self, engine: Arc<AlertRuleEngine>) -> Self {
+        self.alert_rules = Some(engine);
+        self
+    }
+    
+    /// Hand a tenant's alert channels to the security alert sink and its rules to the rule engine
+    async fn configure_alert_channels(&self, tenant_id: &str, audit_config: &TenantAuditConfig) -> Result<(), MultiTenantError> {
+        if let Some(sink) = &self.security_sink {
+            sink.set_tenant_channels(tenant_id, audit_config.alerting_config.alert_channels.clone()).await;
+        }
+        if let Some(engine) = &self.alert_rules {
+            engine.set_tenant_config(tenant_id, &audit_config.alerting_config).await
+                .map_err(|e| MultiTenantError::InvalidAlertRule { tenant_id: tenant_id.to_string(), error: e.to_string() })?;
+        }
+        Ok(())
+    }
+    
+    /// Use a scheduler with persisted export progress instead of the in-memory default
+    pub fn with_audit_exports(mut self, audit_exports: Arc<AuditExportScheduler>) -> Self {
+        self.audit_exports = audit_exports;
+        self
+    }
+    
+    /// Audit export scheduler, e.g. to `spawn` it
+    pub fn audit_exports(&self) -> &Arc<AuditExportScheduler> {
+        &self.audit_exports
+    }
+    
     /// Create a new tenant
     pub async fn create_tenant(
         &self,
-        tenant_config: TenantConfig,
+        mut tenant_config: TenantConfig,
         app_state: &AppState,
     ) -> Result<String, MultiTenantError> {
         let tenant_id = tenant_config.tenant_id.clone();
@@ -950,17 +1463,31 @@ impl MultiTenantSystem {
             return Err(MultiTenantError::TenantAlreadyExists { tenant_id });
         }
         
-        // Provision tenant resources
-        self.provision_tenant_resources(&tenant_config).await?;
-        
-        // Setup tenant isolation
-        self.setup_tenant_isolation(&tenant_config).await?;
+        // Only new tenants are provisioned; a suspended or terminated config is refused
+        if !matches!(tenant_config.status, TenantStatus::Provisioning | TenantStatus::Active) {
+            return Err(MultiTenantError::InvalidStatusTransition {
+                tenant_id,
+                from: tenant_config.status,
+                to: TenantStatus::Active,
+            });
+        }
         
-        // Initialize tenant monitoring
-        self.initialize_tenant_monitoring(&tenant_config).await?;
+        // No operations reach the tenant until provisioning completes
+        self.set_gate_status(&tenant_id, TenantStatus::Provisioning).await?;
+        if let Err(e) = self.provision_tenant(&tenant_config).await {
+            if let Err(forget_error) = self.database_manager.forget_tenant_status(&tenant_id).await {
+                tracing::error!(tenant_id = %tenant_id, error = %forget_error, "Failed to clear status of unprovisioned tenant");
+            }
+            return Err(e);
+        }
+        tenant_config.status = TenantStatus::Active;
+        self.set_gate_status(&tenant_id, TenantStatus::Active).await?;
         
         // Store tenant configuration
         self.tenants.write().await.insert(tenant_id.clone(), tenant_config.clone());
+        self.audit_exports.configure_tenant(&tenant_id, &tenant_config.security_config.audit_config).await;
+        self.configure_alert_channels(&tenant_id, &tenant_config.security_config.audit_config).await?;
+        self.configure_health_probing(&tenant_id, &tenant_config.network_config).await;
         
         // Log tenant creation
         self.forensic_logger.log_tenant_operation(
@@ -975,11 +1502,28 @@ impl MultiTenantSystem {
             })
         ).await?;
         
-        tracing::info!(tenant_id = %tenant_id, "Tenant created successfully");
+        crate::security_log!(info, tenant_id = %tenant_id, "Tenant created successfully");
         
         Ok(tenant_id)
     }
     
+    /// Provision resources, isolation and monitoring for a new tenant
+    async fn provision_tenant(&self, tenant_config: &TenantConfig) -> Result<(), MultiTenantError> {
+        // A tenant whose keys can't be reached can't store anything
+        self.select_key_provider(&tenant_config.tenant_id, &tenant_config.security_config.key_management).await?;
+        validate_rules(&tenant_config.security_config.audit_config.alerting_config)
+            .map_err(|e| MultiTenantError::InvalidAlertRule { tenant_id: tenant_config.tenant_id.clone(), error: e.to_string() })?;
+        
+        // Provision tenant resources
+        self.provision_tenant_resources(tenant_config).await?;
+        
+        // Setup tenant isolation
+        self.setup_tenant_isolation(tenant_config).await?;
+        
+        // Initialize tenant monitoring
+        self.initialize_tenant_monitoring(tenant_config).await
+    }
+    
     /// Get tenant configuration
     pub async fn get_tenant(&self, tenant_id: &str) -> Option<TenantConfig> {
         self.tenants.read().await.get(tenant_id).cloned()
@@ -998,6 +1542,12 @@ impl MultiTenantSystem {
             // Apply updates
             self.apply_tenant_updates(tenant, updates).await?;
             tenant.updated_at = Utc::now();
+            self.set_gate_status(tenant_id, tenant.status.clone()).await?;
+            self.audit_exports.configure_tenant(tenant_id, &tenant.security_config.audit_config).await;
+            self.configure_alert_channels(tenant_id, &tenant.security_config.audit_config).await?;
+            self.configure_health_probing(tenant_id, &tenant.network_config).await;
+            // A suspension or security change must not be outlived by cached allows
+            self.isolation_engine.access_validator.invalidate_tenant(tenant_id).await;
             
             // Log tenant update
             self.forensic_logger.log_tenant_operation(
@@ -1025,11 +1575,25 @@ impl MultiTenantSystem {
         let tenant = self.tenants.write().await.remove(tenant_id)
             .ok_or_else(|| MultiTenantError::TenantNotFound { tenant_id: tenant_id.to_string() })?;
         
+        // Block the tenant's operations and sessions before tearing anything down
+        self.set_gate_status(tenant_id, TenantStatus::Deprovisioning).await?;
+        self.terminate_tenant_sessions(tenant_id).await;
+        
         // Deprovision tenant resources
         self.deprovision_tenant_resources(&tenant).await?;
+        self.set_gate_status(tenant_id, TenantStatus::Terminated).await?;
         
         // Remove tenant monitoring
         self.resource_monitors.write().await.remove(tenant_id);
+        self.audit_exports.remove_tenant(tenant_id).await;
+        if let Some(sink) = &self.security_sink {
+            sink.remove_tenant_channels(tenant_id).await;
+        }
+        if let Some(engine) = &self.alert_rules {
+            engine.remove_tenant(tenant_id).await;
+        }
+        self.health_probers.write().await.remove(tenant_id);
+        self.isolation_engine.remove_tenant(tenant_id).await;
         
         // Log tenant deletion
         self.forensic_logger.log_tenant_operation(
@@ -1042,11 +1606,131 @@ impl MultiTenantSystem {
             })
         ).await?;
         
-        tracing::info!(tenant_id = %tenant_id, "Tenant deleted successfully");
+        crate::security_log!(info, tenant_id = %tenant_id, "Tenant deleted successfully");
         
         Ok(())
     }
     
+    /// Suspend a tenant (e.g. for non-payment): its operations are refused and sessions ended
+    pub async fn suspend_tenant(
+        &self,
+        tenant_id: &str,
+        reason: &str,
+        app_state: &AppState,
+    ) -> Result<(), MultiTenantError> {
+        self.transition_tenant(tenant_id, TenantStatus::Suspended).await?;
+        let sessions_terminated = self.terminate_tenant_sessions(tenant_id).await;
+        
+        self.forensic_logger.log_tenant_operation(
+            "tenant_suspended",
+            tenant_id,
+            &app_state.context,
+            serde_json::json!({
+                "reason": reason,
+                "sessions_terminated": sessions_terminated,
+            })
+        ).await?;
+        
+        crate::security_log!(warn, tenant_id = %tenant_id, reason = %reason, "Tenant suspended");
+        
+        Ok(())
+    }
+    
+    /// Lift a suspension; the tenant's users must sign in again
+    pub async fn resume_tenant(
+        &self,
+        tenant_id: &str,
+        app_state: &AppState,
+    ) -> Result<(), MultiTenantError> {
+        self.transition_tenant(tenant_id, TenantStatus::Active).await?;
+        
+        self.forensic_logger.log_tenant_operation(
+            "tenant_resumed",
+            tenant_id,
+            &app_state.context,
+            serde_json::json!({}),
+        ).await?;
+        
+        crate::security_log!(info, tenant_id = %tenant_id, "Tenant resumed");
+        
+        Ok(())
+    }
+    
+    /// Store `status` for `tenant_id` and enforce it from the next operation on
+    async fn set_gate_status(&self, tenant_id: &str, status: TenantStatus) -> Result<(), MultiTenantError> {
+        self.database_manager.set_tenant_status(tenant_id, status).await
+            .map_err(|e| MultiTenantError::StatusNotStored { tenant_id: tenant_id.to_string(), error: e.to_string() })
+    }
+    
+    /// Move a tenant between `Active`/`Maintenance` and `Suspended`, effective immediately
+    async fn transition_tenant(
+        &self,
+        tenant_id: &str,
+        to: TenantStatus,
+    ) -> Result<(), MultiTenantError> {
+        let mut tenants = self.tenants.write().await;
+        let tenant = tenants.get_mut(tenant_id)
+            .ok_or_else(|| MultiTenantError::TenantNotFound { tenant_id: tenant_id.to_string() })?;
+        
+        let allowed = match to {
+            TenantStatus::Suspended => matches!(tenant.status, TenantStatus::Active | TenantStatus::Maintenance),
+            TenantStatus::Active => tenant.status == TenantStatus::Suspended,
+            _ => false,
+        };
+        if !allowed {
+            return Err(MultiTenantError::InvalidStatusTransition {
+                tenant_id: tenant_id.to_string(),
+                from: tenant.status.clone(),
+                to,
+            });
+        }
+        
+        self.set_gate_status(tenant_id, to.clone()).await?;
+        tenant.status = to;
+        tenant.updated_at = Utc::now();
+        self.isolation_engine.access_validator.invalidate_tenant(tenant_id).await;
+        
+        Ok(())
+    }
+    
+    /// End every session in the tenant; failures are logged, not returned
+    async fn terminate_tenant_sessions(&self, tenant_id: &str) -> usize {
+        match self.security_manager.terminate_tenant_sessions(tenant_id).await {
+            Ok(terminated) => terminated,
+            Err(e) => {
+                tracing::error!(tenant_id = %tenant_id, error = %e, "Failed to terminate tenant sessions");
+                0
+            }
+        }
+    }
+    
+    /// Apply every tenant's retention policies to its expired forensic data and entities
+    pub async fn enforce_retention(&self) -> Result<Vec<RetentionSweepReport>, DatabaseError> {
+        let sweeper = RetentionSweeper::new(self.database_manager.clone());
+        let tenants: Vec<TenantConfig> = self.tenants.read().await.values().cloned().collect();
+        let now = Utc::now();
+        
+        let mut reports = Vec::new();
+        for tenant in &tenants {
+            reports.extend(sweeper.sweep_tenant(tenant, now).await?);
+        }
+        Ok(reports)
+    }
+    
+    /// Enforce retention every `interval` until the runtime shuts down; failed sweeps are
+    /// logged and retried at the next tick
+    pub fn spawn_retention_sweeper(self: Arc<Self>, interval: std::time::Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.enforce_retention().await {
+                    tracing::error!(error = %e, "Retention sweep failed");
+                }
+            }
+        })
+    }
+    
     /// List all tenants
     pub async fn list_tenants(&self) -> Vec<TenantSummary> {
         self.tenants
@@ -1065,6 +1749,68 @@ impl MultiTenantSystem {
             .collect()
     }
     
+    /// Allow `policy.source_tenant` cross-tenant access under `policy`, replacing any policy with the same id
+    pub async fn grant_cross_tenant_policy(
+        &self,
+        policy: CrossTenantPolicy,
+        app_state: &AppState,
+    ) -> Result<(), MultiTenantError> {
+        let source_tenant = policy.source_tenant.clone();
+        let mut tenants = self.tenants.write().await;
+        let tenant = tenants.get_mut(&source_tenant)
+            .ok_or_else(|| MultiTenantError::TenantNotFound { tenant_id: source_tenant.clone() })?;
+        
+        let policies = &mut tenant.isolation_config.cross_tenant_policies;
+        policies.retain(|existing| existing.policy_id != policy.policy_id);
+        policies.push(policy.clone());
+        tenant.updated_at = Utc::now();
+        self.isolation_engine.grant_policy(policy.clone()).await;
+        
+        self.forensic_logger.log_tenant_operation(
+            "cross_tenant_policy_granted",
+            &source_tenant,
+            &app_state.context,
+            serde_json::json!({
+                "policy_id": policy.policy_id,
+                "target_tenant": policy.target_tenant,
+                "allowed_operations": policy.allowed_operations,
+            })
+        ).await?;
+        
+        Ok(())
+    }
+    
+    /// Revoke a cross-tenant policy; access it granted is denied from the next check on
+    pub async fn revoke_cross_tenant_policy(
+        &self,
+        policy_id: &str,
+        app_state: &AppState,
+    ) -> Result<(), MultiTenantError> {
+        let mut tenants = self.tenants.write().await;
+        let mut revoked = None;
+        for tenant in tenants.values_mut() {
+            let policies = &mut tenant.isolation_config.cross_tenant_policies;
+            if let Some(index) = policies.iter().position(|policy| policy.policy_id == policy_id) {
+                revoked = Some(policies.remove(index));
+                tenant.updated_at = Utc::now();
+            }
+        }
+        let revoked = self.isolation_engine.revoke_policy(policy_id).await.or(revoked)
+            .ok_or_else(|| MultiTenantError::PolicyNotFound { policy_id: policy_id.to_string() })?;
+        
+        self.forensic_logger.log_tenant_operation(
+            "cross_tenant_policy_revoked",
+            &revoked.source_tenant,
+            &app_state.context,
+            serde_json::json!({
+                "policy_id": policy_id,
+                "target_tenant": revoked.target_tenant,
+            })
+        ).await?;
+        
+        Ok(())
+    }
+    
     /// Check if cross-tenant access is allowed
     pub async fn check_cross_tenant_access(
         &self,
@@ -1073,6 +1819,20 @@ impl MultiTenantSystem {
         operation: &str,
         app_state: &AppState,
     ) -> Result<bool, MultiTenantError> {
+        // Suspended or otherwise inactive tenants get no cross-tenant access either way
+        {
+            let tenants = self.tenants.read().await;
+            for tenant_id in [source_tenant, target_tenant] {
+                if let Some(tenant) = tenants.get(tenant_id) {
+                    if !matches!(tenant.status, TenantStatus::Active) {
+                        return Err(MultiTenantError::CrossTenantAccessDenied {
+                            reason: format!("Tenant {} is {:?}", tenant_id, tenant.status),
+                        });
+                    }
+                }
+            }
+        }
+        
         // Use isolation engine to validate access
         self.isolation_engine.validate_cross_tenant_access(
             source_tenant,
@@ -1091,6 +1851,83 @@ impl MultiTenantSystem {
             .map(|monitor| monitor.current_usage.clone())
     }
     
+    /// Limits and current usage of every tenant; tenants not yet monitored show no usage
+    pub async fn tenant_quota_usage(&self) -> Vec<TenantQuotaUsage> {
+        let tenants = self.tenants.read().await;
+        let monitors = self.resource_monitors.read().await;
+        let mut quotas: Vec<TenantQuotaUsage> = tenants.values()
+            .map(|tenant| TenantQuotaUsage {
+                tenant_id: tenant.tenant_id.clone(),
+                limits: tenant.resource_limits.clone(),
+                usage: monitors.get(&tenant.tenant_id)
+                    .map(|monitor| monitor.current_usage.clone())
+                    .unwrap_or_default(),
+            })
+            .collect();
+        quotas.sort_by(|a, b| a.tenant_id.cmp(&b.tenant_id));
+        quotas
+    }
+    
+    /// Record a tenant's current usage, as reported by a collector
+    pub async fn record_resource_usage(&self, tenant_id: &str, usage: ResourceUsage) -> Result<(), MultiTenantError> {
+        let mut monitors = self.resource_monitors.write().await;
+        let monitor = monitors.get_mut(tenant_id)
+            .ok_or_else(|| MultiTenantError::TenantNotFound { tenant_id: tenant_id.to_string() })?;
+        monitor.current_usage = usage;
+        monitor.last_updated = Utc::now();
+        Ok(())
+    }
+    
+    /// Snapshot usage of every tenant whose reporting interval has elapsed
+    ///
+    /// Returns alerts raised by sustained threshold breaches.
+    pub async fn sample_resource_usage(&self, now: DateTime<Utc>) -> Vec<ResourceAlert> {
+        let tenants = self.tenants.read().await;
+        let policies = self.isolation_engine.isolation_policies.read().await;
+        let mut monitors = self.resource_monitors.write().await;
+        
+        let mut raised = Vec::new();
+        for (tenant_id, monitor) in monitors.iter_mut() {
+            let (Some(tenant), Some(policy)) = (tenants.get(tenant_id), policies.get(tenant_id)) else {
+                continue;
+            };
+            let config = &policy.monitoring_config;
+            if !config.metrics_collection || !monitor.is_sample_due(config, now) {
+                continue;
+            }
+            for alert in monitor.record_snapshot(config, &tenant.resource_limits, now) {
+                tracing::warn!(tenant_id = %tenant_id, metric = %alert.metric, "{}", alert.message);
+                raised.push(alert);
+            }
+        }
+        raised
+    }
+    
+    /// Sample resource usage every `tick` until the runtime shuts down
+    ///
+    /// Each tenant is still snapshotted only at its own `reporting_frequency`.
+    pub fn spawn_usage_sampler(self: Arc<Self>, tick: std::time::Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(tick);
+            loop {
+                ticker.tick().await;
+                self.sample_resource_usage(Utc::now()).await;
+            }
+        })
+    }
+    
+    /// `metric` over the last `window`, oldest first, for charting
+    pub async fn usage_trend(&self, tenant_id: &str, metric: &str, window: Duration) -> Vec<(DateTime<Utc>, f64)> {
+        let limits = match self.tenants.read().await.get(tenant_id) {
+            Some(tenant) => tenant.resource_limits.clone(),
+            None => return Vec::new(),
+        };
+        self.resource_monitors.read().await
+            .get(tenant_id)
+            .map(|monitor| monitor.trend(metric, &limits, Utc::now() - window))
+            .unwrap_or_default()
+    }
+    
     /// Get tenant metrics summary
     pub async fn get_tenant_metrics_summary(&self) -> TenantMetricsSummary {
         let tenants = self.tenants.read().await;
@@ -1143,6 +1980,8 @@ impl MultiTenantSystem {
                 real_time_monitoring: true,
                 alert_thresholds: self.create_default_alert_thresholds(),
                 reporting_frequency: Duration::minutes(5),
+                history_retention: Duration::hours(24),
+                breach_samples: 3,
             },
         };
         
@@ -1156,22 +1995,7 @@ impl MultiTenantSystem {
     }
     
     async fn initialize_tenant_monitoring(&self, tenant_config: &TenantConfig) -> Result<(), MultiTenantError> {
-        let resource_monitor = TenantResourceMonitor {
-            tenant_id: tenant_config.tenant_id.clone(),
-            current_usage: ResourceUsage {
-                cpu_usage_percent: 0.0,
-                memory_usage_mb: 0,
-                storage_usage_gb: 0,
-                network_usage_mbps: 0.0,
-                database_connections: 0,
-                api_requests_per_minute: 0,
-                active_users: 0,
-                active_sessions: 0,
-            },
-            usage_history: Vec::new(),
-            alerts: Vec::new(),
-            last_updated: Utc::now(),
-        };
+        let resource_monitor = TenantResourceMonitor::new(&tenant_config.tenant_id, Utc::now());
         
         self.resource_monitors
             .write()
@@ -1234,9 +2058,16 @@ impl MultiTenantSystem {
         }
         
         if let Some(security_config) = updates.security_config {
+            self.select_key_provider(&tenant.tenant_id, &security_config.key_management).await?;
+            validate_rules(&security_config.audit_config.alerting_config)
+                .map_err(|e| MultiTenantError::InvalidAlertRule { tenant_id: tenant.tenant_id.clone(), error: e.to_string() })?;
             tenant.security_config = security_config;
         }
         
+        if let Some(status) = updates.status {
+            tenant.status = status;
+        }
+        
         // Additional update logic would go here
         
         Ok(())
@@ -1250,6 +2081,9 @@ pub struct TenantConfigUpdate {
     pub security_config: Option<TenantSecurityConfig>,
     pub network_config: Option<TenantNetworkConfig>,
     pub storage_config: Option<TenantStorageConfig>,
+    /// E.g. `Suspended`; cross-tenant access is denied while not `Active`
+    #[serde(default)]
+    pub status: Option<TenantStatus>,
 }
 
 /// Tenant summary for listing
@@ -1288,14 +2122,27 @@ impl TenantIsolationEngine {
         target_tenant: &str,
         operation: &str,
         app_state: &AppState,
+    ) -> Result<bool, MultiTenantError> {
+        self.decide_cross_tenant_access(source_tenant, target_tenant, operation).await
+    }
+    
+    async fn decide_cross_tenant_access(
+        &self,
+        source_tenant: &str,
+        target_tenant: &str,
+        operation: &str,
     ) -> Result<bool, MultiTenantError> {
         // Check cache first
-        let cache_key = format!("{}->{}:{}", source_tenant, target_tenant, operation);
+        let cache_key = AccessCacheKey {
+            source_tenant: source_tenant.to_string(),
+            target_tenant: target_tenant.to_string(),
+            operation: operation.to_string(),
+        };
         if let Some(decision) = self.access_validator.get_cached_decision(&cache_key).await {
-            if decision.expires_at > Utc::now() {
-                return Ok(decision.allowed);
-            }
+            return Ok(decision.allowed);
         }
+        // Taken before reading policies, so a decision racing a policy change is never cached
+        let generation = self.access_validator.generation();
         
         // Validate access based on isolation policies
         let policies = self.isolation_policies.read().await;
@@ -1312,8 +2159,10 @@ impl TenantIsolationEngine {
                             AccessDecision {
                                 allowed: true,
                                 reason: "Cross-tenant policy allows access".to_string(),
-                                expires_at: Utc::now() + Duration::minutes(30),
+                                expires_at: Utc::now() + Duration::minutes(ALLOW_DECISION_TTL_MINUTES),
                                 conditions: policy.conditions.iter().map(|c| c.condition_type.clone()).collect(),
+                                policy_id: Some(policy.policy_id.clone()),
+                                generation,
                             }
                         ).await;
                         
@@ -1329,8 +2178,10 @@ impl TenantIsolationEngine {
             AccessDecision {
                 allowed: false,
                 reason: "No cross-tenant policy allows this access".to_string(),
-                expires_at: Utc::now() + Duration::minutes(5),
+                expires_at: Utc::now() + Duration::minutes(DENY_DECISION_TTL_MINUTES),
                 conditions: vec![],
+                policy_id: None,
+                generation,
             }
         ).await;
         
@@ -1339,6 +2190,40 @@ impl TenantIsolationEngine {
         })
     }
     
+    /// Add a policy to its source tenant's isolation policy; `false` if that tenant has none
+    async fn grant_policy(&self, policy: CrossTenantPolicy) -> bool {
+        let mut policies = self.isolation_policies.write().await;
+        let Some(source_policy) = policies.get_mut(&policy.source_tenant) else {
+            return false;
+        };
+        source_policy.isolation_config.cross_tenant_policies.retain(|existing| existing.policy_id != policy.policy_id);
+        source_policy.isolation_config.cross_tenant_policies.push(policy);
+        // Denials cached before the grant are stale
+        self.access_validator.bump_generation().await;
+        true
+    }
+    
+    /// Remove a policy and every decision it granted
+    async fn revoke_policy(&self, policy_id: &str) -> Option<CrossTenantPolicy> {
+        let mut revoked = None;
+        for isolation_policy in self.isolation_policies.write().await.values_mut() {
+            let policies = &mut isolation_policy.isolation_config.cross_tenant_policies;
+            if let Some(index) = policies.iter().position(|policy| policy.policy_id == policy_id) {
+                revoked = Some(policies.remove(index));
+            }
+        }
+        self.access_validator.invalidate_policy(policy_id).await;
+        self.access_validator.bump_generation().await;
+        revoked
+    }
+    
+    /// Drop a deleted tenant's isolation policy and every decision involving it
+    async fn remove_tenant(&self, tenant_id: &str) {
+        self.isolation_policies.write().await.remove(tenant_id);
+        self.access_validator.invalidate_tenant(tenant_id).await;
+        self.access_validator.bump_generation().await;
+    }
+    
     async fn evaluate_policy_conditions(&self, conditions: &[PolicyCondition]) -> bool {
         // Evaluate all conditions - all must be true
         for condition in conditions {
@@ -1367,16 +2252,58 @@ impl TenantIsolationEngine {
 impl CrossTenantAccessValidator {
     fn new() -> Self {
         Self {
-            access_cache: Arc::new(RwLock::new(HashMap::new())),
+            access_cache: Arc::new(RwLock::new(TtlCache::new(ACCESS_CACHE_CAPACITY))),
+            generation: AtomicU64::new(0),
+        }
+    }
+    
+    fn generation(&self) -> u64 {
+        self.generation.load(Ordering::Acquire)
+    }
+    
+    /// Cached decision, unless it has expired or predates the latest policy change
+    ///
+    /// Checks share the read lock; an expired entry is skipped and left to be evicted.
+    async fn get_cached_decision(&self, cache_key: &AccessCacheKey) -> Option<AccessDecision> {
+        let generation = self.generation();
+        self.access_cache.read().await.peek(cache_key)
+            .filter(|decision| decision.generation == generation)
+            .cloned()
+    }
+    
+    /// Cache a decision until its `expires_at`, unless policies changed while it was being made
+    async fn cache_decision(&self, cache_key: AccessCacheKey, decision: AccessDecision) {
+        let Ok(ttl) = (decision.expires_at - Utc::now()).to_std() else {
+            return;
+        };
+        let mut cache = self.access_cache.write().await;
+        if decision.generation == self.generation() {
+            cache.insert_with_ttl(cache_key, decision, ttl);
         }
     }
     
-    async fn get_cached_decision(&self, cache_key: &str) -> Option<AccessDecision> {
-        self.access_cache.read().await.get(cache_key).cloned()
+    /// Forget every decision to or from `tenant_id`, e.g. after it is suspended
+    pub async fn invalidate_tenant(&self, tenant_id: &str) {
+        self.access_cache.write().await
+            .remove_where(|key, _| key.source_tenant == tenant_id || key.target_tenant == tenant_id);
+    }
+    
+    /// Forget every decision `policy_id` granted
+    pub async fn invalidate_policy(&self, policy_id: &str) {
+        self.access_cache.write().await
+            .remove_where(|_, decision| decision.policy_id.as_deref() == Some(policy_id));
     }
     
-    async fn cache_decision(&self, cache_key: String, decision: AccessDecision) {
-        self.access_cache.write().await.insert(cache_key, decision);
+    /// Hit, miss and eviction counts for the decision cache
+    pub async fn cache_stats(&self) -> TtlCacheStats {
+        self.access_cache.read().await.stats()
+    }
+    
+    /// Invalidate every cached decision at once; call on any policy mutation
+    pub async fn bump_generation(&self) {
+        let mut cache = self.access_cache.write().await;
+        self.generation.fetch_add(1, Ordering::AcqRel);
+        cache.clear();
     }
 }
 
@@ -1498,4 +2425,118 @@ mod tests {
         assert_eq!(tenant_config.tenant_id, parsed.tenant_id);
         assert_eq!(tenant_config.tenant_name, parsed.tenant_name);
     }
+    
+    #[tokio::test]
+    async fn test_revoked_policy_denies_despite_cached_allow() {
+        let engine = TenantIsolationEngine::new().await.unwrap();
+        engine.isolation_policies.write().await.insert("acme".to_string(), IsolationPolicy {
+            tenant_id: "acme".to_string(),
+            isolation_config: IsolationConfig {
+                isolation_level: IsolationLevel::Shared,
+                database_isolation: DatabaseIsolation::SeparateSchema,
+                compute_isolation: ComputeIsolation::SeparateProcess,
+                network_isolation: NetworkIsolation::VirtualNetwork,
+                storage_isolation: StorageIsolation::SeparateVolume,
+                cross_tenant_policies: vec![],
+            },
+            enforcement_rules: vec![],
+            monitoring_config: MonitoringConfig {
+                metrics_collection: false,
+                real_time_monitoring: false,
+                alert_thresholds: HashMap::new(),
+                reporting_frequency: Duration::minutes(5),
+                history_retention: Duration::hours(24),
+                breach_samples: 3,
+            },
+        });
+        
+        // Nothing shared yet; the cached denial must not outlive the grant
+        assert!(engine.decide_cross_tenant_access("acme", "globex", "read").await.is_err());
+        assert!(engine.grant_policy(CrossTenantPolicy {
+            policy_id: "share-reports".to_string(),
+            source_tenant: "acme".to_string(),
+            target_tenant: "globex".to_string(),
+            allowed_operations: vec!["read".to_string()],
+            conditions: vec![],
+            expiry: None,
+        }).await);
+        assert!(engine.decide_cross_tenant_access("acme", "globex", "read").await.unwrap());
+        
+        let key = AccessCacheKey {
+            source_tenant: "acme".to_string(),
+            target_tenant: "globex".to_string(),
+            operation: "read".to_string(),
+        };
+        let cached = engine.access_validator.get_cached_decision(&key).await.unwrap();
+        assert!(cached.allowed);
+        assert_eq!(cached.policy_id.as_deref(), Some("share-reports"));
+        
+        assert!(engine.revoke_policy("share-reports").await.is_some());
+        assert!(engine.access_validator.get_cached_decision(&key).await.is_none());
+        assert!(engine.decide_cross_tenant_access("acme", "globex", "read").await.is_err());
+        
+        // A decision made before a policy change is never cached after it
+        let stale = AccessDecision { generation: engine.access_validator.generation(), ..cached };
+        engine.access_validator.bump_generation().await;
+        engine.access_validator.cache_decision(key.clone(), stale).await;
+        assert!(engine.access_validator.get_cached_decision(&key).await.is_none());
+    }
+    
+    #[test]
+    fn test_resource_alert_needs_sustained_breach() {
+        let limits = TenantResourceLimits {
+            cpu_cores: 4.0,
+            cpu_burst_limit: 8.0,
+            memory_mb: 1000,
+            memory_burst_mb: 2000,
+            storage_gb: 100,
+            storage_iops: 3000,
+            network_bandwidth_mbps: 1000,
+            network_connections: 10000,
+            database_connections: 100,
+            database_storage_gb: 50,
+            api_requests_per_minute: 10000,
+            api_requests_per_hour: 100000,
+            max_users: 100,
+            max_sessions: 500,
+            custom_limits: HashMap::new(),
+        };
+        let config = MonitoringConfig {
+            metrics_collection: true,
+            real_time_monitoring: true,
+            alert_thresholds: HashMap::from([("cpu_usage_percent".to_string(), 80.0)]),
+            reporting_frequency: Duration::minutes(5),
+            history_retention: Duration::hours(1),
+            breach_samples: 3,
+        };
+        let start = Utc::now();
+        let mut monitor = TenantResourceMonitor::new("acme", start);
+        let sample = |monitor: &mut TenantResourceMonitor, i: i64, cpu: f64| {
+            monitor.current_usage.cpu_usage_percent = cpu;
+            monitor.record_snapshot(&config, &limits, start + Duration::minutes(5 * i))
+        };
+        
+        // A spike, then a breach sustained for three samples
+        let raised: Vec<usize> = [95.0, 40.0, 90.0, 91.0, 92.0, 93.0]
+            .iter()
+            .enumerate()
+            .map(|(i, &cpu)| sample(&mut monitor, i as i64, cpu).len())
+            .collect();
+        assert_eq!(raised, vec![0, 0, 0, 0, 1, 0]);
+        assert!(matches!(monitor.alerts[0].alert_type, ResourceAlertType::CPUThreshold));
+        
+        // Dropping back under the threshold resolves the alert
+        assert!(sample(&mut monitor, 6, 30.0).is_empty());
+        assert!(monitor.alerts[0].resolved_at.is_some());
+        
+        // History stays within the retention window
+        for i in 7..40 {
+            sample(&mut monitor, i, 50.0);
+        }
+        assert_eq!(monitor.usage_history.len(), 13);
+        let trend = monitor.trend("cpu_usage_percent", &limits, start + Duration::minutes(5 * 37));
+        assert_eq!(trend.len(), 3);
+        assert!(trend.iter().all(|&(_, cpu)| cpu == 50.0));
+        assert!(monitor.trend("memory_usage_percent", &limits, start).iter().all(|&(_, memory)| memory == 0.0));
+    }
 }
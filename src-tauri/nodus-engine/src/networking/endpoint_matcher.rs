@@ -0,0 +1,327 @@
+// src-tauri/src/networking/endpoint_matcher.rs
+// Endpoint Matcher - Compiled host/path patterns for network policy lookup
+// Matches parsed URLs so subdomain confusion and `..` traversal can't satisfy a rule
+
+use regex::Regex;
+use std::cmp::Ordering;
+
+use super::NetworkError;
+
+/// Compiled endpoint pattern
+///
+/// Supported forms:
+/// - `*` matches every endpoint
+/// - `[scheme://]host[:port][/path]` glob, where `*` in the host matches one or more
+///   labels (`*.example.com` does not match `example.com`), `*` in the path matches
+///   within a segment and `**` matches any number of segments
+/// - `regex:<expr>` anchored regex evaluated against `host` + normalized path
+#[derive(Debug, Clone)]
+pub struct EndpointPattern {
+    source: String,
+    kind: PatternKind,
+}
+
+#[derive(Debug, Clone)]
+enum PatternKind {
+    Any,
+    Glob {
+        scheme: Option<String>,
+        host: Vec<String>,
+        port: Option<u16>,
+        path: Option<Vec<String>>,
+    },
+    Regex(Regex),
+}
+
+/// Specificity used to pick one policy when several patterns match; higher wins
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Specificity {
+    exact_host: bool,
+    host_literal_len: usize,
+    path_literal_len: usize,
+}
+
+impl EndpointPattern {
+    /// Compile a pattern once, at policy registration time
+    pub fn compile(pattern: &str) -> Result<Self, NetworkError> {
+        let trimmed = pattern.trim();
+
+        let kind = if trimmed == "*" {
+            PatternKind::Any
+        } else if let Some(expr) = trimmed.strip_prefix("regex:") {
+            let anchored = format!("^(?:{})$", expr);
+            PatternKind::Regex(Regex::new(&anchored).map_err(|e| {
+                NetworkError::PolicyViolation(format!("Invalid endpoint regex {}: {}", pattern, e))
+            })?)
+        } else {
+            Self::compile_glob(trimmed)?
+        };
+
+        Ok(Self {
+            source: pattern.to_string(),
+            kind,
+        })
+    }
+
+    /// Pattern as registered
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// Whether a request URL falls under this pattern
+    ///
+    /// The URL is parsed first, so userinfo, `..` segments and host case are resolved
+    /// before anything is compared.
+    pub fn matches(&self, url: &str) -> bool {
+        let parsed = match url::Url::parse(url) {
+            Ok(parsed) => parsed,
+            Err(_) => return false,
+        };
+        let host = match parsed.host_str() {
+            Some(host) => host.trim_end_matches('.').to_ascii_lowercase(),
+            None => return false,
+        };
+
+        match &self.kind {
+            PatternKind::Any => true,
+            PatternKind::Regex(regex) => regex.is_match(&format!("{}{}", host, parsed.path())),
+            PatternKind::Glob { scheme, host: host_pattern, port, path } => {
+                if let Some(scheme) = scheme {
+                    if parsed.scheme() != scheme {
+                        return false;
+                    }
+                }
+
+                if let Some(port) = port {
+                    if parsed.port_or_known_default() != Some(*port) {
+                        return false;
+                    }
+                }
+
+                let labels: Vec<&str> = host.split('.').collect();
+                if !Self::host_matches(host_pattern, &labels) {
+                    return false;
+                }
+
+                match path {
+                    Some(path_pattern) => {
+                        let segments: Vec<&str> = parsed.path_segments()
+                            .map(|segments| segments.filter(|s| !s.is_empty()).collect())
+                            .unwrap_or_default();
+                        Self::path_matches(path_pattern, &segments)
+                    }
+                    None => true,
+                }
+            }
+        }
+    }
+
+    /// How specific this pattern is, for most-specific-wins lookup
+    pub fn specificity(&self) -> Specificity {
+        match &self.kind {
+            PatternKind::Any => Specificity {
+                exact_host: false,
+                host_literal_len: 0,
+                path_literal_len: 0,
+            },
+            PatternKind::Regex(_) => Specificity {
+                exact_host: false,
+                host_literal_len: 0,
+                path_literal_len: 1,
+            },
+            PatternKind::Glob { host, path, .. } => Specificity {
+                exact_host: !host.iter().any(|label| label.contains('*')),
+                host_literal_len: Self::literal_len(host),
+                path_literal_len: path.as_deref().map(Self::literal_len).unwrap_or(0),
+            },
+        }
+    }
+
+    /// Order two patterns by specificity, falling back to the pattern text so lookup is deterministic
+    pub fn cmp_specificity(&self, other: &Self) -> Ordering {
+        self.specificity()
+            .cmp(&other.specificity())
+            .then_with(|| other.source.cmp(&self.source))
+    }
+
+    fn compile_glob(pattern: &str) -> Result<PatternKind, NetworkError> {
+        let (scheme, rest) = match pattern.split_once("://") {
+            Some((scheme, rest)) => (Some(scheme.to_ascii_lowercase()), rest),
+            None => (None, pattern),
+        };
+
+        let (authority, path) = match rest.find('/') {
+            Some(index) => (&rest[..index], Some(&rest[index..])),
+            None => (rest, None),
+        };
+
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (
+                host,
+                Some(port.parse::<u16>().map_err(|_| {
+                    NetworkError::PolicyViolation(format!("Invalid port in endpoint pattern {}", pattern))
+                })?),
+            ),
+            None => (authority, None),
+        };
+
+        if host.is_empty() || host.contains('@') {
+            return Err(NetworkError::PolicyViolation(format!(
+                "Invalid host in endpoint pattern {}", pattern
+            )));
+        }
+
+        let host_labels = host.trim_end_matches('.')
+            .split('.')
+            .map(|label| label.to_ascii_lowercase())
+            .collect();
+
+        let path_segments = path.map(|path| {
+            path.split('/')
+                .filter(|segment| !segment.is_empty())
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        });
+
+        if let Some(segments) = &path_segments {
+            if segments.iter().any(|segment| segment == "." || segment == "..") {
+                return Err(NetworkError::PolicyViolation(format!(
+                    "Dot segments are not allowed in endpoint pattern {}", pattern
+                )));
+            }
+        }
+
+        Ok(PatternKind::Glob {
+            scheme,
+            host: host_labels,
+            port,
+            path: path_segments,
+        })
+    }
+
+    fn host_matches(pattern: &[String], labels: &[&str]) -> bool {
+        match pattern.split_first() {
+            None => labels.is_empty(),
+            // Leading `*` consumes one or more labels
+            Some((first, rest)) if first == "*" => {
+                (1..=labels.len()).any(|taken| Self::host_matches(rest, &labels[taken..]))
+            }
+            Some((first, rest)) => match labels.split_first() {
+                Some((label, remaining)) => {
+                    Self::segment_matches(first, label) && Self::host_matches(rest, remaining)
+                }
+                None => false,
+            },
+        }
+    }
+
+    fn path_matches(pattern: &[String], segments: &[&str]) -> bool {
+        match pattern.split_first() {
+            None => segments.is_empty(),
+            Some((first, rest)) if first == "**" => {
+                (0..=segments.len()).any(|taken| Self::path_matches(rest, &segments[taken..]))
+            }
+            Some((first, rest)) => match segments.split_first() {
+                Some((segment, remaining)) => {
+                    Self::segment_matches(first, segment) && Self::path_matches(rest, remaining)
+                }
+                None => false,
+            },
+        }
+    }
+
+    /// Match one label or segment where `*` matches any run of characters
+    fn segment_matches(pattern: &str, value: &str) -> bool {
+        let parts: Vec<&str> = pattern.split('*').collect();
+        if parts.len() == 1 {
+            return pattern == value;
+        }
+
+        let (first, last) = (parts[0], parts[parts.len() - 1]);
+        if !value.starts_with(first) || value.len() < first.len() + last.len() || !value.ends_with(last) {
+            return false;
+        }
+
+        let mut remaining = &value[first.len()..value.len() - last.len()];
+        for part in &parts[1..parts.len() - 1] {
+            match remaining.find(part) {
+                Some(index) => remaining = &remaining[index + part.len()..],
+                None => return false,
+            }
+        }
+        true
+    }
+
+    fn literal_len(parts: &[String]) -> usize {
+        parts.iter().map(|part| part.chars().filter(|c| *c != '*').count()).sum()
+    }
+}
+
+/// Whether `domain` is `rule` or a subdomain of it (label-aware, unlike a substring check)
+pub fn domain_matches(domain: &str, rule: &str) -> bool {
+    let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+    let rule = rule.trim_end_matches('.').trim_start_matches("*.").to_ascii_lowercase();
+
+    domain == rule || domain.ends_with(&format!(".{}", rule))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subdomain_confusion_rejected() {
+        let pattern = EndpointPattern::compile("api.internal").unwrap();
+
+        assert!(pattern.matches("https://api.internal/v1/users"));
+        assert!(!pattern.matches("https://api.internal.evil.com/v1/users"));
+        assert!(!pattern.matches("https://evil.com/?next=api.internal"));
+        assert!(!pattern.matches("https://api.internal@evil.com/"));
+
+        assert!(domain_matches("api.internal", "api.internal"));
+        assert!(domain_matches("eu.api.internal", "api.internal"));
+        assert!(!domain_matches("api.internal.evil.com", "api.internal"));
+        assert!(!domain_matches("notapi.internal", "api.internal"));
+
+        let wildcard = EndpointPattern::compile("*.bank.com").unwrap();
+        assert!(wildcard.matches("https://secure.bank.com/"));
+        assert!(!wildcard.matches("https://bank.com/"));
+        assert!(!wildcard.matches("https://bank.com.evil.org/"));
+    }
+
+    #[test]
+    fn test_path_traversal_rejected() {
+        let pattern = EndpointPattern::compile("https://bank.com/api/**").unwrap();
+
+        assert!(pattern.matches("https://bank.com/api/accounts/1"));
+        assert!(!pattern.matches("https://bank.com/api/../evil"));
+        assert!(!pattern.matches("https://bank.com/api/%2e%2e/evil"));
+        assert!(!pattern.matches("http://bank.com/api/accounts"));
+
+        assert!(EndpointPattern::compile("bank.com/api/../admin").is_err());
+    }
+
+    #[test]
+    fn test_anchored_regex() {
+        let pattern = EndpointPattern::compile(r"regex:api\.internal/v[0-9]+/.*").unwrap();
+
+        assert!(pattern.matches("https://api.internal/v2/users"));
+        assert!(!pattern.matches("https://xapi.internal/v2/users"));
+        assert!(EndpointPattern::compile("regex:(").is_err());
+    }
+
+    #[test]
+    fn test_most_specific_wins() {
+        let mut patterns = vec![
+            EndpointPattern::compile("*").unwrap(),
+            EndpointPattern::compile("bank.com/api/payments/**").unwrap(),
+            EndpointPattern::compile("*.com").unwrap(),
+            EndpointPattern::compile("bank.com").unwrap(),
+        ];
+        patterns.sort_by(|a, b| b.cmp_specificity(a));
+
+        let url = "https://bank.com/api/payments/42";
+        let best = patterns.iter().find(|p| p.matches(url)).unwrap();
+        assert_eq!(best.as_str(), "bank.com/api/payments/**");
+    }
+}
@@ -0,0 +1,706 @@
+// src-tauri/src/networking/gateway.rs
+// Gateway Routes - Route table placed in front of SecureNetworkTransport
+// Each route names the permission and clearance it requires, its rate limit and its upstream
+// Errors and rate limit scopes are the enterprise API gateway's, which owns the router
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{Mutex, RwLock};
+use uuid::Uuid;
+
+use super::endpoint_matcher::EndpointPattern;
+use super::gateway_transform::{CompiledTransforms, RouteTransforms};
+use super::health_probe::HealthProber;
+use super::{
+    HttpMethod, NetworkContext, NetworkError, SecureNetworkTransport, SecureRequest, SecureResponse,
+    SecurityRequirements,
+};
+use crate::clock::{self, Clock};
+use crate::enterprise::api_gateway::{GatewayError, RateLimitScope};
+use crate::multi_tenant::MultiTenantSystem;
+use crate::observability::automatic_instrumentation::InstrumentationHooks;
+use crate::observability::{ForensicEnvelope, ObservabilityContext, QosClass};
+use crate::security::api_keys::API_KEY_HEADER;
+use crate::security::{has_permission, ClassificationLevel, Permission};
+use crate::state::UserContext;
+
+/// Authority route paths are matched under, so they get the same normalization as endpoint URLs
+const ROUTE_AUTHORITY: &str = "gateway.local";
+
+/// Header the caller's tenant is forwarded upstream in; a caller-supplied value is replaced
+pub const TENANT_HEADER: &str = "x-tenant-id";
+
+/// Most rate limit buckets kept; idle buckets are evicted first, then the least recently used
+const MAX_RATE_LIMIT_BUCKETS: usize = 10_000;
+
+/// A path the gateway serves and where it forwards to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GatewayRoute {
+    pub route_id: String,
+    /// Path pattern: `*` matches within a segment, `**` any number of segments
+    pub path: String,
+    /// Methods the route serves; empty serves every method
+    #[serde(default)]
+    pub methods: Vec<HttpMethod>,
+    /// Permission the caller must hold, if any
+    #[serde(default)]
+    pub permission: Option<Permission>,
+    /// Clearance the caller must dominate; also the classification of forwarded requests
+    #[serde(default = "default_clearance")]
+    pub clearance: ClassificationLevel,
+    #[serde(default)]
+    pub rate_limit: Option<RouteRateLimit>,
+    /// Base URL the request path is appended to
+    pub upstream: String,
+    /// Tried in order when `upstream` is unhealthy
+    #[serde(default)]
+    pub fallback_upstreams: Vec<String>,
+    #[serde(default)]
+    pub transforms: RouteTransforms,
+}
+
+impl GatewayRoute {
+    /// The primary upstream, then the fallbacks
+    pub fn upstreams(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.upstream.as_str()).chain(self.fallback_upstreams.iter().map(String::as_str))
+    }
+}
+
+fn default_clearance() -> ClassificationLevel {
+    ClassificationLevel::Unclassified
+}
+
+/// Requests one caller may make to a route per window
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RouteRateLimit {
+    pub requests: u32,
+    pub window_seconds: u64,
+    /// Who shares an allowance; API key callers act as their own user, so PerApiKey and PerUser agree
+    #[serde(default)]
+    pub scope: RateLimitScope,
+}
+
+/// Load balancer health per tenant; `MultiTenantSystem` in production
+#[async_trait::async_trait]
+pub trait TenantHealthSource: Send + Sync + std::fmt::Debug {
+    async fn prober_for(&self, tenant_id: &str) -> Option<Arc<HealthProber>>;
+}
+
+#[async_trait::async_trait]
+impl TenantHealthSource for MultiTenantSystem {
+    async fn prober_for(&self, tenant_id: &str) -> Option<Arc<HealthProber>> {
+        self.health_prober(tenant_id).await
+    }
+}
+
+/// Request arriving at the gateway
+#[derive(Debug, Clone)]
+pub struct GatewayRequest {
+    pub method: HttpMethod,
+    /// Path and optional query string, e.g. `/api/users?page=2`
+    pub path: String,
+    pub headers: HashMap<String, String>,
+    pub body: Option<Vec<u8>>,
+    /// Address the request came from, for PerIP rate limits
+    pub source_ip: Option<String>,
+}
+
+impl GatewayRequest {
+    pub fn new(method: HttpMethod, path: &str) -> Self {
+        Self {
+            method,
+            path: path.to_string(),
+            headers: HashMap::new(),
+            body: None,
+            source_ip: None,
+        }
+    }
+
+    /// Key a machine caller presented, to validate with `ApiKeyManager`
+    pub fn api_key(&self) -> Option<&str> {
+        self.headers.iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(API_KEY_HEADER))
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn route_path(&self) -> &str {
+        self.path.split(['?', '#']).next().unwrap_or_default()
+    }
+}
+
+#[derive(Debug)]
+struct CompiledRoute {
+    route: GatewayRoute,
+    pattern: EndpointPattern,
+    transforms: CompiledTransforms,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    refilled_at: Instant,
+    capacity: f64,
+    per_second: f64,
+}
+
+impl TokenBucket {
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.refilled_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.per_second).min(self.capacity);
+        self.refilled_at = now;
+    }
+
+    /// Would be full by `now`, so dropping it changes nothing
+    fn is_idle(&self, now: Instant) -> bool {
+        let elapsed = now.saturating_duration_since(self.refilled_at).as_secs_f64();
+        self.tokens + elapsed * self.per_second >= self.capacity
+    }
+}
+
+/// Route table with per-route authorization and rate limits, forwarding through `SecureNetworkTransport`
+///
+/// When several routes match, the most specific path wins; routes with the same path are
+/// tried in registration order.
+#[derive(Debug)]
+pub struct GatewayRouter {
+    routes: RwLock<Vec<Arc<CompiledRoute>>>,
+    /// Keyed by route and the caller's scope key, at most `MAX_RATE_LIMIT_BUCKETS`
+    buckets: Mutex<HashMap<(String, String), TokenBucket>>,
+    transport: Arc<SecureNetworkTransport>,
+    health: Option<Arc<dyn TenantHealthSource>>,
+    clock: Arc<dyn Clock>,
+}
+
+impl GatewayRouter {
+    pub fn new(transport: Arc<SecureNetworkTransport>) -> Self {
+        Self {
+            routes: RwLock::new(Vec::new()),
+            buckets: Mutex::new(HashMap::new()),
+            transport,
+            health: None,
+            clock: clock::system_clock(),
+        }
+    }
+
+    /// Refill rate limits by `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Skip upstreams the caller's tenant's prober reports unhealthy
+    pub fn with_tenant_health(mut self, source: Arc<dyn TenantHealthSource>) -> Self {
+        self.health = Some(source);
+        self
+    }
+
+    /// Add `route`, replacing any route with the same id
+    pub async fn add_route(&self, route: GatewayRoute) -> Result<(), GatewayError> {
+        let invalid = |reason: String| GatewayError::InvalidRoute { route_id: route.route_id.clone(), reason };
+        if !route.path.starts_with('/') {
+            return Err(invalid(format!("path {} must start with /", route.path)));
+        }
+        if let Some(upstream) = route.upstreams().find(|upstream| url::Url::parse(upstream).is_err()) {
+            return Err(invalid(format!("upstream {} is not a URL", upstream)));
+        }
+        if route.rate_limit.is_some_and(|limit| limit.requests == 0 || limit.window_seconds == 0) {
+            return Err(invalid("rate limit must allow at least one request per window".to_string()));
+        }
+        if route.rate_limit.is_some_and(|limit| limit.scope == RateLimitScope::Custom) {
+            return Err(invalid("custom rate limit scopes are not supported by routes".to_string()));
+        }
+        let pattern = EndpointPattern::compile(&format!("{}{}", ROUTE_AUTHORITY, route.path))
+            .map_err(|e| invalid(e.to_string()))?;
+        let transforms = CompiledTransforms::compile(&route.transforms).map_err(|e| invalid(e.to_string()))?;
+
+        let mut routes = self.routes.write().await;
+        routes.retain(|existing| existing.route.route_id != route.route_id);
+        routes.push(Arc::new(CompiledRoute { route, pattern, transforms }));
+        // Stable, so routes with the same path keep registration order
+        routes.sort_by(|a, b| b.pattern.cmp_specificity(&a.pattern));
+        Ok(())
+    }
+
+    pub async fn remove_route(&self, route_id: &str) -> bool {
+        let mut routes = self.routes.write().await;
+        let before = routes.len();
+        routes.retain(|existing| existing.route.route_id != route_id);
+        self.buckets.lock().await.retain(|(route, _), _| route != route_id);
+        routes.len() != before
+    }
+
+    /// Routes in match order
+    pub async fn routes(&self) -> Vec<GatewayRoute> {
+        self.routes.read().await.iter().map(|compiled| compiled.route.clone()).collect()
+    }
+
+    /// The route that serves `method` on `path`
+    pub async fn resolve(&self, method: &HttpMethod, path: &str) -> Option<GatewayRoute> {
+        self.matching(method, path).await.map(|compiled| compiled.route.clone())
+    }
+
+    async fn matching(&self, method: &HttpMethod, path: &str) -> Option<Arc<CompiledRoute>> {
+        if !path.starts_with('/') {
+            return None;
+        }
+        let url = format!("http://{}{}", ROUTE_AUTHORITY, path);
+        self.routes
+            .read()
+            .await
+            .iter()
+            .find(|compiled| {
+                (compiled.route.methods.is_empty() || compiled.route.methods.contains(method))
+                    && compiled.pattern.matches(&url)
+            })
+            .cloned()
+    }
+
+    /// Match `request`, then check the caller's authorization and rate limit
+    ///
+    /// Denials are audited through `hooks`, with the reason; throttled requests are not, since they were authorized.
+    pub async fn admit<H>(&self, request: &GatewayRequest, user: &UserContext, hooks: &H) -> Result<GatewayRoute, GatewayError>
+    where
+        H: InstrumentationHooks + ?Sized,
+    {
+        Ok(self.admit_compiled(request, user, hooks).await?.route.clone())
+    }
+
+    async fn admit_compiled<H>(&self, request: &GatewayRequest, user: &UserContext, hooks: &H) -> Result<Arc<CompiledRoute>, GatewayError>
+    where
+        H: InstrumentationHooks + ?Sized,
+    {
+        let compiled = self.matching(&request.method, request.route_path()).await.ok_or_else(|| GatewayError::RouteNotFound {
+            path: format!("{} {}", request.method.as_str(), request.route_path()),
+        })?;
+        let route = &compiled.route;
+
+        if let Some(reason) = Self::denial(route, user) {
+            let mut context = ObservabilityContext::new(
+                "api_gateway",
+                &format!("route.{}", route.route_id),
+                route.clearance.clone(),
+                &user.user_id,
+                user.session_id,
+            );
+            context.tenant_id = user.tenant_id.clone();
+            // Denied either way; a failed audit write is only reported
+            let audited = match hooks.audit_start(&context).await {
+                Ok(()) => hooks.audit_end(&context, false).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = audited {
+                tracing::error!(route_id = %route.route_id, "Failed to audit gateway denial: {}", e);
+            }
+            let envelope = ForensicEnvelope::new(
+                context.operation_id,
+                "gateway_denied",
+                &user.user_id,
+                user.session_id,
+                route.clearance.clone(),
+                "deny",
+            )
+            .with_resource(&route.route_id)
+            .with_metadata(serde_json::json!({
+                "reason": reason,
+                "method": request.method.as_str(),
+                "path": request.route_path(),
+                "tenant_id": user.tenant_id,
+            }));
+            hooks.audit_network(envelope).await;
+            crate::security_log!(warn, user_id = %user.user_id, route_id = %route.route_id, "Gateway route denied: {}", reason);
+            return Err(GatewayError::AuthorizationFailed { route_id: route.route_id.clone(), reason });
+        }
+
+        if let Some(limit) = route.rate_limit {
+            let caller = Self::rate_limit_key(limit.scope, request, user);
+            if let Err(retry_after) = self.try_acquire(&route.route_id, caller, limit).await {
+                tracing::debug!(route_id = %route.route_id, user_id = %user.user_id, "Gateway route rate limited");
+                return Err(GatewayError::RateLimitExceeded {
+                    limit_type: format!("{:?} on route {}", limit.scope, route.route_id),
+                    retry_after: Some(retry_after),
+                });
+            }
+        }
+
+        Ok(compiled)
+    }
+
+    /// Admit `request`, transform it, forward it to its route's upstream and transform the response
+    pub async fn forward<H>(&self, mut request: GatewayRequest, user: &UserContext, hooks: &H) -> Result<SecureResponse, GatewayError>
+    where
+        H: InstrumentationHooks + ?Sized,
+    {
+        let compiled = self.admit_compiled(&request, user, hooks).await?;
+        let route = &compiled.route;
+        let upstream = self.upstream_for(route, user.tenant_id.as_deref()).await?;
+        let transform_failed = |error: String| GatewayError::TransformationFailed { transformation_id: route.route_id.clone(), error };
+
+        // Rewrite the path the route matched, with dot segments already resolved
+        let matched = url::Url::parse(&format!("http://{}{}", ROUTE_AUTHORITY, request.path))
+            .map_err(|e| NetworkError::InvalidUrl(e.to_string()))?;
+        let mut target = compiled.transforms.rewrite_path(matched.path()).map_err(|e| transform_failed(e.to_string()))?;
+        if let Some(query) = matched.query() {
+            target.push(if target.contains('?') { '&' } else { '?' });
+            target.push_str(query);
+        }
+        // The caller's key authenticates it to the gateway, never to the upstream
+        request.headers.retain(|name, _| !name.eq_ignore_ascii_case(API_KEY_HEADER));
+        compiled.transforms.apply_request_headers(&mut request.headers);
+        // The tenant is the caller's, whatever the request or its transforms claimed
+        request.headers.retain(|name, _| !name.eq_ignore_ascii_case(TENANT_HEADER));
+        if let Some(tenant_id) = &user.tenant_id {
+            request.headers.insert(TENANT_HEADER.to_string(), tenant_id.clone());
+        }
+
+        let secure_request = SecureRequest {
+            request_id: Uuid::new_v4(),
+            url: format!("{}{}", upstream.trim_end_matches('/'), target),
+            method: request.method,
+            headers: request.headers,
+            body: request.body,
+            classification: route.clearance.clone(),
+            user_id: user.user_id.clone(),
+            session_id: user.session_id,
+            timeout_ms: None,
+            retry_policy: None,
+            cache_policy: None,
+            security_requirements: SecurityRequirements::default(),
+            idempotency_key: None,
+            qos: QosClass::Interactive,
+        };
+        let context = NetworkContext {
+            user_id: user.user_id.clone(),
+            session_id: user.session_id,
+            security_label: user.to_security_label(),
+            tenant_id: user.tenant_id.clone(),
+            source_ip: request.source_ip,
+            user_agent: None,
+        };
+
+        let mut response = self.transport.request(secure_request, context, hooks).await?;
+        let stripped = compiled.transforms
+            .apply_response(&mut response, &user.to_security_label())
+            .map_err(|e| transform_failed(e.to_string()))?;
+        if stripped > 0 {
+            tracing::debug!(route_id = %route.route_id, user_id = %user.user_id, stripped, "Stripped response fields above caller clearance");
+        }
+        Ok(response)
+    }
+
+    /// First of the route's upstreams the tenant's health prober doesn't report unhealthy
+    async fn upstream_for<'a>(&self, route: &'a GatewayRoute, tenant_id: Option<&str>) -> Result<&'a str, GatewayError> {
+        let prober = match (&self.health, tenant_id) {
+            (Some(health), Some(tenant_id)) => health.prober_for(tenant_id).await,
+            _ => None,
+        };
+        let Some(prober) = prober else {
+            return Ok(&route.upstream);
+        };
+        for upstream in route.upstreams() {
+            if prober.is_healthy(upstream).await {
+                return Ok(upstream);
+            }
+        }
+        Err(GatewayError::NoHealthyUpstream { route_id: route.route_id.clone() })
+    }
+
+    /// Why `user` may not call `route`, if they may not
+    fn denial(route: &GatewayRoute, user: &UserContext) -> Option<String> {
+        if !user.is_valid() {
+            return Some("user context expired".to_string());
+        }
+        if !user.clearance_level.dominates(&route.clearance) {
+            return Some(format!("requires {:?} clearance", route.clearance));
+        }
+        match route.permission {
+            Some(permission) if !has_permission(user, permission) => Some(format!("missing {} permission", permission)),
+            _ => None,
+        }
+    }
+
+    /// Who shares the allowance of a route limited by `scope`
+    fn rate_limit_key<'a>(scope: RateLimitScope, request: &'a GatewayRequest, user: &'a UserContext) -> &'a str {
+        match scope {
+            RateLimitScope::Global => "*",
+            RateLimitScope::PerTenant => user.tenant_id.as_deref().unwrap_or(&user.user_id),
+            RateLimitScope::PerIP => request.source_ip.as_deref().unwrap_or(&user.user_id),
+            // Custom scopes are rejected when the route is added
+            RateLimitScope::PerApiKey | RateLimitScope::PerUser | RateLimitScope::Custom => &user.user_id,
+        }
+    }
+
+    /// Spend one request from the caller's bucket, or report how long until one is available
+    async fn try_acquire(&self, route_id: &str, caller: &str, limit: RouteRateLimit) -> Result<(), Duration> {
+        let capacity = f64::from(limit.requests);
+        let now = self.clock.instant();
+        let key = (route_id.to_string(), caller.to_string());
+
+        let mut buckets = self.buckets.lock().await;
+        if !buckets.contains_key(&key) && buckets.len() >= MAX_RATE_LIMIT_BUCKETS {
+            Self::evict_bucket(&mut buckets, now);
+        }
+        let bucket = buckets.entry(key).or_insert(TokenBucket {
+            tokens: capacity,
+            refilled_at: now,
+            capacity,
+            per_second: capacity / limit.window_seconds as f64,
+        });
+        bucket.refill(now);
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / bucket.per_second))
+        }
+    }
+
+    /// Make room for one bucket: drop every idle bucket, or failing that the least recently used
+    fn evict_bucket(buckets: &mut HashMap<(String, String), TokenBucket>, now: Instant) {
+        buckets.retain(|_, bucket| !bucket.is_idle(now));
+        if buckets.len() < MAX_RATE_LIMIT_BUCKETS {
+            return;
+        }
+        let oldest = buckets.iter().min_by_key(|(_, bucket)| bucket.refilled_at).map(|(key, _)| key.clone());
+        if let Some(oldest) = oldest {
+            buckets.remove(&oldest);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+    use crate::license::LicenseManager;
+    use crate::multi_tenant::HealthCheckConfig;
+    use crate::observability::forensic_logger::ForensicError;
+
+    #[derive(Default)]
+    struct RecordingHooks {
+        events: Mutex<Vec<String>>,
+    }
+
+    #[async_trait::async_trait]
+    impl InstrumentationHooks for RecordingHooks {
+        async fn audit_start(&self, _context: &ObservabilityContext) -> Result<(), ForensicError> {
+            self.events.lock().await.push("audit_start".to_string());
+            Ok(())
+        }
+        async fn audit_end(&self, _context: &ObservabilityContext, success: bool) -> Result<(), ForensicError> {
+            self.events.lock().await.push(if success { "audit_end" } else { "audit_end_failed" }.to_string());
+            Ok(())
+        }
+        async fn metrics_start(&self, _context: &ObservabilityContext) {}
+        async fn metrics_end(&self, _context: &ObservabilityContext, _duration: Duration) {}
+        async fn audit_network(&self, envelope: ForensicEnvelope) {
+            let reason = envelope.metadata["reason"].as_str().unwrap_or_default().to_string();
+            self.events.lock().await.push(format!("{}: {}", envelope.event_type, reason));
+        }
+    }
+
+    #[derive(Debug, Default)]
+    struct TenantProbers(HashMap<String, Arc<HealthProber>>);
+
+    #[async_trait::async_trait]
+    impl TenantHealthSource for TenantProbers {
+        async fn prober_for(&self, tenant_id: &str) -> Option<Arc<HealthProber>> {
+            self.0.get(tenant_id).cloned()
+        }
+    }
+
+    async fn transport() -> Arc<SecureNetworkTransport> {
+        let license_manager = Arc::new(LicenseManager::new().await.unwrap());
+        Arc::new(SecureNetworkTransport::new(license_manager).await.unwrap())
+    }
+
+    async fn router() -> GatewayRouter {
+        GatewayRouter::new(transport().await)
+    }
+
+    fn route(route_id: &str, path: &str) -> GatewayRoute {
+        GatewayRoute {
+            route_id: route_id.to_string(),
+            path: path.to_string(),
+            methods: vec![],
+            permission: None,
+            clearance: ClassificationLevel::Unclassified,
+            rate_limit: None,
+            upstream: "https://upstream.internal".to_string(),
+            fallback_upstreams: vec![],
+            transforms: RouteTransforms::default(),
+        }
+    }
+
+    fn user(user_id: &str, clearance: ClassificationLevel, permissions: &[Permission]) -> UserContext {
+        UserContext::new(
+            user_id.to_string(),
+            clearance,
+            vec![],
+            permissions.iter().map(|permission| permission.as_str().to_string()).collect(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_most_specific_route_wins() {
+        let router = router().await;
+        router.add_route(route("api", "/api/**")).await.unwrap();
+        router.add_route(route("user", "/api/users/*")).await.unwrap();
+        router.add_route(route("me", "/api/users/me")).await.unwrap();
+        router.add_route(GatewayRoute { methods: vec![HttpMethod::GET], ..route("read-admin", "/admin/**") }).await.unwrap();
+
+        let routes = &router;
+        let resolved = |method, path| async move { routes.resolve(&method, path).await.map(|route| route.route_id) };
+        assert_eq!(resolved(HttpMethod::GET, "/api/users/me").await.as_deref(), Some("me"));
+        assert_eq!(resolved(HttpMethod::GET, "/api/users/42").await.as_deref(), Some("user"));
+        assert_eq!(resolved(HttpMethod::DELETE, "/api/orders/7").await.as_deref(), Some("api"));
+        assert_eq!(resolved(HttpMethod::GET, "/admin/audit").await.as_deref(), Some("read-admin"));
+        assert_eq!(resolved(HttpMethod::GET, "/api/users/../../admin/audit").await.as_deref(), Some("read-admin"));
+
+        let hooks = RecordingHooks::default();
+        let anyone = user("alice", ClassificationLevel::Internal, &[]);
+        let query = GatewayRequest::new(HttpMethod::GET, "/api/users/me?fields=name");
+        assert_eq!(router.admit(&query, &anyone, &hooks).await.unwrap().route_id, "me");
+        for path in ["/admin/audit", "/status"] {
+            let request = GatewayRequest::new(if path == "/status" { HttpMethod::GET } else { HttpMethod::POST }, path);
+            let error = router.admit(&request, &anyone, &hooks).await.unwrap_err();
+            assert!(matches!(error, GatewayError::RouteNotFound { .. }));
+            assert_eq!(error.status_code(), 404);
+        }
+
+        // Re-adding a route replaces it rather than shadowing it
+        router.add_route(route("me", "/me")).await.unwrap();
+        assert_eq!(resolved(HttpMethod::GET, "/api/users/me").await.as_deref(), Some("user"));
+        assert_eq!(router.routes().await.len(), 4);
+        assert!(router.add_route(route("bad", "api/**")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_route_denied_without_permission_or_clearance_is_audited() {
+        let router = router().await;
+        router.add_route(GatewayRoute {
+            permission: Some(Permission::AuditExport),
+            clearance: ClassificationLevel::Confidential,
+            ..route("audit-export", "/audit/export")
+        }).await.unwrap();
+        let request = GatewayRequest::new(HttpMethod::POST, "/audit/export");
+        let hooks = RecordingHooks::default();
+
+        let uncleared = user("bob", ClassificationLevel::Internal, &[Permission::AuditExport]);
+        let error = router.admit(&request, &uncleared, &hooks).await.unwrap_err();
+        assert!(matches!(error, GatewayError::AuthorizationFailed { .. }));
+        assert_eq!(error.status_code(), 403);
+
+        let unpermitted = user("carol", ClassificationLevel::Secret, &[Permission::Read]);
+        assert_eq!(router.admit(&request, &unpermitted, &hooks).await.unwrap_err().status_code(), 403);
+        assert_eq!(
+            *hooks.events.lock().await,
+            vec![
+                "audit_start",
+                "audit_end_failed",
+                "gateway_denied: requires Confidential clearance",
+                "audit_start",
+                "audit_end_failed",
+                "gateway_denied: missing audit_export permission",
+            ]
+        );
+
+        let auditor = user("dave", ClassificationLevel::Secret, &[Permission::AuditExport]);
+        assert_eq!(router.admit(&request, &auditor, &hooks).await.unwrap().route_id, "audit-export");
+    }
+
+    #[tokio::test]
+    async fn test_route_rate_limited_per_caller() {
+        let clock = Arc::new(MockClock::default());
+        let router = router().await.with_clock(clock.clone());
+        router.add_route(GatewayRoute {
+            rate_limit: Some(RouteRateLimit { requests: 2, window_seconds: 60, scope: RateLimitScope::PerUser }),
+            ..route("search", "/search")
+        }).await.unwrap();
+        router.add_route(GatewayRoute {
+            rate_limit: Some(RouteRateLimit { requests: 1, window_seconds: 60, scope: RateLimitScope::PerTenant }),
+            ..route("export", "/export")
+        }).await.unwrap();
+        let request = GatewayRequest::new(HttpMethod::GET, "/search");
+        let hooks = RecordingHooks::default();
+        let alice = user("alice", ClassificationLevel::Internal, &[]).with_tenant("acme");
+
+        assert!(router.admit(&request, &alice, &hooks).await.is_ok());
+        assert!(router.admit(&request, &alice, &hooks).await.is_ok());
+        let error = router.admit(&request, &alice, &hooks).await.unwrap_err();
+        assert_eq!(error.status_code(), 429);
+        assert!(matches!(error, GatewayError::RateLimitExceeded { retry_after: Some(retry_after), .. } if retry_after == Duration::from_secs(30)));
+        assert!(hooks.events.lock().await.is_empty());
+
+        // Other callers have their own allowance, and the caller's refills over the window
+        let bob = user("bob", ClassificationLevel::Internal, &[]).with_tenant("acme");
+        assert!(router.admit(&request, &bob, &hooks).await.is_ok());
+        clock.advance(Duration::from_secs(31));
+        assert!(router.admit(&request, &alice, &hooks).await.is_ok());
+        assert!(router.admit(&request, &alice, &hooks).await.is_err());
+
+        // A tenant-scoped limit is shared by the tenant's users
+        let export = GatewayRequest::new(HttpMethod::GET, "/export");
+        assert!(router.admit(&export, &alice, &hooks).await.is_ok());
+        assert_eq!(router.admit(&export, &bob, &hooks).await.unwrap_err().status_code(), 429);
+
+        let custom = RouteRateLimit { requests: 1, window_seconds: 60, scope: RateLimitScope::Custom };
+        assert!(router.add_route(GatewayRoute { rate_limit: Some(custom), ..route("custom", "/custom") }).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_buckets_are_bounded() {
+        let clock = Arc::new(MockClock::default());
+        let router = router().await.with_clock(clock.clone());
+        let limit = RouteRateLimit { requests: 1, window_seconds: 60, scope: RateLimitScope::PerUser };
+
+        for caller in 0..MAX_RATE_LIMIT_BUCKETS {
+            assert!(router.try_acquire("search", &caller.to_string(), limit).await.is_ok());
+        }
+        // Every bucket is in use, so the least recently used one makes room
+        clock.advance(Duration::from_secs(1));
+        assert!(router.try_acquire("search", "newcomer", limit).await.is_ok());
+        assert_eq!(router.buckets.lock().await.len(), MAX_RATE_LIMIT_BUCKETS);
+
+        // Once buckets have refilled they're dropped rather than counted against the bound
+        clock.advance(Duration::from_secs(60));
+        assert!(router.try_acquire("search", "latecomer", limit).await.is_ok());
+        assert_eq!(router.buckets.lock().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_unhealthy_upstreams_are_skipped_per_tenant() {
+        let transport = transport().await;
+        let prober = Arc::new(HealthProber::new(transport.clone(), HealthCheckConfig {
+            protocol: "HTTPS".to_string(),
+            port: 443,
+            path: "/healthz".to_string(),
+            interval_seconds: 10,
+            timeout_seconds: 2,
+            healthy_threshold: 1,
+            unhealthy_threshold: 1,
+        }));
+        let probers = TenantProbers(HashMap::from([("acme".to_string(), prober.clone())]));
+        let router = GatewayRouter::new(transport).with_tenant_health(Arc::new(probers));
+        let primary = "https://upstream.internal";
+        let replica = "https://replica.internal";
+        let orders = GatewayRoute { fallback_upstreams: vec![replica.to_string()], ..route("orders", "/orders") };
+        prober.add_target(primary).await;
+        prober.add_target(replica).await;
+
+        assert_eq!(router.upstream_for(&orders, Some("acme")).await.unwrap(), primary);
+        prober.record(primary, Err("timed out".to_string())).await;
+        assert_eq!(router.upstream_for(&orders, Some("acme")).await.unwrap(), replica);
+
+        prober.record(replica, Err("status 503".to_string())).await;
+        assert_eq!(router.upstream_for(&orders, Some("acme")).await.unwrap_err().status_code(), 503);
+        // Another tenant's probes don't apply to this one
+        assert_eq!(router.upstream_for(&orders, Some("globex")).await.unwrap(), primary);
+        assert_eq!(router.upstream_for(&orders, None).await.unwrap(), primary);
+
+        prober.record(primary, Ok(())).await;
+        assert_eq!(router.upstream_for(&orders, Some("acme")).await.unwrap(), primary);
+    }
+}
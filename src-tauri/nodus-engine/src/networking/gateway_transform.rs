@@ -0,0 +1,303 @@
+// src-tauri/src/networking/gateway_transform.rs
+// Gateway Transforms - Declarative header, path and body rules applied around forwarding
+// Response fields are stripped by the same label dominance MAC reads are decided by
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::SecureResponse;
+use crate::security::{ClassificationLevel, SecurityLabel};
+
+/// Transform rules for one route
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RouteTransforms {
+    /// Applied, in order, to the request before forwarding
+    pub request_headers: Vec<HeaderRule>,
+    pub path_rewrite: Option<PathRewrite>,
+    /// Applied, in order, to the response before returning it
+    pub response_headers: Vec<HeaderRule>,
+    /// JSON response fields removed for callers whose label doesn't dominate the field's
+    pub classified_fields: Vec<ClassifiedField>,
+}
+
+/// Header change; names match case-insensitively
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum HeaderRule {
+    /// Set `name`, replacing any existing value
+    Add { name: String, value: String },
+    Remove { name: String },
+    Rename { from: String, to: String },
+}
+
+/// Regex rewrite of the forwarded path; `replacement` may use `$1` or `${name}` captures
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PathRewrite {
+    pub pattern: String,
+    pub replacement: String,
+}
+
+/// A response field and the label a caller's must dominate to see it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClassifiedField {
+    /// Dotted path into the JSON body; arrays apply the rest of the path to each element
+    pub path: String,
+    pub classification: ClassificationLevel,
+    #[serde(default)]
+    pub compartments: Vec<String>,
+}
+
+impl ClassifiedField {
+    pub fn label(&self) -> SecurityLabel {
+        SecurityLabel::new(self.classification.clone(), self.compartments.clone())
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TransformError {
+    #[error("Invalid path rewrite pattern {pattern}: {reason}")]
+    InvalidPattern { pattern: String, reason: String },
+
+    #[error("Path rewrite produced {path}, which is not absolute")]
+    InvalidRewrite { path: String },
+
+    #[error("Response body can't be filtered: {reason}")]
+    UnfilterableBody { reason: String },
+}
+
+/// `RouteTransforms` with the path rewrite compiled, built once when the route is added
+#[derive(Debug, Clone)]
+pub struct CompiledTransforms {
+    rules: RouteTransforms,
+    path_rewrite: Option<Regex>,
+}
+
+impl CompiledTransforms {
+    pub fn compile(rules: &RouteTransforms) -> Result<Self, TransformError> {
+        let path_rewrite = rules.path_rewrite.as_ref()
+            .map(|rewrite| Regex::new(&rewrite.pattern).map_err(|e| TransformError::InvalidPattern {
+                pattern: rewrite.pattern.clone(),
+                reason: e.to_string(),
+            }))
+            .transpose()?;
+        Ok(Self { rules: rules.clone(), path_rewrite })
+    }
+
+    pub fn rules(&self) -> &RouteTransforms {
+        &self.rules
+    }
+
+    /// The path to forward, after the rewrite if one is configured
+    pub fn rewrite_path(&self, path: &str) -> Result<String, TransformError> {
+        let (Some(regex), Some(rewrite)) = (&self.path_rewrite, &self.rules.path_rewrite) else {
+            return Ok(path.to_string());
+        };
+        let rewritten = regex.replace(path, rewrite.replacement.as_str()).into_owned();
+        if !rewritten.starts_with('/') {
+            return Err(TransformError::InvalidRewrite { path: rewritten });
+        }
+        Ok(rewritten)
+    }
+
+    pub fn apply_request_headers(&self, headers: &mut HashMap<String, String>) {
+        apply_header_rules(&self.rules.request_headers, headers);
+    }
+
+    /// Apply the response header rules, then strip fields `caller` may not see
+    ///
+    /// Returns how many fields were removed. A body that must be filtered but isn't JSON is an
+    /// error rather than being passed through.
+    pub fn apply_response(&self, response: &mut SecureResponse, caller: &SecurityLabel) -> Result<usize, TransformError> {
+        apply_header_rules(&self.rules.response_headers, &mut response.headers);
+
+        let hidden: Vec<&ClassifiedField> = self.rules.classified_fields.iter()
+            .filter(|field| !caller.dominates(&field.label()))
+            .collect();
+        let body = match &response.body {
+            Some(body) if !hidden.is_empty() && !body.is_empty() => body,
+            _ => return Ok(0),
+        };
+
+        let mut value: serde_json::Value = serde_json::from_slice(body)
+            .map_err(|e| TransformError::UnfilterableBody { reason: e.to_string() })?;
+        let stripped: usize = hidden.iter()
+            .map(|field| strip_field(&mut value, &field.path.split('.').collect::<Vec<_>>()))
+            .sum();
+
+        if stripped > 0 {
+            let filtered = serde_json::to_vec(&value)
+                .map_err(|e| TransformError::UnfilterableBody { reason: e.to_string() })?;
+            response.body = Some(filtered);
+            remove_header(&mut response.headers, "content-length");
+        }
+        Ok(stripped)
+    }
+}
+
+fn apply_header_rules(rules: &[HeaderRule], headers: &mut HashMap<String, String>) {
+    for rule in rules {
+        match rule {
+            HeaderRule::Add { name, value } => {
+                remove_header(headers, name);
+                headers.insert(name.clone(), value.clone());
+            }
+            HeaderRule::Remove { name } => {
+                remove_header(headers, name);
+            }
+            HeaderRule::Rename { from, to } => {
+                if let Some(value) = remove_header(headers, from) {
+                    remove_header(headers, to);
+                    headers.insert(to.clone(), value);
+                }
+            }
+        }
+    }
+}
+
+/// Remove every header named `name` in any case, returning one of the values
+fn remove_header(headers: &mut HashMap<String, String>, name: &str) -> Option<String> {
+    let keys: Vec<String> = headers.keys().filter(|key| key.eq_ignore_ascii_case(name)).cloned().collect();
+    keys.into_iter().filter_map(|key| headers.remove(&key)).last()
+}
+
+/// Remove the field at `path`, descending into every element of arrays on the way
+fn strip_field(value: &mut serde_json::Value, path: &[&str]) -> usize {
+    match value {
+        serde_json::Value::Array(items) => items.iter_mut().map(|item| strip_field(item, path)).sum(),
+        serde_json::Value::Object(object) => match path {
+            [] => 0,
+            [last] => usize::from(object.remove(*last).is_some()),
+            [next, rest @ ..] => object.get_mut(*next).map_or(0, |child| strip_field(child, rest)),
+        },
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::networking::NetworkObservabilityMetadata;
+    use uuid::Uuid;
+
+    fn response(body: serde_json::Value) -> SecureResponse {
+        SecureResponse {
+            request_id: Uuid::new_v4(),
+            status_code: 200,
+            headers: HashMap::from([
+                ("Content-Length".to_string(), "0".to_string()),
+                ("X-Upstream-Version".to_string(), "7".to_string()),
+            ]),
+            body: Some(serde_json::to_vec(&body).unwrap()),
+            response_time_ms: 1,
+            cached: false,
+            security_validated: true,
+            served_from_fallback: false,
+            observability_metadata: NetworkObservabilityMetadata {
+                operation_id: "gateway-test".to_string(),
+                dns_resolution_time_ms: 0,
+                tcp_connection_time_ms: None,
+                tls_handshake_time_ms: None,
+                request_time_ms: 0,
+                response_time_ms: 1,
+                bytes_sent: 0,
+                bytes_received: 0,
+                interceptors_executed: vec![],
+            },
+        }
+    }
+
+    #[test]
+    fn test_path_rewrite_uses_capture_groups() {
+        let transforms = CompiledTransforms::compile(&RouteTransforms {
+            path_rewrite: Some(PathRewrite {
+                pattern: r"^/api/v1/users/(?P<id>[^/]+)/orders$".to_string(),
+                replacement: "/orders?customer=${id}".to_string(),
+            }),
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(transforms.rewrite_path("/api/v1/users/42/orders").unwrap(), "/orders?customer=42");
+        assert_eq!(transforms.rewrite_path("/api/v1/health").unwrap(), "/api/v1/health");
+
+        let escaping = CompiledTransforms::compile(&RouteTransforms {
+            path_rewrite: Some(PathRewrite { pattern: "^/".to_string(), replacement: "evil.com/".to_string() }),
+            ..Default::default()
+        })
+        .unwrap();
+        assert!(matches!(escaping.rewrite_path("/x"), Err(TransformError::InvalidRewrite { .. })));
+        assert!(CompiledTransforms::compile(&RouteTransforms {
+            path_rewrite: Some(PathRewrite { pattern: "(".to_string(), replacement: String::new() }),
+            ..Default::default()
+        })
+        .is_err());
+    }
+
+    #[test]
+    fn test_header_rules_inject_remove_and_rename() {
+        let transforms = CompiledTransforms::compile(&RouteTransforms {
+            request_headers: vec![
+                HeaderRule::Add { name: "X-Gateway".to_string(), value: "nodus".to_string() },
+                HeaderRule::Remove { name: "cookie".to_string() },
+                HeaderRule::Rename { from: "x-tenant".to_string(), to: "X-Upstream-Tenant".to_string() },
+            ],
+            ..Default::default()
+        })
+        .unwrap();
+        let mut headers = HashMap::from([
+            ("x-gateway".to_string(), "spoofed".to_string()),
+            ("Cookie".to_string(), "session=1".to_string()),
+            ("X-Tenant".to_string(), "acme".to_string()),
+        ]);
+
+        transforms.apply_request_headers(&mut headers);
+
+        assert_eq!(headers, HashMap::from([
+            ("X-Gateway".to_string(), "nodus".to_string()),
+            ("X-Upstream-Tenant".to_string(), "acme".to_string()),
+        ]));
+    }
+
+    #[test]
+    fn test_fields_above_caller_clearance_are_stripped() {
+        let transforms = CompiledTransforms::compile(&RouteTransforms {
+            response_headers: vec![HeaderRule::Remove { name: "x-upstream-version".to_string() }],
+            classified_fields: vec![
+                ClassifiedField { path: "ssn".to_string(), classification: ClassificationLevel::Secret, compartments: vec![] },
+                ClassifiedField {
+                    path: "accounts.balance".to_string(),
+                    classification: ClassificationLevel::Confidential,
+                    compartments: vec!["finance".to_string()],
+                },
+            ],
+            ..Default::default()
+        })
+        .unwrap();
+        let body = serde_json::json!({
+            "name": "Ada",
+            "ssn": "123-45-6789",
+            "accounts": [{ "id": 1, "balance": 10 }, { "id": 2, "balance": 20 }],
+        });
+
+        // Confidential without the finance compartment sees neither field
+        let mut filtered = response(body.clone());
+        let analyst = SecurityLabel::new(ClassificationLevel::Confidential, vec![]);
+        assert_eq!(transforms.apply_response(&mut filtered, &analyst).unwrap(), 3);
+        let value: serde_json::Value = serde_json::from_slice(filtered.body.as_ref().unwrap()).unwrap();
+        assert_eq!(value, serde_json::json!({ "name": "Ada", "accounts": [{ "id": 1 }, { "id": 2 }] }));
+        assert!(filtered.headers.is_empty());
+
+        // A Secret caller in the compartment dominates both labels and sees everything
+        let mut full = response(body.clone());
+        let officer = SecurityLabel::new(ClassificationLevel::Secret, vec!["finance".to_string()]);
+        assert_eq!(transforms.apply_response(&mut full, &officer).unwrap(), 0);
+        assert_eq!(serde_json::from_slice::<serde_json::Value>(full.body.as_ref().unwrap()).unwrap(), body);
+
+        // A body that should be filtered but can't be parsed is never passed through
+        let mut opaque = response(body);
+        opaque.body = Some(b"ssn=123-45-6789".to_vec());
+        assert!(transforms.apply_response(&mut opaque, &analyst).is_err());
+    }
+}
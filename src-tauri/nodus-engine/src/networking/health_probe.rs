@@ -0,0 +1,330 @@
+// src-tauri/src/networking/health_probe.rs
+// Health Probing - Periodic checks of load balancer targets through SecureNetworkTransport's probe path
+// Targets change state only after the configured number of consecutive results, so one blip doesn't flap routing
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use super::{HttpMethod, SecureNetworkTransport, SecureRequest, SecurityRequirements};
+use crate::multi_tenant::{HealthCheckConfig, LoadBalancerConfig};
+use crate::observability::{MetricsRegistry, QosClass};
+use crate::security::ClassificationLevel;
+
+/// Gauge per target, suffixed with the target: 1 while healthy, 0 while unhealthy
+pub const TARGET_HEALTHY_METRIC: &str = "load_balancer.target_healthy";
+/// Counter of healthy/unhealthy transitions across all targets
+pub const TARGET_TRANSITIONS_METRIC: &str = "load_balancer.target_transitions";
+/// Counter of failed probes across all targets
+pub const PROBE_FAILURES_METRIC: &str = "load_balancer.probe_failures";
+
+/// Identity probes are sent as
+const PROBER_USER: &str = "system:health_prober";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TargetState {
+    Healthy,
+    Unhealthy,
+}
+
+/// Current state of one target and the run of results since it last changed direction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TargetHealth {
+    pub state: TargetState,
+    pub consecutive_successes: u32,
+    pub consecutive_failures: u32,
+    pub last_checked: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+}
+
+impl Default for TargetHealth {
+    /// Targets start healthy, so traffic flows before the first probe completes
+    fn default() -> Self {
+        Self {
+            state: TargetState::Healthy,
+            consecutive_successes: 0,
+            consecutive_failures: 0,
+            last_checked: None,
+            last_error: None,
+        }
+    }
+}
+
+/// Probes load balancer targets and keeps the health map routing consults
+#[derive(Debug)]
+pub struct HealthProber {
+    config: HealthCheckConfig,
+    targets: RwLock<HashMap<String, TargetHealth>>,
+    transport: Arc<SecureNetworkTransport>,
+    metrics_registry: Option<Arc<MetricsRegistry>>,
+}
+
+impl HealthProber {
+    pub fn new(transport: Arc<SecureNetworkTransport>, config: HealthCheckConfig) -> Self {
+        Self {
+            config,
+            targets: RwLock::new(HashMap::new()),
+            transport,
+            metrics_registry: None,
+        }
+    }
+
+    /// Prober for every target of `load_balancer`, checked per its health check config
+    pub fn for_load_balancer(transport: Arc<SecureNetworkTransport>, load_balancer: &LoadBalancerConfig) -> Self {
+        let mut prober = Self::new(transport, load_balancer.health_check_config.clone());
+        prober.targets = RwLock::new(
+            load_balancer.targets.iter()
+                .map(|target| (target.clone(), TargetHealth::default()))
+                .collect(),
+        );
+        prober
+    }
+
+    /// Publish target health and transitions to `registry`
+    pub fn with_metrics_registry(mut self, registry: Arc<MetricsRegistry>) -> Self {
+        self.metrics_registry = Some(registry);
+        self
+    }
+
+    /// Start probing `target`, a base URL; a target already known keeps its state
+    pub async fn add_target(&self, target: &str) {
+        let mut targets = self.targets.write().await;
+        if !targets.contains_key(target) {
+            targets.insert(target.to_string(), TargetHealth::default());
+            drop(targets);
+            self.publish(target, TargetState::Healthy);
+        }
+    }
+
+    pub async fn remove_target(&self, target: &str) -> bool {
+        self.targets.write().await.remove(target).is_some()
+    }
+
+    /// Whether traffic may go to `target`; targets this prober doesn't check are assumed healthy
+    pub async fn is_healthy(&self, target: &str) -> bool {
+        self.targets
+            .read()
+            .await
+            .get(target)
+            .map_or(true, |health| health.state == TargetState::Healthy)
+    }
+
+    pub async fn health(&self, target: &str) -> Option<TargetHealth> {
+        self.targets.read().await.get(target).cloned()
+    }
+
+    /// Every probed target and its health
+    pub async fn snapshot(&self) -> HashMap<String, TargetHealth> {
+        self.targets.read().await.clone()
+    }
+
+    /// Apply one probe result to `target`, returning its state afterwards
+    ///
+    /// A healthy target turns unhealthy after `unhealthy_threshold` consecutive failures, and an
+    /// unhealthy one recovers after `healthy_threshold` consecutive successes. Results from
+    /// targets removed while a probe was in flight are ignored.
+    pub async fn record(&self, target: &str, result: Result<(), String>) -> Option<TargetState> {
+        let (previous, next) = {
+            let mut targets = self.targets.write().await;
+            let health = targets.get_mut(target)?;
+            health.last_checked = Some(Utc::now());
+            match &result {
+                Ok(()) => {
+                    health.consecutive_successes = health.consecutive_successes.saturating_add(1);
+                    health.consecutive_failures = 0;
+                    health.last_error = None;
+                }
+                Err(error) => {
+                    health.consecutive_failures = health.consecutive_failures.saturating_add(1);
+                    health.consecutive_successes = 0;
+                    health.last_error = Some(error.clone());
+                }
+            }
+
+            let previous = health.state;
+            health.state = match previous {
+                TargetState::Healthy if health.consecutive_failures >= self.config.unhealthy_threshold.max(1) => {
+                    TargetState::Unhealthy
+                }
+                TargetState::Unhealthy if health.consecutive_successes >= self.config.healthy_threshold.max(1) => {
+                    TargetState::Healthy
+                }
+                unchanged => unchanged,
+            };
+            (previous, health.state)
+        };
+
+        if let (Err(_), Some(registry)) = (&result, &self.metrics_registry) {
+            registry.increment_counter(PROBE_FAILURES_METRIC, 1);
+        }
+        if next != previous {
+            tracing::warn!(target_url = %target, "Load balancer target {:?} -> {:?}", previous, next);
+            if let Some(registry) = &self.metrics_registry {
+                registry.increment_counter(TARGET_TRANSITIONS_METRIC, 1);
+            }
+            self.publish(target, next);
+        }
+        Some(next)
+    }
+
+    /// Probe every target once, concurrently
+    pub async fn probe_all(&self) {
+        let targets: Vec<String> = self.targets.read().await.keys().cloned().collect();
+        let probes = targets.iter().map(|target| async move {
+            let result = self.probe(target).await;
+            self.record(target, result).await;
+        });
+        futures::future::join_all(probes).await;
+    }
+
+    /// Probe every `interval_seconds` until the runtime shuts down or the task is aborted
+    pub fn spawn(self: Arc<Self>) -> tokio::task::JoinHandle<()> {
+        let interval = Duration::from_secs(u64::from(self.config.interval_seconds.max(1)));
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                self.probe_all().await;
+            }
+        })
+    }
+
+    /// Request the health check path on `target`; any 2xx response is a success
+    ///
+    /// Sent on the transport's probe path, so a target's circuit breaker neither blocks
+    /// its probes nor is tripped by them.
+    async fn probe(&self, target: &str) -> Result<(), String> {
+        let url = self.probe_url(target)?;
+        let request = SecureRequest {
+            request_id: Uuid::new_v4(),
+            url,
+            method: HttpMethod::GET,
+            headers: HashMap::new(),
+            body: None,
+            classification: ClassificationLevel::Unclassified,
+            user_id: PROBER_USER.to_string(),
+            session_id: Uuid::nil(),
+            timeout_ms: Some(u64::from(self.config.timeout_seconds.max(1)) * 1000),
+            retry_policy: None,
+            cache_policy: None,
+            security_requirements: SecurityRequirements::default(),
+            idempotency_key: None,
+            qos: QosClass::Background,
+        };
+
+        match self.transport.probe(&request).await {
+            Ok(status) if (200..300).contains(&status) => Ok(()),
+            Ok(status) => Err(format!("status {}", status)),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// `target` with the configured protocol, port and path
+    fn probe_url(&self, target: &str) -> Result<String, String> {
+        let mut url = url::Url::parse(target).map_err(|e| format!("invalid target {}: {}", target, e))?;
+        let scheme = self.config.protocol.to_ascii_lowercase();
+        if matches!(scheme.as_str(), "http" | "https") && url.set_scheme(&scheme).is_err() {
+            return Err(format!("can't probe {} over {}", target, self.config.protocol));
+        }
+        if self.config.port != 0 && url.set_port(Some(self.config.port)).is_err() {
+            return Err(format!("can't probe {} on port {}", target, self.config.port));
+        }
+        url.set_path(&self.config.path);
+        Ok(url.to_string())
+    }
+
+    fn publish(&self, target: &str, state: TargetState) {
+        if let Some(registry) = &self.metrics_registry {
+            let healthy = if state == TargetState::Healthy { 1.0 } else { 0.0 };
+            registry.set_gauge(&format!("{}.{}", TARGET_HEALTHY_METRIC, target), healthy);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::license::LicenseManager;
+    use crate::multi_tenant::LoadBalancerType;
+
+    const TARGET: &str = "https://10.0.0.5";
+
+    async fn prober(healthy_threshold: u32, unhealthy_threshold: u32) -> (HealthProber, Arc<MetricsRegistry>) {
+        let license_manager = Arc::new(LicenseManager::new().await.unwrap());
+        let transport = Arc::new(SecureNetworkTransport::new(license_manager).await.unwrap());
+        let registry = Arc::new(MetricsRegistry::new());
+        let load_balancer = LoadBalancerConfig {
+            load_balancer_type: LoadBalancerType::ApplicationLoadBalancer,
+            health_check_config: HealthCheckConfig {
+                protocol: "HTTP".to_string(),
+                port: 8081,
+                path: "/healthz".to_string(),
+                interval_seconds: 10,
+                timeout_seconds: 2,
+                healthy_threshold,
+                unhealthy_threshold,
+            },
+            ssl_config: None,
+            targets: vec![TARGET.to_string()],
+        };
+        let prober = HealthProber::for_load_balancer(transport, &load_balancer).with_metrics_registry(registry.clone());
+        (prober, registry)
+    }
+
+    fn fail() -> Result<(), String> {
+        Err("connection refused".to_string())
+    }
+
+    #[tokio::test]
+    async fn test_flapping_target_changes_state_only_after_consecutive_counts() {
+        let (prober, _) = prober(2, 3).await;
+        prober.add_target(TARGET).await;
+        assert!(prober.is_healthy(TARGET).await);
+
+        // Failures interrupted by a success never reach the unhealthy threshold
+        for result in [fail(), fail(), Ok(()), fail(), fail(), Ok(())] {
+            assert_eq!(prober.record(TARGET, result).await, Some(TargetState::Healthy));
+        }
+
+        assert_eq!(prober.record(TARGET, fail()).await, Some(TargetState::Healthy));
+        assert_eq!(prober.record(TARGET, fail()).await, Some(TargetState::Healthy));
+        assert_eq!(prober.record(TARGET, fail()).await, Some(TargetState::Unhealthy));
+        assert!(!prober.is_healthy(TARGET).await);
+
+        // A lone success while unhealthy doesn't bring it back
+        for result in [Ok(()), fail(), Ok(()), fail()] {
+            assert_eq!(prober.record(TARGET, result).await, Some(TargetState::Unhealthy));
+        }
+        assert_eq!(prober.record(TARGET, Ok(())).await, Some(TargetState::Unhealthy));
+        assert_eq!(prober.record(TARGET, Ok(())).await, Some(TargetState::Healthy));
+
+        let health = prober.health(TARGET).await.unwrap();
+        assert_eq!(health.consecutive_successes, 2);
+        assert!(health.last_error.is_none());
+        assert_eq!(prober.record("https://unknown", Ok(())).await, None);
+        assert!(prober.is_healthy("https://unknown").await);
+    }
+
+    #[tokio::test]
+    async fn test_target_health_published_to_metrics() {
+        let (prober, registry) = prober(1, 2).await;
+        let gauge = format!("{}.{}", TARGET_HEALTHY_METRIC, TARGET);
+
+        prober.record(TARGET, fail()).await;
+        prober.record(TARGET, fail()).await;
+        let snapshot = registry.snapshot().await;
+        assert_eq!(snapshot.gauges.get(&gauge), Some(&0.0));
+        assert_eq!(snapshot.counters.get(PROBE_FAILURES_METRIC), Some(&2));
+        assert_eq!(snapshot.counters.get(TARGET_TRANSITIONS_METRIC), Some(&1));
+
+        prober.record(TARGET, Ok(())).await;
+        let snapshot = registry.snapshot().await;
+        assert_eq!(snapshot.gauges.get(&gauge), Some(&1.0));
+        assert_eq!(snapshot.counters.get(TARGET_TRANSITIONS_METRIC), Some(&2));
+        assert_eq!(prober.probe_url(TARGET).unwrap(), "http://10.0.0.5:8081/healthz");
+    }
+}
@@ -2,7 +2,8 @@
 // CDS Transport - Secure Networking with Automatic Observability
 // Replaces CDS.js and provides enterprise-grade network security
 
-use serde::{Deserialize, Serialize};
+use arc_swap::ArcSwap;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use std::collections::HashMap;
@@ -10,17 +11,32 @@ use uuid::Uuid;
 use reqwest::{Client, Response};
 use std::time::{Duration, Instant};
 
-use crate::observability::{ObservabilityContext, AutomaticInstrumentation};
+use crate::observability::{ObservabilityContext, AutomaticInstrumentation, QosClass, QosScheduler};
+use crate::observability::automatic_instrumentation::InstrumentationHooks;
+use crate::clock::{self, Clock};
 use crate::security::{SecurityLabel, ClassificationLevel};
 use crate::license::LicenseManager;
-use crate::state::AppState;
+use self::endpoint_matcher::EndpointPattern;
+use self::retry_budget::{RetryBudgetPolicy, RetryBudgetStatus, RetryBudgets};
+use self::timing::{PhaseTimings, TimingResolver};
 
 pub mod cds_transport;
+pub mod endpoint_matcher;
+pub mod gateway;
+pub mod gateway_transform;
+pub mod health_probe;
+pub mod network_audit;
 pub mod network_security;
 pub mod request_interceptor;
 pub mod response_cache;
+pub mod retry_budget;
+pub mod timing;
 
 pub use cds_transport::CDSTransport;
+pub use gateway::{GatewayRequest, GatewayRoute, GatewayRouter, RouteRateLimit, TenantHealthSource, TENANT_HEADER};
+pub use gateway_transform::{ClassifiedField, HeaderRule, PathRewrite, RouteTransforms};
+pub use health_probe::{HealthProber, TargetHealth, TargetState};
+pub use network_audit::NetworkAuditor;
 pub use network_security::NetworkSecurityManager;
 pub use request_interceptor::RequestInterceptor;
 pub use response_cache::ResponseCache;
@@ -29,8 +45,8 @@ pub use response_cache::ResponseCache;
 /// Replaces all direct fetch() calls with audited, policy-compliant networking
 #[derive(Debug)]
 pub struct SecureNetworkTransport {
-    // HTTP client with security configuration
-    http_client: Client,
+    // HTTP client with security configuration, swapped whole by `reconfigure`
+    http: ArcSwap<TransportClient>,
     
     // Automatic observability
     automatic_instrumentation: AutomaticInstrumentation,
@@ -46,7 +62,7 @@ pub struct SecureNetworkTransport {
     response_cache: ResponseCache,
     
     // Network policies
-    network_policies: Arc<RwLock<HashMap<String, NetworkPolicy>>>,
+    network_policies: Arc<RwLock<HashMap<String, RegisteredPolicy>>>,
     
     // Performance tracking
     request_metrics: Arc<RwLock<HashMap<String, RequestMetrics>>>,
@@ -56,6 +72,73 @@ pub struct SecureNetworkTransport {
     
     // Circuit breaker for external services
     circuit_breakers: Arc<RwLock<HashMap<String, NetworkCircuitBreaker>>>,
+    
+    // Per-endpoint retry allowance, throttling retries before the breaker trips
+    retry_budgets: RetryBudgets,
+
+    // Captures each request into a forensic envelope at its policy's audit level
+    network_auditor: NetworkAuditor,
+
+    // Time source for cache TTLs, retry budgets and circuit breakers
+    clock: Arc<dyn Clock>,
+}
+
+/// HTTP client built from the `NetworkConfig` it carries
+#[derive(Debug)]
+struct TransportClient {
+    client: Client,
+    config: NetworkConfig,
+}
+
+/// Client settings and default security requirements of the transport
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetworkConfig {
+    /// Whole-request timeout for requests that don't set `timeout_ms`
+    pub timeout_ms: u64,
+    pub tcp_keepalive_secs: u64,
+    pub pool_max_idle_per_host: usize,
+    /// Checked for endpoints no registered `NetworkPolicy` covers; its TLS version is
+    /// also the client's minimum
+    pub security_requirements: Option<SecurityRequirements>,
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            timeout_ms: 30_000,
+            tcp_keepalive_secs: 60,
+            pool_max_idle_per_host: 10,
+            security_requirements: None,
+        }
+    }
+}
+
+impl TransportClient {
+    fn build(config: NetworkConfig) -> Result<Self, NetworkError> {
+        let mut builder = Client::builder()
+            .timeout(Duration::from_millis(config.timeout_ms))
+            .danger_accept_invalid_certs(false) // Always validate certificates
+            .tcp_keepalive(Duration::from_secs(config.tcp_keepalive_secs))
+            .pool_max_idle_per_host(config.pool_max_idle_per_host)
+            .dns_resolver(Arc::new(TimingResolver)); // Reports DNS time per request
+        if let Some(version) = config.security_requirements.as_ref().and_then(|r| r.min_tls_version.as_deref()) {
+            builder = builder.min_tls_version(tls_version(version)?);
+        }
+        let client = builder.build()
+            .map_err(|e| NetworkError::ClientConfigurationError(e.to_string()))?;
+        Ok(Self { client, config })
+    }
+}
+
+fn tls_version(version: &str) -> Result<reqwest::tls::Version, NetworkError> {
+    match version {
+        "1.0" => Ok(reqwest::tls::Version::TLS_1_0),
+        "1.1" => Ok(reqwest::tls::Version::TLS_1_1),
+        "1.2" => Ok(reqwest::tls::Version::TLS_1_2),
+        "1.3" => Ok(reqwest::tls::Version::TLS_1_3),
+        other => Err(NetworkError::ClientConfigurationError(format!("unknown TLS version {}", other))),
+    }
 }
 
 /// Network request with security and observability metadata
@@ -73,6 +156,12 @@ pub struct SecureRequest {
     pub retry_policy: Option<RetryPolicy>,
     pub cache_policy: Option<CachePolicy>,
     pub security_requirements: SecurityRequirements,
+    /// Sent as `Idempotency-Key` on every attempt so the server can deduplicate retries
+    #[serde(default)]
+    pub idempotency_key: Option<String>,
+    /// Scheduling priority of the request's instrumented operation
+    #[serde(default)]
+    pub qos: QosClass,
 }
 
 /// HTTP methods for network requests
@@ -97,18 +186,26 @@ pub struct SecureResponse {
     pub response_time_ms: u64,
     pub cached: bool,
     pub security_validated: bool,
+    /// Produced by the endpoint's `NetworkFallback` rather than the requested endpoint
+    #[serde(default)]
+    pub served_from_fallback: bool,
     pub observability_metadata: NetworkObservabilityMetadata,
 }
 
 /// Network observability metadata
+///
+/// Phase times cover the final attempt and add up to `SecureResponse::response_time_ms`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NetworkObservabilityMetadata {
     pub operation_id: String,
-    pub dns_resolution_time_ms: u64,
-    pub tcp_connection_time_ms: u64,
-    pub tls_handshake_time_ms: u64,
-    pub request_time_ms: u64,
-    pub response_time_ms: u64,
+    pub dns_resolution_time_ms: u64, // Zero when a pooled connection was reused
+    // Not measured (reqwest 0.11 has no connect hook) and omitted; connection setup counts in request_time_ms
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tcp_connection_time_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls_handshake_time_ms: Option<u64>,
+    pub request_time_ms: u64,        // Connection setup, upload and server wait until headers arrive
+    pub response_time_ms: u64,       // Body transfer
     pub bytes_sent: u64,
     pub bytes_received: u64,
     pub interceptors_executed: Vec<String>,
@@ -145,12 +242,39 @@ pub struct NetworkPolicy {
     pub rate_limits: Option<RateLimit>,
     pub audit_level: AuditLevel,
     pub data_classification: ClassificationLevel,
+    /// Served when the endpoint's circuit breaker is open or a request exhausts its retries
+    #[serde(default)]
+    pub fallback: Option<NetworkFallback>,
 }
 
-/// Network audit levels
+/// Graceful-degradation response for an unavailable endpoint
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum NetworkFallback {
+    /// Fixed response, e.g. an empty list the UI can render
+    Stub {
+        status_code: u16,
+        #[serde(default)]
+        headers: HashMap<String, String>,
+        #[serde(default)]
+        body: Option<Vec<u8>>,
+    },
+    /// Send the request to another origin, keeping its path and query
+    AlternateEndpoint { base_url: String },
+}
+
+/// Network policy with its endpoint pattern compiled at registration
+#[derive(Debug, Clone)]
+struct RegisteredPolicy {
+    pattern: EndpointPattern,
+    policy: NetworkPolicy,
+}
+
+/// Network audit levels; endpoints without a policy are audited at `Basic`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AuditLevel {
     None,        // No audit logging
+    #[default]
     Basic,       // Request/response metadata only
     Full,        // Full request/response including body
     Headers,     // Headers and metadata only
@@ -173,6 +297,26 @@ pub struct RetryPolicy {
     pub backoff_multiplier: f64,
     pub retry_on_status: Vec<u16>,
     pub retry_on_timeout: bool,
+    /// Retry POST/PATCH even without an idempotency key (risks duplicate submissions)
+    #[serde(default)]
+    pub retry_non_idempotent: bool,
+    #[serde(default)]
+    pub jitter: RetryJitter,
+}
+
+/// How retry delays are randomized below their exponential bound
+///
+/// Without jitter, clients that failed together retry together and hit the upstream in waves.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetryJitter {
+    /// Exactly the exponential delay
+    None,
+    /// Anywhere between zero and the exponential delay
+    #[default]
+    Full,
+    /// Half the exponential delay plus up to the other half
+    Equal,
 }
 
 /// Cache policy for response caching
@@ -186,7 +330,7 @@ pub struct CachePolicy {
 }
 
 /// Request metrics for performance monitoring
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 struct RequestMetrics {
     pub total_requests: u64,
     pub successful_requests: u64,
@@ -196,6 +340,10 @@ struct RequestMetrics {
     pub p99_response_time_ms: f64,
     pub bytes_transferred: u64,
     pub cache_hit_ratio: f64,
+    #[serde(default)]
+    pub cache_lookups: u64,
+    #[serde(default)]
+    pub cache_hits: u64,
 }
 
 /// Circuit breaker for network endpoints
@@ -271,16 +419,10 @@ impl SecureNetworkTransport {
     /// Create new secure network transport
     pub async fn new(license_manager: Arc<LicenseManager>) -> Result<Self, NetworkError> {
         // Configure HTTP client with security settings
-        let http_client = Client::builder()
-            .timeout(Duration::from_secs(30))
-            .danger_accept_invalid_certs(false) // Always validate certificates
-            .tcp_keepalive(Duration::from_secs(60))
-            .pool_max_idle_per_host(10)
-            .build()
-            .map_err(|e| NetworkError::ClientConfigurationError(e.to_string()))?;
+        let http = TransportClient::build(NetworkConfig::default())?;
 
         Ok(Self {
-            http_client,
+            http: ArcSwap::from_pointee(http),
             automatic_instrumentation: AutomaticInstrumentation::new(license_manager.clone()),
             security_manager: NetworkSecurityManager::new(),
             request_interceptors: Arc::new(RwLock::new(Vec::new())),
@@ -290,16 +432,67 @@ impl SecureNetworkTransport {
             request_metrics: Arc::new(RwLock::new(HashMap::new())),
             license_manager,
             circuit_breakers: Arc::new(RwLock::new(HashMap::new())),
+            retry_budgets: RetryBudgets::default(),
+            network_auditor: NetworkAuditor::new()
+                .map_err(|e| NetworkError::ClientConfigurationError(format!("PII patterns: {}", e)))?,
+            clock: clock::system_clock(),
         })
     }
 
+    /// Replace the default per-endpoint retry budget
+    pub fn with_retry_budget(mut self, policy: RetryBudgetPolicy) -> Self {
+        self.retry_budgets = RetryBudgets::new(policy).with_clock(self.clock.clone());
+        self
+    }
+
+    /// Capture audited requests with `auditor`, e.g. to change the body size cap
+    pub fn with_network_auditor(mut self, auditor: NetworkAuditor) -> Self {
+        self.network_auditor = auditor;
+        self
+    }
+
+    /// Schedule requests in `scheduler`'s per-class slots, shared with the rest of the application
+    pub fn with_qos_scheduler(mut self, scheduler: Arc<QosScheduler>) -> Self {
+        self.automatic_instrumentation = self.automatic_instrumentation.with_qos_scheduler(scheduler);
+        self
+    }
+
+    /// Read time from `clock` instead of the system clock
+    ///
+    /// Request latency is still measured on the real clock.
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.response_cache = self.response_cache.with_clock(clock.clone());
+        self.retry_budgets = self.retry_budgets.with_clock(clock.clone());
+        self.clock = clock;
+        self
+    }
+
+    /// Switch to a client built from `config`
+    ///
+    /// Requests already sent finish on the old client; interceptors, cache, policies and
+    /// circuit breakers carry over. On error the current client stays in place.
+    pub fn reconfigure(&self, config: NetworkConfig) -> Result<(), NetworkError> {
+        self.http.store(Arc::new(TransportClient::build(config)?));
+        Ok(())
+    }
+
+    pub fn network_config(&self) -> NetworkConfig {
+        self.http.load().config.clone()
+    }
+
     /// Execute secure HTTP request with automatic observability (main method)
-    pub async fn request(
+    ///
+    /// `hooks` receives the audit and metrics events, normally the `AppState`, including one
+    /// network envelope holding what the endpoint policy's `AuditLevel` asks for.
+    pub async fn request<H>(
         &self,
         request: SecureRequest,
         context: NetworkContext,
-        app_state: &AppState,
-    ) -> Result<SecureResponse, NetworkError> {
+        hooks: &H,
+    ) -> Result<SecureResponse, NetworkError>
+    where
+        H: InstrumentationHooks + ?Sized,
+    {
         let start_time = Instant::now();
 
         // Create observability context
@@ -309,7 +502,12 @@ impl SecureNetworkTransport {
             request.classification.clone(),
             &context.user_id,
             context.session_id,
-        );
+        )
+        .with_qos(request.qos);
+        let audit_level = self.matching_policy(&request.url).await
+            .map(|policy| policy.audit_level)
+            .unwrap_or_default();
+        let audit = self.network_auditor.begin(audit_level, &request);
 
         // Execute with automatic observability
         let result = self.automatic_instrumentation.instrument_operation(
@@ -317,16 +515,41 @@ impl SecureNetworkTransport {
             async {
                 self.execute_secure_request(request, context).await
             },
-            app_state,
+            hooks,
         ).await;
 
         // Update request metrics
         let duration = start_time.elapsed();
         self.update_request_metrics(&obs_context.operation, duration, result.is_ok()).await;
 
+        if let Some(audit) = audit {
+            hooks.audit_network(self.network_auditor.finish(audit, &obs_context, &result)).await;
+        }
+
         result
     }
 
+    /// Send a health probe, returning the status code it got
+    ///
+    /// Checked against network policy and security requirements like any request, but sent
+    /// once, uncached and outside the circuit breaker: a probe has to see the target as it is
+    /// now, and must not trip or hold open the breaker that traffic relies on.
+    pub async fn probe(&self, request: &SecureRequest) -> Result<u16, NetworkError> {
+        self.validate_network_policy(request).await?;
+        self.security_manager.validate_request(request).await?;
+
+        let http = self.http.load_full();
+        let mut probe = http.client.request(request.method.to_reqwest_method(), &request.url);
+        for (key, value) in &request.headers {
+            probe = probe.header(key, value);
+        }
+        if let Some(timeout_ms) = request.timeout_ms {
+            probe = probe.timeout(Duration::from_millis(timeout_ms));
+        }
+        let response = probe.send().await.map_err(|e| NetworkError::RequestError(e.to_string()))?;
+        Ok(response.status().as_u16())
+    }
+
     /// Execute secure request with all security and observability features
     async fn execute_secure_request(
         &self,
@@ -338,7 +561,8 @@ impl SecureNetworkTransport {
 
         // Check circuit breaker
         if self.is_circuit_breaker_open(&request.url).await {
-            return Err(NetworkError::CircuitBreakerOpen(request.url.clone()));
+            let error = NetworkError::CircuitBreakerOpen(request.url.clone());
+            return self.serve_fallback(&request, &context, error).await;
         }
 
         // Check cache first
@@ -347,16 +571,29 @@ impl SecureNetworkTransport {
         }
 
         // Execute request interceptors
-        self.execute_request_interceptors(&mut request, &context).await?;
+        let mut interceptor_trace = Vec::new();
+        self.execute_request_interceptors(&mut request, &context, &mut interceptor_trace).await?;
 
         // Validate security requirements
         self.security_manager.validate_request(&request).await?;
 
         // Execute HTTP request with retries
-        let response = self.execute_with_retries(&request, &context).await?;
+        let (response, timings) = match self.execute_with_retries(&request, &context).await {
+            Ok(sent) => sent,
+            // The endpoint answered; the conflict is the caller's to resolve
+            Err(error @ NetworkError::PreconditionFailed { .. }) => {
+                self.update_circuit_breaker(&request.url, true).await;
+                return Err(error);
+            }
+            Err(error) => {
+                self.update_circuit_breaker(&request.url, false).await;
+                return self.serve_fallback(&request, &context, error).await;
+            }
+        };
 
         // Execute response interceptors
-        let mut secure_response = self.convert_to_secure_response(response, &request).await?;
+        let mut secure_response = self.convert_to_secure_response(response, timings, &request).await?;
+        secure_response.observability_metadata.interceptors_executed = interceptor_trace;
         self.execute_response_interceptors(&mut secure_response, &request, &context).await?;
 
         // Cache response if appropriate
@@ -368,20 +605,90 @@ impl SecureNetworkTransport {
         Ok(secure_response)
     }
 
+    /// Serve the endpoint's fallback in place of `error`, or return `error` when there is none
+    async fn serve_fallback(
+        &self,
+        request: &SecureRequest,
+        context: &NetworkContext,
+        error: NetworkError,
+    ) -> Result<SecureResponse, NetworkError> {
+        let fallback = match self.matching_policy(&request.url).await.and_then(|policy| policy.fallback) {
+            Some(fallback) => fallback,
+            None => return Err(error),
+        };
+        tracing::warn!("Serving fallback for {} {}: {}", request.method.as_str(), request.url, error);
+
+        match fallback {
+            NetworkFallback::Stub { status_code, headers, body } => Ok(SecureResponse {
+                request_id: request.request_id,
+                status_code,
+                headers,
+                body,
+                response_time_ms: 0,
+                cached: false,
+                security_validated: true,
+                served_from_fallback: true,
+                observability_metadata: NetworkObservabilityMetadata {
+                    operation_id: request.request_id.to_string(),
+                    dns_resolution_time_ms: 0,
+                    tcp_connection_time_ms: None,
+                    tls_handshake_time_ms: None,
+                    request_time_ms: 0,
+                    response_time_ms: 0,
+                    bytes_sent: 0,
+                    bytes_received: 0,
+                    interceptors_executed: Vec::new(),
+                },
+            }),
+            NetworkFallback::AlternateEndpoint { base_url } => {
+                let mut alternate = request.clone();
+                alternate.url = alternate_url(&request.url, &base_url)?;
+                if self.is_circuit_breaker_open(&alternate.url).await {
+                    return Err(error);
+                }
+                self.security_manager.validate_request(&alternate).await?;
+                // The alternate is held to its own endpoint's policy, not the failed one's
+                self.validate_network_policy(&alternate).await?;
+
+                // The alternate's own failure is reported as the original error
+                let sent = self.execute_with_retries(&alternate, context).await;
+                self.update_circuit_breaker(&alternate.url, sent.is_ok()).await;
+                let (response, timings) = sent.map_err(|_| error)?;
+
+                let mut secure_response = self.convert_to_secure_response(response, timings, &alternate).await?;
+                secure_response.served_from_fallback = true;
+                self.execute_response_interceptors(&mut secure_response, &alternate, context).await?;
+                Ok(secure_response)
+            }
+        }
+    }
+
     /// Execute HTTP request with retry logic
     async fn execute_with_retries(
         &self,
         request: &SecureRequest,
         _context: &NetworkContext,
-    ) -> Result<Response, NetworkError> {
+    ) -> Result<(Response, PhaseTimings), NetworkError> {
         let retry_policy = request.retry_policy.clone().unwrap_or_default();
         let mut attempt = 0;
 
+        // Every attempt goes through the client current when the request started
+        let http = self.http.load_full();
+
+        // Every retry spends from the endpoint's budget; once it's empty requests get one attempt
+        let endpoint = self.extract_domain(&request.url).unwrap_or_else(|_| request.url.clone());
+
+        // Replaying a request the server may already have processed is only safe when
+        // it can deduplicate it (or the caller explicitly accepts the risk)
+        let replay_safe = request.method.is_idempotent()
+            || request.idempotency_key.is_some()
+            || retry_policy.retry_non_idempotent;
+
         loop {
             attempt += 1;
 
             // Build HTTP request
-            let mut http_request = self.http_client
+            let mut http_request = http.client
                 .request(request.method.to_reqwest_method(), &request.url);
 
             // Add headers
@@ -389,6 +696,11 @@ impl SecureNetworkTransport {
                 http_request = http_request.header(key, value);
             }
 
+            // Same key on every attempt of this logical request
+            if let Some(key) = &request.idempotency_key {
+                http_request = http_request.header("Idempotency-Key", key);
+            }
+
             // Add body if present
             if let Some(body) = &request.body {
                 http_request = http_request.body(body.clone());
@@ -399,28 +711,57 @@ impl SecureNetworkTransport {
                 http_request = http_request.timeout(Duration::from_millis(timeout_ms));
             }
 
-            // Execute request
-            match http_request.send().await {
+            // Execute request, timing its phases
+            let (result, timings) = timing::send_timed(http_request).await;
+            match result {
                 Ok(response) => {
                     let status = response.status().as_u16();
+
+                    // A conditional request lost a race; replaying it would fail the same way
+                    if response.status() == reqwest::StatusCode::PRECONDITION_FAILED {
+                        return Err(NetworkError::PreconditionFailed {
+                            url: request.url.clone(),
+                            current_etag: response.headers()
+                                .get(reqwest::header::ETAG)
+                                .and_then(|value| value.to_str().ok())
+                                .map(str::to_string),
+                        });
+                    }
                     
                     // Check if response indicates success
                     if status < 500 && !retry_policy.retry_on_status.contains(&status) {
-                        return Ok(response);
+                        return Ok((response, timings));
                     }
 
                     // Check if we should retry
+                    if !replay_safe {
+                        return Err(NetworkError::HttpError(
+                            status,
+                            "Not retried: non-idempotent request has no idempotency key".to_string(),
+                        ));
+                    }
                     if attempt >= retry_policy.max_attempts {
                         return Err(NetworkError::HttpError(status, "Max retries exceeded".to_string()));
                     }
+                    if !self.retry_budgets.try_acquire(&endpoint).await {
+                        return Err(NetworkError::HttpError(
+                            status,
+                            format!("Not retried: retry budget for {} exhausted", endpoint),
+                        ));
+                    }
 
                     // Calculate delay and retry
                     let delay = self.calculate_retry_delay(attempt, &retry_policy);
                     tokio::time::sleep(delay).await;
                 },
                 Err(error) => {
-                    // Check if error is retriable
-                    if attempt >= retry_policy.max_attempts || !self.is_retriable_error(&error) {
+                    // Check if error is retriable; a failed connect never reached the server
+                    let retriable = self.is_retriable_error(&error)
+                        && (replay_safe || error.is_connect())
+                        && (retry_policy.retry_on_timeout || !error.is_timeout());
+                    if attempt >= retry_policy.max_attempts || !retriable
+                        || !self.retry_budgets.try_acquire(&endpoint).await
+                    {
                         return Err(NetworkError::RequestError(error.to_string()));
                     }
 
@@ -432,47 +773,60 @@ impl SecureNetworkTransport {
         }
     }
 
+    /// Most-specific registered policy matching `url`
+    async fn matching_policy(&self, url: &str) -> Option<NetworkPolicy> {
+        let policies = self.network_policies.read().await;
+        policies.values()
+            .filter(|registered| registered.pattern.matches(url))
+            .max_by(|a, b| a.pattern.cmp_specificity(&b.pattern))
+            .map(|registered| registered.policy.clone())
+    }
+
     /// Validate network policy for request
+    ///
+    /// Endpoints without a registered policy are held to the transport's default requirements, if any.
     async fn validate_network_policy(&self, request: &SecureRequest) -> Result<(), NetworkError> {
-        let policies = self.network_policies.read().await;
-        
-        for policy in policies.values() {
-            if self.matches_endpoint_pattern(&request.url, &policy.endpoint_pattern) {
-                // Check allowed methods
-                if !policy.allowed_methods.contains(&request.method) {
-                    return Err(NetworkError::PolicyViolation(
-                        format!("Method {} not allowed for endpoint {}", 
-                            request.method.as_str(), request.url)
-                    ));
-                }
+        if let Some(policy) = self.matching_policy(&request.url).await {
+            // Check allowed methods
+            if !policy.allowed_methods.contains(&request.method) {
+                return Err(NetworkError::PolicyViolation(
+                    format!("Method {} not allowed for endpoint {}", 
+                        request.method.as_str(), request.url)
+                ));
+            }
 
-                // Check security requirements
-                if policy.security_requirements.require_tls && !request.url.starts_with("https://") {
-                    return Err(NetworkError::SecurityViolation(
-                        "HTTPS required but request uses HTTP".to_string()
-                    ));
-                }
+            return self.check_security_requirements(&policy.security_requirements, request);
+        }
 
-                // Check domain restrictions
-                if let Some(allowed_domains) = &policy.security_requirements.allowed_domains {
-                    let domain = self.extract_domain(&request.url)?;
-                    if !allowed_domains.iter().any(|allowed| domain.contains(allowed)) {
-                        return Err(NetworkError::SecurityViolation(
-                            format!("Domain {} not in allowed list", domain)
-                        ));
-                    }
-                }
+        match &self.http.load().config.security_requirements {
+            Some(requirements) => self.check_security_requirements(requirements, request),
+            None => Ok(()),
+        }
+    }
 
-                if let Some(blocked_domains) = &policy.security_requirements.blocked_domains {
-                    let domain = self.extract_domain(&request.url)?;
-                    if blocked_domains.iter().any(|blocked| domain.contains(blocked)) {
-                        return Err(NetworkError::SecurityViolation(
-                            format!("Domain {} is blocked", domain)
-                        ));
-                    }
-                }
+    fn check_security_requirements(&self, requirements: &SecurityRequirements, request: &SecureRequest) -> Result<(), NetworkError> {
+        if requirements.require_tls && !request.url.starts_with("https://") {
+            return Err(NetworkError::SecurityViolation(
+                "HTTPS required but request uses HTTP".to_string()
+            ));
+        }
+
+        // Check domain restrictions
+        if let Some(allowed_domains) = &requirements.allowed_domains {
+            let domain = self.extract_domain(&request.url)?;
+            if !allowed_domains.iter().any(|allowed| endpoint_matcher::domain_matches(&domain, allowed)) {
+                return Err(NetworkError::SecurityViolation(
+                    format!("Domain {} not in allowed list", domain)
+                ));
+            }
+        }
 
-                break;
+        if let Some(blocked_domains) = &requirements.blocked_domains {
+            let domain = self.extract_domain(&request.url)?;
+            if blocked_domains.iter().any(|blocked| endpoint_matcher::domain_matches(&domain, blocked)) {
+                return Err(NetworkError::SecurityViolation(
+                    format!("Domain {} is blocked", domain)
+                ));
             }
         }
 
@@ -499,10 +853,12 @@ impl SecureNetworkTransport {
         interceptors.sort_by_key(|i| i.priority());
     }
 
-    /// Set network policy for endpoint pattern
-    pub async fn set_network_policy(&self, policy: NetworkPolicy) {
+    /// Set network policy for endpoint pattern (compiled once here; invalid patterns are rejected)
+    pub async fn set_network_policy(&self, policy: NetworkPolicy) -> Result<(), NetworkError> {
+        let pattern = EndpointPattern::compile(&policy.endpoint_pattern)?;
         let mut policies = self.network_policies.write().await;
-        policies.insert(policy.endpoint_pattern.clone(), policy);
+        policies.insert(policy.endpoint_pattern.clone(), RegisteredPolicy { pattern, policy });
+        Ok(())
     }
 
     /// Get network metrics for monitoring
@@ -512,26 +868,45 @@ impl SecureNetworkTransport {
 
     /// Get circuit breaker status
     pub async fn get_circuit_breaker_status(&self) -> HashMap<String, CircuitBreakerState> {
-        let breakers = self.circuit_breakers.read().await;
-        breakers.iter()
-            .map(|(url, breaker)| (url.clone(), breaker.state.clone()))
+        let mut breakers = self.circuit_breakers.write().await;
+        breakers.iter_mut()
+            .map(|(url, breaker)| {
+                self.half_open_if_cooled(breaker);
+                (url.clone(), breaker.state.clone())
+            })
             .collect()
     }
 
+    /// Retry budget of every endpoint that has retried
+    pub async fn get_retry_budget_status(&self) -> HashMap<String, RetryBudgetStatus> {
+        self.retry_budgets.status().await
+    }
+
     // Helper methods
 
+    /// Run request interceptors in order, appending each to `trace` as it runs
     async fn execute_request_interceptors(
         &self,
         request: &mut SecureRequest,
         context: &NetworkContext,
+        trace: &mut Vec<String>,
     ) -> Result<(), NetworkError> {
         let interceptors = self.request_interceptors.read().await;
         for interceptor in interceptors.iter() {
-            interceptor.intercept_request(request, context).await?;
+            let result = interceptor.intercept_request(request, context).await;
+            trace.push(interceptor_trace_entry("request", interceptor.name(), &result));
+            if let Err(e) = result {
+                tracing::warn!(
+                    "Request {} short-circuited by interceptor {} ({}): {}",
+                    request.request_id, interceptor.name(), trace.join(", "), e
+                );
+                return Err(e);
+            }
         }
         Ok(())
     }
 
+    /// Run response interceptors in order, appending each to the response's metadata
     async fn execute_response_interceptors(
         &self,
         response: &mut SecureResponse,
@@ -540,19 +915,34 @@ impl SecureNetworkTransport {
     ) -> Result<(), NetworkError> {
         let interceptors = self.response_interceptors.read().await;
         for interceptor in interceptors.iter() {
-            interceptor.intercept_response(response, request, context).await?;
+            let result = interceptor.intercept_response(response, request, context).await;
+            let entry = interceptor_trace_entry("response", interceptor.name(), &result);
+            response.observability_metadata.interceptors_executed.push(entry);
+            if let Err(e) = result {
+                tracing::warn!(
+                    "Response to {} short-circuited by interceptor {} ({}): {}",
+                    request.request_id,
+                    interceptor.name(),
+                    response.observability_metadata.interceptors_executed.join(", "),
+                    e
+                );
+                return Err(e);
+            }
         }
         Ok(())
     }
 
     async fn check_cache(&self, request: &SecureRequest) -> Result<Option<SecureResponse>, NetworkError> {
         if let Some(cache_policy) = &request.cache_policy {
-            let cache_key = cache_policy.cache_key.clone()
-                .unwrap_or_else(|| format!("{}:{}", request.method.as_str(), request.url));
+            let cache_key = self.response_cache
+                .request_key(request.method.as_str(), &request.url, &request.headers, cache_policy)
+                .await;
             
-            if let Some(cached) = self.response_cache.get(&cache_key).await {
-                return Ok(Some(cached));
-            }
+            let cached = self.response_cache.get(&cache_key).await;
+            let endpoint = format!("{} {}", request.method.as_str(), request.url);
+            self.record_cache_lookup(&endpoint, cached.is_some()).await;
+
+            return Ok(cached);
         }
         Ok(None)
     }
@@ -563,15 +953,17 @@ impl SecureNetworkTransport {
         response: &SecureResponse,
     ) -> Result<(), NetworkError> {
         if let Some(cache_policy) = &request.cache_policy {
-            if cache_policy.cache_on_status.contains(&response.status_code) {
-                let cache_key = cache_policy.cache_key.clone()
-                    .unwrap_or_else(|| format!("{}:{}", request.method.as_str(), request.url));
+            // Honors upstream Cache-Control when the policy asks for it
+            if let Some(ttl) = self.response_cache.ttl_for(response, cache_policy) {
+                // Learn upstream Vary before keying so the entry is partitioned correctly
+                self.response_cache
+                    .record_vary(request.method.as_str(), &request.url, cache_policy, response)
+                    .await;
+                let cache_key = self.response_cache
+                    .request_key(request.method.as_str(), &request.url, &request.headers, cache_policy)
+                    .await;
                 
-                self.response_cache.set(
-                    cache_key,
-                    response.clone(),
-                    Duration::from_secs(cache_policy.ttl_seconds),
-                ).await;
+                self.response_cache.set(cache_key, response.clone(), ttl).await;
             }
         }
         Ok(())
@@ -579,7 +971,8 @@ impl SecureNetworkTransport {
 
     async fn convert_to_secure_response(
         &self,
-        response: Response,
+        mut response: Response,
+        mut timings: PhaseTimings,
         request: &SecureRequest,
     ) -> Result<SecureResponse, NetworkError> {
         let status_code = response.status().as_u16();
@@ -592,10 +985,18 @@ impl SecureNetworkTransport {
             }
         }
 
-        // Read body
-        let body = response.bytes().await
+        // Validate content type and declared length before reading anything
+        let mut body_guard = ResponseBodyGuard::new(&request.security_requirements, &headers)?;
+
+        // Stream the body so an oversized response is aborted instead of buffered
+        let body_started = Instant::now();
+        while let Some(chunk) = response.chunk().await
             .map_err(|e| NetworkError::ResponseError(e.to_string()))?
-            .to_vec();
+        {
+            body_guard.push(&chunk)?;
+        }
+        let body = body_guard.into_body();
+        timings.response = body_started.elapsed();
 
         let bytes_received = body.len() as u64;
 
@@ -604,16 +1005,17 @@ impl SecureNetworkTransport {
             status_code,
             headers,
             body: Some(body),
-            response_time_ms: 0, // Will be calculated by caller
+            response_time_ms: timings.total().as_millis() as u64,
             cached: false,
             security_validated: true,
+            served_from_fallback: false,
             observability_metadata: NetworkObservabilityMetadata {
                 operation_id: request.request_id.to_string(),
-                dns_resolution_time_ms: 0,
-                tcp_connection_time_ms: 0,
-                tls_handshake_time_ms: 0,
-                request_time_ms: 0,
-                response_time_ms: 0,
+                dns_resolution_time_ms: timings.dns_resolution.as_millis() as u64,
+                tcp_connection_time_ms: None,
+                tls_handshake_time_ms: None,
+                request_time_ms: timings.request.as_millis() as u64,
+                response_time_ms: timings.response.as_millis() as u64,
                 bytes_sent: request.body.as_ref().map(|b| b.len()).unwrap_or(0) as u64,
                 bytes_received,
                 interceptors_executed: Vec::new(),
@@ -628,16 +1030,7 @@ impl SecureNetworkTransport {
         success: bool,
     ) {
         let mut metrics = self.request_metrics.write().await;
-        let metric = metrics.entry(endpoint.to_string()).or_insert(RequestMetrics {
-            total_requests: 0,
-            successful_requests: 0,
-            failed_requests: 0,
-            avg_response_time_ms: 0.0,
-            p95_response_time_ms: 0.0,
-            p99_response_time_ms: 0.0,
-            bytes_transferred: 0,
-            cache_hit_ratio: 0.0,
-        });
+        let metric = metrics.entry(endpoint.to_string()).or_default();
 
         metric.total_requests += 1;
         if success {
@@ -652,15 +1045,54 @@ impl SecureNetworkTransport {
         metric.p99_response_time_ms = metric.p99_response_time_ms.max(duration_ms);
     }
 
+    async fn record_cache_lookup(&self, endpoint: &str, hit: bool) {
+        let mut metrics = self.request_metrics.write().await;
+        let metric = metrics.entry(endpoint.to_string()).or_default();
+
+        metric.cache_lookups += 1;
+        if hit {
+            metric.cache_hits += 1;
+        }
+        metric.cache_hit_ratio = metric.cache_hits as f64 / metric.cache_lookups as f64;
+    }
+
     async fn is_circuit_breaker_open(&self, url: &str) -> bool {
-        let breakers = self.circuit_breakers.read().await;
-        if let Some(breaker) = breakers.get(url) {
+        let mut breakers = self.circuit_breakers.write().await;
+        if let Some(breaker) = breakers.get_mut(url) {
+            self.half_open_if_cooled(breaker);
             breaker.state == CircuitBreakerState::Open
         } else {
             false
         }
     }
 
+    /// Endpoints whose circuit breaker is currently open
+    pub async fn open_circuit_breakers(&self) -> Vec<String> {
+        let mut breakers = self.circuit_breakers.write().await;
+        let mut open: Vec<String> = breakers.iter_mut()
+            .filter_map(|(endpoint, breaker)| {
+                self.half_open_if_cooled(breaker);
+                (breaker.state == CircuitBreakerState::Open).then(|| endpoint.clone())
+            })
+            .collect();
+        open.sort();
+        open
+    }
+
+    /// Move an open breaker to half-open once `timeout_seconds` have passed on the clock
+    /// since its last failure, so the next request tests the endpoint
+    fn half_open_if_cooled(&self, breaker: &mut NetworkCircuitBreaker) {
+        if breaker.state != CircuitBreakerState::Open {
+            return;
+        }
+        let cooled = breaker.last_failure_time.map_or(true, |failed_at| {
+            self.clock.instant().saturating_duration_since(failed_at) >= Duration::from_secs(breaker.timeout_seconds)
+        });
+        if cooled {
+            breaker.state = CircuitBreakerState::HalfOpen;
+        }
+    }
+
     async fn update_circuit_breaker(&self, url: &str, success: bool) {
         let mut breakers = self.circuit_breakers.write().await;
         let breaker = breakers.entry(url.to_string()).or_insert(NetworkCircuitBreaker {
@@ -679,19 +1111,15 @@ impl SecureNetworkTransport {
             }
         } else {
             breaker.current_failures += 1;
-            breaker.last_failure_time = Some(Instant::now());
+            breaker.last_failure_time = Some(self.clock.instant());
             
-            if breaker.current_failures >= breaker.failure_threshold {
+            // A failed trial request reopens a half-open breaker straight away
+            if breaker.state == CircuitBreakerState::HalfOpen || breaker.current_failures >= breaker.failure_threshold {
                 breaker.state = CircuitBreakerState::Open;
             }
         }
     }
 
-    fn matches_endpoint_pattern(&self, url: &str, pattern: &str) -> bool {
-        // Simple pattern matching (in production, use regex)
-        url.contains(pattern) || pattern == "*"
-    }
-
     fn extract_domain(&self, url: &str) -> Result<String, NetworkError> {
         url::Url::parse(url)
             .map_err(|e| NetworkError::InvalidUrl(e.to_string()))?
@@ -705,14 +1133,130 @@ impl SecureNetworkTransport {
     }
 
     fn calculate_retry_delay(&self, attempt: u32, retry_policy: &RetryPolicy) -> Duration {
-        let delay_ms = (retry_policy.base_delay_ms as f64 * 
-            retry_policy.backoff_multiplier.powi(attempt as i32 - 1)) as u64;
-        
-        let capped_delay = delay_ms.min(retry_policy.max_delay_ms);
-        Duration::from_millis(capped_delay)
+        retry_policy.retry_delay(attempt, &mut rand::thread_rng())
+    }
+}
+
+impl SecureRequest {
+    /// Attach an idempotency key, allowing retries of POST/PATCH
+    pub fn with_idempotency_key(mut self, key: impl Into<String>) -> Self {
+        self.idempotency_key = Some(key.into());
+        self
+    }
+
+    /// Schedule the request as `qos` instead of Interactive
+    pub fn with_qos(mut self, qos: QosClass) -> Self {
+        self.qos = qos;
+        self
+    }
+
+    /// Serialize `value` as the JSON body and set `Content-Type: application/json`
+    pub fn with_json_body<T: Serialize + ?Sized>(self, value: &T) -> Result<Self, NetworkError> {
+        let body = serde_json::to_vec(value)
+            .map_err(|e| NetworkError::SerializationError(e.to_string()))?;
+        Ok(self.with_body(body, "application/json"))
+    }
+
+    /// URL-encode `fields` as the body and set `Content-Type: application/x-www-form-urlencoded`
+    pub fn with_form_body<K: AsRef<str>, V: AsRef<str>>(self, fields: &[(K, V)]) -> Self {
+        let body = url::form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(fields.iter().map(|(key, value)| (key.as_ref(), value.as_ref())))
+            .finish();
+        self.with_body(body.into_bytes(), "application/x-www-form-urlencoded")
+    }
+
+    /// Raw body with an explicit content type, replacing any existing `Content-Type` header
+    pub fn with_body(mut self, body: Vec<u8>, content_type: &str) -> Self {
+        self.set_header("Content-Type", content_type);
+        self.body = Some(body);
+        self
+    }
+
+    /// Apply only if the resource's current ETag is `etag`; otherwise fails with `PreconditionFailed`
+    pub fn with_if_match(mut self, etag: impl Into<String>) -> Self {
+        self.set_header("If-Match", etag);
+        self
+    }
+
+    /// Apply only if the resource's current ETag is not `etag`; `"*"` applies only if it doesn't exist
+    pub fn with_if_none_match(mut self, etag: impl Into<String>) -> Self {
+        self.set_header("If-None-Match", etag);
+        self
+    }
+
+    /// `If-Match` on the entity version the change was based on, so a newer remote version isn't overwritten
+    pub fn with_expected_version(self, version: i64) -> Self {
+        self.with_if_match(version_etag(version))
+    }
+
+    /// Set `name`, replacing any existing header of that name in another case
+    fn set_header(&mut self, name: &str, value: impl Into<String>) {
+        self.headers.retain(|key, _| !key.eq_ignore_ascii_case(name));
+        self.headers.insert(name.to_string(), value.into());
     }
 }
 
+/// Strong ETag for an entity version
+pub fn version_etag(version: i64) -> String {
+    format!("\"{}\"", version)
+}
+
+/// Entity version from an ETag made by `version_etag`; weak ETags are accepted
+pub fn version_from_etag(etag: &str) -> Option<i64> {
+    let etag = etag.trim();
+    etag.strip_prefix("W/").unwrap_or(etag)
+        .strip_prefix('"')?
+        .strip_suffix('"')?
+        .parse()
+        .ok()
+}
+
+impl SecureResponse {
+    /// Media type from the `Content-Type` header, lowercased and without parameters
+    pub fn content_type(&self) -> Option<String> {
+        self.headers.iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case("content-type"))
+            .and_then(|(_, value)| value.split(';').next())
+            .map(|value| value.trim().to_ascii_lowercase())
+    }
+
+    /// The `ETag` header, if the server sent one
+    pub fn etag(&self) -> Option<&str> {
+        self.headers.iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case("etag"))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Deserialize a JSON body, rejecting responses that aren't declared as JSON
+    pub fn json_response<T: DeserializeOwned>(&self) -> Result<T, NetworkError> {
+        let content_type = self.content_type().unwrap_or_default();
+        if content_type != "application/json" && !content_type.ends_with("+json") {
+            return Err(NetworkError::ResponseError(format!(
+                "Expected a JSON response, got content type {:?}", content_type
+            )));
+        }
+
+        let body = self.body.as_deref().unwrap_or_default();
+        serde_json::from_slice(body)
+            .map_err(|e| NetworkError::SerializationError(e.to_string()))
+    }
+}
+
+/// `url` with its scheme, host and port replaced by those of `base_url`
+fn alternate_url(url: &str, base_url: &str) -> Result<String, NetworkError> {
+    let original = url::Url::parse(url).map_err(|e| NetworkError::InvalidUrl(e.to_string()))?;
+    let mut alternate = url::Url::parse(base_url).map_err(|e| NetworkError::InvalidUrl(e.to_string()))?;
+    alternate.set_path(original.path());
+    alternate.set_query(original.query());
+    Ok(alternate.to_string())
+}
+
+/// Audit entry for one interceptor run, e.g. `request:auth_header:ok`
+fn interceptor_trace_entry(phase: &str, name: &str, result: &Result<(), NetworkError>) -> String {
+    let outcome = if result.is_ok() { "ok" } else { "short_circuited" };
+    format!("{}:{}:{}", phase, name, outcome)
+}
+
 impl HttpMethod {
     fn as_str(&self) -> &str {
         match self {
@@ -726,6 +1270,11 @@ impl HttpMethod {
         }
     }
 
+    /// Whether repeating the request has no effect beyond the first (RFC 9110 semantics)
+    fn is_idempotent(&self) -> bool {
+        !matches!(self, HttpMethod::POST | HttpMethod::PATCH)
+    }
+
     fn to_reqwest_method(&self) -> reqwest::Method {
         match self {
             HttpMethod::GET => reqwest::Method::GET,
@@ -739,6 +1288,21 @@ impl HttpMethod {
     }
 }
 
+impl RetryPolicy {
+    /// Delay before retry number `attempt` (1-based), jittered with `rng`; never above `max_delay_ms`
+    pub fn retry_delay<R: rand::Rng + ?Sized>(&self, attempt: u32, rng: &mut R) -> Duration {
+        let bound_ms = (self.base_delay_ms as f64 * self.backoff_multiplier.powi(attempt as i32 - 1)) as u64;
+        let bound_ms = bound_ms.min(self.max_delay_ms);
+
+        let delay_ms = match self.jitter {
+            RetryJitter::None => bound_ms,
+            RetryJitter::Full => rng.gen_range(0..=bound_ms),
+            RetryJitter::Equal => bound_ms / 2 + rng.gen_range(0..=bound_ms - bound_ms / 2),
+        };
+        Duration::from_millis(delay_ms.min(self.max_delay_ms))
+    }
+}
+
 impl Default for RetryPolicy {
     fn default() -> Self {
         Self {
@@ -748,6 +1312,8 @@ impl Default for RetryPolicy {
             backoff_multiplier: 2.0,
             retry_on_status: vec![500, 502, 503, 504],
             retry_on_timeout: true,
+            retry_non_idempotent: false,
+            jitter: RetryJitter::Full,
         }
     }
 }
@@ -767,6 +1333,74 @@ impl Default for SecurityRequirements {
     }
 }
 
+/// Enforces `SecurityRequirements` response limits while a body is streamed in
+#[derive(Debug)]
+struct ResponseBodyGuard {
+    max_bytes: Option<u64>,
+    body: Vec<u8>,
+}
+
+impl ResponseBodyGuard {
+    /// Check `Content-Type` and the declared `Content-Length` before any body is read
+    fn new(
+        requirements: &SecurityRequirements,
+        headers: &HashMap<String, String>,
+    ) -> Result<Self, NetworkError> {
+        if let Some(allowed) = &requirements.content_type_validation {
+            let media_type = headers.get("content-type")
+                .and_then(|value| value.split(';').next())
+                .map(|value| value.trim().to_ascii_lowercase())
+                .ok_or_else(|| NetworkError::SecurityViolation("Response has no Content-Type".to_string()))?;
+
+            let permitted = allowed.iter().any(|allowed_type| {
+                let allowed_type = allowed_type.to_ascii_lowercase();
+                match allowed_type.strip_suffix("/*") {
+                    Some(top_level) => media_type.split('/').next() == Some(top_level),
+                    None => media_type == allowed_type,
+                }
+            });
+            if !permitted {
+                return Err(NetworkError::SecurityViolation(format!(
+                    "Content type {} not permitted", media_type
+                )));
+            }
+        }
+
+        let max_bytes = requirements.max_response_size_bytes;
+        if let (Some(max), Some(declared)) = (
+            max_bytes,
+            headers.get("content-length").and_then(|value| value.trim().parse::<u64>().ok()),
+        ) {
+            if declared > max {
+                return Err(NetworkError::SecurityViolation(format!(
+                    "Declared response size {} exceeds limit of {} bytes", declared, max
+                )));
+            }
+        }
+
+        Ok(Self { max_bytes, body: Vec::new() })
+    }
+
+    /// Append a chunk, failing as soon as the running total passes the limit
+    fn push(&mut self, chunk: &[u8]) -> Result<(), NetworkError> {
+        let received = (self.body.len() + chunk.len()) as u64;
+        if let Some(max) = self.max_bytes {
+            if received > max {
+                return Err(NetworkError::SecurityViolation(format!(
+                    "Response body exceeds limit of {} bytes", max
+                )));
+            }
+        }
+
+        self.body.extend_from_slice(chunk);
+        Ok(())
+    }
+
+    fn into_body(self) -> Vec<u8> {
+        self.body
+    }
+}
+
 /// Network transport errors
 #[derive(Debug, thiserror::Error)]
 pub enum NetworkError {
@@ -778,6 +1412,10 @@ pub enum NetworkError {
     
     #[error("HTTP error {0}: {1}")]
     HttpError(u16, String),
+
+    /// `412 Precondition Failed`: the resource changed since the ETag or version the request was based on
+    #[error("Precondition failed for {url} (current ETag: {current_etag:?})")]
+    PreconditionFailed { url: String, current_etag: Option<String> },
     
     #[error("Request error: {0}")]
     RequestError(String),
@@ -799,12 +1437,26 @@ pub enum NetworkError {
     
     #[error("Interceptor error: {0}")]
     InterceptorError(String),
+    
+    #[error("Serialization error: {0}")]
+    SerializationError(String),
+}
+
+impl From<crate::observability::InstrumentationError> for NetworkError {
+    fn from(error: crate::observability::InstrumentationError) -> Self {
+        match error {
+            crate::observability::InstrumentationError::CircuitOpen(open) => Self::CircuitBreakerOpen(open.operation),
+            crate::observability::InstrumentationError::Qos(qos) => Self::RequestError(qos.to_string()),
+            audit => Self::SecurityViolation(audit.to_string()),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::license::LicenseManager;
+    use crate::observability::forensic_logger::ForensicError;
 
     #[tokio::test]
     async fn test_secure_network_transport_creation() {
@@ -834,4 +1486,805 @@ mod tests {
         assert_eq!(policy.base_delay_ms, 1000);
         assert!(policy.retry_on_status.contains(&500));
     }
+
+    #[test]
+    fn test_jittered_retry_delay_varies_within_bound() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut policy = RetryPolicy {
+            base_delay_ms: 100,
+            max_delay_ms: 1000,
+            ..Default::default()
+        };
+        let mut rng = StdRng::seed_from_u64(7);
+
+        // Third attempt: bound is 100 * 2^2 = 400ms
+        let delays: Vec<Duration> = (0..50).map(|_| policy.retry_delay(3, &mut rng)).collect();
+        assert!(delays.iter().all(|delay| *delay <= Duration::from_millis(400)));
+        assert!(delays.iter().any(|delay| *delay != delays[0]));
+
+        // Past the cap the bound is max_delay_ms, jitter included
+        assert!((0..50).all(|_| policy.retry_delay(12, &mut rng) <= Duration::from_millis(1000)));
+
+        policy.jitter = RetryJitter::Equal;
+        assert!((0..50).all(|_| {
+            let delay = policy.retry_delay(3, &mut rng);
+            delay >= Duration::from_millis(200) && delay <= Duration::from_millis(400)
+        }));
+
+        policy.jitter = RetryJitter::None;
+        assert_eq!(policy.retry_delay(3, &mut rng), Duration::from_millis(400));
+    }
+
+    #[test]
+    fn test_oversized_body_rejected() {
+        let requirements = SecurityRequirements {
+            max_response_size_bytes: Some(16),
+            ..Default::default()
+        };
+
+        // Fast path: declared length over the limit is rejected before reading
+        let declared: HashMap<String, String> = [("content-length".to_string(), "1024".to_string())].into();
+        assert!(matches!(
+            ResponseBodyGuard::new(&requirements, &declared),
+            Err(NetworkError::SecurityViolation(_))
+        ));
+
+        // Streamed: no Content-Length, abort once the running total passes the limit
+        let mut guard = ResponseBodyGuard::new(&requirements, &HashMap::new()).unwrap();
+        assert!(guard.push(&[0u8; 10]).is_ok());
+        assert!(matches!(guard.push(&[0u8; 10]), Err(NetworkError::SecurityViolation(_))));
+    }
+
+    #[test]
+    fn test_disallowed_content_type_rejected() {
+        let requirements = SecurityRequirements {
+            content_type_validation: Some(vec!["application/json".to_string(), "text/*".to_string()]),
+            ..Default::default()
+        };
+        let with_type = |content_type: &str| -> HashMap<String, String> {
+            [("content-type".to_string(), content_type.to_string())].into()
+        };
+
+        assert!(ResponseBodyGuard::new(&requirements, &with_type("application/json; charset=utf-8")).is_ok());
+        assert!(ResponseBodyGuard::new(&requirements, &with_type("text/plain")).is_ok());
+        assert!(matches!(
+            ResponseBodyGuard::new(&requirements, &with_type("application/octet-stream")),
+            Err(NetworkError::SecurityViolation(_))
+        ));
+        assert!(ResponseBodyGuard::new(&requirements, &HashMap::new()).is_err());
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Reading {
+        sensor: String,
+        values: Vec<f64>,
+    }
+
+    fn test_request() -> SecureRequest {
+        SecureRequest {
+            request_id: Uuid::new_v4(),
+            url: "https://api.example.com/readings".to_string(),
+            method: HttpMethod::POST,
+            headers: [("content-type".to_string(), "text/plain".to_string())].into(),
+            body: None,
+            classification: ClassificationLevel::Internal,
+            user_id: "test-user".to_string(),
+            session_id: Uuid::new_v4(),
+            timeout_ms: None,
+            retry_policy: None,
+            cache_policy: None,
+            security_requirements: SecurityRequirements::default(),
+            idempotency_key: None,
+            qos: QosClass::default(),
+        }
+    }
+
+    /// Mock transport that echoes the request body back with its content type
+    fn echo_transport(request: &SecureRequest) -> SecureResponse {
+        let content_type = request.headers.get("Content-Type").cloned().unwrap_or_default();
+        SecureResponse {
+            request_id: request.request_id,
+            status_code: 200,
+            headers: [("content-type".to_string(), format!("{}; charset=utf-8", content_type))].into(),
+            body: request.body.clone(),
+            response_time_ms: 0,
+            cached: false,
+            security_validated: true,
+            served_from_fallback: false,
+            observability_metadata: NetworkObservabilityMetadata {
+                operation_id: request.request_id.to_string(),
+                dns_resolution_time_ms: 0,
+                tcp_connection_time_ms: None,
+                tls_handshake_time_ms: None,
+                request_time_ms: 0,
+                response_time_ms: 0,
+                bytes_sent: 0,
+                bytes_received: 0,
+                interceptors_executed: Vec::new(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_json_body_round_trip() {
+        let reading = Reading { sensor: "north-3".to_string(), values: vec![1.5, 2.25] };
+        let request = test_request().with_json_body(&reading).unwrap();
+
+        // The builder replaces the existing header regardless of its case
+        assert_eq!(request.headers.len(), 1);
+        assert_eq!(request.headers["Content-Type"], "application/json");
+
+        let response = echo_transport(&request);
+        assert_eq!(response.json_response::<Reading>().unwrap(), reading);
+    }
+
+    #[test]
+    fn test_form_body_and_content_type_mismatch() {
+        let request = test_request().with_form_body(&[("sensor", "north 3"), ("unit", "°C")]);
+        assert_eq!(request.headers["Content-Type"], "application/x-www-form-urlencoded");
+        assert_eq!(request.body.as_deref(), Some("sensor=north+3&unit=%C2%B0C".as_bytes()));
+
+        // A form response isn't JSON, even if the body happens to parse
+        let response = echo_transport(&request);
+        assert!(matches!(response.json_response::<Reading>(), Err(NetworkError::ResponseError(_))));
+
+        // Raw byte path still available
+        let raw = test_request().with_body(b"{}".to_vec(), "application/vnd.nodus+json");
+        assert!(matches!(
+            echo_transport(&raw).json_response::<Reading>(),
+            Err(NetworkError::SerializationError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_phase_timings_sum_to_total() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        // Local server that takes 50ms to answer, then 30ms to finish the body
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut buf = [0u8; 1024];
+            let _ = socket.read(&mut buf).await.unwrap();
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            socket.write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 10\r\n\r\nhello").await.unwrap();
+            socket.flush().await.unwrap();
+            tokio::time::sleep(Duration::from_millis(30)).await;
+            socket.write_all(b"world").await.unwrap();
+        });
+
+        let license_manager = Arc::new(LicenseManager::new().await.unwrap());
+        let transport = SecureNetworkTransport::new(license_manager).await.unwrap();
+        let mut request = test_request();
+        request.method = HttpMethod::GET;
+        request.url = format!("http://localhost:{}/status", port);
+        request.headers.clear();
+        let context = NetworkContext {
+            user_id: "test-user".to_string(),
+            session_id: request.session_id,
+            security_label: SecurityLabel::new(ClassificationLevel::Internal, vec![]),
+            tenant_id: None,
+            source_ip: None,
+            user_agent: None,
+        };
+
+        let started = Instant::now();
+        let (response, timings) = transport.execute_with_retries(&request, &context).await.unwrap();
+        let response = transport.convert_to_secure_response(response, timings, &request).await.unwrap();
+        let elapsed = started.elapsed().as_millis() as u64;
+
+        assert_eq!(response.body.as_deref(), Some(b"helloworld".as_ref()));
+        let metadata = &response.observability_metadata;
+        assert!(metadata.request_time_ms >= 50);
+        assert!(metadata.response_time_ms >= 25);
+
+        assert_eq!((metadata.tcp_connection_time_ms, metadata.tls_handshake_time_ms), (None, None));
+        let phases = metadata.dns_resolution_time_ms
+            + metadata.request_time_ms
+            + metadata.response_time_ms;
+        assert!(phases.abs_diff(response.response_time_ms) <= 2);
+        assert!(response.response_time_ms <= elapsed);
+        assert!(elapsed - response.response_time_ms <= 10);
+    }
+
+    struct HeaderStamp;
+
+    #[async_trait::async_trait]
+    impl RequestInterceptor for HeaderStamp {
+        async fn intercept_request(
+            &self,
+            request: &mut SecureRequest,
+            _context: &NetworkContext,
+        ) -> Result<(), NetworkError> {
+            request.headers.insert("X-Stamped".to_string(), "1".to_string());
+            Ok(())
+        }
+
+        fn name(&self) -> &str {
+            "header_stamp"
+        }
+    }
+
+    struct StatusAudit;
+
+    #[async_trait::async_trait]
+    impl ResponseInterceptor for StatusAudit {
+        async fn intercept_response(
+            &self,
+            response: &mut SecureResponse,
+            _request: &SecureRequest,
+            _context: &NetworkContext,
+        ) -> Result<(), NetworkError> {
+            if response.status_code >= 400 {
+                return Err(NetworkError::InterceptorError("error status".to_string()));
+            }
+            Ok(())
+        }
+
+        fn name(&self) -> &str {
+            "status_audit"
+        }
+    }
+
+    #[tokio::test]
+    async fn test_interceptor_names_recorded_in_metadata() {
+        let license_manager = Arc::new(LicenseManager::new().await.unwrap());
+        let transport = SecureNetworkTransport::new(license_manager).await.unwrap();
+        transport.add_request_interceptor(HeaderStamp).await;
+        transport.add_response_interceptor(StatusAudit).await;
+
+        let mut request = test_request();
+        let context = NetworkContext {
+            user_id: "test-user".to_string(),
+            session_id: request.session_id,
+            security_label: SecurityLabel::new(ClassificationLevel::Internal, vec![]),
+            tenant_id: None,
+            source_ip: None,
+            user_agent: None,
+        };
+
+        // Same sequence as execute_secure_request, with the echo transport standing in for HTTP
+        let mut trace = Vec::new();
+        transport.execute_request_interceptors(&mut request, &context, &mut trace).await.unwrap();
+        let mut response = echo_transport(&request);
+        response.observability_metadata.interceptors_executed = trace;
+        transport.execute_response_interceptors(&mut response, &request, &context).await.unwrap();
+
+        assert_eq!(request.headers["X-Stamped"], "1");
+        assert_eq!(
+            response.observability_metadata.interceptors_executed,
+            vec!["request:header_stamp:ok", "response:status_audit:ok"]
+        );
+
+        // A failing interceptor is recorded as having short-circuited the chain
+        response.status_code = 503;
+        response.observability_metadata.interceptors_executed.clear();
+        assert!(transport.execute_response_interceptors(&mut response, &request, &context).await.is_err());
+        assert_eq!(
+            response.observability_metadata.interceptors_executed,
+            vec!["response:status_audit:short_circuited"]
+        );
+    }
+
+    /// Local server that answers the first `failures` requests with 503 (or never, when
+    /// `hang` is set) and records each request's `Idempotency-Key` header
+    async fn counting_server(failures: usize, hang: bool) -> (u16, Arc<std::sync::Mutex<Vec<Option<String>>>>) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = seen.clone();
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buf = vec![0u8; 4096];
+                let read = socket.read(&mut buf).await.unwrap();
+                let head = String::from_utf8_lossy(&buf[..read]).to_string();
+                let key = head.lines()
+                    .find_map(|line| line.strip_prefix("idempotency-key:"))
+                    .map(|value| value.trim().to_string());
+                let count = {
+                    let mut seen = recorded.lock().unwrap();
+                    seen.push(key);
+                    seen.len()
+                };
+
+                if hang {
+                    tokio::spawn(async move {
+                        tokio::time::sleep(Duration::from_secs(5)).await;
+                        drop(socket);
+                    });
+                    continue;
+                }
+                let status = if count <= failures { "503 Service Unavailable" } else { "200 OK" };
+                let reply = format!("HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n", status);
+                socket.write_all(reply.as_bytes()).await.unwrap();
+            }
+        });
+        (port, seen)
+    }
+
+    /// Local server holding a resource at `current_etag`: requests whose `If-Match` names
+    /// another ETag get 412, the rest 200. Records each request's `If-Match` header
+    async fn conditional_server(current_etag: &'static str) -> (u16, Arc<std::sync::Mutex<Vec<Option<String>>>>) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = seen.clone();
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buf = vec![0u8; 4096];
+                let read = socket.read(&mut buf).await.unwrap();
+                let head = String::from_utf8_lossy(&buf[..read]).to_string();
+                let if_match = head.lines()
+                    .find_map(|line| line.strip_prefix("if-match:"))
+                    .map(|value| value.trim().to_string());
+                recorded.lock().unwrap().push(if_match.clone());
+
+                let status = match if_match {
+                    Some(etag) if etag != current_etag => "412 Precondition Failed",
+                    _ => "200 OK",
+                };
+                let reply = format!(
+                    "HTTP/1.1 {}\r\nETag: {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    status, current_etag
+                );
+                socket.write_all(reply.as_bytes()).await.unwrap();
+            }
+        });
+        (port, seen)
+    }
+
+    fn fast_retries(request: &mut SecureRequest, port: u16) {
+        request.url = format!("http://127.0.0.1:{}/orders", port);
+        request.retry_policy = Some(RetryPolicy {
+            max_attempts: 3,
+            base_delay_ms: 1,
+            max_delay_ms: 5,
+            ..Default::default()
+        });
+    }
+
+    #[tokio::test]
+    async fn test_idempotency_key_reused_across_retries() {
+        let license_manager = Arc::new(LicenseManager::new().await.unwrap());
+        let transport = SecureNetworkTransport::new(license_manager).await.unwrap();
+        let context = NetworkContext {
+            user_id: "test-user".to_string(),
+            session_id: Uuid::new_v4(),
+            security_label: SecurityLabel::new(ClassificationLevel::Internal, vec![]),
+            tenant_id: None,
+            source_ip: None,
+            user_agent: None,
+        };
+
+        // Keyed POST is retried through two 503s, sending the same key each time
+        let (port, seen) = counting_server(2, false).await;
+        let mut request = test_request().with_idempotency_key("order-7f3a");
+        fast_retries(&mut request, port);
+        let (response, _) = transport.execute_with_retries(&request, &context).await.unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+        assert_eq!(*seen.lock().unwrap(), vec![Some("order-7f3a".to_string()); 3]);
+
+        // Keyless POST is not replayed after a 503
+        let (port, seen) = counting_server(2, false).await;
+        let mut request = test_request();
+        fast_retries(&mut request, port);
+        assert!(matches!(
+            transport.execute_with_retries(&request, &context).await,
+            Err(NetworkError::HttpError(503, _))
+        ));
+        assert_eq!(seen.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_keyless_post_not_retried_on_timeout_unless_allowed() {
+        let license_manager = Arc::new(LicenseManager::new().await.unwrap());
+        let transport = SecureNetworkTransport::new(license_manager).await.unwrap();
+        let context = NetworkContext {
+            user_id: "test-user".to_string(),
+            session_id: Uuid::new_v4(),
+            security_label: SecurityLabel::new(ClassificationLevel::Internal, vec![]),
+            tenant_id: None,
+            source_ip: None,
+            user_agent: None,
+        };
+
+        let (port, seen) = counting_server(0, true).await;
+        let mut request = test_request();
+        fast_retries(&mut request, port);
+        request.timeout_ms = Some(100);
+        assert!(transport.execute_with_retries(&request, &context).await.is_err());
+        assert_eq!(seen.lock().unwrap().len(), 1);
+
+        // Explicit opt-in restores retries
+        request.retry_policy.as_mut().unwrap().retry_non_idempotent = true;
+        seen.lock().unwrap().clear();
+        assert!(transport.execute_with_retries(&request, &context).await.is_err());
+        assert_eq!(seen.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_reconfigured_timeout_applies_to_later_requests() {
+        let license_manager = Arc::new(LicenseManager::new().await.unwrap());
+        let transport = SecureNetworkTransport::new(license_manager).await.unwrap();
+        let context = NetworkContext {
+            user_id: "test-user".to_string(),
+            session_id: Uuid::new_v4(),
+            security_label: SecurityLabel::new(ClassificationLevel::Internal, vec![]),
+            tenant_id: None,
+            source_ip: None,
+            user_agent: None,
+        };
+        let (port, seen) = counting_server(0, true).await;
+        let mut request = test_request();
+        fast_retries(&mut request, port);
+        transport.set_network_policy(fallback_policy(&request.url, empty_list_stub())).await.unwrap();
+
+        transport.reconfigure(NetworkConfig { timeout_ms: 100, ..Default::default() }).unwrap();
+        let started = Instant::now();
+        assert!(transport.execute_with_retries(&request, &context).await.is_err());
+        assert!(started.elapsed() < Duration::from_secs(2));
+        assert_eq!(seen.lock().unwrap().len(), 1);
+        assert_eq!(transport.network_config().timeout_ms, 100);
+
+        // A rejected config leaves the running client, and registered policies survive swaps
+        assert!(transport.reconfigure(NetworkConfig {
+            security_requirements: Some(SecurityRequirements { min_tls_version: Some("0.9".to_string()), ..Default::default() }),
+            ..Default::default()
+        }).is_err());
+        assert_eq!(transport.network_config().timeout_ms, 100);
+        assert!(transport.matching_policy(&request.url).await.is_some());
+
+        // Defaults apply to endpoints no policy covers
+        transport.reconfigure(NetworkConfig {
+            security_requirements: Some(SecurityRequirements::default()),
+            ..Default::default()
+        }).unwrap();
+        let mut uncovered = test_request();
+        uncovered.url = format!("http://127.0.0.1:{}/reports", port);
+        assert!(matches!(
+            transport.validate_network_policy(&uncovered).await,
+            Err(NetworkError::SecurityViolation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_sustained_failures_exhaust_retry_budget() {
+        let license_manager = Arc::new(LicenseManager::new().await.unwrap());
+        let transport = SecureNetworkTransport::new(license_manager).await.unwrap()
+            .with_retry_budget(RetryBudgetPolicy { retries_per_window: 4, window_seconds: 3600 });
+        let context = NetworkContext {
+            user_id: "test-user".to_string(),
+            session_id: Uuid::new_v4(),
+            security_label: SecurityLabel::new(ClassificationLevel::Internal, vec![]),
+            tenant_id: None,
+            source_ip: None,
+            user_agent: None,
+        };
+
+        // Upstream that never recovers
+        let (port, seen) = counting_server(usize::MAX, false).await;
+        let mut request = test_request().with_idempotency_key("order-7f3a");
+        fast_retries(&mut request, port);
+
+        // The first two requests retry twice each, spending the whole budget
+        for _ in 0..2 {
+            assert!(matches!(
+                transport.execute_with_retries(&request, &context).await,
+                Err(NetworkError::HttpError(503, _))
+            ));
+        }
+        assert_eq!(seen.lock().unwrap().len(), 6);
+
+        // After that each request gets a single attempt
+        for _ in 0..3 {
+            assert!(transport.execute_with_retries(&request, &context).await.is_err());
+        }
+        assert_eq!(seen.lock().unwrap().len(), 9);
+
+        let status = transport.get_retry_budget_status().await;
+        let budget = &status["127.0.0.1"];
+        assert!(budget.exhausted);
+        assert_eq!((budget.retries_granted, budget.retries_denied), (4, 3));
+    }
+
+    fn fallback_policy(endpoint_pattern: &str, fallback: NetworkFallback) -> NetworkPolicy {
+        NetworkPolicy {
+            policy_id: "readings".to_string(),
+            endpoint_pattern: endpoint_pattern.to_string(),
+            allowed_methods: vec![HttpMethod::GET, HttpMethod::POST],
+            security_requirements: SecurityRequirements { require_tls: false, ..Default::default() },
+            rate_limits: None,
+            audit_level: AuditLevel::Basic,
+            data_classification: ClassificationLevel::Internal,
+            fallback: Some(fallback),
+        }
+    }
+
+    fn empty_list_stub() -> NetworkFallback {
+        NetworkFallback::Stub {
+            status_code: 200,
+            headers: [("Content-Type".to_string(), "application/json".to_string())].into(),
+            body: Some(b"[]".to_vec()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_open_breaker_serves_stub_fallback() {
+        let license_manager = Arc::new(LicenseManager::new().await.unwrap());
+        let transport = SecureNetworkTransport::new(license_manager).await.unwrap();
+        let context = NetworkContext {
+            user_id: "test-user".to_string(),
+            session_id: Uuid::new_v4(),
+            security_label: SecurityLabel::new(ClassificationLevel::Internal, vec![]),
+            tenant_id: None,
+            source_ip: None,
+            user_agent: None,
+        };
+        transport
+            .set_network_policy(fallback_policy("https://api.example.com/**", empty_list_stub()))
+            .await
+            .unwrap();
+
+        let request = test_request();
+        for _ in 0..5 {
+            transport.update_circuit_breaker(&request.url, false).await;
+        }
+        let response = transport.execute_secure_request(request.clone(), context.clone()).await.unwrap();
+        assert!(response.served_from_fallback);
+        assert_eq!(response.request_id, request.request_id);
+        assert_eq!(response.json_response::<Vec<Reading>>().unwrap(), Vec::new());
+
+        // Endpoints without a fallback still report the open breaker
+        let mut request = test_request();
+        request.url = "https://other.example.com/readings".to_string();
+        for _ in 0..5 {
+            transport.update_circuit_breaker(&request.url, false).await;
+        }
+        assert!(matches!(
+            transport.execute_secure_request(request, context).await,
+            Err(NetworkError::CircuitBreakerOpen(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_alternate_endpoint_must_pass_network_policy() {
+        let license_manager = Arc::new(LicenseManager::new().await.unwrap());
+        let transport = SecureNetworkTransport::new(license_manager).await.unwrap();
+        let context = NetworkContext {
+            user_id: "test-user".to_string(),
+            session_id: Uuid::new_v4(),
+            security_label: SecurityLabel::new(ClassificationLevel::Internal, vec![]),
+            tenant_id: None,
+            source_ip: None,
+            user_agent: None,
+        };
+        let (port, seen) = counting_server(0, true).await;
+        transport
+            .set_network_policy(fallback_policy(
+                "https://api.example.com/**",
+                NetworkFallback::AlternateEndpoint { base_url: format!("http://127.0.0.1:{}", port) },
+            ))
+            .await
+            .unwrap();
+        // Endpoints no policy covers, the alternate among them, may not reach loopback
+        transport.reconfigure(NetworkConfig {
+            security_requirements: Some(SecurityRequirements {
+                require_tls: false,
+                blocked_domains: Some(vec!["127.0.0.1".to_string()]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }).unwrap();
+
+        let request = test_request();
+        for _ in 0..5 {
+            transport.update_circuit_breaker(&request.url, false).await;
+        }
+        assert!(matches!(
+            transport.execute_secure_request(request, context).await,
+            Err(NetworkError::SecurityViolation(_))
+        ));
+        assert!(seen.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_open_breaker_half_opens_when_clock_passes_timeout() {
+        let license_manager = Arc::new(LicenseManager::new().await.unwrap());
+        let clock = Arc::new(crate::clock::MockClock::default());
+        let transport = SecureNetworkTransport::new(license_manager).await.unwrap().with_clock(clock.clone());
+        let url = test_request().url;
+        for _ in 0..5 {
+            transport.update_circuit_breaker(&url, false).await;
+        }
+        assert!(transport.is_circuit_breaker_open(&url).await);
+
+        clock.advance(Duration::from_secs(59));
+        assert_eq!(transport.open_circuit_breakers().await, vec![url.clone()]);
+
+        // Past the 60s timeout the next request is let through as a trial
+        clock.advance(Duration::from_secs(1));
+        assert!(!transport.is_circuit_breaker_open(&url).await);
+        assert_eq!(transport.get_circuit_breaker_status().await[&url], CircuitBreakerState::HalfOpen);
+
+        // A failed trial reopens it for another full timeout
+        transport.update_circuit_breaker(&url, false).await;
+        assert!(transport.is_circuit_breaker_open(&url).await);
+        clock.advance(Duration::from_secs(60));
+        assert!(!transport.is_circuit_breaker_open(&url).await);
+
+        // A successful trial closes it
+        transport.update_circuit_breaker(&url, true).await;
+        assert_eq!(transport.get_circuit_breaker_status().await[&url], CircuitBreakerState::Closed);
+        assert!(transport.open_circuit_breakers().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_probe_bypasses_and_leaves_open_breaker() {
+        let license_manager = Arc::new(LicenseManager::new().await.unwrap());
+        let transport = SecureNetworkTransport::new(license_manager).await.unwrap();
+        let (port, seen) = counting_server(1, false).await;
+        let mut request = test_request();
+        request.method = HttpMethod::GET;
+        request.body = None;
+        request.url = format!("http://127.0.0.1:{}/healthz", port);
+        for _ in 0..5 {
+            transport.update_circuit_breaker(&request.url, false).await;
+        }
+
+        // Each probe reaches the target, and its result, good or bad, leaves the breaker alone
+        assert_eq!(transport.probe(&request).await.unwrap(), 503);
+        assert_eq!(transport.probe(&request).await.unwrap(), 200);
+        assert_eq!(seen.lock().unwrap().len(), 2);
+        assert_eq!(transport.open_circuit_breakers().await, vec![request.url.clone()]);
+    }
+
+    #[derive(Default)]
+    struct NetworkAuditHooks {
+        envelopes: std::sync::Mutex<Vec<crate::observability::ForensicEnvelope>>,
+    }
+
+    #[async_trait::async_trait]
+    impl InstrumentationHooks for NetworkAuditHooks {
+        async fn audit_start(&self, _context: &ObservabilityContext) -> Result<(), ForensicError> {
+            Ok(())
+        }
+        async fn audit_end(&self, _context: &ObservabilityContext, _success: bool) -> Result<(), ForensicError> {
+            Ok(())
+        }
+        async fn metrics_start(&self, _context: &ObservabilityContext) {}
+        async fn metrics_end(&self, _context: &ObservabilityContext, _duration: Duration) {}
+        async fn audit_network(&self, envelope: crate::observability::ForensicEnvelope) {
+            self.envelopes.lock().unwrap().push(envelope);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_policy_audit_level_drives_network_envelope() {
+        let license_manager = Arc::new(LicenseManager::new().await.unwrap());
+        let transport = SecureNetworkTransport::new(license_manager).await.unwrap();
+        let context = NetworkContext {
+            user_id: "test-user".to_string(),
+            session_id: Uuid::new_v4(),
+            security_label: SecurityLabel::new(ClassificationLevel::Internal, vec![]),
+            tenant_id: None,
+            source_ip: None,
+            user_agent: None,
+        };
+        let request = test_request();
+        for _ in 0..5 {
+            transport.update_circuit_breaker(&request.url, false).await;
+        }
+
+        let mut policy = fallback_policy("https://api.example.com/**", empty_list_stub());
+        policy.audit_level = AuditLevel::Headers;
+        transport.set_network_policy(policy.clone()).await.unwrap();
+        let hooks = NetworkAuditHooks::default();
+        transport.request(request.clone(), context.clone(), &hooks).await.unwrap();
+        {
+            let envelopes = hooks.envelopes.lock().unwrap();
+            assert_eq!(envelopes.len(), 1);
+            assert_eq!(envelopes[0].event_type, network_audit::NETWORK_AUDIT_EVENT);
+            assert_eq!(envelopes[0].metadata["request_headers"]["content-type"], "text/plain");
+            assert_eq!(envelopes[0].metadata["served_from_fallback"], true);
+            assert!(envelopes[0].metadata.get("response_body").is_none());
+        }
+
+        policy.audit_level = AuditLevel::None;
+        transport.set_network_policy(policy).await.unwrap();
+        transport.request(request, context, &hooks).await.unwrap();
+        assert_eq!(hooks.envelopes.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_exhausted_retries_serve_stub_or_alternate_endpoint() {
+        let license_manager = Arc::new(LicenseManager::new().await.unwrap());
+        let transport = SecureNetworkTransport::new(license_manager).await.unwrap();
+        let context = NetworkContext {
+            user_id: "test-user".to_string(),
+            session_id: Uuid::new_v4(),
+            security_label: SecurityLabel::new(ClassificationLevel::Internal, vec![]),
+            tenant_id: None,
+            source_ip: None,
+            user_agent: None,
+        };
+        let (port, seen) = counting_server(usize::MAX, false).await;
+        let mut request = test_request().with_idempotency_key("order-7f3a");
+        fast_retries(&mut request, port);
+
+        transport.set_network_policy(fallback_policy("127.0.0.1", empty_list_stub())).await.unwrap();
+        let response = transport.execute_secure_request(request.clone(), context.clone()).await.unwrap();
+        assert!(response.served_from_fallback);
+        assert_eq!(response.body.as_deref(), Some(b"[]".as_ref()));
+        assert_eq!(seen.lock().unwrap().len(), 3);
+
+        // An alternate endpoint receives the same request, path and idempotency key included
+        let (alternate_port, alternate_seen) = counting_server(0, false).await;
+        let alternate = NetworkFallback::AlternateEndpoint {
+            base_url: format!("http://127.0.0.1:{}", alternate_port),
+        };
+        transport.set_network_policy(fallback_policy("127.0.0.1", alternate)).await.unwrap();
+        let response = transport.execute_secure_request(request, context).await.unwrap();
+        assert!(response.served_from_fallback);
+        assert_eq!(response.status_code, 200);
+        assert_eq!(*alternate_seen.lock().unwrap(), vec![Some("order-7f3a".to_string())]);
+    }
+
+    #[test]
+    fn test_conditional_headers_replace_existing() {
+        let request = test_request()
+            .with_if_match("\"1\"")
+            .with_expected_version(7)
+            .with_if_none_match("*");
+        let if_match: Vec<_> = request.headers.iter()
+            .filter(|(key, _)| key.eq_ignore_ascii_case("if-match"))
+            .collect();
+        assert_eq!(if_match, vec![(&"If-Match".to_string(), &"\"7\"".to_string())]);
+        assert_eq!(request.headers["If-None-Match"], "*");
+        assert_eq!(version_from_etag(&version_etag(42)), Some(42));
+        assert_eq!(version_from_etag("W/\"3\""), Some(3));
+        assert_eq!(version_from_etag("\"abc\""), None);
+    }
+
+    #[tokio::test]
+    async fn test_if_match_sent_and_412_mapped_to_precondition_failed() {
+        let license_manager = Arc::new(LicenseManager::new().await.unwrap());
+        let transport = SecureNetworkTransport::new(license_manager).await.unwrap();
+        let context = NetworkContext {
+            user_id: "test-user".to_string(),
+            session_id: Uuid::new_v4(),
+            security_label: SecurityLabel::new(ClassificationLevel::Internal, vec![]),
+            tenant_id: None,
+            source_ip: None,
+            user_agent: None,
+        };
+        let (port, seen) = conditional_server("\"8\"").await;
+
+        // Based on the current version: applied
+        let mut request = test_request().with_idempotency_key("order-7f3a").with_expected_version(8);
+        fast_retries(&mut request, port);
+        let (response, _) = transport.execute_with_retries(&request, &context).await.unwrap();
+        assert_eq!(response.status().as_u16(), 200);
+
+        // Based on a stale version: typed conflict carrying the current ETag, never retried
+        // even when the policy lists 412 as retryable
+        let mut request = test_request().with_idempotency_key("order-7f3a").with_expected_version(7);
+        fast_retries(&mut request, port);
+        request.retry_policy.as_mut().unwrap().retry_on_status.push(412);
+        match transport.execute_with_retries(&request, &context).await {
+            Err(NetworkError::PreconditionFailed { url, current_etag }) => {
+                assert_eq!(url, request.url);
+                assert_eq!(current_etag.as_deref().and_then(version_from_etag), Some(8));
+            }
+            other => panic!("expected PreconditionFailed, got {:?}", other.map(|(response, _)| response.status())),
+        }
+        assert_eq!(*seen.lock().unwrap(), vec![Some("\"8\"".to_string()), Some("\"7\"".to_string())]);
+    }
 }
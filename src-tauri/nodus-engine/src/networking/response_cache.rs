@@ -8,26 +8,30 @@ use tokio::sync::RwLock;
 use std::collections::HashMap;
 use std::time::Duration;
 use chrono::{DateTime, Utc};
-use lru::LruCache;
-use std::num::NonZeroUsize;
 
 use super::{SecureResponse, CachePolicy};
+use crate::cache::TtlCache;
+use crate::clock::{self, Clock};
 use crate::security::ClassificationLevel;
 
 /// High-performance response cache with enterprise features
 #[derive(Debug)]
 pub struct ResponseCache {
-    // LRU cache for response data
-    cache: Arc<RwLock<LruCache<String, CachedResponse>>>,
+    // LRU cache for response data, expiring each entry after its TTL
+    cache: Arc<RwLock<TtlCache<String, CachedResponse>>>,
     
     // Cache metadata for management
     cache_metadata: Arc<RwLock<HashMap<String, CacheMetadata>>>,
     
     // Cache configuration
     config: CacheConfig,
-    
-    // Cache statistics
-    stats: Arc<RwLock<CacheStats>>,
+
+    // Request headers learned from upstream `Vary` responses, keyed by base cache key and
+    // bounded like the entries; a forgotten resource is re-learned on its next response
+    vary_headers: Arc<RwLock<TtlCache<String, Vec<String>>>>,
+
+    // Time source for TTL checks
+    clock: Arc<dyn Clock>,
 }
 
 /// Cached response with metadata
@@ -81,10 +85,9 @@ pub struct CacheStats {
 impl ResponseCache {
     /// Create new response cache
     pub fn new(max_entries: usize) -> Self {
-        let cache_size = NonZeroUsize::new(max_entries).unwrap_or(NonZeroUsize::new(1000).unwrap());
-        
+        let clock = clock::system_clock();
         Self {
-            cache: Arc::new(RwLock::new(LruCache::new(cache_size))),
+            cache: Arc::new(RwLock::new(Self::entries(max_entries, clock.clone()))),
             cache_metadata: Arc::new(RwLock::new(HashMap::new())),
             config: CacheConfig {
                 max_entries,
@@ -94,95 +97,110 @@ impl ResponseCache {
                 respect_cache_control: true,
                 cache_classification_limit: ClassificationLevel::Internal,
             },
-            stats: Arc::new(RwLock::new(CacheStats::default())),
+            vary_headers: Arc::new(RwLock::new(Self::entries(max_entries, clock.clone()))),
+            clock,
         }
     }
 
+    /// Read time from `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.cache = Arc::new(RwLock::new(Self::entries(self.config.max_entries, clock.clone())));
+        self.vary_headers = Arc::new(RwLock::new(Self::entries(self.config.max_entries, clock.clone())));
+        self.clock = clock;
+        self
+    }
+
+    fn entries<V>(max_entries: usize, clock: Arc<dyn Clock>) -> TtlCache<String, V> {
+        let capacity = if max_entries == 0 { 1000 } else { max_entries };
+        TtlCache::new(capacity).with_clock(clock)
+    }
+
     /// Get cached response
     pub async fn get(&self, key: &str) -> Option<SecureResponse> {
-        let mut stats = self.stats.write().await;
-        stats.total_requests += 1;
-
-        let mut cache = self.cache.write().await;
-        
-        if let Some(cached) = cache.get_mut(key) {
-            // Check if cache entry is still valid
-                    // Compare using chrono DateTime since `Instant` is not serializable
-                    let age = chrono::Utc::now().signed_duration_since(cached.cached_at);
-                    let ttl_chrono = chrono::Duration::from_std(cached.ttl).unwrap_or(chrono::Duration::seconds(0));
-                    if age < ttl_chrono {
-                // Update access statistics
-                cached.access_count += 1;
-                stats.cache_hits += 1;
-                stats.hit_ratio = stats.cache_hits as f64 / stats.total_requests as f64;
-                
-                // Update metadata
-                self.update_access_metadata(key).await;
-                
-                // Return cloned response with updated cache flag
-                let mut response = cached.response.clone();
-                response.cached = true;
-                return Some(response);
-            } else {
-                // Entry expired, remove it
-                cache.pop(key);
-                self.remove_metadata(key).await;
+        let key = key.to_string();
+        let hit = self.cache.write().await.get_mut(&key).map(|cached| {
+            cached.access_count += 1;
+            let mut response = cached.response.clone();
+            response.cached = true;
+            response
+        });
+
+        match hit {
+            Some(response) => {
+                self.update_access_metadata(&key).await;
+                Some(response)
+            }
+            None => {
+                // The entry may have just expired
+                self.remove_metadata(&key).await;
+                None
             }
         }
-
-        stats.cache_misses += 1;
-        stats.hit_ratio = stats.cache_hits as f64 / stats.total_requests as f64;
-        None
     }
 
-    /// Set cached response
+    /// Set cached response, evicting least-recently-used entries to stay within
+    /// both the entry capacity and the memory bound
     pub async fn set(&self, key: String, response: SecureResponse, ttl: Duration) {
-        // Check if response should be cached based on classification
-        if response.status_code >= 400 {
-            return; // Don't cache error responses
+        if response.status_code >= 400 || ttl.is_zero() {
+            return; // Don't cache error responses or already-stale entries
         }
 
-        // Calculate response size
         let size_bytes = self.calculate_response_size(&response);
-        
-        // Check memory limits
-        if !self.check_memory_limits(size_bytes).await {
-            return; // Skip caching if memory limit would be exceeded
+        let max_memory_bytes = self.config.max_memory_mb * 1024 * 1024;
+        if size_bytes > max_memory_bytes {
+            return; // Could never fit, even in an empty cache
         }
 
         let cached_response = CachedResponse {
             response: response.clone(),
-            cached_at: chrono::Utc::now(),
+            cached_at: self.clock.now(),
             ttl,
             classification: ClassificationLevel::Internal, // Should be determined from response
             access_count: 0,
             size_bytes,
         };
 
-        // Add to cache
-        let mut cache = self.cache.write().await;
-        let evicted = cache.put(key.clone(), cached_response).is_some();
-        
-        if evicted {
-            let mut stats = self.stats.write().await;
-            stats.evictions += 1;
+        let mut evicted_keys = Vec::new();
+        {
+            let mut cache = self.cache.write().await;
+            cache.remove(&key);
+            // Expired entries still hold their memory until they're swept
+            evicted_keys.extend(cache.sweep_expired());
+
+            // Evict from the LRU end until the new entry fits in memory
+            let mut memory_in_use = Self::memory_in_use(&cache);
+            while memory_in_use + size_bytes > max_memory_bytes {
+                match cache.evict_lru() {
+                    Some((evicted_key, evicted)) => {
+                        memory_in_use = memory_in_use.saturating_sub(evicted.size_bytes);
+                        evicted_keys.push(evicted_key);
+                    }
+                    None => break,
+                }
+            }
+
+            // At the entry capacity, inserting evicts the least-recently-used entry
+            if let Some((evicted_key, _)) = cache.insert_with_ttl(key.clone(), cached_response, ttl) {
+                evicted_keys.push(evicted_key);
+            }
+        }
+
+        if !evicted_keys.is_empty() {
+            let mut metadata = self.cache_metadata.write().await;
+            for evicted_key in &evicted_keys {
+                metadata.remove(evicted_key);
+            }
         }
 
-        // Update metadata
         self.set_metadata(key, &response, ttl, size_bytes).await;
-        
-        // Update statistics
-        self.update_cache_stats().await;
     }
 
     /// Invalidate cache entry
     pub async fn invalidate(&self, key: &str) -> bool {
-        let mut cache = self.cache.write().await;
-        let removed = cache.pop(key).is_some();
+        let removed = self.cache.write().await.remove(&key.to_string()).is_some();
         
         if removed {
             self.remove_metadata(key).await;
-            self.update_cache_stats().await;
         }
         
         removed
@@ -190,47 +208,83 @@ impl ResponseCache {
 
     /// Invalidate cache entries by pattern
     pub async fn invalidate_pattern(&self, pattern: &str) -> u32 {
-        let mut invalidated = 0;
-        let keys_to_remove: Vec<String>;
-        
-        // Collect keys that match pattern
-        {
-            let cache = self.cache.read().await;
-            keys_to_remove = cache.iter()
-                .filter_map(|(key, _)| {
-                    if key.contains(pattern) {
-                        Some(key.clone())
-                    } else {
-                        None
-                    }
-                })
-                .collect();
-        }
-        
-        // Remove matching entries
-        for key in keys_to_remove {
-            if self.invalidate(&key).await {
-                invalidated += 1;
-            }
-        }
-        
-        invalidated
+        self.invalidate_where(|key| key.contains(pattern)).await
+    }
+
+    /// Invalidate every entry whose key starts with `prefix` (e.g. "GET:https://api.example.com/users")
+    pub async fn invalidate_by_prefix(&self, prefix: &str) -> u32 {
+        self.invalidate_where(|key| key.starts_with(prefix)).await
     }
 
     /// Clear all cache entries
     pub async fn clear(&self) {
-        let mut cache = self.cache.write().await;
-        cache.clear();
-        
-        let mut metadata = self.cache_metadata.write().await;
-        metadata.clear();
-        
-        self.reset_stats().await;
+        {
+            let mut cache = self.cache.write().await;
+            cache.clear();
+            cache.reset_stats();
+        }
+        self.vary_headers.write().await.clear();
+        self.cache_metadata.write().await.clear();
+    }
+
+    /// Fraction of lookups served from cache since the last reset
+    pub async fn hit_ratio(&self) -> f64 {
+        self.cache.read().await.stats().hit_ratio()
+    }
+
+    /// Effective TTL for a response under a cache policy, or `None` if it must not be cached
+    ///
+    /// With `respect_cache_headers`, upstream `Cache-Control: no-store`/`no-cache` disable
+    /// caching and `max-age` overrides the policy's `ttl_seconds`.
+    pub fn ttl_for(&self, response: &SecureResponse, cache_policy: &CachePolicy) -> Option<Duration> {
+        if response.status_code >= 400 || !cache_policy.cache_on_status.contains(&response.status_code) {
+            return None;
+        }
+
+        let policy_ttl = Duration::from_secs(cache_policy.ttl_seconds);
+        if !cache_policy.respect_cache_headers {
+            return Some(policy_ttl);
+        }
+
+        // `Vary: *` means no request can be matched against the stored response
+        if response.headers.get("vary").map(|vary| vary.trim() == "*").unwrap_or(false) {
+            return None;
+        }
+
+        let directives = response.headers.get("cache-control")
+            .map(|value| CacheDirectives::parse(value))
+            .unwrap_or_default();
+
+        if directives.no_store || directives.no_cache {
+            return None;
+        }
+
+        match directives.max_age {
+            Some(0) => None,
+            Some(max_age) => Some(Duration::from_secs(max_age)),
+            None => Some(policy_ttl),
+        }
     }
 
     /// Get cache statistics
     pub async fn get_stats(&self) -> CacheStats {
-        self.stats.read().await.clone()
+        let cache = self.cache.read().await;
+        let counts = cache.stats();
+        let memory_usage_bytes = Self::memory_in_use(&cache);
+        CacheStats {
+            total_requests: counts.hits + counts.misses,
+            cache_hits: counts.hits,
+            cache_misses: counts.misses,
+            cache_size: counts.size,
+            memory_usage_bytes,
+            evictions: counts.evictions,
+            hit_ratio: counts.hit_ratio(),
+            avg_response_size: if counts.size > 0 {
+                memory_usage_bytes as f64 / counts.size as f64
+            } else {
+                0.0
+            },
+        }
     }
 
     /// Get cache metadata for enterprise monitoring
@@ -243,15 +297,9 @@ impl ResponseCache {
     pub async fn configure(&mut self, config: CacheConfig) {
         self.config = config;
         
-        // If max_entries changed, recreate cache
-        if let Ok(new_size) = NonZeroUsize::try_from(self.config.max_entries) {
-            let mut cache = self.cache.write().await;
-            let old_cache = std::mem::replace(&mut *cache, LruCache::new(new_size));
-            
-            // Transfer entries up to new limit
-            for (key, value) in old_cache.into_iter().take(self.config.max_entries) {
-                cache.put(key, value);
-            }
+        // Evict least-recently-used entries beyond a smaller limit
+        if self.config.max_entries > 0 {
+            self.cache.write().await.resize(self.config.max_entries);
         }
     }
 
@@ -301,35 +349,81 @@ impl ResponseCache {
         key
     }
 
+    /// Cache key for a request, partitioned by the policy's `vary_on_headers` and, when
+    /// `respect_cache_headers` is set, by headers learned from an upstream `Vary` response
+    pub async fn request_key(
+        &self,
+        method: &str,
+        url: &str,
+        headers: &HashMap<String, String>,
+        cache_policy: &CachePolicy,
+    ) -> String {
+        let base_key = Self::base_key(method, url, cache_policy);
+
+        let mut vary_on: Vec<String> = cache_policy.vary_on_headers.iter()
+            .map(|name| name.to_ascii_lowercase())
+            .collect();
+        if cache_policy.respect_cache_headers {
+            if let Some(learned) = self.vary_headers.write().await.get(&base_key) {
+                vary_on.extend(learned.iter().cloned());
+            }
+        }
+        vary_on.sort();
+        vary_on.dedup();
+
+        // Header names are case-insensitive
+        let request_headers: HashMap<String, String> = headers.iter()
+            .map(|(name, value)| (name.to_ascii_lowercase(), value.clone()))
+            .collect();
+
+        let mut key = base_key;
+        for header_name in &vary_on {
+            let value = request_headers.get(header_name).map(String::as_str).unwrap_or("");
+            key.push_str(&format!("&{}={}", header_name, value));
+        }
+        key
+    }
+
+    /// Record the request headers an upstream response varies on, so later lookups for the
+    /// same resource are partitioned by them
+    pub async fn record_vary(
+        &self,
+        method: &str,
+        url: &str,
+        cache_policy: &CachePolicy,
+        response: &SecureResponse,
+    ) {
+        if !cache_policy.respect_cache_headers {
+            return;
+        }
+
+        let Some(vary) = response.headers.get("vary") else {
+            return;
+        };
+
+        let mut header_names: Vec<String> = vary.split(',')
+            .map(|name| name.trim().to_ascii_lowercase())
+            .filter(|name| !name.is_empty() && name != "*")
+            .collect();
+        if header_names.is_empty() {
+            return;
+        }
+        header_names.sort();
+        header_names.dedup();
+
+        self.vary_headers.write().await.insert(Self::base_key(method, url, cache_policy), header_names);
+    }
+
     /// Cleanup expired entries
     pub async fn cleanup_expired(&self) -> u32 {
-        let mut removed = 0;
-        let keys_to_remove: Vec<String>;
-        
-        // Collect expired keys
-        {
-            let cache = self.cache.read().await;
-            keys_to_remove = cache.iter()
-                .filter_map(|(key, cached)| {
-                    let age = chrono::Utc::now().signed_duration_since(cached.cached_at);
-                    let ttl_chrono = chrono::Duration::from_std(cached.ttl).unwrap_or(chrono::Duration::seconds(0));
-                    if age >= ttl_chrono {
-                        Some(key.clone())
-                    } else {
-                        None
-                    }
-                })
-                .collect();
-        }
-        
-        // Remove expired entries
-        for key in keys_to_remove {
-            if self.invalidate(&key).await {
-                removed += 1;
+        let expired = self.cache.write().await.sweep_expired();
+        if !expired.is_empty() {
+            let mut metadata = self.cache_metadata.write().await;
+            for key in &expired {
+                metadata.remove(key);
             }
         }
-        
-        removed
+        expired.len() as u32
     }
 
     /// Export cache contents for debugging/analysis
@@ -359,10 +453,32 @@ impl ResponseCache {
 
     // Private helper methods
 
+    fn base_key(method: &str, url: &str, cache_policy: &CachePolicy) -> String {
+        cache_policy.cache_key.clone()
+            .unwrap_or_else(|| format!("{}:{}", method, url))
+    }
+
+    /// Remove the entries `matches` selects by key, with their metadata
+    async fn invalidate_where(&self, matches: impl Fn(&str) -> bool) -> u32 {
+        let removed = self.cache.write().await.remove_where(|key, _| matches(key));
+        if !removed.is_empty() {
+            let mut metadata = self.cache_metadata.write().await;
+            for (key, _) in &removed {
+                metadata.remove(key);
+            }
+        }
+        removed.len() as u32
+    }
+
+    /// Memory held by every entry, expired ones included
+    fn memory_in_use(cache: &TtlCache<String, CachedResponse>) -> usize {
+        cache.values_held().map(|cached| cached.size_bytes).sum()
+    }
+
     async fn update_access_metadata(&self, key: &str) {
         let mut metadata = self.cache_metadata.write().await;
         if let Some(meta) = metadata.get_mut(key) {
-            meta.last_accessed = chrono::Utc::now();
+            meta.last_accessed = self.clock.now();
             meta.access_count += 1;
         }
     }
@@ -374,11 +490,12 @@ impl ResponseCache {
         ttl: Duration,
         size_bytes: usize,
     ) {
+        let now = self.clock.now();
         let metadata = CacheMetadata {
             key: key.clone(),
             classification: ClassificationLevel::Internal, // Should be determined from response
-            created_at: chrono::Utc::now(),
-            last_accessed: chrono::Utc::now(),
+            created_at: now,
+            last_accessed: now,
             access_count: 0,
             size_bytes,
             ttl_seconds: ttl.as_secs(),
@@ -394,14 +511,6 @@ impl ResponseCache {
         metadata.remove(key);
     }
 
-    async fn check_memory_limits(&self, additional_bytes: usize) -> bool {
-        let stats = self.stats.read().await;
-        let new_memory_usage = stats.memory_usage_bytes + additional_bytes;
-        let max_memory_bytes = self.config.max_memory_mb * 1024 * 1024;
-        
-        new_memory_usage <= max_memory_bytes
-    }
-
     fn calculate_response_size(&self, response: &SecureResponse) -> usize {
         let headers_size: usize = response.headers.iter()
             .map(|(k, v)| k.len() + v.len())
@@ -411,33 +520,37 @@ impl ResponseCache {
         
         headers_size + body_size + std::mem::size_of::<SecureResponse>()
     }
+}
 
-    async fn update_cache_stats(&self) {
-        let cache = self.cache.read().await;
-        let mut stats = self.stats.write().await;
-        
-        stats.cache_size = cache.len();
-        
-        // Calculate memory usage
-        let mut total_memory = 0;
-        let mut total_response_size = 0;
-        let mut response_count = 0;
-        
-        for cached in cache.iter() {
-            total_memory += cached.1.size_bytes;
-            total_response_size += cached.1.size_bytes;
-            response_count += 1;
-        }
-        
-        stats.memory_usage_bytes = total_memory;
-        if response_count > 0 {
-            stats.avg_response_size = total_response_size as f64 / response_count as f64;
+/// Parsed upstream `Cache-Control` directives relevant to a client-side cache
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CacheDirectives {
+    pub no_store: bool,
+    pub no_cache: bool,
+    pub max_age: Option<u64>,
+}
+
+impl CacheDirectives {
+    /// Parse a `Cache-Control` header value (directive names are case-insensitive)
+    pub fn parse(header_value: &str) -> Self {
+        let mut directives = Self::default();
+
+        for directive in header_value.split(',') {
+            let directive = directive.trim();
+            let (name, value) = match directive.split_once('=') {
+                Some((name, value)) => (name.trim(), Some(value.trim().trim_matches('"'))),
+                None => (directive, None),
+            };
+
+            match name.to_ascii_lowercase().as_str() {
+                "no-store" => directives.no_store = true,
+                "no-cache" => directives.no_cache = true,
+                "max-age" => directives.max_age = value.and_then(|v| v.parse().ok()),
+                _ => {}
+            }
         }
-    }
 
-    async fn reset_stats(&self) {
-        let mut stats = self.stats.write().await;
-        *stats = CacheStats::default();
+        directives
     }
 }
 
@@ -517,11 +630,12 @@ mod tests {
             response_time_ms: 100,
             cached: false,
             security_validated: true,
+            served_from_fallback: false,
             observability_metadata: crate::networking::NetworkObservabilityMetadata {
                 operation_id: "test".to_string(),
                 dns_resolution_time_ms: 0,
-                tcp_connection_time_ms: 0,
-                tls_handshake_time_ms: 0,
+                tcp_connection_time_ms: None,
+                tls_handshake_time_ms: None,
                 request_time_ms: 0,
                 response_time_ms: 0,
                 bytes_sent: 0,
@@ -539,7 +653,8 @@ mod tests {
 
     #[tokio::test]
     async fn test_cache_expiration() {
-        let cache = ResponseCache::new(100);
+        let clock = Arc::new(crate::clock::MockClock::default());
+        let cache = ResponseCache::new(100).with_clock(clock.clone());
         
         let response = SecureResponse {
             request_id: uuid::Uuid::new_v4(),
@@ -549,11 +664,12 @@ mod tests {
             response_time_ms: 100,
             cached: false,
             security_validated: true,
+            served_from_fallback: false,
             observability_metadata: crate::networking::NetworkObservabilityMetadata {
                 operation_id: "test".to_string(),
                 dns_resolution_time_ms: 0,
-                tcp_connection_time_ms: 0,
-                tls_handshake_time_ms: 0,
+                tcp_connection_time_ms: None,
+                tls_handshake_time_ms: None,
                 request_time_ms: 0,
                 response_time_ms: 0,
                 bytes_sent: 0,
@@ -562,14 +678,13 @@ mod tests {
             },
         };
         
-        // Set with very short TTL
-        cache.set("test_key".to_string(), response, Duration::from_millis(1)).await;
-        
-        // Wait for expiration
-        tokio::time::sleep(Duration::from_millis(10)).await;
-        
-        let cached_response = cache.get("test_key").await;
-        assert!(cached_response.is_none());
+        cache.set("test_key".to_string(), response, Duration::from_secs(60)).await;
+
+        clock.advance(Duration::from_secs(59));
+        assert!(cache.get("test_key").await.is_some());
+
+        clock.advance(Duration::from_secs(1));
+        assert!(cache.get("test_key").await.is_none());
     }
 
     #[tokio::test]
@@ -584,11 +699,12 @@ mod tests {
             response_time_ms: 100,
             cached: false,
             security_validated: true,
+            served_from_fallback: false,
             observability_metadata: crate::networking::NetworkObservabilityMetadata {
                 operation_id: "test".to_string(),
                 dns_resolution_time_ms: 0,
-                tcp_connection_time_ms: 0,
-                tls_handshake_time_ms: 0,
+                tcp_connection_time_ms: None,
+                tls_handshake_time_ms: None,
                 request_time_ms: 0,
                 response_time_ms: 0,
                 bytes_sent: 0,
@@ -623,4 +739,184 @@ mod tests {
         assert_eq!(stats.cache_misses, 1);
         assert_eq!(stats.hit_ratio, 0.0);
     }
+
+    fn test_response(headers: &[(&str, &str)]) -> SecureResponse {
+        SecureResponse {
+            request_id: uuid::Uuid::new_v4(),
+            status_code: 200,
+            headers: headers.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+            body: Some(b"test response".to_vec()),
+            response_time_ms: 100,
+            cached: false,
+            security_validated: true,
+            served_from_fallback: false,
+            observability_metadata: crate::networking::NetworkObservabilityMetadata {
+                operation_id: "test".to_string(),
+                dns_resolution_time_ms: 0,
+                tcp_connection_time_ms: None,
+                tls_handshake_time_ms: None,
+                request_time_ms: 0,
+                response_time_ms: 0,
+                bytes_sent: 0,
+                bytes_received: 13,
+                interceptors_executed: Vec::new(),
+            },
+        }
+    }
+
+    fn test_policy(respect_cache_headers: bool) -> CachePolicy {
+        CachePolicy {
+            cache_key: None,
+            ttl_seconds: 300,
+            vary_on_headers: Vec::new(),
+            cache_on_status: vec![200],
+            respect_cache_headers,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lru_eviction_at_capacity() {
+        let cache = ResponseCache::new(2);
+
+        cache.set("a".to_string(), test_response(&[]), Duration::from_secs(300)).await;
+        cache.set("b".to_string(), test_response(&[]), Duration::from_secs(300)).await;
+        // Touch "a" so "b" becomes least recently used
+        assert!(cache.get("a").await.is_some());
+        cache.set("c".to_string(), test_response(&[]), Duration::from_secs(300)).await;
+
+        assert!(cache.get("a").await.is_some());
+        assert!(cache.get("b").await.is_none());
+        assert!(cache.get("c").await.is_some());
+
+        let stats = cache.get_stats().await;
+        assert_eq!(stats.evictions, 1);
+        assert_eq!(stats.cache_size, 2);
+        assert_eq!(cache.get_metadata().await.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_expired_entries_count_against_memory_until_swept() {
+        let clock = Arc::new(crate::clock::MockClock::default());
+        let mut cache = ResponseCache::new(10).with_clock(clock.clone());
+        cache.config.max_memory_mb = 1;
+        let large = || SecureResponse { body: Some(vec![0; 600 * 1024]), ..test_response(&[]) };
+
+        cache.set("a".to_string(), large(), Duration::from_secs(10)).await;
+        clock.advance(Duration::from_secs(10));
+        assert_eq!(cache.get_stats().await.cache_size, 1);
+        assert!(cache.get_stats().await.memory_usage_bytes > 600 * 1024);
+
+        // The expired entry is swept to make room rather than overlooked
+        cache.set("b".to_string(), large(), Duration::from_secs(300)).await;
+        let stats = cache.get_stats().await;
+        assert_eq!(stats.cache_size, 1);
+        assert!(stats.memory_usage_bytes <= 1024 * 1024);
+        assert_eq!(stats.evictions, 0);
+        assert_eq!(cache.get_metadata().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_by_prefix_and_clear() {
+        let cache = ResponseCache::new(10);
+        for key in ["GET:https://api/users/1", "GET:https://api/users/2", "GET:https://api/orders/1"] {
+            cache.set(key.to_string(), test_response(&[]), Duration::from_secs(300)).await;
+        }
+
+        assert_eq!(cache.invalidate_by_prefix("GET:https://api/users").await, 2);
+        assert!(cache.get("GET:https://api/orders/1").await.is_some());
+
+        cache.clear().await;
+        assert!(cache.get("GET:https://api/orders/1").await.is_none());
+    }
+
+    #[test]
+    fn test_cache_control_overrides_ttl() {
+        let cache = ResponseCache::new(10);
+
+        let no_store = test_response(&[("cache-control", "private, no-store")]);
+        assert_eq!(cache.ttl_for(&no_store, &test_policy(true)), None);
+        assert_eq!(cache.ttl_for(&no_store, &test_policy(false)), Some(Duration::from_secs(300)));
+
+        let max_age = test_response(&[("cache-control", "public, Max-Age=60")]);
+        assert_eq!(cache.ttl_for(&max_age, &test_policy(true)), Some(Duration::from_secs(60)));
+
+        let plain = test_response(&[]);
+        assert_eq!(cache.ttl_for(&plain, &test_policy(true)), Some(Duration::from_secs(300)));
+    }
+
+    #[tokio::test]
+    async fn test_hit_ratio_tracking() {
+        let cache = ResponseCache::new(10);
+        cache.set("key".to_string(), test_response(&[]), Duration::from_secs(300)).await;
+
+        let _ = cache.get("key").await;
+        let _ = cache.get("key").await;
+        let _ = cache.get("missing").await;
+        let _ = cache.get("missing").await;
+
+        assert_eq!(cache.hit_ratio().await, 0.5);
+    }
+
+    #[tokio::test]
+    async fn test_vary_on_headers_partitions_entries() {
+        let cache = ResponseCache::new(10);
+        let mut policy = test_policy(false);
+        policy.vary_on_headers = vec!["Accept-Language".to_string()];
+
+        let english: HashMap<String, String> = [("accept-language".to_string(), "en".to_string())].into();
+        let french: HashMap<String, String> = [("Accept-Language".to_string(), "fr".to_string())].into();
+
+        let english_key = cache.request_key("GET", "https://api/greeting", &english, &policy).await;
+        let french_key = cache.request_key("GET", "https://api/greeting", &french, &policy).await;
+        assert_ne!(english_key, french_key);
+
+        cache.set(english_key.clone(), test_response(&[("content-language", "en")]), Duration::from_secs(300)).await;
+        assert!(cache.get(&french_key).await.is_none());
+
+        cache.set(french_key.clone(), test_response(&[("content-language", "fr")]), Duration::from_secs(300)).await;
+        let cached = cache.get(&english_key).await.unwrap();
+        assert_eq!(cached.headers.get("content-language").unwrap(), "en");
+        assert_eq!(cache.get_stats().await.cache_size, 2);
+    }
+
+    #[tokio::test]
+    async fn test_learned_vary_is_bounded_by_capacity() {
+        let cache = ResponseCache::new(2);
+        let policy = test_policy(true);
+        let gzip: HashMap<String, String> = [("accept-encoding".to_string(), "gzip".to_string())].into();
+        let varies = test_response(&[("vary", "Accept-Encoding")]);
+
+        for url in ["https://api/a", "https://api/b", "https://api/c"] {
+            cache.record_vary("GET", url, &policy, &varies).await;
+        }
+        assert_eq!(cache.vary_headers.read().await.len(), 2);
+
+        // The least recently used resource is forgotten and keyed without its Vary again
+        assert_eq!(
+            cache.request_key("GET", "https://api/a", &gzip, &policy).await,
+            cache.request_key("GET", "https://api/a", &HashMap::new(), &policy).await
+        );
+        assert_ne!(
+            cache.request_key("GET", "https://api/c", &gzip, &policy).await,
+            cache.request_key("GET", "https://api/c", &HashMap::new(), &policy).await
+        );
+    }
+
+    #[tokio::test]
+    async fn test_upstream_vary_is_learned() {
+        let cache = ResponseCache::new(10);
+        let policy = test_policy(true);
+        let gzip: HashMap<String, String> = [("accept-encoding".to_string(), "gzip".to_string())].into();
+        let identity = HashMap::new();
+
+        let before = cache.request_key("GET", "https://api/data", &gzip, &policy).await;
+        assert_eq!(before, cache.request_key("GET", "https://api/data", &identity, &policy).await);
+
+        cache.record_vary("GET", "https://api/data", &policy, &test_response(&[("vary", "Accept-Encoding")])).await;
+        let after = cache.request_key("GET", "https://api/data", &gzip, &policy).await;
+        assert_ne!(after, cache.request_key("GET", "https://api/data", &identity, &policy).await);
+
+        let wildcard = test_response(&[("vary", "*")]);
+        assert_eq!(cache.ttl_for(&wildcard, &policy), None);
+    }
 }
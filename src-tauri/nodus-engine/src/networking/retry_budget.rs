@@ -0,0 +1,132 @@
+// src-tauri/src/networking/retry_budget.rs
+// Retry Budgets - Per-endpoint token buckets that cap how many retries an endpoint gets
+// When an upstream is down, requests stop retrying once its budget is spent instead of multiplying the load
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::Mutex;
+
+use crate::clock::{self, Clock};
+
+/// Retries allowed per endpoint over a window
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetryBudgetPolicy {
+    /// Retries available at once, and refilled over `window_seconds`
+    pub retries_per_window: u32,
+    pub window_seconds: u64,
+}
+
+impl Default for RetryBudgetPolicy {
+    fn default() -> Self {
+        Self {
+            retries_per_window: 20,
+            window_seconds: 60,
+        }
+    }
+}
+
+/// Budget of one endpoint, as exposed in network metrics
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetryBudgetStatus {
+    pub available: f64,
+    pub capacity: u32,
+    /// While exhausted, requests to the endpoint get a single attempt
+    pub exhausted: bool,
+    pub retries_granted: u64,
+    pub retries_denied: u64,
+}
+
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    refilled_at: Instant,
+    granted: u64,
+    denied: u64,
+}
+
+/// Retry token buckets keyed by endpoint
+#[derive(Debug)]
+pub struct RetryBudgets {
+    policy: RetryBudgetPolicy,
+    buckets: Mutex<HashMap<String, TokenBucket>>,
+    clock: Arc<dyn Clock>,
+}
+
+impl RetryBudgets {
+    pub fn new(policy: RetryBudgetPolicy) -> Self {
+        Self {
+            policy,
+            buckets: Mutex::new(HashMap::new()),
+            clock: clock::system_clock(),
+        }
+    }
+
+    /// Refill buckets by `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Whether `endpoint` has no retry to spend right now
+    pub async fn is_exhausted(&self, endpoint: &str) -> bool {
+        let mut buckets = self.buckets.lock().await;
+        buckets.get_mut(endpoint).is_some_and(|bucket| {
+            self.refill(bucket);
+            bucket.tokens < 1.0
+        })
+    }
+
+    /// Spend one retry for `endpoint`; false (and counted as denied) if the budget is exhausted
+    pub async fn try_acquire(&self, endpoint: &str) -> bool {
+        let mut buckets = self.buckets.lock().await;
+        let bucket = buckets.entry(endpoint.to_string()).or_insert_with(|| TokenBucket {
+            tokens: self.policy.retries_per_window as f64,
+            refilled_at: self.clock.instant(),
+            granted: 0,
+            denied: 0,
+        });
+        self.refill(bucket);
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            bucket.granted += 1;
+            true
+        } else {
+            bucket.denied += 1;
+            false
+        }
+    }
+
+    pub async fn status(&self) -> HashMap<String, RetryBudgetStatus> {
+        let mut buckets = self.buckets.lock().await;
+        buckets.iter_mut()
+            .map(|(endpoint, bucket)| {
+                self.refill(bucket);
+                (endpoint.clone(), RetryBudgetStatus {
+                    available: bucket.tokens,
+                    capacity: self.policy.retries_per_window,
+                    exhausted: bucket.tokens < 1.0,
+                    retries_granted: bucket.granted,
+                    retries_denied: bucket.denied,
+                })
+            })
+            .collect()
+    }
+
+    fn refill(&self, bucket: &mut TokenBucket) {
+        let capacity = self.policy.retries_per_window as f64;
+        let per_second = capacity / self.policy.window_seconds.max(1) as f64;
+        let now = self.clock.instant();
+        let elapsed = now.duration_since(bucket.refilled_at).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * per_second).min(capacity);
+        bucket.refilled_at = now;
+    }
+}
+
+impl Default for RetryBudgets {
+    fn default() -> Self {
+        Self::new(RetryBudgetPolicy::default())
+    }
+}
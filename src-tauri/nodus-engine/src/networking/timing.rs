@@ -0,0 +1,89 @@
+// src-tauri/src/networking/timing.rs
+// Request Phase Timing - Measures where time goes in an outbound request
+// DNS is timed by a custom resolver; the remaining phases are timed around send() and the body read
+
+use hyper::client::connect::dns::Name;
+use reqwest::dns::{Addrs, Resolve, Resolving};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+tokio::task_local! {
+    // Recorder for the request currently being sent on this task
+    static CURRENT_REQUEST: Arc<PhaseRecorder>;
+}
+
+/// Phase timings captured for a single request attempt
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PhaseTimings {
+    /// Name resolution; zero when a pooled connection was reused
+    pub dns_resolution: Duration,
+    /// Connection setup, request upload and server processing, up to the response headers
+    pub request: Duration,
+    /// Streaming the response body
+    pub response: Duration,
+}
+
+impl PhaseTimings {
+    pub fn total(&self) -> Duration {
+        self.dns_resolution + self.request + self.response
+    }
+}
+
+/// Collects phase timings reported from inside reqwest (the resolver) for one attempt
+#[derive(Debug, Default)]
+pub struct PhaseRecorder {
+    dns_micros: AtomicU64,
+}
+
+impl PhaseRecorder {
+    fn record_dns(&self, elapsed: Duration) {
+        self.dns_micros.fetch_add(elapsed.as_micros() as u64, Ordering::Relaxed);
+    }
+
+    fn dns(&self) -> Duration {
+        Duration::from_micros(self.dns_micros.load(Ordering::Relaxed))
+    }
+}
+
+/// Send a request, timing DNS resolution and the wait for response headers
+///
+/// `response` is left at zero for the caller to fill in once the body has been read.
+pub async fn send_timed(
+    request: reqwest::RequestBuilder,
+) -> (Result<reqwest::Response, reqwest::Error>, PhaseTimings) {
+    let recorder = Arc::new(PhaseRecorder::default());
+    let started = Instant::now();
+    let result = CURRENT_REQUEST.scope(recorder.clone(), request.send()).await;
+    let headers_elapsed = started.elapsed();
+
+    let dns_resolution = recorder.dns().min(headers_elapsed);
+    let timings = PhaseTimings {
+        dns_resolution,
+        request: headers_elapsed - dns_resolution,
+        response: Duration::ZERO,
+    };
+    (result, timings)
+}
+
+/// System resolver that reports lookup time to the request being sent
+#[derive(Debug, Default)]
+pub struct TimingResolver;
+
+impl Resolve for TimingResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        // Captured now: the lookup future may be polled after the request's scope ends
+        let recorder = CURRENT_REQUEST.try_with(Arc::clone).ok();
+        let host = name.as_str().to_string();
+
+        Box::pin(async move {
+            let started = Instant::now();
+            // Port is replaced by the connector with the one from the URL
+            let addrs: Vec<_> = tokio::net::lookup_host((host.as_str(), 0)).await?.collect();
+            if let Some(recorder) = recorder {
+                recorder.record_dns(started.elapsed());
+            }
+            Ok(Box::new(addrs.into_iter()) as Addrs)
+        })
+    }
+}
@@ -0,0 +1,538 @@
+// src-tauri/src/observability/alert_rules.rs
+// Alert Rules - Evaluates tenant AlertRule conditions against MetricsRegistry rollups
+// A rule fires once its condition has held for its dwell time and resolves as soon as it stops holding
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::multi_tenant::{AlertChannel, AlertRule, AlertSeverity, AlertingConfig};
+use crate::observability::exporters::security_sink::webhook_url;
+use crate::observability::exporters::WebhookPoster;
+use crate::observability::metrics_registry::MetricsRegistry;
+use crate::observability::rollup::{RollupStats, RollupWindow};
+use crate::security::ClassificationLevel;
+
+/// Rollup window conditions are evaluated over
+const EVALUATION_WINDOW: RollupWindow = RollupWindow::OneMinute;
+
+/// How often rules are evaluated
+pub const DEFAULT_ALERT_RULE_TICK: std::time::Duration = std::time::Duration::from_secs(15);
+
+/// Which aggregate of the rollup a condition compares
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ConditionAggregate {
+    Avg,
+    Min,
+    Max,
+    Sum,
+    Count,
+    P99,
+    Rate,
+}
+
+impl ConditionAggregate {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "avg" => Some(Self::Avg),
+            "min" => Some(Self::Min),
+            "max" => Some(Self::Max),
+            "sum" => Some(Self::Sum),
+            "count" => Some(Self::Count),
+            "p99" => Some(Self::P99),
+            "rate" => Some(Self::Rate),
+            _ => None,
+        }
+    }
+
+    fn of(&self, stats: &RollupStats) -> f64 {
+        match self {
+            Self::Avg => stats.mean(),
+            Self::Min => stats.min,
+            Self::Max => stats.max,
+            Self::Sum => stats.sum,
+            Self::Count => stats.count as f64,
+            Self::P99 => stats.p99,
+            Self::Rate => stats.rate,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Comparison {
+    GreaterThan,
+    GreaterOrEqual,
+    LessThan,
+    LessOrEqual,
+    Equal,
+    NotEqual,
+}
+
+impl Comparison {
+    fn parse(operator: &str) -> Option<Self> {
+        match operator {
+            ">" => Some(Self::GreaterThan),
+            ">=" => Some(Self::GreaterOrEqual),
+            "<" => Some(Self::LessThan),
+            "<=" => Some(Self::LessOrEqual),
+            "==" => Some(Self::Equal),
+            "!=" => Some(Self::NotEqual),
+            _ => None,
+        }
+    }
+
+    fn holds(&self, value: f64, threshold: f64) -> bool {
+        match self {
+            Self::GreaterThan => value > threshold,
+            Self::GreaterOrEqual => value >= threshold,
+            Self::LessThan => value < threshold,
+            Self::LessOrEqual => value <= threshold,
+            Self::Equal => value == threshold,
+            Self::NotEqual => value != threshold,
+        }
+    }
+}
+
+/// Parsed form of `[aggregate(]metric[)] <op> <threshold> [for <duration>]`
+///
+/// e.g. `cpu_usage_percent > 80 for 5m` or `rate(auth.failures) >= 2.5 for 30s`. A bare metric
+/// compares its average over the last minute; durations take an `s`, `m` or `h` suffix.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AlertCondition {
+    pub metric: String,
+    pub aggregate: ConditionAggregate,
+    pub comparison: Comparison,
+    pub threshold: f64,
+    /// How long the comparison must hold before the rule fires
+    pub for_duration: Duration,
+}
+
+impl AlertCondition {
+    pub fn parse(condition: &str) -> Result<Self, String> {
+        let tokens: Vec<&str> = condition.split_whitespace().collect();
+        let (expression, for_duration) = match tokens.as_slice() {
+            [metric, operator, threshold] => ([*metric, *operator, *threshold], Duration::zero()),
+            [metric, operator, threshold, "for", duration] => ([*metric, *operator, *threshold], parse_duration(duration)?),
+            _ => return Err("expected `metric <op> <threshold> [for <duration>]`".to_string()),
+        };
+        let [target, operator, threshold] = expression;
+
+        let (aggregate, metric) = match target.split_once('(') {
+            Some((aggregate, rest)) => {
+                let metric = rest.strip_suffix(')').ok_or_else(|| format!("unclosed `(` in {}", target))?;
+                let aggregate = ConditionAggregate::parse(aggregate)
+                    .ok_or_else(|| format!("unknown aggregate {}", aggregate))?;
+                (aggregate, metric)
+            }
+            None => (ConditionAggregate::Avg, target),
+        };
+        if metric.is_empty() || metric.contains(['(', ')', '{', '}']) {
+            return Err(format!("invalid metric name {:?}", metric));
+        }
+
+        let comparison = Comparison::parse(operator).ok_or_else(|| format!("unknown operator {}", operator))?;
+        let threshold: f64 = threshold.parse().map_err(|_| format!("invalid threshold {}", threshold))?;
+        if !threshold.is_finite() {
+            return Err(format!("invalid threshold {}", threshold));
+        }
+
+        Ok(Self { metric: metric.to_string(), aggregate, comparison, threshold, for_duration })
+    }
+
+    /// The compared value, and whether the comparison holds; no data never holds
+    fn evaluate(&self, stats: Option<&RollupStats>) -> Option<(f64, bool)> {
+        let value = self.aggregate.of(stats?);
+        Some((value, self.comparison.holds(value, self.threshold)))
+    }
+}
+
+fn parse_duration(duration: &str) -> Result<Duration, String> {
+    let invalid = || format!("invalid duration {}", duration);
+    let split = duration.len().checked_sub(1).filter(|&split| duration.is_char_boundary(split)).ok_or_else(invalid)?;
+    let (amount, unit) = duration.split_at(split);
+    let amount: i64 = amount.parse().map_err(|_| invalid())?;
+    if amount < 0 {
+        return Err(invalid());
+    }
+    match unit {
+        "s" => Ok(Duration::seconds(amount)),
+        "m" => Ok(Duration::minutes(amount)),
+        "h" => Ok(Duration::hours(amount)),
+        _ => Err(invalid()),
+    }
+}
+
+/// Alert raised by a rule; `resolved_at` is set once the condition stops holding
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RuleAlert {
+    pub alert_id: String,
+    pub tenant_id: String,
+    pub rule_id: String,
+    pub condition: String,
+    pub severity: AlertSeverity,
+    /// Compared value when the alert fired
+    pub value: f64,
+    pub triggered_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+impl RuleAlert {
+    fn payload(&self) -> Value {
+        let status = if self.resolved_at.is_some() { "resolved" } else { "firing" };
+        serde_json::json!({
+            "alert_id": self.alert_id,
+            "source": "nodus",
+            "status": status,
+            "text": format!("[{:?}] {} {}: {}", self.severity, self.rule_id, status, self.condition),
+            "tenant_id": self.tenant_id,
+            "rule_id": self.rule_id,
+            "condition": self.condition,
+            "severity": self.severity,
+            "value": self.value,
+            "triggered_at": self.triggered_at,
+            "resolved_at": self.resolved_at,
+        })
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AlertRuleError {
+    #[error("Invalid condition for rule {rule_id} ({condition:?}): {reason}")]
+    InvalidCondition {
+        rule_id: String,
+        condition: String,
+        reason: String,
+    },
+}
+
+/// A rule with its parsed condition and dwell/firing state
+#[derive(Debug)]
+struct RuleState {
+    rule: AlertRule,
+    condition: AlertCondition,
+    /// Since when the condition has held without interruption
+    holding_since: Option<DateTime<Utc>>,
+    firing: Option<RuleAlert>,
+}
+
+#[derive(Debug, Default)]
+struct TenantRules {
+    rules: Vec<RuleState>,
+    channels: Vec<AlertChannel>,
+}
+
+/// Evaluates every tenant's alert rules against the metrics registry
+///
+/// Metrics are matched by their `tenant` label. Firing and resolution are both posted to the
+/// rule's channels, which are looked up in the tenant's `alert_channels` by `name` or type.
+#[derive(Debug)]
+pub struct AlertRuleEngine {
+    metrics: Arc<MetricsRegistry>,
+    poster: Arc<dyn WebhookPoster>,
+    tenants: RwLock<HashMap<String, TenantRules>>,
+}
+
+impl AlertRuleEngine {
+    pub fn new(metrics: Arc<MetricsRegistry>, poster: Arc<dyn WebhookPoster>) -> Self {
+        Self { metrics, poster, tenants: RwLock::new(HashMap::new()) }
+    }
+
+    /// Load a tenant's rules and channels, replacing its previous ones
+    ///
+    /// Every condition is parsed up front, so a bad rule rejects the whole config. Alerts of
+    /// rules that remain (same `rule_id` and condition) keep firing across the reload.
+    pub async fn set_tenant_config(&self, tenant_id: &str, config: &AlertingConfig) -> Result<(), AlertRuleError> {
+        let parsed = parse_rules(config)?;
+
+        let mut tenants = self.tenants.write().await;
+        let mut previous = tenants.remove(tenant_id).unwrap_or_default();
+        let rules = parsed.into_iter()
+            .map(|(rule, condition)| {
+                let kept = previous.rules.iter()
+                    .position(|state| state.rule.rule_id == rule.rule_id && state.condition == condition)
+                    .map(|index| previous.rules.swap_remove(index));
+                RuleState {
+                    holding_since: kept.as_ref().and_then(|state| state.holding_since),
+                    firing: kept.and_then(|state| state.firing),
+                    rule,
+                    condition,
+                }
+            })
+            .collect();
+        tenants.insert(tenant_id.to_string(), TenantRules { rules, channels: config.alert_channels.clone() });
+        Ok(())
+    }
+
+    pub async fn remove_tenant(&self, tenant_id: &str) {
+        self.tenants.write().await.remove(tenant_id);
+    }
+
+    /// Alerts currently firing for `tenant_id`
+    pub async fn active_alerts(&self, tenant_id: &str) -> Vec<RuleAlert> {
+        self.tenants.read().await.get(tenant_id)
+            .map(|tenant| tenant.rules.iter().filter_map(|state| state.firing.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Evaluate every rule now; see `evaluate_at`
+    pub async fn evaluate(&self) -> Vec<RuleAlert> {
+        self.evaluate_at(Utc::now()).await
+    }
+
+    /// Evaluate every rule at `now` and notify the channels of those that fired or resolved
+    ///
+    /// Returns the alerts that changed state; resolved ones carry `resolved_at`.
+    pub async fn evaluate_at(&self, now: DateTime<Utc>) -> Vec<RuleAlert> {
+        let mut changed = Vec::new();
+        let mut notifications = Vec::new();
+        {
+            let mut tenants = self.tenants.write().await;
+            for (tenant_id, tenant) in tenants.iter_mut() {
+                for state in &mut tenant.rules {
+                    let Some(alert) = self.step(tenant_id, state, now) else {
+                        continue;
+                    };
+                    let urls: Vec<String> = state.rule.channels.iter()
+                        .filter_map(|name| channel_named(&tenant.channels, name))
+                        .filter_map(webhook_url)
+                        .map(str::to_string)
+                        .collect();
+                    notifications.push((urls, alert.payload()));
+                    changed.push(alert);
+                }
+            }
+        }
+
+        for (urls, payload) in notifications {
+            for url in urls {
+                if let Err(e) = self.poster.post(&url, &payload, ClassificationLevel::Internal).await {
+                    tracing::warn!("Alert notification to {} failed: {}", url, e);
+                }
+            }
+        }
+        changed
+    }
+
+    /// Advance one rule to `now`, returning its alert if it fired or resolved
+    fn step(&self, tenant_id: &str, state: &mut RuleState, now: DateTime<Utc>) -> Option<RuleAlert> {
+        let stats = self.metrics.tenant_rollup_at(&state.condition.metric, tenant_id, EVALUATION_WINDOW, now);
+        let evaluation = state.condition.evaluate(stats.as_ref());
+
+        match evaluation {
+            Some((value, true)) => {
+                let holding_since = *state.holding_since.get_or_insert(now);
+                if state.firing.is_some() || now - holding_since < state.condition.for_duration {
+                    return None;
+                }
+                let alert = RuleAlert {
+                    alert_id: Uuid::new_v4().to_string(),
+                    tenant_id: tenant_id.to_string(),
+                    rule_id: state.rule.rule_id.clone(),
+                    condition: state.rule.condition.clone(),
+                    severity: state.rule.severity.clone(),
+                    value,
+                    triggered_at: now,
+                    resolved_at: None,
+                };
+                tracing::warn!(tenant_id = %tenant_id, rule_id = %alert.rule_id, "Alert rule fired: {} (value {})", alert.condition, value);
+                state.firing = Some(alert.clone());
+                Some(alert)
+            }
+            _ => {
+                state.holding_since = None;
+                let mut alert = state.firing.take()?;
+                alert.resolved_at = Some(now);
+                tracing::info!(tenant_id = %tenant_id, rule_id = %alert.rule_id, "Alert rule resolved: {}", alert.condition);
+                Some(alert)
+            }
+        }
+    }
+
+    /// Evaluate rules every `tick` until the runtime shuts down
+    ///
+    /// The tick bounds how precisely `for` durations are honoured.
+    pub fn spawn(self: Arc<Self>, tick: std::time::Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(tick);
+            loop {
+                ticker.tick().await;
+                self.evaluate().await;
+            }
+        })
+    }
+}
+
+/// Check that every rule of `config` parses, e.g. before accepting a tenant config
+pub fn validate_rules(config: &AlertingConfig) -> Result<(), AlertRuleError> {
+    parse_rules(config).map(drop)
+}
+
+fn parse_rules(config: &AlertingConfig) -> Result<Vec<(AlertRule, AlertCondition)>, AlertRuleError> {
+    config.alert_rules.iter()
+        .map(|rule| {
+            let condition = AlertCondition::parse(&rule.condition).map_err(|reason| AlertRuleError::InvalidCondition {
+                rule_id: rule.rule_id.clone(),
+                condition: rule.condition.clone(),
+                reason,
+            })?;
+            Ok((rule.clone(), condition))
+        })
+        .collect()
+}
+
+/// Channel a rule refers to: by `configuration.name`, else by channel type
+fn channel_named<'a>(channels: &'a [AlertChannel], name: &str) -> Option<&'a AlertChannel> {
+    channels.iter()
+        .find(|channel| channel.configuration.get("name").and_then(Value::as_str) == Some(name))
+        .or_else(|| channels.iter().find(|channel| channel.channel_type == name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::observability::exporters::security_sink::tests::RecordingPoster;
+
+    fn config(condition: &str) -> AlertingConfig {
+        AlertingConfig {
+            real_time_alerts: true,
+            alert_channels: vec![AlertChannel {
+                channel_type: "slack".to_string(),
+                configuration: serde_json::json!({"name": "ops", "url": "https://hooks.example.com/ops"}),
+            }],
+            alert_rules: vec![AlertRule {
+                rule_id: "cpu-high".to_string(),
+                condition: condition.to_string(),
+                severity: AlertSeverity::High,
+                channels: vec!["ops".to_string()],
+            }],
+        }
+    }
+
+    /// Registry with a `cpu_usage_percent` gauge series for tenants acme and globex
+    async fn registry() -> Arc<MetricsRegistry> {
+        let registry = Arc::new(MetricsRegistry::new());
+        registry.register_gauge("cpu_usage_percent").await.unwrap();
+        for tenant_id in ["acme", "globex"] {
+            let labels = HashMap::from([("tenant".to_string(), tenant_id.to_string())]);
+            registry.record("cpu_usage_percent", 0.0, &labels).await.unwrap();
+        }
+        registry
+    }
+
+    /// Tests run a day ahead, clear of the samples `registry()` recorded at the real time
+    fn start() -> DateTime<Utc> {
+        Utc::now() + Duration::days(1)
+    }
+
+    fn record_cpu(registry: &MetricsRegistry, tenant_id: &str, value: f64, at: DateTime<Utc>) {
+        registry.record_rollup(&format!("cpu_usage_percent{{tenant=\"{}\"}}", tenant_id), value, at);
+    }
+
+    #[test]
+    fn test_condition_grammar() {
+        let condition = AlertCondition::parse("cpu_usage_percent > 80 for 5m").unwrap();
+        assert_eq!(condition.metric, "cpu_usage_percent");
+        assert_eq!((condition.aggregate, condition.comparison, condition.threshold), (ConditionAggregate::Avg, Comparison::GreaterThan, 80.0));
+        assert_eq!(condition.for_duration, Duration::minutes(5));
+
+        let condition = AlertCondition::parse("rate(auth.failures) >= 2.5").unwrap();
+        assert_eq!((condition.aggregate, condition.for_duration), (ConditionAggregate::Rate, Duration::zero()));
+
+        for invalid in ["cpu > 80 for 5", "cpu >> 80", "cpu > high", "median(cpu) > 1", "cpu > 80 during 5m", ""] {
+            assert!(AlertCondition::parse(invalid).is_err(), "{} should not parse", invalid);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_threshold_crossing_fires_for_tenant_only() {
+        let registry = registry().await;
+        let poster = Arc::new(RecordingPoster::default());
+        let engine = AlertRuleEngine::new(registry.clone(), poster.clone());
+        engine.set_tenant_config("acme", &config("cpu_usage_percent > 80")).await.unwrap();
+        engine.set_tenant_config("globex", &config("cpu_usage_percent > 80")).await.unwrap();
+
+        let start = start();
+        record_cpu(&registry, "acme", 50.0, start);
+        record_cpu(&registry, "globex", 95.0, start);
+        assert!(engine.evaluate_at(start).await.iter().all(|alert| alert.tenant_id == "globex"));
+
+        record_cpu(&registry, "acme", 150.0, start + Duration::seconds(10));
+        let fired = engine.evaluate_at(start + Duration::seconds(10)).await;
+        assert_eq!(fired.len(), 1);
+        assert_eq!((fired[0].tenant_id.as_str(), fired[0].value), ("acme", 100.0));
+
+        // Still holding: no second notification
+        assert!(engine.evaluate_at(start + Duration::seconds(20)).await.is_empty());
+        let posts = poster.posts.lock().unwrap();
+        assert_eq!(posts.len(), 2);
+        assert!(posts.iter().all(|(url, payload)| url == "https://hooks.example.com/ops" && payload["status"] == "firing"));
+    }
+
+    #[tokio::test]
+    async fn test_for_duration_requires_sustained_condition() {
+        let registry = registry().await;
+        let poster = Arc::new(RecordingPoster::default());
+        let engine = AlertRuleEngine::new(registry.clone(), poster.clone());
+        engine.set_tenant_config("acme", &config("max(cpu_usage_percent) > 80 for 2m")).await.unwrap();
+
+        let start = start();
+        let at = |seconds| start + Duration::seconds(seconds);
+        record_cpu(&registry, "acme", 90.0, at(0));
+        assert!(engine.evaluate_at(at(0)).await.is_empty());
+        record_cpu(&registry, "acme", 90.0, at(60));
+        assert!(engine.evaluate_at(at(60)).await.is_empty());
+
+        // The window empties out, breaking the dwell; it starts over from the next breach
+        assert!(engine.evaluate_at(at(125)).await.is_empty());
+        record_cpu(&registry, "acme", 90.0, at(130));
+        assert!(engine.evaluate_at(at(130)).await.is_empty());
+        record_cpu(&registry, "acme", 90.0, at(190));
+        assert!(engine.evaluate_at(at(190)).await.is_empty());
+        record_cpu(&registry, "acme", 90.0, at(250));
+        let fired = engine.evaluate_at(at(250)).await;
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].triggered_at, at(250));
+        assert_eq!(engine.active_alerts("acme").await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_alert_resolves_when_condition_clears() {
+        let registry = registry().await;
+        let poster = Arc::new(RecordingPoster::default());
+        let engine = AlertRuleEngine::new(registry.clone(), poster.clone());
+        engine.set_tenant_config("acme", &config("cpu_usage_percent > 80")).await.unwrap();
+
+        let start = start();
+        record_cpu(&registry, "acme", 95.0, start);
+        assert_eq!(engine.evaluate_at(start).await.len(), 1);
+
+        // Reloading an unchanged rule keeps it firing
+        engine.set_tenant_config("acme", &config("cpu_usage_percent > 80")).await.unwrap();
+        assert_eq!(engine.active_alerts("acme").await.len(), 1);
+
+        // The breach ages out of the window, and the minute's average drops under the threshold
+        record_cpu(&registry, "acme", 20.0, start + Duration::seconds(90));
+        let resolved = engine.evaluate_at(start + Duration::seconds(90)).await;
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(resolved[0].resolved_at, Some(start + Duration::seconds(90)));
+        assert!(engine.active_alerts("acme").await.is_empty());
+
+        let posts = poster.posts.lock().unwrap();
+        assert_eq!(posts.iter().map(|(_, payload)| payload["status"].as_str().unwrap()).collect::<Vec<_>>(), ["firing", "resolved"]);
+        assert_eq!(posts[0].1["alert_id"], posts[1].1["alert_id"]);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_rule_rejects_config() {
+        let engine = AlertRuleEngine::new(registry().await, Arc::new(RecordingPoster::default()));
+        let error = engine.set_tenant_config("acme", &config("cpu_usage_percent above 80")).await.unwrap_err();
+        assert!(matches!(error, AlertRuleError::InvalidCondition { ref rule_id, .. } if rule_id == "cpu-high"));
+        assert!(validate_rules(&config("cpu_usage_percent above 80")).is_err());
+        assert!(validate_rules(&config("cpu_usage_percent > 80 for 5m")).is_ok());
+    }
+}
@@ -0,0 +1,520 @@
+// src-tauri/src/observability/audit_export.rs
+// Scheduled Audit Export - Runs each tenant's AuditExportConfig at its cadence to its destination
+// A persisted per-tenant watermark makes every envelope go out once, across runs and restarts
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::multi_tenant::{AuditExportConfig, ExportDestination, ExportFormat, TenantAuditConfig};
+use crate::observability::exporters::{cef, CefFormatter, ExportError};
+use crate::observability::forensic_logger::ForensicError;
+use crate::observability::{ForensicEnvelope, ForensicLogger};
+
+/// Most envelopes loaded and delivered in one batch; a run keeps going until it has caught up
+pub const EXPORT_BATCH_SIZE: usize = 10_000;
+
+/// How often the scheduler checks for due exports
+pub const DEFAULT_EXPORT_TICK: Duration = Duration::from_secs(60);
+
+/// File persisting export progress across restarts; unset keeps progress in memory
+pub const AUDIT_EXPORT_STATE_ENV: &str = "NODUS_AUDIT_EXPORT_STATE";
+
+/// Position of the last exported envelope; envelopes are exported in (timestamp, id) order
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct ExportWatermark {
+    pub timestamp: DateTime<Utc>,
+    pub envelope_id: Uuid,
+}
+
+impl ExportWatermark {
+    pub fn of(envelope: &ForensicEnvelope) -> Self {
+        Self { timestamp: envelope.timestamp, envelope_id: envelope.envelope_id }
+    }
+
+    /// Whether `envelope` comes after this watermark and so still needs exporting
+    pub fn precedes(&self, envelope: &ForensicEnvelope) -> bool {
+        *self < Self::of(envelope)
+    }
+}
+
+/// Export progress of one tenant, persisted across restarts
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AuditExportStatus {
+    pub watermark: Option<ExportWatermark>,
+    pub last_run_at: Option<DateTime<Utc>>,
+    pub last_success_at: Option<DateTime<Utc>>,
+    pub last_error: Option<String>,
+    pub consecutive_failures: u32,
+    pub envelopes_exported: u64,
+}
+
+/// Envelopes of one tenant rendered in its configured format
+#[derive(Debug, Clone)]
+pub struct AuditExportBatch {
+    pub tenant_id: String,
+    pub format: ExportFormat,
+    pub payload: Vec<u8>,
+    pub envelope_count: usize,
+    pub first: ExportWatermark,
+    pub last: ExportWatermark,
+}
+
+/// Forensic envelopes available for export
+#[async_trait::async_trait]
+pub trait AuditEnvelopeSource: Send + Sync {
+    /// Envelopes of `tenant_id` after `watermark` (all if None), oldest first, at most `limit`
+    async fn envelopes_after(
+        &self,
+        tenant_id: &str,
+        watermark: Option<&ExportWatermark>,
+        limit: usize,
+    ) -> Result<Vec<ForensicEnvelope>, ForensicError>;
+}
+
+/// Where a batch goes, selected by `ExportDestination::destination_type`
+#[async_trait::async_trait]
+pub trait AuditExportSink: Send + Sync {
+    async fn deliver(&self, destination: &ExportDestination, batch: &AuditExportBatch) -> Result<(), ExportError>;
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AuditExportError {
+    #[error("Audit source error: {0}")]
+    Source(#[from] ForensicError),
+
+    #[error("Export delivery failed: {0}")]
+    Delivery(#[from] ExportError),
+
+    #[error("No audit export destination of type {0}")]
+    UnknownDestination(String),
+
+    #[error("Audit export state error: {0}")]
+    State(String),
+}
+
+#[async_trait::async_trait]
+impl AuditEnvelopeSource for ForensicLogger {
+    /// Pages through `forensic_log` by tenant, resuming after the watermark
+    async fn envelopes_after(
+        &self,
+        tenant_id: &str,
+        watermark: Option<&ExportWatermark>,
+        limit: usize,
+    ) -> Result<Vec<ForensicEnvelope>, ForensicError> {
+        self.database()
+            .tenant_forensic_envelopes_after(
+                tenant_id,
+                watermark.map(|watermark| (watermark.timestamp, watermark.envelope_id)),
+                limit,
+            )
+            .await
+            .map_err(|e| ForensicError::DatabaseError(e.to_string()))
+    }
+}
+
+/// Writes each batch to a new file in `configuration.directory` (destination type "file")
+#[derive(Debug, Default)]
+pub struct FileExportSink;
+
+#[async_trait::async_trait]
+impl AuditExportSink for FileExportSink {
+    async fn deliver(&self, destination: &ExportDestination, batch: &AuditExportBatch) -> Result<(), ExportError> {
+        let directory = destination.configuration.get("directory")
+            .and_then(|directory| directory.as_str())
+            .ok_or_else(|| ExportError::Custom("File export destination needs a directory".to_string()))?;
+        let extension = match batch.format {
+            ExportFormat::JSON => "json",
+            ExportFormat::CSV => "csv",
+            ExportFormat::SIEM | ExportFormat::CEF => "cef",
+        };
+        // Named after the first envelope, so a retried batch overwrites its earlier attempt
+        let path = PathBuf::from(directory).join(format!(
+            "audit-{}-{}-{}.{}",
+            batch.tenant_id,
+            batch.first.timestamp.format("%Y%m%dT%H%M%S"),
+            batch.first.envelope_id,
+            extension,
+        ));
+
+        tokio::fs::create_dir_all(directory).await
+            .map_err(|e| ExportError::IOError(e.to_string()))?;
+        tokio::fs::write(&path, &batch.payload).await
+            .map_err(|e| ExportError::IOError(e.to_string()))
+    }
+}
+
+/// Render envelopes in a tenant's configured export format
+pub fn render_envelopes(format: &ExportFormat, envelopes: &[ForensicEnvelope]) -> Result<Vec<u8>, ForensicError> {
+    match format {
+        ExportFormat::JSON => serde_json::to_vec(envelopes)
+            .map_err(|e| ForensicError::SerializationError(e.to_string())),
+        ExportFormat::CSV => ForensicLogger::export_to_csv(envelopes).map(String::into_bytes),
+        ExportFormat::SIEM | ExportFormat::CEF => {
+            Ok(CefFormatter::default().format_envelopes(envelopes, Some(&cef::default_redaction())).into_bytes())
+        }
+    }
+}
+
+/// Runs per-tenant audit exports when they fall due
+///
+/// The watermark is persisted after each tenant's run, so a crash mid-run can re-deliver
+/// that run's batches; otherwise each envelope is exported exactly once.
+pub struct AuditExportScheduler {
+    source: Arc<dyn AuditEnvelopeSource>,
+    sinks: HashMap<String, Arc<dyn AuditExportSink>>,
+    jobs: tokio::sync::RwLock<HashMap<String, AuditExportConfig>>,
+    state_path: Option<PathBuf>,
+    state: tokio::sync::Mutex<HashMap<String, AuditExportStatus>>,
+}
+
+impl std::fmt::Debug for AuditExportScheduler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AuditExportScheduler")
+            .field("destinations", &self.sinks.keys().collect::<Vec<_>>())
+            .field("state_path", &self.state_path)
+            .finish_non_exhaustive()
+    }
+}
+
+impl AuditExportScheduler {
+    /// Scheduler with in-memory progress and the built-in "file" destination
+    pub fn new(source: Arc<dyn AuditEnvelopeSource>) -> Self {
+        Self {
+            source,
+            sinks: HashMap::new(),
+            jobs: tokio::sync::RwLock::new(HashMap::new()),
+            state_path: None,
+            state: tokio::sync::Mutex::new(HashMap::new()),
+        }
+        .with_destination("file", Arc::new(FileExportSink))
+    }
+
+    /// Scheduler persisting progress to a JSON file, resuming from any progress saved there
+    pub async fn open(path: impl Into<PathBuf>, source: Arc<dyn AuditEnvelopeSource>) -> Result<Self, AuditExportError> {
+        let mut scheduler = Self::new(source);
+        let path = path.into();
+
+        match tokio::fs::read(&path).await {
+            Ok(contents) => {
+                let state = serde_json::from_slice(&contents)
+                    .map_err(|e| AuditExportError::State(e.to_string()))?;
+                scheduler.state = tokio::sync::Mutex::new(state);
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(AuditExportError::State(e.to_string())),
+        }
+
+        scheduler.state_path = Some(path);
+        Ok(scheduler)
+    }
+
+    /// Deliver to destinations of `destination_type` through `sink`
+    pub fn with_destination(mut self, destination_type: &str, sink: Arc<dyn AuditExportSink>) -> Self {
+        self.sinks.insert(destination_type.to_string(), sink);
+        self
+    }
+
+    /// Schedule (or unschedule, when it has no export config) a tenant's export
+    pub async fn configure_tenant(&self, tenant_id: &str, audit_config: &TenantAuditConfig) {
+        let mut jobs = self.jobs.write().await;
+        match &audit_config.export_config {
+            Some(export_config) => jobs.insert(tenant_id.to_string(), export_config.clone()),
+            None => jobs.remove(tenant_id),
+        };
+    }
+
+    /// Stop exporting for a tenant; its progress is kept
+    pub async fn remove_tenant(&self, tenant_id: &str) {
+        self.jobs.write().await.remove(tenant_id);
+    }
+
+    pub async fn status(&self) -> HashMap<String, AuditExportStatus> {
+        self.state.lock().await.clone()
+    }
+
+    /// Run every export due now
+    pub async fn run_due(&self) -> HashMap<String, Result<usize, AuditExportError>> {
+        self.run_due_at(Utc::now()).await
+    }
+
+    /// Run every export due at `now`, keyed by tenant with the number of envelopes exported
+    pub async fn run_due_at(&self, now: DateTime<Utc>) -> HashMap<String, Result<usize, AuditExportError>> {
+        let jobs = self.jobs.read().await.clone();
+        let mut state = self.state.lock().await;
+        let mut results = HashMap::new();
+
+        for (tenant_id, config) in jobs {
+            let status = state.entry(tenant_id.clone()).or_default();
+            // A failed run is retried on the next tick rather than a full interval later
+            let due = status.last_success_at
+                .map_or(true, |last_success| now >= last_success + config.export_frequency.interval());
+            if !due {
+                continue;
+            }
+
+            status.last_run_at = Some(now);
+            let result = self.export_tenant(&tenant_id, &config, status).await;
+            match &result {
+                Ok(exported) => {
+                    status.last_success_at = Some(now);
+                    status.last_error = None;
+                    status.consecutive_failures = 0;
+                    tracing::info!(tenant_id = %tenant_id, exported, "Audit export delivered envelopes");
+                }
+                Err(e) => {
+                    status.last_error = Some(e.to_string());
+                    status.consecutive_failures += 1;
+                    tracing::error!(
+                        "Audit export for tenant {} failed ({} in a row): {}",
+                        tenant_id,
+                        status.consecutive_failures,
+                        e
+                    );
+                }
+            }
+
+            if let Err(e) = self.persist(&state).await {
+                tracing::error!("Failed to persist audit export state: {}", e);
+            }
+            results.insert(tenant_id, result);
+        }
+
+        results
+    }
+
+    /// Export everything after the tenant's watermark, advancing it batch by batch
+    async fn export_tenant(
+        &self,
+        tenant_id: &str,
+        config: &AuditExportConfig,
+        status: &mut AuditExportStatus,
+    ) -> Result<usize, AuditExportError> {
+        let destination_type = &config.destination.destination_type;
+        let sink = self.sinks.get(destination_type)
+            .ok_or_else(|| AuditExportError::UnknownDestination(destination_type.clone()))?;
+
+        let mut exported = 0;
+        loop {
+            let envelopes = self.source
+                .envelopes_after(tenant_id, status.watermark.as_ref(), EXPORT_BATCH_SIZE)
+                .await?;
+            let (first, last) = match (envelopes.first(), envelopes.last()) {
+                (Some(first), Some(last)) => (ExportWatermark::of(first), ExportWatermark::of(last)),
+                _ => return Ok(exported),
+            };
+
+            let batch = AuditExportBatch {
+                tenant_id: tenant_id.to_string(),
+                format: config.export_format.clone(),
+                payload: render_envelopes(&config.export_format, &envelopes)?,
+                envelope_count: envelopes.len(),
+                first,
+                last: last.clone(),
+            };
+            sink.deliver(&config.destination, &batch).await?;
+
+            status.watermark = Some(last);
+            status.envelopes_exported += batch.envelope_count as u64;
+            exported += batch.envelope_count;
+            if envelopes.len() < EXPORT_BATCH_SIZE {
+                return Ok(exported);
+            }
+        }
+    }
+
+    /// Rewrite the state file atomically (temp file + rename)
+    async fn persist(&self, state: &HashMap<String, AuditExportStatus>) -> Result<(), AuditExportError> {
+        let path = match &self.state_path {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+
+        let data = serde_json::to_vec_pretty(state)
+            .map_err(|e| AuditExportError::State(e.to_string()))?;
+        let tmp = path.with_extension("tmp");
+        tokio::fs::write(&tmp, &data).await
+            .map_err(|e| AuditExportError::State(e.to_string()))?;
+        tokio::fs::rename(&tmp, path).await
+            .map_err(|e| AuditExportError::State(e.to_string()))
+    }
+
+    /// Check for due exports every `tick` until the runtime shuts down
+    pub fn spawn(self: Arc<Self>, tick: Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(tick);
+            loop {
+                ticker.tick().await;
+                self.run_due().await;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::multi_tenant::{AlertingConfig, ExportFrequency};
+    use crate::security::ClassificationLevel;
+    use std::sync::Mutex;
+
+    /// Tenant of an envelope, as recorded in its metadata by the forensic logger
+    fn envelope_tenant(envelope: &ForensicEnvelope) -> Option<&str> {
+        envelope.metadata.get("tenant_id")?.as_str()
+    }
+
+    #[derive(Default)]
+    struct MemorySource {
+        envelopes: Mutex<Vec<ForensicEnvelope>>,
+    }
+
+    impl MemorySource {
+        fn log(&self, tenant_id: &str, timestamp: DateTime<Utc>) -> Uuid {
+            let mut envelope = ForensicEnvelope::new(
+                Uuid::new_v4(),
+                "tenant.operation",
+                "clerk",
+                Uuid::new_v4(),
+                ClassificationLevel::Internal,
+                "update",
+            )
+            .with_metadata(serde_json::json!({"tenant_id": tenant_id}));
+            envelope.timestamp = timestamp;
+            let id = envelope.envelope_id;
+            self.envelopes.lock().unwrap().push(envelope);
+            id
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl AuditEnvelopeSource for MemorySource {
+        async fn envelopes_after(
+            &self,
+            tenant_id: &str,
+            watermark: Option<&ExportWatermark>,
+            limit: usize,
+        ) -> Result<Vec<ForensicEnvelope>, ForensicError> {
+            let mut envelopes: Vec<ForensicEnvelope> = self.envelopes.lock().unwrap().iter()
+                .filter(|envelope| envelope_tenant(envelope) == Some(tenant_id))
+                .filter(|envelope| watermark.map_or(true, |watermark| watermark.precedes(envelope)))
+                .cloned()
+                .collect();
+            envelopes.sort_by_key(ExportWatermark::of);
+            envelopes.truncate(limit);
+            Ok(envelopes)
+        }
+    }
+
+    /// Destination recording the envelope ids of every JSON batch it receives
+    #[derive(Default)]
+    struct RecordingSink {
+        delivered: Mutex<Vec<Uuid>>,
+        fail: std::sync::atomic::AtomicBool,
+    }
+
+    #[async_trait::async_trait]
+    impl AuditExportSink for RecordingSink {
+        async fn deliver(&self, _destination: &ExportDestination, batch: &AuditExportBatch) -> Result<(), ExportError> {
+            if self.fail.load(std::sync::atomic::Ordering::Relaxed) {
+                return Err(ExportError::NetworkError("SIEM unreachable".to_string()));
+            }
+            let envelopes: Vec<ForensicEnvelope> = serde_json::from_slice(&batch.payload).unwrap();
+            assert_eq!(envelopes.len(), batch.envelope_count);
+            self.delivered.lock().unwrap().extend(envelopes.iter().map(|envelope| envelope.envelope_id));
+            Ok(())
+        }
+    }
+
+    fn hourly_json_export() -> TenantAuditConfig {
+        TenantAuditConfig {
+            retention_days: 365,
+            export_config: Some(AuditExportConfig {
+                export_frequency: ExportFrequency::Hourly,
+                export_format: ExportFormat::JSON,
+                destination: ExportDestination {
+                    destination_type: "memory".to_string(),
+                    configuration: serde_json::json!({}),
+                },
+            }),
+            alerting_config: AlertingConfig {
+                real_time_alerts: false,
+                alert_channels: vec![],
+                alert_rules: vec![],
+            },
+            compliance_frameworks: vec![],
+        }
+    }
+
+    async fn scheduler(path: &std::path::Path, source: &Arc<MemorySource>, sink: &Arc<RecordingSink>) -> AuditExportScheduler {
+        let scheduler = AuditExportScheduler::open(path, source.clone())
+            .await
+            .unwrap()
+            .with_destination("memory", sink.clone());
+        scheduler.configure_tenant("acme", &hourly_json_export()).await;
+        scheduler
+    }
+
+    #[tokio::test]
+    async fn test_new_envelopes_exported_exactly_once_across_runs_and_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let state_path = dir.path().join("audit_export_state.json");
+        let source = Arc::new(MemorySource::default());
+        let sink = Arc::new(RecordingSink::default());
+        let t0 = Utc::now();
+
+        let mut expected: Vec<Uuid> = (1..=3)
+            .map(|i| source.log("acme", t0 - chrono::Duration::minutes(10 - i)))
+            .collect();
+        source.log("globex", t0);
+
+        let first = scheduler(&state_path, &source, &sink).await;
+        let results = first.run_due_at(t0).await;
+        assert_eq!(*results["acme"].as_ref().unwrap(), 3);
+
+        // New records arrive, but nothing runs until the next hour
+        expected.push(source.log("acme", t0 + chrono::Duration::minutes(5)));
+        expected.push(source.log("acme", t0 + chrono::Duration::minutes(5)));
+        assert!(first.run_due_at(t0 + chrono::Duration::minutes(30)).await.is_empty());
+        drop(first);
+
+        // After a restart the persisted watermark picks up exactly where the last run stopped
+        let second = scheduler(&state_path, &source, &sink).await;
+        let results = second.run_due_at(t0 + chrono::Duration::hours(1)).await;
+        assert_eq!(*results["acme"].as_ref().unwrap(), 2);
+
+        expected[3..].sort();
+        assert_eq!(*sink.delivered.lock().unwrap(), expected);
+        let status = &second.status().await["acme"];
+        assert_eq!(status.envelopes_exported, 5);
+        assert_eq!(status.last_success_at, Some(t0 + chrono::Duration::hours(1)));
+        assert!(status.last_error.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_failed_export_keeps_watermark_and_retries() {
+        let source = Arc::new(MemorySource::default());
+        let sink = Arc::new(RecordingSink::default());
+        let scheduler = AuditExportScheduler::new(source.clone()).with_destination("memory", sink.clone());
+        scheduler.configure_tenant("acme", &hourly_json_export()).await;
+        let t0 = Utc::now();
+        let id = source.log("acme", t0);
+
+        sink.fail.store(true, std::sync::atomic::Ordering::Relaxed);
+        assert!(matches!(scheduler.run_due_at(t0).await["acme"], Err(AuditExportError::Delivery(_))));
+        let status = &scheduler.status().await["acme"];
+        assert!(status.watermark.is_none());
+        assert_eq!(status.consecutive_failures, 1);
+        assert!(status.last_error.as_deref().unwrap().contains("SIEM unreachable"));
+
+        // Retried on the next tick, not an hour later
+        sink.fail.store(false, std::sync::atomic::Ordering::Relaxed);
+        let results = scheduler.run_due_at(t0 + chrono::Duration::minutes(1)).await;
+        assert_eq!(*results["acme"].as_ref().unwrap(), 1);
+        assert_eq!(*sink.delivered.lock().unwrap(), vec![id]);
+        assert_eq!(scheduler.status().await["acme"].consecutive_failures, 0);
+    }
+}
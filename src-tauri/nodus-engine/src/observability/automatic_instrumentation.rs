@@ -6,11 +6,20 @@ use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use std::collections::HashMap;
-use lru::LruCache;
-use std::num::NonZeroUsize;
 use uuid::Uuid;
 
-use crate::observability::{ObservabilityContext, InstrumentationDecision, PerformanceState};
+use crate::cache::TtlCache;
+use crate::clock::{self, Clock};
+use crate::observability::{ForensicEnvelope, ForensicLogger, MetricsRegistry, ObservabilityContext, InstrumentationDecision, PerformanceBudget, PerformanceState};
+use crate::observability::forensic_logger::ForensicError;
+use crate::observability::exporters::{ExportEngine, ExportReport};
+use crate::observability::budget::{BudgetBreach, BudgetEscalator, CircuitOpen, OperationBudgets};
+use crate::observability::performance_state::{self, PerformanceStateMonitor};
+use crate::observability::qos::{QosError, QosScheduler};
+use crate::observability::sampling::TailSampler;
+use crate::observability::observation::{
+    ObservationContext, ObservationRecord, OperationResult, PerformanceMetrics, SecurityEvent,
+};
 use crate::security::{ClassificationLevel, SecurityLabel};
 use crate::license::LicenseManager;
 use crate::state::AppState;
@@ -20,24 +29,37 @@ use crate::state::AppState;
 #[derive(Debug)]
 pub struct AutomaticInstrumentation {
     // Pre-computed instrumentation decisions for sub-0.1ms performance
-    decision_cache: Arc<RwLock<LruCache<String, CachedDecision>>>,
+    decision_cache: Arc<RwLock<TtlCache<String, InstrumentationDecision>>>,
     
     // Policy engine for runtime decisions
     policy_engine: PolicyEngine,
     
     // Performance state tracking
     performance_monitor: PerformanceMonitor,
+
+    // Platform state derived from live load; the published state without one
+    performance_state_monitor: Option<Arc<PerformanceStateMonitor>>,
     
     // Enterprise feature gates
     license_manager: Arc<LicenseManager>,
-}
 
-/// Cached instrumentation decision with timestamp
-#[derive(Debug, Clone)]
-struct CachedDecision {
-    decision: InstrumentationDecision,
-    created_at: chrono::DateTime<chrono::Utc>,
-    hit_count: u64,
+    // Receives an observation record for every instrumented operation
+    export_engine: Option<Arc<ExportEngine>>,
+
+    // Drops routine successes after they complete; every record is exported without one
+    tail_sampler: Option<Arc<TailSampler>>,
+
+    // Runs policy-driven remediation for operations over their budget
+    budget_escalator: Option<Arc<BudgetEscalator>>,
+
+    // Caps concurrent operations per QoS class; unlimited without one
+    qos_scheduler: Option<Arc<QosScheduler>>,
+
+    // How long each component's operations may take, from the observability policy
+    operation_budgets: OperationBudgets,
+
+    // Times operations and the instrumentation around them
+    clock: Arc<dyn Clock>,
 }
 
 /// Policy engine for instrumentation decisions
@@ -78,12 +100,14 @@ struct ComponentPolicy {
 }
 
 /// Performance-based instrumentation policy
+///
+/// Forensic operations (see `requires_forensic_audit`) always keep their audit trail.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct PerformancePolicy {
-    pub reduce_instrumentation: bool,
-    pub priority_operations_only: bool,
-    pub disable_payload_logging: bool,
-    pub emergency_mode: bool,
+    pub reduce_instrumentation: bool,   // Drop payload logging for non-forensic operations
+    pub priority_operations_only: bool, // Drop metrics and audit for non-forensic operations
+    pub disable_payload_logging: bool,  // Drop payload logging for every operation
+    pub emergency_mode: bool,           // Skip non-forensic instrumentation entirely
 }
 
 /// Tenant-specific policy overrides
@@ -156,54 +180,95 @@ struct OperationMetrics {
 impl AutomaticInstrumentation {
     /// Create new automatic instrumentation system
     pub fn new(license_manager: Arc<LicenseManager>) -> Self {
-        let cache_size = NonZeroUsize::new(2048).unwrap(); // 2K decisions cached
+        // 2K decisions cached, each recomputed after 5 minutes
+        let decision_cache = TtlCache::new(2048).with_ttl(std::time::Duration::from_secs(300));
         
         Self {
-            decision_cache: Arc::new(RwLock::new(LruCache::new(cache_size))),
+            decision_cache: Arc::new(RwLock::new(decision_cache)),
             policy_engine: PolicyEngine::new(),
             performance_monitor: PerformanceMonitor::new(),
+            performance_state_monitor: None,
             license_manager,
+            export_engine: None,
+            tail_sampler: None,
+            budget_escalator: None,
+            qos_scheduler: None,
+            operation_budgets: OperationBudgets::default(),
+            clock: clock::system_clock(),
         }
     }
 
+    /// Hold operations to the policy's `budgets` instead of the default budget
+    pub fn with_operation_budgets(mut self, budgets: OperationBudgets) -> Self {
+        self.operation_budgets = budgets;
+        self
+    }
+
+    /// Decide under `monitor`'s state instead of the one the running monitor last published
+    pub fn with_performance_monitor(mut self, monitor: Arc<PerformanceStateMonitor>) -> Self {
+        self.performance_state_monitor = Some(monitor);
+        self
+    }
+
+    /// Time operations by `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Export an observation record for every instrumented operation
+    pub fn with_export_engine(mut self, engine: Arc<ExportEngine>) -> Self {
+        self.export_engine = Some(engine);
+        self
+    }
+
+    /// Tail-sample records once the operation's outcome is known
+    ///
+    /// Errors, critical security events and operations over their performance budget are always
+    /// exported; routine successes are down-sampled.
+    pub fn with_tail_sampler(mut self, sampler: Arc<TailSampler>) -> Self {
+        self.tail_sampler = Some(sampler);
+        self
+    }
+
+    /// Escalate performance budget breaches through `escalator`
+    ///
+    /// Breaches are recorded as security events on the operation's observation either way.
+    /// Operations whose circuit the escalator has opened fail fast instead of running.
+    pub fn with_budget_escalator(mut self, escalator: Arc<BudgetEscalator>) -> Self {
+        self.budget_escalator = Some(escalator);
+        self
+    }
+
+    /// Run each operation in a slot of its context's QoS class from `scheduler`
+    ///
+    /// The operation waits for the slot before its duration is measured, so queueing
+    /// doesn't count against its performance budget.
+    pub fn with_qos_scheduler(mut self, scheduler: Arc<QosScheduler>) -> Self {
+        self.qos_scheduler = Some(scheduler);
+        self
+    }
+
     /// Core instrumentation decision engine (replaces manual forensic calls)
     /// This is called by every execution gateway for automatic observability
     pub async fn should_instrument(
         &self,
         context: &ObservabilityContext,
     ) -> InstrumentationDecision {
-        let cache_key = context.cache_key();
+        // Decisions depend on the live performance state, so it's part of the key
+        let performance_state = self.performance_state().await;
+        let cache_key = format!("{}|{:?}", context.cache_key(), performance_state);
         
         // FAST PATH: Check cache first (sub-0.1ms performance target)
-        {
-            let mut cache = self.decision_cache.write().await;
-            if let Some(cached) = cache.get_mut(&cache_key) {
-                // Update hit count for analytics
-                cached.hit_count += 1;
-                
-                // Check if cache entry is still valid
-                let age = chrono::Utc::now() - cached.created_at;
-                if age.num_seconds() < 300 { // 5 minute TTL
-                    return cached.decision.clone();
-                }
-                
-                // Remove expired entry
-                cache.pop(&cache_key);
-            }
+        if let Some(decision) = self.decision_cache.write().await.get(&cache_key) {
+            return decision.clone();
         }
 
         // SLOW PATH: Compute new decision (policy engine)
-        let decision = self.compute_instrumentation_decision(context).await;
+        let decision = self.compute_instrumentation_decision(context, &performance_state).await;
         
         // Cache the decision
-        {
-            let mut cache = self.decision_cache.write().await;
-            cache.put(cache_key, CachedDecision {
-                decision: decision.clone(),
-                created_at: chrono::Utc::now(),
-                hit_count: 1,
-            });
-        }
+        self.decision_cache.write().await.insert(cache_key, decision.clone());
 
         decision
     }
@@ -212,10 +277,8 @@ impl AutomaticInstrumentation {
     async fn compute_instrumentation_decision(
         &self,
         context: &ObservabilityContext,
+        performance_state: &PerformanceState,
     ) -> InstrumentationDecision {
-        // Get current performance state for optimization decisions
-        let performance_state = self.performance_monitor.get_current_state().await;
-        
         // Check license tier for feature availability
         let license_tier = self.license_manager.get_tier().await;
         
@@ -242,20 +305,6 @@ impl AutomaticInstrumentation {
                 comp_policy.metrics_operations.contains(&context.operation);
         }
 
-        // Apply performance-based policy (automatic optimization)
-        if let Some(perf_policy) = self.policy_engine.get_performance_policy(&performance_state) {
-            if perf_policy.reduce_instrumentation {
-                decision.metrics_enabled = false;
-                decision.full_payload_logging = false;
-                decision.overhead_budget_ms = std::cmp::min(decision.overhead_budget_ms, 1);
-            }
-            
-            if perf_policy.emergency_mode {
-                decision.enabled = false; // Disable all instrumentation under extreme load
-                return decision;
-            }
-        }
-
         // Apply tenant-specific overrides (enterprise feature)
         if let Some(tenant_id) = &context.tenant_id {
             if let Some(tenant_policy) = self.policy_engine.get_tenant_policy(tenant_id) {
@@ -301,16 +350,103 @@ impl AutomaticInstrumentation {
             }
         }
 
+        // Apply performance-based policy last so load shedding bounds every override above
+        if let Some(perf_policy) = self.policy_engine.get_performance_policy(performance_state) {
+            degrade_for_performance(
+                &mut decision,
+                perf_policy,
+                requires_forensic_audit(&context.classification),
+            );
+        }
+
         decision
     }
 
+    /// State decisions are made under: the worse of the platform's load-derived state and
+    /// any overrun this instrumentation has reported itself
+    async fn performance_state(&self) -> PerformanceState {
+        let platform = match &self.performance_state_monitor {
+            Some(monitor) => monitor.state(),
+            None => performance_state::current(),
+        };
+        performance_state::most_severe(platform, self.performance_monitor.get_current_state().await)
+    }
+
+    /// Override the performance state used for instrumentation decisions
+    ///
+    /// Lets a system load monitor shed observability overhead ahead of budget overruns.
+    pub async fn set_performance_state(&self, state: PerformanceState) {
+        let mut current = self.performance_monitor.current_state.write().await;
+        *current = state;
+    }
+
+    /// Build an observation record for a completed operation and hand it to the exporters
+    ///
+    /// Returns `None` without building a record when the operation isn't instrumented or no
+    /// export engine is attached, and without exporting it when the tail sampler drops it.
+    pub async fn record_observation(
+        &self,
+        context: &ObservabilityContext,
+        result: OperationResult,
+        performance: PerformanceMetrics,
+        events: Vec<SecurityEvent>,
+    ) -> Option<ExportReport> {
+        let engine = self.export_engine.as_ref()?;
+        let decision = self.should_instrument(context).await;
+        if !decision.enabled {
+            return None;
+        }
+
+        let mut record = observation_record(context, result, performance, events);
+        let budget = self.operation_budget(context);
+        if !self.tail_sample(context, &mut record, &budget).await {
+            return None;
+        }
+        Some(engine.export(&record).await)
+    }
+
+    /// Whether the tail sampler keeps `record`; always true without a sampler
+    async fn tail_sample(&self, context: &ObservabilityContext, record: &mut ObservationRecord, budget: &PerformanceBudget) -> bool {
+        let Some(sampler) = &self.tail_sampler else {
+            return true;
+        };
+        let decision = sampler.sample(record, budget).await;
+        if !decision.keep {
+            tracing::trace!("Tail sampler dropped {}.{} ({:?})", context.component, context.operation, decision.reason);
+        }
+        decision.keep
+    }
+
     /// Execute automatic instrumentation for an operation
-    pub async fn instrument_operation<T, E>(
+    ///
+    /// Canonical wrapper for commands and execution gateways; `hooks` is normally the
+    /// `AppState`, whose forensic logger and metrics registry receive the events. With an
+    /// export engine attached, each instrumented operation also yields one observation record,
+    /// queued for the engine's background worker so slow exporters don't hold up the caller.
+    /// While the budget escalator holds the operation's circuit open, it fails fast without running.
+    ///
+    /// Audits fail closed: when the decision requires one and its start can't be recorded the
+    /// operation doesn't run, and when its end can't be recorded the result is withheld.
+    pub async fn instrument_operation<T, E, H>(
         &self,
         context: &ObservabilityContext,
         operation: impl std::future::Future<Output = Result<T, E>>,
-        app_state: &AppState,
-    ) -> Result<T, E> {
+        hooks: &H,
+    ) -> Result<T, E>
+    where
+        E: std::fmt::Display + From<InstrumentationError>,
+        H: InstrumentationHooks + ?Sized,
+    {
+        if let Some(escalator) = &self.budget_escalator {
+            escalator.admit(context).await.map_err(|open| E::from(open.into()))?;
+        }
+
+        // Held until the operation finishes
+        let _permit = match &self.qos_scheduler {
+            Some(scheduler) => Some(scheduler.acquire(context.qos).await.map_err(|e| E::from(e.into()))?),
+            None => None,
+        };
+
         let decision = self.should_instrument(context).await;
         
         if !decision.enabled {
@@ -318,38 +454,50 @@ impl AutomaticInstrumentation {
             return operation.await;
         }
 
-        let start_time = std::time::Instant::now();
-        
         // Pre-operation instrumentation
+        let overhead_start = self.clock.instant();
         if decision.audit_required {
-            if let Err(e) = app_state.forensic_logger.log_operation_start(context).await {
-                tracing::warn!("Failed to log operation start: {}", e);
-            }
+            hooks.audit_start(context).await
+                .map_err(|e| E::from(InstrumentationError::audit_unavailable(context, &e)))?;
         }
         
         if decision.metrics_enabled {
-            app_state.metrics_registry.record_operation_start(context).await;
+            hooks.metrics_start(context).await;
         }
 
         // Execute the operation
+        let start_time = self.clock.instant();
+        let mut overhead = start_time.saturating_duration_since(overhead_start);
         let result = operation.await;
         
-        let duration = start_time.elapsed();
+        let end_time = self.clock.instant();
+        let duration = end_time.saturating_duration_since(start_time);
         
         // Post-operation instrumentation
-        if decision.audit_required {
-            if let Err(e) = app_state.forensic_logger.log_operation_end(context, &result).await {
-                tracing::warn!("Failed to log operation end: {}", e);
-            }
-        }
+        let audit_end = if decision.audit_required {
+            hooks.audit_end(context, result.is_ok()).await
+        } else {
+            Ok(())
+        };
         
         if decision.metrics_enabled {
-            app_state.metrics_registry.record_operation_end(context, duration).await;
+            hooks.metrics_end(context, duration).await;
         }
+        let result = match audit_end {
+            Ok(()) => result,
+            Err(e) => {
+                tracing::error!("Withholding result of {}.{}: {}", context.component, context.operation, e);
+                Err(E::from(InstrumentationError::audit_unavailable(context, &e)))
+            }
+        };
+        overhead += self.clock.instant().saturating_duration_since(end_time);
+        self.enforce_overhead_budget(context, overhead, decision.overhead_budget_ms).await;
 
         // Performance budget checking
+        let budget = self.operation_budget(context);
+        let mut events = Vec::new();
         if decision.performance_tracking {
-            self.check_performance_budget(context, duration, decision.overhead_budget_ms).await;
+            events.extend(self.check_performance_budget(context, duration, &budget).await);
         }
 
         // Update performance monitoring for future decisions
@@ -359,56 +507,98 @@ impl AutomaticInstrumentation {
             result.is_ok(),
         ).await;
 
+        if let Some(engine) = &self.export_engine {
+            let performance = PerformanceMetrics {
+                duration_ns: duration.as_nanos() as u64,
+                ..Default::default()
+            };
+            let mut record = observation_record(context, operation_result(&result), performance, events);
+            if self.tail_sample(context, &mut record, &budget).await {
+                engine.submit(record);
+            }
+        }
+
         result
     }
 
-    /// Check performance budget and trigger alerts if exceeded
+    /// Budget an operation runs against, from the policy; performance-critical components get critical budgets
+    fn operation_budget(&self, context: &ObservabilityContext) -> PerformanceBudget {
+        let critical = self
+            .policy_engine
+            .get_component_policy(&context.component)
+            .is_some_and(|policy| policy.performance_critical);
+        let budget_ms = self.operation_budgets.budget_ms(&context.component, &context.operation);
+        PerformanceBudget::new(budget_ms, &format!("{}.{}", context.component, context.operation), critical)
+    }
+
+    /// Shed instrumentation when the time spent instrumenting an operation exceeds its overhead budget
+    ///
+    /// Raises the performance state, so later decisions carry less instrumentation until load recovers.
+    async fn enforce_overhead_budget(&self, context: &ObservabilityContext, overhead: std::time::Duration, budget_ms: u64) {
+        let overhead_ms = overhead.as_millis() as u64;
+        if overhead_ms <= budget_ms {
+            return;
+        }
+        let overage_percent = if budget_ms == 0 {
+            f64::INFINITY
+        } else {
+            overhead_ms.saturating_sub(budget_ms) as f64 / budget_ms as f64 * 100.0
+        };
+        tracing::warn!(
+            "Instrumentation overhead exceeded for {}.{}: {}ms (budget: {}ms)",
+            context.component,
+            context.operation,
+            overhead_ms,
+            budget_ms
+        );
+        self.performance_monitor
+            .report_performance_issue(&format!("{} instrumentation", context.component), overage_percent)
+            .await;
+    }
+
+    /// Check performance budget and escalate if exceeded
+    ///
+    /// Returns the security event recording the breach, if there was one.
     async fn check_performance_budget(
         &self,
         context: &ObservabilityContext,
         actual_duration: std::time::Duration,
-        budget_ms: u64,
-    ) {
-        let actual_ms = actual_duration.as_millis() as u64;
-        
-        if actual_ms > budget_ms {
-            let overage = actual_ms - budget_ms;
-            let overage_percent = (overage as f64 / budget_ms as f64) * 100.0;
-            
-            tracing::warn!(
-                "Performance budget exceeded: {}.{} took {}ms (budget: {}ms, overage: {:.1}%)",
-                context.component,
-                context.operation,
-                actual_ms,
-                budget_ms,
-                overage_percent
-            );
+        budget: &PerformanceBudget,
+    ) -> Option<SecurityEvent> {
+        let breach = BudgetBreach::check(budget, actual_duration.as_millis() as u64, context)?;
+        let overage_percent = breach.overage_percent();
 
-            // For critical overages, update performance state
-            if overage_percent > 50.0 {
-                self.performance_monitor.report_performance_issue(
-                    &context.component,
-                    overage_percent,
-                ).await;
-            }
+        tracing::warn!(
+            "Performance budget exceeded: {} took {}ms (budget: {}ms, overage: {:.1}%)",
+            breach.operation,
+            breach.actual_ms,
+            breach.budget_ms,
+            overage_percent
+        );
+
+        // For critical overages, update performance state
+        if overage_percent > 50.0 {
+            self.performance_monitor.report_performance_issue(
+                &context.component,
+                overage_percent,
+            ).await;
+        }
+
+        match &self.budget_escalator {
+            Some(escalator) => Some(escalator.escalate(&breach).await.event),
+            None => Some(breach.security_event()),
         }
     }
 
     /// Get instrumentation statistics for observability dashboard
     pub async fn get_instrumentation_stats(&self) -> InstrumentationStats {
-        let cache = self.decision_cache.read().await;
-        let total_decisions = cache.len();
-        let cache_hits: u64 = cache.iter().map(|(_, decision)| decision.hit_count).sum();
+        let cache = self.decision_cache.read().await.stats();
         
         InstrumentationStats {
-            total_decisions: total_decisions as u64,
-            cache_hits,
-            cache_hit_ratio: if total_decisions > 0 {
-                cache_hits as f64 / total_decisions as f64
-            } else {
-                0.0
-            },
-            performance_state: self.performance_monitor.get_current_state().await,
+            total_decisions: cache.hits + cache.misses,
+            cache_hits: cache.hits,
+            cache_hit_ratio: cache.hit_ratio(),
+            performance_state: self.performance_state().await,
             system_load: self.performance_monitor.get_system_load().await,
         }
     }
@@ -449,6 +639,199 @@ impl AutomaticInstrumentation {
     }
 }
 
+/// Observation record for an operation described by `context`
+fn observation_record(
+    context: &ObservabilityContext,
+    result: OperationResult,
+    performance: PerformanceMetrics,
+    events: Vec<SecurityEvent>,
+) -> ObservationRecord {
+    let mut record = ObservationRecord::new(
+        &format!("{}.{}", context.component, context.operation),
+        ObservationContext {
+            user_id: Some(context.user_id.clone()),
+            session_id: Some(context.session_id.to_string()),
+            request_id: Some(context.operation_id.to_string()),
+            tenant_id: context.tenant_id.clone(),
+            classification: Some(context.classification.clone()),
+            ..Default::default()
+        },
+        result,
+    );
+    record.started_at = context.timestamp;
+    record.performance = performance;
+    record.security_events = events;
+    record.metadata.insert("performance_state".to_string(), format!("{:?}", context.performance_state));
+    if let Some(parent) = context.parent_operation_id {
+        record.metadata.insert("parent_operation_id".to_string(), parent.to_string());
+    }
+    record
+}
+
+/// Outcome of an operation as recorded; return values are never captured here
+fn operation_result<T, E: std::fmt::Display>(result: &Result<T, E>) -> OperationResult {
+    match result {
+        Ok(_) => OperationResult::Success { return_value: None },
+        Err(e) => OperationResult::Error {
+            error_type: short_type_name::<E>().to_string(),
+            error_message: e.to_string(),
+            error_code: None,
+        },
+    }
+}
+
+/// Type name without its module path or generic parameters, e.g. `CryptoError`
+fn short_type_name<E>() -> &'static str {
+    let name = std::any::type_name::<E>();
+    let path = name.split('<').next().unwrap_or(name);
+    path.rsplit("::").next().unwrap_or(path)
+}
+
+/// Forensic-grade classifications whose audit trail survives any load shedding
+fn requires_forensic_audit(classification: &ClassificationLevel) -> bool {
+    classification.dominates(&ClassificationLevel::Secret)
+}
+
+/// Reduce a decision under load; forensic operations never lose their audit trail
+fn degrade_for_performance(
+    decision: &mut InstrumentationDecision,
+    policy: &PerformancePolicy,
+    forensic: bool,
+) {
+    if policy.disable_payload_logging || (policy.reduce_instrumentation && !forensic) {
+        decision.full_payload_logging = false;
+    }
+
+    if policy.priority_operations_only && !forensic {
+        decision.metrics_enabled = false;
+        decision.audit_required = false;
+    }
+
+    if policy.emergency_mode {
+        if forensic {
+            decision.performance_tracking = false;
+            decision.overhead_budget_ms = std::cmp::min(decision.overhead_budget_ms, 1);
+        } else {
+            decision.enabled = false;
+        }
+    }
+}
+
+/// Audit and metrics sinks invoked by `instrument_operation` according to its decision
+#[async_trait::async_trait]
+pub trait InstrumentationHooks: Send + Sync {
+    /// Record the start of an audited operation; an error keeps the operation from running
+    async fn audit_start(&self, context: &ObservabilityContext) -> Result<(), ForensicError>;
+    /// Record the end of an audited operation; an error withholds its result
+    async fn audit_end(&self, context: &ObservabilityContext, success: bool) -> Result<(), ForensicError>;
+    async fn metrics_start(&self, context: &ObservabilityContext);
+    async fn metrics_end(&self, context: &ObservabilityContext, duration: std::time::Duration);
+
+    /// Record a network operation's envelope, already reduced to its audit level
+    async fn audit_network(&self, _envelope: ForensicEnvelope) {}
+}
+
+/// Why an instrumented operation was refused or its result withheld
+#[derive(Debug, thiserror::Error)]
+pub enum InstrumentationError {
+    #[error(transparent)]
+    CircuitOpen(#[from] CircuitOpen),
+
+    /// No QoS slot could be had, e.g. Background work paused past its deadline
+    #[error(transparent)]
+    Qos(#[from] QosError),
+
+    /// The policy requires an audit record of the operation and it couldn't be written
+    #[error("Audit of {operation} could not be recorded: {reason}")]
+    AuditUnavailable { operation: String, reason: String },
+}
+
+impl InstrumentationError {
+    fn audit_unavailable(context: &ObservabilityContext, error: &ForensicError) -> Self {
+        Self::AuditUnavailable {
+            operation: format!("{}.{}", context.component, context.operation),
+            reason: error.to_string(),
+        }
+    }
+}
+
+impl From<InstrumentationError> for String {
+    fn from(error: InstrumentationError) -> Self {
+        error.to_string()
+    }
+}
+
+/// Hooks recording to a forensic logger and metrics registry directly
+///
+/// What `AppState` records through; lets components built before the `AppState` (such as the
+/// alert sink's transport poster) be audited the same way.
+#[derive(Debug, Clone)]
+pub struct RecorderHooks {
+    forensic_logger: Arc<ForensicLogger>,
+    metrics_registry: Arc<MetricsRegistry>,
+}
+
+impl RecorderHooks {
+    pub fn new(forensic_logger: Arc<ForensicLogger>, metrics_registry: Arc<MetricsRegistry>) -> Self {
+        Self { forensic_logger, metrics_registry }
+    }
+}
+
+#[async_trait::async_trait]
+impl InstrumentationHooks for RecorderHooks {
+    async fn audit_start(&self, context: &ObservabilityContext) -> Result<(), ForensicError> {
+        self.forensic_logger.log_operation_start(context).await
+    }
+
+    async fn audit_end(&self, context: &ObservabilityContext, success: bool) -> Result<(), ForensicError> {
+        let outcome: Result<(), ()> = if success { Ok(()) } else { Err(()) };
+        self.forensic_logger.log_operation_end(context, &outcome).await
+    }
+
+    async fn metrics_start(&self, context: &ObservabilityContext) {
+        self.metrics_registry.record_operation_start(context).await;
+    }
+
+    async fn metrics_end(&self, context: &ObservabilityContext, duration: std::time::Duration) {
+        self.metrics_registry.record_operation_end(context, duration).await;
+    }
+
+    async fn audit_network(&self, envelope: ForensicEnvelope) {
+        if let Err(e) = self.forensic_logger.log_network_event(envelope).await {
+            tracing::warn!("Failed to log network event: {}", e);
+        }
+    }
+}
+
+impl AppState {
+    fn recorder_hooks(&self) -> RecorderHooks {
+        RecorderHooks::new(self.forensic_logger.clone(), self.metrics_registry.clone())
+    }
+}
+
+#[async_trait::async_trait]
+impl InstrumentationHooks for AppState {
+    async fn audit_start(&self, context: &ObservabilityContext) -> Result<(), ForensicError> {
+        self.recorder_hooks().audit_start(context).await
+    }
+
+    async fn audit_end(&self, context: &ObservabilityContext, success: bool) -> Result<(), ForensicError> {
+        self.recorder_hooks().audit_end(context, success).await
+    }
+
+    async fn metrics_start(&self, context: &ObservabilityContext) {
+        self.recorder_hooks().metrics_start(context).await;
+    }
+
+    async fn metrics_end(&self, context: &ObservabilityContext, duration: std::time::Duration) {
+        self.recorder_hooks().metrics_end(context, duration).await;
+    }
+
+    async fn audit_network(&self, envelope: ForensicEnvelope) {
+        self.recorder_hooks().audit_network(envelope).await;
+    }
+}
+
 /// Statistics about the instrumentation system
 #[derive(Debug, Serialize, Deserialize)]
 pub struct InstrumentationStats {
@@ -523,14 +906,26 @@ impl PolicyEngine {
                 cache_ttl_seconds: 60, // Shorter cache for sensitive data
             },
         );
+        
+        self.classification_policies.insert(
+            ClassificationLevel::NatoSecret,
+            ClassificationPolicy {
+                audit_required: true,
+                metrics_enabled: true,
+                performance_tracking: true,
+                full_payload_logging: true,
+                overhead_budget_ms: 1,
+                cache_ttl_seconds: 60,
+            },
+        );
 
         // Component-specific policies
         self.component_policies.insert(
             "storage".to_string(),
             ComponentPolicy {
                 enabled: true,
-                audit_operations: vec!["put".to_string(), "delete".to_string()],
-                metrics_operations: vec!["get".to_string(), "put".to_string(), "delete".to_string()],
+                audit_operations: vec!["put".to_string(), "delete".to_string(), "dry_run".to_string()],
+                metrics_operations: vec!["get".to_string(), "put".to_string(), "delete".to_string(), "dry_run".to_string()],
                 performance_critical: true,
                 max_overhead_ms: 2,
             },
@@ -558,11 +953,21 @@ impl PolicyEngine {
             },
         );
         
+        self.performance_policies.insert(
+            PerformanceState::Degraded,
+            PerformancePolicy {
+                reduce_instrumentation: true,
+                priority_operations_only: false,
+                disable_payload_logging: false,
+                emergency_mode: false,
+            },
+        );
+        
         self.performance_policies.insert(
             PerformanceState::HighLoad,
             PerformancePolicy {
                 reduce_instrumentation: true,
-                priority_operations_only: true,
+                priority_operations_only: false,
                 disable_payload_logging: true,
                 emergency_mode: false,
             },
@@ -724,4 +1129,481 @@ mod tests {
         assert!(secret_decision.full_payload_logging);
         assert!(secret_decision.overhead_budget_ms < unclassified_decision.overhead_budget_ms);
     }
+
+    #[test]
+    fn test_performance_degradation_matrix() {
+        use ClassificationLevel::*;
+        use PerformanceState::*;
+
+        let engine = PolicyEngine::new();
+        let classifications = [Unclassified, Internal, Confidential, Secret, NatoSecret];
+
+        // (enabled, audit_required, metrics_enabled, full_payload_logging) per classification
+        let expected: [(PerformanceState, [(bool, bool, bool, bool); 5]); 4] = [
+            (Normal, [
+                (true, false, true, false),
+                (true, true, true, false),
+                (true, true, true, true),
+                (true, true, true, true),
+                (true, true, true, true),
+            ]),
+            (Degraded, [
+                (true, false, true, false),
+                (true, true, true, false),
+                (true, true, true, false),
+                (true, true, true, true),
+                (true, true, true, true),
+            ]),
+            (HighLoad, [
+                (true, false, true, false),
+                (true, true, true, false),
+                (true, true, true, false),
+                (true, true, true, false),
+                (true, true, true, false),
+            ]),
+            (Critical, [
+                (false, false, false, false),
+                (false, false, false, false),
+                (false, false, false, false),
+                (true, true, true, false),
+                (true, true, true, false),
+            ]),
+        ];
+
+        for (state, row) in expected.iter() {
+            for (classification, want) in classifications.iter().zip(row.iter()) {
+                let class_policy = engine.get_classification_policy(classification).unwrap();
+                let mut decision = InstrumentationDecision {
+                    audit_required: class_policy.audit_required,
+                    metrics_enabled: class_policy.metrics_enabled,
+                    performance_tracking: class_policy.performance_tracking,
+                    full_payload_logging: class_policy.full_payload_logging,
+                    overhead_budget_ms: class_policy.overhead_budget_ms,
+                    ..Default::default()
+                };
+                degrade_for_performance(
+                    &mut decision,
+                    engine.get_performance_policy(state).unwrap(),
+                    requires_forensic_audit(classification),
+                );
+
+                let got = (
+                    decision.enabled,
+                    decision.audit_required,
+                    decision.metrics_enabled,
+                    decision.full_payload_logging,
+                );
+                assert_eq!(got, *want, "{:?} under {:?}", classification, state);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_critical_state_keeps_forensic_audit() {
+        let license_manager = Arc::new(LicenseManager::new().await.unwrap());
+        let instrumentation = AutomaticInstrumentation::new(license_manager);
+        
+        let secret_context = ObservabilityContext::new(
+            "storage",
+            "put",
+            ClassificationLevel::Secret,
+            "test-user",
+            Uuid::new_v4(),
+        );
+        let internal_context = ObservabilityContext::new(
+            "storage",
+            "put",
+            ClassificationLevel::Internal,
+            "test-user",
+            Uuid::new_v4(),
+        );
+        assert!(instrumentation.should_instrument(&internal_context).await.enabled);
+        
+        // Cached Normal decisions must not outlive a state change
+        instrumentation.set_performance_state(PerformanceState::Critical).await;
+        assert!(!instrumentation.should_instrument(&internal_context).await.enabled);
+        
+        let secret_decision = instrumentation.should_instrument(&secret_context).await;
+        assert!(secret_decision.enabled);
+        assert!(secret_decision.audit_required);
+        assert!(!secret_decision.full_payload_logging);
+    }
+
+    #[derive(Default)]
+    struct RecordingHooks {
+        events: std::sync::Mutex<Vec<&'static str>>,
+    }
+
+    impl RecordingHooks {
+        fn take(&self) -> Vec<&'static str> {
+            std::mem::take(&mut *self.events.lock().unwrap())
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl InstrumentationHooks for RecordingHooks {
+        async fn audit_start(&self, _context: &ObservabilityContext) -> Result<(), ForensicError> {
+            self.events.lock().unwrap().push("audit_start");
+            Ok(())
+        }
+        async fn audit_end(&self, _context: &ObservabilityContext, success: bool) -> Result<(), ForensicError> {
+            self.events.lock().unwrap().push(if success { "audit_end" } else { "audit_end_failed" });
+            Ok(())
+        }
+        async fn metrics_start(&self, _context: &ObservabilityContext) {
+            self.events.lock().unwrap().push("metrics_start");
+        }
+        async fn metrics_end(&self, _context: &ObservabilityContext, _duration: std::time::Duration) {
+            self.events.lock().unwrap().push("metrics_end");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_instrument_operation_fires_hooks_per_decision() {
+        let license_manager = Arc::new(LicenseManager::new().await.unwrap());
+        let instrumentation = AutomaticInstrumentation::new(license_manager);
+        let hooks = RecordingHooks::default();
+        
+        // Audited and metered operation
+        let put_context = ObservabilityContext::new(
+            "storage",
+            "put",
+            ClassificationLevel::Internal,
+            "test-user",
+            Uuid::new_v4(),
+        );
+        let result = instrumentation
+            .instrument_operation(&put_context, async { Ok::<_, String>(42) }, &hooks)
+            .await;
+        assert_eq!(result, Ok(42));
+        assert_eq!(hooks.take(), vec!["audit_start", "metrics_start", "audit_end", "metrics_end"]);
+        
+        // Reads are metered but not audited; failures still reach the hooks
+        let get_context = ObservabilityContext::new(
+            "storage",
+            "get",
+            ClassificationLevel::Internal,
+            "test-user",
+            Uuid::new_v4(),
+        );
+        let result = instrumentation
+            .instrument_operation(&get_context, async { Err::<(), _>("boom".to_string()) }, &hooks)
+            .await;
+        assert!(result.is_err());
+        assert_eq!(hooks.take(), vec!["metrics_start", "metrics_end"]);
+        
+        // Disabled instrumentation still runs the operation without hooks
+        instrumentation.set_performance_state(PerformanceState::Critical).await;
+        let result = instrumentation
+            .instrument_operation(&put_context, async { Ok::<_, String>("ran") }, &hooks)
+            .await;
+        assert_eq!(result, Ok("ran"));
+        assert!(hooks.take().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_instrumented_operation_exports_one_record() {
+        use crate::observability::exporters::tests::CapturingExporter;
+
+        let exporter = Arc::new(CapturingExporter::new("capture"));
+        let engine = Arc::new(ExportEngine::new(vec![exporter.clone()]));
+        let license_manager = Arc::new(LicenseManager::new().await.unwrap());
+        let instrumentation = AutomaticInstrumentation::new(license_manager).with_export_engine(engine);
+        let hooks = RecordingHooks::default();
+
+        let mut context = ObservabilityContext::new(
+            "storage",
+            "put",
+            ClassificationLevel::Internal,
+            "test-user",
+            Uuid::new_v4(),
+        );
+        context.tenant_id = Some("tenant-a".to_string());
+        let result = instrumentation
+            .instrument_operation(&context, async { Err::<(), _>("disk full".to_string()) }, &hooks)
+            .await;
+        assert!(result.is_err());
+
+        // Export runs in the background
+        let records = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            loop {
+                let records = exporter.records.lock().await.clone();
+                if !records.is_empty() {
+                    return records;
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(records.len(), 1);
+        let record = &records[0];
+        assert_eq!(record.operation, "storage.put");
+        assert_eq!(record.context.request_id, Some(context.operation_id.to_string()));
+        assert_eq!(record.context.tenant_id.as_deref(), Some("tenant-a"));
+        assert!(matches!(
+            &record.result,
+            OperationResult::Error { error_type, error_message, .. }
+                if error_type == "String" && error_message == "disk full"
+        ));
+
+        // Unsampled operations produce no record at all
+        instrumentation.set_performance_state(PerformanceState::Critical).await;
+        let result = instrumentation
+            .instrument_operation(&context, async { Ok::<_, String>(()) }, &hooks)
+            .await;
+        assert!(result.is_ok());
+        let report = instrumentation
+            .record_observation(
+                &context,
+                OperationResult::Success { return_value: None },
+                PerformanceMetrics::default(),
+                Vec::new(),
+            )
+            .await;
+        assert!(report.is_none());
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        assert_eq!(exporter.records.lock().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_tail_sampler_keeps_errors_and_drops_routine_successes() {
+        use crate::observability::exporters::tests::CapturingExporter;
+
+        let exporter = Arc::new(CapturingExporter::new("capture"));
+        let engine = Arc::new(ExportEngine::new(vec![exporter.clone()]));
+        let sampler = Arc::new(TailSampler::new(0.0).with_rarity_scale(0.001).with_seed(11));
+        let license_manager = Arc::new(LicenseManager::new().await.unwrap());
+        let instrumentation = AutomaticInstrumentation::new(license_manager)
+            .with_export_engine(engine)
+            .with_tail_sampler(sampler);
+
+        let context = ObservabilityContext::new(
+            "storage",
+            "get",
+            ClassificationLevel::Internal,
+            "test-user",
+            Uuid::new_v4(),
+        );
+        let fast = || PerformanceMetrics { duration_ns: 100_000, ..Default::default() };
+        let failed = || OperationResult::Error {
+            error_type: "String".to_string(),
+            error_message: "timeout".to_string(),
+            error_code: None,
+        };
+
+        // First sighting of an operation is rare enough to keep; repeats fall to the base rate
+        let success = || OperationResult::Success { return_value: None };
+        assert!(instrumentation.record_observation(&context, success(), fast(), Vec::new()).await.is_some());
+        for _ in 0..20 {
+            assert!(instrumentation.record_observation(&context, success(), fast(), Vec::new()).await.is_none());
+            assert!(instrumentation.record_observation(&context, failed(), fast(), Vec::new()).await.is_some());
+        }
+        assert_eq!(exporter.records.lock().await.len(), 21);
+    }
+
+    #[tokio::test]
+    async fn test_critical_budget_breach_is_recorded_and_escalated_once() {
+        use crate::observability::budget::tests::RecordingHandler;
+        use crate::observability::budget::{BudgetEscalationPolicy, Remediation, BUDGET_BREACH_EVENT};
+        use crate::observability::exporters::tests::CapturingExporter;
+        use crate::observability::observation::{SecurityEventType, SecuritySeverity};
+
+        let exporter = Arc::new(CapturingExporter::new("capture"));
+        let engine = Arc::new(ExportEngine::new(vec![exporter.clone()]));
+        let alert = Arc::new(RecordingHandler::default());
+        let escalator = BudgetEscalator::new(BudgetEscalationPolicy::default())
+            .with_handler(Remediation::Alert, alert.clone());
+        let license_manager = Arc::new(LicenseManager::new().await.unwrap());
+        let budgets = OperationBudgets {
+            budgets_ms: HashMap::from([("storage".to_string(), 5)]),
+            ..OperationBudgets::default()
+        };
+        let clock = Arc::new(crate::clock::MockClock::default());
+        let instrumentation = AutomaticInstrumentation::new(license_manager)
+            .with_export_engine(engine)
+            .with_budget_escalator(Arc::new(escalator))
+            .with_operation_budgets(budgets)
+            .with_clock(clock.clone());
+        let hooks = RecordingHooks::default();
+
+        // Storage is performance-critical, so overrunning its few-millisecond budget is a critical breach
+        let context = ObservabilityContext::new(
+            "storage",
+            "put",
+            ClassificationLevel::Internal,
+            "test-user",
+            Uuid::new_v4(),
+        );
+        let slow = async {
+            clock.advance(std::time::Duration::from_millis(30));
+            Ok::<_, String>(())
+        };
+        assert!(instrumentation.instrument_operation(&context, slow, &hooks).await.is_ok());
+
+        let breaches = alert.wait_for(1).await;
+        assert_eq!(breaches.len(), 1);
+        assert!(breaches[0].critical);
+        assert_eq!(breaches[0].operation, "storage.put");
+        assert_eq!(breaches[0].context.operation_id, context.operation_id);
+        assert_eq!((breaches[0].actual_ms, breaches[0].budget_ms), (30, 5));
+
+        let records = tokio::time::timeout(std::time::Duration::from_secs(5), async {
+            loop {
+                let records = exporter.records.lock().await.clone();
+                if !records.is_empty() {
+                    return records;
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+        let event = &records[0].security_events[0];
+        assert_eq!(event.event_type, SecurityEventType::Custom(BUDGET_BREACH_EVENT.to_string()));
+        assert_eq!(event.severity, SecuritySeverity::Critical);
+    }
+
+    #[tokio::test]
+    async fn test_tail_sampler_judges_records_by_the_policy_budget() {
+        use crate::observability::exporters::tests::CapturingExporter;
+
+        let exporter = Arc::new(CapturingExporter::new("capture"));
+        let engine = Arc::new(ExportEngine::new(vec![exporter.clone()]));
+        let sampler = Arc::new(TailSampler::new(0.0).with_rarity_scale(0.001).with_seed(11));
+        let budgets = OperationBudgets {
+            budgets_ms: HashMap::from([("storage.put".to_string(), 5)]),
+            ..OperationBudgets::default()
+        };
+        let license_manager = Arc::new(LicenseManager::new().await.unwrap());
+        let instrumentation = AutomaticInstrumentation::new(license_manager)
+            .with_export_engine(engine)
+            .with_tail_sampler(sampler)
+            .with_operation_budgets(budgets);
+
+        // 10ms is far over the few-millisecond overhead budget, but within the 100ms default
+        let context = |operation| ObservabilityContext::new("storage", operation, ClassificationLevel::Internal, "test-user", Uuid::new_v4());
+        let took_10ms = || PerformanceMetrics { duration_ns: 10_000_000, ..Default::default() };
+        let success = || OperationResult::Success { return_value: None };
+        assert!(instrumentation.record_observation(&context("get"), success(), took_10ms(), Vec::new()).await.is_some());
+        for _ in 0..5 {
+            assert!(instrumentation.record_observation(&context("get"), success(), took_10ms(), Vec::new()).await.is_none());
+            // storage.put has its own 5ms budget, so the same timing is over budget and kept
+            assert!(instrumentation.record_observation(&context("put"), success(), took_10ms(), Vec::new()).await.is_some());
+        }
+    }
+
+    /// Hooks whose forensic store rejects audit records, from the start or only at the end
+    struct FailingAuditHooks {
+        fail_start: bool,
+    }
+
+    #[async_trait::async_trait]
+    impl InstrumentationHooks for FailingAuditHooks {
+        async fn audit_start(&self, _context: &ObservabilityContext) -> Result<(), ForensicError> {
+            if self.fail_start { Err(ForensicError::WriterClosed) } else { Ok(()) }
+        }
+        async fn audit_end(&self, _context: &ObservabilityContext, _success: bool) -> Result<(), ForensicError> {
+            Err(ForensicError::WriterClosed)
+        }
+        async fn metrics_start(&self, _context: &ObservabilityContext) {}
+        async fn metrics_end(&self, _context: &ObservabilityContext, _duration: std::time::Duration) {}
+    }
+
+    #[tokio::test]
+    async fn test_required_audit_fails_closed() {
+        let license_manager = Arc::new(LicenseManager::new().await.unwrap());
+        let instrumentation = AutomaticInstrumentation::new(license_manager);
+        let put = ObservabilityContext::new("storage", "put", ClassificationLevel::Internal, "test-user", Uuid::new_v4());
+        let ran = std::sync::atomic::AtomicBool::new(false);
+
+        // Audited writes don't run when their start can't be recorded
+        let result = instrumentation
+            .instrument_operation(&put, async {
+                ran.store(true, std::sync::atomic::Ordering::SeqCst);
+                Ok::<_, String>(())
+            }, &FailingAuditHooks { fail_start: true })
+            .await;
+        assert!(result.unwrap_err().contains("storage.put could not be recorded"));
+        assert!(!ran.load(std::sync::atomic::Ordering::SeqCst));
+
+        // ...and their result is withheld when the end can't be
+        let result = instrumentation
+            .instrument_operation(&put, async { Ok::<_, String>(42) }, &FailingAuditHooks { fail_start: false })
+            .await;
+        assert!(result.is_err());
+
+        // Operations the policy doesn't audit are unaffected
+        let get = ObservabilityContext::new("storage", "get", ClassificationLevel::Internal, "test-user", Uuid::new_v4());
+        let result = instrumentation
+            .instrument_operation(&get, async { Ok::<_, String>(42) }, &FailingAuditHooks { fail_start: true })
+            .await;
+        assert_eq!(result, Ok(42));
+    }
+
+    /// Hooks that take `delay` of `clock` time to record each event
+    struct SlowHooks {
+        clock: Arc<crate::clock::MockClock>,
+        delay: std::time::Duration,
+    }
+
+    #[async_trait::async_trait]
+    impl InstrumentationHooks for SlowHooks {
+        async fn audit_start(&self, _context: &ObservabilityContext) -> Result<(), ForensicError> {
+            self.clock.advance(self.delay);
+            Ok(())
+        }
+        async fn audit_end(&self, _context: &ObservabilityContext, _success: bool) -> Result<(), ForensicError> {
+            self.clock.advance(self.delay);
+            Ok(())
+        }
+        async fn metrics_start(&self, _context: &ObservabilityContext) {}
+        async fn metrics_end(&self, _context: &ObservabilityContext, _duration: std::time::Duration) {}
+    }
+
+    #[tokio::test]
+    async fn test_decisions_follow_the_load_derived_performance_state() {
+        use crate::observability::metrics_registry::{OPERATIONS_METRIC, OPERATION_LATENCY_METRIC};
+        use crate::observability::performance_state::PerformanceThresholds;
+
+        let metrics = Arc::new(MetricsRegistry::new());
+        let monitor = Arc::new(PerformanceStateMonitor::new(metrics.clone(), PerformanceThresholds::default()));
+        let license_manager = Arc::new(LicenseManager::new().await.unwrap());
+        let instrumentation = AutomaticInstrumentation::new(license_manager).with_performance_monitor(monitor.clone());
+        let context = ObservabilityContext::new("storage", "get", ClassificationLevel::Unclassified, "test-user", Uuid::new_v4());
+        assert!(instrumentation.should_instrument(&context).await.enabled);
+
+        // p99 latency past the critical limit sheds unclassified instrumentation entirely
+        let start = chrono::Utc::now();
+        for tenth in 0..100 {
+            let at = start + chrono::Duration::milliseconds(tenth * 100);
+            metrics.record_rollup(OPERATIONS_METRIC, 1.0, at);
+            metrics.record_rollup(OPERATION_LATENCY_METRIC, 1500.0, at);
+        }
+        assert_eq!(monitor.evaluate_at(start + chrono::Duration::seconds(10)), PerformanceState::Critical);
+        assert!(!instrumentation.should_instrument(&context).await.enabled);
+        assert_eq!(instrumentation.get_instrumentation_stats().await.performance_state, PerformanceState::Critical);
+    }
+
+    #[tokio::test]
+    async fn test_instrumentation_over_its_overhead_budget_sheds_load() {
+        let clock = Arc::new(crate::clock::MockClock::default());
+        let license_manager = Arc::new(LicenseManager::new().await.unwrap());
+        let instrumentation = AutomaticInstrumentation::new(license_manager).with_clock(clock.clone());
+        let context = ObservabilityContext::new("storage", "put", ClassificationLevel::Internal, "test-user", Uuid::new_v4());
+        let budget_ms = instrumentation.should_instrument(&context).await.overhead_budget_ms;
+
+        // Within the overhead budget nothing changes, though the operation itself takes far longer
+        let within = SlowHooks { clock: clock.clone(), delay: std::time::Duration::from_millis(budget_ms / 2) };
+        let slow_operation = async {
+            clock.advance(std::time::Duration::from_millis(50));
+            Ok::<_, String>(())
+        };
+        instrumentation.instrument_operation(&context, slow_operation, &within).await.unwrap();
+        assert_eq!(instrumentation.performance_monitor.get_current_state().await, PerformanceState::Normal);
+
+        let over = SlowHooks { clock: clock.clone(), delay: std::time::Duration::from_millis(budget_ms * 2) };
+        instrumentation.instrument_operation(&context, async { Ok::<_, String>(()) }, &over).await.unwrap();
+        assert_ne!(instrumentation.performance_monitor.get_current_state().await, PerformanceState::Normal);
+    }
 }
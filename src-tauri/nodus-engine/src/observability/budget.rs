@@ -0,0 +1,489 @@
+// src-tauri/src/observability/budget.rs
+// Budget Escalation - Turn performance budget overruns into security events, metrics and remediation
+// Which remediation runs for which overrun comes from the observability policy
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+
+use crate::clock::{self, Clock};
+use crate::observability::observation::{SecurityEvent, SecurityEventType, SecuritySeverity};
+use crate::observability::{BudgetResult, MetricsRegistry, ObservabilityContext, PerformanceBudget};
+
+/// Security event type recorded for every budget breach
+pub const BUDGET_BREACH_EVENT: &str = "performance_budget_breach";
+
+/// Counter of every budget breach
+pub const BUDGET_BREACHES_METRIC: &str = "performance_budget.breaches";
+
+/// Counter of breaches of critical budgets
+pub const CRITICAL_BUDGET_BREACHES_METRIC: &str = "performance_budget.critical_breaches";
+
+/// Duration budget of operations the policy doesn't list
+pub const DEFAULT_OPERATION_BUDGET_MS: u64 = 100;
+
+/// How long an operation's circuit stays open after a breach unless the policy says otherwise
+pub const DEFAULT_CIRCUIT_OPEN_SECONDS: u64 = 30;
+
+/// How long operations may take, from the observability policy
+///
+/// Separate from an instrumentation decision's overhead budget, which bounds only the time
+/// spent instrumenting the operation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OperationBudgets {
+    /// Budget of operations with no entry in `budgets_ms`
+    pub default_ms: u64,
+    /// Budgets by component, or by `component.operation` to single out one operation
+    pub budgets_ms: HashMap<String, u64>,
+}
+
+impl Default for OperationBudgets {
+    fn default() -> Self {
+        Self {
+            default_ms: DEFAULT_OPERATION_BUDGET_MS,
+            budgets_ms: HashMap::new(),
+        }
+    }
+}
+
+impl OperationBudgets {
+    /// Budget of `operation` of `component`: its own entry, else its component's, else the default
+    pub fn budget_ms(&self, component: &str, operation: &str) -> u64 {
+        self.budgets_ms
+            .get(&format!("{}.{}", component, operation))
+            .or_else(|| self.budgets_ms.get(component))
+            .copied()
+            .unwrap_or(self.default_ms)
+    }
+}
+
+/// Action a budget breach can trigger
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Remediation {
+    /// Reduce instrumentation and admitted work until load recovers
+    ShedLoad,
+    /// Fail the operation fast until it recovers
+    OpenCircuit,
+    /// Notify operators
+    Alert,
+}
+
+/// Remediations run for each kind of budget breach
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BudgetEscalationPolicy {
+    /// Breaches are still recorded when disabled; only remediation is skipped
+    pub enabled: bool,
+    /// Run when a non-critical budget is exceeded
+    pub exceeded: Vec<Remediation>,
+    /// Run when a critical budget is exceeded
+    pub critical_exceeded: Vec<Remediation>,
+    /// How long `OpenCircuit` fails an operation fast before letting it run again
+    pub circuit_open_seconds: u64,
+}
+
+impl Default for BudgetEscalationPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            exceeded: Vec::new(),
+            critical_exceeded: vec![Remediation::Alert],
+            circuit_open_seconds: DEFAULT_CIRCUIT_OPEN_SECONDS,
+        }
+    }
+}
+
+impl BudgetEscalationPolicy {
+    /// Remediations for `breach`, each listed once
+    pub fn remediations_for(&self, breach: &BudgetBreach) -> Vec<Remediation> {
+        if !self.enabled {
+            return Vec::new();
+        }
+        let configured = if breach.critical { &self.critical_exceeded } else { &self.exceeded };
+        let mut remediations = Vec::with_capacity(configured.len());
+        for remediation in configured {
+            if !remediations.contains(remediation) {
+                remediations.push(*remediation);
+            }
+        }
+        remediations
+    }
+}
+
+/// An operation that ran over its performance budget
+#[derive(Debug, Clone)]
+pub struct BudgetBreach {
+    pub operation: String,
+    pub budget_ms: u64,
+    pub actual_ms: u64,
+    pub critical: bool,
+    pub context: ObservabilityContext,
+    pub detected_at: DateTime<Utc>,
+}
+
+impl BudgetBreach {
+    /// Breach described by `result`, or `None` when the operation stayed within budget
+    pub fn from_result(result: &BudgetResult, budget: &PerformanceBudget, context: &ObservabilityContext) -> Option<Self> {
+        let (budget_ms, actual_ms, critical) = match *result {
+            BudgetResult::WithinBudget => return None,
+            BudgetResult::Exceeded { budget, actual } => (budget, actual, false),
+            BudgetResult::CriticalExceeded { budget, actual } => (budget, actual, true),
+        };
+        Some(Self {
+            operation: budget.operation_name.clone(),
+            budget_ms,
+            actual_ms,
+            critical,
+            context: context.clone(),
+            detected_at: Utc::now(),
+        })
+    }
+
+    /// Check `actual_ms` against `budget`
+    pub fn check(budget: &PerformanceBudget, actual_ms: u64, context: &ObservabilityContext) -> Option<Self> {
+        Self::from_result(&budget.check_budget(actual_ms), budget, context)
+    }
+
+    /// How far over budget the operation ran, as a percentage of the budget
+    pub fn overage_percent(&self) -> f64 {
+        let overage = self.actual_ms.saturating_sub(self.budget_ms) as f64;
+        if self.budget_ms == 0 {
+            return f64::INFINITY;
+        }
+        overage / self.budget_ms as f64 * 100.0
+    }
+
+    /// Security event recording the breach; critical budgets yield a Critical event
+    pub fn security_event(&self) -> SecurityEvent {
+        let mut data = HashMap::new();
+        data.insert("operation".to_string(), serde_json::json!(self.operation));
+        data.insert("component".to_string(), serde_json::json!(self.context.component));
+        data.insert("operation_id".to_string(), serde_json::json!(self.context.operation_id.to_string()));
+        data.insert("budget_ms".to_string(), serde_json::json!(self.budget_ms));
+        data.insert("actual_ms".to_string(), serde_json::json!(self.actual_ms));
+        data.insert("classification".to_string(), serde_json::json!(format!("{:?}", self.context.classification)));
+        if let Some(tenant_id) = &self.context.tenant_id {
+            data.insert("tenant_id".to_string(), serde_json::json!(tenant_id));
+        }
+
+        SecurityEvent {
+            event_type: SecurityEventType::Custom(BUDGET_BREACH_EVENT.to_string()),
+            severity: if self.critical { SecuritySeverity::Critical } else { SecuritySeverity::Medium },
+            description: format!(
+                "{} took {}ms against a {}ms budget",
+                self.operation, self.actual_ms, self.budget_ms
+            ),
+            timestamp: self.detected_at,
+            data,
+        }
+    }
+}
+
+/// Carries out a remediation; register one per remediation with `BudgetEscalator::with_handler`
+///
+/// Handlers run on their own task, so a slow remediation never holds up the breaching operation.
+#[async_trait::async_trait]
+pub trait BreachHandler: Send + Sync + fmt::Debug {
+    async fn handle(&self, breach: &BudgetBreach);
+}
+
+/// An operation refused because its budget circuit is open
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{operation} is failing fast over its performance budget; retry in {retry_after_ms}ms")]
+pub struct CircuitOpen {
+    pub operation: String,
+    pub retry_after_ms: u64,
+}
+
+/// Per-operation circuits opened by the `OpenCircuit` remediation
+///
+/// An open circuit fails its operation fast until the open period passes; the next run then
+/// goes ahead, and a further breach opens the circuit again.
+#[derive(Debug)]
+pub struct BudgetCircuits {
+    open_for_ms: AtomicU64,
+    clock: Arc<dyn Clock>,
+    /// Open circuits by operation name, with when each closes; closed circuits are dropped
+    open_until: RwLock<HashMap<String, Instant>>,
+}
+
+impl BudgetCircuits {
+    pub fn new(open_for: Duration) -> Self {
+        Self {
+            open_for_ms: AtomicU64::new(open_for.as_millis() as u64),
+            clock: clock::system_clock(),
+            open_until: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Time open periods by `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Keep circuits opened from now on open for `open_for`
+    pub fn set_open_for(&self, open_for: Duration) {
+        self.open_for_ms.store(open_for.as_millis() as u64, Ordering::Relaxed);
+    }
+
+    /// Open `operation`'s circuit for the configured period
+    pub async fn open(&self, operation: &str) {
+        let until = self.clock.instant() + Duration::from_millis(self.open_for_ms.load(Ordering::Relaxed));
+        self.open_until.write().await.insert(operation.to_string(), until);
+    }
+
+    /// Whether `operation` may run; closes its circuit once the open period has passed
+    pub async fn check(&self, operation: &str) -> Result<(), CircuitOpen> {
+        let now = self.clock.instant();
+        match self.open_until.read().await.get(operation) {
+            None => return Ok(()),
+            Some(until) if now < *until => {
+                return Err(CircuitOpen {
+                    operation: operation.to_string(),
+                    retry_after_ms: until.saturating_duration_since(now).as_millis() as u64,
+                });
+            }
+            Some(_) => {}
+        }
+
+        let mut open_until = self.open_until.write().await;
+        if open_until.get(operation).is_some_and(|until| now >= *until) {
+            open_until.remove(operation);
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl BreachHandler for BudgetCircuits {
+    async fn handle(&self, breach: &BudgetBreach) {
+        self.open(&breach.operation).await;
+    }
+}
+
+/// Outcome of escalating one breach
+#[derive(Debug, Clone)]
+pub struct Escalation {
+    pub event: SecurityEvent,
+    /// Remediations the policy called for, whether or not a handler was registered
+    pub remediations: Vec<Remediation>,
+}
+
+/// Records budget breaches and runs the remediations the escalation policy calls for
+#[derive(Debug, Default)]
+pub struct BudgetEscalator {
+    policy: RwLock<BudgetEscalationPolicy>,
+    handlers: HashMap<Remediation, Vec<Arc<dyn BreachHandler>>>,
+    circuits: Option<Arc<BudgetCircuits>>,
+    metrics_registry: Option<Arc<MetricsRegistry>>,
+}
+
+impl BudgetEscalator {
+    pub fn new(policy: BudgetEscalationPolicy) -> Self {
+        Self {
+            policy: RwLock::new(policy),
+            handlers: HashMap::new(),
+            circuits: None,
+            metrics_registry: None,
+        }
+    }
+
+    /// Run `handler` whenever the policy calls for `remediation`
+    pub fn with_handler(mut self, remediation: Remediation, handler: Arc<dyn BreachHandler>) -> Self {
+        self.handlers.entry(remediation).or_default().push(handler);
+        self
+    }
+
+    /// Open `circuits` for `OpenCircuit`, kept open for the policy's period
+    pub fn with_circuits(mut self, circuits: Arc<BudgetCircuits>) -> Self {
+        circuits.set_open_for(Duration::from_secs(self.policy.get_mut().circuit_open_seconds));
+        self.circuits = Some(circuits.clone());
+        self.with_handler(Remediation::OpenCircuit, circuits)
+    }
+
+    /// Count breaches in `registry`
+    pub fn with_metrics_registry(mut self, registry: Arc<MetricsRegistry>) -> Self {
+        self.metrics_registry = Some(registry);
+        self
+    }
+
+    pub async fn policy(&self) -> BudgetEscalationPolicy {
+        self.policy.read().await.clone()
+    }
+
+    /// Swap in a new policy; the next breach uses it
+    pub async fn set_policy(&self, policy: BudgetEscalationPolicy) {
+        if let Some(circuits) = &self.circuits {
+            circuits.set_open_for(Duration::from_secs(policy.circuit_open_seconds));
+        }
+        *self.policy.write().await = policy;
+    }
+
+    /// Whether `context`'s operation may run, or its budget circuit is open
+    pub async fn admit(&self, context: &ObservabilityContext) -> Result<(), CircuitOpen> {
+        match &self.circuits {
+            Some(circuits) => circuits.check(&format!("{}.{}", context.component, context.operation)).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Record `breach` and start each remediation the policy calls for once
+    ///
+    /// Handlers are spawned rather than awaited; the escalation reports what was started.
+    pub async fn escalate(&self, breach: &BudgetBreach) -> Escalation {
+        if let Some(registry) = &self.metrics_registry {
+            registry.increment_counter(BUDGET_BREACHES_METRIC, 1);
+            registry.increment_counter(
+                &format!("{}.{}.budget_breaches", breach.context.component, breach.context.operation),
+                1,
+            );
+            if breach.critical {
+                registry.increment_counter(CRITICAL_BUDGET_BREACHES_METRIC, 1);
+            }
+        }
+
+        let remediations = self.policy.read().await.remediations_for(breach);
+        for remediation in &remediations {
+            tracing::warn!(
+                "Escalating budget breach of {} ({}ms > {}ms): {:?}",
+                breach.operation, breach.actual_ms, breach.budget_ms, remediation
+            );
+            for handler in self.handlers.get(remediation).into_iter().flatten() {
+                let handler = handler.clone();
+                let breach = breach.clone();
+                tokio::spawn(async move { handler.handle(&breach).await });
+            }
+        }
+
+        Escalation { event: breach.security_event(), remediations }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+    use crate::security::ClassificationLevel;
+    use tokio::sync::Mutex;
+    use uuid::Uuid;
+
+    /// Handler that keeps every breach it's asked to remediate
+    #[derive(Debug, Default)]
+    pub(crate) struct RecordingHandler {
+        pub(crate) breaches: Mutex<Vec<BudgetBreach>>,
+    }
+
+    impl RecordingHandler {
+        /// Breaches handled once `count` have arrived; handlers run on their own tasks
+        pub(crate) async fn wait_for(&self, count: usize) -> Vec<BudgetBreach> {
+            tokio::time::timeout(Duration::from_secs(5), async {
+                loop {
+                    let breaches = self.breaches.lock().await.clone();
+                    if breaches.len() >= count {
+                        return breaches;
+                    }
+                    tokio::task::yield_now().await;
+                }
+            })
+            .await
+            .unwrap()
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl BreachHandler for RecordingHandler {
+        async fn handle(&self, breach: &BudgetBreach) {
+            self.breaches.lock().await.push(breach.clone());
+        }
+    }
+
+    fn context() -> ObservabilityContext {
+        ObservabilityContext::new("storage", "put", ClassificationLevel::Internal, "test-user", Uuid::new_v4())
+    }
+
+    #[tokio::test]
+    async fn test_critical_breach_runs_configured_remediation_once() {
+        let alert = Arc::new(RecordingHandler::default());
+        let shed = Arc::new(RecordingHandler::default());
+        let registry = Arc::new(MetricsRegistry::new());
+        let escalator = BudgetEscalator::new(BudgetEscalationPolicy {
+            exceeded: vec![Remediation::ShedLoad],
+            critical_exceeded: vec![Remediation::Alert, Remediation::Alert],
+            ..BudgetEscalationPolicy::default()
+        })
+        .with_handler(Remediation::Alert, alert.clone())
+        .with_handler(Remediation::ShedLoad, shed.clone())
+        .with_metrics_registry(registry.clone());
+
+        let budget = PerformanceBudget::new(10, "storage.put", true);
+        assert!(BudgetBreach::check(&budget, 10, &context()).is_none());
+        let breach = BudgetBreach::check(&budget, 25, &context()).unwrap();
+        let escalation = escalator.escalate(&breach).await;
+
+        assert_eq!(escalation.remediations, vec![Remediation::Alert]);
+        assert_eq!(escalation.event.severity, SecuritySeverity::Critical);
+        assert_eq!(escalation.event.event_type, SecurityEventType::Custom(BUDGET_BREACH_EVENT.to_string()));
+        assert_eq!(escalation.event.data["actual_ms"], serde_json::json!(25));
+        assert_eq!(alert.wait_for(1).await.len(), 1);
+        assert!(shed.breaches.lock().await.is_empty());
+        assert_eq!(breach.overage_percent(), 150.0);
+
+        let snapshot = registry.snapshot().await;
+        assert_eq!(snapshot.counters.get(CRITICAL_BUDGET_BREACHES_METRIC), Some(&1));
+
+        // Disabling escalation still records the breach but starts nothing
+        escalator.set_policy(BudgetEscalationPolicy { enabled: false, ..escalator.policy().await }).await;
+        let escalation = escalator.escalate(&breach).await;
+        assert!(escalation.remediations.is_empty());
+        tokio::task::yield_now().await;
+        assert_eq!(alert.breaches.lock().await.len(), 1);
+        assert_eq!(registry.snapshot().await.counters.get(BUDGET_BREACHES_METRIC), Some(&2));
+    }
+
+    #[tokio::test]
+    async fn test_open_circuit_fails_the_operation_fast_until_the_period_passes() {
+        let clock = Arc::new(MockClock::default());
+        let circuits = Arc::new(BudgetCircuits::new(Duration::from_secs(60)).with_clock(clock.clone()));
+        let escalator = BudgetEscalator::new(BudgetEscalationPolicy {
+            exceeded: vec![Remediation::OpenCircuit],
+            circuit_open_seconds: 10,
+            ..BudgetEscalationPolicy::default()
+        })
+        .with_circuits(circuits.clone());
+
+        let context = context();
+        assert!(escalator.admit(&context).await.is_ok());
+        let breach = BudgetBreach::check(&PerformanceBudget::new(10, "storage.put", false), 25, &context).unwrap();
+        assert_eq!(escalator.escalate(&breach).await.remediations, vec![Remediation::OpenCircuit]);
+
+        // The handler runs on its own task; once it has, the operation is refused for the policy's period
+        let refused = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                if let Err(refused) = escalator.admit(&context).await {
+                    return refused;
+                }
+                tokio::task::yield_now().await;
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(refused, CircuitOpen { operation: "storage.put".to_string(), retry_after_ms: 10_000 });
+
+        // Other operations are unaffected, and the circuit closes once the period passes
+        let other = ObservabilityContext::new("storage", "get", ClassificationLevel::Internal, "test-user", Uuid::new_v4());
+        assert!(escalator.admit(&other).await.is_ok());
+        clock.advance(Duration::from_secs(9));
+        assert!(escalator.admit(&context).await.is_err());
+        clock.advance(Duration::from_secs(1));
+        assert!(escalator.admit(&context).await.is_ok());
+        assert!(circuits.open_until.read().await.is_empty());
+    }
+}
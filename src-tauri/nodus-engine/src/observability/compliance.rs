@@ -0,0 +1,690 @@
+// src-tauri/src/observability/compliance.rs
+// Compliance Engine - Evaluate each observed operation against GDPR and HIPAA rules
+// Evidence is attached to the observation record and tallied into per-framework scores for the dashboard
+
+use chrono::{DateTime, Duration, Utc};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::sync::Arc;
+use thiserror::Error;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::database::DatabaseManager;
+use crate::observability::observation::{
+    ComplianceFramework, ComplianceRecord, ComplianceStatus, ObservationRecord, PrivacyLevel,
+};
+use crate::security::ClassificationLevel;
+
+/// Operation name suffixes that change stored data
+const WRITE_OPERATIONS: &[&str] = &["create", "update", "put", "write", "insert", "upsert", "save", "delete", "import"];
+
+/// Frameworks the engine has rules for
+pub const SUPPORTED_FRAMEWORKS: [ComplianceFramework; 2] = [ComplianceFramework::GDPR, ComplianceFramework::HIPAA];
+
+/// Compliance records kept by the in-memory store; the oldest are dropped first
+pub const DEFAULT_HISTORY_LIMIT: usize = 50_000;
+
+/// Longest window a summary may cover
+pub const MAX_COMPLIANCE_WINDOW_DAYS: i64 = 366;
+
+/// Records read from the store per page while summarizing
+const SUMMARY_PAGE_SIZE: usize = 1_000;
+
+/// Operations listed in a summary's top violators
+pub const TOP_VIOLATIONS: usize = 10;
+
+/// Compliance standing of one framework across the operations assessed so far
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComplianceScore {
+    pub framework: ComplianceFramework,
+    pub compliant: u64,
+    pub non_compliant: u64,
+    /// Records whose status couldn't be determined; they don't count toward the score
+    pub unknown: u64,
+    /// Percentage of determined records that were compliant; `None` until one is
+    pub score: Option<f64>,
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+struct Tally {
+    compliant: u64,
+    non_compliant: u64,
+    unknown: u64,
+}
+
+/// A compliance record with what it takes to window and scope it
+#[derive(Debug, Clone, PartialEq)]
+pub struct AssessedRecord {
+    pub id: Uuid,
+    pub at: DateTime<Utc>,
+    pub operation: String,
+    pub tenant_id: Option<String>,
+    pub classification: Option<ClassificationLevel>,
+    pub record: ComplianceRecord,
+}
+
+#[derive(Error, Debug)]
+pub enum ComplianceError {
+    #[error("Compliance window starts after it ends")]
+    EmptyWindow,
+
+    #[error("Compliance window is longer than {MAX_COMPLIANCE_WINDOW_DAYS} days")]
+    WindowTooLong,
+
+    #[error("Compliance store error: {0}")]
+    Store(String),
+}
+
+/// Durable storage for assessed compliance records; `DatabaseManager` in production
+#[async_trait::async_trait]
+pub trait ComplianceStore: Send + Sync + std::fmt::Debug {
+    async fn store_assessed(&self, records: &[AssessedRecord]) -> Result<(), ComplianceError>;
+
+    /// Up to `limit` records of `framework` in `window`, ordered by `(at, id)` and starting
+    /// after `after`; only `tenant_id`'s when one is given
+    async fn assessed_page(
+        &self,
+        framework: &ComplianceFramework,
+        window: ComplianceWindow,
+        tenant_id: Option<&str>,
+        after: Option<(DateTime<Utc>, Uuid)>,
+        limit: usize,
+    ) -> Result<Vec<AssessedRecord>, ComplianceError>;
+}
+
+#[async_trait::async_trait]
+impl ComplianceStore for DatabaseManager {
+    async fn store_assessed(&self, records: &[AssessedRecord]) -> Result<(), ComplianceError> {
+        self.store_compliance_records(records)
+            .await
+            .map_err(|e| ComplianceError::Store(e.to_string()))
+    }
+
+    async fn assessed_page(
+        &self,
+        framework: &ComplianceFramework,
+        window: ComplianceWindow,
+        tenant_id: Option<&str>,
+        after: Option<(DateTime<Utc>, Uuid)>,
+        limit: usize,
+    ) -> Result<Vec<AssessedRecord>, ComplianceError> {
+        self.compliance_records_page(&framework_key(framework), window.start, window.end, tenant_id, after, limit)
+            .await
+            .map_err(|e| ComplianceError::Store(e.to_string()))
+    }
+}
+
+/// Assessed records held in memory, oldest dropped past a limit; for tests and stores without a database
+#[derive(Debug)]
+pub struct InMemoryComplianceStore {
+    records: RwLock<VecDeque<AssessedRecord>>,
+    limit: usize,
+}
+
+impl Default for InMemoryComplianceStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_HISTORY_LIMIT)
+    }
+}
+
+impl InMemoryComplianceStore {
+    pub fn new(limit: usize) -> Self {
+        Self { records: RwLock::new(VecDeque::new()), limit }
+    }
+}
+
+#[async_trait::async_trait]
+impl ComplianceStore for InMemoryComplianceStore {
+    async fn store_assessed(&self, records: &[AssessedRecord]) -> Result<(), ComplianceError> {
+        let mut held = self.records.write().await;
+        held.extend(records.iter().cloned());
+        let excess = held.len().saturating_sub(self.limit);
+        held.drain(..excess);
+        Ok(())
+    }
+
+    async fn assessed_page(
+        &self,
+        framework: &ComplianceFramework,
+        window: ComplianceWindow,
+        tenant_id: Option<&str>,
+        after: Option<(DateTime<Utc>, Uuid)>,
+        limit: usize,
+    ) -> Result<Vec<AssessedRecord>, ComplianceError> {
+        let held = self.records.read().await;
+        let mut page: Vec<AssessedRecord> = held.iter()
+            .filter(|assessed| {
+                assessed.record.framework == *framework
+                    && window.contains(assessed.at)
+                    && tenant_id.map_or(true, |tenant| assessed.tenant_id.as_deref() == Some(tenant))
+                    && after.map_or(true, |after| (assessed.at, assessed.id) > after)
+            })
+            .cloned()
+            .collect();
+        page.sort_by_key(|assessed| (assessed.at, assessed.id));
+        page.truncate(limit);
+        Ok(page)
+    }
+}
+
+/// Stored form of a framework name
+pub fn framework_key(framework: &ComplianceFramework) -> String {
+    match framework {
+        ComplianceFramework::Custom(name) => format!("custom:{}", name),
+        builtin => format!("{:?}", builtin),
+    }
+}
+
+/// Half-open time range `[start, end)` a summary covers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct ComplianceWindow {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+}
+
+impl ComplianceWindow {
+    /// The `length` leading up to `end`
+    pub fn ending_at(end: DateTime<Utc>, length: Duration) -> Self {
+        Self { start: end - length, end }
+    }
+
+    pub fn contains(&self, at: DateTime<Utc>) -> bool {
+        self.start <= at && at < self.end
+    }
+
+    /// Reject empty windows and those longer than `MAX_COMPLIANCE_WINDOW_DAYS`
+    pub fn validate(&self) -> Result<(), ComplianceError> {
+        if self.start >= self.end {
+            return Err(ComplianceError::EmptyWindow);
+        }
+        if self.end - self.start > Duration::days(MAX_COMPLIANCE_WINDOW_DAYS) {
+            return Err(ComplianceError::WindowTooLong);
+        }
+        Ok(())
+    }
+
+    /// Hourly trend points for windows up to two days, daily beyond
+    fn trend_step(&self) -> Duration {
+        if self.end - self.start <= Duration::days(2) {
+            Duration::hours(1)
+        } else {
+            Duration::days(1)
+        }
+    }
+}
+
+/// Which records a caller may see in a summary
+#[derive(Debug, Clone)]
+pub struct ComplianceScope {
+    /// Only this tenant's operations; `None` for platform-wide callers
+    pub tenant_id: Option<String>,
+    /// Operations classified above this level are left out, as are unclassified records
+    pub clearance: ClassificationLevel,
+}
+
+/// Pass/fail counts of one requirement
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct RequirementSummary {
+    pub requirement: String,
+    pub passed: u64,
+    pub failed: u64,
+    pub unknown: u64,
+}
+
+/// Pass/fail counts of one trend period
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct CompliancePoint {
+    pub period_start: DateTime<Utc>,
+    pub passed: u64,
+    pub failed: u64,
+}
+
+/// An operation and how many violations it produced
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ViolatingOperation {
+    pub operation: String,
+    pub violations: u64,
+}
+
+/// At-a-glance posture of one framework over a window
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ComplianceSummary {
+    pub framework: ComplianceFramework,
+    pub window: ComplianceWindow,
+    /// Percentage of passed among passed and failed; `None` with neither
+    pub score: Option<f64>,
+    /// By requirement name
+    pub requirements: Vec<RequirementSummary>,
+    /// One point per hour or day of the window, oldest first
+    pub trend: Vec<CompliancePoint>,
+    /// Most violations first
+    pub top_violations: Vec<ViolatingOperation>,
+}
+
+/// What a record says about the operation it observed
+struct OperationFacts<'a> {
+    personal_data: bool,
+    health_data: bool,
+    write: bool,
+    encrypted: bool,
+    user_id: Option<&'a str>,
+    consent: Option<&'a str>,
+    lawful_basis: Option<&'a str>,
+}
+
+impl<'a> OperationFacts<'a> {
+    fn of(record: &'a ObservationRecord) -> Self {
+        let protection = record.privacy_protection.as_ref();
+        let privacy_level = record.context.privacy_level.as_ref();
+        let health_data = privacy_level == Some(&PrivacyLevel::PHI);
+        let action = record.operation.rsplit('.').next().unwrap_or(&record.operation);
+
+        Self {
+            personal_data: health_data
+                || privacy_level == Some(&PrivacyLevel::PII)
+                || protection.is_some_and(|protection| protection.pii_detected),
+            health_data,
+            write: WRITE_OPERATIONS.iter().any(|write| action.starts_with(write)),
+            encrypted: protection.is_some_and(|protection| protection.encryption_applied),
+            user_id: record.context.user_id.as_deref().filter(|user| !user.is_empty()),
+            consent: record.metadata.get("consent").map(String::as_str),
+            lawful_basis: record.metadata.get("lawful_basis").map(String::as_str),
+        }
+    }
+}
+
+/// Generates compliance evidence for observed operations and keeps per-framework scores
+#[derive(Debug)]
+pub struct ComplianceEngine {
+    frameworks: Vec<ComplianceFramework>,
+    tallies: RwLock<HashMap<ComplianceFramework, Tally>>,
+    store: Arc<dyn ComplianceStore>,
+}
+
+impl Default for ComplianceEngine {
+    fn default() -> Self {
+        Self::new(SUPPORTED_FRAMEWORKS.to_vec())
+    }
+}
+
+impl ComplianceEngine {
+    /// Assess operations against `frameworks`; frameworks without rules are ignored
+    pub fn new(frameworks: Vec<ComplianceFramework>) -> Self {
+        let frameworks: Vec<ComplianceFramework> = frameworks.into_iter()
+            .filter(|framework| {
+                let supported = SUPPORTED_FRAMEWORKS.contains(framework);
+                if !supported {
+                    tracing::warn!("No compliance rules for {:?}; it will not be assessed", framework);
+                }
+                supported
+            })
+            .collect();
+        Self {
+            frameworks,
+            tallies: RwLock::new(HashMap::new()),
+            store: Arc::new(InMemoryComplianceStore::default()),
+        }
+    }
+
+    /// Keep assessed records for summaries in `store` instead of memory
+    pub fn with_store(mut self, store: Arc<dyn ComplianceStore>) -> Self {
+        self.store = store;
+        self
+    }
+
+    pub fn frameworks(&self) -> &[ComplianceFramework] {
+        &self.frameworks
+    }
+
+    /// Compliance records for the operation `record` observed
+    ///
+    /// Operations touching no personal data yield none.
+    pub fn evaluate(&self, record: &ObservationRecord) -> Vec<ComplianceRecord> {
+        let facts = OperationFacts::of(record);
+        if !facts.personal_data {
+            return Vec::new();
+        }
+
+        self.frameworks.iter()
+            .flat_map(|framework| match framework {
+                ComplianceFramework::GDPR => gdpr_records(record, &facts),
+                ComplianceFramework::HIPAA => hipaa_records(record, &facts),
+                _ => Vec::new(),
+            })
+            .collect()
+    }
+
+    /// Attach the operation's compliance records to `record`, count them toward the scores
+    /// and keep them for summaries
+    ///
+    /// The records stay attached even if the store rejects them.
+    pub async fn assess(&self, record: &mut ObservationRecord) -> Result<(), ComplianceError> {
+        let records = self.evaluate(record);
+        if records.is_empty() {
+            return Ok(());
+        }
+
+        {
+            let mut tallies = self.tallies.write().await;
+            for compliance in &records {
+                let tally = tallies.entry(compliance.framework.clone()).or_default();
+                match compliance.status {
+                    ComplianceStatus::Compliant => tally.compliant += 1,
+                    ComplianceStatus::NonCompliant => tally.non_compliant += 1,
+                    ComplianceStatus::Unknown => tally.unknown += 1,
+                }
+            }
+        }
+        let assessed: Vec<AssessedRecord> = records.iter()
+            .map(|compliance| AssessedRecord {
+                id: Uuid::new_v4(),
+                at: record.completed_at.unwrap_or(record.started_at),
+                operation: record.operation.clone(),
+                tenant_id: record.context.tenant_id.clone(),
+                classification: record.context.classification.clone(),
+                record: compliance.clone(),
+            })
+            .collect();
+        record.compliance_records.extend(records);
+        self.store.store_assessed(&assessed).await
+    }
+
+    /// Posture of `framework` over `window`, counting only records `scope` may see
+    ///
+    /// The window may be at most `MAX_COMPLIANCE_WINDOW_DAYS` long; stored records are read
+    /// a page at a time, so a long window doesn't load its whole history at once.
+    pub async fn compliance_summary(
+        &self,
+        framework: &ComplianceFramework,
+        window: ComplianceWindow,
+        scope: &ComplianceScope,
+    ) -> Result<ComplianceSummary, ComplianceError> {
+        window.validate()?;
+        let step = window.trend_step();
+        let mut trend: Vec<CompliancePoint> = std::iter::successors(Some(window.start), |start| Some(*start + step))
+            .take_while(|start| *start < window.end)
+            .map(|period_start| CompliancePoint { period_start, passed: 0, failed: 0 })
+            .collect();
+        let mut requirements: BTreeMap<String, RequirementSummary> = BTreeMap::new();
+        let mut violations: HashMap<String, u64> = HashMap::new();
+
+        let mut after = None;
+        loop {
+            let page = self.store
+                .assessed_page(framework, window, scope.tenant_id.as_deref(), after, SUMMARY_PAGE_SIZE)
+                .await?;
+            let Some(last) = page.last() else {
+                break;
+            };
+            after = Some((last.at, last.id));
+            let exhausted = page.len() < SUMMARY_PAGE_SIZE;
+
+            let visible = page.iter().filter(|assessed| {
+                window.contains(assessed.at)
+                    && assessed.classification.as_ref().is_some_and(|level| scope.clearance.dominates(level))
+            });
+            for assessed in visible {
+                let requirement = requirements.entry(assessed.record.requirement.clone())
+                    .or_insert_with(|| RequirementSummary {
+                        requirement: assessed.record.requirement.clone(),
+                        passed: 0,
+                        failed: 0,
+                        unknown: 0,
+                    });
+                let point = ((assessed.at - window.start).num_seconds() / step.num_seconds()) as usize;
+                match assessed.record.status {
+                    ComplianceStatus::Compliant => {
+                        requirement.passed += 1;
+                        trend[point].passed += 1;
+                    }
+                    ComplianceStatus::NonCompliant => {
+                        requirement.failed += 1;
+                        trend[point].failed += 1;
+                        *violations.entry(assessed.operation.clone()).or_default() += 1;
+                    }
+                    ComplianceStatus::Unknown => requirement.unknown += 1,
+                }
+            }
+            if exhausted {
+                break;
+            }
+        }
+
+        let (passed, failed) = requirements.values()
+            .fold((0, 0), |(passed, failed), requirement| (passed + requirement.passed, failed + requirement.failed));
+        let mut top_violations: Vec<ViolatingOperation> = violations.into_iter()
+            .map(|(operation, violations)| ViolatingOperation { operation, violations })
+            .collect();
+        top_violations.sort_by(|a, b| b.violations.cmp(&a.violations).then_with(|| a.operation.cmp(&b.operation)));
+        top_violations.truncate(TOP_VIOLATIONS);
+
+        Ok(ComplianceSummary {
+            framework: framework.clone(),
+            window,
+            score: (passed + failed > 0).then(|| passed as f64 * 100.0 / (passed + failed) as f64),
+            requirements: requirements.into_values().collect(),
+            trend,
+            top_violations,
+        })
+    }
+
+    /// Score of one framework
+    pub async fn score(&self, framework: &ComplianceFramework) -> ComplianceScore {
+        let tally = self.tallies.read().await.get(framework).copied().unwrap_or_default();
+        let determined = tally.compliant + tally.non_compliant;
+        ComplianceScore {
+            framework: framework.clone(),
+            compliant: tally.compliant,
+            non_compliant: tally.non_compliant,
+            unknown: tally.unknown,
+            score: (determined > 0).then(|| tally.compliant as f64 * 100.0 / determined as f64),
+        }
+    }
+
+    /// Scores of every assessed framework
+    pub async fn scores(&self) -> Vec<ComplianceScore> {
+        let mut scores = Vec::with_capacity(self.frameworks.len());
+        for framework in &self.frameworks {
+            scores.push(self.score(framework).await);
+        }
+        scores
+    }
+}
+
+fn compliance_record(
+    framework: ComplianceFramework,
+    requirement: &str,
+    status: ComplianceStatus,
+    evidence: serde_json::Value,
+) -> ComplianceRecord {
+    ComplianceRecord { framework, requirement: requirement.to_string(), status, evidence }
+}
+
+/// Audit trail entry naming who performed the operation
+fn access_logged(framework: ComplianceFramework, requirement: &str, record: &ObservationRecord, facts: &OperationFacts) -> ComplianceRecord {
+    let (status, evidence) = match facts.user_id {
+        Some(_) => (ComplianceStatus::Compliant, "access logged"),
+        None => (ComplianceStatus::NonCompliant, "access not attributable to a user"),
+    };
+    compliance_record(framework, requirement, status, serde_json::json!({
+        "evidence": evidence,
+        "observation_id": record.observation_id,
+        "user_id": facts.user_id,
+    }))
+}
+
+/// Encryption of personal data written at rest
+fn encryption_applied(framework: ComplianceFramework, requirement: &str, record: &ObservationRecord, facts: &OperationFacts) -> ComplianceRecord {
+    let (status, evidence) = if facts.encrypted {
+        (ComplianceStatus::Compliant, "encryption applied")
+    } else {
+        (ComplianceStatus::NonCompliant, "personal data written unencrypted")
+    };
+    compliance_record(framework, requirement, status, serde_json::json!({
+        "evidence": evidence,
+        "observation_id": record.observation_id,
+        "operation": record.operation,
+    }))
+}
+
+fn gdpr_records(record: &ObservationRecord, facts: &OperationFacts) -> Vec<ComplianceRecord> {
+    let (status, evidence) = match (facts.consent, facts.lawful_basis) {
+        (Some("granted"), _) => (ComplianceStatus::Compliant, "consent recorded".to_string()),
+        (Some(consent), _) => (ComplianceStatus::NonCompliant, format!("consent {}", consent)),
+        (None, Some(basis)) => (ComplianceStatus::Compliant, format!("lawful basis: {}", basis)),
+        (None, None) => (ComplianceStatus::Unknown, "no consent or lawful basis recorded".to_string()),
+    };
+
+    let mut records = vec![
+        access_logged(ComplianceFramework::GDPR, "Art. 30 records of processing", record, facts),
+        compliance_record(ComplianceFramework::GDPR, "Art. 6 lawfulness of processing", status, serde_json::json!({
+            "evidence": evidence,
+            "observation_id": record.observation_id,
+        })),
+    ];
+    if facts.write {
+        records.push(encryption_applied(ComplianceFramework::GDPR, "Art. 32 security of processing", record, facts));
+    }
+    records
+}
+
+fn hipaa_records(record: &ObservationRecord, facts: &OperationFacts) -> Vec<ComplianceRecord> {
+    let mut records = vec![access_logged(ComplianceFramework::HIPAA, "164.312(b) audit controls", record, facts)];
+    if facts.write {
+        records.push(encryption_applied(ComplianceFramework::HIPAA, "164.312(a)(2)(iv) encryption", record, facts));
+    }
+    if facts.health_data && facts.user_id.is_none() {
+        records.push(compliance_record(
+            ComplianceFramework::HIPAA,
+            "164.312(d) person or entity authentication",
+            ComplianceStatus::NonCompliant,
+            serde_json::json!({
+                "evidence": "health data accessed without an authenticated user",
+                "observation_id": record.observation_id,
+            }),
+        ));
+    }
+    records
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::observability::observation::{ObservationContext, OperationResult, PrivacyProtection};
+
+    fn pii_record(operation: &str, encrypted: bool) -> ObservationRecord {
+        let mut record = ObservationRecord::new(
+            operation,
+            ObservationContext {
+                user_id: Some("clerk-17".to_string()),
+                privacy_level: Some(PrivacyLevel::PII),
+                ..Default::default()
+            },
+            OperationResult::Success { return_value: None },
+        );
+        record.privacy_protection = Some(PrivacyProtection {
+            encryption_applied: encrypted,
+            ..Default::default()
+        });
+        record
+    }
+
+    fn status(record: &ObservationRecord, framework: ComplianceFramework, requirement: &str) -> Option<ComplianceStatus> {
+        record.compliance_records.iter()
+            .find(|compliance| compliance.framework == framework && compliance.requirement.starts_with(requirement))
+            .map(|compliance| compliance.status.clone())
+    }
+
+    #[tokio::test]
+    async fn test_unencrypted_pii_write_is_hipaa_non_compliant() {
+        let engine = ComplianceEngine::default();
+
+        let mut unencrypted = pii_record("patients.update", false);
+        engine.assess(&mut unencrypted).await.unwrap();
+        assert_eq!(status(&unencrypted, ComplianceFramework::HIPAA, "164.312(a)"), Some(ComplianceStatus::NonCompliant));
+        assert_eq!(status(&unencrypted, ComplianceFramework::HIPAA, "164.312(b)"), Some(ComplianceStatus::Compliant));
+        assert_eq!(status(&unencrypted, ComplianceFramework::GDPR, "Art. 6"), Some(ComplianceStatus::Unknown));
+
+        let mut encrypted = pii_record("patients.update", true);
+        encrypted.metadata.insert("consent".to_string(), "granted".to_string());
+        engine.assess(&mut encrypted).await.unwrap();
+        assert_eq!(status(&encrypted, ComplianceFramework::HIPAA, "164.312(a)"), Some(ComplianceStatus::Compliant));
+        assert_eq!(status(&encrypted, ComplianceFramework::GDPR, "Art. 6"), Some(ComplianceStatus::Compliant));
+
+        // Reads aren't held to the encryption rule, and non-personal operations yield nothing
+        let mut read = pii_record("patients.read", false);
+        engine.assess(&mut read).await.unwrap();
+        assert_eq!(status(&read, ComplianceFramework::HIPAA, "164.312(a)"), None);
+        let mut metrics = ObservationRecord::new("metrics.update", ObservationContext::default(), OperationResult::InProgress);
+        engine.assess(&mut metrics).await.unwrap();
+        assert!(metrics.compliance_records.is_empty());
+
+        // HIPAA: three audit controls and one encryption compliant, one encryption not
+        let hipaa = engine.score(&ComplianceFramework::HIPAA).await;
+        assert_eq!((hipaa.compliant, hipaa.non_compliant), (4, 1));
+        assert_eq!(hipaa.score, Some(80.0));
+        assert_eq!(engine.score(&ComplianceFramework::SOX).await.score, None);
+    }
+
+    async fn assess_at(engine: &ComplianceEngine, operation: &str, at: DateTime<Utc>, tenant: &str, classification: ClassificationLevel) {
+        let mut record = pii_record(operation, false);
+        record.completed_at = Some(at);
+        record.context.tenant_id = Some(tenant.to_string());
+        record.context.classification = Some(classification);
+        engine.assess(&mut record).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_summary_counts_only_violations_in_window_and_scope() {
+        // A store shared with a second engine stands in for records persisted before a restart
+        let store = Arc::new(InMemoryComplianceStore::default());
+        let engine = ComplianceEngine::default().with_store(store.clone());
+        let end = "2024-03-10T00:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let window = ComplianceWindow::ending_at(end, Duration::days(7));
+
+        assess_at(&engine, "patients.update", end - Duration::days(1), "acme", ClassificationLevel::Internal).await;
+        assess_at(&engine, "patients.update", end - Duration::days(6), "acme", ClassificationLevel::Internal).await;
+        assess_at(&engine, "billing.write", end - Duration::hours(3), "acme", ClassificationLevel::Internal).await;
+        // Outside the window, another tenant's, and above the caller's clearance
+        assess_at(&engine, "patients.update", end - Duration::days(8), "acme", ClassificationLevel::Internal).await;
+        assess_at(&engine, "patients.update", end, "acme", ClassificationLevel::Internal).await;
+        assess_at(&engine, "patients.update", end - Duration::days(1), "globex", ClassificationLevel::Internal).await;
+        assess_at(&engine, "patients.update", end - Duration::days(1), "acme", ClassificationLevel::Secret).await;
+
+        let scope = ComplianceScope {
+            tenant_id: Some("acme".to_string()),
+            clearance: ClassificationLevel::Confidential,
+        };
+        let restarted = ComplianceEngine::default().with_store(store);
+        let summary = restarted.compliance_summary(&ComplianceFramework::HIPAA, window, &scope).await.unwrap();
+
+        let encryption = summary.requirements.iter()
+            .find(|requirement| requirement.requirement.starts_with("164.312(a)"))
+            .unwrap();
+        assert_eq!((encryption.passed, encryption.failed), (0, 3));
+        assert_eq!(summary.score, Some(50.0));
+        assert_eq!(summary.top_violations, vec![
+            ViolatingOperation { operation: "patients.update".to_string(), violations: 2 },
+            ViolatingOperation { operation: "billing.write".to_string(), violations: 1 },
+        ]);
+
+        assert_eq!(summary.trend.len(), 7);
+        assert_eq!(summary.trend[1].failed, 1);
+        assert_eq!(summary.trend[6].failed, 2);
+        assert_eq!(summary.trend.iter().map(|point| point.failed).sum::<u64>(), 3);
+
+        // Windows are capped so a summary's trend and scan stay bounded
+        let too_long = ComplianceWindow::ending_at(end, Duration::days(MAX_COMPLIANCE_WINDOW_DAYS + 1));
+        assert!(matches!(
+            restarted.compliance_summary(&ComplianceFramework::HIPAA, too_long, &scope).await,
+            Err(ComplianceError::WindowTooLong)
+        ));
+        let empty = ComplianceWindow { start: end, end };
+        assert!(matches!(
+            restarted.compliance_summary(&ComplianceFramework::HIPAA, empty, &scope).await,
+            Err(ComplianceError::EmptyWindow)
+        ));
+    }
+}
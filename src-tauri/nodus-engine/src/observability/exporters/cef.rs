@@ -0,0 +1,377 @@
+// src-tauri/src/observability/exporters/cef.rs
+// CEF Export - Renders forensic envelopes and observation records as ArcSight Common Event Format
+// One event per line, for SIEMs (ArcSight, Splunk, QRadar) ingesting from a file or syslog forwarder
+
+use std::fmt::Write as _;
+
+use super::redaction::{self, RedactionMode, RedactionPolicy};
+use super::{ExportError, ExportFormat, ExporterConfig, ObservabilityExporter};
+use crate::observability::observation::{ObservationRecord, OperationResult};
+use crate::observability::ForensicEnvelope;
+use crate::security::ClassificationLevel;
+
+/// Device fields of the CEF header and the event renderer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CefFormatter {
+    pub device_vendor: String,
+    pub device_product: String,
+    pub device_version: String,
+}
+
+impl Default for CefFormatter {
+    fn default() -> Self {
+        Self {
+            device_vendor: "Nodus".to_string(),
+            device_product: "Nodus Engine".to_string(),
+            device_version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+}
+
+impl CefFormatter {
+    /// One CEF line (without trailing newline) for a forensic envelope
+    ///
+    /// Signature id is the event type, name the action, severity from the classification.
+    pub fn format_envelope(&self, envelope: &ForensicEnvelope) -> String {
+        let mut line = self.header(&envelope.event_type, &envelope.action, &envelope.classification);
+        let mut extension = Extension::new(&mut line);
+        extension.push("rt", &envelope.timestamp.timestamp_millis().to_string());
+        extension.push("externalId", &envelope.envelope_id.to_string());
+        extension.push("suser", &envelope.user_id);
+        extension.push("act", &envelope.action);
+        extension.push_custom(1, "operationId", &envelope.operation_id.to_string());
+        extension.push_custom(2, "sessionId", &envelope.session_id.to_string());
+        extension.push_custom(3, "classification", &envelope.classification.to_string());
+        if let Some(resource) = &envelope.resource {
+            extension.push_custom(4, "resource", resource);
+        }
+        extension.push_custom(5, "auditTrailHash", &envelope.audit_trail_hash);
+        if !envelope.metadata.is_null() && envelope.metadata != serde_json::json!({}) {
+            extension.push("msg", &envelope.metadata.to_string());
+        }
+        line
+    }
+
+    /// Envelopes as newline-terminated CEF lines, each redacted under `redaction` first
+    pub fn format_envelopes(&self, envelopes: &[ForensicEnvelope], redaction: Option<&RedactionPolicy>) -> String {
+        envelopes.iter()
+            .map(|envelope| self.format_envelope(&redaction::apply_envelope(envelope, redaction)) + "\n")
+            .collect()
+    }
+
+    /// One CEF line (without trailing newline) for an observation record
+    pub fn format_record(&self, record: &ObservationRecord) -> String {
+        let classification = record.context.classification.clone().unwrap_or(ClassificationLevel::Unclassified);
+        let mut line = self.header(&record.operation, &record.operation, &classification);
+        let mut extension = Extension::new(&mut line);
+        extension.push("rt", &record.started_at.timestamp_millis().to_string());
+        if let Some(completed_at) = record.completed_at {
+            extension.push("end", &completed_at.timestamp_millis().to_string());
+        }
+        extension.push("externalId", &record.observation_id);
+        if let Some(user_id) = &record.context.user_id {
+            extension.push("suser", user_id);
+        }
+        match &record.result {
+            OperationResult::Success { .. } => extension.push("outcome", "success"),
+            OperationResult::Error { error_message, .. } => {
+                extension.push("outcome", "failure");
+                extension.push("reason", error_message);
+            }
+            OperationResult::InProgress => {}
+        }
+        if let Some(session_id) = &record.context.session_id {
+            extension.push_custom(2, "sessionId", session_id);
+        }
+        extension.push_custom(3, "classification", &classification.to_string());
+        line
+    }
+
+    fn header(&self, signature_id: &str, name: &str, classification: &ClassificationLevel) -> String {
+        format!(
+            "CEF:0|{}|{}|{}|{}|{}|{}|",
+            escape_header(&self.device_vendor),
+            escape_header(&self.device_product),
+            escape_header(&self.device_version),
+            escape_header(signature_id),
+            escape_header(name),
+            severity(classification),
+        )
+    }
+}
+
+/// Confidential and above, and any PII/PHI/financial/legal payload, reach the SIEM only hashed
+pub fn default_redaction() -> RedactionPolicy {
+    RedactionPolicy::new(ClassificationLevel::Confidential, RedactionMode::Hash)
+}
+
+/// CEF severity (0-10) for a classification, by its built-in baseline
+pub fn severity(classification: &ClassificationLevel) -> u8 {
+    match classification.builtin_baseline() {
+        ClassificationLevel::Unclassified => 1,
+        ClassificationLevel::Internal => 3,
+        ClassificationLevel::Confidential => 5,
+        ClassificationLevel::Secret => 8,
+        ClassificationLevel::NatoSecret | ClassificationLevel::Custom(_) => 10,
+    }
+}
+
+/// Space-separated `key=value` pairs appended to a CEF line
+struct Extension<'a> {
+    line: &'a mut String,
+    empty: bool,
+}
+
+impl<'a> Extension<'a> {
+    fn new(line: &'a mut String) -> Self {
+        Self { line, empty: true }
+    }
+
+    fn push(&mut self, key: &str, value: &str) {
+        if !self.empty {
+            self.line.push(' ');
+        }
+        self.empty = false;
+        let _ = write!(self.line, "{}={}", key, escape_extension(value));
+    }
+
+    /// `csN` custom string with its `csNLabel`
+    fn push_custom(&mut self, slot: u8, label: &str, value: &str) {
+        self.push(&format!("cs{}Label", slot), label);
+        self.push(&format!("cs{}", slot), value);
+    }
+}
+
+/// Header fields escape `\` and `|`; line breaks are not allowed, so they become spaces
+fn escape_header(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '|' => escaped.push_str("\\|"),
+            '\r' | '\n' => escaped.push(' '),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Extension values escape `\` and `=`, and encode line breaks as `\n` / `\r`
+fn escape_extension(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '=' => escaped.push_str("\\="),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Appends observation records to a file as CEF lines, for a SIEM forwarder to pick up
+///
+/// Records are redacted by the `ExportEngine` under `ExporterConfig::redaction`; streamed
+/// envelopes are redacted here under the same policy.
+#[derive(Debug)]
+pub struct CefExporter {
+    file_path: String,
+    formatter: CefFormatter,
+    config: ExporterConfig,
+}
+
+impl CefExporter {
+    pub fn new(file_path: impl Into<String>) -> Self {
+        Self {
+            file_path: file_path.into(),
+            formatter: CefFormatter::default(),
+            config: ExporterConfig {
+                name: "cef_file".to_string(),
+                format: ExportFormat::CEF,
+                batch_size: None,
+                timeout_ms: Some(30_000),
+                retry_config: None,
+                server_side_encryption: None,
+                compression: None,
+                redaction: Some(default_redaction()),
+            },
+        }
+    }
+
+    /// Replace the default redaction policy; None exports at full fidelity
+    pub fn with_redaction(mut self, redaction: Option<RedactionPolicy>) -> Self {
+        self.config.redaction = redaction;
+        self
+    }
+
+    /// Use custom device vendor/product/version fields
+    pub fn with_formatter(mut self, formatter: CefFormatter) -> Self {
+        self.formatter = formatter;
+        self
+    }
+
+    /// Stream a forensic envelope to the same destination
+    pub async fn export_envelope(&self, envelope: &ForensicEnvelope) -> Result<(), ExportError> {
+        let envelope = redaction::apply_envelope(envelope, self.config.redaction.as_ref());
+        self.append_line(self.formatter.format_envelope(&envelope)).await
+    }
+
+    async fn append_line(&self, mut line: String) -> Result<(), ExportError> {
+        use tokio::io::AsyncWriteExt;
+
+        line.push('\n');
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.file_path)
+            .await
+            .map_err(|e| ExportError::IOError(e.to_string()))?;
+        file.write_all(line.as_bytes()).await
+            .map_err(|e| ExportError::IOError(e.to_string()))?;
+        file.flush().await
+            .map_err(|e| ExportError::IOError(e.to_string()))
+    }
+}
+
+#[async_trait::async_trait]
+impl ObservabilityExporter for CefExporter {
+    async fn export(&self, record: &ObservationRecord) -> Result<(), ExportError> {
+        self.append_line(self.formatter.format_record(record)).await
+    }
+
+    fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    fn config(&self) -> ExporterConfig {
+        self.config.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+    use uuid::Uuid;
+
+    fn formatter() -> CefFormatter {
+        CefFormatter {
+            device_vendor: "Nodus".to_string(),
+            device_product: "Nodus Engine".to_string(),
+            device_version: "1.4.0".to_string(),
+        }
+    }
+
+    fn known_envelope() -> ForensicEnvelope {
+        ForensicEnvelope {
+            envelope_id: Uuid::parse_str("6f1c2b9e-0d4a-4c1e-9a7b-2f3e4d5c6b7a").unwrap(),
+            operation_id: Uuid::parse_str("11111111-2222-4333-8444-555555555555").unwrap(),
+            event_type: "data.event".to_string(),
+            timestamp: Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap(),
+            user_id: "analyst".to_string(),
+            session_id: Uuid::parse_str("aaaaaaaa-bbbb-4ccc-8ddd-eeeeeeeeeeee").unwrap(),
+            classification: ClassificationLevel::Confidential,
+            action: "update".to_string(),
+            resource: Some("entity:42".to_string()),
+            before_state: None,
+            after_state: None,
+            metadata: serde_json::json!({"operation_type": "update"}),
+            audit_trail_hash: "q83v".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_known_envelope_renders_spec_compliant_cef() {
+        let line = formatter().format_envelope(&known_envelope());
+        assert_eq!(
+            line,
+            "CEF:0|Nodus|Nodus Engine|1.4.0|data.event|update|5|\
+             rt=1709294400000 externalId=6f1c2b9e-0d4a-4c1e-9a7b-2f3e4d5c6b7a suser=analyst act=update \
+             cs1Label=operationId cs1=11111111-2222-4333-8444-555555555555 \
+             cs2Label=sessionId cs2=aaaaaaaa-bbbb-4ccc-8ddd-eeeeeeeeeeee \
+             cs3Label=classification cs3=CONFIDENTIAL cs4Label=resource cs4=entity:42 \
+             cs5Label=auditTrailHash cs5=q83v msg={\"operation_type\":\"update\"}"
+        );
+        assert_eq!(line.lines().count(), 1);
+    }
+
+    #[test]
+    fn test_cef_escaping() {
+        let envelope = ForensicEnvelope {
+            event_type: "security|event".to_string(),
+            action: "login\\sso\nretry".to_string(),
+            user_id: "a=b".to_string(),
+            resource: None,
+            metadata: serde_json::json!({}),
+            audit_trail_hash: "x\\y=\r\n".to_string(),
+            ..known_envelope()
+        };
+        let line = formatter().format_envelope(&envelope);
+
+        // Header: pipes and backslashes escaped, line breaks flattened
+        assert!(line.starts_with("CEF:0|Nodus|Nodus Engine|1.4.0|security\\|event|login\\\\sso retry|5|"));
+        // Extension: equals signs and backslashes escaped, line breaks encoded
+        assert!(line.contains(" suser=a\\=b "));
+        assert!(line.contains(" act=login\\\\sso\\nretry "));
+        assert!(line.ends_with(" cs5=x\\\\y\\=\\r\\n"));
+        assert!(!line.contains("cs4") && !line.contains("msg="));
+        assert_eq!(line.lines().count(), 1);
+
+        assert_eq!(severity(&ClassificationLevel::Unclassified), 1);
+        assert_eq!(severity(&ClassificationLevel::NatoSecret), 10);
+    }
+
+    #[tokio::test]
+    async fn test_cef_exporter_streams_redacted_lines() {
+        use crate::observability::exporters::ExportEngine;
+        use crate::observability::observation::ObservationContext;
+        use std::sync::Arc;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.cef");
+        let exporter = Arc::new(CefExporter::new(path.to_string_lossy().to_string()).with_formatter(formatter()));
+        let engine = ExportEngine::new(vec![exporter.clone()]);
+
+        let record = ObservationRecord::new(
+            "entity.write",
+            ObservationContext {
+                user_id: Some("analyst".to_string()),
+                classification: Some(ClassificationLevel::Secret),
+                ..Default::default()
+            },
+            OperationResult::Error {
+                error_type: "Denied".to_string(),
+                error_message: "no write down".to_string(),
+                error_code: None,
+            },
+        );
+        assert!(engine.export(&record).await.is_success());
+        exporter.export_envelope(&known_envelope()).await.unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("CEF:0|Nodus|Nodus Engine|1.4.0|entity.write|entity.write|8|"));
+        assert!(lines[0].contains(" suser=analyst outcome=failure reason=[REDACTED] sha256:"));
+        assert!(!lines[0].contains("no write down"));
+
+        // Confidential envelope: who and what kept, resource and metadata hashed
+        assert!(lines[1].contains(" suser=analyst act=update "));
+        assert!(lines[1].contains(" cs4Label=resource cs4=sha256:"));
+        assert!(!lines[1].contains("entity:42") && !lines[1].contains("operation_type"));
+        assert_eq!(
+            lines[1],
+            formatter().format_envelopes(&[known_envelope()], Some(&default_redaction())).trim_end()
+        );
+
+        // Without redaction the envelope goes out at full fidelity
+        let unredacted = CefExporter::new(path.to_string_lossy().to_string())
+            .with_formatter(formatter())
+            .with_redaction(None);
+        unredacted.export_envelope(&known_envelope()).await.unwrap();
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(contents.lines().last().unwrap(), formatter().format_envelope(&known_envelope()));
+    }
+}
@@ -0,0 +1,160 @@
+// src-tauri/src/observability/exporters/dead_letter.rs
+// Dead-letter queue for records an exporter could not deliver after exhausting its retries
+// Bounded and optionally persisted as JSONL so failed records survive restarts until replayed
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use super::ExportError;
+use crate::observability::observation::ObservationRecord;
+
+/// A record that failed to reach one exporter
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetter {
+    /// Record as handed to the exporter (already redacted for it)
+    pub record: ObservationRecord,
+    pub exporter: String,
+    pub last_error: String,
+    pub attempts: usize,
+    pub failed_at: DateTime<Utc>,
+}
+
+impl DeadLetter {
+    /// Whether both entries hold the same record for the same exporter
+    fn is_for(&self, other: &DeadLetter) -> bool {
+        self.exporter == other.exporter && self.record.observation_id == other.record.observation_id
+    }
+}
+
+/// Bounded dead-letter queue; the oldest entry is evicted when full
+#[derive(Debug)]
+pub struct DeadLetterQueue {
+    capacity: usize,
+    path: Option<PathBuf>,
+    entries: tokio::sync::Mutex<VecDeque<DeadLetter>>,
+    evicted: AtomicU64,
+}
+
+impl DeadLetterQueue {
+    /// In-memory queue holding at most `capacity` entries
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            path: None,
+            entries: tokio::sync::Mutex::new(VecDeque::new()),
+            evicted: AtomicU64::new(0),
+        }
+    }
+
+    /// Queue persisted to a JSONL file, loading any entries left from a previous run
+    pub async fn open(path: impl Into<PathBuf>, capacity: usize) -> Result<Self, ExportError> {
+        let mut queue = Self::new(capacity);
+        let path = path.into();
+
+        match tokio::fs::read_to_string(&path).await {
+            Ok(contents) => {
+                let mut entries = VecDeque::new();
+                for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+                    let entry: DeadLetter = serde_json::from_str(line)
+                        .map_err(|e| ExportError::SerializationFailed(e.to_string()))?;
+                    entries.push_back(entry);
+                }
+                while entries.len() > queue.capacity {
+                    entries.pop_front();
+                }
+                queue.entries = tokio::sync::Mutex::new(entries);
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(ExportError::IOError(e.to_string())),
+        }
+
+        queue.path = Some(path);
+        Ok(queue)
+    }
+
+    /// Add a failed record, evicting (and alerting on) the oldest entry when full
+    pub async fn push(&self, entry: DeadLetter) -> Result<(), ExportError> {
+        let mut entries = self.entries.lock().await;
+        if entries.len() >= self.capacity {
+            if let Some(evicted) = entries.pop_front() {
+                self.evicted.fetch_add(1, Ordering::Relaxed);
+                tracing::error!(
+                    "Dead-letter queue full ({} entries); dropped oldest record {} for exporter {}",
+                    self.capacity,
+                    evicted.record.observation_id,
+                    evicted.exporter
+                );
+            }
+        }
+        entries.push_back(entry);
+        self.persist(&entries).await
+    }
+
+    /// Remove an entry once its exporter has accepted the record
+    pub(crate) async fn acknowledge(&self, delivered: &DeadLetter) -> Result<(), ExportError> {
+        let mut entries = self.entries.lock().await;
+        if let Some(position) = entries.iter().position(|entry| entry.is_for(delivered)) {
+            entries.remove(position);
+        }
+        self.persist(&entries).await
+    }
+
+    /// Record another failed delivery of an entry, leaving it in place
+    ///
+    /// Does nothing if the entry was evicted in the meantime.
+    pub(crate) async fn record_failure(&self, failed: DeadLetter) -> Result<(), ExportError> {
+        let mut entries = self.entries.lock().await;
+        if let Some(entry) = entries.iter_mut().find(|entry| entry.is_for(&failed)) {
+            *entry = failed;
+        }
+        self.persist(&entries).await
+    }
+
+    /// Snapshot of queued entries, oldest first
+    pub async fn entries(&self) -> Vec<DeadLetter> {
+        self.entries.lock().await.iter().cloned().collect()
+    }
+
+    pub async fn len(&self) -> usize {
+        self.entries.lock().await.len()
+    }
+
+    pub async fn is_empty(&self) -> bool {
+        self.entries.lock().await.is_empty()
+    }
+
+    /// Wait for any in-progress write and rewrite the backing file from memory
+    pub async fn sync(&self) -> Result<(), ExportError> {
+        let entries = self.entries.lock().await;
+        self.persist(&entries).await
+    }
+
+    /// Entries dropped because the queue was full
+    pub fn evicted_count(&self) -> u64 {
+        self.evicted.load(Ordering::Relaxed)
+    }
+
+    /// Rewrite the backing file atomically (temp file + rename)
+    async fn persist(&self, entries: &VecDeque<DeadLetter>) -> Result<(), ExportError> {
+        let path = match &self.path {
+            Some(path) => path,
+            None => return Ok(()),
+        };
+
+        let mut data = Vec::new();
+        for entry in entries {
+            serde_json::to_writer(&mut data, entry)
+                .map_err(|e| ExportError::SerializationFailed(e.to_string()))?;
+            data.push(b'\n');
+        }
+
+        let tmp = path.with_extension("tmp");
+        tokio::fs::write(&tmp, &data).await
+            .map_err(|e| ExportError::IOError(e.to_string()))?;
+        tokio::fs::rename(&tmp, path).await
+            .map_err(|e| ExportError::IOError(e.to_string()))
+    }
+}
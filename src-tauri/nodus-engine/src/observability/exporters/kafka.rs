@@ -0,0 +1,463 @@
+// src-tauri/src/observability/exporters/kafka.rs
+// Kafka Exporter - Real-time forensic feed for SOC consumers
+// Publishes each record keyed by tenant/classification and waits for the broker's acknowledgement
+
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+use super::redaction::{RedactionMode, RedactionPolicy};
+use super::{protobuf, ExportError, ExportFormat, ExporterConfig, ObservabilityExporter, RetryConfig};
+use crate::observability::observation::ObservationRecord;
+use crate::security::ClassificationLevel;
+
+/// Streaming exporter publishing every record to a Kafka topic
+///
+/// Delivery is at-least-once: `export` only succeeds once the broker has acknowledged the
+/// record, and broker errors are retryable so the `ExportEngine` retries them per the
+/// configured `RetryConfig` before dead-lettering. Consumers dedupe on the `observation_id`
+/// header.
+#[derive(Debug)]
+pub struct KafkaExporter {
+    settings: KafkaExporterConfig,
+    config: ExporterConfig,
+    producer: Arc<dyn StreamProducer>,
+    in_flight: Semaphore,
+}
+
+/// Broker, topic and buffering settings for the Kafka exporter
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct KafkaExporterConfig {
+    pub bootstrap_servers: String,
+    pub topic: String,
+    pub client_id: String,
+    /// Records allowed to await acknowledgement at once; beyond this exports report backpressure
+    pub max_in_flight: usize,
+    /// How long the producer keeps retrying a record before reporting it undelivered
+    pub message_timeout_ms: u64,
+}
+
+impl Default for KafkaExporterConfig {
+    fn default() -> Self {
+        Self {
+            bootstrap_servers: "localhost:9092".to_string(),
+            topic: "nodus.forensic".to_string(),
+            client_id: "nodus-engine".to_string(),
+            max_in_flight: 10_000,
+            message_timeout_ms: 30_000,
+        }
+    }
+}
+
+/// Streaming feed section of the export policy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KafkaExportPolicy {
+    #[serde(flatten)]
+    pub settings: KafkaExporterConfig,
+    #[serde(default = "default_feed_format")]
+    pub format: ExportFormat,
+    /// Applied before records leave the engine for the feed
+    #[serde(default = "default_feed_redaction")]
+    pub redaction: RedactionPolicy,
+}
+
+fn default_feed_format() -> ExportFormat {
+    ExportFormat::JSON
+}
+
+/// Confidential and above, and any PII/PHI/financial/legal payload, reach the feed only hashed
+fn default_feed_redaction() -> RedactionPolicy {
+    RedactionPolicy::new(ClassificationLevel::Confidential, RedactionMode::Hash)
+}
+
+impl Default for KafkaExportPolicy {
+    fn default() -> Self {
+        Self {
+            settings: KafkaExporterConfig::default(),
+            format: default_feed_format(),
+            redaction: default_feed_redaction(),
+        }
+    }
+}
+
+/// One message handed to the producer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamMessage {
+    pub topic: String,
+    pub key: String,
+    pub payload: Vec<u8>,
+    pub headers: Vec<(String, String)>,
+}
+
+/// Broker acknowledgement for a published message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeliveryAck {
+    pub partition: i32,
+    pub offset: i64,
+}
+
+/// Publishes messages to a streaming broker
+///
+/// `send` resolves once the broker has acknowledged the message. A full local send
+/// buffer is reported as `ExportError::Backpressure`.
+#[async_trait::async_trait]
+pub trait StreamProducer: Send + Sync + std::fmt::Debug {
+    async fn send(&self, message: StreamMessage) -> Result<DeliveryAck, ExportError>;
+}
+
+impl KafkaExporter {
+    /// Create new Kafka exporter publishing JSON through `producer`
+    pub fn new(settings: KafkaExporterConfig, producer: Arc<dyn StreamProducer>) -> Self {
+        let in_flight = Semaphore::new(settings.max_in_flight.max(1));
+        Self {
+            settings,
+            config: ExporterConfig {
+                name: "kafka".to_string(),
+                format: ExportFormat::JSON,
+                batch_size: None,
+                timeout_ms: Some(30_000),
+                retry_config: Some(RetryConfig::default()),
+                server_side_encryption: None,
+                compression: None,
+                redaction: None,
+            },
+            producer,
+            in_flight,
+        }
+    }
+
+    /// Exporter publishing in the format and with the redaction `policy` configures
+    pub fn from_policy(policy: &KafkaExportPolicy, producer: Arc<dyn StreamProducer>) -> Self {
+        let mut exporter = Self::new(policy.settings.clone(), producer);
+        exporter.config.format = policy.format.clone();
+        exporter.config.redaction = Some(policy.redaction.clone());
+        exporter
+    }
+
+    /// Override the exporter configuration (format, timeouts, retries)
+    pub fn with_config(mut self, config: ExporterConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Whether every in-flight slot is waiting on the broker, so new exports are refused
+    pub fn is_backpressured(&self) -> bool {
+        self.in_flight.available_permits() == 0
+    }
+
+    /// Encode a record in the configured format, one record per message
+    fn encode(&self, record: &ObservationRecord) -> Result<Vec<u8>, ExportError> {
+        match &self.config.format {
+            ExportFormat::JSON => serde_json::to_vec(record)
+                .map_err(|e| ExportError::SerializationFailed(e.to_string())),
+            ExportFormat::Protobuf => Ok(protobuf::encode(record)),
+            other => Err(ExportError::SerializationFailed(format!(
+                "Kafka exporter does not support {:?}",
+                other
+            ))),
+        }
+    }
+}
+
+/// Message key `<tenant>/<classification>`, so each tenant and level maps to a stable partition
+pub fn partition_key(record: &ObservationRecord) -> String {
+    let tenant = record.context.tenant_id.as_deref().unwrap_or("system");
+    let classification = record.context.classification.as_ref().map_or("unclassified", |level| level.key());
+    format!("{}/{}", tenant, classification)
+}
+
+#[async_trait::async_trait]
+impl ObservabilityExporter for KafkaExporter {
+    async fn export(&self, record: &ObservationRecord) -> Result<(), ExportError> {
+        let payload = self.encode(record)?;
+
+        // Held until the broker acknowledges, bounding records awaiting delivery
+        let _permit = self.in_flight.try_acquire().map_err(|_| {
+            ExportError::Backpressure(format!(
+                "{} records awaiting acknowledgement on {}",
+                self.settings.max_in_flight, self.settings.topic
+            ))
+        })?;
+
+        let message = StreamMessage {
+            topic: self.settings.topic.clone(),
+            key: partition_key(record),
+            payload,
+            headers: vec![
+                ("observation_id".to_string(), record.observation_id.clone()),
+                ("format".to_string(), format!("{:?}", self.config.format).to_lowercase()),
+            ],
+        };
+
+        let ack = self.producer.send(message).await?;
+        tracing::trace!(
+            "Record {} acknowledged on {} partition {} offset {}",
+            record.observation_id,
+            self.settings.topic,
+            ack.partition,
+            ack.offset
+        );
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    fn config(&self) -> ExporterConfig {
+        self.config.clone()
+    }
+}
+
+/// librdkafka producer with idempotent, fully acknowledged delivery
+#[cfg(feature = "observability_kafka")]
+pub struct RdKafkaProducer {
+    producer: rdkafka::producer::FutureProducer,
+}
+
+#[cfg(feature = "observability_kafka")]
+impl std::fmt::Debug for RdKafkaProducer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RdKafkaProducer").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "observability_kafka")]
+impl RdKafkaProducer {
+    /// Connect a producer to the configured brokers
+    pub fn new(settings: &KafkaExporterConfig) -> Result<Self, ExportError> {
+        let producer = rdkafka::ClientConfig::new()
+            .set("bootstrap.servers", &settings.bootstrap_servers)
+            .set("client.id", &settings.client_id)
+            .set("acks", "all")
+            .set("enable.idempotence", "true")
+            .set("message.timeout.ms", settings.message_timeout_ms.to_string())
+            .set("queue.buffering.max.messages", settings.max_in_flight.max(1).to_string())
+            .create()
+            .map_err(|e| ExportError::Custom(format!("Failed to create Kafka producer: {}", e)))?;
+
+        Ok(Self { producer })
+    }
+}
+
+#[cfg(feature = "observability_kafka")]
+#[async_trait::async_trait]
+impl StreamProducer for RdKafkaProducer {
+    async fn send(&self, message: StreamMessage) -> Result<DeliveryAck, ExportError> {
+        use rdkafka::error::{KafkaError, RDKafkaErrorCode};
+        use rdkafka::message::{Header, OwnedHeaders};
+        use rdkafka::producer::FutureRecord;
+
+        let headers = message.headers.iter().fold(OwnedHeaders::new(), |headers, (key, value)| {
+            headers.insert(Header { key: key.as_str(), value: Some(value.as_str()) })
+        });
+        let record = FutureRecord::to(&message.topic)
+            .key(message.key.as_str())
+            .payload(message.payload.as_slice())
+            .headers(headers);
+
+        // Never wait for queue space: a full local queue is surfaced as backpressure
+        match self.producer.send(record, std::time::Duration::ZERO).await {
+            Ok((partition, offset)) => Ok(DeliveryAck { partition, offset }),
+            Err((KafkaError::MessageProduction(RDKafkaErrorCode::QueueFull), _)) => Err(
+                ExportError::Backpressure(format!("Kafka producer queue full for {}", message.topic)),
+            ),
+            Err((KafkaError::MessageProduction(RDKafkaErrorCode::MessageSizeTooLarge), _)) => Err(
+                ExportError::SerializationFailed(format!("Record too large for {}", message.topic)),
+            ),
+            Err((e, _)) => Err(ExportError::NetworkError(format!("Kafka delivery to {} failed: {}", message.topic, e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::observability::exporters::ExportEngine;
+    use crate::observability::observation::{ObservationContext, OperationResult};
+    use crate::security::ClassificationLevel;
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{Hash, Hasher};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::sync::{Mutex, Notify};
+
+    /// In-memory broker that partitions by key hash and can fail or stall sends
+    #[derive(Debug)]
+    struct MockBroker {
+        partitions: Mutex<Vec<Vec<StreamMessage>>>,
+        failures_left: AtomicUsize,
+        stall: Option<Arc<Notify>>,
+    }
+
+    impl MockBroker {
+        fn new(partitions: usize) -> Self {
+            Self {
+                partitions: Mutex::new(vec![Vec::new(); partitions]),
+                failures_left: AtomicUsize::new(0),
+                stall: None,
+            }
+        }
+
+        async fn partition_of(&self, key: &str) -> Vec<usize> {
+            let partitions = self.partitions.lock().await;
+            (0..partitions.len())
+                .filter(|p| partitions[*p].iter().any(|message| message.key == key))
+                .collect()
+        }
+
+        async fn messages(&self) -> Vec<StreamMessage> {
+            self.partitions.lock().await.iter().flatten().cloned().collect()
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl StreamProducer for MockBroker {
+        async fn send(&self, message: StreamMessage) -> Result<DeliveryAck, ExportError> {
+            if let Some(stall) = &self.stall {
+                stall.notified().await;
+            }
+            if self.failures_left.load(Ordering::SeqCst) > 0 {
+                self.failures_left.fetch_sub(1, Ordering::SeqCst);
+                return Err(ExportError::NetworkError("leader not available".to_string()));
+            }
+
+            let mut partitions = self.partitions.lock().await;
+            let mut hasher = DefaultHasher::new();
+            message.key.hash(&mut hasher);
+            let partition = (hasher.finish() % partitions.len() as u64) as usize;
+            partitions[partition].push(message);
+            Ok(DeliveryAck { partition: partition as i32, offset: partitions[partition].len() as i64 - 1 })
+        }
+    }
+
+    fn record(tenant: &str, classification: ClassificationLevel) -> ObservationRecord {
+        ObservationRecord::new(
+            "storage.put",
+            ObservationContext {
+                tenant_id: Some(tenant.to_string()),
+                classification: Some(classification),
+                ..Default::default()
+            },
+            OperationResult::Success { return_value: None },
+        )
+    }
+
+    fn settings(max_in_flight: usize) -> KafkaExporterConfig {
+        KafkaExporterConfig { max_in_flight, ..Default::default() }
+    }
+
+    #[tokio::test]
+    async fn test_records_round_trip_keyed_by_tenant_and_classification() {
+        let broker = Arc::new(MockBroker::new(8));
+        let exporter = KafkaExporter::new(settings(16), broker.clone());
+
+        let records = vec![
+            record("tenant-a", ClassificationLevel::Secret),
+            record("tenant-b", ClassificationLevel::Internal),
+            record("tenant-a", ClassificationLevel::Secret),
+        ];
+        for record in &records {
+            exporter.export(record).await.unwrap();
+        }
+
+        let messages = broker.messages().await;
+        assert_eq!(messages.len(), 3);
+        for record in &records {
+            let message = messages
+                .iter()
+                .find(|message| message.headers.contains(&("observation_id".to_string(), record.observation_id.clone())))
+                .unwrap();
+            assert_eq!(message.topic, "nodus.forensic");
+            let parsed: ObservationRecord = serde_json::from_slice(&message.payload).unwrap();
+            assert_eq!(parsed.observation_id, record.observation_id);
+        }
+
+        // Same tenant and classification always land on one partition
+        assert_eq!(broker.partition_of("tenant-a/secret").await.len(), 1);
+        assert_eq!(broker.partition_of("tenant-b/internal").await.len(), 1);
+
+        // Protobuf messages decode back to the record
+        let mut config = exporter.config();
+        config.format = ExportFormat::Protobuf;
+        let exporter = exporter.with_config(config);
+        let protobuf_record = record("tenant-c", ClassificationLevel::Confidential);
+        exporter.export(&protobuf_record).await.unwrap();
+
+        let messages = broker.messages().await;
+        let message = messages.iter().find(|message| message.key == "tenant-c/confidential").unwrap();
+        assert_eq!(protobuf::decode(&message.payload).unwrap(), protobuf_record);
+    }
+
+    #[tokio::test]
+    async fn test_policy_redaction_applies_before_publishing() {
+        let broker = Arc::new(MockBroker::new(2));
+        let policy: KafkaExportPolicy = toml::from_str(r#"
+            bootstrap_servers = "broker:9092"
+            topic = "soc.feed"
+        "#).unwrap();
+        let exporter = KafkaExporter::from_policy(&policy, broker.clone());
+        assert_eq!(exporter.config().redaction.unwrap().mode, RedactionMode::Hash);
+        let engine = ExportEngine::new(vec![Arc::new(exporter)]);
+
+        let mut secret = record("tenant-a", ClassificationLevel::Secret);
+        secret.result = OperationResult::Error {
+            error_type: "String".to_string(),
+            error_message: "patient 4711 not found".to_string(),
+            error_code: None,
+        };
+        assert!(engine.export(&secret).await.is_success());
+        assert!(engine.export(&record("tenant-a", ClassificationLevel::Internal)).await.is_success());
+
+        let messages = broker.messages().await;
+        assert!(messages.iter().all(|message| message.topic == "soc.feed"));
+        let published: Vec<ObservationRecord> = messages.iter()
+            .map(|message| serde_json::from_slice(&message.payload).unwrap())
+            .collect();
+        let published_secret = published.iter().find(|r| r.observation_id == secret.observation_id).unwrap();
+        assert!(matches!(
+            &published_secret.result,
+            OperationResult::Error { error_message, .. } if !error_message.contains("4711")
+        ));
+        let published_internal = published.iter().find(|r| r.observation_id != secret.observation_id).unwrap();
+        assert!(published_internal.privacy_protection.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_broker_errors_retry_and_full_buffer_reports_backpressure() {
+        // Transient broker errors are retried by the engine until acknowledged
+        let broker = Arc::new(MockBroker::new(4));
+        broker.failures_left.store(2, Ordering::SeqCst);
+        let mut exporter = KafkaExporter::new(settings(16), broker.clone());
+        let mut config = exporter.config();
+        config.retry_config = Some(RetryConfig { initial_delay_ms: 1, max_delay_ms: 5, ..Default::default() });
+        exporter = exporter.with_config(config);
+
+        let engine = ExportEngine::new(vec![Arc::new(exporter)]);
+        let report = engine.export(&record("tenant-a", ClassificationLevel::Internal)).await;
+        assert!(report.is_success());
+        assert_eq!(broker.messages().await.len(), 1);
+
+        // With every slot awaiting acknowledgement, further exports are refused
+        let stall = Arc::new(Notify::new());
+        let stalled = Arc::new(MockBroker { stall: Some(stall.clone()), ..MockBroker::new(1) });
+        let exporter = Arc::new(KafkaExporter::new(settings(1), stalled.clone()));
+
+        let pending = tokio::spawn({
+            let exporter = exporter.clone();
+            async move { exporter.export(&record("tenant-a", ClassificationLevel::Internal)).await }
+        });
+        while !exporter.is_backpressured() {
+            tokio::task::yield_now().await;
+        }
+
+        let refused = exporter.export(&record("tenant-a", ClassificationLevel::Internal)).await;
+        assert!(matches!(refused, Err(ExportError::Backpressure(_))));
+        assert!(refused.unwrap_err().is_retryable());
+
+        stall.notify_one();
+        pending.await.unwrap().unwrap();
+        assert!(!exporter.is_backpressured());
+        assert_eq!(stalled.messages().await.len(), 1);
+    }
+}
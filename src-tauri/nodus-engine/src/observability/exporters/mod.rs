@@ -0,0 +1,1115 @@
+// src-tauri/src/observability/exporters/mod.rs
+// Observability Exporters - Ship ObservationRecords to downstream backends
+// Local JSONL for development, object storage for long-term forensic retention, Kafka for live feeds
+
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, OnceLock, Weak};
+use std::time::Duration;
+use tokio::sync::{mpsc, Notify};
+
+use crate::observability::compliance::ComplianceEngine;
+use crate::observability::observation::ObservationRecord;
+use crate::observability::pii::PiiDetector;
+
+pub mod cef;
+pub mod dead_letter;
+pub mod kafka;
+pub mod protobuf;
+pub mod redaction;
+pub mod s3;
+pub mod security_sink;
+
+pub use cef::{CefExporter, CefFormatter};
+pub use dead_letter::{DeadLetter, DeadLetterQueue};
+pub use kafka::{KafkaExportPolicy, KafkaExporter, KafkaExporterConfig, StreamProducer};
+pub use redaction::{RedactionMode, RedactionPolicy};
+pub use s3::{S3Exporter, S3ExporterConfig, S3Mode};
+pub use security_sink::{SecurityAlertPolicy, SecurityEventSink, SecurityEventSinkConfig, TransportPoster, WebhookPoster};
+
+/// Trait for observability exporters
+#[async_trait::async_trait]
+pub trait ObservabilityExporter: Send + Sync + std::fmt::Debug {
+    /// Export a single observation record to the backend
+    async fn export(&self, record: &ObservationRecord) -> Result<(), ExportError>;
+
+    /// Flush any buffered records (no-op for unbuffered exporters)
+    async fn flush(&self) -> Result<(), ExportError> {
+        Ok(())
+    }
+
+    /// Whether a failed export leaves the record buffered for a later flush
+    ///
+    /// The engine neither retries nor dead-letters such failures, since the
+    /// exporter still holds the record.
+    fn buffers_on_failure(&self) -> bool {
+        false
+    }
+
+    /// Exporter name used in logs and metrics
+    fn name(&self) -> &str;
+
+    /// Exporter configuration
+    fn config(&self) -> ExporterConfig;
+}
+
+/// Exporter configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExporterConfig {
+    pub name: String,
+    pub format: ExportFormat,
+    pub batch_size: Option<usize>,
+    pub timeout_ms: Option<u64>,
+    pub retry_config: Option<RetryConfig>,
+    pub server_side_encryption: Option<ServerSideEncryption>,
+    #[serde(default)]
+    pub compression: Option<Compression>,
+    /// Redaction applied before records reach this exporter (None = full fidelity)
+    #[serde(default)]
+    pub redaction: Option<RedactionPolicy>,
+}
+
+/// Export formats
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExportFormat {
+    JSON,
+    MessagePack,
+    Protobuf,
+    /// ArcSight Common Event Format, one event per line
+    CEF,
+    Custom(String),
+}
+
+/// Write-time compression for file exporters
+///
+/// Each flushed batch is written as an independent gzip member / zstd frame, so
+/// appending to an existing file always yields a stream `zcat`/`zstdcat` can read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Compression {
+    Gzip,
+    Zstd,
+}
+
+impl Compression {
+    /// Compress one batch into a self-contained gzip member or zstd frame
+    pub fn compress(&self, data: &[u8]) -> Result<Vec<u8>, ExportError> {
+        use std::io::Write;
+
+        match self {
+            Compression::Gzip => {
+                let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(data)
+                    .map_err(|e| ExportError::IOError(e.to_string()))?;
+                encoder.finish()
+                    .map_err(|e| ExportError::IOError(e.to_string()))
+            }
+            Compression::Zstd => zstd::stream::encode_all(data, 0)
+                .map_err(|e| ExportError::IOError(e.to_string())),
+        }
+    }
+
+    /// Decompress a file made of one or more appended members/frames
+    pub fn decompress(&self, data: &[u8]) -> Result<Vec<u8>, ExportError> {
+        use std::io::Read;
+
+        let mut output = Vec::new();
+        match self {
+            Compression::Gzip => {
+                flate2::read::MultiGzDecoder::new(data).read_to_end(&mut output)
+                    .map_err(|e| ExportError::IOError(e.to_string()))?;
+            }
+            Compression::Zstd => {
+                output = zstd::stream::decode_all(data)
+                    .map_err(|e| ExportError::IOError(e.to_string()))?;
+            }
+        }
+        Ok(output)
+    }
+
+    /// Conventional file extension for the compressed output
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Compression::Gzip => "gz",
+            Compression::Zstd => "zst",
+        }
+    }
+}
+
+/// Server-side encryption requested from the storage backend
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ServerSideEncryption {
+    /// Backend-managed AES-256 keys (SSE-S3)
+    Aes256,
+    /// KMS-managed keys, optionally pinned to a specific key id (SSE-KMS)
+    Kms { key_id: Option<String> },
+}
+
+/// Retry configuration for transient export failures
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RetryConfig {
+    pub max_attempts: usize,
+    pub initial_delay_ms: u64,
+    pub backoff_multiplier: f64,
+    pub max_delay_ms: u64,
+}
+
+impl RetryConfig {
+    /// Backoff delay before the given retry attempt (1-based)
+    pub fn delay_for_attempt(&self, attempt: usize) -> Duration {
+        let exponent = attempt.saturating_sub(1) as i32;
+        let delay = self.initial_delay_ms as f64 * self.backoff_multiplier.powi(exponent);
+        Duration::from_millis(delay.min(self.max_delay_ms as f64) as u64)
+    }
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_delay_ms: 200,
+            backoff_multiplier: 2.0,
+            max_delay_ms: 10_000,
+        }
+    }
+}
+
+/// Export errors
+#[derive(Debug, thiserror::Error)]
+pub enum ExportError {
+    #[error("Serialization failed: {0}")]
+    SerializationFailed(String),
+
+    #[error("I/O error: {0}")]
+    IOError(String),
+
+    #[error("Network error: {0}")]
+    NetworkError(String),
+
+    #[error("Authentication failed: {0}")]
+    AuthenticationFailed(String),
+
+    #[error("Rate limited: {0}")]
+    RateLimited(String),
+
+    #[error("Timed out: {0}")]
+    Timeout(String),
+
+    /// The exporter's send buffer is full; retry once in-flight records are acknowledged
+    #[error("Backpressure: {0}")]
+    Backpressure(String),
+
+    #[error("Custom error: {0}")]
+    Custom(String),
+}
+
+impl ExportError {
+    /// Whether the error is transient and worth retrying
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            ExportError::NetworkError(_)
+                | ExportError::RateLimited(_)
+                | ExportError::Timeout(_)
+                | ExportError::Backpressure(_)
+        )
+    }
+}
+
+/// Serialize a record as one entry of a record stream
+///
+/// JSON records become NDJSON lines and protobuf records length-delimited messages
+/// (see `protobuf::decode_stream`); other formats can't be streamed this way.
+pub(crate) fn frame_record(record: &ObservationRecord, format: &ExportFormat) -> Result<Vec<u8>, ExportError> {
+    match format {
+        ExportFormat::JSON => {
+            let mut line = serde_json::to_vec(record)
+                .map_err(|e| ExportError::SerializationFailed(e.to_string()))?;
+            line.push(b'\n');
+            Ok(line)
+        }
+        ExportFormat::Protobuf => Ok(protobuf::encode_length_delimited(record)),
+        other => Err(ExportError::SerializationFailed(format!("{:?} records can't be streamed", other))),
+    }
+}
+
+/// JSON file exporter for development and local forensic storage
+#[derive(Debug)]
+pub struct JsonFileExporter {
+    file_path: String,
+    config: ExporterConfig,
+    // Pending NDJSON lines, only used when compression is enabled
+    pending: tokio::sync::Mutex<PendingBatch>,
+}
+
+#[derive(Debug, Default)]
+struct PendingBatch {
+    data: Vec<u8>,
+    records: usize,
+}
+
+impl JsonFileExporter {
+    /// Create new JSON file exporter
+    pub fn new(file_path: impl Into<String>) -> Self {
+        Self {
+            file_path: file_path.into(),
+            config: ExporterConfig {
+                name: "json_file".to_string(),
+                format: ExportFormat::JSON,
+                batch_size: Some(100),
+                timeout_ms: Some(30_000),
+                retry_config: None,
+                server_side_encryption: None,
+                compression: None,
+                redaction: None,
+            },
+            pending: tokio::sync::Mutex::new(PendingBatch::default()),
+        }
+    }
+
+    /// Compress output at write time; records are buffered up to `batch_size`
+    /// and each batch is appended as an independent compressed block
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.config.compression = Some(compression);
+        self
+    }
+
+    async fn append(&self, bytes: &[u8]) -> Result<(), ExportError> {
+        use tokio::io::AsyncWriteExt;
+
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.file_path)
+            .await
+            .map_err(|e| ExportError::IOError(e.to_string()))?;
+
+        file.write_all(bytes).await
+            .map_err(|e| ExportError::IOError(e.to_string()))?;
+        file.flush().await
+            .map_err(|e| ExportError::IOError(e.to_string()))
+    }
+
+    /// Compress and append a batch in a single write
+    async fn write_batch(&self, compression: Compression, batch: &[u8]) -> Result<(), ExportError> {
+        let compressed = compression.compress(batch)?;
+        self.append(&compressed).await
+    }
+}
+
+#[async_trait::async_trait]
+impl ObservabilityExporter for JsonFileExporter {
+    async fn export(&self, record: &ObservationRecord) -> Result<(), ExportError> {
+        let line = frame_record(record, &self.config.format)?;
+
+        let compression = match self.config.compression {
+            Some(compression) => compression,
+            None => return self.append(&line).await,
+        };
+
+        // Hold the lock across the write so batches land in export order
+        let mut pending = self.pending.lock().await;
+        let record_start = pending.data.len();
+        pending.data.extend_from_slice(&line);
+        pending.records += 1;
+
+        if pending.records >= self.config.batch_size.unwrap_or(1).max(1) {
+            if let Err(e) = self.write_batch(compression, &pending.data).await {
+                // Earlier records stay buffered for the next write; this one goes back to the caller
+                pending.data.truncate(record_start);
+                pending.records -= 1;
+                return Err(e);
+            }
+            *pending = PendingBatch::default();
+        }
+
+        Ok(())
+    }
+
+    async fn flush(&self) -> Result<(), ExportError> {
+        let compression = match self.config.compression {
+            Some(compression) => compression,
+            None => return Ok(()),
+        };
+
+        let mut pending = self.pending.lock().await;
+        if pending.records == 0 {
+            return Ok(());
+        }
+        // Cleared only once written, so a failed flush loses nothing
+        self.write_batch(compression, &pending.data).await?;
+        *pending = PendingBatch::default();
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    fn config(&self) -> ExporterConfig {
+        self.config.clone()
+    }
+}
+
+/// Records that can wait for background export before `submit` starts dropping them
+pub const DEFAULT_EXPORT_QUEUE_CAPACITY: usize = 1024;
+
+/// Dead-lettered records kept before the oldest are evicted
+pub const DEFAULT_DEAD_LETTER_CAPACITY: usize = 10_000;
+
+/// Exporters the engine is built with at startup (`ObservabilityPolicy::exports`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportPolicy {
+    /// JSONL file receiving every record at full fidelity
+    #[serde(default)]
+    pub local_path: Option<String>,
+    /// Records that exhaust their exporter's retries are persisted here; kept in memory without one
+    #[serde(default)]
+    pub dead_letter_path: Option<PathBuf>,
+    #[serde(default = "default_dead_letter_capacity")]
+    pub dead_letter_capacity: usize,
+    /// Records waiting for background export before new ones are dropped
+    #[serde(default = "default_queue_capacity")]
+    pub queue_capacity: usize,
+    /// Webhook alerts for high-severity security events; none are posted without it
+    #[serde(default)]
+    pub security_alerts: Option<SecurityAlertPolicy>,
+    /// Real-time feed for SOC consumers; needs the `observability_kafka` feature
+    #[serde(default)]
+    pub kafka: Option<KafkaExportPolicy>,
+}
+
+fn default_dead_letter_capacity() -> usize {
+    DEFAULT_DEAD_LETTER_CAPACITY
+}
+
+fn default_queue_capacity() -> usize {
+    DEFAULT_EXPORT_QUEUE_CAPACITY
+}
+
+impl Default for ExportPolicy {
+    fn default() -> Self {
+        Self {
+            local_path: None,
+            dead_letter_path: None,
+            dead_letter_capacity: DEFAULT_DEAD_LETTER_CAPACITY,
+            queue_capacity: DEFAULT_EXPORT_QUEUE_CAPACITY,
+            security_alerts: None,
+            kafka: None,
+        }
+    }
+}
+
+/// Export engine that hands each record to every configured exporter
+#[derive(Debug)]
+pub struct ExportEngine {
+    exporters: Vec<Arc<dyn ObservabilityExporter>>,
+    dead_letters: Option<Arc<DeadLetterQueue>>,
+    pii_detector: Option<Arc<PiiDetector>>,
+    compliance_engine: Option<Arc<ComplianceEngine>>,
+    queue_capacity: usize,
+    // Started by the first `submit`, so building an engine needs no runtime
+    queue: OnceLock<mpsc::Sender<ObservationRecord>>,
+    // Submitted records not yet exported; `drain` waits for this to reach zero
+    queued: AtomicUsize,
+    queue_idle: Notify,
+    dropped: AtomicU64,
+}
+
+impl Default for ExportEngine {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+impl ExportEngine {
+    pub fn new(exporters: Vec<Arc<dyn ObservabilityExporter>>) -> Self {
+        Self {
+            exporters,
+            dead_letters: None,
+            pii_detector: None,
+            compliance_engine: None,
+            queue_capacity: DEFAULT_EXPORT_QUEUE_CAPACITY,
+            queue: OnceLock::new(),
+            queued: AtomicUsize::new(0),
+            queue_idle: Notify::new(),
+            dropped: AtomicU64::new(0),
+        }
+    }
+
+    /// Engine with the local store and dead-letter queue `policy` configures
+    ///
+    /// Exporters that need the network transport (alert sinks, streaming feeds) are added
+    /// with `add_exporter`.
+    pub async fn from_policy(policy: &ExportPolicy) -> Result<Self, ExportError> {
+        let mut exporters: Vec<Arc<dyn ObservabilityExporter>> = Vec::new();
+        if let Some(path) = &policy.local_path {
+            exporters.push(Arc::new(JsonFileExporter::new(path.clone())));
+        }
+        let dead_letters = match &policy.dead_letter_path {
+            Some(path) => DeadLetterQueue::open(path, policy.dead_letter_capacity).await?,
+            None => DeadLetterQueue::new(policy.dead_letter_capacity),
+        };
+        Ok(Self::new(exporters)
+            .with_dead_letter_queue(Arc::new(dead_letters))
+            .with_queue_capacity(policy.queue_capacity))
+    }
+
+    /// Hold at most `capacity` records waiting for background export
+    pub fn with_queue_capacity(mut self, capacity: usize) -> Self {
+        self.queue_capacity = capacity.max(1);
+        self
+    }
+
+    /// Redact PII found by `detector` from every record before any exporter sees it
+    pub fn with_pii_detector(mut self, detector: Arc<PiiDetector>) -> Self {
+        self.pii_detector = Some(detector);
+        self
+    }
+
+    /// Attach compliance records from `engine` to every record before export
+    pub fn with_compliance_engine(mut self, engine: Arc<ComplianceEngine>) -> Self {
+        self.compliance_engine = Some(engine);
+        self
+    }
+
+    pub fn compliance_engine(&self) -> Option<&Arc<ComplianceEngine>> {
+        self.compliance_engine.as_ref()
+    }
+
+    /// Route records that exhaust an exporter's retries to a dead-letter queue
+    pub fn with_dead_letter_queue(mut self, queue: Arc<DeadLetterQueue>) -> Self {
+        self.dead_letters = Some(queue);
+        self
+    }
+
+    pub fn dead_letter_queue(&self) -> Option<&Arc<DeadLetterQueue>> {
+        self.dead_letters.as_ref()
+    }
+
+    /// Register an additional exporter
+    pub fn add_exporter(&mut self, exporter: Arc<dyn ObservabilityExporter>) {
+        self.exporters.push(exporter);
+    }
+
+    /// Export a record to all exporters concurrently
+    ///
+    /// With a PII detector configured, the record is first redacted for every exporter, and with a
+    /// compliance engine it then gets its compliance records. Each exporter runs in its own task with the record redacted according to its own
+    /// `ExporterConfig::redaction`, retried per its `RetryConfig`, and each attempt bounded by
+    /// its `timeout_ms`. A failing, hung or panicking exporter doesn't affect the others.
+    /// With a dead-letter queue configured, records that still fail are queued there.
+    pub async fn export(&self, record: &ObservationRecord) -> ExportReport {
+        let mut record = match &self.pii_detector {
+            Some(detector) => detector.protect_record(record),
+            None => Cow::Borrowed(record),
+        };
+        if let Some(compliance) = &self.compliance_engine {
+            if let Err(e) = compliance.assess(record.to_mut()).await {
+                tracing::error!("Failed to keep compliance records of observation {}: {}", record.observation_id, e);
+            }
+        }
+        let deliveries = self.exporters.iter().map(|exporter| {
+            let exporter = exporter.clone();
+            let record = redaction::apply(&record, exporter.config().redaction.as_ref()).into_owned();
+            let dead_letters = self.dead_letters.clone();
+            tokio::spawn(async move { deliver(exporter.as_ref(), record, dead_letters.as_deref()).await })
+        });
+        let joined = futures::future::join_all(deliveries).await;
+
+        let outcomes = self.exporters.iter().zip(joined).map(|(exporter, joined)| {
+            let outcome = joined.unwrap_or_else(|e| {
+                tracing::error!("Exporter {} aborted: {}", exporter.name(), e);
+                ExportOutcome::Failed(ExportError::Custom(format!("Exporter task aborted: {}", e)))
+            });
+            (exporter.name().to_string(), outcome)
+        });
+
+        ExportReport { outcomes: outcomes.collect() }
+    }
+
+    /// Queue `record` for export by a background worker; returns false if it was dropped
+    ///
+    /// The queue is bounded so a slow exporter applies back-pressure as dropped records
+    /// (counted by `dropped_records`) rather than as unbounded memory growth. Must be called
+    /// within a Tokio runtime; the worker is started on first use.
+    pub fn submit(self: &Arc<Self>, record: ObservationRecord) -> bool {
+        let sender = self.queue.get_or_init(|| self.start_queue());
+        self.queued.fetch_add(1, Ordering::SeqCst);
+        match sender.try_send(record) {
+            Ok(()) => true,
+            Err(e) => {
+                self.finish_queued();
+                let dropped = self.dropped.fetch_add(1, Ordering::Relaxed) + 1;
+                let record = match e {
+                    mpsc::error::TrySendError::Full(record) | mpsc::error::TrySendError::Closed(record) => record,
+                };
+                tracing::warn!("Export queue full, dropped observation {} ({} dropped so far)", record.observation_id, dropped);
+                false
+            }
+        }
+    }
+
+    /// Records `submit` has dropped because the queue was full
+    pub fn dropped_records(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+
+    /// Wait until every submitted record has been exported
+    pub async fn drain(&self) {
+        loop {
+            // Registered before the check so a worker finishing in between still wakes us
+            let idle = self.queue_idle.notified();
+            if self.queued.load(Ordering::SeqCst) == 0 {
+                return;
+            }
+            idle.await;
+        }
+    }
+
+    /// Worker exporting queued records one at a time; it holds the engine weakly and exits
+    /// once the engine (and with it the sender) is dropped
+    fn start_queue(self: &Arc<Self>) -> mpsc::Sender<ObservationRecord> {
+        let (sender, mut receiver) = mpsc::channel::<ObservationRecord>(self.queue_capacity);
+        let engine: Weak<Self> = Arc::downgrade(self);
+        tokio::spawn(async move {
+            while let Some(record) = receiver.recv().await {
+                let Some(engine) = engine.upgrade() else {
+                    break;
+                };
+                let report = engine.export(&record).await;
+                if !report.is_success() {
+                    tracing::warn!("Observation {} was not delivered to every exporter", record.observation_id);
+                }
+                engine.finish_queued();
+            }
+        });
+        sender
+    }
+
+    fn finish_queued(&self) {
+        if self.queued.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.queue_idle.notify_waiters();
+        }
+    }
+
+    /// Re-attempt every dead-lettered record against its exporter; returns how many were delivered
+    ///
+    /// Each entry leaves the queue only once its exporter accepts it, so a crash or error
+    /// mid-replay loses nothing; records that fail again (or whose exporter is no longer
+    /// registered) stay queued with the new error.
+    pub async fn replay_dead_letters(&self) -> Result<usize, ExportError> {
+        let queue = match &self.dead_letters {
+            Some(queue) => queue,
+            None => return Ok(0),
+        };
+
+        let mut delivered = 0;
+        for mut entry in queue.entries().await {
+            let exporter = self.exporters.iter().find(|exporter| exporter.name() == entry.exporter);
+            let outcome = match exporter {
+                Some(exporter) => export_with_retry(exporter.as_ref(), &entry.record).await,
+                None => Err((ExportError::Custom(format!("Exporter {} is not registered", entry.exporter)), 0)),
+            };
+
+            match outcome {
+                Ok(()) => {
+                    queue.acknowledge(&entry).await?;
+                    delivered += 1;
+                }
+                Err((error, attempts)) => {
+                    entry.last_error = error.to_string();
+                    entry.attempts += attempts;
+                    entry.failed_at = chrono::Utc::now();
+                    queue.record_failure(entry).await?;
+                }
+            }
+        }
+
+        Ok(delivered)
+    }
+
+    /// Flush all buffered exporters
+    pub async fn flush(&self) -> Result<(), ExportError> {
+        for exporter in &self.exporters {
+            exporter.flush().await?;
+        }
+        Ok(())
+    }
+}
+
+/// What happened to a record at one exporter
+#[derive(Debug)]
+pub enum ExportOutcome {
+    Delivered,
+    /// Retries exhausted; the record is waiting in the dead-letter queue
+    DeadLettered { last_error: String },
+    Failed(ExportError),
+}
+
+/// Per-exporter outcomes of one fan-out export, in registration order
+#[derive(Debug)]
+pub struct ExportReport {
+    pub outcomes: Vec<(String, ExportOutcome)>,
+}
+
+impl ExportReport {
+    /// Whether every exporter received the record
+    pub fn is_success(&self) -> bool {
+        self.outcomes.iter().all(|(_, outcome)| matches!(outcome, ExportOutcome::Delivered))
+    }
+
+    /// Outcome for the named exporter
+    pub fn outcome(&self, exporter: &str) -> Option<&ExportOutcome> {
+        self.outcomes.iter().find(|(name, _)| name == exporter).map(|(_, outcome)| outcome)
+    }
+}
+
+/// Deliver one record to one exporter, dead-lettering it if every attempt fails
+async fn deliver(
+    exporter: &dyn ObservabilityExporter,
+    record: ObservationRecord,
+    dead_letters: Option<&DeadLetterQueue>,
+) -> ExportOutcome {
+    let (error, attempts) = match export_with_retry(exporter, &record).await {
+        Ok(()) => return ExportOutcome::Delivered,
+        Err(failure) => failure,
+    };
+
+    let queue = match dead_letters {
+        Some(queue) if !exporter.buffers_on_failure() => queue,
+        _ => return ExportOutcome::Failed(error),
+    };
+
+    tracing::warn!(
+        "Exporter {} failed after {} attempts, dead-lettering {}: {}",
+        exporter.name(),
+        attempts,
+        record.observation_id,
+        error
+    );
+    let last_error = error.to_string();
+    let entry = DeadLetter {
+        record,
+        exporter: exporter.name().to_string(),
+        last_error: last_error.clone(),
+        attempts,
+        failed_at: chrono::Utc::now(),
+    };
+    match queue.push(entry).await {
+        Ok(()) => ExportOutcome::DeadLettered { last_error },
+        Err(e) => ExportOutcome::Failed(e),
+    }
+}
+
+/// Export with the exporter's own retry policy and per-attempt timeout;
+/// on failure returns the last error and attempts made
+async fn export_with_retry(
+    exporter: &dyn ObservabilityExporter,
+    record: &ObservationRecord,
+) -> Result<(), (ExportError, usize)> {
+    let config = exporter.config();
+    let retry = config.retry_config;
+    let timeout = config.timeout_ms.map(Duration::from_millis);
+    let max_attempts = match (&retry, exporter.buffers_on_failure()) {
+        (Some(retry), false) => retry.max_attempts.max(1),
+        _ => 1,
+    };
+    let mut attempt = 1;
+
+    loop {
+        let result = match timeout {
+            Some(timeout) => tokio::time::timeout(timeout, exporter.export(record))
+                .await
+                .unwrap_or_else(|_| Err(ExportError::Timeout(format!("no response within {:?}", timeout)))),
+            None => exporter.export(record).await,
+        };
+
+        match result {
+            Ok(()) => return Ok(()),
+            Err(e) if e.is_retryable() && attempt < max_attempts => {
+                if let Some(retry) = &retry {
+                    tokio::time::sleep(retry.delay_for_attempt(attempt)).await;
+                }
+                attempt += 1;
+            }
+            Err(e) => return Err((e, attempt)),
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::observability::observation::{ObservationContext, OperationResult};
+    use crate::security::ClassificationLevel;
+
+    /// Exporter that keeps every record it receives in memory
+    #[derive(Debug)]
+    pub(crate) struct CapturingExporter {
+        pub(crate) config: ExporterConfig,
+        pub(crate) records: tokio::sync::Mutex<Vec<ObservationRecord>>,
+    }
+
+    impl CapturingExporter {
+        pub(crate) fn new(name: &str) -> Self {
+            let mut config = JsonFileExporter::new("").config;
+            config.name = name.to_string();
+            Self { config, records: tokio::sync::Mutex::new(Vec::new()) }
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl ObservabilityExporter for CapturingExporter {
+        async fn export(&self, record: &ObservationRecord) -> Result<(), ExportError> {
+            self.records.lock().await.push(record.clone());
+            Ok(())
+        }
+
+        fn name(&self) -> &str {
+            &self.config.name
+        }
+
+        fn config(&self) -> ExporterConfig {
+            self.config.clone()
+        }
+    }
+
+    /// Exporter that fails with a retryable error until marked healthy
+    #[derive(Debug)]
+    struct FlakyExporter {
+        inner: CapturingExporter,
+        healthy: std::sync::atomic::AtomicBool,
+        attempts: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl ObservabilityExporter for FlakyExporter {
+        async fn export(&self, record: &ObservationRecord) -> Result<(), ExportError> {
+            use std::sync::atomic::Ordering;
+
+            self.attempts.fetch_add(1, Ordering::SeqCst);
+            if !self.healthy.load(Ordering::SeqCst) {
+                return Err(ExportError::NetworkError("connection refused".to_string()));
+            }
+            self.inner.export(record).await
+        }
+
+        fn name(&self) -> &str {
+            self.inner.name()
+        }
+
+        fn config(&self) -> ExporterConfig {
+            self.inner.config()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_exports_are_dead_lettered_and_replayed() {
+        use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+        let dir = tempfile::tempdir().unwrap();
+        let dlq_path = dir.path().join("dead_letters.jsonl");
+
+        let mut inner = CapturingExporter::new("siem");
+        inner.config.retry_config = Some(RetryConfig {
+            max_attempts: 2,
+            initial_delay_ms: 1,
+            backoff_multiplier: 1.0,
+            max_delay_ms: 1,
+        });
+        let siem = Arc::new(FlakyExporter {
+            inner,
+            healthy: AtomicBool::new(false),
+            attempts: AtomicUsize::new(0),
+        });
+        let queue = Arc::new(DeadLetterQueue::open(&dlq_path, 2).await.unwrap());
+        let engine = ExportEngine::new(vec![siem.clone()]).with_dead_letter_queue(queue.clone());
+
+        let records: Vec<ObservationRecord> = (0..3)
+            .map(|i| ObservationRecord::new(
+                &format!("operation_{}", i),
+                ObservationContext::default(),
+                OperationResult::Success { return_value: None },
+            ))
+            .collect();
+        for record in &records {
+            let report = engine.export(record).await;
+            assert!(matches!(report.outcome("siem"), Some(ExportOutcome::DeadLettered { .. })));
+        }
+
+        // Every record used both attempts; the bounded queue evicted the oldest
+        assert_eq!(siem.attempts.load(Ordering::SeqCst), 6);
+        let entries = queue.entries().await;
+        assert_eq!(entries.len(), 2);
+        assert_eq!(queue.evicted_count(), 1);
+        assert_eq!(entries[0].record.operation, "operation_1");
+        assert_eq!(entries[0].exporter, "siem");
+        assert_eq!(entries[0].attempts, 2);
+        assert!(entries[0].last_error.contains("connection refused"));
+
+        // Queue survives a restart
+        assert_eq!(DeadLetterQueue::open(&dlq_path, 2).await.unwrap().len().await, 2);
+
+        // A failed replay keeps both entries and records the extra attempts
+        assert_eq!(engine.replay_dead_letters().await.unwrap(), 0);
+        let entries = queue.entries().await;
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].record.operation, "operation_1");
+        assert_eq!(entries[0].attempts, 4);
+        assert_eq!(DeadLetterQueue::open(&dlq_path, 2).await.unwrap().len().await, 2);
+
+        siem.healthy.store(true, Ordering::SeqCst);
+        assert_eq!(engine.replay_dead_letters().await.unwrap(), 2);
+        assert!(queue.is_empty().await);
+        let delivered: Vec<String> = siem.inner.records.lock().await.iter().map(|r| r.operation.clone()).collect();
+        assert_eq!(delivered, vec!["operation_1", "operation_2"]);
+        assert!(DeadLetterQueue::open(&dlq_path, 2).await.unwrap().is_empty().await);
+    }
+
+    /// Exporter that misbehaves in a fixed way
+    #[derive(Debug)]
+    struct BrokenExporter {
+        config: ExporterConfig,
+        hang: bool,
+    }
+
+    #[async_trait::async_trait]
+    impl ObservabilityExporter for BrokenExporter {
+        async fn export(&self, _record: &ObservationRecord) -> Result<(), ExportError> {
+            if self.hang {
+                std::future::pending::<()>().await;
+            }
+            panic!("exporter bug");
+        }
+
+        fn name(&self) -> &str {
+            &self.config.name
+        }
+
+        fn config(&self) -> ExporterConfig {
+            self.config.clone()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fan_out_isolates_failing_exporters() {
+        let local = Arc::new(CapturingExporter::new("local_forensic"));
+        let archive = Arc::new(CapturingExporter::new("archive"));
+        let mut config = CapturingExporter::new("siem").config;
+        let panicking = Arc::new(BrokenExporter { config: config.clone(), hang: false });
+        config.name = "hung_siem".to_string();
+        config.timeout_ms = Some(20);
+        let hung = Arc::new(BrokenExporter { config, hang: true });
+
+        let engine = ExportEngine::new(vec![local.clone(), panicking, archive.clone(), hung]);
+        let record = ObservationRecord::new(
+            "entity.write",
+            ObservationContext::default(),
+            OperationResult::Success { return_value: None },
+        );
+
+        let started = std::time::Instant::now();
+        let report = engine.export(&record).await;
+        assert!(started.elapsed() < Duration::from_secs(1));
+
+        assert!(!report.is_success());
+        assert!(matches!(report.outcome("local_forensic"), Some(ExportOutcome::Delivered)));
+        assert!(matches!(report.outcome("archive"), Some(ExportOutcome::Delivered)));
+        assert!(matches!(report.outcome("siem"), Some(ExportOutcome::Failed(ExportError::Custom(_)))));
+        assert!(matches!(report.outcome("hung_siem"), Some(ExportOutcome::Failed(ExportError::Timeout(_)))));
+        assert_eq!(local.records.lock().await.len(), 1);
+        assert_eq!(archive.records.lock().await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_submit_drops_records_beyond_queue_capacity() {
+        let capture = Arc::new(CapturingExporter::new("capture"));
+        let engine = Arc::new(ExportEngine::new(vec![capture.clone()]).with_queue_capacity(2));
+        let record = |i: usize| ObservationRecord::new(
+            &format!("operation_{}", i),
+            ObservationContext::default(),
+            OperationResult::Success { return_value: None },
+        );
+
+        // The current-thread test runtime can't run the worker between submits,
+        // so the queue fills at its capacity and the rest are dropped
+        let accepted: Vec<bool> = (0..4).map(|i| engine.submit(record(i))).collect();
+        assert_eq!(accepted, vec![true, true, false, false]);
+        assert_eq!(engine.dropped_records(), 2);
+
+        engine.drain().await;
+        let delivered: Vec<String> = capture.records.lock().await.iter().map(|r| r.operation.clone()).collect();
+        assert_eq!(delivered, vec!["operation_0", "operation_1"]);
+
+        // Once drained the queue takes records again
+        assert!(engine.submit(record(4)));
+        engine.drain().await;
+        assert_eq!(capture.records.lock().await.len(), 3);
+    }
+
+    #[test]
+    fn test_retry_delay_is_capped() {
+        let retry = RetryConfig {
+            max_attempts: 10,
+            initial_delay_ms: 100,
+            backoff_multiplier: 2.0,
+            max_delay_ms: 1_000,
+        };
+
+        assert_eq!(retry.delay_for_attempt(1), Duration::from_millis(100));
+        assert_eq!(retry.delay_for_attempt(3), Duration::from_millis(400));
+        assert_eq!(retry.delay_for_attempt(8), Duration::from_millis(1_000));
+    }
+
+    #[tokio::test]
+    async fn test_json_file_exporter() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("export.jsonl");
+        let exporter = JsonFileExporter::new(path.to_string_lossy().to_string());
+
+        let record = ObservationRecord::new(
+            "test_operation",
+            ObservationContext::default(),
+            OperationResult::Success { return_value: None },
+        );
+
+        exporter.export(&record).await.unwrap();
+        exporter.export(&record).await.unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(contents.lines().count(), 2);
+    }
+
+    async fn roundtrip_compressed(compression: Compression) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(format!("export.jsonl.{}", compression.extension()));
+        let mut exporter = JsonFileExporter::new(path.to_string_lossy().to_string())
+            .with_compression(compression);
+        exporter.config.batch_size = Some(2);
+
+        // 5 records with batch size 2 -> two full blocks plus one flushed on demand
+        let operations: Vec<String> = (0..5).map(|i| format!("operation_{}", i)).collect();
+        for operation in &operations {
+            let record = ObservationRecord::new(
+                operation,
+                ObservationContext::default(),
+                OperationResult::Success { return_value: Some(serde_json::json!({"op": operation})) },
+            );
+            exporter.export(&record).await.unwrap();
+        }
+        exporter.flush().await.unwrap();
+
+        let compressed = std::fs::read(&path).unwrap();
+        let decompressed = compression.decompress(&compressed).unwrap();
+        let records: Vec<ObservationRecord> = String::from_utf8(decompressed).unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        let exported: Vec<String> = records.into_iter().map(|r| r.operation).collect();
+        assert_eq!(exported, operations);
+    }
+
+    #[tokio::test]
+    async fn test_gzip_compressed_roundtrip() {
+        roundtrip_compressed(Compression::Gzip).await;
+    }
+
+    #[tokio::test]
+    async fn test_zstd_compressed_roundtrip() {
+        roundtrip_compressed(Compression::Zstd).await;
+    }
+
+    #[tokio::test]
+    async fn test_failed_batch_write_keeps_buffered_records() {
+        let dir = tempfile::tempdir().unwrap();
+        let log_dir = dir.path().join("logs");
+        let path = log_dir.join("export.jsonl.gz");
+        let mut exporter = JsonFileExporter::new(path.to_string_lossy().to_string())
+            .with_compression(Compression::Gzip);
+        exporter.config.batch_size = Some(2);
+
+        let record = |operation: &str| ObservationRecord::new(
+            operation,
+            ObservationContext::default(),
+            OperationResult::Success { return_value: None },
+        );
+
+        // The directory doesn't exist yet, so the batch write fails
+        exporter.export(&record("first")).await.unwrap();
+        assert!(exporter.export(&record("second")).await.is_err());
+        assert!(exporter.flush().await.is_err());
+
+        // The failed record is the caller's to retry; the buffered one is still there
+        std::fs::create_dir(&log_dir).unwrap();
+        exporter.export(&record("second")).await.unwrap();
+        exporter.flush().await.unwrap();
+
+        let decompressed = Compression::Gzip.decompress(&std::fs::read(&path).unwrap()).unwrap();
+        let exported: Vec<String> = String::from_utf8(decompressed).unwrap()
+            .lines()
+            .map(|line| serde_json::from_str::<ObservationRecord>(line).unwrap().operation)
+            .collect();
+        assert_eq!(exported, vec!["first", "second"]);
+    }
+
+    #[tokio::test]
+    async fn test_engine_redacts_per_exporter() {
+        let local = Arc::new(CapturingExporter::new("local_forensic"));
+        let mut siem = CapturingExporter::new("siem");
+        siem.config.redaction = Some(RedactionPolicy::new(ClassificationLevel::Confidential, RedactionMode::Strip));
+        let siem = Arc::new(siem);
+
+        let engine = ExportEngine::new(vec![local.clone(), siem.clone()]);
+        let record = ObservationRecord::new(
+            "entity.read",
+            ObservationContext {
+                classification: Some(ClassificationLevel::Secret),
+                ..Default::default()
+            },
+            OperationResult::Success { return_value: Some(serde_json::json!({"codename": "BLUEFOX"})) },
+        );
+        assert!(engine.export(&record).await.is_success());
+
+        let local_records = local.records.lock().await;
+        assert!(matches!(local_records[0].result, OperationResult::Success { return_value: Some(_) }));
+        assert!(local_records[0].privacy_protection.is_none());
+
+        let siem_records = siem.records.lock().await;
+        assert!(matches!(siem_records[0].result, OperationResult::Success { return_value: None }));
+        assert!(siem_records[0].privacy_protection.as_ref().unwrap().redaction_applied);
+    }
+
+    #[tokio::test]
+    async fn test_engine_redacts_pii_before_export() {
+        let exporter = Arc::new(CapturingExporter::new("local_forensic"));
+        let engine = ExportEngine::new(vec![exporter.clone()])
+            .with_pii_detector(Arc::new(PiiDetector::new().unwrap()));
+        let record = ObservationRecord::new(
+            "user.invite",
+            ObservationContext {
+                classification: Some(ClassificationLevel::Unclassified),
+                ..Default::default()
+            },
+            OperationResult::Error {
+                error_type: "Conflict".to_string(),
+                error_message: "jane.doe@example.com is already invited".to_string(),
+                error_code: None,
+            },
+        );
+        assert!(engine.export(&record).await.is_success());
+
+        let exported = exporter.records.lock().await;
+        assert!(exported[0].privacy_protection.as_ref().unwrap().pii_detected);
+        match &exported[0].result {
+            OperationResult::Error { error_message, .. } => {
+                assert_eq!(error_message, "[REDACTED:email] is already invited");
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+        // The caller's record is left intact
+        assert!(record.privacy_protection.is_none());
+    }
+}
@@ -0,0 +1,400 @@
+// src-tauri/src/observability/exporters/protobuf.rs
+// Protobuf Codec - ObservationRecord <-> nodus.observability.v1 wire messages
+// Message types are generated from proto/observation.proto; field numbers there are permanent
+
+use chrono::{DateTime, TimeZone, Utc};
+use prost::Message;
+
+use super::ExportError;
+use crate::observability::observation::{
+    ComplianceFramework, ComplianceRecord, ComplianceStatus, ObservationContext, ObservationRecord,
+    OperationResult, PerformanceMetrics, PrivacyLevel, PrivacyProtection, SecurityEvent,
+    SecurityEventType, SecuritySeverity,
+};
+use crate::security::ClassificationLevel;
+
+/// Message types of `proto/observation.proto` (package `nodus.observability.v1`), generated by build.rs
+#[allow(clippy::all, clippy::pedantic, clippy::nursery)]
+pub mod proto {
+    include!(concat!(env!("OUT_DIR"), "/nodus.observability.v1.rs"));
+}
+
+/// Encode a record as a single protobuf message
+pub fn encode(record: &ObservationRecord) -> Vec<u8> {
+    to_proto(record).encode_to_vec()
+}
+
+/// Encode a record as a length-delimited message, the framing used for record streams
+pub fn encode_length_delimited(record: &ObservationRecord) -> Vec<u8> {
+    to_proto(record).encode_length_delimited_to_vec()
+}
+
+/// Decode a single protobuf message
+pub fn decode(bytes: &[u8]) -> Result<ObservationRecord, ExportError> {
+    let message = proto::ObservationRecord::decode(bytes).map_err(|e| invalid(e.to_string()))?;
+    from_proto(message)
+}
+
+/// Decode a stream of length-delimited messages, e.g. a protobuf object written by the S3 exporter
+pub fn decode_stream(mut bytes: &[u8]) -> Result<Vec<ObservationRecord>, ExportError> {
+    let mut records = Vec::new();
+    while !bytes.is_empty() {
+        let message = proto::ObservationRecord::decode_length_delimited(&mut bytes)
+            .map_err(|e| invalid(e.to_string()))?;
+        records.push(from_proto(message)?);
+    }
+    Ok(records)
+}
+
+fn invalid(detail: impl std::fmt::Display) -> ExportError {
+    ExportError::SerializationFailed(format!("Invalid protobuf observation: {}", detail))
+}
+
+fn to_proto(record: &ObservationRecord) -> proto::ObservationRecord {
+    proto::ObservationRecord {
+        observation_id: record.observation_id.clone(),
+        operation: record.operation.clone(),
+        started_at: Some(timestamp_to_proto(&record.started_at)),
+        completed_at: record.completed_at.as_ref().map(timestamp_to_proto),
+        result: Some(result_to_proto(&record.result)),
+        performance: Some(performance_to_proto(&record.performance)),
+        security_events: record.security_events.iter().map(security_event_to_proto).collect(),
+        compliance_records: record.compliance_records.iter().map(compliance_to_proto).collect(),
+        privacy_protection: record.privacy_protection.as_ref().map(|protection| proto::PrivacyProtection {
+            pii_detected: protection.pii_detected,
+            redaction_applied: protection.redaction_applied,
+            encryption_applied: protection.encryption_applied,
+        }),
+        metadata: record.metadata.clone(),
+        context: Some(context_to_proto(&record.context)),
+    }
+}
+
+fn from_proto(message: proto::ObservationRecord) -> Result<ObservationRecord, ExportError> {
+    Ok(ObservationRecord {
+        observation_id: message.observation_id,
+        operation: message.operation,
+        started_at: timestamp_from_proto(message.started_at.ok_or_else(|| invalid("missing started_at"))?)?,
+        completed_at: message.completed_at.map(timestamp_from_proto).transpose()?,
+        result: result_from_proto(message.result.ok_or_else(|| invalid("missing result"))?)?,
+        performance: performance_from_proto(message.performance.unwrap_or_default()),
+        security_events: message.security_events.into_iter().map(security_event_from_proto).collect::<Result<_, _>>()?,
+        compliance_records: message.compliance_records.into_iter().map(compliance_from_proto).collect::<Result<_, _>>()?,
+        privacy_protection: message.privacy_protection.map(|protection| PrivacyProtection {
+            pii_detected: protection.pii_detected,
+            redaction_applied: protection.redaction_applied,
+            encryption_applied: protection.encryption_applied,
+        }),
+        metadata: message.metadata,
+        context: context_from_proto(message.context.unwrap_or_default())?,
+    })
+}
+
+fn timestamp_to_proto(at: &DateTime<Utc>) -> proto::Timestamp {
+    proto::Timestamp { seconds: at.timestamp(), nanos: at.timestamp_subsec_nanos() as i32 }
+}
+
+fn timestamp_from_proto(at: proto::Timestamp) -> Result<DateTime<Utc>, ExportError> {
+    u32::try_from(at.nanos)
+        .ok()
+        .and_then(|nanos| Utc.timestamp_opt(at.seconds, nanos).single())
+        .ok_or_else(|| invalid(format!("timestamp {}.{:09} out of range", at.seconds, at.nanos)))
+}
+
+fn json_from_proto(json: &str) -> Result<serde_json::Value, ExportError> {
+    serde_json::from_str(json).map_err(|e| invalid(format!("bad JSON value: {}", e)))
+}
+
+fn result_to_proto(result: &OperationResult) -> proto::OperationResult {
+    use proto::operation_result::{Error, InProgress, Outcome, Success};
+
+    let outcome = match result {
+        OperationResult::Success { return_value } => Outcome::Success(Success {
+            return_value_json: return_value.as_ref().map(|value| value.to_string()),
+        }),
+        OperationResult::Error { error_type, error_message, error_code } => Outcome::Error(Error {
+            error_type: error_type.clone(),
+            error_message: error_message.clone(),
+            error_code: error_code.clone(),
+        }),
+        OperationResult::InProgress => Outcome::InProgress(InProgress {}),
+    };
+    proto::OperationResult { outcome: Some(outcome) }
+}
+
+fn result_from_proto(result: proto::OperationResult) -> Result<OperationResult, ExportError> {
+    use proto::operation_result::Outcome;
+
+    Ok(match result.outcome.ok_or_else(|| invalid("missing operation outcome"))? {
+        Outcome::Success(success) => OperationResult::Success {
+            return_value: success.return_value_json.as_deref().map(json_from_proto).transpose()?,
+        },
+        Outcome::Error(error) => OperationResult::Error {
+            error_type: error.error_type,
+            error_message: error.error_message,
+            error_code: error.error_code,
+        },
+        Outcome::InProgress(_) => OperationResult::InProgress,
+    })
+}
+
+fn performance_to_proto(performance: &PerformanceMetrics) -> proto::PerformanceMetrics {
+    proto::PerformanceMetrics {
+        duration_ns: performance.duration_ns,
+        cpu_usage: performance.cpu_usage,
+        memory_usage_bytes: performance.memory_usage_bytes,
+        network_io_bytes: performance.network_io_bytes,
+        disk_io_bytes: performance.disk_io_bytes,
+        custom_metrics: performance.custom_metrics.clone(),
+    }
+}
+
+fn performance_from_proto(performance: proto::PerformanceMetrics) -> PerformanceMetrics {
+    PerformanceMetrics {
+        duration_ns: performance.duration_ns,
+        cpu_usage: performance.cpu_usage,
+        memory_usage_bytes: performance.memory_usage_bytes,
+        network_io_bytes: performance.network_io_bytes,
+        disk_io_bytes: performance.disk_io_bytes,
+        custom_metrics: performance.custom_metrics,
+    }
+}
+
+fn security_event_to_proto(event: &SecurityEvent) -> proto::SecurityEvent {
+    let (event_type, custom_event_type) = match &event.event_type {
+        SecurityEventType::SuspiciousAccess => (proto::SecurityEventType::SuspiciousAccess, String::new()),
+        SecurityEventType::AuthenticationFailure => (proto::SecurityEventType::AuthenticationFailure, String::new()),
+        SecurityEventType::AuthorizationViolation => (proto::SecurityEventType::AuthorizationViolation, String::new()),
+        SecurityEventType::DataAccessAnomaly => (proto::SecurityEventType::DataAccessAnomaly, String::new()),
+        SecurityEventType::PotentialAttack => (proto::SecurityEventType::PotentialAttack, String::new()),
+        SecurityEventType::Custom(name) => (proto::SecurityEventType::Custom, name.clone()),
+    };
+    let severity = match event.severity {
+        SecuritySeverity::Low => proto::SecuritySeverity::Low,
+        SecuritySeverity::Medium => proto::SecuritySeverity::Medium,
+        SecuritySeverity::High => proto::SecuritySeverity::High,
+        SecuritySeverity::Critical => proto::SecuritySeverity::Critical,
+    };
+
+    proto::SecurityEvent {
+        event_type: event_type as i32,
+        custom_event_type,
+        severity: severity as i32,
+        description: event.description.clone(),
+        timestamp: Some(timestamp_to_proto(&event.timestamp)),
+        data_json: event.data.iter().map(|(key, value)| (key.clone(), value.to_string())).collect(),
+    }
+}
+
+fn security_event_from_proto(event: proto::SecurityEvent) -> Result<SecurityEvent, ExportError> {
+    let event_type = match proto::SecurityEventType::try_from(event.event_type) {
+        Ok(proto::SecurityEventType::SuspiciousAccess) => SecurityEventType::SuspiciousAccess,
+        Ok(proto::SecurityEventType::AuthenticationFailure) => SecurityEventType::AuthenticationFailure,
+        Ok(proto::SecurityEventType::AuthorizationViolation) => SecurityEventType::AuthorizationViolation,
+        Ok(proto::SecurityEventType::DataAccessAnomaly) => SecurityEventType::DataAccessAnomaly,
+        Ok(proto::SecurityEventType::PotentialAttack) => SecurityEventType::PotentialAttack,
+        Ok(proto::SecurityEventType::Custom) => SecurityEventType::Custom(event.custom_event_type),
+        _ => return Err(invalid(format!("unknown security event type {}", event.event_type))),
+    };
+    let severity = match proto::SecuritySeverity::try_from(event.severity) {
+        Ok(proto::SecuritySeverity::Low) => SecuritySeverity::Low,
+        Ok(proto::SecuritySeverity::Medium) => SecuritySeverity::Medium,
+        Ok(proto::SecuritySeverity::High) => SecuritySeverity::High,
+        Ok(proto::SecuritySeverity::Critical) => SecuritySeverity::Critical,
+        _ => return Err(invalid(format!("unknown security severity {}", event.severity))),
+    };
+
+    Ok(SecurityEvent {
+        event_type,
+        severity,
+        description: event.description,
+        timestamp: timestamp_from_proto(event.timestamp.ok_or_else(|| invalid("security event without timestamp"))?)?,
+        data: event
+            .data_json
+            .into_iter()
+            .map(|(key, json)| Ok((key, json_from_proto(&json)?)))
+            .collect::<Result<_, ExportError>>()?,
+    })
+}
+
+fn compliance_to_proto(record: &ComplianceRecord) -> proto::ComplianceRecord {
+    let (framework, custom_framework) = match &record.framework {
+        ComplianceFramework::SOX => (proto::ComplianceFramework::Sox, String::new()),
+        ComplianceFramework::HIPAA => (proto::ComplianceFramework::Hipaa, String::new()),
+        ComplianceFramework::GDPR => (proto::ComplianceFramework::Gdpr, String::new()),
+        ComplianceFramework::PCIDSS => (proto::ComplianceFramework::Pcidss, String::new()),
+        ComplianceFramework::Custom(name) => (proto::ComplianceFramework::Custom, name.clone()),
+    };
+    let status = match record.status {
+        ComplianceStatus::Compliant => proto::ComplianceStatus::Compliant,
+        ComplianceStatus::NonCompliant => proto::ComplianceStatus::NonCompliant,
+        ComplianceStatus::Unknown => proto::ComplianceStatus::Unknown,
+    };
+
+    proto::ComplianceRecord {
+        framework: framework as i32,
+        custom_framework,
+        requirement: record.requirement.clone(),
+        status: status as i32,
+        evidence_json: record.evidence.to_string(),
+    }
+}
+
+fn compliance_from_proto(record: proto::ComplianceRecord) -> Result<ComplianceRecord, ExportError> {
+    let framework = match proto::ComplianceFramework::try_from(record.framework) {
+        Ok(proto::ComplianceFramework::Sox) => ComplianceFramework::SOX,
+        Ok(proto::ComplianceFramework::Hipaa) => ComplianceFramework::HIPAA,
+        Ok(proto::ComplianceFramework::Gdpr) => ComplianceFramework::GDPR,
+        Ok(proto::ComplianceFramework::Pcidss) => ComplianceFramework::PCIDSS,
+        Ok(proto::ComplianceFramework::Custom) => ComplianceFramework::Custom(record.custom_framework),
+        _ => return Err(invalid(format!("unknown compliance framework {}", record.framework))),
+    };
+    // Statuses added after this build read as Unknown rather than failing the record
+    let status = match proto::ComplianceStatus::try_from(record.status) {
+        Ok(proto::ComplianceStatus::Compliant) => ComplianceStatus::Compliant,
+        Ok(proto::ComplianceStatus::NonCompliant) => ComplianceStatus::NonCompliant,
+        _ => ComplianceStatus::Unknown,
+    };
+
+    Ok(ComplianceRecord {
+        framework,
+        requirement: record.requirement,
+        status,
+        evidence: json_from_proto(&record.evidence_json)?,
+    })
+}
+
+fn context_to_proto(context: &ObservationContext) -> proto::ObservationContext {
+    proto::ObservationContext {
+        user_id: context.user_id.clone(),
+        session_id: context.session_id.clone(),
+        request_id: context.request_id.clone(),
+        trace_id: context.trace_id.clone(),
+        span_id: context.span_id.clone(),
+        tenant_id: context.tenant_id.clone(),
+        classification: context.classification.as_ref().map(|level| level.key().to_string()),
+        privacy_level: context.privacy_level.as_ref().map(|level| {
+            let level = match level {
+                PrivacyLevel::None => proto::PrivacyLevel::None,
+                PrivacyLevel::PII => proto::PrivacyLevel::Pii,
+                PrivacyLevel::PHI => proto::PrivacyLevel::Phi,
+                PrivacyLevel::Financial => proto::PrivacyLevel::Financial,
+                PrivacyLevel::Legal => proto::PrivacyLevel::Legal,
+            };
+            level as i32
+        }),
+    }
+}
+
+fn context_from_proto(context: proto::ObservationContext) -> Result<ObservationContext, ExportError> {
+    let privacy_level = match context.privacy_level.map(proto::PrivacyLevel::try_from) {
+        None => None,
+        Some(Ok(proto::PrivacyLevel::None)) => Some(PrivacyLevel::None),
+        Some(Ok(proto::PrivacyLevel::Pii)) => Some(PrivacyLevel::PII),
+        Some(Ok(proto::PrivacyLevel::Phi)) => Some(PrivacyLevel::PHI),
+        Some(Ok(proto::PrivacyLevel::Financial)) => Some(PrivacyLevel::Financial),
+        Some(Ok(proto::PrivacyLevel::Legal)) => Some(PrivacyLevel::Legal),
+        Some(_) => return Err(invalid("unknown privacy level")),
+    };
+
+    Ok(ObservationContext {
+        user_id: context.user_id,
+        session_id: context.session_id,
+        request_id: context.request_id,
+        trace_id: context.trace_id,
+        span_id: context.span_id,
+        tenant_id: context.tenant_id,
+        classification: context.classification.as_deref().map(ClassificationLevel::from_key),
+        privacy_level,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn populated_record() -> ObservationRecord {
+        let mut record = ObservationRecord::new(
+            "storage.put",
+            ObservationContext {
+                user_id: Some("analyst".to_string()),
+                session_id: Some("session-1".to_string()),
+                request_id: Some("request-1".to_string()),
+                trace_id: Some("trace-1".to_string()),
+                span_id: Some("span-1".to_string()),
+                tenant_id: Some("tenant-a".to_string()),
+                classification: Some(ClassificationLevel::Custom("export_controlled".to_string())),
+                privacy_level: Some(PrivacyLevel::PHI),
+            },
+            OperationResult::Error {
+                error_type: "StorageError".to_string(),
+                error_message: "disk full".to_string(),
+                error_code: Some("ENOSPC".to_string()),
+            },
+        );
+        record.performance = PerformanceMetrics {
+            duration_ns: 1_234_567,
+            cpu_usage: Some(12.5),
+            memory_usage_bytes: Some(4096),
+            network_io_bytes: None,
+            disk_io_bytes: Some(0),
+            custom_metrics: HashMap::from([("retries".to_string(), 2.0)]),
+        };
+        record.security_events = vec![
+            SecurityEvent {
+                event_type: SecurityEventType::DataAccessAnomaly,
+                severity: SecuritySeverity::High,
+                description: "bulk read".to_string(),
+                timestamp: record.started_at,
+                data: HashMap::from([("rows".to_string(), serde_json::json!({"count": 5000}))]),
+            },
+            SecurityEvent {
+                event_type: SecurityEventType::Custom("canary_touched".to_string()),
+                severity: SecuritySeverity::Critical,
+                description: "canary".to_string(),
+                timestamp: record.started_at,
+                data: HashMap::new(),
+            },
+        ];
+        record.compliance_records = vec![ComplianceRecord {
+            framework: ComplianceFramework::Custom("ITAR".to_string()),
+            requirement: "120.10".to_string(),
+            status: ComplianceStatus::NonCompliant,
+            evidence: serde_json::json!(["entity:42", null, 1.5]),
+        }];
+        record.privacy_protection = Some(PrivacyProtection {
+            pii_detected: true,
+            redaction_applied: true,
+            encryption_applied: false,
+        });
+        record.metadata.insert("parent_operation_id".to_string(), "op-0".to_string());
+        record
+    }
+
+    #[test]
+    fn test_populated_record_round_trips_through_protobuf() {
+        let record = populated_record();
+        assert_eq!(decode(&encode(&record)).unwrap(), record);
+
+        let mut in_progress = ObservationRecord::new(
+            "async.run",
+            ObservationContext::default(),
+            OperationResult::Success { return_value: Some(serde_json::json!({"ok": true})) },
+        );
+        in_progress.completed_at = None;
+        let mut stream = encode_length_delimited(&record);
+        stream.extend(encode_length_delimited(&in_progress));
+        assert_eq!(decode_stream(&stream).unwrap(), vec![record, in_progress]);
+    }
+
+    #[test]
+    fn test_decode_rejects_unknown_enum_values() {
+        let mut message = to_proto(&populated_record());
+        message.security_events[0].event_type = 99;
+        assert!(matches!(
+            decode(&message.encode_to_vec()),
+            Err(ExportError::SerializationFailed(_))
+        ));
+    }
+}
@@ -0,0 +1,229 @@
+// src-tauri/src/observability/exporters/redaction.rs
+// Export Redaction - Strip or hash sensitive payloads before records leave the engine
+// Applied per exporter so a SIEM feed can be redacted while the local store keeps full fidelity
+
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use ring::digest;
+
+use crate::observability::observation::{ObservationRecord, OperationResult, PrivacyLevel};
+use crate::observability::ForensicEnvelope;
+use crate::security::ClassificationLevel;
+
+/// Marker written in place of redacted error messages
+pub const REDACTED_MARKER: &str = "[REDACTED]";
+
+/// Per-exporter redaction policy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RedactionPolicy {
+    /// Records at or above this classification are redacted
+    pub classification_floor: ClassificationLevel,
+    /// Records carrying any of these privacy levels are redacted regardless of classification
+    pub privacy_levels: Vec<PrivacyLevel>,
+    pub mode: RedactionMode,
+}
+
+/// How redacted values are replaced
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RedactionMode {
+    /// Drop the value entirely
+    Strip,
+    /// Replace the value with its SHA-256 so identical payloads can still be correlated
+    Hash,
+}
+
+impl RedactionPolicy {
+    /// Redact everything at or above `classification_floor`, plus any PII/PHI/financial/legal payload
+    pub fn new(classification_floor: ClassificationLevel, mode: RedactionMode) -> Self {
+        Self {
+            classification_floor,
+            privacy_levels: vec![
+                PrivacyLevel::PII,
+                PrivacyLevel::PHI,
+                PrivacyLevel::Financial,
+                PrivacyLevel::Legal,
+            ],
+            mode,
+        }
+    }
+
+    /// Whether a record must be redacted before export under this policy
+    pub fn requires_redaction(&self, record: &ObservationRecord) -> bool {
+        let above_floor = self.at_or_above_floor(record.context.classification.as_ref());
+
+        let sensitive_privacy = record.context.privacy_level
+            .as_ref()
+            .map(|level| self.privacy_levels.contains(level))
+            .unwrap_or(false);
+
+        above_floor || sensitive_privacy
+    }
+
+    /// Whether an envelope's payloads must be redacted before export under this policy
+    pub fn requires_envelope_redaction(&self, envelope: &ForensicEnvelope) -> bool {
+        self.at_or_above_floor(Some(&envelope.classification))
+    }
+
+    fn at_or_above_floor(&self, classification: Option<&ClassificationLevel>) -> bool {
+        match classification {
+            // Anything not strictly below the floor, including incomparable levels
+            Some(level) => *level == self.classification_floor || !self.classification_floor.dominates(level),
+            // Unlabelled records fail closed
+            None => true,
+        }
+    }
+}
+
+/// Apply an exporter's redaction policy, borrowing the record untouched when no redaction is needed
+pub fn apply<'a>(record: &'a ObservationRecord, policy: Option<&RedactionPolicy>) -> Cow<'a, ObservationRecord> {
+    match policy {
+        Some(policy) if policy.requires_redaction(record) => Cow::Owned(redact(record, policy.mode)),
+        _ => Cow::Borrowed(record),
+    }
+}
+
+fn redact(record: &ObservationRecord, mode: RedactionMode) -> ObservationRecord {
+    let mut redacted = record.clone();
+
+    redacted.result = match &record.result {
+        OperationResult::Success { return_value } => OperationResult::Success {
+            return_value: match (return_value, mode) {
+                (Some(value), RedactionMode::Hash) => Some(serde_json::json!({ "sha256": hash_value(value) })),
+                _ => None,
+            },
+        },
+        OperationResult::Error { error_type, error_message, error_code } => OperationResult::Error {
+            error_type: error_type.clone(),
+            error_message: match mode {
+                RedactionMode::Strip => REDACTED_MARKER.to_string(),
+                RedactionMode::Hash => format!("{} sha256:{}", REDACTED_MARKER, hash_bytes(error_message.as_bytes())),
+            },
+            error_code: error_code.clone(),
+        },
+        OperationResult::InProgress => OperationResult::InProgress,
+    };
+
+    let mut protection = record.privacy_protection.clone().unwrap_or_default();
+    protection.redaction_applied = true;
+    redacted.privacy_protection = Some(protection);
+
+    redacted
+}
+
+/// Apply a redaction policy to a forensic envelope, borrowing it untouched when no redaction is needed
+///
+/// Who did what stays intact for the audit trail; the resource, states and metadata are
+/// stripped or hashed.
+pub fn apply_envelope<'a>(envelope: &'a ForensicEnvelope, policy: Option<&RedactionPolicy>) -> Cow<'a, ForensicEnvelope> {
+    match policy {
+        Some(policy) if policy.requires_envelope_redaction(envelope) => Cow::Owned(redact_envelope(envelope, policy.mode)),
+        _ => Cow::Borrowed(envelope),
+    }
+}
+
+fn redact_envelope(envelope: &ForensicEnvelope, mode: RedactionMode) -> ForensicEnvelope {
+    let redact_state = |state: &Option<serde_json::Value>| match (state, mode) {
+        (Some(value), RedactionMode::Hash) => Some(serde_json::json!({ "sha256": hash_value(value) })),
+        _ => None,
+    };
+
+    let mut redacted = envelope.clone();
+    redacted.resource = match (&envelope.resource, mode) {
+        (Some(resource), RedactionMode::Hash) => Some(format!("sha256:{}", hash_bytes(resource.as_bytes()))),
+        _ => None,
+    };
+    redacted.before_state = redact_state(&envelope.before_state);
+    redacted.after_state = redact_state(&envelope.after_state);
+    redacted.metadata = match mode {
+        RedactionMode::Strip => serde_json::json!({ "redaction_applied": true }),
+        RedactionMode::Hash => serde_json::json!({
+            "redaction_applied": true,
+            "sha256": hash_value(&envelope.metadata),
+        }),
+    };
+
+    redacted
+}
+
+fn hash_value(value: &serde_json::Value) -> String {
+    hash_bytes(value.to_string().as_bytes())
+}
+
+fn hash_bytes(bytes: &[u8]) -> String {
+    hex::encode(digest::digest(&digest::SHA256, bytes).as_ref())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::observability::observation::ObservationContext;
+
+    fn record(classification: ClassificationLevel, result: OperationResult) -> ObservationRecord {
+        ObservationRecord::new(
+            "entity.read",
+            ObservationContext {
+                classification: Some(classification),
+                ..Default::default()
+            },
+            result,
+        )
+    }
+
+    #[test]
+    fn test_below_floor_is_borrowed_unchanged() {
+        let policy = RedactionPolicy::new(ClassificationLevel::Confidential, RedactionMode::Strip);
+        let original = record(
+            ClassificationLevel::Internal,
+            OperationResult::Success { return_value: Some(serde_json::json!({"ok": true})) },
+        );
+
+        assert!(matches!(apply(&original, Some(&policy)), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_strip_removes_return_value() {
+        let policy = RedactionPolicy::new(ClassificationLevel::Confidential, RedactionMode::Strip);
+        let original = record(
+            ClassificationLevel::Secret,
+            OperationResult::Success { return_value: Some(serde_json::json!({"ssn": "123-45-6789"})) },
+        );
+
+        let redacted = apply(&original, Some(&policy));
+        assert!(matches!(redacted.result, OperationResult::Success { return_value: None }));
+        assert!(redacted.privacy_protection.as_ref().unwrap().redaction_applied);
+    }
+
+    #[test]
+    fn test_hash_redacts_error_message() {
+        let policy = RedactionPolicy::new(ClassificationLevel::Confidential, RedactionMode::Hash);
+        let original = record(
+            ClassificationLevel::Confidential,
+            OperationResult::Error {
+                error_type: "NotFound".to_string(),
+                error_message: "entity for agent BLUEFOX missing".to_string(),
+                error_code: None,
+            },
+        );
+
+        let redacted = apply(&original, Some(&policy));
+        match &redacted.result {
+            OperationResult::Error { error_message, .. } => {
+                assert!(error_message.starts_with(REDACTED_MARKER));
+                assert!(!error_message.contains("BLUEFOX"));
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_privacy_level_forces_redaction() {
+        let policy = RedactionPolicy::new(ClassificationLevel::Secret, RedactionMode::Strip);
+        let mut original = record(
+            ClassificationLevel::Unclassified,
+            OperationResult::Success { return_value: Some(serde_json::json!({"email": "a@b.c"})) },
+        );
+        original.context.privacy_level = Some(PrivacyLevel::PII);
+
+        assert!(policy.requires_redaction(&original));
+    }
+}
@@ -0,0 +1,538 @@
+// src-tauri/src/observability/exporters/s3.rs
+// S3 Exporter - Immutable long-term retention of forensic records in object storage
+// Buffers records into NDJSON (or length-delimited protobuf) objects under time-partitioned keys
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use tokio::sync::Mutex;
+use chrono::{DateTime, Datelike, Timelike, Utc};
+use ring::{digest, hmac};
+use base64::{Engine as _, engine::general_purpose};
+
+use super::{frame_record, ExportError, ExportFormat, ExporterConfig, ObservabilityExporter, RetryConfig, ServerSideEncryption};
+use crate::observability::observation::ObservationRecord;
+
+/// S3 exporter for long-term immutable forensic retention
+#[derive(Debug)]
+pub struct S3Exporter {
+    settings: S3ExporterConfig,
+    config: ExporterConfig,
+    client: reqwest::Client,
+    buffer: Mutex<ObjectBuffer>,
+}
+
+/// Bucket, credential, and rolling settings for the S3 exporter
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct S3ExporterConfig {
+    pub bucket: String,
+    pub region: String,
+    /// Custom endpoint (e.g. a local MinIO); switches to path-style addressing
+    pub endpoint: Option<String>,
+    pub key_prefix: String,
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    pub object_lock: Option<ObjectLockSettings>,
+    /// Roll to a new object once the buffered object reaches this size
+    pub roll_max_bytes: usize,
+    /// Roll to a new object once the buffered object is this old
+    pub roll_max_age_secs: u64,
+    pub mode: S3Mode,
+}
+
+/// Upload mode
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum S3Mode {
+    /// Signed PUTs against S3 or the configured endpoint
+    Live,
+    /// Write objects under a local directory using the same key layout (tests, air-gapped staging)
+    DryRun { local_dir: PathBuf },
+}
+
+/// Object-lock retention applied to every uploaded object
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ObjectLockSettings {
+    pub mode: ObjectLockMode,
+    pub retain_days: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ObjectLockMode {
+    Governance,
+    Compliance,
+}
+
+/// NDJSON object currently being filled
+#[derive(Debug, Default)]
+struct ObjectBuffer {
+    data: Vec<u8>,
+    records: usize,
+    opened_at: Option<DateTime<Utc>>,
+}
+
+impl ObjectBuffer {
+    fn should_roll(&self, settings: &S3ExporterConfig, now: DateTime<Utc>) -> bool {
+        let age_exceeded = self.opened_at
+            .map(|opened| (now - opened).num_seconds() >= settings.roll_max_age_secs as i64)
+            .unwrap_or(false);
+        self.data.len() >= settings.roll_max_bytes || age_exceeded
+    }
+
+    fn take(&mut self) -> Option<(Vec<u8>, DateTime<Utc>)> {
+        let opened_at = self.opened_at.take()?;
+        self.records = 0;
+        Some((std::mem::take(&mut self.data), opened_at))
+    }
+
+    /// Put an object that failed to upload back in front of newer records
+    fn restore(&mut self, mut data: Vec<u8>, opened_at: DateTime<Utc>) {
+        self.records += data.iter().filter(|b| **b == b'\n').count();
+        data.extend_from_slice(&self.data);
+        self.data = data;
+        self.opened_at = Some(self.opened_at.map_or(opened_at, |current| current.min(opened_at)));
+    }
+}
+
+impl Default for S3ExporterConfig {
+    fn default() -> Self {
+        Self {
+            bucket: String::new(),
+            region: "us-east-1".to_string(),
+            endpoint: None,
+            key_prefix: "forensic".to_string(),
+            access_key_id: String::new(),
+            secret_access_key: String::new(),
+            object_lock: None,
+            roll_max_bytes: 64 * 1024 * 1024, // 64MB objects
+            roll_max_age_secs: 300,           // or every 5 minutes
+            mode: S3Mode::Live,
+        }
+    }
+}
+
+impl S3Exporter {
+    /// Create new S3 exporter
+    pub fn new(settings: S3ExporterConfig, server_side_encryption: Option<ServerSideEncryption>) -> Self {
+        Self {
+            settings,
+            config: ExporterConfig {
+                name: "s3".to_string(),
+                format: ExportFormat::JSON,
+                batch_size: None,
+                timeout_ms: Some(30_000),
+                retry_config: Some(RetryConfig::default()),
+                server_side_encryption,
+                compression: None,
+                redaction: None,
+            },
+            client: reqwest::Client::new(),
+            buffer: Mutex::new(ObjectBuffer::default()),
+        }
+    }
+
+    /// Override the exporter configuration (timeouts, retries, encryption)
+    pub fn with_config(mut self, config: ExporterConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Build the time-partitioned object key for an object opened at `opened_at`
+    fn object_key(&self, opened_at: DateTime<Utc>) -> String {
+        let prefix = self.settings.key_prefix.trim_matches('/');
+        let partition = format!(
+            "{:04}/{:02}/{:02}/{:02}",
+            opened_at.year(), opened_at.month(), opened_at.day(), opened_at.hour()
+        );
+        let extension = match self.config.format {
+            ExportFormat::Protobuf => "pb",
+            _ => "jsonl",
+        };
+        let object_name = format!(
+            "{}-{}.{}",
+            opened_at.format("%Y%m%dT%H%M%S%.3fZ"),
+            uuid::Uuid::new_v4(),
+            extension
+        );
+
+        if prefix.is_empty() {
+            format!("{}/{}", partition, object_name)
+        } else {
+            format!("{}/{}/{}", prefix, partition, object_name)
+        }
+    }
+
+    /// Upload the buffered object, restoring it to the buffer if every attempt fails
+    async fn upload_object(&self, data: Vec<u8>, opened_at: DateTime<Utc>) -> Result<(), ExportError> {
+        let key = self.object_key(opened_at);
+        let retry = self.config.retry_config.clone().unwrap_or_default();
+        let mut attempt = 1;
+
+        loop {
+            match self.put_object(&key, &data).await {
+                Ok(()) => return Ok(()),
+                Err(e) if e.is_retryable() && attempt < retry.max_attempts => {
+                    tracing::warn!("S3 upload of {} failed (attempt {}): {}", key, attempt, e);
+                    tokio::time::sleep(retry.delay_for_attempt(attempt)).await;
+                    attempt += 1;
+                }
+                Err(e) => {
+                    tracing::error!("S3 upload of {} abandoned after {} attempts: {}", key, attempt, e);
+                    self.buffer.lock().await.restore(data, opened_at);
+                    return Err(e);
+                }
+            }
+        }
+    }
+
+    async fn put_object(&self, key: &str, body: &[u8]) -> Result<(), ExportError> {
+        match &self.settings.mode {
+            S3Mode::DryRun { local_dir } => {
+                let path = local_dir.join(key);
+                if let Some(parent) = path.parent() {
+                    tokio::fs::create_dir_all(parent).await
+                        .map_err(|e| ExportError::IOError(e.to_string()))?;
+                }
+                tokio::fs::write(&path, body).await
+                    .map_err(|e| ExportError::IOError(e.to_string()))
+            }
+            S3Mode::Live => self.put_object_signed(key, body).await,
+        }
+    }
+
+    /// Signed (AWS SigV4) PUT of a single object
+    async fn put_object_signed(&self, key: &str, body: &[u8]) -> Result<(), ExportError> {
+        let (url, canonical_uri) = self.object_url(key)?;
+        let host = match url.port() {
+            Some(port) => format!("{}:{}", url.host_str().unwrap_or_default(), port),
+            None => url.host_str().unwrap_or_default().to_string(),
+        };
+
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date_stamp = now.format("%Y%m%d").to_string();
+        let payload_digest = digest::digest(&digest::SHA256, body);
+
+        let mut headers = BTreeMap::new();
+        headers.insert("content-type".to_string(), "application/x-ndjson".to_string());
+        headers.insert("host".to_string(), host);
+        headers.insert("x-amz-checksum-sha256".to_string(), general_purpose::STANDARD.encode(payload_digest.as_ref()));
+        headers.insert("x-amz-content-sha256".to_string(), hex::encode(payload_digest.as_ref()));
+        headers.insert("x-amz-date".to_string(), amz_date.clone());
+
+        match &self.config.server_side_encryption {
+            Some(ServerSideEncryption::Aes256) => {
+                headers.insert("x-amz-server-side-encryption".to_string(), "AES256".to_string());
+            }
+            Some(ServerSideEncryption::Kms { key_id }) => {
+                headers.insert("x-amz-server-side-encryption".to_string(), "aws:kms".to_string());
+                if let Some(key_id) = key_id {
+                    headers.insert("x-amz-server-side-encryption-aws-kms-key-id".to_string(), key_id.clone());
+                }
+            }
+            None => {}
+        }
+
+        if let Some(lock) = &self.settings.object_lock {
+            let mode = match lock.mode {
+                ObjectLockMode::Governance => "GOVERNANCE",
+                ObjectLockMode::Compliance => "COMPLIANCE",
+            };
+            let retain_until = now + chrono::Duration::days(lock.retain_days as i64);
+            headers.insert("x-amz-object-lock-mode".to_string(), mode.to_string());
+            headers.insert(
+                "x-amz-object-lock-retain-until-date".to_string(),
+                retain_until.format("%Y-%m-%dT%H:%M:%SZ").to_string(),
+            );
+        }
+
+        let authorization = sign_request(
+            "PUT",
+            &canonical_uri,
+            &headers,
+            &hex::encode(payload_digest.as_ref()),
+            &self.settings.access_key_id,
+            &self.settings.secret_access_key,
+            &self.settings.region,
+            &amz_date,
+            &date_stamp,
+        );
+
+        let mut request = self.client.put(url).body(body.to_vec());
+        for (name, value) in headers.iter().filter(|(name, _)| name.as_str() != "host") {
+            request = request.header(name.as_str(), value.as_str());
+        }
+        request = request.header("authorization", authorization);
+        if let Some(timeout_ms) = self.config.timeout_ms {
+            request = request.timeout(std::time::Duration::from_millis(timeout_ms));
+        }
+
+        let response = request.send().await
+            .map_err(|e| ExportError::NetworkError(e.to_string()))?;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+
+        let detail = format!("{} {}", status, response.text().await.unwrap_or_default());
+        Err(match status.as_u16() {
+            429 | 503 => ExportError::RateLimited(detail),
+            401 | 403 => ExportError::AuthenticationFailed(detail),
+            code if code >= 500 => ExportError::NetworkError(detail),
+            _ => ExportError::Custom(detail),
+        })
+    }
+
+    /// Resolve the object URL and its canonical URI (virtual-hosted for AWS, path-style for custom endpoints)
+    fn object_url(&self, key: &str) -> Result<(reqwest::Url, String), ExportError> {
+        let encoded_key = key.split('/').map(uri_encode).collect::<Vec<_>>().join("/");
+
+        let (base, canonical_uri) = match &self.settings.endpoint {
+            Some(endpoint) => (
+                format!("{}/{}/{}", endpoint.trim_end_matches('/'), self.settings.bucket, encoded_key),
+                format!("/{}/{}", uri_encode(&self.settings.bucket), encoded_key),
+            ),
+            None => (
+                format!("https://{}.s3.{}.amazonaws.com/{}", self.settings.bucket, self.settings.region, encoded_key),
+                format!("/{}", encoded_key),
+            ),
+        };
+
+        let url = reqwest::Url::parse(&base)
+            .map_err(|e| ExportError::Custom(format!("Invalid S3 endpoint: {}", e)))?;
+        Ok((url, canonical_uri))
+    }
+}
+
+#[async_trait::async_trait]
+impl ObservabilityExporter for S3Exporter {
+    async fn export(&self, record: &ObservationRecord) -> Result<(), ExportError> {
+        let line = frame_record(record, &self.config.format)?;
+
+        let now = Utc::now();
+        let ready = {
+            let mut buffer = self.buffer.lock().await;
+            if buffer.opened_at.is_none() {
+                buffer.opened_at = Some(now);
+            }
+            buffer.data.extend_from_slice(&line);
+            buffer.records += 1;
+
+            if buffer.should_roll(&self.settings, now) {
+                buffer.take()
+            } else {
+                None
+            }
+        };
+
+        match ready {
+            Some((data, opened_at)) => self.upload_object(data, opened_at).await,
+            None => Ok(()),
+        }
+    }
+
+    async fn flush(&self) -> Result<(), ExportError> {
+        let pending = self.buffer.lock().await.take();
+        match pending {
+            Some((data, opened_at)) if !data.is_empty() => self.upload_object(data, opened_at).await,
+            _ => Ok(()),
+        }
+    }
+
+    fn buffers_on_failure(&self) -> bool {
+        true
+    }
+
+    fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    fn config(&self) -> ExporterConfig {
+        self.config.clone()
+    }
+}
+
+/// Build the SigV4 Authorization header value
+#[allow(clippy::too_many_arguments)]
+fn sign_request(
+    method: &str,
+    canonical_uri: &str,
+    headers: &BTreeMap<String, String>,
+    payload_hash: &str,
+    access_key_id: &str,
+    secret_access_key: &str,
+    region: &str,
+    amz_date: &str,
+    date_stamp: &str,
+) -> String {
+    let canonical_headers: String = headers.iter()
+        .map(|(name, value)| format!("{}:{}\n", name, value.trim()))
+        .collect();
+    let signed_headers = headers.keys().cloned().collect::<Vec<_>>().join(";");
+
+    let canonical_request = format!(
+        "{}\n{}\n\n{}\n{}\n{}",
+        method, canonical_uri, canonical_headers, signed_headers, payload_hash
+    );
+
+    let scope = format!("{}/{}/s3/aws4_request", date_stamp, region);
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        amz_date,
+        scope,
+        hex::encode(digest::digest(&digest::SHA256, canonical_request.as_bytes()).as_ref())
+    );
+
+    let signing_key = derive_signing_key(secret_access_key, date_stamp, region, "s3");
+    let signature = hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, &signing_key), string_to_sign.as_bytes());
+
+    format!(
+        "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+        access_key_id,
+        scope,
+        signed_headers,
+        hex::encode(signature.as_ref())
+    )
+}
+
+/// Derive the SigV4 signing key for a date/region/service scope
+fn derive_signing_key(secret_access_key: &str, date_stamp: &str, region: &str, service: &str) -> Vec<u8> {
+    let sign = |key: &[u8], data: &str| -> Vec<u8> {
+        hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, key), data.as_bytes()).as_ref().to_vec()
+    };
+
+    let k_date = sign(format!("AWS4{}", secret_access_key).as_bytes(), date_stamp);
+    let k_region = sign(&k_date, region);
+    let k_service = sign(&k_region, service);
+    sign(&k_service, "aws4_request")
+}
+
+/// URI-encode a single path segment per the SigV4 rules
+fn uri_encode(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use crate::observability::observation::{ObservationContext, OperationResult};
+
+    fn dry_run_exporter(dir: &std::path::Path, roll_max_bytes: usize) -> S3Exporter {
+        S3Exporter::new(
+            S3ExporterConfig {
+                bucket: "forensic-archive".to_string(),
+                roll_max_bytes,
+                roll_max_age_secs: 3600,
+                mode: S3Mode::DryRun { local_dir: dir.to_path_buf() },
+                ..Default::default()
+            },
+            Some(ServerSideEncryption::Aes256),
+        )
+    }
+
+    fn test_record() -> ObservationRecord {
+        ObservationRecord::new(
+            "storage.put",
+            ObservationContext::default(),
+            OperationResult::Success { return_value: None },
+        )
+    }
+
+    fn object_files(dir: &std::path::Path) -> Vec<PathBuf> {
+        let mut files = Vec::new();
+        let mut pending = vec![dir.to_path_buf()];
+        while let Some(current) = pending.pop() {
+            for entry in std::fs::read_dir(current).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    pending.push(path);
+                } else {
+                    files.push(path);
+                }
+            }
+        }
+        files
+    }
+
+    #[test]
+    fn test_signing_key_derivation() {
+        // Reference vector from the AWS SigV4 documentation
+        let key = derive_signing_key(
+            "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+            "20120215",
+            "us-east-1",
+            "iam",
+        );
+        assert_eq!(
+            hex::encode(key),
+            "f4780e2d9f65fa895f9c67b32ce1baf0b0d8a43505a000a1a9e090d414db404d"
+        );
+    }
+
+    #[test]
+    fn test_object_key_is_time_partitioned() {
+        let dir = tempfile::tempdir().unwrap();
+        let exporter = dry_run_exporter(dir.path(), 1024);
+        let opened_at = Utc.with_ymd_and_hms(2024, 3, 5, 7, 30, 0).unwrap();
+
+        let key = exporter.object_key(opened_at);
+        assert!(key.starts_with("forensic/2024/03/05/07/"));
+        assert!(key.ends_with(".jsonl"));
+    }
+
+    #[test]
+    fn test_path_style_url_for_custom_endpoint() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut exporter = dry_run_exporter(dir.path(), 1024);
+        exporter.settings.endpoint = Some("http://localhost:9000/".to_string());
+
+        let (url, canonical_uri) = exporter.object_url("forensic/2024/a b.jsonl").unwrap();
+        assert_eq!(url.as_str(), "http://localhost:9000/forensic-archive/forensic/2024/a%20b.jsonl");
+        assert_eq!(canonical_uri, "/forensic-archive/forensic/2024/a%20b.jsonl");
+    }
+
+    #[tokio::test]
+    async fn test_rolls_object_at_size_threshold() {
+        let dir = tempfile::tempdir().unwrap();
+        let exporter = dry_run_exporter(dir.path(), 1);
+
+        for _ in 0..3 {
+            exporter.export(&test_record()).await.unwrap();
+        }
+
+        let files = object_files(dir.path());
+        assert_eq!(files.len(), 3);
+        for file in files {
+            assert_eq!(std::fs::read_to_string(file).unwrap().lines().count(), 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_flush_uploads_partial_object() {
+        let dir = tempfile::tempdir().unwrap();
+        let exporter = dry_run_exporter(dir.path(), 1024 * 1024);
+
+        for _ in 0..3 {
+            exporter.export(&test_record()).await.unwrap();
+        }
+        assert!(object_files(dir.path()).is_empty());
+
+        exporter.flush().await.unwrap();
+
+        let files = object_files(dir.path());
+        assert_eq!(files.len(), 1);
+        let contents = std::fs::read_to_string(&files[0]).unwrap();
+        assert_eq!(contents.lines().count(), 3);
+        for line in contents.lines() {
+            let _: ObservationRecord = serde_json::from_str(line).unwrap();
+        }
+    }
+}
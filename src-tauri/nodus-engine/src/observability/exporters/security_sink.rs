@@ -0,0 +1,472 @@
+// src-tauri/src/observability/exporters/security_sink.rs
+// Security Event Sink - Webhook alerts (Slack, PagerDuty, ...) for high-severity security events
+// Posts through SecureNetworkTransport, with dedup and per-channel rate limiting against alert storms
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use tokio::sync::{Mutex, RwLock};
+use uuid::Uuid;
+
+use super::{ExportError, ExportFormat, ExporterConfig, ObservabilityExporter};
+use crate::multi_tenant::AlertChannel;
+use crate::networking::{HttpMethod, NetworkContext, SecureNetworkTransport, SecureRequest, SecurityRequirements};
+use crate::observability::automatic_instrumentation::InstrumentationHooks;
+use crate::observability::observation::{ObservationRecord, SecurityEvent, SecuritySeverity};
+use crate::observability::QosClass;
+use crate::security::{ClassificationLevel, SecurityLabel};
+
+/// Channel types the sink delivers to; each takes a `url` in its configuration
+const WEBHOOK_CHANNEL_TYPES: [&str; 3] = ["webhook", "slack", "pagerduty"];
+
+/// Thresholds and storm protection for the security event sink
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecurityEventSinkConfig {
+    /// Events below this severity are never posted; a channel may raise it with `min_severity`
+    pub min_severity: SecuritySeverity,
+    /// An identical event to the same channel is posted at most once per window
+    pub dedup_window_secs: u64,
+    /// At most this many posts per channel per `rate_window_secs`
+    pub max_alerts_per_window: usize,
+    pub rate_window_secs: u64,
+}
+
+impl Default for SecurityEventSinkConfig {
+    fn default() -> Self {
+        Self {
+            min_severity: SecuritySeverity::High,
+            dedup_window_secs: 300,
+            max_alerts_per_window: 20,
+            rate_window_secs: 60,
+        }
+    }
+}
+
+/// Alert sink section of the export policy; tenants add their own channels at runtime
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SecurityAlertPolicy {
+    #[serde(flatten)]
+    pub settings: SecurityEventSinkConfig,
+    /// Channels for events without a tenant, or whose tenant has none configured
+    #[serde(default)]
+    pub default_channels: Vec<AlertChannel>,
+}
+
+/// Delivers a webhook payload; `TransportPoster` in production
+#[async_trait::async_trait]
+pub trait WebhookPoster: Send + Sync + std::fmt::Debug {
+    async fn post(&self, url: &str, payload: &Value, classification: ClassificationLevel) -> Result<(), ExportError>;
+}
+
+/// Posts through `SecureNetworkTransport`, so alerts get its policy, TLS and circuit-breaker checks
+pub struct TransportPoster {
+    transport: Arc<SecureNetworkTransport>,
+    hooks: Arc<dyn InstrumentationHooks>,
+}
+
+impl TransportPoster {
+    /// `hooks` receives the transport's own audit and metrics events (normally the `AppState`)
+    pub fn new(transport: Arc<SecureNetworkTransport>, hooks: Arc<dyn InstrumentationHooks>) -> Self {
+        Self { transport, hooks }
+    }
+}
+
+impl std::fmt::Debug for TransportPoster {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TransportPoster").finish_non_exhaustive()
+    }
+}
+
+#[async_trait::async_trait]
+impl WebhookPoster for TransportPoster {
+    async fn post(&self, url: &str, payload: &Value, classification: ClassificationLevel) -> Result<(), ExportError> {
+        let request = SecureRequest {
+            request_id: Uuid::new_v4(),
+            url: url.to_string(),
+            method: HttpMethod::POST,
+            headers: HashMap::new(),
+            body: None,
+            classification: classification.clone(),
+            user_id: "security_event_sink".to_string(),
+            session_id: Uuid::nil(),
+            timeout_ms: Some(10_000),
+            retry_policy: None,
+            cache_policy: None,
+            security_requirements: SecurityRequirements::default(),
+            idempotency_key: Some(Uuid::new_v4().to_string()),
+            qos: QosClass::Batch,
+        }
+        .with_json_body(payload)
+        .map_err(|e| ExportError::SerializationFailed(e.to_string()))?;
+
+        let context = NetworkContext {
+            user_id: "security_event_sink".to_string(),
+            session_id: Uuid::nil(),
+            security_label: SecurityLabel::new(classification, vec![]),
+            tenant_id: payload["tenant_id"].as_str().map(str::to_string),
+            source_ip: None,
+            user_agent: None,
+        };
+
+        let response = self.transport.request(request, context, self.hooks.as_ref()).await
+            .map_err(|e| ExportError::NetworkError(e.to_string()))?;
+        match response.status_code {
+            200..=299 => Ok(()),
+            429 => Err(ExportError::RateLimited(format!("webhook {} returned 429", url))),
+            status => Err(ExportError::Custom(format!("webhook {} returned {}", url, status))),
+        }
+    }
+}
+
+/// A webhook an `AlertChannel` resolves to
+#[derive(Debug, Clone, PartialEq)]
+struct WebhookTarget {
+    url: String,
+    min_severity: Option<SecuritySeverity>,
+}
+
+impl WebhookTarget {
+    /// `None` for channel types the sink doesn't deliver to, or a channel without a url
+    fn from_channel(channel: &AlertChannel) -> Option<Self> {
+        if !WEBHOOK_CHANNEL_TYPES.contains(&channel.channel_type.to_lowercase().as_str()) {
+            return None;
+        }
+        let url = webhook_url(channel)?.to_string();
+        let min_severity = channel.configuration.get("min_severity")
+            .and_then(|severity| serde_json::from_value(severity.clone()).ok());
+        Some(Self { url, min_severity })
+    }
+}
+
+/// The `url` a webhook, Slack or PagerDuty channel posts to
+pub(crate) fn webhook_url(channel: &AlertChannel) -> Option<&str> {
+    channel.configuration.get("url")?.as_str()
+}
+
+/// Dedup and rate-limit bookkeeping
+#[derive(Debug, Default)]
+struct StormGuard {
+    /// Dedup key to when it was last posted
+    last_posted: HashMap<String, DateTime<Utc>>,
+    /// Channel url to recent post times
+    recent_posts: HashMap<String, VecDeque<DateTime<Utc>>>,
+}
+
+/// Exporter that posts high-severity security events to webhook alert channels
+///
+/// Registered with the `ExportEngine` like any exporter; records without qualifying security
+/// events pass through untouched. Channels come from the record's tenant, falling back to the
+/// default channels. Only event metadata is posted; event data stays in the forensic record.
+#[derive(Debug)]
+pub struct SecurityEventSink {
+    settings: SecurityEventSinkConfig,
+    config: ExporterConfig,
+    poster: Arc<dyn WebhookPoster>,
+    default_channels: Vec<AlertChannel>,
+    tenant_channels: RwLock<HashMap<String, Vec<AlertChannel>>>,
+    guard: Mutex<StormGuard>,
+}
+
+impl SecurityEventSink {
+    pub fn new(settings: SecurityEventSinkConfig, poster: Arc<dyn WebhookPoster>) -> Self {
+        Self {
+            settings,
+            config: ExporterConfig {
+                name: "security_event_sink".to_string(),
+                format: ExportFormat::JSON,
+                batch_size: None,
+                timeout_ms: Some(15_000),
+                retry_config: None,
+                server_side_encryption: None,
+                compression: None,
+                redaction: None,
+            },
+            poster,
+            default_channels: Vec::new(),
+            tenant_channels: RwLock::new(HashMap::new()),
+            guard: Mutex::new(StormGuard::default()),
+        }
+    }
+
+    /// Sink with the thresholds and default channels of `policy`
+    pub fn from_policy(policy: &SecurityAlertPolicy, poster: Arc<dyn WebhookPoster>) -> Self {
+        Self::new(policy.settings.clone(), poster).with_default_channels(policy.default_channels.clone())
+    }
+
+    /// Channels for records without a tenant, or whose tenant has none configured
+    pub fn with_default_channels(mut self, channels: Vec<AlertChannel>) -> Self {
+        self.default_channels = channels;
+        self
+    }
+
+    /// Replace a tenant's alert channels (typically its `AlertingConfig::alert_channels`)
+    pub async fn set_tenant_channels(&self, tenant_id: &str, channels: Vec<AlertChannel>) {
+        self.tenant_channels.write().await.insert(tenant_id.to_string(), channels);
+    }
+
+    pub async fn remove_tenant_channels(&self, tenant_id: &str) {
+        self.tenant_channels.write().await.remove(tenant_id);
+    }
+
+    async fn targets(&self, tenant_id: Option<&str>) -> Vec<WebhookTarget> {
+        let tenant_channels = self.tenant_channels.read().await;
+        let channels = tenant_id
+            .and_then(|tenant_id| tenant_channels.get(tenant_id))
+            .filter(|channels| !channels.is_empty())
+            .unwrap_or(&self.default_channels);
+        channels.iter().filter_map(WebhookTarget::from_channel).collect()
+    }
+
+    /// Claim a post slot for `dedup_key` on `url`, or `None` if it's a duplicate or rate limited
+    ///
+    /// The slot is claimed before posting so concurrent duplicates are suppressed too; a failed
+    /// post releases it with `release`.
+    async fn claim(&self, url: &str, dedup_key: &str, now: DateTime<Utc>) -> Option<Option<DateTime<Utc>>> {
+        let mut guard = self.guard.lock().await;
+        let dedup_window = Duration::seconds(self.settings.dedup_window_secs as i64);
+        let rate_window = Duration::seconds(self.settings.rate_window_secs as i64);
+
+        let previous = guard.last_posted.get(dedup_key).copied();
+        if previous.is_some_and(|posted_at| now - posted_at < dedup_window) {
+            return None;
+        }
+
+        let recent = guard.recent_posts.entry(url.to_string()).or_default();
+        while recent.front().is_some_and(|posted_at| now - *posted_at >= rate_window) {
+            recent.pop_front();
+        }
+        if recent.len() >= self.settings.max_alerts_per_window {
+            tracing::warn!("Security alert to {} rate limited ({} in {}s)", url, recent.len(), self.settings.rate_window_secs);
+            return None;
+        }
+        recent.push_back(now);
+
+        guard.last_posted.insert(dedup_key.to_string(), now);
+        guard.last_posted.retain(|_, posted_at| now - *posted_at < dedup_window);
+        Some(previous)
+    }
+
+    /// Undo a claim whose post failed, so a retry or the next occurrence isn't suppressed
+    async fn release(&self, url: &str, dedup_key: &str, claimed_at: DateTime<Utc>, previous: Option<DateTime<Utc>>) {
+        let mut guard = self.guard.lock().await;
+        match previous {
+            Some(posted_at) => guard.last_posted.insert(dedup_key.to_string(), posted_at),
+            None => guard.last_posted.remove(dedup_key),
+        };
+        if let Some(recent) = guard.recent_posts.get_mut(url) {
+            if let Some(position) = recent.iter().rposition(|posted_at| *posted_at == claimed_at) {
+                recent.remove(position);
+            }
+        }
+    }
+}
+
+/// Dedup identity of an event: same tenant, type, severity and description
+fn dedup_key(url: &str, tenant_id: Option<&str>, event: &SecurityEvent) -> String {
+    format!("{}|{}|{:?}|{:?}|{}", url, tenant_id.unwrap_or("-"), event.event_type, event.severity, event.description)
+}
+
+/// Structured alert body; `text` is the summary chat integrations such as Slack display
+pub fn webhook_payload(record: &ObservationRecord, event: &SecurityEvent) -> Value {
+    let tenant_id = record.context.tenant_id.as_deref();
+    serde_json::json!({
+        "alert_id": Uuid::new_v4().to_string(),
+        "source": "nodus",
+        "text": format!("[{:?}] {:?}: {}", event.severity, event.event_type, event.description),
+        "severity": event.severity,
+        "event_type": event.event_type,
+        "description": event.description,
+        "timestamp": event.timestamp,
+        "tenant_id": tenant_id,
+        "user_id": record.context.user_id,
+        "operation": record.operation,
+        "observation_id": record.observation_id,
+        "classification": record.context.classification,
+    })
+}
+
+#[async_trait::async_trait]
+impl ObservabilityExporter for SecurityEventSink {
+    async fn export(&self, record: &ObservationRecord) -> Result<(), ExportError> {
+        let events: Vec<&SecurityEvent> = record.security_events.iter()
+            .filter(|event| event.severity >= self.settings.min_severity)
+            .collect();
+        if events.is_empty() {
+            return Ok(());
+        }
+
+        let tenant_id = record.context.tenant_id.as_deref();
+        let classification = record.context.classification.clone().unwrap_or(ClassificationLevel::Internal);
+        let mut failure = None;
+
+        for target in self.targets(tenant_id).await {
+            for event in &events {
+                if target.min_severity.is_some_and(|min_severity| event.severity < min_severity) {
+                    continue;
+                }
+
+                let key = dedup_key(&target.url, tenant_id, event);
+                let now = Utc::now();
+                let previous = match self.claim(&target.url, &key, now).await {
+                    Some(previous) => previous,
+                    None => continue,
+                };
+
+                let payload = webhook_payload(record, event);
+                if let Err(e) = self.poster.post(&target.url, &payload, classification.clone()).await {
+                    tracing::warn!("Security alert to {} failed: {}", target.url, e);
+                    self.release(&target.url, &key, now, previous).await;
+                    failure = Some(e);
+                }
+            }
+        }
+
+        failure.map_or(Ok(()), Err)
+    }
+
+    fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    fn config(&self) -> ExporterConfig {
+        self.config.clone()
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::observability::observation::{ObservationContext, OperationResult, SecurityEventType};
+    use std::sync::Mutex;
+
+    /// Poster that records every webhook instead of sending it
+    #[derive(Debug, Default)]
+    pub(crate) struct RecordingPoster {
+        pub(crate) posts: Mutex<Vec<(String, Value)>>,
+        pub(crate) fail: std::sync::atomic::AtomicBool,
+    }
+
+    #[async_trait::async_trait]
+    impl WebhookPoster for RecordingPoster {
+        async fn post(&self, url: &str, payload: &Value, _classification: ClassificationLevel) -> Result<(), ExportError> {
+            if self.fail.load(std::sync::atomic::Ordering::SeqCst) {
+                return Err(ExportError::NetworkError("connection refused".to_string()));
+            }
+            self.posts.lock().unwrap().push((url.to_string(), payload.clone()));
+            Ok(())
+        }
+    }
+
+    fn channel(url: &str, configuration: Value) -> AlertChannel {
+        let mut configuration = configuration;
+        configuration["url"] = Value::from(url);
+        AlertChannel { channel_type: "webhook".to_string(), configuration }
+    }
+
+    fn record(tenant_id: Option<&str>, severity: SecuritySeverity, description: &str) -> ObservationRecord {
+        let mut record = ObservationRecord::new(
+            "auth.login",
+            ObservationContext { tenant_id: tenant_id.map(str::to_string), ..Default::default() },
+            OperationResult::Success { return_value: None },
+        );
+        record.security_events.push(SecurityEvent {
+            event_type: SecurityEventType::PotentialAttack,
+            severity,
+            description: description.to_string(),
+            timestamp: Utc::now(),
+            data: HashMap::new(),
+        });
+        record
+    }
+
+    fn sink(poster: Arc<RecordingPoster>) -> SecurityEventSink {
+        SecurityEventSink::new(SecurityEventSinkConfig::default(), poster)
+            .with_default_channels(vec![channel("https://hooks.example.com/ops", serde_json::json!({}))])
+    }
+
+    #[tokio::test]
+    async fn test_critical_event_posts_once_within_dedup_window() {
+        let poster = Arc::new(RecordingPoster::default());
+        let sink = sink(poster.clone());
+
+        let attack = record(None, SecuritySeverity::Critical, "credential stuffing from 203.0.113.7");
+        sink.export(&attack).await.unwrap();
+        sink.export(&attack).await.unwrap();
+        sink.export(&record(None, SecuritySeverity::Critical, "credential stuffing from 203.0.113.7")).await.unwrap();
+        sink.export(&record(None, SecuritySeverity::Medium, "unusual login hour")).await.unwrap();
+
+        {
+            let posts = poster.posts.lock().unwrap();
+            assert_eq!(posts.len(), 1);
+            assert_eq!(posts[0].0, "https://hooks.example.com/ops");
+            assert_eq!(posts[0].1["severity"], "Critical");
+            assert_eq!(posts[0].1["operation"], "auth.login");
+        }
+
+        // A different event is not a duplicate
+        sink.export(&record(None, SecuritySeverity::High, "privilege escalation attempt")).await.unwrap();
+        assert_eq!(poster.posts.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_sink_from_policy_section() {
+        let policy: SecurityAlertPolicy = toml::from_str(r#"
+            min_severity = "Critical"
+            dedup_window_secs = 60
+            max_alerts_per_window = 5
+            rate_window_secs = 60
+            default_channels = [{ channel_type = "slack", configuration = { url = "https://hooks.example.com/soc" } }]
+        "#).unwrap();
+        let poster = Arc::new(RecordingPoster::default());
+        let sink = SecurityEventSink::from_policy(&policy, poster.clone());
+
+        sink.export(&record(None, SecuritySeverity::High, "port scan")).await.unwrap();
+        sink.export(&record(None, SecuritySeverity::Critical, "tamper detected")).await.unwrap();
+        let posts = poster.posts.lock().unwrap().clone();
+        assert_eq!(posts.len(), 1);
+        assert_eq!(posts[0].0, "https://hooks.example.com/soc");
+    }
+
+    #[tokio::test]
+    async fn test_failed_post_does_not_suppress_retry() {
+        let poster = Arc::new(RecordingPoster::default());
+        let sink = sink(poster.clone());
+        let attack = record(None, SecuritySeverity::Critical, "tamper detected");
+
+        poster.fail.store(true, std::sync::atomic::Ordering::SeqCst);
+        assert!(matches!(sink.export(&attack).await, Err(ExportError::NetworkError(_))));
+        poster.fail.store(false, std::sync::atomic::Ordering::SeqCst);
+        sink.export(&attack).await.unwrap();
+
+        assert_eq!(poster.posts.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_tenant_channels_and_rate_limit() {
+        let poster = Arc::new(RecordingPoster::default());
+        let settings = SecurityEventSinkConfig { max_alerts_per_window: 2, ..Default::default() };
+        let sink = SecurityEventSink::new(settings, poster.clone())
+            .with_default_channels(vec![channel("https://hooks.example.com/ops", serde_json::json!({}))]);
+        sink.set_tenant_channels("acme", vec![
+            channel("https://acme.example.com/pager", serde_json::json!({"min_severity": "Critical"})),
+            AlertChannel { channel_type: "email".to_string(), configuration: serde_json::json!({"to": "soc@acme.example"}) },
+        ]).await;
+
+        // High is below the tenant channel's own threshold
+        sink.export(&record(Some("acme"), SecuritySeverity::High, "port scan")).await.unwrap();
+        assert!(poster.posts.lock().unwrap().is_empty());
+
+        for attempt in 0..4 {
+            sink.export(&record(Some("acme"), SecuritySeverity::Critical, &format!("exfiltration {}", attempt))).await.unwrap();
+        }
+        let posts = poster.posts.lock().unwrap().clone();
+        assert_eq!(posts.len(), 2);
+        assert!(posts.iter().all(|(url, payload)| url == "https://acme.example.com/pager" && payload["tenant_id"] == "acme"));
+
+        // Other tenants fall back to the default channels, with their own rate limit
+        sink.export(&record(Some("globex"), SecuritySeverity::Critical, "exfiltration 0")).await.unwrap();
+        assert_eq!(poster.posts.lock().unwrap().last().unwrap().0, "https://hooks.example.com/ops");
+    }
+}
@@ -4,16 +4,21 @@
 
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
-use tokio::sync::RwLock;
-use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc, oneshot, RwLock};
+use std::collections::VecDeque;
+use std::path::PathBuf;
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
-use ring::{digest, hmac};
-use base64::{Engine as _, engine::general_purpose};
+use futures::StreamExt;
 
 use crate::observability::{ObservabilityContext, ForensicEnvelope};
-use crate::security::{SecurityLabel, ClassificationLevel};
+use crate::observability::exporters::{cef, CefFormatter};
+use crate::observability::integrity::{ChainVerification, ForensicLinker, CHAIN_GENESIS};
+use crate::observability::log_redaction::SecurityLogEvent;
+use crate::security::{SecurityEvent, SecurityLabel, ClassificationLevel};
 use crate::database::DatabaseManager;
+use crate::policy::policy_engine::SystemAuditLevel;
 
 /// Forensic Logger for automatic audit trail creation
 /// Implements the "Zero Manual Logging" approach from your observability plan
@@ -22,20 +27,23 @@ pub struct ForensicLogger {
     // Database for persistent audit storage
     db_manager: Arc<DatabaseManager>,
     
-    // In-memory buffer for high-performance logging
-    buffer: Arc<RwLock<ForensicBuffer>>,
+    // Background batch writer, so logging doesn't wait on an insert per envelope
+    writer: ForensicWriter,
     
     // Audit trail integrity verification
     integrity_verifier: IntegrityVerifier,
     
     // Enterprise features
     compliance_requirements: Arc<RwLock<ComplianceRequirements>>,
+    
+    // The policy's global audit level, which decides how durably envelopes are written
+    audit_level: Arc<RwLock<SystemAuditLevel>>,
 }
 
 /// High-performance in-memory buffer for audit events
 #[derive(Debug)]
 struct ForensicBuffer {
-    // Pending envelopes (not yet written to database)
+    // Pending envelopes (not yet written to database), in chain order
     pending_envelopes: Vec<ForensicEnvelope>,
     
     // Buffer statistics
@@ -51,11 +59,9 @@ struct ForensicBuffer {
 /// Audit trail integrity verification using cryptographic hashes
 #[derive(Debug, Clone)]
 struct IntegrityVerifier {
-    // HMAC key for audit trail integrity (should be from secure storage in production)
-    verification_key: hmac::Key,
-    
-    // Hash chain for audit trail continuity
-    last_hash: Arc<RwLock<String>>,
+    // HMAC chain and its head, shared with the database's transactional writes so
+    // every stored envelope is in one chain; held while linking so links are strictly ordered
+    linker: Arc<ForensicLinker>,
     
     // Integrity statistics
     total_verifications: Arc<RwLock<u64>>,
@@ -140,30 +146,33 @@ pub struct AuditSearchResults {
 impl ForensicLogger {
     /// Create new forensic logger with database connection
     pub async fn new(db_manager: Arc<DatabaseManager>) -> Result<Self, ForensicError> {
-        // Initialize HMAC key for integrity verification
-        let verification_key = hmac::Key::new(
-            hmac::HMAC_SHA256,
-            b"nodus_audit_integrity_key_2024" // In production, load from secure storage
-        );
-
+        // The database resumed the chain from its last stored link when it connected
         let integrity_verifier = IntegrityVerifier {
-            verification_key,
-            last_hash: Arc::new(RwLock::new("genesis".to_string())),
+            linker: db_manager.forensic_linker().clone(),
             total_verifications: Arc::new(RwLock::new(0)),
             failed_verifications: Arc::new(RwLock::new(0)),
         };
 
-        let logger = Self {
+        let writer = ForensicWriter::spawn(db_manager.clone(), ForensicWriterConfig::default());
+
+        Ok(Self {
             db_manager,
-            buffer: Arc::new(RwLock::new(ForensicBuffer::new())),
+            writer,
             integrity_verifier,
             compliance_requirements: Arc::new(RwLock::new(ComplianceRequirements::default())),
-        };
+            audit_level: Arc::new(RwLock::new(SystemAuditLevel::default())),
+        })
+    }
 
-        // Start background flush task
-        logger.start_background_flush().await;
+    /// Write envelopes at `level`, normally the policy's `global.audit_level`
+    pub fn with_audit_level(mut self, level: SystemAuditLevel) -> Self {
+        self.audit_level = Arc::new(RwLock::new(level));
+        self
+    }
 
-        Ok(logger)
+    /// Switch to `level` for envelopes logged from now on
+    pub async fn set_audit_level(&self, level: SystemAuditLevel) {
+        *self.audit_level.write().await = level;
     }
 
     /// Log operation start (called automatically by instrumentation)
@@ -235,6 +244,73 @@ impl ForensicLogger {
         self.log_envelope(envelope).await
     }
 
+    /// Log a license feature-gate decision with the full event as metadata
+    pub async fn log_license_event(&self, event: &SecurityEvent) -> Result<(), ForensicError> {
+        let event_type = match event {
+            SecurityEvent::LicenseValidated { .. } => "license_validated",
+            SecurityEvent::LicenseValidationFailed { .. } => "license_validation_failed",
+            _ => "license_event",
+        };
+        let envelope = ForensicEnvelope::new(
+            Uuid::new_v4(),
+            "security.license",
+            "system",
+            Uuid::new_v4(),
+            ClassificationLevel::Confidential,
+            event_type,
+        )
+        .with_metadata(serde_json::json!({
+            "event": event,
+            "event_category": "security",
+        }));
+
+        self.log_envelope(envelope).await
+    }
+
+    /// Store license feature-gate events until the license manager is dropped
+    pub fn forward_license_events(
+        self: Arc<Self>,
+        mut events: broadcast::Receiver<SecurityEvent>,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            loop {
+                match events.recv().await {
+                    Ok(event) => {
+                        if let Err(e) = self.log_license_event(&event).await {
+                            tracing::error!("Failed to store license gate event: {}", e);
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(missed)) => {
+                        tracing::warn!("Forensic store missed {} license gate events", missed);
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+
+    /// Log a security event emitted through `security_log!`, keeping every field unredacted
+    pub async fn log_security_log_event(&self, event: &SecurityLogEvent) -> Result<(), ForensicError> {
+        let user_id = event.fields.get("user_id").and_then(|v| v.as_str()).unwrap_or("system");
+        let envelope = ForensicEnvelope::new(
+            Uuid::new_v4(),
+            "security.log",
+            user_id,
+            Uuid::new_v4(),
+            ClassificationLevel::Confidential,
+            &event.message,
+        )
+        .with_metadata(serde_json::json!({
+            "event_category": "security",
+            "severity": event.level.to_lowercase(),
+            "target": event.target,
+            "logged_at": event.timestamp,
+            "fields": event.fields,
+        }));
+
+        self.log_envelope(envelope).await
+    }
+
     /// Log system event (startup, configuration change, etc.)
     pub async fn log_system_event(
         &self,
@@ -288,23 +364,68 @@ impl ForensicLogger {
         self.log_envelope(envelope).await
     }
 
-    /// Core envelope logging with integrity verification
-    async fn log_envelope(&self, mut envelope: ForensicEnvelope) -> Result<(), ForensicError> {
-        // Generate integrity hash for this envelope
-        envelope.audit_trail_hash = self.integrity_verifier.generate_hash(&envelope).await?;
+    /// Log a data event whose states hold a data subject's personal data
+    ///
+    /// States are sealed under the subject's key before they are chained, so `erase_subject`
+    /// can later make them unrecoverable without breaking the hash chain.
+    pub async fn log_subject_data_event(
+        &self,
+        operation: &str,
+        resource: &str,
+        subject_id: &str,
+        user_id: &str,
+        session_id: Uuid,
+        classification: ClassificationLevel,
+        before_state: Option<serde_json::Value>,
+        after_state: Option<serde_json::Value>,
+    ) -> Result<(), ForensicError> {
+        let key = self.db_manager.subject_key(subject_id).await
+            .map_err(|e| ForensicError::DatabaseError(e.to_string()))?;
+        let seal = |state: Option<serde_json::Value>| state
+            .map(|state| key.seal(&state))
+            .transpose()
+            .map_err(|e| ForensicError::SerializationError(e.to_string()));
 
-        // Add to buffer for high-performance logging
-        {
-            let mut buffer = self.buffer.write().await;
-            buffer.add_envelope(envelope.clone());
-        }
+        let envelope = ForensicEnvelope::new(
+            Uuid::new_v4(),
+            "data.event",
+            user_id,
+            session_id,
+            classification,
+            operation,
+        )
+        .with_resource(resource)
+        .with_state_change(seal(before_state)?, seal(after_state)?)
+        .with_metadata(serde_json::json!({
+            "event_category": "data",
+            "operation_type": operation,
+            "data_subject": subject_id
+        }));
 
-        // For high-priority events (security violations, errors), flush immediately
-        if self.is_high_priority_event(&envelope) {
-            self.flush_buffer_to_database().await?;
-        }
+        self.log_envelope(envelope).await
+    }
 
-        Ok(())
+    /// Log a network operation captured by the transport at its endpoint's audit level
+    pub async fn log_network_event(&self, envelope: ForensicEnvelope) -> Result<(), ForensicError> {
+        self.log_envelope(envelope).await
+    }
+
+    /// Core envelope logging with integrity verification
+    ///
+    /// `Forensic`-level envelopes return once stored; the rest once queued.
+    async fn log_envelope(&self, mut envelope: ForensicEnvelope) -> Result<(), ForensicError> {
+        let level = Self::audit_level(*self.audit_level.read().await, &envelope);
+
+        // Link into the hash chain and queue under the head lock, so the writer
+        // receives envelopes in chain order; the link is undone if it can't be queued
+        let stored = {
+            let mut link = self.integrity_verifier.linker.lock().await;
+            link.append(&mut envelope);
+            let stored = self.writer.enqueue(envelope, level).await?;
+            link.commit();
+            stored
+        };
+        ForensicWriter::durable(stored).await
     }
 
     /// Log a tenant-specific operation (convenience wrapper)
@@ -349,38 +470,79 @@ impl ForensicLogger {
         self.log_envelope(envelope).await
     }
 
-    /// Check if event requires immediate persistence
-    fn is_high_priority_event(&self, envelope: &ForensicEnvelope) -> bool {
-        envelope.event_type.contains("security") ||
-        envelope.event_type.contains("error") ||
-        envelope.classification == ClassificationLevel::Secret ||
-        envelope.classification == ClassificationLevel::NatoSecret
+    /// Audit level of an envelope: the policy's level, raised to `Forensic` for Secret-and-above envelopes
+    fn audit_level(policy_level: SystemAuditLevel, envelope: &ForensicEnvelope) -> SystemAuditLevel {
+        if envelope.classification.dominates(&ClassificationLevel::Secret) {
+            SystemAuditLevel::Forensic
+        } else {
+            policy_level
+        }
     }
 
-    /// Flush buffer to database (called periodically and on high-priority events)
-    async fn flush_buffer_to_database(&self) -> Result<(), ForensicError> {
-        let envelopes = {
-            let mut buffer = self.buffer.write().await;
-            let envelopes = buffer.drain_envelopes();
-            if envelopes.is_empty() {
-                return Ok(());
+    /// Recompute the hash chain over stored envelopes in `[from, to]`
+    ///
+    /// Buffered envelopes are flushed first so the range is complete. Stops at the first
+    /// envelope whose stored link doesn't match, and records that verification ran.
+    pub async fn verify_integrity(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        verified_by: &str,
+    ) -> Result<ChainVerification, ForensicError> {
+        self.flush().await?;
+
+        let anchor = self.db_manager.latest_forensic_link(Some(from)).await
+            .map_err(|e| ForensicError::DatabaseError(e.to_string()))?
+            .map_or_else(|| CHAIN_GENESIS.to_string(), |(hash, _)| hash);
+
+        let mut verifier = self.integrity_verifier.linker.chain().verifier(anchor);
+        let mut envelopes = self.db_manager.stream_forensic_envelopes(from, to);
+        while let Some(envelope) = envelopes.next().await {
+            let (envelope, retained_digest) = envelope.map_err(|e| ForensicError::DatabaseError(e.to_string()))?;
+            let verified = match &retained_digest {
+                Some(digest) => verifier.push_retained(&envelope, digest),
+                None => verifier.push(&envelope),
+            };
+            if !verified {
+                break;
             }
-            envelopes
-        };
-
-        // Batch insert to database for performance
-        for envelope in envelopes {
-            self.db_manager.store_forensic_envelope(&envelope).await
-                .map_err(|e| ForensicError::DatabaseError(e.to_string()))?;
         }
+        drop(envelopes);
+        let verification = verifier.finish();
 
-        // Update buffer statistics
-        {
-            let mut buffer = self.buffer.write().await;
-            buffer.last_flush_time = Utc::now();
+        *self.integrity_verifier.total_verifications.write().await += 1;
+        if !verification.is_intact() {
+            *self.integrity_verifier.failed_verifications.write().await += 1;
         }
 
-        Ok(())
+        let description = match &verification.first_break {
+            None => format!(
+                "Forensic chain intact from {} to {}: {} envelopes verified by {}",
+                from.to_rfc3339(), to.to_rfc3339(), verification.envelopes_verified, verified_by
+            ),
+            Some(broken) => format!(
+                "Forensic chain broken at envelope {} (verification {} to {} by {})",
+                broken.envelope_id, from.to_rfc3339(), to.to_rfc3339(), verified_by
+            ),
+        };
+        self.log_security_event("forensic_integrity_verification", &description, verified_by).await?;
+
+        Ok(verification)
+    }
+
+    /// Database the envelopes are stored in
+    pub(crate) fn database(&self) -> &Arc<DatabaseManager> {
+        &self.db_manager
+    }
+
+    /// Write every envelope logged so far to the database, e.g. before shutdown
+    pub async fn flush(&self) -> Result<(), ForensicError> {
+        self.writer.flush().await
+    }
+
+    /// Store the envelopes the writer dead-lettered; returns how many were stored
+    pub async fn replay_dead_letters(&self) -> Result<usize, ForensicError> {
+        self.writer.replay_dead_letters().await
     }
 
     /// Query forensic logs within a time range. Returns a list of envelopes.
@@ -402,23 +564,7 @@ impl ForensicLogger {
     ) -> Result<AuditSearchResults, ForensicError> {
         let start_time = std::time::Instant::now();
 
-        // Build database query based on criteria
-        let mut query_filters = HashMap::new();
-        
-        if let Some(start_time) = criteria.start_time {
-            query_filters.insert("start_time".to_string(), serde_json::Value::String(start_time.to_rfc3339()));
-        }
-        
-        if let Some(end_time) = criteria.end_time {
-            query_filters.insert("end_time".to_string(), serde_json::Value::String(end_time.to_rfc3339()));
-        }
-        
-        if let Some(user_id) = criteria.user_id {
-            query_filters.insert("user_id".to_string(), serde_json::Value::String(user_id));
-        }
-
-        // Execute search (simplified - in production, use proper SQL queries)
-        let envelopes = self.search_database_envelopes(query_filters).await?;
+        let envelopes = self.search_database_envelopes(&criteria).await?;
         
         // Verify integrity of returned envelopes
         let integrity_verified = self.verify_envelope_integrity(&envelopes).await?;
@@ -448,30 +594,37 @@ impl ForensicLogger {
                 Ok(json.into_bytes())
             },
             "csv" => {
-                let csv = self.export_to_csv(&search_results.envelopes)?;
+                let csv = Self::export_to_csv(&search_results.envelopes)?;
                 Ok(csv.into_bytes())
             },
             "xml" => {
-                let xml = self.export_to_xml(&search_results.envelopes)?;
+                let xml = Self::export_to_xml(&search_results.envelopes)?;
                 Ok(xml.into_bytes())
             },
+            "cef" => {
+                let cef = CefFormatter::default()
+                    .format_envelopes(&search_results.envelopes, Some(&cef::default_redaction()));
+                Ok(cef.into_bytes())
+            },
             _ => Err(ForensicError::UnsupportedFormat(format.to_string())),
         }
     }
 
     /// Get forensic logging statistics for monitoring
     pub async fn get_logging_stats(&self) -> ForensicStats {
-        let buffer = self.buffer.read().await;
+        let pending_events = self.writer.pending().await as u64;
+        let buffer = self.writer.buffer.read().await;
         let integrity_stats = self.integrity_verifier.get_stats().await;
         
         ForensicStats {
             total_events_logged: buffer.total_events_buffered,
-            pending_events: buffer.pending_envelopes.len() as u64,
+            pending_events,
             buffer_size_bytes: buffer.buffer_size_bytes,
             last_flush_time: buffer.last_flush_time,
             avg_envelope_size_bytes: buffer.avg_envelope_size_bytes,
             integrity_verifications: integrity_stats.total_verifications,
             failed_verifications: integrity_stats.failed_verifications,
+            dead_lettered_events: self.writer.dead_letters.len().await as u64,
         }
     }
 
@@ -483,44 +636,25 @@ impl ForensicLogger {
 
     // Private helper methods
 
-    /// Start background task for periodic buffer flushing
-    async fn start_background_flush(&self) {
-        let buffer = self.buffer.clone();
-        let db_manager = self.db_manager.clone();
-        
-        tokio::spawn(async move {
-            let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(30));
-            
-            loop {
-                interval.tick().await;
-                
-                // Flush buffer every 30 seconds
-                let envelopes = {
-                    let mut buffer_guard = buffer.write().await;
-                    buffer_guard.drain_envelopes()
-                };
-                
-                if !envelopes.is_empty() {
-                    for envelope in envelopes {
-                        if let Err(e) = db_manager.store_forensic_envelope(&envelope).await {
-                            tracing::error!("Failed to store forensic envelope: {}", e);
-                        }
-                    }
-                    
-                    let mut buffer_guard = buffer.write().await;
-                    buffer_guard.last_flush_time = Utc::now();
-                }
-            }
-        });
-    }
-
-    /// Search database for envelopes matching criteria
+    /// Search database for envelopes matching criteria by time range, user and classification
     async fn search_database_envelopes(
         &self,
-        _filters: HashMap<String, serde_json::Value>,
+        criteria: &AuditSearchCriteria,
     ) -> Result<Vec<ForensicEnvelope>, ForensicError> {
-        // Placeholder - in production, implement proper database queries
-        Ok(Vec::new())
+        let classifications: Vec<String> = criteria.classification_levels.iter()
+            .map(|level| level.key().to_string())
+            .collect();
+        self.db_manager
+            .search_forensic_envelopes(
+                criteria.start_time,
+                criteria.end_time,
+                criteria.user_id.as_deref(),
+                &classifications,
+                criteria.limit,
+                criteria.offset,
+            )
+            .await
+            .map_err(|e| ForensicError::DatabaseError(e.to_string()))
     }
 
     /// Verify integrity of a set of envelopes
@@ -530,7 +664,7 @@ impl ForensicLogger {
     }
 
     /// Export envelopes to CSV format
-    fn export_to_csv(&self, envelopes: &[ForensicEnvelope]) -> Result<String, ForensicError> {
+    pub(crate) fn export_to_csv(envelopes: &[ForensicEnvelope]) -> Result<String, ForensicError> {
         let mut csv = String::from("timestamp,user_id,action,resource,classification\n");
         
         for envelope in envelopes {
@@ -548,7 +682,7 @@ impl ForensicLogger {
     }
 
     /// Export envelopes to XML format
-    fn export_to_xml(&self, envelopes: &[ForensicEnvelope]) -> Result<String, ForensicError> {
+    pub(crate) fn export_to_xml(envelopes: &[ForensicEnvelope]) -> Result<String, ForensicError> {
         let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<audit_trail>\n");
         
         for envelope in envelopes {
@@ -576,6 +710,8 @@ pub struct ForensicStats {
     pub avg_envelope_size_bytes: f64,
     pub integrity_verifications: u64,
     pub failed_verifications: u64,
+    /// Envelopes the writer gave up on, awaiting `replay_dead_letters`
+    pub dead_lettered_events: u64,
 }
 
 /// Integrity verification statistics
@@ -611,36 +747,25 @@ impl ForensicBuffer {
         self.avg_envelope_size_bytes = (self.avg_envelope_size_bytes + envelope_size as f64) / 2.0;
     }
 
-    fn drain_envelopes(&mut self) -> Vec<ForensicEnvelope> {
-        let envelopes = std::mem::take(&mut self.pending_envelopes);
-        self.buffer_size_bytes = 0;
-        envelopes
+    /// Oldest envelopes not yet stored, at most `max`
+    fn pending_batch(&self, max: usize) -> Vec<ForensicEnvelope> {
+        self.pending_envelopes.iter().take(max).cloned().collect()
     }
-}
 
-impl IntegrityVerifier {
-    /// Generate integrity hash for an envelope
-    async fn generate_hash(&self, envelope: &ForensicEnvelope) -> Result<String, ForensicError> {
-        let last_hash = self.last_hash.read().await;
-        
-        // Create message for hash chain: previous_hash + envelope_data
-        let envelope_data = serde_json::to_string(envelope)
-            .map_err(|e| ForensicError::SerializationError(e.to_string()))?;
-        
-        let message = format!("{}:{}", *last_hash, envelope_data);
-        
-        // Generate HMAC
-        let signature = hmac::sign(&self.verification_key, message.as_bytes());
-        let hash = general_purpose::STANDARD.encode(signature.as_ref());
-        
-        // Update last hash for chain continuity
-        drop(last_hash);
-        let mut last_hash_mut = self.last_hash.write().await;
-        *last_hash_mut = hash.clone();
-        
-        Ok(hash)
+    /// Drop the oldest `count` envelopes once they are stored
+    fn remove_stored(&mut self, count: usize) {
+        let count = count.min(self.pending_envelopes.len());
+        self.pending_envelopes.drain(..count);
+        self.buffer_size_bytes = if self.pending_envelopes.is_empty() {
+            0
+        } else {
+            self.buffer_size_bytes.saturating_sub((self.avg_envelope_size_bytes * count as f64) as usize)
+        };
+        self.last_flush_time = Utc::now();
     }
+}
 
+impl IntegrityVerifier {
     /// Get integrity verification statistics
     async fn get_stats(&self) -> IntegrityStats {
         IntegrityStats {
@@ -666,6 +791,355 @@ impl Default for ComplianceRequirements {
     }
 }
 
+/// Durable storage for sealed envelopes; `DatabaseManager` in production
+#[async_trait::async_trait]
+pub trait ForensicStore: Send + Sync + std::fmt::Debug {
+    /// Store `envelopes`, given in chain order, all or none
+    async fn store_envelopes(&self, envelopes: &[ForensicEnvelope]) -> Result<(), ForensicError>;
+}
+
+#[async_trait::async_trait]
+impl ForensicStore for DatabaseManager {
+    async fn store_envelopes(&self, envelopes: &[ForensicEnvelope]) -> Result<(), ForensicError> {
+        self.store_forensic_envelopes(envelopes)
+            .await
+            .map_err(|e| ForensicError::DatabaseError(e.to_string()))
+    }
+}
+
+/// Batching and backpressure settings for the forensic writer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForensicWriterConfig {
+    /// Envelopes queued for the writer before logging waits for room
+    pub queue_capacity: usize,
+    /// Most envelopes per multi-row insert
+    pub max_batch_size: usize,
+    /// How long a partial batch of fire-and-forget envelopes waits for more
+    pub max_batch_delay_ms: u64,
+    /// Wait before retrying a batch the store rejected
+    pub retry_delay_ms: u64,
+    /// Attempts at a batch before it is dead-lettered
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+    /// Most dead-lettered envelopes kept; the oldest are dropped beyond it
+    #[serde(default = "default_dead_letter_capacity")]
+    pub dead_letter_capacity: usize,
+    /// JSONL file keeping dead-lettered envelopes across restarts; memory only if unset
+    #[serde(default)]
+    pub dead_letter_path: Option<PathBuf>,
+}
+
+fn default_max_attempts() -> u32 {
+    5
+}
+
+fn default_dead_letter_capacity() -> usize {
+    100_000
+}
+
+impl Default for ForensicWriterConfig {
+    fn default() -> Self {
+        Self {
+            queue_capacity: 10_000,
+            max_batch_size: 256,
+            max_batch_delay_ms: 200,
+            retry_delay_ms: 1_000,
+            max_attempts: default_max_attempts(),
+            dead_letter_capacity: default_dead_letter_capacity(),
+            dead_letter_path: None,
+        }
+    }
+}
+
+/// Resolved once the envelopes it covers are stored, or failed to be
+type DurableAck = oneshot::Sender<Result<(), String>>;
+
+enum WriterCommand {
+    Write {
+        envelope: Box<ForensicEnvelope>,
+        ack: Option<DurableAck>,
+    },
+    Flush(DurableAck),
+}
+
+/// Background task writing sealed envelopes in batches, in the order they were queued
+///
+/// A batch the store rejects is retried before anything newer is written; anyone waiting
+/// on it is told it isn't durable yet. After `max_attempts` it is dead-lettered so newer
+/// envelopes aren't held up behind it, leaving a gap in the stored chain until replayed.
+#[derive(Debug, Clone)]
+pub struct ForensicWriter {
+    commands: mpsc::Sender<WriterCommand>,
+    buffer: Arc<RwLock<ForensicBuffer>>,
+    store: Arc<dyn ForensicStore>,
+    dead_letters: Arc<ForensicDeadLetters>,
+    max_batch_size: usize,
+}
+
+impl ForensicWriter {
+    pub fn spawn(store: Arc<dyn ForensicStore>, config: ForensicWriterConfig) -> Self {
+        let (commands, receiver) = mpsc::channel(config.queue_capacity.max(1));
+        let buffer = Arc::new(RwLock::new(ForensicBuffer::new()));
+        let dead_letters = Arc::new(ForensicDeadLetters::new(config.dead_letter_capacity, config.dead_letter_path.clone()));
+        let max_batch_size = config.max_batch_size.max(1);
+        tokio::spawn(run_writer(store.clone(), buffer.clone(), dead_letters.clone(), receiver, config));
+        Self { commands, buffer, store, dead_letters, max_batch_size }
+    }
+
+    /// Envelopes dead-lettered so far, oldest first
+    pub async fn dead_letters(&self) -> Vec<ForensicEnvelope> {
+        self.dead_letters.envelopes.lock().await.iter().cloned().collect()
+    }
+
+    /// Store dead-lettered envelopes, oldest first; returns how many were stored
+    ///
+    /// Their links are intact, so once stored the chain verifies across the gap they left.
+    /// Each batch leaves the dead letters only once the store has it.
+    pub async fn replay_dead_letters(&self) -> Result<usize, ForensicError> {
+        let mut envelopes = self.dead_letters.envelopes.lock().await;
+        let mut replayed = 0;
+        let mut result = Ok(());
+        while !envelopes.is_empty() {
+            let batch: Vec<ForensicEnvelope> = envelopes.iter().take(self.max_batch_size).cloned().collect();
+            if let Err(e) = self.store.store_envelopes(&batch).await {
+                result = Err(e);
+                break;
+            }
+            envelopes.drain(..batch.len());
+            replayed += batch.len();
+        }
+        self.dead_letters.persist(&envelopes).await?;
+        result.map(|()| replayed)
+    }
+
+    /// Queue an envelope, waiting only while the queue is full
+    ///
+    /// `Forensic`-level envelopes also wait until the store has them.
+    pub async fn write(&self, envelope: ForensicEnvelope, level: SystemAuditLevel) -> Result<(), ForensicError> {
+        let stored = self.enqueue(envelope, level).await?;
+        Self::durable(stored).await
+    }
+
+    /// Wait until everything queued so far is stored
+    pub async fn flush(&self) -> Result<(), ForensicError> {
+        let (ack, stored) = oneshot::channel();
+        self.commands
+            .send(WriterCommand::Flush(ack))
+            .await
+            .map_err(|_| ForensicError::WriterClosed)?;
+        Self::durable(Some(stored)).await
+    }
+
+    /// Envelopes accepted but not yet stored
+    pub async fn pending(&self) -> usize {
+        let queued = self.commands.max_capacity() - self.commands.capacity();
+        queued + self.buffer.read().await.pending_envelopes.len()
+    }
+
+    /// Queue an envelope; for `Forensic` level, also return a receiver resolved once it is stored
+    async fn enqueue(
+        &self,
+        envelope: ForensicEnvelope,
+        level: SystemAuditLevel,
+    ) -> Result<Option<oneshot::Receiver<Result<(), String>>>, ForensicError> {
+        let (ack, stored) = match level {
+            SystemAuditLevel::Forensic => {
+                let (ack, stored) = oneshot::channel();
+                (Some(ack), Some(stored))
+            }
+            SystemAuditLevel::Basic | SystemAuditLevel::Full => (None, None),
+        };
+        self.commands
+            .send(WriterCommand::Write { envelope: Box::new(envelope), ack })
+            .await
+            .map_err(|_| ForensicError::WriterClosed)?;
+        Ok(stored)
+    }
+
+    async fn durable(stored: Option<oneshot::Receiver<Result<(), String>>>) -> Result<(), ForensicError> {
+        match stored {
+            None => Ok(()),
+            Some(stored) => match stored.await {
+                Ok(result) => result.map_err(ForensicError::DatabaseError),
+                Err(_) => Err(ForensicError::WriterClosed),
+            },
+        }
+    }
+}
+
+async fn run_writer(
+    store: Arc<dyn ForensicStore>,
+    buffer: Arc<RwLock<ForensicBuffer>>,
+    dead_letters: Arc<ForensicDeadLetters>,
+    mut commands: mpsc::Receiver<WriterCommand>,
+    config: ForensicWriterConfig,
+) {
+    if let Err(e) = dead_letters.load().await {
+        tracing::error!("Could not load forensic dead letters: {}", e);
+    }
+
+    let batch_delay = Duration::from_millis(config.max_batch_delay_ms);
+    while let Some(command) = commands.recv().await {
+        let mut waiters = Vec::new();
+        accept(command, &buffer, &mut waiters).await;
+
+        // Fill the batch, but don't hold up anyone waiting on durability
+        let deadline = tokio::time::Instant::now() + batch_delay;
+        while buffer.read().await.pending_envelopes.len() < config.max_batch_size {
+            let next = if waiters.is_empty() {
+                tokio::time::timeout_at(deadline, commands.recv()).await.ok().flatten()
+            } else {
+                commands.try_recv().ok()
+            };
+            match next {
+                Some(command) => accept(command, &buffer, &mut waiters).await,
+                None => break,
+            }
+        }
+
+        write_buffered(store.as_ref(), &buffer, &dead_letters, &config, waiters).await;
+    }
+}
+
+async fn accept(command: WriterCommand, buffer: &RwLock<ForensicBuffer>, waiters: &mut Vec<DurableAck>) {
+    match command {
+        WriterCommand::Write { envelope, ack } => {
+            buffer.write().await.add_envelope(*envelope);
+            waiters.extend(ack);
+        }
+        WriterCommand::Flush(ack) => waiters.push(ack),
+    }
+}
+
+/// Store every buffered envelope in order, dead-lettering a batch rejected `max_attempts` times
+async fn write_buffered(
+    store: &dyn ForensicStore,
+    buffer: &RwLock<ForensicBuffer>,
+    dead_letters: &ForensicDeadLetters,
+    config: &ForensicWriterConfig,
+    waiters: Vec<DurableAck>,
+) {
+    let mut waiters = Some(waiters);
+    let mut attempts = 0;
+    loop {
+        let batch = buffer.read().await.pending_batch(config.max_batch_size.max(1));
+        if batch.is_empty() {
+            break;
+        }
+        match store.store_envelopes(&batch).await {
+            Ok(()) => {
+                buffer.write().await.remove_stored(batch.len());
+                attempts = 0;
+            }
+            Err(e) => {
+                for ack in waiters.take().into_iter().flatten() {
+                    let _ = ack.send(Err(e.to_string()));
+                }
+                attempts += 1;
+                if attempts < config.max_attempts.max(1) {
+                    tracing::error!("Failed to store {} forensic envelopes, retrying: {}", batch.len(), e);
+                    tokio::time::sleep(Duration::from_millis(config.retry_delay_ms)).await;
+                    continue;
+                }
+
+                tracing::error!(
+                    "Dead-lettering {} forensic envelopes after {} failed attempts: {}",
+                    batch.len(), attempts, e
+                );
+                buffer.write().await.remove_stored(batch.len());
+                if let Err(e) = dead_letters.push(batch).await {
+                    tracing::error!("Could not persist forensic dead letters: {}", e);
+                }
+                attempts = 0;
+            }
+        }
+    }
+    for ack in waiters.into_iter().flatten() {
+        let _ = ack.send(Ok(()));
+    }
+}
+
+/// Envelopes the writer gave up on, oldest first, optionally kept in a JSONL file
+#[derive(Debug)]
+struct ForensicDeadLetters {
+    capacity: usize,
+    path: Option<PathBuf>,
+    envelopes: tokio::sync::Mutex<VecDeque<ForensicEnvelope>>,
+}
+
+impl ForensicDeadLetters {
+    fn new(capacity: usize, path: Option<PathBuf>) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            path,
+            envelopes: tokio::sync::Mutex::new(VecDeque::new()),
+        }
+    }
+
+    async fn len(&self) -> usize {
+        self.envelopes.lock().await.len()
+    }
+
+    /// Pick up envelopes dead-lettered by a previous run
+    async fn load(&self) -> Result<(), ForensicError> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+        let contents = match tokio::fs::read_to_string(path).await {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(ForensicError::DeadLetterError(e.to_string())),
+        };
+
+        let mut envelopes = self.envelopes.lock().await;
+        for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+            let envelope = serde_json::from_str(line)
+                .map_err(|e| ForensicError::SerializationError(e.to_string()))?;
+            envelopes.push_back(envelope);
+        }
+        self.trim(&mut envelopes);
+        Ok(())
+    }
+
+    async fn push(&self, batch: Vec<ForensicEnvelope>) -> Result<(), ForensicError> {
+        let mut envelopes = self.envelopes.lock().await;
+        envelopes.extend(batch);
+        self.trim(&mut envelopes);
+        self.persist(&envelopes).await
+    }
+
+    fn trim(&self, envelopes: &mut VecDeque<ForensicEnvelope>) {
+        let excess = envelopes.len().saturating_sub(self.capacity);
+        if excess > 0 {
+            envelopes.drain(..excess);
+            tracing::error!(
+                "Forensic dead letters full ({} envelopes); dropped the oldest {}",
+                self.capacity, excess
+            );
+        }
+    }
+
+    /// Rewrite the backing file atomically (temp file + rename)
+    async fn persist(&self, envelopes: &VecDeque<ForensicEnvelope>) -> Result<(), ForensicError> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        let mut data = Vec::new();
+        for envelope in envelopes {
+            serde_json::to_writer(&mut data, envelope)
+                .map_err(|e| ForensicError::SerializationError(e.to_string()))?;
+            data.push(b'\n');
+        }
+
+        let tmp = path.with_extension("tmp");
+        tokio::fs::write(&tmp, &data).await
+            .map_err(|e| ForensicError::DeadLetterError(e.to_string()))?;
+        tokio::fs::rename(&tmp, path).await
+            .map_err(|e| ForensicError::DeadLetterError(e.to_string()))
+    }
+}
+
 /// Forensic logging errors
 #[derive(Debug, thiserror::Error)]
 pub enum ForensicError {
@@ -683,6 +1157,12 @@ pub enum ForensicError {
     
     #[error("Buffer overflow - too many pending events")]
     BufferOverflow,
+
+    #[error("Forensic writer has stopped")]
+    WriterClosed,
+
+    #[error("Dead-letter storage error: {0}")]
+    DeadLetterError(String),
 }
 
 #[cfg(test)]
@@ -714,9 +1194,11 @@ mod tests {
         assert_eq!(buffer.pending_envelopes.len(), 1);
         assert_eq!(buffer.total_events_buffered, 1);
         
-        let drained = buffer.drain_envelopes();
-        assert_eq!(drained.len(), 1);
+        let batch = buffer.pending_batch(10);
+        assert_eq!(batch.len(), 1);
+        buffer.remove_stored(batch.len());
         assert_eq!(buffer.pending_envelopes.len(), 0);
+        assert_eq!(buffer.buffer_size_bytes, 0);
     }
 
     #[test]
@@ -726,4 +1208,164 @@ mod tests {
         assert!(requirements.encryption_at_rest);
         assert!(requirements.tamper_detection);
     }
+
+    /// Store that blocks while the test holds `gate` for writing, and can fail on demand
+    #[derive(Debug, Default)]
+    struct GatedStore {
+        gate: RwLock<()>,
+        failures_left: std::sync::Mutex<usize>,
+        stored: std::sync::Mutex<Vec<String>>,
+    }
+
+    #[async_trait::async_trait]
+    impl ForensicStore for GatedStore {
+        async fn store_envelopes(&self, envelopes: &[ForensicEnvelope]) -> Result<(), ForensicError> {
+            let _open = self.gate.read().await;
+            {
+                let mut failures_left = self.failures_left.lock().unwrap();
+                if *failures_left > 0 {
+                    *failures_left -= 1;
+                    return Err(ForensicError::DatabaseError("connection reset".into()));
+                }
+            }
+            self.stored.lock().unwrap().extend(envelopes.iter().map(|e| e.action.clone()));
+            Ok(())
+        }
+    }
+
+    fn envelope(action: &str) -> ForensicEnvelope {
+        ForensicEnvelope::new(
+            Uuid::new_v4(),
+            "test.event",
+            "test-user",
+            Uuid::new_v4(),
+            ClassificationLevel::Internal,
+            action,
+        )
+    }
+
+    fn fast_config() -> ForensicWriterConfig {
+        ForensicWriterConfig {
+            queue_capacity: 16,
+            max_batch_size: 2,
+            max_batch_delay_ms: 10,
+            retry_delay_ms: 10,
+            max_attempts: 3,
+            dead_letter_capacity: 16,
+            dead_letter_path: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_forensic_level_waits_until_stored() {
+        let store = Arc::new(GatedStore::default());
+        let writer = ForensicWriter::spawn(store.clone(), fast_config());
+        let closed = store.gate.write().await;
+
+        // Lower levels return as soon as the envelope is queued
+        tokio::time::timeout(Duration::from_secs(1), writer.write(envelope("basic"), SystemAuditLevel::Basic))
+            .await
+            .expect("basic write must not wait for the store")
+            .unwrap();
+        tokio::time::timeout(Duration::from_secs(1), writer.write(envelope("full"), SystemAuditLevel::Full))
+            .await
+            .expect("full write must not wait for the store")
+            .unwrap();
+        assert!(store.stored.lock().unwrap().is_empty());
+
+        let forensic = tokio::spawn({
+            let writer = writer.clone();
+            async move { writer.write(envelope("forensic"), SystemAuditLevel::Forensic).await }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!forensic.is_finished(), "forensic write returned before it was stored");
+
+        drop(closed);
+        forensic.await.unwrap().unwrap();
+        assert_eq!(*store.stored.lock().unwrap(), vec!["basic", "full", "forensic"]);
+    }
+
+    #[tokio::test]
+    async fn test_rejected_batch_is_reported_and_retried_in_order() {
+        let store = Arc::new(GatedStore::default());
+        let writer = ForensicWriter::spawn(store.clone(), fast_config());
+
+        for action in ["a", "b", "c"] {
+            writer.write(envelope(action), SystemAuditLevel::Basic).await.unwrap();
+        }
+        writer.flush().await.unwrap();
+
+        *store.failures_left.lock().unwrap() = 2;
+        let result = writer.write(envelope("d"), SystemAuditLevel::Forensic).await;
+        assert!(matches!(result, Err(ForensicError::DatabaseError(_))));
+
+        // The rejected envelope is still stored, ahead of anything logged after it
+        writer.write(envelope("e"), SystemAuditLevel::Basic).await.unwrap();
+        writer.flush().await.unwrap();
+        assert_eq!(*store.stored.lock().unwrap(), vec!["a", "b", "c", "d", "e"]);
+        assert_eq!(writer.pending().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_backpressure_only_when_queue_full() {
+        let store = Arc::new(GatedStore::default());
+        let config = ForensicWriterConfig { queue_capacity: 2, max_batch_size: 1, ..fast_config() };
+        let writer = ForensicWriter::spawn(store.clone(), config);
+        let closed = store.gate.write().await;
+
+        // The writer takes the first envelope and blocks in the store; two more fit the queue
+        writer.write(envelope("1"), SystemAuditLevel::Basic).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        writer.write(envelope("2"), SystemAuditLevel::Basic).await.unwrap();
+        writer.write(envelope("3"), SystemAuditLevel::Basic).await.unwrap();
+
+        let full = tokio::time::timeout(Duration::from_millis(50), writer.write(envelope("4"), SystemAuditLevel::Basic));
+        assert!(full.await.is_err(), "write should wait while the queue is full");
+
+        drop(closed);
+        writer.write(envelope("4"), SystemAuditLevel::Basic).await.unwrap();
+        writer.flush().await.unwrap();
+        assert_eq!(*store.stored.lock().unwrap(), vec!["1", "2", "3", "4"]);
+    }
+
+    #[tokio::test]
+    async fn test_batch_rejected_max_attempts_is_dead_lettered_and_replayed() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("forensic_dead_letters.jsonl");
+        let store = Arc::new(GatedStore::default());
+        let config = ForensicWriterConfig { dead_letter_path: Some(path.clone()), ..fast_config() };
+        let writer = ForensicWriter::spawn(store.clone(), config.clone());
+
+        *store.failures_left.lock().unwrap() = 3;
+        let result = writer.write(envelope("lost"), SystemAuditLevel::Forensic).await;
+        assert!(matches!(result, Err(ForensicError::DatabaseError(_))));
+
+        // Newer envelopes are no longer held up behind the batch
+        writer.write(envelope("next"), SystemAuditLevel::Forensic).await.unwrap();
+        assert_eq!(*store.stored.lock().unwrap(), vec!["next"]);
+        let dead: Vec<String> = writer.dead_letters().await.into_iter().map(|e| e.action).collect();
+        assert_eq!(dead, vec!["lost"]);
+
+        // Kept across restarts until replayed
+        let restarted = ForensicWriter::spawn(store.clone(), config);
+        restarted.flush().await.unwrap();
+        assert_eq!(restarted.dead_letters().await.len(), 1);
+        assert_eq!(restarted.replay_dead_letters().await.unwrap(), 1);
+        assert_eq!(*store.stored.lock().unwrap(), vec!["next", "lost"]);
+        assert!(restarted.dead_letters().await.is_empty());
+        assert!(std::fs::read_to_string(&path).unwrap().trim().is_empty());
+    }
+
+    #[test]
+    fn test_audit_level_follows_policy() {
+        let mut event = envelope("x");
+        event.event_type = "security.violation".into();
+        assert_eq!(ForensicLogger::audit_level(SystemAuditLevel::Basic, &event), SystemAuditLevel::Basic);
+        assert_eq!(ForensicLogger::audit_level(SystemAuditLevel::Full, &event), SystemAuditLevel::Full);
+        assert_eq!(ForensicLogger::audit_level(SystemAuditLevel::Forensic, &event), SystemAuditLevel::Forensic);
+
+        // Secret and above is always written durably
+        event.classification = ClassificationLevel::Secret;
+        assert_eq!(ForensicLogger::audit_level(SystemAuditLevel::Basic, &event), SystemAuditLevel::Forensic);
+    }
 }
@@ -0,0 +1,334 @@
+// src-tauri/src/observability/health.rs
+// Platform Health - Aggregates subsystem status for liveness and readiness checks
+// Component results are cached briefly so dashboards and probes can poll frequently
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+use crate::database::DatabaseManager;
+use crate::license::{LicenseManager, LicenseStatus};
+use crate::networking::SecureNetworkTransport;
+use crate::policy::policy_engine::{SystemHealth, UnifiedPolicyEngine};
+
+/// How long a component result is reused before probing again
+pub const DEFAULT_HEALTH_CACHE_TTL: Duration = Duration::from_secs(1);
+
+/// Health of a component or the whole platform, best first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, JsonSchema)]
+pub enum HealthStatus {
+    Healthy,
+    Degraded,
+    Unhealthy,
+}
+
+/// Latest probe result for one component
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ComponentHealth {
+    pub name: String,
+    pub status: HealthStatus,
+    /// An unhealthy critical component makes the platform unhealthy rather than degraded
+    pub critical: bool,
+    pub detail: Option<String>,
+    pub latency_ms: u64,
+    pub checked_at: DateTime<Utc>,
+}
+
+/// Readiness: whether dependencies are usable
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct HealthReport {
+    pub status: HealthStatus,
+    /// False while any component is unhealthy
+    pub ready: bool,
+    pub components: Vec<ComponentHealth>,
+    pub checked_at: DateTime<Utc>,
+}
+
+/// Liveness: the process is up and answering, without touching dependencies
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct LivenessReport {
+    pub alive: bool,
+    pub uptime_secs: u64,
+    pub checked_at: DateTime<Utc>,
+}
+
+/// A subsystem the health check can poll
+#[async_trait]
+pub trait HealthProbe: Send + Sync {
+    fn name(&self) -> &str;
+
+    /// Whether the platform is unusable without this component
+    fn critical(&self) -> bool {
+        false
+    }
+
+    async fn check(&self) -> (HealthStatus, Option<String>);
+}
+
+/// Aggregates registered probes into liveness and readiness reports
+pub struct HealthCheck {
+    probes: Vec<Arc<dyn HealthProbe>>,
+    cache_ttl: Duration,
+    // Held while refreshing, so concurrent callers share one round of probes
+    cache: Mutex<HashMap<String, (Instant, ComponentHealth)>>,
+    started: Instant,
+}
+
+impl HealthCheck {
+    pub fn new() -> Self {
+        Self {
+            probes: Vec::new(),
+            cache_ttl: DEFAULT_HEALTH_CACHE_TTL,
+            cache: Mutex::new(HashMap::new()),
+            started: Instant::now(),
+        }
+    }
+
+    pub fn with_probe(mut self, probe: Arc<dyn HealthProbe>) -> Self {
+        self.probes.push(probe);
+        self
+    }
+
+    pub fn with_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.cache_ttl = ttl;
+        self
+    }
+
+    pub fn liveness(&self) -> LivenessReport {
+        LivenessReport {
+            alive: true,
+            uptime_secs: self.started.elapsed().as_secs(),
+            checked_at: Utc::now(),
+        }
+    }
+
+    /// Probe every component (reusing results younger than the cache TTL) and combine them
+    pub async fn readiness(&self) -> HealthReport {
+        let mut cache = self.cache.lock().await;
+
+        let stale: Vec<&Arc<dyn HealthProbe>> = self.probes.iter()
+            .filter(|probe| {
+                cache.get(probe.name())
+                    .map_or(true, |(probed_at, _)| probed_at.elapsed() >= self.cache_ttl)
+            })
+            .collect();
+        let fresh = futures::future::join_all(stale.into_iter().map(|probe| async move {
+            let started = Instant::now();
+            let (status, detail) = probe.check().await;
+            ComponentHealth {
+                name: probe.name().to_string(),
+                status,
+                critical: probe.critical(),
+                detail,
+                latency_ms: started.elapsed().as_millis() as u64,
+                checked_at: Utc::now(),
+            }
+        }))
+        .await;
+        for component in fresh {
+            cache.insert(component.name.clone(), (Instant::now(), component));
+        }
+
+        let components: Vec<ComponentHealth> = self.probes.iter()
+            .filter_map(|probe| cache.get(probe.name()).map(|(_, component)| component.clone()))
+            .collect();
+        HealthReport {
+            status: overall_status(&components),
+            ready: components.iter().all(|component| component.status != HealthStatus::Unhealthy),
+            components,
+            checked_at: Utc::now(),
+        }
+    }
+}
+
+impl std::fmt::Debug for HealthCheck {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let probes: Vec<&str> = self.probes.iter().map(|probe| probe.name()).collect();
+        f.debug_struct("HealthCheck")
+            .field("probes", &probes)
+            .field("cache_ttl", &self.cache_ttl)
+            .finish()
+    }
+}
+
+impl Default for HealthCheck {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Unhealthy if a critical component is down, degraded if anything else is amiss
+fn overall_status(components: &[ComponentHealth]) -> HealthStatus {
+    if components.iter().any(|c| c.critical && c.status == HealthStatus::Unhealthy) {
+        HealthStatus::Unhealthy
+    } else if components.iter().any(|c| c.status != HealthStatus::Healthy) {
+        HealthStatus::Degraded
+    } else {
+        HealthStatus::Healthy
+    }
+}
+
+#[async_trait]
+impl HealthProbe for DatabaseManager {
+    fn name(&self) -> &str {
+        "database"
+    }
+
+    async fn check(&self) -> (HealthStatus, Option<String>) {
+        let health = self.health_check().await;
+        if health.healthy {
+            (HealthStatus::Healthy, None)
+        } else {
+            (HealthStatus::Unhealthy, health.error)
+        }
+    }
+}
+
+#[async_trait]
+impl HealthProbe for LicenseManager {
+    fn name(&self) -> &str {
+        "license"
+    }
+
+    fn critical(&self) -> bool {
+        true
+    }
+
+    async fn check(&self) -> (HealthStatus, Option<String>) {
+        match self.get_license_info().await {
+            // Community tier runs without a license
+            None => (HealthStatus::Healthy, Some("community tier".to_string())),
+            Some(license) if license.expires_at.map_or(false, |expires_at| expires_at < Utc::now()) => {
+                (HealthStatus::Unhealthy, Some("license expired".to_string()))
+            }
+            Some(license) if license.status != LicenseStatus::Valid => {
+                (HealthStatus::Unhealthy, Some(format!("license {:?}", license.status)))
+            }
+            Some(_) => (HealthStatus::Healthy, None),
+        }
+    }
+}
+
+#[async_trait]
+impl HealthProbe for SecureNetworkTransport {
+    fn name(&self) -> &str {
+        "network"
+    }
+
+    async fn check(&self) -> (HealthStatus, Option<String>) {
+        let open = self.open_circuit_breakers().await;
+        if open.is_empty() {
+            (HealthStatus::Healthy, None)
+        } else {
+            (HealthStatus::Degraded, Some(format!("circuit open for {}", open.join(", "))))
+        }
+    }
+}
+
+#[async_trait]
+impl HealthProbe for UnifiedPolicyEngine {
+    fn name(&self) -> &str {
+        "policy"
+    }
+
+    async fn check(&self) -> (HealthStatus, Option<String>) {
+        let status = match self.get_policy_status().await {
+            Ok(status) => status,
+            Err(e) => return (HealthStatus::Degraded, Some(e.to_string())),
+        };
+        if !status.global_enabled {
+            return (HealthStatus::Degraded, Some("policy engine globally disabled".to_string()));
+        }
+
+        let unhealthy: Vec<String> = status.system_statuses.iter()
+            .filter(|system| system.enabled && !matches!(system.health, SystemHealth::Healthy))
+            .map(|system| format!("{:?}: {:?}", system.system, system.health))
+            .collect();
+        if unhealthy.is_empty() {
+            (HealthStatus::Healthy, None)
+        } else {
+            (HealthStatus::Degraded, Some(unhealthy.join(", ")))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Probe with a fixed answer that counts how often it is polled
+    struct FixedProbe {
+        name: &'static str,
+        status: HealthStatus,
+        critical: bool,
+        calls: AtomicUsize,
+    }
+
+    impl FixedProbe {
+        fn new(name: &'static str, status: HealthStatus, critical: bool) -> Arc<Self> {
+            Arc::new(Self { name, status, critical, calls: AtomicUsize::new(0) })
+        }
+    }
+
+    #[async_trait]
+    impl HealthProbe for FixedProbe {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn critical(&self) -> bool {
+            self.critical
+        }
+
+        async fn check(&self) -> (HealthStatus, Option<String>) {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            (self.status, None)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_database_down_degrades_platform() {
+        // Nothing listens on port 1, so the database probe fails
+        let pool = sqlx::postgres::PgPoolOptions::new()
+            .acquire_timeout(Duration::from_millis(500))
+            .connect_lazy("postgresql://127.0.0.1:1/nodus")
+            .unwrap();
+        let database = Arc::new(DatabaseManager::from_pool(pool, false, Arc::new(crate::security::InMemoryKeyProvider::new())));
+        let license = FixedProbe::new("license", HealthStatus::Healthy, true);
+        let health = HealthCheck::new().with_probe(database).with_probe(license);
+
+        let report = health.readiness().await;
+        assert_eq!(report.status, HealthStatus::Degraded);
+        assert!(!report.ready);
+        let db = report.components.iter().find(|c| c.name == "database").unwrap();
+        assert_eq!(db.status, HealthStatus::Unhealthy);
+        assert!(db.detail.is_some());
+
+        // Liveness never touches dependencies
+        assert!(health.liveness().alive);
+    }
+
+    #[tokio::test]
+    async fn test_critical_failure_and_result_caching() {
+        let license = FixedProbe::new("license", HealthStatus::Unhealthy, true);
+        let network = FixedProbe::new("network", HealthStatus::Healthy, false);
+        let health = HealthCheck::new()
+            .with_probe(license.clone())
+            .with_probe(network.clone())
+            .with_cache_ttl(Duration::from_millis(200));
+
+        assert_eq!(health.readiness().await.status, HealthStatus::Unhealthy);
+        health.readiness().await;
+        assert_eq!(license.calls.load(Ordering::SeqCst), 1);
+
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        health.readiness().await;
+        assert_eq!(network.calls.load(Ordering::SeqCst), 2);
+    }
+}
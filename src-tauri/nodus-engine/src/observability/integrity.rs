@@ -0,0 +1,492 @@
+// src-tauri/src/observability/integrity.rs
+// Forensic Log Integrity - HMAC hash chain over stored envelopes and signed verification attestations
+// Each link covers a digest of every stored field of its envelope plus the previous link, so edits and deletions break the chain
+
+use base64::{Engine as _, engine::general_purpose};
+use chrono::{DateTime, SubsecRound, Utc};
+use ring::{digest, hmac};
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use uuid::Uuid;
+
+use crate::license::MachineFingerprint;
+use crate::observability::ForensicEnvelope;
+use crate::security::{ClassificationLevel, Permission};
+
+/// Link preceding the first envelope ever chained
+pub const CHAIN_GENESIS: &str = "genesis";
+
+/// HMAC key of the stored chain (in production, load from secure storage)
+pub(crate) const FORENSIC_CHAIN_KEY: &[u8] = b"nodus_audit_integrity_key_2024";
+
+/// Permission an auditor needs to run integrity verification
+pub const FORENSIC_AUDIT_PERMISSION: &str = Permission::ForensicAudit.as_str();
+
+/// Clearance an auditor needs to run integrity verification
+pub const MIN_AUDITOR_CLEARANCE: ClassificationLevel = ClassificationLevel::Secret;
+
+/// Base64 SHA-256 of every stored field of `envelope` but its link
+///
+/// Timestamps are taken at microsecond precision, which is what Postgres keeps.
+pub fn content_digest(envelope: &ForensicEnvelope) -> String {
+    let fields = serde_json::json!([
+        envelope.envelope_id,
+        envelope.operation_id,
+        envelope.event_type,
+        envelope.timestamp.timestamp_micros(),
+        envelope.user_id,
+        envelope.session_id,
+        envelope.classification.key(),
+        envelope.action,
+        envelope.resource,
+        envelope.before_state,
+        envelope.after_state,
+        envelope.metadata,
+    ]);
+    general_purpose::STANDARD.encode(digest::digest(&digest::SHA256, fields.to_string().as_bytes()).as_ref())
+}
+
+/// HMAC hash chain over forensic envelopes
+#[derive(Debug, Clone)]
+pub struct ForensicChain {
+    key: hmac::Key,
+}
+
+impl ForensicChain {
+    pub fn new(key: &[u8]) -> Self {
+        Self { key: hmac::Key::new(hmac::HMAC_SHA256, key) }
+    }
+
+    /// Link for `envelope` following `previous`, over the digest of its content
+    pub fn link(&self, previous: &str, envelope: &ForensicEnvelope) -> String {
+        self.link_digest(previous, &content_digest(envelope))
+    }
+
+    /// Link for content with digest `digest` following `previous`
+    ///
+    /// Retention keeps the digest of the rows it clears, so they still verify.
+    pub fn link_digest(&self, previous: &str, digest: &str) -> String {
+        let message = format!("{}:{}", previous, digest);
+        general_purpose::STANDARD.encode(hmac::sign(&self.key, message.as_bytes()).as_ref())
+    }
+
+    /// Start verifying envelopes that follow the link `anchor`
+    pub fn verifier(&self, anchor: impl Into<String>) -> ChainVerifier<'_> {
+        ChainVerifier {
+            chain: self,
+            previous: anchor.into(),
+            verified: 0,
+            retained: 0,
+            first_break: None,
+        }
+    }
+}
+
+/// Chain position for the next envelope: the previous link and its timestamp
+#[derive(Debug, Clone)]
+pub struct ChainHead {
+    pub hash: String,
+    pub timestamp: Option<DateTime<Utc>>,
+}
+
+impl ChainHead {
+    pub fn genesis() -> Self {
+        Self { hash: CHAIN_GENESIS.to_string(), timestamp: None }
+    }
+
+    /// Stamp `envelope` strictly after the head and link it in
+    ///
+    /// Stored rows are read back in timestamp order, so chain order and timestamp order must agree.
+    pub fn append(&mut self, chain: &ForensicChain, envelope: &mut ForensicEnvelope) {
+        let mut timestamp = Utc::now().trunc_subsecs(6);
+        if let Some(last) = self.timestamp {
+            if timestamp <= last {
+                timestamp = last + chrono::Duration::microseconds(1);
+            }
+        }
+        envelope.timestamp = timestamp;
+        envelope.audit_trail_hash = chain.link(&self.hash, envelope);
+
+        self.hash = envelope.audit_trail_hash.clone();
+        self.timestamp = Some(timestamp);
+    }
+}
+
+/// The chain and its head, shared by everything that stores forensic rows
+///
+/// The logger and the transactional writes of `DatabaseManager` link through the same head,
+/// so every stored envelope is part of one chain.
+#[derive(Debug)]
+pub struct ForensicLinker {
+    chain: ForensicChain,
+    head: tokio::sync::Mutex<ChainHead>,
+}
+
+impl ForensicLinker {
+    /// Linker starting from genesis
+    pub fn new(chain: ForensicChain) -> Self {
+        Self { chain, head: tokio::sync::Mutex::new(ChainHead::genesis()) }
+    }
+
+    pub fn chain(&self) -> &ForensicChain {
+        &self.chain
+    }
+
+    /// Continue from `head`, e.g. the last stored link after a restart
+    pub async fn resume(&self, head: ChainHead) {
+        *self.head.lock().await = head;
+    }
+
+    /// Take the head; links appended through the guard are kept only once it is committed
+    pub async fn lock(&self) -> ChainLink<'_> {
+        let head = self.head.lock().await;
+        let start = head.clone();
+        ChainLink { chain: &self.chain, head, start, committed: false }
+    }
+}
+
+/// Exclusive hold on the chain head
+///
+/// Dropped without `commit` (the write it linked for failed), the head goes back to where
+/// it was, so the next envelope doesn't link to one that was never stored.
+#[derive(Debug)]
+pub struct ChainLink<'a> {
+    chain: &'a ForensicChain,
+    head: tokio::sync::MutexGuard<'a, ChainHead>,
+    start: ChainHead,
+    committed: bool,
+}
+
+impl ChainLink<'_> {
+    /// Stamp `envelope` and link it in after the envelopes appended so far
+    pub fn append(&mut self, envelope: &mut ForensicEnvelope) {
+        self.head.append(self.chain, envelope);
+    }
+
+    /// Keep the appended links; call once the envelopes are stored (or queued in order)
+    pub fn commit(mut self) {
+        self.committed = true;
+    }
+}
+
+impl Drop for ChainLink<'_> {
+    fn drop(&mut self) {
+        if !self.committed {
+            *self.head = self.start.clone();
+        }
+    }
+}
+
+/// Envelope where the recomputed chain stopped matching the stored one
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct ChainBreak {
+    pub envelope_id: Uuid,
+    pub timestamp: DateTime<Utc>,
+    /// Zero-based position among the envelopes in the range
+    pub position: u64,
+}
+
+/// Outcome of recomputing the chain over a range
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ChainVerification {
+    pub envelopes_verified: u64,
+    /// Of those, rows cleared by retention, verified by the digest kept of their original content
+    #[serde(default)]
+    pub envelopes_retained: u64,
+    pub first_break: Option<ChainBreak>,
+    /// Last link that verified
+    pub head_hash: String,
+}
+
+impl ChainVerification {
+    pub fn is_intact(&self) -> bool {
+        self.first_break.is_none()
+    }
+}
+
+/// Recomputes the chain one stored envelope at a time, in timestamp order
+#[derive(Debug)]
+pub struct ChainVerifier<'a> {
+    chain: &'a ForensicChain,
+    previous: String,
+    verified: u64,
+    retained: u64,
+    first_break: Option<ChainBreak>,
+}
+
+impl ChainVerifier<'_> {
+    /// Check the next envelope; returns false once the chain is broken
+    pub fn push(&mut self, envelope: &ForensicEnvelope) -> bool {
+        self.push_digest(envelope, &content_digest(envelope))
+    }
+
+    /// Check the next envelope, a row retention cleared, by the digest kept of its original content
+    pub fn push_retained(&mut self, envelope: &ForensicEnvelope, digest: &str) -> bool {
+        let verified = self.push_digest(envelope, digest);
+        if verified {
+            self.retained += 1;
+        }
+        verified
+    }
+
+    fn push_digest(&mut self, envelope: &ForensicEnvelope, digest: &str) -> bool {
+        if self.first_break.is_some() {
+            return false;
+        }
+        let expected = self.chain.link_digest(&self.previous, digest);
+        if !constant_time_eq::constant_time_eq(expected.as_bytes(), envelope.audit_trail_hash.as_bytes()) {
+            self.first_break = Some(ChainBreak {
+                envelope_id: envelope.envelope_id,
+                timestamp: envelope.timestamp,
+                position: self.verified,
+            });
+            return false;
+        }
+
+        self.previous = expected;
+        self.verified += 1;
+        true
+    }
+
+    pub fn finish(self) -> ChainVerification {
+        ChainVerification {
+            envelopes_verified: self.verified,
+            envelopes_retained: self.retained,
+            first_break: self.first_break,
+            head_hash: self.previous,
+        }
+    }
+}
+
+/// Signed statement of what an integrity verification found
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct IntegrityAttestation {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub verification: ChainVerification,
+    pub verified_by: String,
+    pub verified_at: DateTime<Utc>,
+    /// Base64 Ed25519 device key of the machine that ran the verification
+    pub device_public_key: String,
+    /// Base64 Ed25519 signature over `signed_message`
+    pub signature: String,
+}
+
+impl IntegrityAttestation {
+    pub fn sign(
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        verification: ChainVerification,
+        verified_by: &str,
+        machine: &MachineFingerprint,
+    ) -> Self {
+        let mut attestation = Self {
+            from,
+            to,
+            verification,
+            verified_by: verified_by.to_string(),
+            verified_at: Utc::now(),
+            device_public_key: machine.public_key(),
+            signature: String::new(),
+        };
+        attestation.signature = machine.sign(&attestation.signed_message());
+        attestation
+    }
+
+    /// Bytes covered by the signature: every field except the signature itself
+    pub fn signed_message(&self) -> Vec<u8> {
+        serde_json::json!([
+            self.from,
+            self.to,
+            self.verification,
+            self.verified_by,
+            self.verified_at,
+            self.device_public_key,
+        ])
+        .to_string()
+        .into_bytes()
+    }
+
+    /// Check the signature against the embedded device key
+    pub fn verify_signature(&self) -> bool {
+        let (Ok(public_key), Ok(signature)) = (
+            general_purpose::STANDARD.decode(&self.device_public_key),
+            general_purpose::STANDARD.decode(&self.signature),
+        ) else {
+            return false;
+        };
+        ring::signature::UnparsedPublicKey::new(&ring::signature::ED25519, public_key)
+            .verify(&self.signed_message(), &signature)
+            .is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chained_log(chain: &ForensicChain, count: usize) -> Vec<ForensicEnvelope> {
+        let mut head = ChainHead::genesis();
+        (0..count)
+            .map(|i| {
+                let mut envelope = ForensicEnvelope::new(
+                    Uuid::new_v4(),
+                    "data.operation",
+                    "analyst",
+                    Uuid::new_v4(),
+                    ClassificationLevel::Internal,
+                    "update",
+                )
+                .with_metadata(serde_json::json!({"sequence": i, "amount": 12.5}));
+                head.append(chain, &mut envelope);
+                envelope
+            })
+            .collect()
+    }
+
+    fn verify(chain: &ForensicChain, envelopes: &[ForensicEnvelope]) -> ChainVerification {
+        let mut verifier = chain.verifier(CHAIN_GENESIS);
+        for envelope in envelopes {
+            if !verifier.push(envelope) {
+                break;
+            }
+        }
+        verifier.finish()
+    }
+
+    #[test]
+    fn test_mutated_row_reported_as_break_point() {
+        let chain = ForensicChain::new(b"test-integrity-key");
+        let mut log = chained_log(&chain, 5);
+
+        // Survives a trip through storage
+        let stored: Vec<ForensicEnvelope> = log.iter()
+            .map(|e| serde_json::from_str(&serde_json::to_string(e).unwrap()).unwrap())
+            .collect();
+        let intact = verify(&chain, &stored);
+        assert!(intact.is_intact());
+        assert_eq!(intact.envelopes_verified, 5);
+        assert_eq!(intact.head_hash, log[4].audit_trail_hash);
+        assert!(log.windows(2).all(|pair| pair[0].timestamp < pair[1].timestamp));
+
+        log[2].metadata = serde_json::json!({"sequence": 2, "amount": 1250.0});
+        let tampered = verify(&chain, &log);
+        let broken = tampered.first_break.unwrap();
+        assert_eq!(broken.envelope_id, log[2].envelope_id);
+        assert_eq!(broken.position, 2);
+        assert_eq!(tampered.envelopes_verified, 2);
+    }
+
+    #[test]
+    fn test_deleted_row_breaks_at_successor() {
+        let chain = ForensicChain::new(b"test-integrity-key");
+        let mut log = chained_log(&chain, 4);
+        log.remove(1);
+
+        let result = verify(&chain, &log);
+        assert_eq!(result.first_break.unwrap().envelope_id, log[1].envelope_id);
+
+        // A different key can't produce a matching chain
+        let other = ForensicChain::new(b"someone-else");
+        assert_eq!(verify(&other, &chained_log(&chain, 2)).first_break.unwrap().position, 0);
+    }
+
+    #[test]
+    fn test_rows_written_outside_the_chain_are_breaks() {
+        let chain = ForensicChain::new(b"test-integrity-key");
+        let mut log = chained_log(&chain, 3);
+
+        // A row inserted with `ForensicEnvelope::new`'s placeholder link is not skipped
+        let mut stray = ForensicEnvelope::new(
+            Uuid::new_v4(),
+            "legal_hold.placed",
+            "admin",
+            Uuid::new_v4(),
+            ClassificationLevel::Confidential,
+            "place_legal_hold",
+        );
+        stray.timestamp = log[1].timestamp + chrono::Duration::microseconds(1);
+        log.insert(2, stray.clone());
+
+        let result = verify(&chain, &log);
+        let broken = result.first_break.unwrap();
+        assert_eq!((broken.envelope_id, broken.position), (stray.envelope_id, 2));
+    }
+
+    #[test]
+    fn test_rows_cleared_by_retention_verify_by_kept_digest() {
+        let chain = ForensicChain::new(b"test-integrity-key");
+        let mut log = chained_log(&chain, 3);
+
+        // Retention keeps the original digest, then clears the row's content
+        let digest = content_digest(&log[1]);
+        log[1].metadata = serde_json::json!({});
+        log[1].user_id = "[ANONYMIZED]".to_string();
+
+        let mut verifier = chain.verifier(CHAIN_GENESIS);
+        assert!(verifier.push(&log[0]));
+        assert!(verifier.push_retained(&log[1], &digest));
+        assert!(verifier.push(&log[2]));
+        let result = verifier.finish();
+        assert!(result.is_intact());
+        assert_eq!((result.envelopes_verified, result.envelopes_retained), (3, 1));
+
+        // Without the digest the cleared row is a break, and a made-up digest doesn't help
+        assert_eq!(verify(&chain, &log).first_break.unwrap().position, 1);
+        let mut verifier = chain.verifier(CHAIN_GENESIS);
+        verifier.push(&log[0]);
+        assert!(!verifier.push_retained(&log[1], &content_digest(&log[1])));
+    }
+
+    #[tokio::test]
+    async fn test_uncommitted_links_roll_back_the_head() {
+        let linker = ForensicLinker::new(ForensicChain::new(b"test-integrity-key"));
+        let envelope = || ForensicEnvelope::new(
+            Uuid::new_v4(),
+            "api_key.issued",
+            "admin",
+            Uuid::new_v4(),
+            ClassificationLevel::Internal,
+            "issue",
+        );
+
+        let mut stored = envelope();
+        let mut link = linker.lock().await;
+        link.append(&mut stored);
+        link.commit();
+
+        // The transaction this was linked for rolled back
+        {
+            let mut link = linker.lock().await;
+            link.append(&mut envelope());
+        }
+
+        let mut next = envelope();
+        let mut link = linker.lock().await;
+        link.append(&mut next);
+        link.commit();
+
+        let result = verify(linker.chain(), &[stored, next]);
+        assert!(result.is_intact());
+        assert_eq!(result.envelopes_verified, 2);
+    }
+
+    #[test]
+    fn test_attestation_signature() {
+        let chain = ForensicChain::new(b"test-integrity-key");
+        let machine = MachineFingerprint::derive(&[1u8; 32], &["machine-a", "board-a"]).unwrap();
+        let verification = verify(&chain, &chained_log(&chain, 3));
+
+        let mut attestation = IntegrityAttestation::sign(
+            Utc::now() - chrono::Duration::hours(1),
+            Utc::now(),
+            verification,
+            "auditor",
+            &machine,
+        );
+        assert!(attestation.verify_signature());
+
+        attestation.verification.envelopes_verified += 1;
+        assert!(!attestation.verify_signature());
+    }
+}
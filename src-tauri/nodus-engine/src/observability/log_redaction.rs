@@ -0,0 +1,351 @@
+// src-tauri/src/observability/log_redaction.rs
+// Classification-aware log redaction - masks or hashes registered sensitive fields before they reach log output
+// Security events keep full detail on a separate path into the forensic store
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io::Write;
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use ring::hmac;
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::Layer;
+
+use crate::observability::ForensicLogger;
+
+/// Tracing target for security-relevant events; see [`security_log!`](crate::security_log)
+pub const SECURITY_LOG_TARGET: &str = "nodus::security";
+
+/// Replacement written for masked fields
+pub const MASKED_VALUE: &str = "[REDACTED]";
+
+/// How a sensitive field appears in non-forensic logs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RedactionRule {
+    /// Replaced with [`MASKED_VALUE`]
+    Mask,
+    /// Replaced with a keyed hash, so entries for the same value can still be correlated
+    Hash,
+}
+
+/// Registry of field names whose values must not appear in log output
+#[derive(Clone)]
+pub struct SensitiveFieldRegistry {
+    rules: HashMap<String, RedactionRule>,
+    hash_key: hmac::Key,
+}
+
+impl SensitiveFieldRegistry {
+    /// Empty registry; hashes are keyed per process unless a key is set with [`with_hash_key`](Self::with_hash_key)
+    pub fn new() -> Self {
+        let key: [u8; 32] = rand::random();
+        Self {
+            rules: HashMap::new(),
+            hash_key: hmac::Key::new(hmac::HMAC_SHA256, &key),
+        }
+    }
+
+    /// Register `field` under `rule`, replacing any earlier rule for it
+    pub fn with_field(mut self, field: &str, rule: RedactionRule) -> Self {
+        self.rules.insert(field.to_string(), rule);
+        self
+    }
+
+    /// Key hashed fields with `key`, so hashes stay comparable across restarts and hosts
+    pub fn with_hash_key(mut self, key: &[u8]) -> Self {
+        self.hash_key = hmac::Key::new(hmac::HMAC_SHA256, key);
+        self
+    }
+
+    pub fn rule_for(&self, field: &str) -> Option<RedactionRule> {
+        self.rules.get(field).copied()
+    }
+
+    /// Value of `field` as it may appear in log output
+    pub fn redact(&self, field: &str, value: &str) -> Option<String> {
+        self.rule_for(field).map(|rule| match rule {
+            RedactionRule::Mask => MASKED_VALUE.to_string(),
+            RedactionRule::Hash => {
+                let tag = hmac::sign(&self.hash_key, value.as_bytes());
+                format!("hmac:{}", hex::encode(&tag.as_ref()[..8]))
+            }
+        })
+    }
+}
+
+impl Default for SensitiveFieldRegistry {
+    /// Identities are hashed; clearance and compartment detail is masked
+    fn default() -> Self {
+        Self::new()
+            .with_field("user_id", RedactionRule::Hash)
+            .with_field("tenant_id", RedactionRule::Hash)
+            .with_field("subject_id", RedactionRule::Hash)
+            .with_field("clearance", RedactionRule::Mask)
+            .with_field("compartments", RedactionRule::Mask)
+            .with_field("classification_detail", RedactionRule::Mask)
+            .with_field("source_ip", RedactionRule::Mask)
+    }
+}
+
+impl fmt::Debug for SensitiveFieldRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SensitiveFieldRegistry").field("rules", &self.rules).finish_non_exhaustive()
+    }
+}
+
+/// Tracing layer writing one JSON line per event with sensitive fields redacted
+///
+/// Values of sensitive fields are also replaced where they were interpolated into the message.
+pub struct RedactingLayer<W> {
+    registry: Arc<SensitiveFieldRegistry>,
+    make_writer: W,
+}
+
+impl<W> RedactingLayer<W>
+where
+    W: for<'a> MakeWriter<'a> + 'static,
+{
+    pub fn new(registry: SensitiveFieldRegistry, make_writer: W) -> Self {
+        Self { registry: Arc::new(registry), make_writer }
+    }
+}
+
+impl<S, W> Layer<S> for RedactingLayer<W>
+where
+    S: Subscriber,
+    W: for<'a> MakeWriter<'a> + 'static,
+{
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let mut fields = FieldCollector::default();
+        event.record(&mut fields);
+
+        let mut message = fields.message.unwrap_or_default();
+        let mut redacted = serde_json::Map::new();
+        for (name, value) in fields.values {
+            let raw = json_text(&value);
+            match self.registry.redact(&name, &raw) {
+                Some(replacement) => {
+                    if !raw.is_empty() {
+                        message = message.replace(&raw, &replacement);
+                    }
+                    redacted.insert(name, serde_json::Value::String(replacement));
+                }
+                None => {
+                    redacted.insert(name, value);
+                }
+            }
+        }
+
+        let metadata = event.metadata();
+        let line = serde_json::json!({
+            "timestamp": Utc::now(),
+            "level": metadata.level().as_str(),
+            "target": metadata.target(),
+            "message": message,
+            "fields": redacted,
+        });
+        let mut writer = self.make_writer.make_writer_for(metadata);
+        let _ = writeln!(writer, "{}", line);
+    }
+}
+
+/// Security log event with every field unredacted, bound for the forensic store
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SecurityLogEvent {
+    pub timestamp: DateTime<Utc>,
+    pub level: String,
+    pub target: String,
+    pub message: String,
+    pub fields: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Tracing layer passing events logged under [`SECURITY_LOG_TARGET`] on in full
+pub struct ForensicLogLayer {
+    events: mpsc::UnboundedSender<SecurityLogEvent>,
+}
+
+impl ForensicLogLayer {
+    /// Layer and the receiving end of its events, to hand to [`forward_to_forensic_store`]
+    pub fn channel() -> (Self, mpsc::UnboundedReceiver<SecurityLogEvent>) {
+        let (events, receiver) = mpsc::unbounded_channel();
+        (Self { events }, receiver)
+    }
+}
+
+impl<S: Subscriber> Layer<S> for ForensicLogLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        if metadata.target() != SECURITY_LOG_TARGET {
+            return;
+        }
+
+        let mut fields = FieldCollector::default();
+        event.record(&mut fields);
+        let _ = self.events.send(SecurityLogEvent {
+            timestamp: Utc::now(),
+            level: metadata.level().as_str().to_string(),
+            target: metadata.target().to_string(),
+            message: fields.message.unwrap_or_default(),
+            fields: fields.values.into_iter().collect(),
+        });
+    }
+}
+
+/// Store security log events in the forensic log until the layer's sender is dropped
+pub fn forward_to_forensic_store(
+    forensic_logger: Arc<ForensicLogger>,
+    mut events: mpsc::UnboundedReceiver<SecurityLogEvent>,
+) -> tokio::task::JoinHandle<()> {
+    tokio::spawn(async move {
+        while let Some(event) = events.recv().await {
+            if let Err(e) = forensic_logger.log_security_log_event(&event).await {
+                // Logged outside the security target, so this cannot feed back into the channel
+                tracing::error!("Failed to store security log event: {}", e);
+            }
+        }
+    })
+}
+
+/// Field values of one event in recording order, with the message kept apart
+#[derive(Default)]
+struct FieldCollector {
+    message: Option<String>,
+    values: Vec<(String, serde_json::Value)>,
+}
+
+impl Visit for FieldCollector {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.record(field, serde_json::Value::String(value.to_string()));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.record(field, value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.record(field, value.into());
+    }
+
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.record(field, value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.record(field, value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.record(field, serde_json::Value::String(format!("{:?}", value)));
+    }
+}
+
+impl FieldCollector {
+    fn record(&mut self, field: &Field, value: serde_json::Value) {
+        if field.name() == "message" {
+            self.message = Some(json_text(&value));
+        } else {
+            self.values.push((field.name().to_string(), value));
+        }
+    }
+}
+
+fn json_text(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Log a security-relevant event: redacted in regular logs, stored in full in the forensic log
+///
+/// Pass identities and clearance detail as fields (`user_id = %context.user_id`) rather than
+/// interpolating them, so they are matched against the [`SensitiveFieldRegistry`].
+#[macro_export]
+macro_rules! security_log {
+    (error, $($arg:tt)+) => {
+        ::tracing::error!(target: $crate::observability::log_redaction::SECURITY_LOG_TARGET, $($arg)+)
+    };
+    (warn, $($arg:tt)+) => {
+        ::tracing::warn!(target: $crate::observability::log_redaction::SECURITY_LOG_TARGET, $($arg)+)
+    };
+    (info, $($arg:tt)+) => {
+        ::tracing::info!(target: $crate::observability::log_redaction::SECURITY_LOG_TARGET, $($arg)+)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[derive(Clone, Default)]
+    struct CapturedOutput(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for CapturedOutput {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for CapturedOutput {
+        type Writer = Self;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    #[test]
+    fn test_sensitive_fields_masked_in_logs_but_kept_for_forensics() {
+        let output = CapturedOutput::default();
+        let registry = SensitiveFieldRegistry::default().with_hash_key(b"test-key");
+        let expected_hash = registry.redact("user_id", "alice").unwrap();
+        let (forensic_layer, mut forensic_events) = ForensicLogLayer::channel();
+        let subscriber = tracing_subscriber::registry()
+            .with(RedactingLayer::new(registry, output.clone()))
+            .with(forensic_layer);
+
+        tracing::subscriber::with_default(subscriber, || {
+            security_log!(
+                warn,
+                user_id = "alice",
+                clearance = "Secret",
+                entity_count = 3,
+                "Search by alice dropped 3 entities"
+            );
+            tracing::info!(user_id = "alice", "Routine event");
+        });
+
+        let logged = String::from_utf8(output.0.lock().unwrap().clone()).unwrap();
+        assert!(!logged.contains("alice"));
+        assert!(!logged.contains("Secret"));
+        let lines: Vec<serde_json::Value> = logged.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["target"], SECURITY_LOG_TARGET);
+        assert_eq!(lines[0]["message"], format!("Search by {} dropped 3 entities", expected_hash));
+        assert_eq!(lines[0]["fields"]["user_id"], expected_hash.as_str());
+        assert_eq!(lines[0]["fields"]["clearance"], MASKED_VALUE);
+        assert_eq!(lines[0]["fields"]["entity_count"], 3);
+        assert_eq!(lines[1]["fields"]["user_id"], expected_hash.as_str());
+
+        // Only the security event reaches the forensic path, in full
+        let event = forensic_events.try_recv().unwrap();
+        assert_eq!(event.message, "Search by alice dropped 3 entities");
+        assert_eq!(event.fields["user_id"], "alice");
+        assert_eq!(event.fields["clearance"], "Secret");
+        assert!(forensic_events.try_recv().is_err());
+    }
+}
@@ -5,13 +5,14 @@
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 use chrono::{DateTime, Utc, Duration};
 use dashmap::DashMap;
 use std::sync::atomic::{AtomicU64, AtomicF64, Ordering};
 
 use crate::observability::{ObservabilityContext, MetricsDataPoint};
+use crate::observability::rollup::{RollupSeries, RollupStats, RollupWindow, PERCENTILE_BOUNDS};
 use crate::security::ClassificationLevel;
 
 /// High-performance metrics registry with automatic collection
@@ -39,6 +40,18 @@ pub struct MetricsRegistry {
     
     // Performance tracking
     collection_stats: Arc<RwLock<CollectionStats>>,
+    
+    // Application-registered metrics and the label sets of their series; registered
+    // counters keep fractional totals
+    custom_metrics: Arc<DashMap<String, CustomMetric>>,
+    custom_counters: Arc<DashMap<String, AtomicF64>>,
+    series_labels: Arc<DashMap<String, HashMap<String, String>>>,
+    
+    // 1m/5m/1h windows per metric; unaffected by snapshot resets
+    rollups: Arc<DashMap<String, RollupSeries>>,
+    
+    // Serializes snapshot/reset so concurrent scrapes can't interleave
+    snapshot_gate: Arc<tokio::sync::Mutex<()>>,
 }
 
 /// High-performance histogram for latency tracking
@@ -66,6 +79,29 @@ struct Timer {
     max_duration_ms: AtomicF64,
 }
 
+/// Counter of every instrumented operation, across components
+pub const OPERATIONS_METRIC: &str = "operations.count";
+/// Latency (ms) of every instrumented operation, across components
+pub const OPERATION_LATENCY_METRIC: &str = "operations.latency";
+
+/// Upper bound on distinct label sets per custom metric, to stop series explosion
+pub const MAX_SERIES_PER_METRIC: usize = 100;
+
+/// Custom metric registered by application code
+#[derive(Debug)]
+struct CustomMetric {
+    kind: CustomMetricKind,
+    series: HashSet<String>,
+}
+
+/// Kind of a custom metric; histograms carry their bucket bounds
+#[derive(Debug, Clone, PartialEq)]
+enum CustomMetricKind {
+    Counter,
+    Gauge,
+    Histogram(Vec<f64>),
+}
+
 /// Cached metric for ultra-fast access
 #[derive(Debug)]
 struct CachedMetric {
@@ -174,10 +210,135 @@ struct CollectionStats {
 pub struct MetricsSnapshot {
     pub timestamp: DateTime<Utc>,
     pub counters: HashMap<String, u64>,
+    /// Series of counters registered with `register_counter`, which may hold fractional totals
+    #[serde(default)]
+    pub custom_counters: HashMap<String, f64>,
     pub gauges: HashMap<String, f64>,
     pub histograms: HashMap<String, HistogramSnapshot>,
     pub timers: HashMap<String, TimerSnapshot>,
     pub collection_stats: CollectionStats,
+    /// Label sets of labeled series, keyed by series name (`metric{key="value"}`)
+    #[serde(default)]
+    pub labels: HashMap<String, HashMap<String, String>>,
+}
+
+impl MetricsSnapshot {
+    /// Flatten into data points: counters and gauges by name, histograms and timers
+    /// as `<name>.count` / `<name>.sum` style series
+    pub fn data_points(&self) -> Vec<MetricsDataPoint> {
+        let point = |series: String, value: f64| {
+            let labels = self.labels.get(&series).cloned().unwrap_or_default();
+            MetricsDataPoint {
+                metric_id: Uuid::new_v4(),
+                name: metric_name(&series).to_string(),
+                value,
+                timestamp: self.timestamp,
+                labels,
+                operation_id: None,
+            }
+        };
+
+        let mut points = Vec::new();
+        points.extend(self.counters.iter().map(|(name, value)| point(name.clone(), *value as f64)));
+        points.extend(self.custom_counters.iter().map(|(name, value)| point(name.clone(), *value)));
+        points.extend(self.gauges.iter().map(|(name, value)| point(name.clone(), *value)));
+        for (series, histogram) in &self.histograms {
+            let mut count = point(series.clone(), histogram.count as f64);
+            count.name.push_str(".count");
+            let mut sum = point(series.clone(), histogram.sum);
+            sum.name.push_str(".sum");
+            points.extend([count, sum]);
+        }
+        for (name, timer) in &self.timers {
+            points.push(point(format!("{}.count", name), timer.count as f64));
+            points.push(point(format!("{}.total_ms", name), timer.total_duration_ms));
+        }
+        points
+    }
+
+    /// Keep only series labeled `tenant=<tenant_id>`; unlabeled system metrics are dropped
+    pub fn scoped_to_tenant(&self, tenant_id: &str) -> MetricsSnapshot {
+        let owned = |series: &String| self.labels.get(series)
+            .and_then(|labels| labels.get("tenant"))
+            .map_or(false, |tenant| tenant == tenant_id);
+
+        MetricsSnapshot {
+            timestamp: self.timestamp,
+            counters: self.counters.iter().filter(|(k, _)| owned(k)).map(|(k, v)| (k.clone(), *v)).collect(),
+            custom_counters: self.custom_counters.iter().filter(|(k, _)| owned(k)).map(|(k, v)| (k.clone(), *v)).collect(),
+            gauges: self.gauges.iter().filter(|(k, _)| owned(k)).map(|(k, v)| (k.clone(), *v)).collect(),
+            histograms: self.histograms.iter().filter(|(k, _)| owned(k)).map(|(k, v)| (k.clone(), v.clone())).collect(),
+            timers: self.timers.iter().filter(|(k, _)| owned(k)).map(|(k, v)| (k.clone(), v.clone())).collect(),
+            collection_stats: self.collection_stats.clone(),
+            labels: self.labels.iter().filter(|(k, _)| owned(k)).map(|(k, v)| (k.clone(), v.clone())).collect(),
+        }
+    }
+
+    /// Changes since `previous`: counter and histogram increments, gauges that moved,
+    /// and timers whose operation count changed
+    ///
+    /// A counter that went backwards was reset in between, so its full value is the delta.
+    pub fn delta_since(&self, previous: &MetricsSnapshot) -> MetricsSnapshot {
+        let counters = self.counters.iter()
+            .filter_map(|(name, value)| {
+                let delta = match previous.counters.get(name) {
+                    Some(before) if before <= value => value - before,
+                    _ => *value,
+                };
+                (delta > 0).then(|| (name.clone(), delta))
+            })
+            .collect();
+
+        let custom_counters = self.custom_counters.iter()
+            .filter_map(|(name, value)| {
+                let delta = match previous.custom_counters.get(name) {
+                    Some(before) if before <= value => value - before,
+                    _ => *value,
+                };
+                (delta > 0.0).then(|| (name.clone(), delta))
+            })
+            .collect();
+
+        let gauges = self.gauges.iter()
+            .filter(|(name, value)| previous.gauges.get(*name) != Some(*value))
+            .map(|(name, value)| (name.clone(), *value))
+            .collect();
+
+        let histograms = self.histograms.iter()
+            .filter_map(|(name, current)| {
+                let delta = match previous.histograms.get(name) {
+                    Some(before) if before.count <= current.count => {
+                        if before.count == current.count {
+                            return None;
+                        }
+                        let buckets: Vec<(f64, u64)> = current.buckets.iter()
+                            .zip(&before.buckets)
+                            .map(|((bound, now), (_, then))| (*bound, now.saturating_sub(*then)))
+                            .collect();
+                        Histogram::build_snapshot(current.count - before.count, current.sum - before.sum, buckets)
+                    }
+                    _ => current.clone(),
+                };
+                Some((name.clone(), delta))
+            })
+            .collect();
+
+        let timers = self.timers.iter()
+            .filter(|(name, timer)| previous.timers.get(*name).map(|before| before.count) != Some(timer.count))
+            .map(|(name, timer)| (name.clone(), timer.clone()))
+            .collect();
+
+        MetricsSnapshot {
+            timestamp: self.timestamp,
+            counters,
+            custom_counters,
+            gauges,
+            histograms,
+            timers,
+            collection_stats: self.collection_stats.clone(),
+            labels: self.labels.clone(),
+        }
+    }
 }
 
 /// Histogram snapshot for percentile data
@@ -238,6 +399,11 @@ impl MetricsRegistry {
             export_targets: Arc::new(RwLock::new(Vec::new())),
             real_time_buffer: Arc::new(RwLock::new(RealTimeBuffer::new())),
             collection_stats: Arc::new(RwLock::new(CollectionStats::default())),
+            custom_metrics: Arc::new(DashMap::new()),
+            custom_counters: Arc::new(DashMap::new()),
+            series_labels: Arc::new(DashMap::new()),
+            rollups: Arc::new(DashMap::new()),
+            snapshot_gate: Arc::new(tokio::sync::Mutex::new(())),
         }
     }
 
@@ -248,6 +414,7 @@ impl MetricsRegistry {
         // Increment operation counter
         let counter_key = format!("{}.{}.count", context.component, context.operation);
         self.increment_counter(&counter_key, 1);
+        self.increment_counter(OPERATIONS_METRIC, 1);
         
         // Start timer for this operation
         let timer_key = format!("{}.{}.duration", context.component, context.operation);
@@ -277,6 +444,7 @@ impl MetricsRegistry {
         // Record duration in histogram for percentile calculations
         let histogram_key = format!("{}.{}.latency", context.component, context.operation);
         self.record_histogram(&histogram_key, duration.as_millis() as f64).await;
+        self.record_histogram(OPERATION_LATENCY_METRIC, duration.as_millis() as f64).await;
         
         // Update performance state gauge
         let performance_gauge = format!("performance.{:?}", context.performance_state);
@@ -296,6 +464,7 @@ impl MetricsRegistry {
         
         // Update cache for fast access
         let now = Utc::now();
+        self.record_rollup(name, value as f64, now);
         self.metric_cache.insert(
             name.to_string(),
             CachedMetric {
@@ -315,6 +484,7 @@ impl MetricsRegistry {
         // Atomic float operations using bit manipulation
         let bits = value.to_bits();
         gauge.store(f64::from_bits(bits), Ordering::Relaxed);
+        self.record_rollup(name, value, Utc::now());
         
         // Update cache
         self.metric_cache.insert(
@@ -334,6 +504,56 @@ impl MetricsRegistry {
             .or_insert_with(|| Histogram::new());
         
         histogram.record(value).await;
+        self.record_rollup(name, value, Utc::now());
+    }
+
+    /// Observations per second of `name` over the last `window`; 0 if it saw none
+    ///
+    /// Counters count increments, not the amounts added.
+    pub fn rate(&self, name: &str, window: RollupWindow) -> f64 {
+        self.rollup(name, window).map_or(0.0, |stats| stats.rate)
+    }
+
+    /// Count/sum/min/max/rate of `name` over the last `window`
+    pub fn rollup(&self, name: &str, window: RollupWindow) -> Option<RollupStats> {
+        self.rollup_at(name, window, Utc::now())
+    }
+
+    pub(crate) fn rollup_at(&self, name: &str, window: RollupWindow, now: DateTime<Utc>) -> Option<RollupStats> {
+        self.rollups.get(name)?.stats(window, now)
+    }
+
+    /// Rollup of every series of `name` labeled `tenant=<tenant_id>`, merged
+    ///
+    /// Counts, sums and rates add up across series; `p99` is the highest series p99.
+    pub fn tenant_rollup(&self, name: &str, tenant_id: &str, window: RollupWindow) -> Option<RollupStats> {
+        self.tenant_rollup_at(name, tenant_id, window, Utc::now())
+    }
+
+    pub(crate) fn tenant_rollup_at(
+        &self,
+        name: &str,
+        tenant_id: &str,
+        window: RollupWindow,
+        now: DateTime<Utc>,
+    ) -> Option<RollupStats> {
+        self.series_labels.iter()
+            .filter(|series| metric_name(series.key()) == name
+                && series.value().get("tenant").is_some_and(|tenant| tenant == tenant_id))
+            .filter_map(|series| self.rollup_at(series.key(), window, now))
+            .reduce(|merged, stats| RollupStats {
+                window,
+                count: merged.count + stats.count,
+                sum: merged.sum + stats.sum,
+                min: merged.min.min(stats.min),
+                max: merged.max.max(stats.max),
+                p99: merged.p99.max(stats.p99),
+                rate: merged.rate + stats.rate,
+            })
+    }
+
+    pub(crate) fn record_rollup(&self, name: &str, value: f64, at: DateTime<Utc>) {
+        self.rollups.entry(name.to_string()).or_default().record(value, at);
     }
 
     /// Start timer for operation duration tracking
@@ -354,12 +574,47 @@ impl MetricsRegistry {
 
     /// Get real-time metrics snapshot for dashboards
     pub async fn get_metrics_snapshot(&self) -> MetricsSnapshot {
+        self.snapshot().await
+    }
+
+    /// Point-in-time snapshot of every metric
+    pub async fn snapshot(&self) -> MetricsSnapshot {
+        let _gate = self.snapshot_gate.lock().await;
+        self.collect_snapshot(false).await
+    }
+
+    /// Snapshot, then zero cumulative metrics for delta-based backends
+    ///
+    /// Counters, histograms and timer aggregates are read and zeroed with one atomic swap
+    /// per value, so an increment racing with the scrape lands in exactly one snapshot.
+    /// Gauges are point-in-time values and keep their current value.
+    pub async fn snapshot_and_reset(&self) -> MetricsSnapshot {
+        let _gate = self.snapshot_gate.lock().await;
+        self.collect_snapshot(true).await
+    }
+
+    /// Collect all metrics, swapping cumulative values to zero when `reset` is set
+    async fn collect_snapshot(&self, reset: bool) -> MetricsSnapshot {
         let timestamp = Utc::now();
         
         // Collect counters
         let mut counters = HashMap::new();
         for entry in self.counters.iter() {
-            counters.insert(entry.key().clone(), entry.value().load(Ordering::Relaxed));
+            let value = if reset {
+                entry.value().swap(0, Ordering::Relaxed)
+            } else {
+                entry.value().load(Ordering::Relaxed)
+            };
+            counters.insert(entry.key().clone(), value);
+        }
+        let mut custom_counters = HashMap::new();
+        for entry in self.custom_counters.iter() {
+            let value = if reset {
+                entry.value().swap(0.0, Ordering::Relaxed)
+            } else {
+                entry.value().load(Ordering::Relaxed)
+            };
+            custom_counters.insert(entry.key().clone(), value);
         }
         
         // Collect gauges
@@ -371,27 +626,171 @@ impl MetricsRegistry {
         // Collect histograms
         let mut histograms = HashMap::new();
         for entry in self.histograms.iter() {
-            let snapshot = entry.value().get_snapshot().await;
+            let snapshot = if reset {
+                entry.value().take_snapshot().await
+            } else {
+                entry.value().get_snapshot().await
+            };
             histograms.insert(entry.key().clone(), snapshot);
         }
         
         // Collect timers
         let mut timers = HashMap::new();
         for entry in self.timers.iter() {
-            let snapshot = entry.value().get_snapshot();
+            let snapshot = if reset {
+                entry.value().take_snapshot()
+            } else {
+                entry.value().get_snapshot()
+            };
             timers.insert(entry.key().clone(), snapshot);
         }
         
         let collection_stats = self.collection_stats.read().await.clone();
+        let labels = self.series_labels.iter()
+            .map(|entry| (entry.key().clone(), entry.value().clone()))
+            .collect();
         
         MetricsSnapshot {
             timestamp,
             counters,
+            custom_counters,
             gauges,
             histograms,
             timers,
             collection_stats,
+            labels,
+        }
+    }
+
+    /// Register a custom counter; re-registering the same kind is a no-op
+    pub async fn register_counter(&self, name: &str, description: &str) -> Result<(), MetricsError> {
+        self.register_custom(name, CustomMetricKind::Counter, MetricType::Counter, description).await
+    }
+
+    /// Register a custom histogram with ascending bucket upper bounds
+    pub async fn register_histogram(&self, name: &str, buckets: Vec<f64>) -> Result<(), MetricsError> {
+        if buckets.is_empty() || buckets.windows(2).any(|pair| pair[0].partial_cmp(&pair[1]) != Some(std::cmp::Ordering::Less)) {
+            return Err(MetricsError::ConfigurationError(format!(
+                "Histogram {} needs strictly ascending buckets", name
+            )));
+        }
+        self.register_custom(name, CustomMetricKind::Histogram(buckets), MetricType::Histogram, "").await
+    }
+
+    /// Register a custom gauge
+    pub async fn register_gauge(&self, name: &str) -> Result<(), MetricsError> {
+        self.register_custom(name, CustomMetricKind::Gauge, MetricType::Gauge, "").await
+    }
+
+    /// Record a value for a registered custom metric under a label set
+    ///
+    /// Counters add `value`, gauges are set to it and histograms observe it. Each new label
+    /// set creates a series; a metric may hold at most `MAX_SERIES_PER_METRIC` of them.
+    pub async fn record(
+        &self,
+        name: &str,
+        value: f64,
+        labels: &HashMap<String, String>,
+    ) -> Result<(), MetricsError> {
+        if !value.is_finite() {
+            return Err(MetricsError::InvalidValue(format!("{} = {}", name, value)));
+        }
+
+        let series = series_key(name, labels);
+        let kind = {
+            let mut metric = self.custom_metrics.get_mut(name)
+                .ok_or_else(|| MetricsError::UnknownMetric(name.to_string()))?;
+            if !metric.series.contains(&series) {
+                if metric.series.len() >= MAX_SERIES_PER_METRIC {
+                    return Err(MetricsError::CardinalityExceeded {
+                        metric: name.to_string(),
+                        limit: MAX_SERIES_PER_METRIC,
+                    });
+                }
+                metric.series.insert(series.clone());
+                if !labels.is_empty() {
+                    self.series_labels.insert(series.clone(), labels.clone());
+                }
+            }
+            metric.kind.clone()
+        };
+
+        match kind {
+            CustomMetricKind::Counter => {
+                if value < 0.0 {
+                    return Err(MetricsError::InvalidValue(format!("Counter {} cannot decrease", name)));
+                }
+                self.add_to_custom_counter(&series, value);
+            }
+            CustomMetricKind::Gauge => self.set_gauge(&series, value),
+            CustomMetricKind::Histogram(buckets) => {
+                let histogram = self.histograms
+                    .entry(series)
+                    .or_insert_with(|| Histogram::with_buckets(buckets));
+                histogram.record(value).await;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Add to a registered counter's series, keeping fractions
+    fn add_to_custom_counter(&self, series: &str, value: f64) {
+        let counter = self.custom_counters
+            .entry(series.to_string())
+            .or_insert_with(|| AtomicF64::new(0.0));
+
+        // Atomic float addition using compare-and-swap
+        let mut current = counter.load(Ordering::Relaxed);
+        let total = loop {
+            match counter.compare_exchange_weak(current, current + value, Ordering::Relaxed, Ordering::Relaxed) {
+                Ok(_) => break current + value,
+                Err(actual) => current = actual,
+            }
+        };
+
+        let now = Utc::now();
+        self.record_rollup(series, value, now);
+        self.metric_cache.insert(
+            series.to_string(),
+            CachedMetric {
+                value: total,
+                last_updated: now,
+                access_count: AtomicU64::new(1),
+            },
+        );
+    }
+
+    async fn register_custom(
+        &self,
+        name: &str,
+        kind: CustomMetricKind,
+        metric_type: MetricType,
+        description: &str,
+    ) -> Result<(), MetricsError> {
+        if name.is_empty() || name.contains(['{', '}']) {
+            return Err(MetricsError::ConfigurationError(format!("Invalid metric name: {:?}", name)));
         }
+
+        {
+            let entry = self.custom_metrics.entry(name.to_string()).or_insert_with(|| CustomMetric {
+                kind: kind.clone(),
+                series: HashSet::new(),
+            });
+            if entry.kind != kind {
+                return Err(MetricsError::AlreadyRegistered(name.to_string()));
+            }
+        }
+
+        self.register_metric(
+            name,
+            metric_type,
+            ClassificationLevel::Internal,
+            description,
+            "",
+            HashMap::new(),
+        ).await;
+        Ok(())
     }
 
     /// Query metrics with filtering and aggregation
@@ -524,13 +923,18 @@ impl MetricsRegistry {
         
         // Export counters
         for (name, value) in &snapshot.counters {
-            output.push_str(&format!("# TYPE {} counter\n", name));
+            output.push_str(&format!("# TYPE {} counter\n", metric_name(name)));
+            output.push_str(&format!("{} {}\n", name, value));
+        }
+        
+        for (name, value) in &snapshot.custom_counters {
+            output.push_str(&format!("# TYPE {} counter\n", metric_name(name)));
             output.push_str(&format!("{} {}\n", name, value));
         }
         
         // Export gauges
         for (name, value) in &snapshot.gauges {
-            output.push_str(&format!("# TYPE {} gauge\n", name));
+            output.push_str(&format!("# TYPE {} gauge\n", metric_name(name)));
             output.push_str(&format!("{} {}\n", name, value));
         }
         
@@ -551,10 +955,32 @@ impl MetricsRegistry {
     }
 }
 
+/// Series name for a metric and label set, Prometheus style: `name{a="1",b="2"}`
+fn series_key(name: &str, labels: &HashMap<String, String>) -> String {
+    if labels.is_empty() {
+        return name.to_string();
+    }
+
+    let mut pairs: Vec<_> = labels.iter().collect();
+    pairs.sort();
+    let rendered: Vec<String> = pairs.iter()
+        .map(|(key, value)| format!("{}=\"{}\"", key, value.replace('\\', "\\\\").replace('"', "\\\"")))
+        .collect();
+    format!("{}{{{}}}", name, rendered.join(","))
+}
+
+/// Metric name without its label set
+fn metric_name(series: &str) -> &str {
+    series.split('{').next().unwrap_or(series)
+}
+
 impl Histogram {
     fn new() -> Self {
         // Default buckets for latency measurements (in milliseconds)
-        let bucket_bounds = vec![0.1, 0.5, 1.0, 2.5, 5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 500.0, 1000.0];
+        Self::with_buckets(PERCENTILE_BOUNDS.to_vec())
+    }
+
+    fn with_buckets(bucket_bounds: Vec<f64>) -> Self {
         let buckets = bucket_bounds
             .into_iter()
             .map(|bound| HistogramBucket {
@@ -600,21 +1026,41 @@ impl Histogram {
 
     async fn get_snapshot(&self) -> HistogramSnapshot {
         let buckets = self.buckets.read().await;
-        let count = self.total_count.load(Ordering::Relaxed);
-        let sum = self.total_sum.load(Ordering::Relaxed);
-        
-        let mean = if count > 0 { sum / count as f64 } else { 0.0 };
-        
-        // Calculate percentiles (simplified implementation)
-        let p50 = self.calculate_percentile(&buckets, count, 0.5);
-        let p95 = self.calculate_percentile(&buckets, count, 0.95);
-        let p99 = self.calculate_percentile(&buckets, count, 0.99);
-        
         let bucket_data: Vec<(f64, u64)> = buckets
             .iter()
             .map(|b| (b.upper_bound, b.count.load(Ordering::Relaxed)))
             .collect();
 
+        Self::build_snapshot(
+            self.total_count.load(Ordering::Relaxed),
+            self.total_sum.load(Ordering::Relaxed),
+            bucket_data,
+        )
+    }
+
+    /// Snapshot and zero the histogram in one pass
+    async fn take_snapshot(&self) -> HistogramSnapshot {
+        let buckets = self.buckets.read().await;
+        let bucket_data: Vec<(f64, u64)> = buckets
+            .iter()
+            .map(|b| (b.upper_bound, b.count.swap(0, Ordering::Relaxed)))
+            .collect();
+
+        Self::build_snapshot(
+            self.total_count.swap(0, Ordering::Relaxed),
+            self.total_sum.swap(0.0, Ordering::Relaxed),
+            bucket_data,
+        )
+    }
+
+    fn build_snapshot(count: u64, sum: f64, buckets: Vec<(f64, u64)>) -> HistogramSnapshot {
+        let mean = if count > 0 { sum / count as f64 } else { 0.0 };
+        
+        // Calculate percentiles (simplified implementation)
+        let p50 = Self::calculate_percentile(&buckets, count, 0.5);
+        let p95 = Self::calculate_percentile(&buckets, count, 0.95);
+        let p99 = Self::calculate_percentile(&buckets, count, 0.99);
+
         HistogramSnapshot {
             count,
             sum,
@@ -622,11 +1068,11 @@ impl Histogram {
             p50,
             p95,
             p99,
-            buckets: bucket_data,
+            buckets,
         }
     }
 
-    fn calculate_percentile(&self, buckets: &[HistogramBucket], total_count: u64, percentile: f64) -> f64 {
+    fn calculate_percentile(buckets: &[(f64, u64)], total_count: u64, percentile: f64) -> f64 {
         if total_count == 0 {
             return 0.0;
         }
@@ -634,15 +1080,15 @@ impl Histogram {
         let target_count = (total_count as f64 * percentile) as u64;
         let mut cumulative_count = 0;
         
-        for bucket in buckets {
-            cumulative_count += bucket.count.load(Ordering::Relaxed);
+        for (upper_bound, count) in buckets {
+            cumulative_count += count;
             if cumulative_count >= target_count {
-                return bucket.upper_bound;
+                return *upper_bound;
             }
         }
         
         // Return the last bucket's upper bound if not found
-        buckets.last().map(|b| b.upper_bound).unwrap_or(0.0)
+        buckets.last().map(|(upper_bound, _)| *upper_bound).unwrap_or(0.0)
     }
 }
 
@@ -727,6 +1173,21 @@ impl Timer {
             max_duration_ms: self.max_duration_ms.load(Ordering::Relaxed),
         }
     }
+
+    /// Snapshot and zero the aggregates; in-flight operations keep their start times
+    fn take_snapshot(&self) -> TimerSnapshot {
+        let count = self.completed_operations.swap(0, Ordering::Relaxed);
+        let total_duration = self.total_duration_ms.swap(0.0, Ordering::Relaxed);
+        let avg_duration = if count > 0 { total_duration / count as f64 } else { 0.0 };
+        
+        TimerSnapshot {
+            count,
+            total_duration_ms: total_duration,
+            avg_duration_ms: avg_duration,
+            min_duration_ms: self.min_duration_ms.swap(f64::MAX, Ordering::Relaxed),
+            max_duration_ms: self.max_duration_ms.swap(0.0, Ordering::Relaxed),
+        }
+    }
 }
 
 impl RealTimeBuffer {
@@ -785,6 +1246,18 @@ pub enum MetricsError {
     
     #[error("Configuration error: {0}")]
     ConfigurationError(String),
+    
+    #[error("Unknown metric: {0}")]
+    UnknownMetric(String),
+    
+    #[error("Metric already registered with a different type: {0}")]
+    AlreadyRegistered(String),
+    
+    #[error("Metric {metric} exceeds {limit} label sets")]
+    CardinalityExceeded { metric: String, limit: usize },
+    
+    #[error("Invalid metric value: {0}")]
+    InvalidValue(String),
 }
 
 #[cfg(test)]
@@ -812,6 +1285,34 @@ mod tests {
         assert_eq!(gauge.load(Ordering::Relaxed), 42.5);
     }
 
+    #[test]
+    fn test_rollup_rate_from_known_arrival_rate() {
+        let registry = MetricsRegistry::new();
+        let start = Utc::now();
+
+        // 20 operations per second for over an hour, each taking 2-6ms
+        for ms in (0..4_200_000).step_by(50) {
+            let latency = 2.0 + (ms / 50 % 5) as f64;
+            registry.record_rollup("data.write.count", 1.0, start + Duration::milliseconds(ms));
+            registry.record_rollup("data.write.latency", latency, start + Duration::milliseconds(ms));
+        }
+        let now = start + Duration::minutes(70);
+
+        for window in RollupWindow::ALL {
+            let rate = registry.rollup_at("data.write.count", window, now).unwrap().rate;
+            assert!((rate - 20.0).abs() < 0.5, "{:?} rate {}", window, rate);
+        }
+        let latency = registry.rollup_at("data.write.latency", RollupWindow::FiveMinutes, now).unwrap();
+        assert_eq!((latency.min, latency.max), (2.0, 6.0));
+        assert!((latency.mean() - 4.0).abs() < 0.01);
+
+        // Traffic stops: the minute window empties while the hour still remembers it
+        let idle = now + Duration::minutes(2);
+        assert!(registry.rollup_at("data.write.count", RollupWindow::OneMinute, idle).is_none());
+        assert!(registry.rollup_at("data.write.count", RollupWindow::OneHour, idle).unwrap().rate > 0.0);
+        assert_eq!(registry.rate("unknown.metric", RollupWindow::OneMinute), 0.0);
+    }
+
     #[tokio::test]
     async fn test_histogram_recording() {
         let histogram = Histogram::new();
@@ -852,4 +1353,119 @@ mod tests {
         assert_eq!(snapshot.counters["test.counter"], 10);
         assert_eq!(snapshot.gauges["test.gauge"], 3.14);
     }
+
+    #[tokio::test]
+    async fn test_snapshot_and_reset_zeroes_counters() {
+        let registry = MetricsRegistry::new();
+        
+        registry.increment_counter("test.counter", 7);
+        registry.set_gauge("test.gauge", 0.75);
+        registry.record_histogram("test.latency", 3.0).await;
+        
+        let scraped = registry.snapshot_and_reset().await;
+        assert_eq!(scraped.counters["test.counter"], 7);
+        assert_eq!(scraped.histograms["test.latency"].count, 1);
+        
+        // Next snapshot starts from zero; gauges keep their value
+        let next = registry.snapshot().await;
+        assert_eq!(next.counters["test.counter"], 0);
+        assert_eq!(next.histograms["test.latency"].count, 0);
+        assert_eq!(next.gauges["test.gauge"], 0.75);
+        
+        registry.increment_counter("test.counter", 2);
+        assert_eq!(registry.snapshot().await.counters["test.counter"], 2);
+    }
+
+    #[tokio::test]
+    async fn test_labeled_custom_counter_in_snapshot() {
+        let registry = MetricsRegistry::new();
+        registry.register_counter("exports.completed", "Finished exports").await.unwrap();
+        
+        let labels: HashMap<String, String> = [
+            ("format".to_string(), "csv".to_string()),
+            ("tenant".to_string(), "acme".to_string()),
+        ].into();
+        registry.record("exports.completed", 2.0, &labels).await.unwrap();
+        registry.record("exports.completed", 1.0, &labels).await.unwrap();
+        
+        let snapshot = registry.snapshot().await;
+        let series = r#"exports.completed{format="csv",tenant="acme"}"#;
+        assert_eq!(snapshot.custom_counters[series], 3.0);
+        assert_eq!(snapshot.labels[series], labels);
+        
+        let point = snapshot.data_points().into_iter()
+            .find(|point| point.name == "exports.completed")
+            .unwrap();
+        assert_eq!(point.value, 3.0);
+        assert_eq!(point.labels, labels);
+        
+        // Recording an unregistered name is rejected
+        assert!(matches!(
+            registry.record("exports.failed", 1.0, &labels).await,
+            Err(MetricsError::UnknownMetric(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_custom_counter_keeps_fractions() {
+        let registry = MetricsRegistry::new();
+        registry.register_counter("storage.gb_written", "Gigabytes written").await.unwrap();
+        
+        let labels = HashMap::new();
+        registry.record("storage.gb_written", 0.25, &labels).await.unwrap();
+        registry.record("storage.gb_written", 1.5, &labels).await.unwrap();
+        
+        let scraped = registry.snapshot_and_reset().await;
+        assert_eq!(scraped.custom_counters["storage.gb_written"], 1.75);
+        assert_eq!(registry.snapshot().await.custom_counters["storage.gb_written"], 0.0);
+        assert!(matches!(
+            registry.record("storage.gb_written", -0.5, &labels).await,
+            Err(MetricsError::InvalidValue(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_custom_metric_cardinality_limit() {
+        let registry = MetricsRegistry::new();
+        registry.register_gauge("queue.depth").await.unwrap();
+        
+        for i in 0..MAX_SERIES_PER_METRIC {
+            let labels = [("queue".to_string(), i.to_string())].into();
+            registry.record("queue.depth", 1.0, &labels).await.unwrap();
+        }
+        
+        let overflow = [("queue".to_string(), "one-too-many".to_string())].into();
+        assert!(matches!(
+            registry.record("queue.depth", 1.0, &overflow).await,
+            Err(MetricsError::CardinalityExceeded { .. })
+        ));
+        
+        // Existing series keep accepting values
+        let existing = [("queue".to_string(), "0".to_string())].into();
+        assert!(registry.record("queue.depth", 5.0, &existing).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_tenant_scoped_delta() {
+        let registry = MetricsRegistry::new();
+        registry.register_counter("jobs.run", "Jobs run").await.unwrap();
+        let acme: HashMap<String, String> = [("tenant".to_string(), "acme".to_string())].into();
+        let globex: HashMap<String, String> = [("tenant".to_string(), "globex".to_string())].into();
+        
+        registry.record("jobs.run", 4.0, &acme).await.unwrap();
+        registry.record("jobs.run", 9.0, &globex).await.unwrap();
+        registry.increment_counter("system.requests", 1);
+        let before = registry.snapshot().await.scoped_to_tenant("acme");
+        assert_eq!(before.custom_counters.len(), 1);
+        assert!(before.counters.is_empty());
+        
+        registry.record("jobs.run", 2.0, &acme).await.unwrap();
+        registry.record("jobs.run", 1.0, &globex).await.unwrap();
+        let after = registry.snapshot().await.scoped_to_tenant("acme");
+        
+        let delta = after.delta_since(&before);
+        assert_eq!(delta.custom_counters.len(), 1);
+        assert_eq!(delta.custom_counters[r#"jobs.run{tenant="acme"}"#], 2.0);
+        assert!(after.delta_since(&after).custom_counters.is_empty());
+    }
 }
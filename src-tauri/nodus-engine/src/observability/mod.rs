@@ -12,19 +12,49 @@ use chrono::{DateTime, Utc};
 use crate::database::DatabaseManager;
 use crate::security::{SecurityLabel, ClassificationLevel};
 
+pub mod alert_rules;
+pub mod audit_export;
 pub mod forensic_logger;
 pub mod metrics_registry;
 // action_dispatcher and async_orchestrator are implemented at crate root (consolidated)
 // pub mod action_dispatcher;
 // pub mod async_orchestrator;
 pub mod automatic_instrumentation;
-
-pub use forensic_logger::ForensicLogger;
+pub mod budget;
+pub mod compliance;
+pub mod observation;
+pub mod exporters;
+pub mod health;
+pub mod integrity;
+pub mod log_redaction;
+pub mod performance_state;
+pub mod pii;
+pub mod qos;
+pub mod rollup;
+pub mod sampling;
+
+pub use alert_rules::{AlertCondition, AlertRuleEngine, AlertRuleError, RuleAlert};
+pub use audit_export::{AuditExportScheduler, AuditExportStatus};
+pub use forensic_logger::{ForensicLogger, ForensicStore, ForensicWriter, ForensicWriterConfig};
 pub use metrics_registry::MetricsRegistry;
+pub use performance_state::{PerformanceStateMonitor, PerformanceThresholds};
+pub use qos::{QosClass, QosError, QosLimits, QosPermit, QosPolicy, QosScheduler};
+pub use pii::{PatternMatcher, PiiDetector, PiiKind, PiiMatcher};
+pub use rollup::{RollupStats, RollupWindow};
+pub use sampling::{SamplingDecision, SamplingReason, TailSampler, TailSamplingPolicy};
 // Re-export root-level implementations instead of expecting them under observability/
 pub use crate::action_dispatcher::ActionDispatcher;
 pub use crate::async_orchestrator::AsyncOrchestrator;
-pub use automatic_instrumentation::AutomaticInstrumentation;
+pub use automatic_instrumentation::{AutomaticInstrumentation, InstrumentationError, RecorderHooks};
+pub use budget::{
+    BreachHandler, BudgetBreach, BudgetCircuits, BudgetEscalationPolicy, BudgetEscalator, CircuitOpen, OperationBudgets, Remediation,
+};
+pub use compliance::{ComplianceEngine, ComplianceError, ComplianceScope, ComplianceScore, ComplianceStore, ComplianceSummary, ComplianceWindow, InMemoryComplianceStore};
+pub use observation::ObservationRecord;
+pub use exporters::{ExportEngine, ExportPolicy, ObservabilityExporter};
+pub use health::{HealthCheck, HealthProbe, HealthReport, HealthStatus, LivenessReport};
+pub use integrity::{ChainLink, ChainVerification, ForensicChain, ForensicLinker, IntegrityAttestation};
+pub use log_redaction::{ForensicLogLayer, RedactingLayer, RedactionRule, SecurityLogEvent, SensitiveFieldRegistry};
 
 /// Observability context for operation tracking
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -39,6 +69,9 @@ pub struct ObservabilityContext {
     pub timestamp: DateTime<Utc>,
     pub session_id: Uuid,
     pub parent_operation_id: Option<Uuid>,
+    /// Scheduling priority when capacity is short
+    #[serde(default)]
+    pub qos: QosClass,
 }
 
 /// Performance state for automatic optimization decisions
@@ -179,15 +212,22 @@ impl ObservabilityContext {
             component: component.to_string(),
             operation: operation.to_string(),
             classification,
-            performance_state: PerformanceState::Normal,
+            performance_state: performance_state::current(),
             tenant_id: None,
             user_id: user_id.to_string(),
             timestamp: Utc::now(),
             session_id,
             parent_operation_id: None,
+            qos: QosClass::Interactive,
         }
     }
 
+    /// Schedule the operation as `qos` instead of Interactive
+    pub fn with_qos(mut self, qos: QosClass) -> Self {
+        self.qos = qos;
+        self
+    }
+
     /// Create child context for nested operations
     pub fn create_child(&self, component: &str, operation: &str) -> Self {
         Self {
@@ -201,6 +241,7 @@ impl ObservabilityContext {
             timestamp: Utc::now(),
             session_id: self.session_id,
             parent_operation_id: Some(self.operation_id),
+            qos: self.qos,
         }
     }
 
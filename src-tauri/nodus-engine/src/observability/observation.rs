@@ -0,0 +1,195 @@
+// src-tauri/src/observability/observation.rs
+// Observation Records - Per-operation observability data handed to exporters
+// Ported from the observability toolkit so exporters have a single record model
+
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+
+use crate::security::ClassificationLevel;
+
+/// Observation record created for each instrumented operation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ObservationRecord {
+    pub observation_id: String,
+    pub operation: String,
+    pub started_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub result: OperationResult,
+    pub performance: PerformanceMetrics,
+    pub security_events: Vec<SecurityEvent>,
+    pub compliance_records: Vec<ComplianceRecord>,
+    pub privacy_protection: Option<PrivacyProtection>,
+    pub metadata: HashMap<String, String>,
+    pub context: ObservationContext,
+}
+
+/// Operation outcome captured in an observation record
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum OperationResult {
+    /// Operation completed successfully (return value only when payload logging is on)
+    Success {
+        return_value: Option<serde_json::Value>,
+    },
+
+    /// Operation failed with error (message may be redacted before export)
+    Error {
+        error_type: String,
+        error_message: String,
+        error_code: Option<String>,
+    },
+
+    /// Operation still in progress
+    InProgress,
+}
+
+/// Performance metrics for an observed operation
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PerformanceMetrics {
+    pub duration_ns: u64,
+    pub cpu_usage: Option<f64>,
+    pub memory_usage_bytes: Option<u64>,
+    pub network_io_bytes: Option<u64>,
+    pub disk_io_bytes: Option<u64>,
+    pub custom_metrics: HashMap<String, f64>,
+}
+
+/// Security event detected during an observed operation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SecurityEvent {
+    pub event_type: SecurityEventType,
+    pub severity: SecuritySeverity,
+    pub description: String,
+    pub timestamp: DateTime<Utc>,
+    pub data: HashMap<String, serde_json::Value>,
+}
+
+/// Types of security events
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SecurityEventType {
+    SuspiciousAccess,
+    AuthenticationFailure,
+    AuthorizationViolation,
+    DataAccessAnomaly,
+    PotentialAttack,
+    Custom(String),
+}
+
+/// Security event severity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum SecuritySeverity {
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+/// Privacy protection levels for observed payloads
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PrivacyLevel {
+    None,
+    PII,
+    PHI,
+    Financial,
+    Legal,
+}
+
+/// Compliance frameworks an observation can carry evidence for
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+pub enum ComplianceFramework {
+    SOX,
+    HIPAA,
+    GDPR,
+    PCIDSS,
+    Custom(String),
+}
+
+/// Compliance evidence attached to an observation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ComplianceRecord {
+    pub framework: ComplianceFramework,
+    pub requirement: String,
+    pub status: ComplianceStatus,
+    pub evidence: serde_json::Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ComplianceStatus {
+    Compliant,
+    NonCompliant,
+    Unknown,
+}
+
+/// Privacy protection applied to an observation before export
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrivacyProtection {
+    pub pii_detected: bool,
+    pub redaction_applied: bool,
+    pub encryption_applied: bool,
+}
+
+/// Context information for an observation
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ObservationContext {
+    pub user_id: Option<String>,
+    pub session_id: Option<String>,
+    pub request_id: Option<String>,
+    pub trace_id: Option<String>,
+    pub span_id: Option<String>,
+    pub tenant_id: Option<String>,
+    pub classification: Option<ClassificationLevel>,
+    pub privacy_level: Option<PrivacyLevel>,
+}
+
+impl ObservationRecord {
+    /// Create a completed record for an operation
+    pub fn new(operation: &str, context: ObservationContext, result: OperationResult) -> Self {
+        let now = Utc::now();
+        Self {
+            observation_id: uuid::Uuid::new_v4().to_string(),
+            operation: operation.to_string(),
+            started_at: now,
+            completed_at: Some(now),
+            result,
+            performance: PerformanceMetrics::default(),
+            security_events: Vec::new(),
+            compliance_records: Vec::new(),
+            privacy_protection: None,
+            metadata: HashMap::new(),
+            context,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_observation_record_roundtrip() {
+        let record = ObservationRecord::new(
+            "storage.put",
+            ObservationContext {
+                user_id: Some("user-456".to_string()),
+                classification: Some(ClassificationLevel::Confidential),
+                ..Default::default()
+            },
+            OperationResult::Success {
+                return_value: Some(serde_json::json!({"status": "ok"})),
+            },
+        );
+
+        let json = serde_json::to_string(&record).unwrap();
+        let parsed: ObservationRecord = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(parsed.operation, "storage.put");
+        assert_eq!(parsed.context.classification, Some(ClassificationLevel::Confidential));
+    }
+
+    #[test]
+    fn test_severity_ordering() {
+        assert!(SecuritySeverity::Critical > SecuritySeverity::High);
+        assert!(SecuritySeverity::Medium > SecuritySeverity::Low);
+    }
+}
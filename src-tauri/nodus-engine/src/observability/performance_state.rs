@@ -0,0 +1,296 @@
+// src-tauri/src/observability/performance_state.rs
+// Performance State Monitor - Derives PerformanceState from rolled-up load metrics
+// Escalates as soon as any signal reaches a level's limit; steps down only once load clears it by the hysteresis margin
+
+use arc_swap::ArcSwap;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::database::DatabaseManager;
+use crate::observability::budget::{BreachHandler, BudgetBreach};
+use crate::observability::metrics_registry::{OPERATIONS_METRIC, OPERATION_LATENCY_METRIC};
+use crate::observability::rollup::RollupWindow;
+use crate::observability::{MetricsRegistry, PerformanceState};
+
+/// Gauge of the share of database connections in use, sampled on each evaluation
+pub const DB_POOL_SATURATION_METRIC: &str = "database.pool.saturation";
+
+/// How often the spawned monitor re-evaluates
+pub const DEFAULT_EVALUATION_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Severity of the state last published by a running monitor, read by `current`
+static CURRENT_STATE: AtomicU8 = AtomicU8::new(0);
+
+/// Limits at which a level is entered; any one signal reaching its limit is enough
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoadLimits {
+    pub operations_per_sec: f64,
+    pub p99_latency_ms: f64,
+    /// Fraction of the connection pool in use, 0 to 1
+    pub pool_saturation: f64,
+}
+
+/// `[observability.performance_state]` policy section
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PerformanceThresholds {
+    /// Rollup window the signals are read over
+    pub window: RollupWindow,
+    pub degraded: LoadLimits,
+    pub high_load: LoadLimits,
+    pub critical: LoadLimits,
+    /// Fraction below a level's limits that every signal must fall before leaving it
+    pub hysteresis: f64,
+}
+
+impl Default for PerformanceThresholds {
+    fn default() -> Self {
+        Self {
+            window: RollupWindow::OneMinute,
+            degraded: LoadLimits { operations_per_sec: 500.0, p99_latency_ms: 100.0, pool_saturation: 0.7 },
+            high_load: LoadLimits { operations_per_sec: 1000.0, p99_latency_ms: 250.0, pool_saturation: 0.85 },
+            critical: LoadLimits { operations_per_sec: 2000.0, p99_latency_ms: 1000.0, pool_saturation: 0.95 },
+            hysteresis: 0.2,
+        }
+    }
+}
+
+/// Load signals a state is derived from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct LoadSample {
+    pub operations_per_sec: f64,
+    pub p99_latency_ms: f64,
+    pub pool_saturation: f64,
+}
+
+impl LoadLimits {
+    fn reached_by(&self, sample: &LoadSample, scale: f64) -> bool {
+        sample.operations_per_sec >= self.operations_per_sec * scale
+            || sample.p99_latency_ms >= self.p99_latency_ms * scale
+            || sample.pool_saturation >= self.pool_saturation * scale
+    }
+}
+
+impl PerformanceThresholds {
+    /// State to move to from `current` under `sample`
+    pub fn next_state(&self, current: &PerformanceState, sample: &LoadSample) -> PerformanceState {
+        let reached = self.level_for(sample, 1.0);
+        if severity(&reached) >= severity(current) {
+            return reached;
+        }
+
+        // Only step down as far as load has cleared the lowered limits
+        let held = self.level_for(sample, 1.0 - self.hysteresis);
+        if severity(&held) < severity(current) {
+            held
+        } else {
+            current.clone()
+        }
+    }
+
+    /// Most severe level whose limits, scaled by `scale`, the sample reaches
+    fn level_for(&self, sample: &LoadSample, scale: f64) -> PerformanceState {
+        if self.critical.reached_by(sample, scale) {
+            PerformanceState::Critical
+        } else if self.high_load.reached_by(sample, scale) {
+            PerformanceState::HighLoad
+        } else if self.degraded.reached_by(sample, scale) {
+            PerformanceState::Degraded
+        } else {
+            PerformanceState::Normal
+        }
+    }
+}
+
+/// Tracks the platform's performance state from live metrics
+#[derive(Debug)]
+pub struct PerformanceStateMonitor {
+    metrics: Arc<MetricsRegistry>,
+    database: Option<Arc<DatabaseManager>>,
+    thresholds: ArcSwap<PerformanceThresholds>,
+    /// Severity of the monitor's current state
+    state: AtomicU8,
+}
+
+impl PerformanceStateMonitor {
+    pub fn new(metrics: Arc<MetricsRegistry>, thresholds: PerformanceThresholds) -> Self {
+        Self {
+            metrics,
+            database: None,
+            thresholds: ArcSwap::from_pointee(thresholds),
+            state: AtomicU8::new(severity(&PerformanceState::Normal)),
+        }
+    }
+
+    /// Sample this database's pool saturation on each evaluation
+    pub fn with_database(mut self, database: Arc<DatabaseManager>) -> Self {
+        self.database = Some(database);
+        self
+    }
+
+    /// Swap in new thresholds; the next evaluation uses them
+    pub fn set_thresholds(&self, thresholds: PerformanceThresholds) {
+        self.thresholds.store(Arc::new(thresholds));
+    }
+
+    pub fn state(&self) -> PerformanceState {
+        from_severity(self.state.load(Ordering::Relaxed))
+    }
+
+    /// Re-derive the state from current load and publish it for new observability contexts
+    pub fn evaluate(&self) -> PerformanceState {
+        if let Some(database) = &self.database {
+            self.metrics.set_gauge(DB_POOL_SATURATION_METRIC, database.pool_saturation());
+        }
+        let state = self.evaluate_at(Utc::now());
+        CURRENT_STATE.store(severity(&state), Ordering::Relaxed);
+        state
+    }
+
+    /// Load over the configured window ending at `now`
+    pub fn sample_at(&self, now: DateTime<Utc>) -> LoadSample {
+        let window = self.thresholds.load().window;
+        let rollup = |name| self.metrics.rollup_at(name, window, now);
+        LoadSample {
+            operations_per_sec: rollup(OPERATIONS_METRIC).map_or(0.0, |stats| stats.rate),
+            p99_latency_ms: rollup(OPERATION_LATENCY_METRIC).map_or(0.0, |stats| stats.p99),
+            pool_saturation: rollup(DB_POOL_SATURATION_METRIC).map_or(0.0, |stats| stats.mean()),
+        }
+    }
+
+    /// Re-derive the state from load over the window ending at `now`, without publishing it
+    pub(crate) fn evaluate_at(&self, now: DateTime<Utc>) -> PerformanceState {
+        let sample = self.sample_at(now);
+        let state = self.state();
+        let next = self.thresholds.load().next_state(&state, &sample);
+        if next != state {
+            tracing::warn!("Performance state {:?} -> {:?} ({:?})", state, next, sample);
+            self.state.store(severity(&next), Ordering::Relaxed);
+        }
+        next
+    }
+
+    /// Move to at least `floor` and publish it, shedding instrumentation ahead of measured load
+    ///
+    /// Later evaluations step back down through the hysteresis margin as load allows.
+    pub fn shed_to(&self, floor: PerformanceState) -> PerformanceState {
+        let previous = self.state.fetch_max(severity(&floor), Ordering::Relaxed);
+        if severity(&floor) > previous {
+            tracing::warn!("Performance state {:?} -> {:?} (shedding load)", from_severity(previous), floor);
+        }
+        let state = self.state.load(Ordering::Relaxed);
+        CURRENT_STATE.fetch_max(state, Ordering::Relaxed);
+        from_severity(state)
+    }
+
+    /// Evaluate every `interval` until the runtime shuts down
+    pub fn spawn(self: Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                self.evaluate();
+            }
+        })
+    }
+}
+
+/// `ShedLoad`: a budget breach moves to Degraded, a critical one to HighLoad
+#[async_trait::async_trait]
+impl BreachHandler for PerformanceStateMonitor {
+    async fn handle(&self, breach: &BudgetBreach) {
+        self.shed_to(if breach.critical { PerformanceState::HighLoad } else { PerformanceState::Degraded });
+    }
+}
+
+/// State most recently published by a running monitor; Normal if none is running
+pub fn current() -> PerformanceState {
+    from_severity(CURRENT_STATE.load(Ordering::Relaxed))
+}
+
+/// The more severe of two states
+pub fn most_severe(a: PerformanceState, b: PerformanceState) -> PerformanceState {
+    if severity(&b) > severity(&a) { b } else { a }
+}
+
+fn from_severity(severity: u8) -> PerformanceState {
+    match severity {
+        0 => PerformanceState::Normal,
+        1 => PerformanceState::Degraded,
+        2 => PerformanceState::HighLoad,
+        _ => PerformanceState::Critical,
+    }
+}
+
+fn severity(state: &PerformanceState) -> u8 {
+    match state {
+        PerformanceState::Normal => 0,
+        PerformanceState::Degraded => 1,
+        PerformanceState::HighLoad => 2,
+        PerformanceState::Critical => 3,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Ten operations a second at `latency_ms` for `seconds`, starting `from` seconds after `start`
+    fn drive(metrics: &MetricsRegistry, start: DateTime<Utc>, from: i64, seconds: i64, latency_ms: f64) {
+        for tenth in 0..seconds * 10 {
+            let at = start + chrono::Duration::milliseconds(from * 1000 + tenth * 100);
+            metrics.record_rollup(OPERATIONS_METRIC, 1.0, at);
+            metrics.record_rollup(OPERATION_LATENCY_METRIC, latency_ms, at);
+        }
+    }
+
+    #[test]
+    fn test_rising_latency_escalates_and_recovers_with_hysteresis() {
+        let metrics = Arc::new(MetricsRegistry::new());
+        let monitor = PerformanceStateMonitor::new(metrics.clone(), PerformanceThresholds::default());
+        let start = Utc::now();
+        let at = |seconds| start + chrono::Duration::seconds(seconds);
+
+        drive(&metrics, start, 0, 10, 20.0);
+        assert_eq!(monitor.evaluate_at(at(10)), PerformanceState::Normal);
+
+        drive(&metrics, start, 10, 10, 300.0);
+        assert_eq!(monitor.evaluate_at(at(20)), PerformanceState::HighLoad);
+
+        drive(&metrics, start, 20, 10, 1500.0);
+        assert_eq!(monitor.evaluate_at(at(30)), PerformanceState::Critical);
+        assert!(monitor.sample_at(at(30)).p99_latency_ms >= 1000.0);
+
+        // Once the spike leaves the window, latency just under the high-load limit holds
+        // HighLoad instead of dropping to Degraded
+        drive(&metrics, start, 30, 70, 240.0);
+        assert_eq!(monitor.evaluate_at(at(100)), PerformanceState::HighLoad);
+        drive(&metrics, start, 100, 10, 240.0);
+        assert_eq!(monitor.evaluate_at(at(110)), PerformanceState::HighLoad);
+
+        drive(&metrics, start, 110, 70, 20.0);
+        assert_eq!(monitor.evaluate_at(at(180)), PerformanceState::Normal);
+        assert_eq!(monitor.state(), PerformanceState::Normal);
+    }
+
+    #[test]
+    fn test_boundary_load_does_not_flap() {
+        let thresholds = PerformanceThresholds::default();
+        let near = |p99_latency_ms| LoadSample { p99_latency_ms, ..LoadSample::default() };
+
+        let mut state = PerformanceState::Normal;
+        for p99 in [250.0, 245.0, 251.0, 230.0, 249.0] {
+            state = thresholds.next_state(&state, &near(p99));
+            assert_eq!(state, PerformanceState::HighLoad);
+        }
+
+        // Saturated pool alone is enough, and the rate limit applies likewise
+        let saturated = LoadSample { pool_saturation: 0.97, ..LoadSample::default() };
+        assert_eq!(thresholds.next_state(&state, &saturated), PerformanceState::Critical);
+        let busy = LoadSample { operations_per_sec: 600.0, ..LoadSample::default() };
+        assert_eq!(thresholds.next_state(&PerformanceState::Normal, &busy), PerformanceState::Degraded);
+    }
+}
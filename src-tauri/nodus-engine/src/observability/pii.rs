@@ -0,0 +1,411 @@
+// src-tauri/src/observability/pii.rs
+// PII Detection - Find emails, SSNs, card and phone numbers in payloads and redact them in place
+// Runs before export so personal data never leaves the engine, whatever the record's classification
+
+use regex::Regex;
+use ring::hmac;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::fmt;
+use std::ops::Range;
+use std::sync::Arc;
+
+use crate::database::SecureEntity;
+use crate::observability::exporters::RedactionMode;
+use crate::observability::observation::{ObservationRecord, OperationResult, PrivacyLevel, PrivacyProtection};
+
+/// Category of personal data a matcher finds
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PiiKind {
+    Email,
+    Ssn,
+    CreditCard,
+    PhoneNumber,
+    Custom(String),
+}
+
+impl fmt::Display for PiiKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PiiKind::Email => f.write_str("email"),
+            PiiKind::Ssn => f.write_str("ssn"),
+            PiiKind::CreditCard => f.write_str("credit_card"),
+            PiiKind::PhoneNumber => f.write_str("phone_number"),
+            PiiKind::Custom(name) => f.write_str(name),
+        }
+    }
+}
+
+/// PII found in a piece of text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PiiMatch {
+    pub kind: PiiKind,
+    /// Byte range of the match in the scanned text
+    pub range: Range<usize>,
+}
+
+/// Finds one kind of PII in text; implement this for detection the built-in matchers don't cover
+pub trait PiiMatcher: Send + Sync + fmt::Debug {
+    fn kind(&self) -> PiiKind;
+
+    /// Byte ranges of every match in `text`
+    fn find(&self, text: &str) -> Vec<Range<usize>>;
+}
+
+/// Regex matcher, optionally confirming each candidate with a check the regex can't express
+#[derive(Debug)]
+pub struct PatternMatcher {
+    kind: PiiKind,
+    regex: Regex,
+    confirm: Option<fn(&str) -> bool>,
+}
+
+impl PatternMatcher {
+    /// Match `pattern` as PII of `kind`
+    pub fn new(kind: PiiKind, pattern: &str) -> Result<Self, regex::Error> {
+        Ok(Self { kind, regex: Regex::new(pattern)?, confirm: None })
+    }
+
+    /// Match any of `terms` as a whole word, ignoring case
+    ///
+    /// Fails only if the terms make the pattern larger than the regex size limit.
+    pub fn dictionary<S: AsRef<str>>(kind: PiiKind, terms: &[S]) -> Result<Self, regex::Error> {
+        let alternatives: Vec<String> = terms.iter().map(|term| regex::escape(term.as_ref())).collect();
+        Self::new(kind, &format!(r"(?i)\b(?:{})\b", alternatives.join("|")))
+    }
+
+    pub fn email() -> Result<Self, regex::Error> {
+        Self::builtin(PiiKind::Email, r"(?i)\b[a-z0-9._%+-]+@[a-z0-9-]+(?:\.[a-z0-9-]+)*\.[a-z]{2,}\b", None)
+    }
+
+    /// US social security numbers, skipping ranges the SSA never assigns
+    pub fn ssn() -> Result<Self, regex::Error> {
+        Self::builtin(PiiKind::Ssn, r"\b\d{3}-\d{2}-\d{4}\b", Some(valid_ssn))
+    }
+
+    /// 13 to 19 digit card numbers, optionally grouped by spaces or dashes, that pass the Luhn check
+    pub fn credit_card() -> Result<Self, regex::Error> {
+        Self::builtin(PiiKind::CreditCard, r"\b\d(?:[ -]?\d){12,18}\b", Some(luhn_valid))
+    }
+
+    /// North American numbers with separators, with an optional country code
+    pub fn phone_number() -> Result<Self, regex::Error> {
+        Self::builtin(
+            PiiKind::PhoneNumber,
+            r"(?:\+\d{1,3}[ .-]?)?(?:\(\d{3}\) ?|\b\d{3}[ .-])\d{3}[ .-]\d{4}\b",
+            None,
+        )
+    }
+
+    fn builtin(kind: PiiKind, pattern: &str, confirm: Option<fn(&str) -> bool>) -> Result<Self, regex::Error> {
+        Ok(Self { confirm, ..Self::new(kind, pattern)? })
+    }
+}
+
+impl PiiMatcher for PatternMatcher {
+    fn kind(&self) -> PiiKind {
+        self.kind.clone()
+    }
+
+    fn find(&self, text: &str) -> Vec<Range<usize>> {
+        self.regex.find_iter(text)
+            .filter(|found| self.confirm.map_or(true, |confirm| confirm(found.as_str())))
+            .map(|found| found.range())
+            .collect()
+    }
+}
+
+fn valid_ssn(candidate: &str) -> bool {
+    let mut parts = candidate.split('-');
+    let (Some(area), Some(group), Some(serial)) = (parts.next(), parts.next(), parts.next()) else {
+        return false;
+    };
+    area != "000" && area != "666" && !area.starts_with('9') && group != "00" && serial != "0000"
+}
+
+fn luhn_valid(candidate: &str) -> bool {
+    let digits: Vec<u32> = candidate.chars().filter_map(|c| c.to_digit(10)).collect();
+    if !(13..=19).contains(&digits.len()) {
+        return false;
+    }
+    let sum: u32 = digits.iter().rev().enumerate()
+        .map(|(i, &digit)| match (i % 2 == 1, digit * 2) {
+            (true, doubled) if doubled > 9 => doubled - 9,
+            (true, doubled) => doubled,
+            (false, _) => digit,
+        })
+        .sum();
+    sum % 10 == 0
+}
+
+/// Detects PII in observation records and entity data and redacts it
+///
+/// How a match is replaced depends on the payload's `PrivacyLevel`: masked by default,
+/// or replaced with a keyed hash for levels configured to hash, so matches stay correlatable.
+#[derive(Clone)]
+pub struct PiiDetector {
+    matchers: Vec<Arc<dyn PiiMatcher>>,
+    level_modes: Vec<(PrivacyLevel, RedactionMode)>,
+    default_mode: RedactionMode,
+    hash_key: hmac::Key,
+}
+
+impl fmt::Debug for PiiDetector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PiiDetector")
+            .field("matchers", &self.matchers)
+            .field("level_modes", &self.level_modes)
+            .field("default_mode", &self.default_mode)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PiiDetector {
+    /// Built-in matchers for emails, SSNs, card and phone numbers; financial and legal
+    /// payloads are hashed, everything else masked
+    pub fn new() -> Result<Self, regex::Error> {
+        Ok(Self::empty()
+            .with_matcher(Arc::new(PatternMatcher::email()?))
+            .with_matcher(Arc::new(PatternMatcher::ssn()?))
+            .with_matcher(Arc::new(PatternMatcher::credit_card()?))
+            .with_matcher(Arc::new(PatternMatcher::phone_number()?))
+            .with_level_mode(PrivacyLevel::Financial, RedactionMode::Hash)
+            .with_level_mode(PrivacyLevel::Legal, RedactionMode::Hash))
+    }
+
+    /// Detector without matchers, masking everything; hashes are keyed per process
+    pub fn empty() -> Self {
+        let key: [u8; 32] = rand::random();
+        Self {
+            matchers: Vec::new(),
+            level_modes: Vec::new(),
+            default_mode: RedactionMode::Strip,
+            hash_key: hmac::Key::new(hmac::HMAC_SHA256, &key),
+        }
+    }
+
+    /// Also detect what `matcher` finds; earlier matchers win where matches overlap
+    pub fn with_matcher(mut self, matcher: Arc<dyn PiiMatcher>) -> Self {
+        self.matchers.push(matcher);
+        self
+    }
+
+    /// Replace matches in payloads at `level` according to `mode`
+    pub fn with_level_mode(mut self, level: PrivacyLevel, mode: RedactionMode) -> Self {
+        self.level_modes.retain(|(existing, _)| *existing != level);
+        self.level_modes.push((level, mode));
+        self
+    }
+
+    /// Key hashed matches with `key`, so hashes stay comparable across restarts and hosts
+    pub fn with_hash_key(mut self, key: &[u8]) -> Self {
+        self.hash_key = hmac::Key::new(hmac::HMAC_SHA256, key);
+        self
+    }
+
+    /// How matches are replaced in a payload at `level`
+    pub fn mode_for(&self, level: Option<&PrivacyLevel>) -> RedactionMode {
+        level
+            .and_then(|level| self.level_modes.iter().find(|(configured, _)| configured == level))
+            .map_or(self.default_mode, |(_, mode)| *mode)
+    }
+
+    /// Non-overlapping PII matches in `text`, in order
+    pub fn detect(&self, text: &str) -> Vec<PiiMatch> {
+        let mut found: Vec<PiiMatch> = Vec::new();
+        for matcher in &self.matchers {
+            for range in matcher.find(text) {
+                let overlaps = found.iter().any(|taken| taken.range.start < range.end && range.start < taken.range.end);
+                if !overlaps {
+                    found.push(PiiMatch { kind: matcher.kind(), range });
+                }
+            }
+        }
+        found.sort_by_key(|found| found.range.start);
+        found
+    }
+
+    /// Whether `text` holds any PII
+    pub fn contains_pii(&self, text: &str) -> bool {
+        self.matchers.iter().any(|matcher| !matcher.find(text).is_empty())
+    }
+
+    /// Whether any string in `value` holds PII
+    pub fn json_contains_pii(&self, value: &serde_json::Value) -> bool {
+        match value {
+            serde_json::Value::String(text) => self.contains_pii(text),
+            serde_json::Value::Array(items) => items.iter().any(|item| self.json_contains_pii(item)),
+            serde_json::Value::Object(fields) => fields.values().any(|field| self.json_contains_pii(field)),
+            _ => false,
+        }
+    }
+
+    /// `text` with its PII replaced, or `None` if it has none
+    pub fn redact_text(&self, text: &str, mode: RedactionMode) -> Option<String> {
+        let matches = self.detect(text);
+        if matches.is_empty() {
+            return None;
+        }
+
+        let mut redacted = String::with_capacity(text.len());
+        let mut copied = 0;
+        for found in matches {
+            redacted.push_str(&text[copied..found.range.start]);
+            redacted.push_str(&self.replacement(&found.kind, &text[found.range.clone()], mode));
+            copied = found.range.end;
+        }
+        redacted.push_str(&text[copied..]);
+        Some(redacted)
+    }
+
+    /// Redact every string in `value` in place; true if any PII was found
+    pub fn redact_json(&self, value: &mut serde_json::Value, mode: RedactionMode) -> bool {
+        match value {
+            serde_json::Value::String(text) => self.redact_string(text, mode),
+            serde_json::Value::Array(items) => items.iter_mut()
+                .fold(false, |found, item| self.redact_json(item, mode) | found),
+            serde_json::Value::Object(fields) => fields.values_mut()
+                .fold(false, |found, field| self.redact_json(field, mode) | found),
+            _ => false,
+        }
+    }
+
+    /// The record with PII in its payloads redacted, borrowed untouched if it has none
+    ///
+    /// Scans the result, metadata values and security event descriptions and data; the record
+    /// is only copied once PII has been found in it.
+    pub fn protect_record<'a>(&self, record: &'a ObservationRecord) -> Cow<'a, ObservationRecord> {
+        if !self.record_contains_pii(record) {
+            return Cow::Borrowed(record);
+        }
+
+        let mode = self.mode_for(record.context.privacy_level.as_ref());
+        let mut protected = record.clone();
+        match &mut protected.result {
+            OperationResult::Success { return_value: Some(value) } => {
+                self.redact_json(value, mode);
+            }
+            OperationResult::Error { error_message, .. } => {
+                self.redact_string(error_message, mode);
+            }
+            OperationResult::Success { return_value: None } | OperationResult::InProgress => {}
+        }
+        for value in protected.metadata.values_mut() {
+            self.redact_string(value, mode);
+        }
+        for event in &mut protected.security_events {
+            self.redact_string(&mut event.description, mode);
+            for value in event.data.values_mut() {
+                self.redact_json(value, mode);
+            }
+        }
+
+        let protection = protected.privacy_protection.get_or_insert_with(PrivacyProtection::default);
+        protection.pii_detected = true;
+        protection.redaction_applied = true;
+        Cow::Owned(protected)
+    }
+
+    /// Redact PII in an entity's data in place, reporting what was done
+    pub fn protect_entity(&self, entity: &mut SecureEntity, level: Option<&PrivacyLevel>) -> PrivacyProtection {
+        let found = self.redact_json(&mut entity.data, self.mode_for(level));
+        PrivacyProtection {
+            pii_detected: found,
+            redaction_applied: found,
+            encryption_applied: false,
+        }
+    }
+
+    fn record_contains_pii(&self, record: &ObservationRecord) -> bool {
+        let in_result = match &record.result {
+            OperationResult::Success { return_value: Some(value) } => self.json_contains_pii(value),
+            OperationResult::Error { error_message, .. } => self.contains_pii(error_message),
+            OperationResult::Success { return_value: None } | OperationResult::InProgress => false,
+        };
+        in_result
+            || record.metadata.values().any(|value| self.contains_pii(value))
+            || record.security_events.iter().any(|event| {
+                self.contains_pii(&event.description) || event.data.values().any(|value| self.json_contains_pii(value))
+            })
+    }
+
+    fn redact_string(&self, text: &mut String, mode: RedactionMode) -> bool {
+        match self.redact_text(text, mode) {
+            Some(redacted) => {
+                *text = redacted;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn replacement(&self, kind: &PiiKind, matched: &str, mode: RedactionMode) -> String {
+        match mode {
+            RedactionMode::Strip => format!("[REDACTED:{}]", kind),
+            RedactionMode::Hash => {
+                let tag = hmac::sign(&self.hash_key, matched.as_bytes());
+                format!("[{} hmac:{}]", kind, hex::encode(&tag.as_ref()[..8]))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::observability::observation::ObservationContext;
+
+    #[test]
+    fn test_builtin_matchers() {
+        let detector = PiiDetector::new().unwrap();
+        let kinds = |text: &str| detector.detect(text).into_iter().map(|found| found.kind).collect::<Vec<_>>();
+
+        assert_eq!(kinds("mail jane.doe@example.co.uk today"), vec![PiiKind::Email]);
+        assert_eq!(kinds("ssn 123-45-6789, not 000-12-3456"), vec![PiiKind::Ssn]);
+        assert_eq!(kinds("card 4111 1111 1111 1111 vs 4111 1111 1111 1112"), vec![PiiKind::CreditCard]);
+        assert_eq!(kinds("call (555) 867-5309 or +1 555.867.5309"), vec![PiiKind::PhoneNumber, PiiKind::PhoneNumber]);
+        assert!(kinds("order 12345 shipped 2024-01-01").is_empty());
+    }
+
+    #[test]
+    fn test_custom_matcher_and_hash_mode() {
+        let detector = PiiDetector::empty()
+            .with_matcher(Arc::new(PatternMatcher::dictionary(PiiKind::Custom("codename".to_string()), &["BlueFox"]).unwrap()))
+            .with_hash_key(b"fixed key");
+
+        let masked = detector.redact_text("asset bluefox met BLUEFOXES", RedactionMode::Strip).unwrap();
+        assert_eq!(masked, "asset [REDACTED:codename] met BLUEFOXES");
+
+        let hashed = detector.redact_text("asset bluefox", RedactionMode::Hash).unwrap();
+        assert!(hashed.starts_with("asset [codename hmac:"));
+        assert_eq!(detector.redact_text("asset bluefox", RedactionMode::Hash).unwrap(), hashed);
+        assert!(detector.redact_text("nothing here", RedactionMode::Strip).is_none());
+    }
+
+    #[test]
+    fn test_protect_record_flags_and_redacts() {
+        let detector = PiiDetector::new().unwrap();
+        let record = ObservationRecord::new(
+            "user.update",
+            ObservationContext::default(),
+            OperationResult::Success {
+                return_value: Some(serde_json::json!({"contact": {"email": "jane@example.com"}, "id": 7})),
+            },
+        );
+
+        let protected = detector.protect_record(&record);
+        let protection = protected.privacy_protection.as_ref().unwrap();
+        assert!(protection.pii_detected && protection.redaction_applied);
+        match &protected.result {
+            OperationResult::Success { return_value: Some(value) } => {
+                assert_eq!(value["contact"]["email"], "[REDACTED:email]");
+                assert_eq!(value["id"], 7);
+            }
+            other => panic!("unexpected result {:?}", other),
+        }
+
+        let clean = ObservationRecord::new("user.read", ObservationContext::default(), OperationResult::InProgress);
+        assert!(matches!(detector.protect_record(&clean), Cow::Borrowed(_)));
+    }
+}
@@ -0,0 +1,285 @@
+// src-tauri/src/observability/qos.rs
+// QoS Scheduling - Per-class concurrency limits so background work can't starve interactive operations
+// Each class draws from its own semaphore; Background work waits out a Critical performance state, up to a deadline
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use super::{performance_state, PerformanceState};
+
+/// How often paused Background work re-checks the performance state
+pub const DEFAULT_RESUME_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Longest Background work waits out a Critical state before giving up
+pub const DEFAULT_MAX_BACKGROUND_PAUSE: Duration = Duration::from_secs(60);
+
+/// Priority an operation is scheduled with when capacity is short
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum QosClass {
+    /// A user is waiting on the result
+    #[default]
+    Interactive,
+    /// Bulk work someone asked for, e.g. an export
+    Batch,
+    /// Maintenance nobody is waiting on, e.g. sync; paused while the system is Critical
+    Background,
+}
+
+/// Concurrent operations allowed per class
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QosLimits {
+    pub interactive: usize,
+    pub batch: usize,
+    pub background: usize,
+}
+
+impl Default for QosLimits {
+    fn default() -> Self {
+        Self {
+            interactive: 64,
+            batch: 16,
+            background: 8,
+        }
+    }
+}
+
+/// Scheduling settings from the observability policy
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QosPolicy {
+    pub limits: QosLimits,
+    /// Longest Background work waits out a Critical state before failing
+    pub max_background_pause_seconds: u64,
+}
+
+impl Default for QosPolicy {
+    fn default() -> Self {
+        Self {
+            limits: QosLimits::default(),
+            max_background_pause_seconds: DEFAULT_MAX_BACKGROUND_PAUSE.as_secs(),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum QosError {
+    #[error("{0:?} scheduling has been shut down")]
+    Closed(QosClass),
+
+    /// The system stayed Critical for the whole pause deadline
+    #[error("Background work paused for {0:?} while the system is Critical")]
+    PausedTooLong(Duration),
+}
+
+/// A running operation's slot in its class; released on drop
+#[derive(Debug)]
+pub struct QosPermit {
+    class: QosClass,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl QosPermit {
+    pub fn class(&self) -> QosClass {
+        self.class
+    }
+}
+
+/// Hands out per-class slots, so each class's capacity is reserved for it alone
+pub struct QosScheduler {
+    interactive: Arc<Semaphore>,
+    batch: Arc<Semaphore>,
+    background: Arc<Semaphore>,
+    limits: QosLimits,
+    state: Arc<dyn Fn() -> PerformanceState + Send + Sync>,
+    resume_interval: Duration,
+    max_pause: Duration,
+}
+
+impl fmt::Debug for QosScheduler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QosScheduler")
+            .field("limits", &self.limits)
+            .field("max_pause", &self.max_pause)
+            .field("interactive_available", &self.interactive.available_permits())
+            .field("batch_available", &self.batch.available_permits())
+            .field("background_available", &self.background.available_permits())
+            .finish()
+    }
+}
+
+impl Default for QosScheduler {
+    fn default() -> Self {
+        Self::new(QosLimits::default())
+    }
+}
+
+impl QosScheduler {
+    /// Scheduler with `limits` (each at least one), paused by the published performance state
+    pub fn new(limits: QosLimits) -> Self {
+        let limits = QosLimits {
+            interactive: limits.interactive.max(1),
+            batch: limits.batch.max(1),
+            background: limits.background.max(1),
+        };
+        Self {
+            interactive: Arc::new(Semaphore::new(limits.interactive)),
+            batch: Arc::new(Semaphore::new(limits.batch)),
+            background: Arc::new(Semaphore::new(limits.background)),
+            limits,
+            state: Arc::new(performance_state::current),
+            resume_interval: DEFAULT_RESUME_INTERVAL,
+            max_pause: DEFAULT_MAX_BACKGROUND_PAUSE,
+        }
+    }
+
+    /// Scheduler with the policy's limits and pause deadline
+    pub fn from_policy(policy: &QosPolicy) -> Self {
+        Self::new(policy.limits.clone()).with_max_pause(Duration::from_secs(policy.max_background_pause_seconds))
+    }
+
+    /// Read the performance state from `state` instead of the running monitor
+    pub fn with_state_source(mut self, state: Arc<dyn Fn() -> PerformanceState + Send + Sync>) -> Self {
+        self.state = state;
+        self
+    }
+
+    /// Re-check a Critical state every `interval` while Background work is paused
+    pub fn with_resume_interval(mut self, interval: Duration) -> Self {
+        self.resume_interval = interval;
+        self
+    }
+
+    /// Fail Background work still paused after `max_pause` instead of waiting on
+    pub fn with_max_pause(mut self, max_pause: Duration) -> Self {
+        self.max_pause = max_pause;
+        self
+    }
+
+    pub fn limits(&self) -> &QosLimits {
+        &self.limits
+    }
+
+    /// Slots of `class` not currently held
+    pub fn available(&self, class: QosClass) -> usize {
+        self.semaphore(class).available_permits()
+    }
+
+    /// Whether new Background work is being held back
+    pub fn background_paused(&self) -> bool {
+        (self.state)() == PerformanceState::Critical
+    }
+
+    /// Wait for a slot in `class`; Background work also waits until the state leaves Critical,
+    /// failing if it hasn't within the pause deadline
+    pub async fn acquire(&self, class: QosClass) -> Result<QosPermit, QosError> {
+        if class == QosClass::Background {
+            let deadline = tokio::time::Instant::now() + self.max_pause;
+            while self.background_paused() {
+                let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+                if remaining.is_zero() {
+                    return Err(QosError::PausedTooLong(self.max_pause));
+                }
+                tokio::time::sleep(self.resume_interval.min(remaining)).await;
+            }
+        }
+        let permit = self.semaphore(class)
+            .clone()
+            .acquire_owned()
+            .await
+            .map_err(|_| QosError::Closed(class))?;
+        Ok(QosPermit { class, _permit: permit })
+    }
+
+    /// A slot in `class` if one is free now and the class isn't paused
+    pub fn try_acquire(&self, class: QosClass) -> Option<QosPermit> {
+        if class == QosClass::Background && self.background_paused() {
+            return None;
+        }
+        let permit = self.semaphore(class).clone().try_acquire_owned().ok()?;
+        Some(QosPermit { class, _permit: permit })
+    }
+
+    fn semaphore(&self, class: QosClass) -> &Arc<Semaphore> {
+        match class {
+            QosClass::Interactive => &self.interactive,
+            QosClass::Batch => &self.batch,
+            QosClass::Background => &self.background,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    const WAIT: Duration = Duration::from_millis(100);
+
+    fn scheduler() -> QosScheduler {
+        QosScheduler::new(QosLimits { interactive: 1, batch: 1, background: 2 })
+            .with_state_source(Arc::new(|| PerformanceState::Normal))
+    }
+
+    #[tokio::test]
+    async fn test_saturated_background_does_not_block_interactive() {
+        let scheduler = scheduler();
+        let _held: Vec<QosPermit> = vec![
+            scheduler.acquire(QosClass::Background).await.unwrap(),
+            scheduler.acquire(QosClass::Background).await.unwrap(),
+        ];
+        assert_eq!(scheduler.available(QosClass::Background), 0);
+        assert!(scheduler.try_acquire(QosClass::Background).is_none());
+        assert!(tokio::time::timeout(WAIT, scheduler.acquire(QosClass::Background)).await.is_err());
+
+        // Interactive and Batch slots are untouched by Background load
+        let interactive = tokio::time::timeout(WAIT, scheduler.acquire(QosClass::Interactive)).await.unwrap().unwrap();
+        assert_eq!(interactive.class(), QosClass::Interactive);
+        assert!(scheduler.try_acquire(QosClass::Batch).is_some());
+
+        // A class's own limit still applies
+        assert!(scheduler.try_acquire(QosClass::Interactive).is_none());
+        drop(interactive);
+        assert!(scheduler.try_acquire(QosClass::Interactive).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_background_paused_while_critical() {
+        let critical = Arc::new(AtomicBool::new(true));
+        let source = critical.clone();
+        let scheduler = Arc::new(scheduler()
+            .with_state_source(Arc::new(move || {
+                if source.load(Ordering::SeqCst) { PerformanceState::Critical } else { PerformanceState::Normal }
+            }))
+            .with_resume_interval(Duration::from_millis(5)));
+
+        assert!(scheduler.background_paused());
+        assert!(scheduler.try_acquire(QosClass::Background).is_none());
+        assert!(tokio::time::timeout(WAIT, scheduler.acquire(QosClass::Background)).await.is_err());
+        assert!(scheduler.try_acquire(QosClass::Interactive).is_some());
+
+        let waiting = tokio::spawn({
+            let scheduler = scheduler.clone();
+            async move { scheduler.acquire(QosClass::Background).await.map(|permit| permit.class()) }
+        });
+        critical.store(false, Ordering::SeqCst);
+        assert_eq!(tokio::time::timeout(WAIT, waiting).await.unwrap().unwrap().unwrap(), QosClass::Background);
+    }
+
+    #[tokio::test]
+    async fn test_background_pause_gives_up_at_deadline() {
+        let policy = QosPolicy { limits: QosLimits { interactive: 3, batch: 2, background: 1 }, max_background_pause_seconds: 60 };
+        let scheduler = QosScheduler::from_policy(&policy)
+            .with_state_source(Arc::new(|| PerformanceState::Critical))
+            .with_max_pause(Duration::from_millis(20))
+            .with_resume_interval(Duration::from_millis(5));
+        assert_eq!(scheduler.limits(), &policy.limits);
+
+        let paused = tokio::time::timeout(WAIT, scheduler.acquire(QosClass::Background)).await.unwrap();
+        assert!(matches!(paused, Err(QosError::PausedTooLong(waited)) if waited == Duration::from_millis(20)));
+        assert!(scheduler.acquire(QosClass::Interactive).await.is_ok());
+    }
+}
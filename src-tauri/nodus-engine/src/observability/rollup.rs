@@ -0,0 +1,219 @@
+// src-tauri/src/observability/rollup.rs
+// Metric Rollups - Time-windowed count/sum/min/max/p99/rate per metric
+// Each window is a fixed ring of time buckets, so memory stays bounded and stale buckets expire on reuse
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Buckets per window; a window's resolution is its length divided by this
+const BUCKETS_PER_WINDOW: usize = 60;
+
+/// Upper bounds of the value distribution kept per bucket for percentiles (latencies in ms)
+pub const PERCENTILE_BOUNDS: [f64; 12] = [0.1, 0.5, 1.0, 2.5, 5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 500.0, 1000.0];
+
+// One slot per bound plus one for values above the last bound
+const DISTRIBUTION_SLOTS: usize = PERCENTILE_BOUNDS.len() + 1;
+
+/// Length of time a rollup covers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RollupWindow {
+    OneMinute,
+    FiveMinutes,
+    OneHour,
+}
+
+impl RollupWindow {
+    pub const ALL: [RollupWindow; 3] = [Self::OneMinute, Self::FiveMinutes, Self::OneHour];
+
+    pub fn seconds(&self) -> i64 {
+        match self {
+            Self::OneMinute => 60,
+            Self::FiveMinutes => 300,
+            Self::OneHour => 3600,
+        }
+    }
+
+    fn bucket_millis(&self) -> i64 {
+        self.seconds() * 1000 / BUCKETS_PER_WINDOW as i64
+    }
+
+    fn index(&self) -> usize {
+        match self {
+            Self::OneMinute => 0,
+            Self::FiveMinutes => 1,
+            Self::OneHour => 2,
+        }
+    }
+}
+
+/// Aggregate of the observations that fell inside a window
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RollupStats {
+    pub window: RollupWindow,
+    pub count: u64,
+    pub sum: f64,
+    pub min: f64,
+    pub max: f64,
+    /// Upper bound of the `PERCENTILE_BOUNDS` band holding the 99th percentile, capped at `max`
+    pub p99: f64,
+    /// Observations per second over the window
+    pub rate: f64,
+}
+
+impl RollupStats {
+    pub fn mean(&self) -> f64 {
+        self.sum / self.count as f64
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    // Which slot of time (bucket width since the epoch) this bucket currently holds
+    slot: i64,
+    count: u64,
+    sum: f64,
+    min: f64,
+    max: f64,
+    distribution: [u32; DISTRIBUTION_SLOTS],
+}
+
+impl Bucket {
+    const EMPTY: Bucket = Bucket {
+        slot: i64::MIN,
+        count: 0,
+        sum: 0.0,
+        min: f64::MAX,
+        max: f64::MIN,
+        distribution: [0; DISTRIBUTION_SLOTS],
+    };
+}
+
+#[derive(Debug, Clone)]
+struct Ring {
+    window: RollupWindow,
+    buckets: [Bucket; BUCKETS_PER_WINDOW],
+}
+
+impl Ring {
+    fn new(window: RollupWindow) -> Self {
+        Self { window, buckets: [Bucket::EMPTY; BUCKETS_PER_WINDOW] }
+    }
+
+    fn record(&mut self, value: f64, at_ms: i64) {
+        let slot = at_ms.div_euclid(self.window.bucket_millis());
+        let bucket = &mut self.buckets[slot.rem_euclid(BUCKETS_PER_WINDOW as i64) as usize];
+        if bucket.slot != slot {
+            // Whatever was here is at least a full window old
+            *bucket = Bucket { slot, ..Bucket::EMPTY };
+        }
+        bucket.count += 1;
+        bucket.sum += value;
+        bucket.min = bucket.min.min(value);
+        bucket.max = bucket.max.max(value);
+        let band = PERCENTILE_BOUNDS.iter().position(|bound| value <= *bound).unwrap_or(PERCENTILE_BOUNDS.len());
+        bucket.distribution[band] += 1;
+    }
+
+    fn stats(&self, now_ms: i64) -> Option<RollupStats> {
+        let width = self.window.bucket_millis();
+        let current = now_ms.div_euclid(width);
+        let oldest = current - BUCKETS_PER_WINDOW as i64 + 1;
+
+        let mut stats = RollupStats {
+            window: self.window,
+            count: 0,
+            sum: 0.0,
+            min: f64::MAX,
+            max: f64::MIN,
+            p99: 0.0,
+            rate: 0.0,
+        };
+        let mut distribution = [0u64; DISTRIBUTION_SLOTS];
+        for bucket in self.buckets.iter().filter(|b| (oldest..=current).contains(&b.slot)) {
+            stats.count += bucket.count;
+            stats.sum += bucket.sum;
+            stats.min = stats.min.min(bucket.min);
+            stats.max = stats.max.max(bucket.max);
+            for (total, count) in distribution.iter_mut().zip(bucket.distribution) {
+                *total += count as u64;
+            }
+        }
+        if stats.count == 0 {
+            return None;
+        }
+
+        let target = (stats.count as f64 * 0.99).ceil() as u64;
+        let mut seen = 0;
+        let band = distribution.iter().position(|count| {
+            seen += count;
+            seen >= target
+        });
+        stats.p99 = band
+            .and_then(|band| PERCENTILE_BOUNDS.get(band))
+            .map_or(stats.max, |bound| bound.min(stats.max));
+
+        // The oldest bucket is complete and the current one is only partly elapsed
+        let covered_ms = (BUCKETS_PER_WINDOW as i64 - 1) * width + (now_ms - current * width);
+        stats.rate = stats.count as f64 * 1000.0 / covered_ms as f64;
+        Some(stats)
+    }
+}
+
+/// Rolling windows for one metric
+#[derive(Debug, Clone)]
+pub struct RollupSeries {
+    rings: [Ring; 3],
+}
+
+impl RollupSeries {
+    pub fn new() -> Self {
+        Self { rings: RollupWindow::ALL.map(Ring::new) }
+    }
+
+    pub fn record(&mut self, value: f64, at: DateTime<Utc>) {
+        let at_ms = at.timestamp_millis();
+        for ring in &mut self.rings {
+            ring.record(value, at_ms);
+        }
+    }
+
+    /// Aggregate over `window` ending at `now`; None if nothing was observed in it
+    pub fn stats(&self, window: RollupWindow, now: DateTime<Utc>) -> Option<RollupStats> {
+        self.rings[window.index()].stats(now.timestamp_millis())
+    }
+}
+
+impl Default for RollupSeries {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_old_buckets_expire() {
+        let start = Utc::now();
+        let mut series = RollupSeries::new();
+        series.record(4.0, start);
+        series.record(10.0, start + chrono::Duration::seconds(30));
+
+        let stats = series.stats(RollupWindow::OneMinute, start + chrono::Duration::seconds(45)).unwrap();
+        assert_eq!((stats.count, stats.sum, stats.min, stats.max), (2, 14.0, 4.0, 10.0));
+        assert_eq!(stats.p99, 10.0);
+
+        // Past a minute the first observation has dropped out of the short window only
+        let later = start + chrono::Duration::seconds(75);
+        assert_eq!(series.stats(RollupWindow::OneMinute, later).unwrap().count, 1);
+        assert_eq!(series.stats(RollupWindow::FiveMinutes, later).unwrap().count, 2);
+        assert!(series.stats(RollupWindow::OneMinute, start + chrono::Duration::minutes(3)).is_none());
+
+        // A recycled bucket doesn't keep what it held a window ago
+        series.record(1.0, start + chrono::Duration::seconds(60));
+        let stats = series.stats(RollupWindow::OneMinute, start + chrono::Duration::seconds(60)).unwrap();
+        assert_eq!((stats.count, stats.min), (2, 1.0));
+    }
+}
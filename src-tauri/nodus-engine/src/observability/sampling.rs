@@ -0,0 +1,251 @@
+// src-tauri/src/observability/sampling.rs
+// Tail Sampling - Decide whether to keep an observation once the operation's outcome is known
+// Errors, critical security events and budget overruns are always kept; routine successes are
+// down-sampled, more aggressively the more often the operation has been seen recently
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+use crate::clock::{system_clock, Clock};
+use crate::observability::observation::{ObservationRecord, OperationResult, SecuritySeverity};
+use crate::observability::{BudgetResult, PerformanceBudget};
+
+/// Keep probability routine successes decay toward
+pub const DEFAULT_BASE_RATE: f64 = 0.1;
+
+/// Time for an operation's recent-occurrence count to halve
+pub const DEFAULT_HALF_LIFE: Duration = Duration::from_secs(60);
+
+/// Recent occurrences over which the keep probability falls by a factor of e toward the base rate
+pub const DEFAULT_RARITY_SCALE: f64 = 10.0;
+
+/// Metadata key recording the keep probability of a down-sampled record, for reweighting
+pub const SAMPLE_RATE_METADATA: &str = "sample_rate";
+
+/// Tail sampling settings from the observability policy
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TailSamplingPolicy {
+    /// Export every record when disabled
+    pub enabled: bool,
+    /// Keep probability routine successes decay toward
+    pub base_rate: f64,
+    /// Time for an operation's recent-occurrence count to halve
+    pub half_life_seconds: u64,
+}
+
+impl Default for TailSamplingPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            base_rate: DEFAULT_BASE_RATE,
+            half_life_seconds: DEFAULT_HALF_LIFE.as_secs(),
+        }
+    }
+}
+
+/// Why a record was kept or dropped
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SamplingReason {
+    Error,
+    CriticalSecurityEvent,
+    OverBudget,
+    /// Routine success, kept or dropped with the given probability of keeping it
+    Sampled(f64),
+}
+
+/// Outcome of a tail-sampling decision
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SamplingDecision {
+    pub keep: bool,
+    pub reason: SamplingReason,
+}
+
+/// Exponentially decaying count of recent occurrences
+#[derive(Debug, Clone, Copy)]
+struct DecayedCount {
+    value: f64,
+    updated: Instant,
+}
+
+/// Priority sampler applied after an operation completes
+///
+/// A routine success of an operation seen `n` times recently is kept with probability
+/// `base + (1 - base) * exp(-(n - 1) / rarity_scale)`, so the first occurrence of a rare
+/// operation is always kept while hot paths settle at the base rate.
+#[derive(Debug)]
+pub struct TailSampler {
+    base_rate: f64,
+    half_life: Duration,
+    rarity_scale: f64,
+    counts: Mutex<HashMap<String, DecayedCount>>,
+    rng: Mutex<StdRng>,
+    clock: Arc<dyn Clock>,
+}
+
+impl Default for TailSampler {
+    fn default() -> Self {
+        Self::new(DEFAULT_BASE_RATE)
+    }
+}
+
+impl TailSampler {
+    /// Sampler keeping at least `base_rate` of routine successes (clamped to 0..=1)
+    pub fn new(base_rate: f64) -> Self {
+        Self {
+            base_rate: base_rate.clamp(0.0, 1.0),
+            half_life: DEFAULT_HALF_LIFE,
+            rarity_scale: DEFAULT_RARITY_SCALE,
+            counts: Mutex::new(HashMap::new()),
+            rng: Mutex::new(StdRng::from_entropy()),
+            clock: system_clock(),
+        }
+    }
+
+    /// Time for an operation's recent-occurrence count to halve
+    /// Sampler configured by `policy`; `None` when sampling is disabled
+    pub fn from_policy(policy: &TailSamplingPolicy) -> Option<Self> {
+        policy.enabled.then(|| {
+            Self::new(policy.base_rate).with_half_life(Duration::from_secs(policy.half_life_seconds.max(1)))
+        })
+    }
+
+    pub fn with_half_life(mut self, half_life: Duration) -> Self {
+        self.half_life = half_life;
+        self
+    }
+
+    /// Recent occurrences over which routine successes decay toward the base rate
+    pub fn with_rarity_scale(mut self, rarity_scale: f64) -> Self {
+        self.rarity_scale = rarity_scale.max(f64::MIN_POSITIVE);
+        self
+    }
+
+    /// Seed the sampler's random source, for reproducible decisions
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.rng = Mutex::new(StdRng::seed_from_u64(seed));
+        self
+    }
+
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Decide whether to keep `record`, whose operation ran against `budget`
+    ///
+    /// Kept down-sampled records carry their keep probability under `SAMPLE_RATE_METADATA`.
+    pub async fn sample(&self, record: &mut ObservationRecord, budget: &PerformanceBudget) -> SamplingDecision {
+        let decision = self.decide(record, budget).await;
+        if let SamplingDecision { keep: true, reason: SamplingReason::Sampled(rate) } = decision {
+            record.metadata.insert(SAMPLE_RATE_METADATA.to_string(), format!("{:.4}", rate));
+        }
+        decision
+    }
+
+    /// Decide whether to keep `record` without annotating it
+    pub async fn decide(&self, record: &ObservationRecord, budget: &PerformanceBudget) -> SamplingDecision {
+        let keep = |reason| SamplingDecision { keep: true, reason };
+
+        if matches!(record.result, OperationResult::Error { .. }) {
+            return keep(SamplingReason::Error);
+        }
+        if record.security_events.iter().any(|event| event.severity == SecuritySeverity::Critical) {
+            return keep(SamplingReason::CriticalSecurityEvent);
+        }
+        let duration_ms = record.performance.duration_ns / 1_000_000;
+        if !matches!(budget.check_budget(duration_ms), BudgetResult::WithinBudget) {
+            return keep(SamplingReason::OverBudget);
+        }
+
+        let rate = self.keep_probability(&record.operation).await;
+        let roll: f64 = self.rng.lock().await.gen();
+        SamplingDecision {
+            keep: roll < rate,
+            reason: SamplingReason::Sampled(rate),
+        }
+    }
+
+    /// Count one more occurrence of `operation` and return its keep probability
+    async fn keep_probability(&self, operation: &str) -> f64 {
+        let now = self.clock.instant();
+        let mut counts = self.counts.lock().await;
+        let count = counts
+            .entry(operation.to_string())
+            .or_insert(DecayedCount { value: 0.0, updated: now });
+
+        let halvings = now.saturating_duration_since(count.updated).as_secs_f64() / self.half_life.as_secs_f64();
+        count.value = count.value * 0.5f64.powf(halvings) + 1.0;
+        count.updated = now;
+
+        let rarity = (-(count.value - 1.0) / self.rarity_scale).exp();
+        self.base_rate + (1.0 - self.base_rate) * rarity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+    use crate::observability::observation::ObservationContext;
+
+    fn success(duration_ms: u64) -> ObservationRecord {
+        let mut record = ObservationRecord::new(
+            "storage.get",
+            ObservationContext::default(),
+            OperationResult::Success { return_value: None },
+        );
+        record.performance.duration_ns = duration_ms * 1_000_000;
+        record
+    }
+
+    #[tokio::test]
+    async fn test_routine_successes_decay_toward_base_rate() {
+        let clock = Arc::new(MockClock::default());
+        let sampler = TailSampler::new(0.1).with_seed(7).with_clock(clock.clone());
+        let budget = PerformanceBudget::new(50, "storage.get", false);
+
+        // A rare operation's first occurrence is always kept
+        let first = sampler.decide(&success(1), &budget).await;
+        assert_eq!(first, SamplingDecision { keep: true, reason: SamplingReason::Sampled(1.0) });
+
+        let mut kept = 0;
+        for _ in 0..1000 {
+            kept += usize::from(sampler.decide(&success(1), &budget).await.keep);
+        }
+        assert!(kept > 50 && kept < 250, "kept {} of 1000", kept);
+
+        // Once the operation goes quiet it counts as rare again
+        clock.advance(Duration::from_secs(3600));
+        assert!(sampler.decide(&success(1), &budget).await.keep);
+    }
+
+    #[tokio::test]
+    async fn test_slow_or_flagged_operations_are_always_kept() {
+        let sampler = TailSampler::new(0.0).with_rarity_scale(0.001).with_seed(7);
+        let budget = PerformanceBudget::new(50, "storage.get", false);
+        for _ in 0..10 {
+            sampler.decide(&success(1), &budget).await;
+        }
+
+        let mut slow = success(80);
+        assert_eq!(sampler.sample(&mut slow, &budget).await.reason, SamplingReason::OverBudget);
+        assert!(!slow.metadata.contains_key(SAMPLE_RATE_METADATA));
+
+        let mut flagged = success(1);
+        flagged.security_events.push(crate::observability::observation::SecurityEvent {
+            event_type: crate::observability::observation::SecurityEventType::PotentialAttack,
+            severity: SecuritySeverity::Critical,
+            description: "replayed token".to_string(),
+            timestamp: chrono::Utc::now(),
+            data: HashMap::new(),
+        });
+        assert!(sampler.decide(&flagged, &budget).await.keep);
+        assert!(!sampler.decide(&success(1), &budget).await.keep);
+    }
+}
@@ -5,12 +5,15 @@
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 use ring::{signature, digest};
 use uuid::Uuid;
+use base64::{Engine as _, engine::general_purpose};
 
 use crate::security::{SecurityManager, ClassificationLevel, SecurityLabel};
+use crate::database::DatabaseContext;
 use crate::license::{LicenseManager, LicenseTier};
 use crate::observability::ForensicLogger;
 use crate::state::AppState;
@@ -19,10 +22,10 @@ use crate::state::AppState;
 #[derive(Debug)]
 pub struct EnterprisePluginSystem {
     /// Loaded and verified plugins
-    loaded_plugins: Arc<RwLock<HashMap<String, LoadedPlugin>>>,
+    plugins: PluginRegistry,
     
-    /// Plugin verification keys (public keys for signature verification)
-    verification_keys: HashMap<String, Vec<u8>>,
+    /// Checks plugin signatures against trusted publisher keys
+    verifier: PluginVerifier,
     
     /// Security manager for access control
     security_manager: Arc<SecurityManager>,
@@ -35,6 +38,17 @@ pub struct EnterprisePluginSystem {
     
     /// Plugin sandbox configurations
     sandbox_configs: HashMap<String, SandboxConfig>,
+    
+    /// Compiles and runs WASM plugins
+    #[cfg(feature = "plugins_wasm")]
+    wasm_runtime: crate::plugin_wasm::WasmPluginRuntime,
+    
+    /// Database and transport WASM plugins' host calls are carried out through; WASM plugins
+    /// are refused until these are set
+    #[cfg(feature = "plugins_wasm")]
+    host_services: Option<(Arc<crate::database::DatabaseManager>, Arc<crate::networking::SecureNetworkTransport>)>,
+    #[cfg(feature = "plugins_wasm")]
+    pii_detector: Option<Arc<crate::observability::PiiDetector>>,
 }
 
 /// Plugin manifest with cryptographic signatures
@@ -62,6 +76,10 @@ pub struct PluginManifest {
     pub capabilities: Vec<PluginCapability>,
     pub permissions: Vec<PluginPermission>,
     
+    /// Resource scope enforced on every call the plugin makes into the host
+    #[serde(default)]
+    pub sandbox: PluginCapabilities,
+    
     /// Entry points for different contexts
     pub entry_points: PluginEntryPoints,
     
@@ -71,13 +89,91 @@ pub struct PluginManifest {
     /// Plugin file hashes for integrity verification
     pub file_hashes: HashMap<String, String>,
     
-    /// Cryptographic signature of the manifest
+    /// Base64 Ed25519 signature over `signing_digest`: this manifest and every plugin file
     pub signature: String,
     
-    /// Public key fingerprint for signature verification
+    /// Publisher key the signature was made with
     pub key_fingerprint: String,
 }
 
+/// Name of the manifest file in a plugin directory; every other file is an artifact
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+impl PluginManifest {
+    /// SHA-256 digest a publisher signs
+    ///
+    /// Covers the manifest with its signature left empty, as canonical JSON with sorted keys,
+    /// then each artifact's path and hex SHA-256 in path order, so no file can be changed,
+    /// added or removed and no manifest field edited without breaking the signature.
+    pub fn signing_digest(&self, artifacts: &BTreeMap<String, String>) -> Result<digest::Digest, PluginError> {
+        let unsigned = Self { signature: String::new(), ..self.clone() };
+        let value = serde_json::to_value(&unsigned)
+            .map_err(|e| PluginError::InvalidManifest { reason: format!("Failed to encode manifest: {}", e) })?;
+
+        let mut context = digest::Context::new(&digest::SHA256);
+        context.update(canonical_json(&value).as_bytes());
+        for (path, sha256) in artifacts {
+            context.update(b"\n");
+            context.update(path.as_bytes());
+            context.update(b"\0");
+            context.update(sha256.as_bytes());
+        }
+        Ok(context.finish())
+    }
+}
+
+/// `value` as JSON with object keys sorted at every level
+fn canonical_json(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            let fields: Vec<String> = entries.into_iter()
+                .map(|(key, value)| format!("{}:{}", serde_json::Value::String(key.clone()), canonical_json(value)))
+                .collect();
+            format!("{{{}}}", fields.join(","))
+        }
+        serde_json::Value::Array(items) => {
+            format!("[{}]", items.iter().map(canonical_json).collect::<Vec<_>>().join(","))
+        }
+        other => other.to_string(),
+    }
+}
+
+/// Hex SHA-256 of every file under `plugin_path` but the manifest, by `/`-separated relative path
+///
+/// Anything other than regular files and directories, such as a symlink, is refused.
+pub async fn read_plugin_artifacts(plugin_path: &Path) -> Result<BTreeMap<String, String>, PluginError> {
+    let unreadable = |path: &Path, e: std::io::Error| PluginError::InvalidManifest {
+        reason: format!("Failed to read plugin file {}: {}", path.display(), e),
+    };
+
+    let mut artifacts = BTreeMap::new();
+    let mut pending = vec![(plugin_path.to_path_buf(), String::new())];
+    while let Some((dir, prefix)) = pending.pop() {
+        let mut entries = tokio::fs::read_dir(&dir).await.map_err(|e| unreadable(&dir, e))?;
+        while let Some(entry) = entries.next_entry().await.map_err(|e| unreadable(&dir, e))? {
+            let path = entry.path();
+            let name = entry.file_name().into_string().map_err(|_| PluginError::InvalidManifest {
+                reason: format!("Plugin file name is not UTF-8: {}", path.display()),
+            })?;
+            let relative = format!("{}{}", prefix, name);
+            let file_type = entry.file_type().await.map_err(|e| unreadable(&path, e))?;
+            if file_type.is_dir() {
+                pending.push((path, format!("{}/", relative)));
+            } else if !file_type.is_file() {
+                return Err(PluginError::InvalidManifest {
+                    reason: format!("Plugin file is not a regular file: {}", relative),
+                });
+            } else if relative != MANIFEST_FILE {
+                let content = tokio::fs::read(&path).await.map_err(|e| unreadable(&path, e))?;
+                artifacts.insert(relative, hex::encode(digest::digest(&digest::SHA256, &content).as_ref()));
+            }
+        }
+    }
+    Ok(artifacts)
+}
+
 /// Plugin capability declarations
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum PluginCapability {
@@ -126,6 +222,111 @@ pub enum PluginCapability {
     },
 }
 
+/// Resource scope granted to a plugin; anything not listed is denied
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginCapabilities {
+    /// Hosts the plugin may reach; `*.example.com` also matches subdomains
+    #[serde(default)]
+    pub network_domains: Vec<String>,
+    #[serde(default)]
+    pub readable_entity_types: Vec<String>,
+    #[serde(default)]
+    pub writable_entity_types: Vec<String>,
+    /// Highest classification the plugin may observe or write
+    #[serde(default = "unclassified")]
+    pub max_classification: ClassificationLevel,
+}
+
+fn unclassified() -> ClassificationLevel {
+    ClassificationLevel::Unclassified
+}
+
+impl Default for PluginCapabilities {
+    fn default() -> Self {
+        Self {
+            network_domains: Vec::new(),
+            readable_entity_types: Vec::new(),
+            writable_entity_types: Vec::new(),
+            max_classification: unclassified(),
+        }
+    }
+}
+
+/// A call from a plugin into the host, checked against its `PluginCapabilities`
+#[derive(Debug, Clone, Copy)]
+pub enum PluginOperation<'a> {
+    Network { domain: &'a str },
+    ReadEntity { entity_type: &'a str, classification: &'a ClassificationLevel },
+    WriteEntity { entity_type: &'a str, classification: &'a ClassificationLevel },
+}
+
+impl PluginCapabilities {
+    /// Deny `operation` unless it falls inside the granted scope
+    pub fn authorize(&self, plugin_id: &str, operation: &PluginOperation) -> Result<(), PluginError> {
+        let denied = |reason: String| Err(PluginError::CapabilityDenied {
+            plugin_id: plugin_id.to_string(),
+            reason,
+        });
+
+        match operation {
+            PluginOperation::Network { domain } => {
+                if !self.network_domains.iter().any(|allowed| domain_matches(allowed, domain)) {
+                    return denied(format!("network access to {}", domain));
+                }
+            }
+            PluginOperation::ReadEntity { entity_type, classification } => {
+                if !self.readable_entity_types.iter().any(|t| t == entity_type) {
+                    return denied(format!("read of {} entities", entity_type));
+                }
+                if !self.max_classification.dominates(classification) {
+                    return denied(format!("read of {} data", classification));
+                }
+            }
+            PluginOperation::WriteEntity { entity_type, classification } => {
+                if !self.writable_entity_types.iter().any(|t| t == entity_type) {
+                    return denied(format!("write of {} entities", entity_type));
+                }
+                if !self.max_classification.dominates(classification) {
+                    return denied(format!("write of {} data", classification));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Deny grants that `tier` does not allow plugins to hold
+    pub fn licensed_for(&self, plugin_id: &str, tier: &LicenseTier) -> Result<(), PluginError> {
+        let denied = |reason: &str| Err(PluginError::CapabilityDenied {
+            plugin_id: plugin_id.to_string(),
+            reason: format!("{} not licensed for {:?} tier", reason, tier),
+        });
+
+        if !self.network_domains.is_empty() && tier_rank(tier) < tier_rank(&LicenseTier::Pro) {
+            return denied("network access");
+        }
+        if !self.writable_entity_types.is_empty() && tier_rank(tier) < tier_rank(&LicenseTier::Pro) {
+            return denied("entity writes");
+        }
+        if let Some(max) = max_plugin_classification(tier) {
+            if !max.dominates(&self.max_classification) {
+                return denied(&format!("{} data", self.max_classification));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn domain_matches(allowed: &str, domain: &str) -> bool {
+    let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+    let allowed = allowed.to_ascii_lowercase();
+    match allowed.strip_prefix("*.") {
+        Some(parent) => domain == parent || domain.ends_with(&format!(".{}", parent)),
+        None => domain == allowed,
+    }
+}
+
 /// Plugin permission model
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PluginPermission {
@@ -202,14 +403,166 @@ pub struct PluginDependency {
     pub optional: bool,
 }
 
+/// A plugin whose signature and requested capabilities passed verification
+#[derive(Debug, Clone)]
+pub struct VerifiedPlugin {
+    pub manifest: PluginManifest,
+    /// Trusted publisher key the signature verified against
+    pub publisher: String,
+    /// Hex `signing_digest` the signature verified over
+    pub sha256: String,
+    pub verified_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Verifies plugin signatures against trusted publisher keys and capabilities against a license tier
+#[derive(Debug, Clone, Default)]
+pub struct PluginVerifier {
+    /// Raw Ed25519 public keys by publisher key id
+    trusted_keys: HashMap<String, Vec<u8>>,
+}
+
+impl PluginVerifier {
+    pub fn new(trusted_keys: HashMap<String, Vec<u8>>) -> Self {
+        Self { trusted_keys }
+    }
+
+    /// Check the signature over the manifest and `artifacts`, then that `tier` grants every
+    /// requested capability
+    ///
+    /// `artifacts` are the plugin's files as `read_plugin_artifacts` lists them.
+    pub fn verify(
+        &self,
+        manifest: &PluginManifest,
+        artifacts: &BTreeMap<String, String>,
+        tier: &LicenseTier,
+    ) -> Result<VerifiedPlugin, PluginError> {
+        let signature_failed = || PluginError::SignatureVerificationFailed { plugin_id: manifest.id.clone() };
+
+        let public_key = self.trusted_keys
+            .get(&manifest.key_fingerprint)
+            .ok_or_else(signature_failed)?;
+        let signature_bytes = general_purpose::STANDARD
+            .decode(&manifest.signature)
+            .map_err(|_| signature_failed())?;
+        let signed = manifest.signing_digest(artifacts)?;
+        signature::UnparsedPublicKey::new(&signature::ED25519, public_key)
+            .verify(signed.as_ref(), &signature_bytes)
+            .map_err(|_| signature_failed())?;
+
+        if tier_rank(tier) < tier_rank(&manifest.required_license) {
+            return Err(PluginError::InsufficientLicense {
+                plugin_id: manifest.id.clone(),
+                required_license: manifest.required_license.clone(),
+            });
+        }
+        for capability in &manifest.capabilities {
+            if let Some(permission) = unlicensed_capability(capability, tier) {
+                return Err(PluginError::PermissionDenied {
+                    plugin_id: manifest.id.clone(),
+                    permission: permission.to_string(),
+                });
+            }
+        }
+        manifest.sandbox.licensed_for(&manifest.id, tier)?;
+
+        Ok(VerifiedPlugin {
+            manifest: manifest.clone(),
+            publisher: manifest.key_fingerprint.clone(),
+            sha256: hex::encode(signed.as_ref()),
+            verified_at: chrono::Utc::now(),
+        })
+    }
+}
+
+fn tier_rank(tier: &LicenseTier) -> u8 {
+    match tier {
+        LicenseTier::Community => 0,
+        LicenseTier::Pro => 1,
+        LicenseTier::Enterprise => 2,
+        LicenseTier::Defense => 3,
+    }
+}
+
+/// Check that `candidate` may replace the loaded `current`: same plugin, strictly newer version
+///
+/// Refusing equal and older versions stops a validly signed old build being replayed.
+fn check_upgrade(current: &PluginManifest, candidate: &PluginManifest) -> Result<(), PluginError> {
+    let invalid = |reason: String| Err(PluginError::InvalidManifest { reason });
+    if candidate.id != current.id {
+        return invalid(format!("reload of {} got plugin {}", current.id, candidate.id));
+    }
+    match (parse_version(&current.version), parse_version(&candidate.version)) {
+        (Some(loaded), Some(next)) if next > loaded => Ok(()),
+        (Some(_), Some(_)) => invalid(format!(
+            "version {} of {} is not newer than loaded {}",
+            candidate.version, candidate.id, current.version
+        )),
+        _ => invalid(format!("unparseable version {} or {}", current.version, candidate.version)),
+    }
+}
+
+/// Numeric `major.minor.patch` components, ignoring pre-release and build suffixes
+fn parse_version(version: &str) -> Option<Vec<u64>> {
+    version
+        .split(['-', '+'])
+        .next()?
+        .split('.')
+        .map(|part| part.parse().ok())
+        .collect()
+}
+
+/// Highest classification a plugin may observe under `tier`; `None` is unrestricted
+fn max_plugin_classification(tier: &LicenseTier) -> Option<ClassificationLevel> {
+    match tier {
+        LicenseTier::Community => Some(ClassificationLevel::Internal),
+        LicenseTier::Pro => Some(ClassificationLevel::Confidential),
+        LicenseTier::Enterprise => Some(ClassificationLevel::Secret),
+        LicenseTier::Defense => None,
+    }
+}
+
+/// The permission `capability` needs that `tier` doesn't grant, if any
+fn unlicensed_capability(capability: &PluginCapability, tier: &LicenseTier) -> Option<&'static str> {
+    let rank = tier_rank(tier);
+    let (pro, enterprise, defense) = (1, 2, 3);
+
+    match capability {
+        PluginCapability::DatabaseAccess { admin: true, .. } if rank < defense => Some("database_admin"),
+        PluginCapability::DatabaseAccess { write: true, .. } if rank < pro => Some("database_write"),
+        PluginCapability::DatabaseAccess { classification_levels, .. } => {
+            let max = max_plugin_classification(tier)?;
+            classification_levels.iter()
+                .any(|level| !max.dominates(level))
+                .then_some("database_classification")
+        }
+        PluginCapability::NetworkAccess { inbound: true, .. } if rank < enterprise => Some("network_inbound"),
+        PluginCapability::NetworkAccess { outbound: true, .. } if rank < pro => Some("network_outbound"),
+        PluginCapability::FileSystemAccess { write_paths, .. } if !write_paths.is_empty() && rank < pro => {
+            Some("filesystem_write")
+        }
+        PluginCapability::CryptographicAccess { key_management: true, .. } if rank < enterprise => {
+            Some("crypto_key_management")
+        }
+        PluginCapability::CryptographicAccess { signing: true, .. } if rank < pro => Some("crypto_signing"),
+        PluginCapability::ObservabilityAccess { audit_read: true, .. } if rank < enterprise => Some("audit_read"),
+        PluginCapability::ObservabilityAccess { forensic_write: true, .. } if rank < enterprise => {
+            Some("forensic_write")
+        }
+        _ => None,
+    }
+}
+
 /// Loaded plugin runtime information
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct LoadedPlugin {
     pub manifest: PluginManifest,
     pub plugin_path: PathBuf,
     pub load_time: chrono::DateTime<chrono::Utc>,
     pub status: PluginStatus,
     pub runtime_context: PluginRuntimeContext,
+    /// Compiled module, for plugins whose entry point is WASM
+    #[cfg(feature = "plugins_wasm")]
+    pub wasm: Option<Arc<crate::plugin_wasm::WasmPlugin>>,
 }
 
 /// Plugin runtime status
@@ -223,12 +576,13 @@ pub enum PluginStatus {
 }
 
 /// Plugin runtime context and sandboxing
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct PluginRuntimeContext {
     pub plugin_id: String,
     pub sandbox_config: SandboxConfig,
     pub resource_limits: ResourceLimits,
     pub granted_permissions: Vec<PluginPermission>,
+    pub capabilities: PluginCapabilities,
 }
 
 /// Sandbox configuration for plugin isolation
@@ -298,11 +652,35 @@ pub enum PluginError {
         error: String 
     },
     
+    #[error("Capability denied for plugin: {plugin_id}, {reason}")]
+    CapabilityDenied {
+        plugin_id: String,
+        reason: String
+    },
+    
     #[error("Sandbox violation by plugin: {plugin_id}, violation: {violation}")]
     SandboxViolation { 
         plugin_id: String, 
         violation: String 
     },
+    
+    #[error("Plugin execution failed: {plugin_id}, error: {error}")]
+    ExecutionFailed {
+        plugin_id: String,
+        error: String
+    },
+    
+    #[error("Plugin not accepting calls: {plugin_id}, status: {status}")]
+    NotAccepting {
+        plugin_id: String,
+        status: String
+    },
+    
+    #[error("Plugin calls still in flight: {plugin_id}, after waiting {waited:?}")]
+    DrainTimeout {
+        plugin_id: String,
+        waited: Duration
+    },
 }
 
 // Allow converting forensic logging errors into plugin errors for convenient `?` use
@@ -312,6 +690,194 @@ impl From<crate::observability::ForensicError> for PluginError {
     }
 }
 
+/// How long unload and reload wait for in-flight calls by default
+pub const DEFAULT_DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Loaded plugins, each in its own slot
+///
+/// A slot carries the plugin's status and a gate every call holds while it runs, so a
+/// plugin can be drained, failed or swapped without touching any other plugin.
+#[derive(Debug)]
+pub struct PluginRegistry {
+    slots: RwLock<HashMap<String, Arc<PluginSlot>>>,
+    drain_timeout: Duration,
+}
+
+#[derive(Debug)]
+struct PluginSlot {
+    plugin: LoadedPlugin,
+    status: parking_lot::Mutex<PluginStatus>,
+    /// Calls hold a read guard; draining takes the write guard
+    calls: Arc<RwLock<()>>,
+}
+
+impl PluginSlot {
+    fn new(mut plugin: LoadedPlugin) -> Arc<Self> {
+        let status = std::mem::replace(&mut plugin.status, PluginStatus::Active);
+        Arc::new(Self {
+            plugin,
+            status: parking_lot::Mutex::new(status),
+            calls: Arc::new(RwLock::new(())),
+        })
+    }
+
+    fn status(&self) -> PluginStatus {
+        self.status.lock().clone()
+    }
+
+    fn set_status(&self, status: PluginStatus) {
+        *self.status.lock() = status;
+    }
+}
+
+/// A call running in a plugin; unloading the plugin waits until every guard is dropped
+#[derive(Debug)]
+pub struct PluginCallGuard {
+    slot: Arc<PluginSlot>,
+    _call: tokio::sync::OwnedRwLockReadGuard<()>,
+}
+
+impl PluginCallGuard {
+    pub fn plugin(&self) -> &LoadedPlugin {
+        &self.slot.plugin
+    }
+
+    /// Mark the plugin failed; it takes no further calls until reloaded
+    pub fn fail(&self, reason: &str) {
+        self.slot.set_status(PluginStatus::Error(reason.to_string()));
+    }
+}
+
+impl Default for PluginRegistry {
+    fn default() -> Self {
+        Self::new(DEFAULT_DRAIN_TIMEOUT)
+    }
+}
+
+impl PluginRegistry {
+    pub fn new(drain_timeout: Duration) -> Self {
+        Self {
+            slots: RwLock::new(HashMap::new()),
+            drain_timeout,
+        }
+    }
+
+    /// Register a newly loaded plugin
+    pub async fn insert(&self, plugin: LoadedPlugin) -> Result<(), PluginError> {
+        let mut slots = self.slots.write().await;
+        let plugin_id = plugin.manifest.id.clone();
+        if slots.contains_key(&plugin_id) {
+            return Err(PluginError::LoadingFailed {
+                plugin_id,
+                error: "already loaded; reload it instead".to_string(),
+            });
+        }
+        slots.insert(plugin_id, PluginSlot::new(plugin));
+        Ok(())
+    }
+
+    /// Start a call into an active plugin
+    pub async fn begin_call(&self, plugin_id: &str) -> Result<PluginCallGuard, PluginError> {
+        let slot = self.slot(plugin_id).await?;
+        let not_accepting = |status: PluginStatus| PluginError::NotAccepting {
+            plugin_id: plugin_id.to_string(),
+            status: format!("{:?}", status),
+        };
+
+        let status = slot.status();
+        if !matches!(status, PluginStatus::Active) {
+            return Err(not_accepting(status));
+        }
+        let call = slot.calls.clone().read_owned().await;
+
+        // A drain may have begun while this call waited for the gate
+        let status = slot.status();
+        if !matches!(status, PluginStatus::Active) {
+            return Err(not_accepting(status));
+        }
+        Ok(PluginCallGuard { slot, _call: call })
+    }
+
+    /// Stop new calls, wait for in-flight ones, then remove the plugin
+    ///
+    /// If calls are still running after the drain timeout the plugin is left loaded and active.
+    pub async fn remove(&self, plugin_id: &str) -> Result<LoadedPlugin, PluginError> {
+        let slot = self.slot(plugin_id).await?;
+        let _drained = self.drain(&slot, plugin_id).await?;
+        self.slots.write().await.remove(plugin_id);
+        Ok(self.release(slot))
+    }
+
+    /// Drain the loaded version of `plugin`, then swap `plugin` in its place
+    ///
+    /// Returns the version that was replaced. On a drain timeout the old version stays active.
+    pub async fn replace(&self, plugin: LoadedPlugin) -> Result<LoadedPlugin, PluginError> {
+        let plugin_id = plugin.manifest.id.clone();
+        let old = self.slot(&plugin_id).await?;
+        let _drained = self.drain(&old, &plugin_id).await?;
+        self.slots.write().await.insert(plugin_id, PluginSlot::new(plugin));
+        Ok(self.release(old))
+    }
+
+    pub async fn manifest(&self, plugin_id: &str) -> Option<PluginManifest> {
+        self.slots.read().await.get(plugin_id).map(|slot| slot.plugin.manifest.clone())
+    }
+
+    pub async fn capabilities(&self, plugin_id: &str) -> Result<PluginCapabilities, PluginError> {
+        Ok(self.slot(plugin_id).await?.plugin.runtime_context.capabilities.clone())
+    }
+
+    pub async fn list(&self) -> Vec<(String, PluginStatus)> {
+        self.slots
+            .read()
+            .await
+            .iter()
+            .map(|(id, slot)| (id.clone(), slot.status()))
+            .collect()
+    }
+
+    async fn slot(&self, plugin_id: &str) -> Result<Arc<PluginSlot>, PluginError> {
+        self.slots
+            .read()
+            .await
+            .get(plugin_id)
+            .cloned()
+            .ok_or_else(|| PluginError::PluginNotFound { plugin_id: plugin_id.to_string() })
+    }
+
+    /// Mark the slot unloading and wait for its calls to finish, restoring the status on timeout
+    async fn drain(&self, slot: &Arc<PluginSlot>, plugin_id: &str) -> Result<tokio::sync::OwnedRwLockWriteGuard<()>, PluginError> {
+        let previous = {
+            let mut status = slot.status.lock();
+            if matches!(*status, PluginStatus::Unloading) {
+                return Err(PluginError::NotAccepting {
+                    plugin_id: plugin_id.to_string(),
+                    status: format!("{:?}", *status),
+                });
+            }
+            std::mem::replace(&mut *status, PluginStatus::Unloading)
+        };
+
+        match tokio::time::timeout(self.drain_timeout, slot.calls.clone().write_owned()).await {
+            Ok(drained) => Ok(drained),
+            Err(_) => {
+                slot.set_status(previous);
+                Err(PluginError::DrainTimeout {
+                    plugin_id: plugin_id.to_string(),
+                    waited: self.drain_timeout,
+                })
+            }
+        }
+    }
+
+    /// The drained slot's plugin, with the status it was drained in
+    fn release(&self, slot: Arc<PluginSlot>) -> LoadedPlugin {
+        let mut plugin = slot.plugin.clone();
+        plugin.status = slot.status();
+        plugin
+    }
+}
+
 impl EnterprisePluginSystem {
     /// Create new enterprise plugin system
     pub async fn new(
@@ -320,51 +886,55 @@ impl EnterprisePluginSystem {
         forensic_logger: Arc<ForensicLogger>,
     ) -> Result<Self, PluginError> {
         let system = Self {
-            loaded_plugins: Arc::new(RwLock::new(HashMap::new())),
-            verification_keys: Self::load_verification_keys().await?,
+            plugins: PluginRegistry::default(),
+            verifier: PluginVerifier::new(Self::load_verification_keys().await?),
             security_manager,
             license_manager,
             forensic_logger,
             sandbox_configs: Self::create_default_sandbox_configs(),
+            #[cfg(feature = "plugins_wasm")]
+            wasm_runtime: crate::plugin_wasm::WasmPluginRuntime::new()?,
+            #[cfg(feature = "plugins_wasm")]
+            host_services: None,
+            #[cfg(feature = "plugins_wasm")]
+            pii_detector: None,
         };
         
         Ok(system)
     }
     
+    /// Carry out WASM plugins' entity reads through `database` and fetches through `transport`
+    #[cfg(feature = "plugins_wasm")]
+    pub fn with_host_services(
+        mut self,
+        database: Arc<crate::database::DatabaseManager>,
+        transport: Arc<crate::networking::SecureNetworkTransport>,
+    ) -> Self {
+        self.host_services = Some((database, transport));
+        self
+    }
+
+    /// Redact PII found by `detector` from entity data handed to WASM plugins
+    #[cfg(feature = "plugins_wasm")]
+    pub fn with_pii_detector(mut self, detector: Arc<crate::observability::PiiDetector>) -> Self {
+        self.pii_detector = Some(detector);
+        self
+    }
+    
     /// Load and verify a plugin from disk
     pub async fn load_plugin<P: AsRef<Path>>(
         &self,
         plugin_path: P,
         app_state: &AppState,
     ) -> Result<String, PluginError> {
-        let plugin_path = plugin_path.as_ref();
-        
-        // 1. Load and parse plugin manifest
-        let manifest = self.load_manifest(plugin_path).await?;
-        
-        // 2. Verify cryptographic signature
-        self.verify_plugin_signature(&manifest, plugin_path).await?;
-        
-        // 3. Check license requirements
-        self.verify_license_requirements(&manifest).await?;
-        
-        // 4. Validate plugin permissions
-        self.validate_permissions(&manifest).await?;
-        
-        // 5. Verify file integrity
-        self.verify_file_integrity(&manifest, plugin_path).await?;
+        let loaded_plugin = self.prepare_plugin(plugin_path.as_ref()).await?;
+        let manifest = loaded_plugin.manifest.clone();
         
-        // 6. Create runtime context and sandbox
-        let runtime_context = self.create_runtime_context(&manifest).await?;
-        
-        // 7. Load plugin into sandbox
-        let loaded_plugin = self.load_plugin_runtime(&manifest, plugin_path.to_path_buf(), runtime_context).await?;
-        
-        // 8. Register plugin
+        // 6. Register plugin
         let plugin_id = manifest.id.clone();
-        self.loaded_plugins.write().await.insert(plugin_id.clone(), loaded_plugin);
+        self.plugins.insert(loaded_plugin).await?;
         
-        // 9. Log plugin loading for audit
+        // 7. Log plugin loading for audit
         self.forensic_logger.log_plugin_operation(
             "plugin_loaded",
             &plugin_id,
@@ -383,101 +953,226 @@ impl EnterprisePluginSystem {
         Ok(plugin_id)
     }
     
+    /// Upgrade a loaded plugin to the version at `plugin_path` without a restart
+    ///
+    /// The new version is verified before anything changes and must be newer than the loaded
+    /// one. The old version stops taking calls and its in-flight calls drain before the swap;
+    /// if they don't drain in time the old version stays loaded.
+    pub async fn reload_plugin<P: AsRef<Path>>(
+        &self,
+        plugin_id: &str,
+        plugin_path: P,
+        app_state: &AppState,
+    ) -> Result<(), PluginError> {
+        let current = self.plugins.manifest(plugin_id).await
+            .ok_or_else(|| PluginError::PluginNotFound { plugin_id: plugin_id.to_string() })?;
+        let replacement = self.prepare_plugin(plugin_path.as_ref()).await?;
+        check_upgrade(&current, &replacement.manifest)?;
+
+        let new_version = replacement.manifest.version.clone();
+        let result = self.plugins.replace(replacement).await;
+        let (event, details) = match &result {
+            Ok(_) => ("plugin_reloaded", serde_json::json!({
+                "from_version": current.version,
+                "to_version": new_version,
+            })),
+            Err(e) => ("plugin_reload_rejected", serde_json::json!({
+                "from_version": current.version,
+                "to_version": new_version,
+                "error": e.to_string(),
+            })),
+        };
+        let logged = self.forensic_logger.log_plugin_operation(event, plugin_id, &app_state.context, details).await;
+
+        // The old version is out of the registry once replaced, so free it even if the audit write failed
+        let old = result?;
+        self.cleanup_plugin_resources(&old).await?;
+        logged?;
+        tracing::info!(plugin_id = %plugin_id, "Plugin reloaded: {} -> {}", current.version, new_version);
+        Ok(())
+    }
+    
+    /// Read, verify and sandbox the plugin at `plugin_path`, ready to register
+    async fn prepare_plugin(&self, plugin_path: &Path) -> Result<LoadedPlugin, PluginError> {
+        // 1. Load and parse plugin manifest
+        let manifest = self.load_manifest(plugin_path).await?;
+        
+        // 2. Verify the signature over the manifest and every plugin file, and its capabilities against the license
+        let entry_point = manifest.entry_points.main.as_ref()
+            .ok_or_else(|| PluginError::InvalidManifest {
+                reason: "Signed plugins must declare a main entry point".to_string()
+            })?;
+        let artifacts = read_plugin_artifacts(plugin_path).await?;
+        if !artifacts.contains_key(entry_point) {
+            return Err(PluginError::InvalidManifest {
+                reason: format!("Plugin file not found: {}", entry_point)
+            });
+        }
+        self.verify_plugin(&manifest, &artifacts).await?;
+        
+        // 3. Verify file integrity
+        self.verify_file_integrity(&manifest, &artifacts)?;
+        
+        // 4. Create runtime context and sandbox
+        let runtime_context = self.create_runtime_context(&manifest).await?;
+        
+        // 5. Load plugin into sandbox
+        self.load_plugin_runtime(&manifest, plugin_path.to_path_buf(), &artifacts, runtime_context).await
+    }
+    
+    /// Verify a plugin's signature and capabilities against the current license tier
+    ///
+    /// Every outcome is recorded as a forensic security event. Fails closed: if the
+    /// event can't be recorded, an otherwise valid plugin is rejected too.
+    pub async fn verify_plugin(
+        &self,
+        manifest: &PluginManifest,
+        artifacts: &BTreeMap<String, String>,
+    ) -> Result<VerifiedPlugin, PluginError> {
+        let tier = self.license_manager.get_tier().await;
+        let result = self.verifier.verify(manifest, artifacts, &tier);
+
+        let (event_type, description) = match &result {
+            Ok(verified) => (
+                "plugin_verified",
+                format!("Plugin {} {} verified (publisher {}, sha256 {})",
+                    manifest.id, manifest.version, verified.publisher, verified.sha256),
+            ),
+            Err(e) => ("plugin_rejected", format!("Plugin {} {} rejected: {}", manifest.id, manifest.version, e)),
+        };
+        if let Err(e) = self.forensic_logger.log_security_event(event_type, &description, "system").await {
+            tracing::error!(plugin_id = %manifest.id, "Failed to record plugin verification: {}", e);
+            return Err(result.err().unwrap_or_else(|| PluginError::LoadingFailed {
+                plugin_id: manifest.id.clone(),
+                error: format!("Verification could not be audited: {}", e),
+            }));
+        }
+
+        if let Err(e) = &result {
+            tracing::warn!(plugin_id = %manifest.id, "Plugin rejected: {}", e);
+        }
+        result
+    }
+    
     /// Unload a plugin
+    ///
+    /// New calls are refused at once; the plugin is removed after its in-flight calls drain.
+    /// If they don't drain in time the unload is rejected and the plugin stays active.
     pub async fn unload_plugin(
         &self,
         plugin_id: &str,
         app_state: &AppState,
     ) -> Result<(), PluginError> {
-        let mut plugins = self.loaded_plugins.write().await;
+        let plugin = match self.plugins.remove(plugin_id).await {
+            Ok(plugin) => plugin,
+            Err(e @ PluginError::DrainTimeout { .. }) => {
+                self.forensic_logger.log_plugin_operation(
+                    "plugin_unload_rejected",
+                    plugin_id,
+                    &app_state.context,
+                    serde_json::json!({ "error": e.to_string() })
+                ).await?;
+                return Err(e);
+            }
+            Err(e) => return Err(e),
+        };
         
-        if let Some(mut plugin) = plugins.remove(plugin_id) {
-            // Update status to unloading
-            plugin.status = PluginStatus::Unloading;
-            
-            // Perform cleanup operations
-            self.cleanup_plugin_resources(&plugin).await?;
-            
-            // Log unloading for audit
-            self.forensic_logger.log_plugin_operation(
-                "plugin_unloaded",
-                plugin_id,
-                &app_state.context,
-                serde_json::json!({
-                    "plugin_name": plugin.manifest.name,
-                    "load_duration": chrono::Utc::now().signed_duration_since(plugin.load_time).num_seconds(),
-                })
-            ).await?;
-            
-            tracing::info!(plugin_id = %plugin_id, "Plugin unloaded successfully");
-            Ok(())
-        } else {
-            Err(PluginError::PluginNotFound { plugin_id: plugin_id.to_string() })
+        // Perform cleanup operations
+        self.cleanup_plugin_resources(&plugin).await?;
+        
+        // Log unloading for audit
+        self.forensic_logger.log_plugin_operation(
+            "plugin_unloaded",
+            plugin_id,
+            &app_state.context,
+            serde_json::json!({
+                "plugin_name": plugin.manifest.name,
+                "version": plugin.manifest.version,
+                "load_duration": chrono::Utc::now().signed_duration_since(plugin.load_time).num_seconds(),
+            })
+        ).await?;
+        
+        tracing::info!(plugin_id = %plugin_id, "Plugin unloaded successfully");
+        Ok(())
+    }
+    
+    /// Check a call from a loaded plugin into the host against its granted capabilities
+    ///
+    /// Host bridges (database, network) call this before acting for a plugin; denials
+    /// are recorded as forensic security events.
+    pub async fn authorize_plugin_operation(
+        &self,
+        plugin_id: &str,
+        operation: &PluginOperation<'_>,
+    ) -> Result<(), PluginError> {
+        let capabilities = self.plugins.capabilities(plugin_id).await?;
+
+        let result = capabilities.authorize(plugin_id, operation);
+        if let Err(e) = &result {
+            tracing::warn!(plugin_id = %plugin_id, "{}", e);
+            self.forensic_logger.log_security_event("plugin_capability_denied", &e.to_string(), "system").await?;
         }
+        result
     }
     
     /// Get plugin information
     pub async fn get_plugin_info(&self, plugin_id: &str) -> Option<PluginManifest> {
-        self.loaded_plugins
-            .read()
-            .await
-            .get(plugin_id)
-            .map(|plugin| plugin.manifest.clone())
+        self.plugins.manifest(plugin_id).await
     }
     
     /// List all loaded plugins
     pub async fn list_plugins(&self) -> Vec<(String, PluginStatus)> {
-        self.loaded_plugins
-            .read()
-            .await
-            .iter()
-            .map(|(id, plugin)| (id.clone(), plugin.status.clone()))
-            .collect()
+        self.plugins.list().await
     }
     
     /// Execute plugin command
+    ///
+    /// The plugin acts for `caller`: its host calls are checked against the caller's clearance
+    /// as well as its own capabilities.
     pub async fn execute_plugin_command(
         &self,
         plugin_id: &str,
         command: &str,
         parameters: serde_json::Value,
+        caller: &DatabaseContext,
         app_state: &AppState,
     ) -> Result<serde_json::Value, PluginError> {
-        let plugins = self.loaded_plugins.read().await;
+        let call = self.plugins.begin_call(plugin_id).await?;
+        let plugin = call.plugin();
         
-        if let Some(plugin) = plugins.get(plugin_id) {
-            // Verify command permissions
-            self.verify_command_permissions(plugin, command).await?;
-            
-            // Execute in sandbox
-            let result = self.execute_sandboxed_command(
-                plugin,
-                command,
-                parameters,
-                app_state,
-            ).await?;
-            
-            // Log command execution
-            self.forensic_logger.log_plugin_operation(
-                "plugin_command_executed",
-                plugin_id,
-                &app_state.context,
-                serde_json::json!({
-                    "command": command,
-                    "success": true,
-                })
-            ).await?;
-            
-            Ok(result)
-        } else {
-            Err(PluginError::PluginNotFound { plugin_id: plugin_id.to_string() })
-        }
+        // Verify command permissions
+        self.verify_command_permissions(plugin, command).await?;
+        
+        // Execute in sandbox; a plugin that breaks its sandbox takes no more calls until reloaded
+        let result = match self.execute_sandboxed_command(plugin, command, parameters, caller, app_state).await {
+            Ok(result) => result,
+            Err(e) => {
+                if matches!(e, PluginError::SandboxViolation { .. } | PluginError::ExecutionFailed { .. }) {
+                    call.fail(&e.to_string());
+                    tracing::warn!(plugin_id = %plugin_id, "Plugin failed and was disabled: {}", e);
+                }
+                return Err(e);
+            }
+        };
+        
+        // Log command execution
+        self.forensic_logger.log_plugin_operation(
+            "plugin_command_executed",
+            plugin_id,
+            &app_state.context,
+            serde_json::json!({
+                "command": command,
+                "success": true,
+            })
+        ).await?;
+        
+        Ok(result)
     }
     
     // Private helper methods
     
     async fn load_verification_keys() -> Result<HashMap<String, Vec<u8>>, PluginError> {
-        // Load public keys for plugin signature verification
+        // Load raw Ed25519 publisher keys for plugin signature verification
         // In production, these would be embedded or loaded from secure storage
         let mut keys = HashMap::new();
         
@@ -531,7 +1226,7 @@ impl EnterprisePluginSystem {
     }
     
     async fn load_manifest<P: AsRef<Path>>(&self, plugin_path: P) -> Result<PluginManifest, PluginError> {
-        let manifest_path = plugin_path.as_ref().join("manifest.json");
+        let manifest_path = plugin_path.as_ref().join(MANIFEST_FILE);
         let manifest_content = tokio::fs::read_to_string(&manifest_path)
             .await
             .map_err(|e| PluginError::InvalidManifest { 
@@ -544,124 +1239,19 @@ impl EnterprisePluginSystem {
             })
     }
     
-    async fn verify_plugin_signature(
-        &self,
-        manifest: &PluginManifest,
-        plugin_path: &Path,
-    ) -> Result<(), PluginError> {
-        // Get verification key
-        let key_data = self.verification_keys
-            .get(&manifest.key_fingerprint)
-            .ok_or_else(|| PluginError::SignatureVerificationFailed { 
-                plugin_id: manifest.id.clone() 
-            })?;
-        
-        // Parse public key
-        let public_key = signature::UnparsedPublicKey::new(
-            &signature::ECDSA_P256_SHA256_ASN1,
-            key_data
-        );
-        
-        // Create manifest hash for verification
-        let manifest_json = serde_json::to_string(manifest).unwrap();
-        let manifest_hash = digest::digest(&digest::SHA256, manifest_json.as_bytes());
-        
-        // Verify signature
-        let signature_bytes = general_purpose::STANDARD
-            .decode(&manifest.signature)
-            .map_err(|_| PluginError::SignatureVerificationFailed { 
-                plugin_id: manifest.id.clone() 
-            })?;
-        
-        public_key
-            .verify(manifest_hash.as_ref(), &signature_bytes)
-            .map_err(|_| PluginError::SignatureVerificationFailed { 
-                plugin_id: manifest.id.clone() 
-            })?;
-        
-        Ok(())
-    }
-    
-    async fn verify_license_requirements(&self, manifest: &PluginManifest) -> Result<(), PluginError> {
-        let current_license = self.license_manager.get_current_license().await;
-        
-        // Check if current license meets plugin requirements
-        match (&current_license.tier, &manifest.required_license) {
-            (LicenseTier::Community, LicenseTier::Enterprise | LicenseTier::Defense) => {
-                return Err(PluginError::InsufficientLicense {
-                    plugin_id: manifest.id.clone(),
-                    required_license: manifest.required_license.clone(),
-                });
-            },
-            (LicenseTier::Enterprise, LicenseTier::Defense) => {
-                return Err(PluginError::InsufficientLicense {
-                    plugin_id: manifest.id.clone(),
-                    required_license: manifest.required_license.clone(),
-                });
-            },
-            _ => {} // License is sufficient
-        }
-        
-        Ok(())
-    }
-    
-    async fn validate_permissions(&self, manifest: &PluginManifest) -> Result<(), PluginError> {
-        // Validate that plugin permissions are reasonable and allowed
-        for capability in &manifest.capabilities {
-            self.validate_capability(capability, manifest).await?;
-        }
-        
-        Ok(())
-    }
-    
-    async fn validate_capability(
+    fn verify_file_integrity(
         &self,
-        capability: &PluginCapability,
         manifest: &PluginManifest,
-    ) -> Result<(), PluginError> {
-        match capability {
-            PluginCapability::DatabaseAccess { admin: true, .. } => {
-                // Admin database access requires Defense license
-                if !matches!(manifest.required_license, LicenseTier::Defense) {
-                    return Err(PluginError::PermissionDenied {
-                        plugin_id: manifest.id.clone(),
-                        permission: "database_admin".to_string(),
-                    });
-                }
-            },
-            PluginCapability::CryptographicAccess { key_management: true, .. } => {
-                // Key management requires Enterprise or Defense license
-                if matches!(manifest.required_license, LicenseTier::Community) {
-                    return Err(PluginError::PermissionDenied {
-                        plugin_id: manifest.id.clone(),
-                        permission: "crypto_key_management".to_string(),
-                    });
-                }
-            },
-            _ => {} // Other capabilities are generally allowed
-        }
-        
-        Ok(())
-    }
-    
-    async fn verify_file_integrity(
-        &self,
-        manifest: &PluginManifest,
-        plugin_path: &Path,
+        artifacts: &BTreeMap<String, String>,
     ) -> Result<(), PluginError> {
         // Verify file hashes match manifest
         for (file_path, expected_hash) in &manifest.file_hashes {
-            let full_path = plugin_path.join(file_path);
-            let file_content = tokio::fs::read(&full_path)
-                .await
-                .map_err(|_| PluginError::InvalidManifest {
+            let actual_hash_hex = artifacts.get(file_path)
+                .ok_or_else(|| PluginError::InvalidManifest {
                     reason: format!("Plugin file not found: {}", file_path)
                 })?;
             
-            let actual_hash = digest::digest(&digest::SHA256, &file_content);
-            let actual_hash_hex = hex::encode(actual_hash.as_ref());
-            
-            if &actual_hash_hex != expected_hash {
+            if actual_hash_hex != expected_hash {
                 return Err(PluginError::InvalidManifest {
                     reason: format!("File integrity check failed for: {}", file_path)
                 });
@@ -681,6 +1271,7 @@ impl EnterprisePluginSystem {
             sandbox_config,
             resource_limits,
             granted_permissions,
+            capabilities: manifest.sandbox.clone(),
         })
     }
     
@@ -716,8 +1307,14 @@ impl EnterprisePluginSystem {
         &self,
         manifest: &PluginManifest,
         plugin_path: PathBuf,
+        artifacts: &BTreeMap<String, String>,
         runtime_context: PluginRuntimeContext,
     ) -> Result<LoadedPlugin, PluginError> {
+        #[cfg(feature = "plugins_wasm")]
+        let wasm = self.compile_wasm(manifest, &plugin_path, artifacts, &runtime_context).await?;
+        #[cfg(not(feature = "plugins_wasm"))]
+        let _ = artifacts;
+        
         // Create loaded plugin structure
         Ok(LoadedPlugin {
             manifest: manifest.clone(),
@@ -725,9 +1322,45 @@ impl EnterprisePluginSystem {
             load_time: chrono::Utc::now(),
             status: PluginStatus::Active,
             runtime_context,
+            #[cfg(feature = "plugins_wasm")]
+            wasm,
         })
     }
     
+    /// Compile a `.wasm` entry point, linking only the host functions its sandbox grants
+    #[cfg(feature = "plugins_wasm")]
+    async fn compile_wasm(
+        &self,
+        manifest: &PluginManifest,
+        plugin_path: &Path,
+        artifacts: &BTreeMap<String, String>,
+        runtime_context: &PluginRuntimeContext,
+    ) -> Result<Option<Arc<crate::plugin_wasm::WasmPlugin>>, PluginError> {
+        let Some(entry_point) = manifest.entry_points.main.as_ref().filter(|entry| entry.ends_with(".wasm")) else {
+            return Ok(None);
+        };
+        if self.host_services.is_none() {
+            return Err(PluginError::LoadingFailed {
+                plugin_id: manifest.id.clone(),
+                error: "WASM plugins need host services, and none are configured".to_string(),
+            });
+        }
+        
+        // Compiled as read now, so it must still be the file the signature covered
+        let bytes = tokio::fs::read(plugin_path.join(entry_point))
+            .await
+            .map_err(|_| PluginError::InvalidManifest {
+                reason: format!("Plugin file not found: {}", entry_point)
+            })?;
+        if artifacts.get(entry_point) != Some(&hex::encode(digest::digest(&digest::SHA256, &bytes).as_ref())) {
+            return Err(PluginError::SignatureVerificationFailed { plugin_id: manifest.id.clone() });
+        }
+        
+        let limits = crate::plugin_wasm::WasmLimits::from(&runtime_context.resource_limits);
+        let plugin = self.wasm_runtime.load(&manifest.id, &bytes, runtime_context.capabilities.clone(), limits)?;
+        Ok(Some(Arc::new(plugin)))
+    }
+    
     async fn cleanup_plugin_resources(&self, plugin: &LoadedPlugin) -> Result<(), PluginError> {
         // Cleanup any resources allocated to the plugin
         tracing::debug!(plugin_id = %plugin.manifest.id, "Cleaning up plugin resources");
@@ -776,8 +1409,30 @@ impl EnterprisePluginSystem {
         plugin: &LoadedPlugin,
         command: &str,
         parameters: serde_json::Value,
+        caller: &DatabaseContext,
         app_state: &AppState,
     ) -> Result<serde_json::Value, PluginError> {
+        #[cfg(feature = "plugins_wasm")]
+        if let Some(wasm) = &plugin.wasm {
+            let (database, transport) = self.host_services.clone().ok_or_else(|| PluginError::NotAccepting {
+                plugin_id: plugin.manifest.id.clone(),
+                status: "no host services configured".to_string(),
+            })?;
+            let host = crate::plugin_wasm::EnginePluginHost::new(
+                &plugin.manifest.id,
+                caller,
+                wasm.capabilities(),
+                database,
+                transport,
+                crate::observability::RecorderHooks::new(app_state.forensic_logger.clone(), app_state.metrics_registry.clone()),
+                self.forensic_logger.clone(),
+            )
+            .with_pii_detector(self.pii_detector.clone());
+            return Ok(wasm.call(command, &parameters, Arc::new(host)).await?.value);
+        }
+        #[cfg(not(feature = "plugins_wasm"))]
+        let _ = (caller, app_state);
+        
         // Execute the command within the plugin's sandbox
         // This is a simplified implementation - real sandboxing would use
         // OS-level isolation mechanisms
@@ -816,6 +1471,7 @@ mod tests {
             classification_level: ClassificationLevel::Confidential,
             capabilities: vec![],
             permissions: vec![],
+            sandbox: PluginCapabilities::default(),
             entry_points: PluginEntryPoints {
                 main: None,
                 forensic_hooks: None,
@@ -835,4 +1491,337 @@ mod tests {
         assert_eq!(manifest.id, parsed.id);
         assert_eq!(manifest.name, parsed.name);
     }
+
+    fn artifacts(bytes: &[u8]) -> BTreeMap<String, String> {
+        BTreeMap::from([
+            ("plugin.wasm".to_string(), hex::encode(digest::digest(&digest::SHA256, bytes).as_ref())),
+            ("assets/icon.svg".to_string(), hex::encode(digest::digest(&digest::SHA256, b"<svg/>").as_ref())),
+        ])
+    }
+
+    fn sign(mut manifest: PluginManifest, publisher: &signature::Ed25519KeyPair, artifacts: &BTreeMap<String, String>) -> PluginManifest {
+        let signed = manifest.signing_digest(artifacts).unwrap();
+        manifest.signature = general_purpose::STANDARD.encode(publisher.sign(signed.as_ref()).as_ref());
+        manifest
+    }
+
+    fn signed_manifest(publisher: &signature::Ed25519KeyPair, bytes: &[u8]) -> PluginManifest {
+        let manifest = PluginManifest {
+            id: "network-forensics".to_string(),
+            name: "Network Forensics".to_string(),
+            version: "1.2.0".to_string(),
+            description: "Captures outbound request metadata".to_string(),
+            author: "Nodus".to_string(),
+            license: "Commercial".to_string(),
+            required_license: LicenseTier::Community,
+            classification_level: ClassificationLevel::Internal,
+            capabilities: vec![PluginCapability::DatabaseAccess {
+                read: true,
+                write: false,
+                admin: false,
+                classification_levels: vec![ClassificationLevel::Internal],
+            }],
+            permissions: vec![],
+            sandbox: PluginCapabilities::default(),
+            entry_points: PluginEntryPoints {
+                main: Some("plugin.wasm".to_string()),
+                forensic_hooks: None,
+                ui_components: None,
+                command_handlers: None,
+                background_services: None,
+            },
+            dependencies: vec![],
+            file_hashes: HashMap::new(),
+            signature: String::new(),
+            key_fingerprint: "enterprise".to_string(),
+        };
+        sign(manifest, publisher, &artifacts(bytes))
+    }
+
+    fn verifier(publisher: &signature::Ed25519KeyPair) -> PluginVerifier {
+        use ring::signature::KeyPair;
+        PluginVerifier::new(HashMap::from([
+            ("enterprise".to_string(), publisher.public_key().as_ref().to_vec()),
+        ]))
+    }
+
+    #[test]
+    fn test_tampered_plugin_rejected() {
+        let publisher = signature::Ed25519KeyPair::from_seed_unchecked(&[3u8; 32]).unwrap();
+        let bytes = b"\0asm plugin body".to_vec();
+        let manifest = signed_manifest(&publisher, &bytes);
+        let verifier = verifier(&publisher);
+
+        let verified = verifier.verify(&manifest, &artifacts(&bytes), &LicenseTier::Community).unwrap();
+        assert_eq!(verified.publisher, "enterprise");
+        assert_eq!(verified.sha256.len(), 64);
+
+        let mut tampered = bytes.clone();
+        tampered[6] ^= 0x01;
+        assert!(matches!(
+            verifier.verify(&manifest, &artifacts(&tampered), &LicenseTier::Defense),
+            Err(PluginError::SignatureVerificationFailed { .. })
+        ));
+
+        // Signed by a key nobody trusts
+        let impostor = signature::Ed25519KeyPair::from_seed_unchecked(&[4u8; 32]).unwrap();
+        let forged = signed_manifest(&impostor, &bytes);
+        assert!(matches!(
+            verifier.verify(&forged, &artifacts(&bytes), &LicenseTier::Defense),
+            Err(PluginError::SignatureVerificationFailed { .. })
+        ));
+    }
+
+    #[test]
+    fn test_signature_covers_manifest_and_every_artifact() {
+        let publisher = signature::Ed25519KeyPair::from_seed_unchecked(&[3u8; 32]).unwrap();
+        let bytes = b"\0asm plugin body".to_vec();
+        let manifest = signed_manifest(&publisher, &bytes);
+        let verifier = verifier(&publisher);
+        let rejected = |manifest: &PluginManifest, artifacts: &BTreeMap<String, String>| matches!(
+            verifier.verify(manifest, artifacts, &LicenseTier::Defense),
+            Err(PluginError::SignatureVerificationFailed { .. })
+        );
+
+        // A file other than the entry point changed, added or removed
+        let mut changed = artifacts(&bytes);
+        changed.insert("assets/icon.svg".to_string(), hex::encode(digest::digest(&digest::SHA256, b"<svg onload=x/>").as_ref()));
+        assert!(rejected(&manifest, &changed));
+        let mut added = artifacts(&bytes);
+        added.insert("loader.js".to_string(), hex::encode(digest::digest(&digest::SHA256, b"eval()").as_ref()));
+        assert!(rejected(&manifest, &added));
+        let mut removed = artifacts(&bytes);
+        removed.remove("assets/icon.svg");
+        assert!(rejected(&manifest, &removed));
+
+        // Manifest fields edited after signing
+        let mut widened = manifest.clone();
+        widened.sandbox.network_domains.push("*".to_string());
+        assert!(rejected(&widened, &artifacts(&bytes)));
+        let mut reclassified = manifest.clone();
+        reclassified.classification_level = ClassificationLevel::Secret;
+        assert!(rejected(&reclassified, &artifacts(&bytes)));
+
+        // Key order in the manifest's maps doesn't matter
+        let reparsed: PluginManifest = serde_json::from_str(&serde_json::to_string(&manifest).unwrap()).unwrap();
+        assert!(verifier.verify(&reparsed, &artifacts(&bytes), &LicenseTier::Community).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_artifacts_cover_nested_files_but_not_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        tokio::fs::write(dir.path().join(MANIFEST_FILE), b"{}").await.unwrap();
+        tokio::fs::write(dir.path().join("plugin.wasm"), b"\0asm").await.unwrap();
+        tokio::fs::create_dir(dir.path().join("assets")).await.unwrap();
+        tokio::fs::write(dir.path().join("assets").join("icon.svg"), b"<svg/>").await.unwrap();
+
+        let artifacts = read_plugin_artifacts(dir.path()).await.unwrap();
+        assert_eq!(artifacts.keys().collect::<Vec<_>>(), vec!["assets/icon.svg", "plugin.wasm"]);
+        assert_eq!(artifacts["plugin.wasm"], hex::encode(digest::digest(&digest::SHA256, b"\0asm").as_ref()));
+    }
+
+    #[test]
+    fn test_capability_overreach_rejected() {
+        let publisher = signature::Ed25519KeyPair::from_seed_unchecked(&[3u8; 32]).unwrap();
+        let bytes = b"\0asm plugin body".to_vec();
+        let artifacts = artifacts(&bytes);
+        let verifier = verifier(&publisher);
+
+        let mut manifest = signed_manifest(&publisher, &bytes);
+        manifest.capabilities.push(PluginCapability::NetworkAccess {
+            outbound: true,
+            inbound: true,
+            domains: vec!["*".to_string()],
+        });
+        let manifest = sign(manifest, &publisher, &artifacts);
+        match verifier.verify(&manifest, &artifacts, &LicenseTier::Pro) {
+            Err(PluginError::PermissionDenied { permission, .. }) => assert_eq!(permission, "network_inbound"),
+            other => panic!("expected capability overreach to be denied, got {:?}", other),
+        }
+        assert!(verifier.verify(&manifest, &artifacts, &LicenseTier::Enterprise).is_ok());
+
+        // Observing SECRET data needs more than a Pro license
+        let mut manifest = signed_manifest(&publisher, &bytes);
+        manifest.capabilities = vec![PluginCapability::DatabaseAccess {
+            read: true,
+            write: false,
+            admin: false,
+            classification_levels: vec![ClassificationLevel::Secret],
+        }];
+        let manifest = sign(manifest, &publisher, &artifacts);
+        assert!(matches!(
+            verifier.verify(&manifest, &artifacts, &LicenseTier::Pro),
+            Err(PluginError::PermissionDenied { .. })
+        ));
+    }
+
+    fn user_reader() -> PluginCapabilities {
+        PluginCapabilities {
+            network_domains: vec!["*.nodus.dev".to_string()],
+            readable_entity_types: vec!["user".to_string()],
+            writable_entity_types: vec![],
+            max_classification: ClassificationLevel::Confidential,
+        }
+    }
+
+    #[test]
+    fn test_read_only_user_plugin_denied_write_and_secret() {
+        let capabilities = user_reader();
+        let internal = ClassificationLevel::Internal;
+        let secret = ClassificationLevel::Secret;
+
+        assert!(capabilities.authorize("reader", &PluginOperation::ReadEntity {
+            entity_type: "user",
+            classification: &internal,
+        }).is_ok());
+        assert!(matches!(
+            capabilities.authorize("reader", &PluginOperation::WriteEntity { entity_type: "user", classification: &internal }),
+            Err(PluginError::CapabilityDenied { .. })
+        ));
+        assert!(matches!(
+            capabilities.authorize("reader", &PluginOperation::ReadEntity { entity_type: "user", classification: &secret }),
+            Err(PluginError::CapabilityDenied { .. })
+        ));
+        assert!(matches!(
+            capabilities.authorize("reader", &PluginOperation::ReadEntity { entity_type: "report", classification: &internal }),
+            Err(PluginError::CapabilityDenied { .. })
+        ));
+
+        assert!(capabilities.authorize("reader", &PluginOperation::Network { domain: "api.nodus.dev" }).is_ok());
+        assert!(capabilities.authorize("reader", &PluginOperation::Network { domain: "nodus.dev.evil.test" }).is_err());
+    }
+
+    #[test]
+    fn test_capabilities_must_be_licensed() {
+        let capabilities = user_reader();
+        assert!(matches!(
+            capabilities.licensed_for("reader", &LicenseTier::Community),
+            Err(PluginError::CapabilityDenied { .. })
+        ));
+        assert!(capabilities.licensed_for("reader", &LicenseTier::Pro).is_ok());
+
+        // Missing from an older manifest: no network, no writes, unclassified only
+        let manifest: PluginCapabilities = serde_json::from_str("{}").unwrap();
+        assert!(manifest.licensed_for("legacy", &LicenseTier::Community).is_ok());
+        assert_eq!(manifest.max_classification, ClassificationLevel::Unclassified);
+    }
+
+    fn loaded(id: &str, version: &str) -> LoadedPlugin {
+        let manifest = PluginManifest {
+            id: id.to_string(),
+            name: id.to_string(),
+            version: version.to_string(),
+            description: String::new(),
+            author: "Nodus".to_string(),
+            license: "Commercial".to_string(),
+            required_license: LicenseTier::Community,
+            classification_level: ClassificationLevel::Internal,
+            capabilities: vec![],
+            permissions: vec![],
+            sandbox: PluginCapabilities::default(),
+            entry_points: PluginEntryPoints {
+                main: Some("plugin.wasm".to_string()),
+                forensic_hooks: None,
+                ui_components: None,
+                command_handlers: None,
+                background_services: None,
+            },
+            dependencies: vec![],
+            file_hashes: HashMap::new(),
+            signature: String::new(),
+            key_fingerprint: "enterprise".to_string(),
+        };
+        LoadedPlugin {
+            runtime_context: PluginRuntimeContext {
+                plugin_id: id.to_string(),
+                sandbox_config: SandboxConfig {
+                    isolation_level: IsolationLevel::Strict,
+                    allowed_syscalls: vec![],
+                    memory_limit: 64 * 1024 * 1024,
+                    cpu_limit: 25.0,
+                    network_isolation: true,
+                    filesystem_isolation: true,
+                },
+                resource_limits: ResourceLimits {
+                    max_memory: 64 * 1024 * 1024,
+                    max_cpu_percent: 50.0,
+                    max_file_handles: 100,
+                    max_network_connections: 10,
+                    execution_timeout: Duration::from_secs(30),
+                },
+                granted_permissions: vec![],
+                capabilities: manifest.sandbox.clone(),
+            },
+            manifest,
+            plugin_path: PathBuf::from(id),
+            load_time: chrono::Utc::now(),
+            status: PluginStatus::Active,
+            #[cfg(feature = "plugins_wasm")]
+            wasm: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unload_rejected_while_call_in_flight() {
+        let registry = Arc::new(PluginRegistry::new(Duration::from_millis(100)));
+        registry.insert(loaded("scanner", "1.0.0")).await.unwrap();
+        let call = registry.begin_call("scanner").await.unwrap();
+
+        // The call outlasts the drain timeout, so the unload is refused and the plugin stays up
+        assert!(matches!(registry.remove("scanner").await, Err(PluginError::DrainTimeout { .. })));
+        assert!(matches!(registry.list().await[..], [(_, PluginStatus::Active)]));
+
+        // Once draining, new calls are refused and the unload finishes when the call does
+        let unload = tokio::spawn({
+            let registry = registry.clone();
+            async move { registry.remove("scanner").await }
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert!(matches!(registry.begin_call("scanner").await, Err(PluginError::NotAccepting { .. })));
+        assert!(!unload.is_finished());
+        drop(call);
+
+        let unloaded = unload.await.unwrap().unwrap();
+        assert_eq!(unloaded.manifest.version, "1.0.0");
+        assert!(registry.list().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_reload_swaps_to_new_version() {
+        let registry = Arc::new(PluginRegistry::new(Duration::from_secs(5)));
+        registry.insert(loaded("scanner", "1.0.0")).await.unwrap();
+        registry.insert(loaded("exporter", "2.0.0")).await.unwrap();
+        assert!(registry.insert(loaded("scanner", "1.0.0")).await.is_err());
+
+        // Only strictly newer builds of the same plugin may replace it
+        let current = registry.manifest("scanner").await.unwrap();
+        assert!(check_upgrade(&current, &loaded("scanner", "1.1.0").manifest).is_ok());
+        assert!(check_upgrade(&current, &loaded("scanner", "1.0.0").manifest).is_err());
+        assert!(check_upgrade(&current, &loaded("scanner", "0.9.9").manifest).is_err());
+        assert!(check_upgrade(&current, &loaded("exporter", "9.0.0").manifest).is_err());
+
+        let call = registry.begin_call("scanner").await.unwrap();
+        let reload = tokio::spawn({
+            let registry = registry.clone();
+            async move { registry.replace(loaded("scanner", "1.1.0")).await }
+        });
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert_eq!(call.plugin().manifest.version, "1.0.0");
+        assert!(!reload.is_finished());
+        drop(call);
+
+        let replaced = reload.await.unwrap().unwrap();
+        assert_eq!(replaced.manifest.version, "1.0.0");
+        let call = registry.begin_call("scanner").await.unwrap();
+        assert_eq!(call.plugin().manifest.version, "1.1.0");
+
+        // A plugin that fails is disabled on its own; its neighbours keep serving
+        call.fail("sandbox violation");
+        drop(call);
+        assert!(matches!(registry.begin_call("scanner").await, Err(PluginError::NotAccepting { .. })));
+        assert!(registry.begin_call("exporter").await.is_ok());
+        registry.replace(loaded("scanner", "1.1.1")).await.unwrap();
+        assert!(registry.begin_call("scanner").await.is_ok());
+    }
 }
+
@@ -0,0 +1,640 @@
+// src-tauri/src/plugin_wasm.rs
+// WASM Plugin Runtime - Sandboxed execution for untrusted third-party and community plugins
+// A plugin sees only the host functions its capabilities grant and runs on a fuel, memory and time budget
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+use wasmtime::{Caller, Config, Engine, Extern, Linker, Module, Store, StoreLimits, StoreLimitsBuilder, Trap};
+
+use crate::database::{DatabaseContext, DatabaseManager};
+use crate::networking::{HttpMethod, NetworkContext, SecureNetworkTransport, SecureRequest, SecurityRequirements};
+use crate::observability::{ForensicLogger, PiiDetector, QosClass, RecorderHooks};
+use crate::plugin_system::{PluginCapabilities, PluginError, PluginOperation, ResourceLimits};
+use crate::security::{ClassificationLevel, SecurityLabel};
+
+/// Import module every host function lives under
+pub const HOST_MODULE: &str = "nodus";
+
+/// Instructions' worth of fuel a plugin call gets by default
+pub const DEFAULT_FUEL: u64 = 10_000_000;
+
+/// Fuel a running plugin burns between yields to the async runtime, so timeouts can fire
+const FUEL_YIELD_INTERVAL: u64 = 10_000;
+
+/// Host call status: the plugin's capabilities don't cover the call
+pub const HOST_DENIED: i64 = -1;
+/// Host call status: nothing found
+pub const HOST_NOT_FOUND: i64 = -2;
+/// Host call status: the host failed to carry out the call
+pub const HOST_ERROR: i64 = -3;
+
+/// Budget a plugin call runs under
+#[derive(Debug, Clone)]
+pub struct WasmLimits {
+    /// Fuel for one call, including the host calls it makes
+    pub fuel: u64,
+    /// Linear memory the plugin may grow to
+    pub max_memory_bytes: usize,
+    /// Wall-clock limit on one call, for plugins blocked in slow host calls
+    pub timeout: Duration,
+}
+
+impl Default for WasmLimits {
+    fn default() -> Self {
+        Self {
+            fuel: DEFAULT_FUEL,
+            max_memory_bytes: 64 * 1024 * 1024,
+            timeout: Duration::from_secs(5),
+        }
+    }
+}
+
+impl From<&ResourceLimits> for WasmLimits {
+    fn from(limits: &ResourceLimits) -> Self {
+        Self {
+            max_memory_bytes: limits.max_memory,
+            timeout: limits.execution_timeout,
+            ..Self::default()
+        }
+    }
+}
+
+/// Host services plugin I/O is carried out by, after the runtime has checked the plugin's capabilities
+#[async_trait::async_trait]
+pub trait PluginHost: Send + Sync + fmt::Debug {
+    /// Entity `id` of `entity_type` with its classification, if it exists
+    async fn read_entity(&self, entity_type: &str, id: &str) -> Result<Option<(ClassificationLevel, serde_json::Value)>, String>;
+
+    /// Body of an HTTP GET of `url`
+    async fn http_get(&self, url: &str) -> Result<Vec<u8>, String>;
+
+    /// Called for every host call a plugin's capabilities deny, e.g. to record it forensically
+    async fn denied(&self, _error: &PluginError) {}
+}
+
+/// Host services backed by the engine, for one call on behalf of one caller
+///
+/// Entity reads go through the `DatabaseManager`'s MAC checks and fetches through the
+/// transport's network policy, both at the lower of the caller's clearance and the plugin's
+/// `max_classification`, so a plugin can never hand its caller more than the caller could read.
+/// With a PII detector, entity data reaches the plugin only redacted.
+pub struct EnginePluginHost {
+    plugin_id: String,
+    context: DatabaseContext,
+    database: Arc<DatabaseManager>,
+    transport: Arc<SecureNetworkTransport>,
+    hooks: RecorderHooks,
+    forensic_logger: Arc<ForensicLogger>,
+    pii_detector: Option<Arc<PiiDetector>>,
+}
+
+impl fmt::Debug for EnginePluginHost {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EnginePluginHost")
+            .field("plugin_id", &self.plugin_id)
+            .field("label", &self.context.security_label)
+            .finish_non_exhaustive()
+    }
+}
+
+impl EnginePluginHost {
+    pub fn new(
+        plugin_id: &str,
+        caller: &DatabaseContext,
+        capabilities: &PluginCapabilities,
+        database: Arc<DatabaseManager>,
+        transport: Arc<SecureNetworkTransport>,
+        hooks: RecorderHooks,
+        forensic_logger: Arc<ForensicLogger>,
+    ) -> Self {
+        let context = DatabaseContext {
+            security_label: plugin_label(&caller.security_label, capabilities),
+            ..caller.clone()
+        };
+        Self { plugin_id: plugin_id.to_string(), context, database, transport, hooks, forensic_logger, pii_detector: None }
+    }
+
+    /// Redact PII found by `detector` from entities before the plugin sees them
+    pub fn with_pii_detector(mut self, detector: Option<Arc<PiiDetector>>) -> Self {
+        self.pii_detector = detector;
+        self
+    }
+}
+
+/// Label a plugin acts at: the caller's, capped at the plugin's `max_classification`
+pub fn plugin_label(caller: &SecurityLabel, capabilities: &PluginCapabilities) -> SecurityLabel {
+    if caller.level.dominates(&capabilities.max_classification) {
+        SecurityLabel::new(capabilities.max_classification.clone(), caller.compartments.iter().cloned().collect::<Vec<_>>())
+    } else {
+        caller.clone()
+    }
+}
+
+#[async_trait::async_trait]
+impl PluginHost for EnginePluginHost {
+    async fn read_entity(&self, entity_type: &str, id: &str) -> Result<Option<(ClassificationLevel, serde_json::Value)>, String> {
+        let Ok(entity_id) = Uuid::parse_str(id) else {
+            return Ok(None);
+        };
+        let entity = self.database.read_entity(entity_id, &self.context).await.map_err(|e| e.to_string())?;
+        Ok(entity
+            .filter(|entity| entity.entity_type == entity_type)
+            .map(|mut entity| {
+                if let Some(detector) = &self.pii_detector {
+                    detector.protect_entity(&mut entity, None);
+                }
+                (entity.classification, entity.data)
+            }))
+    }
+
+    async fn http_get(&self, url: &str) -> Result<Vec<u8>, String> {
+        let request = SecureRequest {
+            request_id: Uuid::new_v4(),
+            url: url.to_string(),
+            method: HttpMethod::GET,
+            headers: HashMap::new(),
+            body: None,
+            classification: self.context.security_label.level.clone(),
+            user_id: self.context.user_id.clone(),
+            session_id: self.context.session_id,
+            timeout_ms: Some(10_000),
+            retry_policy: None,
+            cache_policy: None,
+            security_requirements: SecurityRequirements::default(),
+            idempotency_key: None,
+            qos: QosClass::Batch,
+        };
+        let context = NetworkContext {
+            user_id: self.context.user_id.clone(),
+            session_id: self.context.session_id,
+            security_label: self.context.security_label.clone(),
+            tenant_id: self.context.tenant_id.clone(),
+            source_ip: None,
+            user_agent: Some(format!("nodus-plugin/{}", self.plugin_id)),
+        };
+        let response = self.transport.request(request, context, &self.hooks).await.map_err(|e| e.to_string())?;
+        match response.status_code {
+            200..=299 => Ok(response.body.unwrap_or_default()),
+            status => Err(format!("{} returned {}", url, status)),
+        }
+    }
+
+    async fn denied(&self, error: &PluginError) {
+        let description = format!("Plugin {} host call denied for {}: {}", self.plugin_id, self.context.user_id, error);
+        if let Err(e) = self.forensic_logger.log_security_event("plugin_host_call_denied", &description, &self.context.user_id).await {
+            tracing::error!(plugin_id = %self.plugin_id, "Failed to record denied host call: {}", e);
+        }
+    }
+}
+
+/// Compiles WASM plugins and runs them sandboxed
+///
+/// Plugin ABI: a plugin exports `memory` and `alloc(len) -> ptr`. Each entry point takes the
+/// JSON input as `(ptr, len)` and returns its JSON output packed as `ptr << 32 | len`. Host
+/// functions under `HOST_MODULE` answer the same way, or with a negative `HOST_*` status.
+#[derive(Clone)]
+pub struct WasmPluginRuntime {
+    engine: Engine,
+}
+
+impl fmt::Debug for WasmPluginRuntime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WasmPluginRuntime").finish_non_exhaustive()
+    }
+}
+
+impl WasmPluginRuntime {
+    pub fn new() -> Result<Self, PluginError> {
+        let mut config = Config::new();
+        config.async_support(true);
+        config.consume_fuel(true);
+        let engine = Engine::new(&config).map_err(|e| PluginError::LoadingFailed {
+            plugin_id: "wasm_runtime".to_string(),
+            error: e.to_string(),
+        })?;
+        Ok(Self { engine })
+    }
+
+    /// Compile `bytes` for `plugin_id`, linking only the host functions `capabilities` grant
+    ///
+    /// Fails if the plugin imports anything else.
+    pub fn load(
+        &self,
+        plugin_id: &str,
+        bytes: &[u8],
+        capabilities: PluginCapabilities,
+        limits: WasmLimits,
+    ) -> Result<WasmPlugin, PluginError> {
+        let module = Module::new(&self.engine, bytes).map_err(|e| PluginError::LoadingFailed {
+            plugin_id: plugin_id.to_string(),
+            error: e.to_string(),
+        })?;
+
+        let granted = granted_host_functions(&capabilities);
+        for import in module.imports() {
+            if import.module() != HOST_MODULE || !granted.contains(&import.name()) {
+                return Err(PluginError::CapabilityDenied {
+                    plugin_id: plugin_id.to_string(),
+                    reason: format!("import of {}::{}", import.module(), import.name()),
+                });
+            }
+        }
+
+        let mut linker = Linker::new(&self.engine);
+        link_host_functions(&mut linker, &granted).map_err(|e| PluginError::LoadingFailed {
+            plugin_id: plugin_id.to_string(),
+            error: e.to_string(),
+        })?;
+
+        Ok(WasmPlugin {
+            plugin_id: plugin_id.to_string(),
+            engine: self.engine.clone(),
+            module,
+            linker,
+            capabilities,
+            limits,
+        })
+    }
+}
+
+/// A compiled plugin; every call runs in a fresh instance so no state survives between calls
+pub struct WasmPlugin {
+    plugin_id: String,
+    engine: Engine,
+    module: Module,
+    linker: Linker<HostState>,
+    capabilities: PluginCapabilities,
+    limits: WasmLimits,
+}
+
+impl fmt::Debug for WasmPlugin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WasmPlugin")
+            .field("plugin_id", &self.plugin_id)
+            .field("capabilities", &self.capabilities)
+            .field("limits", &self.limits)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Output of a plugin call
+#[derive(Debug, Clone, PartialEq)]
+pub struct WasmCallOutput {
+    pub value: serde_json::Value,
+    pub fuel_consumed: u64,
+}
+
+impl WasmPlugin {
+    pub fn plugin_id(&self) -> &str {
+        &self.plugin_id
+    }
+
+    pub fn capabilities(&self) -> &PluginCapabilities {
+        &self.capabilities
+    }
+
+    /// Run entry point `export` on `input`, with its host calls carried out by `host`
+    ///
+    /// Running out of fuel or time is a sandbox violation; the plugin is stopped either way.
+    pub async fn call(&self, export: &str, input: &serde_json::Value, host: Arc<dyn PluginHost>) -> Result<WasmCallOutput, PluginError> {
+        match tokio::time::timeout(self.limits.timeout, self.run(export, input, host)).await {
+            Ok(result) => result,
+            Err(_) => Err(PluginError::SandboxViolation {
+                plugin_id: self.plugin_id.clone(),
+                violation: format!("{} ran past its {:?} time limit", export, self.limits.timeout),
+            }),
+        }
+    }
+
+    async fn run(&self, export: &str, input: &serde_json::Value, host: Arc<dyn PluginHost>) -> Result<WasmCallOutput, PluginError> {
+        let state = HostState {
+            plugin_id: self.plugin_id.clone(),
+            capabilities: self.capabilities.clone(),
+            host,
+            limits: StoreLimitsBuilder::new()
+                .memory_size(self.limits.max_memory_bytes)
+                .instances(1)
+                .build(),
+        };
+        let mut store = Store::new(&self.engine, state);
+        store.limiter(|state| &mut state.limits);
+        store.set_fuel(self.limits.fuel).map_err(|e| self.failed(e))?;
+        store.fuel_async_yield_interval(Some(FUEL_YIELD_INTERVAL)).map_err(|e| self.failed(e))?;
+
+        let result = async {
+            let instance = self.linker.instantiate_async(&mut store, &self.module).await?;
+            let memory = instance
+                .get_memory(&mut store, "memory")
+                .ok_or_else(|| wasmtime::Error::msg("plugin exports no memory"))?;
+            let alloc = instance.get_typed_func::<i32, i32>(&mut store, "alloc")?;
+            let entry = instance.get_typed_func::<(i32, i32), i64>(&mut store, export)?;
+
+            let input = serde_json::to_vec(input)?;
+            let ptr = alloc.call_async(&mut store, input.len() as i32).await?;
+            memory.write(&mut store, ptr as u32 as usize, &input)?;
+            let packed = entry.call_async(&mut store, (ptr, input.len() as i32)).await?;
+
+            let (ptr, len) = unpack(packed);
+            let output = memory
+                .data(&store)
+                .get(ptr..ptr + len)
+                .ok_or_else(|| wasmtime::Error::msg("output lies outside plugin memory"))?;
+            let value = if output.is_empty() { serde_json::Value::Null } else { serde_json::from_slice(output)? };
+            Ok::<_, wasmtime::Error>(value)
+        }
+        .await;
+
+        let fuel_consumed = self.limits.fuel - store.get_fuel().unwrap_or(0);
+        match result {
+            Ok(value) => Ok(WasmCallOutput { value, fuel_consumed }),
+            Err(e) if e.downcast_ref::<Trap>() == Some(&Trap::OutOfFuel) => Err(PluginError::SandboxViolation {
+                plugin_id: self.plugin_id.clone(),
+                violation: format!("{} exhausted its fuel ({} units)", export, self.limits.fuel),
+            }),
+            Err(e) => Err(self.failed(e)),
+        }
+    }
+
+    fn failed(&self, error: impl fmt::Display) -> PluginError {
+        PluginError::ExecutionFailed {
+            plugin_id: self.plugin_id.clone(),
+            error: error.to_string(),
+        }
+    }
+}
+
+/// Per-call store data host functions act on
+struct HostState {
+    plugin_id: String,
+    capabilities: PluginCapabilities,
+    host: Arc<dyn PluginHost>,
+    limits: StoreLimits,
+}
+
+/// Host functions a plugin with `capabilities` may import
+fn granted_host_functions(capabilities: &PluginCapabilities) -> Vec<&'static str> {
+    let mut granted = vec!["log"];
+    if !capabilities.readable_entity_types.is_empty() {
+        granted.push("read_entity");
+    }
+    if !capabilities.network_domains.is_empty() {
+        granted.push("http_get");
+    }
+    granted
+}
+
+fn link_host_functions(linker: &mut Linker<HostState>, granted: &[&str]) -> wasmtime::Result<()> {
+    linker.func_wrap(HOST_MODULE, "log", |mut caller: Caller<'_, HostState>, ptr: i32, len: i32| {
+        let message = String::from_utf8_lossy(&guest_bytes(&mut caller, ptr, len)?).into_owned();
+        tracing::debug!(plugin_id = %caller.data().plugin_id, "{}", message);
+        Ok(())
+    })?;
+
+    if granted.contains(&"read_entity") {
+        linker.func_wrap_async(
+            HOST_MODULE,
+            "read_entity",
+            |mut caller: Caller<'_, HostState>, (type_ptr, type_len, id_ptr, id_len): (i32, i32, i32, i32)| {
+                Box::new(async move {
+                    let entity_type = guest_string(&mut caller, type_ptr, type_len)?;
+                    let id = guest_string(&mut caller, id_ptr, id_len)?;
+
+                    // Check the type before touching the database, the classification once it's known
+                    let unclassified = ClassificationLevel::Unclassified;
+                    let requested = PluginOperation::ReadEntity { entity_type: &entity_type, classification: &unclassified };
+                    if !authorized(&caller, &requested).await {
+                        return Ok(HOST_DENIED);
+                    }
+                    let host = caller.data().host.clone();
+                    let (classification, entity) = match host.read_entity(&entity_type, &id).await {
+                        Ok(Some(found)) => found,
+                        Ok(None) => return Ok(HOST_NOT_FOUND),
+                        Err(e) => {
+                            tracing::warn!(plugin_id = %caller.data().plugin_id, "Entity read failed: {}", e);
+                            return Ok(HOST_ERROR);
+                        }
+                    };
+                    let read = PluginOperation::ReadEntity { entity_type: &entity_type, classification: &classification };
+                    if !authorized(&caller, &read).await {
+                        return Ok(HOST_DENIED);
+                    }
+                    write_guest(&mut caller, &serde_json::to_vec(&entity)?).await
+                })
+            },
+        )?;
+    }
+
+    if granted.contains(&"http_get") {
+        linker.func_wrap_async(
+            HOST_MODULE,
+            "http_get",
+            |mut caller: Caller<'_, HostState>, (url_ptr, url_len): (i32, i32)| {
+                Box::new(async move {
+                    let url = guest_string(&mut caller, url_ptr, url_len)?;
+                    let Some(domain) = url::Url::parse(&url).ok().and_then(|u| u.host_str().map(str::to_string)) else {
+                        return Ok(HOST_ERROR);
+                    };
+                    if !authorized(&caller, &PluginOperation::Network { domain: &domain }).await {
+                        return Ok(HOST_DENIED);
+                    }
+                    let host = caller.data().host.clone();
+                    match host.http_get(&url).await {
+                        Ok(body) => write_guest(&mut caller, &body).await,
+                        Err(e) => {
+                            tracing::warn!(plugin_id = %caller.data().plugin_id, "Plugin fetch of {} failed: {}", url, e);
+                            Ok(HOST_ERROR)
+                        }
+                    }
+                })
+            },
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Check `operation` against the calling plugin's capabilities, reporting denials to the host
+async fn authorized(caller: &Caller<'_, HostState>, operation: &PluginOperation<'_>) -> bool {
+    let state = caller.data();
+    match state.capabilities.authorize(&state.plugin_id, operation) {
+        Ok(()) => true,
+        Err(e) => {
+            tracing::warn!(plugin_id = %state.plugin_id, "{}", e);
+            state.host.denied(&e).await;
+            false
+        }
+    }
+}
+
+fn guest_memory(caller: &mut Caller<'_, HostState>) -> wasmtime::Result<wasmtime::Memory> {
+    match caller.get_export("memory") {
+        Some(Extern::Memory(memory)) => Ok(memory),
+        _ => Err(wasmtime::Error::msg("plugin exports no memory")),
+    }
+}
+
+fn guest_bytes(caller: &mut Caller<'_, HostState>, ptr: i32, len: i32) -> wasmtime::Result<Vec<u8>> {
+    let memory = guest_memory(caller)?;
+    let (start, len) = (ptr as u32 as usize, len as u32 as usize);
+    memory
+        .data(&caller)
+        .get(start..start + len)
+        .map(<[u8]>::to_vec)
+        .ok_or_else(|| wasmtime::Error::msg("host call argument lies outside plugin memory"))
+}
+
+fn guest_string(caller: &mut Caller<'_, HostState>, ptr: i32, len: i32) -> wasmtime::Result<String> {
+    String::from_utf8(guest_bytes(caller, ptr, len)?).map_err(wasmtime::Error::msg)
+}
+
+/// Copy `bytes` into memory the plugin allocates, returning them packed for the plugin
+async fn write_guest(caller: &mut Caller<'_, HostState>, bytes: &[u8]) -> wasmtime::Result<i64> {
+    let alloc = match caller.get_export("alloc") {
+        Some(Extern::Func(func)) => func.typed::<i32, i32>(&caller)?,
+        _ => return Err(wasmtime::Error::msg("plugin exports no alloc")),
+    };
+    let ptr = alloc.call_async(&mut *caller, bytes.len() as i32).await?;
+    guest_memory(caller)?.write(&mut *caller, ptr as u32 as usize, bytes)?;
+    Ok(pack(ptr as u32, bytes.len() as u32))
+}
+
+fn pack(ptr: u32, len: u32) -> i64 {
+    ((ptr as i64) << 32) | len as i64
+}
+
+fn unpack(packed: i64) -> (usize, usize) {
+    ((packed as u64 >> 32) as usize, (packed as u64 & 0xffff_ffff) as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Plugin with a bump allocator, an echo entry point, one that reads entity "e1" and one that never returns
+    const PLUGIN: &str = r#"
+        (module
+            (import "nodus" "read_entity" (func $read_entity (param i32 i32 i32 i32) (result i64)))
+            (memory (export "memory") 1)
+            (data (i32.const 0) "casee1")
+            (global $next (mut i32) (i32.const 1024))
+            (func (export "alloc") (param $len i32) (result i32)
+                (local $ptr i32)
+                (local.set $ptr (global.get $next))
+                (global.set $next (i32.add (global.get $next) (local.get $len)))
+                (local.get $ptr))
+            (func (export "echo") (param $ptr i32) (param $len i32) (result i64)
+                (i64.or
+                    (i64.shl (i64.extend_i32_u (local.get $ptr)) (i64.const 32))
+                    (i64.extend_i32_u (local.get $len))))
+            (func (export "read_case") (param i32 i32) (result i64)
+                (local $result i64)
+                (local.set $result (call $read_entity (i32.const 0) (i32.const 4) (i32.const 4) (i32.const 2)))
+                (if (i64.lt_s (local.get $result) (i64.const 0))
+                    (then (return (i64.const 0))))
+                (local.get $result))
+            (func (export "spin") (param i32 i32) (result i64)
+                (loop $forever (br $forever))
+                (i64.const 0)))
+    "#;
+
+    #[derive(Debug)]
+    struct FakeHost {
+        classification: ClassificationLevel,
+        denials: Mutex<Vec<String>>,
+    }
+
+    #[async_trait::async_trait]
+    impl PluginHost for FakeHost {
+        async fn read_entity(&self, _entity_type: &str, id: &str) -> Result<Option<(ClassificationLevel, serde_json::Value)>, String> {
+            Ok(Some((self.classification.clone(), serde_json::json!({ "id": id }))))
+        }
+
+        async fn http_get(&self, _url: &str) -> Result<Vec<u8>, String> {
+            Err("offline".to_string())
+        }
+
+        async fn denied(&self, error: &PluginError) {
+            self.denials.lock().unwrap().push(error.to_string());
+        }
+    }
+
+    fn host(classification: ClassificationLevel) -> Arc<FakeHost> {
+        Arc::new(FakeHost { classification, denials: Mutex::new(Vec::new()) })
+    }
+
+    fn can_read_cases() -> PluginCapabilities {
+        PluginCapabilities {
+            readable_entity_types: vec!["case".to_string()],
+            max_classification: ClassificationLevel::Internal,
+            ..PluginCapabilities::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_plugin_runs_and_reads_through_capability_checks() {
+        let runtime = WasmPluginRuntime::new().unwrap();
+        let internal_host = host(ClassificationLevel::Internal);
+        let plugin = runtime
+            .load("echo", PLUGIN.as_bytes(), can_read_cases(), WasmLimits::default())
+            .unwrap();
+
+        let input = serde_json::json!({ "event": "login", "attempts": 3 });
+        let output = plugin.call("echo", &input, internal_host.clone()).await.unwrap();
+        assert_eq!(output.value, input);
+        assert!(output.fuel_consumed > 0);
+
+        // Readable type at a classification the plugin may see
+        let output = plugin.call("read_case", &serde_json::Value::Null, internal_host.clone()).await.unwrap();
+        assert_eq!(output.value, serde_json::json!({ "id": "e1" }));
+
+        // Same read of data above the plugin's clearance is denied and reported
+        let secret_host = host(ClassificationLevel::Secret);
+        let output = plugin.call("read_case", &serde_json::Value::Null, secret_host.clone()).await.unwrap();
+        assert_eq!(output.value, serde_json::Value::Null);
+        assert_eq!(secret_host.denials.lock().unwrap().len(), 1);
+        assert!(internal_host.denials.lock().unwrap().is_empty());
+
+        // Without the capability the import isn't provided at all
+        let err = runtime
+            .load("echo", PLUGIN.as_bytes(), PluginCapabilities::default(), WasmLimits::default())
+            .unwrap_err();
+        assert!(matches!(err, PluginError::CapabilityDenied { .. }), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_fuel_exhaustion_stops_runaway_plugin() {
+        let runtime = WasmPluginRuntime::new().unwrap();
+        let limits = WasmLimits { fuel: 100_000, ..WasmLimits::default() };
+        let plugin = runtime
+            .load("spinner", PLUGIN.as_bytes(), can_read_cases(), limits)
+            .unwrap();
+
+        let started = std::time::Instant::now();
+        let err = plugin.call("spin", &serde_json::Value::Null, host(ClassificationLevel::Internal)).await.unwrap_err();
+        assert!(
+            matches!(&err, PluginError::SandboxViolation { violation, .. } if violation.contains("fuel")),
+            "{}",
+            err
+        );
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        // The plugin is still usable afterwards, with a fresh budget
+        assert!(plugin.call("echo", &serde_json::json!(1), host(ClassificationLevel::Internal)).await.is_ok());
+    }
+
+    #[test]
+    fn test_plugin_acts_at_the_lower_of_caller_and_plugin_labels() {
+        let capabilities = can_read_cases();
+        let secret = SecurityLabel::new(ClassificationLevel::Secret, vec!["ALPHA".to_string()]);
+        let capped = plugin_label(&secret, &capabilities);
+        assert_eq!(capped.level, ClassificationLevel::Internal);
+        assert!(capped.compartments.contains("ALPHA"));
+
+        let public = SecurityLabel::new(ClassificationLevel::Unclassified, Vec::new());
+        assert_eq!(plugin_label(&public, &capabilities).level, ClassificationLevel::Unclassified);
+    }
+}
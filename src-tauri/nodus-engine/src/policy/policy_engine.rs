@@ -2,22 +2,26 @@
 // Unified Policy Engine - Configure Everything Through One System
 // Controls all innovations: AI Oracle, Time Travel, Zero-Downtime, Ads, Database, Quantum Security
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use std::collections::{HashMap, BTreeMap};
+use std::path::Path;
 use chrono::{DateTime, Utc, Duration};
 use uuid::Uuid;
 
-use crate::observability::{ForensicLogger, MetricsRegistry};
-use crate::security::{SecurityManager, ClassificationLevel};
+use crate::observability::{BudgetEscalationPolicy, BudgetEscalator, ExportPolicy, ForensicLogger, MetricsRegistry, OperationBudgets, PerformanceStateMonitor, PerformanceThresholds, QosPolicy, TailSamplingPolicy};
+use crate::security::{SecurityManager, ClassificationLevel, MacPolicy, TranquilityMode};
 // Temporarily comment out AI Oracle import (experimental module)
 // use crate::ai::SecurityOracle;
 use crate::temporal::TemporalForensicEngine;
 use crate::hot_config::ZeroDowntimeSystem;
 // Temporarily comment out Privacy Ad Platform integration (skeleton)
 // use crate::advertising::PrivacyAdPlatform;
-use crate::database::SelfOptimizingDatabase;
+use crate::database::{DbConfig, SelfOptimizingDatabase};
+use crate::multi_tenant::{MultiTenantSystem, TenantQuotaUsage};
+use crate::networking::{NetworkConfig, SecureNetworkTransport};
 // Temporarily comment out Post-Quantum Security integration (skeleton)
 // use crate::quantum::PostQuantumSecurity;
 use crate::state::AppState;
@@ -48,11 +52,35 @@ pub struct UnifiedPolicyEngine {
     
     /// Conditional policy engine
     conditional_engine: ConditionalPolicyEngine,
+    
+    /// Tenant limits and usage for simulating quota changes
+    tenant_quotas: Option<Arc<dyn TenantQuotaSource>>,
+}
+
+/// Tenant limits and current usage, for simulating quota changes; `MultiTenantSystem` in production
+#[async_trait::async_trait]
+pub trait TenantQuotaSource: Send + Sync + std::fmt::Debug {
+    async fn tenant_quotas(&self) -> Vec<TenantQuotaUsage>;
+}
+
+#[async_trait::async_trait]
+impl TenantQuotaSource for MultiTenantSystem {
+    async fn tenant_quotas(&self) -> Vec<TenantQuotaUsage> {
+        self.tenant_quota_usage().await
+    }
 }
 
 /// Master configuration that controls ALL system innovations
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SystemPolicyConfig {
+    /// Layout version of the whole document; files without one are version 1
+    #[serde(default = "legacy_schema_version")]
+    pub schema_version: u32,
+    
+    /// Schema version of each section; a section not listed is at version 1
+    #[serde(default)]
+    pub section_versions: BTreeMap<String, u32>,
+    
     /// Global system settings
     pub global: GlobalSystemPolicy,
     
@@ -80,6 +108,10 @@ pub struct SystemPolicyConfig {
     /// Enterprise features configuration
     pub enterprise: EnterprisePolicy,
     
+    /// Secure network transport configuration
+    #[serde(default)]
+    pub networking: NetworkingPolicy,
+    
     /// Compliance framework configuration
     pub compliance: CompliancePolicy,
     
@@ -113,6 +145,10 @@ pub struct GlobalSystemPolicy {
     
     /// Emergency shutdown conditions
     pub emergency_conditions: Vec<EmergencyCondition>,
+    
+    /// Mandatory access control settings, including the tranquility principle
+    #[serde(default)]
+    pub mac: MacPolicy,
 }
 
 /// AI Oracle policy - controls prediction and auto-remediation
@@ -260,6 +296,10 @@ pub struct DatabasePolicy {
     
     /// Schema evolution
     pub schema_evolution: SchemaEvolutionPolicy,
+    
+    /// Connection pool settings the database manager starts with; `DATABASE_URL` overrides `url`
+    #[serde(default)]
+    pub connection: DbConfig,
 }
 
 /// Quantum security policy - controls future-proof cryptography
@@ -346,10 +386,15 @@ pub struct PolicyOrchestrator {
     
     /// Policy application tracker
     application_tracker: PolicyApplicationTracker,
+    
+    /// Subsystems reconfigured when their policy section changes
+    subsystems: SubsystemCoordinator,
 }
 
 impl UnifiedPolicyEngine {
     /// Create new unified policy engine
+    ///
+    /// Starts from the default policy; use `with_policy` when subsystems were built from a loaded one.
     pub async fn new(
         forensic_logger: Arc<ForensicLogger>,
         metrics_registry: Arc<MetricsRegistry>,
@@ -373,28 +418,37 @@ impl UnifiedPolicyEngine {
             hot_reload,
             inheritance_engine,
             conditional_engine,
+            tenant_quotas: None,
         })
     }
     
+    /// Start from `policy`, the one the running subsystems were built with
+    pub fn with_policy(mut self, policy: SystemPolicyConfig) -> Self {
+        self.policy_config = Arc::new(RwLock::new(policy));
+        self
+    }
+    
+    /// Reconfigure `subsystem` whenever its policy section changes
+    pub async fn register_subsystem(&self, subsystem: Arc<dyn Reconfigurable>) {
+        self.orchestrator.subsystems.register(subsystem).await;
+    }
+    
+    /// Take tenant limits and usage from `source` when simulating quota changes
+    pub fn with_tenant_quotas(mut self, source: Arc<dyn TenantQuotaSource>) -> Self {
+        self.tenant_quotas = Some(source);
+        self
+    }
+    
     /// Load complete system policy from configuration
     pub async fn load_policy_from_file(
         &self,
         config_path: &str,
         app_state: &AppState,
     ) -> Result<PolicyLoadResult, PolicyError> {
-        // 1. Load and parse configuration
-        let config_content = tokio::fs::read_to_string(config_path).await
-            .map_err(|e| PolicyError::ConfigLoadFailed(e.to_string()))?;
-        
-        let system_policy: SystemPolicyConfig = toml::from_str(&config_content)
-            .map_err(|e| PolicyError::ConfigParseFailed(e.to_string()))?;
-        
-        // 2. Validate policy configuration
-        let validation_result = self.validator.validate_system_policy(&system_policy).await?;
-        if !validation_result.valid {
-            return Err(PolicyError::PolicyValidationFailed {
-                errors: validation_result.errors,
-            });
+        // 1-2. Load, migrate to the current schema, parse and validate configuration
+        let (system_policy, migrations_applied, validation_result) = read_valid_policy_file(&self.validator, config_path).await?;
+        for migration in &migrations_applied {
+            tracing::info!("Migrated policy {}: {}", config_path, migration);
         }
         
         // 3. Apply conditional logic
@@ -406,17 +460,27 @@ impl UnifiedPolicyEngine {
         // 4. Apply inheritance rules
         let final_policy = self.inheritance_engine.apply_inheritance(&resolved_policy).await?;
         
-        // 5. Store the new policy
-        {
+        // 5-6. Store the new policy and apply it to all systems under the write lock, so a
+        //      rollback can't undo a change made in between
+        let application_result = {
             let mut config = self.policy_config.write().await;
-            *config = final_policy.clone();
-        }
-        
-        // 6. Apply configuration to all systems
-        let application_result = self.orchestrator.apply_policy_to_all_systems(
-            &final_policy,
-            app_state,
-        ).await?;
+            let previous_policy = std::mem::replace(&mut *config, final_policy.clone());
+            match self.orchestrator.apply_policy_to_all_systems(
+                &previous_policy,
+                &final_policy,
+                app_state,
+            ).await {
+                Ok(result) if !result.rolled_back => result,
+                Ok(result) => {
+                    *config = previous_policy;
+                    return Err(PolicyError::SystemApplicationFailed(result.errors.join("; ")));
+                }
+                Err(e) => {
+                    *config = previous_policy;
+                    return Err(e);
+                }
+            }
+        };
         
         // 7. Audit the policy change
         self.audit_system.record_policy_change(
@@ -431,9 +495,34 @@ impl UnifiedPolicyEngine {
             validation_result,
             application_result,
             affected_systems: self.get_affected_systems(&final_policy).await?,
+            migrations_applied,
         })
     }
     
+    /// Reload the policy whenever `config_path` changes on disk
+    ///
+    /// Each reload goes through `load_policy_from_file`, so an unreadable or invalid file is
+    /// logged and rejected while the running policy stays in force. Replaces any earlier watch.
+    pub async fn watch_policy_file(self: &Arc<Self>, config_path: &str, app_state: Arc<AppState>) -> Result<(), PolicyError> {
+        let engine = Arc::downgrade(self);
+        let path = config_path.to_string();
+        self.hot_reload.watch(Path::new(config_path), move || {
+            let (engine, path, app_state) = (engine.clone(), path.clone(), app_state.clone());
+            async move {
+                // The engine owns the watch, so it is only gone while shutting down
+                let Some(engine) = engine.upgrade() else {
+                    return Ok(());
+                };
+                engine.load_policy_from_file(&path, &app_state).await.map(|_| ())
+            }
+        }).await
+    }
+    
+    /// Stop reloading the policy file
+    pub async fn stop_watching_policy_file(&self) {
+        self.hot_reload.stop().await;
+    }
+    
     /// Update specific policy section (hot reload)
     pub async fn update_policy_section(
         &self,
@@ -456,27 +545,37 @@ impl UnifiedPolicyEngine {
             });
         }
         
-        // 2. Apply update to configuration
-        let updated_policy = {
+        // 2-3. Update the configuration and apply it to the affected systems only, under the
+        //      write lock so a rollback restores exactly what this update replaced
+        let affected_systems = self.get_systems_affected_by_section(section_path).await?;
+        let application_result = {
             let mut config = self.policy_config.write().await;
-            self.apply_section_update(&mut config, section_path, new_config)?;
-            config.clone()
+            let previous_policy = config.clone();
+            if let Err(e) = apply_section_update(&mut config, section_path, new_config) {
+                *config = previous_policy;
+                return Err(e);
+            }
+            let result = self.orchestrator.apply_policy_to_systems(
+                &previous_policy,
+                &config,
+                &affected_systems,
+                app_state,
+            ).await;
+            if !matches!(result, Ok(PolicyApplicationResult { rolled_back: false, .. })) {
+                *config = previous_policy;
+            }
+            result?
         };
         
-        // 3. Apply changes to affected systems only
-        let affected_systems = self.get_systems_affected_by_section(section_path).await?;
-        let application_result = self.orchestrator.apply_policy_to_systems(
-            &updated_policy,
-            &affected_systems,
-            app_state,
-        ).await?;
-        
-        // 4. Audit the update
+        // 4. Audit the update, including one the systems rolled back
         self.audit_system.record_policy_update(
             &update_id,
             section_path,
             &application_result,
         ).await?;
+        if application_result.rolled_back {
+            return Err(PolicyError::SystemApplicationFailed(application_result.errors.join("; ")));
+        }
         
         Ok(PolicyUpdateResult {
             update_id,
@@ -493,17 +592,7 @@ impl UnifiedPolicyEngine {
         T: for<'de> Deserialize<'de>,
     {
         let config = self.policy_config.read().await;
-        
-        let section_value = match system {
-            SystemType::AiOracle => serde_json::to_value(&config.ai_oracle)?,
-            SystemType::TemporalForensics => serde_json::to_value(&config.temporal_forensics)?,
-            SystemType::ZeroDowntime => serde_json::to_value(&config.zero_downtime)?,
-            SystemType::Advertising => serde_json::to_value(&config.advertising)?,
-            SystemType::Database => serde_json::to_value(&config.database)?,
-            SystemType::QuantumSecurity => serde_json::to_value(&config.quantum_security)?,
-            SystemType::Observability => serde_json::to_value(&config.observability)?,
-            SystemType::Enterprise => serde_json::to_value(&config.enterprise)?,
-        };
+        let section_value = system_section(&config, system)?;
         
         let typed_config: T = serde_json::from_value(section_value)?;
         Ok(typed_config)
@@ -518,35 +607,33 @@ impl UnifiedPolicyEngine {
     ) -> Result<SystemToggleResult, PolicyError> {
         let toggle_id = Uuid::new_v4().to_string();
         
-        // Update policy configuration
-        {
+        // Update the configuration and apply it to the specific system under the write lock
+        let application_result = {
             let mut config = self.policy_config.write().await;
-            match system {
-                SystemType::AiOracle => config.ai_oracle.enabled = enabled,
-                SystemType::TemporalForensics => config.temporal_forensics.enabled = enabled,
-                SystemType::ZeroDowntime => config.zero_downtime.enabled = enabled,
-                SystemType::Advertising => config.advertising.enabled = enabled,
-                SystemType::Database => config.database.enabled = enabled,
-                SystemType::QuantumSecurity => config.quantum_security.enabled = enabled,
-                SystemType::Observability => config.observability.enabled = enabled,
-                SystemType::Enterprise => config.enterprise.enabled = enabled,
+            let previous_policy = config.clone();
+            set_system_enabled_in(&mut config, system, enabled);
+            let result = self.orchestrator.apply_policy_to_systems(
+                &previous_policy,
+                &config,
+                &[system],
+                app_state,
+            ).await;
+            if !matches!(result, Ok(PolicyApplicationResult { rolled_back: false, .. })) {
+                *config = previous_policy;
             }
-        }
-        
-        // Apply the change to the specific system
-        let application_result = self.orchestrator.toggle_system(
-            system,
-            enabled,
-            app_state,
-        ).await?;
+            result?
+        };
         
-        // Audit the toggle
+        // Audit the toggle, including one the system rolled back
         self.audit_system.record_system_toggle(
             &toggle_id,
             system,
             enabled,
             &application_result,
         ).await?;
+        if application_result.rolled_back {
+            return Err(PolicyError::SystemApplicationFailed(application_result.errors.join("; ")));
+        }
         
         Ok(SystemToggleResult {
             toggle_id,
@@ -570,6 +657,7 @@ impl UnifiedPolicyEngine {
             self.get_system_status(SystemType::QuantumSecurity, &config).await?,
             self.get_system_status(SystemType::Observability, &config).await?,
             self.get_system_status(SystemType::Enterprise, &config).await?,
+            self.get_system_status(SystemType::Networking, &config).await?,
         ];
         
         Ok(PolicyStatus {
@@ -582,44 +670,230 @@ impl UnifiedPolicyEngine {
         })
     }
     
-    // Private implementation methods...
-    
-    async fn apply_section_update(
-        &self,
-        config: &mut SystemPolicyConfig,
-        section_path: &str,
-        new_value: serde_json::Value,
-    ) -> Result<(), PolicyError> {
-        match section_path {
-            "ai_oracle" => {
-                config.ai_oracle = serde_json::from_value(new_value)?;
-            },
-            "temporal_forensics" => {
-                config.temporal_forensics = serde_json::from_value(new_value)?;
-            },
-            "zero_downtime" => {
-                config.zero_downtime = serde_json::from_value(new_value)?;
-            },
-            "advertising" => {
-                config.advertising = serde_json::from_value(new_value)?;
-            },
-            "database" => {
-                config.database = serde_json::from_value(new_value)?;
-            },
-            "quantum_security" => {
-                config.quantum_security = serde_json::from_value(new_value)?;
-            },
-            _ => {
-                return Err(PolicyError::InvalidSectionPath(section_path.to_string()));
+    /// Preview one change against a copy of the running policy, without applying it
+    ///
+    /// Reports the systems the change would restart, tenants whose quota it changes (and
+    /// whether they are already above the new limit), and validation problems. Unlike a
+    /// dry-run load it targets a single change; nothing live is touched.
+    pub async fn simulate_policy_change(&self, change: PolicyChange) -> SimulationReport {
+        let current = self.policy_config.read().await.clone();
+        let tenants = match (&change, &self.tenant_quotas) {
+            (PolicyChange::SetTenantQuota { .. }, Some(source)) => Some(source.tenant_quotas().await),
+            (PolicyChange::SetTenantQuota { .. }, None) => None,
+            _ => Some(Vec::new()),
+        };
+        simulate_change(&current, tenants.as_deref(), change)
+    }
+}
+
+/// One granular change to simulate
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PolicyChange {
+    /// Turn a system on or off, as `set_system_enabled` would
+    SetSystemEnabled { system: SystemType, enabled: bool },
+    /// Replace a section, as `update_policy_section` would
+    UpdateSection { section: String, config: serde_json::Value },
+    /// Set a `TenantResourceLimits` quota, e.g. `storage_gb`, for one tenant or all of them
+    SetTenantQuota { tenant_id: Option<String>, resource: String, limit: u64 },
+}
+
+/// What a simulated change would do
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SimulationReport {
+    pub change: PolicyChange,
+    /// The change would pass validation
+    pub valid: bool,
+    pub validation_errors: Vec<String>,
+    /// Systems whose policy section the change alters, and so would restart
+    pub restarted_systems: Vec<SystemType>,
+    /// Tenants whose quota the change alters
+    pub affected_tenants: Vec<TenantQuotaImpact>,
+    pub warnings: Vec<String>,
+    pub simulated_at: DateTime<Utc>,
+}
+
+/// A tenant's quota before and after a simulated change
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct TenantQuotaImpact {
+    pub tenant_id: String,
+    pub resource: String,
+    pub current_limit: u64,
+    pub new_limit: u64,
+    pub current_usage: f64,
+    /// Already using more than the new limit allows
+    pub over_limit: bool,
+}
+
+/// Apply `change` to a copy of `current` and report the difference
+///
+/// `tenants` is `None` when tenant usage is unavailable, in which case quota impact is
+/// reported as a warning rather than guessed.
+pub fn simulate_change(
+    current: &SystemPolicyConfig,
+    tenants: Option<&[TenantQuotaUsage]>,
+    change: PolicyChange,
+) -> SimulationReport {
+    let mut simulated = current.clone();
+    let mut validation_errors = Vec::new();
+    let mut affected_tenants = Vec::new();
+    let mut warnings = Vec::new();
+    
+    match &change {
+        PolicyChange::SetSystemEnabled { system, enabled } => {
+            set_system_enabled_in(&mut simulated, *system, *enabled);
+        }
+        PolicyChange::UpdateSection { section, config } => {
+            if let Err(e) = apply_section_update(&mut simulated, section, config.clone()) {
+                validation_errors.push(match e {
+                    PolicyError::SerializationError(e) => format!("malformed section: {}", e),
+                    e => e.to_string(),
+                });
             }
         }
-        
-        Ok(())
+        PolicyChange::SetTenantQuota { tenant_id, resource, limit } => match tenants {
+            None => warnings.push("tenant usage is unavailable; quota impact not simulated".to_string()),
+            Some(tenants) => {
+                let targets: Vec<&TenantQuotaUsage> = tenants.iter()
+                    .filter(|tenant| tenant_id.as_ref().map_or(true, |id| *id == tenant.tenant_id))
+                    .collect();
+                if let Some(tenant_id) = tenant_id.as_ref().filter(|_| targets.is_empty()) {
+                    validation_errors.push(format!("unknown tenant {}", tenant_id));
+                }
+                for tenant in targets {
+                    let mut limits = tenant.limits.clone();
+                    let (Some(current_limit), true) = (tenant.limits.quota(resource), limits.set_quota(resource, *limit)) else {
+                        validation_errors.push(format!("unknown quota {}", resource));
+                        break;
+                    };
+                    let new_limit = limits.quota(resource).unwrap_or(*limit);
+                    if new_limit == current_limit {
+                        continue;
+                    }
+                    let current_usage = tenant.usage.quota_usage(resource, &tenant.limits).unwrap_or(0.0);
+                    let over_limit = current_usage > new_limit as f64;
+                    if over_limit {
+                        warnings.push(format!(
+                            "tenant {} uses {} of {}, above the new limit {}",
+                            tenant.tenant_id, current_usage, resource, new_limit
+                        ));
+                    }
+                    affected_tenants.push(TenantQuotaImpact {
+                        tenant_id: tenant.tenant_id.clone(),
+                        resource: resource.clone(),
+                        current_limit,
+                        new_limit,
+                        current_usage,
+                        over_limit,
+                    });
+                }
+            }
+        },
+    }
+    
+    validation_errors.extend(validate_policy(&simulated).errors);
+    
+    let restarted_systems: Vec<SystemType> = SystemType::ALL.into_iter()
+        .filter(|&system| system_section(current, system).ok() != system_section(&simulated, system).ok())
+        .collect();
+    for &system in &restarted_systems {
+        if system_enabled(current, system) && !system_enabled(&simulated, system) {
+            warnings.push(format!("{:?} would be disabled", system));
+        }
+    }
+    
+    SimulationReport {
+        change,
+        valid: validation_errors.is_empty(),
+        validation_errors,
+        restarted_systems,
+        affected_tenants,
+        warnings,
+        simulated_at: Utc::now(),
     }
 }
 
+/// A system's section of the policy, as JSON
+fn system_section(config: &SystemPolicyConfig, system: SystemType) -> Result<serde_json::Value, PolicyError> {
+    Ok(match system {
+        SystemType::AiOracle => serde_json::to_value(&config.ai_oracle)?,
+        SystemType::TemporalForensics => serde_json::to_value(&config.temporal_forensics)?,
+        SystemType::ZeroDowntime => serde_json::to_value(&config.zero_downtime)?,
+        SystemType::Advertising => serde_json::to_value(&config.advertising)?,
+        SystemType::Database => serde_json::to_value(&config.database)?,
+        SystemType::QuantumSecurity => serde_json::to_value(&config.quantum_security)?,
+        SystemType::Observability => serde_json::to_value(&config.observability)?,
+        SystemType::Enterprise => serde_json::to_value(&config.enterprise)?,
+        SystemType::Networking => serde_json::to_value(&config.networking)?,
+    })
+}
+
+fn system_enabled(config: &SystemPolicyConfig, system: SystemType) -> bool {
+    match system {
+        SystemType::AiOracle => config.ai_oracle.enabled,
+        SystemType::TemporalForensics => config.temporal_forensics.enabled,
+        SystemType::ZeroDowntime => config.zero_downtime.enabled,
+        SystemType::Advertising => config.advertising.enabled,
+        SystemType::Database => config.database.enabled,
+        SystemType::QuantumSecurity => config.quantum_security.enabled,
+        SystemType::Observability => config.observability.enabled,
+        SystemType::Enterprise => config.enterprise.enabled,
+        SystemType::Networking => config.networking.enabled,
+    }
+}
+
+fn set_system_enabled_in(config: &mut SystemPolicyConfig, system: SystemType, enabled: bool) {
+    match system {
+        SystemType::AiOracle => config.ai_oracle.enabled = enabled,
+        SystemType::TemporalForensics => config.temporal_forensics.enabled = enabled,
+        SystemType::ZeroDowntime => config.zero_downtime.enabled = enabled,
+        SystemType::Advertising => config.advertising.enabled = enabled,
+        SystemType::Database => config.database.enabled = enabled,
+        SystemType::QuantumSecurity => config.quantum_security.enabled = enabled,
+        SystemType::Observability => config.observability.enabled = enabled,
+        SystemType::Enterprise => config.enterprise.enabled = enabled,
+        SystemType::Networking => config.networking.enabled = enabled,
+    }
+}
+
+/// Replace one section of `config`
+fn apply_section_update(
+    config: &mut SystemPolicyConfig,
+    section_path: &str,
+    new_value: serde_json::Value,
+) -> Result<(), PolicyError> {
+    match section_path {
+        "ai_oracle" => {
+            config.ai_oracle = serde_json::from_value(new_value)?;
+        },
+        "temporal_forensics" => {
+            config.temporal_forensics = serde_json::from_value(new_value)?;
+        },
+        "zero_downtime" => {
+            config.zero_downtime = serde_json::from_value(new_value)?;
+        },
+        "advertising" => {
+            config.advertising = serde_json::from_value(new_value)?;
+        },
+        "database" => {
+            config.database = serde_json::from_value(new_value)?;
+        },
+        "quantum_security" => {
+            config.quantum_security = serde_json::from_value(new_value)?;
+        },
+        "networking" => {
+            config.networking = serde_json::from_value(new_value)?;
+        },
+        _ => {
+            return Err(PolicyError::InvalidSectionPath(section_path.to_string()));
+        }
+    }
+    
+    Ok(())
+}
+
 /// System types for policy management
-#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub enum SystemType {
     AiOracle,
     TemporalForensics,
@@ -629,6 +903,21 @@ pub enum SystemType {
     QuantumSecurity,
     Observability,
     Enterprise,
+    Networking,
+}
+
+impl SystemType {
+    pub const ALL: [SystemType; 9] = [
+        SystemType::AiOracle,
+        SystemType::TemporalForensics,
+        SystemType::ZeroDowntime,
+        SystemType::Advertising,
+        SystemType::Database,
+        SystemType::QuantumSecurity,
+        SystemType::Observability,
+        SystemType::Enterprise,
+        SystemType::Networking,
+    ];
 }
 
 /// Policy engine errors
@@ -654,12 +943,17 @@ pub enum PolicyError {
     
     #[error("Serialization error: {0}")]
     SerializationError(#[from] serde_json::Error),
+    
+    #[error("Policy hot reload failed: {0}")]
+    HotReloadFailed(String),
 }
 
 /// Default implementation with sensible defaults
 impl Default for SystemPolicyConfig {
     fn default() -> Self {
         Self {
+            schema_version: POLICY_SCHEMA_VERSION,
+            section_versions: current_section_versions(),
             global: GlobalSystemPolicy {
                 system_enabled: true,
                 performance_budget_ms: 1,  // 1ms global budget
@@ -668,6 +962,7 @@ impl Default for SystemPolicyConfig {
                 resource_limits: SystemResourceLimits::default(),
                 audit_level: SystemAuditLevel::Full,
                 emergency_conditions: vec![],
+                mac: MacPolicy::default(),
             },
             ai_oracle: AIOraclePolicy {
                 enabled: true,
@@ -728,6 +1023,7 @@ impl Default for SystemPolicyConfig {
                 performance_budgets: DatabasePerformanceBudgets::default(),
                 autonomous_tuning: AutonomousTuningPolicy::default(),
                 schema_evolution: SchemaEvolutionPolicy::default(),
+                connection: DbConfig::default(),
             },
             quantum_security: QuantumSecurityPolicy {
                 enabled: true,
@@ -739,6 +1035,7 @@ impl Default for SystemPolicyConfig {
             },
             observability: ObservabilityPolicy::default(),
             enterprise: EnterprisePolicy::default(),
+            networking: NetworkingPolicy::default(),
             compliance: CompliancePolicy::default(),
             multi_tenant: MultiTenantPolicy::default(),
             environments: HashMap::new(),
@@ -750,7 +1047,7 @@ impl Default for SystemPolicyConfig {
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SystemResourceLimits {}
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SystemAuditLevel { Basic, Full, Forensic }
 
 impl Default for SystemAuditLevel {
@@ -860,14 +1157,40 @@ pub struct PostQuantumAlgorithmPolicy {}
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CryptoAgilityPolicy {}
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ObservabilityPolicy {
     pub enabled: bool,
+    /// Load thresholds the performance state monitor escalates at
+    #[serde(default)]
+    pub performance_state: PerformanceThresholds,
+    /// How long each component's operations may take
+    #[serde(default)]
+    pub operation_budgets: OperationBudgets,
+    /// Remediation run when an operation exceeds its performance budget
+    #[serde(default)]
+    pub budget_escalation: BudgetEscalationPolicy,
+    /// Which routine observation records are dropped once their outcome is known
+    #[serde(default)]
+    pub tail_sampling: TailSamplingPolicy,
+    /// Where observation records are exported
+    #[serde(default)]
+    pub exports: ExportPolicy,
+    /// Concurrency per QoS class, shared by instrumented commands and network requests
+    #[serde(default)]
+    pub qos: QosPolicy,
 }
 
 impl Default for ObservabilityPolicy {
     fn default() -> Self {
-        Self { enabled: true }
+        Self {
+            enabled: true,
+            performance_state: PerformanceThresholds::default(),
+            operation_budgets: OperationBudgets::default(),
+            budget_escalation: BudgetEscalationPolicy::default(),
+            tail_sampling: TailSamplingPolicy::default(),
+            exports: ExportPolicy::default(),
+            qos: QosPolicy::default(),
+        }
     }
 }
 
@@ -882,6 +1205,20 @@ impl Default for EnterprisePolicy {
     }
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkingPolicy {
+    pub enabled: bool,
+    /// Client settings and default requirements, applied to the running transport
+    #[serde(default)]
+    pub transport: NetworkConfig,
+}
+
+impl Default for NetworkingPolicy {
+    fn default() -> Self {
+        Self { enabled: true, transport: NetworkConfig::default() }
+    }
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct CompliancePolicy {}
 
@@ -899,6 +1236,8 @@ pub struct PolicyLoadResult {
     pub validation_result: PolicyValidationResult,
     pub application_result: PolicyApplicationResult,
     pub affected_systems: Vec<SystemType>,
+    /// Schema migrations the file needed, in the order they ran
+    pub migrations_applied: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -951,12 +1290,205 @@ struct PolicyValidator {}
 impl PolicyValidator {
     async fn new() -> Result<Self, PolicyError> { Ok(Self {}) }
     
-    async fn validate_system_policy(&self, _policy: &SystemPolicyConfig) -> Result<PolicyValidationResult, PolicyError> {
-        Ok(PolicyValidationResult { valid: true, errors: vec![] })
+    /// Check every section, collecting all problems rather than stopping at the first
+    async fn validate_system_policy(&self, policy: &SystemPolicyConfig) -> Result<PolicyValidationResult, PolicyError> {
+        Ok(validate_policy(policy))
     }
     
-    async fn validate_policy_update(&self, _section: &str, _config: &serde_json::Value) -> Result<PolicyValidationResult, PolicyError> {
-        Ok(PolicyValidationResult { valid: true, errors: vec![] })
+    /// Check a replacement for one section, as `update_policy_section` would apply it
+    async fn validate_policy_update(&self, section: &str, config: &serde_json::Value) -> Result<PolicyValidationResult, PolicyError> {
+        fn check<T: for<'de> Deserialize<'de>>(
+            config: &serde_json::Value,
+            errors: &mut Vec<String>,
+            validate: impl FnOnce(&T, &mut Vec<String>),
+        ) {
+            match T::deserialize(config) {
+                Ok(section) => validate(&section, errors),
+                Err(e) => errors.push(format!("malformed section: {}", e)),
+            }
+        }
+        
+        let mut errors = Vec::new();
+        match section {
+            "ai_oracle" => check(config, &mut errors, validate_ai_oracle),
+            "temporal_forensics" => check(config, &mut errors, validate_temporal_forensics),
+            "zero_downtime" => check(config, &mut errors, validate_zero_downtime),
+            "advertising" => check::<AdvertisingPolicy>(config, &mut errors, |_, _| {}),
+            "database" => check(config, &mut errors, validate_database),
+            "quantum_security" => check::<QuantumSecurityPolicy>(config, &mut errors, |_, _| {}),
+            "networking" => check(config, &mut errors, validate_networking),
+            _ => errors.push(format!("unknown section {}", section)),
+        }
+        Ok(PolicyValidationResult::from_errors(errors))
+    }
+}
+
+impl PolicyValidationResult {
+    fn from_errors(errors: Vec<String>) -> Self {
+        Self { valid: errors.is_empty(), errors }
+    }
+}
+
+fn validate_policy(policy: &SystemPolicyConfig) -> PolicyValidationResult {
+    let mut errors = Vec::new();
+    validate_global(&policy.global, &mut errors);
+    validate_ai_oracle(&policy.ai_oracle, &mut errors);
+    validate_temporal_forensics(&policy.temporal_forensics, &mut errors);
+    validate_zero_downtime(&policy.zero_downtime, &mut errors);
+    validate_database(&policy.database, &mut errors);
+    validate_observability(&policy.observability, &mut errors);
+    validate_networking(&policy.networking, &mut errors);
+    PolicyValidationResult::from_errors(errors)
+}
+
+fn check_fraction(field: &str, value: f64, errors: &mut Vec<String>) {
+    if !(0.0..=1.0).contains(&value) {
+        errors.push(format!("{} must be between 0.0 and 1.0, got {}", field, value));
+    }
+}
+
+fn check_positive_duration(field: &str, value: Duration, errors: &mut Vec<String>) {
+    if value <= Duration::zero() {
+        errors.push(format!("{} must be positive, got {}", field, value));
+    }
+}
+
+fn validate_global(global: &GlobalSystemPolicy, errors: &mut Vec<String>) {
+    if global.performance_budget_ms == 0 {
+        errors.push("global.performance_budget_ms must be at least 1".to_string());
+    }
+    if global.mac.tranquility != TranquilityMode::Off && global.mac.tranquility_window_seconds == 0 {
+        errors.push("global.mac.tranquility_window_seconds must be positive when tranquility is enforced".to_string());
+    }
+    if global.mac.max_tracked_objects == 0 {
+        errors.push("global.mac.max_tracked_objects must be at least 1".to_string());
+    }
+}
+
+fn validate_ai_oracle(oracle: &AIOraclePolicy, errors: &mut Vec<String>) {
+    check_fraction("ai_oracle.prediction_confidence_threshold", oracle.prediction_confidence_threshold, errors);
+    if oracle.enabled && oracle.enabled_predictions.is_empty() {
+        errors.push("ai_oracle is enabled but enabled_predictions is empty".to_string());
+    }
+    
+    let remediation = &oracle.auto_remediation;
+    check_fraction("ai_oracle.auto_remediation.min_confidence", remediation.min_confidence, errors);
+    if !remediation.enabled {
+        return;
+    }
+    if remediation.allowed_actions.is_empty() {
+        errors.push("ai_oracle.auto_remediation is enabled but allowed_actions is empty".to_string());
+    }
+    if remediation.max_attempts == 0 {
+        errors.push("ai_oracle.auto_remediation.max_attempts must be at least 1".to_string());
+    }
+    check_positive_duration("ai_oracle.auto_remediation.timeout", remediation.timeout, errors);
+    // Remediating on predictions the oracle wouldn't even report makes no sense
+    if remediation.min_confidence < oracle.prediction_confidence_threshold {
+        errors.push(format!(
+            "ai_oracle.auto_remediation.min_confidence ({}) is below prediction_confidence_threshold ({})",
+            remediation.min_confidence, oracle.prediction_confidence_threshold
+        ));
+    }
+}
+
+fn validate_temporal_forensics(forensics: &TemporalForensicsPolicy, errors: &mut Vec<String>) {
+    check_positive_duration("temporal_forensics.retention_period", forensics.retention_period, errors);
+    check_positive_duration("temporal_forensics.snapshot_frequency", forensics.snapshot_frequency, errors);
+    if forensics.snapshot_frequency > forensics.retention_period {
+        errors.push(format!(
+            "temporal_forensics.snapshot_frequency ({}) exceeds retention_period ({})",
+            forensics.snapshot_frequency, forensics.retention_period
+        ));
+    }
+}
+
+fn validate_zero_downtime(zero_downtime: &ZeroDowntimePolicy, errors: &mut Vec<String>) {
+    if !zero_downtime.enabled {
+        return;
+    }
+    if zero_downtime.allowed_update_types.is_empty() {
+        errors.push("zero_downtime is enabled but allowed_update_types is empty".to_string());
+    }
+    if zero_downtime.deployment_strategies.is_empty() {
+        errors.push("zero_downtime is enabled but deployment_strategies is empty".to_string());
+    }
+}
+
+fn validate_database(database: &DatabasePolicy, errors: &mut Vec<String>) {
+    if let Err(e) = database.connection.validate() {
+        errors.push(format!("database.connection: {}", e));
+    }
+}
+
+fn validate_observability(observability: &ObservabilityPolicy, errors: &mut Vec<String>) {
+    let budgets = &observability.operation_budgets;
+    if budgets.default_ms == 0 {
+        errors.push("observability.operation_budgets.default_ms must be at least 1".to_string());
+    }
+    for (operation, budget_ms) in &budgets.budgets_ms {
+        if *budget_ms == 0 {
+            errors.push(format!("observability.operation_budgets.budgets_ms.{} must be at least 1", operation));
+        }
+    }
+    check_fraction("observability.tail_sampling.base_rate", observability.tail_sampling.base_rate, errors);
+    if observability.tail_sampling.half_life_seconds == 0 {
+        errors.push("observability.tail_sampling.half_life_seconds must be at least 1".to_string());
+    }
+    if observability.budget_escalation.circuit_open_seconds == 0 {
+        errors.push("observability.budget_escalation.circuit_open_seconds must be at least 1".to_string());
+    }
+
+    let thresholds = &observability.performance_state;
+    if !(0.0..1.0).contains(&thresholds.hysteresis) {
+        errors.push(format!(
+            "observability.performance_state.hysteresis must be at least 0.0 and below 1.0, got {}",
+            thresholds.hysteresis
+        ));
+    }
+    
+    let levels = [("degraded", &thresholds.degraded), ("high_load", &thresholds.high_load), ("critical", &thresholds.critical)];
+    for (level, limits) in levels {
+        for (signal, value) in [("operations_per_sec", limits.operations_per_sec), ("p99_latency_ms", limits.p99_latency_ms)] {
+            if value.is_nan() || value <= 0.0 {
+                errors.push(format!("observability.performance_state.{}.{} must be positive, got {}", level, signal, value));
+            }
+        }
+        if limits.pool_saturation.is_nan() || limits.pool_saturation <= 0.0 || limits.pool_saturation > 1.0 {
+            errors.push(format!(
+                "observability.performance_state.{}.pool_saturation must be above 0.0 and at most 1.0, got {}",
+                level, limits.pool_saturation
+            ));
+        }
+    }
+    
+    // Each level must be reached no earlier than the one below it
+    for pair in levels.windows(2) {
+        let ((lower, below), (upper, above)) = (pair[0], pair[1]);
+        let signals = [
+            ("operations_per_sec", below.operations_per_sec, above.operations_per_sec),
+            ("p99_latency_ms", below.p99_latency_ms, above.p99_latency_ms),
+            ("pool_saturation", below.pool_saturation, above.pool_saturation),
+        ];
+        for (signal, lower_value, upper_value) in signals {
+            if upper_value < lower_value {
+                errors.push(format!(
+                    "observability.performance_state.{}.{} ({}) is below {}.{} ({})",
+                    upper, signal, upper_value, lower, signal, lower_value
+                ));
+            }
+        }
+    }
+}
+
+fn validate_networking(networking: &NetworkingPolicy, errors: &mut Vec<String>) {
+    let transport = &networking.transport;
+    if transport.timeout_ms == 0 {
+        errors.push("networking.transport.timeout_ms must be positive".to_string());
+    }
+    let min_tls_version = transport.security_requirements.as_ref().and_then(|r| r.min_tls_version.as_deref());
+    if let Some(version) = min_tls_version.filter(|v| !["1.0", "1.1", "1.2", "1.3"].contains(v)) {
+        errors.push(format!("networking.transport.security_requirements.min_tls_version must be 1.0 to 1.3, got {}", version));
     }
 }
 
@@ -973,11 +1505,290 @@ impl PolicyAuditSystem {
     async fn record_system_toggle(&self, _id: &str, _system: SystemType, _enabled: bool, _result: &PolicyApplicationResult) -> Result<(), PolicyError> { Ok(()) }
 }
 
+/// How long writes to the policy file must settle before it is reloaded
+const POLICY_RELOAD_DEBOUNCE: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Read a policy TOML file, migrating it to the current schema before parsing
+///
+/// Returns the policy and a description of each migration that ran.
+pub(crate) async fn read_policy_file(config_path: impl AsRef<Path>) -> Result<(SystemPolicyConfig, Vec<String>), PolicyError> {
+    let config_content = tokio::fs::read_to_string(config_path).await
+        .map_err(|e| PolicyError::ConfigLoadFailed(e.to_string()))?;
+    
+    parse_policy_document(&config_content)
+}
+
+/// Environment variable naming the system policy file loaded at startup
+pub const SYSTEM_POLICY_ENV: &str = "NODUS_SYSTEM_POLICY";
+
+/// Policy subsystems are built with at startup: the file named by `NODUS_SYSTEM_POLICY`,
+/// or the defaults when it isn't set
+pub async fn startup_policy() -> Result<SystemPolicyConfig, PolicyError> {
+    match std::env::var(SYSTEM_POLICY_ENV) {
+        Ok(path) => read_policy_file(&path).await.map(|(policy, _)| policy),
+        Err(_) => Ok(SystemPolicyConfig::default()),
+    }
+}
+
+/// Read a policy file as `read_policy_file` does, rejecting a policy `validator` finds invalid
+async fn read_valid_policy_file(
+    validator: &PolicyValidator,
+    config_path: impl AsRef<Path>,
+) -> Result<(SystemPolicyConfig, Vec<String>, PolicyValidationResult), PolicyError> {
+    let (system_policy, migrations_applied) = read_policy_file(config_path).await?;
+    let validation_result = validator.validate_system_policy(&system_policy).await?;
+    if !validation_result.valid {
+        return Err(PolicyError::PolicyValidationFailed {
+            errors: validation_result.errors,
+        });
+    }
+    Ok((system_policy, migrations_applied, validation_result))
+}
+
+/// Parse a policy document, migrating it to the current schema first
+pub(crate) fn parse_policy_document(content: &str) -> Result<(SystemPolicyConfig, Vec<String>), PolicyError> {
+    let mut document: toml::Table = toml::from_str(content)
+        .map_err(|e| PolicyError::ConfigParseFailed(e.to_string()))?;
+    let migrations_applied = migrate_policy_document(&mut document)?;
+    
+    let policy = toml::Value::Table(document).try_into()
+        .map_err(|e: toml::de::Error| PolicyError::ConfigParseFailed(e.to_string()))?;
+    Ok((policy, migrations_applied))
+}
+
+/// Current layout version of the policy document
+pub const POLICY_SCHEMA_VERSION: u32 = 2;
+
+fn legacy_schema_version() -> u32 {
+    1
+}
+
+/// One step upgrading a policy document, or one of its sections, from `from_version`
+struct PolicyMigration {
+    /// Section the step rewrites; `None` for the document as a whole
+    section: Option<&'static str>,
+    from_version: u32,
+    description: &'static str,
+    apply: fn(&mut toml::Table) -> Result<(), String>,
+}
+
+/// Every schema change, oldest first within each scope
+///
+/// Changing the shape of a section means adding a step here, which bumps that section's
+/// current version. Steps tolerate documents that already have the new shape.
+static POLICY_MIGRATIONS: &[PolicyMigration] = &[
+    PolicyMigration {
+        section: None,
+        from_version: 1,
+        description: "track per-section schema versions",
+        apply: |document| {
+            document.entry("section_versions").or_insert_with(|| toml::Value::Table(toml::Table::new()));
+            Ok(())
+        },
+    },
+    PolicyMigration {
+        section: Some("database"),
+        from_version: 1,
+        description: "add default connection pool settings",
+        apply: |section| insert_default(section, "connection", &DbConfig::default()),
+    },
+    PolicyMigration {
+        section: Some("observability"),
+        from_version: 1,
+        description: "add default performance_state thresholds",
+        apply: |section| insert_default(section, "performance_state", &PerformanceThresholds::default()),
+    },
+];
+
+fn insert_default<T: Serialize>(table: &mut toml::Table, field: &str, default: &T) -> Result<(), String> {
+    if !table.contains_key(field) {
+        let value = toml::Value::try_from(default).map_err(|e| e.to_string())?;
+        table.insert(field.to_string(), value);
+    }
+    Ok(())
+}
+
+/// Version a scope reaches once every registered step has run
+fn current_version(section: Option<&str>) -> u32 {
+    POLICY_MIGRATIONS.iter()
+        .filter(|migration| migration.section == section)
+        .map(|migration| migration.from_version + 1)
+        .max()
+        .unwrap_or(1)
+}
+
+/// Current schema version of every section that has ever changed shape
+pub fn current_section_versions() -> BTreeMap<String, u32> {
+    POLICY_MIGRATIONS.iter()
+        .filter_map(|migration| migration.section)
+        .map(|section| (section.to_string(), current_version(Some(section))))
+        .collect()
+}
+
+/// Run the steps of `section` (or the document, for `None`) from `version` up to current
+fn run_migrations(
+    section: Option<&str>,
+    mut version: u32,
+    table: &mut toml::Table,
+    applied: &mut Vec<String>,
+) -> Result<u32, PolicyError> {
+    let scope = section.unwrap_or("document");
+    let current = current_version(section);
+    if version > current {
+        return Err(PolicyError::ConfigParseFailed(format!(
+            "{} schema version {} is newer than the supported version {}",
+            scope, version, current
+        )));
+    }
+    
+    while let Some(migration) = POLICY_MIGRATIONS.iter()
+        .find(|migration| migration.section == section && migration.from_version == version)
+    {
+        (migration.apply)(table).map_err(|e| PolicyError::ConfigParseFailed(format!(
+            "{} migration from version {} failed: {}",
+            scope, version, e
+        )))?;
+        version += 1;
+        applied.push(format!("{} v{} -> v{}: {}", scope, migration.from_version, version, migration.description));
+    }
+    Ok(version)
+}
+
+/// Upgrade a parsed policy document in place to the current schema
+///
+/// Returns a description of each step that ran; a current document comes back unchanged.
+fn migrate_policy_document(document: &mut toml::Table) -> Result<Vec<String>, PolicyError> {
+    let version_of = |value: &toml::Value, what: &str| value.as_integer()
+        .and_then(|version| u32::try_from(version).ok())
+        .filter(|version| *version >= 1)
+        .ok_or_else(|| PolicyError::ConfigParseFailed(format!("{} must be a positive integer", what)));
+    
+    let mut applied = Vec::new();
+    let document_version = match document.get("schema_version") {
+        Some(version) => version_of(version, "schema_version")?,
+        None => legacy_schema_version(),
+    };
+    let document_version = run_migrations(None, document_version, document, &mut applied)?;
+    document.insert("schema_version".to_string(), toml::Value::Integer(document_version.into()));
+    
+    let mut section_versions = match document.remove("section_versions") {
+        Some(toml::Value::Table(versions)) => versions,
+        Some(_) => return Err(PolicyError::ConfigParseFailed("section_versions must be a table".to_string())),
+        None => toml::Table::new(),
+    };
+    for (section, current) in current_section_versions() {
+        let Some(toml::Value::Table(table)) = document.get_mut(&section) else {
+            continue;
+        };
+        let version = match section_versions.get(&section) {
+            Some(version) => version_of(version, &format!("section_versions.{}", section))?,
+            None => 1,
+        };
+        let version = run_migrations(Some(&section), version, table, &mut applied)?;
+        debug_assert_eq!(version, current);
+        section_versions.insert(section, toml::Value::Integer(version.into()));
+    }
+    document.insert("section_versions".to_string(), toml::Value::Table(section_versions));
+    
+    Ok(applied)
+}
+
+/// Watches the policy file and reloads it once writes settle
+#[derive(Debug)]
+struct PolicyHotReload {
+    debounce: std::time::Duration,
+    active: tokio::sync::Mutex<Option<PolicyFileWatch>>,
+}
+
+/// A running watch; dropping it stops the watcher and its reload task
 #[derive(Debug)]
-struct PolicyHotReload {}
+struct PolicyFileWatch {
+    _watcher: notify::RecommendedWatcher,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl Drop for PolicyFileWatch {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
 
 impl PolicyHotReload {
-    async fn new() -> Result<Self, PolicyError> { Ok(Self {}) }
+    async fn new() -> Result<Self, PolicyError> { Ok(Self::with_debounce(POLICY_RELOAD_DEBOUNCE)) }
+    
+    fn with_debounce(debounce: std::time::Duration) -> Self {
+        Self { debounce, active: tokio::sync::Mutex::new(None) }
+    }
+    
+    /// Call `reload` after writes to `path` have been quiet for the debounce interval
+    ///
+    /// The parent directory is watched, so editors that save by renaming over the file are
+    /// still seen. A failed reload is logged; the caller keeps whatever it was running.
+    async fn watch<F, Fut>(&self, path: &Path, reload: F) -> Result<(), PolicyError>
+    where
+        F: Fn() -> Fut + Send + 'static,
+        Fut: std::future::Future<Output = Result<(), PolicyError>> + Send + 'static,
+    {
+        use notify::Watcher;
+        
+        let file_name = path.file_name()
+            .ok_or_else(|| PolicyError::HotReloadFailed(format!("{} is not a file", path.display())))?
+            .to_owned();
+        let directory = path.parent()
+            .filter(|directory| !directory.as_os_str().is_empty())
+            .unwrap_or(Path::new("."));
+        
+        let (changes, received) = tokio::sync::mpsc::unbounded_channel();
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| match event {
+            Ok(event) if is_write_to(&event, &file_name) => {
+                let _ = changes.send(());
+            }
+            Ok(_) => {}
+            Err(e) => tracing::warn!("Policy file watch error: {}", e),
+        }).map_err(|e| PolicyError::HotReloadFailed(e.to_string()))?;
+        watcher.watch(directory, notify::RecursiveMode::NonRecursive)
+            .map_err(|e| PolicyError::HotReloadFailed(format!("{}: {}", directory.display(), e)))?;
+        
+        let task = tokio::spawn(debounced_reloads(received, self.debounce, path.display().to_string(), reload));
+        *self.active.lock().await = Some(PolicyFileWatch { _watcher: watcher, task });
+        Ok(())
+    }
+    
+    async fn stop(&self) {
+        self.active.lock().await.take();
+    }
+}
+
+/// Whether `event` created or modified the file named `file_name`
+fn is_write_to(event: &notify::Event, file_name: &std::ffi::OsStr) -> bool {
+    matches!(event.kind, notify::EventKind::Create(_) | notify::EventKind::Modify(_))
+        && event.paths.iter().any(|path| path.file_name() == Some(file_name))
+}
+
+/// Run `reload` once per burst of `changes`, after `debounce` passes without another one
+async fn debounced_reloads<F, Fut>(
+    mut changes: tokio::sync::mpsc::UnboundedReceiver<()>,
+    debounce: std::time::Duration,
+    source: String,
+    reload: F,
+)
+where
+    F: Fn() -> Fut,
+    Fut: std::future::Future<Output = Result<(), PolicyError>>,
+{
+    while changes.recv().await.is_some() {
+        loop {
+            match tokio::time::timeout(debounce, changes.recv()).await {
+                Ok(Some(())) => continue,
+                Ok(None) => return,
+                Err(_) => break,
+            }
+        }
+        match reload().await {
+            Ok(()) => tracing::info!("Reloaded policy from {}", source),
+            Err(e) => tracing::warn!("Rejected policy change in {}, keeping the running policy: {}", source, e),
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -1017,19 +1828,202 @@ impl PolicyOrchestrator {
             database: Arc::new(SelfOptimizingDatabase::new().await.unwrap()),
             quantum_security: Arc::new(PostQuantumSecurity::new().await.unwrap()),
             application_tracker: PolicyApplicationTracker {},
+            subsystems: SubsystemCoordinator::default(),
         })
     }
     
-    async fn apply_policy_to_all_systems(&self, _policy: &SystemPolicyConfig, _state: &AppState) -> Result<PolicyApplicationResult, PolicyError> {
-        Ok(PolicyApplicationResult { success: true, errors: vec![] })
+    async fn apply_policy_to_all_systems(&self, previous: &SystemPolicyConfig, policy: &SystemPolicyConfig, _state: &AppState) -> Result<PolicyApplicationResult, PolicyError> {
+        Ok(self.subsystems.apply(previous, policy, &SystemType::ALL).await)
+    }
+    
+    async fn apply_policy_to_systems(&self, previous: &SystemPolicyConfig, policy: &SystemPolicyConfig, systems: &[SystemType], _state: &AppState) -> Result<PolicyApplicationResult, PolicyError> {
+        Ok(self.subsystems.apply(previous, policy, systems).await)
+    }
+}
+
+/// A running subsystem that takes its settings from the policy
+///
+/// `reconfigure` is all-or-nothing: on error the subsystem keeps the settings it had.
+#[async_trait::async_trait]
+pub trait Reconfigurable: Send + Sync + std::fmt::Debug {
+    /// Name reported in application results
+    fn name(&self) -> &str;
+    
+    /// Policy section the subsystem reads
+    fn system(&self) -> SystemType;
+    
+    /// Whether failing to reconfigure rolls back the whole apply
+    fn critical(&self) -> bool {
+        true
     }
     
-    async fn apply_policy_to_systems(&self, _policy: &SystemPolicyConfig, _systems: &[SystemType], _state: &AppState) -> Result<PolicyApplicationResult, PolicyError> {
-        Ok(PolicyApplicationResult { success: true, errors: vec![] })
+    /// Whether moving from `current` to `next` needs a restart rather than a hot swap
+    fn requires_restart(&self, _current: &SystemPolicyConfig, _next: &SystemPolicyConfig) -> bool {
+        false
     }
     
-    async fn toggle_system(&self, _system: SystemType, _enabled: bool, _state: &AppState) -> Result<PolicyApplicationResult, PolicyError> {
-        Ok(PolicyApplicationResult { success: true, errors: vec![] })
+    /// Stop taking new work and wait for in-flight work to finish, ahead of a restart
+    async fn quiesce(&self) -> Result<(), PolicyError> {
+        Ok(())
+    }
+    
+    /// Take new work again after a restart, whether or not it succeeded
+    async fn resume(&self) {}
+    
+    /// Switch to the settings in `config`
+    async fn reconfigure(&self, config: &SystemPolicyConfig) -> Result<(), PolicyError>;
+}
+
+#[async_trait::async_trait]
+impl Reconfigurable for PerformanceStateMonitor {
+    fn name(&self) -> &str {
+        "performance_state_monitor"
+    }
+    
+    fn system(&self) -> SystemType {
+        SystemType::Observability
+    }
+    
+    fn critical(&self) -> bool {
+        false
+    }
+    
+    async fn reconfigure(&self, config: &SystemPolicyConfig) -> Result<(), PolicyError> {
+        self.set_thresholds(config.observability.performance_state.clone());
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Reconfigurable for BudgetEscalator {
+    fn name(&self) -> &str {
+        "budget_escalator"
+    }
+    
+    fn system(&self) -> SystemType {
+        SystemType::Observability
+    }
+    
+    fn critical(&self) -> bool {
+        false
+    }
+    
+    async fn reconfigure(&self, config: &SystemPolicyConfig) -> Result<(), PolicyError> {
+        self.set_policy(config.observability.budget_escalation.clone()).await;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Reconfigurable for ForensicLogger {
+    fn name(&self) -> &str {
+        "forensic_logger"
+    }
+    
+    fn system(&self) -> SystemType {
+        SystemType::Observability
+    }
+    
+    async fn reconfigure(&self, config: &SystemPolicyConfig) -> Result<(), PolicyError> {
+        self.set_audit_level(config.global.audit_level).await;
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl Reconfigurable for SecureNetworkTransport {
+    fn name(&self) -> &str {
+        "secure_network_transport"
+    }
+    
+    fn system(&self) -> SystemType {
+        SystemType::Networking
+    }
+    
+    async fn reconfigure(&self, config: &SystemPolicyConfig) -> Result<(), PolicyError> {
+        SecureNetworkTransport::reconfigure(self, config.networking.transport.clone())
+            .map_err(|e| PolicyError::SystemApplicationFailed(e.to_string()))
+    }
+}
+
+/// Moves registered subsystems between policies, all together or not at all
+#[derive(Debug, Default)]
+struct SubsystemCoordinator {
+    subsystems: RwLock<Vec<Arc<dyn Reconfigurable>>>,
+    /// One apply at a time, so a rollback restores what the subsystems actually ran
+    apply_lock: tokio::sync::Mutex<()>,
+}
+
+impl SubsystemCoordinator {
+    async fn register(&self, subsystem: Arc<dyn Reconfigurable>) {
+        self.subsystems.write().await.push(subsystem);
+    }
+    
+    /// Move the subsystems of `systems` from `previous` to `next`, in registration order
+    ///
+    /// A failed non-critical subsystem is reported and skipped. A failed critical one stops
+    /// the apply and moves every subsystem already switched back to `previous`.
+    async fn apply(&self, previous: &SystemPolicyConfig, next: &SystemPolicyConfig, systems: &[SystemType]) -> PolicyApplicationResult {
+        let _apply = self.apply_lock.lock().await;
+        let subsystems: Vec<Arc<dyn Reconfigurable>> = self.subsystems.read().await.iter()
+            .filter(|subsystem| systems.contains(&subsystem.system()))
+            .cloned()
+            .collect();
+        
+        let mut switched = Vec::new();
+        let mut outcomes = Vec::new();
+        let mut rolled_back = false;
+        for subsystem in subsystems {
+            let restarted = subsystem.requires_restart(previous, next);
+            let result = Self::switch(subsystem.as_ref(), restarted, next).await;
+            outcomes.push(SubsystemApplication {
+                subsystem: subsystem.name().to_string(),
+                system: subsystem.system(),
+                restarted,
+                success: result.is_ok(),
+                error: result.as_ref().err().map(ToString::to_string),
+            });
+            match result {
+                Ok(()) => switched.push(subsystem),
+                Err(e) if subsystem.critical() => {
+                    tracing::error!("Critical subsystem {} rejected the policy, rolling back: {}", subsystem.name(), e);
+                    rolled_back = true;
+                    break;
+                }
+                Err(e) => tracing::warn!("Subsystem {} rejected the policy: {}", subsystem.name(), e),
+            }
+        }
+        
+        let mut errors: Vec<String> = outcomes.iter()
+            .filter_map(|outcome| outcome.error.as_ref().map(|e| format!("{}: {}", outcome.subsystem, e)))
+            .collect();
+        if rolled_back {
+            for subsystem in switched.iter().rev() {
+                let restart = subsystem.requires_restart(next, previous);
+                if let Err(e) = Self::switch(subsystem.as_ref(), restart, previous).await {
+                    tracing::error!("Subsystem {} could not be rolled back: {}", subsystem.name(), e);
+                    errors.push(format!("{}: rollback failed: {}", subsystem.name(), e));
+                }
+            }
+        }
+        
+        PolicyApplicationResult {
+            success: errors.is_empty(),
+            errors,
+            subsystems: outcomes,
+            rolled_back,
+        }
+    }
+    
+    /// Hot-swap `config` into the subsystem, or quiesce it first when it must restart
+    async fn switch(subsystem: &dyn Reconfigurable, restart: bool, config: &SystemPolicyConfig) -> Result<(), PolicyError> {
+        if !restart {
+            return subsystem.reconfigure(config).await;
+        }
+        subsystem.quiesce().await?;
+        let result = subsystem.reconfigure(config).await;
+        subsystem.resume().await;
+        result
     }
 }
 
@@ -1043,6 +2037,23 @@ pub struct PolicyValidationResult {
 pub struct PolicyApplicationResult {
     pub success: bool,
     pub errors: Vec<String>,
+    /// Outcome for each subsystem tried, in the order they were applied
+    #[serde(default)]
+    pub subsystems: Vec<SubsystemApplication>,
+    /// A critical subsystem failed and every subsystem was moved back to the previous policy
+    #[serde(default)]
+    pub rolled_back: bool,
+}
+
+/// Outcome of applying a policy to one subsystem
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubsystemApplication {
+    pub subsystem: String,
+    pub system: SystemType,
+    /// Quiesced and restarted rather than hot-swapped
+    pub restarted: bool,
+    pub success: bool,
+    pub error: Option<String>,
 }
 
 // Additional placeholder types
@@ -1128,11 +2139,13 @@ impl UnifiedPolicyEngine {
             SystemType::QuantumSecurity,
             SystemType::Observability,
             SystemType::Enterprise,
+            SystemType::Networking,
         ])
     }
     
     async fn get_systems_affected_by_section(&self, section: &str) -> Result<Vec<SystemType>, PolicyError> {
         let system = match section {
+            "global" => SystemType::ALL.to_vec(),
             "ai_oracle" => vec![SystemType::AiOracle],
             "temporal_forensics" => vec![SystemType::TemporalForensics],
             "zero_downtime" => vec![SystemType::ZeroDowntime],
@@ -1141,6 +2154,7 @@ impl UnifiedPolicyEngine {
             "quantum_security" => vec![SystemType::QuantumSecurity],
             "observability" => vec![SystemType::Observability],
             "enterprise" => vec![SystemType::Enterprise],
+            "networking" => vec![SystemType::Networking],
             _ => vec![],
         };
         Ok(system)
@@ -1186,6 +2200,379 @@ mod tests {
         assert_eq!(config.ai_oracle.enabled, parsed.ai_oracle.enabled);
     }
     
+    fn default_document() -> toml::Table {
+        toml::from_str(&toml::to_string(&SystemPolicyConfig::default()).unwrap()).unwrap()
+    }
+    
+    /// A policy file as written before schema versioning
+    fn v1_document() -> String {
+        let mut document = default_document();
+        document.remove("schema_version");
+        document.remove("section_versions");
+        document["database"].as_table_mut().unwrap().remove("connection");
+        document["observability"].as_table_mut().unwrap().remove("performance_state");
+        toml::to_string(&document).unwrap()
+    }
+    
+    #[tokio::test]
+    async fn test_v1_document_migrates_to_current_schema() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("policy.toml");
+        std::fs::write(&path, v1_document()).unwrap();
+        
+        let (policy, migrations) = read_policy_file(&path).await.unwrap();
+        assert_eq!(migrations, [
+            "document v1 -> v2: track per-section schema versions",
+            "database v1 -> v2: add default connection pool settings",
+            "observability v1 -> v2: add default performance_state thresholds",
+        ]);
+        assert_eq!(policy.schema_version, POLICY_SCHEMA_VERSION);
+        assert_eq!(policy.section_versions, current_section_versions());
+        assert_eq!(policy.database.connection.max_connections, DbConfig::default().max_connections);
+        
+        // The migrated policy is valid and, written back out, needs no further migration
+        let validator = PolicyValidator::new().await.unwrap();
+        assert!(validator.validate_system_policy(&policy).await.unwrap().valid);
+        let (reparsed, migrations) = parse_policy_document(&toml::to_string(&policy).unwrap()).unwrap();
+        assert!(migrations.is_empty());
+        assert_eq!(reparsed.database.connection.max_connections, DbConfig::default().max_connections);
+    }
+    
+    #[test]
+    fn test_migration_rejects_newer_documents() {
+        let mut document = default_document();
+        document.insert("schema_version".to_string(), toml::Value::Integer(i64::from(POLICY_SCHEMA_VERSION) + 1));
+        let error = parse_policy_document(&toml::to_string(&document).unwrap()).unwrap_err();
+        assert!(error.to_string().contains("newer than the supported version"), "{}", error);
+        
+        // Sections are versioned independently of the document
+        let mut document = default_document();
+        document["section_versions"].as_table_mut().unwrap().insert("database".to_string(), toml::Value::Integer(3));
+        let error = parse_policy_document(&toml::to_string(&document).unwrap()).unwrap_err();
+        assert!(error.to_string().contains("database schema version 3 is newer than the supported version 2"), "{}", error);
+    }
+    
+    #[tokio::test]
+    async fn test_validator_reports_each_invalid_setting() {
+        let validator = PolicyValidator::new().await.unwrap();
+        let default_result = validator.validate_system_policy(&SystemPolicyConfig::default()).await.unwrap();
+        assert!(default_result.valid, "{:?}", default_result.errors);
+        
+        type BreakPolicy = fn(&mut SystemPolicyConfig);
+        let cases: Vec<(BreakPolicy, &str)> = vec![
+            (|p| p.global.performance_budget_ms = 0, "global.performance_budget_ms must be at least 1"),
+            (|p| p.ai_oracle.prediction_confidence_threshold = 1.5, "ai_oracle.prediction_confidence_threshold must be between 0.0 and 1.0, got 1.5"),
+            (|p| p.ai_oracle.auto_remediation.min_confidence = -0.1, "ai_oracle.auto_remediation.min_confidence must be between 0.0 and 1.0"),
+            (|p| p.ai_oracle.enabled_predictions.clear(), "ai_oracle is enabled but enabled_predictions is empty"),
+            (|p| p.ai_oracle.auto_remediation.allowed_actions.clear(), "ai_oracle.auto_remediation is enabled but allowed_actions is empty"),
+            (|p| p.ai_oracle.auto_remediation.max_attempts = 0, "ai_oracle.auto_remediation.max_attempts must be at least 1"),
+            (|p| p.ai_oracle.auto_remediation.timeout = Duration::seconds(-5), "ai_oracle.auto_remediation.timeout must be positive"),
+            (|p| p.ai_oracle.auto_remediation.min_confidence = 0.5, "ai_oracle.auto_remediation.min_confidence (0.5) is below prediction_confidence_threshold (0.8)"),
+            (|p| p.temporal_forensics.retention_period = Duration::zero(), "temporal_forensics.retention_period must be positive"),
+            (|p| p.temporal_forensics.snapshot_frequency = Duration::days(3000), "temporal_forensics.snapshot_frequency (PT259200000S) exceeds retention_period"),
+            (|p| p.zero_downtime.allowed_update_types.clear(), "zero_downtime is enabled but allowed_update_types is empty"),
+            (|p| p.zero_downtime.deployment_strategies.clear(), "zero_downtime is enabled but deployment_strategies is empty"),
+            (|p| p.database.connection.min_connections = 50, "database.connection: Invalid database config: max_connections (20) is below min_connections (50)"),
+            (|p| p.observability.performance_state.hysteresis = 1.0, "observability.performance_state.hysteresis must be at least 0.0 and below 1.0, got 1"),
+            (|p| p.observability.performance_state.critical.pool_saturation = 1.2, "observability.performance_state.critical.pool_saturation must be above 0.0 and at most 1.0, got 1.2"),
+            (|p| p.observability.performance_state.degraded.p99_latency_ms = -1.0, "observability.performance_state.degraded.p99_latency_ms must be positive, got -1"),
+            (|p| p.observability.performance_state.high_load.operations_per_sec = 100.0, "observability.performance_state.high_load.operations_per_sec (100) is below degraded.operations_per_sec (500)"),
+        ];
+        
+        for (break_policy, expected) in cases {
+            let mut policy = SystemPolicyConfig::default();
+            break_policy(&mut policy);
+            let result = validator.validate_system_policy(&policy).await.unwrap();
+            assert!(!result.valid, "expected {:?}", expected);
+            assert!(result.errors.iter().any(|error| error.starts_with(expected)), "expected {:?} in {:?}", expected, result.errors);
+        }
+        
+        // Disabled subsystems don't need a coherent sub-config
+        let mut policy = SystemPolicyConfig::default();
+        policy.zero_downtime.enabled = false;
+        policy.zero_downtime.deployment_strategies.clear();
+        policy.ai_oracle.auto_remediation.enabled = false;
+        policy.ai_oracle.auto_remediation.allowed_actions.clear();
+        assert!(validator.validate_system_policy(&policy).await.unwrap().valid);
+    }
+    
+    #[tokio::test]
+    async fn test_validator_checks_section_updates() {
+        let validator = PolicyValidator::new().await.unwrap();
+        
+        let mut oracle = SystemPolicyConfig::default().ai_oracle;
+        oracle.prediction_confidence_threshold = 1.5;
+        let result = validator.validate_policy_update("ai_oracle", &serde_json::to_value(&oracle).unwrap()).await.unwrap();
+        assert!(result.errors.iter().any(|error| error.starts_with("ai_oracle.prediction_confidence_threshold")));
+        
+        let result = validator.validate_policy_update("database", &serde_json::json!({"enabled": "yes"})).await.unwrap();
+        assert!(!result.valid && result.errors[0].starts_with("malformed section"));
+        
+        let result = validator.validate_policy_update("nonexistent", &serde_json::json!({})).await.unwrap();
+        assert_eq!(result.errors, ["unknown section nonexistent"]);
+    }
+    
+    #[tokio::test]
+    async fn test_hot_reload_applies_valid_file_and_keeps_policy_on_invalid_one() {
+        let directory = tempfile::tempdir().unwrap();
+        let path = directory.path().join("policy.toml");
+        let mut policy = SystemPolicyConfig::default();
+        
+        // Reloads as `watch_policy_file` does, reporting each outcome; change events are fed
+        // by hand, so the test doesn't depend on filesystem notification timing
+        let validator = Arc::new(PolicyValidator::new().await.unwrap());
+        let running = Arc::new(RwLock::new(policy.clone()));
+        let (changes, received) = tokio::sync::mpsc::unbounded_channel();
+        let (reloaded, mut outcomes) = tokio::sync::mpsc::unbounded_channel();
+        let (target, file) = (running.clone(), path.clone());
+        let reloads = tokio::spawn(debounced_reloads(received, std::time::Duration::from_millis(10), path.display().to_string(), move || {
+            let (validator, target, file, reloaded) = (validator.clone(), target.clone(), file.clone(), reloaded.clone());
+            async move {
+                let result = match read_valid_policy_file(&validator, &file).await {
+                    Ok((policy, _, _)) => {
+                        *target.write().await = policy;
+                        Ok(())
+                    }
+                    Err(e) => Err(e),
+                };
+                let _ = reloaded.send(result.is_ok());
+                result
+            }
+        }));
+        
+        // A burst of writes settles into a single reload of the final content
+        for budget in [5, 6, 7] {
+            policy.global.performance_budget_ms = budget;
+            std::fs::write(&path, toml::to_string(&policy).unwrap()).unwrap();
+            changes.send(()).unwrap();
+        }
+        assert_eq!(outcomes.recv().await, Some(true));
+        assert_eq!(running.read().await.global.performance_budget_ms, 7);
+        
+        // A policy that parses but fails validation is rejected and the running policy stays
+        policy.global.performance_budget_ms = 8;
+        policy.ai_oracle.prediction_confidence_threshold = 1.5;
+        std::fs::write(&path, toml::to_string(&policy).unwrap()).unwrap();
+        changes.send(()).unwrap();
+        assert_eq!(outcomes.recv().await, Some(false));
+        
+        // So is a file that doesn't parse
+        std::fs::write(&path, "[global\nperformance_budget_ms = ").unwrap();
+        changes.send(()).unwrap();
+        assert_eq!(outcomes.recv().await, Some(false));
+        assert_eq!(running.read().await.global.performance_budget_ms, 7);
+        
+        drop(changes);
+        reloads.await.unwrap();
+        assert!(outcomes.recv().await.is_none(), "one reload per burst");
+    }
+    
+    fn tenant(tenant_id: &str, storage_limit_gb: u64, storage_used_gb: u64) -> TenantQuotaUsage {
+        TenantQuotaUsage {
+            tenant_id: tenant_id.to_string(),
+            limits: crate::multi_tenant::TenantResourceLimits {
+                cpu_cores: 4.0,
+                cpu_burst_limit: 8.0,
+                memory_mb: 1000,
+                memory_burst_mb: 2000,
+                storage_gb: storage_limit_gb,
+                storage_iops: 3000,
+                network_bandwidth_mbps: 1000,
+                network_connections: 10000,
+                database_connections: 100,
+                database_storage_gb: 50,
+                api_requests_per_minute: 10000,
+                api_requests_per_hour: 100000,
+                max_users: 100,
+                max_sessions: 500,
+                custom_limits: HashMap::new(),
+            },
+            usage: crate::multi_tenant::ResourceUsage {
+                storage_usage_gb: storage_used_gb,
+                ..Default::default()
+            },
+        }
+    }
+    
+    #[test]
+    fn test_simulated_quota_reduction_flags_tenants_over_new_limit() {
+        let config = SystemPolicyConfig::default();
+        let tenants = vec![tenant("acme", 100, 80), tenant("globex", 100, 20), tenant("initech", 40, 30)];
+        
+        let report = simulate_change(&config, Some(&tenants), PolicyChange::SetTenantQuota {
+            tenant_id: None,
+            resource: "storage_gb".to_string(),
+            limit: 50,
+        });
+        
+        assert!(report.valid, "{:?}", report.validation_errors);
+        assert!(report.restarted_systems.is_empty());
+        // initech's limit is raised, so it is listed but not over
+        let affected: Vec<(&str, bool)> = report.affected_tenants.iter()
+            .map(|impact| (impact.tenant_id.as_str(), impact.over_limit))
+            .collect();
+        assert_eq!(affected, vec![("acme", true), ("globex", false), ("initech", false)]);
+        assert_eq!(report.affected_tenants[0].current_limit, 100);
+        assert_eq!(report.affected_tenants[0].current_usage, 80.0);
+        assert_eq!(report.warnings.len(), 1);
+        
+        let report = simulate_change(&config, Some(&tenants), PolicyChange::SetTenantQuota {
+            tenant_id: Some("initech".to_string()),
+            resource: "storage_gb".to_string(),
+            limit: 40,
+        });
+        assert!(report.affected_tenants.is_empty());
+        
+        let report = simulate_change(&config, Some(&tenants), PolicyChange::SetTenantQuota {
+            tenant_id: Some("umbrella".to_string()),
+            resource: "storage_gb".to_string(),
+            limit: 50,
+        });
+        assert!(!report.valid);
+        let report = simulate_change(&config, Some(&tenants), PolicyChange::SetTenantQuota {
+            tenant_id: None,
+            resource: "parking_spaces".to_string(),
+            limit: 50,
+        });
+        assert!(!report.valid);
+    }
+    
+    #[test]
+    fn test_simulated_change_reports_restarts_and_validation() {
+        let config = SystemPolicyConfig::default();
+        
+        let report = simulate_change(&config, None, PolicyChange::SetSystemEnabled {
+            system: SystemType::Observability,
+            enabled: !config.observability.enabled,
+        });
+        assert_eq!(report.restarted_systems, vec![SystemType::Observability]);
+        
+        let mut oracle = serde_json::to_value(&config.ai_oracle).unwrap();
+        oracle["prediction_confidence_threshold"] = serde_json::json!(1.5);
+        let report = simulate_change(&config, None, PolicyChange::UpdateSection {
+            section: "ai_oracle".to_string(),
+            config: oracle,
+        });
+        assert_eq!(report.restarted_systems, vec![SystemType::AiOracle]);
+        assert!(!report.valid);
+    }
+    
+    /// Subsystem that records what it went through, keyed by `performance_budget_ms`
+    #[derive(Debug)]
+    struct FakeSubsystem {
+        name: &'static str,
+        system: SystemType,
+        critical: bool,
+        restart: bool,
+        rejects: Option<u64>,
+        events: Arc<std::sync::Mutex<Vec<String>>>,
+    }
+    
+    impl FakeSubsystem {
+        fn new(name: &'static str, system: SystemType, events: &Arc<std::sync::Mutex<Vec<String>>>) -> Self {
+            Self { name, system, critical: true, restart: false, rejects: None, events: events.clone() }
+        }
+    }
+    
+    #[async_trait::async_trait]
+    impl Reconfigurable for FakeSubsystem {
+        fn name(&self) -> &str {
+            self.name
+        }
+        
+        fn system(&self) -> SystemType {
+            self.system
+        }
+        
+        fn critical(&self) -> bool {
+            self.critical
+        }
+        
+        fn requires_restart(&self, _current: &SystemPolicyConfig, _next: &SystemPolicyConfig) -> bool {
+            self.restart
+        }
+        
+        async fn quiesce(&self) -> Result<(), PolicyError> {
+            self.events.lock().unwrap().push(format!("{} quiesce", self.name));
+            Ok(())
+        }
+        
+        async fn resume(&self) {
+            self.events.lock().unwrap().push(format!("{} resume", self.name));
+        }
+        
+        async fn reconfigure(&self, config: &SystemPolicyConfig) -> Result<(), PolicyError> {
+            let budget = config.global.performance_budget_ms;
+            if self.rejects == Some(budget) {
+                return Err(PolicyError::SystemApplicationFailed(format!("{} rejected", budget)));
+            }
+            self.events.lock().unwrap().push(format!("{} {}", self.name, budget));
+            Ok(())
+        }
+    }
+    
+    fn budgets(previous: u64, next: u64) -> (SystemPolicyConfig, SystemPolicyConfig) {
+        let mut before = SystemPolicyConfig::default();
+        before.global.performance_budget_ms = previous;
+        let mut after = before.clone();
+        after.global.performance_budget_ms = next;
+        (before, after)
+    }
+    
+    #[tokio::test]
+    async fn test_critical_subsystem_failure_rolls_back_the_apply() {
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let coordinator = SubsystemCoordinator::default();
+        coordinator.register(Arc::new(FakeSubsystem::new("oracle", SystemType::AiOracle, &events))).await;
+        coordinator.register(Arc::new(FakeSubsystem {
+            restart: true,
+            ..FakeSubsystem::new("database", SystemType::Database, &events)
+        })).await;
+        coordinator.register(Arc::new(FakeSubsystem {
+            rejects: Some(20),
+            ..FakeSubsystem::new("forensics", SystemType::TemporalForensics, &events)
+        })).await;
+        coordinator.register(Arc::new(FakeSubsystem::new("metrics", SystemType::Observability, &events))).await;
+        
+        let (before, after) = budgets(10, 20);
+        let result = coordinator.apply(&before, &after, &SystemType::ALL).await;
+        
+        assert!(result.rolled_back);
+        assert!(!result.success);
+        let outcomes: Vec<(&str, bool)> = result.subsystems.iter()
+            .map(|outcome| (outcome.subsystem.as_str(), outcome.success))
+            .collect();
+        assert_eq!(outcomes, vec![("oracle", true), ("database", true), ("forensics", false)]);
+        assert!(result.subsystems[1].restarted);
+        
+        // Applied subsystems go back in reverse order; the rest are never touched
+        assert_eq!(*events.lock().unwrap(), vec![
+            "oracle 20", "database quiesce", "database 20", "database resume",
+            "database quiesce", "database 10", "database resume", "oracle 10",
+        ]);
+    }
+    
+    #[tokio::test]
+    async fn test_non_critical_failure_and_unselected_systems_do_not_roll_back() {
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let coordinator = SubsystemCoordinator::default();
+        coordinator.register(Arc::new(FakeSubsystem {
+            critical: false,
+            rejects: Some(20),
+            ..FakeSubsystem::new("metrics", SystemType::Observability, &events)
+        })).await;
+        coordinator.register(Arc::new(FakeSubsystem::new("database", SystemType::Database, &events))).await;
+        coordinator.register(Arc::new(FakeSubsystem::new("oracle", SystemType::AiOracle, &events))).await;
+        
+        let (before, after) = budgets(10, 20);
+        let result = coordinator.apply(&before, &after, &[SystemType::Observability, SystemType::Database]).await;
+        
+        assert!(!result.rolled_back);
+        assert!(!result.success);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(*events.lock().unwrap(), vec!["database 20"]);
+    }
+    
     #[tokio::test]
     async fn test_policy_engine_creation() {
         let forensic_logger = Arc::new(ForensicLogger::new().await.unwrap());
@@ -0,0 +1,209 @@
+// src-tauri/src/security/abac.rs
+// Attribute-Based Access Control - Attribute predicates layered on top of MAC
+// MAC decides clearance/compartments first; ABAC rules can only narrow access further
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+use super::{MACEngine, MACOperation, SecurityError, SecurityLabel, UserContext};
+
+/// Resource being accessed: its MAC label plus attributes for ABAC rules
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AbacResource {
+    pub label: SecurityLabel,
+    #[serde(default)]
+    pub attributes: HashMap<String, Value>,
+}
+
+/// Attribute predicate evaluated against the subject's and resource's attributes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum AbacRule {
+    /// Subject attribute equals a literal (e.g. `citizenship == "US"`)
+    SubjectEquals { attribute: String, value: Value },
+
+    /// Subject attribute is one of the listed values
+    SubjectIn { attribute: String, values: Vec<Value> },
+
+    /// Subject and resource carry the same value for their attributes (e.g. `project`)
+    AttributesMatch {
+        subject_attribute: String,
+        resource_attribute: String,
+    },
+
+    /// Every value of the resource attribute is held by the subject attribute (need-to-know)
+    SubjectHoldsAll {
+        subject_attribute: String,
+        resource_attribute: String,
+    },
+
+    All { rules: Vec<AbacRule> },
+    Any { rules: Vec<AbacRule> },
+    Not { rule: Box<AbacRule> },
+}
+
+impl AbacRule {
+    /// Evaluate the predicate; missing attributes never satisfy a positive predicate
+    pub fn evaluate(&self, subject: &HashMap<String, Value>, resource: &HashMap<String, Value>) -> bool {
+        match self {
+            AbacRule::SubjectEquals { attribute, value } => subject.get(attribute) == Some(value),
+            AbacRule::SubjectIn { attribute, values } => subject.get(attribute)
+                .map(|actual| values.contains(actual))
+                .unwrap_or(false),
+            AbacRule::AttributesMatch { subject_attribute, resource_attribute } => {
+                match (subject.get(subject_attribute), resource.get(resource_attribute)) {
+                    (Some(held), Some(required)) => held == required,
+                    _ => false,
+                }
+            }
+            AbacRule::SubjectHoldsAll { subject_attribute, resource_attribute } => {
+                let required = match resource.get(resource_attribute) {
+                    Some(required) => as_values(required),
+                    // Nothing required by the resource
+                    None => return true,
+                };
+                let held = subject.get(subject_attribute).map(as_values).unwrap_or_default();
+                required.iter().all(|tag| held.contains(tag))
+            }
+            AbacRule::All { rules } => rules.iter().all(|rule| rule.evaluate(subject, resource)),
+            AbacRule::Any { rules } => rules.iter().any(|rule| rule.evaluate(subject, resource)),
+            AbacRule::Not { rule } => !rule.evaluate(subject, resource),
+        }
+    }
+}
+
+/// Decide access by combining MAC with an ABAC rule
+///
+/// The context must be unexpired and pass the MAC check for `operation` before the
+/// attribute rule is consulted, so a rule can deny access that MAC grants but never
+/// grant access that MAC denies.
+pub async fn evaluate_abac(
+    mac_engine: &MACEngine,
+    context: &UserContext,
+    resource: &AbacResource,
+    operation: MACOperation,
+    rule: &AbacRule,
+) -> Result<(), SecurityError> {
+    if !context.is_valid_at(mac_engine.clock().now()) {
+        return Err(SecurityError::ContextExpired);
+    }
+
+    let subject = context.to_security_label();
+    let mac_allowed = match operation {
+        MACOperation::Read => mac_engine.can_read(&subject, &resource.label).await,
+        MACOperation::Write => mac_engine.can_write(&subject, &resource.label).await,
+    };
+    if !mac_allowed {
+        return Err(SecurityError::MACViolation { operation });
+    }
+
+    if !rule.evaluate(&context.attributes, &resource.attributes) {
+        return Err(SecurityError::AttributeDenied(format!("{:?}", operation)));
+    }
+
+    Ok(())
+}
+
+/// Treat a scalar as a one-element set so tags can be given either way
+fn as_values(value: &Value) -> Vec<Value> {
+    match value {
+        Value::Array(values) => values.clone(),
+        other => vec![other.clone()],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::ClassificationLevel;
+    use serde_json::json;
+
+    fn analyst(attributes: Vec<(&str, Value)>) -> UserContext {
+        UserContext {
+            user_id: uuid::Uuid::new_v4(),
+            level: ClassificationLevel::Secret,
+            compartments: ["ALPHA".to_string()].into_iter().collect(),
+            expires: chrono::Utc::now() + chrono::Duration::hours(1),
+            roles: vec!["analyst".to_string()],
+            tenant_id: None,
+            attributes: attributes.into_iter().map(|(k, v)| (k.to_string(), v)).collect(),
+        }
+    }
+
+    fn need_to_know() -> AbacRule {
+        AbacRule::SubjectHoldsAll {
+            subject_attribute: "need_to_know".to_string(),
+            resource_attribute: "need_to_know".to_string(),
+        }
+    }
+
+    fn report() -> AbacResource {
+        AbacResource {
+            label: SecurityLabel::new(ClassificationLevel::Confidential, vec!["ALPHA".to_string()]),
+            attributes: [("need_to_know".to_string(), json!(["OPERATION_X"]))].into(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_need_to_know_denies_despite_clearance() {
+        let mac = MACEngine::with_decision_floor(0);
+        let context = analyst(vec![("need_to_know", json!(["OPERATION_Y"]))]);
+
+        // Secret/ALPHA clears MAC for a Confidential/ALPHA report...
+        assert!(mac.can_read(&context.to_security_label(), &report().label).await);
+
+        // ...but the need-to-know rule still denies
+        let result = evaluate_abac(&mac, &context, &report(), MACOperation::Read, &need_to_know()).await;
+        assert!(matches!(result, Err(SecurityError::AttributeDenied(_))));
+
+        let cleared = analyst(vec![("need_to_know", json!(["OPERATION_X", "OPERATION_Y"]))]);
+        assert!(evaluate_abac(&mac, &cleared, &report(), MACOperation::Read, &need_to_know()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_rule_cannot_override_mac() {
+        let mac = MACEngine::with_decision_floor(0);
+        let mut context = analyst(vec![("citizenship", json!("US"))]);
+        context.level = ClassificationLevel::Internal;
+
+        let rule = AbacRule::SubjectEquals { attribute: "citizenship".to_string(), value: json!("US") };
+        let result = evaluate_abac(&mac, &context, &report(), MACOperation::Read, &rule).await;
+        assert!(matches!(result, Err(SecurityError::MACViolation { .. })));
+    }
+
+    #[test]
+    fn test_composite_rules_and_missing_attributes() {
+        let subject: HashMap<String, Value> = [
+            ("citizenship".to_string(), json!("US")),
+            ("project".to_string(), json!("apollo")),
+        ].into();
+        let resource: HashMap<String, Value> = [("project".to_string(), json!("apollo"))].into();
+
+        let rule = AbacRule::All {
+            rules: vec![
+                AbacRule::SubjectIn { attribute: "citizenship".to_string(), values: vec![json!("US"), json!("UK")] },
+                AbacRule::AttributesMatch {
+                    subject_attribute: "project".to_string(),
+                    resource_attribute: "project".to_string(),
+                },
+            ],
+        };
+        assert!(rule.evaluate(&subject, &resource));
+        assert!(!rule.evaluate(&HashMap::new(), &resource));
+    }
+
+    #[test]
+    fn test_context_without_attributes_deserializes() {
+        let context: UserContext = serde_json::from_value(json!({
+            "user_id": uuid::Uuid::new_v4(),
+            "level": "secret",
+            "compartments": [],
+            "expires": chrono::Utc::now(),
+            "roles": [],
+            "tenant_id": null
+        })).unwrap();
+
+        assert!(context.attributes.is_empty());
+    }
+}
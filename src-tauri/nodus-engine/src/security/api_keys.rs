@@ -0,0 +1,529 @@
+// src-tauri/src/security/api_keys.rs
+// API Keys - Tenant-scoped keys for machine-to-machine gateway callers
+// Only a digest of each secret is stored; a validated key yields the contexts MAC and permission checks take
+// Keys never grant more than their issuer holds, and each issue and revocation is recorded in the forensic log
+
+use base64::{Engine as _, engine::general_purpose};
+use chrono::{DateTime, Duration, Utc};
+use ring::{digest, rand};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+use zeroize::Zeroizing;
+
+use super::{constant_time, has_permission, Permission, PermissionHolder, SecurityLabel, UserContext};
+use crate::clock::{self, Clock};
+use crate::database::DatabaseManager;
+use crate::observability::ForensicEnvelope;
+
+/// Prefix of every presented key, so leaked keys are easy to scan for
+pub const API_KEY_PREFIX: &str = "nodus_";
+
+/// Header gateway callers present their key in
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Lifetime of the context a validated key without an expiry produces
+pub const API_KEY_CONTEXT_TTL_MINUTES: i64 = 5;
+
+/// A newly issued key; the only time the secret is available
+pub struct ApiKey {
+    pub key_id: Uuid,
+    pub tenant_id: String,
+    pub expires_at: Option<DateTime<Utc>>,
+    token: Zeroizing<String>,
+}
+
+impl std::fmt::Debug for ApiKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ApiKey")
+            .field("key_id", &self.key_id)
+            .field("tenant_id", &self.tenant_id)
+            .field("expires_at", &self.expires_at)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ApiKey {
+    /// The value the caller presents, `nodus_<key id>_<secret>`
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+}
+
+/// What the store keeps for a key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredApiKey {
+    pub key_id: Uuid,
+    pub tenant_id: String,
+    pub permissions: Vec<Permission>,
+    pub label: SecurityLabel,
+    /// Base64 SHA-256 of the key id and secret
+    pub secret_hash: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+/// Caller identity established by a valid key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiKeyContext {
+    pub key_id: Uuid,
+    pub tenant_id: String,
+    pub label: SecurityLabel,
+    permission_names: Vec<String>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub validated_at: DateTime<Utc>,
+}
+
+impl ApiKeyContext {
+    /// Principal name the key acts as in audit records
+    pub fn principal(&self) -> String {
+        format!("api_key:{}", self.key_id)
+    }
+
+    pub fn permissions(&self) -> Vec<Permission> {
+        self.permission_names.iter().filter_map(|name| Permission::from_name(name)).collect()
+    }
+
+    /// Context for MAC decisions, valid until the key expires or for a short TTL
+    pub fn to_mac_context(&self) -> UserContext {
+        UserContext {
+            user_id: self.key_id,
+            level: self.label.level.clone(),
+            compartments: self.label.compartments.clone(),
+            expires: self.context_expiry(),
+            roles: Vec::new(),
+            tenant_id: Some(self.tenant_id.clone()),
+            attributes: HashMap::from([
+                ("principal_type".to_string(), serde_json::json!("api_key")),
+                ("tenant_id".to_string(), serde_json::json!(self.tenant_id)),
+            ]),
+        }
+    }
+
+    /// Context for the gateway and other callers of `has_permission`
+    pub fn to_user_context(&self) -> crate::state::UserContext {
+        let mut compartments: Vec<String> = self.label.compartments.iter().cloned().collect();
+        compartments.sort();
+        let mut context = crate::state::UserContext::new(
+            self.principal(),
+            self.label.level.clone(),
+            compartments,
+            self.permission_names.clone(),
+        );
+        context.session_id = self.key_id;
+        context.login_time = self.validated_at;
+        context.expires = self.context_expiry();
+        context.with_tenant(&self.tenant_id)
+    }
+
+    fn context_expiry(&self) -> DateTime<Utc> {
+        let ttl = self.validated_at + Duration::minutes(API_KEY_CONTEXT_TTL_MINUTES);
+        self.expires_at.map_or(ttl, |expires_at| expires_at.min(ttl))
+    }
+}
+
+impl PermissionHolder for ApiKeyContext {
+    fn permission_names(&self) -> &[String] {
+        &self.permission_names
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ApiKeyError {
+    #[error("Malformed API key")]
+    Malformed,
+
+    /// Unknown key id or wrong secret; deliberately not told apart
+    #[error("Invalid API key")]
+    Invalid,
+
+    #[error("API key {key_id} has been revoked")]
+    Revoked { key_id: Uuid },
+
+    #[error("API key {key_id} has expired")]
+    Expired { key_id: Uuid },
+
+    /// The key would grant more than the user issuing or revoking it holds
+    #[error("Not permitted to manage this API key: {0}")]
+    Unauthorized(String),
+
+    #[error("API key store error: {0}")]
+    Store(String),
+}
+
+/// Persistence for issued keys; each change is stored together with the envelope recording it
+#[async_trait::async_trait]
+pub trait ApiKeyStore: Send + Sync {
+    async fn insert(&self, key: StoredApiKey, event: &ForensicEnvelope) -> Result<(), ApiKeyError>;
+
+    async fn get(&self, key_id: Uuid) -> Result<Option<StoredApiKey>, ApiKeyError>;
+
+    /// Mark the key revoked at `at`; false if there's no such key, in which case `event` isn't stored
+    async fn revoke(&self, key_id: Uuid, at: DateTime<Utc>, event: &ForensicEnvelope) -> Result<bool, ApiKeyError>;
+}
+
+#[async_trait::async_trait]
+impl ApiKeyStore for DatabaseManager {
+    async fn insert(&self, key: StoredApiKey, event: &ForensicEnvelope) -> Result<(), ApiKeyError> {
+        self.insert_api_key(&key, event).await.map_err(|e| ApiKeyError::Store(e.to_string()))
+    }
+
+    async fn get(&self, key_id: Uuid) -> Result<Option<StoredApiKey>, ApiKeyError> {
+        self.api_key(key_id).await.map_err(|e| ApiKeyError::Store(e.to_string()))
+    }
+
+    async fn revoke(&self, key_id: Uuid, at: DateTime<Utc>, event: &ForensicEnvelope) -> Result<bool, ApiKeyError> {
+        self.revoke_api_key(key_id, at, event).await.map_err(|e| ApiKeyError::Store(e.to_string()))
+    }
+}
+
+/// Keys held in process memory, with the envelopes recorded for them; for development and tests
+#[derive(Debug, Default)]
+pub struct InMemoryApiKeyStore {
+    keys: RwLock<HashMap<Uuid, StoredApiKey>>,
+    events: RwLock<Vec<ForensicEnvelope>>,
+}
+
+impl InMemoryApiKeyStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Envelopes recorded so far, oldest first
+    pub async fn events(&self) -> Vec<ForensicEnvelope> {
+        self.events.read().await.clone()
+    }
+}
+
+#[async_trait::async_trait]
+impl ApiKeyStore for InMemoryApiKeyStore {
+    async fn insert(&self, key: StoredApiKey, event: &ForensicEnvelope) -> Result<(), ApiKeyError> {
+        self.keys.write().await.insert(key.key_id, key);
+        self.events.write().await.push(event.clone());
+        Ok(())
+    }
+
+    async fn get(&self, key_id: Uuid) -> Result<Option<StoredApiKey>, ApiKeyError> {
+        Ok(self.keys.read().await.get(&key_id).cloned())
+    }
+
+    async fn revoke(&self, key_id: Uuid, at: DateTime<Utc>, event: &ForensicEnvelope) -> Result<bool, ApiKeyError> {
+        let mut keys = self.keys.write().await;
+        let Some(key) = keys.get_mut(&key_id) else {
+            return Ok(false);
+        };
+        key.revoked_at.get_or_insert(at);
+        self.events.write().await.push(event.clone());
+        Ok(true)
+    }
+}
+
+/// Issues, validates and revokes API keys
+pub struct ApiKeyManager {
+    store: Arc<dyn ApiKeyStore>,
+    clock: Arc<dyn Clock>,
+}
+
+impl ApiKeyManager {
+    pub fn new(store: Arc<dyn ApiKeyStore>) -> Self {
+        Self { store, clock: clock::system_clock() }
+    }
+
+    /// Judge expiry by `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Issue a key for `tenant_id` granting `permissions` up to `label`, expiring after `expiry` if given
+    ///
+    /// `issuer` must hold every permission granted, dominate `label` and, if scoped to a tenant, belong to `tenant_id`.
+    pub async fn issue_api_key(
+        &self,
+        issuer: &crate::state::UserContext,
+        tenant_id: &str,
+        permissions: Vec<Permission>,
+        label: SecurityLabel,
+        expiry: Option<Duration>,
+    ) -> Result<ApiKey, ApiKeyError> {
+        Self::authorize(issuer, tenant_id)?;
+        if let Some(permission) = permissions.iter().find(|permission| !has_permission(issuer, **permission)) {
+            return Err(ApiKeyError::Unauthorized(format!("issuer does not hold {}", permission)));
+        }
+        if !issuer.to_security_label().dominates(&label) {
+            return Err(ApiKeyError::Unauthorized("key label exceeds the issuer's clearance".to_string()));
+        }
+
+        let mut secret = Zeroizing::new([0u8; 32]);
+        rand::SecureRandom::fill(&rand::SystemRandom::new(), secret.as_mut())
+            .map_err(|_| ApiKeyError::Store("failed to generate API key secret".to_string()))?;
+        let secret = Zeroizing::new(general_purpose::URL_SAFE_NO_PAD.encode(secret.as_ref()));
+
+        let key_id = Uuid::new_v4();
+        let now = self.clock.now();
+        let expires_at = expiry.map(|expiry| now + expiry);
+        let key = StoredApiKey {
+            key_id,
+            tenant_id: tenant_id.to_string(),
+            permissions,
+            label,
+            secret_hash: secret_hash(key_id, &secret),
+            created_at: now,
+            expires_at,
+            revoked_at: None,
+        };
+        let event = key_envelope("api_key.issued", &key, issuer);
+        self.store.insert(key, &event).await?;
+
+        crate::security_log!(info, key_id = %key_id, tenant_id = %tenant_id, issuer = %issuer.user_id, "API key issued");
+        Ok(ApiKey {
+            key_id,
+            tenant_id: tenant_id.to_string(),
+            expires_at,
+            token: Zeroizing::new(format!("{}{}_{}", API_KEY_PREFIX, key_id.simple(), secret.as_str())),
+        })
+    }
+
+    /// Check `presented` against its stored digest, then its revocation and expiry
+    pub async fn validate_api_key(&self, presented: &str) -> Result<ApiKeyContext, ApiKeyError> {
+        let (key_id, secret) = parse_token(presented).ok_or(ApiKeyError::Malformed)?;
+        let stored = self.store.get(key_id).await?;
+
+        // Hash and compare even for an unknown id, so a miss takes as long as a wrong secret
+        let presented_hash = secret_hash(key_id, secret);
+        let expected = stored.as_ref().map_or("", |key| key.secret_hash.as_str());
+        let matches = constant_time::compare_strings(&presented_hash, expected);
+        let key = match stored {
+            Some(key) if matches => key,
+            _ => return Err(ApiKeyError::Invalid),
+        };
+
+        let now = self.clock.now();
+        if key.revoked_at.is_some_and(|revoked_at| revoked_at <= now) {
+            crate::security_log!(warn, key_id = %key_id, tenant_id = %key.tenant_id, "Revoked API key presented");
+            return Err(ApiKeyError::Revoked { key_id });
+        }
+        if key.expires_at.is_some_and(|expires_at| expires_at <= now) {
+            return Err(ApiKeyError::Expired { key_id });
+        }
+
+        Ok(ApiKeyContext {
+            key_id,
+            tenant_id: key.tenant_id,
+            label: key.label,
+            permission_names: key.permissions.iter().map(|permission| permission.as_str().to_string()).collect(),
+            expires_at: key.expires_at,
+            validated_at: now,
+        })
+    }
+
+    /// Revoke `key_id` from now on on behalf of `revoker`; false if there's no such key
+    pub async fn revoke_api_key(&self, key_id: Uuid, revoker: &crate::state::UserContext) -> Result<bool, ApiKeyError> {
+        let Some(key) = self.store.get(key_id).await? else {
+            return Ok(false);
+        };
+        Self::authorize(revoker, &key.tenant_id)?;
+
+        let event = key_envelope("api_key.revoked", &key, revoker);
+        let revoked = self.store.revoke(key_id, self.clock.now(), &event).await?;
+        if revoked {
+            crate::security_log!(warn, key_id = %key_id, tenant_id = %key.tenant_id, revoker = %revoker.user_id, "API key revoked");
+        }
+        Ok(revoked)
+    }
+
+    /// Whether `user` may manage keys of `tenant_id`: with a live context, and in that tenant if scoped to one
+    fn authorize(user: &crate::state::UserContext, tenant_id: &str) -> Result<(), ApiKeyError> {
+        if !user.is_valid() {
+            return Err(ApiKeyError::Unauthorized("user context expired".to_string()));
+        }
+        match &user.tenant_id {
+            Some(own) if own != tenant_id => Err(ApiKeyError::Unauthorized(format!("user belongs to tenant {}", own))),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Forensic record of `event_type` on `key` by `actor`; never carries the secret or its digest
+fn key_envelope(event_type: &str, key: &StoredApiKey, actor: &crate::state::UserContext) -> ForensicEnvelope {
+    ForensicEnvelope::new(
+        Uuid::new_v4(),
+        event_type,
+        &actor.user_id,
+        actor.session_id,
+        key.label.level.clone(),
+        event_type,
+    )
+    .with_resource(&format!("api_key:{}", key.key_id))
+    .with_metadata(serde_json::json!({
+        "key_id": key.key_id,
+        "tenant_id": key.tenant_id,
+        "permissions": key.permissions.iter().map(|permission| permission.as_str()).collect::<Vec<_>>(),
+        "expires_at": key.expires_at,
+    }))
+}
+
+/// Split `nodus_<key id>_<secret>` into its key id and secret
+fn parse_token(presented: &str) -> Option<(Uuid, &str)> {
+    let (key_id, secret) = presented.trim().strip_prefix(API_KEY_PREFIX)?.split_once('_')?;
+    if secret.is_empty() {
+        return None;
+    }
+    Some((Uuid::parse_str(key_id).ok()?, secret))
+}
+
+/// Digest stored in place of the secret; the key id is bound in so digests can't be swapped between keys
+fn secret_hash(key_id: Uuid, secret: &str) -> String {
+    let mut context = digest::Context::new(&digest::SHA256);
+    context.update(key_id.as_bytes());
+    context.update(secret.as_bytes());
+    general_purpose::STANDARD.encode(context.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+    use crate::security::{has_permission, ClassificationLevel, MACEngine, MACOperation};
+
+    fn manager() -> (ApiKeyManager, Arc<InMemoryApiKeyStore>, Arc<MockClock>) {
+        let store = Arc::new(InMemoryApiKeyStore::new());
+        let clock = Arc::new(MockClock::default());
+        (ApiKeyManager::new(store.clone()).with_clock(clock.clone()), store, clock)
+    }
+
+    fn label(level: ClassificationLevel) -> SecurityLabel {
+        SecurityLabel::new(level, vec!["billing".to_string()])
+    }
+
+    /// Tenant administrator able to issue any of the keys below
+    fn admin() -> crate::state::UserContext {
+        crate::state::UserContext::new(
+            "acme-admin".to_string(),
+            ClassificationLevel::Secret,
+            vec!["billing".to_string()],
+            vec![Permission::Read.as_str().to_string(), Permission::Write.as_str().to_string()],
+        )
+        .with_tenant("acme")
+    }
+
+    #[tokio::test]
+    async fn test_valid_key_yields_scoped_contexts() {
+        // System clock, since the contexts are checked by components that use it
+        let manager = ApiKeyManager::new(Arc::new(InMemoryApiKeyStore::new()));
+        let key = manager
+            .issue_api_key(&admin(), "acme", vec![Permission::Read], label(ClassificationLevel::Confidential), None)
+            .await
+            .unwrap();
+
+        let context = manager.validate_api_key(key.token()).await.unwrap();
+        assert_eq!(context.key_id, key.key_id);
+        assert_eq!(context.tenant_id, "acme");
+        assert_eq!(context.to_mac_context().tenant_id.as_deref(), Some("acme"));
+        assert!(has_permission(&context, Permission::Read));
+        assert!(!has_permission(&context, Permission::Write));
+
+        let user = context.to_user_context();
+        assert!(user.is_valid());
+        assert!(has_permission(&user, Permission::Read));
+        assert_eq!(user.clearance_level, ClassificationLevel::Confidential);
+        assert_eq!(user.tenant_id.as_deref(), Some("acme"));
+
+        let mac = MACEngine::with_decision_floor(0);
+        let object = label(ClassificationLevel::Internal);
+        assert!(mac.verdict_for_context(MACOperation::Read, &context.to_mac_context(), &object).await.allowed);
+        assert!(!mac.verdict_for_context(MACOperation::Read, &context.to_mac_context(), &label(ClassificationLevel::Secret)).await.allowed);
+
+        // A right key id with the wrong secret, and a garbled key, both fail
+        let forged = format!("{}{}_{}", API_KEY_PREFIX, key.key_id.simple(), "A".repeat(43));
+        assert!(matches!(manager.validate_api_key(&forged).await, Err(ApiKeyError::Invalid)));
+        assert!(matches!(manager.validate_api_key("not-a-key").await, Err(ApiKeyError::Malformed)));
+    }
+
+    #[tokio::test]
+    async fn test_revoked_key_rejected() {
+        let (manager, _, _) = manager();
+        let key = manager
+            .issue_api_key(&admin(), "acme", vec![Permission::Read], label(ClassificationLevel::Internal), None)
+            .await
+            .unwrap();
+        assert!(manager.validate_api_key(key.token()).await.is_ok());
+
+        assert!(manager.revoke_api_key(key.key_id, &admin()).await.unwrap());
+        assert!(matches!(manager.validate_api_key(key.token()).await, Err(ApiKeyError::Revoked { .. })));
+        assert!(!manager.revoke_api_key(Uuid::new_v4(), &admin()).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_expired_key_rejected() {
+        let (manager, _, clock) = manager();
+        let key = manager
+            .issue_api_key(&admin(), "acme", vec![Permission::Read], label(ClassificationLevel::Internal), Some(Duration::hours(1)))
+            .await
+            .unwrap();
+
+        // The context never outlives the key
+        let context = manager.validate_api_key(key.token()).await.unwrap();
+        assert!(context.to_mac_context().expires <= key.expires_at.unwrap());
+
+        clock.advance(std::time::Duration::from_secs(3600));
+        assert!(matches!(manager.validate_api_key(key.token()).await, Err(ApiKeyError::Expired { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_raw_secret_never_stored() {
+        let (manager, store, _) = manager();
+        let key = manager
+            .issue_api_key(&admin(), "acme", vec![Permission::Read], label(ClassificationLevel::Internal), None)
+            .await
+            .unwrap();
+        let (_, secret) = parse_token(key.token()).unwrap();
+
+        let stored = store.get(key.key_id).await.unwrap().unwrap();
+        let persisted = serde_json::to_string(&stored).unwrap();
+        assert!(!persisted.contains(secret));
+        assert!(!persisted.contains(key.token()));
+        assert_eq!(stored.secret_hash, secret_hash(key.key_id, secret));
+        assert!(!format!("{:?}", key).contains(secret));
+    }
+
+    #[tokio::test]
+    async fn test_keys_bounded_by_issuer_and_audited() {
+        let (manager, store, _) = manager();
+        let reader = crate::state::UserContext::new(
+            "acme-reader".to_string(),
+            ClassificationLevel::Internal,
+            vec!["billing".to_string()],
+            vec![Permission::Read.as_str().to_string()],
+        )
+        .with_tenant("acme");
+
+        // No permission, clearance or tenant beyond the issuer's own
+        let over_permission = manager.issue_api_key(&reader, "acme", vec![Permission::Write], label(ClassificationLevel::Internal), None).await;
+        assert!(matches!(over_permission, Err(ApiKeyError::Unauthorized(_))));
+        let over_clearance = manager.issue_api_key(&reader, "acme", vec![Permission::Read], label(ClassificationLevel::Secret), None).await;
+        assert!(matches!(over_clearance, Err(ApiKeyError::Unauthorized(_))));
+        let other_tenant = manager.issue_api_key(&reader, "globex", vec![Permission::Read], label(ClassificationLevel::Internal), None).await;
+        assert!(matches!(other_tenant, Err(ApiKeyError::Unauthorized(_))));
+        assert!(store.events().await.is_empty());
+
+        let key = manager
+            .issue_api_key(&reader, "acme", vec![Permission::Read], label(ClassificationLevel::Internal), None)
+            .await
+            .unwrap();
+        let outsider = admin().with_tenant("globex");
+        assert!(matches!(manager.revoke_api_key(key.key_id, &outsider).await, Err(ApiKeyError::Unauthorized(_))));
+        assert!(manager.revoke_api_key(key.key_id, &reader).await.unwrap());
+
+        let events = store.events().await;
+        let kinds: Vec<&str> = events.iter().map(|event| event.event_type.as_str()).collect();
+        assert_eq!(kinds, ["api_key.issued", "api_key.revoked"]);
+        assert!(events.iter().all(|event| event.user_id == "acme-reader"));
+        assert_eq!(events[0].metadata["tenant_id"], "acme");
+        let (_, secret) = parse_token(key.token()).unwrap();
+        assert!(!serde_json::to_string(&events).unwrap().contains(secret));
+    }
+}
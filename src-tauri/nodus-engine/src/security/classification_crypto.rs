@@ -9,9 +9,10 @@ use std::collections::HashMap;
 use uuid::Uuid;
 use ring::{aead, pbkdf2, rand};
 use ring::aead::BoundKey;
-use zeroize::{Zeroize, ZeroizeOnDrop};
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
 
-use super::{ClassificationLevel, SecurityLabel};
+use super::{classification_lattice, ClassificationLevel, SecurityError, SecurityLabel};
+use super::key_provider::{system_key_provider, KeyProvider, KeyProviderError, MASTER_KEY_ID};
 use crate::observability::{ObservabilityContext, AutomaticInstrumentation};
 use crate::license::LicenseManager;
 use crate::state::AppState;
@@ -23,8 +24,8 @@ pub struct ClassificationCrypto {
     // Crypto domain management
     crypto_domains: Arc<RwLock<HashMap<ClassificationLevel, CryptoDomain>>>,
     
-    // Master key for key derivation
-    master_key: Arc<MasterKey>,
+    // Holds the master key; domain and field keys are kept wrapped under it
+    key_provider: Arc<dyn KeyProvider>,
     
     // Key derivation cache for performance
     derived_key_cache: Arc<RwLock<HashMap<String, DerivedKeyEntry>>>,
@@ -40,6 +41,61 @@ pub struct ClassificationCrypto {
     
     // Key rotation management
     key_rotation: KeyRotationManager,
+    
+    // Versioned field-encryption keys
+    field_keyring: Arc<RwLock<FieldKeyring>>,
+    
+    // Where field key versions are persisted, so sealed fields outlive the process
+    field_key_store: Arc<dyn FieldKeyStore>,
+}
+
+/// Versioned field-encryption keys; retired versions stay available for decryption
+#[derive(Debug, Default)]
+struct FieldKeyring {
+    versions: HashMap<String, StoredFieldKey>,
+    active: HashMap<ClassificationLevel, String>,
+}
+
+/// One field key version as persisted: a random data key, only ever wrapped by the key provider
+#[derive(Debug, Clone)]
+pub struct StoredFieldKey {
+    pub key_id: String,
+    pub classification: ClassificationLevel,
+    pub version: u32,
+    pub wrapped_key: Vec<u8>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// Persistence of wrapped field key versions; the newest version of a classification is its active one
+#[async_trait::async_trait]
+pub trait FieldKeyStore: std::fmt::Debug + Send + Sync {
+    /// Every stored version, retired ones included
+    async fn field_keys(&self) -> Result<Vec<StoredFieldKey>, CryptoError>;
+
+    /// Store a new version; fails if its key id or version is already taken
+    async fn insert_field_key(&self, key: &StoredFieldKey) -> Result<(), CryptoError>;
+}
+
+/// Field keys held in process memory; for development and tests
+#[derive(Debug, Default)]
+pub struct InMemoryFieldKeyStore {
+    keys: RwLock<Vec<StoredFieldKey>>,
+}
+
+#[async_trait::async_trait]
+impl FieldKeyStore for InMemoryFieldKeyStore {
+    async fn field_keys(&self) -> Result<Vec<StoredFieldKey>, CryptoError> {
+        Ok(self.keys.read().await.clone())
+    }
+
+    async fn insert_field_key(&self, key: &StoredFieldKey) -> Result<(), CryptoError> {
+        let mut keys = self.keys.write().await;
+        if keys.iter().any(|stored| stored.key_id == key.key_id) {
+            return Err(CryptoError::KeyStore(format!("Field key {} already stored", key.key_id)));
+        }
+        keys.push(key.clone());
+        Ok(())
+    }
 }
 
 /// Crypto domain for a specific classification level
@@ -52,14 +108,7 @@ struct CryptoDomain {
     pub aad_binding_required: bool,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub last_rotation: chrono::DateTime<chrono::Utc>,
-}
-
-/// Master key for key derivation (stored securely)
-#[derive(Debug)]
-struct MasterKey {
-    key_material: [u8; 32], // 256-bit master key
-    salt: [u8; 16],         // 128-bit salt
-    created_at: chrono::DateTime<chrono::Utc>,
+    pub wrapped_key: Vec<u8>, // Domain key, wrapped under the master key
 }
 
 /// Derived key cache entry
@@ -110,6 +159,30 @@ pub struct EncryptedData {
     pub metadata: EncryptionMetadata,
 }
 
+/// Self-describing encrypted field: carries everything needed to pick the key and
+/// check clearance, and the header is authenticated along with the ciphertext
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncryptedBlob {
+    pub key_id: String,
+    pub classification: ClassificationLevel,
+    /// Sorted, so the authenticated header is deterministic
+    pub compartments: Vec<String>,
+    pub nonce: Vec<u8>,
+    pub ciphertext: Vec<u8>,
+}
+
+impl EncryptedBlob {
+    /// Security label the field was encrypted under
+    pub fn label(&self) -> SecurityLabel {
+        SecurityLabel::new(self.classification.clone(), self.compartments.clone())
+    }
+
+    /// Header bytes bound into the GCM tag, so relabeling a blob fails authentication
+    fn header_aad(&self) -> Vec<u8> {
+        format!("{}|{}|{}", self.key_id, self.classification, self.compartments.join(",")).into_bytes()
+    }
+}
+
 /// Additional authentication data for binding
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AdditionalAuthData {
@@ -182,33 +255,59 @@ pub enum RotationType {
 }
 
 impl ClassificationCrypto {
-    /// Create new classification crypto system
+    /// Create new classification crypto system with the system key provider
     pub async fn new(license_manager: Arc<LicenseManager>) -> Result<Self, CryptoError> {
-        // Generate or load master key
-        let master_key = Arc::new(MasterKey::generate()?);
-        
+        Self::with_key_provider(license_manager, system_key_provider().await?).await
+    }
+
+    /// Create a classification crypto system whose keys are wrapped by `key_provider`,
+    /// with field keys that last only as long as the process
+    pub async fn with_key_provider(
+        license_manager: Arc<LicenseManager>,
+        key_provider: Arc<dyn KeyProvider>,
+    ) -> Result<Self, CryptoError> {
+        Self::with_field_key_store(license_manager, key_provider, Arc::new(InMemoryFieldKeyStore::default())).await
+    }
+
+    /// Create a classification crypto system resuming the field keys in `field_key_store`;
+    /// classifications without one get a first version, stored before it is used
+    pub async fn with_field_key_store(
+        license_manager: Arc<LicenseManager>,
+        key_provider: Arc<dyn KeyProvider>,
+        field_key_store: Arc<dyn FieldKeyStore>,
+    ) -> Result<Self, CryptoError> {
         // Initialize crypto domains for each classification level
         let mut crypto_domains = HashMap::new();
         
-        for classification in &[
-            ClassificationLevel::Unclassified,
-            ClassificationLevel::Internal,
-            ClassificationLevel::Confidential,
-            ClassificationLevel::Secret,
-            ClassificationLevel::NatoSecret,
-        ] {
-            let domain = CryptoDomain::new(classification.clone())?;
-            crypto_domains.insert(classification.clone(), domain);
+        for classification in classification_lattice::active().levels() {
+            let domain = CryptoDomain::new(classification.clone(), new_wrapped_key(key_provider.as_ref()).await?)?;
+            crypto_domains.insert(classification, domain);
+        }
+
+        let mut field_keyring = FieldKeyring::default();
+        let mut stored = field_key_store.field_keys().await?;
+        stored.sort_by_key(|key| key.version);
+        for key in stored {
+            field_keyring.install(key);
+        }
+        for classification in crypto_domains.keys() {
+            if !field_keyring.active.contains_key(classification) {
+                let key = field_keyring.next_version(classification.clone(), key_provider.as_ref()).await?;
+                field_key_store.insert_field_key(&key).await?;
+                field_keyring.install(key);
+            }
         }
 
         Ok(Self {
             crypto_domains: Arc::new(RwLock::new(crypto_domains)),
-            master_key,
+            key_provider,
             derived_key_cache: Arc::new(RwLock::new(HashMap::new())),
             automatic_instrumentation: AutomaticInstrumentation::new(license_manager.clone()),
             crypto_stats: Arc::new(RwLock::new(CryptoStats::default())),
             license_manager,
             key_rotation: KeyRotationManager::new(),
+            field_keyring: Arc::new(RwLock::new(field_keyring)),
+            field_key_store,
         })
     }
 
@@ -337,7 +436,7 @@ impl ClassificationCrypto {
         }
 
         // Create new crypto domain
-        let new_domain = CryptoDomain::new(classification.clone())?;
+        let new_domain = CryptoDomain::new(classification.clone(), new_wrapped_key(self.key_provider.as_ref()).await?)?;
         
         // Update domains
         {
@@ -357,6 +456,146 @@ impl ClassificationCrypto {
         Ok(())
     }
 
+    /// Encrypt a field at rest under the key for `label`'s classification (AES-256-GCM)
+    pub async fn encrypt_field(
+        &self,
+        plaintext: &[u8],
+        label: &SecurityLabel,
+    ) -> Result<EncryptedBlob, SecurityError> {
+        let (key_id, wrapped_key) = {
+            let keyring = self.field_keyring.read().await;
+            let key_id = keyring.active.get(&label.level)
+                .ok_or_else(|| SecurityError::InvalidClassification(label.level.to_string()))?
+                .clone();
+            let wrapped_key = keyring.versions[&key_id].wrapped_key.clone();
+            (key_id, wrapped_key)
+        };
+        let key = self.field_key(&wrapped_key).await?;
+
+        let mut nonce = [0u8; aead::NONCE_LEN];
+        rand::SecureRandom::fill(&rand::SystemRandom::new(), &mut nonce)
+            .map_err(|_| SecurityError::CryptoError(CryptoError::RandomGenerationFailed.to_string()))?;
+
+        let mut compartments: Vec<String> = label.compartments.iter().cloned().collect();
+        compartments.sort();
+        let mut blob = EncryptedBlob {
+            key_id,
+            classification: label.level.clone(),
+            compartments,
+            nonce: nonce.to_vec(),
+            ciphertext: plaintext.to_vec(),
+        };
+
+        let aad = blob.header_aad();
+        key.seal_in_place_append_tag(
+            aead::Nonce::assume_unique_for_key(nonce),
+            aead::Aad::from(&aad),
+            &mut blob.ciphertext,
+        ).map_err(|_| SecurityError::CryptoError(CryptoError::EncryptionFailed.to_string()))?;
+
+        self.update_crypto_stats(true, std::time::Duration::ZERO, plaintext.len()).await;
+        Ok(blob)
+    }
+
+    /// Decrypt a field for `subject`, who must dominate the blob's label
+    ///
+    /// Clearance is checked before any key material is touched; a modified ciphertext
+    /// or header fails GCM authentication.
+    pub async fn decrypt_field(
+        &self,
+        blob: &EncryptedBlob,
+        subject: &SecurityLabel,
+    ) -> Result<Vec<u8>, SecurityError> {
+        let object = blob.label();
+        if subject.level.rank() < object.level.rank() {
+            return Err(SecurityError::InsufficientClearance);
+        }
+        if !subject.dominates(&object) {
+            return Err(SecurityError::CompartmentDenied);
+        }
+
+        self.open_field(blob).await
+    }
+
+    /// Introduce a new active field key version for `classification`; returns its key id
+    ///
+    /// Earlier versions are kept so existing blobs still decrypt; `reencrypt_batch`
+    /// moves them onto the new key over time. The version is stored before anything is
+    /// sealed under it.
+    pub async fn rotate_key(&self, classification: ClassificationLevel) -> Result<String, SecurityError> {
+        let mut keyring = self.field_keyring.write().await;
+        let key = keyring.next_version(classification.clone(), self.key_provider.as_ref())
+            .await
+            .map_err(|e| SecurityError::CryptoError(e.to_string()))?;
+        self.field_key_store.insert_field_key(&key).await
+            .map_err(|e| SecurityError::CryptoError(e.to_string()))?;
+        let key_id = keyring.install(key);
+
+        crate::security_log!(info, classification = %classification, key_id = %key_id, "Rotated field key");
+        Ok(key_id)
+    }
+
+    /// Whether a blob was sealed under a retired key version
+    pub async fn needs_reencryption(&self, blob: &EncryptedBlob) -> bool {
+        let keyring = self.field_keyring.read().await;
+        keyring.active.get(&blob.classification) != Some(&blob.key_id)
+    }
+
+    /// Re-seal blobs still on retired key versions under the active key, in place
+    ///
+    /// Meant for a background job that walks stored fields in batches and persists the
+    /// upgraded blobs; blobs already on the active key are left untouched. Labels are
+    /// preserved, and no clearance check applies since plaintext never leaves this call.
+    /// Returns how many blobs were upgraded.
+    pub async fn reencrypt_batch(&self, blobs: &mut [EncryptedBlob]) -> Result<usize, SecurityError> {
+        let mut upgraded = 0;
+        for blob in blobs.iter_mut() {
+            if !self.needs_reencryption(blob).await {
+                continue;
+            }
+
+            let mut plaintext = self.open_field(blob).await?;
+            let upgraded_blob = self.encrypt_field(&plaintext, &blob.label()).await;
+            plaintext.zeroize();
+            *blob = upgraded_blob?;
+            upgraded += 1;
+        }
+        Ok(upgraded)
+    }
+
+    /// Authenticate and decrypt a blob with the key version it names
+    async fn open_field(&self, blob: &EncryptedBlob) -> Result<Vec<u8>, SecurityError> {
+        let wrapped_key = {
+            let keyring = self.field_keyring.read().await;
+            let version = keyring.versions.get(&blob.key_id)
+                .filter(|version| version.classification == blob.classification)
+                .ok_or_else(|| SecurityError::CryptoError(format!("Unknown key id: {}", blob.key_id)))?;
+            version.wrapped_key.clone()
+        };
+        let key = self.field_key(&wrapped_key).await?;
+        let nonce = aead::Nonce::try_assume_unique_for_key(&blob.nonce)
+            .map_err(|_| SecurityError::CryptoError(CryptoError::NonceError.to_string()))?;
+
+        let aad = blob.header_aad();
+        let mut in_out = blob.ciphertext.clone();
+        let plaintext = key.open_in_place(nonce, aead::Aad::from(&aad), &mut in_out)
+            .map_err(|_| SecurityError::CryptoError(CryptoError::DecryptionFailed.to_string()))?;
+
+        let plaintext = plaintext.to_vec();
+        self.update_crypto_stats(false, std::time::Duration::ZERO, plaintext.len()).await;
+        Ok(plaintext)
+    }
+
+    /// Field key for a key version, unwrapped by the key provider for this use only
+    async fn field_key(&self, wrapped_key: &[u8]) -> Result<aead::LessSafeKey, SecurityError> {
+        let material = self.key_provider.unwrap(MASTER_KEY_ID, wrapped_key).await
+            .map_err(|e| SecurityError::CryptoError(CryptoError::from(e).to_string()))?;
+        let key = aead::UnboundKey::new(&aead::AES_256_GCM, &material)
+            .map_err(|_| SecurityError::CryptoError(CryptoError::KeyCreationFailed.to_string()))?;
+
+        Ok(aead::LessSafeKey::new(key))
+    }
+
     /// Get crypto statistics for monitoring
     pub async fn get_crypto_stats(&self) -> CryptoStats {
         self.crypto_stats.read().await.clone()
@@ -560,13 +799,14 @@ impl ClassificationCrypto {
             user_id
         );
 
-        // Derive key using PBKDF2
+        // Derive key using PBKDF2 from the domain key, unwrapped for this call only
+        let domain_key = self.key_provider.unwrap(MASTER_KEY_ID, &domain.wrapped_key).await?;
         let mut derived_key = [0u8; 32]; // 256-bit key
         pbkdf2::derive(
             pbkdf2::PBKDF2_HMAC_SHA256,
             std::num::NonZeroU32::new(domain.key_derivation_config.iterations).unwrap(),
-            &self.master_key.salt,
             derivation_input.as_bytes(),
+            &domain_key,
             &mut derived_key,
         );
 
@@ -630,47 +870,79 @@ impl ClassificationCrypto {
     }
 }
 
-impl MasterKey {
-    /// Generate new master key with secure random material
-    fn generate() -> Result<Self, CryptoError> {
-        let rng = rand::SystemRandom::new();
-        
-        let mut key_material = [0u8; 32];
-        rand::SecureRandom::fill(&rng, &mut key_material)
-            .map_err(|_| CryptoError::RandomGenerationFailed)?;
+/// Fresh random data key, wrapped under the master key
+async fn new_wrapped_key(key_provider: &dyn KeyProvider) -> Result<Vec<u8>, CryptoError> {
+    let mut material = Zeroizing::new([0u8; 32]);
+    rand::SecureRandom::fill(&rand::SystemRandom::new(), material.as_mut())
+        .map_err(|_| CryptoError::RandomGenerationFailed)?;
+    Ok(key_provider.wrap(MASTER_KEY_ID, material.as_ref()).await?)
+}
 
-        let mut salt = [0u8; 16];
-        rand::SecureRandom::fill(&rng, &mut salt)
-            .map_err(|_| CryptoError::RandomGenerationFailed)?;
+/// Key id for a field key version at a classification
+fn field_key_id(classification: &ClassificationLevel, version: u32) -> String {
+    format!("field:{}:v{}", classification, version)
+}
 
-        Ok(Self {
-            key_material,
-            salt,
+impl FieldKeyring {
+    /// The next key version for `classification`, not yet installed
+    async fn next_version(
+        &self,
+        classification: ClassificationLevel,
+        key_provider: &dyn KeyProvider,
+    ) -> Result<StoredFieldKey, CryptoError> {
+        let version = self.versions.values()
+            .filter(|existing| existing.classification == classification)
+            .map(|existing| existing.version)
+            .max()
+            .unwrap_or(0) + 1;
+
+        let wrapped_key = new_wrapped_key(key_provider).await?;
+
+        Ok(StoredFieldKey {
+            key_id: field_key_id(&classification, version),
+            classification,
+            version,
+            wrapped_key,
             created_at: chrono::Utc::now(),
         })
     }
+
+    /// Add `key` and make it active for its classification, unless a newer version is; returns its id
+    fn install(&mut self, key: StoredFieldKey) -> String {
+        let key_id = key.key_id.clone();
+        let newer_active = self.active.get(&key.classification)
+            .and_then(|active| self.versions.get(active))
+            .is_some_and(|active| active.version > key.version);
+        if !newer_active {
+            self.active.insert(key.classification.clone(), key_id.clone());
+        }
+        self.versions.insert(key_id.clone(), key);
+        key_id
+    }
 }
 
 impl CryptoDomain {
-    /// Create new crypto domain for classification level
-    fn new(classification: ClassificationLevel) -> Result<Self, CryptoError> {
-        let encryption_algorithm = match classification {
+    /// Create new crypto domain for classification level, keyed by `wrapped_key`
+    fn new(classification: ClassificationLevel, wrapped_key: Vec<u8>) -> Result<Self, CryptoError> {
+        // Custom lattice levels take the parameters of the highest built-in level they dominate
+        let baseline = classification.builtin_baseline();
+        let encryption_algorithm = match baseline {
             ClassificationLevel::Unclassified | ClassificationLevel::Internal => 
                 EncryptionAlgorithm::AES256GCM,
             ClassificationLevel::Confidential | ClassificationLevel::Secret => 
                 EncryptionAlgorithm::AES256GCM,
-            ClassificationLevel::NatoSecret => 
+            ClassificationLevel::NatoSecret | ClassificationLevel::Custom(_) => 
                 EncryptionAlgorithm::ChaCha20Poly1305, // Stronger for NATO SECRET
         };
 
         let key_derivation_config = KeyDerivationConfig {
             algorithm: KeyDerivationAlgorithm::PBKDF2SHA256,
-            iterations: match classification {
+            iterations: match baseline {
                 ClassificationLevel::Unclassified => 10_000,
                 ClassificationLevel::Internal => 50_000,
                 ClassificationLevel::Confidential => 100_000,
                 ClassificationLevel::Secret => 200_000,
-                ClassificationLevel::NatoSecret => 500_000, // Maximum security
+                ClassificationLevel::NatoSecret | ClassificationLevel::Custom(_) => 500_000, // Maximum security
             },
             salt_length: 16,
             key_length: 32,
@@ -681,13 +953,14 @@ impl CryptoDomain {
             domain_id: Uuid::new_v4(),
             encryption_algorithm,
             key_derivation_config,
-            aad_binding_required: matches!(classification, 
+            aad_binding_required: matches!(baseline, 
                 ClassificationLevel::Confidential | 
                 ClassificationLevel::Secret | 
                 ClassificationLevel::NatoSecret
             ),
             created_at: chrono::Utc::now(),
             last_rotation: chrono::Utc::now(),
+            wrapped_key,
         })
     }
 }
@@ -769,6 +1042,15 @@ pub enum CryptoError {
     
     #[error("Feature not available: {0}")]
     FeatureNotAvailable(String),
+    
+    #[error("Key provider error: {0}")]
+    KeyProvider(#[from] KeyProviderError),
+    
+    #[error("Field key store error: {0}")]
+    KeyStore(String),
+    
+    #[error(transparent)]
+    Instrumentation(#[from] crate::observability::InstrumentationError),
 }
 
 #[cfg(test)]
@@ -807,18 +1089,128 @@ mod tests {
 
     #[test]
     fn test_crypto_domain_creation() {
-        let domain = CryptoDomain::new(ClassificationLevel::Secret).unwrap();
+        let domain = CryptoDomain::new(ClassificationLevel::Secret, Vec::new()).unwrap();
         
         assert_eq!(domain.classification, ClassificationLevel::Secret);
         assert!(domain.aad_binding_required);
         assert_eq!(domain.key_derivation_config.iterations, 200_000);
     }
 
-    #[test]
-    fn test_master_key_generation() {
-        let master_key = MasterKey::generate().unwrap();
+    #[tokio::test]
+    async fn test_field_keys_unwrapped_through_key_provider() {
+        use crate::security::key_provider::tests::RecordingKms;
+        use crate::security::key_provider::KmsKeyProvider;
+
+        let kms = Arc::new(RecordingKms::with_key("hsm/master", &[3u8; 32]));
+        let provider = Arc::new(KmsKeyProvider::new(kms.clone()).with_key_label(MASTER_KEY_ID, "hsm/master"));
+        let license_manager = Arc::new(LicenseManager::new().await.unwrap());
+        let crypto = ClassificationCrypto::with_key_provider(license_manager, provider).await.unwrap();
+        assert!(kms.called("encrypt:hsm/master"));
+        assert!(!kms.called("decrypt:hsm/master"));
+        
+        let blob = crypto.encrypt_field(b"grid reference", &secret_alpha()).await.unwrap();
+        assert!(kms.called("decrypt:hsm/master"));
+        kms.calls.lock().unwrap().clear();
+        assert_eq!(crypto.decrypt_field(&blob, &secret_alpha()).await.unwrap(), b"grid reference");
+        assert_eq!(*kms.calls.lock().unwrap(), vec!["decrypt:hsm/master"]);
+    }
+
+    fn secret_alpha() -> SecurityLabel {
+        SecurityLabel::new(ClassificationLevel::Secret, vec!["ALPHA".to_string()])
+    }
+
+    #[tokio::test]
+    async fn test_field_encryption_round_trip_and_tamper_detection() {
+        let license_manager = Arc::new(LicenseManager::new().await.unwrap());
+        let crypto = ClassificationCrypto::new(license_manager).await.unwrap();
+        
+        let blob = crypto.encrypt_field(b"launch codes", &secret_alpha()).await.unwrap();
+        assert_eq!(blob.classification, ClassificationLevel::Secret);
+        assert_ne!(blob.ciphertext, b"launch codes".to_vec());
+        assert_eq!(crypto.decrypt_field(&blob, &secret_alpha()).await.unwrap(), b"launch codes");
+        
+        let mut tampered = blob.clone();
+        tampered.ciphertext[0] ^= 0x01;
+        assert!(matches!(
+            crypto.decrypt_field(&tampered, &secret_alpha()).await,
+            Err(SecurityError::CryptoError(_))
+        ));
+        
+        // Relabeling the header down to a lower level also breaks authentication
+        let mut relabeled = blob.clone();
+        relabeled.compartments.clear();
+        assert!(crypto.decrypt_field(&relabeled, &secret_alpha()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_field_decryption_requires_dominance() {
+        let license_manager = Arc::new(LicenseManager::new().await.unwrap());
+        let crypto = ClassificationCrypto::new(license_manager).await.unwrap();
+        let blob = crypto.encrypt_field(b"source identity", &secret_alpha()).await.unwrap();
+        
+        let confidential = SecurityLabel::new(ClassificationLevel::Confidential, vec!["ALPHA".to_string()]);
+        assert!(matches!(
+            crypto.decrypt_field(&blob, &confidential).await,
+            Err(SecurityError::InsufficientClearance)
+        ));
         
-        assert_eq!(master_key.key_material.len(), 32);
-        assert_eq!(master_key.salt.len(), 16);
+        let wrong_compartment = SecurityLabel::new(ClassificationLevel::NatoSecret, vec!["BRAVO".to_string()]);
+        assert!(matches!(
+            crypto.decrypt_field(&blob, &wrong_compartment).await,
+            Err(SecurityError::CompartmentDenied)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_blob_from_v1_decrypts_after_rotation() {
+        let license_manager = Arc::new(LicenseManager::new().await.unwrap());
+        let crypto = ClassificationCrypto::new(license_manager).await.unwrap();
+        
+        let old_blob = crypto.encrypt_field(b"v1 payload", &secret_alpha()).await.unwrap();
+        assert_eq!(old_blob.key_id, "field:SECRET:v1");
+        
+        let new_key = crypto.rotate_key(ClassificationLevel::Secret).await.unwrap();
+        assert_eq!(new_key, "field:SECRET:v2");
+        
+        // New writes use v2; v1 data still decrypts
+        let new_blob = crypto.encrypt_field(b"v2 payload", &secret_alpha()).await.unwrap();
+        assert_eq!(new_blob.key_id, new_key);
+        assert_eq!(crypto.decrypt_field(&old_blob, &secret_alpha()).await.unwrap(), b"v1 payload");
+        
+        // Lazy upgrade moves only the v1 blob
+        let mut stored = vec![old_blob, new_blob.clone()];
+        assert_eq!(crypto.reencrypt_batch(&mut stored).await.unwrap(), 1);
+        assert_eq!(stored[0].key_id, new_key);
+        assert_eq!(stored[1], new_blob);
+        assert_eq!(crypto.decrypt_field(&stored[0], &secret_alpha()).await.unwrap(), b"v1 payload");
+        assert_eq!(crypto.reencrypt_batch(&mut stored).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_field_keys_survive_a_restart() {
+        let provider: Arc<dyn KeyProvider> = Arc::new(
+            crate::security::key_provider::InMemoryKeyProvider::new().with_generated_key(MASTER_KEY_ID).unwrap(),
+        );
+        let store = Arc::new(InMemoryFieldKeyStore::default());
+        let license_manager = Arc::new(LicenseManager::new().await.unwrap());
+
+        let crypto = ClassificationCrypto::with_field_key_store(license_manager.clone(), provider.clone(), store.clone())
+            .await.unwrap();
+        let v1_blob = crypto.encrypt_field(b"v1 payload", &secret_alpha()).await.unwrap();
+        crypto.rotate_key(ClassificationLevel::Secret).await.unwrap();
+        let v2_blob = crypto.encrypt_field(b"v2 payload", &secret_alpha()).await.unwrap();
+        drop(crypto);
+
+        // A new process with the same store and provider opens both and keeps v2 active
+        let restarted = ClassificationCrypto::with_field_key_store(license_manager, provider, store.clone())
+            .await.unwrap();
+        assert_eq!(restarted.decrypt_field(&v1_blob, &secret_alpha()).await.unwrap(), b"v1 payload");
+        assert_eq!(restarted.decrypt_field(&v2_blob, &secret_alpha()).await.unwrap(), b"v2 payload");
+        assert!(!restarted.needs_reencryption(&v2_blob).await);
+        assert!(restarted.needs_reencryption(&v1_blob).await);
+
+        // No versions were minted on restart
+        let stored = store.field_keys().await.unwrap();
+        assert_eq!(stored.iter().filter(|key| key.classification == ClassificationLevel::Secret).count(), 2);
     }
 }
@@ -0,0 +1,432 @@
+// src-tauri/src/security/classification_lattice.rs
+// Classification Lattice - Configurable classification levels and their partial order
+// The five built-in levels form the default chain; coalition deployments can load extra
+// national caveats and intermediate levels from config
+
+use arc_swap::ArcSwap;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use super::{ClassificationLevel, SecurityError};
+
+/// Lattice in effect for rank/parse/display and MAC dominance checks
+static ACTIVE_LATTICE: Lazy<ArcSwap<ClassificationLattice>> =
+    Lazy::new(|| ArcSwap::from_pointee(ClassificationLattice::default()));
+
+/// Built-in levels, lowest first; every lattice must contain them in this order
+const BUILTIN_LEVELS: [ClassificationLevel; 5] = [
+    ClassificationLevel::Unclassified,
+    ClassificationLevel::Internal,
+    ClassificationLevel::Confidential,
+    ClassificationLevel::Secret,
+    ClassificationLevel::NatoSecret,
+];
+
+/// One level of a lattice as written in config
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LevelDefinition {
+    /// Canonical key, e.g. "eu_restricted"
+    pub name: String,
+    /// Display form, e.g. "EU RESTRICTED"; defaults to the upper-cased name
+    #[serde(default)]
+    pub display: Option<String>,
+    /// Levels this one directly dominates
+    #[serde(default)]
+    pub dominates: Vec<String>,
+    /// Extra spellings accepted when parsing
+    #[serde(default)]
+    pub aliases: Vec<String>,
+}
+
+/// Lattice configuration file contents
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LatticeConfig {
+    pub levels: Vec<LevelDefinition>,
+}
+
+/// Classification levels with a partial order given by "dominates" edges
+#[derive(Debug, Clone)]
+pub struct ClassificationLattice {
+    levels: Vec<LevelDefinition>,
+    /// Name, display form and aliases (lower-cased) to level index
+    lookup: HashMap<String, usize>,
+    /// `dominance[a][b]`: level `a` dominates level `b` (reflexive, transitive)
+    dominance: Vec<Vec<bool>>,
+    /// Length of the longest chain below each level
+    ranks: Vec<u8>,
+    /// Greatest level
+    top: usize,
+}
+
+impl Default for ClassificationLattice {
+    /// The built-in five-level chain, built directly: a chain needs none of `from_config`'s checks
+    fn default() -> Self {
+        let levels: Vec<LevelDefinition> = BUILTIN_LEVELS.iter().enumerate()
+            .map(|(index, level)| LevelDefinition {
+                name: level.key().to_string(),
+                display: None,
+                dominates: index.checked_sub(1)
+                    .map(|below| vec![BUILTIN_LEVELS[below].key().to_string()])
+                    .unwrap_or_default(),
+                // As `from_config` adds for levels serialized before lattices existed
+                aliases: if *level == ClassificationLevel::NatoSecret { vec!["natosecret".to_string()] } else { Vec::new() },
+            })
+            .collect();
+
+        let mut lookup = HashMap::new();
+        for (index, level) in levels.iter().enumerate() {
+            for spelling in std::iter::once(&level.name).chain(&level.aliases) {
+                lookup.insert(spelling.clone(), index);
+            }
+        }
+        let count = levels.len();
+        Self {
+            levels,
+            lookup,
+            dominance: (0..count).map(|a| (0..count).map(|b| a >= b).collect()).collect(),
+            ranks: (0..count).map(|index| u8::try_from(index).unwrap_or(u8::MAX)).collect(),
+            top: count.saturating_sub(1),
+        }
+    }
+}
+
+impl ClassificationLattice {
+    /// Build and validate a lattice from its definition
+    pub fn from_config(config: LatticeConfig) -> Result<Self, SecurityError> {
+        let invalid = |reason: String| SecurityError::InvalidLattice(reason);
+        let mut levels = config.levels;
+
+        // Levels serialized before lattices existed used the serde form "natosecret"
+        if let Some(nato) = levels.iter_mut().find(|level| level.name == "nato_secret") {
+            if !nato.aliases.iter().any(|alias| alias == "natosecret") {
+                nato.aliases.push("natosecret".to_string());
+            }
+        }
+
+        let mut lookup = HashMap::new();
+        for (index, level) in levels.iter().enumerate() {
+            if level.name.is_empty() || level.name != level.name.to_lowercase() {
+                return Err(invalid(format!("level name '{}' must be non-empty lowercase", level.name)));
+            }
+            if lookup.insert(level.name.clone(), index).is_some() {
+                return Err(invalid(format!("duplicate level '{}'", level.name)));
+            }
+        }
+        for (index, level) in levels.iter().enumerate() {
+            for spelling in level.display.iter().chain(&level.aliases) {
+                match lookup.insert(spelling.to_lowercase(), index) {
+                    Some(other) if other != index => {
+                        return Err(invalid(format!("'{}' names more than one level", spelling)));
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        // Transitive closure of the direct edges
+        let count = levels.len();
+        let mut dominance = vec![vec![false; count]; count];
+        for (index, level) in levels.iter().enumerate() {
+            dominance[index][index] = true;
+            for below in &level.dominates {
+                let below = *lookup.get(below.as_str())
+                    .ok_or_else(|| invalid(format!("'{}' dominates unknown level '{}'", level.name, below)))?;
+                dominance[index][below] = true;
+            }
+        }
+        for via in 0..count {
+            let below_via = dominance[via].clone();
+            for row in dominance.iter_mut().filter(|row| row[via]) {
+                for (to, &below) in below_via.iter().enumerate() {
+                    row[to] |= below;
+                }
+            }
+        }
+        for a in 0..count {
+            for b in (a + 1)..count {
+                if dominance[a][b] && dominance[b][a] {
+                    return Err(invalid(format!(
+                        "'{}' and '{}' dominate each other",
+                        levels[a].name, levels[b].name
+                    )));
+                }
+            }
+        }
+
+        // Aggregation needs a unique least upper bound for every pair
+        let mut top = 0;
+        for a in 0..count {
+            for b in (a + 1)..count {
+                if least_upper_bound(&dominance, a, b).is_none() {
+                    return Err(invalid(format!(
+                        "'{}' and '{}' have no least upper bound",
+                        levels[a].name, levels[b].name
+                    )));
+                }
+            }
+            if dominance[a][top] {
+                top = a;
+            }
+        }
+
+        // Code relies on the built-in levels (e.g. audit at Secret and above), so they must
+        // all be present and keep their relative order
+        let mut previous: Option<usize> = None;
+        for builtin in &BUILTIN_LEVELS {
+            let index = *lookup.get(builtin.key())
+                .ok_or_else(|| invalid(format!("built-in level '{}' is missing", builtin.key())))?;
+            if let Some(previous) = previous {
+                if !dominance[index][previous] {
+                    return Err(invalid(format!(
+                        "'{}' must dominate '{}'",
+                        levels[index].name, levels[previous].name
+                    )));
+                }
+            }
+            previous = Some(index);
+        }
+
+        // Acyclic, so ranks settle after at most `count` passes
+        let mut ranks = vec![0u8; count];
+        for _ in 0..count {
+            for (index, level) in levels.iter().enumerate() {
+                for below in &level.dominates {
+                    let below = lookup[below.as_str()];
+                    ranks[index] = ranks[index].max(ranks[below].saturating_add(1));
+                }
+            }
+        }
+
+        Ok(Self { levels, lookup, dominance, ranks, top })
+    }
+
+    /// Parse a lattice from TOML (`[[levels]]` tables)
+    pub fn from_toml(contents: &str) -> Result<Self, SecurityError> {
+        let config: LatticeConfig = toml::from_str(contents)
+            .map_err(|e| SecurityError::InvalidLattice(e.to_string()))?;
+        Self::from_config(config)
+    }
+
+    /// Load a lattice from a TOML file
+    pub async fn load(path: &str) -> Result<Self, SecurityError> {
+        let contents = tokio::fs::read_to_string(path).await
+            .map_err(|e| SecurityError::InvalidLattice(format!("{}: {}", path, e)))?;
+        Self::from_toml(&contents)
+    }
+
+    /// Resolve a name, display form or alias (case-insensitive)
+    pub fn parse(&self, s: &str) -> Option<ClassificationLevel> {
+        self.lookup.get(&s.to_lowercase())
+            .map(|&index| ClassificationLevel::from_key(&self.levels[index].name))
+    }
+
+    /// Whether `a` dominates `b`; levels unknown to the lattice dominate nothing
+    pub fn dominates(&self, a: &ClassificationLevel, b: &ClassificationLevel) -> bool {
+        match (self.index_of(a), self.index_of(b)) {
+            (Some(a), Some(b)) => self.dominance[a][b],
+            _ => false,
+        }
+    }
+
+    /// Height of the level in the lattice; incomparable levels may share a rank
+    pub fn rank(&self, level: &ClassificationLevel) -> u8 {
+        self.index_of(level).map(|index| self.ranks[index]).unwrap_or(0)
+    }
+
+    pub fn display_name(&self, level: &ClassificationLevel) -> String {
+        match self.index_of(level).and_then(|index| self.levels[index].display.clone()) {
+            Some(display) => display,
+            None => level.key().to_uppercase(),
+        }
+    }
+
+    /// Every level, in config order
+    pub fn levels(&self) -> Vec<ClassificationLevel> {
+        self.levels.iter()
+            .map(|level| ClassificationLevel::from_key(&level.name))
+            .collect()
+    }
+
+    /// Least upper bound of two levels; `None` if either is unknown to the lattice
+    pub fn join(&self, a: &ClassificationLevel, b: &ClassificationLevel) -> Option<ClassificationLevel> {
+        let index = least_upper_bound(&self.dominance, self.index_of(a)?, self.index_of(b)?)?;
+        Some(ClassificationLevel::from_key(&self.levels[index].name))
+    }
+
+    /// Greatest level, which dominates every other
+    pub fn top(&self) -> ClassificationLevel {
+        ClassificationLevel::from_key(&self.levels[self.top].name)
+    }
+
+    /// Highest built-in level that `level` dominates, e.g. to pick crypto parameters
+    pub fn builtin_baseline(&self, level: &ClassificationLevel) -> ClassificationLevel {
+        BUILTIN_LEVELS.iter()
+            .rev()
+            .find(|builtin| self.dominates(level, builtin))
+            .cloned()
+            .unwrap_or(ClassificationLevel::Unclassified)
+    }
+
+    fn index_of(&self, level: &ClassificationLevel) -> Option<usize> {
+        self.lookup.get(level.key()).copied()
+    }
+}
+
+/// The common upper bound of `a` and `b` that every other common upper bound dominates
+fn least_upper_bound(dominance: &[Vec<bool>], a: usize, b: usize) -> Option<usize> {
+    let upper: Vec<usize> = (0..dominance.len())
+        .filter(|&candidate| dominance[candidate][a] && dominance[candidate][b])
+        .collect();
+    upper.iter()
+        .copied()
+        .find(|&candidate| upper.iter().all(|&other| dominance[other][candidate]))
+}
+
+/// The lattice currently in effect
+pub fn active() -> Arc<ClassificationLattice> {
+    ACTIVE_LATTICE.load_full()
+}
+
+/// Replace the active lattice (normally once at startup)
+pub fn install(lattice: ClassificationLattice) {
+    ACTIVE_LATTICE.store(Arc::new(lattice));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::{MACEngine, SecurityLabel};
+
+    const COALITION: &str = r#"
+        [[levels]]
+        name = "unclassified"
+
+        [[levels]]
+        name = "internal"
+        dominates = ["unclassified"]
+
+        [[levels]]
+        name = "eu_restricted"
+        display = "EU RESTRICTED"
+        dominates = ["unclassified"]
+
+        [[levels]]
+        name = "confidential"
+        dominates = ["internal", "eu_restricted"]
+
+        [[levels]]
+        name = "secret"
+        dominates = ["confidential"]
+
+        [[levels]]
+        name = "nato_secret"
+        dominates = ["secret"]
+    "#;
+
+    fn eu_restricted() -> ClassificationLevel {
+        ClassificationLevel::Custom("eu_restricted".to_string())
+    }
+
+    #[test]
+    fn test_default_is_the_validated_builtin_chain() {
+        let levels = BUILTIN_LEVELS.iter().enumerate()
+            .map(|(index, level)| LevelDefinition {
+                name: level.key().to_string(),
+                display: None,
+                dominates: index.checked_sub(1).map(|below| vec![BUILTIN_LEVELS[below].key().to_string()]).unwrap_or_default(),
+                aliases: Vec::new(),
+            })
+            .collect();
+        let validated = ClassificationLattice::from_config(LatticeConfig { levels }).unwrap();
+        let default = ClassificationLattice::default();
+
+        assert_eq!(default.lookup, validated.lookup);
+        assert_eq!(default.dominance, validated.dominance);
+        assert_eq!(default.ranks, validated.ranks);
+        assert_eq!(default.top, validated.top);
+    }
+
+    #[test]
+    fn test_custom_lattice_dominance() {
+        let lattice = ClassificationLattice::from_toml(COALITION).unwrap();
+
+        assert!(lattice.dominates(&ClassificationLevel::Confidential, &eu_restricted()));
+        assert!(lattice.dominates(&ClassificationLevel::NatoSecret, &eu_restricted()));
+        assert!(lattice.dominates(&eu_restricted(), &ClassificationLevel::Unclassified));
+        assert!(!lattice.dominates(&eu_restricted(), &ClassificationLevel::Confidential));
+
+        // Sibling levels are incomparable
+        assert!(!lattice.dominates(&eu_restricted(), &ClassificationLevel::Internal));
+        assert!(!lattice.dominates(&ClassificationLevel::Internal, &eu_restricted()));
+        assert_eq!(lattice.rank(&eu_restricted()), lattice.rank(&ClassificationLevel::Internal));
+
+        assert_eq!(lattice.parse("EU RESTRICTED"), Some(eu_restricted()));
+        assert_eq!(lattice.parse("eu_restricted"), Some(eu_restricted()));
+        assert_eq!(lattice.display_name(&eu_restricted()), "EU RESTRICTED");
+        assert_eq!(lattice.builtin_baseline(&eu_restricted()), ClassificationLevel::Unclassified);
+        assert_eq!(lattice.levels().len(), 6);
+    }
+
+    #[test]
+    fn test_join_of_incomparable_levels() {
+        let lattice = ClassificationLattice::from_toml(COALITION).unwrap();
+
+        assert_eq!(
+            lattice.join(&eu_restricted(), &ClassificationLevel::Internal),
+            Some(ClassificationLevel::Confidential)
+        );
+        assert_eq!(lattice.join(&eu_restricted(), &ClassificationLevel::Unclassified), Some(eu_restricted()));
+        assert_eq!(
+            lattice.join(&eu_restricted(), &ClassificationLevel::Secret),
+            Some(ClassificationLevel::Secret)
+        );
+        assert_eq!(lattice.join(&eu_restricted(), &ClassificationLevel::Custom("unknown".to_string())), None);
+        assert_eq!(lattice.top(), ClassificationLevel::NatoSecret);
+
+        // Two incomparable maximal levels leave no join for the pair
+        let two_tops = format!("{}\n[[levels]]\nname = \"us_only\"\ndominates = [\"secret\"]\n", COALITION);
+        assert!(ClassificationLattice::from_toml(&two_tops).is_err());
+    }
+
+    #[test]
+    fn test_invalid_lattices_rejected() {
+        let cyclic = COALITION.replace(
+            "dominates = [\"unclassified\"]\n\n        [[levels]]\n        name = \"confidential\"",
+            "dominates = [\"confidential\"]\n\n        [[levels]]\n        name = \"confidential\"",
+        );
+        assert_ne!(cyclic, COALITION);
+        assert!(ClassificationLattice::from_toml(&cyclic).is_err());
+
+        let dangling = COALITION.replace("dominates = [\"secret\"]", "dominates = [\"top_secret\"]");
+        assert!(ClassificationLattice::from_toml(&dangling).is_err());
+
+        let missing_builtin = r#"
+            [[levels]]
+            name = "unclassified"
+        "#;
+        assert!(ClassificationLattice::from_toml(missing_builtin).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_injected_lattice_drives_mac_checks() {
+        let lattice = Arc::new(ClassificationLattice::from_toml(COALITION).unwrap());
+
+        let level = lattice.parse("EU RESTRICTED").unwrap();
+        assert_eq!(level, eu_restricted());
+        assert_eq!(lattice.display_name(&level), "EU RESTRICTED");
+        assert_eq!(level.key(), "eu_restricted");
+
+        let mac = MACEngine::new().with_lattice(lattice);
+        let restricted_data = SecurityLabel::new(eu_restricted(), vec![]);
+        let internal_user = SecurityLabel::new(ClassificationLevel::Internal, vec![]);
+        let confidential_user = SecurityLabel::new(ClassificationLevel::Confidential, vec![]);
+        assert!(mac.can_read(&confidential_user, &restricted_data).await);
+        assert!(!mac.can_read(&internal_user, &restricted_data).await);
+        assert!(!mac.can_write(&internal_user, &restricted_data).await);
+
+        // The default lattice doesn't know the level, so the default engine denies it
+        assert!(!MACEngine::new().can_read(&confidential_user, &restricted_data).await);
+    }
+}
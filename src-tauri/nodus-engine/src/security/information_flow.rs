@@ -0,0 +1,201 @@
+// src-tauri/src/security/information_flow.rs
+// Information Flow Tracker - Taint tracking of source labels across operations
+// Derived data keeps the provenance of what was read, so aggregated data can't be written down
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use super::{SecurityError, SecurityLabel};
+use crate::observability::ObservabilityContext;
+
+/// One source read by an operation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlowSource {
+    /// What was read, e.g. `entity:<id>`
+    pub source: String,
+    pub label: SecurityLabel,
+    pub read_at: DateTime<Utc>,
+}
+
+/// What one operation has read, directly and through the operations around it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlowRecord {
+    pub operation_id: Uuid,
+    pub parent_operation_id: Option<Uuid>,
+    pub component: String,
+    pub operation: String,
+    /// Sources this operation read itself
+    pub sources: Vec<FlowSource>,
+    /// High-water mark of everything that flowed into the operation
+    pub taint: SecurityLabel,
+}
+
+/// Taint tracker for operations, keyed by `ObservabilityContext::operation_id`
+///
+/// An operation's taint is the least upper bound of every label it has read. Child
+/// operations start with their parent's taint, and a child's reads also taint its
+/// ancestors, since its results flow back to them. Writes must go to a label that
+/// dominates the writer's taint.
+#[derive(Debug, Default)]
+pub struct InformationFlowTracker {
+    flows: RwLock<HashMap<Uuid, FlowRecord>>,
+}
+
+impl InformationFlowTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start tracking `context`, inheriting its parent's taint if the parent is tracked
+    pub async fn track(&self, context: &ObservabilityContext) {
+        let mut flows = self.flows.write().await;
+        Self::ensure(&mut flows, context);
+    }
+
+    /// Child context of `parent` for a nested operation, tracked with the parent's taint
+    pub async fn child(&self, parent: &ObservabilityContext, component: &str, operation: &str) -> ObservabilityContext {
+        let child = parent.create_child(component, operation);
+        let mut flows = self.flows.write().await;
+        Self::ensure(&mut flows, parent);
+        Self::ensure(&mut flows, &child);
+        child
+    }
+
+    /// Record that the operation read `source` labeled `label`
+    pub async fn record_read(&self, context: &ObservabilityContext, source: &str, label: &SecurityLabel) {
+        let mut flows = self.flows.write().await;
+        let record = Self::ensure(&mut flows, context);
+        record.sources.push(FlowSource {
+            source: source.to_string(),
+            label: label.clone(),
+            read_at: Utc::now(),
+        });
+        record.taint = record.taint.lub(label);
+
+        let mut ancestor = record.parent_operation_id;
+        while let Some(operation_id) = ancestor {
+            let Some(record) = flows.get_mut(&operation_id) else {
+                break;
+            };
+            record.taint = record.taint.lub(label);
+            ancestor = record.parent_operation_id;
+        }
+    }
+
+    /// Check that the operation may write to `target`: it must dominate the accumulated taint
+    pub async fn check_write(&self, context: &ObservabilityContext, target: &SecurityLabel) -> Result<(), SecurityError> {
+        let Some(taint) = self.taint(context.operation_id).await else {
+            return Ok(());
+        };
+        if !target.dominates(&taint) {
+            return Err(SecurityError::FlowViolation {
+                operation_id: context.operation_id,
+                taint: Box::new(taint),
+                target: Box::new(target.clone()),
+            });
+        }
+        Ok(())
+    }
+
+    /// Accumulated taint of an operation, if it is tracked
+    pub async fn taint(&self, operation_id: Uuid) -> Option<SecurityLabel> {
+        self.flows.read().await.get(&operation_id).map(|record| record.taint.clone())
+    }
+
+    /// The operation's flow record followed by those of its tracked ancestors, nearest first
+    pub async fn provenance(&self, operation_id: Uuid) -> Vec<FlowRecord> {
+        let flows = self.flows.read().await;
+        let mut chain = Vec::new();
+        let mut next = Some(operation_id);
+        while let Some(record) = next.and_then(|operation_id| flows.get(&operation_id)) {
+            chain.push(record.clone());
+            next = record.parent_operation_id;
+        }
+        chain
+    }
+
+    /// Stop tracking a finished operation
+    pub async fn finish(&self, operation_id: Uuid) {
+        self.flows.write().await.remove(&operation_id);
+    }
+
+    fn ensure<'a>(flows: &'a mut HashMap<Uuid, FlowRecord>, context: &ObservabilityContext) -> &'a mut FlowRecord {
+        let taint = context.parent_operation_id
+            .and_then(|parent| flows.get(&parent))
+            .map(|parent| parent.taint.clone())
+            .unwrap_or_else(SecurityLabel::public);
+        flows.entry(context.operation_id).or_insert_with(|| FlowRecord {
+            operation_id: context.operation_id,
+            parent_operation_id: context.parent_operation_id,
+            component: context.component.clone(),
+            operation: context.operation.clone(),
+            sources: Vec::new(),
+            taint,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::ClassificationLevel;
+
+    fn context() -> ObservabilityContext {
+        ObservabilityContext::new("storage", "report", ClassificationLevel::Unclassified, "analyst", Uuid::new_v4())
+    }
+
+    fn label(level: ClassificationLevel, compartments: &[&str]) -> SecurityLabel {
+        SecurityLabel::new(level, compartments.iter().map(|c| c.to_string()).collect())
+    }
+
+    #[tokio::test]
+    async fn test_write_below_aggregated_reads_is_blocked() {
+        let tracker = InformationFlowTracker::new();
+        let operation = context();
+        tracker.track(&operation).await;
+
+        tracker.record_read(&operation, "entity:a", &label(ClassificationLevel::Confidential, &["ALPHA"])).await;
+        tracker.record_read(&operation, "entity:b", &label(ClassificationLevel::Secret, &[])).await;
+
+        // Each source alone is dominated, but not their aggregate
+        assert!(matches!(
+            tracker.check_write(&operation, &label(ClassificationLevel::Secret, &[])).await,
+            Err(SecurityError::FlowViolation { .. })
+        ));
+        assert!(tracker.check_write(&operation, &label(ClassificationLevel::Confidential, &["ALPHA"])).await.is_err());
+        assert!(tracker.check_write(&operation, &label(ClassificationLevel::Secret, &["ALPHA"])).await.is_ok());
+
+        // Untracked operations have read nothing
+        assert!(tracker.check_write(&context(), &SecurityLabel::public()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_taint_propagates_through_child_operations() {
+        let tracker = InformationFlowTracker::new();
+        let parent = context();
+        tracker.record_read(&parent, "entity:a", &label(ClassificationLevel::Confidential, &[])).await;
+
+        // Children start with the parent's taint
+        let child = tracker.child(&parent, "storage", "summarize").await;
+        assert!(tracker.check_write(&child, &label(ClassificationLevel::Internal, &[])).await.is_err());
+
+        // A child's reads flow back to its ancestors
+        let grandchild = tracker.child(&child, "storage", "lookup").await;
+        tracker.record_read(&grandchild, "entity:b", &label(ClassificationLevel::Secret, &["BETA"])).await;
+        assert!(tracker.check_write(&parent, &label(ClassificationLevel::Secret, &[])).await.is_err());
+        assert!(tracker.check_write(&parent, &label(ClassificationLevel::Secret, &["BETA"])).await.is_ok());
+
+        let chain = tracker.provenance(grandchild.operation_id).await;
+        let operations: Vec<Uuid> = chain.iter().map(|record| record.operation_id).collect();
+        assert_eq!(operations, vec![grandchild.operation_id, child.operation_id, parent.operation_id]);
+        assert_eq!(chain[0].sources[0].source, "entity:b");
+        assert_eq!(chain[2].sources[0].source, "entity:a");
+
+        tracker.finish(grandchild.operation_id).await;
+        assert!(tracker.taint(grandchild.operation_id).await.is_none());
+        assert_eq!(tracker.provenance(child.operation_id).await.len(), 2);
+    }
+}
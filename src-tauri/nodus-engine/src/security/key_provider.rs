@@ -0,0 +1,471 @@
+// src-tauri/src/security/key_provider.rs
+// Key Provider - Pluggable source of key material (in-memory, file, external KMS/HSM)
+// Master and verification keys are held by the provider; callers only ask it to wrap, unwrap or verify
+
+use ring::{aead, hmac, rand};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use base64::{Engine as _, engine::general_purpose};
+use zeroize::Zeroizing;
+
+/// Key that wraps the classification crypto data keys
+pub const MASTER_KEY_ID: &str = "classification-master";
+
+/// Directory holding system-managed key files; unset means an ephemeral dev provider
+pub const KEY_DIR_ENV: &str = "NODUS_KEY_DIR";
+
+/// Source of key material, selected by the tenant's `KeyManagementStrategy`
+///
+/// Implementations never hand out the keys they hold: data keys are stored wrapped
+/// and unwrapped per use, and license MACs are checked by the provider itself.
+#[async_trait::async_trait]
+pub trait KeyProvider: std::fmt::Debug + Send + Sync {
+    /// Backend name for audit records, e.g. "memory", "file", "kms"
+    fn backend(&self) -> &'static str;
+
+    /// Encrypt `plaintext` (typically a data key) under key `key_id`
+    async fn wrap(&self, key_id: &str, plaintext: &[u8]) -> Result<Vec<u8>, KeyProviderError>;
+
+    /// Decrypt what `wrap` produced under the same key
+    async fn unwrap(&self, key_id: &str, wrapped: &[u8]) -> Result<Zeroizing<Vec<u8>>, KeyProviderError>;
+
+    /// Check an HMAC-SHA256 `tag` over `message` made with key `key_id`
+    async fn verify_mac(&self, key_id: &str, message: &[u8], tag: &[u8]) -> Result<bool, KeyProviderError>;
+}
+
+/// Provider for system-managed keys: key files under `NODUS_KEY_DIR`, with the master key
+/// generated on first start, or an in-memory provider with a fresh master key when it isn't
+/// set (development only)
+pub async fn system_key_provider() -> Result<Arc<dyn KeyProvider>, KeyProviderError> {
+    match std::env::var(KEY_DIR_ENV) {
+        Ok(dir) => {
+            let provider = FileKeyProvider::new(dir);
+            if provider.ensure_key(MASTER_KEY_ID).await? {
+                tracing::info!("Generated master key in {}", provider.dir.display());
+            }
+            Ok(Arc::new(provider))
+        }
+        Err(_) => {
+            tracing::warn!("{} not set; using an ephemeral in-memory key provider", KEY_DIR_ENV);
+            Ok(Arc::new(InMemoryKeyProvider::new().with_generated_key(MASTER_KEY_ID)?))
+        }
+    }
+}
+
+/// Keys held in process memory; for development and tests
+#[derive(Default)]
+pub struct InMemoryKeyProvider {
+    keys: HashMap<String, Zeroizing<Vec<u8>>>,
+}
+
+impl std::fmt::Debug for InMemoryKeyProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InMemoryKeyProvider")
+            .field("key_ids", &self.keys.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl InMemoryKeyProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_key(mut self, key_id: &str, material: &[u8]) -> Self {
+        self.keys.insert(key_id.to_string(), Zeroizing::new(material.to_vec()));
+        self
+    }
+
+    /// Add a random 256-bit key
+    pub fn with_generated_key(self, key_id: &str) -> Result<Self, KeyProviderError> {
+        let material = random_key()?;
+        Ok(self.with_key(key_id, &material))
+    }
+
+    fn key(&self, key_id: &str) -> Result<Zeroizing<Vec<u8>>, KeyProviderError> {
+        self.keys.get(key_id)
+            .cloned()
+            .ok_or_else(|| KeyProviderError::KeyNotFound(key_id.to_string()))
+    }
+}
+
+#[async_trait::async_trait]
+impl KeyProvider for InMemoryKeyProvider {
+    fn backend(&self) -> &'static str {
+        "memory"
+    }
+
+    async fn wrap(&self, key_id: &str, plaintext: &[u8]) -> Result<Vec<u8>, KeyProviderError> {
+        seal(&self.key(key_id)?, key_id, plaintext)
+    }
+
+    async fn unwrap(&self, key_id: &str, wrapped: &[u8]) -> Result<Zeroizing<Vec<u8>>, KeyProviderError> {
+        open(&self.key(key_id)?, key_id, wrapped)
+    }
+
+    async fn verify_mac(&self, key_id: &str, message: &[u8], tag: &[u8]) -> Result<bool, KeyProviderError> {
+        Ok(mac_matches(&self.key(key_id)?, message, tag))
+    }
+}
+
+/// Keys stored one per file as `<dir>/<key_id>.key` (base64), readable by the owner only
+///
+/// Files are read on each use, so a key can be rotated by replacing its file.
+#[derive(Debug, Clone)]
+pub struct FileKeyProvider {
+    dir: PathBuf,
+}
+
+impl FileKeyProvider {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Write a random 256-bit key for `key_id` with owner-only permissions
+    pub async fn generate_key(&self, key_id: &str) -> Result<(), KeyProviderError> {
+        let path = self.key_path(key_id)?;
+        tokio::fs::create_dir_all(&self.dir).await?;
+        let material = random_key()?;
+        let encoded = Zeroizing::new(general_purpose::STANDARD.encode(material.as_slice()));
+
+        let mut options = tokio::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        options.mode(0o600);
+        let mut file = options.open(&path).await?;
+        tokio::io::AsyncWriteExt::write_all(&mut file, encoded.as_bytes()).await?;
+        file.sync_all().await?;
+        Ok(())
+    }
+
+    /// Generate `key_id` unless its file already exists; true if it was generated
+    pub async fn ensure_key(&self, key_id: &str) -> Result<bool, KeyProviderError> {
+        if tokio::fs::try_exists(self.key_path(key_id)?).await? {
+            return Ok(false);
+        }
+        match self.generate_key(key_id).await {
+            Ok(()) => Ok(true),
+            // Another process bootstrapped it first
+            Err(KeyProviderError::Io(e)) if e.kind() == std::io::ErrorKind::AlreadyExists => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn key_path(&self, key_id: &str) -> Result<PathBuf, KeyProviderError> {
+        let valid = !key_id.is_empty()
+            && !key_id.starts_with('.')
+            && key_id.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+        if !valid {
+            return Err(KeyProviderError::InvalidKeyId(key_id.to_string()));
+        }
+        Ok(self.dir.join(format!("{}.key", key_id)))
+    }
+
+    async fn key(&self, key_id: &str) -> Result<Zeroizing<Vec<u8>>, KeyProviderError> {
+        let path = self.key_path(key_id)?;
+        if !tokio::fs::try_exists(&path).await? {
+            return Err(KeyProviderError::KeyNotFound(key_id.to_string()));
+        }
+        check_owner_only(&path).await?;
+
+        let encoded = Zeroizing::new(tokio::fs::read_to_string(&path).await?);
+        general_purpose::STANDARD.decode(encoded.trim())
+            .map(Zeroizing::new)
+            .map_err(|_| KeyProviderError::InvalidKey(key_id.to_string()))
+    }
+}
+
+#[async_trait::async_trait]
+impl KeyProvider for FileKeyProvider {
+    fn backend(&self) -> &'static str {
+        "file"
+    }
+
+    async fn wrap(&self, key_id: &str, plaintext: &[u8]) -> Result<Vec<u8>, KeyProviderError> {
+        seal(&self.key(key_id).await?, key_id, plaintext)
+    }
+
+    async fn unwrap(&self, key_id: &str, wrapped: &[u8]) -> Result<Zeroizing<Vec<u8>>, KeyProviderError> {
+        open(&self.key(key_id).await?, key_id, wrapped)
+    }
+
+    async fn verify_mac(&self, key_id: &str, message: &[u8], tag: &[u8]) -> Result<bool, KeyProviderError> {
+        Ok(mac_matches(&self.key(key_id).await?, message, tag))
+    }
+}
+
+/// Key files must not be readable by group or others
+#[cfg(unix)]
+async fn check_owner_only(path: &Path) -> Result<(), KeyProviderError> {
+    use std::os::unix::fs::PermissionsExt;
+    if tokio::fs::metadata(path).await?.permissions().mode() & 0o077 != 0 {
+        return Err(KeyProviderError::InsecurePermissions(path.to_path_buf()));
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+async fn check_owner_only(_path: &Path) -> Result<(), KeyProviderError> {
+    Ok(())
+}
+
+/// Operations of an external key manager on keys that never leave it
+///
+/// Implemented by bindings to a PKCS#11 module (`C_Encrypt`, `C_Decrypt`,
+/// `C_VerifyMac` on a key found by label) or to a cloud KMS API.
+#[async_trait::async_trait]
+pub trait KmsClient: std::fmt::Debug + Send + Sync {
+    async fn encrypt(&self, key_label: &str, plaintext: &[u8]) -> Result<Vec<u8>, KeyProviderError>;
+
+    async fn decrypt(&self, key_label: &str, ciphertext: &[u8]) -> Result<Vec<u8>, KeyProviderError>;
+
+    async fn verify_mac(&self, key_label: &str, message: &[u8], tag: &[u8]) -> Result<bool, KeyProviderError>;
+}
+
+/// Keys held by an external KMS or HSM, addressed by label
+///
+/// Key ids map to labels through `with_key_label`; unmapped ids are used as labels as-is.
+#[derive(Debug, Clone)]
+pub struct KmsKeyProvider {
+    client: Arc<dyn KmsClient>,
+    key_labels: HashMap<String, String>,
+}
+
+impl KmsKeyProvider {
+    pub fn new(client: Arc<dyn KmsClient>) -> Self {
+        Self { client, key_labels: HashMap::new() }
+    }
+
+    pub fn with_key_label(mut self, key_id: &str, label: &str) -> Self {
+        self.key_labels.insert(key_id.to_string(), label.to_string());
+        self
+    }
+
+    fn label<'a>(&'a self, key_id: &'a str) -> &'a str {
+        self.key_labels.get(key_id).map(String::as_str).unwrap_or(key_id)
+    }
+}
+
+#[async_trait::async_trait]
+impl KeyProvider for KmsKeyProvider {
+    fn backend(&self) -> &'static str {
+        "kms"
+    }
+
+    async fn wrap(&self, key_id: &str, plaintext: &[u8]) -> Result<Vec<u8>, KeyProviderError> {
+        self.client.encrypt(self.label(key_id), plaintext).await
+    }
+
+    async fn unwrap(&self, key_id: &str, wrapped: &[u8]) -> Result<Zeroizing<Vec<u8>>, KeyProviderError> {
+        self.client.decrypt(self.label(key_id), wrapped).await.map(Zeroizing::new)
+    }
+
+    async fn verify_mac(&self, key_id: &str, message: &[u8], tag: &[u8]) -> Result<bool, KeyProviderError> {
+        self.client.verify_mac(self.label(key_id), message, tag).await
+    }
+}
+
+fn random_key() -> Result<Zeroizing<Vec<u8>>, KeyProviderError> {
+    let mut material = Zeroizing::new(vec![0u8; 32]);
+    rand::SecureRandom::fill(&rand::SystemRandom::new(), &mut material)
+        .map_err(|_| KeyProviderError::CryptoFailed)?;
+    Ok(material)
+}
+
+fn aead_key(material: &[u8], key_id: &str) -> Result<aead::LessSafeKey, KeyProviderError> {
+    aead::UnboundKey::new(&aead::AES_256_GCM, material)
+        .map(aead::LessSafeKey::new)
+        .map_err(|_| KeyProviderError::InvalidKey(key_id.to_string()))
+}
+
+/// AES-256-GCM with the key id as AAD; output is nonce || ciphertext || tag
+fn seal(material: &[u8], key_id: &str, plaintext: &[u8]) -> Result<Vec<u8>, KeyProviderError> {
+    let key = aead_key(material, key_id)?;
+    let mut nonce = [0u8; aead::NONCE_LEN];
+    rand::SecureRandom::fill(&rand::SystemRandom::new(), &mut nonce)
+        .map_err(|_| KeyProviderError::CryptoFailed)?;
+
+    let mut in_out = plaintext.to_vec();
+    key.seal_in_place_append_tag(
+        aead::Nonce::assume_unique_for_key(nonce),
+        aead::Aad::from(key_id.as_bytes()),
+        &mut in_out,
+    ).map_err(|_| KeyProviderError::CryptoFailed)?;
+
+    let mut wrapped = nonce.to_vec();
+    wrapped.extend_from_slice(&in_out);
+    Ok(wrapped)
+}
+
+fn open(material: &[u8], key_id: &str, wrapped: &[u8]) -> Result<Zeroizing<Vec<u8>>, KeyProviderError> {
+    if wrapped.len() < aead::NONCE_LEN {
+        return Err(KeyProviderError::CryptoFailed);
+    }
+    let key = aead_key(material, key_id)?;
+    let (nonce, ciphertext) = wrapped.split_at(aead::NONCE_LEN);
+    let nonce = aead::Nonce::try_assume_unique_for_key(nonce)
+        .map_err(|_| KeyProviderError::CryptoFailed)?;
+
+    let mut in_out = Zeroizing::new(ciphertext.to_vec());
+    let plaintext = key.open_in_place(nonce, aead::Aad::from(key_id.as_bytes()), &mut in_out)
+        .map_err(|_| KeyProviderError::CryptoFailed)?;
+    Ok(Zeroizing::new(plaintext.to_vec()))
+}
+
+fn mac_matches(material: &[u8], message: &[u8], tag: &[u8]) -> bool {
+    hmac::verify(&hmac::Key::new(hmac::HMAC_SHA256, material), message, tag).is_ok()
+}
+
+/// Key provider errors
+#[derive(Debug, thiserror::Error)]
+pub enum KeyProviderError {
+    #[error("Key not found: {0}")]
+    KeyNotFound(String),
+
+    #[error("Invalid key id: {0}")]
+    InvalidKeyId(String),
+
+    #[error("Key material for {0} is malformed")]
+    InvalidKey(String),
+
+    #[error("Key file {0} is readable by group or others")]
+    InsecurePermissions(PathBuf),
+
+    #[error("Cryptographic operation failed")]
+    CryptoFailed,
+
+    #[error("Key provider not configured: {0}")]
+    NotConfigured(String),
+
+    #[error("Key manager error: {0}")]
+    Backend(String),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use crate::multi_tenant::KeyManagementStrategy;
+
+    /// KMS stand-in that records each operation as `op:label`
+    #[derive(Debug, Default)]
+    pub(crate) struct RecordingKms {
+        keys: InMemoryKeyProvider,
+        pub calls: Mutex<Vec<String>>,
+    }
+
+    impl RecordingKms {
+        pub fn with_key(label: &str, material: &[u8]) -> Self {
+            Self { keys: InMemoryKeyProvider::new().with_key(label, material), calls: Mutex::default() }
+        }
+
+        pub fn called(&self, call: &str) -> bool {
+            self.calls.lock().unwrap().iter().any(|c| c == call)
+        }
+
+        fn record(&self, op: &str, label: &str) {
+            self.calls.lock().unwrap().push(format!("{}:{}", op, label));
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl KmsClient for RecordingKms {
+        async fn encrypt(&self, key_label: &str, plaintext: &[u8]) -> Result<Vec<u8>, KeyProviderError> {
+            self.record("encrypt", key_label);
+            self.keys.wrap(key_label, plaintext).await
+        }
+
+        async fn decrypt(&self, key_label: &str, ciphertext: &[u8]) -> Result<Vec<u8>, KeyProviderError> {
+            self.record("decrypt", key_label);
+            self.keys.unwrap(key_label, ciphertext).await.map(|key| key.to_vec())
+        }
+
+        async fn verify_mac(&self, key_label: &str, message: &[u8], tag: &[u8]) -> Result<bool, KeyProviderError> {
+            self.record("verify_mac", key_label);
+            self.keys.verify_mac(key_label, message, tag).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_wrap_unwrap_and_mac() {
+        let provider = InMemoryKeyProvider::new()
+            .with_generated_key(MASTER_KEY_ID).unwrap()
+            .with_key("license", b"license-mac-key");
+
+        let wrapped = provider.wrap(MASTER_KEY_ID, b"data key").await.unwrap();
+        assert_ne!(wrapped, b"data key".to_vec());
+        assert_eq!(provider.unwrap(MASTER_KEY_ID, &wrapped).await.unwrap().as_slice(), b"data key");
+
+        // Bound to the key id, and tampering is detected
+        assert!(provider.unwrap("license", &wrapped).await.is_err());
+        let mut tampered = wrapped.clone();
+        *tampered.last_mut().unwrap() ^= 0x01;
+        assert!(matches!(provider.unwrap(MASTER_KEY_ID, &tampered).await, Err(KeyProviderError::CryptoFailed)));
+        assert!(matches!(provider.wrap("missing", b"x").await, Err(KeyProviderError::KeyNotFound(_))));
+
+        let tag = hmac::sign(&hmac::Key::new(hmac::HMAC_SHA256, b"license-mac-key"), b"terms");
+        assert!(provider.verify_mac("license", b"terms", tag.as_ref()).await.unwrap());
+        assert!(!provider.verify_mac("license", b"other terms", tag.as_ref()).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_file_provider_requires_owner_only_key_files() {
+        let dir = std::env::temp_dir().join(format!("nodus-keys-{}", uuid::Uuid::new_v4()));
+        let provider = FileKeyProvider::new(&dir);
+        assert!(provider.ensure_key(MASTER_KEY_ID).await.unwrap());
+        assert!(!provider.ensure_key(MASTER_KEY_ID).await.unwrap(), "an existing key is kept");
+
+        let wrapped = provider.wrap(MASTER_KEY_ID, b"data key").await.unwrap();
+        assert_eq!(provider.unwrap(MASTER_KEY_ID, &wrapped).await.unwrap().as_slice(), b"data key");
+        assert!(matches!(provider.wrap("../etc/passwd", b"x").await, Err(KeyProviderError::InvalidKeyId(_))));
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let path = dir.join(format!("{}.key", MASTER_KEY_ID));
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+            assert!(matches!(
+                provider.unwrap(MASTER_KEY_ID, &wrapped).await,
+                Err(KeyProviderError::InsecurePermissions(_))
+            ));
+        }
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_kms_provider_maps_key_ids_to_labels() {
+        let kms = Arc::new(RecordingKms::with_key("hsm/master-7", &[9u8; 32]));
+        let provider = KmsKeyProvider::new(kms.clone()).with_key_label(MASTER_KEY_ID, "hsm/master-7");
+
+        let wrapped = provider.wrap(MASTER_KEY_ID, b"data key").await.unwrap();
+        assert_eq!(provider.unwrap(MASTER_KEY_ID, &wrapped).await.unwrap().as_slice(), b"data key");
+        assert_eq!(*kms.calls.lock().unwrap(), vec!["encrypt:hsm/master-7", "decrypt:hsm/master-7"]);
+    }
+
+    #[test]
+    fn test_strategy_selects_provider() {
+        let system: Arc<dyn KeyProvider> = Arc::new(InMemoryKeyProvider::new());
+        let customer: Arc<dyn KeyProvider> =
+            Arc::new(KmsKeyProvider::new(Arc::new(RecordingKms::default())));
+
+        let selected = KeyManagementStrategy::SystemManaged
+            .select_provider(system.clone(), Some(customer.clone())).unwrap();
+        assert_eq!(selected.backend(), "memory");
+
+        for strategy in [KeyManagementStrategy::CustomerManaged, KeyManagementStrategy::BYOK, KeyManagementStrategy::Hybrid] {
+            let selected = strategy.select_provider(system.clone(), Some(customer.clone())).unwrap();
+            assert_eq!(selected.backend(), "kms");
+        }
+
+        assert!(matches!(
+            KeyManagementStrategy::BYOK.select_provider(system.clone(), None),
+            Err(KeyProviderError::NotConfigured(_))
+        ));
+        assert_eq!(KeyManagementStrategy::Hybrid.select_provider(system, None).unwrap().backend(), "memory");
+    }
+}
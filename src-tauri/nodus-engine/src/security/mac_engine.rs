@@ -2,97 +2,268 @@
 // MAC Engine Implementation - Replaces MACEngine.js
 // Bell-LaPadula "No Read Up, No Write Down" enforcement
 
-use super::{ClassificationLevel, SecurityLabel, SecurityError, MACOperation, constant_time};
+use super::{ClassificationLevel, SecurityLabel, SecurityError, MACOperation, UserContext, constant_time};
+use super::classification_lattice::{self, ClassificationLattice};
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 use std::collections::HashMap;
+use std::sync::Arc;
 use tokio::sync::RwLock;
-use lru::LruCache;
-use std::num::NonZeroUsize;
+use uuid::Uuid;
+
+use crate::cache::TtlCache;
+use crate::clock::{self, Clock};
 
 /// MAC decision cache entry
 #[derive(Debug, Clone)]
 struct MACDecision {
-    allowed: bool,
+    outcome: Result<(), MACDenialReason>,
     timestamp: chrono::DateTime<chrono::Utc>,
 }
 
+/// Machine-readable reason a MAC decision was denied
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub enum MACDenialReason {
+    /// The subject's clearance has lapsed
+    ContextExpired,
+    /// Subject level doesn't dominate the object's (No Read Up)
+    InsufficientClearance,
+    /// Object level doesn't dominate the subject's (No Write Down)
+    WriteDown,
+    /// Compartments don't nest the way the operation requires
+    CompartmentDenied,
+}
+
+/// Outcome of a MAC decision with the reason for a denial
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MACVerdict {
+    pub allowed: bool,
+    pub reason: Option<MACDenialReason>,
+}
+
+impl From<Result<(), MACDenialReason>> for MACVerdict {
+    fn from(outcome: Result<(), MACDenialReason>) -> Self {
+        Self { allowed: outcome.is_ok(), reason: outcome.err() }
+    }
+}
+
+/// Default minimum wall-clock time for a MAC decision
+pub const DEFAULT_DECISION_FLOOR_MS: u64 = 150;
+
+/// Default time after an access during which a session counts as using the object
+pub const DEFAULT_TRANQUILITY_WINDOW_SECS: i64 = 15 * 60;
+
+/// Default number of objects whose recent accesses are tracked for tranquility
+pub const DEFAULT_MAX_TRACKED_OBJECTS: usize = 100_000;
+
+/// Tranquility principle: whether an object's label may change while sessions use it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+pub enum TranquilityMode {
+    /// Labels may change whenever relabeling is authorized
+    #[default]
+    Off,
+    /// Labels of objects in use never change
+    Strong,
+    /// Labels of objects in use may only be raised
+    Weak,
+}
+
+/// MAC settings of the system policy's `global.mac` section
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct MacPolicy {
+    /// Tranquility principle enforced on reclassification
+    pub tranquility: TranquilityMode,
+    /// Seconds after its last access during which a session counts as using an object
+    pub tranquility_window_seconds: u64,
+    /// Objects whose accesses are tracked at once; the least recently used is forgotten first
+    pub max_tracked_objects: usize,
+    /// Minimum wall-clock time of every decision, so timing doesn't reveal why access was denied
+    pub decision_floor_ms: u64,
+}
+
+impl Default for MacPolicy {
+    fn default() -> Self {
+        Self {
+            tranquility: TranquilityMode::Off,
+            tranquility_window_seconds: DEFAULT_TRANQUILITY_WINDOW_SECS as u64,
+            max_tracked_objects: DEFAULT_MAX_TRACKED_OBJECTS,
+            decision_floor_ms: DEFAULT_DECISION_FLOOR_MS,
+        }
+    }
+}
+
+/// Why a reclassification was refused under tranquility
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, thiserror::Error)]
+pub enum TranquilityViolation {
+    /// Strong tranquility: the object was accessed by another active session
+    #[error("object is in use by {sessions} active session(s)")]
+    InUse { sessions: usize },
+    /// Weak tranquility: the new label doesn't dominate the current one
+    #[error("object in use may only be upgraded")]
+    Downgrade,
+}
+
 /// MAC Engine for Bell-LaPadula enforcement (replaces your JS MACEngine)
+#[derive(Debug)]
 pub struct MACEngine {
-    // LRU cache for MAC decisions (replaces JS Map cache)
-    cache: RwLock<LruCache<String, MACDecision>>,
+    // LRU cache for MAC decisions (replaces JS Map cache); decisions depend only on the labels, so never expire
+    cache: RwLock<TtlCache<String, MACDecision>>,
+
+    // Every decision (cached or computed, granted or denied) takes at least this long
+    decision_floor_ms: u64,
+
+    // Tranquility policy for reclassifying objects
+    tranquility: TranquilityMode,
+    tranquility_window: chrono::Duration,
+
+    // Last access per object per session, for deciding whether an object is in use
+    object_access: RwLock<HashMap<Uuid, HashMap<Uuid, chrono::DateTime<chrono::Utc>>>>,
+    max_tracked_objects: usize,
+
+    // Time source for context expiry and tranquility windows
+    clock: Arc<dyn Clock>,
+
+    // Lattice levels are compared in; the active lattice when unset
+    lattice: Option<Arc<ClassificationLattice>>,
 }
 
 impl MACEngine {
     /// Create new MAC engine with bounded cache (replaces JS constructor)
     pub fn new() -> Self {
+        Self::with_decision_floor(DEFAULT_DECISION_FLOOR_MS)
+    }
+
+    /// Create MAC engine with a custom constant-time floor for decisions
+    pub fn with_decision_floor(decision_floor_ms: u64) -> Self {
         Self {
-            cache: RwLock::new(LruCache::new(NonZeroUsize::new(1024).unwrap())),
+            cache: RwLock::new(TtlCache::new(1024)),
+            decision_floor_ms,
+            tranquility: TranquilityMode::Off,
+            tranquility_window: chrono::Duration::seconds(DEFAULT_TRANQUILITY_WINDOW_SECS),
+            object_access: RwLock::new(HashMap::new()),
+            max_tracked_objects: DEFAULT_MAX_TRACKED_OBJECTS,
+            clock: clock::system_clock(),
+            lattice: None,
         }
     }
 
-    /// Check read access under "No Read Up" rule (replaces JS canRead)
-    pub async fn can_read(&self, subject: &SecurityLabel, object: &SecurityLabel) -> bool {
-        let cache_key = format!("read::{}::{}", 
-            self.label_to_cache_key(subject),
-            self.label_to_cache_key(object)
-        );
+    /// Engine with the decision floor and tranquility policy `policy` configures
+    pub fn from_policy(policy: &MacPolicy) -> Self {
+        let window = i64::try_from(policy.tranquility_window_seconds).unwrap_or(i64::MAX);
+        Self::with_decision_floor(policy.decision_floor_ms)
+            .with_tranquility(policy.tranquility, chrono::Duration::seconds(window))
+            .with_max_tracked_objects(policy.max_tracked_objects)
+    }
 
-        // Check cache first (replaces JS cache check)
-        {
-            let cache = self.cache.read().await;
-            if let Some(decision) = cache.peek(&cache_key) {
-                // Cache hit - return cached result
-                return decision.allowed;
-            }
-        }
+    /// Read time from `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
 
-        // Compute MAC decision with constant-time operation
-        let allowed = constant_time::security_operation(async {
-            Ok(self.evaluate_read_access(subject, object))
-        }, 150).await.unwrap_or(false);
+    /// Compare levels in `lattice` instead of the active one
+    pub fn with_lattice(mut self, lattice: Arc<ClassificationLattice>) -> Self {
+        self.lattice = Some(lattice);
+        self
+    }
 
-        // Cache the result
-        {
-            let mut cache = self.cache.write().await;
-            cache.put(cache_key, MACDecision {
-                allowed,
-                timestamp: chrono::Utc::now(),
-            });
-        }
+    /// Clock the engine decides expiry and tranquility by
+    pub fn clock(&self) -> &Arc<dyn Clock> {
+        &self.clock
+    }
 
-        allowed
+    /// Enforce `mode`, treating a session as using an object for `window` after its last access
+    pub fn with_tranquility(mut self, mode: TranquilityMode, window: chrono::Duration) -> Self {
+        self.tranquility = mode;
+        self.tranquility_window = window;
+        self
+    }
+
+    /// Track accesses to at most `max_tracked_objects` objects at once
+    pub fn with_max_tracked_objects(mut self, max_tracked_objects: usize) -> Self {
+        self.max_tracked_objects = max_tracked_objects.max(1);
+        self
+    }
+
+    /// Tranquility policy in force
+    pub fn tranquility(&self) -> TranquilityMode {
+        self.tranquility
+    }
+
+    /// Constant-time floor applied to every decision
+    pub fn decision_floor_ms(&self) -> u64 {
+        self.decision_floor_ms
+    }
+
+    /// Check read access under "No Read Up" rule (replaces JS canRead)
+    pub async fn can_read(&self, subject: &SecurityLabel, object: &SecurityLabel) -> bool {
+        self.verdict(MACOperation::Read, subject, object).await.allowed
     }
 
     /// Check write access under "No Write Down" rule (replaces JS canWrite)
     pub async fn can_write(&self, subject: &SecurityLabel, object: &SecurityLabel) -> bool {
-        let cache_key = format!("write::{}::{}", 
+        self.verdict(MACOperation::Write, subject, object).await.allowed
+    }
+
+    /// Decide `operation` for a label pair, with the reason for a denial
+    pub async fn verdict(&self, operation: MACOperation, subject: &SecurityLabel, object: &SecurityLabel) -> MACVerdict {
+        self.padded(self.decide(operation, subject, object)).await
+    }
+
+    /// Decide `operation` for a user context; an expired context is denied inside the same floor
+    pub async fn verdict_for_context(&self, operation: MACOperation, context: &UserContext, object: &SecurityLabel) -> MACVerdict {
+        self.padded(async {
+            if !context.is_valid_at(self.clock.now()) {
+                return Err(MACDenialReason::ContextExpired);
+            }
+            self.decide(operation, &context.to_security_label(), object).await
+        }).await
+    }
+
+    /// Run a decision inside the constant-time wrapper
+    ///
+    /// Every reason, and a grant, returns only once the floor has elapsed.
+    async fn padded(&self, decision: impl std::future::Future<Output = Result<(), MACDenialReason>>) -> MACVerdict {
+        constant_time::security_operation(async {
+            Ok::<_, SecurityError>(decision.await)
+        }, self.decision_floor_ms).await
+            .unwrap_or(Err(MACDenialReason::InsufficientClearance))
+            .into()
+    }
+
+    /// Make a MAC decision, consulting the cache
+    ///
+    /// Only ever called inside `padded`, so a cache hit can't be timed apart from a
+    /// fresh evaluation.
+    async fn decide(&self, operation: MACOperation, subject: &SecurityLabel, object: &SecurityLabel) -> Result<(), MACDenialReason> {
+        let cache_key = format!("{}::{}::{}",
+            match operation {
+                MACOperation::Read => "read",
+                MACOperation::Write => "write",
+            },
             self.label_to_cache_key(subject),
             self.label_to_cache_key(object)
         );
 
-        // Check cache first
-        {
-            let cache = self.cache.read().await;
-            if let Some(decision) = cache.peek(&cache_key) {
-                return decision.allowed;
-            }
+        // Check cache first (replaces JS cache check); a hit keeps the decision from being evicted
+        let cached = self.cache.write().await.get(&cache_key).map(|decision| decision.outcome);
+        if let Some(outcome) = cached {
+            return outcome;
         }
 
-        // Compute MAC decision with constant-time operation
-        let allowed = constant_time::security_operation(async {
-            Ok(self.evaluate_write_access(subject, object))
-        }, 150).await.unwrap_or(false);
+        let outcome = match operation {
+            MACOperation::Read => self.evaluate_read_access(subject, object),
+            MACOperation::Write => self.evaluate_write_access(subject, object),
+        };
 
         // Cache the result
-        {
-            let mut cache = self.cache.write().await;
-            cache.put(cache_key, MACDecision {
-                allowed,
-                timestamp: chrono::Utc::now(),
-            });
-        }
+        self.cache.write().await.insert(cache_key, MACDecision {
+            outcome,
+            timestamp: self.clock.now(),
+        });
 
-        allowed
+        outcome
     }
 
     /// Enforce read access with error on violation (replaces JS enforceNoReadUp)
@@ -123,26 +294,45 @@ impl MACEngine {
         Ok(())
     }
 
+    /// Whether `a` is at or above `b` in the engine's lattice
+    fn level_dominates(&self, a: &ClassificationLevel, b: &ClassificationLevel) -> bool {
+        match &self.lattice {
+            Some(lattice) => lattice.dominates(a, b),
+            None => classification_lattice::active().dominates(a, b),
+        }
+    }
+
+    /// [`SecurityLabel::dominates`] in the engine's lattice
+    fn label_dominates(&self, a: &SecurityLabel, b: &SecurityLabel) -> bool {
+        self.level_dominates(&a.level, &b.level) && b.compartments.is_subset(&a.compartments)
+    }
+
     /// Evaluate read access (Bell-LaPadula "No Read Up")
-    fn evaluate_read_access(&self, subject: &SecurityLabel, object: &SecurityLabel) -> bool {
-        // Subject clearance must be >= object classification
-        if subject.level.rank() < object.level.rank() {
-            return false;
+    fn evaluate_read_access(&self, subject: &SecurityLabel, object: &SecurityLabel) -> Result<(), MACDenialReason> {
+        // Subject clearance must dominate object classification
+        if !self.level_dominates(&subject.level, &object.level) {
+            return Err(MACDenialReason::InsufficientClearance);
         }
 
         // Subject compartments must be superset of object compartments
-        self.is_superset(&subject.compartments, &object.compartments)
+        if !self.is_superset(&subject.compartments, &object.compartments) {
+            return Err(MACDenialReason::CompartmentDenied);
+        }
+        Ok(())
     }
 
     /// Evaluate write access (Bell-LaPadula "No Write Down") 
-    fn evaluate_write_access(&self, subject: &SecurityLabel, object: &SecurityLabel) -> bool {
-        // Subject clearance must be <= object classification
-        if subject.level.rank() > object.level.rank() {
-            return false;
+    fn evaluate_write_access(&self, subject: &SecurityLabel, object: &SecurityLabel) -> Result<(), MACDenialReason> {
+        // Object classification must dominate subject clearance
+        if !self.level_dominates(&object.level, &subject.level) {
+            return Err(MACDenialReason::WriteDown);
         }
 
         // Subject compartments must be subset of object compartments
-        self.is_subset(&subject.compartments, &object.compartments)
+        if !self.is_subset(&subject.compartments, &object.compartments) {
+            return Err(MACDenialReason::CompartmentDenied);
+        }
+        Ok(())
     }
 
     /// Check if set A is superset of set B (replaces JS isSuperset)
@@ -157,7 +347,7 @@ impl MACEngine {
 
     /// Generate cache key from security label (replaces JS getCacheKey)
     fn label_to_cache_key(&self, label: &SecurityLabel) -> String {
-        let mut compartments: Vec<_> = label.compartments.iter().collect();
+        let mut compartments: Vec<&str> = label.compartments.iter().map(String::as_str).collect();
         compartments.sort();
         format!("{}|{}", 
             format!("{:?}", label.level).to_lowercase(),
@@ -165,12 +355,127 @@ impl MACEngine {
         )
     }
 
+    /// Note that `session_id` accessed `object_id` now; a no-op with tranquility off
+    pub async fn record_access(&self, object_id: Uuid, session_id: Uuid) {
+        self.record_accesses_at([object_id], session_id, self.clock.now()).await
+    }
+
+    /// Note that `session_id` accessed every object in `object_ids` now, e.g. a query's results
+    pub async fn record_accesses(&self, object_ids: impl IntoIterator<Item = Uuid>, session_id: Uuid) {
+        self.record_accesses_at(object_ids, session_id, self.clock.now()).await
+    }
+
+    async fn record_access_at(&self, object_id: Uuid, session_id: Uuid, at: chrono::DateTime<chrono::Utc>) {
+        self.record_accesses_at([object_id], session_id, at).await
+    }
+
+    /// Past `max_tracked_objects`, accesses older than the window are dropped and then the
+    /// least recently used objects, so memory stays bounded however many objects are read
+    async fn record_accesses_at(
+        &self,
+        object_ids: impl IntoIterator<Item = Uuid>,
+        session_id: Uuid,
+        at: chrono::DateTime<chrono::Utc>,
+    ) {
+        if self.tranquility == TranquilityMode::Off {
+            return;
+        }
+        let mut object_access = self.object_access.write().await;
+        for object_id in object_ids {
+            if !object_access.contains_key(&object_id) && object_access.len() >= self.max_tracked_objects {
+                self.make_room(&mut object_access, at);
+            }
+            object_access.entry(object_id).or_default().insert(session_id, at);
+        }
+    }
+
+    fn make_room(&self, object_access: &mut HashMap<Uuid, HashMap<Uuid, chrono::DateTime<chrono::Utc>>>, now: chrono::DateTime<chrono::Utc>) {
+        object_access.retain(|_, sessions| {
+            sessions.retain(|_, last| now - *last < self.tranquility_window);
+            !sessions.is_empty()
+        });
+        if object_access.len() < self.max_tracked_objects {
+            return;
+        }
+        let least_recent = object_access.iter()
+            .filter_map(|(object_id, sessions)| Some((*sessions.values().max()?, *object_id)))
+            .min();
+        if let Some((_, object_id)) = least_recent {
+            tracing::warn!(%object_id, "Tranquility tracking is full; forgetting the least recently used object");
+            object_access.remove(&object_id);
+        }
+    }
+
+    /// Forget a closed session's accesses, so its objects are no longer in use on its account
+    pub async fn end_session(&self, session_id: Uuid) {
+        let mut object_access = self.object_access.write().await;
+        object_access.retain(|_, sessions| {
+            sessions.remove(&session_id);
+            !sessions.is_empty()
+        });
+    }
+
+    /// Check a relabel of `object_id` from `current` to `new_label` against the tranquility policy
+    ///
+    /// The object is in use if a session other than `requester` accessed it within the
+    /// window. Under strong tranquility no relabel of an object in use is allowed; under
+    /// weak tranquility only one the new label dominates.
+    pub async fn check_tranquility(
+        &self,
+        object_id: Uuid,
+        current: &SecurityLabel,
+        new_label: &SecurityLabel,
+        requester: Uuid,
+    ) -> Result<(), TranquilityViolation> {
+        self.check_tranquility_at(object_id, current, new_label, requester, self.clock.now()).await
+    }
+
+    async fn check_tranquility_at(
+        &self,
+        object_id: Uuid,
+        current: &SecurityLabel,
+        new_label: &SecurityLabel,
+        requester: Uuid,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), TranquilityViolation> {
+        let unchanged = self.label_dominates(current, new_label) && self.label_dominates(new_label, current);
+        if self.tranquility == TranquilityMode::Off || unchanged {
+            return Ok(());
+        }
+
+        let sessions = {
+            let mut object_access = self.object_access.write().await;
+            let Some(accesses) = object_access.get_mut(&object_id) else {
+                return Ok(());
+            };
+            accesses.retain(|_, last| now - *last < self.tranquility_window);
+            let sessions = accesses.keys().filter(|session| **session != requester).count();
+            if accesses.is_empty() {
+                object_access.remove(&object_id);
+            }
+            sessions
+        };
+        if sessions == 0 {
+            return Ok(());
+        }
+
+        match self.tranquility {
+            TranquilityMode::Off => Ok(()),
+            TranquilityMode::Strong => Err(TranquilityViolation::InUse { sessions }),
+            TranquilityMode::Weak if self.label_dominates(new_label, current) => Ok(()),
+            TranquilityMode::Weak => Err(TranquilityViolation::Downgrade),
+        }
+    }
+
     /// Get cache statistics for observability
     pub async fn get_cache_stats(&self) -> HashMap<String, u64> {
-        let cache = self.cache.read().await;
+        let counts = self.cache.read().await.stats();
         let mut stats = HashMap::new();
-        stats.insert("size".to_string(), cache.len() as u64);
-        stats.insert("capacity".to_string(), cache.cap().get() as u64);
+        stats.insert("size".to_string(), counts.size as u64);
+        stats.insert("capacity".to_string(), counts.capacity as u64);
+        stats.insert("hits".to_string(), counts.hits);
+        stats.insert("misses".to_string(), counts.misses);
+        stats.insert("evictions".to_string(), counts.evictions);
         stats
     }
 
@@ -267,5 +572,170 @@ mod tests {
         
         let stats = mac.get_cache_stats().await;
         assert!(stats.get("size").unwrap() > &0);
+        assert_eq!((stats["hits"], stats["misses"]), (1, 1));
+    }
+
+    #[tokio::test]
+    async fn test_granted_and_denied_take_same_time() {
+        let floor_ms = 25;
+        let mac = MACEngine::with_decision_floor(floor_ms);
+
+        let user = create_label(ClassificationLevel::Confidential, vec!["ALPHA"]);
+        let readable = create_label(ClassificationLevel::Internal, vec!["ALPHA"]);
+        let unreadable = create_label(ClassificationLevel::Secret, vec!["BETA"]);
+
+        let mut durations = Vec::new();
+        // Fresh and cached decisions, granted and denied
+        for _ in 0..2 {
+            for object in [&readable, &unreadable] {
+                let start = std::time::Instant::now();
+                let _ = mac.can_read(&user, object).await;
+                durations.push(start.elapsed());
+            }
+        }
+
+        let min = *durations.iter().min().unwrap();
+        let max = *durations.iter().max().unwrap();
+        assert!(min >= std::time::Duration::from_millis(floor_ms));
+        assert!(max - min < std::time::Duration::from_millis(15), "timing band too wide: {:?}", durations);
+    }
+
+    fn context(level: ClassificationLevel, compartments: Vec<&str>, expires_in: chrono::Duration) -> UserContext {
+        UserContext {
+            user_id: uuid::Uuid::new_v4(),
+            level,
+            compartments: compartments.into_iter().map(|s| s.to_string()).collect(),
+            expires: chrono::Utc::now() + expires_in,
+            roles: Vec::new(),
+            tenant_id: None,
+            attributes: HashMap::new(),
+        }
+    }
+
+    fn denied(reason: MACDenialReason) -> MACVerdict {
+        MACVerdict { allowed: false, reason: Some(reason) }
+    }
+
+    #[tokio::test]
+    async fn test_verdict_reports_each_denial_reason() {
+        let mac = MACEngine::with_decision_floor(1);
+        let analyst = context(ClassificationLevel::Confidential, vec!["ALPHA"], chrono::Duration::hours(1));
+        let lapsed = context(ClassificationLevel::Secret, vec!["ALPHA", "BETA"], -chrono::Duration::seconds(1));
+
+        let secret_alpha = create_label(ClassificationLevel::Secret, vec!["ALPHA"]);
+        let internal_alpha = create_label(ClassificationLevel::Internal, vec!["ALPHA"]);
+        let confidential_beta = create_label(ClassificationLevel::Confidential, vec!["BETA"]);
+        let public = create_label(ClassificationLevel::Unclassified, vec![]);
+
+        assert_eq!(mac.verdict_for_context(MACOperation::Read, &lapsed, &public).await, denied(MACDenialReason::ContextExpired));
+        assert_eq!(mac.verdict_for_context(MACOperation::Read, &analyst, &secret_alpha).await, denied(MACDenialReason::InsufficientClearance));
+        assert_eq!(mac.verdict_for_context(MACOperation::Write, &analyst, &internal_alpha).await, denied(MACDenialReason::WriteDown));
+        assert_eq!(mac.verdict_for_context(MACOperation::Read, &analyst, &confidential_beta).await, denied(MACDenialReason::CompartmentDenied));
+        assert_eq!(mac.verdict_for_context(MACOperation::Write, &analyst, &confidential_beta).await, denied(MACDenialReason::CompartmentDenied));
+
+        // Level is checked before compartments, and a cached denial keeps its reason
+        let secret_beta = create_label(ClassificationLevel::Secret, vec!["BETA"]);
+        for _ in 0..2 {
+            assert_eq!(mac.verdict_for_context(MACOperation::Read, &analyst, &secret_beta).await, denied(MACDenialReason::InsufficientClearance));
+        }
+
+        let granted = mac.verdict_for_context(MACOperation::Read, &analyst, &internal_alpha).await;
+        assert_eq!(granted, MACVerdict { allowed: true, reason: None });
+        assert_eq!(serde_json::to_value(denied(MACDenialReason::CompartmentDenied)).unwrap()["reason"], "CompartmentDenied");
+    }
+
+    #[tokio::test]
+    async fn test_expired_context_takes_the_decision_floor() {
+        let floor_ms = 25;
+        let mac = MACEngine::with_decision_floor(floor_ms);
+        let lapsed = context(ClassificationLevel::Secret, vec![], -chrono::Duration::seconds(1));
+
+        let start = std::time::Instant::now();
+        let verdict = mac.verdict_for_context(MACOperation::Read, &lapsed, &SecurityLabel::public()).await;
+        assert_eq!(verdict.reason, Some(MACDenialReason::ContextExpired));
+        assert!(start.elapsed() >= std::time::Duration::from_millis(floor_ms));
+    }
+
+    #[tokio::test]
+    async fn test_strong_tranquility_blocks_relabel_while_in_use() {
+        let mac = MACEngine::with_decision_floor(0)
+            .with_tranquility(TranquilityMode::Strong, chrono::Duration::minutes(5));
+        let (object, reader, relabeler) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let confidential = create_label(ClassificationLevel::Confidential, vec![]);
+        let secret = create_label(ClassificationLevel::Secret, vec![]);
+        let now = chrono::Utc::now();
+
+        mac.record_access_at(object, reader, now).await;
+        assert_eq!(
+            mac.check_tranquility_at(object, &confidential, &secret, relabeler, now).await,
+            Err(TranquilityViolation::InUse { sessions: 1 })
+        );
+        // The requester's own access doesn't count, and a no-op relabel is always fine
+        assert!(mac.check_tranquility_at(object, &confidential, &secret, reader, now).await.is_ok());
+        assert!(mac.check_tranquility_at(object, &confidential, &confidential, relabeler, now).await.is_ok());
+
+        // Once the access is stale, or the session ends, the object is no longer in use
+        let later = now + chrono::Duration::minutes(6);
+        assert!(mac.check_tranquility_at(object, &confidential, &secret, relabeler, later).await.is_ok());
+        mac.record_access_at(object, reader, now).await;
+        mac.end_session(reader).await;
+        assert!(mac.check_tranquility_at(object, &confidential, &secret, relabeler, now).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_weak_tranquility_allows_only_upgrades_while_in_use() {
+        let mac = MACEngine::with_decision_floor(0)
+            .with_tranquility(TranquilityMode::Weak, chrono::Duration::minutes(5));
+        let (object, reader, relabeler) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let secret_alpha = create_label(ClassificationLevel::Secret, vec!["ALPHA"]);
+        let now = chrono::Utc::now();
+        mac.record_access_at(object, reader, now).await;
+
+        let upgrade = create_label(ClassificationLevel::NatoSecret, vec!["ALPHA", "BETA"]);
+        assert!(mac.check_tranquility_at(object, &secret_alpha, &upgrade, relabeler, now).await.is_ok());
+
+        let downgrade = create_label(ClassificationLevel::Confidential, vec!["ALPHA"]);
+        let dropped_compartment = create_label(ClassificationLevel::Secret, vec![]);
+        for new_label in [&downgrade, &dropped_compartment] {
+            assert_eq!(
+                mac.check_tranquility_at(object, &secret_alpha, new_label, relabeler, now).await,
+                Err(TranquilityViolation::Downgrade)
+            );
+        }
+
+        // With tranquility off nothing is tracked or refused
+        let off = MACEngine::with_decision_floor(0);
+        off.record_access_at(object, reader, now).await;
+        assert!(off.check_tranquility_at(object, &secret_alpha, &downgrade, relabeler, now).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_tracking_from_policy_is_bounded() {
+        let policy = MacPolicy {
+            tranquility: TranquilityMode::Strong,
+            tranquility_window_seconds: 300,
+            max_tracked_objects: 2,
+            decision_floor_ms: 40,
+        };
+        let mac = MACEngine::from_policy(&policy);
+        assert_eq!(mac.tranquility(), TranquilityMode::Strong);
+        assert_eq!(mac.decision_floor_ms(), 40);
+        let (reader, relabeler) = (Uuid::new_v4(), Uuid::new_v4());
+        let (first, second, third) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let confidential = create_label(ClassificationLevel::Confidential, vec![]);
+        let secret = create_label(ClassificationLevel::Secret, vec![]);
+        let now = chrono::Utc::now();
+
+        // A full table first drops stale accesses, then the least recently used object
+        mac.record_access_at(first, reader, now - chrono::Duration::minutes(10)).await;
+        mac.record_access_at(second, reader, now - chrono::Duration::minutes(2)).await;
+        mac.record_accesses_at([third], reader, now - chrono::Duration::minutes(1)).await;
+        assert_eq!(mac.object_access.read().await.len(), 2);
+        mac.record_accesses_at([first], reader, now).await;
+        let tracked = mac.object_access.read().await;
+        assert_eq!(tracked.len(), 2);
+        assert!(tracked.contains_key(&first) && tracked.contains_key(&third));
+        drop(tracked);
+        assert!(mac.check_tranquility_at(third, &confidential, &secret, relabeler, now).await.is_err());
     }
 }
@@ -10,7 +10,7 @@ use uuid::Uuid;
 
 use super::{
     SecurityLabel, ClassificationLevel, MACEngine, ClassificationCrypto,
-    SecurityError, SecurityContext, TenantPolicyService,
+    SecurityError, SecurityContext, TenantPolicyService, RoleDefinitions,
 };
 use crate::observability::{ObservabilityContext, ForensicLogger, AutomaticInstrumentation};
 use crate::license::LicenseManager;
@@ -370,9 +370,13 @@ impl SecurityManager {
     }
 
     /// Create security context for user
+    ///
+    /// Permissions come from the roles `role_definitions` assigns the user, never from
+    /// whatever the caller put on `user_context`.
     pub async fn create_security_context(
         &self,
         user_context: &UserContext,
+        role_definitions: &RoleDefinitions,
         session_id: Uuid,
         authentication_method: AuthenticationMethod,
         source_ip: Option<String>,
@@ -396,7 +400,7 @@ impl SecurityManager {
             tenant_id: None, // TODO: Extract from user context
             created_at: chrono::Utc::now(),
             last_accessed: chrono::Utc::now(),
-            permissions: user_context.permissions.clone(),
+            permissions: role_definitions.granted_permissions(&user_context.user_id),
             compartment_access: user_context.compartments.clone(),
             security_attributes: HashMap::new(),
         };
@@ -451,6 +455,39 @@ impl SecurityManager {
         None
     }
 
+    /// Re-resolve the session's permissions from `role_definitions`; returns the permissions now held
+    ///
+    /// Called on session refresh so a role change reaches a logged-in user without a new login.
+    pub async fn refresh_security_context(
+        &self,
+        session_id: Uuid,
+        role_definitions: &RoleDefinitions,
+    ) -> Result<Vec<String>, SecurityError> {
+        let (user_id, previous, permissions) = {
+            let mut contexts = self.active_security_contexts.write().await;
+            let context = contexts.values_mut()
+                .find(|context| context.session_id == session_id)
+                .ok_or(SecurityError::SessionNotFound(session_id))?;
+            let permissions = role_definitions.granted_permissions(&context.user_id);
+            let previous = std::mem::replace(&mut context.permissions, permissions.clone());
+            context.last_accessed = chrono::Utc::now();
+            (context.user_id.clone(), previous, permissions)
+        };
+
+        if previous != permissions {
+            self.forensic_logger.log_security_event(
+                "security.context.permissions_refreshed",
+                &format!(
+                    "Permissions of session {} for user {} changed from [{}] to [{}]",
+                    session_id, user_id, previous.join(", "), permissions.join(", "),
+                ),
+                &user_id,
+            ).await.map_err(|e| SecurityError::AuditError(e.to_string()))?;
+        }
+
+        Ok(permissions)
+    }
+
     /// Update security context with new activity
     pub async fn update_security_context(
         &self,
@@ -525,6 +562,21 @@ impl SecurityManager {
         Ok(())
     }
 
+    /// Terminate every security context belonging to `tenant_id`, returning how many ended
+    pub async fn terminate_tenant_sessions(&self, tenant_id: &str) -> Result<usize, SecurityError> {
+        let session_ids: Vec<Uuid> = self.active_security_contexts.read().await
+            .values()
+            .filter(|context| context.tenant_id.as_deref() == Some(tenant_id))
+            .map(|context| context.session_id)
+            .collect();
+
+        for session_id in &session_ids {
+            self.terminate_security_context(*session_id).await?;
+        }
+
+        Ok(session_ids.len())
+    }
+
     /// Encrypt data with security context
     pub async fn encrypt_data(
         &self,
@@ -3,6 +3,7 @@
 // Maps to: SecurityManager.js, MACEngine.js, ClassificationCrypto.js, etc.
 
 use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
 use std::collections::{HashMap, HashSet};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
@@ -11,69 +12,156 @@ use constant_time_eq::constant_time_eq;
 use std::fmt;
 
 pub mod mac_engine;
+pub mod api_keys;
+pub mod abac;
 pub mod classification_crypto;
+pub mod classification_lattice;
+pub mod classifier;
+pub mod subject_keys;
+pub mod key_provider;
+pub mod permissions;
 pub mod security_manager;
-// pub mod information_flow; // consolidated/not present as separate file
+pub mod information_flow;
 // pub mod tenant_policy; // consolidated/not present as separate file
 
-pub use mac_engine::MACEngine;
-pub use classification_crypto::ClassificationCrypto;
+pub use mac_engine::{MACDenialReason, MACEngine, MACVerdict, MacPolicy, TranquilityMode, TranquilityViolation};
+pub use abac::{AbacResource, AbacRule, evaluate_abac};
+pub use api_keys::{ApiKey, ApiKeyContext, ApiKeyError, ApiKeyManager, ApiKeyStore, InMemoryApiKeyStore};
+pub use classification_crypto::{ClassificationCrypto, EncryptedBlob, FieldKeyStore, InMemoryFieldKeyStore, StoredFieldKey};
+pub use classification_lattice::ClassificationLattice;
+pub use classifier::{ClassificationClassifier, ClassificationSuggestion};
+pub use subject_keys::{SealedValue, SubjectKey};
+pub use key_provider::{FileKeyProvider, InMemoryKeyProvider, KeyProvider, KeyProviderError, KmsClient, KmsKeyProvider};
+pub use permissions::{has_permission, Permission, PermissionHolder, RoleAssignment, RoleDefinitions};
 pub use security_manager::SecurityManager;
 pub use information_flow::InformationFlowTracker;
 pub use tenant_policy::TenantPolicyService;
 
 /// Security classification levels (maps to your JS enum)
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize, sqlx::Type)]
-#[sqlx(type_name = "classification_level")]
-#[serde(rename_all = "lowercase")]
+///
+/// The built-in variants are the default lattice; `Custom` holds the key of an extra level
+/// loaded from a lattice config. Ordering, parsing and display all come from the active
+/// `ClassificationLattice`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ClassificationLevel {
     Unclassified,
     Internal,
     Confidential, 
     Secret,
     NatoSecret,
+    Custom(String),
 }
 
 impl ClassificationLevel {
-    /// Convert to numeric rank for comparison (replaces JS level_rank function)
+    /// Height in the active lattice; use `dominates` for access decisions
     pub fn rank(&self) -> u8 {
-        match self {
-            ClassificationLevel::Unclassified => 0,
-            ClassificationLevel::Internal => 1,
-            ClassificationLevel::Confidential => 2,
-            ClassificationLevel::Secret => 3,
-            ClassificationLevel::NatoSecret => 4,
-        }
+        classification_lattice::active().rank(self)
+    }
+
+    /// Whether this level is at or above `other` in the active lattice
+    pub fn dominates(&self, other: &ClassificationLevel) -> bool {
+        classification_lattice::active().dominates(self, other)
+    }
+
+    /// Highest built-in level this one dominates
+    pub fn builtin_baseline(&self) -> ClassificationLevel {
+        classification_lattice::active().builtin_baseline(self)
     }
     
-    /// Parse from string (replaces JS string parsing)
+    /// Parse a level name, display form or alias known to the active lattice
     pub fn from_str(s: &str) -> Result<Self, SecurityError> {
-        match s.to_lowercase().as_str() {
-            "unclassified" => Ok(ClassificationLevel::Unclassified),
-            "internal" => Ok(ClassificationLevel::Internal),
-            "confidential" => Ok(ClassificationLevel::Confidential),
-            "secret" => Ok(ClassificationLevel::Secret),
-            "nato_secret" => Ok(ClassificationLevel::NatoSecret),
-            _ => Err(SecurityError::InvalidClassification(s.to_string())),
+        classification_lattice::active()
+            .parse(s.trim())
+            .ok_or_else(|| SecurityError::InvalidClassification(s.to_string()))
+    }
+
+    /// Lattice key of the level
+    pub fn key(&self) -> &str {
+        match self {
+            ClassificationLevel::Unclassified => "unclassified",
+            ClassificationLevel::Internal => "internal",
+            ClassificationLevel::Confidential => "confidential",
+            ClassificationLevel::Secret => "secret",
+            ClassificationLevel::NatoSecret => "nato_secret",
+            ClassificationLevel::Custom(key) => key,
+        }
+    }
+
+    pub(crate) fn from_key(key: &str) -> Self {
+        match key {
+            "unclassified" => ClassificationLevel::Unclassified,
+            "internal" => ClassificationLevel::Internal,
+            "confidential" => ClassificationLevel::Confidential,
+            "secret" => ClassificationLevel::Secret,
+            "nato_secret" => ClassificationLevel::NatoSecret,
+            other => ClassificationLevel::Custom(other.to_string()),
         }
     }
 }
 
 impl fmt::Display for ClassificationLevel {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let s = match self {
-            ClassificationLevel::Unclassified => "UNCLASSIFIED",
-            ClassificationLevel::Internal => "INTERNAL",
-            ClassificationLevel::Confidential => "CONFIDENTIAL",
-            ClassificationLevel::Secret => "SECRET",
-            ClassificationLevel::NatoSecret => "NATO_SECRET",
-        };
-        write!(f, "{}", s)
+        write!(f, "{}", classification_lattice::active().display_name(self))
+    }
+}
+
+impl Serialize for ClassificationLevel {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            // Built-in levels keep their original lowercase serde form
+            ClassificationLevel::NatoSecret => serializer.serialize_str("natosecret"),
+            level => serializer.serialize_str(level.key()),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ClassificationLevel {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        ClassificationLevel::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+// A string rather than an enum: custom levels come from the lattice loaded at runtime
+impl JsonSchema for ClassificationLevel {
+    fn schema_name() -> String {
+        "ClassificationLevel".to_string()
+    }
+
+    fn json_schema(gen: &mut schemars::gen::SchemaGenerator) -> schemars::schema::Schema {
+        let mut schema = String::json_schema(gen).into_object();
+        schema.metadata().description =
+            Some("Level name, display form or alias known to the active classification lattice".to_string());
+        schema.into()
+    }
+}
+
+// Stored as the lattice key, so renaming a level's display form never orphans stored rows
+impl sqlx::Type<sqlx::Postgres> for ClassificationLevel {
+    fn type_info() -> sqlx::postgres::PgTypeInfo {
+        sqlx::postgres::PgTypeInfo::with_name("classification_level")
+    }
+
+    fn compatible(ty: &sqlx::postgres::PgTypeInfo) -> bool {
+        *ty == Self::type_info() || <String as sqlx::Type<sqlx::Postgres>>::compatible(ty)
+    }
+}
+
+impl<'q> sqlx::Encode<'q, sqlx::Postgres> for ClassificationLevel {
+    fn encode_by_ref(&self, buf: &mut sqlx::postgres::PgArgumentBuffer) -> sqlx::encode::IsNull {
+        <&str as sqlx::Encode<sqlx::Postgres>>::encode(self.key(), buf)
+    }
+}
+
+impl<'r> sqlx::Decode<'r, sqlx::Postgres> for ClassificationLevel {
+    fn decode(value: sqlx::postgres::PgValueRef<'r>) -> Result<Self, sqlx::error::BoxDynError> {
+        let s = <&str as sqlx::Decode<sqlx::Postgres>>::decode(value)?;
+        Ok(ClassificationLevel::from_key(s))
     }
 }
 
 /// Security label structure (replaces JS security label objects)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct SecurityLabel {
     pub level: ClassificationLevel,
     pub compartments: HashSet<String>,
@@ -90,22 +178,59 @@ impl SecurityLabel {
     pub fn public() -> Self {
         Self::new(ClassificationLevel::Unclassified, vec![])
     }
+    
+    /// Whether this label is at least `other`'s level and holds all of its compartments
+    pub fn dominates(&self, other: &SecurityLabel) -> bool {
+        self.level.dominates(&other.level) && other.compartments.is_subset(&self.compartments)
+    }
+
+    /// Least upper bound: the lowest level dominating both, with the union of compartments
+    pub fn lub(&self, other: &SecurityLabel) -> SecurityLabel {
+        let lattice = classification_lattice::active();
+        // Levels unknown to the lattice have no join; fall back to its top (fail closed)
+        let level = lattice.join(&self.level, &other.level).unwrap_or_else(|| lattice.top());
+        SecurityLabel {
+            level,
+            compartments: self.compartments.union(&other.compartments).cloned().collect(),
+        }
+    }
+
+    /// High-water mark of a set of source labels, for entities derived from all of them
+    pub fn aggregate(sources: &[SecurityLabel]) -> SecurityLabel {
+        match sources.split_first() {
+            Some((first, rest)) => rest.iter().fold(first.clone(), |label, source| label.lub(source)),
+            None => SecurityLabel::public(),
+        }
+    }
+}
+
+/// Whether `result` may label an entity built from `sources` (it must dominate every source)
+pub fn can_aggregate(sources: &[SecurityLabel], result: &SecurityLabel) -> bool {
+    sources.iter().all(|source| result.dominates(source))
 }
 
 /// User security context (replaces JS user context objects)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct UserContext {
     pub user_id: Uuid,
     pub level: ClassificationLevel,
     pub compartments: HashSet<String>,
     pub expires: DateTime<Utc>,
     pub roles: Vec<String>,
-    pub tenant_id: Option<Uuid>,
+    pub tenant_id: Option<String>,
+    /// ABAC attributes (citizenship, need-to-know tags, project, ...)
+    #[serde(default)]
+    pub attributes: HashMap<String, serde_json::Value>,
 }
 
 impl UserContext {
     pub fn is_valid(&self) -> bool {
-        Utc::now() < self.expires
+        self.is_valid_at(Utc::now())
+    }
+
+    /// Whether the context is unexpired at `now`
+    pub fn is_valid_at(&self, now: DateTime<Utc>) -> bool {
+        now < self.expires
     }
     
     pub fn to_security_label(&self) -> SecurityLabel {
@@ -117,7 +242,7 @@ impl UserContext {
 }
 
 /// MAC operation types (replaces JS operation strings)
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub enum MACOperation {
     Read,
     Write,
@@ -129,23 +254,44 @@ pub enum SecurityError {
     #[error("Invalid classification: {0}")]
     InvalidClassification(String),
     
+    #[error("Invalid classification lattice: {0}")]
+    InvalidLattice(String),
+    
+    #[error("Invalid classification rule: {0}")]
+    InvalidRule(String),
+    
+    #[error("Invalid role definitions: {0}")]
+    InvalidRoles(String),
+    
     #[error("MAC policy violation: {operation:?} access denied")]
     MACViolation { operation: MACOperation },
     
     #[error("User context expired")]
     ContextExpired,
     
+    #[error("No security context for session {0}")]
+    SessionNotFound(Uuid),
+    
     #[error("Insufficient clearance")]
     InsufficientClearance,
     
     #[error("Compartment access denied")]
     CompartmentDenied,
     
+    #[error("Attribute policy denied {0} access")]
+    AttributeDenied(String),
+    
     #[error("Cryptographic operation failed: {0}")]
     CryptoError(String),
     
     #[error("License validation failed: {feature}")]
     LicenseError { feature: String },
+    
+    #[error("Information flow violation: operation {operation_id} has read {} data, cannot write to {}", .taint.level, .target.level)]
+    FlowViolation { operation_id: Uuid, taint: Box<SecurityLabel>, target: Box<SecurityLabel> },
+    
+    #[error(transparent)]
+    Instrumentation(#[from] crate::observability::InstrumentationError),
 }
 
 /// Constant-time comparison utilities (replaces ct.js)
@@ -198,13 +344,17 @@ pub enum SecurityEvent {
         user_id: Uuid,
     },
     ContextExpired {
-        user_id: Uuid,
+        user_id: String,
+        level: ClassificationLevel,
+        expired_at: DateTime<Utc>,
     },
     MACDecision {
         operation: MACOperation,
         subject: SecurityLabel,
         object: SecurityLabel,
         allowed: bool,
+        #[serde(default)]
+        reason: Option<MACDenialReason>,
     },
     MACViolation {
         operation: MACOperation,
@@ -243,6 +393,29 @@ mod tests {
         assert!(label.compartments.contains("ALPHA"));
     }
     
+    #[test]
+    fn test_aggregation_takes_high_water_mark() {
+        let alpha_confidential = SecurityLabel::new(ClassificationLevel::Confidential, vec!["ALPHA".to_string()]);
+        let beta_secret = SecurityLabel::new(ClassificationLevel::Secret, vec!["BETA".to_string()]);
+        let internal = SecurityLabel::new(ClassificationLevel::Internal, vec![]);
+        let sources = vec![alpha_confidential.clone(), beta_secret.clone(), internal.clone()];
+
+        let lub = alpha_confidential.lub(&beta_secret);
+        assert_eq!(lub.level, ClassificationLevel::Secret);
+        assert_eq!(lub.compartments, ["ALPHA", "BETA"].iter().map(|c| c.to_string()).collect());
+
+        let aggregated = SecurityLabel::aggregate(&sources);
+        assert_eq!(aggregated.level, ClassificationLevel::Secret);
+        assert_eq!(aggregated.compartments, lub.compartments);
+        assert!(can_aggregate(&sources, &aggregated));
+
+        // Dropping a compartment or the level would downgrade a source
+        let missing_alpha = SecurityLabel::new(ClassificationLevel::NatoSecret, vec!["BETA".to_string()]);
+        assert!(!can_aggregate(&sources, &missing_alpha));
+        let too_low = SecurityLabel::new(ClassificationLevel::Confidential, vec!["ALPHA".to_string(), "BETA".to_string()]);
+        assert!(!can_aggregate(&sources, &too_low));
+    }
+    
     #[tokio::test]
     async fn test_constant_time_operation() {
         use std::time::Instant;
@@ -0,0 +1,268 @@
+// src-tauri/src/security/permissions.rs
+// Permission Model - Typed permissions and the roles that grant them
+// Contexts still carry permission names; every check goes through `has_permission` so grants stay auditable
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
+use std::fmt;
+
+use super::security_manager::SecurityContext;
+use super::SecurityError;
+use crate::database::DatabaseContext;
+use crate::state::UserContext;
+
+/// Something an operator may be allowed to do
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Permission {
+    /// Read entities
+    Read,
+    /// Create, update and delete entities
+    Write,
+    /// Change an entity's label
+    Relabel,
+    /// Propose or approve a label that does not dominate the old one
+    RelabelDowngrade,
+    /// Erase entities or a data subject's keys for good
+    Erase,
+    /// Place or release legal holds
+    LegalHold,
+    /// Manage tenants, and work in one under maintenance
+    TenantAdmin,
+    /// Validate, inspect and update the license
+    LicenseAdmin,
+    /// Search the audit trail
+    AuditAccess,
+    /// Export the audit trail
+    AuditExport,
+    /// Verify forensic chain integrity
+    ForensicAudit,
+}
+
+impl Permission {
+    pub const ALL: [Permission; 11] = [
+        Permission::Read,
+        Permission::Write,
+        Permission::Relabel,
+        Permission::RelabelDowngrade,
+        Permission::Erase,
+        Permission::LegalHold,
+        Permission::TenantAdmin,
+        Permission::LicenseAdmin,
+        Permission::AuditAccess,
+        Permission::AuditExport,
+        Permission::ForensicAudit,
+    ];
+
+    /// Name carried in contexts and role definitions
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Permission::Read => "read",
+            Permission::Write => "write",
+            Permission::Relabel => "relabel",
+            Permission::RelabelDowngrade => "relabel_downgrade",
+            Permission::Erase => "erase",
+            Permission::LegalHold => "legal_hold",
+            Permission::TenantAdmin => "tenant_admin",
+            Permission::LicenseAdmin => "license_admin",
+            Permission::AuditAccess => "audit_access",
+            Permission::AuditExport => "audit_export",
+            Permission::ForensicAudit => "forensic_audit",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|permission| permission.as_str() == name)
+    }
+}
+
+impl fmt::Display for Permission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A context carrying the names of the permissions it was granted
+pub trait PermissionHolder {
+    fn permission_names(&self) -> &[String];
+}
+
+impl PermissionHolder for UserContext {
+    fn permission_names(&self) -> &[String] {
+        &self.permissions
+    }
+}
+
+impl PermissionHolder for SecurityContext {
+    fn permission_names(&self) -> &[String] {
+        &self.permissions
+    }
+}
+
+impl PermissionHolder for DatabaseContext {
+    fn permission_names(&self) -> &[String] {
+        &self.permissions
+    }
+}
+
+/// Whether `context` holds `permission`
+pub fn has_permission(context: &impl PermissionHolder, permission: Permission) -> bool {
+    context.permission_names().iter().any(|held| held == permission.as_str())
+}
+
+/// A configured role (`[[roles]]` in the role config)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoleDefinition {
+    pub name: String,
+    #[serde(default)]
+    pub permissions: Vec<Permission>,
+}
+
+/// Roles held by one user (`[[assignments]]` in the role config)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoleAssignment {
+    pub user_id: String,
+    #[serde(default)]
+    pub roles: Vec<String>,
+}
+
+/// Role config file contents
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct RoleConfig {
+    #[serde(default)]
+    pub roles: Vec<RoleDefinition>,
+    #[serde(default)]
+    pub assignments: Vec<RoleAssignment>,
+}
+
+/// Which permissions each role grants, and which roles each user holds
+#[derive(Debug, Clone, Default)]
+pub struct RoleDefinitions {
+    roles: HashMap<String, BTreeSet<Permission>>,
+    assignments: HashMap<String, Vec<String>>,
+}
+
+impl RoleDefinitions {
+    pub fn new(roles: Vec<RoleDefinition>) -> Result<Self, SecurityError> {
+        let mut definitions = HashMap::new();
+        for role in roles {
+            if role.name.trim().is_empty() {
+                return Err(SecurityError::InvalidRoles("role without a name".to_string()));
+            }
+            if definitions.insert(role.name.clone(), role.permissions.into_iter().collect()).is_some() {
+                return Err(SecurityError::InvalidRoles(format!("role '{}' defined twice", role.name)));
+            }
+        }
+        Ok(Self { roles: definitions, assignments: HashMap::new() })
+    }
+
+    /// Assign roles to users; a user may be assigned only once
+    pub fn with_assignments(mut self, assignments: Vec<RoleAssignment>) -> Result<Self, SecurityError> {
+        for assignment in assignments {
+            if assignment.user_id.trim().is_empty() {
+                return Err(SecurityError::InvalidRoles("assignment without a user".to_string()));
+            }
+            if self.assignments.insert(assignment.user_id.clone(), assignment.roles).is_some() {
+                return Err(SecurityError::InvalidRoles(format!("user '{}' assigned twice", assignment.user_id)));
+            }
+        }
+        Ok(self)
+    }
+
+    /// Parse roles from TOML (`[[roles]]` and `[[assignments]]` tables)
+    pub fn from_toml(contents: &str) -> Result<Self, SecurityError> {
+        let config: RoleConfig = toml::from_str(contents)
+            .map_err(|e| SecurityError::InvalidRoles(e.to_string()))?;
+        Self::new(config.roles)?.with_assignments(config.assignments)
+    }
+
+    /// Load roles from a TOML file
+    pub async fn load(path: &str) -> Result<Self, SecurityError> {
+        let contents = tokio::fs::read_to_string(path).await
+            .map_err(|e| SecurityError::InvalidRoles(format!("{}: {}", path, e)))?;
+        Self::from_toml(&contents)
+    }
+
+    pub fn role_names(&self) -> impl Iterator<Item = &str> {
+        self.roles.keys().map(String::as_str)
+    }
+
+    /// Permissions granted by one role; `None` if the role is not defined
+    pub fn role(&self, name: &str) -> Option<&BTreeSet<Permission>> {
+        self.roles.get(name)
+    }
+
+    /// Union of the permissions granted by `roles`; undefined roles grant nothing
+    pub fn permissions_for(&self, roles: &[String]) -> BTreeSet<Permission> {
+        roles.iter()
+            .filter_map(|role| {
+                let granted = self.roles.get(role);
+                if granted.is_none() {
+                    tracing::warn!("Role '{}' is not defined; it grants no permissions", role);
+                }
+                granted
+            })
+            .flatten()
+            .copied()
+            .collect()
+    }
+
+    /// Roles assigned to `user_id`; users without an assignment hold none
+    pub fn roles_of(&self, user_id: &str) -> Vec<String> {
+        self.assignments.get(user_id).cloned().unwrap_or_default()
+    }
+
+    /// Names of the permissions `user_id` holds through their assigned roles
+    pub fn granted_permissions(&self, user_id: &str) -> Vec<String> {
+        self.permissions_for(&self.roles_of(user_id))
+            .into_iter()
+            .map(|permission| permission.as_str().to_string())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_role_definitions_from_toml() {
+        let roles = RoleDefinitions::from_toml(r#"
+            [[roles]]
+            name = "analyst"
+            permissions = ["read", "write"]
+
+            [[roles]]
+            name = "records_officer"
+            permissions = ["relabel", "relabel_downgrade", "legal_hold"]
+        "#).unwrap();
+
+        let granted = roles.permissions_for(&["analyst".to_string(), "records_officer".to_string(), "ghost".to_string()]);
+        assert!(granted.contains(&Permission::Write));
+        assert!(granted.contains(&Permission::RelabelDowngrade));
+        assert!(!granted.contains(&Permission::TenantAdmin));
+        assert_eq!(Permission::from_name("tenant_admin"), Some(Permission::TenantAdmin));
+
+        assert!(RoleDefinitions::from_toml("[[roles]]\nname = \"x\"\npermissions = [\"launch_missiles\"]").is_err());
+        assert!(RoleDefinitions::from_toml("[[roles]]\nname = \"x\"\n[[roles]]\nname = \"x\"").is_err());
+    }
+
+    #[test]
+    fn test_assignments_grant_permissions_per_user() {
+        let roles = RoleDefinitions::from_toml(r#"
+            [[roles]]
+            name = "analyst"
+            permissions = ["read", "write"]
+
+            [[assignments]]
+            user_id = "alice"
+            roles = ["analyst"]
+        "#).unwrap();
+
+        assert_eq!(roles.roles_of("alice"), vec!["analyst".to_string()]);
+        assert_eq!(roles.granted_permissions("alice"), vec!["read".to_string(), "write".to_string()]);
+        assert!(roles.granted_permissions("mallory").is_empty());
+
+        assert!(RoleDefinitions::from_toml("[[assignments]]\nuser_id = \"a\"\n[[assignments]]\nuser_id = \"a\"").is_err());
+    }
+}
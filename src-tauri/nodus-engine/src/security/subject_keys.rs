@@ -0,0 +1,191 @@
+// src-tauri/src/security/subject_keys.rs
+// Subject Keys - Per-data-subject encryption keys for GDPR crypto-shredding
+// Personal data is sealed under its subject's key, so destroying the key erases every stored copy without touching the rows
+
+use base64::{Engine as _, engine::general_purpose};
+use ring::{aead, rand};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use super::SecurityError;
+
+/// JSON key a sealed value is stored under, e.g. `{"subject_sealed": {...}}`
+pub const SEALED_MARKER: &str = "subject_sealed";
+
+/// AES-256-GCM key for one data subject's personal data
+#[derive(Zeroize, ZeroizeOnDrop)]
+pub struct SubjectKey {
+    #[zeroize(skip)]
+    pub subject_id: String,
+    #[zeroize(skip)]
+    pub key_id: Uuid,
+    material: [u8; 32],
+}
+
+impl std::fmt::Debug for SubjectKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SubjectKey")
+            .field("subject_id", &self.subject_id)
+            .field("key_id", &self.key_id)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A value sealed under a subject key; unrecoverable once that key is destroyed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SealedValue {
+    pub subject_id: String,
+    pub key_id: Uuid,
+    /// Base64
+    pub nonce: String,
+    /// Base64, GCM tag appended
+    pub ciphertext: String,
+}
+
+impl SealedValue {
+    /// The sealed value in `value`, if it is one produced by `SubjectKey::seal`
+    pub fn from_json(value: &serde_json::Value) -> Option<Self> {
+        serde_json::from_value(value.get(SEALED_MARKER)?.clone()).ok()
+    }
+
+    /// Subject and key id bound into the GCM tag, so a value can't be moved to another subject
+    fn aad(&self) -> Vec<u8> {
+        format!("{}|{}", self.subject_id, self.key_id).into_bytes()
+    }
+}
+
+impl SubjectKey {
+    /// Fresh random key for `subject_id`
+    pub fn generate(subject_id: &str) -> Result<Self, SecurityError> {
+        let mut material = [0u8; 32];
+        rand::SecureRandom::fill(&rand::SystemRandom::new(), &mut material)
+            .map_err(|_| SecurityError::CryptoError("Failed to generate subject key".to_string()))?;
+
+        Ok(Self {
+            subject_id: subject_id.to_string(),
+            key_id: Uuid::new_v4(),
+            material,
+        })
+    }
+
+    /// Rebuild a stored key
+    pub fn from_material(subject_id: &str, key_id: Uuid, material: &[u8]) -> Result<Self, SecurityError> {
+        let material: [u8; 32] = material.try_into()
+            .map_err(|_| SecurityError::CryptoError(format!("Subject key {} has the wrong length", key_id)))?;
+        Ok(Self { subject_id: subject_id.to_string(), key_id, material })
+    }
+
+    /// Raw key bytes, for storage only
+    pub(crate) fn material(&self) -> &[u8] {
+        &self.material
+    }
+
+    /// Seal `value` as `{"subject_sealed": SealedValue}`
+    pub fn seal(&self, value: &serde_json::Value) -> Result<serde_json::Value, SecurityError> {
+        let mut nonce = [0u8; aead::NONCE_LEN];
+        rand::SecureRandom::fill(&rand::SystemRandom::new(), &mut nonce)
+            .map_err(|_| SecurityError::CryptoError("Failed to generate nonce".to_string()))?;
+
+        let mut sealed = SealedValue {
+            subject_id: self.subject_id.clone(),
+            key_id: self.key_id,
+            nonce: general_purpose::STANDARD.encode(nonce),
+            ciphertext: String::new(),
+        };
+        let mut in_out = serde_json::to_vec(value)
+            .map_err(|e| SecurityError::CryptoError(e.to_string()))?;
+        self.aead_key()?
+            .seal_in_place_append_tag(
+                aead::Nonce::assume_unique_for_key(nonce),
+                aead::Aad::from(sealed.aad()),
+                &mut in_out,
+            )
+            .map_err(|_| SecurityError::CryptoError("Subject seal failed".to_string()))?;
+        sealed.ciphertext = general_purpose::STANDARD.encode(&in_out);
+        in_out.zeroize();
+
+        Ok(serde_json::json!({ SEALED_MARKER: sealed }))
+    }
+
+    /// Open a value sealed under this key
+    pub fn open(&self, sealed: &SealedValue) -> Result<serde_json::Value, SecurityError> {
+        if sealed.key_id != self.key_id || sealed.subject_id != self.subject_id {
+            return Err(SecurityError::CryptoError(format!("Value was not sealed under key {}", self.key_id)));
+        }
+        let decode = |field: &str| general_purpose::STANDARD.decode(field)
+            .map_err(|_| SecurityError::CryptoError("Malformed sealed value".to_string()));
+        let nonce = aead::Nonce::try_assume_unique_for_key(&decode(&sealed.nonce)?)
+            .map_err(|_| SecurityError::CryptoError("Malformed sealed value".to_string()))?;
+
+        let mut in_out = decode(&sealed.ciphertext)?;
+        let plaintext = self.aead_key()?
+            .open_in_place(nonce, aead::Aad::from(sealed.aad()), &mut in_out)
+            .map_err(|_| SecurityError::CryptoError("Subject open failed".to_string()))?;
+        let value = serde_json::from_slice(plaintext)
+            .map_err(|e| SecurityError::CryptoError(e.to_string()));
+        in_out.zeroize();
+        value
+    }
+
+    fn aead_key(&self) -> Result<aead::LessSafeKey, SecurityError> {
+        let key = aead::UnboundKey::new(&aead::AES_256_GCM, &self.material)
+            .map_err(|_| SecurityError::CryptoError("Invalid subject key".to_string()))?;
+        Ok(aead::LessSafeKey::new(key))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::observability::integrity::{ChainHead, ForensicChain, CHAIN_GENESIS};
+    use crate::observability::ForensicEnvelope;
+    use crate::security::ClassificationLevel;
+
+    #[test]
+    fn test_shredded_data_unrecoverable_but_chain_verifies() {
+        let chain = ForensicChain::new(b"test-integrity-key");
+        let key = SubjectKey::generate("subject-42").unwrap();
+        let personal = serde_json::json!({"name": "Jane Doe", "email": "jane@example.com"});
+
+        // Personal data reaches the forensic log only sealed, and the chain covers the ciphertext
+        let mut head = ChainHead::genesis();
+        let log: Vec<ForensicEnvelope> = (0..3)
+            .map(|i| {
+                let mut envelope = ForensicEnvelope::new(
+                    Uuid::new_v4(),
+                    "data.event",
+                    "clerk",
+                    Uuid::new_v4(),
+                    ClassificationLevel::Internal,
+                    "update",
+                )
+                .with_state_change(None, Some(key.seal(&personal).unwrap()))
+                .with_metadata(serde_json::json!({"sequence": i}));
+                head.append(&chain, &mut envelope);
+                envelope
+            })
+            .collect();
+        let stored = serde_json::to_string(&log).unwrap();
+        assert!(!stored.contains("jane@example.com"));
+
+        let sealed = SealedValue::from_json(log[1].after_state.as_ref().unwrap()).unwrap();
+        assert_eq!(key.open(&sealed).unwrap(), personal);
+
+        // Erasure destroys the key; the rows themselves are untouched
+        drop(key);
+        let stored: Vec<ForensicEnvelope> = serde_json::from_str(&stored).unwrap();
+
+        // A key issued to the same subject afterwards can't open the old data
+        let reissued = SubjectKey::generate("subject-42").unwrap();
+        assert!(reissued.open(&sealed).is_err());
+        let forged = SubjectKey::from_material("subject-42", sealed.key_id, &[0u8; 32]).unwrap();
+        assert!(forged.open(&sealed).is_err());
+
+        let mut verifier = chain.verifier(CHAIN_GENESIS);
+        assert!(stored.iter().all(|envelope| verifier.push(envelope)));
+        let verification = verifier.finish();
+        assert!(verification.is_intact());
+        assert_eq!(verification.envelopes_verified, 3);
+    }
+}
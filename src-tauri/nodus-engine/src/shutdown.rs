@@ -0,0 +1,347 @@
+// src-tauri/src/shutdown.rs
+// Shutdown Coordinator - Orderly teardown on SIGTERM or window close
+// Stops new operations, waits for in-flight ones, then closes resources in reverse start order
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{Notify, RwLock};
+
+use crate::database::DatabaseManager;
+use crate::observability::{ExportEngine, ForensicLogger};
+
+/// How long shutdown waits for in-flight operations before tearing down anyway
+pub const DEFAULT_DRAIN_TIMEOUT: Duration = Duration::from_secs(10);
+/// Upper bound on each teardown step, so one hung resource can't block exit
+pub const DEFAULT_STEP_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, thiserror::Error)]
+pub enum ShutdownError {
+    #[error("Shutting down; not accepting new operations")]
+    ShuttingDown,
+}
+
+/// A resource with work to finish or connections to close before exit
+#[async_trait]
+pub trait ShutdownHook: Send + Sync {
+    fn name(&self) -> &str;
+
+    async fn shutdown(&self) -> Result<(), String>;
+}
+
+/// Result of one teardown step
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StepOutcome {
+    Completed,
+    Failed(String),
+    TimedOut,
+}
+
+/// What happened during shutdown, steps in teardown order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShutdownReport {
+    /// Whether every in-flight operation finished before the drain timeout
+    pub drained: bool,
+    pub steps: Vec<(String, StepOutcome)>,
+}
+
+impl ShutdownReport {
+    pub fn is_clean(&self) -> bool {
+        self.drained && self.steps.iter().all(|(_, outcome)| *outcome == StepOutcome::Completed)
+    }
+}
+
+/// Tracks in-flight operations and runs registered hooks once on shutdown
+pub struct ShutdownCoordinator {
+    accepting: AtomicBool,
+    in_flight: Arc<InFlight>,
+    // Registration order is start order; teardown walks it backwards
+    hooks: RwLock<Vec<Arc<dyn ShutdownHook>>>,
+    drain_timeout: Duration,
+    step_timeout: Duration,
+    // Holds the report so a second trigger (signal + window close) waits for and reuses it
+    report: tokio::sync::Mutex<Option<ShutdownReport>>,
+}
+
+#[derive(Default)]
+struct InFlight {
+    count: AtomicUsize,
+    idle: Notify,
+}
+
+/// Keeps shutdown waiting until the operation it was issued for is done
+#[must_use = "the operation is only tracked while the guard is alive"]
+pub struct OperationGuard {
+    in_flight: Arc<InFlight>,
+}
+
+impl Drop for OperationGuard {
+    fn drop(&mut self) {
+        if self.in_flight.count.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.in_flight.idle.notify_waiters();
+        }
+    }
+}
+
+impl ShutdownCoordinator {
+    pub fn new() -> Self {
+        Self {
+            accepting: AtomicBool::new(true),
+            in_flight: Arc::new(InFlight::default()),
+            hooks: RwLock::new(Vec::new()),
+            drain_timeout: DEFAULT_DRAIN_TIMEOUT,
+            step_timeout: DEFAULT_STEP_TIMEOUT,
+            report: tokio::sync::Mutex::new(None),
+        }
+    }
+
+    pub fn with_timeouts(mut self, drain_timeout: Duration, step_timeout: Duration) -> Self {
+        self.drain_timeout = drain_timeout;
+        self.step_timeout = step_timeout;
+        self
+    }
+
+    /// Register a resource; call in the order resources are started
+    pub async fn register(&self, hook: Arc<dyn ShutdownHook>) {
+        self.hooks.write().await.push(hook);
+    }
+
+    pub fn is_accepting(&self) -> bool {
+        self.accepting.load(Ordering::SeqCst)
+    }
+
+    /// Start an operation that shutdown must wait for; refused once shutdown has begun
+    pub fn begin_operation(&self) -> Result<OperationGuard, ShutdownError> {
+        self.in_flight.count.fetch_add(1, Ordering::SeqCst);
+        let guard = OperationGuard { in_flight: self.in_flight.clone() };
+        // Checked after counting, so shutdown either sees this operation or we see the flag
+        if !self.is_accepting() {
+            return Err(ShutdownError::ShuttingDown);
+        }
+        Ok(guard)
+    }
+
+    /// Stop accepting operations, drain in-flight ones and run every hook in reverse order
+    ///
+    /// Safe to call more than once; later calls wait for the first and return its report.
+    pub async fn shutdown(&self) -> ShutdownReport {
+        let mut report = self.report.lock().await;
+        if let Some(report) = report.as_ref() {
+            return report.clone();
+        }
+
+        self.accepting.store(false, Ordering::SeqCst);
+        tracing::info!("Shutdown started; waiting for in-flight operations");
+        let drained = tokio::time::timeout(self.drain_timeout, self.wait_idle()).await.is_ok();
+        if !drained {
+            tracing::warn!(
+                "{} operations still running after {}ms; tearing down anyway",
+                self.in_flight.count.load(Ordering::SeqCst),
+                self.drain_timeout.as_millis()
+            );
+        }
+
+        let hooks: Vec<Arc<dyn ShutdownHook>> = self.hooks.read().await.iter().rev().cloned().collect();
+        let mut steps = Vec::with_capacity(hooks.len());
+        for hook in hooks {
+            let outcome = match tokio::time::timeout(self.step_timeout, hook.shutdown()).await {
+                Ok(Ok(())) => StepOutcome::Completed,
+                Ok(Err(e)) => {
+                    tracing::error!("Shutdown of {} failed: {}", hook.name(), e);
+                    StepOutcome::Failed(e)
+                }
+                Err(_) => {
+                    tracing::error!("Shutdown of {} timed out after {}ms", hook.name(), self.step_timeout.as_millis());
+                    StepOutcome::TimedOut
+                }
+            };
+            steps.push((hook.name().to_string(), outcome));
+        }
+
+        let finished = ShutdownReport { drained, steps };
+        tracing::info!("Shutdown complete (clean: {})", finished.is_clean());
+        *report = Some(finished.clone());
+        finished
+    }
+
+    async fn wait_idle(&self) {
+        loop {
+            let idle = self.in_flight.idle.notified();
+            if self.in_flight.count.load(Ordering::SeqCst) == 0 {
+                return;
+            }
+            idle.await;
+        }
+    }
+}
+
+impl std::fmt::Debug for ShutdownCoordinator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // Not blocking on the lock; hooks show as None while one is being registered
+        let hooks: Option<Vec<String>> = self.hooks.try_read().ok()
+            .map(|hooks| hooks.iter().map(|hook| hook.name().to_string()).collect());
+        f.debug_struct("ShutdownCoordinator")
+            .field("accepting", &self.is_accepting())
+            .field("in_flight", &self.in_flight.count.load(Ordering::SeqCst))
+            .field("hooks", &hooks)
+            .finish()
+    }
+}
+
+impl Default for ShutdownCoordinator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Resolves on SIGTERM or Ctrl-C
+pub async fn termination_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+        match signal(SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                tokio::select! {
+                    _ = sigterm.recv() => {}
+                    _ = tokio::signal::ctrl_c() => {}
+                }
+            }
+            Err(e) => {
+                tracing::warn!("Cannot listen for SIGTERM: {}", e);
+                let _ = tokio::signal::ctrl_c().await;
+            }
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+    }
+}
+
+#[async_trait]
+impl ShutdownHook for ExportEngine {
+    fn name(&self) -> &str {
+        "exporters"
+    }
+
+    /// Export queued records and flush buffered batches, then make sure the dead-letter file
+    /// has every record that failed
+    async fn shutdown(&self) -> Result<(), String> {
+        self.drain().await;
+        let flushed = self.flush().await.map_err(|e| e.to_string());
+        if let Some(queue) = self.dead_letter_queue() {
+            queue.sync().await.map_err(|e| e.to_string())?;
+        }
+        flushed
+    }
+}
+
+#[async_trait]
+impl ShutdownHook for ForensicLogger {
+    fn name(&self) -> &str {
+        "forensic_logger"
+    }
+
+    async fn shutdown(&self) -> Result<(), String> {
+        self.flush().await.map_err(|e| e.to_string())
+    }
+}
+
+#[async_trait]
+impl ShutdownHook for DatabaseManager {
+    fn name(&self) -> &str {
+        "database"
+    }
+
+    async fn shutdown(&self) -> Result<(), String> {
+        self.close().await;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::observability::exporters::{Compression, JsonFileExporter, ObservabilityExporter};
+    use crate::observability::observation::{ObservationContext, OperationResult};
+    use crate::observability::ObservationRecord;
+
+    /// Hook that records its name when run
+    struct RecordingHook {
+        name: &'static str,
+        order: Arc<std::sync::Mutex<Vec<&'static str>>>,
+    }
+
+    #[async_trait]
+    impl ShutdownHook for RecordingHook {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        async fn shutdown(&self) -> Result<(), String> {
+            self.order.lock().unwrap().push(self.name);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_buffered_batches_flushed_on_shutdown() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("export.jsonl.gz");
+        let exporter = JsonFileExporter::new(path.to_string_lossy().to_string())
+            .with_compression(Compression::Gzip);
+        let exporter: Arc<dyn ObservabilityExporter> = Arc::new(exporter);
+        let engine = Arc::new(ExportEngine::new(vec![exporter]));
+
+        // Fewer records than the batch size, so everything is still buffered
+        for i in 0..3 {
+            let record = ObservationRecord::new(
+                &format!("operation_{}", i),
+                ObservationContext::default(),
+                OperationResult::Success { return_value: None },
+            );
+            assert!(engine.export(&record).await.is_success());
+        }
+        assert!(!path.exists());
+
+        let coordinator = ShutdownCoordinator::new();
+        coordinator.register(engine).await;
+        let report = coordinator.shutdown().await;
+        assert!(report.is_clean());
+
+        let compressed = std::fs::read(&path).unwrap();
+        let decompressed = Compression::Gzip.decompress(&compressed).unwrap();
+        assert_eq!(String::from_utf8(decompressed).unwrap().lines().count(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_drains_in_flight_then_tears_down_in_reverse() {
+        let order = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let coordinator = Arc::new(ShutdownCoordinator::new());
+        for name in ["database", "forensic_logger", "exporters"] {
+            coordinator.register(Arc::new(RecordingHook { name, order: order.clone() })).await;
+        }
+
+        let operation = coordinator.begin_operation().unwrap();
+        let shutdown = tokio::spawn({
+            let coordinator = coordinator.clone();
+            async move { coordinator.shutdown().await }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        // New work is refused while the in-flight operation holds teardown back
+        assert!(matches!(coordinator.begin_operation(), Err(ShutdownError::ShuttingDown)));
+        assert!(order.lock().unwrap().is_empty());
+
+        drop(operation);
+        let report = shutdown.await.unwrap();
+        assert!(report.is_clean());
+        assert_eq!(*order.lock().unwrap(), vec!["exporters", "forensic_logger", "database"]);
+
+        // A second trigger reuses the first result without rerunning hooks
+        coordinator.shutdown().await;
+        assert_eq!(order.lock().unwrap().len(), 3);
+    }
+}
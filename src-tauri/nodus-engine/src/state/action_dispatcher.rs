@@ -534,7 +534,7 @@ impl ActionValidator {
         
         if let Some(policy) = policies.get(&action.action_type) {
             // Check clearance level
-            if context.security_label.level.rank() < policy.required_clearance.rank() {
+            if !context.security_label.level.dominates(&policy.required_clearance) {
                 return Err(ActionError::InsufficientClearance {
                     required: policy.required_clearance.clone(),
                     user_level: context.security_label.level.clone(),
@@ -695,6 +695,9 @@ pub enum ActionError {
     
     #[error("Action execution failed: {0}")]
     ExecutionFailed(String),
+    
+    #[error(transparent)]
+    Instrumentation(#[from] crate::observability::InstrumentationError),
 }
 
 #[cfg(test)]
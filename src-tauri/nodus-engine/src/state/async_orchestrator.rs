@@ -767,6 +767,16 @@ pub enum OrchestrationError {
     ExecutionFailed(String),
 }
 
+impl From<crate::observability::InstrumentationError> for OrchestrationError {
+    fn from(error: crate::observability::InstrumentationError) -> Self {
+        match error {
+            crate::observability::InstrumentationError::CircuitOpen(open) => Self::CircuitBreakerOpen(open.operation),
+            crate::observability::InstrumentationError::Qos(qos) => Self::ResourceLimitExceeded(qos.to_string()),
+            audit => Self::ExecutionFailed(audit.to_string()),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -8,10 +8,12 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
-use crate::database::DatabaseManager;
+use crate::clock::{self, Clock};
+use crate::database::{DatabaseManager, DeclassificationWorkflow};
 use crate::license::LicenseManager;
-use crate::observability::{ActionDispatcher, ForensicLogger, MetricsRegistry};
-use crate::security::{ClassificationLevel, SecurityLabel, SecurityManager};
+use crate::multi_tenant::SessionConfig;
+use crate::observability::{ActionDispatcher, AutomaticInstrumentation, ExportEngine, ForensicLogger, MetricsRegistry, QosScheduler};
+use crate::security::{ClassificationLevel, InformationFlowTracker, RoleDefinitions, SecurityContext, SecurityError, SecurityEvent, SecurityLabel, SecurityManager};
 
 /// Core application state (replaces HybridStateManager.js)
 #[derive(Debug)]
@@ -23,14 +25,28 @@ pub struct AppState {
     pub forensic_logger: std::sync::Arc<ForensicLogger>,
     pub action_dispatcher: std::sync::Arc<ActionDispatcher>,
     pub license_manager: std::sync::Arc<LicenseManager>,
+    // Exporters receiving observation records from instrumented commands, when configured
+    pub export_engine: Option<std::sync::Arc<ExportEngine>>,
+    // Instrumentation shared by every command, so decisions and budgets are tracked in one place
+    pub automatic_instrumentation: std::sync::Arc<AutomaticInstrumentation>,
+    // Per-class concurrency shared by instrumentation and the network transport
+    pub qos_scheduler: std::sync::Arc<QosScheduler>,
+    // Taint of what each in-flight operation has read, checked before it writes
+    pub information_flow: std::sync::Arc<InformationFlowTracker>,
+    // Two-person downgrades, with pending requests kept in the database
+    pub declassification: std::sync::Arc<DeclassificationWorkflow>,
     // Global/system-level observability context used as a convenient default by many modules
     pub context: crate::observability::ObservabilityContext,
 
     // Application state
     pub user_contexts: RwLock<HashMap<String, UserContext>>,
+    /// Role grants applied to each user context as it is set
+    pub role_definitions: RwLock<RoleDefinitions>,
     pub active_sessions: RwLock<HashMap<Uuid, SessionState>>,
     pub system_config: RwLock<SystemConfig>,
     pub initialized: bool,
+    /// Time source for session and user context expiry, and for commands checking deadlines
+    pub(crate) clock: Arc<dyn Clock>,
 }
 
 /// User context for security decisions (replaces JS SecurityContext)
@@ -41,9 +57,24 @@ pub struct UserContext {
     pub compartments: Vec<String>,
     pub session_id: Uuid,
     pub login_time: chrono::DateTime<chrono::Utc>,
+    /// Effective permissions; `set_user_context` adds those granted by `roles`
     pub permissions: Vec<String>,
+    #[serde(default)]
+    pub roles: Vec<String>,
+    /// Clearance lapses at this instant; contexts stored without one expire immediately
+    #[serde(default = "chrono::Utc::now")]
+    pub expires: chrono::DateTime<chrono::Utc>,
+    /// Tenant the context is scoped to; `None` for system-wide users
+    #[serde(default)]
+    pub tenant_id: Option<String>,
 }
 
+/// Lifetime of a newly established user context
+pub const USER_CONTEXT_TTL_HOURS: i64 = 8;
+
+/// How often sessions past their idle or absolute timeout are swept
+pub const DEFAULT_SESSION_SWEEP_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
 /// Session state tracking (replaces JS session management)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SessionState {
@@ -55,6 +86,34 @@ pub struct SessionState {
     pub workspace_data: serde_json::Value,
 }
 
+/// Why a session stopped being valid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SessionExpiry {
+    /// No validated operation within `idle_timeout_minutes`
+    IdleTimeout,
+    /// Older than `session_timeout_minutes`, regardless of activity
+    AbsoluteTimeout,
+}
+
+/// Session validation errors
+#[derive(Debug, thiserror::Error)]
+pub enum SessionError {
+    #[error("Session not found: {0}")]
+    NotFound(Uuid),
+
+    #[error("Session inactive: {0}")]
+    Inactive(Uuid),
+
+    #[error("Session {0} expired: {1:?}")]
+    Expired(Uuid, SessionExpiry),
+
+    #[error("Concurrent session limit reached: {0} active")]
+    LimitExceeded(u32),
+
+    #[error("Session audit failed: {0}")]
+    AuditFailed(String),
+}
+
 /// System configuration (replaces JS config management)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SystemConfig {
@@ -62,6 +121,8 @@ pub struct SystemConfig {
     pub observability_level: ObservabilityLevel,
     pub license_tier: LicenseTier,
     pub security_settings: SecuritySettings,
+    #[serde(default)]
+    pub session_config: SessionConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -105,32 +166,89 @@ impl AppState {
         action_dispatcher: std::sync::Arc<ActionDispatcher>,
         license_manager: std::sync::Arc<LicenseManager>,
     ) -> Self {
+        let declassification = std::sync::Arc::new(DeclassificationWorkflow::new(db_manager.clone()));
         Self {
             security_manager,
             db_manager,
             metrics_registry,
             forensic_logger,
             action_dispatcher,
+            automatic_instrumentation: std::sync::Arc::new(AutomaticInstrumentation::new(license_manager.clone())),
+            qos_scheduler: std::sync::Arc::new(QosScheduler::default()),
             license_manager,
+            export_engine: None,
+            information_flow: std::sync::Arc::new(InformationFlowTracker::new()),
+            declassification,
             context: crate::observability::ObservabilityContext::new(
                 "system", "startup", ClassificationLevel::Internal, "system", uuid::Uuid::new_v4()
             ),
             user_contexts: RwLock::new(HashMap::new()),
+            role_definitions: RwLock::new(RoleDefinitions::default()),
             active_sessions: RwLock::new(HashMap::new()),
             system_config: RwLock::new(SystemConfig::default()),
             initialized: false,
+            clock: clock::system_clock(),
         }
     }
 
+    /// Read time from `clock` instead of the system clock for session and context expiry
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Send observation records from instrumented commands to `engine`
+    pub fn with_export_engine(mut self, engine: std::sync::Arc<ExportEngine>) -> Self {
+        self.export_engine = Some(engine);
+        self
+    }
+
+    /// Instrument commands with `instrumentation` instead of a bare license-gated instance
+    pub fn with_automatic_instrumentation(mut self, instrumentation: std::sync::Arc<AutomaticInstrumentation>) -> Self {
+        self.automatic_instrumentation = instrumentation;
+        self
+    }
+
+    /// Schedule work in `scheduler`, the one instrumentation and the transport were built with
+    pub fn with_qos_scheduler(mut self, scheduler: std::sync::Arc<QosScheduler>) -> Self {
+        self.qos_scheduler = scheduler;
+        self
+    }
+
+    /// Replace the role definitions; contexts set from now on get the new grants
+    pub async fn set_role_definitions(&self, role_definitions: RoleDefinitions) -> Result<(), String> {
+        let mut roles: Vec<String> = role_definitions.role_names().map(str::to_string).collect();
+        roles.sort();
+        self.forensic_logger
+            .log_security_event(
+                "security.roles.updated",
+                &format!("Role definitions replaced: {}", roles.join(", ")),
+                "system",
+            )
+            .await
+            .map_err(|e| format!("Failed to log security event: {}", e))?;
+
+        *self.role_definitions.write().await = role_definitions;
+        Ok(())
+    }
+
     /// Set user context for security decisions (replaces JS setUserContext)
-    pub async fn set_user_context(&self, user_context: UserContext) -> Result<(), String> {
+    ///
+    /// The context's roles are resolved against the current role definitions here, so a
+    /// change to the definitions reaches a user the next time their context is set.
+    pub async fn set_user_context(&self, mut user_context: UserContext) -> Result<(), String> {
+        user_context.apply_roles(&*self.role_definitions.read().await);
+
         // Security audit for context change
         self.forensic_logger
             .log_security_event(
                 "user.context.set",
                 &format!(
-                    "User {} context established with clearance {:?}",
-                    user_context.user_id, user_context.clearance_level
+                    "User {} context established with clearance {:?}, roles [{}], permissions [{}]",
+                    user_context.user_id,
+                    user_context.clearance_level,
+                    user_context.roles.join(", "),
+                    user_context.permissions.join(", "),
                 ),
                 &user_context.user_id,
             )
@@ -144,20 +262,53 @@ impl AppState {
         Ok(())
     }
 
-    /// Get user context (replaces JS getUserContext)
+    /// Get user context (replaces JS getUserContext); expired contexts are treated as absent
     pub async fn get_user_context(&self, user_id: &str) -> Option<UserContext> {
-        let contexts = self.user_contexts.read().await;
-        contexts.get(user_id).cloned()
+        self.get_valid_user_context(user_id).await.ok()
+    }
+
+    /// Get an unexpired user context
+    ///
+    /// An expired context is cleared, audited as a `SecurityEvent::ContextExpired` and reported as
+    /// `SecurityError::ContextExpired` so command handlers can deny the operation. A missing
+    /// context is refused the same way, so the clearance stays denied after the expired one
+    /// has been cleared.
+    pub async fn get_valid_user_context(&self, user_id: &str) -> Result<UserContext, SecurityError> {
+        let expired = match take_valid_context(&self.user_contexts, user_id, self.clock.now()).await {
+            Ok(context) => return Ok(context),
+            Err(expired) => expired,
+        };
+
+        if let Some(context) = expired {
+            let event = context.expired_event();
+            let logged = match serde_json::to_string(&event) {
+                Ok(description) => self.forensic_logger
+                    .log_security_event("ContextExpired", &description, &context.user_id)
+                    .await
+                    .map_err(|e| e.to_string()),
+                Err(e) => Err(e.to_string()),
+            };
+            if let Err(e) = logged {
+                tracing::error!("Failed to log context expiry: {}", e);
+            }
+        }
+
+        Err(SecurityError::ContextExpired)
     }
 
     /// Create new session (replaces JS session management)
+    ///
+    /// Rejected with `SessionError::LimitExceeded` once the license's concurrent-session
+    /// cap is reached; only live sessions count, so ones past their timeouts free their
+    /// slot even before the sweeper has closed them.
     pub async fn create_session(
         &self,
         user_id: String,
         security_label: SecurityLabel,
-    ) -> Result<Uuid, String> {
+    ) -> Result<Uuid, SessionError> {
         let session_id = Uuid::new_v4();
-        let now = chrono::Utc::now();
+        let now = self.clock.now();
+        let config = self.system_config.read().await.session_config.clone();
 
         let session = SessionState {
             user_id: user_id.clone(),
@@ -168,23 +319,141 @@ impl AppState {
             workspace_data: serde_json::Value::Object(serde_json::Map::new()),
         };
 
-        // Log session creation
-        self.forensic_logger
+        // Count and insert under one lock so concurrent logins can't both take the last slot
+        {
+            let mut sessions = self.active_sessions.write().await;
+            check_session_capacity(&self.license_manager, &sessions, &config, now).await?;
+            sessions.insert(session_id, session);
+        }
+
+        // Log session creation; a session that can't be audited doesn't stand
+        if let Err(e) = self.forensic_logger
             .log_security_event(
                 "session.create",
                 &format!("Session {} created for user {}", session_id, user_id),
                 &user_id,
             )
             .await
-            .map_err(|e| format!("Failed to log session creation: {}", e))?;
-
-        // Store session
-        let mut sessions = self.active_sessions.write().await;
-        sessions.insert(session_id, session);
+        {
+            self.active_sessions.write().await.remove(&session_id);
+            return Err(SessionError::AuditFailed(e.to_string()));
+        }
 
         Ok(session_id)
     }
 
+    /// Validate a session for a command handler, touching `last_activity` on success
+    ///
+    /// A session past its idle or absolute timeout is marked inactive and a
+    /// `session.expired` forensic event is recorded.
+    pub async fn validate_session(&self, session_id: Uuid) -> Result<(), SessionError> {
+        let now = self.clock.now();
+        let config = self.system_config.read().await.session_config.clone();
+
+        let (user_id, reason) = {
+            let mut sessions = self.active_sessions.write().await;
+            let session = sessions.get_mut(&session_id)
+                .ok_or(SessionError::NotFound(session_id))?;
+
+            if !session.is_active {
+                return Err(SessionError::Inactive(session_id));
+            }
+
+            match session.expiry_at(&config, now) {
+                Some(reason) => {
+                    session.is_active = false;
+                    (session.user_id.clone(), reason)
+                }
+                None => {
+                    session.last_activity = now;
+                    return Ok(());
+                }
+            }
+        };
+
+        self.log_session_expired(session_id, &user_id, reason).await;
+        Err(SessionError::Expired(session_id, reason))
+    }
+
+    /// End `session_id`, freeing its slot; false if it wasn't open
+    pub async fn end_session(&self, session_id: Uuid) -> bool {
+        self.active_sessions.write().await.remove(&session_id).is_some()
+    }
+
+    /// Security context of `session_id` for a command handler, once `validate_session` passes
+    pub async fn session_context(&self, session_id: Uuid) -> Result<SecurityContext, SessionError> {
+        self.validate_session(session_id).await?;
+        self.security_manager.get_security_context(session_id).await
+            .ok_or(SessionError::NotFound(session_id))
+    }
+
+    /// Whether `session_id` is active and within its timeouts, without counting as activity
+    pub async fn session_is_live(&self, session_id: Uuid) -> bool {
+        let now = self.clock.now();
+        let config = self.system_config.read().await.session_config.clone();
+        self.active_sessions.read().await.get(&session_id)
+            .is_some_and(|session| session.is_active && session.expiry_at(&config, now).is_none())
+    }
+
+    /// Mark every session past its idle or absolute timeout inactive; returns how many expired
+    ///
+    /// Sessions closed by an earlier sweep are dropped, so callers still see why they were closed
+    /// for one sweep interval.
+    pub async fn expire_sessions(&self) -> usize {
+        let now = self.clock.now();
+        let config = self.system_config.read().await.session_config.clone();
+
+        let expired: Vec<(Uuid, String, SessionExpiry)> = {
+            let mut sessions = self.active_sessions.write().await;
+            sessions.retain(|_, session| session.is_active);
+            sessions.iter_mut()
+                .filter(|(_, session)| session.is_active)
+                .filter_map(|(session_id, session)| {
+                    session.expiry_at(&config, now).map(|reason| {
+                        session.is_active = false;
+                        (*session_id, session.user_id.clone(), reason)
+                    })
+                })
+                .collect()
+        };
+
+        for (session_id, user_id, reason) in &expired {
+            self.log_session_expired(*session_id, user_id, *reason).await;
+        }
+
+        expired.len()
+    }
+
+    /// Spawn a background task that sweeps expired sessions on a fixed interval
+    pub fn spawn_session_sweeper(self: Arc<Self>, interval: std::time::Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+
+            loop {
+                ticker.tick().await;
+
+                let expired = self.expire_sessions().await;
+                if expired > 0 {
+                    crate::security_log!(info, expired, "Expired idle or timed-out sessions");
+                }
+            }
+        })
+    }
+
+    async fn log_session_expired(&self, session_id: Uuid, user_id: &str, reason: SessionExpiry) {
+        // Expiry stands even if the audit write fails
+        if let Err(e) = self.forensic_logger
+            .log_security_event(
+                "session.expired",
+                &format!("Session {} for user {} expired: {:?}", session_id, user_id, reason),
+                user_id,
+            )
+            .await
+        {
+            tracing::error!("Failed to log session expiry: {}", e);
+        }
+    }
+
     /// Update system configuration (replaces JS config updates)
     pub async fn update_system_config<F>(&self, updater: F) -> Result<(), String>
     where
@@ -230,8 +499,72 @@ impl Default for SystemConfig {
                 require_signed_plugins: false,
                 enable_classification_crypto: false,
             },
+            session_config: SessionConfig::default(),
+        }
+    }
+}
+
+/// Check that one more session fits under the license's `concurrent_sessions` limit at `now`
+async fn check_session_capacity(
+    license_manager: &LicenseManager,
+    sessions: &HashMap<Uuid, SessionState>,
+    config: &SessionConfig,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Result<(), SessionError> {
+    let active = sessions.values()
+        .filter(|session| session.is_active && session.expiry_at(config, now).is_none())
+        .count() as u32;
+
+    if license_manager.check_limit("concurrent_sessions", active + 1).await {
+        Ok(())
+    } else {
+        Err(SessionError::LimitExceeded(active))
+    }
+}
+
+impl SessionState {
+    /// Expiry reason at `now` under `config`, or `None` if the session is still valid
+    pub fn expiry_at(
+        &self,
+        config: &SessionConfig,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Option<SessionExpiry> {
+        let session_timeout = chrono::Duration::minutes(config.session_timeout_minutes as i64);
+        let idle_timeout = chrono::Duration::minutes(config.idle_timeout_minutes as i64);
+
+        if now - self.created_at >= session_timeout {
+            Some(SessionExpiry::AbsoluteTimeout)
+        } else if now - self.last_activity >= idle_timeout {
+            Some(SessionExpiry::IdleTimeout)
+        } else {
+            None
+        }
+    }
+}
+
+/// `user_id`'s context if it's still valid at `now`; otherwise the context this call found
+/// expired and removed, or None when there was none to remove
+async fn take_valid_context(
+    contexts: &RwLock<HashMap<String, UserContext>>,
+    user_id: &str,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Result<UserContext, Option<UserContext>> {
+    {
+        let contexts = contexts.read().await;
+        match contexts.get(user_id) {
+            None => return Err(None),
+            Some(context) if context.is_valid_at(now) => return Ok(context.clone()),
+            Some(_) => {}
         }
     }
+
+    let mut contexts = contexts.write().await;
+    match contexts.get(user_id) {
+        Some(context) if !context.is_valid_at(now) => Err(contexts.remove(user_id)),
+        // Refreshed or cleared while we waited for the write lock
+        Some(context) => Ok(context.clone()),
+        None => Err(None),
+    }
 }
 
 impl UserContext {
@@ -242,13 +575,57 @@ impl UserContext {
         compartments: Vec<String>,
         permissions: Vec<String>,
     ) -> Self {
+        let login_time = chrono::Utc::now();
+
         Self {
             user_id,
             clearance_level,
             compartments,
             session_id: Uuid::new_v4(),
-            login_time: chrono::Utc::now(),
+            login_time,
             permissions,
+            roles: Vec::new(),
+            expires: login_time + chrono::Duration::hours(USER_CONTEXT_TTL_HOURS),
+            tenant_id: None,
+        }
+    }
+
+    pub fn with_roles(mut self, roles: Vec<String>) -> Self {
+        self.roles = roles;
+        self
+    }
+
+    /// Scope the context to `tenant_id`
+    pub fn with_tenant(mut self, tenant_id: &str) -> Self {
+        self.tenant_id = Some(tenant_id.to_string());
+        self
+    }
+
+    /// Add the permissions `role_definitions` grant through this context's roles
+    pub fn apply_roles(&mut self, role_definitions: &RoleDefinitions) {
+        for permission in role_definitions.permissions_for(&self.roles) {
+            if !self.permissions.iter().any(|held| held == permission.as_str()) {
+                self.permissions.push(permission.as_str().to_string());
+            }
+        }
+    }
+
+    /// Whether the context's clearance is still in force
+    pub fn is_valid(&self) -> bool {
+        self.is_valid_at(chrono::Utc::now())
+    }
+
+    /// Whether the context's clearance is in force at `now`
+    pub fn is_valid_at(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        now < self.expires
+    }
+
+    /// Security event recording that this context's clearance lapsed
+    pub fn expired_event(&self) -> SecurityEvent {
+        SecurityEvent::ContextExpired {
+            user_id: self.user_id.clone(),
+            level: self.clearance_level.clone(),
+            expired_at: self.expires,
         }
     }
 
@@ -261,7 +638,7 @@ impl UserContext {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::security::ClassificationLevel;
+    use crate::security::{has_permission, ClassificationLevel, Permission};
 
     #[tokio::test]
     async fn test_user_context_management() {
@@ -293,4 +670,167 @@ mod tests {
         assert_eq!(security_label.level, ClassificationLevel::Confidential);
         assert_eq!(security_label.compartments.len(), 2);
     }
+
+    #[test]
+    fn test_session_idle_expiry_with_advanced_clock() {
+        let config = SessionConfig {
+            session_timeout_minutes: 480,
+            concurrent_session_limit: 5,
+            idle_timeout_minutes: 30,
+        };
+        let clock = crate::clock::MockClock::default();
+        let minutes = |n: u64| std::time::Duration::from_secs(n * 60);
+        let start = clock.now();
+        let mut session = SessionState {
+            user_id: "test-user".to_string(),
+            created_at: start,
+            last_activity: start,
+            security_label: SecurityLabel::new(ClassificationLevel::Internal, vec![]),
+            is_active: true,
+            workspace_data: serde_json::Value::Null,
+        };
+
+        // Just inside, then just past the idle timeout
+        clock.advance(minutes(29));
+        assert_eq!(session.expiry_at(&config, clock.now()), None);
+        clock.advance(minutes(2));
+        assert_eq!(session.expiry_at(&config, clock.now()), Some(SessionExpiry::IdleTimeout));
+
+        // Activity resets the idle window but not the absolute timeout
+        session.last_activity = start + chrono::Duration::minutes(470);
+        clock.advance(minutes(444));
+        assert_eq!(session.expiry_at(&config, clock.now()), None);
+        clock.advance(minutes(6));
+        assert_eq!(session.expiry_at(&config, clock.now()), Some(SessionExpiry::AbsoluteTimeout));
+    }
+
+    fn sessions_with(active: usize, inactive: usize) -> HashMap<Uuid, SessionState> {
+        let now = chrono::Utc::now();
+        (0..active + inactive)
+            .map(|i| {
+                (Uuid::new_v4(), SessionState {
+                    user_id: format!("user-{}", i),
+                    created_at: now,
+                    last_activity: now,
+                    security_label: SecurityLabel::new(ClassificationLevel::Internal, vec![]),
+                    is_active: i < active,
+                    workspace_data: serde_json::Value::Null,
+                })
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_session_capacity_at_community_limit() {
+        // No license file or env var: community tier with a 3-session cap
+        let license_manager = LicenseManager::new().await.unwrap();
+        let config = SessionConfig { session_timeout_minutes: 480, concurrent_session_limit: 5, idle_timeout_minutes: 30 };
+        let now = chrono::Utc::now();
+
+        // Two active plus expired sessions: the third fits exactly at the limit
+        assert!(check_session_capacity(&license_manager, &sessions_with(2, 4), &config, now).await.is_ok());
+
+        // Three active: one more would be over
+        assert!(matches!(
+            check_session_capacity(&license_manager, &sessions_with(3, 0), &config, now).await,
+            Err(SessionError::LimitExceeded(3))
+        ));
+
+        // Still marked active but idle past the timeout, which the sweeper hasn't reached yet
+        let mut stale = sessions_with(3, 0);
+        if let Some(session) = stale.values_mut().next() {
+            session.last_activity = now - chrono::Duration::minutes(31);
+        }
+        assert!(check_session_capacity(&license_manager, &stale, &config, now).await.is_ok());
+    }
+
+    #[test]
+    fn test_user_context_expiry() {
+        let mut user_context = UserContext::new(
+            "test-user".to_string(),
+            ClassificationLevel::Secret,
+            vec![],
+            vec!["read".to_string()],
+        );
+        assert!(user_context.is_valid());
+        assert!(!user_context.is_valid_at(user_context.expires));
+
+        user_context.expires = chrono::Utc::now() - chrono::Duration::minutes(1);
+        assert!(!user_context.is_valid());
+        assert!(matches!(
+            user_context.expired_event(),
+            SecurityEvent::ContextExpired { user_id, level: ClassificationLevel::Secret, expired_at }
+                if user_id == "test-user" && expired_at == user_context.expires
+        ));
+
+        // Contexts persisted before expiry tracking fail closed
+        let mut legacy = serde_json::to_value(&user_context).unwrap();
+        legacy.as_object_mut().unwrap().remove("expires");
+        let legacy: UserContext = serde_json::from_value(legacy).unwrap();
+        assert!(!legacy.is_valid());
+    }
+
+    #[tokio::test]
+    async fn test_expired_context_stays_denied() {
+        let mut user_context = UserContext::new(
+            "test-user".to_string(),
+            ClassificationLevel::Secret,
+            vec![],
+            vec!["read".to_string()],
+        );
+        let now = user_context.login_time;
+        let contexts = RwLock::new(HashMap::from([("test-user".to_string(), user_context.clone())]));
+        assert!(take_valid_context(&contexts, "test-user", now).await.is_ok());
+
+        // The first call after expiry clears the context and reports it for auditing
+        user_context.expires = now;
+        contexts.write().await.insert("test-user".to_string(), user_context);
+        assert!(matches!(
+            take_valid_context(&contexts, "test-user", now).await,
+            Err(Some(expired)) if expired.user_id == "test-user"
+        ));
+        // Later calls find nothing and are denied too
+        assert!(matches!(take_valid_context(&contexts, "test-user", now).await, Err(None)));
+        assert!(contexts.read().await.is_empty());
+    }
+
+    fn roles(toml: &str) -> RoleDefinitions {
+        RoleDefinitions::from_toml(toml).unwrap()
+    }
+
+    fn analyst_login() -> UserContext {
+        UserContext::new("analyst-1".to_string(), ClassificationLevel::Secret, vec![], vec![])
+            .with_roles(vec!["analyst".to_string()])
+    }
+
+    #[test]
+    fn test_role_lacking_permission_denied() {
+        let definitions = roles("[[roles]]\nname = \"analyst\"\npermissions = [\"read\", \"write\"]");
+        let mut user_context = analyst_login();
+        user_context.apply_roles(&definitions);
+
+        assert!(has_permission(&user_context, Permission::Write));
+        assert!(!has_permission(&user_context, Permission::Relabel));
+        assert!(!has_permission(&user_context, Permission::TenantAdmin));
+    }
+
+    #[test]
+    fn test_role_change_applies_on_next_context_set() {
+        let before = roles("[[roles]]\nname = \"analyst\"\npermissions = [\"read\"]");
+        let mut current = analyst_login();
+        current.apply_roles(&before);
+        assert!(!has_permission(&current, Permission::Relabel));
+
+        // The grant reaches the user once their context is set again
+        let after = roles("[[roles]]\nname = \"analyst\"\npermissions = [\"read\", \"relabel\"]");
+        assert!(!has_permission(&current, Permission::Relabel));
+        let mut next = analyst_login();
+        next.apply_roles(&after);
+        assert!(has_permission(&next, Permission::Relabel));
+
+        // Revocations too
+        let mut revoked = analyst_login();
+        revoked.apply_roles(&roles("[[roles]]\nname = \"analyst\"\npermissions = []"));
+        assert!(!has_permission(&revoked, Permission::Read));
+    }
 }
@@ -0,0 +1,562 @@
+// src/sync/conflict_resolution.rs
+// Conflict Resolution - Merges revisions of one entity edited concurrently on different devices
+// Strategies are chosen per entity type; whatever the strategy, the merged label is never lowered
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Debug;
+use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::SyncError;
+use crate::database::SecureEntity;
+use crate::security::SecurityLabel;
+
+/// Per-replica edit counters, keyed by client id
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionVector(BTreeMap<String, u64>);
+
+/// How two version vectors are ordered
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Causality {
+    Equal,
+    /// Every edit in this vector was seen by the other
+    Before,
+    /// This vector has seen every edit in the other
+    After,
+    Concurrent,
+}
+
+impl VersionVector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set `replica`'s counter
+    pub fn with_entry(mut self, replica: impl Into<String>, counter: u64) -> Self {
+        self.0.insert(replica.into(), counter);
+        self
+    }
+
+    pub fn get(&self, replica: &str) -> u64 {
+        self.0.get(replica).copied().unwrap_or(0)
+    }
+
+    /// Record one more edit by `replica`
+    pub fn increment(&mut self, replica: &str) {
+        *self.0.entry(replica.to_string()).or_insert(0) += 1;
+    }
+
+    pub fn compare(&self, other: &VersionVector) -> Causality {
+        let replicas: BTreeSet<&String> = self.0.keys().chain(other.0.keys()).collect();
+        let (mut ahead, mut behind) = (false, false);
+        for replica in replicas {
+            match self.get(replica).cmp(&other.get(replica)) {
+                Ordering::Greater => ahead = true,
+                Ordering::Less => behind = true,
+                Ordering::Equal => {}
+            }
+        }
+        match (ahead, behind) {
+            (false, false) => Causality::Equal,
+            (true, false) => Causality::After,
+            (false, true) => Causality::Before,
+            (true, true) => Causality::Concurrent,
+        }
+    }
+
+    /// Pointwise maximum: the vector of a revision that has seen both
+    pub fn merged(&self, other: &VersionVector) -> VersionVector {
+        let mut merged = self.clone();
+        for (replica, &counter) in &other.0 {
+            let entry = merged.0.entry(replica.clone()).or_insert(0);
+            *entry = (*entry).max(counter);
+        }
+        merged
+    }
+}
+
+/// One side of a conflict: an entity state and the version vector it was written at
+#[derive(Debug, Clone)]
+pub struct EntityRevision {
+    pub entity: SecureEntity,
+    pub vector: VersionVector,
+}
+
+/// Two revisions of the same entity, as held locally and as received from the server
+#[derive(Debug, Clone)]
+pub struct EntityConflict {
+    /// Last revision both sides share, if known; without it field-level merge can't tell
+    /// an edit from an untouched field, and every difference is a conflict
+    pub base: Option<SecureEntity>,
+    pub local: EntityRevision,
+    pub remote: EntityRevision,
+}
+
+impl EntityConflict {
+    /// Side written last, by `updated_at`
+    ///
+    /// Ties are broken on version, then writer, so every device picks the same side.
+    pub fn later_side(&self) -> ConflictSide {
+        let order = |entity: &SecureEntity| (entity.updated_at, entity.version, entity.updated_by.clone());
+        if order(&self.remote.entity) > order(&self.local.entity) {
+            ConflictSide::Remote
+        } else {
+            ConflictSide::Local
+        }
+    }
+
+    pub fn side(&self, side: ConflictSide) -> &EntityRevision {
+        match side {
+            ConflictSide::Local => &self.local,
+            ConflictSide::Remote => &self.remote,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflictSide {
+    Local,
+    Remote,
+}
+
+/// A data field the two sides disagree on
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FieldConflict {
+    /// Dotted path into `data`, as in query filters; `data` when the values aren't objects
+    pub field: String,
+    pub local: Option<Value>,
+    pub remote: Option<Value>,
+    /// Side whose value the merged entity holds
+    pub kept: ConflictSide,
+}
+
+/// Merged revision and the fields that had to be decided one way or the other
+#[derive(Debug, Clone)]
+pub struct ConflictResolution {
+    pub entity: SecureEntity,
+    pub vector: VersionVector,
+    pub conflicts: Vec<FieldConflict>,
+}
+
+impl ConflictResolution {
+    /// Take `side` unchanged, with no conflicts
+    fn keep(conflict: &EntityConflict, side: ConflictSide) -> Self {
+        Self {
+            entity: conflict.side(side).entity.clone(),
+            vector: conflict.local.vector.merged(&conflict.remote.vector),
+            conflicts: Vec::new(),
+        }
+    }
+
+    pub fn conflicting_fields(&self) -> Vec<&str> {
+        self.conflicts.iter().map(|conflict| conflict.field.as_str()).collect()
+    }
+}
+
+/// A way of merging conflicting revisions of an entity
+///
+/// The resolver raises the result's label afterwards, so strategies only decide the data.
+pub trait ConflictStrategy: Debug + Send + Sync {
+    fn resolve(&self, conflict: &EntityConflict) -> Result<ConflictResolution, SyncError>;
+}
+
+/// The revision written last wins whole; every field it overrides is reported
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LastWriterWins;
+
+impl ConflictStrategy for LastWriterWins {
+    fn resolve(&self, conflict: &EntityConflict) -> Result<ConflictResolution, SyncError> {
+        let kept = conflict.later_side();
+        let mut conflicts = Vec::new();
+        collect_differences(
+            "",
+            Some(&conflict.local.entity.data),
+            Some(&conflict.remote.entity.data),
+            kept,
+            &mut conflicts,
+        );
+        Ok(ConflictResolution { conflicts, ..ConflictResolution::keep(conflict, kept) })
+    }
+}
+
+/// The revision written first wins whole; every field the later one changed is reported
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FirstWriterWins;
+
+impl ConflictStrategy for FirstWriterWins {
+    fn resolve(&self, conflict: &EntityConflict) -> Result<ConflictResolution, SyncError> {
+        let kept = match conflict.later_side() {
+            ConflictSide::Local => ConflictSide::Remote,
+            ConflictSide::Remote => ConflictSide::Local,
+        };
+        let mut conflicts = Vec::new();
+        collect_differences(
+            "",
+            Some(&conflict.local.entity.data),
+            Some(&conflict.remote.entity.data),
+            kept,
+            &mut conflicts,
+        );
+        Ok(ConflictResolution { conflicts, ..ConflictResolution::keep(conflict, kept) })
+    }
+}
+
+/// A revision that has seen the other's edits wins without conflict; truly concurrent
+/// revisions go to the `concurrent` strategy (field-level merge by default)
+#[derive(Debug, Clone)]
+pub struct VersionVectorMerge {
+    concurrent: Arc<dyn ConflictStrategy>,
+}
+
+impl Default for VersionVectorMerge {
+    fn default() -> Self {
+        Self { concurrent: Arc::new(FieldLevelMerge) }
+    }
+}
+
+impl VersionVectorMerge {
+    /// Resolve concurrent revisions with `strategy`
+    pub fn with_concurrent_strategy(mut self, strategy: impl ConflictStrategy + 'static) -> Self {
+        self.concurrent = Arc::new(strategy);
+        self
+    }
+}
+
+impl ConflictStrategy for VersionVectorMerge {
+    fn resolve(&self, conflict: &EntityConflict) -> Result<ConflictResolution, SyncError> {
+        match conflict.local.vector.compare(&conflict.remote.vector) {
+            Causality::After => Ok(ConflictResolution::keep(conflict, ConflictSide::Local)),
+            Causality::Before => Ok(ConflictResolution::keep(conflict, ConflictSide::Remote)),
+            Causality::Equal if conflict.local.entity.data == conflict.remote.entity.data => {
+                Ok(ConflictResolution::keep(conflict, ConflictSide::Local))
+            }
+            Causality::Equal | Causality::Concurrent => self.concurrent.resolve(conflict),
+        }
+    }
+}
+
+/// Three-way merge of `data` against the common base
+///
+/// Fields changed on one side only take that change; nested objects are merged field by
+/// field. Fields both sides changed differently are conflicts, kept from the later writer.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FieldLevelMerge;
+
+impl ConflictStrategy for FieldLevelMerge {
+    fn resolve(&self, conflict: &EntityConflict) -> Result<ConflictResolution, SyncError> {
+        let later = conflict.later_side();
+        let mut conflicts = Vec::new();
+        let data = merge_value(
+            "",
+            conflict.base.as_ref().map(|base| &base.data),
+            Some(&conflict.local.entity.data),
+            Some(&conflict.remote.entity.data),
+            later,
+            &mut conflicts,
+        );
+
+        let (local, remote) = (&conflict.local.entity, &conflict.remote.entity);
+        let entity = SecureEntity {
+            data: data.unwrap_or(Value::Null),
+            version: local.version.max(remote.version) + 1,
+            ..conflict.side(later).entity.clone()
+        };
+        Ok(ConflictResolution {
+            entity,
+            vector: conflict.local.vector.merged(&conflict.remote.vector),
+            conflicts,
+        })
+    }
+}
+
+/// Picks a conflict strategy by entity type and enforces the label high-water mark
+#[derive(Debug, Clone)]
+pub struct ConflictResolver {
+    strategies: HashMap<String, Arc<dyn ConflictStrategy>>,
+    default_strategy: Arc<dyn ConflictStrategy>,
+}
+
+impl Default for ConflictResolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConflictResolver {
+    /// Last writer wins for every entity type
+    pub fn new() -> Self {
+        Self {
+            strategies: HashMap::new(),
+            default_strategy: Arc::new(LastWriterWins),
+        }
+    }
+
+    /// Resolve conflicts on `entity_type` with `strategy`
+    pub fn with_strategy(mut self, entity_type: impl Into<String>, strategy: impl ConflictStrategy + 'static) -> Self {
+        self.strategies.insert(entity_type.into(), Arc::new(strategy));
+        self
+    }
+
+    /// Resolve conflicts on entity types without their own strategy with `strategy`
+    pub fn with_default_strategy(mut self, strategy: impl ConflictStrategy + 'static) -> Self {
+        self.default_strategy = Arc::new(strategy);
+        self
+    }
+
+    pub fn strategy_for(&self, entity_type: &str) -> &dyn ConflictStrategy {
+        self.strategies.get(entity_type).unwrap_or(&self.default_strategy).as_ref()
+    }
+
+    /// Merge two revisions of one entity with its type's strategy
+    ///
+    /// The result is labelled at the least upper bound of both revisions, so a merge can
+    /// only raise the classification; lowering it takes an explicit relabel.
+    pub fn resolve(&self, conflict: &EntityConflict) -> Result<ConflictResolution, SyncError> {
+        let (local, remote) = (&conflict.local.entity, &conflict.remote.entity);
+        if local.id != remote.id || local.entity_type != remote.entity_type {
+            return Err(SyncError::SyncConflict {
+                entity_id: local.id.to_string(),
+                reason: format!("cannot merge with {} entity {}", remote.entity_type, remote.id),
+            });
+        }
+
+        let mut resolution = self.strategy_for(&local.entity_type).resolve(conflict)?;
+        let label = SecurityLabel::aggregate(&[label_of(local), label_of(remote), label_of(&resolution.entity)]);
+        resolution.entity.classification = label.level;
+        resolution.entity.compartments = label.compartments.into_iter().collect::<BTreeSet<_>>().into_iter().collect();
+        Ok(resolution)
+    }
+}
+
+fn label_of(entity: &SecureEntity) -> SecurityLabel {
+    SecurityLabel::new(entity.classification.clone(), entity.compartments.clone())
+}
+
+fn child_path(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+fn field_conflict(path: &str, local: Option<&Value>, remote: Option<&Value>, kept: ConflictSide) -> FieldConflict {
+    FieldConflict {
+        field: if path.is_empty() { "data".to_string() } else { path.to_string() },
+        local: local.cloned(),
+        remote: remote.cloned(),
+        kept,
+    }
+}
+
+/// Three-way merge of one value; `None` means the field is absent
+fn merge_value(
+    path: &str,
+    base: Option<&Value>,
+    local: Option<&Value>,
+    remote: Option<&Value>,
+    later: ConflictSide,
+    conflicts: &mut Vec<FieldConflict>,
+) -> Option<Value> {
+    if local == remote || remote == base {
+        return local.cloned();
+    }
+    if local == base {
+        return remote.cloned();
+    }
+
+    if let (Some(Value::Object(local)), Some(Value::Object(remote))) = (local, remote) {
+        let base = base.and_then(Value::as_object);
+        let keys: BTreeSet<&String> = local.keys().chain(remote.keys()).collect();
+        let mut merged = Map::new();
+        for key in keys {
+            let path = child_path(path, key);
+            let value = merge_value(
+                &path,
+                base.and_then(|base| base.get(key)),
+                local.get(key),
+                remote.get(key),
+                later,
+                conflicts,
+            );
+            if let Some(value) = value {
+                merged.insert(key.clone(), value);
+            }
+        }
+        return Some(Value::Object(merged));
+    }
+
+    conflicts.push(field_conflict(path, local, remote, later));
+    match later {
+        ConflictSide::Local => local.cloned(),
+        ConflictSide::Remote => remote.cloned(),
+    }
+}
+
+/// Every field where `local` and `remote` differ, descending into objects on both sides
+fn collect_differences(
+    path: &str,
+    local: Option<&Value>,
+    remote: Option<&Value>,
+    kept: ConflictSide,
+    conflicts: &mut Vec<FieldConflict>,
+) {
+    match (local, remote) {
+        _ if local == remote => {}
+        (Some(Value::Object(local)), Some(Value::Object(remote))) => {
+            let keys: BTreeSet<&String> = local.keys().chain(remote.keys()).collect();
+            for key in keys {
+                collect_differences(&child_path(path, key), local.get(key), remote.get(key), kept, conflicts);
+            }
+        }
+        _ => conflicts.push(field_conflict(path, local, remote, kept)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::ClassificationLevel;
+    use chrono::{Duration, Utc};
+    use serde_json::json;
+    use uuid::Uuid;
+
+    fn entity(data: Value, level: ClassificationLevel, compartments: &[&str]) -> SecureEntity {
+        let now = Utc::now();
+        SecureEntity {
+            id: Uuid::nil(),
+            entity_type: "note".to_string(),
+            data,
+            created_at: now,
+            updated_at: now,
+            created_by: "alice".to_string(),
+            updated_by: "alice".to_string(),
+            classification: level,
+            compartments: compartments.iter().map(|c| c.to_string()).collect(),
+            version: 1,
+            tenant_id: None,
+            deleted_at: None,
+            deleted_by: None,
+        }
+    }
+
+    /// `base` edited into `local` on the laptop and, a minute later, into `remote` on the phone
+    fn conflict(base: &SecureEntity, local: Value, remote: Value) -> EntityConflict {
+        let edited = |data: Value, replica: &str, minutes: i64| EntityRevision {
+            entity: SecureEntity {
+                data,
+                version: base.version + 1,
+                updated_by: replica.to_string(),
+                updated_at: base.updated_at + Duration::minutes(minutes),
+                ..base.clone()
+            },
+            vector: VersionVector::new().with_entry("origin", 1).with_entry(replica, 1),
+        };
+        EntityConflict {
+            base: Some(base.clone()),
+            local: edited(local, "laptop", 1),
+            remote: edited(remote, "phone", 2),
+        }
+    }
+
+    #[test]
+    fn test_last_writer_wins_reports_overridden_fields() {
+        let base = entity(json!({"title": "Plan", "status": "open"}), ClassificationLevel::Internal, &[]);
+        let conflict = conflict(
+            &base,
+            json!({"title": "Plan v2", "status": "open", "owner": "alice"}),
+            json!({"title": "Plan", "status": "closed"}),
+        );
+
+        let resolution = ConflictResolver::new().resolve(&conflict).unwrap();
+        assert_eq!(resolution.entity.data, conflict.remote.entity.data);
+        assert_eq!(resolution.conflicting_fields(), vec!["owner", "status", "title"]);
+        assert!(resolution.conflicts.iter().all(|field| field.kept == ConflictSide::Remote));
+        assert_eq!(resolution.vector, VersionVector::new().with_entry("origin", 1).with_entry("laptop", 1).with_entry("phone", 1));
+
+        // First writer wins keeps the laptop's earlier edit instead
+        let resolution = ConflictResolver::new().with_strategy("note", FirstWriterWins).resolve(&conflict).unwrap();
+        assert_eq!(resolution.entity.data, conflict.local.entity.data);
+        assert!(resolution.conflicts.iter().all(|field| field.kept == ConflictSide::Local));
+    }
+
+    #[test]
+    fn test_version_vector_newer_revision_wins_and_concurrent_ones_merge() {
+        let base = entity(json!({"title": "Plan", "status": "open"}), ClassificationLevel::Internal, &[]);
+        let resolver = ConflictResolver::new().with_strategy("note", VersionVectorMerge::default());
+
+        // The phone's edit was made after syncing the laptop's, so it simply supersedes it
+        let mut sequential = conflict(&base, json!({"title": "Plan v2", "status": "open"}), json!({"title": "Plan v2", "status": "closed"}));
+        sequential.remote.vector = sequential.local.vector.merged(&sequential.remote.vector);
+        let resolution = resolver.resolve(&sequential).unwrap();
+        assert_eq!(resolution.entity.data, json!({"title": "Plan v2", "status": "closed"}));
+        assert!(resolution.conflicts.is_empty());
+        assert_eq!(resolution.entity.version, sequential.remote.entity.version);
+
+        // Neither saw the other: merged field by field
+        let concurrent = conflict(&base, json!({"title": "Plan v2", "status": "open"}), json!({"title": "Plan", "status": "closed"}));
+        assert_eq!(concurrent.local.vector.compare(&concurrent.remote.vector), Causality::Concurrent);
+        let resolution = resolver.resolve(&concurrent).unwrap();
+        assert_eq!(resolution.entity.data, json!({"title": "Plan v2", "status": "closed"}));
+        assert!(resolution.conflicts.is_empty());
+    }
+
+    #[test]
+    fn test_field_merge_keeps_concurrent_edits_to_different_keys() {
+        let base = entity(
+            json!({"title": "Plan", "status": "open", "tags": ["q3"], "owner": {"name": "alice", "team": "ops"}}),
+            ClassificationLevel::Internal,
+            &[],
+        );
+        let edits = conflict(
+            &base,
+            json!({"title": "Plan v2", "status": "open", "owner": {"name": "alice", "team": "sre"}}),
+            json!({"title": "Plan", "status": "closed", "tags": ["q3"], "owner": {"name": "bob", "team": "ops"}}),
+        );
+
+        let resolver = ConflictResolver::new().with_strategy("note", FieldLevelMerge);
+        let resolution = resolver.resolve(&edits).unwrap();
+        // The laptop's title edit, tag removal and team change survive alongside the phone's edits
+        assert_eq!(
+            resolution.entity.data,
+            json!({"title": "Plan v2", "status": "closed", "owner": {"name": "bob", "team": "sre"}})
+        );
+        assert!(resolution.conflicts.is_empty());
+        assert_eq!(resolution.entity.version, 3);
+
+        // Both changing one field is a conflict, kept from the later writer
+        let clash = conflict(&base, json!({"status": "blocked"}), json!({"status": "closed"}));
+        let resolution = FieldLevelMerge.resolve(&clash).unwrap();
+        assert_eq!(resolution.entity.data["status"], "closed");
+        assert!(resolution.conflicting_fields().contains(&"status"));
+        let status = resolution.conflicts.iter().find(|field| field.field == "status").unwrap();
+        assert_eq!((status.local.clone(), status.kept), (Some(json!("blocked")), ConflictSide::Remote));
+    }
+
+    #[test]
+    fn test_merge_never_downgrades_classification() {
+        let base = entity(json!({"title": "Plan"}), ClassificationLevel::Internal, &[]);
+        let mut conflict = conflict(&base, json!({"title": "Sources"}), json!({"title": "Plan", "status": "closed"}));
+        conflict.local.entity.classification = ClassificationLevel::Secret;
+        conflict.local.entity.compartments = vec!["ALPHA".to_string()];
+        conflict.remote.entity.compartments = vec!["BRAVO".to_string()];
+
+        // The later, Internal revision wins the data but not the label
+        for resolver in [
+            ConflictResolver::new(),
+            ConflictResolver::new().with_default_strategy(FieldLevelMerge),
+            ConflictResolver::new().with_default_strategy(VersionVectorMerge::default()),
+        ] {
+            let resolution = resolver.resolve(&conflict).unwrap();
+            assert_eq!(resolution.entity.classification, ClassificationLevel::Secret);
+            assert_eq!(resolution.entity.compartments, vec!["ALPHA".to_string(), "BRAVO".to_string()]);
+        }
+
+        let mut other = conflict.clone();
+        other.remote.entity.id = Uuid::new_v4();
+        assert!(matches!(ConflictResolver::new().resolve(&other), Err(SyncError::SyncConflict { .. })));
+    }
+}
@@ -0,0 +1,618 @@
+// src/sync/delta.rs
+// Delta Sync - Pushes entity edits as JSON Patch documents against the last-synced version
+// Patches apply remotely only at their base version; anything else falls back to the full document
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::io::{Read, Write};
+use std::sync::Arc;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use super::SyncError;
+use crate::database::SecureEntity;
+use crate::networking::{
+    version_from_etag, HttpMethod, NetworkContext, NetworkError, SecureNetworkTransport, SecureRequest,
+    SecureResponse, SecurityRequirements,
+};
+use crate::observability::automatic_instrumentation::InstrumentationHooks;
+use crate::observability::QosClass;
+use crate::security::{ClassificationLevel, SecurityLabel};
+
+/// Media type of a patch body
+pub const JSON_PATCH_CONTENT_TYPE: &str = "application/json-patch+json";
+
+/// One RFC 6902 operation on an entity's `data`; paths are JSON Pointers
+///
+/// Only object members are patched: arrays and scalars that changed are replaced whole.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOp {
+    Add { path: String, value: Value },
+    Remove { path: String },
+    Replace { path: String, value: Value },
+}
+
+/// Operations turning `base` into `target`
+pub fn diff(base: &Value, target: &Value) -> Vec<PatchOp> {
+    let mut patch = Vec::new();
+    diff_into(String::new(), base, target, &mut patch);
+    patch
+}
+
+fn diff_into(path: String, base: &Value, target: &Value, patch: &mut Vec<PatchOp>) {
+    if base == target {
+        return;
+    }
+    match (base, target) {
+        (Value::Object(base), Value::Object(target)) => {
+            for (key, base_value) in base {
+                let path = format!("{}/{}", path, escape_pointer_token(key));
+                match target.get(key) {
+                    Some(target_value) => diff_into(path, base_value, target_value, patch),
+                    None => patch.push(PatchOp::Remove { path }),
+                }
+            }
+            for (key, value) in target {
+                if !base.contains_key(key) {
+                    let path = format!("{}/{}", path, escape_pointer_token(key));
+                    patch.push(PatchOp::Add { path, value: value.clone() });
+                }
+            }
+        }
+        _ => patch.push(PatchOp::Replace { path, value: target.clone() }),
+    }
+}
+
+/// `document` with `patch` applied; fails without partial effect if any operation doesn't apply
+pub fn apply_patch(document: &Value, patch: &[PatchOp]) -> Result<Value, SyncError> {
+    let mut document = document.clone();
+    for op in patch {
+        match op {
+            PatchOp::Replace { path, value } => {
+                let target = document.pointer_mut(path)
+                    .ok_or_else(|| invalid_patch(path, "no such member"))?;
+                *target = value.clone();
+            }
+            PatchOp::Add { path, value } => {
+                let (parent, key) = member_of(&mut document, path)?;
+                parent.insert(key, value.clone());
+            }
+            PatchOp::Remove { path } => {
+                let (parent, key) = member_of(&mut document, path)?;
+                parent.remove(&key).ok_or_else(|| invalid_patch(path, "no such member"))?;
+            }
+        }
+    }
+    Ok(document)
+}
+
+/// The object holding the member at `path`, and the member's key
+fn member_of<'a>(document: &'a mut Value, path: &str) -> Result<(&'a mut serde_json::Map<String, Value>, String), SyncError> {
+    let (parent, key) = path.rsplit_once('/')
+        .ok_or_else(|| invalid_patch(path, "not a member path"))?;
+    let parent = document.pointer_mut(parent)
+        .and_then(Value::as_object_mut)
+        .ok_or_else(|| invalid_patch(path, "parent is not an object"))?;
+    Ok((parent, unescape_pointer_token(key)))
+}
+
+fn escape_pointer_token(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+fn unescape_pointer_token(token: &str) -> String {
+    token.replace("~1", "/").replace("~0", "~")
+}
+
+fn invalid_patch(path: &str, reason: &str) -> SyncError {
+    SyncError::InvalidPatch { path: path.to_string(), reason: reason.to_string() }
+}
+
+/// An edit to one entity's data, relative to the version both sides last agreed on
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntityDelta {
+    pub entity_id: Uuid,
+    /// Version the patch was computed against; the remote applies it only at this version
+    pub base_version: i64,
+    pub patch: Vec<PatchOp>,
+}
+
+/// Apply `delta` to the stored `entity`, as the receiving side of a delta push
+pub fn apply_delta(entity: &SecureEntity, delta: &EntityDelta, actor: &str, now: DateTime<Utc>) -> Result<SecureEntity, SyncError> {
+    if entity.id != delta.entity_id || entity.version != delta.base_version {
+        return Err(SyncError::BaseVersionMismatch {
+            entity_id: delta.entity_id.to_string(),
+            expected: delta.base_version,
+            current: (entity.id == delta.entity_id).then_some(entity.version),
+        });
+    }
+    Ok(SecureEntity {
+        data: apply_patch(&entity.data, &delta.patch)?,
+        version: entity.version + 1,
+        updated_at: now,
+        updated_by: actor.to_string(),
+        ..entity.clone()
+    })
+}
+
+/// Why an edit is sent as the whole document
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FullSyncReason {
+    /// Never synced, so there is nothing to diff against
+    NoBase,
+    /// More local versions than `max_version_gap` since the last sync
+    BaseTooOld,
+    /// Classification or compartments changed, which a data patch can't carry
+    LabelChanged,
+    /// The patch would be no smaller than the document
+    PatchNotSmaller,
+}
+
+/// What to send for one edited entity
+#[derive(Debug, Clone)]
+pub enum SyncPayload {
+    Delta(EntityDelta),
+    Full(FullSyncReason),
+}
+
+/// When to send patches instead of documents, and when to compress them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeltaSyncConfig {
+    /// Local versions since the last sync beyond which the full document is sent
+    pub max_version_gap: i64,
+    /// Send the document when the patch exceeds this fraction of its size
+    pub max_patch_ratio: f64,
+    /// Bodies at least this large are gzipped
+    pub compress_min_bytes: usize,
+}
+
+impl Default for DeltaSyncConfig {
+    fn default() -> Self {
+        Self {
+            max_version_gap: 16,
+            max_patch_ratio: 0.5,
+            compress_min_bytes: 1024,
+        }
+    }
+}
+
+impl DeltaSyncConfig {
+    /// Decide how to send `current`, given `base`, the version last synced
+    pub fn plan(&self, base: Option<&SecureEntity>, current: &SecureEntity) -> SyncPayload {
+        let Some(base) = base else {
+            return SyncPayload::Full(FullSyncReason::NoBase);
+        };
+        if current.version - base.version > self.max_version_gap {
+            return SyncPayload::Full(FullSyncReason::BaseTooOld);
+        }
+        let mut compartments = current.compartments.clone();
+        let mut base_compartments = base.compartments.clone();
+        compartments.sort();
+        base_compartments.sort();
+        if current.classification != base.classification || compartments != base_compartments {
+            return SyncPayload::Full(FullSyncReason::LabelChanged);
+        }
+
+        let patch = diff(&base.data, &current.data);
+        let patch_size = serde_json::to_vec(&patch).map(|body| body.len()).unwrap_or(usize::MAX);
+        let document_size = serde_json::to_vec(current).map(|body| body.len()).unwrap_or(0);
+        if patch_size as f64 > document_size as f64 * self.max_patch_ratio {
+            return SyncPayload::Full(FullSyncReason::PatchNotSmaller);
+        }
+        SyncPayload::Delta(EntityDelta { entity_id: current.id, base_version: base.version, patch })
+    }
+
+    /// JSON body of `value`, gzipped if it is at least `compress_min_bytes`
+    pub fn encode<T: Serialize + ?Sized>(&self, value: &T) -> Result<EncodedBody, SyncError> {
+        let body = serde_json::to_vec(value)
+            .map_err(|e| SyncError::SerializationError { error: e.to_string() })?;
+        if body.len() < self.compress_min_bytes {
+            return Ok(EncodedBody { body, gzip: false });
+        }
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(&body)
+            .and_then(|_| encoder.finish())
+            .map(|body| EncodedBody { body, gzip: true })
+            .map_err(|e| SyncError::SerializationError { error: e.to_string() })
+    }
+}
+
+/// A request body as sent, `Content-Encoding: gzip` when `gzip` is set
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncodedBody {
+    pub body: Vec<u8>,
+    pub gzip: bool,
+}
+
+impl EncodedBody {
+    pub fn decode<T: serde::de::DeserializeOwned>(&self) -> Result<T, SyncError> {
+        let serialization = |e: &dyn std::fmt::Display| SyncError::SerializationError { error: e.to_string() };
+        if !self.gzip {
+            return serde_json::from_slice(&self.body).map_err(|e| serialization(&e));
+        }
+        let mut json = Vec::new();
+        flate2::read::GzDecoder::new(self.body.as_slice()).read_to_end(&mut json)
+            .map_err(|e| serialization(&e))?;
+        serde_json::from_slice(&json).map_err(|e| serialization(&e))
+    }
+}
+
+/// Remote store of entities that accepts version-checked patches
+///
+/// Both writes fail with `SyncError::BaseVersionMismatch` when the remote is not at
+/// `base_version`. `TransportSyncEndpoint` in production.
+#[async_trait]
+pub trait SyncEndpoint: Send + Sync + Debug {
+    /// Apply the encoded patch to `entity`'s remote copy; returns the new remote version
+    async fn patch_entity(&self, entity: &SecureEntity, base_version: i64, patch: &EncodedBody) -> Result<i64, SyncError>;
+
+    /// Store `entity` whole; `None` creates it and fails if it already exists
+    async fn put_entity(&self, entity: &SecureEntity, base_version: Option<i64>, document: &EncodedBody) -> Result<i64, SyncError>;
+
+    async fn get_entity(&self, entity_id: Uuid) -> Result<SecureEntity, SyncError>;
+}
+
+#[async_trait]
+impl<E: SyncEndpoint + ?Sized> SyncEndpoint for Box<E> {
+    async fn patch_entity(&self, entity: &SecureEntity, base_version: i64, patch: &EncodedBody) -> Result<i64, SyncError> {
+        (**self).patch_entity(entity, base_version, patch).await
+    }
+
+    async fn put_entity(&self, entity: &SecureEntity, base_version: Option<i64>, document: &EncodedBody) -> Result<i64, SyncError> {
+        (**self).put_entity(entity, base_version, document).await
+    }
+
+    async fn get_entity(&self, entity_id: Uuid) -> Result<SecureEntity, SyncError> {
+        (**self).get_entity(entity_id).await
+    }
+}
+
+/// No Read Up on a fetched copy: `clearance` must dominate the entity's label
+fn check_clearance(clearance: &SecurityLabel, entity: &SecureEntity) -> Result<(), SyncError> {
+    let label = SecurityLabel::new(entity.classification.clone(), entity.compartments.clone());
+    if clearance.dominates(&label) {
+        return Ok(());
+    }
+    Err(SyncError::AccessDenied {
+        entity_id: entity.id.to_string(),
+        reason: format!("labelled {} with compartments {:?}, above the session's clearance", entity.classification, entity.compartments),
+    })
+}
+
+/// Outcome of pushing one entity
+#[derive(Debug, Clone)]
+pub enum DeltaPush {
+    Delta { version: i64, bytes_sent: usize },
+    Full { version: i64, bytes_sent: usize, reason: FullSyncReason },
+    /// The remote moved on from our base; merge with this copy (`ConflictResolver`) and push the result
+    Resync { remote: Box<SecureEntity> },
+}
+
+/// Pushes edits as patches where it pays, documents where it doesn't
+#[derive(Debug)]
+pub struct DeltaSync<E: SyncEndpoint> {
+    endpoint: E,
+    config: DeltaSyncConfig,
+}
+
+impl<E: SyncEndpoint> DeltaSync<E> {
+    pub fn new(endpoint: E) -> Self {
+        Self { endpoint, config: DeltaSyncConfig::default() }
+    }
+
+    pub fn with_config(mut self, config: DeltaSyncConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Push `current`, diffed against `base`, the version last synced
+    pub async fn push(&self, base: Option<&SecureEntity>, current: &SecureEntity) -> Result<DeltaPush, SyncError> {
+        let result = match self.config.plan(base, current) {
+            SyncPayload::Delta(delta) => {
+                let body = self.config.encode(&delta.patch)?;
+                self.endpoint.patch_entity(current, delta.base_version, &body).await
+                    .map(|version| DeltaPush::Delta { version, bytes_sent: body.body.len() })
+            }
+            SyncPayload::Full(reason) => {
+                let body = self.config.encode(current)?;
+                self.endpoint.put_entity(current, base.map(|base| base.version), &body).await
+                    .map(|version| DeltaPush::Full { version, bytes_sent: body.body.len(), reason })
+            }
+        };
+
+        match result {
+            Err(SyncError::BaseVersionMismatch { entity_id, expected, current: remote_version }) => {
+                tracing::info!(%entity_id, expected, ?remote_version, "Sync base diverged, fetching full remote document");
+                let remote = self.endpoint.get_entity(current.id).await?;
+                Ok(DeltaPush::Resync { remote: Box::new(remote) })
+            }
+            result => result,
+        }
+    }
+}
+
+/// `SyncEndpoint` over `SecureNetworkTransport`: `PATCH`/`PUT`/`GET {base_url}/entities/{id}`,
+/// with the base version as `If-Match` and the new version read back from the `ETag`
+pub struct TransportSyncEndpoint {
+    transport: Arc<SecureNetworkTransport>,
+    hooks: Arc<dyn InstrumentationHooks>,
+    base_url: String,
+    context: NetworkContext,
+}
+
+impl TransportSyncEndpoint {
+    /// `hooks` receives the transport's own audit and metrics events (normally the `AppState`)
+    pub fn new(
+        transport: Arc<SecureNetworkTransport>,
+        hooks: Arc<dyn InstrumentationHooks>,
+        base_url: impl Into<String>,
+        context: NetworkContext,
+    ) -> Self {
+        Self { transport, hooks, base_url: base_url.into(), context }
+    }
+
+    fn request(&self, method: HttpMethod, entity_id: Uuid, classification: ClassificationLevel) -> SecureRequest {
+        SecureRequest {
+            request_id: Uuid::new_v4(),
+            url: format!("{}/entities/{}", self.base_url.trim_end_matches('/'), entity_id),
+            method,
+            headers: HashMap::new(),
+            body: None,
+            classification,
+            user_id: self.context.user_id.clone(),
+            session_id: self.context.session_id,
+            timeout_ms: None,
+            retry_policy: None,
+            cache_policy: None,
+            security_requirements: SecurityRequirements::default(),
+            idempotency_key: None,
+            qos: QosClass::Background,
+        }
+    }
+
+    /// Send a version-checked write and return the new version from its `ETag`
+    async fn write(&self, request: SecureRequest, entity_id: Uuid, base_version: Option<i64>, body: &EncodedBody) -> Result<i64, SyncError> {
+        let mut request = request.with_idempotency_key(Uuid::new_v4().to_string());
+        if body.gzip {
+            request.headers.insert("Content-Encoding".to_string(), "gzip".to_string());
+        }
+        let request = match base_version {
+            Some(version) => request.with_expected_version(version),
+            None => request.with_if_none_match("*"),
+        };
+
+        let response = self.send(request).await.map_err(|e| match e {
+            SyncError::BaseVersionMismatch { current, .. } => SyncError::BaseVersionMismatch {
+                entity_id: entity_id.to_string(),
+                expected: base_version.unwrap_or(0),
+                current,
+            },
+            e => e,
+        })?;
+        response.etag()
+            .and_then(version_from_etag)
+            .ok_or_else(|| SyncError::ServerError {
+                status: response.status_code,
+                message: "write response carried no version ETag".to_string(),
+            })
+    }
+
+    /// Send `request`, mapping a 412 to `BaseVersionMismatch` (filled in by `write`) and other failures to `SyncError`
+    async fn send(&self, request: SecureRequest) -> Result<SecureResponse, SyncError> {
+        let response = self.transport.request(request, self.context.clone(), self.hooks.as_ref()).await
+            .map_err(|e| match e {
+                NetworkError::PreconditionFailed { url, current_etag } => SyncError::BaseVersionMismatch {
+                    entity_id: url,
+                    expected: 0,
+                    current: current_etag.as_deref().and_then(version_from_etag),
+                },
+                e => SyncError::NetworkError { error: e.to_string() },
+            })?;
+        match response.status_code {
+            200..=299 => Ok(response),
+            status => Err(SyncError::ServerError {
+                status,
+                message: String::from_utf8_lossy(response.body.as_deref().unwrap_or_default()).into_owned(),
+            }),
+        }
+    }
+}
+
+impl Debug for TransportSyncEndpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TransportSyncEndpoint")
+            .field("base_url", &self.base_url)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl SyncEndpoint for TransportSyncEndpoint {
+    async fn patch_entity(&self, entity: &SecureEntity, base_version: i64, patch: &EncodedBody) -> Result<i64, SyncError> {
+        let request = self.request(HttpMethod::PATCH, entity.id, entity.classification.clone())
+            .with_body(patch.body.clone(), JSON_PATCH_CONTENT_TYPE);
+        self.write(request, entity.id, Some(base_version), patch).await
+    }
+
+    async fn put_entity(&self, entity: &SecureEntity, base_version: Option<i64>, document: &EncodedBody) -> Result<i64, SyncError> {
+        let request = self.request(HttpMethod::PUT, entity.id, entity.classification.clone())
+            .with_body(document.body.clone(), "application/json");
+        self.write(request, entity.id, base_version, document).await
+    }
+
+    async fn get_entity(&self, entity_id: Uuid) -> Result<SecureEntity, SyncError> {
+        // The remote copy's label isn't known yet, so ask at the session's clearance
+        let request = self.request(HttpMethod::GET, entity_id, self.context.security_label.level.clone());
+        let response = self.send(request).await?;
+        let entity: SecureEntity = response.json_response()
+            .map_err(|e| SyncError::SerializationError { error: e.to_string() })?;
+        // The server may not enforce compartments, so check the copy before handing it on
+        check_clearance(&self.context.security_label, &entity)?;
+        Ok(entity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use serde_json::json;
+    use std::sync::Mutex;
+
+    fn entity(data: Value) -> SecureEntity {
+        let now = Utc::now();
+        SecureEntity {
+            id: Uuid::new_v4(),
+            entity_type: "document".to_string(),
+            data,
+            created_at: now,
+            updated_at: now,
+            created_by: "alice".to_string(),
+            updated_by: "alice".to_string(),
+            classification: ClassificationLevel::Internal,
+            compartments: Vec::new(),
+            version: 3,
+            tenant_id: None,
+            deleted_at: None,
+            deleted_by: None,
+        }
+    }
+
+    fn edited(base: &SecureEntity, data: Value) -> SecureEntity {
+        SecureEntity { data, version: base.version + 1, updated_at: base.updated_at + Duration::seconds(1), ..base.clone() }
+    }
+
+    /// Remote holding one entity, applying patches as a sync server would
+    #[derive(Debug)]
+    struct MemoryEndpoint {
+        remote: Mutex<SecureEntity>,
+    }
+
+    #[async_trait]
+    impl SyncEndpoint for MemoryEndpoint {
+        async fn patch_entity(&self, entity: &SecureEntity, base_version: i64, patch: &EncodedBody) -> Result<i64, SyncError> {
+            let delta = EntityDelta { entity_id: entity.id, base_version, patch: patch.decode()? };
+            let mut remote = self.remote.lock().unwrap();
+            *remote = apply_delta(&remote, &delta, &entity.updated_by, Utc::now())?;
+            Ok(remote.version)
+        }
+
+        async fn put_entity(&self, entity: &SecureEntity, base_version: Option<i64>, document: &EncodedBody) -> Result<i64, SyncError> {
+            let mut remote = self.remote.lock().unwrap();
+            if base_version != Some(remote.version) {
+                return Err(SyncError::BaseVersionMismatch {
+                    entity_id: entity.id.to_string(),
+                    expected: base_version.unwrap_or(0),
+                    current: Some(remote.version),
+                });
+            }
+            let document: SecureEntity = document.decode()?;
+            *remote = SecureEntity { version: remote.version + 1, ..document };
+            Ok(remote.version)
+        }
+
+        async fn get_entity(&self, _entity_id: Uuid) -> Result<SecureEntity, SyncError> {
+            Ok(self.remote.lock().unwrap().clone())
+        }
+    }
+
+    fn large_document() -> Value {
+        let sections: serde_json::Map<String, Value> = (0..50)
+            .map(|i| (format!("section_{}", i), json!({"heading": format!("Section {}", i), "body": "lorem ipsum ".repeat(40)})))
+            .collect();
+        json!({"title": "Handbook", "status": "draft", "sections": sections})
+    }
+
+    #[tokio::test]
+    async fn test_single_field_edit_sends_small_patch() {
+        let base = entity(large_document());
+        let mut data = base.data.clone();
+        data["sections"]["section_7"]["heading"] = json!("Escalation");
+        let current = edited(&base, data);
+
+        let SyncPayload::Delta(delta) = DeltaSyncConfig::default().plan(Some(&base), &current) else {
+            panic!("expected a delta");
+        };
+        assert_eq!(delta.patch, vec![PatchOp::Replace {
+            path: "/sections/section_7/heading".to_string(),
+            value: json!("Escalation"),
+        }]);
+
+        let sync = DeltaSync::new(MemoryEndpoint { remote: Mutex::new(base.clone()) });
+        let document_size = serde_json::to_vec(&current).unwrap().len();
+        match sync.push(Some(&base), &current).await.unwrap() {
+            DeltaPush::Delta { version, bytes_sent } => {
+                assert_eq!(version, base.version + 1);
+                assert!(bytes_sent * 50 < document_size, "{} bytes for a {} byte document", bytes_sent, document_size);
+            }
+            other => panic!("expected a delta push, got {:?}", other),
+        }
+        assert_eq!(sync.endpoint.remote.lock().unwrap().data, current.data);
+    }
+
+    #[tokio::test]
+    async fn test_diverged_base_triggers_full_resync() {
+        let base = entity(json!({"title": "Plan", "status": "open"}));
+        let current = edited(&base, json!({"title": "Plan v2", "status": "open"}));
+        // Another device pushed first, so the remote is past our base
+        let theirs = edited(&base, json!({"title": "Plan", "status": "closed"}));
+        let sync = DeltaSync::new(MemoryEndpoint { remote: Mutex::new(theirs.clone()) });
+
+        match sync.push(Some(&base), &current).await.unwrap() {
+            DeltaPush::Resync { remote } => assert_eq!((remote.version, remote.data), (theirs.version, theirs.data)),
+            other => panic!("expected a resync, got {:?}", other),
+        }
+
+        // Too many local versions since the last sync: the document goes whole
+        let far = SecureEntity { version: base.version + 20, ..current.clone() };
+        assert!(matches!(DeltaSyncConfig::default().plan(Some(&base), &far), SyncPayload::Full(FullSyncReason::BaseTooOld)));
+        let relabelled = SecureEntity { classification: ClassificationLevel::Secret, ..current };
+        assert!(matches!(DeltaSyncConfig::default().plan(Some(&base), &relabelled), SyncPayload::Full(FullSyncReason::LabelChanged)));
+    }
+
+    #[test]
+    fn test_patch_round_trips_additions_removals_and_escaped_keys() {
+        let base = json!({"a/b": 1, "n": {"keep": true, "drop": 1}, "list": [1, 2]});
+        let target = json!({"a/b": 2, "n": {"keep": true, "new~": "x"}, "list": [1, 2, 3], "added": null});
+
+        let patch = diff(&base, &target);
+        assert!(patch.contains(&PatchOp::Replace { path: "/a~1b".to_string(), value: json!(2) }));
+        assert!(patch.contains(&PatchOp::Remove { path: "/n/drop".to_string() }));
+        assert_eq!(apply_patch(&base, &patch).unwrap(), target);
+
+        let stale = EntityDelta { entity_id: Uuid::nil(), base_version: 1, patch: patch.clone() };
+        let err = apply_delta(&entity(base), &stale, "alice", Utc::now()).unwrap_err();
+        assert!(matches!(err, SyncError::BaseVersionMismatch { .. }));
+        assert!(apply_patch(&json!({}), &[PatchOp::Remove { path: "/missing".to_string() }]).is_err());
+    }
+
+    #[test]
+    fn test_fetched_copy_must_be_dominated_by_clearance() {
+        let remote = SecureEntity { compartments: vec!["hr".to_string()], ..entity(json!({"title": "Payroll"})) };
+
+        let cleared = SecurityLabel::new(ClassificationLevel::Confidential, vec!["hr".to_string()]);
+        check_clearance(&cleared, &remote).unwrap();
+
+        let missing_compartment = SecurityLabel::new(ClassificationLevel::Secret, Vec::new());
+        assert!(matches!(check_clearance(&missing_compartment, &remote), Err(SyncError::AccessDenied { .. })));
+        let too_low = SecurityLabel::new(ClassificationLevel::Unclassified, vec!["hr".to_string()]);
+        assert!(matches!(check_clearance(&too_low, &remote), Err(SyncError::AccessDenied { .. })));
+    }
+
+    #[test]
+    fn test_large_patch_is_gzipped() {
+        let config = DeltaSyncConfig::default();
+        let patch = diff(&json!({}), &large_document());
+        let encoded = config.encode(&patch).unwrap();
+        assert!(encoded.gzip);
+        assert!(encoded.body.len() < serde_json::to_vec(&patch).unwrap().len() / 4);
+        assert_eq!(encoded.decode::<Vec<PatchOp>>().unwrap(), patch);
+
+        let small = config.encode(&[PatchOp::Remove { path: "/a".to_string() }]).unwrap();
+        assert!(!small.gzip);
+    }
+}
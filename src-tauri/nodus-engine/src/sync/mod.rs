@@ -12,16 +12,31 @@ use chrono::{DateTime, Utc, Duration};
 use uuid::Uuid;
 
 use crate::storage::{StorageManager, StoredEntity, StorageContext, StorageError};
-use crate::security::SecurityManager;
+use crate::database::SecureEntity;
+use crate::security::{ClassificationLevel, SecurityLabel, SecurityManager};
 use crate::observability::instrument::instrument;
 use crate::policy::policy_snapshot::current_policy;
 
 // Sub-modules (consolidated in this file or not present)
-// pub mod conflict_resolution;
+pub mod conflict_resolution;
+pub mod delta;
+pub mod offline_queue;
 // pub mod sync_client;
 // pub mod websocket_sync;
 // pub mod batch_processor;
 
+pub use conflict_resolution::{
+    ConflictResolution, ConflictResolver, ConflictStrategy, EntityConflict, EntityRevision, FieldConflict,
+    FieldLevelMerge, FirstWriterWins, LastWriterWins, VersionVector, VersionVectorMerge,
+};
+pub use delta::{
+    DeltaPush, DeltaSync, DeltaSyncConfig, EncodedBody, EntityDelta, FullSyncReason, PatchOp, SyncEndpoint,
+    TransportSyncEndpoint,
+};
+pub use offline_queue::{
+    ClearanceProvider, MergedReplay, OfflineOperation, OfflineQueue, Quarantine, QueuedOperation, ReplayReport,
+};
+
 /// Sync errors
 #[derive(Debug, thiserror::Error)]
 pub enum SyncError {
@@ -48,6 +63,16 @@ pub enum SyncError {
     
     #[error("Server error: {status} - {message}")]
     ServerError { status: u16, message: String },
+    
+    /// The remote entity is no longer at the version a change was based on
+    #[error("Base version mismatch: {entity_id} expected {expected}, remote at {current:?}")]
+    BaseVersionMismatch { entity_id: String, expected: i64, current: Option<i64> },
+    
+    #[error("Invalid patch at {path}: {reason}")]
+    InvalidPatch { path: String, reason: String },
+    
+    #[error("Access denied to {entity_id}: {reason}")]
+    AccessDenied { entity_id: String, reason: String },
 }
 
 /// Change record for synchronization
@@ -181,6 +206,10 @@ pub struct SyncManager {
     pending_changes: Arc<Mutex<VecDeque<ChangeRecord>>>,
     conflict_resolver: ConflictResolver,
     change_listeners: Arc<RwLock<Vec<Box<dyn Fn(&ChangeRecord) + Send + Sync>>>>,
+    // Pushes entity edits as patches when set; deletes still go through `sync_client`
+    delta_sync: Option<Arc<DeltaSync<Box<dyn SyncEndpoint>>>>,
+    // Each entity as last pushed through `delta_sync`, the base of its next patch
+    synced_entities: Arc<RwLock<HashMap<Uuid, SecureEntity>>>,
     metrics: SyncMetrics,
     client_id: String,
 }
@@ -195,13 +224,6 @@ struct SyncMetrics {
     pub conflicts_resolved: Arc<std::sync::atomic::AtomicU64>,
 }
 
-/// Conflict resolver
-#[derive(Debug)]
-pub struct ConflictResolver {
-    strategies: HashMap<String, ConflictResolutionStrategy>,
-    default_strategy: ConflictResolutionStrategy,
-}
-
 impl SyncManager {
     /// Create a new sync manager
     pub fn new(
@@ -219,6 +241,8 @@ impl SyncManager {
             pending_changes: Arc::new(Mutex::new(VecDeque::new())),
             conflict_resolver: ConflictResolver::new(),
             change_listeners: Arc::new(RwLock::new(Vec::new())),
+            delta_sync: None,
+            synced_entities: Arc::new(RwLock::new(HashMap::new())),
             metrics: SyncMetrics {
                 syncs_total: Arc::new(std::sync::atomic::AtomicU64::new(0)),
                 syncs_successful: Arc::new(std::sync::atomic::AtomicU64::new(0)),
@@ -231,6 +255,12 @@ impl SyncManager {
         }
     }
     
+    /// Push creates and updates through `delta_sync`, as patches against the copy last pushed
+    pub fn with_delta_sync(mut self, delta_sync: DeltaSync<Box<dyn SyncEndpoint>>) -> Self {
+        self.delta_sync = Some(Arc::new(delta_sync));
+        self
+    }
+    
     /// Start sync manager
     pub async fn start(&self) -> Result<(), SyncError> {
         instrument("sync_start", || async {
@@ -371,10 +401,23 @@ impl SyncManager {
         listeners.push(Box::new(listener));
     }
     
-    /// Force conflict resolution
+    /// Force conflict resolution with `resolution` instead of the entity type's configured strategy
     pub async fn resolve_conflict(&self, conflict: ConflictRecord, resolution: ConflictResolutionStrategy) -> Result<(), SyncError> {
         instrument("sync_resolve_conflict", || async {
-            self.conflict_resolver.resolve_conflict(conflict, resolution, &self.storage_manager).await
+            let entity_type = conflict.local_change.entity_type.clone();
+            let resolver = match resolution {
+                ConflictResolutionStrategy::LastWriteWins => ConflictResolver::new().with_strategy(entity_type, LastWriterWins),
+                ConflictResolutionStrategy::FirstWriteWins => ConflictResolver::new().with_strategy(entity_type, FirstWriterWins),
+                ConflictResolutionStrategy::Merge => ConflictResolver::new().with_strategy(entity_type, FieldLevelMerge),
+                ConflictResolutionStrategy::Manual => {
+                    // Queue for manual resolution
+                    return Err(SyncError::SyncConflict {
+                        entity_id: conflict.entity_id,
+                        reason: "Manual resolution required".to_string(),
+                    });
+                }
+            };
+            self.settle_conflict(&conflict, &resolver).await
         }).await
     }
     
@@ -392,19 +435,32 @@ impl SyncManager {
             changes
         };
         
+        let (delta_changes, pending_changes): (Vec<ChangeRecord>, Vec<ChangeRecord>) = match &self.delta_sync {
+            Some(_) => pending_changes.into_iter().partition(|change| change.data.is_some()),
+            None => (Vec::new(), pending_changes),
+        };
+        if let Some(delta_sync) = &self.delta_sync {
+            for change in delta_changes {
+                match self.push_delta(delta_sync, &change).await {
+                    Ok(()) => changes_pushed += 1,
+                    Err(e) => {
+                        tracing::warn!(entity_id = %change.entity_id, error = %e, "Delta push failed, change re-queued");
+                        self.pending_changes.lock().await.push_back(change);
+                    }
+                }
+            }
+        }
+        
         if !pending_changes.is_empty() {
             let client = self.sync_client.lock().await;
             let push_result = client.push_changes(pending_changes.clone()).await?;
-            changes_pushed = push_result.accepted.len() as u64;
+            changes_pushed += push_result.accepted.len() as u64;
             
-            // Handle conflicts
+            // Handle conflicts with each entity type's strategy
             for conflict in push_result.conflicts {
-                if let Ok(()) = self.conflict_resolver.resolve_conflict(
-                    conflict,
-                    ConflictResolutionStrategy::LastWriteWins,
-                    &self.storage_manager,
-                ).await {
-                    conflicts_resolved += 1;
+                match self.settle_conflict(&conflict, &self.conflict_resolver).await {
+                    Ok(()) => conflicts_resolved += 1,
+                    Err(e) => tracing::warn!(entity_id = %conflict.entity_id, error = %e, "Conflict left unresolved"),
                 }
             }
             
@@ -457,6 +513,126 @@ impl SyncManager {
         })
     }
     
+    /// Settle a conflict the server reported through `resolver`
+    ///
+    /// The merge is stored locally, labelled no lower than the local copy, and queued so the
+    /// server receives it on the next push. A delete on either side leaves nothing to merge,
+    /// so the later change stands.
+    async fn settle_conflict(&self, conflict: &ConflictRecord, resolver: &ConflictResolver) -> Result<(), SyncError> {
+        let (local, remote) = (&conflict.local_change, &conflict.remote_change);
+        let (Some(local_data), Some(remote_data)) = (&local.data, &remote.data) else {
+            if remote.timestamp > local.timestamp {
+                return self.apply_remote_change(remote.clone()).await;
+            }
+            self.pending_changes.lock().await.push_back(local.clone());
+            return Ok(());
+        };
+
+        let ctx = self.storage_context(local).await;
+        let stored = self.storage_manager.get(&local.entity_id, &ctx).await
+            .map_err(|e| SyncError::StorageError { error: e.to_string() })?;
+        let label = stored_label(stored.as_ref())?;
+
+        let resolution = resolver.resolve(&EntityConflict {
+            base: None,
+            local: change_revision(local, local_data, &label)?,
+            remote: change_revision(remote, remote_data, &label)?,
+        })?;
+        if !resolution.conflicts.is_empty() {
+            tracing::info!(entity_id = %local.entity_id, fields = ?resolution.conflicting_fields(), "Conflicting fields decided by strategy");
+        }
+
+        let merged = StoredEntity {
+            id: local.entity_id.clone(),
+            entity_type: local.entity_type.clone(),
+            data: resolution.entity.data.clone(),
+            created_at: stored.as_ref().map_or(local.timestamp, |entity| entity.created_at),
+            updated_at: Utc::now(),
+            created_by: stored.as_ref().map_or_else(|| local.actor.clone(), |entity| entity.created_by.clone()),
+            updated_by: local.actor.clone(),
+            version: stored.as_ref().map_or(local.sync_vector.version, |entity| entity.version),
+            classification: resolution.entity.classification.key().to_string(),
+            compartments: resolution.entity.compartments.clone(),
+            tenant_id: local.tenant_id.clone(),
+            deleted_at: None,
+            sync_status: super::storage::SyncStatus::Pending,
+        };
+        self.storage_manager.put(&local.entity_id, merged, &ctx).await
+            .map_err(|e| SyncError::StorageError { error: e.to_string() })?;
+
+        self.pending_changes.lock().await.push_back(ChangeRecord {
+            id: Uuid::new_v4(),
+            operation: ChangeOperation::Update,
+            data: Some(resolution.entity.data),
+            timestamp: Utc::now(),
+            sync_vector: SyncVector {
+                client_id: self.client_id.clone(),
+                version: local.sync_vector.version.max(remote.sync_vector.version) + 1,
+                server_version: remote.sync_vector.server_version,
+            },
+            dependencies: vec![remote.id],
+            ..local.clone()
+        });
+        Ok(())
+    }
+
+    /// Push one create or update through `delta_sync`
+    ///
+    /// When the remote has moved past our base, its copy is merged with the conflict resolver
+    /// and the merge pushed on top of it; it reaches local storage with the next pull.
+    async fn push_delta(&self, delta_sync: &DeltaSync<Box<dyn SyncEndpoint>>, change: &ChangeRecord) -> Result<(), SyncError> {
+        let Some(data) = &change.data else {
+            return Ok(());
+        };
+        let ctx = self.storage_context(change).await;
+        let stored = self.storage_manager.get(&change.entity_id, &ctx).await
+            .map_err(|e| SyncError::StorageError { error: e.to_string() })?;
+        let local = change_revision(change, data, &stored_label(stored.as_ref())?)?;
+        let base = self.synced_entities.read().await.get(&local.entity.id).cloned();
+        let current = SecureEntity {
+            version: base.as_ref().map_or(local.entity.version, |base| base.version + 1),
+            ..local.entity
+        };
+
+        let (pushed, version) = match delta_sync.push(base.as_ref(), &current).await? {
+            DeltaPush::Delta { version, .. } | DeltaPush::Full { version, .. } => (current, version),
+            DeltaPush::Resync { remote } => {
+                let resolution = self.conflict_resolver.resolve(&EntityConflict {
+                    base,
+                    local: EntityRevision { entity: current, vector: local.vector },
+                    remote: EntityRevision { entity: (*remote).clone(), vector: VersionVector::new() },
+                })?;
+                let merged = SecureEntity { version: remote.version + 1, ..resolution.entity };
+                match delta_sync.push(Some(remote.as_ref()), &merged).await? {
+                    DeltaPush::Delta { version, .. } | DeltaPush::Full { version, .. } => (merged, version),
+                    DeltaPush::Resync { .. } => {
+                        return Err(SyncError::SyncConflict {
+                            entity_id: change.entity_id.clone(),
+                            reason: "remote changed again while merging".to_string(),
+                        });
+                    }
+                }
+            }
+        };
+        self.synced_entities.write().await.insert(pushed.id, SecureEntity { version, ..pushed });
+        Ok(())
+    }
+
+    /// Storage context of the session that made `change`, at its current clearance
+    async fn storage_context(&self, change: &ChangeRecord) -> StorageContext {
+        let label = self.security_manager.get_security_context(change.session_id).await
+            .map(|context| context.security_label)
+            .unwrap_or_else(|| SecurityLabel::new(ClassificationLevel::Unclassified, Vec::new()));
+        StorageContext {
+            user_id: change.actor.clone(),
+            session_id: change.session_id,
+            tenant_id: change.tenant_id.clone(),
+            classification_level: label.level.key().to_string(),
+            compartments: label.compartments.into_iter().collect(),
+            operation_id: Uuid::new_v4(),
+        }
+    }
+
     async fn apply_remote_change(&self, change: ChangeRecord) -> Result<(), SyncError> {
         let ctx = StorageContext {
             user_id: change.actor.clone(),
@@ -554,98 +730,51 @@ impl Clone for SyncManager {
             pending_changes: self.pending_changes.clone(),
             conflict_resolver: self.conflict_resolver.clone(),
             change_listeners: self.change_listeners.clone(),
+            delta_sync: self.delta_sync.clone(),
+            synced_entities: self.synced_entities.clone(),
             metrics: self.metrics.clone(),
             client_id: self.client_id.clone(),
         }
     }
 }
 
-impl ConflictResolver {
-    pub fn new() -> Self {
-        Self {
-            strategies: HashMap::new(),
-            default_strategy: ConflictResolutionStrategy::LastWriteWins,
-        }
-    }
-    
-    pub async fn resolve_conflict(
-        &self,
-        conflict: ConflictRecord,
-        strategy: ConflictResolutionStrategy,
-        storage_manager: &StorageManager,
-    ) -> Result<(), SyncError> {
-        match strategy {
-            ConflictResolutionStrategy::LastWriteWins => {
-                self.resolve_last_write_wins(conflict, storage_manager).await
-            }
-            ConflictResolutionStrategy::FirstWriteWins => {
-                self.resolve_first_write_wins(conflict, storage_manager).await
-            }
-            ConflictResolutionStrategy::Merge => {
-                self.resolve_merge(conflict, storage_manager).await
-            }
-            ConflictResolutionStrategy::Manual => {
-                // Queue for manual resolution
-                Err(SyncError::SyncConflict {
-                    entity_id: conflict.entity_id,
-                    reason: "Manual resolution required".to_string(),
-                })
-            }
-        }
-    }
-    
-    async fn resolve_last_write_wins(
-        &self,
-        conflict: ConflictRecord,
-        storage_manager: &StorageManager,
-    ) -> Result<(), SyncError> {
-        let winning_change = if conflict.remote_change.timestamp > conflict.local_change.timestamp {
-            conflict.remote_change
-        } else {
-            conflict.local_change
-        };
-        
-        // Apply the winning change
-        // Implementation would depend on the specific change type
-        Ok(())
-    }
-    
-    async fn resolve_first_write_wins(
-        &self,
-        conflict: ConflictRecord,
-        storage_manager: &StorageManager,
-    ) -> Result<(), SyncError> {
-        let winning_change = if conflict.local_change.timestamp < conflict.remote_change.timestamp {
-            conflict.local_change
-        } else {
-            conflict.remote_change
-        };
-        
-        // Apply the winning change
-        Ok(())
-    }
-    
-    async fn resolve_merge(
-        &self,
-        conflict: ConflictRecord,
-        storage_manager: &StorageManager,
-    ) -> Result<(), SyncError> {
-        // Implement field-level merging
-        // This would be specific to your data models
-        Err(SyncError::SyncConflict {
-            entity_id: conflict.entity_id,
-            reason: "Merge resolution not implemented".to_string(),
-        })
+/// Label of the locally stored copy; unclassified when there is none yet
+fn stored_label(stored: Option<&StoredEntity>) -> Result<SecurityLabel, SyncError> {
+    match stored {
+        Some(entity) => Ok(SecurityLabel::new(
+            ClassificationLevel::from_str(&entity.classification)
+                .map_err(|e| SyncError::StorageError { error: e.to_string() })?,
+            entity.compartments.clone(),
+        )),
+        None => Ok(SecurityLabel::new(ClassificationLevel::Unclassified, Vec::new())),
     }
 }
 
-impl Clone for ConflictResolver {
-    fn clone(&self) -> Self {
-        Self {
-            strategies: self.strategies.clone(),
-            default_strategy: self.default_strategy.clone(),
-        }
-    }
+/// One side of a conflict as the resolver sees it, labelled `label`
+fn change_revision(change: &ChangeRecord, data: &Value, label: &SecurityLabel) -> Result<EntityRevision, SyncError> {
+    let id = Uuid::parse_str(&change.entity_id).map_err(|_| SyncError::SyncConflict {
+        entity_id: change.entity_id.clone(),
+        reason: "entity id is not a UUID".to_string(),
+    })?;
+    let mut compartments: Vec<String> = label.compartments.iter().cloned().collect();
+    compartments.sort();
+    let entity = SecureEntity {
+        id,
+        entity_type: change.entity_type.clone(),
+        data: data.clone(),
+        created_at: change.timestamp,
+        updated_at: change.timestamp,
+        created_by: change.actor.clone(),
+        updated_by: change.actor.clone(),
+        classification: label.level.clone(),
+        compartments,
+        version: i64::try_from(change.sync_vector.version).unwrap_or(i64::MAX),
+        tenant_id: change.tenant_id.clone(),
+        deleted_at: None,
+        deleted_by: None,
+    };
+    let vector = VersionVector::new().with_entry(change.sync_vector.client_id.clone(), change.sync_vector.version);
+    Ok(EntityRevision { entity, vector })
 }
 
 /// Utility functions for change creation
@@ -0,0 +1,497 @@
+// src/sync/offline_queue.rs
+// Offline Queue - Entity writes made without connectivity, replayed in order once it returns
+// Persisted as JSONL; replay goes through the EntityStore's MAC checks and quarantines what no longer passes
+
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use uuid::Uuid;
+
+use super::conflict_resolution::{ConflictResolver, EntityConflict, EntityRevision, FieldConflict, VersionVector};
+use super::{SyncClient, SyncError};
+use crate::database::{DatabaseContext, DatabaseError, EntityStore, SecureEntity};
+use crate::security::SecurityLabel;
+
+/// An entity write made offline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum OfflineOperation {
+    /// `local_id` is the id the entity was given locally; later queued ops on it are
+    /// pointed at the stored entity once it is created
+    Create { local_id: Uuid, entity_type: String, data: Value },
+    /// `base` is the local copy the edit was made against
+    Update { base: Box<SecureEntity>, updates: Value },
+    Delete { entity_id: Uuid },
+}
+
+impl OfflineOperation {
+    fn remap(&mut self, local_id: Uuid, entity_id: Uuid) {
+        match self {
+            OfflineOperation::Update { base, .. } if base.id == local_id => base.id = entity_id,
+            OfflineOperation::Delete { entity_id: id } if *id == local_id => *id = entity_id,
+            _ => {}
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueuedOperation {
+    pub id: Uuid,
+    pub operation: OfflineOperation,
+    /// Context the write was made under, including the clearance held then
+    pub context: DatabaseContext,
+    pub queued_at: DateTime<Utc>,
+    /// Set when replay refused the op; it stays queued for review but is skipped
+    #[serde(default)]
+    pub quarantine: Option<Quarantine>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Quarantine {
+    pub reason: String,
+    pub quarantined_at: DateTime<Utc>,
+}
+
+/// Current clearance of the user behind a queued op
+#[async_trait]
+pub trait ClearanceProvider: Send + Sync {
+    /// `None` if the user may no longer act at all (disabled, removed from the tenant)
+    async fn current_clearance(&self, user_id: &str, tenant_id: Option<&str>) -> Option<SecurityLabel>;
+}
+
+/// Update merged with edits made remotely while it was queued
+#[derive(Debug, Clone, Serialize)]
+pub struct MergedReplay {
+    pub operation_id: Uuid,
+    pub entity_id: Uuid,
+    pub conflicts: Vec<FieldConflict>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReplayReport {
+    /// Ops applied and removed from the queue, in order
+    pub applied: Vec<Uuid>,
+    pub merged: Vec<MergedReplay>,
+    pub quarantined: Vec<Uuid>,
+    /// Replay stopped at a failure that may be transient; that op and the rest stay queued
+    pub interrupted: Option<String>,
+}
+
+enum ReplayOutcome {
+    Applied { created: Option<(Uuid, Uuid)>, merged: Option<MergedReplay> },
+    Quarantined(String),
+    Interrupted(String),
+}
+
+/// Durable FIFO of offline entity writes
+///
+/// Replay drains it through an `EntityStore`, so every op faces the normal MAC, tenant and
+/// lifecycle checks under the user's clearance at replay time. Ops queued under a clearance
+/// the user no longer holds are quarantined rather than applied: their data may carry
+/// information the user was cleared for only then.
+#[derive(Debug)]
+pub struct OfflineQueue {
+    path: Option<PathBuf>,
+    entries: tokio::sync::Mutex<VecDeque<QueuedOperation>>,
+}
+
+impl Default for OfflineQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OfflineQueue {
+    /// In-memory queue, lost on restart
+    pub fn new() -> Self {
+        Self { path: None, entries: tokio::sync::Mutex::new(VecDeque::new()) }
+    }
+
+    /// Queue persisted to a JSONL file, loading any ops left from a previous run
+    pub async fn open(path: impl Into<PathBuf>) -> Result<Self, SyncError> {
+        let path = path.into();
+        let mut entries = VecDeque::new();
+        match tokio::fs::read_to_string(&path).await {
+            Ok(contents) => {
+                for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+                    let entry: QueuedOperation = serde_json::from_str(line)
+                        .map_err(|e| SyncError::SerializationError { error: e.to_string() })?;
+                    entries.push_back(entry);
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => return Err(SyncError::StorageError { error: e.to_string() }),
+        }
+
+        Ok(Self { path: Some(path), entries: tokio::sync::Mutex::new(entries) })
+    }
+
+    /// Queue `operation`, made under `context`; returns the op's id
+    pub async fn enqueue(&self, operation: OfflineOperation, context: &DatabaseContext) -> Result<Uuid, SyncError> {
+        let entry = QueuedOperation {
+            id: Uuid::new_v4(),
+            operation,
+            context: context.clone(),
+            queued_at: Utc::now(),
+            quarantine: None,
+        };
+        let id = entry.id;
+        let mut entries = self.entries.lock().await;
+        entries.push_back(entry);
+        self.persist(&entries).await?;
+        Ok(id)
+    }
+
+    /// Ops still to replay, oldest first
+    pub async fn pending(&self) -> Vec<QueuedOperation> {
+        self.entries.lock().await.iter().filter(|entry| entry.quarantine.is_none()).cloned().collect()
+    }
+
+    pub async fn quarantined(&self) -> Vec<QueuedOperation> {
+        self.entries.lock().await.iter().filter(|entry| entry.quarantine.is_some()).cloned().collect()
+    }
+
+    /// Drop a quarantined op once reviewed; `false` if it isn't quarantined
+    pub async fn discard(&self, operation_id: Uuid) -> Result<bool, SyncError> {
+        let mut entries = self.entries.lock().await;
+        let before = entries.len();
+        entries.retain(|entry| entry.id != operation_id || entry.quarantine.is_none());
+        if entries.len() == before {
+            return Ok(false);
+        }
+        self.persist(&entries).await?;
+        Ok(true)
+    }
+
+    /// Replay if `client` reports connectivity; `None` if it doesn't
+    pub async fn replay_when_connected(
+        &self,
+        client: &dyn SyncClient,
+        store: &dyn EntityStore,
+        resolver: &ConflictResolver,
+        clearances: &dyn ClearanceProvider,
+    ) -> Result<Option<ReplayReport>, SyncError> {
+        if !client.is_connected() {
+            return Ok(None);
+        }
+        self.replay(store, resolver, clearances).await.map(Some)
+    }
+
+    /// Apply pending ops in the order they were queued
+    ///
+    /// Updates whose entity changed meanwhile are merged with `resolver`. The queue is
+    /// persisted after every op, and creates use the op id as idempotency key, so a crash
+    /// mid-replay never applies an op twice.
+    pub async fn replay(
+        &self,
+        store: &dyn EntityStore,
+        resolver: &ConflictResolver,
+        clearances: &dyn ClearanceProvider,
+    ) -> Result<ReplayReport, SyncError> {
+        // Held throughout, so ops queued during replay wait their turn
+        let mut entries = self.entries.lock().await;
+        let mut report = ReplayReport::default();
+        let mut index = 0;
+        while let Some(entry) = entries.get(index).cloned() {
+            if entry.quarantine.is_some() {
+                index += 1;
+                continue;
+            }
+
+            match replay_one(&entry, store, resolver, clearances).await {
+                ReplayOutcome::Applied { created, merged } => {
+                    entries.remove(index);
+                    if let Some((local_id, entity_id)) = created {
+                        for later in entries.iter_mut() {
+                            later.operation.remap(local_id, entity_id);
+                        }
+                    }
+                    report.applied.push(entry.id);
+                    report.merged.extend(merged);
+                }
+                ReplayOutcome::Quarantined(reason) => {
+                    crate::security_log!(warn, operation_id = %entry.id, user_id = %entry.context.user_id, %reason, "Quarantined offline operation");
+                    if let Some(quarantined) = entries.get_mut(index) {
+                        quarantined.quarantine = Some(Quarantine { reason, quarantined_at: Utc::now() });
+                    }
+                    report.quarantined.push(entry.id);
+                    index += 1;
+                }
+                ReplayOutcome::Interrupted(reason) => {
+                    report.interrupted = Some(reason);
+                    break;
+                }
+            }
+            self.persist(&entries).await?;
+        }
+        Ok(report)
+    }
+
+    /// Rewrite the backing file atomically (temp file + rename)
+    async fn persist(&self, entries: &VecDeque<QueuedOperation>) -> Result<(), SyncError> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        let mut data = Vec::new();
+        for entry in entries {
+            serde_json::to_writer(&mut data, entry)
+                .map_err(|e| SyncError::SerializationError { error: e.to_string() })?;
+            data.push(b'\n');
+        }
+
+        let tmp = path.with_extension("tmp");
+        tokio::fs::write(&tmp, &data).await
+            .map_err(|e| SyncError::StorageError { error: e.to_string() })?;
+        tokio::fs::rename(&tmp, path).await
+            .map_err(|e| SyncError::StorageError { error: e.to_string() })
+    }
+}
+
+async fn replay_one(
+    entry: &QueuedOperation,
+    store: &dyn EntityStore,
+    resolver: &ConflictResolver,
+    clearances: &dyn ClearanceProvider,
+) -> ReplayOutcome {
+    let queued_label = &entry.context.security_label;
+    let Some(clearance) = clearances.current_clearance(&entry.context.user_id, entry.context.tenant_id.as_deref()).await else {
+        return ReplayOutcome::Quarantined("user may no longer act".to_string());
+    };
+    if !clearance.dominates(queued_label) {
+        return ReplayOutcome::Quarantined(format!(
+            "clearance is now {} but the write was made at {}", clearance.level, queued_label.level
+        ));
+    }
+    // Replayed at the label it was queued under, so a since-raised clearance can't write up
+    let context = entry.context.clone();
+
+    let result = match &entry.operation {
+        OfflineOperation::Create { local_id, entity_type, data } => store
+            .create_entity_idempotent(entity_type, data.clone(), &entry.id.to_string(), &context).await
+            .map(|entity| ReplayOutcome::Applied { created: Some((*local_id, entity.id)), merged: None }),
+        OfflineOperation::Update { base, updates } => replay_update(entry, base, updates, store, resolver, &context).await,
+        OfflineOperation::Delete { entity_id } => store.delete_entity(*entity_id, &context).await
+            .map(|deleted| if deleted {
+                ReplayOutcome::Applied { created: None, merged: None }
+            } else {
+                ReplayOutcome::Quarantined("delete refused: entity missing, already deleted or not writable".to_string())
+            }),
+    };
+    match result {
+        Ok(outcome) => outcome,
+        Err(DatabaseError::Connection(e)) => ReplayOutcome::Interrupted(e.to_string()),
+        Err(e) => ReplayOutcome::Quarantined(e.to_string()),
+    }
+}
+
+async fn replay_update(
+    entry: &QueuedOperation,
+    base: &SecureEntity,
+    updates: &Value,
+    store: &dyn EntityStore,
+    resolver: &ConflictResolver,
+    context: &DatabaseContext,
+) -> Result<ReplayOutcome, DatabaseError> {
+    let Some(current) = store.read_entity(base.id, context).await? else {
+        return Ok(ReplayOutcome::Quarantined("entity missing or no longer readable".to_string()));
+    };
+    if !context.can_write(&current) {
+        return Ok(ReplayOutcome::Quarantined(format!(
+            "a {} clearance may not write the {} entity", context.security_label.level, current.classification
+        )));
+    }
+
+    if current.version == base.version {
+        if updates.as_object().is_some_and(Map::is_empty) {
+            return Ok(ReplayOutcome::Applied { created: None, merged: None });
+        }
+        return Ok(match store.update_entity(current.id, updates.clone(), context).await? {
+            Some(_) => ReplayOutcome::Applied { created: None, merged: None },
+            None => ReplayOutcome::Quarantined("update refused".to_string()),
+        });
+    }
+
+    let local = base.updated(updates.clone(), &entry.context, entry.queued_at);
+    let conflict = EntityConflict {
+        base: Some(base.clone()),
+        local: EntityRevision { entity: local, vector: VersionVector::new() },
+        remote: EntityRevision { entity: current.clone(), vector: VersionVector::new() },
+    };
+    let resolution = match resolver.resolve(&conflict) {
+        Ok(resolution) => resolution,
+        Err(e) => return Ok(ReplayOutcome::Quarantined(e.to_string())),
+    };
+
+    // Stored whole, so keys the merge dropped go too, under the label the resolver raised it to
+    let label = SecurityLabel::new(resolution.entity.classification.clone(), resolution.entity.compartments.clone());
+    let merged = MergedReplay { operation_id: entry.id, entity_id: current.id, conflicts: resolution.conflicts };
+    Ok(match store.merge_entity(current.id, current.version, resolution.entity.data, &label, context).await? {
+        Some(_) => ReplayOutcome::Applied { created: None, merged: Some(merged) },
+        None => ReplayOutcome::Quarantined("merge refused: entity modified again or not writable at the merged label".to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::InMemoryEntityStore;
+    use crate::security::ClassificationLevel;
+    use crate::sync::FieldLevelMerge;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    /// Clearances as the directory now reports them
+    struct Directory(HashMap<String, SecurityLabel>);
+
+    #[async_trait]
+    impl ClearanceProvider for Directory {
+        async fn current_clearance(&self, user_id: &str, _tenant_id: Option<&str>) -> Option<SecurityLabel> {
+            self.0.get(user_id).cloned()
+        }
+    }
+
+    fn context(user_id: &str, level: ClassificationLevel) -> DatabaseContext {
+        DatabaseContext::new(user_id.to_string(), Uuid::new_v4(), SecurityLabel::new(level, Vec::new()), None)
+    }
+
+    fn directory(users: &[(&str, ClassificationLevel)]) -> Directory {
+        Directory(users.iter().map(|(user, level)| (user.to_string(), SecurityLabel::new(level.clone(), Vec::new()))).collect())
+    }
+
+    #[tokio::test]
+    async fn test_replay_applies_ops_in_order_and_survives_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("offline.jsonl");
+        let alice = context("alice", ClassificationLevel::Internal);
+
+        // Offline: create a note, edit it, then create and delete a scratch one
+        let queue = OfflineQueue::open(&path).await.unwrap();
+        let note = SecureEntity::created(Uuid::new_v4(), "note", json!({"title": "Draft"}), alice.security_label.clone(), &alice, Utc::now());
+        let scratch_id = Uuid::new_v4();
+        queue.enqueue(OfflineOperation::Create { local_id: note.id, entity_type: "note".to_string(), data: note.data.clone() }, &alice).await.unwrap();
+        queue.enqueue(OfflineOperation::Update { base: Box::new(note.clone()), updates: json!({"title": "Final"}) }, &alice).await.unwrap();
+        queue.enqueue(OfflineOperation::Create { local_id: scratch_id, entity_type: "note".to_string(), data: json!({"title": "Scratch"}) }, &alice).await.unwrap();
+        queue.enqueue(OfflineOperation::Delete { entity_id: scratch_id }, &alice).await.unwrap();
+        drop(queue);
+
+        // Restarted with connectivity
+        let queue = OfflineQueue::open(&path).await.unwrap();
+        let queued: Vec<Uuid> = queue.pending().await.iter().map(|entry| entry.id).collect();
+        assert_eq!(queued.len(), 4);
+
+        let store = InMemoryEntityStore::new();
+        let report = queue.replay(&store, &ConflictResolver::new(), &directory(&[("alice", ClassificationLevel::Internal)])).await.unwrap();
+        assert_eq!(report.applied, queued);
+        assert!(report.quarantined.is_empty() && report.interrupted.is_none());
+        assert!(queue.pending().await.is_empty());
+        assert!(OfflineQueue::open(&path).await.unwrap().pending().await.is_empty());
+
+        let notes = store.query_entities(Some("note"), None, &alice, None, None).await.unwrap();
+        assert_eq!(notes.entities.len(), 1);
+        assert_eq!((notes.entities[0].data.clone(), notes.entities[0].version), (json!({"title": "Final"}), 2));
+    }
+
+    #[tokio::test]
+    async fn test_write_under_lost_clearance_is_quarantined() {
+        let queue = OfflineQueue::new();
+        let officer = context("officer", ClassificationLevel::Secret);
+        let analyst = context("analyst", ClassificationLevel::Internal);
+        let secret = queue.enqueue(OfflineOperation::Create {
+            local_id: Uuid::new_v4(), entity_type: "report".to_string(), data: json!({"source": "HUMINT"}),
+        }, &officer).await.unwrap();
+        let internal = queue.enqueue(OfflineOperation::Create {
+            local_id: Uuid::new_v4(), entity_type: "report".to_string(), data: json!({"title": "Roadmap"}),
+        }, &analyst).await.unwrap();
+
+        // The officer was downgraded while offline
+        let store = InMemoryEntityStore::new();
+        let clearances = directory(&[("officer", ClassificationLevel::Confidential), ("analyst", ClassificationLevel::Internal)]);
+        let report = queue.replay(&store, &ConflictResolver::new(), &clearances).await.unwrap();
+        assert_eq!((report.quarantined, report.applied), (vec![secret], vec![internal]));
+        assert_eq!(store.len().await, 1);
+
+        // Quarantined ops stay for review and aren't retried
+        let quarantined = queue.quarantined().await;
+        assert!(quarantined[0].quarantine.as_ref().unwrap().reason.contains("clearance"));
+        let again = queue.replay(&store, &ConflictResolver::new(), &clearances).await.unwrap();
+        assert!(again.applied.is_empty() && again.quarantined.is_empty());
+        assert!(queue.discard(secret).await.unwrap());
+        assert!(queue.quarantined().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_update_edited_remotely_is_merged() {
+        let store = InMemoryEntityStore::new();
+        let alice = context("alice", ClassificationLevel::Internal);
+        let bob = context("bob", ClassificationLevel::Internal);
+        let base = store.create_entity("note", json!({"title": "Plan", "status": "open"}), &alice).await.unwrap();
+
+        let queue = OfflineQueue::new();
+        queue.enqueue(OfflineOperation::Update { base: Box::new(base.clone()), updates: json!({"title": "Plan v2"}) }, &alice).await.unwrap();
+        // Bob edits another field while Alice is offline
+        store.update_entity(base.id, json!({"status": "closed"}), &bob).await.unwrap();
+
+        let resolver = ConflictResolver::new().with_strategy("note", FieldLevelMerge);
+        let clearances = directory(&[("alice", ClassificationLevel::Internal)]);
+        let report = queue.replay(&store, &resolver, &clearances).await.unwrap();
+        assert_eq!(report.merged.len(), 1);
+        assert!(report.merged[0].conflicts.is_empty());
+
+        let merged = store.read_entity(base.id, &alice).await.unwrap().unwrap();
+        assert_eq!(merged.data, json!({"title": "Plan v2", "status": "closed"}));
+    }
+
+    #[tokio::test]
+    async fn test_merge_stores_removed_keys_and_raised_label() {
+        let store = InMemoryEntityStore::new();
+        let alpha = SecurityLabel::new(ClassificationLevel::Internal, vec!["ALPHA".to_string()]);
+        let alice = DatabaseContext::new("alice".to_string(), Uuid::new_v4(), alpha.clone(), None);
+        let bob = DatabaseContext::new("bob".to_string(), Uuid::new_v4(), alpha.clone(), None);
+        let base = SecureEntity::created(Uuid::new_v4(), "note", json!({"title": "Plan", "draft": true}), alpha.clone(), &alice, Utc::now());
+        store.insert(base.clone()).await;
+
+        let queue = OfflineQueue::new();
+        queue.enqueue(OfflineOperation::Update { base: Box::new(base.clone()), updates: json!({"title": "Plan v2"}) }, &alice).await.unwrap();
+        // While Alice is offline Bob drops the draft flag and the ALPHA compartment
+        let unlabelled = SecurityLabel::new(ClassificationLevel::Internal, Vec::new());
+        store.insert(SecureEntity { compartments: Vec::new(), ..base.clone() }).await;
+        store.merge_entity(base.id, base.version, json!({"title": "Plan"}), &unlabelled, &bob).await.unwrap().unwrap();
+
+        let resolver = ConflictResolver::new().with_strategy("note", FieldLevelMerge);
+        let clearances = Directory(HashMap::from([("alice".to_string(), alpha)]));
+        let report = queue.replay(&store, &resolver, &clearances).await.unwrap();
+        assert_eq!(report.merged.len(), 1);
+
+        // The removed key stays removed and Alice's edit keeps its compartment
+        let merged = store.read_entity(base.id, &alice).await.unwrap().unwrap();
+        assert_eq!(merged.data, json!({"title": "Plan v2"}));
+        assert_eq!(merged.compartments, vec!["ALPHA".to_string()]);
+        assert!(store.read_entity(base.id, &context("carol", ClassificationLevel::Internal)).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_replay_writes_at_the_queued_label() {
+        let store = InMemoryEntityStore::new();
+        let analyst = context("analyst", ClassificationLevel::Internal);
+        let base = store.create_entity("note", json!({"title": "Roadmap"}), &analyst).await.unwrap();
+
+        let queue = OfflineQueue::new();
+        let local_id = Uuid::new_v4();
+        queue.enqueue(OfflineOperation::Create { local_id, entity_type: "note".to_string(), data: json!({"title": "Agenda"}) }, &analyst).await.unwrap();
+        queue.enqueue(OfflineOperation::Update { base: Box::new(base.clone()), updates: json!({"title": "Roadmap v2"}) }, &analyst).await.unwrap();
+
+        // Promoted to Secret while offline: the queued Internal writes still land at Internal
+        let clearances = directory(&[("analyst", ClassificationLevel::Secret)]);
+        let report = queue.replay(&store, &ConflictResolver::new(), &clearances).await.unwrap();
+        assert_eq!(report.applied.len(), 2);
+        assert!(report.quarantined.is_empty());
+
+        let notes = store.query_entities(Some("note"), None, &analyst, None, None).await.unwrap();
+        assert_eq!(notes.entities.len(), 2);
+        assert!(notes.entities.iter().all(|note| note.classification == ClassificationLevel::Internal));
+    }
+}